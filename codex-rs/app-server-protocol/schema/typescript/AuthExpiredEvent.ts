@@ -0,0 +1,17 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type AuthExpiredEvent = { 
+/**
+ * Identifier to pass back in `Op::ReauthComplete`.
+ */
+id: string, 
+/**
+ * Turn ID that this pause belongs to.
+ */
+turn_id: string, 
+/**
+ * Why the tokens could not be refreshed.
+ */
+message: string, };
@@ -0,0 +1,8 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The quantity a budget limit applies to.
+ */
+export type BudgetKind = "tokens" | "cost_usd" | "wall_clock_minutes" | "exec_calls" | "steps";
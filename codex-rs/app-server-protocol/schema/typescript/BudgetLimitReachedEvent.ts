@@ -0,0 +1,23 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BudgetKind } from "./BudgetKind";
+import type { BudgetScope } from "./BudgetScope";
+
+export type BudgetLimitReachedEvent = { 
+/**
+ * Identifier to pass back in `Op::BudgetApproval`.
+ */
+id: string, 
+/**
+ * Turn ID that this pause belongs to.
+ */
+turn_id: string, scope: BudgetScope, kind: BudgetKind, 
+/**
+ * Amount consumed so far, in the unit of `kind`.
+ */
+used: number, 
+/**
+ * Configured limit, in the unit of `kind`.
+ */
+limit: number, };
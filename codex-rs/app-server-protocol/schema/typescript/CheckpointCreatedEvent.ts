@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type CheckpointCreatedEvent = { 
+/**
+ * Identifier to pass to `Op::RevertToCheckpoint`.
+ */
+checkpoint_id: string, 
+/**
+ * Turn ID whose changes the checkpoint precedes.
+ */
+turn_id: string, };
@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ConfigReloadedEvent = { 
+/**
+ * Config keys whose new values now apply to this session.
+ */
+applied: Array<string>, 
+/**
+ * Config keys that changed but only take effect in a new session.
+ */
+deferred: Array<string>, };
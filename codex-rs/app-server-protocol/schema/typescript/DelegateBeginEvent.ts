@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type DelegateBeginEvent = { 
+/**
+ * Identifier for the `delegate` tool call.
+ */
+call_id: string, 
+/**
+ * Task handed to the sub-agent.
+ */
+task: string, };
@@ -0,0 +1,17 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type DelegateEndEvent = { 
+/**
+ * Identifier for the `delegate` tool call.
+ */
+call_id: string, 
+/**
+ * Final message of the sub-agent, returned to the model as the tool result.
+ */
+output: string | null, 
+/**
+ * Why the sub-agent stopped without finishing its task.
+ */
+error: string | null, };
@@ -0,0 +1,14 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EventMsg } from "./EventMsg";
+
+export type DelegateProgressEvent = { 
+/**
+ * Identifier for the `delegate` tool call.
+ */
+call_id: string, 
+/**
+ * The sub-agent event, unchanged.
+ */
+msg: EventMsg, };
@@ -1,5 +1,12 @@
 // GENERATED CODE! DO NOT MODIFY BY HAND!
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JsonValue } from "./serde_json/JsonValue";
 
-export type ElicitationRequestEvent = { server_name: string, id: string | number, message: string, };
+export type ElicitationRequestEvent = { server_name: string, id: string | number, message: string, 
+/**
+ * JSON schema of the object the server asks the user to fill in; its properties are flat
+ * fields of type string, number, integer, boolean, or a string enum. Unset when the server
+ * only asks for a confirmation.
+ */
+requested_schema: JsonValue | null, };
@@ -35,15 +35,7 @@ source: ExecCommandSource,
  */
 interaction_input?: string, 
 /**
- * Captured stdout
- */
-stdout: string, 
-/**
- * Captured stderr
- */
-stderr: string, 
-/**
- * Captured aggregated output
+ * Captured stdout and stderr, interleaved in the order they were produced.
  */
 aggregated_output: string, 
 /**
@@ -58,6 +50,10 @@ duration: string,
  * Formatted output from the command, as seen by the model.
  */
 formatted_output: string, 
+/**
+ * File holding the untruncated output when `formatted_output` had to be truncated.
+ */
+output_artifact?: string, 
 /**
  * Completion status for this command execution.
  */
@@ -0,0 +1,9 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type GetMcpPromptResponseEvent = { server: string, name: string, 
+/**
+ * Text of the expanded prompt's messages, or why the server could not expand it.
+ */
+result: { Ok : string } | { Err : string }, };
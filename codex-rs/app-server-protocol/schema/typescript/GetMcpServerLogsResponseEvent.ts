@@ -0,0 +1,9 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type GetMcpServerLogsResponseEvent = { 
+/**
+ * The latest lines each server wrote to stderr, oldest first, keyed by server name.
+ */
+logs: { [key in string]?: Array<string> }, };
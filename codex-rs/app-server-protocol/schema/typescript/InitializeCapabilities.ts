@@ -14,4 +14,10 @@ experimentalApi: boolean,
  * Exact notification method names that should be suppressed for this
  * connection (for example `codex/event/session_configured`).
  */
-optOutNotificationMethods?: Array<string> | null, };
+optOutNotificationMethods?: Array<string> | null, 
+/**
+ * Codex protocol version the client was built against. `codex/event/*` notifications for
+ * event types added in later versions are not sent to this connection. Omit to receive every
+ * event type.
+ */
+protocolVersion?: number | null, };
@@ -2,4 +2,8 @@
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type InitializeResponse = { userAgent: string, };
+export type InitializeResponse = { userAgent: string, 
+/**
+ * Codex protocol version spoken by the server.
+ */
+protocolVersion: number, };
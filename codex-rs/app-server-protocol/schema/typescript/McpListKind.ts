@@ -0,0 +1,9 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A list published by an MCP server, which the server announces changes to with
+ * `notifications/<list>/list_changed`.
+ */
+export type McpListKind = "tools" | "prompts" | "resources";
@@ -0,0 +1,14 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { McpInvocation } from "./McpInvocation";
+
+export type McpToolCallQueuedEvent = { 
+/**
+ * Identifier of the McpToolCallBegin event sent once the call starts.
+ */
+call_id: string, invocation: McpInvocation, 
+/**
+ * Place in line, starting at 1 for the next call to start. Sent again whenever it changes.
+ */
+position: number, };
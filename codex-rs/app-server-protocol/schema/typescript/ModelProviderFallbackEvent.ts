@@ -0,0 +1,17 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ModelProviderFallbackEvent = { 
+/**
+ * Display name of the provider that became unavailable.
+ */
+from_provider: string, 
+/**
+ * Display name of the provider now serving the session.
+ */
+to_provider: string, 
+/**
+ * Error that triggered the switch.
+ */
+reason: string, };
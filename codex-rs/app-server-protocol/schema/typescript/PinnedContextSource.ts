@@ -0,0 +1,8 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Where a pinned context item comes from.
+ */
+export type PinnedContextSource = { "type": "text", text: string, } | { "type": "file", path: string, };
@@ -0,0 +1,10 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PinnedContextEntry } from "./PinnedContextEntry";
+
+/**
+ * Response payload for `Op::PinContext`, `Op::UnpinContext`, and
+ * `Op::ListPinnedContext`.
+ */
+export type PinnedContextUpdatedEvent = { items: Array<PinnedContextEntry>, };
@@ -0,0 +1,10 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ProjectDocsLoadedEvent = { 
+/**
+ * Files that contributed instructions, ordered from the repository root
+ * to the current working directory.
+ */
+paths: Array<string>, };
@@ -0,0 +1,10 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { UserInput } from "./UserInput";
+
+export type QueuedUserInput = { 
+/**
+ * Id of the submission, which becomes the id of its turn.
+ */
+id: string, items: Array<UserInput>, };
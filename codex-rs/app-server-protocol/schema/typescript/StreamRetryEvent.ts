@@ -0,0 +1,21 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type StreamRetryEvent = { 
+/**
+ * 1-based number of the upcoming retry.
+ */
+attempt: number, 
+/**
+ * Retries allowed before the turn fails.
+ */
+max_attempts: number, 
+/**
+ * Wait before the retry is sent, in milliseconds.
+ */
+delay_ms: number, 
+/**
+ * Error that triggered the retry.
+ */
+reason: string, };
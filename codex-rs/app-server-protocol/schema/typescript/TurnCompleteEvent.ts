@@ -1,5 +1,10 @@
 // GENERATED CODE! DO NOT MODIFY BY HAND!
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { UsageReport } from "./UsageReport";
 
-export type TurnCompleteEvent = { turn_id: string, last_agent_message: string | null, };
+export type TurnCompleteEvent = { turn_id: string, last_agent_message: string | null, 
+/**
+ * Token usage and estimated cost of this turn and of the session so far.
+ */
+usage: UsageReport | null, };
@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AskForApproval } from "./AskForApproval";
+import type { ReasoningEffort } from "./ReasoningEffort";
+import type { SandboxPolicy } from "./SandboxPolicy";
+
+export type TurnSettingsEvent = { turn_id: string, model: string, effort: ReasoningEffort | null, approval_policy: AskForApproval, sandbox_policy: SandboxPolicy, cwd: string, 
+/**
+ * True when these settings apply to this turn only and the session
+ * settings are restored afterwards.
+ */
+turn_only: boolean, };
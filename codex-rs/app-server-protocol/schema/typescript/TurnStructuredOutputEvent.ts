@@ -0,0 +1,14 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JsonValue } from "./serde_json/JsonValue";
+
+export type TurnStructuredOutputEvent = { turn_id: string, 
+/**
+ * The final message parsed as JSON.
+ */
+output: JsonValue, 
+/**
+ * How many times the model was asked to correct a final message that did not match.
+ */
+retries: number, };
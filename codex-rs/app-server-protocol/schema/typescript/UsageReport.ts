@@ -0,0 +1,14 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { UsageSummary } from "./UsageSummary";
+
+export type UsageReport = { 
+/**
+ * Usage of the current turn, or of the most recent one when no turn is running.
+ */
+turn: UsageSummary, 
+/**
+ * Usage of the whole session. Unlike `TokenUsageInfo`, this is not reset by compaction.
+ */
+session: UsageSummary, };
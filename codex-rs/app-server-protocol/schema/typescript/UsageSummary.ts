@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TokenUsage } from "./TokenUsage";
+
+/**
+ * Token counts and estimated cost accumulated over part of a session.
+ */
+export type UsageSummary = { token_usage: TokenUsage, 
+/**
+ * Estimated cost in US dollars, or `None` when a model without configured pricing was used.
+ */
+cost_usd: number | null, };
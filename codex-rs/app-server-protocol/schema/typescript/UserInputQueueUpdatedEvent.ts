@@ -0,0 +1,10 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { QueuedUserInput } from "./QueuedUserInput";
+
+export type UserInputQueueUpdatedEvent = { 
+/**
+ * Queued input, in the order the turns will start.
+ */
+queued: Array<QueuedUserInput>, };
@@ -0,0 +1,17 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type WorktreeCreatedEvent = { 
+/**
+ * Root of the worktree the session edits.
+ */
+path: string, 
+/**
+ * Branch checked out in the worktree.
+ */
+branch: string, 
+/**
+ * Root of the repository the session was started from.
+ */
+repo_root: string, };
@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type WorktreeMergedEvent = { 
+/**
+ * Branch that was merged.
+ */
+branch: string, 
+/**
+ * New `HEAD` of the original repository, or `None` when there was nothing to merge.
+ */
+commit: string | null, };
@@ -20,13 +20,18 @@ export type { ApplyPatchApprovalResponse } from "./ApplyPatchApprovalResponse";
 export type { ArchiveConversationParams } from "./ArchiveConversationParams";
 export type { ArchiveConversationResponse } from "./ArchiveConversationResponse";
 export type { AskForApproval } from "./AskForApproval";
+export type { AuthExpiredEvent } from "./AuthExpiredEvent";
 export type { AuthMode } from "./AuthMode";
 export type { AuthStatusChangeNotification } from "./AuthStatusChangeNotification";
 export type { BackgroundEventEvent } from "./BackgroundEventEvent";
+export type { BudgetKind } from "./BudgetKind";
+export type { BudgetLimitReachedEvent } from "./BudgetLimitReachedEvent";
+export type { BudgetScope } from "./BudgetScope";
 export type { ByteRange } from "./ByteRange";
 export type { CallToolResult } from "./CallToolResult";
 export type { CancelLoginChatGptParams } from "./CancelLoginChatGptParams";
 export type { CancelLoginChatGptResponse } from "./CancelLoginChatGptResponse";
+export type { CheckpointCreatedEvent } from "./CheckpointCreatedEvent";
 export type { ClientInfo } from "./ClientInfo";
 export type { ClientNotification } from "./ClientNotification";
 export type { ClientRequest } from "./ClientRequest";
@@ -43,6 +48,7 @@ export type { CollabWaitingBeginEvent } from "./CollabWaitingBeginEvent";
 export type { CollabWaitingEndEvent } from "./CollabWaitingEndEvent";
 export type { CollaborationMode } from "./CollaborationMode";
 export type { CollaborationModeMask } from "./CollaborationModeMask";
+export type { ConfigReloadedEvent } from "./ConfigReloadedEvent";
 export type { ContentItem } from "./ContentItem";
 export type { ContextCompactedEvent } from "./ContextCompactedEvent";
 export type { ContextCompactionItem } from "./ContextCompactionItem";
@@ -50,6 +56,9 @@ export type { ConversationGitInfo } from "./ConversationGitInfo";
 export type { ConversationSummary } from "./ConversationSummary";
 export type { CreditsSnapshot } from "./CreditsSnapshot";
 export type { CustomPrompt } from "./CustomPrompt";
+export type { DelegateBeginEvent } from "./DelegateBeginEvent";
+export type { DelegateEndEvent } from "./DelegateEndEvent";
+export type { DelegateProgressEvent } from "./DelegateProgressEvent";
 export type { DeprecationNoticeEvent } from "./DeprecationNoticeEvent";
 export type { DynamicToolCallRequest } from "./DynamicToolCallRequest";
 export type { ElicitationRequestEvent } from "./ElicitationRequestEvent";
@@ -85,6 +94,9 @@ export type { GetAuthStatusResponse } from "./GetAuthStatusResponse";
 export type { GetConversationSummaryParams } from "./GetConversationSummaryParams";
 export type { GetConversationSummaryResponse } from "./GetConversationSummaryResponse";
 export type { GetHistoryEntryResponseEvent } from "./GetHistoryEntryResponseEvent";
+export type { GetMcpPromptResponseEvent } from "./GetMcpPromptResponseEvent";
+export type { GetMcpServerLogsResponseEvent } from "./GetMcpServerLogsResponseEvent";
+export type { GetUsageResponseEvent } from "./GetUsageResponseEvent";
 export type { GetUserAgentResponse } from "./GetUserAgentResponse";
 export type { GetUserSavedConfigResponse } from "./GetUserSavedConfigResponse";
 export type { GhostCommit } from "./GhostCommit";
@@ -104,6 +116,7 @@ export type { ItemStartedEvent } from "./ItemStartedEvent";
 export type { ListConversationsParams } from "./ListConversationsParams";
 export type { ListConversationsResponse } from "./ListConversationsResponse";
 export type { ListCustomPromptsResponseEvent } from "./ListCustomPromptsResponseEvent";
+export type { ListMcpPromptsResponseEvent } from "./ListMcpPromptsResponseEvent";
 export type { ListRemoteSkillsResponseEvent } from "./ListRemoteSkillsResponseEvent";
 export type { ListSkillsResponseEvent } from "./ListSkillsResponseEvent";
 export type { LocalShellAction } from "./LocalShellAction";
@@ -116,15 +129,21 @@ export type { LoginChatGptResponse } from "./LoginChatGptResponse";
 export type { LogoutChatGptResponse } from "./LogoutChatGptResponse";
 export type { McpAuthStatus } from "./McpAuthStatus";
 export type { McpInvocation } from "./McpInvocation";
+export type { McpListChangedEvent } from "./McpListChangedEvent";
+export type { McpListKind } from "./McpListKind";
 export type { McpListToolsResponseEvent } from "./McpListToolsResponseEvent";
+export type { McpServerHealthEvent } from "./McpServerHealthEvent";
+export type { McpServerHealthStatus } from "./McpServerHealthStatus";
 export type { McpStartupCompleteEvent } from "./McpStartupCompleteEvent";
 export type { McpStartupFailure } from "./McpStartupFailure";
 export type { McpStartupStatus } from "./McpStartupStatus";
 export type { McpStartupUpdateEvent } from "./McpStartupUpdateEvent";
 export type { McpToolCallBeginEvent } from "./McpToolCallBeginEvent";
 export type { McpToolCallEndEvent } from "./McpToolCallEndEvent";
+export type { McpToolCallQueuedEvent } from "./McpToolCallQueuedEvent";
 export type { MessagePhase } from "./MessagePhase";
 export type { ModeKind } from "./ModeKind";
+export type { ModelProviderFallbackEvent } from "./ModelProviderFallbackEvent";
 export type { ModelRerouteEvent } from "./ModelRerouteEvent";
 export type { ModelRerouteReason } from "./ModelRerouteReason";
 export type { NetworkAccess } from "./NetworkAccess";
@@ -137,11 +156,18 @@ export type { PatchApplyBeginEvent } from "./PatchApplyBeginEvent";
 export type { PatchApplyEndEvent } from "./PatchApplyEndEvent";
 export type { PatchApplyStatus } from "./PatchApplyStatus";
 export type { Personality } from "./Personality";
+export type { PinnedContextEntry } from "./PinnedContextEntry";
+export type { PinnedContextSource } from "./PinnedContextSource";
+export type { PinnedContextUpdatedEvent } from "./PinnedContextUpdatedEvent";
 export type { PlanDeltaEvent } from "./PlanDeltaEvent";
 export type { PlanItem } from "./PlanItem";
 export type { PlanItemArg } from "./PlanItemArg";
 export type { PlanType } from "./PlanType";
 export type { Profile } from "./Profile";
+export type { ProjectDocsLoadedEvent } from "./ProjectDocsLoadedEvent";
+export type { Prompt } from "./Prompt";
+export type { PromptArgument } from "./PromptArgument";
+export type { QueuedUserInput } from "./QueuedUserInput";
 export type { RateLimitSnapshot } from "./RateLimitSnapshot";
 export type { RateLimitWindow } from "./RateLimitWindow";
 export type { RawResponseItemEvent } from "./RawResponseItemEvent";
@@ -198,6 +224,7 @@ export type { SkillToolDependency } from "./SkillToolDependency";
 export type { SkillsListEntry } from "./SkillsListEntry";
 export type { StepStatus } from "./StepStatus";
 export type { StreamErrorEvent } from "./StreamErrorEvent";
+export type { StreamRetryEvent } from "./StreamRetryEvent";
 export type { SubAgentSource } from "./SubAgentSource";
 export type { TerminalInteractionEvent } from "./TerminalInteractionEvent";
 export type { TextElement } from "./TextElement";
@@ -214,12 +241,17 @@ export type { TurnAbortedEvent } from "./TurnAbortedEvent";
 export type { TurnCompleteEvent } from "./TurnCompleteEvent";
 export type { TurnDiffEvent } from "./TurnDiffEvent";
 export type { TurnItem } from "./TurnItem";
+export type { TurnSettingsEvent } from "./TurnSettingsEvent";
 export type { TurnStartedEvent } from "./TurnStartedEvent";
+export type { TurnStructuredOutputEvent } from "./TurnStructuredOutputEvent";
 export type { UndoCompletedEvent } from "./UndoCompletedEvent";
 export type { UndoStartedEvent } from "./UndoStartedEvent";
 export type { UpdatePlanArgs } from "./UpdatePlanArgs";
+export type { UsageReport } from "./UsageReport";
+export type { UsageSummary } from "./UsageSummary";
 export type { UserInfoResponse } from "./UserInfoResponse";
 export type { UserInput } from "./UserInput";
+export type { UserInputQueueUpdatedEvent } from "./UserInputQueueUpdatedEvent";
 export type { UserMessageEvent } from "./UserMessageEvent";
 export type { UserMessageItem } from "./UserMessageItem";
 export type { UserSavedConfig } from "./UserSavedConfig";
@@ -231,4 +263,6 @@ export type { WebSearchBeginEvent } from "./WebSearchBeginEvent";
 export type { WebSearchEndEvent } from "./WebSearchEndEvent";
 export type { WebSearchItem } from "./WebSearchItem";
 export type { WebSearchMode } from "./WebSearchMode";
+export type { WorktreeCreatedEvent } from "./WorktreeCreatedEvent";
+export type { WorktreeMergedEvent } from "./WorktreeMergedEvent";
 export * as v2 from "./v2";
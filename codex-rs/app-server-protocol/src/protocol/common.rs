@@ -890,6 +890,7 @@ mod tests {
                         "codex/event/session_configured".to_string(),
                         "item/agentMessage/delta".to_string(),
                     ]),
+                    protocol_version: None,
                 }),
             },
         };
@@ -955,6 +956,7 @@ mod tests {
                             "codex/event/session_configured".to_string(),
                             "item/agentMessage/delta".to_string(),
                         ]),
+                        protocol_version: None,
                     }),
                 },
             }
@@ -56,12 +56,20 @@ pub struct InitializeCapabilities {
     /// connection (for example `codex/event/session_configured`).
     #[ts(optional = nullable)]
     pub opt_out_notification_methods: Option<Vec<String>>,
+    /// Codex protocol version the client was built against. `codex/event/*` notifications for
+    /// event types added in later versions are not sent to this connection. Omit to receive every
+    /// event type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional = nullable)]
+    pub protocol_version: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 pub struct InitializeResponse {
     pub user_agent: String,
+    /// Codex protocol version spoken by the server.
+    pub protocol_version: u32,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
@@ -1199,6 +1199,30 @@ pub(crate) async fn apply_bespoke_event_handling(
             )
             .await;
         }
+        EventMsg::BudgetLimitReached(event) => {
+            // Clients have no way to confirm budget overruns yet, so stop the turn.
+            if let Err(err) = conversation
+                .submit(Op::BudgetApproval {
+                    id: event.id,
+                    decision: ReviewDecision::Denied,
+                })
+                .await
+            {
+                error!("failed to decline budget limit: {err}");
+            }
+        }
+        EventMsg::AuthExpired(event) => {
+            // Clients have no way to sign in again mid-turn yet, so stop the turn.
+            if let Err(err) = conversation
+                .submit(Op::ReauthComplete {
+                    id: event.id,
+                    success: false,
+                })
+                .await
+            {
+                error!("failed to decline re-authentication: {err}");
+            }
+        }
 
         _ => {}
     }
@@ -226,6 +226,8 @@ mod tests {
                 allow_unix_sockets: Some(vec!["/tmp/proxy.sock".to_string()]),
                 allow_local_binding: Some(true),
             }),
+            blocked_tools: None,
+            requirements_url: None,
         };
 
         let mapped = map_requirements_toml_to_api(requirements);
@@ -276,6 +278,8 @@ mod tests {
             rules: None,
             enforce_residency: None,
             network: None,
+            blocked_tools: None,
+            requirements_url: None,
         };
 
         let mapped = map_requirements_toml_to_api(requirements);
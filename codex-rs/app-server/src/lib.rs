@@ -4,6 +4,7 @@ use codex_cloud_requirements::cloud_requirements_loader;
 use codex_core::AuthManager;
 use codex_core::config::Config;
 use codex_core::config::ConfigBuilder;
+use codex_core::config::types::LogFormat;
 use codex_core::config_loader::CloudRequirementsLoader;
 use codex_core::config_loader::ConfigLayerStackOrdering;
 use codex_core::config_loader::LoaderOverrides;
@@ -13,6 +14,7 @@ use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::io::ErrorKind;
 use std::io::Result as IoResult;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::RwLock;
@@ -20,6 +22,7 @@ use std::sync::atomic::AtomicBool;
 
 use crate::message_processor::MessageProcessor;
 use crate::message_processor::MessageProcessorArgs;
+use crate::metrics_endpoint::start_metrics_endpoint;
 use crate::outgoing_message::ConnectionId;
 use crate::outgoing_message::OutgoingEnvelope;
 use crate::outgoing_message::OutgoingMessageSender;
@@ -40,6 +43,8 @@ use codex_core::check_execpolicy_for_warnings;
 use codex_core::config_loader::ConfigLoadError;
 use codex_core::config_loader::TextRange as CoreTextRange;
 use codex_feedback::CodexFeedback;
+use codex_otel::json_log::JsonLogLayer;
+use codex_otel::json_log::log_writer;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
 use toml::Value as TomlValue;
@@ -59,6 +64,7 @@ mod error_code;
 mod filters;
 mod fuzzy_file_search;
 mod message_processor;
+mod metrics_endpoint;
 mod models;
 mod outgoing_message;
 mod thread_state;
@@ -209,6 +215,7 @@ pub async fn run_main(
         loader_overrides,
         default_analytics_enabled,
         AppServerTransport::Stdio,
+        Vec::new(),
     )
     .await
 }
@@ -219,6 +226,7 @@ pub async fn run_main_with_transport(
     loader_overrides: LoaderOverrides,
     default_analytics_enabled: bool,
     transport: AppServerTransport,
+    websocket_allowed_origins: Vec<String>,
 ) -> IoResult<()> {
     let (transport_event_tx, mut transport_event_rx) =
         mpsc::channel::<TransportEvent>(CHANNEL_CAPACITY);
@@ -233,8 +241,14 @@ pub async fn run_main_with_transport(
             start_stdio_connection(transport_event_tx.clone(), &mut stdio_handles).await?;
         }
         AppServerTransport::WebSocket { bind_address } => {
-            websocket_accept_handle =
-                Some(start_websocket_acceptor(bind_address, transport_event_tx.clone()).await?);
+            websocket_accept_handle = Some(
+                start_websocket_acceptor(
+                    bind_address,
+                    websocket_allowed_origins,
+                    transport_event_tx.clone(),
+                )
+                .await?,
+            );
         }
     }
     let shutdown_when_no_connections = matches!(transport, AppServerTransport::Stdio);
@@ -272,7 +286,7 @@ pub async fn run_main_with_transport(
             }
 
             let auth_manager = AuthManager::shared(
-                config.codex_home.clone(),
+                config.auth_home(),
                 false,
                 config.cli_auth_credentials_store_mode,
             );
@@ -340,12 +354,36 @@ pub async fn run_main_with_transport(
         )
     })?;
 
+    let mut metrics_endpoint_handle = None;
+    if let Some(listen) = config.otel.prometheus_listen.as_deref() {
+        let bind_address: SocketAddr = listen.parse().map_err(|e| {
+            std::io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("invalid otel.prometheus_listen `{listen}`: {e}"),
+            )
+        })?;
+        if let Some(metrics) = otel.as_ref().and_then(|o| o.metrics().cloned()) {
+            metrics_endpoint_handle = Some(start_metrics_endpoint(bind_address, metrics).await?);
+        }
+    }
+
     // Install a simple subscriber so `tracing` output is visible.  Users can
     // control the log level with `RUST_LOG`.
-    let stderr_fmt = tracing_subscriber::fmt::layer()
-        .with_writer(std::io::stderr)
-        .with_span_events(tracing_subscriber::fmt::format::FmtSpan::FULL)
-        .with_filter(EnvFilter::from_default_env());
+    let writer = log_writer(config.log_file.as_deref())?;
+    let (stderr_fmt, json_log_layer) = match config.log_format {
+        LogFormat::Text => {
+            let stderr_fmt = tracing_subscriber::fmt::layer()
+                .with_writer(writer)
+                .with_ansi(config.log_file.is_none())
+                .with_span_events(tracing_subscriber::fmt::format::FmtSpan::FULL)
+                .with_filter(EnvFilter::from_default_env());
+            (Some(stderr_fmt), None)
+        }
+        LogFormat::Json => (
+            None,
+            Some(JsonLogLayer::new(writer).with_filter(EnvFilter::from_default_env())),
+        ),
+    };
 
     let feedback_layer = feedback.logger_layer();
     let feedback_metadata_layer = feedback.metadata_layer();
@@ -356,6 +394,7 @@ pub async fn run_main_with_transport(
 
     let _ = tracing_subscriber::registry()
         .with(stderr_fmt)
+        .with(json_log_layer)
         .with(feedback_layer)
         .with(feedback_metadata_layer)
         .with(otel_logger_layer)
@@ -587,6 +626,10 @@ pub async fn run_main_with_transport(
         handle.abort();
     }
 
+    if let Some(handle) = metrics_endpoint_handle {
+        handle.abort();
+    }
+
     for handle in stdio_handles {
         let _ = handle.await;
     }
@@ -20,6 +20,11 @@ struct AppServerArgs {
         default_value = AppServerTransport::DEFAULT_LISTEN_URL
     )]
     listen: AppServerTransport,
+
+    /// Browser origin, such as `https://app.example.com`, allowed to open a websocket
+    /// connection. Pages served from loopback hosts are always allowed. Repeatable.
+    #[arg(long = "allowed-origin", value_name = "ORIGIN")]
+    allowed_origins: Vec<String>,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -41,6 +46,7 @@ fn main() -> anyhow::Result<()> {
             loader_overrides,
             false,
             transport,
+            args.allowed_origins,
         )
         .await?;
         Ok(())
@@ -48,7 +48,9 @@ use codex_core::default_client::set_default_client_residency_requirement;
 use codex_core::default_client::set_default_originator;
 use codex_feedback::CodexFeedback;
 use codex_protocol::ThreadId;
+use codex_protocol::protocol::PROTOCOL_VERSION;
 use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::event_types_newer_than;
 use tokio::sync::broadcast;
 use tokio::time::Duration;
 use tokio::time::timeout;
@@ -161,7 +163,7 @@ impl MessageProcessor {
             config_warnings,
         } = args;
         let auth_manager = AuthManager::shared(
-            config.codex_home.clone(),
+            config.auth_home(),
             false,
             config.cli_auth_credentials_store_mode,
         );
@@ -261,16 +263,28 @@ impl MessageProcessor {
                     // shared thread when another connected client did not opt into
                     // experimental API). Proposed direction is instance-global first-write-wins
                     // with initialize-time mismatch rejection.
-                    let (experimental_api_enabled, opt_out_notification_methods) =
-                        match params.capabilities {
-                            Some(capabilities) => (
-                                capabilities.experimental_api,
-                                capabilities
-                                    .opt_out_notification_methods
-                                    .unwrap_or_default(),
-                            ),
-                            None => (false, Vec::new()),
-                        };
+                    let (
+                        experimental_api_enabled,
+                        mut opt_out_notification_methods,
+                        client_protocol_version,
+                    ) = match params.capabilities {
+                        Some(capabilities) => (
+                            capabilities.experimental_api,
+                            capabilities
+                                .opt_out_notification_methods
+                                .unwrap_or_default(),
+                            capabilities.protocol_version,
+                        ),
+                        None => (false, Vec::new(), None),
+                    };
+                    // Clients built against an older protocol are not sent event types they
+                    // cannot decode.
+                    if let Some(client_protocol_version) = client_protocol_version {
+                        opt_out_notification_methods.extend(
+                            event_types_newer_than(client_protocol_version)
+                                .map(|event_type| format!("codex/event/{event_type}")),
+                        );
+                    }
                     session.experimental_api_enabled = experimental_api_enabled;
                     session.opted_out_notification_methods =
                         opt_out_notification_methods.into_iter().collect();
@@ -307,7 +321,10 @@ impl MessageProcessor {
                     }
 
                     let user_agent = get_codex_user_agent();
-                    let response = InitializeResponse { user_agent };
+                    let response = InitializeResponse {
+                        user_agent,
+                        protocol_version: PROTOCOL_VERSION,
+                    };
                     self.outgoing.send_response(request_id, response).await;
 
                     session.initialized = true;
@@ -0,0 +1,125 @@
+//! A minimal HTTP endpoint serving Prometheus metrics at `/metrics`.
+//!
+//! Enabled with `otel.prometheus_listen`. Every request is answered on its own connection and the
+//! connection is closed afterwards, which is all a Prometheus scraper needs.
+
+use std::io::Result as IoResult;
+use std::net::SocketAddr;
+
+use codex_otel::metrics::MetricsClient;
+use codex_otel::metrics::PROMETHEUS_CONTENT_TYPE;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+use tokio::task::JoinHandle;
+use tracing::error;
+use tracing::info;
+use tracing::warn;
+
+/// Requests with a larger head are rejected.
+const MAX_REQUEST_HEAD_BYTES: usize = 8 * 1024;
+
+pub(crate) async fn start_metrics_endpoint(
+    bind_address: SocketAddr,
+    metrics: MetricsClient,
+) -> IoResult<JoinHandle<()>> {
+    let listener = TcpListener::bind(bind_address).await?;
+    let local_addr = listener.local_addr()?;
+    info!("app-server metrics listening on http://{local_addr}/metrics");
+
+    Ok(tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _peer_addr)) => {
+                    let metrics = metrics.clone();
+                    tokio::spawn(async move {
+                        if let Err(err) = serve_connection(stream, &metrics).await {
+                            warn!("failed to serve metrics request: {err}");
+                        }
+                    });
+                }
+                Err(err) => {
+                    error!("failed to accept metrics connection: {err}");
+                }
+            }
+        }
+    }))
+}
+
+async fn serve_connection(mut stream: TcpStream, metrics: &MetricsClient) -> IoResult<()> {
+    let Some(request_line) = read_request_line(&mut stream).await? else {
+        return write_response(
+            &mut stream,
+            "400 Bad Request",
+            "text/plain",
+            "bad request\n",
+        )
+        .await;
+    };
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+    let path = path.split('?').next().unwrap_or_default();
+    match (method, path) {
+        ("GET", "/metrics") => match metrics.prometheus_text() {
+            Ok(body) => write_response(&mut stream, "200 OK", PROMETHEUS_CONTENT_TYPE, &body).await,
+            Err(err) => {
+                warn!("failed to collect metrics: {err}");
+                write_response(
+                    &mut stream,
+                    "500 Internal Server Error",
+                    "text/plain",
+                    "failed to collect metrics\n",
+                )
+                .await
+            }
+        },
+        ("GET", _) => {
+            write_response(&mut stream, "404 Not Found", "text/plain", "not found\n").await
+        }
+        _ => {
+            write_response(
+                &mut stream,
+                "405 Method Not Allowed",
+                "text/plain",
+                "method not allowed\n",
+            )
+            .await
+        }
+    }
+}
+
+/// Reads the request head and returns its first line, or `None` when the head is malformed or too
+/// large.
+async fn read_request_line(stream: &mut TcpStream) -> IoResult<Option<String>> {
+    let mut head = Vec::new();
+    let mut buf = [0; 1024];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        if head.len() > MAX_REQUEST_HEAD_BYTES {
+            return Ok(None);
+        }
+        let read = stream.read(&mut buf).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        head.extend_from_slice(&buf[..read]);
+    }
+    let head = String::from_utf8_lossy(&head);
+    Ok(head.lines().next().map(str::to_string))
+}
+
+async fn write_response(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> IoResult<()> {
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
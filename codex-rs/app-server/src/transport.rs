@@ -15,6 +15,7 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::io::ErrorKind;
 use std::io::Result as IoResult;
+use std::net::IpAddr;
 use std::net::SocketAddr;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -22,6 +23,7 @@ use std::sync::RwLock;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
+use std::time::Duration;
 use tokio::io::AsyncBufReadExt;
 use tokio::io::AsyncWriteExt;
 use tokio::io::BufReader;
@@ -30,8 +32,14 @@ use tokio::net::TcpListener;
 use tokio::net::TcpStream;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
-use tokio_tungstenite::accept_async;
+use tokio::time::Instant;
+use tokio_tungstenite::accept_hdr_async;
 use tokio_tungstenite::tungstenite::Message as WebSocketMessage;
+use tokio_tungstenite::tungstenite::handshake::server::ErrorResponse;
+use tokio_tungstenite::tungstenite::handshake::server::Request;
+use tokio_tungstenite::tungstenite::handshake::server::Response;
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::tungstenite::http::header::ORIGIN;
 use tracing::debug;
 use tracing::error;
 use tracing::info;
@@ -42,6 +50,10 @@ use tracing::warn;
 /// plenty for an interactive CLI.
 pub(crate) const CHANNEL_CAPACITY: usize = 128;
 
+/// How often websocket connections are pinged, so proxies between a browser and the server do
+/// not close them while a turn is quiet.
+const WEBSOCKET_PING_INTERVAL: Duration = Duration::from_secs(30);
+
 fn colorize(text: &str, style: Style) -> String {
     text.if_supports_color(Stream::Stderr, |value| value.style(style))
         .to_string()
@@ -251,8 +263,13 @@ pub(crate) async fn start_stdio_connection(
     Ok(())
 }
 
+/// Accepts websocket connections on `bind_address`. Browsers send an `Origin` header, and only
+/// pages served from a loopback host or listed in `allowed_origins` may connect, so other sites
+/// open in the browser cannot drive the server. Clients that send no `Origin` are not browsers and
+/// are always accepted.
 pub(crate) async fn start_websocket_acceptor(
     bind_address: SocketAddr,
+    allowed_origins: Vec<String>,
     transport_event_tx: mpsc::Sender<TransportEvent>,
 ) -> IoResult<JoinHandle<()>> {
     let listener = TcpListener::bind(bind_address).await?;
@@ -261,6 +278,7 @@ pub(crate) async fn start_websocket_acceptor(
     info!("app-server websocket listening on ws://{local_addr}");
 
     let connection_counter = Arc::new(AtomicU64::new(1));
+    let allowed_origins = Arc::new(allowed_origins);
     Ok(tokio::spawn(async move {
         loop {
             match listener.accept().await {
@@ -269,10 +287,12 @@ pub(crate) async fn start_websocket_acceptor(
                     let connection_id =
                         ConnectionId(connection_counter.fetch_add(1, Ordering::Relaxed));
                     let transport_event_tx_for_connection = transport_event_tx.clone();
+                    let allowed_origins = Arc::clone(&allowed_origins);
                     tokio::spawn(async move {
                         run_websocket_connection(
                             connection_id,
                             stream,
+                            &allowed_origins,
                             transport_event_tx_for_connection,
                         )
                         .await;
@@ -289,9 +309,13 @@ pub(crate) async fn start_websocket_acceptor(
 async fn run_websocket_connection(
     connection_id: ConnectionId,
     stream: TcpStream,
+    allowed_origins: &[String],
     transport_event_tx: mpsc::Sender<TransportEvent>,
 ) {
-    let websocket_stream = match accept_async(stream).await {
+    let check_origin = |request: &Request, response: Response| {
+        check_websocket_origin(request, allowed_origins).map(|()| response)
+    };
+    let websocket_stream = match accept_hdr_async(stream, check_origin).await {
         Ok(stream) => stream,
         Err(err) => {
             warn!("failed to complete websocket handshake: {err}");
@@ -313,8 +337,17 @@ async fn run_websocket_connection(
     }
 
     let (mut websocket_writer, mut websocket_reader) = websocket_stream.split();
+    let mut ping_interval = tokio::time::interval_at(
+        Instant::now() + WEBSOCKET_PING_INTERVAL,
+        WEBSOCKET_PING_INTERVAL,
+    );
     loop {
         tokio::select! {
+            _ = ping_interval.tick() => {
+                if websocket_writer.send(WebSocketMessage::Ping(Default::default())).await.is_err() {
+                    break;
+                }
+            }
             outgoing_message = writer_rx.recv() => {
                 let Some(outgoing_message) = outgoing_message else {
                     break;
@@ -365,6 +398,47 @@ async fn run_websocket_connection(
         .await;
 }
 
+fn check_websocket_origin(
+    request: &Request,
+    allowed_origins: &[String],
+) -> Result<(), ErrorResponse> {
+    let Some(origin) = request.headers().get(ORIGIN) else {
+        return Ok(());
+    };
+    let origin = origin.to_str().unwrap_or_default();
+    if is_allowed_origin(origin, allowed_origins) {
+        return Ok(());
+    }
+
+    warn!("rejecting websocket connection from origin `{origin}`");
+    let mut response = ErrorResponse::new(Some(format!(
+        "origin `{origin}` is not allowed; start the app server with --allowed-origin {origin}"
+    )));
+    *response.status_mut() = StatusCode::FORBIDDEN;
+    Err(response)
+}
+
+fn is_allowed_origin(origin: &str, allowed_origins: &[String]) -> bool {
+    let origin = origin.trim_end_matches('/');
+    allowed_origins
+        .iter()
+        .any(|allowed| allowed.trim_end_matches('/').eq_ignore_ascii_case(origin))
+        || is_loopback_origin(origin)
+}
+
+/// Whether `origin` (`scheme://host[:port]`) names a loopback host.
+fn is_loopback_origin(origin: &str) -> bool {
+    let Some((_scheme, authority)) = origin.split_once("://") else {
+        return false;
+    };
+    let host = match authority.strip_prefix('[') {
+        Some(bracketed) => bracketed.split(']').next().unwrap_or_default(),
+        None => authority.split(':').next().unwrap_or_default(),
+    };
+    host.eq_ignore_ascii_case("localhost")
+        || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
 async fn forward_incoming_message(
     transport_event_tx: &mpsc::Sender<TransportEvent>,
     writer: &mpsc::Sender<OutgoingMessage>,
@@ -560,6 +634,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn websocket_origin_allows_loopback_and_listed_origins() {
+        let allowed_origins = vec!["https://app.example.com/".to_string()];
+        for origin in [
+            "http://localhost:5173",
+            "http://127.0.0.1:8080",
+            "http://[::1]:3000",
+            "https://LOCALHOST",
+            "https://app.example.com",
+        ] {
+            assert!(is_allowed_origin(origin, &allowed_origins), "{origin}");
+        }
+        for origin in [
+            "https://evil.example.com",
+            "https://app.example.com.evil.example",
+            "http://localhost.evil.example",
+            "null",
+        ] {
+            assert!(!is_allowed_origin(origin, &allowed_origins), "{origin}");
+        }
+    }
+
     #[tokio::test]
     async fn enqueue_incoming_request_returns_overload_error_when_queue_is_full() {
         let connection_id = ConnectionId(42);
@@ -27,6 +27,10 @@ use tokio_tungstenite::MaybeTlsStream;
 use tokio_tungstenite::WebSocketStream;
 use tokio_tungstenite::connect_async;
 use tokio_tungstenite::tungstenite::Message as WebSocketMessage;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::tungstenite::http::header::ORIGIN;
 
 const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(5);
 
@@ -78,12 +82,64 @@ async fn websocket_transport_routes_per_connection_handshake_and_responses() ->
     Ok(())
 }
 
+#[tokio::test]
+async fn websocket_transport_rejects_browser_origins_that_are_not_allowed() -> Result<()> {
+    let server = create_mock_responses_server_sequence_unchecked(Vec::new()).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), "never")?;
+
+    let bind_addr = reserve_local_addr()?;
+    let mut process = spawn_websocket_server_with_args(
+        codex_home.path(),
+        bind_addr,
+        &["--allowed-origin", "https://app.example.com"],
+    )
+    .await?;
+    // Wait until the server accepts connections.
+    drop(connect_websocket(bind_addr).await?);
+
+    let rejected = connect_websocket_with_origin(bind_addr, "https://evil.example.com").await;
+    match rejected {
+        Err(tokio_tungstenite::tungstenite::Error::Http(response)) => {
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        }
+        other => bail!(
+            "expected the handshake to be rejected, got {:?}",
+            other.map(|_| ())
+        ),
+    }
+
+    for origin in ["https://app.example.com", "http://localhost:5173"] {
+        let (mut ws, _response) = connect_websocket_with_origin(bind_addr, origin)
+            .await
+            .with_context(|| format!("origin {origin} should be allowed"))?;
+        send_initialize_request(&mut ws, 1, "browser_client").await?;
+        let init = read_response_for_id(&mut ws, 1).await?;
+        assert_eq!(init.id, RequestId::Integer(1));
+    }
+
+    process
+        .kill()
+        .await
+        .context("failed to stop websocket app-server process")?;
+    Ok(())
+}
+
 async fn spawn_websocket_server(codex_home: &Path, bind_addr: SocketAddr) -> Result<Child> {
+    spawn_websocket_server_with_args(codex_home, bind_addr, &[]).await
+}
+
+async fn spawn_websocket_server_with_args(
+    codex_home: &Path,
+    bind_addr: SocketAddr,
+    args: &[&str],
+) -> Result<Child> {
     let program = codex_utils_cargo_bin::cargo_bin("codex-app-server")
         .context("should find app-server binary")?;
     let mut cmd = Command::new(program);
     cmd.arg("--listen")
         .arg(format!("ws://{bind_addr}"))
+        .args(args)
         .stdin(Stdio::null())
         .stdout(Stdio::null())
         .stderr(Stdio::piped())
@@ -129,6 +185,21 @@ async fn connect_websocket(bind_addr: SocketAddr) -> Result<WsClient> {
     }
 }
 
+async fn connect_websocket_with_origin(
+    bind_addr: SocketAddr,
+    origin: &str,
+) -> tokio_tungstenite::tungstenite::Result<(
+    WsClient,
+    tokio_tungstenite::tungstenite::handshake::client::Response,
+)> {
+    let mut request = format!("ws://{bind_addr}").into_client_request()?;
+    request.headers_mut().insert(
+        ORIGIN,
+        HeaderValue::from_str(origin).expect("origin should be a valid header value"),
+    );
+    connect_async(request).await
+}
+
 async fn send_initialize_request(stream: &mut WsClient, id: i64, client_name: &str) -> Result<()> {
     let params = InitializeParams {
         client_info: ClientInfo {
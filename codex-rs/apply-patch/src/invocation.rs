@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::path::Path;
+use std::path::PathBuf;
 use std::sync::LazyLock;
 
 use tree_sitter::Parser;
@@ -15,10 +16,16 @@ use crate::ApplyPatchFileChange;
 use crate::ApplyPatchFileUpdate;
 use crate::IoError;
 use crate::MaybeApplyPatchVerified;
+use crate::PatchValidation;
+use crate::file_update;
+use crate::merge::MergeOutcome;
+use crate::merge::merge_update_with_base;
 use crate::parser::Hunk;
 use crate::parser::ParseError;
 use crate::parser::parse_patch;
+use crate::parser::render_patch;
 use crate::unified_diff_from_chunks;
+use crate::validate_hunks;
 use std::str::Utf8Error;
 use tree_sitter::LanguageError;
 
@@ -130,6 +137,18 @@ pub fn maybe_parse_apply_patch(argv: &[String]) -> MaybeApplyPatch {
 /// cwd must be an absolute path so that we can resolve relative paths in the
 /// patch.
 pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApplyPatchVerified {
+    maybe_parse_apply_patch_verified_with_base(argv, cwd, |_| None)
+}
+
+/// Like [`maybe_parse_apply_patch_verified`], but an update whose hunks no longer match the file
+/// is merged three ways when `base_contents` returns the contents of that path as the model last
+/// saw it. Cleanly merged updates are rewritten to apply to the current file. Files whose edits
+/// overlap are left out of the patch and reported in [`ApplyPatchAction::conflicts`].
+pub fn maybe_parse_apply_patch_verified_with_base<'a>(
+    argv: &[String],
+    cwd: &Path,
+    base_contents: impl Fn(&Path) -> Option<&'a str>,
+) -> MaybeApplyPatchVerified {
     // Detect a raw patch body passed directly as the command or as the body of a shell
     // script. In these cases, report an explicit error rather than applying the patch.
     if let [body] = argv
@@ -149,23 +168,21 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
             hunks,
             workdir,
         }) => {
-            let effective_cwd = workdir
-                .as_ref()
-                .map(|dir| {
-                    let path = Path::new(dir);
-                    if path.is_absolute() {
-                        path.to_path_buf()
-                    } else {
-                        cwd.join(path)
-                    }
-                })
-                .unwrap_or_else(|| cwd.to_path_buf());
+            let effective_cwd = effective_cwd(workdir.as_deref(), cwd);
             let mut changes = HashMap::new();
+            let mut conflicts = Vec::new();
+            let mut applied_hunks = Vec::with_capacity(hunks.len());
+            let mut patch_rewritten = false;
             for hunk in hunks {
                 let path = hunk.resolve_path(&effective_cwd);
-                match hunk {
+                match &hunk {
                     Hunk::AddFile { contents, .. } => {
-                        changes.insert(path, ApplyPatchFileChange::Add { content: contents });
+                        changes.insert(
+                            path,
+                            ApplyPatchFileChange::Add {
+                                content: contents.clone(),
+                            },
+                        );
                     }
                     Hunk::DeleteFile { .. } => {
                         let content = match std::fs::read_to_string(&path) {
@@ -182,30 +199,86 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                         changes.insert(path, ApplyPatchFileChange::Delete { content });
                     }
                     Hunk::UpdateFile {
-                        move_path, chunks, ..
+                        path: hunk_path,
+                        move_path,
+                        chunks,
                     } => {
-                        let ApplyPatchFileUpdate {
-                            unified_diff,
-                            content: contents,
-                        } = match unified_diff_from_chunks(&path, &chunks) {
-                            Ok(diff) => diff,
+                        let (update, merged_chunks) = match unified_diff_from_chunks(&path, chunks)
+                        {
+                            Ok(diff) => (diff, None),
+                            Err(e @ ApplyPatchError::ComputeReplacements(_)) => {
+                                let Some(base) = base_contents(&path) else {
+                                    return MaybeApplyPatchVerified::CorrectnessError(e);
+                                };
+                                match merge_update_with_base(&path, chunks, base) {
+                                    Ok(MergeOutcome::Merged {
+                                        original_contents,
+                                        new_contents,
+                                        chunks,
+                                    }) => (
+                                        file_update(&original_contents, new_contents, 1),
+                                        Some(chunks),
+                                    ),
+                                    Ok(MergeOutcome::Conflict(conflict)) => {
+                                        conflicts.push(conflict);
+                                        patch_rewritten = true;
+                                        continue;
+                                    }
+                                    // The patch does not match what the model saw either.
+                                    Err(_) => return MaybeApplyPatchVerified::CorrectnessError(e),
+                                }
+                            }
                             Err(e) => {
                                 return MaybeApplyPatchVerified::CorrectnessError(e);
                             }
                         };
+                        if let Some(chunks) = merged_chunks {
+                            patch_rewritten = true;
+                            if chunks.is_empty() && move_path.is_none() {
+                                // The file already contains the patch's changes.
+                                continue;
+                            }
+                            let hunk_path = hunk_path.clone();
+                            let move_path = move_path.clone();
+                            changes.insert(
+                                path,
+                                ApplyPatchFileChange::Update {
+                                    unified_diff: update.unified_diff,
+                                    move_path: move_path.as_ref().map(|p| effective_cwd.join(p)),
+                                    new_content: update.content,
+                                },
+                            );
+                            applied_hunks.push(Hunk::UpdateFile {
+                                path: hunk_path,
+                                move_path,
+                                chunks,
+                            });
+                            continue;
+                        }
+                        let ApplyPatchFileUpdate {
+                            unified_diff,
+                            content: contents,
+                        } = update;
                         changes.insert(
                             path,
                             ApplyPatchFileChange::Update {
                                 unified_diff,
-                                move_path: move_path.map(|p| effective_cwd.join(p)),
+                                move_path: move_path.as_ref().map(|p| effective_cwd.join(p)),
                                 new_content: contents,
                             },
                         );
                     }
                 }
+                applied_hunks.push(hunk);
             }
+            let patch = if patch_rewritten {
+                render_patch(&applied_hunks)
+            } else {
+                patch
+            };
             MaybeApplyPatchVerified::Body(ApplyPatchAction {
                 changes,
+                conflicts,
                 patch,
                 cwd: effective_cwd,
             })
@@ -216,6 +289,34 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
     }
 }
 
+/// Validates every hunk of the apply_patch invocation in `argv` against the working tree without
+/// applying it. Returns `None` when `argv` is not a parseable apply_patch invocation.
+pub fn validate_apply_patch(argv: &[String], cwd: &Path) -> Option<PatchValidation> {
+    match maybe_parse_apply_patch(argv) {
+        MaybeApplyPatch::Body(ApplyPatchArgs { hunks, workdir, .. }) => Some(validate_hunks(
+            &hunks,
+            &effective_cwd(workdir.as_deref(), cwd),
+        )),
+        MaybeApplyPatch::ShellParseError(_)
+        | MaybeApplyPatch::PatchParseError(_)
+        | MaybeApplyPatch::NotApplyPatch => None,
+    }
+}
+
+/// Resolves the `cd` target of a heredoc invocation, if any, against `cwd`.
+fn effective_cwd(workdir: Option<&str>, cwd: &Path) -> PathBuf {
+    workdir
+        .map(|dir| {
+            let path = Path::new(dir);
+            if path.is_absolute() {
+                path.to_path_buf()
+            } else {
+                cwd.join(path)
+            }
+        })
+        .unwrap_or_else(|| cwd.to_path_buf())
+}
+
 /// Extract the heredoc body (and optional `cd` workdir) from a `bash -lc` script
 /// that invokes the apply_patch tool using a heredoc.
 ///
@@ -746,6 +847,7 @@ PATCH"#,
         assert_eq!(
             result,
             MaybeApplyPatchVerified::Body(ApplyPatchAction {
+                conflicts: Vec::new(),
                 changes: HashMap::from([(
                     session_dir.path().join(relative_path),
                     ApplyPatchFileChange::Update {
@@ -1,7 +1,9 @@
 mod invocation;
+mod merge;
 mod parser;
 mod seek_sequence;
 mod standalone_executable;
+mod validate;
 
 use std::collections::HashMap;
 use std::path::Path;
@@ -9,16 +11,24 @@ use std::path::PathBuf;
 
 use anyhow::Context;
 use anyhow::Result;
+pub use merge::ApplyPatchConflict;
 pub use parser::Hunk;
 pub use parser::ParseError;
 use parser::ParseError::*;
 use parser::UpdateFileChunk;
 pub use parser::parse_patch;
+pub use seek_sequence::FuzzyMatch;
 use similar::TextDiff;
 use thiserror::Error;
 
 pub use invocation::maybe_parse_apply_patch_verified;
+pub use invocation::maybe_parse_apply_patch_verified_with_base;
+pub use invocation::validate_apply_patch;
 pub use standalone_executable::main;
+pub use validate::HunkStatus;
+pub use validate::HunkValidation;
+pub use validate::PatchValidation;
+pub use validate::validate_hunks;
 
 use crate::invocation::ExtractHeredocError;
 
@@ -119,6 +129,10 @@ pub enum MaybeApplyPatchVerified {
 pub struct ApplyPatchAction {
     changes: HashMap<PathBuf, ApplyPatchFileChange>,
 
+    /// Files left unchanged because the patch conflicted with edits made since the model last
+    /// saw them. Their hunks are not part of `patch`.
+    conflicts: Vec<ApplyPatchConflict>,
+
     /// The raw patch argument that can be used with `apply_patch` as an exec
     /// call. i.e., if the original arg was parsed in "lenient" mode with a
     /// heredoc, this should be the value without the heredoc wrapper.
@@ -138,6 +152,11 @@ impl ApplyPatchAction {
         &self.changes
     }
 
+    /// Returns the files whose updates could not be merged and will not be applied.
+    pub fn conflicts(&self) -> &[ApplyPatchConflict] {
+        &self.conflicts
+    }
+
     /// Should be used exclusively for testing. (Not worth the overhead of
     /// creating a feature flag for this.)
     pub fn new_add_for_test(path: &Path, content: String) -> Self {
@@ -161,6 +180,7 @@ impl ApplyPatchAction {
         #[expect(clippy::expect_used)]
         Self {
             changes,
+            conflicts: Vec::new(),
             cwd: path
                 .parent()
                 .expect("path should have parent")
@@ -271,6 +291,10 @@ fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
     if hunks.is_empty() {
         anyhow::bail!("No files were modified.");
     }
+    // Check every hunk up front so a patch that does not apply leaves the tree untouched.
+    if let Some(message) = validate_hunks(hunks, Path::new("")).first_conflict() {
+        anyhow::bail!("{message}");
+    }
 
     let mut added: Vec<PathBuf> = Vec::new();
     let mut modified: Vec<PathBuf> = Vec::new();
@@ -340,35 +364,55 @@ fn derive_new_contents_from_chunks(
     path: &Path,
     chunks: &[UpdateFileChunk],
 ) -> std::result::Result<AppliedPatch, ApplyPatchError> {
-    let original_contents = match std::fs::read_to_string(path) {
-        Ok(contents) => contents,
-        Err(err) => {
-            return Err(ApplyPatchError::IoError(IoError {
-                context: format!("Failed to read file to update {}", path.display()),
-                source: err,
-            }));
-        }
-    };
+    let original_contents = read_file_to_update(path)?;
+    let new_contents = apply_chunks_to_contents(&original_contents, path, chunks)?;
+    Ok(AppliedPatch {
+        original_contents,
+        new_contents,
+    })
+}
 
-    let mut original_lines: Vec<String> = original_contents.split('\n').map(String::from).collect();
+fn read_file_to_update(path: &Path) -> std::result::Result<String, ApplyPatchError> {
+    std::fs::read_to_string(path).map_err(|err| {
+        ApplyPatchError::IoError(IoError {
+            context: format!("Failed to read file to update {}", path.display()),
+            source: err,
+        })
+    })
+}
 
-    // Drop the trailing empty element that results from the final newline so
-    // that line counts match the behaviour of standard `diff`.
-    if original_lines.last().is_some_and(String::is_empty) {
-        original_lines.pop();
+/// Applies `chunks` to `original_contents`, which is used in error messages as the contents of
+/// `path`, and returns the new contents.
+fn apply_chunks_to_contents(
+    original_contents: &str,
+    path: &Path,
+    chunks: &[UpdateFileChunk],
+) -> std::result::Result<String, ApplyPatchError> {
+    let original_lines = split_lines(original_contents);
+    let replacements = compute_replacements(&original_lines, path, chunks)?;
+    Ok(join_lines(apply_replacements(
+        original_lines,
+        &replacements,
+    )))
+}
+
+/// Joins `lines` into file contents that end with a newline.
+fn join_lines(mut lines: Vec<String>) -> String {
+    if !lines.last().is_some_and(String::is_empty) {
+        lines.push(String::new());
     }
+    lines.join("\n")
+}
 
-    let replacements = compute_replacements(&original_lines, path, chunks)?;
-    let new_lines = apply_replacements(original_lines, &replacements);
-    let mut new_lines = new_lines;
-    if !new_lines.last().is_some_and(String::is_empty) {
-        new_lines.push(String::new());
+fn split_lines(contents: &str) -> Vec<String> {
+    let mut lines: Vec<String> = contents.split('\n').map(String::from).collect();
+
+    // Drop the trailing empty element that results from the final newline so
+    // that line counts match the behaviour of standard `diff`.
+    if lines.last().is_some_and(String::is_empty) {
+        lines.pop();
     }
-    let new_contents = new_lines.join("\n");
-    Ok(AppliedPatch {
-        original_contents,
-        new_contents,
-    })
+    lines
 }
 
 /// Compute a list of replacements needed to transform `original_lines` into the
@@ -383,85 +427,111 @@ fn compute_replacements(
     let mut line_index: usize = 0;
 
     for chunk in chunks {
-        // If a chunk has a `change_context`, we use seek_sequence to find it, then
-        // adjust our `line_index` to continue from there.
-        if let Some(ctx_line) = &chunk.change_context {
-            if let Some(idx) = seek_sequence::seek_sequence(
-                original_lines,
-                std::slice::from_ref(ctx_line),
-                line_index,
-                false,
-            ) {
-                line_index = idx + 1;
-            } else {
-                return Err(ApplyPatchError::ComputeReplacements(format!(
-                    "Failed to find context '{}' in {}",
-                    ctx_line,
-                    path.display()
-                )));
-            }
-        }
-
-        if chunk.old_lines.is_empty() {
-            // Pure addition (no old lines). We'll add them at the end or just
-            // before the final empty line if one exists.
-            let insertion_idx = if original_lines.last().is_some_and(String::is_empty) {
-                original_lines.len() - 1
-            } else {
-                original_lines.len()
-            };
-            replacements.push((insertion_idx, 0, chunk.new_lines.clone()));
-            continue;
-        }
-
-        // Otherwise, try to match the existing lines in the file with the old lines
-        // from the chunk. If found, schedule that region for replacement.
-        // Attempt to locate the `old_lines` verbatim within the file.  In many
-        // real‑world diffs the last element of `old_lines` is an *empty* string
-        // representing the terminating newline of the region being replaced.
-        // This sentinel is not present in `original_lines` because we strip the
-        // trailing empty slice emitted by `split('\n')`.  If a direct search
-        // fails and the pattern ends with an empty string, retry without that
-        // final element so that modifications touching the end‑of‑file can be
-        // located reliably.
-
-        let mut pattern: &[String] = &chunk.old_lines;
-        let mut found =
-            seek_sequence::seek_sequence(original_lines, pattern, line_index, chunk.is_end_of_file);
+        let located = locate_chunk(original_lines, path, chunk, line_index)?;
+        replacements.push(located.replacement);
+        line_index = located.next_line_index;
+    }
 
-        let mut new_slice: &[String] = &chunk.new_lines;
+    replacements.sort_by(|(lhs_idx, _, _), (rhs_idx, _, _)| lhs_idx.cmp(rhs_idx));
 
-        if found.is_none() && pattern.last().is_some_and(String::is_empty) {
-            // Retry without the trailing empty line which represents the final
-            // newline in the file.
-            pattern = &pattern[..pattern.len() - 1];
-            if new_slice.last().is_some_and(String::is_empty) {
-                new_slice = &new_slice[..new_slice.len() - 1];
-            }
+    Ok(replacements)
+}
 
-            found = seek_sequence::seek_sequence(
-                original_lines,
-                pattern,
-                line_index,
-                chunk.is_end_of_file,
-            );
-        }
+/// Where a single chunk applies within a file.
+struct LocatedChunk {
+    replacement: (usize, usize, Vec<String>),
+    /// Where the search for the next chunk starts.
+    next_line_index: usize,
+    /// The loosest match needed to find the chunk's context and old lines, if any was fuzzy.
+    fuzzy: Option<FuzzyMatch>,
+}
 
-        if let Some(start_idx) = found {
-            replacements.push((start_idx, pattern.len(), new_slice.to_vec()));
-            line_index = start_idx + pattern.len();
+/// Finds where `chunk` applies in `original_lines`, searching from `line_index`.
+fn locate_chunk(
+    original_lines: &[String],
+    path: &Path,
+    chunk: &UpdateFileChunk,
+    mut line_index: usize,
+) -> std::result::Result<LocatedChunk, ApplyPatchError> {
+    let mut fuzzy = None;
+
+    // If a chunk has a `change_context`, we use seek_sequence to find it, then
+    // adjust our `line_index` to continue from there.
+    if let Some(ctx_line) = &chunk.change_context {
+        if let Some((idx, context_fuzzy)) = seek_sequence::seek_sequence(
+            original_lines,
+            std::slice::from_ref(ctx_line),
+            line_index,
+            false,
+        ) {
+            line_index = idx + 1;
+            fuzzy = context_fuzzy;
         } else {
             return Err(ApplyPatchError::ComputeReplacements(format!(
-                "Failed to find expected lines in {}:\n{}",
-                path.display(),
-                chunk.old_lines.join("\n"),
+                "Failed to find context '{}' in {}",
+                ctx_line,
+                path.display()
             )));
         }
     }
 
-    replacements.sort_by(|(lhs_idx, _, _), (rhs_idx, _, _)| lhs_idx.cmp(rhs_idx));
+    if chunk.old_lines.is_empty() {
+        // Pure addition (no old lines). We'll add them at the end or just
+        // before the final empty line if one exists.
+        let insertion_idx = if original_lines.last().is_some_and(String::is_empty) {
+            original_lines.len() - 1
+        } else {
+            original_lines.len()
+        };
+        return Ok(LocatedChunk {
+            replacement: (insertion_idx, 0, chunk.new_lines.clone()),
+            next_line_index: line_index,
+            fuzzy,
+        });
+    }
 
-    Ok(replacements)
+    // Otherwise, try to match the existing lines in the file with the old lines
+    // from the chunk. If found, schedule that region for replacement.
+    // Attempt to locate the `old_lines` verbatim within the file.  In many
+    // real‑world diffs the last element of `old_lines` is an *empty* string
+    // representing the terminating newline of the region being replaced.
+    // This sentinel is not present in `original_lines` because we strip the
+    // trailing empty slice emitted by `split('\n')`.  If a direct search
+    // fails and the pattern ends with an empty string, retry without that
+    // final element so that modifications touching the end‑of‑file can be
+    // located reliably.
+
+    let mut pattern: &[String] = &chunk.old_lines;
+    let mut found =
+        seek_sequence::seek_sequence(original_lines, pattern, line_index, chunk.is_end_of_file);
+
+    let mut new_slice: &[String] = &chunk.new_lines;
+
+    if found.is_none() && pattern.last().is_some_and(String::is_empty) {
+        // Retry without the trailing empty line which represents the final
+        // newline in the file.
+        pattern = &pattern[..pattern.len() - 1];
+        if new_slice.last().is_some_and(String::is_empty) {
+            new_slice = &new_slice[..new_slice.len() - 1];
+        }
+
+        found =
+            seek_sequence::seek_sequence(original_lines, pattern, line_index, chunk.is_end_of_file);
+    }
+
+    if let Some((start_idx, lines_fuzzy)) = found {
+        Ok(LocatedChunk {
+            replacement: (start_idx, pattern.len(), new_slice.to_vec()),
+            next_line_index: start_idx + pattern.len(),
+            fuzzy: fuzzy.max(lines_fuzzy),
+        })
+    } else {
+        Err(ApplyPatchError::ComputeReplacements(format!(
+            "Failed to find expected lines in {}:\n{}",
+            path.display(),
+            chunk.old_lines.join("\n"),
+        )))
+    }
 }
 
 /// Apply the `(start_index, old_len, new_lines)` replacements to `original_lines`,
@@ -515,12 +585,20 @@ pub fn unified_diff_from_chunks_with_context(
         original_contents,
         new_contents,
     } = derive_new_contents_from_chunks(path, chunks)?;
-    let text_diff = TextDiff::from_lines(&original_contents, &new_contents);
+    Ok(file_update(&original_contents, new_contents, context))
+}
+
+fn file_update(
+    original_contents: &str,
+    new_contents: String,
+    context: usize,
+) -> ApplyPatchFileUpdate {
+    let text_diff = TextDiff::from_lines(original_contents, &new_contents);
     let unified_diff = text_diff.unified_diff().context_radius(context).to_string();
-    Ok(ApplyPatchFileUpdate {
+    ApplyPatchFileUpdate {
         unified_diff,
         content: new_contents,
-    })
+    }
 }
 
 /// Print the summary of changes in git-style format.
@@ -545,6 +623,7 @@ pub fn print_summary(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use assert_matches::assert_matches;
     use pretty_assertions::assert_eq;
     use std::fs;
     use std::string::ToString;
@@ -1062,4 +1141,70 @@ g
         let result = apply_patch(&patch, &mut stdout, &mut stderr);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_verified_patch_merges_with_recorded_contents() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("merge.txt");
+        let base = "a\nb\nc\nd\ne\nf\ng\nh\n";
+        // Edited by someone else after the model read `base`.
+        fs::write(&path, "A\nb\nc\nd\ne\nf\nG\nh\n").unwrap();
+        let patch = wrap_patch(&format!(
+            "*** Update File: {}\n@@\n a\n b\n c\n-d\n+D\n e",
+            path.display()
+        ));
+        let argv = vec!["apply_patch".to_string(), patch];
+
+        assert_matches!(
+            maybe_parse_apply_patch_verified(&argv, dir.path()),
+            MaybeApplyPatchVerified::CorrectnessError(ApplyPatchError::ComputeReplacements(_))
+        );
+        let MaybeApplyPatchVerified::Body(action) =
+            maybe_parse_apply_patch_verified_with_base(&argv, dir.path(), |_| Some(base))
+        else {
+            panic!("expected a merged patch");
+        };
+        assert!(action.conflicts().is_empty());
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&action.patch, &mut stdout, &mut stderr).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "A\nb\nc\nD\ne\nf\nG\nh\n"
+        );
+    }
+
+    #[test]
+    fn test_verified_patch_reports_overlapping_edits_as_conflicts() {
+        let dir = tempdir().unwrap();
+        let conflicted = dir.path().join("conflicted.txt");
+        let added = dir.path().join("added.txt");
+        fs::write(&conflicted, "a\nX\nc\n").unwrap();
+        let patch = wrap_patch(&format!(
+            "*** Update File: {}\n@@\n a\n-b\n+B\n c\n*** Add File: {}\n+new",
+            conflicted.display(),
+            added.display()
+        ));
+        let argv = vec!["apply_patch".to_string(), patch];
+
+        let MaybeApplyPatchVerified::Body(action) =
+            maybe_parse_apply_patch_verified_with_base(&argv, dir.path(), |_| Some("a\nb\nc\n"))
+        else {
+            panic!("expected a partial patch");
+        };
+        let [conflict] = action.conflicts() else {
+            panic!("expected one conflict, got {:?}", action.conflicts());
+        };
+        assert_eq!(conflict.path, conflicted);
+        assert!(conflict.merged.contains("<<<<<<< ours\nB\n"));
+        assert!(conflict.merged.contains("=======\nX\n>>>>>>> theirs\n"));
+        assert_eq!(action.changes().keys().collect::<Vec<_>>(), vec![&added]);
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&action.patch, &mut stdout, &mut stderr).unwrap();
+        assert_eq!(fs::read_to_string(&conflicted).unwrap(), "a\nX\nc\n");
+        assert_eq!(fs::read_to_string(&added).unwrap(), "new\n");
+    }
 }
@@ -0,0 +1,110 @@
+//! Three-way merging of update hunks that no longer match the file on disk.
+//!
+//! When a file changed after the model last read it, the old lines of a hunk may no longer be
+//! found. If the caller kept the contents the model saw, the hunk is applied to those instead and
+//! the result is merged with the current file. A clean merge is turned back into chunks against
+//! the current file so the rewritten patch still applies; overlapping edits are reported as a
+//! conflict and the file is left alone.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use similar::Algorithm;
+
+use crate::ApplyPatchError;
+use crate::apply_chunks_to_contents;
+use crate::parser::UpdateFileChunk;
+use crate::read_file_to_update;
+use crate::split_lines;
+
+/// Lines of unchanged context around each chunk of a merged update.
+const MERGED_CHUNK_CONTEXT: usize = 3;
+
+/// A file whose update could not be merged with the changes made to it since it was recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApplyPatchConflict {
+    pub path: PathBuf,
+    /// The merged file with git-style conflict markers around each overlapping edit. "Ours" is the
+    /// patch and "theirs" is the file on disk.
+    pub merged: String,
+}
+
+pub(crate) enum MergeOutcome {
+    /// The patch merged cleanly. `chunks` turn `original_contents` (the current file) into
+    /// `new_contents`.
+    Merged {
+        original_contents: String,
+        new_contents: String,
+        chunks: Vec<UpdateFileChunk>,
+    },
+    Conflict(ApplyPatchConflict),
+}
+
+/// Applies `chunks` to `base`, the contents of `path` when the patch was written, and merges the
+/// result with the file as it is now. Fails with the error from applying `chunks` to `base` when
+/// the patch does not match the recorded contents either.
+pub(crate) fn merge_update_with_base(
+    path: &Path,
+    chunks: &[UpdateFileChunk],
+    base: &str,
+) -> Result<MergeOutcome, ApplyPatchError> {
+    let ours = apply_chunks_to_contents(base, path, chunks)?;
+    let theirs = read_file_to_update(path)?;
+    let merged = match diffy::merge(base, &ours, &theirs) {
+        Ok(merged) => merged,
+        Err(merged) => {
+            return Ok(MergeOutcome::Conflict(ApplyPatchConflict {
+                path: path.to_path_buf(),
+                merged,
+            }));
+        }
+    };
+    let chunks = chunks_between(path, &theirs, &merged);
+    let new_contents = apply_chunks_to_contents(&theirs, path, &chunks)?;
+    Ok(MergeOutcome::Merged {
+        original_contents: theirs,
+        new_contents,
+        chunks,
+    })
+}
+
+/// Returns chunks that turn `original` into `updated`. Falls back to replacing the whole file
+/// when the diff would not apply unambiguously, for example because the context of one chunk
+/// also appears earlier in the file.
+fn chunks_between(path: &Path, original: &str, updated: &str) -> Vec<UpdateFileChunk> {
+    let old_lines = split_lines(original);
+    let new_lines = split_lines(updated);
+    if old_lines == new_lines {
+        return Vec::new();
+    }
+
+    let ops = similar::capture_diff_slices(Algorithm::Myers, &old_lines, &new_lines);
+    let chunks: Vec<UpdateFileChunk> = similar::group_diff_ops(ops, MERGED_CHUNK_CONTEXT)
+        .iter()
+        .filter_map(|group| {
+            let first = group.first()?;
+            let last = group.last()?;
+            let old_range = first.old_range().start..last.old_range().end;
+            let new_range = first.new_range().start..last.new_range().end;
+            Some(UpdateFileChunk {
+                change_context: None,
+                is_end_of_file: old_range.end == old_lines.len() && !old_range.is_empty(),
+                old_lines: old_lines[old_range].to_vec(),
+                new_lines: new_lines[new_range].to_vec(),
+            })
+        })
+        .collect();
+
+    let applies_cleanly = apply_chunks_to_contents(original, path, &chunks)
+        .is_ok_and(|contents| split_lines(&contents) == new_lines);
+    if applies_cleanly {
+        chunks
+    } else {
+        vec![UpdateFileChunk {
+            change_context: None,
+            old_lines,
+            new_lines,
+            is_end_of_file: false,
+        }]
+    }
+}
@@ -182,6 +182,46 @@ fn parse_patch_text(patch: &str, mode: ParseMode) -> Result<ApplyPatchArgs, Pars
     })
 }
 
+/// Renders `hunks` as patch text that [`parse_patch`] parses back into the same hunks.
+/// Context lines are rendered as a removal followed by an addition, which applies identically.
+pub(crate) fn render_patch(hunks: &[Hunk]) -> String {
+    let mut lines = vec![BEGIN_PATCH_MARKER.to_string()];
+    for hunk in hunks {
+        match hunk {
+            AddFile { path, contents } => {
+                lines.push(format!("{ADD_FILE_MARKER}{}", path.display()));
+                lines.extend(contents.lines().map(|line| format!("+{line}")));
+            }
+            DeleteFile { path } => {
+                lines.push(format!("{DELETE_FILE_MARKER}{}", path.display()));
+            }
+            UpdateFile {
+                path,
+                move_path,
+                chunks,
+            } => {
+                lines.push(format!("{UPDATE_FILE_MARKER}{}", path.display()));
+                if let Some(move_path) = move_path {
+                    lines.push(format!("{MOVE_TO_MARKER}{}", move_path.display()));
+                }
+                for chunk in chunks {
+                    lines.push(match &chunk.change_context {
+                        Some(context) => format!("{CHANGE_CONTEXT_MARKER}{context}"),
+                        None => EMPTY_CHANGE_CONTEXT_MARKER.to_string(),
+                    });
+                    lines.extend(chunk.old_lines.iter().map(|line| format!("-{line}")));
+                    lines.extend(chunk.new_lines.iter().map(|line| format!("+{line}")));
+                    if chunk.is_end_of_file {
+                        lines.push(EOF_MARKER.to_string());
+                    }
+                }
+            }
+        }
+    }
+    lines.push(END_PATCH_MARKER.to_string());
+    lines.join("\n")
+}
+
 /// Checks the start and end lines of the patch text for `apply_patch`,
 /// returning an error if they do not match the expected markers.
 fn check_patch_boundaries_strict(lines: &[&str]) -> Result<(), ParseError> {
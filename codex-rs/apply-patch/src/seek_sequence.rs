@@ -1,5 +1,27 @@
+use std::fmt;
+
+/// How a sequence was found when it did not match exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FuzzyMatch {
+    TrailingWhitespace,
+    Whitespace,
+    Punctuation,
+}
+
+impl fmt::Display for FuzzyMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            Self::TrailingWhitespace => "ignoring trailing whitespace",
+            Self::Whitespace => "ignoring leading and trailing whitespace",
+            Self::Punctuation => "after normalising Unicode punctuation",
+        };
+        f.write_str(description)
+    }
+}
+
 /// Attempt to find the sequence of `pattern` lines within `lines` beginning at or after `start`.
-/// Returns the starting index of the match or `None` if not found. Matches are attempted with
+/// Returns the starting index of the match, and how it was fuzzy if it was not exact, or `None`
+/// if not found. Matches are attempted with
 /// decreasing strictness: exact match, then ignoring trailing whitespace, then ignoring leading
 /// and trailing whitespace. When `eof` is true, we first try starting at the end-of-file (so that
 /// patterns intended to match file endings are applied at the end), and fall back to searching
@@ -14,9 +36,9 @@ pub(crate) fn seek_sequence(
     pattern: &[String],
     start: usize,
     eof: bool,
-) -> Option<usize> {
+) -> Option<(usize, Option<FuzzyMatch>)> {
     if pattern.is_empty() {
-        return Some(start);
+        return Some((start, None));
     }
 
     // When the pattern is longer than the available input there is no possible
@@ -34,7 +56,7 @@ pub(crate) fn seek_sequence(
     // Exact match first.
     for i in search_start..=lines.len().saturating_sub(pattern.len()) {
         if lines[i..i + pattern.len()] == *pattern {
-            return Some(i);
+            return Some((i, None));
         }
     }
     // Then rstrip match.
@@ -47,7 +69,7 @@ pub(crate) fn seek_sequence(
             }
         }
         if ok {
-            return Some(i);
+            return Some((i, Some(FuzzyMatch::TrailingWhitespace)));
         }
     }
     // Finally, trim both sides to allow more lenience.
@@ -60,7 +82,7 @@ pub(crate) fn seek_sequence(
             }
         }
         if ok {
-            return Some(i);
+            return Some((i, Some(FuzzyMatch::Whitespace)));
         }
     }
 
@@ -102,7 +124,7 @@ pub(crate) fn seek_sequence(
             }
         }
         if ok {
-            return Some(i);
+            return Some((i, Some(FuzzyMatch::Punctuation)));
         }
     }
 
@@ -111,6 +133,7 @@ pub(crate) fn seek_sequence(
 
 #[cfg(test)]
 mod tests {
+    use super::FuzzyMatch;
     use super::seek_sequence;
     use std::string::ToString;
 
@@ -122,7 +145,7 @@ mod tests {
     fn test_exact_match_finds_sequence() {
         let lines = to_vec(&["foo", "bar", "baz"]);
         let pattern = to_vec(&["bar", "baz"]);
-        assert_eq!(seek_sequence(&lines, &pattern, 0, false), Some(1));
+        assert_eq!(seek_sequence(&lines, &pattern, 0, false), Some((1, None)));
     }
 
     #[test]
@@ -130,7 +153,10 @@ mod tests {
         let lines = to_vec(&["foo   ", "bar\t\t"]);
         // Pattern omits trailing whitespace.
         let pattern = to_vec(&["foo", "bar"]);
-        assert_eq!(seek_sequence(&lines, &pattern, 0, false), Some(0));
+        assert_eq!(
+            seek_sequence(&lines, &pattern, 0, false),
+            Some((0, Some(FuzzyMatch::TrailingWhitespace)))
+        );
     }
 
     #[test]
@@ -138,7 +164,10 @@ mod tests {
         let lines = to_vec(&["    foo   ", "   bar\t"]);
         // Pattern omits any additional whitespace.
         let pattern = to_vec(&["foo", "bar"]);
-        assert_eq!(seek_sequence(&lines, &pattern, 0, false), Some(0));
+        assert_eq!(
+            seek_sequence(&lines, &pattern, 0, false),
+            Some((0, Some(FuzzyMatch::Whitespace)))
+        );
     }
 
     #[test]
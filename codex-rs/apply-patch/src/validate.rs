@@ -0,0 +1,264 @@
+//! Checking every hunk of a patch against the working tree without changing it.
+//!
+//! Applying stops at the first hunk that does not match, so on its own it can only report one
+//! problem at a time. Validation keeps going and reports, for each hunk and each chunk of an
+//! update, whether it applies exactly, applies only with fuzzy matching, or conflicts. Later hunks
+//! are checked against the files as earlier hunks would leave them.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::FuzzyMatch;
+use crate::apply_replacements;
+use crate::join_lines;
+use crate::locate_chunk;
+use crate::parser::Hunk;
+use crate::read_file_to_update;
+use crate::split_lines;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HunkStatus {
+    Applies,
+    /// Applies, but the lines it replaces were only found with fuzzy matching.
+    Fuzzy(FuzzyMatch),
+    /// Does not apply; the message explains why.
+    Conflicts(String),
+}
+
+/// The result of validating one hunk, or one chunk of an update hunk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HunkValidation {
+    /// The path as written in the patch.
+    pub path: PathBuf,
+    /// The 1-based chunk within an update hunk, or `None` for the hunk as a whole.
+    pub chunk: Option<usize>,
+    pub status: HunkStatus,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PatchValidation {
+    pub hunks: Vec<HunkValidation>,
+}
+
+impl PatchValidation {
+    /// Returns whether every hunk applies, exactly or fuzzily.
+    pub fn applies(&self) -> bool {
+        self.first_conflict().is_none()
+    }
+
+    /// Returns the message of the first hunk that does not apply.
+    pub fn first_conflict(&self) -> Option<&str> {
+        self.hunks.iter().find_map(|hunk| match &hunk.status {
+            HunkStatus::Conflicts(message) => Some(message.as_str()),
+            HunkStatus::Applies | HunkStatus::Fuzzy(_) => None,
+        })
+    }
+}
+
+impl fmt::Display for PatchValidation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = |predicate: fn(&HunkStatus) -> bool| {
+            self.hunks
+                .iter()
+                .filter(|hunk| predicate(&hunk.status))
+                .count()
+        };
+        write!(
+            f,
+            "Patch validation: {} clean, {} fuzzy, {} conflicting",
+            count(|status| matches!(status, HunkStatus::Applies)),
+            count(|status| matches!(status, HunkStatus::Fuzzy(_))),
+            count(|status| matches!(status, HunkStatus::Conflicts(_))),
+        )?;
+        for hunk in &self.hunks {
+            write!(f, "\n- {}", hunk.path.display())?;
+            if let Some(chunk) = hunk.chunk {
+                write!(f, " (chunk {chunk})")?;
+            }
+            match &hunk.status {
+                HunkStatus::Applies => write!(f, ": applies")?,
+                HunkStatus::Fuzzy(fuzzy) => write!(f, ": applies {fuzzy}")?,
+                HunkStatus::Conflicts(message) => write!(f, ": conflicts: {message}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Validates `hunks`, whose paths are relative to `cwd`, against the files on disk.
+pub fn validate_hunks(hunks: &[Hunk], cwd: &Path) -> PatchValidation {
+    // Files written by earlier hunks, or `None` once deleted.
+    let mut pending: HashMap<PathBuf, Option<String>> = HashMap::new();
+    let mut validation = PatchValidation::default();
+    for hunk in hunks {
+        let path = hunk.resolve_path(cwd);
+        match hunk {
+            Hunk::AddFile {
+                path: hunk_path,
+                contents,
+            } => {
+                pending.insert(path, Some(contents.clone()));
+                validation.hunks.push(HunkValidation {
+                    path: hunk_path.clone(),
+                    chunk: None,
+                    status: HunkStatus::Applies,
+                });
+            }
+            Hunk::DeleteFile { path: hunk_path } => {
+                let exists = match pending.get(&path) {
+                    Some(contents) => contents.is_some(),
+                    None => std::fs::symlink_metadata(&path).is_ok_and(|meta| !meta.is_dir()),
+                };
+                let status = if exists {
+                    HunkStatus::Applies
+                } else {
+                    HunkStatus::Conflicts(format!("Failed to delete file {}", path.display()))
+                };
+                pending.insert(path, None);
+                validation.hunks.push(HunkValidation {
+                    path: hunk_path.clone(),
+                    chunk: None,
+                    status,
+                });
+            }
+            Hunk::UpdateFile {
+                path: hunk_path,
+                move_path,
+                chunks,
+            } => {
+                let original_contents = match pending.get(&path) {
+                    Some(Some(contents)) => Ok(contents.clone()),
+                    Some(None) => Err(format!(
+                        "Failed to read file to update {}: deleted earlier in the patch",
+                        path.display()
+                    )),
+                    None => read_file_to_update(&path).map_err(|err| err.to_string()),
+                };
+                let original_contents = match original_contents {
+                    Ok(contents) => contents,
+                    Err(message) => {
+                        validation.hunks.push(HunkValidation {
+                            path: hunk_path.clone(),
+                            chunk: None,
+                            status: HunkStatus::Conflicts(message),
+                        });
+                        continue;
+                    }
+                };
+
+                let original_lines = split_lines(&original_contents);
+                let mut replacements = Vec::new();
+                let mut line_index = 0;
+                for (index, chunk) in chunks.iter().enumerate() {
+                    let status = match locate_chunk(&original_lines, &path, chunk, line_index) {
+                        Ok(located) => {
+                            replacements.push(located.replacement);
+                            line_index = located.next_line_index;
+                            match located.fuzzy {
+                                Some(fuzzy) => HunkStatus::Fuzzy(fuzzy),
+                                None => HunkStatus::Applies,
+                            }
+                        }
+                        Err(err) => HunkStatus::Conflicts(err.to_string()),
+                    };
+                    validation.hunks.push(HunkValidation {
+                        path: hunk_path.clone(),
+                        chunk: Some(index + 1),
+                        status,
+                    });
+                }
+
+                // Later hunks see the chunks that did apply.
+                replacements.sort_by_key(|(start, _, _)| *start);
+                let new_contents = join_lines(apply_replacements(original_lines, &replacements));
+                match move_path {
+                    Some(move_path) => {
+                        pending.insert(path, None);
+                        pending.insert(cwd.join(move_path), Some(new_contents));
+                    }
+                    None => {
+                        pending.insert(path, Some(new_contents));
+                    }
+                }
+            }
+        }
+    }
+    validation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_patch;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn reports_every_hunk() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "one\ntwo  \nthree\n").unwrap();
+        let patch = "*** Begin Patch
+*** Update File: a.txt
+@@
+-one
++ONE
+@@
+-two
++TWO
+@@
+-four
++FOUR
+*** Delete File: missing.txt
+*** Add File: b.txt
++new
+*** Update File: b.txt
+@@
+-new
++newer
+*** End Patch";
+        let hunks = parse_patch(patch).unwrap().hunks;
+
+        let validation = validate_hunks(&hunks, dir.path());
+
+        assert_eq!(
+            validation
+                .hunks
+                .iter()
+                .map(|hunk| (hunk.path.display().to_string(), hunk.chunk, &hunk.status))
+                .collect::<Vec<_>>(),
+            vec![
+                ("a.txt".to_string(), Some(1), &HunkStatus::Applies),
+                (
+                    "a.txt".to_string(),
+                    Some(2),
+                    &HunkStatus::Fuzzy(FuzzyMatch::TrailingWhitespace)
+                ),
+                (
+                    "a.txt".to_string(),
+                    Some(3),
+                    &HunkStatus::Conflicts(format!(
+                        "Failed to find expected lines in {}:\nfour",
+                        dir.path().join("a.txt").display()
+                    ))
+                ),
+                (
+                    "missing.txt".to_string(),
+                    None,
+                    &HunkStatus::Conflicts(format!(
+                        "Failed to delete file {}",
+                        dir.path().join("missing.txt").display()
+                    ))
+                ),
+                ("b.txt".to_string(), None, &HunkStatus::Applies),
+                ("b.txt".to_string(), Some(1), &HunkStatus::Applies),
+            ]
+        );
+        assert!(!validation.applies());
+        assert!(
+            !dir.path().join("b.txt").exists(),
+            "validation never writes"
+        );
+    }
+}
@@ -10,11 +10,23 @@ use crate::get_task::OutputItem;
 use crate::get_task::PrOutputItem;
 use crate::get_task::get_task;
 
-/// Applies the latest diff from a Codex agent task.
+/// Applies the latest diff from a Codex agent task, a patch file, or the patches a recorded
+/// session made in one turn.
 #[derive(Debug, Parser)]
 pub struct ApplyCommand {
+    /// Codex Cloud task id, path to a patch in `apply_patch` format, or `<SESSION>:<TURN>` for
+    /// the patches made in a turn of a recorded session.
+    #[arg(value_name = "TARGET")]
     pub task_id: String,
 
+    /// Check that the changes apply without writing them.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Ask before applying each file change. Not supported for Codex Cloud tasks.
+    #[arg(long, short = 'i')]
+    pub interactive: bool,
+
     #[clap(flatten)]
     pub config_overrides: CliConfigOverrides,
 }
@@ -22,6 +34,9 @@ pub async fn run_apply_command(
     apply_cli: ApplyCommand,
     cwd: Option<PathBuf>,
 ) -> anyhow::Result<()> {
+    if apply_cli.interactive {
+        anyhow::bail!("--interactive only works with patch files and session turns");
+    }
     let config = Config::load_with_cli_overrides(
         apply_cli
             .config_overrides
@@ -30,17 +45,23 @@ pub async fn run_apply_command(
     )
     .await?;
 
-    init_chatgpt_token_from_auth(&config.codex_home, config.cli_auth_credentials_store_mode)
+    init_chatgpt_token_from_auth(&config.auth_home(), config.cli_auth_credentials_store_mode)
         .await?;
 
     let task_response = get_task(&config, apply_cli.task_id).await?;
-    apply_diff_from_task(task_response, cwd).await
+    let diff = task_diff(task_response)?;
+    apply_diff(&diff, cwd, apply_cli.dry_run).await
 }
 
 pub async fn apply_diff_from_task(
     task_response: GetTaskResponse,
     cwd: Option<PathBuf>,
 ) -> anyhow::Result<()> {
+    let diff = task_diff(task_response)?;
+    apply_diff(&diff, cwd, false).await
+}
+
+fn task_diff(task_response: GetTaskResponse) -> anyhow::Result<String> {
     let diff_turn = match task_response.current_diff_task_turn {
         Some(turn) => turn,
         None => anyhow::bail!("No diff turn found"),
@@ -50,18 +71,19 @@ pub async fn apply_diff_from_task(
         _ => None,
     });
     match output_diff {
-        Some(output_diff) => apply_diff(&output_diff.diff, cwd).await,
+        Some(output_diff) => Ok(output_diff.diff.clone()),
         None => anyhow::bail!("No PR output item found"),
     }
 }
 
-async fn apply_diff(diff: &str, cwd: Option<PathBuf>) -> anyhow::Result<()> {
+/// With `preflight`, only checks that the diff applies.
+async fn apply_diff(diff: &str, cwd: Option<PathBuf>, preflight: bool) -> anyhow::Result<()> {
     let cwd = cwd.unwrap_or(std::env::current_dir().unwrap_or_else(|_| std::env::temp_dir()));
     let req = codex_git::ApplyGitRequest {
         cwd,
         diff: diff.to_string(),
         revert: false,
-        preflight: false,
+        preflight,
     };
     let res = codex_git::apply_git_patch(&req)?;
     if res.exit_code != 0 {
@@ -74,6 +96,10 @@ async fn apply_diff(diff: &str, cwd: Option<PathBuf>) -> anyhow::Result<()> {
             res.stderr
         );
     }
-    println!("Successfully applied diff");
+    if preflight {
+        println!("The diff applies cleanly");
+    } else {
+        println!("Successfully applied diff");
+    }
     Ok(())
 }
@@ -20,11 +20,11 @@ pub fn set_chatgpt_token_data(value: TokenData) {
 
 /// Initialize the ChatGPT token from auth.json file
 pub async fn init_chatgpt_token_from_auth(
-    codex_home: &Path,
+    auth_home: &Path,
     auth_credentials_store_mode: AuthCredentialsStoreMode,
 ) -> std::io::Result<()> {
     let auth_manager =
-        AuthManager::new(codex_home.to_path_buf(), false, auth_credentials_store_mode);
+        AuthManager::new(auth_home.to_path_buf(), false, auth_credentials_store_mode);
     if let Some(auth) = auth_manager.auth().await {
         let token_data = auth.get_token_data()?;
         set_chatgpt_token_data(token_data);
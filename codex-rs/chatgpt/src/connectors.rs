@@ -96,7 +96,7 @@ pub async fn list_cached_all_connectors(config: &Config) -> Option<Vec<AppInfo>>
         return Some(Vec::new());
     }
 
-    if init_chatgpt_token_from_auth(&config.codex_home, config.cli_auth_credentials_store_mode)
+    if init_chatgpt_token_from_auth(&config.auth_home(), config.cli_auth_credentials_store_mode)
         .await
         .is_err()
     {
@@ -114,7 +114,7 @@ pub async fn list_all_connectors_with_options(
     if !config.features.enabled(Feature::Apps) {
         return Ok(Vec::new());
     }
-    init_chatgpt_token_from_auth(&config.codex_home, config.cli_auth_credentials_store_mode)
+    init_chatgpt_token_from_auth(&config.auth_home(), config.cli_auth_credentials_store_mode)
         .await?;
 
     let token_data =
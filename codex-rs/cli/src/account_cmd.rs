@@ -0,0 +1,112 @@
+use anyhow::Result;
+use anyhow::bail;
+use codex_core::CodexAuth;
+use codex_core::auth::AuthMode;
+use codex_core::auth::DEFAULT_ACCOUNT;
+use codex_core::auth::account_home;
+use codex_core::auth::list_accounts;
+use codex_core::auth::remove_account;
+use codex_core::auth::set_active_account;
+use codex_core::config::Config;
+use codex_utils_cli::CliConfigOverrides;
+
+/// Subcommands:
+/// - `list`   — print every account and how it is logged in
+/// - `switch` — use another account from now on
+/// - `remove` — log an account out and forget it
+///
+/// Accounts are added with `codex login --name <ACCOUNT>`. A profile can bind one with
+/// `account = "<ACCOUNT>"`, which wins over the account selected here.
+#[derive(Debug, clap::Parser)]
+pub struct AccountCli {
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: AccountSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum AccountSubcommand {
+    /// Print every account; the one in use is marked with `*`.
+    List,
+    /// Use another account from now on. `default` is the account `codex login` without
+    /// `--name` stores.
+    Switch(AccountNameArgs),
+    /// Log an account out and forget it.
+    Remove(AccountNameArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct AccountNameArgs {
+    #[arg(value_name = "ACCOUNT")]
+    pub name: String,
+}
+
+impl AccountCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides).await?;
+        match self.subcommand {
+            AccountSubcommand::List => run_list(&config),
+            AccountSubcommand::Switch(args) => run_switch(&config, &args.name),
+            AccountSubcommand::Remove(args) => run_remove(&config, &args.name),
+        }
+    }
+}
+
+fn run_list(config: &Config) -> Result<()> {
+    let in_use = codex_cli::login::named_account_in_use(config);
+    let in_use = in_use.as_deref().unwrap_or(DEFAULT_ACCOUNT);
+    for name in list_accounts(&config.codex_home)? {
+        let marker = if name == in_use { '*' } else { ' ' };
+        let home = account_home(&config.codex_home, &name);
+        let status =
+            match CodexAuth::from_auth_storage(&home, config.cli_auth_credentials_store_mode) {
+                Ok(Some(auth)) => match auth.auth_mode() {
+                    AuthMode::ApiKey => "API key".to_string(),
+                    AuthMode::Chatgpt => match auth.get_account_email() {
+                        Some(email) => format!("ChatGPT ({email})"),
+                        None => "ChatGPT".to_string(),
+                    },
+                },
+                Ok(None) => "not logged in".to_string(),
+                Err(err) => format!("unreadable credentials: {err}"),
+            };
+        println!("{marker} {name:<16} {status}");
+    }
+    Ok(())
+}
+
+fn run_switch(config: &Config, name: &str) -> Result<()> {
+    set_active_account(&config.codex_home, name)?;
+    println!("Switched to account `{name}`.");
+    if let Some(bound) = config.account.as_deref().filter(|bound| *bound != name) {
+        println!("Note: the current config binds account `{bound}`, which takes precedence.");
+    }
+    Ok(())
+}
+
+fn run_remove(config: &Config, name: &str) -> Result<()> {
+    let removed = remove_account(
+        &config.codex_home,
+        name,
+        config.cli_auth_credentials_store_mode,
+    )?;
+    if name == DEFAULT_ACCOUNT {
+        if removed {
+            println!("Logged out of the default account.");
+        } else {
+            println!("The default account is not logged in.");
+        }
+        return Ok(());
+    }
+    if !removed {
+        bail!("no account named `{name}`");
+    }
+    println!("Removed account `{name}`.");
+    Ok(())
+}
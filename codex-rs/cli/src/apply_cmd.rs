@@ -0,0 +1,255 @@
+//! `codex apply` for patches kept outside Codex Cloud: a patch file in `apply_patch` format, or
+//! the patches a recorded session made in one turn. Both go through the same patch engine the
+//! agent uses, so a change can be applied later or on another branch.
+
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use codex_apply_patch::Hunk;
+use codex_apply_patch::apply_hunks;
+use codex_apply_patch::parse_patch;
+use codex_apply_patch::validate_hunks;
+use codex_chatgpt::apply_command::ApplyCommand;
+use codex_core::RolloutRecorder;
+use codex_core::config::Config;
+use codex_core::read_session_meta_line;
+use codex_core::recorded_patches;
+
+use crate::sessions_cmd::resolve_session;
+
+/// Where the patch to apply comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PatchSource {
+    File(PathBuf),
+    Turn { session: String, turn: usize },
+}
+
+impl PatchSource {
+    /// Reads `target` as a patch file or `<SESSION>:<TURN>`; anything else is a Codex Cloud task.
+    pub(crate) fn parse(target: &str) -> Option<Self> {
+        let path = Path::new(target);
+        if path.is_file() {
+            return Some(PatchSource::File(path.to_path_buf()));
+        }
+        let (session, turn) = target.rsplit_once(':')?;
+        let turn = turn.parse().ok().filter(|turn| *turn > 0)?;
+        (!session.is_empty()).then(|| PatchSource::Turn {
+            session: session.to_string(),
+            turn,
+        })
+    }
+}
+
+pub(crate) async fn run_local_apply(apply_cli: ApplyCommand, source: PatchSource) -> Result<()> {
+    let hunks = match source {
+        PatchSource::File(path) => {
+            let patch = std::fs::read_to_string(&path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            parse_patch(&patch)
+                .with_context(|| format!("{} is not a valid patch", path.display()))?
+                .hunks
+        }
+        PatchSource::Turn { session, turn } => {
+            let overrides = apply_cli
+                .config_overrides
+                .parse_overrides()
+                .map_err(anyhow::Error::msg)?;
+            let config = Config::load_with_cli_overrides(overrides).await?;
+            turn_hunks(&config, &session, turn).await?
+        }
+    };
+
+    let hunks = if apply_cli.interactive {
+        choose_hunks(hunks)?
+    } else {
+        hunks
+    };
+    if hunks.is_empty() {
+        println!("Nothing to apply.");
+        return Ok(());
+    }
+
+    let cwd = std::env::current_dir()?;
+    let validation = validate_hunks(&hunks, &cwd);
+    if apply_cli.dry_run || !validation.applies() {
+        println!("{validation}");
+        if !validation.applies() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let mut stdout = std::io::stdout();
+    let mut stderr = std::io::stderr();
+    apply_hunks(&hunks, &mut stdout, &mut stderr)?;
+    Ok(())
+}
+
+/// The hunks of every patch that applied during `turn`, with paths made relative to the session's
+/// working directory so they apply to the current one.
+async fn turn_hunks(config: &Config, session: &str, turn: usize) -> Result<Vec<Hunk>> {
+    let path = resolve_session(config, session).await?;
+    let session_cwd = read_session_meta_line(&path).await?.meta.cwd;
+    let items = RolloutRecorder::get_rollout_history(&path)
+        .await?
+        .get_rollout_items();
+
+    let mut hunks = Vec::new();
+    let mut found = false;
+    for patch in recorded_patches(&items) {
+        if patch.turn != turn || patch.success == Some(false) {
+            continue;
+        }
+        found = true;
+        let Some(patch_text) = patch.patch else {
+            eprintln!(
+                "Skipping call {}: it edited files through the shell, so its patch was not recorded.",
+                patch.call_id
+            );
+            continue;
+        };
+        let parsed = parse_patch(&patch_text)
+            .with_context(|| format!("call {} recorded an invalid patch", patch.call_id))?;
+        hunks.extend(
+            parsed
+                .hunks
+                .into_iter()
+                .map(|hunk| relative_to(hunk, &session_cwd)),
+        );
+    }
+    if !found {
+        bail!("turn {turn} of session `{session}` did not change any files");
+    }
+    Ok(hunks)
+}
+
+fn relative_to(hunk: Hunk, root: &Path) -> Hunk {
+    let strip = |path: PathBuf| match path.strip_prefix(root) {
+        Ok(relative) => relative.to_path_buf(),
+        Err(_) => path,
+    };
+    match hunk {
+        Hunk::AddFile { path, contents } => Hunk::AddFile {
+            path: strip(path),
+            contents,
+        },
+        Hunk::DeleteFile { path } => Hunk::DeleteFile { path: strip(path) },
+        Hunk::UpdateFile {
+            path,
+            move_path,
+            chunks,
+        } => Hunk::UpdateFile {
+            path: strip(path),
+            move_path: move_path.map(strip),
+            chunks,
+        },
+    }
+}
+
+/// Shows each file change and keeps the ones the user accepts.
+fn choose_hunks(hunks: Vec<Hunk>) -> Result<Vec<Hunk>> {
+    let total = hunks.len();
+    let mut chosen = Vec::with_capacity(total);
+    for (index, hunk) in hunks.into_iter().enumerate() {
+        println!("\n[{}/{total}] {}", index + 1, describe_hunk(&hunk));
+        println!("{}", preview_hunk(&hunk));
+        print!("Apply this change? [y/n/q]: ");
+        std::io::stdout().flush()?;
+
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        match answer.trim().to_ascii_lowercase().as_str() {
+            "y" | "yes" => chosen.push(hunk),
+            "q" | "quit" => break,
+            _ => {}
+        }
+    }
+    Ok(chosen)
+}
+
+fn describe_hunk(hunk: &Hunk) -> String {
+    match hunk {
+        Hunk::AddFile { path, .. } => format!("Add {}", path.display()),
+        Hunk::DeleteFile { path } => format!("Delete {}", path.display()),
+        Hunk::UpdateFile {
+            path,
+            move_path: Some(move_path),
+            ..
+        } => format!(
+            "Update {} and move it to {}",
+            path.display(),
+            move_path.display()
+        ),
+        Hunk::UpdateFile { path, .. } => format!("Update {}", path.display()),
+    }
+}
+
+fn preview_hunk(hunk: &Hunk) -> String {
+    let mut lines = Vec::new();
+    match hunk {
+        Hunk::AddFile { contents, .. } => {
+            lines.extend(contents.lines().map(|line| format!("+{line}")));
+        }
+        Hunk::DeleteFile { .. } => {}
+        Hunk::UpdateFile { chunks, .. } => {
+            for chunk in chunks {
+                lines.push(match &chunk.change_context {
+                    Some(context) => format!("@@ {context}"),
+                    None => "@@".to_string(),
+                });
+                lines.extend(chunk.old_lines.iter().map(|line| format!("-{line}")));
+                lines.extend(chunk.new_lines.iter().map(|line| format!("+{line}")));
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn targets_are_patch_files_session_turns_or_cloud_tasks() -> Result<()> {
+        let dir = tempfile::TempDir::new()?;
+        let patch = dir.path().join("change.patch");
+        std::fs::write(&patch, "*** Begin Patch\n*** End Patch\n")?;
+
+        assert_eq!(
+            PatchSource::parse(&patch.display().to_string()),
+            Some(PatchSource::File(patch.clone()))
+        );
+        assert_eq!(
+            PatchSource::parse("0199a213-81c0-7800-8aa1-bbab2a035a53:2"),
+            Some(PatchSource::Turn {
+                session: "0199a213-81c0-7800-8aa1-bbab2a035a53".to_string(),
+                turn: 2,
+            })
+        );
+        assert_eq!(PatchSource::parse("my-session:0"), None);
+        assert_eq!(PatchSource::parse("task_e_1234"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn session_paths_become_relative_to_the_session_folder() {
+        let hunk = Hunk::UpdateFile {
+            path: PathBuf::from("/work/app/src/lib.rs"),
+            move_path: Some(PathBuf::from("/work/app/src/main.rs")),
+            chunks: Vec::new(),
+        };
+        assert_eq!(
+            relative_to(hunk, Path::new("/work/app")),
+            Hunk::UpdateFile {
+                path: PathBuf::from("src/lib.rs"),
+                move_path: Some(PathBuf::from("src/main.rs")),
+                chunks: Vec::new(),
+            }
+        );
+    }
+}
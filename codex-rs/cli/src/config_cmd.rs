@@ -0,0 +1,347 @@
+use std::io::IsTerminal;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use anyhow::bail;
+use codex_app_server_protocol::ConfigValueWriteParams;
+use codex_app_server_protocol::ConfigWriteErrorCode;
+use codex_app_server_protocol::ConfigWriteResponse;
+use codex_app_server_protocol::MergeStrategy;
+use codex_core::config::Config;
+use codex_core::config::ConfigService;
+use codex_core::config::ConfigServiceError;
+use codex_core::config::ConfigToml;
+use codex_core::config::find_codex_home;
+use codex_core::config::schema::check_config_key;
+use codex_core::config::schema::config_schema_json;
+use codex_core::config::validate_config_files;
+use codex_core::config_loader::format_config_error_with_source;
+use codex_core::config_loader::merge_toml_values;
+use codex_core::secrets::save_config_secret;
+use codex_utils_cli::CliConfigOverrides;
+use toml::Value as TomlValue;
+
+/// Subcommands:
+/// - `get`   — print the effective value of a setting (with `--json`)
+/// - `set`   — write a setting to `~/.codex/config.toml`
+/// - `unset` — remove a setting from `~/.codex/config.toml`
+/// - `list`  — print every effective setting (with `--json`)
+/// - `set-secret` — store a secret in the OS keychain for `{ keychain = "<name>" }` references
+/// - `validate` — report unknown keys and values of the wrong type in the config files
+/// - `schema` — print the JSON Schema for `config.toml`
+///
+/// Every subcommand takes `--profile` to work on the settings of a profile instead.
+#[derive(Debug, clap::Parser)]
+pub struct ConfigCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: ConfigSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ConfigSubcommand {
+    /// Print the effective value of a setting.
+    Get(GetArgs),
+    /// Write a setting to the user config.toml.
+    Set(SetArgs),
+    /// Remove a setting from the user config.toml.
+    Unset(UnsetArgs),
+    /// Print every effective setting.
+    List(ListArgs),
+    /// Store a secret in the OS keychain; the value is read from stdin.
+    SetSecret(SetSecretArgs),
+    /// Report unknown keys and values of the wrong type in the config files.
+    Validate,
+    /// Print the JSON Schema for config.toml.
+    Schema,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct GetArgs {
+    /// Dotted key of the setting, e.g. `model` or `tui.notifications`.
+    pub key: String,
+
+    /// Read the setting as the given profile sees it.
+    #[arg(long)]
+    pub profile: Option<String>,
+
+    /// Output the value as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct SetArgs {
+    /// Dotted key of the setting, e.g. `model` or `tui.notifications`.
+    pub key: String,
+
+    /// New value, parsed as TOML. If it fails to parse as TOML, the raw string is used.
+    pub value: String,
+
+    /// Write the setting to the `[profiles.<PROFILE>]` table.
+    #[arg(long)]
+    pub profile: Option<String>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct UnsetArgs {
+    /// Dotted key of the setting, e.g. `model` or `tui.notifications`.
+    pub key: String,
+
+    /// Remove the setting from the `[profiles.<PROFILE>]` table.
+    #[arg(long)]
+    pub profile: Option<String>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct SetSecretArgs {
+    /// Name to store the secret under; reference it as `{ keychain = "<NAME>" }`, e.g. in a
+    /// provider's `api_key`.
+    pub name: String,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ListArgs {
+    /// List the settings as the given profile sees them.
+    #[arg(long)]
+    pub profile: Option<String>,
+
+    /// Output the settings as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl ConfigCli {
+    pub async fn run(self) -> Result<()> {
+        let ConfigCli {
+            config_overrides,
+            subcommand,
+        } = self;
+
+        match subcommand {
+            ConfigSubcommand::Get(args) => run_get(&config_overrides, args).await,
+            ConfigSubcommand::Set(args) => run_set(args).await,
+            ConfigSubcommand::Unset(args) => run_unset(args).await,
+            ConfigSubcommand::List(args) => run_list(&config_overrides, args).await,
+            ConfigSubcommand::SetSecret(args) => run_set_secret(args),
+            ConfigSubcommand::Validate => run_validate(&config_overrides).await,
+            ConfigSubcommand::Schema => {
+                let schema = config_schema_json()?;
+                println!("{}", String::from_utf8(schema)?);
+                Ok(())
+            }
+        }
+    }
+}
+
+async fn run_get(config_overrides: &CliConfigOverrides, get_args: GetArgs) -> Result<()> {
+    check_config_key(&get_args.key).map_err(anyhow::Error::msg)?;
+    let effective = load_effective_config(config_overrides, get_args.profile).await?;
+    let value = get_args
+        .key
+        .split('.')
+        .try_fold(&effective, |value, segment| value.get(segment))
+        .ok_or_else(|| anyhow!("`{}` is not set", get_args.key))?;
+
+    if get_args.json {
+        println!("{}", serde_json::to_string_pretty(value)?);
+        return Ok(());
+    }
+    match value {
+        TomlValue::String(text) => println!("{text}"),
+        TomlValue::Table(_) => print!("{}", toml::to_string(value)?),
+        _ => println!("{value}"),
+    }
+    Ok(())
+}
+
+async fn run_set(set_args: SetArgs) -> Result<()> {
+    let key_path = profile_key_path(set_args.profile.as_deref(), &set_args.key);
+    check_config_key(&key_path).map_err(anyhow::Error::msg)?;
+    let value = parse_value(&set_args.value);
+    let response = write_value(
+        &key_path,
+        serde_json::to_value(&value).context("failed to convert value")?,
+    )
+    .await?;
+
+    println!(
+        "Set `{key_path}` to {value} in {}.",
+        response.file_path.display()
+    );
+    warn_if_overridden(&response);
+    Ok(())
+}
+
+async fn run_unset(unset_args: UnsetArgs) -> Result<()> {
+    // Unknown keys can still be removed, e.g. settings left over from an older version.
+    let key_path = profile_key_path(unset_args.profile.as_deref(), &unset_args.key);
+    let response = match write_value(&key_path, serde_json::Value::Null).await {
+        Err(err) if err.write_error_code() == Some(ConfigWriteErrorCode::ConfigPathNotFound) => {
+            bail!("`{key_path}` is not set in config.toml");
+        }
+        result => result?,
+    };
+
+    println!("Unset `{key_path}` in {}.", response.file_path.display());
+    warn_if_overridden(&response);
+    Ok(())
+}
+
+async fn run_list(config_overrides: &CliConfigOverrides, list_args: ListArgs) -> Result<()> {
+    let effective = load_effective_config(config_overrides, list_args.profile).await?;
+
+    if list_args.json {
+        println!("{}", serde_json::to_string_pretty(&effective)?);
+        return Ok(());
+    }
+    for (key, value) in flatten_settings(&effective) {
+        println!("{key} = {value}");
+    }
+    Ok(())
+}
+
+fn run_set_secret(set_secret_args: SetSecretArgs) -> Result<()> {
+    let SetSecretArgs { name } = set_secret_args;
+
+    let secret = read_secret_from_stdin(&name)?;
+    save_config_secret(&name, &secret)?;
+    println!("Saved secret '{name}' to the keychain.");
+    println!(
+        "Reference it from config as {{ keychain = \"{name}\" }}, e.g. `api_key = {{ keychain = \"{name}\" }}` for a model provider."
+    );
+    Ok(())
+}
+
+/// Reads one line from stdin, prompting for it when stdin is a terminal.
+pub(crate) fn read_secret_from_stdin(name: &str) -> Result<String> {
+    if std::io::stdin().is_terminal() {
+        eprintln!("Enter the value for '{name}' and press Enter:");
+    }
+    let mut secret = String::new();
+    std::io::stdin()
+        .read_line(&mut secret)
+        .context("failed to read secret from stdin")?;
+    let secret = secret.trim_end_matches(['\r', '\n']);
+    if secret.is_empty() {
+        bail!("No secret provided on stdin.");
+    }
+    Ok(secret.to_string())
+}
+
+async fn run_validate(config_overrides: &CliConfigOverrides) -> Result<()> {
+    let overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+    let cwd = std::env::current_dir().context("failed to resolve the current directory")?;
+    let errors = validate_config_files(&codex_home, &cwd, overrides)
+        .await
+        .context("failed to load configuration")?;
+
+    if errors.is_empty() {
+        println!("No problems found.");
+        return Ok(());
+    }
+    for error in &errors {
+        eprintln!("{}\n", format_config_error_with_source(error));
+    }
+    bail!("found {} problem(s) in config.toml", errors.len());
+}
+
+/// Loads the merged settings of every config layer, with the active profile (or `profile`)
+/// applied on top.
+async fn load_effective_config(
+    config_overrides: &CliConfigOverrides,
+    profile: Option<String>,
+) -> Result<TomlValue> {
+    let overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides)
+        .await
+        .context("failed to load configuration")?;
+    let mut effective = config.config_layer_stack.effective_config();
+
+    let config_toml: ConfigToml = effective
+        .clone()
+        .try_into()
+        .context("failed to parse configuration")?;
+    let profile = config_toml.get_config_profile(profile)?;
+    let mut profile = TomlValue::try_from(profile).context("failed to convert profile")?;
+    // A few profile settings have no top-level counterpart of the same name.
+    if let TomlValue::Table(table) = &mut profile {
+        table.retain(|key, _| check_config_key(key).is_ok());
+    }
+    merge_toml_values(&mut effective, &profile);
+    Ok(effective)
+}
+
+async fn write_value(
+    key_path: &str,
+    value: serde_json::Value,
+) -> Result<ConfigWriteResponse, ConfigServiceError> {
+    let codex_home = find_codex_home().map_err(|source| ConfigServiceError::Io {
+        context: "failed to resolve CODEX_HOME",
+        source,
+    })?;
+    ConfigService::new_with_defaults(codex_home)
+        .write_value(ConfigValueWriteParams {
+            key_path: key_path.to_string(),
+            value,
+            merge_strategy: MergeStrategy::Replace,
+            file_path: None,
+            expected_version: None,
+        })
+        .await
+}
+
+fn warn_if_overridden(response: &ConfigWriteResponse) {
+    if let Some(overridden) = &response.overridden_metadata {
+        eprintln!("Warning: {}", overridden.message);
+    }
+}
+
+fn profile_key_path(profile: Option<&str>, key: &str) -> String {
+    match profile {
+        Some(profile) => format!("profiles.{profile}.{key}"),
+        None => key.to_string(),
+    }
+}
+
+/// Parses `raw` as a TOML value, falling back to the raw string so that `codex config set model
+/// o3` works without quotes.
+fn parse_value(raw: &str) -> TomlValue {
+    toml::from_str::<toml::Table>(&format!("value = {raw}"))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| TomlValue::String(raw.to_string()))
+}
+
+/// Every setting that is not a table, keyed by its dotted path, in key order.
+fn flatten_settings(value: &TomlValue) -> Vec<(String, TomlValue)> {
+    fn collect(value: &TomlValue, path: String, settings: &mut Vec<(String, TomlValue)>) {
+        match value {
+            TomlValue::Table(table) => {
+                let mut entries: Vec<_> = table.iter().collect();
+                entries.sort_by(|(left, _), (right, _)| left.cmp(right));
+                for (key, child) in entries {
+                    let path = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{path}.{key}")
+                    };
+                    collect(child, path, settings);
+                }
+            }
+            _ => settings.push((path, value.clone())),
+        }
+    }
+
+    let mut settings = Vec::new();
+    collect(value, String::new(), &mut settings);
+    settings
+}
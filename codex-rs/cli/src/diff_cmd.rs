@@ -0,0 +1,284 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use codex_apply_patch::Hunk;
+use codex_apply_patch::parse_patch;
+use codex_core::RolloutRecorder;
+use codex_core::config::Config;
+use codex_core::read_session_meta_line;
+use codex_core::recorded_commands;
+use codex_core::recorded_patches;
+use codex_protocol::protocol::FileChange;
+use codex_protocol::protocol::RolloutItem;
+use codex_shell_command::bash::extract_bash_command;
+use codex_utils_cli::CliConfigOverrides;
+
+use crate::sessions_cmd::resolve_session;
+
+/// Prints the changes a recorded session made to files as a unified diff, built from the patches
+/// it applied. A file changed several times appears once per change, in order. Sessions recorded
+/// without patch results show each patch as the agent wrote it.
+#[derive(Debug, clap::Parser)]
+pub struct DiffCli {
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Session id (UUID) or thread name.
+    #[arg(value_name = "SESSION")]
+    pub session: String,
+
+    /// Only the changes made in this turn, counting from 1.
+    #[arg(long, value_name = "N")]
+    pub turn: Option<usize>,
+}
+
+impl DiffCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides).await?;
+        let path = resolve_session(&config, &self.session).await?;
+        let session_cwd = read_session_meta_line(&path).await?.meta.cwd;
+        let items = RolloutRecorder::get_rollout_history(&path)
+            .await?
+            .get_rollout_items();
+        let in_scope = |turn: usize| self.turn.is_none_or(|selected| selected == turn);
+
+        let diff = session_diff(&items, &session_cwd, self.turn);
+        if diff.is_empty() {
+            eprintln!("No file changes were recorded{}.", scope_suffix(self.turn));
+        } else {
+            print!("{diff}");
+        }
+
+        // Commands leave no record of what they changed, so they can only be pointed out.
+        let commands: Vec<_> = recorded_commands(&items)
+            .into_iter()
+            .filter(|command| in_scope(command.turn))
+            .collect();
+        if !commands.is_empty() {
+            eprintln!(
+                "\nThese commands also ran{} and may have changed files not shown above:",
+                scope_suffix(self.turn)
+            );
+            for command in commands {
+                eprintln!(
+                    "  turn {}: {}",
+                    command.turn,
+                    display_command(&command.command)
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The changes made by the patches that applied in `items`, limited to `turn` when given.
+fn session_diff(items: &[RolloutItem], root: &Path, turn: Option<usize>) -> String {
+    let mut diff = String::new();
+    for patch in recorded_patches(items) {
+        if turn.is_some_and(|turn| turn != patch.turn) || patch.success != Some(true) {
+            continue;
+        }
+        let mut changes: Vec<_> = if patch.changes.is_empty() {
+            // Only the call was recorded, so rebuild the changes from the patch itself.
+            patch
+                .patch
+                .as_deref()
+                .and_then(|patch| parse_patch(patch).ok())
+                .map(|parsed| parsed.hunks.into_iter().map(hunk_change).collect())
+                .unwrap_or_default()
+        } else {
+            patch.changes.into_iter().collect()
+        };
+        changes.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (path, change) in changes {
+            diff.push_str(&file_diff(&path, &change, root));
+        }
+    }
+    diff
+}
+
+/// The change an `apply_patch` hunk describes. Update hunks carry no line numbers, and a deleted
+/// file's content is not part of the patch.
+fn hunk_change(hunk: Hunk) -> (PathBuf, FileChange) {
+    match hunk {
+        Hunk::AddFile { path, contents } => (path, FileChange::Add { content: contents }),
+        Hunk::DeleteFile { path } => (
+            path,
+            FileChange::Delete {
+                content: String::new(),
+            },
+        ),
+        Hunk::UpdateFile {
+            path,
+            move_path,
+            chunks,
+        } => {
+            let mut unified_diff = String::new();
+            for chunk in chunks {
+                // Lines shared by the start or end of both sides are context.
+                let old = &chunk.old_lines;
+                let new = &chunk.new_lines;
+                let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+                let suffix = old[prefix..]
+                    .iter()
+                    .rev()
+                    .zip(new[prefix..].iter().rev())
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                unified_diff.push_str(&match &chunk.change_context {
+                    Some(context) => format!("@@ {context}\n"),
+                    None => "@@\n".to_string(),
+                });
+                for line in &old[..prefix] {
+                    unified_diff.push_str(&format!(" {line}\n"));
+                }
+                for line in &old[prefix..old.len() - suffix] {
+                    unified_diff.push_str(&format!("-{line}\n"));
+                }
+                for line in &new[prefix..new.len() - suffix] {
+                    unified_diff.push_str(&format!("+{line}\n"));
+                }
+                for line in &old[old.len() - suffix..] {
+                    unified_diff.push_str(&format!(" {line}\n"));
+                }
+            }
+            (
+                path,
+                FileChange::Update {
+                    unified_diff,
+                    move_path,
+                },
+            )
+        }
+    }
+}
+
+fn scope_suffix(turn: Option<usize>) -> String {
+    match turn {
+        Some(turn) => format!(" in turn {turn}"),
+        None => String::new(),
+    }
+}
+
+fn display_command(command: &[String]) -> String {
+    match extract_bash_command(command) {
+        Some((_, script)) => script.to_string(),
+        None => command.join(" "),
+    }
+}
+
+/// One file's change in `git diff` format, with paths relative to `root` where possible.
+fn file_diff(path: &Path, change: &FileChange, root: &Path) -> String {
+    let display = |path: &Path| -> String {
+        let path = path.strip_prefix(root).unwrap_or(path);
+        path.to_string_lossy().trim_start_matches('/').to_string()
+    };
+    let old = display(path);
+    match change {
+        FileChange::Add { content } => format!(
+            "diff --git a/{old} b/{old}\nnew file mode 100644\n--- /dev/null\n+++ b/{old}\n@@ -0,0 +1,{} @@\n{}",
+            content.lines().count(),
+            prefixed_lines(content, '+')
+        ),
+        FileChange::Delete { content } if content.is_empty() => format!(
+            "diff --git a/{old} b/{old}\ndeleted file mode 100644\n--- a/{old}\n+++ /dev/null\n"
+        ),
+        FileChange::Delete { content } => format!(
+            "diff --git a/{old} b/{old}\ndeleted file mode 100644\n--- a/{old}\n+++ /dev/null\n@@ -1,{} +0,0 @@\n{}",
+            content.lines().count(),
+            prefixed_lines(content, '-')
+        ),
+        FileChange::Update {
+            unified_diff,
+            move_path,
+        } => {
+            let new = move_path
+                .as_deref()
+                .map(display)
+                .unwrap_or_else(|| old.clone());
+            let mut diff = format!("diff --git a/{old} b/{new}\n");
+            if new != old {
+                diff.push_str(&format!("rename from {old}\nrename to {new}\n"));
+            }
+            diff.push_str(&format!("--- a/{old}\n+++ b/{new}\n{unified_diff}"));
+            if !diff.ends_with('\n') {
+                diff.push('\n');
+            }
+            diff
+        }
+    }
+}
+
+fn prefixed_lines(content: &str, prefix: char) -> String {
+    let mut lines: String = content
+        .lines()
+        .map(|line| format!("{prefix}{line}\n"))
+        .collect();
+    if !content.is_empty() && !content.ends_with('\n') {
+        lines.push_str("\\ No newline at end of file\n");
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::ResponseItem;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn formats_changes_like_git_diff() {
+        let root = Path::new("/work/app");
+        assert_eq!(
+            file_diff(
+                Path::new("/work/app/notes.txt"),
+                &FileChange::Add {
+                    content: "one\ntwo".to_string(),
+                },
+                root,
+            ),
+            "diff --git a/notes.txt b/notes.txt\nnew file mode 100644\n--- /dev/null\n+++ b/notes.txt\n@@ -0,0 +1,2 @@\n+one\n+two\n\\ No newline at end of file\n"
+        );
+        assert_eq!(
+            file_diff(
+                Path::new("/work/app/src/lib.rs"),
+                &FileChange::Update {
+                    unified_diff: "@@ -1 +1 @@\n-old\n+new\n".to_string(),
+                    move_path: Some(PathBuf::from("/work/app/src/main.rs")),
+                },
+                root,
+            ),
+            "diff --git a/src/lib.rs b/src/main.rs\nrename from src/lib.rs\nrename to src/main.rs\n--- a/src/lib.rs\n+++ b/src/main.rs\n@@ -1 +1 @@\n-old\n+new\n"
+        );
+    }
+
+    #[test]
+    fn limited_rollouts_show_the_patches_as_written() {
+        // Rollouts in the limited event mode keep the tool calls but not `PatchApplyEnd`.
+        let items = vec![
+            RolloutItem::ResponseItem(ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "call-1".to_string(),
+                name: "apply_patch".to_string(),
+                input: "*** Begin Patch\n*** Update File: src/lib.rs\n@@ fn main() {\n     let a = 1;\n-    old();\n+    new();\n }\n*** Delete File: old.txt\n*** End Patch".to_string(),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::CustomToolCallOutput {
+                call_id: "call-1".to_string(),
+                output: "Success. Updated the following files:\nM src/lib.rs\nD old.txt\n"
+                    .to_string(),
+            }),
+        ];
+
+        assert_eq!(
+            session_diff(&items, Path::new("/work/app"), None),
+            "diff --git a/old.txt b/old.txt\ndeleted file mode 100644\n--- a/old.txt\n+++ /dev/null\n\
+             diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ fn main() {\n     let a = 1;\n-    old();\n+    new();\n }\n"
+        );
+    }
+}
@@ -0,0 +1,417 @@
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use anyhow::Result;
+use codex_core::CodexAuth;
+use codex_core::auth::AuthMode;
+use codex_core::config::CONFIG_TOML_FILE;
+use codex_core::config::Config;
+use codex_core::config::validate_config_files;
+use codex_core::mcp::probe_mcp_server;
+use codex_utils_cli::CliConfigOverrides;
+
+const PROVIDER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Checks that Codex is set up to work on this machine: config, sign-in, the model provider, the
+/// sandbox, MCP servers and the `notify` program. Each problem is printed with a way to fix it.
+#[derive(Debug, clap::Parser)]
+pub struct DoctorCli {
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CheckStatus {
+    Ok,
+    Warning,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CheckResult {
+    name: String,
+    status: CheckStatus,
+    detail: String,
+    /// What to do about a warning or failure.
+    fix: Option<String>,
+}
+
+impl CheckResult {
+    fn ok(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Ok,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn problem(
+        status: CheckStatus,
+        name: impl Into<String>,
+        detail: impl Into<String>,
+        fix: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn skipped(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Skipped,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+}
+
+impl DoctorCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+
+        let config = match Config::load_with_cli_overrides(overrides.clone()).await {
+            Ok(config) => config,
+            Err(err) => {
+                // Nothing else can be checked without config.
+                print_results(&[CheckResult::problem(
+                    CheckStatus::Failed,
+                    "config",
+                    err.to_string(),
+                    "`codex config validate` lists every problem in the config files.",
+                )]);
+                std::process::exit(1);
+            }
+        };
+
+        let mut results = vec![check_config(&config, overrides).await];
+        results.extend(config.startup_warnings.iter().map(|warning| {
+            CheckResult::problem(
+                CheckStatus::Warning,
+                "config",
+                warning.clone(),
+                "See the warning for what to change.",
+            )
+        }));
+        let (auth, credentials) = check_auth(&config).await;
+        results.push(auth);
+        results.push(check_provider(&config, credentials.as_ref()).await);
+        results.push(check_sandbox().await);
+        results.extend(check_mcp_servers(&config).await);
+        results.extend(check_notify(&config));
+
+        print_results(&results);
+        if results
+            .iter()
+            .any(|result| result.status == CheckStatus::Failed)
+        {
+            std::process::exit(1);
+        }
+        Ok(())
+    }
+}
+
+async fn check_config(config: &Config, overrides: Vec<(String, toml::Value)>) -> CheckResult {
+    let config_path = config.codex_home.join(CONFIG_TOML_FILE);
+    match validate_config_files(&config.codex_home, &config.cwd, overrides).await {
+        Ok(errors) if errors.is_empty() => {
+            CheckResult::ok("config", format!("{} is valid", config_path.display()))
+        }
+        Ok(errors) => CheckResult::problem(
+            CheckStatus::Warning,
+            "config",
+            format!("found {} problem(s) in the config files", errors.len()),
+            "Run `codex config validate` to see them.",
+        ),
+        Err(err) => CheckResult::problem(
+            CheckStatus::Failed,
+            "config",
+            err.to_string(),
+            "Run `codex config validate` to see what is wrong.",
+        ),
+    }
+}
+
+/// What a session would authenticate to the model provider with.
+#[derive(Default)]
+struct Credentials {
+    auth_mode: Option<AuthMode>,
+    token: Option<String>,
+    account_id: Option<String>,
+}
+
+/// Loads the credentials a session would use. Whether the provider accepts them is checked by
+/// [`check_provider`].
+async fn check_auth(config: &Config) -> (CheckResult, Option<Credentials>) {
+    let provider = &config.model_provider;
+    if provider.api_key_secret.is_some() || !provider.requires_openai_auth {
+        let token = match provider.api_key().await {
+            Ok(token) => token.or_else(|| provider.experimental_bearer_token.clone()),
+            Err(err) => {
+                let hint = if provider.api_key_secret.is_some() {
+                    "Check the provider's `api_key`."
+                } else {
+                    "Set the environment variable named by the provider's `env_key`."
+                };
+                return (
+                    CheckResult::problem(CheckStatus::Failed, "auth", err.to_string(), hint),
+                    None,
+                );
+            }
+        };
+        let result = match token {
+            Some(_) => CheckResult::ok("auth", format!("API key for {} is set", provider.name)),
+            None => CheckResult::ok(
+                "auth",
+                format!("{} does not need an API key", provider.name),
+            ),
+        };
+        return (
+            result,
+            Some(Credentials {
+                token,
+                ..Default::default()
+            }),
+        );
+    }
+
+    let auth = match CodexAuth::from_auth_storage(
+        &config.auth_home(),
+        config.cli_auth_credentials_store_mode,
+    ) {
+        Ok(Some(auth)) => auth,
+        Ok(None) => {
+            return (
+                CheckResult::problem(
+                    CheckStatus::Failed,
+                    "auth",
+                    "not signed in",
+                    "Run `codex login`.",
+                ),
+                None,
+            );
+        }
+        Err(err) => {
+            return (
+                CheckResult::problem(
+                    CheckStatus::Failed,
+                    "auth",
+                    format!("the stored sign-in cannot be read: {err}"),
+                    "Run `codex logout`, then `codex login`.",
+                ),
+                None,
+            );
+        }
+    };
+    let (signed_in, fix) = match auth.auth_mode() {
+        AuthMode::ApiKey => (
+            "signed in with an API key",
+            "Run `codex login --with-api-key` again.",
+        ),
+        AuthMode::Chatgpt => ("signed in with ChatGPT", "Run `codex login` again."),
+    };
+    match auth.get_token() {
+        Ok(token) => (
+            CheckResult::ok("auth", signed_in),
+            Some(Credentials {
+                auth_mode: Some(auth.auth_mode()),
+                token: Some(token),
+                account_id: auth.get_account_id(),
+            }),
+        ),
+        Err(err) => (
+            CheckResult::problem(
+                CheckStatus::Failed,
+                "auth",
+                format!("the stored sign-in cannot be read: {err}"),
+                fix,
+            ),
+            None,
+        ),
+    }
+}
+
+/// Lists the provider's models at its configured base URL. Any answer shows the provider can be
+/// reached; with `credentials`, a 401 or 403 means they were rejected.
+async fn check_provider(config: &Config, credentials: Option<&Credentials>) -> CheckResult {
+    let provider = &config.model_provider;
+    let name = format!("provider {}", provider.name);
+    let probe = provider
+        .probe(
+            credentials.and_then(|credentials| credentials.auth_mode),
+            credentials.and_then(|credentials| credentials.token.as_deref()),
+            credentials.and_then(|credentials| credentials.account_id.as_deref()),
+            PROVIDER_TIMEOUT,
+        )
+        .await;
+    match probe {
+        Ok((url, status)) if credentials.is_some() && matches!(status.as_u16(), 401 | 403) => {
+            CheckResult::problem(
+                CheckStatus::Failed,
+                name,
+                format!("{url} rejected the credentials with {status}"),
+                "Sign in again with `codex login`, or check the provider's API key.",
+            )
+        }
+        Ok((url, status)) => CheckResult::ok(name, format!("{url} responded with {status}")),
+        Err(err) => CheckResult::problem(
+            CheckStatus::Failed,
+            name,
+            format!("{} could not be reached: {err}", provider.name),
+            "Check your network connection and proxy settings, and the provider's `base_url`.",
+        ),
+    }
+}
+
+/// Runs `true` under the sandbox the way `codex sandbox` does.
+async fn check_sandbox() -> CheckResult {
+    let (platform, fix) = if cfg!(target_os = "macos") {
+        (
+            "macos",
+            "Codex uses Seatbelt (`/usr/bin/sandbox-exec`); check that it is present and not blocked by device management.",
+        )
+    } else if cfg!(target_os = "linux") {
+        (
+            "linux",
+            "Codex needs Landlock (Linux 5.13 or newer) and seccomp. Inside a container that blocks them, use `--sandbox danger-full-access` and rely on the container instead.",
+        )
+    } else {
+        return CheckResult::skipped("sandbox", "not checked on this platform");
+    };
+
+    let output = match std::env::current_exe() {
+        Ok(exe) => {
+            tokio::process::Command::new(exe)
+                .args(["sandbox", platform, "true"])
+                .stdin(Stdio::null())
+                .output()
+                .await
+        }
+        Err(err) => Err(err),
+    };
+    match output {
+        Ok(output) if output.status.success() => {
+            CheckResult::ok("sandbox", "commands can run in the sandbox")
+        }
+        Ok(output) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let reason = stderr
+                .lines()
+                .rev()
+                .find(|line| !line.trim().is_empty())
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("exited with {}", output.status));
+            CheckResult::problem(
+                CheckStatus::Failed,
+                "sandbox",
+                format!("a sandboxed command failed: {reason}"),
+                fix,
+            )
+        }
+        Err(err) => CheckResult::problem(
+            CheckStatus::Failed,
+            "sandbox",
+            format!("the sandbox could not be started: {err}"),
+            fix,
+        ),
+    }
+}
+
+async fn check_mcp_servers(config: &Config) -> Vec<CheckResult> {
+    let mut servers: Vec<_> = config.mcp_servers.get().iter().collect();
+    servers.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut results = Vec::with_capacity(servers.len());
+    for (name, server) in servers {
+        let check = format!("mcp {name}");
+        if !server.enabled {
+            results.push(CheckResult::skipped(check, "disabled"));
+            continue;
+        }
+        results.push(match probe_mcp_server(config, name, server).await {
+            Ok(tools) => CheckResult::ok(check, format!("started with {} tool(s)", tools.len())),
+            Err(err) => CheckResult::problem(
+                CheckStatus::Failed,
+                check,
+                format!("failed to start: {err}"),
+                format!("Run `codex mcp get {name}` to review its settings."),
+            ),
+        });
+    }
+    results
+}
+
+fn check_notify(config: &Config) -> Option<CheckResult> {
+    let program = config.notify.as_ref()?.first()?;
+    Some(match find_program(program) {
+        Some(path) => CheckResult::ok("notify", format!("runs {}", path.display())),
+        None => CheckResult::problem(
+            CheckStatus::Failed,
+            "notify",
+            format!("the notify program `{program}` was not found"),
+            "Install it, or fix `notify` in config.toml.",
+        ),
+    })
+}
+
+/// Resolves `program` the way it is spawned: a path as is, a bare name through `PATH`.
+fn find_program(program: &str) -> Option<std::path::PathBuf> {
+    let path = Path::new(program);
+    if path.components().count() > 1 {
+        return path.is_file().then(|| path.to_path_buf());
+    }
+    which::which(program).ok()
+}
+
+fn print_results(results: &[CheckResult]) {
+    let name_width = results
+        .iter()
+        .map(|result| result.name.len())
+        .max()
+        .unwrap_or_default();
+    for result in results {
+        let marker = match result.status {
+            CheckStatus::Ok => "ok  ",
+            CheckStatus::Warning => "warn",
+            CheckStatus::Failed => "fail",
+            CheckStatus::Skipped => "skip",
+        };
+        println!(
+            "[{marker}] {name:<name_width$}  {detail}",
+            name = result.name,
+            detail = result.detail
+        );
+        if let Some(fix) = &result.fix {
+            println!("       {:<name_width$}  {fix}", "");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn finds_programs_by_path_or_name() {
+        assert_eq!(find_program("/definitely/not/here/notify-send"), None);
+        assert_eq!(find_program("definitely-not-a-real-program-name"), None);
+
+        let dir = tempfile::TempDir::new().expect("tempdir");
+        let program = dir.path().join("notify.sh");
+        std::fs::write(&program, "#!/bin/sh\n").expect("write program");
+        assert_eq!(
+            find_program(&program.display().to_string()),
+            Some(program.clone())
+        );
+    }
+}
@@ -2,8 +2,12 @@ use codex_core::CodexAuth;
 use codex_core::auth::AuthCredentialsStoreMode;
 use codex_core::auth::AuthMode;
 use codex_core::auth::CLIENT_ID;
+use codex_core::auth::DEFAULT_ACCOUNT;
+use codex_core::auth::active_account;
+use codex_core::auth::create_account;
 use codex_core::auth::login_with_api_key;
 use codex_core::auth::logout;
+use codex_core::auth::set_active_account;
 use codex_core::config::Config;
 use codex_login::ServerOptions;
 use codex_login::run_device_code_login;
@@ -44,7 +48,10 @@ pub async fn login_with_chatgpt(
     server.block_until_done().await
 }
 
-pub async fn run_login_with_chatgpt(cli_config_overrides: CliConfigOverrides) -> ! {
+pub async fn run_login_with_chatgpt(
+    cli_config_overrides: CliConfigOverrides,
+    account: Option<String>,
+) -> ! {
     let config = load_config_or_exit(cli_config_overrides).await;
 
     if matches!(config.forced_login_method, Some(ForcedLoginMethod::Api)) {
@@ -55,16 +62,13 @@ pub async fn run_login_with_chatgpt(cli_config_overrides: CliConfigOverrides) ->
     let forced_chatgpt_workspace_id = config.forced_chatgpt_workspace_id.clone();
 
     match login_with_chatgpt(
-        config.codex_home,
+        login_home_or_exit(&config, account.as_deref()),
         forced_chatgpt_workspace_id,
         config.cli_auth_credentials_store_mode,
     )
     .await
     {
-        Ok(_) => {
-            eprintln!("{LOGIN_SUCCESS_MESSAGE}");
-            std::process::exit(0);
-        }
+        Ok(_) => finish_login(&config, account.as_deref()),
         Err(e) => {
             eprintln!("Error logging in: {e}");
             std::process::exit(1);
@@ -75,6 +79,7 @@ pub async fn run_login_with_chatgpt(cli_config_overrides: CliConfigOverrides) ->
 pub async fn run_login_with_api_key(
     cli_config_overrides: CliConfigOverrides,
     api_key: String,
+    account: Option<String>,
 ) -> ! {
     let config = load_config_or_exit(cli_config_overrides).await;
 
@@ -84,14 +89,11 @@ pub async fn run_login_with_api_key(
     }
 
     match login_with_api_key(
-        &config.codex_home,
+        &login_home_or_exit(&config, account.as_deref()),
         &api_key,
         config.cli_auth_credentials_store_mode,
     ) {
-        Ok(_) => {
-            eprintln!("{LOGIN_SUCCESS_MESSAGE}");
-            std::process::exit(0);
-        }
+        Ok(_) => finish_login(&config, account.as_deref()),
         Err(e) => {
             eprintln!("Error logging in: {e}");
             std::process::exit(1);
@@ -131,6 +133,7 @@ pub async fn run_login_with_device_code(
     cli_config_overrides: CliConfigOverrides,
     issuer_base_url: Option<String>,
     client_id: Option<String>,
+    account: Option<String>,
 ) -> ! {
     let config = load_config_or_exit(cli_config_overrides).await;
     if matches!(config.forced_login_method, Some(ForcedLoginMethod::Api)) {
@@ -139,7 +142,7 @@ pub async fn run_login_with_device_code(
     }
     let forced_chatgpt_workspace_id = config.forced_chatgpt_workspace_id.clone();
     let mut opts = ServerOptions::new(
-        config.codex_home,
+        login_home_or_exit(&config, account.as_deref()),
         client_id.unwrap_or(CLIENT_ID.to_string()),
         forced_chatgpt_workspace_id,
         config.cli_auth_credentials_store_mode,
@@ -148,10 +151,7 @@ pub async fn run_login_with_device_code(
         opts.issuer = iss;
     }
     match run_device_code_login(opts).await {
-        Ok(()) => {
-            eprintln!("{LOGIN_SUCCESS_MESSAGE}");
-            std::process::exit(0);
-        }
+        Ok(()) => finish_login(&config, account.as_deref()),
         Err(e) => {
             eprintln!("Error logging in with device code: {e}");
             std::process::exit(1);
@@ -176,7 +176,7 @@ pub async fn run_login_with_device_code_fallback_to_browser(
 
     let forced_chatgpt_workspace_id = config.forced_chatgpt_workspace_id.clone();
     let mut opts = ServerOptions::new(
-        config.codex_home,
+        config.auth_home(),
         client_id.unwrap_or(CLIENT_ID.to_string()),
         forced_chatgpt_workspace_id,
         config.cli_auth_credentials_store_mode,
@@ -224,11 +224,19 @@ pub async fn run_login_with_device_code_fallback_to_browser(
 pub async fn run_login_status(cli_config_overrides: CliConfigOverrides) -> ! {
     let config = load_config_or_exit(cli_config_overrides).await;
 
-    match CodexAuth::from_auth_storage(&config.codex_home, config.cli_auth_credentials_store_mode) {
+    let account_suffix = match named_account_in_use(&config) {
+        Some(account) => format!(" (account `{account}`)"),
+        None => String::new(),
+    };
+    match CodexAuth::from_auth_storage(&config.auth_home(), config.cli_auth_credentials_store_mode)
+    {
         Ok(Some(auth)) => match auth.auth_mode() {
             AuthMode::ApiKey => match auth.get_token() {
                 Ok(api_key) => {
-                    eprintln!("Logged in using an API key - {}", safe_format_key(&api_key));
+                    eprintln!(
+                        "Logged in using an API key - {}{account_suffix}",
+                        safe_format_key(&api_key)
+                    );
                     std::process::exit(0);
                 }
                 Err(e) => {
@@ -237,12 +245,12 @@ pub async fn run_login_status(cli_config_overrides: CliConfigOverrides) -> ! {
                 }
             },
             AuthMode::Chatgpt => {
-                eprintln!("Logged in using ChatGPT");
+                eprintln!("Logged in using ChatGPT{account_suffix}");
                 std::process::exit(0);
             }
         },
         Ok(None) => {
-            eprintln!("Not logged in");
+            eprintln!("Not logged in{account_suffix}");
             std::process::exit(1);
         }
         Err(e) => {
@@ -255,7 +263,7 @@ pub async fn run_login_status(cli_config_overrides: CliConfigOverrides) -> ! {
 pub async fn run_logout(cli_config_overrides: CliConfigOverrides) -> ! {
     let config = load_config_or_exit(cli_config_overrides).await;
 
-    match logout(&config.codex_home, config.cli_auth_credentials_store_mode) {
+    match logout(&config.auth_home(), config.cli_auth_credentials_store_mode) {
         Ok(true) => {
             eprintln!("Successfully logged out");
             std::process::exit(0);
@@ -289,6 +297,42 @@ async fn load_config_or_exit(cli_config_overrides: CliConfigOverrides) -> Config
     }
 }
 
+/// Where a login stores its credentials: the named account being added, or the account in use.
+fn login_home_or_exit(config: &Config, account: Option<&str>) -> PathBuf {
+    let Some(account) = account else {
+        return config.auth_home();
+    };
+    match create_account(&config.codex_home, account) {
+        Ok(home) => home,
+        Err(e) => {
+            eprintln!("Error adding account: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Reports a successful login and, when it added a named account, switches to it.
+fn finish_login(config: &Config, account: Option<&str>) -> ! {
+    eprintln!("{LOGIN_SUCCESS_MESSAGE}");
+    if let Some(account) = account {
+        if let Err(e) = set_active_account(&config.codex_home, account) {
+            eprintln!("Error switching to account `{account}`: {e}");
+            std::process::exit(1);
+        }
+        eprintln!("Switched to account `{account}`");
+    }
+    std::process::exit(0);
+}
+
+/// The named account whose credentials `config` uses, if it is not the default one.
+pub fn named_account_in_use(config: &Config) -> Option<String> {
+    config
+        .account
+        .clone()
+        .or_else(|| active_account(&config.codex_home))
+        .filter(|account| account != DEFAULT_ACCOUNT)
+}
+
 fn safe_format_key(key: &str) -> String {
     if key.len() <= 13 {
         return "***".to_string();
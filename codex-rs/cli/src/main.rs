@@ -29,24 +29,43 @@ use codex_utils_cli::CliConfigOverrides;
 use owo_colors::OwoColorize;
 use std::io::IsTerminal;
 use std::path::PathBuf;
+use std::time::Duration;
 use supports_color::Stream;
 
+mod account_cmd;
 #[cfg(target_os = "macos")]
 mod app_cmd;
+mod apply_cmd;
+mod config_cmd;
 #[cfg(target_os = "macos")]
 mod desktop_app;
+mod diff_cmd;
+mod doctor_cmd;
 mod mcp_cmd;
+mod sessions_cmd;
+mod trust_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
+use crate::account_cmd::AccountCli;
+use crate::config_cmd::ConfigCli;
+use crate::diff_cmd::DiffCli;
+use crate::doctor_cmd::DoctorCli;
 use crate::mcp_cmd::McpCli;
+use crate::sessions_cmd::SessionsCli;
+use crate::trust_cmd::TrustCli;
 
+use codex_core::AuthManager;
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
 use codex_core::config::edit::ConfigEditsBuilder;
 use codex_core::config::find_codex_home;
 use codex_core::features::Stage;
 use codex_core::features::is_known_feature_key;
+use codex_core::models_manager::manager::ModelsManager;
+use codex_core::models_manager::manager::RefreshStrategy;
+use codex_core::replay::ReplayOptions;
+use codex_core::replay::replay_rollout;
 use codex_core::terminal::TerminalName;
 
 /// Codex CLI
@@ -93,9 +112,18 @@ enum Subcommand {
     /// Remove stored authentication credentials.
     Logout(LogoutCommand),
 
+    /// List, switch between and remove named accounts.
+    Account(AccountCli),
+
     /// Manage external MCP servers for Codex.
     Mcp(McpCli),
 
+    /// Read and edit settings in config.toml.
+    Config(ConfigCli),
+
+    /// Manage which projects are trusted to apply their own config.
+    Trust(TrustCli),
+
     /// Start Codex as an MCP server (stdio).
     McpServer,
 
@@ -106,7 +134,11 @@ enum Subcommand {
     #[cfg(target_os = "macos")]
     App(app_cmd::AppCommand),
 
-    /// Generate shell completion scripts.
+    /// Check that Codex is set up correctly and explain how to fix what is not.
+    Doctor(DoctorCli),
+
+    /// Generate shell completion scripts, including your profile, preset and model names.
+    #[clap(visible_alias = "completions")]
     Completion(CompletionCommand),
 
     /// Run commands within a Codex-provided sandbox.
@@ -119,7 +151,8 @@ enum Subcommand {
     #[clap(hide = true)]
     Execpolicy(ExecpolicyCommand),
 
-    /// Apply the latest diff produced by Codex agent as a `git apply` to your local working tree.
+    /// Apply the latest diff of a Codex Cloud task, a patch file, or the patches a session made in
+    /// one turn to your local working tree.
     #[clap(visible_alias = "a")]
     Apply(ApplyCommand),
 
@@ -129,6 +162,15 @@ enum Subcommand {
     /// Fork a previous interactive session (picker by default; use --last to fork the most recent).
     Fork(ForkCommand),
 
+    /// Print the events recorded in a session rollout as JSONL, paced like the original session.
+    Replay(ReplayCommand),
+
+    /// List, show, export and delete recorded sessions.
+    Sessions(SessionsCli),
+
+    /// Print the changes a recorded session, or one of its turns, made to files as a unified diff.
+    Diff(DiffCli),
+
     /// [EXPERIMENTAL] Browse tasks from Codex Cloud and apply changes locally.
     #[clap(name = "cloud", alias = "cloud-tasks")]
     Cloud(CloudTasksCli),
@@ -275,6 +317,10 @@ struct LoginCommand {
     #[arg(long = "device-auth")]
     use_device_code: bool,
 
+    /// Store the credentials as a named account (e.g. `work`) and switch to it.
+    #[arg(long = "name", value_name = "ACCOUNT")]
+    account: Option<String>,
+
     /// EXPERIMENTAL: Use custom OAuth issuer base URL (advanced)
     /// Override the OAuth issuer base URL (advanced)
     #[arg(long = "experimental_issuer", value_name = "URL", hide = true)]
@@ -315,6 +361,11 @@ struct AppServerCommand {
     )]
     listen: codex_app_server::AppServerTransport,
 
+    /// Browser origin, such as `https://app.example.com`, allowed to open a websocket
+    /// connection. Pages served from loopback hosts are always allowed. Repeatable.
+    #[arg(long = "allowed-origin", value_name = "ORIGIN")]
+    allowed_origins: Vec<String>,
+
     /// Controls whether analytics are enabled by default.
     ///
     /// Analytics are disabled by default for app-server. Users have to explicitly opt in
@@ -369,6 +420,21 @@ struct GenerateJsonSchemaCommand {
     experimental: bool,
 }
 
+#[derive(Debug, Parser)]
+struct ReplayCommand {
+    /// Rollout file to replay, for example `~/.codex/sessions/2025/01/01/rollout-….jsonl`.
+    #[arg(value_name = "ROLLOUT")]
+    rollout: PathBuf,
+
+    /// Playback rate relative to the recording, at least 0.01; 0 prints every event at once.
+    #[arg(long, default_value_t = 1.0)]
+    speed: f64,
+
+    /// Longest pause between two events, in seconds.
+    #[arg(long = "max-gap", value_name = "SECONDS", default_value_t = 5)]
+    max_gap: u64,
+}
+
 #[derive(Debug, Parser)]
 struct StdioToUdsCommand {
     /// Path to the Unix domain socket to connect to.
@@ -462,6 +528,21 @@ fn run_update_action(action: UpdateAction) -> anyhow::Result<()> {
     Ok(())
 }
 
+async fn run_replay_command(cmd: ReplayCommand) -> anyhow::Result<()> {
+    let options = ReplayOptions {
+        speed: cmd.speed,
+        max_gap: Duration::from_secs(cmd.max_gap),
+    };
+    let (tx_event, rx_event) = async_channel::unbounded();
+    let replay =
+        tokio::spawn(async move { replay_rollout(&cmd.rollout, options, "", &tx_event).await });
+    while let Ok(event) = rx_event.recv().await {
+        println!("{}", serde_json::to_string(&event)?);
+    }
+    replay.await??;
+    Ok(())
+}
+
 fn run_execpolicycheck(cmd: ExecPolicyCheckCommand) -> anyhow::Result<()> {
     cmd.run()
 }
@@ -597,6 +678,17 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             prepend_config_flags(&mut mcp_cli.config_overrides, root_config_overrides.clone());
             mcp_cli.run().await?;
         }
+        Some(Subcommand::Config(mut config_cli)) => {
+            // Propagate any root-level config overrides (e.g. `-c key=value`).
+            prepend_config_flags(
+                &mut config_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            config_cli.run().await?;
+        }
+        Some(Subcommand::Trust(trust_cli)) => {
+            trust_cli.run()?;
+        }
         Some(Subcommand::AppServer(app_server_cli)) => match app_server_cli.subcommand {
             None => {
                 let transport = app_server_cli.listen;
@@ -606,6 +698,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                     codex_core::config_loader::LoaderOverrides::default(),
                     app_server_cli.analytics_default_enabled,
                     transport,
+                    app_server_cli.allowed_origins,
                 )
                 .await?;
             }
@@ -680,6 +773,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                             login_cli.config_overrides,
                             login_cli.issuer_base_url,
                             login_cli.client_id,
+                            login_cli.account,
                         )
                         .await;
                     } else if login_cli.api_key.is_some() {
@@ -689,9 +783,14 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                         std::process::exit(1);
                     } else if login_cli.with_api_key {
                         let api_key = read_api_key_from_stdin();
-                        run_login_with_api_key(login_cli.config_overrides, api_key).await;
+                        run_login_with_api_key(
+                            login_cli.config_overrides,
+                            api_key,
+                            login_cli.account,
+                        )
+                        .await;
                     } else {
-                        run_login_with_chatgpt(login_cli.config_overrides).await;
+                        run_login_with_chatgpt(login_cli.config_overrides, login_cli.account).await;
                     }
                 }
             }
@@ -703,8 +802,36 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             );
             run_logout(logout_cli.config_overrides).await;
         }
+        Some(Subcommand::Account(mut account_cli)) => {
+            prepend_config_flags(
+                &mut account_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            account_cli.run().await?;
+        }
+        Some(Subcommand::Sessions(mut sessions_cli)) => {
+            prepend_config_flags(
+                &mut sessions_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            sessions_cli.run().await?;
+        }
+        Some(Subcommand::Diff(mut diff_cli)) => {
+            prepend_config_flags(
+                &mut diff_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            diff_cli.run().await?;
+        }
+        Some(Subcommand::Doctor(mut doctor_cli)) => {
+            prepend_config_flags(
+                &mut doctor_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            doctor_cli.run().await?;
+        }
         Some(Subcommand::Completion(completion_cli)) => {
-            print_completion(completion_cli);
+            print_completion(completion_cli, root_config_overrides).await;
         }
         Some(Subcommand::Cloud(mut cloud_cli)) => {
             prepend_config_flags(
@@ -761,12 +888,18 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 &mut apply_cli.config_overrides,
                 root_config_overrides.clone(),
             );
-            run_apply_command(apply_cli, None).await?;
+            match apply_cmd::PatchSource::parse(&apply_cli.task_id) {
+                Some(source) => apply_cmd::run_local_apply(apply_cli, source).await?,
+                None => run_apply_command(apply_cli, None).await?,
+            }
         }
         Some(Subcommand::ResponsesApiProxy(args)) => {
             tokio::task::spawn_blocking(move || codex_responses_api_proxy::run_main(args))
                 .await??;
         }
+        Some(Subcommand::Replay(cmd)) => {
+            run_replay_command(cmd).await?;
+        }
         Some(Subcommand::StdioToUds(cmd)) => {
             let socket_path = cmd.socket_path;
             tokio::task::spawn_blocking(move || codex_stdio_to_uds::run(socket_path.as_path()))
@@ -986,6 +1119,9 @@ fn merge_interactive_cli_flags(interactive: &mut TuiCli, subcommand_cli: TuiCli)
     if let Some(model) = subcommand_cli.model {
         interactive.model = Some(model);
     }
+    if let Some(model_preset) = subcommand_cli.model_preset {
+        interactive.model_preset = Some(model_preset);
+    }
     if subcommand_cli.oss {
         interactive.oss = true;
     }
@@ -1027,12 +1163,110 @@ fn merge_interactive_cli_flags(interactive: &mut TuiCli, subcommand_cli: TuiCli)
         .extend(subcommand_cli.config_overrides.raw_overrides);
 }
 
-fn print_completion(cmd: CompletionCommand) {
+async fn print_completion(cmd: CompletionCommand, root_config_overrides: CliConfigOverrides) {
     let mut app = MultitoolCli::command();
+    // Names are read from config when the script is generated, so the script has to be
+    // regenerated to pick up new ones. Completion still works without them if config is broken.
+    match completion_values(root_config_overrides).await {
+        Ok(values) => {
+            app = with_possible_values(app, "profile", values.profiles);
+            app = with_possible_values(app, "preset", values.presets);
+            app = with_possible_values(app, "model", values.models);
+        }
+        Err(err) => eprintln!("Completing without profile and model names: {err}"),
+    }
     let name = "codex";
     generate(cmd.shell, &mut app, name, &mut std::io::stdout());
 }
 
+#[derive(Debug, Default)]
+struct CompletionValues {
+    profiles: Vec<String>,
+    presets: Vec<String>,
+    models: Vec<String>,
+}
+
+async fn completion_values(
+    root_config_overrides: CliConfigOverrides,
+) -> anyhow::Result<CompletionValues> {
+    let cli_kv_overrides = root_config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(cli_kv_overrides).await?;
+
+    let profiles = config
+        .config_layer_stack
+        .effective_config()
+        .get("profiles")
+        .and_then(toml::Value::as_table)
+        .map(|profiles| profiles.keys().cloned().collect())
+        .unwrap_or_default();
+    let presets = config.model_presets.keys().cloned().collect();
+
+    let auth_manager = AuthManager::shared(
+        config.auth_home(),
+        false,
+        config.cli_auth_credentials_store_mode,
+    );
+    let models_manager = ModelsManager::new(config.codex_home.clone(), auth_manager);
+    let mut models: Vec<String> = models_manager
+        .list_models(RefreshStrategy::Offline)
+        .await
+        .into_iter()
+        .map(|preset| preset.model)
+        .collect();
+    models.sort();
+    models.dedup();
+
+    Ok(CompletionValues {
+        profiles,
+        presets,
+        models,
+    })
+}
+
+/// Offers `values` as the completions of every `--<long>` option, in every subcommand.
+fn with_possible_values(cmd: clap::Command, long: &str, values: Vec<String>) -> clap::Command {
+    if values.is_empty() {
+        return cmd;
+    }
+    // Possible values need `'static` names; the process exits right after printing the script.
+    let values: Vec<&'static str> = values
+        .into_iter()
+        .map(|value| &*Box::leak(value.into_boxed_str()))
+        .collect();
+    set_possible_values(cmd, long, &values)
+}
+
+fn set_possible_values(
+    mut cmd: clap::Command,
+    long: &str,
+    values: &[&'static str],
+) -> clap::Command {
+    let ids: Vec<String> = cmd
+        .get_arguments()
+        .filter(|arg| arg.get_long() == Some(long))
+        .map(|arg| arg.get_id().to_string())
+        .collect();
+    for id in ids {
+        cmd = cmd.mut_arg(id, |arg| {
+            arg.value_parser(clap::builder::PossibleValuesParser::new(
+                values.iter().copied(),
+            ))
+        });
+    }
+    let subcommands: Vec<String> = cmd
+        .get_subcommands()
+        .map(|subcommand| subcommand.get_name().to_string())
+        .collect();
+    for name in subcommands {
+        cmd = cmd.mut_subcommand(name, |subcommand| {
+            set_possible_values(subcommand, long, values)
+        });
+    }
+    cmd
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1092,6 +1326,30 @@ mod tests {
         finalize_fork_interactive(interactive, root_overrides, session_id, last, all, fork_cli)
     }
 
+    #[test]
+    fn completion_offers_model_names_for_every_model_flag() {
+        let app = with_possible_values(
+            MultitoolCli::command(),
+            "model",
+            vec!["gpt-5.2-codex".to_string(), "o3".to_string()],
+        );
+        let possible_models = |cmd: &clap::Command| -> Vec<String> {
+            cmd.get_arguments()
+                .find(|arg| arg.get_long() == Some("model"))
+                .map(|arg| {
+                    arg.get_possible_values()
+                        .iter()
+                        .map(|value| value.get_name().to_string())
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        assert_eq!(possible_models(&app), vec!["gpt-5.2-codex", "o3"]);
+        let exec = app.find_subcommand("exec").expect("exec subcommand exists");
+        assert_eq!(possible_models(exec), vec!["gpt-5.2-codex", "o3"]);
+    }
+
     #[test]
     fn exec_resume_last_accepts_prompt_positional() {
         let cli =
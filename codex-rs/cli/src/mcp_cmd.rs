@@ -14,19 +14,25 @@ use codex_core::config::types::McpServerTransportConfig;
 use codex_core::mcp::auth::McpOAuthLoginSupport;
 use codex_core::mcp::auth::compute_auth_statuses;
 use codex_core::mcp::auth::oauth_login_support;
+use codex_core::mcp::probe_mcp_server;
 use codex_core::protocol::McpAuthStatus;
+use codex_core::secrets::save_config_secret;
 use codex_rmcp_client::delete_oauth_tokens;
 use codex_rmcp_client::perform_oauth_login;
 use codex_utils_cli::CliConfigOverrides;
 use codex_utils_cli::format_env_display::format_env_display;
 
+use crate::config_cmd::read_secret_from_stdin;
+
 /// Subcommands:
 /// - `list`   — list configured servers (with `--json`)
 /// - `get`    — show a single server (with `--json`)
 /// - `add`    — add a server launcher entry to `~/.codex/config.toml`
+/// - `install` — add a server published to npm, PyPI, or a container registry
 /// - `remove` — delete a server entry
 /// - `login`  — authenticate with MCP server using OAuth
 /// - `logout` — remove OAuth credentials for MCP server
+/// - `set-secret` — store a secret in the OS keychain for use in a server's `env`
 #[derive(Debug, clap::Parser)]
 pub struct McpCli {
     #[clap(flatten)]
@@ -41,9 +47,11 @@ pub enum McpSubcommand {
     List(ListArgs),
     Get(GetArgs),
     Add(AddArgs),
+    Install(InstallArgs),
     Remove(RemoveArgs),
     Login(LoginArgs),
     Logout(LogoutArgs),
+    SetSecret(SetSecretArgs),
 }
 
 #[derive(Debug, clap::Parser)]
@@ -71,6 +79,10 @@ pub struct AddArgs {
 
     #[command(flatten)]
     pub transport_args: AddMcpTransportArgs,
+
+    /// Save the entry without starting the server to list its tools.
+    #[arg(long)]
+    pub no_probe: bool,
 }
 
 #[derive(Debug, clap::Args)]
@@ -124,6 +136,50 @@ pub struct AddMcpStreamableHttpArgs {
         requires = "url"
     )]
     pub bearer_token_env_var: Option<String>,
+
+    /// HTTP header to send with every request to the server.
+    /// Only valid with streamable HTTP servers.
+    #[arg(
+        long = "header",
+        value_parser = parse_header_pair,
+        value_name = "NAME=VALUE",
+        requires = "url"
+    )]
+    pub headers: Vec<(String, String)>,
+
+    /// HTTP header whose value is read from an environment variable.
+    /// Only valid with streamable HTTP servers.
+    #[arg(
+        long = "env-header",
+        value_parser = parse_header_pair,
+        value_name = "NAME=ENV_VAR",
+        requires = "url"
+    )]
+    pub env_headers: Vec<(String, String)>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct InstallArgs {
+    /// Package to run: `npm:<package>` (run with npx), `pypi:<package>` (run with uvx), or
+    /// `docker:<image>`.
+    #[arg(value_name = "REGISTRY_REF")]
+    pub reference: String,
+
+    /// Name for the MCP server configuration. Defaults to one derived from the package name.
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// Environment variables to set when launching the server.
+    #[arg(
+        long,
+        value_parser = parse_env_pair,
+        value_name = "KEY=VALUE",
+    )]
+    pub env: Vec<(String, String)>,
+
+    /// Save the entry without starting the server to list its tools.
+    #[arg(long)]
+    pub no_probe: bool,
 }
 
 #[derive(Debug, clap::Parser)]
@@ -148,6 +204,12 @@ pub struct LogoutArgs {
     pub name: String,
 }
 
+#[derive(Debug, clap::Parser)]
+pub struct SetSecretArgs {
+    /// Name to store the secret under; reference it as `{ keychain = "<NAME>" }` in `env`.
+    pub name: String,
+}
+
 impl McpCli {
     pub async fn run(self) -> Result<()> {
         let McpCli {
@@ -165,6 +227,9 @@ impl McpCli {
             McpSubcommand::Add(args) => {
                 run_add(&config_overrides, args).await?;
             }
+            McpSubcommand::Install(args) => {
+                run_install(&config_overrides, args).await?;
+            }
             McpSubcommand::Remove(args) => {
                 run_remove(&config_overrides, args).await?;
             }
@@ -174,6 +239,9 @@ impl McpCli {
             McpSubcommand::Logout(args) => {
                 run_logout(&config_overrides, args).await?;
             }
+            McpSubcommand::SetSecret(args) => {
+                run_set_secret(args)?;
+            }
         }
 
         Ok(())
@@ -192,15 +260,11 @@ async fn run_add(config_overrides: &CliConfigOverrides, add_args: AddArgs) -> Re
     let AddArgs {
         name,
         transport_args,
+        no_probe,
     } = add_args;
 
     validate_server_name(&name)?;
 
-    let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
-    let mut servers = load_global_mcp_servers(&codex_home)
-        .await
-        .with_context(|| format!("failed to load MCP servers from {}", codex_home.display()))?;
-
     let transport = match transport_args {
         AddMcpTransportArgs {
             stdio: Some(stdio), ..
@@ -214,7 +278,13 @@ async fn run_add(config_overrides: &CliConfigOverrides, add_args: AddArgs) -> Re
             let env_map = if stdio.env.is_empty() {
                 None
             } else {
-                Some(stdio.env.into_iter().collect::<HashMap<_, _>>())
+                Some(
+                    stdio
+                        .env
+                        .into_iter()
+                        .map(|(key, value)| (key, value.into()))
+                        .collect::<HashMap<_, _>>(),
+                )
             };
             McpServerTransportConfig::Stdio {
                 command: command_bin,
@@ -229,17 +299,90 @@ async fn run_add(config_overrides: &CliConfigOverrides, add_args: AddArgs) -> Re
                 Some(AddMcpStreamableHttpArgs {
                     url,
                     bearer_token_env_var,
+                    headers,
+                    env_headers,
                 }),
             ..
         } => McpServerTransportConfig::StreamableHttp {
             url,
             bearer_token_env_var,
-            http_headers: None,
-            env_http_headers: None,
+            http_headers: (!headers.is_empty()).then(|| headers.into_iter().collect()),
+            env_http_headers: (!env_headers.is_empty()).then(|| env_headers.into_iter().collect()),
         },
         AddMcpTransportArgs { .. } => bail!("exactly one of --command or --url must be provided"),
     };
 
+    add_server(&config, name, transport, !no_probe).await
+}
+
+async fn run_install(
+    config_overrides: &CliConfigOverrides,
+    install_args: InstallArgs,
+) -> Result<()> {
+    let overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides)
+        .await
+        .context("failed to load configuration")?;
+
+    let InstallArgs {
+        reference,
+        name,
+        env,
+        no_probe,
+    } = install_args;
+
+    let (registry, package) = reference
+        .split_once(':')
+        .filter(|(_, package)| !package.is_empty())
+        .ok_or_else(|| {
+            anyhow!("invalid registry reference '{reference}' (expected <registry>:<package>)")
+        })?;
+    let (command, args) = match registry {
+        "npm" => ("npx", vec!["-y".to_string(), package.to_string()]),
+        "pypi" => ("uvx", vec![package.to_string()]),
+        "docker" => (
+            "docker",
+            vec![
+                "run".to_string(),
+                "-i".to_string(),
+                "--rm".to_string(),
+                package.to_string(),
+            ],
+        ),
+        other => bail!("unsupported registry '{other}' (use npm, pypi, or docker)"),
+    };
+    let name = name.unwrap_or_else(|| server_name_from_package(package));
+    validate_server_name(&name)?;
+
+    let transport = McpServerTransportConfig::Stdio {
+        command: command.to_string(),
+        args,
+        env: (!env.is_empty()).then(|| {
+            env.into_iter()
+                .map(|(key, value)| (key, value.into()))
+                .collect()
+        }),
+        env_vars: Vec::new(),
+        cwd: None,
+    };
+    add_server(&config, name, transport, !no_probe).await
+}
+
+/// Saves the server, logs in when it uses OAuth, and then, when `probe` is set, starts it once
+/// to list its tools so a mistyped command or URL shows up right away.
+async fn add_server(
+    config: &Config,
+    name: String,
+    transport: McpServerTransportConfig,
+    probe: bool,
+) -> Result<()> {
+    let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+    let mut servers = load_global_mcp_servers(&codex_home)
+        .await
+        .with_context(|| format!("failed to load MCP servers from {}", codex_home.display()))?;
+
     let new_entry = McpServerConfig {
         transport: transport.clone(),
         enabled: true,
@@ -249,10 +392,14 @@ async fn run_add(config_overrides: &CliConfigOverrides, add_args: AddArgs) -> Re
         tool_timeout_sec: None,
         enabled_tools: None,
         disabled_tools: None,
+        tool_renames: None,
+        tools: None,
+        tool_cache_ttl_sec: None,
         scopes: None,
+        max_concurrent_calls: None,
     };
 
-    servers.insert(name.clone(), new_entry);
+    servers.insert(name.clone(), new_entry.clone());
 
     ConfigEditsBuilder::new(&codex_home)
         .replace_mcp_servers(&servers)
@@ -283,9 +430,60 @@ async fn run_add(config_overrides: &CliConfigOverrides, add_args: AddArgs) -> Re
         ),
     }
 
+    if probe {
+        match probe_mcp_server(config, &name, &new_entry).await {
+            Ok(tools) if tools.is_empty() => println!("Server '{name}' started; it has no tools."),
+            Ok(tools) => {
+                println!("Server '{name}' started with {} tools:", tools.len());
+                for tool in tools {
+                    match tool.description {
+                        Some(description) => {
+                            let summary = description.lines().next().unwrap_or_default();
+                            println!("  {}  {summary}", tool.name);
+                        }
+                        None => println!("  {}", tool.name),
+                    }
+                }
+            }
+            Err(error) => {
+                eprintln!("Warning: server '{name}' failed to start: {error}");
+                eprintln!(
+                    "Fix the entry with `codex mcp add {name} ...` or remove it with `codex mcp remove {name}`."
+                );
+            }
+        }
+    }
+
     Ok(())
 }
 
+/// Derives a server name from a package name or image reference, e.g.
+/// `@modelcontextprotocol/server-github@1.0` becomes `github`.
+fn server_name_from_package(package: &str) -> String {
+    let base = package.rsplit('/').next().unwrap_or(package);
+    let base = base
+        .split(['@', ':', '=', '<', '>', '[', '~', '!'])
+        .next()
+        .unwrap_or(base);
+    let base = ["mcp-server-", "server-", "mcp-"]
+        .iter()
+        .find_map(|prefix| base.strip_prefix(prefix))
+        .unwrap_or(base);
+    let base = ["-mcp-server", "-server", "-mcp"]
+        .iter()
+        .find_map(|suffix| base.strip_suffix(suffix))
+        .unwrap_or(base);
+    base.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
 async fn run_remove(config_overrides: &CliConfigOverrides, remove_args: RemoveArgs) -> Result<()> {
     config_overrides
         .parse_overrides()
@@ -392,6 +590,17 @@ async fn run_logout(config_overrides: &CliConfigOverrides, logout_args: LogoutAr
     Ok(())
 }
 
+fn run_set_secret(set_secret_args: SetSecretArgs) -> Result<()> {
+    let SetSecretArgs { name } = set_secret_args;
+
+    let secret = read_secret_from_stdin(&name)?;
+    save_config_secret(&name, &secret)?;
+    println!("Saved secret '{name}' to the keychain.");
+    println!("Reference it from an MCP server's env as {{ keychain = \"{name}\" }}.");
+
+    Ok(())
+}
+
 async fn run_list(config_overrides: &CliConfigOverrides, list_args: ListArgs) -> Result<()> {
     let overrides = config_overrides
         .parse_overrides()
@@ -690,12 +899,17 @@ async fn run_get(config_overrides: &CliConfigOverrides, get_args: GetArgs) -> Re
             "transport": transport,
             "enabled_tools": server.enabled_tools.clone(),
             "disabled_tools": server.disabled_tools.clone(),
+            "tool_renames": server.tool_renames.clone(),
+            "tools": server.tools.clone(),
             "startup_timeout_sec": server
                 .startup_timeout_sec
                 .map(|timeout| timeout.as_secs_f64()),
             "tool_timeout_sec": server
                 .tool_timeout_sec
                 .map(|timeout| timeout.as_secs_f64()),
+            "tool_cache_ttl_sec": server
+                .tool_cache_ttl_sec
+                .map(|ttl| ttl.as_secs_f64()),
         }))?;
         println!("{output}");
         return Ok(());
@@ -727,6 +941,14 @@ async fn run_get(config_overrides: &CliConfigOverrides, get_args: GetArgs) -> Re
         let disabled_tools_display = format_tool_list(&server.disabled_tools);
         println!("  disabled_tools: {disabled_tools_display}");
     }
+    if let Some(tool_renames) = server.tool_renames.as_ref() {
+        let mut renames = tool_renames
+            .iter()
+            .map(|(tool, name)| format!("{tool}={name}"))
+            .collect::<Vec<_>>();
+        renames.sort();
+        println!("  tool_renames: {}", renames.join(", "));
+    }
     match &server.transport {
         McpServerTransportConfig::Stdio {
             command,
@@ -816,6 +1038,18 @@ fn parse_env_pair(raw: &str) -> Result<(String, String), String> {
     Ok((key.to_string(), value))
 }
 
+fn parse_header_pair(raw: &str) -> Result<(String, String), String> {
+    let (name, value) = raw
+        .split_once('=')
+        .ok_or_else(|| "headers must be in NAME=VALUE form".to_string())?;
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("headers must be in NAME=VALUE form".to_string());
+    }
+
+    Ok((name.to_string(), value.to_string()))
+}
+
 fn validate_server_name(name: &str) -> Result<()> {
     let is_valid = !name.is_empty()
         && name
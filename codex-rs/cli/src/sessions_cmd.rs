@@ -0,0 +1,514 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use chrono::DateTime;
+use chrono::Local;
+use chrono::NaiveDate;
+use chrono::Utc;
+use codex_core::RolloutRecorder;
+use codex_core::ThreadItem;
+use codex_core::ThreadSortKey;
+use codex_core::config::Config;
+use codex_core::find_thread_path_by_id_str;
+use codex_core::find_thread_path_by_name_str;
+use codex_core::read_rollout_text;
+use codex_core::read_session_meta_line;
+use codex_core::read_thread_model;
+use codex_core::remove_rollout;
+use codex_core::state_db::get_state_db;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use codex_utils_cli::CliConfigOverrides;
+use serde::Serialize;
+
+const PAGE_SIZE: usize = 100;
+const PREVIEW_CHARS: usize = 60;
+
+/// Subcommands:
+/// - `list`   — print recorded sessions, newest first
+/// - `show`   — print a session's details and conversation
+/// - `export` — write a session as Markdown or as its raw JSONL rollout
+/// - `delete` — delete a session
+/// - `prune`  — delete sessions that have not been used for a while
+///
+/// Sessions are read the same way the `codex resume` picker reads them, from the rollout files
+/// under `~/.codex/sessions`.
+#[derive(Debug, clap::Parser)]
+pub struct SessionsCli {
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: SessionsSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SessionsSubcommand {
+    /// Print recorded sessions, newest first.
+    List(ListArgs),
+    /// Print a session's details and conversation.
+    Show(SessionArgs),
+    /// Write a session as Markdown or as its raw JSONL rollout.
+    Export(ExportArgs),
+    /// Delete a session.
+    Delete(SessionArgs),
+    /// Delete sessions that have not been used for a while.
+    Prune(PruneArgs),
+}
+
+#[derive(Debug, Default, clap::Args)]
+pub struct SessionFilter {
+    /// Only sessions started in this directory or below it.
+    #[arg(long, value_name = "DIR")]
+    pub project: Option<PathBuf>,
+
+    /// Only sessions that used this model.
+    #[arg(long)]
+    pub model: Option<String>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ListArgs {
+    #[clap(flatten)]
+    pub filter: SessionFilter,
+
+    /// Only sessions started on or after this date (YYYY-MM-DD, local time).
+    #[arg(long, value_name = "DATE", value_parser = parse_date)]
+    pub since: Option<NaiveDate>,
+
+    /// Only sessions started on or before this date (YYYY-MM-DD, local time).
+    #[arg(long, value_name = "DATE", value_parser = parse_date)]
+    pub until: Option<NaiveDate>,
+
+    /// Print at most this many sessions.
+    #[arg(long, value_name = "N")]
+    pub limit: Option<usize>,
+
+    /// Output the sessions as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct SessionArgs {
+    /// Session id (UUID) or thread name.
+    #[arg(value_name = "SESSION")]
+    pub session: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// The conversation as a Markdown document.
+    Markdown,
+    /// The rollout file as recorded.
+    Jsonl,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ExportArgs {
+    #[clap(flatten)]
+    pub session: SessionArgs,
+
+    #[arg(long, value_enum, default_value_t = ExportFormat::Markdown)]
+    pub format: ExportFormat,
+
+    /// Write to this file instead of stdout.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct PruneArgs {
+    #[clap(flatten)]
+    pub filter: SessionFilter,
+
+    /// Delete sessions last used more than this many days ago.
+    #[arg(long, value_name = "DAYS")]
+    pub older_than: u32,
+
+    /// Print the sessions that would be deleted without deleting them.
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// One row of `codex sessions list`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct SessionSummary {
+    id: Option<String>,
+    path: PathBuf,
+    created_at: Option<DateTime<Utc>>,
+    updated_at: Option<DateTime<Utc>>,
+    cwd: Option<PathBuf>,
+    git_branch: Option<String>,
+    model: Option<String>,
+    model_provider: Option<String>,
+    preview: Option<String>,
+}
+
+impl SessionSummary {
+    async fn from_item(item: ThreadItem) -> Self {
+        let created_at = item.created_at.as_deref().and_then(parse_timestamp);
+        let updated_at = item
+            .updated_at
+            .as_deref()
+            .and_then(parse_timestamp)
+            .or(created_at);
+        let model = read_thread_model(&item.path).await.ok().flatten();
+        Self {
+            id: item.thread_id.map(|id| id.to_string()),
+            created_at,
+            updated_at,
+            cwd: item.cwd,
+            git_branch: item.git_branch,
+            model,
+            model_provider: item.model_provider,
+            preview: item
+                .first_user_message
+                .map(|message| message.trim().to_string())
+                .filter(|message| !message.is_empty()),
+            path: item.path,
+        }
+    }
+}
+
+impl SessionFilter {
+    fn matches(&self, session: &SessionSummary) -> bool {
+        let in_project = self.project.as_deref().is_none_or(|project| {
+            session
+                .cwd
+                .as_deref()
+                .is_some_and(|cwd| cwd.starts_with(project))
+        });
+        let uses_model = self
+            .model
+            .as_deref()
+            .is_none_or(|model| session.model.as_deref() == Some(model));
+        in_project && uses_model
+    }
+
+    fn absolutize(mut self) -> Result<Self> {
+        if let Some(project) = self.project.take() {
+            let project = std::path::absolute(&project)
+                .with_context(|| format!("failed to resolve {}", project.display()))?;
+            // Sessions record canonical working directories.
+            self.project = Some(std::fs::canonicalize(&project).unwrap_or(project));
+        }
+        Ok(self)
+    }
+}
+
+impl SessionsCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides).await?;
+        match self.subcommand {
+            SessionsSubcommand::List(args) => run_list(&config, args).await,
+            SessionsSubcommand::Show(args) => run_show(&config, args).await,
+            SessionsSubcommand::Export(args) => run_export(&config, args).await,
+            SessionsSubcommand::Delete(args) => run_delete(&config, args).await,
+            SessionsSubcommand::Prune(args) => run_prune(&config, args).await,
+        }
+    }
+}
+
+async fn run_list(config: &Config, args: ListArgs) -> Result<()> {
+    let filter = args.filter.absolutize()?;
+    let mut sessions: Vec<SessionSummary> = load_sessions(config)
+        .await?
+        .into_iter()
+        .filter(|session| filter.matches(session))
+        .filter(|session| {
+            let started = session
+                .created_at
+                .map(|created_at| created_at.with_timezone(&Local).date_naive());
+            args.since
+                .is_none_or(|since| started.is_some_and(|started| started >= since))
+                && args
+                    .until
+                    .is_none_or(|until| started.is_some_and(|started| started <= until))
+        })
+        .collect();
+    if let Some(limit) = args.limit {
+        sessions.truncate(limit);
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&sessions)?);
+        return Ok(());
+    }
+    if sessions.is_empty() {
+        println!("No sessions found.");
+        return Ok(());
+    }
+    for session in &sessions {
+        println!(
+            "{id:<36}  {created:<16}  {model:<16}  {cwd}",
+            id = session.id.as_deref().unwrap_or("-"),
+            created = session
+                .created_at
+                .map(|created_at| created_at
+                    .with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            model = session.model.as_deref().unwrap_or("-"),
+            cwd = session
+                .cwd
+                .as_deref()
+                .map(|cwd| cwd.display().to_string())
+                .unwrap_or_else(|| "-".to_string()),
+        );
+        if let Some(preview) = &session.preview {
+            println!("{:<36}  {}", "", truncate_preview(preview));
+        }
+    }
+    Ok(())
+}
+
+async fn run_show(config: &Config, args: SessionArgs) -> Result<()> {
+    let path = resolve_session(config, &args.session).await?;
+    let meta = read_session_meta_line(&path).await?;
+    let model = read_thread_model(&path).await?;
+
+    println!("Session:  {}", meta.meta.id);
+    println!("Started:  {}", meta.meta.timestamp);
+    println!("Folder:   {}", meta.meta.cwd.display());
+    if let Some(branch) = meta.git.as_ref().and_then(|git| git.branch.as_deref()) {
+        println!("Branch:   {branch}");
+    }
+    println!("Model:    {}", model.as_deref().unwrap_or("-"));
+    println!(
+        "Provider: {}",
+        meta.meta.model_provider.as_deref().unwrap_or("-")
+    );
+    println!("Rollout:  {}", path.display());
+
+    for (speaker, message) in transcript(&path).await? {
+        println!();
+        println!("{speaker}:");
+        println!("{}", message.trim_end());
+    }
+    Ok(())
+}
+
+async fn run_export(config: &Config, args: ExportArgs) -> Result<()> {
+    let path = resolve_session(config, &args.session.session).await?;
+    let contents = match args.format {
+        ExportFormat::Jsonl => read_rollout_text(&path).await?,
+        ExportFormat::Markdown => {
+            let meta = read_session_meta_line(&path).await?;
+            let mut markdown = format!(
+                "# Codex session {}\n\n- Started: {}\n- Folder: {}\n",
+                meta.meta.id,
+                meta.meta.timestamp,
+                meta.meta.cwd.display()
+            );
+            if let Some(model) = read_thread_model(&path).await? {
+                markdown.push_str(&format!("- Model: {model}\n"));
+            }
+            for (speaker, message) in transcript(&path).await? {
+                markdown.push_str(&format!("\n## {speaker}\n\n{}\n", message.trim_end()));
+            }
+            markdown
+        }
+    };
+
+    match args.output {
+        Some(output) => {
+            std::fs::write(&output, contents)
+                .with_context(|| format!("failed to write {}", output.display()))?;
+            eprintln!("Exported the session to {}.", output.display());
+        }
+        None => print!("{contents}"),
+    }
+    Ok(())
+}
+
+async fn run_delete(config: &Config, args: SessionArgs) -> Result<()> {
+    let path = resolve_session(config, &args.session).await?;
+    let thread_id = read_session_meta_line(&path)
+        .await
+        .ok()
+        .map(|meta| meta.meta.id);
+    delete_session(config, &path, thread_id).await?;
+    println!("Deleted session {}.", args.session);
+    Ok(())
+}
+
+async fn run_prune(config: &Config, args: PruneArgs) -> Result<()> {
+    let filter = args.filter.absolutize()?;
+    let cutoff = Utc::now() - chrono::Duration::days(i64::from(args.older_than));
+    let stale: Vec<SessionSummary> = load_sessions(config)
+        .await?
+        .into_iter()
+        .filter(|session| filter.matches(session))
+        .filter(|session| {
+            session
+                .updated_at
+                .is_some_and(|updated_at| updated_at < cutoff)
+        })
+        .collect();
+
+    if stale.is_empty() {
+        println!(
+            "No sessions were last used more than {} day(s) ago.",
+            args.older_than
+        );
+        return Ok(());
+    }
+    for session in &stale {
+        if !args.dry_run {
+            let thread_id = session
+                .id
+                .as_deref()
+                .and_then(|id| ThreadId::from_string(id).ok());
+            delete_session(config, &session.path, thread_id).await?;
+        }
+        println!("{}", session.path.display());
+    }
+    if args.dry_run {
+        println!("Would delete {} session(s).", stale.len());
+    } else {
+        println!("Deleted {} session(s).", stale.len());
+    }
+    Ok(())
+}
+
+/// Every recorded session from any source and provider, newest first.
+async fn load_sessions(config: &Config) -> Result<Vec<SessionSummary>> {
+    let mut sessions = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = RolloutRecorder::list_threads(
+            config,
+            PAGE_SIZE,
+            cursor.as_ref(),
+            ThreadSortKey::CreatedAt,
+            &[],
+            None,
+            config.model_provider_id.as_str(),
+        )
+        .await?;
+        for item in page.items {
+            sessions.push(SessionSummary::from_item(item).await);
+        }
+        match page.next_cursor {
+            Some(next_cursor) => cursor = Some(next_cursor),
+            None => break,
+        }
+    }
+    Ok(sessions)
+}
+
+/// Finds a session by id, or by name like `codex resume` does.
+pub(crate) async fn resolve_session(config: &Config, session: &str) -> Result<PathBuf> {
+    let path = if ThreadId::from_string(session).is_ok() {
+        find_thread_path_by_id_str(&config.codex_home, session).await?
+    } else {
+        find_thread_path_by_name_str(&config.codex_home, session).await?
+    };
+    match path {
+        Some(path) => Ok(path),
+        None => bail!("no session matches `{session}`; run `codex sessions list` to see them"),
+    }
+}
+
+/// Removes the rollout and its row in the state database, so the session stops showing up in
+/// `codex resume` right away.
+async fn delete_session(config: &Config, path: &Path, thread_id: Option<ThreadId>) -> Result<()> {
+    remove_rollout(path)
+        .await
+        .with_context(|| format!("failed to delete {}", path.display()))?;
+    if let (Some(thread_id), Some(state_db)) = (thread_id, get_state_db(config, None).await) {
+        state_db.delete_thread(thread_id).await?;
+    }
+    Ok(())
+}
+
+/// The user and agent messages of a session, in order.
+async fn transcript(path: &Path) -> Result<Vec<(&'static str, String)>> {
+    let history = RolloutRecorder::get_rollout_history(path).await?;
+    Ok(history
+        .get_rollout_items()
+        .into_iter()
+        .filter_map(|item| match item {
+            RolloutItem::EventMsg(EventMsg::UserMessage(event)) => Some(("User", event.message)),
+            RolloutItem::EventMsg(EventMsg::AgentMessage(event)) => Some(("Codex", event.message)),
+            _ => None,
+        })
+        .collect())
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("`{value}` is not a date like 2025-01-31"))
+}
+
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .ok()
+}
+
+fn truncate_preview(preview: &str) -> String {
+    let first_line = preview.lines().next().unwrap_or_default();
+    if first_line.chars().count() > PREVIEW_CHARS || preview.lines().nth(1).is_some() {
+        let truncated: String = first_line.chars().take(PREVIEW_CHARS).collect();
+        format!("{}…", truncated.trim_end())
+    } else {
+        first_line.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn session(cwd: &str, model: Option<&str>) -> SessionSummary {
+        SessionSummary {
+            id: None,
+            path: PathBuf::from("/tmp/rollout.jsonl"),
+            created_at: None,
+            updated_at: None,
+            cwd: Some(PathBuf::from(cwd)),
+            git_branch: None,
+            model: model.map(str::to_string),
+            model_provider: None,
+            preview: None,
+        }
+    }
+
+    #[test]
+    fn filters_by_project_and_model() {
+        let filter = SessionFilter {
+            project: Some(PathBuf::from("/work/app")),
+            model: Some("gpt-5.1-codex".to_string()),
+        };
+        assert!(filter.matches(&session("/work/app/server", Some("gpt-5.1-codex"))));
+        assert!(!filter.matches(&session("/work/application", Some("gpt-5.1-codex"))));
+        assert!(!filter.matches(&session("/work/app", Some("gpt-5.1"))));
+        assert!(!filter.matches(&session("/work/app", None)));
+        assert!(SessionFilter::default().matches(&session("/anywhere", None)));
+    }
+
+    #[test]
+    fn previews_show_the_first_line() {
+        assert_eq!(truncate_preview("fix the build"), "fix the build");
+        assert_eq!(
+            truncate_preview("fix the build\nit fails"),
+            "fix the build…"
+        );
+        assert_eq!(
+            truncate_preview(&"a".repeat(PREVIEW_CHARS + 5)),
+            format!("{}…", "a".repeat(PREVIEW_CHARS))
+        );
+    }
+}
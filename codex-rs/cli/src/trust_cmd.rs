@@ -0,0 +1,119 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use anyhow::bail;
+use codex_core::config::clear_project_trust_level;
+use codex_core::config::find_codex_home;
+use codex_core::config::load_project_trust_levels;
+use codex_core::config::set_project_trust_level;
+use codex_core::git_info::resolve_root_git_project_for_trust;
+use codex_protocol::config_types::TrustLevel;
+
+/// Subcommands:
+/// - `add`    — trust a project, so its `.codex/config.toml` is applied
+/// - `remove` — forget the trust decision for a project
+/// - `list`   — print every project with a trust decision
+///
+/// Projects are stored by the root of their git repository, like the trust prompt in the TUI does,
+/// so trusting any folder of a repository (or one of its worktrees) trusts all of it.
+#[derive(Debug, clap::Parser)]
+pub struct TrustCli {
+    #[command(subcommand)]
+    pub subcommand: TrustSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum TrustSubcommand {
+    /// Trust a project; defaults to the current directory.
+    Add(TrustPathArgs),
+    /// Forget the trust decision for a project; defaults to the current directory.
+    Remove(TrustPathArgs),
+    /// Print every project with a trust decision.
+    List(TrustListArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct TrustPathArgs {
+    /// Path to the project.
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct TrustListArgs {
+    /// Output the projects as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl TrustCli {
+    pub fn run(self) -> Result<()> {
+        let codex_home = find_codex_home()?;
+        match self.subcommand {
+            TrustSubcommand::Add(args) => run_add(&codex_home, args),
+            TrustSubcommand::Remove(args) => run_remove(&codex_home, args),
+            TrustSubcommand::List(args) => run_list(&codex_home, args),
+        }
+    }
+}
+
+fn run_add(codex_home: &Path, args: TrustPathArgs) -> Result<()> {
+    let path = absolute_project_path(args.path)?;
+    if !path.is_dir() {
+        bail!("{} is not a directory", path.display());
+    }
+    let project = resolve_root_git_project_for_trust(&path).unwrap_or(path);
+
+    set_project_trust_level(codex_home, &project, TrustLevel::Trusted)?;
+    println!("Trusted {}.", project.display());
+    Ok(())
+}
+
+fn run_remove(codex_home: &Path, args: TrustPathArgs) -> Result<()> {
+    let path = absolute_project_path(args.path)?;
+    let projects = load_project_trust_levels(codex_home)?;
+
+    // An entry may have been written for the folder itself rather than its repository.
+    let candidates = [
+        resolve_root_git_project_for_trust(&path),
+        Some(path.clone()),
+    ];
+    let Some(project) = candidates
+        .into_iter()
+        .flatten()
+        .find(|candidate| projects.contains_key(candidate.to_string_lossy().as_ref()))
+    else {
+        bail!("{} has no trust decision to remove", path.display());
+    };
+
+    clear_project_trust_level(codex_home, &project)?;
+    println!("Removed the trust decision for {}.", project.display());
+    Ok(())
+}
+
+fn run_list(codex_home: &Path, args: TrustListArgs) -> Result<()> {
+    let projects = load_project_trust_levels(codex_home)?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&projects)?);
+        return Ok(());
+    }
+    if projects.is_empty() {
+        println!("No projects have a trust decision. Add one with `codex trust add <path>`.");
+        return Ok(());
+    }
+    for (project, trust_level) in projects {
+        let trust_level = trust_level.to_string();
+        println!("{trust_level:<9}  {project}");
+    }
+    Ok(())
+}
+
+fn absolute_project_path(path: Option<PathBuf>) -> Result<PathBuf> {
+    let path = match path {
+        Some(path) => path,
+        None => std::env::current_dir()?,
+    };
+    // Normalizes the path when it exists; a project that was deleted can still be removed.
+    Ok(std::fs::canonicalize(&path).or_else(|_| std::path::absolute(&path))?)
+}
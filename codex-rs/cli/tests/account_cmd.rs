@@ -0,0 +1,80 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+fn login_with_api_key(codex_home: &Path, api_key: &str, name: Option<&str>) -> Result<()> {
+    let mut cmd = codex_command(codex_home)?;
+    cmd.args(["login", "--with-api-key"]);
+    if let Some(name) = name {
+        cmd.args(["--name", name]);
+    }
+    cmd.write_stdin(api_key).assert().success();
+    Ok(())
+}
+
+#[test]
+fn named_accounts_can_be_switched_bound_and_removed() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    login_with_api_key(codex_home.path(), "sk-default-0000000000", None)?;
+    login_with_api_key(
+        codex_home.path(),
+        "sk-personal-1111111111",
+        Some("personal"),
+    )?;
+
+    // Logging in with a name switches to the new account.
+    codex_command(codex_home.path())?
+        .args(["login", "status"])
+        .assert()
+        .success()
+        .stderr(contains("sk-perso***11111 (account `personal`)"));
+    codex_command(codex_home.path())?
+        .args(["account", "list"])
+        .assert()
+        .success()
+        .stdout("  default          API key\n* personal         API key\n");
+
+    // A binding in the config wins over the active account.
+    codex_command(codex_home.path())?
+        .args(["-c", "account=\"default\"", "login", "status"])
+        .assert()
+        .success()
+        .stderr(contains("sk-defau***00000"));
+
+    codex_command(codex_home.path())?
+        .args(["account", "switch", "default"])
+        .assert()
+        .success()
+        .stdout("Switched to account `default`.\n");
+    codex_command(codex_home.path())?
+        .args(["login", "status"])
+        .assert()
+        .success()
+        .stderr(contains("sk-defau***00000"));
+
+    codex_command(codex_home.path())?
+        .args(["account", "switch", "work"])
+        .assert()
+        .failure()
+        .stderr(contains("no account named `work`"));
+
+    codex_command(codex_home.path())?
+        .args(["account", "remove", "personal"])
+        .assert()
+        .success()
+        .stdout("Removed account `personal`.\n");
+    codex_command(codex_home.path())?
+        .args(["account", "list"])
+        .assert()
+        .success()
+        .stdout("* default          API key\n");
+    Ok(())
+}
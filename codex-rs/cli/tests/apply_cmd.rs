@@ -0,0 +1,133 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+#[test]
+fn dry_run_then_apply_a_patch_file() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let worktree = TempDir::new()?;
+    std::fs::write(worktree.path().join("greeting.txt"), "hello\n")?;
+    let patch = worktree.path().join("change.patch");
+    std::fs::write(
+        &patch,
+        "*** Begin Patch\n*** Update File: greeting.txt\n@@\n-hello\n+hello, world\n*** End Patch\n",
+    )?;
+
+    codex_command(codex_home.path())?
+        .current_dir(worktree.path())
+        .args(["apply", "--dry-run", "change.patch"])
+        .assert()
+        .success()
+        .stdout(contains("1 clean, 0 fuzzy, 0 conflicting"));
+    assert_eq!(
+        std::fs::read_to_string(worktree.path().join("greeting.txt"))?,
+        "hello\n"
+    );
+
+    codex_command(codex_home.path())?
+        .current_dir(worktree.path())
+        .args(["apply", "change.patch"])
+        .assert()
+        .success();
+    assert_eq!(
+        std::fs::read_to_string(worktree.path().join("greeting.txt"))?,
+        "hello, world\n"
+    );
+
+    // The change is already there, so the patch no longer applies.
+    codex_command(codex_home.path())?
+        .current_dir(worktree.path())
+        .args(["apply", "change.patch"])
+        .assert()
+        .failure()
+        .stdout(contains("conflicts"));
+    Ok(())
+}
+
+#[test]
+fn apply_the_patches_of_a_session_turn() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let worktree = TempDir::new()?;
+    let id = "0199a213-81c0-7800-8aa1-bbab2a035a53";
+    let dir = codex_home.path().join("sessions/2025/01/02");
+    std::fs::create_dir_all(&dir)?;
+    let timestamp = "2025-01-02T10:00:00.000Z";
+    let lines = [
+        json!({
+            "timestamp": timestamp,
+            "type": "session_meta",
+            "payload": {
+                "id": id,
+                "timestamp": timestamp,
+                "cwd": "/work/app",
+                "originator": "codex_cli_rs",
+                "cli_version": "0.0.0",
+                "source": "cli"
+            }
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "event_msg",
+            "payload": { "type": "task_started", "turn_id": "turn-1", "model_context_window": null }
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "response_item",
+            "payload": {
+                "type": "custom_tool_call",
+                "call_id": "call-1",
+                "name": "apply_patch",
+                "input": "*** Begin Patch\n*** Add File: /work/app/notes.txt\n+first turn\n*** End Patch"
+            }
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "event_msg",
+            "payload": { "type": "task_started", "turn_id": "turn-2", "model_context_window": null }
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "response_item",
+            "payload": {
+                "type": "custom_tool_call",
+                "call_id": "call-2",
+                "name": "apply_patch",
+                "input": "*** Begin Patch\n*** Add File: /work/app/todo.txt\n+second turn\n*** End Patch"
+            }
+        }),
+    ];
+    let contents: String = lines.iter().map(|line| format!("{line}\n")).collect();
+    std::fs::write(
+        dir.join(format!("rollout-2025-01-02T10-00-00-{id}.jsonl")),
+        contents,
+    )?;
+
+    codex_command(codex_home.path())?
+        .current_dir(worktree.path())
+        .args(["apply", &format!("{id}:2")])
+        .assert()
+        .success();
+    assert_eq!(
+        std::fs::read_to_string(worktree.path().join("todo.txt"))?,
+        "second turn\n"
+    );
+    assert!(!worktree.path().join("notes.txt").exists());
+
+    codex_command(codex_home.path())?
+        .current_dir(worktree.path())
+        .args(["apply", &format!("{id}:3")])
+        .assert()
+        .failure()
+        .stderr(contains("did not change any files"));
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+#[test]
+fn set_get_and_unset_keep_comments() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let config_path = codex_home.path().join("config.toml");
+    std::fs::write(
+        &config_path,
+        "# Pinned for the team.\nmodel = \"gpt-5.2\"\n",
+    )?;
+
+    codex_command(codex_home.path())?
+        .args(["config", "set", "model_context_window", "200000"])
+        .assert()
+        .success()
+        .stdout(contains("Set `model_context_window` to 200000"));
+    codex_command(codex_home.path())?
+        .args([
+            "config",
+            "set",
+            "model",
+            "gpt-5.2-codex",
+            "--profile",
+            "fast",
+        ])
+        .assert()
+        .success();
+
+    let contents = std::fs::read_to_string(&config_path)?;
+    assert!(contents.contains("# Pinned for the team."), "{contents}");
+
+    codex_command(codex_home.path())?
+        .args(["config", "get", "model_context_window"])
+        .assert()
+        .success()
+        .stdout("200000\n");
+    codex_command(codex_home.path())?
+        .args(["config", "get", "model", "--profile", "fast"])
+        .assert()
+        .success()
+        .stdout("gpt-5.2-codex\n");
+
+    codex_command(codex_home.path())?
+        .args(["config", "unset", "model_context_window"])
+        .assert()
+        .success();
+    codex_command(codex_home.path())?
+        .args(["config", "get", "model_context_window"])
+        .assert()
+        .failure()
+        .stderr(contains("`model_context_window` is not set"));
+    Ok(())
+}
+
+#[test]
+fn set_rejects_unknown_keys_and_wrong_types() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    codex_command(codex_home.path())?
+        .args(["config", "set", "modle", "gpt-5.2"])
+        .assert()
+        .failure()
+        .stderr(contains("unknown config key `modle`"));
+    codex_command(codex_home.path())?
+        .args(["config", "set", "model_context_window", "large"])
+        .assert()
+        .failure()
+        .stderr(contains("Invalid configuration"));
+
+    let config_path = codex_home.path().join("config.toml");
+    let contents = std::fs::read_to_string(&config_path).unwrap_or_default();
+    assert_eq!(contents.trim(), "");
+    Ok(())
+}
+
+#[test]
+fn list_prints_dotted_settings() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        "model = \"gpt-5.2\"\n\n[tui]\nnotifications = true\n",
+    )?;
+
+    codex_command(codex_home.path())?
+        .args(["config", "list"])
+        .assert()
+        .success()
+        .stdout(contains("model = \"gpt-5.2\"\n"))
+        .stdout(contains("tui.notifications = true\n"));
+    Ok(())
+}
+
+#[test]
+fn validate_reports_unknown_keys_with_their_location() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        "model = \"gpt-5.2\"\napproval_polcy = \"never\"\n",
+    )?;
+
+    codex_command(codex_home.path())?
+        .current_dir(codex_home.path())
+        .args(["config", "validate"])
+        .assert()
+        .failure()
+        .stderr(contains(
+            "config.toml:2:1: unknown config key `approval_polcy`",
+        ))
+        .stderr(contains("found 1 problem(s) in config.toml"));
+
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        "approval_policy = \"never\"\n",
+    )?;
+    codex_command(codex_home.path())?
+        .current_dir(codex_home.path())
+        .args(["config", "validate"])
+        .assert()
+        .success()
+        .stdout("No problems found.\n");
+    Ok(())
+}
+
+#[test]
+fn schema_prints_the_config_json_schema() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    codex_command(codex_home.path())?
+        .args(["config", "schema"])
+        .assert()
+        .success()
+        .stdout(contains("\"approval_policy\""))
+        .stdout(contains("\"strict_config\""));
+    Ok(())
+}
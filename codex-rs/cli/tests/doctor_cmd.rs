@@ -0,0 +1,36 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+#[test]
+fn doctor_reports_missing_sign_in_and_unreachable_provider() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        "notify = [\"definitely-not-a-real-notify-program\"]\n",
+    )?;
+
+    codex_command(codex_home.path())?
+        .env_remove("OPENAI_API_KEY")
+        // Nothing listens on the discard port, so the provider check fails right away.
+        .env("OPENAI_BASE_URL", "http://127.0.0.1:9/v1")
+        .arg("doctor")
+        .assert()
+        .failure()
+        .stdout(contains("config.toml is valid"))
+        .stdout(contains("not signed in"))
+        .stdout(contains("Run `codex login`."))
+        .stdout(contains("http://127.0.0.1:9/v1 could not be reached"))
+        .stdout(contains(
+            "the notify program `definitely-not-a-real-notify-program` was not found",
+        ));
+    Ok(())
+}
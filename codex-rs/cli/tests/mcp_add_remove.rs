@@ -1,7 +1,9 @@
+use std::collections::HashMap;
 use std::path::Path;
 
 use anyhow::Result;
 use codex_core::config::load_global_mcp_servers;
+use codex_core::config::types::McpEnvValue;
 use codex_core::config::types::McpServerTransportConfig;
 use predicates::str::contains;
 use pretty_assertions::assert_eq;
@@ -97,8 +99,8 @@ async fn add_with_env_preserves_key_order_and_values() -> Result<()> {
     };
 
     assert_eq!(env.len(), 2);
-    assert_eq!(env.get("FOO"), Some(&"bar".to_string()));
-    assert_eq!(env.get("ALPHA"), Some(&"beta".to_string()));
+    assert_eq!(env.get("FOO"), Some(&McpEnvValue::from("bar")));
+    assert_eq!(env.get("ALPHA"), Some(&McpEnvValue::from("beta")));
     assert!(envy.enabled);
 
     Ok(())
@@ -176,6 +178,85 @@ async fn add_streamable_http_with_custom_env_var() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn add_streamable_http_with_headers() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut add_cmd = codex_command(codex_home.path())?;
+    add_cmd
+        .args([
+            "mcp",
+            "add",
+            "docs",
+            "--url",
+            "https://example.com/docs",
+            "--header",
+            "X-Team=platform",
+            "--header",
+            "X-Region=eu",
+            "--env-header",
+            "X-Api-Key=DOCS_API_KEY",
+        ])
+        .assert()
+        .success();
+
+    let servers = load_global_mcp_servers(codex_home.path()).await?;
+    let docs = servers.get("docs").expect("docs server should exist");
+    match &docs.transport {
+        McpServerTransportConfig::StreamableHttp {
+            url,
+            bearer_token_env_var,
+            http_headers,
+            env_http_headers,
+        } => {
+            assert_eq!(url, "https://example.com/docs");
+            assert!(bearer_token_env_var.is_none());
+            assert_eq!(
+                http_headers,
+                &Some(HashMap::from([
+                    ("X-Team".to_string(), "platform".to_string()),
+                    ("X-Region".to_string(), "eu".to_string()),
+                ]))
+            );
+            assert_eq!(
+                env_http_headers,
+                &Some(HashMap::from([(
+                    "X-Api-Key".to_string(),
+                    "DOCS_API_KEY".to_string()
+                )]))
+            );
+        }
+        other => panic!("unexpected transport: {other:?}"),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn add_stdio_rejects_header_flag() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut add_cmd = codex_command(codex_home.path())?;
+    add_cmd
+        .args([
+            "mcp",
+            "add",
+            "docs",
+            "--header",
+            "X-Team=platform",
+            "--",
+            "echo",
+            "hello",
+        ])
+        .assert()
+        .failure();
+
+    let servers = load_global_mcp_servers(codex_home.path()).await?;
+    assert!(servers.is_empty());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn add_streamable_http_rejects_removed_flag() -> Result<()> {
     let codex_home = TempDir::new()?;
@@ -226,3 +307,107 @@ async fn add_cant_add_command_and_url() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn add_probes_the_server_and_prints_its_tools() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let server_bin = codex_utils_cargo_bin::cargo_bin("test_stdio_server")?;
+
+    let mut add_cmd = codex_command(codex_home.path())?;
+    add_cmd
+        .args(["mcp", "add", "rmcp", "--"])
+        .arg(&server_bin)
+        .assert()
+        .success()
+        .stdout(contains("Added global MCP server 'rmcp'."))
+        .stdout(contains("Server 'rmcp' started with"))
+        .stdout(contains("  echo"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn add_keeps_a_server_that_fails_to_start_and_warns() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut add_cmd = codex_command(codex_home.path())?;
+    add_cmd
+        .args([
+            "mcp",
+            "add",
+            "typo",
+            "--",
+            "definitely-not-a-real-mcp-server",
+        ])
+        .assert()
+        .success()
+        .stderr(contains("Warning: server 'typo' failed to start"));
+
+    let servers = load_global_mcp_servers(codex_home.path()).await?;
+    assert!(servers.contains_key("typo"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn install_npm_package_runs_it_with_npx() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut install_cmd = codex_command(codex_home.path())?;
+    install_cmd
+        .args([
+            "mcp",
+            "install",
+            "npm:@modelcontextprotocol/server-github@1.2.0",
+            "--env",
+            "GITHUB_TOKEN=secret",
+            "--no-probe",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("Added global MCP server 'github'."));
+
+    let servers = load_global_mcp_servers(codex_home.path()).await?;
+    let github = servers.get("github").expect("server should exist");
+    match &github.transport {
+        McpServerTransportConfig::Stdio {
+            command, args, env, ..
+        } => {
+            assert_eq!(command, "npx");
+            assert_eq!(
+                args,
+                &vec![
+                    "-y".to_string(),
+                    "@modelcontextprotocol/server-github@1.2.0".to_string()
+                ]
+            );
+            assert_eq!(
+                env,
+                &Some(HashMap::from([(
+                    "GITHUB_TOKEN".to_string(),
+                    McpEnvValue::from("secret")
+                )]))
+            );
+        }
+        other => panic!("unexpected transport: {other:?}"),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn install_rejects_unknown_registries() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut install_cmd = codex_command(codex_home.path())?;
+    install_cmd
+        .args(["mcp", "install", "cargo:some-server"])
+        .assert()
+        .failure()
+        .stderr(contains("unsupported registry 'cargo'"));
+
+    let servers = load_global_mcp_servers(codex_home.path()).await?;
+    assert!(servers.is_empty());
+
+    Ok(())
+}
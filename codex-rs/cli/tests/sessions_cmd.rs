@@ -0,0 +1,209 @@
+use std::fs::FileTimes;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use anyhow::Result;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+/// Writes a rollout with one exchange, the way a session records it.
+fn write_session(
+    codex_home: &Path,
+    id: &str,
+    day: &str,
+    cwd: &Path,
+    model: &str,
+    prompt: &str,
+) -> Result<PathBuf> {
+    let dir = codex_home.join("sessions").join(day.replace('-', "/"));
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("rollout-{day}T10-00-00-{id}.jsonl"));
+    let timestamp = format!("{day}T10:00:00.000Z");
+    let lines = [
+        json!({
+            "timestamp": timestamp,
+            "type": "session_meta",
+            "payload": {
+                "id": id,
+                "timestamp": timestamp,
+                "cwd": cwd,
+                "originator": "codex_cli_rs",
+                "cli_version": "0.0.0",
+                "source": "cli",
+                "model_provider": "openai"
+            }
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "turn_context",
+            "payload": {
+                "cwd": cwd,
+                "approval_policy": "on-request",
+                "sandbox_policy": { "type": "read-only" },
+                "model": model,
+                "summary": "auto"
+            }
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "response_item",
+            "payload": {
+                "type": "message",
+                "role": "user",
+                "content": [{ "type": "input_text", "text": prompt }]
+            }
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "event_msg",
+            "payload": { "type": "user_message", "message": prompt }
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "event_msg",
+            "payload": { "type": "agent_message", "message": "Done." }
+        }),
+    ];
+    let contents: String = lines.iter().map(|line| format!("{line}\n")).collect();
+    std::fs::write(&path, contents)?;
+    Ok(path)
+}
+
+fn listed_ids(codex_home: &Path, args: &[&str]) -> Result<Vec<String>> {
+    let output = codex_command(codex_home)?
+        .args(["sessions", "list", "--json"])
+        .args(args)
+        .output()?;
+    assert!(output.status.success(), "{output:?}");
+    let sessions: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout)?;
+    Ok(sessions
+        .iter()
+        .filter_map(|session| session["id"].as_str().map(str::to_string))
+        .collect())
+}
+
+#[test]
+fn list_show_export_and_delete_sessions() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let app = TempDir::new()?;
+    let app_path = std::fs::canonicalize(app.path())?;
+    let other = TempDir::new()?;
+    let other_path = std::fs::canonicalize(other.path())?;
+    let first = "0199a213-81c0-7800-8aa1-bbab2a035a53";
+    let second = "0199a213-81c0-7800-8aa1-bbab2a035a54";
+    write_session(
+        codex_home.path(),
+        first,
+        "2025-01-02",
+        &app_path,
+        "gpt-5.1-codex",
+        "fix the build",
+    )?;
+    write_session(
+        codex_home.path(),
+        second,
+        "2025-02-03",
+        &other_path,
+        "gpt-5.1",
+        "write the docs",
+    )?;
+
+    assert_eq!(listed_ids(codex_home.path(), &[])?, vec![second, first]);
+    assert_eq!(
+        listed_ids(
+            codex_home.path(),
+            &["--project", &app_path.display().to_string()]
+        )?,
+        vec![first]
+    );
+    assert_eq!(
+        listed_ids(codex_home.path(), &["--model", "gpt-5.1"])?,
+        vec![second]
+    );
+    assert_eq!(
+        listed_ids(codex_home.path(), &["--since", "2025-02-01"])?,
+        vec![second]
+    );
+
+    codex_command(codex_home.path())?
+        .args(["sessions", "show", first])
+        .assert()
+        .success()
+        .stdout(contains("Model:    gpt-5.1-codex"))
+        .stdout(contains("User:\nfix the build\n"))
+        .stdout(contains("Codex:\nDone.\n"));
+
+    codex_command(codex_home.path())?
+        .args(["sessions", "export", first])
+        .assert()
+        .success()
+        .stdout(contains(format!("# Codex session {first}")))
+        .stdout(contains("## User\n\nfix the build\n\n## Codex\n\nDone.\n"));
+
+    codex_command(codex_home.path())?
+        .args(["sessions", "delete", first])
+        .assert()
+        .success()
+        .stdout(format!("Deleted session {first}.\n"));
+    assert_eq!(listed_ids(codex_home.path(), &[])?, vec![second]);
+    codex_command(codex_home.path())?
+        .args(["sessions", "show", first])
+        .assert()
+        .failure()
+        .stderr(contains("no session matches"));
+    Ok(())
+}
+
+#[test]
+fn prune_deletes_sessions_unused_for_longer_than_the_cutoff() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let cwd = std::fs::canonicalize(codex_home.path())?;
+    let stale = "0199a213-81c0-7800-8aa1-bbab2a035a55";
+    let recent = "0199a213-81c0-7800-8aa1-bbab2a035a56";
+    let stale_path = write_session(
+        codex_home.path(),
+        stale,
+        "2025-01-02",
+        &cwd,
+        "gpt-5.1",
+        "old work",
+    )?;
+    write_session(
+        codex_home.path(),
+        recent,
+        "2025-01-03",
+        &cwd,
+        "gpt-5.1",
+        "new work",
+    )?;
+    let last_used = SystemTime::now() - Duration::from_secs(40 * 24 * 60 * 60);
+    std::fs::File::options()
+        .write(true)
+        .open(&stale_path)?
+        .set_times(FileTimes::new().set_modified(last_used))?;
+
+    codex_command(codex_home.path())?
+        .args(["sessions", "prune", "--older-than", "30", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(contains("Would delete 1 session(s)."));
+    assert_eq!(listed_ids(codex_home.path(), &[])?, vec![recent, stale]);
+
+    codex_command(codex_home.path())?
+        .args(["sessions", "prune", "--older-than", "30"])
+        .assert()
+        .success()
+        .stdout(contains("Deleted 1 session(s)."));
+    assert_eq!(listed_ids(codex_home.path(), &[])?, vec![recent]);
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+#[test]
+fn add_list_and_remove_trusted_projects() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let project = TempDir::new()?;
+    let project_path = std::fs::canonicalize(project.path())?;
+    let project_display = project_path.display().to_string();
+
+    codex_command(codex_home.path())?
+        .args(["trust", "list"])
+        .assert()
+        .success()
+        .stdout(contains("No projects have a trust decision"));
+
+    codex_command(codex_home.path())?
+        .args(["trust", "add"])
+        .arg(&project_path)
+        .assert()
+        .success()
+        .stdout(format!("Trusted {project_display}.\n"));
+    codex_command(codex_home.path())?
+        .args(["trust", "list"])
+        .assert()
+        .success()
+        .stdout(format!("trusted    {project_display}\n"));
+
+    let output = codex_command(codex_home.path())?
+        .args(["trust", "list", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let listed: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(
+        listed,
+        serde_json::json!({ project_display.clone(): "trusted" })
+    );
+
+    codex_command(codex_home.path())?
+        .current_dir(&project_path)
+        .args(["trust", "remove"])
+        .assert()
+        .success()
+        .stdout(format!(
+            "Removed the trust decision for {project_display}.\n"
+        ));
+    codex_command(codex_home.path())?
+        .args(["trust", "remove"])
+        .arg(&project_path)
+        .assert()
+        .failure()
+        .stderr(contains("has no trust decision to remove"));
+    Ok(())
+}
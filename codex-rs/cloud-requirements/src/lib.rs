@@ -661,6 +661,8 @@ mod tests {
                 rules: None,
                 enforce_residency: None,
                 network: None,
+                blocked_tools: None,
+                requirements_url: None,
             })
         );
     }
@@ -703,6 +705,8 @@ mod tests {
                 rules: None,
                 enforce_residency: None,
                 network: None,
+                blocked_tools: None,
+                requirements_url: None,
             })
         );
     }
@@ -752,6 +756,8 @@ mod tests {
                 rules: None,
                 enforce_residency: None,
                 network: None,
+                blocked_tools: None,
+                requirements_url: None,
             })
         );
         assert_eq!(fetcher.request_count.load(Ordering::SeqCst), 2);
@@ -808,6 +814,8 @@ mod tests {
                 rules: None,
                 enforce_residency: None,
                 network: None,
+                blocked_tools: None,
+                requirements_url: None,
             })
         );
         assert_eq!(fetcher.request_count.load(Ordering::SeqCst), 0);
@@ -835,6 +843,8 @@ mod tests {
                 rules: None,
                 enforce_residency: None,
                 network: None,
+                blocked_tools: None,
+                requirements_url: None,
             })
         );
 
@@ -882,6 +892,8 @@ mod tests {
                 rules: None,
                 enforce_residency: None,
                 network: None,
+                blocked_tools: None,
+                requirements_url: None,
             })
         );
         assert_eq!(fetcher.request_count.load(Ordering::SeqCst), 1);
@@ -928,6 +940,8 @@ mod tests {
                 rules: None,
                 enforce_residency: None,
                 network: None,
+                blocked_tools: None,
+                requirements_url: None,
             })
         );
         assert_eq!(fetcher.request_count.load(Ordering::SeqCst), 1);
@@ -978,6 +992,8 @@ mod tests {
                 rules: None,
                 enforce_residency: None,
                 network: None,
+                blocked_tools: None,
+                requirements_url: None,
             })
         );
         assert_eq!(fetcher.request_count.load(Ordering::SeqCst), 1);
@@ -1029,6 +1045,8 @@ mod tests {
                 rules: None,
                 enforce_residency: None,
                 network: None,
+                blocked_tools: None,
+                requirements_url: None,
             })
         );
         assert_eq!(fetcher.request_count.load(Ordering::SeqCst), 1);
@@ -1076,6 +1094,8 @@ mod tests {
                 rules: None,
                 enforce_residency: None,
                 network: None,
+                blocked_tools: None,
+                requirements_url: None,
             })
         );
         let payload_bytes = cache_payload_bytes(&cache_file.signed_payload).expect("payload bytes");
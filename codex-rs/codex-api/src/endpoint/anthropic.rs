@@ -0,0 +1,86 @@
+use crate::auth::AuthProvider;
+use crate::common::ResponseStream;
+use crate::endpoint::session::EndpointSession;
+use crate::error::ApiError;
+use crate::provider::Provider;
+use crate::requests::anthropic::ANTHROPIC_VERSION;
+use crate::requests::anthropic::AnthropicMessagesRequest;
+use crate::requests::headers::insert_header;
+use crate::sse::anthropic::spawn_anthropic_stream;
+use crate::telemetry::SseTelemetry;
+use codex_client::HttpTransport;
+use codex_client::RequestTelemetry;
+use http::HeaderMap;
+use http::HeaderValue;
+use http::Method;
+use std::sync::Arc;
+
+/// Streams turns from the Anthropic Messages API (`/v1/messages`).
+pub struct AnthropicMessagesClient<T: HttpTransport, A: AuthProvider> {
+    session: EndpointSession<T, AnthropicAuth<A>>,
+    sse_telemetry: Option<Arc<dyn SseTelemetry>>,
+}
+
+/// Anthropic authenticates with `x-api-key` instead of a bearer token, so the wrapped provider's
+/// token is moved into that header when each request is built.
+struct AnthropicAuth<A>(A);
+
+impl<A: AuthProvider> AuthProvider for AnthropicAuth<A> {
+    fn bearer_token(&self) -> Option<String> {
+        None
+    }
+}
+
+impl<T: HttpTransport, A: AuthProvider> AnthropicMessagesClient<T, A> {
+    pub fn new(transport: T, provider: Provider, auth: A) -> Self {
+        Self {
+            session: EndpointSession::new(transport, provider, AnthropicAuth(auth)),
+            sse_telemetry: None,
+        }
+    }
+
+    pub fn with_telemetry(
+        self,
+        request: Option<Arc<dyn RequestTelemetry>>,
+        sse: Option<Arc<dyn SseTelemetry>>,
+    ) -> Self {
+        Self {
+            session: self.session.with_request_telemetry(request),
+            sse_telemetry: sse,
+        }
+    }
+
+    pub async fn stream_request(
+        &self,
+        request: AnthropicMessagesRequest,
+        extra_headers: HeaderMap,
+    ) -> Result<ResponseStream, ApiError> {
+        let body = serde_json::to_value(&request)
+            .map_err(|e| ApiError::Stream(format!("failed to encode messages request: {e}")))?;
+
+        let mut headers = extra_headers;
+        if let Some(api_key) = self.session.auth().0.bearer_token() {
+            insert_header(&mut headers, "x-api-key", &api_key);
+        }
+        if !headers.contains_key("anthropic-version") {
+            insert_header(&mut headers, "anthropic-version", ANTHROPIC_VERSION);
+        }
+
+        let stream_response = self
+            .session
+            .stream_with(Method::POST, "messages", headers, Some(body), |req| {
+                req.headers.insert(
+                    http::header::ACCEPT,
+                    HeaderValue::from_static("text/event-stream"),
+                );
+            })
+            .await?;
+
+        Ok(spawn_anthropic_stream(
+            stream_response,
+            self.session.provider().stream_idle_timeout,
+            self.sse_telemetry.clone(),
+            request.custom_tools,
+        ))
+    }
+}
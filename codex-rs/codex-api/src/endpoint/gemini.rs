@@ -0,0 +1,87 @@
+use crate::auth::AuthProvider;
+use crate::common::ResponseStream;
+use crate::endpoint::session::EndpointSession;
+use crate::error::ApiError;
+use crate::provider::Provider;
+use crate::requests::gemini::GeminiGenerateContentRequest;
+use crate::requests::headers::insert_header;
+use crate::sse::gemini::spawn_gemini_stream;
+use crate::telemetry::SseTelemetry;
+use codex_client::HttpTransport;
+use codex_client::RequestTelemetry;
+use http::HeaderMap;
+use http::HeaderValue;
+use http::Method;
+use std::sync::Arc;
+
+/// Streams turns from the Gemini API (`/models/{model}:streamGenerateContent`).
+pub struct GeminiClient<T: HttpTransport, A: AuthProvider> {
+    session: EndpointSession<T, GeminiAuth<A>>,
+    sse_telemetry: Option<Arc<dyn SseTelemetry>>,
+}
+
+/// Gemini authenticates with `x-goog-api-key` instead of a bearer token, so the wrapped
+/// provider's token is moved into that header when each request is built.
+struct GeminiAuth<A>(A);
+
+impl<A: AuthProvider> AuthProvider for GeminiAuth<A> {
+    fn bearer_token(&self) -> Option<String> {
+        None
+    }
+}
+
+impl<T: HttpTransport, A: AuthProvider> GeminiClient<T, A> {
+    pub fn new(transport: T, provider: Provider, auth: A) -> Self {
+        Self {
+            session: EndpointSession::new(transport, provider, GeminiAuth(auth)),
+            sse_telemetry: None,
+        }
+    }
+
+    pub fn with_telemetry(
+        self,
+        request: Option<Arc<dyn RequestTelemetry>>,
+        sse: Option<Arc<dyn SseTelemetry>>,
+    ) -> Self {
+        Self {
+            session: self.session.with_request_telemetry(request),
+            sse_telemetry: sse,
+        }
+    }
+
+    pub async fn stream_request(
+        &self,
+        request: GeminiGenerateContentRequest,
+        extra_headers: HeaderMap,
+    ) -> Result<ResponseStream, ApiError> {
+        let body = serde_json::to_value(&request).map_err(|e| {
+            ApiError::Stream(format!("failed to encode generateContent request: {e}"))
+        })?;
+
+        let mut headers = extra_headers;
+        if let Some(api_key) = self.session.auth().0.bearer_token() {
+            insert_header(&mut headers, "x-goog-api-key", &api_key);
+        }
+
+        let path = format!("models/{}:streamGenerateContent", request.model);
+        let stream_response = self
+            .session
+            .stream_with(Method::POST, &path, headers, Some(body), |req| {
+                // Without `alt=sse` the endpoint streams one JSON array instead of SSE events.
+                let separator = if req.url.contains('?') { '&' } else { '?' };
+                req.url = format!("{}{separator}alt=sse", req.url);
+                req.headers.insert(
+                    http::header::ACCEPT,
+                    HeaderValue::from_static("text/event-stream"),
+                );
+            })
+            .await?;
+
+        Ok(spawn_gemini_stream(
+            stream_response,
+            self.session.provider().stream_idle_timeout,
+            self.sse_telemetry.clone(),
+            request.custom_tools,
+        ))
+    }
+}
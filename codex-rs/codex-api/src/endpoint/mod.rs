@@ -1,5 +1,7 @@
 pub mod aggregate;
+pub mod anthropic;
 pub mod compact;
+pub mod gemini;
 pub mod memories;
 pub mod models;
 pub mod realtime_websocket;
@@ -42,6 +42,10 @@ impl<T: HttpTransport, A: AuthProvider> EndpointSession<T, A> {
         &self.provider
     }
 
+    pub(crate) fn auth(&self) -> &A {
+        &self.auth
+    }
+
     fn make_request(
         &self,
         method: &Method,
@@ -26,7 +26,9 @@ pub use crate::common::ResponseStream;
 pub use crate::common::ResponsesApiRequest;
 pub use crate::common::create_text_param_for_request;
 pub use crate::endpoint::aggregate::AggregateStreamExt;
+pub use crate::endpoint::anthropic::AnthropicMessagesClient;
 pub use crate::endpoint::compact::CompactClient;
+pub use crate::endpoint::gemini::GeminiClient;
 pub use crate::endpoint::memories::MemoriesClient;
 pub use crate::endpoint::models::ModelsClient;
 pub use crate::endpoint::realtime_websocket::RealtimeAudioFrame;
@@ -41,6 +43,8 @@ pub use crate::endpoint::responses_websocket::ResponsesWebsocketConnection;
 pub use crate::error::ApiError;
 pub use crate::provider::Provider;
 pub use crate::provider::is_azure_responses_wire_base_url;
+pub use crate::requests::anthropic::AnthropicMessagesRequest;
+pub use crate::requests::gemini::GeminiGenerateContentRequest;
 pub use crate::sse::stream_from_fixture;
 pub use crate::telemetry::SseTelemetry;
 pub use crate::telemetry::WebsocketTelemetry;
@@ -0,0 +1,364 @@
+//! Translation of Responses API requests into Anthropic Messages API requests.
+
+use crate::common::ResponsesApiRequest;
+use crate::requests::CUSTOM_TOOL_INPUT_ARG;
+use crate::requests::custom_tool_as_function;
+use crate::requests::custom_tool_names;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use serde::Serialize;
+use serde_json::Value;
+use serde_json::json;
+use std::collections::HashSet;
+use tracing::debug;
+
+/// Version header value required by the Anthropic Messages API.
+pub const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Upper bound for generated tokens when the request sets none; the Messages API requires an
+/// explicit value.
+const DEFAULT_MAX_TOKENS: u64 = 32_000;
+
+/// Marks the end of a prompt prefix that Anthropic should cache across requests.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum CacheControl {
+    Ephemeral,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct AnthropicMessagesRequest {
+    pub model: String,
+    pub max_tokens: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub system: Vec<Value>,
+    pub messages: Vec<AnthropicMessage>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<AnthropicTool>,
+    pub stream: bool,
+    /// Freeform custom tools declared as regular tools; calls to them are streamed back as
+    /// custom tool calls.
+    #[serde(skip)]
+    pub custom_tools: HashSet<String>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct AnthropicMessage {
+    pub role: AnthropicRole,
+    pub content: Vec<Value>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AnthropicRole {
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct AnthropicTool {
+    pub name: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    pub input_schema: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<CacheControl>,
+}
+
+impl From<&ResponsesApiRequest> for AnthropicMessagesRequest {
+    fn from(request: &ResponsesApiRequest) -> Self {
+        let mut messages: Vec<AnthropicMessage> = Vec::new();
+        for item in &request.input {
+            let Some((role, block)) = content_block_for_item(item) else {
+                continue;
+            };
+            // The Messages API expects alternating roles, and tool results must share the user
+            // message that directly follows the assistant's tool calls.
+            match messages.last_mut() {
+                Some(last) if last.role == role => last.content.extend(block),
+                _ => messages.push(AnthropicMessage {
+                    role,
+                    content: block,
+                }),
+            }
+        }
+        messages.retain(|message| !message.content.is_empty());
+
+        // Cache breakpoints go at the end of the tools, the system prompt, and the conversation so
+        // far. Anthropic caches prefixes in that order, so each turn reuses everything the previous
+        // turn sent, including the AGENTS.md instructions at the start of the history.
+        let mut tools: Vec<AnthropicTool> =
+            request.tools.iter().filter_map(anthropic_tool).collect();
+        if let Some(last) = tools.last_mut() {
+            last.cache_control = Some(CacheControl::Ephemeral);
+        }
+        let system = if request.instructions.is_empty() {
+            Vec::new()
+        } else {
+            vec![with_cache_control(
+                json!({"type": "text", "text": request.instructions}),
+            )]
+        };
+        if let Some(block) = messages
+            .last_mut()
+            .and_then(|message| message.content.last_mut())
+        {
+            *block = with_cache_control(block.take());
+        }
+
+        Self {
+            model: request.model.clone(),
+            max_tokens: request.max_output_tokens.unwrap_or(DEFAULT_MAX_TOKENS),
+            system,
+            messages,
+            tools,
+            stream: true,
+            custom_tools: custom_tool_names(&request.tools),
+        }
+    }
+}
+
+fn with_cache_control(mut block: Value) -> Value {
+    if let Some(object) = block.as_object_mut() {
+        object.insert("cache_control".to_string(), json!(CacheControl::Ephemeral));
+    }
+    block
+}
+
+/// Converts a Responses API function or custom tool into an Anthropic tool definition.
+///
+/// Hosted tools (web search, local shell) have no Messages API equivalent and are dropped.
+fn anthropic_tool(tool: &Value) -> Option<AnthropicTool> {
+    let name = || Some(tool.get("name")?.as_str()?.to_string());
+    match tool.get("type").and_then(Value::as_str) {
+        Some("function") => {}
+        Some("custom") => {
+            let (description, input_schema) = custom_tool_as_function(tool);
+            return Some(AnthropicTool {
+                name: name()?,
+                description,
+                input_schema,
+                cache_control: None,
+            });
+        }
+        _ => {
+            debug!("skipping tool unsupported by the Anthropic Messages API: {tool}");
+            return None;
+        }
+    }
+    Some(AnthropicTool {
+        name: name()?,
+        description: tool
+            .get("description")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        input_schema: tool
+            .get("parameters")
+            .cloned()
+            .unwrap_or_else(|| json!({"type": "object", "properties": {}})),
+        cache_control: None,
+    })
+}
+
+fn content_block_for_item(item: &ResponseItem) -> Option<(AnthropicRole, Vec<Value>)> {
+    match item {
+        ResponseItem::Message { role, content, .. } => {
+            let role = if role == "assistant" {
+                AnthropicRole::Assistant
+            } else {
+                AnthropicRole::User
+            };
+            let blocks = content.iter().filter_map(content_item_block).collect();
+            Some((role, blocks))
+        }
+        ResponseItem::FunctionCall {
+            name,
+            arguments,
+            call_id,
+            ..
+        } => {
+            let input = serde_json::from_str::<Value>(arguments).unwrap_or_else(|_| json!({}));
+            Some((
+                AnthropicRole::Assistant,
+                vec![json!({"type": "tool_use", "id": call_id, "name": name, "input": input})],
+            ))
+        }
+        ResponseItem::FunctionCallOutput { call_id, output } => Some((
+            AnthropicRole::User,
+            vec![tool_result_block(
+                call_id,
+                output.body.to_text().unwrap_or_default(),
+                output.success == Some(false),
+            )],
+        )),
+        ResponseItem::CustomToolCall {
+            call_id,
+            name,
+            input,
+            ..
+        } => Some((
+            AnthropicRole::Assistant,
+            vec![
+                json!({"type": "tool_use", "id": call_id, "name": name, "input": {CUSTOM_TOOL_INPUT_ARG: input}}),
+            ],
+        )),
+        ResponseItem::CustomToolCallOutput { call_id, output } => Some((
+            AnthropicRole::User,
+            vec![tool_result_block(call_id, output.clone(), false)],
+        )),
+        ResponseItem::Reasoning { .. }
+        | ResponseItem::LocalShellCall { .. }
+        | ResponseItem::WebSearchCall { .. }
+        | ResponseItem::GhostSnapshot { .. }
+        | ResponseItem::Compaction { .. }
+        | ResponseItem::Other => None,
+    }
+}
+
+fn content_item_block(item: &ContentItem) -> Option<Value> {
+    match item {
+        ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+            (!text.is_empty()).then(|| json!({"type": "text", "text": text}))
+        }
+        ContentItem::InputImage { image_url } => Some(image_block(image_url)),
+    }
+}
+
+/// Images are sent inline when they arrive as base64 data URLs and by reference otherwise.
+fn image_block(image_url: &str) -> Value {
+    if let Some(rest) = image_url.strip_prefix("data:")
+        && let Some((media_type, data)) = rest.split_once(";base64,")
+    {
+        return json!({
+            "type": "image",
+            "source": {"type": "base64", "media_type": media_type, "data": data},
+        });
+    }
+    json!({"type": "image", "source": {"type": "url", "url": image_url}})
+}
+
+fn tool_result_block(call_id: &str, content: String, is_error: bool) -> Value {
+    json!({
+        "type": "tool_result",
+        "tool_use_id": call_id,
+        "content": content,
+        "is_error": is_error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn user_message(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        }
+    }
+
+    #[test]
+    fn converts_history_tools_and_instructions() {
+        let request = ResponsesApiRequest {
+            model: "claude-sonnet-4-5".to_string(),
+            instructions: "be brief".to_string(),
+            input: vec![
+                user_message("list files"),
+                ResponseItem::FunctionCall {
+                    id: None,
+                    name: "shell".to_string(),
+                    arguments: r#"{"command":["ls"]}"#.to_string(),
+                    call_id: "toolu_1".to_string(),
+                },
+                ResponseItem::FunctionCallOutput {
+                    call_id: "toolu_1".to_string(),
+                    output: FunctionCallOutputPayload::from_text("README.md".to_string()),
+                },
+                user_message("thanks"),
+            ],
+            tools: vec![
+                json!({
+                    "type": "function",
+                    "name": "shell",
+                    "description": "Runs a command",
+                    "strict": false,
+                    "parameters": {"type": "object", "properties": {}},
+                }),
+                json!({"type": "web_search"}),
+            ],
+            tool_choice: "auto".to_string(),
+            parallel_tool_calls: true,
+            reasoning: None,
+            store: false,
+            stream: true,
+            include: Vec::new(),
+            prompt_cache_key: None,
+            max_output_tokens: None,
+            text: None,
+        };
+
+        let actual = serde_json::to_value(AnthropicMessagesRequest::from(&request))
+            .expect("serialize request");
+
+        assert_eq!(
+            actual,
+            json!({
+                "model": "claude-sonnet-4-5",
+                "max_tokens": DEFAULT_MAX_TOKENS,
+                "system": [{
+                    "type": "text",
+                    "text": "be brief",
+                    "cache_control": {"type": "ephemeral"},
+                }],
+                "messages": [
+                    {"role": "user", "content": [{"type": "text", "text": "list files"}]},
+                    {"role": "assistant", "content": [{
+                        "type": "tool_use",
+                        "id": "toolu_1",
+                        "name": "shell",
+                        "input": {"command": ["ls"]},
+                    }]},
+                    {"role": "user", "content": [
+                        {
+                            "type": "tool_result",
+                            "tool_use_id": "toolu_1",
+                            "content": "README.md",
+                            "is_error": false,
+                        },
+                        {
+                            "type": "text",
+                            "text": "thanks",
+                            "cache_control": {"type": "ephemeral"},
+                        },
+                    ]},
+                ],
+                "tools": [{
+                    "name": "shell",
+                    "description": "Runs a command",
+                    "input_schema": {"type": "object", "properties": {}},
+                    "cache_control": {"type": "ephemeral"},
+                }],
+                "stream": true,
+            })
+        );
+    }
+
+    #[test]
+    fn data_url_images_are_sent_inline() {
+        assert_eq!(
+            image_block("data:image/png;base64,AAAA"),
+            json!({
+                "type": "image",
+                "source": {"type": "base64", "media_type": "image/png", "data": "AAAA"},
+            })
+        );
+    }
+}
@@ -0,0 +1,430 @@
+//! Translation of Responses API requests into Gemini `generateContent` requests.
+
+use crate::common::ResponsesApiRequest;
+use crate::requests::CUSTOM_TOOL_INPUT_ARG;
+use crate::requests::custom_tool_as_function;
+use crate::requests::custom_tool_names;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use serde::Serialize;
+use serde_json::Value;
+use serde_json::json;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use tracing::debug;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiGenerateContentRequest {
+    /// Gemini selects the model through the request path rather than the body.
+    #[serde(skip)]
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_instruction: Option<GeminiContent>,
+    pub contents: Vec<GeminiContent>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<GeminiTools>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generation_config: Option<GeminiGenerationConfig>,
+    /// Freeform custom tools declared as functions; calls to them are streamed back as custom
+    /// tool calls.
+    #[serde(skip)]
+    pub custom_tools: HashSet<String>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiGenerationConfig {
+    pub max_output_tokens: u64,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct GeminiContent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<GeminiRole>,
+    pub parts: Vec<Value>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GeminiRole {
+    User,
+    Model,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiTools {
+    pub function_declarations: Vec<GeminiFunctionDeclaration>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiFunctionDeclaration {
+    pub name: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// Sent as full JSON Schema; the OpenAPI-subset `parameters` field rejects some of the
+    /// schemas Codex tools use.
+    pub parameters_json_schema: Value,
+}
+
+impl From<&ResponsesApiRequest> for GeminiGenerateContentRequest {
+    fn from(request: &ResponsesApiRequest) -> Self {
+        // Function responses are matched to their call by name, so remember which tool each
+        // call id refers to.
+        let mut call_names: HashMap<&str, &str> = HashMap::new();
+        let mut contents: Vec<GeminiContent> = Vec::new();
+        for item in &request.input {
+            let Some((role, parts)) = parts_for_item(item, &mut call_names) else {
+                continue;
+            };
+            // Consecutive items from the same side become one turn so function responses sit
+            // directly after the model turn that made the calls.
+            match contents.last_mut() {
+                Some(last) if last.role == Some(role) => last.parts.extend(parts),
+                _ => contents.push(GeminiContent {
+                    role: Some(role),
+                    parts,
+                }),
+            }
+        }
+        contents.retain(|content| !content.parts.is_empty());
+
+        let function_declarations: Vec<GeminiFunctionDeclaration> = request
+            .tools
+            .iter()
+            .filter_map(function_declaration)
+            .collect();
+
+        Self {
+            model: request.model.clone(),
+            system_instruction: (!request.instructions.is_empty()).then(|| GeminiContent {
+                role: None,
+                parts: vec![json!({"text": request.instructions})],
+            }),
+            contents,
+            tools: if function_declarations.is_empty() {
+                Vec::new()
+            } else {
+                vec![GeminiTools {
+                    function_declarations,
+                }]
+            },
+            generation_config: request
+                .max_output_tokens
+                .map(|max_output_tokens| GeminiGenerationConfig { max_output_tokens }),
+            custom_tools: custom_tool_names(&request.tools),
+        }
+    }
+}
+
+/// Converts a Responses API function or custom tool into a Gemini function declaration.
+///
+/// Hosted tools (web search, local shell) have no `generateContent` equivalent and are dropped.
+fn function_declaration(tool: &Value) -> Option<GeminiFunctionDeclaration> {
+    let name = || Some(tool.get("name")?.as_str()?.to_string());
+    match tool.get("type").and_then(Value::as_str) {
+        Some("function") => {}
+        Some("custom") => {
+            let (description, parameters_json_schema) = custom_tool_as_function(tool);
+            return Some(GeminiFunctionDeclaration {
+                name: name()?,
+                description,
+                parameters_json_schema,
+            });
+        }
+        _ => {
+            debug!("skipping tool unsupported by the Gemini API: {tool}");
+            return None;
+        }
+    }
+    Some(GeminiFunctionDeclaration {
+        name: name()?,
+        description: tool
+            .get("description")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        parameters_json_schema: tool
+            .get("parameters")
+            .cloned()
+            .unwrap_or_else(|| json!({"type": "object", "properties": {}})),
+    })
+}
+
+fn parts_for_item<'a>(
+    item: &'a ResponseItem,
+    call_names: &mut HashMap<&'a str, &'a str>,
+) -> Option<(GeminiRole, Vec<Value>)> {
+    match item {
+        ResponseItem::Message { role, content, .. } => {
+            let role = if role == "assistant" {
+                GeminiRole::Model
+            } else {
+                GeminiRole::User
+            };
+            Some((role, content.iter().filter_map(content_item_part).collect()))
+        }
+        ResponseItem::FunctionCall {
+            name,
+            arguments,
+            call_id,
+            ..
+        } => {
+            call_names.insert(call_id, name);
+            let args = serde_json::from_str::<Value>(arguments).unwrap_or_else(|_| json!({}));
+            Some((
+                GeminiRole::Model,
+                vec![json!({"functionCall": {"id": call_id, "name": name, "args": args}})],
+            ))
+        }
+        ResponseItem::FunctionCallOutput { call_id, output } => Some((
+            GeminiRole::User,
+            vec![function_response_part(
+                call_id,
+                call_names.get(call_id.as_str()).copied(),
+                output.body.to_text().unwrap_or_default(),
+                output.success == Some(false),
+            )],
+        )),
+        ResponseItem::CustomToolCall {
+            call_id,
+            name,
+            input,
+            ..
+        } => {
+            call_names.insert(call_id, name);
+            Some((
+                GeminiRole::Model,
+                vec![
+                    json!({"functionCall": {"id": call_id, "name": name, "args": {CUSTOM_TOOL_INPUT_ARG: input}}}),
+                ],
+            ))
+        }
+        ResponseItem::CustomToolCallOutput { call_id, output } => Some((
+            GeminiRole::User,
+            vec![function_response_part(
+                call_id,
+                call_names.get(call_id.as_str()).copied(),
+                output.clone(),
+                false,
+            )],
+        )),
+        ResponseItem::Reasoning { .. }
+        | ResponseItem::LocalShellCall { .. }
+        | ResponseItem::WebSearchCall { .. }
+        | ResponseItem::GhostSnapshot { .. }
+        | ResponseItem::Compaction { .. }
+        | ResponseItem::Other => None,
+    }
+}
+
+fn content_item_part(item: &ContentItem) -> Option<Value> {
+    match item {
+        ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+            (!text.is_empty()).then(|| json!({"text": text}))
+        }
+        ContentItem::InputImage { image_url } => Some(image_part(image_url)),
+    }
+}
+
+/// Images are sent inline when they arrive as base64 data URLs and by reference otherwise.
+fn image_part(image_url: &str) -> Value {
+    if let Some(rest) = image_url.strip_prefix("data:")
+        && let Some((mime_type, data)) = rest.split_once(";base64,")
+    {
+        return json!({"inlineData": {"mimeType": mime_type, "data": data}});
+    }
+    json!({"fileData": {"fileUri": image_url}})
+}
+
+fn function_response_part(
+    call_id: &str,
+    name: Option<&str>,
+    output: String,
+    is_error: bool,
+) -> Value {
+    let response = if is_error {
+        json!({"error": output})
+    } else {
+        json!({"output": output})
+    };
+    json!({
+        "functionResponse": {
+            "id": call_id,
+            "name": name.unwrap_or(call_id),
+            "response": response,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn user_message(content: Vec<ContentItem>) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content,
+            end_turn: None,
+            phase: None,
+        }
+    }
+
+    #[test]
+    fn converts_history_tools_images_and_instructions() {
+        let request = ResponsesApiRequest {
+            model: "gemini-2.5-pro".to_string(),
+            instructions: "be brief".to_string(),
+            input: vec![
+                user_message(vec![
+                    ContentItem::InputText {
+                        text: "what is in this image?".to_string(),
+                    },
+                    ContentItem::InputImage {
+                        image_url: "data:image/png;base64,AAAA".to_string(),
+                    },
+                ]),
+                ResponseItem::FunctionCall {
+                    id: None,
+                    name: "shell".to_string(),
+                    arguments: r#"{"command":["ls"]}"#.to_string(),
+                    call_id: "call-1".to_string(),
+                },
+                ResponseItem::FunctionCallOutput {
+                    call_id: "call-1".to_string(),
+                    output: FunctionCallOutputPayload::from_text("README.md".to_string()),
+                },
+                user_message(vec![ContentItem::InputText {
+                    text: "thanks".to_string(),
+                }]),
+            ],
+            tools: vec![
+                json!({
+                    "type": "function",
+                    "name": "shell",
+                    "description": "Runs a command",
+                    "strict": false,
+                    "parameters": {"type": "object", "properties": {}},
+                }),
+                json!({"type": "web_search"}),
+            ],
+            tool_choice: "auto".to_string(),
+            parallel_tool_calls: true,
+            reasoning: None,
+            store: false,
+            stream: true,
+            include: Vec::new(),
+            prompt_cache_key: None,
+            max_output_tokens: Some(1024),
+            text: None,
+        };
+
+        let actual = serde_json::to_value(GeminiGenerateContentRequest::from(&request))
+            .expect("serialize request");
+
+        assert_eq!(
+            actual,
+            json!({
+                "systemInstruction": {"parts": [{"text": "be brief"}]},
+                "contents": [
+                    {"role": "user", "parts": [
+                        {"text": "what is in this image?"},
+                        {"inlineData": {"mimeType": "image/png", "data": "AAAA"}},
+                    ]},
+                    {"role": "model", "parts": [{"functionCall": {
+                        "id": "call-1",
+                        "name": "shell",
+                        "args": {"command": ["ls"]},
+                    }}]},
+                    {"role": "user", "parts": [
+                        {"functionResponse": {
+                            "id": "call-1",
+                            "name": "shell",
+                            "response": {"output": "README.md"},
+                        }},
+                        {"text": "thanks"},
+                    ]},
+                ],
+                "tools": [{"functionDeclarations": [{
+                    "name": "shell",
+                    "description": "Runs a command",
+                    "parametersJsonSchema": {"type": "object", "properties": {}},
+                }]}],
+                "generationConfig": {"maxOutputTokens": 1024},
+            })
+        );
+    }
+
+    #[test]
+    fn custom_tools_are_declared_as_single_string_functions() {
+        let request = ResponsesApiRequest {
+            model: "gemini-2.5-pro".to_string(),
+            instructions: String::new(),
+            input: vec![
+                ResponseItem::CustomToolCall {
+                    id: None,
+                    status: None,
+                    call_id: "call-1".to_string(),
+                    name: "apply_patch".to_string(),
+                    input: "*** Begin Patch".to_string(),
+                },
+                ResponseItem::CustomToolCallOutput {
+                    call_id: "call-1".to_string(),
+                    output: "Done!".to_string(),
+                },
+            ],
+            tools: vec![json!({
+                "type": "custom",
+                "name": "apply_patch",
+                "description": "Edits files",
+                "format": {"type": "grammar", "syntax": "lark", "definition": "start: patch"},
+            })],
+            tool_choice: "auto".to_string(),
+            parallel_tool_calls: true,
+            reasoning: None,
+            store: false,
+            stream: true,
+            include: Vec::new(),
+            prompt_cache_key: None,
+            max_output_tokens: None,
+            text: None,
+        };
+
+        let translated = GeminiGenerateContentRequest::from(&request);
+        assert_eq!(
+            translated.custom_tools,
+            HashSet::from(["apply_patch".to_string()])
+        );
+        assert_eq!(
+            serde_json::to_value(&translated).expect("serialize request"),
+            json!({
+                "contents": [
+                    {"role": "model", "parts": [{"functionCall": {
+                        "id": "call-1",
+                        "name": "apply_patch",
+                        "args": {"input": "*** Begin Patch"},
+                    }}]},
+                    {"role": "user", "parts": [{"functionResponse": {
+                        "id": "call-1",
+                        "name": "apply_patch",
+                        "response": {"output": "Done!"},
+                    }}]},
+                ],
+                "tools": [{"functionDeclarations": [{
+                    "name": "apply_patch",
+                    "description": "Edits files\n\nThe `input` argument must follow this lark grammar:\nstart: patch",
+                    "parametersJsonSchema": {
+                        "type": "object",
+                        "properties": {"input": {"type": "string"}},
+                        "required": ["input"],
+                    },
+                }]}],
+            })
+        );
+    }
+}
@@ -1,2 +1,50 @@
+pub mod anthropic;
+pub mod gemini;
 pub(crate) mod headers;
 pub mod responses;
+
+use serde_json::Value;
+use serde_json::json;
+use std::collections::HashSet;
+
+/// Function-call argument that carries a freeform custom tool's raw input on APIs that only
+/// support JSON function tools.
+pub(crate) const CUSTOM_TOOL_INPUT_ARG: &str = "input";
+
+/// Names of the freeform custom tools in a Responses API tool list.
+pub(crate) fn custom_tool_names(tools: &[Value]) -> HashSet<String> {
+    tools
+        .iter()
+        .filter(|tool| tool.get("type").and_then(Value::as_str) == Some("custom"))
+        .filter_map(|tool| tool.get("name").and_then(Value::as_str))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Describes a freeform custom tool as a function taking its raw input as a single string,
+/// returning the description and parameter schema to declare it with.
+pub(crate) fn custom_tool_as_function(tool: &Value) -> (String, Value) {
+    let mut description = tool
+        .get("description")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    // The grammar is enforced by the Responses API; elsewhere the model only sees it as text.
+    if let Some(format) = tool.get("format")
+        && let Some(definition) = format.get("definition").and_then(Value::as_str)
+    {
+        let syntax = format
+            .get("syntax")
+            .and_then(Value::as_str)
+            .unwrap_or("grammar");
+        description.push_str(&format!(
+            "\n\nThe `{CUSTOM_TOOL_INPUT_ARG}` argument must follow this {syntax} grammar:\n{definition}"
+        ));
+    }
+    let parameters = json!({
+        "type": "object",
+        "properties": {CUSTOM_TOOL_INPUT_ARG: {"type": "string"}},
+        "required": [CUSTOM_TOOL_INPUT_ARG],
+    });
+    (description, parameters)
+}
@@ -0,0 +1,495 @@
+//! Translation of Anthropic Messages API stream events into [`ResponseEvent`]s.
+
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::sse::function_call_item;
+use crate::telemetry::SseTelemetry;
+use codex_client::ByteStream;
+use codex_client::StreamResponse;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+pub fn spawn_anthropic_stream(
+    stream_response: StreamResponse,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    custom_tools: HashSet<String>,
+) -> ResponseStream {
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(process_anthropic_sse(
+        stream_response.bytes,
+        tx_event,
+        idle_timeout,
+        telemetry,
+        custom_tools,
+    ));
+    ResponseStream { rx_event }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AnthropicStreamEvent {
+    MessageStart {
+        message: MessageStart,
+    },
+    ContentBlockStart {
+        index: usize,
+        content_block: ContentBlock,
+    },
+    ContentBlockDelta {
+        index: usize,
+        delta: ContentBlockDelta,
+    },
+    ContentBlockStop {
+        index: usize,
+    },
+    MessageDelta {
+        #[serde(default)]
+        usage: Option<Usage>,
+    },
+    MessageStop,
+    Error {
+        error: AnthropicError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageStart {
+    id: String,
+    #[serde(default)]
+    usage: Option<Usage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ContentBlock {
+    Text,
+    ToolUse {
+        id: String,
+        name: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ContentBlockDelta {
+    TextDelta {
+        text: String,
+    },
+    InputJsonDelta {
+        partial_json: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Usage {
+    #[serde(default)]
+    input_tokens: Option<i64>,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<i64>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<i64>,
+    #[serde(default)]
+    output_tokens: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicError {
+    r#type: String,
+    #[serde(default)]
+    message: String,
+}
+
+impl From<AnthropicError> for ApiError {
+    fn from(error: AnthropicError) -> Self {
+        match error.r#type.as_str() {
+            "overloaded_error" => ApiError::ServerOverloaded,
+            "invalid_request_error" if error.message.contains("prompt is too long") => {
+                ApiError::ContextWindowExceeded
+            }
+            "invalid_request_error" => ApiError::InvalidRequest {
+                message: error.message,
+            },
+            _ => ApiError::Retryable {
+                message: error.message,
+                delay: None,
+            },
+        }
+    }
+}
+
+/// A content block that is still streaming.
+enum OpenBlock {
+    Text {
+        item_id: String,
+        text: String,
+    },
+    ToolUse {
+        call_id: String,
+        name: String,
+        arguments: String,
+    },
+}
+
+/// Accumulates streamed content blocks until each one is complete.
+#[derive(Default)]
+struct AnthropicStreamState {
+    message_id: String,
+    usage: TokenUsage,
+    blocks: HashMap<usize, OpenBlock>,
+    custom_tools: HashSet<String>,
+}
+
+impl AnthropicStreamState {
+    fn handle(&mut self, event: AnthropicStreamEvent) -> Result<Vec<ResponseEvent>, ApiError> {
+        let events = match event {
+            AnthropicStreamEvent::MessageStart { message } => {
+                self.message_id = message.id;
+                self.record_usage(message.usage.unwrap_or_default());
+                vec![ResponseEvent::Created]
+            }
+            AnthropicStreamEvent::ContentBlockStart {
+                index,
+                content_block,
+            } => match content_block {
+                ContentBlock::Text => {
+                    let item_id = format!("{}-{index}", self.message_id);
+                    let added = assistant_message(item_id.clone(), String::new());
+                    self.blocks.insert(
+                        index,
+                        OpenBlock::Text {
+                            item_id,
+                            text: String::new(),
+                        },
+                    );
+                    vec![ResponseEvent::OutputItemAdded(added)]
+                }
+                ContentBlock::ToolUse { id, name } => {
+                    self.blocks.insert(
+                        index,
+                        OpenBlock::ToolUse {
+                            call_id: id,
+                            name,
+                            arguments: String::new(),
+                        },
+                    );
+                    Vec::new()
+                }
+                ContentBlock::Other => Vec::new(),
+            },
+            AnthropicStreamEvent::ContentBlockDelta { index, delta } => {
+                match (self.blocks.get_mut(&index), delta) {
+                    (
+                        Some(OpenBlock::Text { text, .. }),
+                        ContentBlockDelta::TextDelta { text: delta },
+                    ) => {
+                        text.push_str(&delta);
+                        vec![ResponseEvent::OutputTextDelta(delta)]
+                    }
+                    (
+                        Some(OpenBlock::ToolUse { arguments, .. }),
+                        ContentBlockDelta::InputJsonDelta { partial_json },
+                    ) => {
+                        arguments.push_str(&partial_json);
+                        Vec::new()
+                    }
+                    _ => Vec::new(),
+                }
+            }
+            AnthropicStreamEvent::ContentBlockStop { index } => match self.blocks.remove(&index) {
+                Some(OpenBlock::Text { item_id, text }) => {
+                    vec![ResponseEvent::OutputItemDone(assistant_message(
+                        item_id, text,
+                    ))]
+                }
+                Some(OpenBlock::ToolUse {
+                    call_id,
+                    name,
+                    arguments,
+                }) => {
+                    // Tools without parameters stream no input at all.
+                    let arguments = if arguments.is_empty() {
+                        "{}".to_string()
+                    } else {
+                        arguments
+                    };
+                    vec![ResponseEvent::OutputItemDone(function_call_item(
+                        call_id,
+                        name,
+                        arguments,
+                        &self.custom_tools,
+                    ))]
+                }
+                None => Vec::new(),
+            },
+            AnthropicStreamEvent::MessageDelta { usage } => {
+                self.record_usage(usage.unwrap_or_default());
+                Vec::new()
+            }
+            AnthropicStreamEvent::MessageStop => vec![ResponseEvent::Completed {
+                response_id: std::mem::take(&mut self.message_id),
+                token_usage: Some(self.usage.clone()),
+                can_append: false,
+            }],
+            AnthropicStreamEvent::Error { error } => return Err(error.into()),
+            AnthropicStreamEvent::Other => Vec::new(),
+        };
+        Ok(events)
+    }
+
+    /// Anthropic reports input usage once at message start and cumulative output usage in
+    /// `message_delta`; cached prompt reads are reported separately from `input_tokens`.
+    fn record_usage(&mut self, usage: Usage) {
+        if let Some(input_tokens) = usage.input_tokens {
+            let cache_read = usage.cache_read_input_tokens.unwrap_or(0);
+            self.usage.input_tokens =
+                input_tokens + cache_read + usage.cache_creation_input_tokens.unwrap_or(0);
+            self.usage.cached_input_tokens = cache_read;
+        }
+        if let Some(output_tokens) = usage.output_tokens {
+            self.usage.output_tokens = output_tokens;
+        }
+        self.usage.total_tokens = self.usage.input_tokens + self.usage.output_tokens;
+    }
+}
+
+fn assistant_message(id: String, text: String) -> ResponseItem {
+    ResponseItem::Message {
+        id: Some(id),
+        role: "assistant".to_string(),
+        content: if text.is_empty() {
+            Vec::new()
+        } else {
+            vec![ContentItem::OutputText { text }]
+        },
+        end_turn: None,
+        phase: None,
+    }
+}
+
+pub async fn process_anthropic_sse(
+    stream: ByteStream,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    custom_tools: HashSet<String>,
+) {
+    let mut stream = stream.eventsource();
+    let mut state = AnthropicStreamState {
+        custom_tools,
+        ..Default::default()
+    };
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                debug!("SSE Error: {e:#}");
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream(
+                        "stream closed before message_stop".into(),
+                    )))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", &sse.data);
+
+        let event: AnthropicStreamEvent = match serde_json::from_str(&sse.data) {
+            Ok(event) => event,
+            Err(e) => {
+                debug!("Failed to parse SSE event: {e}, data: {}", &sse.data);
+                continue;
+            }
+        };
+
+        match state.handle(event) {
+            Ok(events) => {
+                for event in events {
+                    let is_completed = matches!(event, ResponseEvent::Completed { .. });
+                    if tx_event.send(Ok(event)).await.is_err() || is_completed {
+                        return;
+                    }
+                }
+            }
+            Err(error) => {
+                let _ = tx_event.send(Err(error)).await;
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use codex_client::TransportError;
+    use futures::TryStreamExt;
+    use pretty_assertions::assert_eq;
+    use serde_json::Value;
+    use serde_json::json;
+    use tokio_test::io::Builder as IoBuilder;
+    use tokio_util::io::ReaderStream;
+
+    async fn run_sse(events: Vec<Value>) -> Vec<Result<ResponseEvent, ApiError>> {
+        let mut body = String::new();
+        for event in events {
+            let kind = event["type"].as_str().expect("event type").to_string();
+            body.push_str(&format!("event: {kind}\ndata: {event}\n\n"));
+        }
+        let reader = IoBuilder::new().read(body.as_bytes()).build();
+        let stream =
+            ReaderStream::new(reader).map_err(|err| TransportError::Network(err.to_string()));
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(16);
+        tokio::spawn(process_anthropic_sse(
+            Box::pin(stream),
+            tx,
+            Duration::from_secs(1),
+            None,
+            HashSet::from(["apply_patch".to_string()]),
+        ));
+
+        let mut events = Vec::new();
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+        events
+    }
+
+    #[tokio::test]
+    async fn streams_text_and_tool_use_blocks() {
+        let events = run_sse(vec![
+            json!({"type": "message_start", "message": {
+                "id": "msg_1",
+                "usage": {"input_tokens": 10, "cache_read_input_tokens": 5, "output_tokens": 1},
+            }}),
+            json!({"type": "content_block_start", "index": 0, "content_block": {"type": "text", "text": ""}}),
+            json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "Hi"}}),
+            json!({"type": "content_block_stop", "index": 0}),
+            json!({"type": "content_block_start", "index": 1, "content_block": {
+                "type": "tool_use", "id": "toolu_1", "name": "shell", "input": {},
+            }}),
+            json!({"type": "content_block_delta", "index": 1, "delta": {
+                "type": "input_json_delta", "partial_json": "{\"command\":",
+            }}),
+            json!({"type": "content_block_delta", "index": 1, "delta": {
+                "type": "input_json_delta", "partial_json": "[\"ls\"]}",
+            }}),
+            json!({"type": "content_block_stop", "index": 1}),
+            json!({"type": "ping"}),
+            json!({"type": "message_delta", "delta": {"stop_reason": "tool_use"}, "usage": {"output_tokens": 7}}),
+            json!({"type": "message_stop"}),
+        ])
+        .await;
+
+        let events: Vec<ResponseEvent> = events
+            .into_iter()
+            .map(|event| event.expect("stream event"))
+            .collect();
+        assert_eq!(events.len(), 6, "unexpected events: {events:?}");
+        assert_matches!(events[0], ResponseEvent::Created);
+        assert_matches!(&events[1], ResponseEvent::OutputItemAdded(ResponseItem::Message { id: Some(id), .. }) if id == "msg_1-0");
+        assert_matches!(&events[2], ResponseEvent::OutputTextDelta(delta) if delta == "Hi");
+        assert_matches!(
+            &events[3],
+            ResponseEvent::OutputItemDone(ResponseItem::Message { content, .. })
+                if content == &vec![ContentItem::OutputText { text: "Hi".to_string() }]
+        );
+        assert_matches!(
+            &events[4],
+            ResponseEvent::OutputItemDone(ResponseItem::FunctionCall { name, arguments, call_id, .. })
+                if name == "shell" && arguments == "{\"command\":[\"ls\"]}" && call_id == "toolu_1"
+        );
+        let ResponseEvent::Completed {
+            response_id,
+            token_usage,
+            ..
+        } = &events[5]
+        else {
+            panic!("expected completion, got {:?}", events[5]);
+        };
+        assert_eq!(response_id, "msg_1");
+        assert_eq!(
+            token_usage,
+            &Some(TokenUsage {
+                input_tokens: 15,
+                cached_input_tokens: 5,
+                output_tokens: 7,
+                reasoning_output_tokens: 0,
+                total_tokens: 22,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn calls_to_custom_tools_become_custom_tool_calls() {
+        let events = run_sse(vec![
+            json!({"type": "message_start", "message": {"id": "msg_1"}}),
+            json!({"type": "content_block_start", "index": 0, "content_block": {
+                "type": "tool_use", "id": "toolu_1", "name": "apply_patch", "input": {},
+            }}),
+            json!({"type": "content_block_delta", "index": 0, "delta": {
+                "type": "input_json_delta", "partial_json": "{\"input\":\"*** Begin Patch\\n*** End Patch\"}",
+            }}),
+            json!({"type": "content_block_stop", "index": 0}),
+            json!({"type": "message_stop"}),
+        ])
+        .await;
+
+        assert_eq!(events.len(), 3, "unexpected events: {events:?}");
+        assert_matches!(
+            &events[1],
+            Ok(ResponseEvent::OutputItemDone(ResponseItem::CustomToolCall { name, input, call_id, .. }))
+                if name == "apply_patch" && input == "*** Begin Patch\n*** End Patch" && call_id == "toolu_1"
+        );
+    }
+
+    #[tokio::test]
+    async fn maps_overloaded_error_event() {
+        let events = run_sse(vec![json!({
+            "type": "error",
+            "error": {"type": "overloaded_error", "message": "Overloaded"},
+        })])
+        .await;
+
+        assert_eq!(events.len(), 1);
+        assert_matches!(events[0], Err(ApiError::ServerOverloaded));
+    }
+}
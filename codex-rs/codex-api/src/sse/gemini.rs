@@ -0,0 +1,475 @@
+//! Translation of Gemini `streamGenerateContent` chunks into [`ResponseEvent`]s.
+
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::sse::function_call_item;
+use crate::telemetry::SseTelemetry;
+use codex_client::ByteStream;
+use codex_client::StreamResponse;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+pub fn spawn_gemini_stream(
+    stream_response: StreamResponse,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    custom_tools: HashSet<String>,
+) -> ResponseStream {
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(process_gemini_sse(
+        stream_response.bytes,
+        tx_event,
+        idle_timeout,
+        telemetry,
+        custom_tools,
+    ));
+    ResponseStream { rx_event }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerateContentChunk {
+    #[serde(default)]
+    response_id: Option<String>,
+    #[serde(default)]
+    candidates: Vec<Candidate>,
+    #[serde(default)]
+    usage_metadata: Option<UsageMetadata>,
+    #[serde(default)]
+    prompt_feedback: Option<PromptFeedback>,
+    #[serde(default)]
+    error: Option<GeminiError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Candidate {
+    #[serde(default)]
+    content: Option<CandidateContent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CandidateContent {
+    #[serde(default)]
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Part {
+    #[serde(default)]
+    text: Option<String>,
+    /// Thought summaries are only returned when requested and are not shown to the user.
+    #[serde(default)]
+    thought: bool,
+    #[serde(default)]
+    function_call: Option<FunctionCall>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FunctionCall {
+    #[serde(default)]
+    id: Option<String>,
+    name: String,
+    #[serde(default)]
+    args: Option<Value>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UsageMetadata {
+    #[serde(default)]
+    prompt_token_count: i64,
+    #[serde(default)]
+    cached_content_token_count: i64,
+    #[serde(default)]
+    candidates_token_count: i64,
+    #[serde(default)]
+    thoughts_token_count: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PromptFeedback {
+    #[serde(default)]
+    block_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiError {
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    message: String,
+}
+
+impl From<GeminiError> for ApiError {
+    fn from(error: GeminiError) -> Self {
+        match error.status.as_str() {
+            "INVALID_ARGUMENT"
+                if error
+                    .message
+                    .contains("exceeds the maximum number of tokens") =>
+            {
+                ApiError::ContextWindowExceeded
+            }
+            "INVALID_ARGUMENT" | "FAILED_PRECONDITION" => ApiError::InvalidRequest {
+                message: error.message,
+            },
+            "UNAVAILABLE" => ApiError::ServerOverloaded,
+            _ => ApiError::Retryable {
+                message: error.message,
+                delay: None,
+            },
+        }
+    }
+}
+
+/// Accumulates streamed text until the response ends; Gemini sends no explicit end event, so
+/// the stream closing marks completion.
+#[derive(Default)]
+struct GeminiStreamState {
+    response_id: Option<String>,
+    text: Option<String>,
+    call_count: usize,
+    usage: TokenUsage,
+    custom_tools: HashSet<String>,
+}
+
+impl GeminiStreamState {
+    fn handle(&mut self, chunk: GenerateContentChunk) -> Result<Vec<ResponseEvent>, ApiError> {
+        if let Some(error) = chunk.error {
+            return Err(error.into());
+        }
+        if let Some(block_reason) = chunk
+            .prompt_feedback
+            .and_then(|feedback| feedback.block_reason)
+        {
+            return Err(ApiError::InvalidRequest {
+                message: format!("prompt blocked by Gemini: {block_reason}"),
+            });
+        }
+
+        let mut events = Vec::new();
+        if self.response_id.is_none() {
+            self.response_id = Some(chunk.response_id.unwrap_or_default());
+            events.push(ResponseEvent::Created);
+        }
+        if let Some(usage) = chunk.usage_metadata {
+            self.record_usage(usage);
+        }
+
+        let parts = chunk
+            .candidates
+            .into_iter()
+            .next()
+            .and_then(|candidate| candidate.content)
+            .map(|content| content.parts)
+            .unwrap_or_default();
+        for part in parts {
+            if let Some(call) = part.function_call {
+                events.extend(self.finish_text());
+                self.call_count += 1;
+                let call_id = call
+                    .id
+                    .unwrap_or_else(|| format!("{}-call-{}", self.response_id(), self.call_count));
+                let arguments = call
+                    .args
+                    .unwrap_or_else(|| Value::Object(Default::default()))
+                    .to_string();
+                events.push(ResponseEvent::OutputItemDone(function_call_item(
+                    call_id,
+                    call.name,
+                    arguments,
+                    &self.custom_tools,
+                )));
+                continue;
+            }
+            let Some(delta) = part.text.filter(|text| !part.thought && !text.is_empty()) else {
+                continue;
+            };
+            match self.text.as_mut() {
+                Some(text) => text.push_str(&delta),
+                None => {
+                    events.push(ResponseEvent::OutputItemAdded(assistant_message(
+                        self.text_item_id(),
+                        String::new(),
+                    )));
+                    self.text = Some(delta.clone());
+                }
+            }
+            events.push(ResponseEvent::OutputTextDelta(delta));
+        }
+        Ok(events)
+    }
+
+    /// Completes the response once the stream closes.
+    fn finish(&mut self) -> Vec<ResponseEvent> {
+        let mut events: Vec<ResponseEvent> = self.finish_text().into_iter().collect();
+        events.push(ResponseEvent::Completed {
+            response_id: self.response_id().to_string(),
+            token_usage: Some(self.usage.clone()),
+            can_append: false,
+        });
+        events
+    }
+
+    fn finish_text(&mut self) -> Option<ResponseEvent> {
+        let text = self.text.take()?;
+        Some(ResponseEvent::OutputItemDone(assistant_message(
+            self.text_item_id(),
+            text,
+        )))
+    }
+
+    fn response_id(&self) -> &str {
+        self.response_id.as_deref().unwrap_or_default()
+    }
+
+    /// Text interrupted by function calls continues as a new message item.
+    fn text_item_id(&self) -> String {
+        format!("{}-{}", self.response_id(), self.call_count)
+    }
+
+    /// Gemini reports cumulative usage on each chunk; `promptTokenCount` already includes cached
+    /// tokens, and thinking tokens are billed as output.
+    fn record_usage(&mut self, usage: UsageMetadata) {
+        self.usage.input_tokens = usage.prompt_token_count;
+        self.usage.cached_input_tokens = usage.cached_content_token_count;
+        self.usage.output_tokens = usage.candidates_token_count + usage.thoughts_token_count;
+        self.usage.reasoning_output_tokens = usage.thoughts_token_count;
+        self.usage.total_tokens = self.usage.input_tokens + self.usage.output_tokens;
+    }
+}
+
+fn assistant_message(id: String, text: String) -> ResponseItem {
+    ResponseItem::Message {
+        id: Some(id),
+        role: "assistant".to_string(),
+        content: if text.is_empty() {
+            Vec::new()
+        } else {
+            vec![ContentItem::OutputText { text }]
+        },
+        end_turn: None,
+        phase: None,
+    }
+}
+
+pub async fn process_gemini_sse(
+    stream: ByteStream,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    custom_tools: HashSet<String>,
+) {
+    let mut stream = stream.eventsource();
+    let mut state = GeminiStreamState {
+        custom_tools,
+        ..Default::default()
+    };
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                debug!("SSE Error: {e:#}");
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) if state.response_id.is_some() => {
+                for event in state.finish() {
+                    if tx_event.send(Ok(event)).await.is_err() {
+                        return;
+                    }
+                }
+                return;
+            }
+            Ok(None) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream(
+                        "stream closed before any content was received".into(),
+                    )))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", &sse.data);
+
+        let chunk: GenerateContentChunk = match serde_json::from_str(&sse.data) {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                debug!("Failed to parse SSE event: {e}, data: {}", &sse.data);
+                continue;
+            }
+        };
+
+        match state.handle(chunk) {
+            Ok(events) => {
+                for event in events {
+                    if tx_event.send(Ok(event)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+            Err(error) => {
+                let _ = tx_event.send(Err(error)).await;
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use codex_client::TransportError;
+    use futures::TryStreamExt;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tokio_test::io::Builder as IoBuilder;
+    use tokio_util::io::ReaderStream;
+
+    async fn run_sse(chunks: Vec<Value>) -> Vec<Result<ResponseEvent, ApiError>> {
+        let body: String = chunks
+            .into_iter()
+            .map(|chunk| format!("data: {chunk}\r\n\r\n"))
+            .collect();
+        let reader = IoBuilder::new().read(body.as_bytes()).build();
+        let stream =
+            ReaderStream::new(reader).map_err(|err| TransportError::Network(err.to_string()));
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(16);
+        tokio::spawn(process_gemini_sse(
+            Box::pin(stream),
+            tx,
+            Duration::from_secs(1),
+            None,
+            HashSet::from(["apply_patch".to_string()]),
+        ));
+
+        let mut events = Vec::new();
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+        events
+    }
+
+    #[tokio::test]
+    async fn streams_text_and_function_calls() {
+        let events = run_sse(vec![
+            json!({"responseId": "resp_1", "candidates": [{"content": {"role": "model", "parts": [
+                {"text": "Let me ", "thought": true},
+                {"text": "Listing"},
+            ]}}]}),
+            json!({"candidates": [{"content": {"role": "model", "parts": [
+                {"text": " files."},
+                {"functionCall": {"name": "shell", "args": {"command": ["ls"]}}},
+            ]}, "finishReason": "STOP"}], "usageMetadata": {
+                "promptTokenCount": 20,
+                "cachedContentTokenCount": 8,
+                "candidatesTokenCount": 6,
+                "thoughtsTokenCount": 3,
+                "totalTokenCount": 29,
+            }}),
+        ])
+        .await;
+
+        let events: Vec<ResponseEvent> = events
+            .into_iter()
+            .map(|event| event.expect("stream event"))
+            .collect();
+        assert_eq!(events.len(), 7, "unexpected events: {events:?}");
+        assert_matches!(events[0], ResponseEvent::Created);
+        assert_matches!(&events[1], ResponseEvent::OutputItemAdded(ResponseItem::Message { id: Some(id), .. }) if id == "resp_1-0");
+        assert_matches!(&events[2], ResponseEvent::OutputTextDelta(delta) if delta == "Listing");
+        assert_matches!(&events[3], ResponseEvent::OutputTextDelta(delta) if delta == " files.");
+        assert_matches!(
+            &events[4],
+            ResponseEvent::OutputItemDone(ResponseItem::Message { content, .. })
+                if content == &vec![ContentItem::OutputText { text: "Listing files.".to_string() }]
+        );
+        assert_matches!(
+            &events[5],
+            ResponseEvent::OutputItemDone(ResponseItem::FunctionCall { name, arguments, call_id, .. })
+                if name == "shell" && arguments == "{\"command\":[\"ls\"]}" && call_id == "resp_1-call-1"
+        );
+        let ResponseEvent::Completed {
+            response_id,
+            token_usage,
+            ..
+        } = &events[6]
+        else {
+            panic!("expected completion, got {:?}", events[6]);
+        };
+        assert_eq!(response_id, "resp_1");
+        assert_eq!(
+            token_usage,
+            &Some(TokenUsage {
+                input_tokens: 20,
+                cached_input_tokens: 8,
+                output_tokens: 9,
+                reasoning_output_tokens: 3,
+                total_tokens: 29,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn calls_to_custom_tools_become_custom_tool_calls() {
+        let events = run_sse(vec![
+            json!({"responseId": "resp_1", "candidates": [{"content": {
+                "role": "model",
+                "parts": [{"functionCall": {
+                    "id": "call_1",
+                    "name": "apply_patch",
+                    "args": {"input": "*** Begin Patch\n*** End Patch"},
+                }}],
+            }}]}),
+        ])
+        .await;
+
+        assert_eq!(events.len(), 3, "unexpected events: {events:?}");
+        assert_matches!(
+            &events[1],
+            Ok(ResponseEvent::OutputItemDone(ResponseItem::CustomToolCall { name, input, call_id, .. }))
+                if name == "apply_patch" && input == "*** Begin Patch\n*** End Patch" && call_id == "call_1"
+        );
+    }
+
+    #[tokio::test]
+    async fn maps_error_chunks() {
+        let events = run_sse(vec![json!({
+            "error": {"code": 503, "status": "UNAVAILABLE", "message": "The model is overloaded."},
+        })])
+        .await;
+
+        assert_eq!(events.len(), 1);
+        assert_matches!(events[0], Err(ApiError::ServerOverloaded));
+    }
+}
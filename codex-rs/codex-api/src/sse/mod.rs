@@ -1,5 +1,45 @@
+pub mod anthropic;
+pub mod gemini;
 pub mod responses;
 
 pub use responses::process_sse;
 pub use responses::spawn_response_stream;
 pub use responses::stream_from_fixture;
+
+use crate::requests::CUSTOM_TOOL_INPUT_ARG;
+use codex_protocol::models::ResponseItem;
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// Builds the output item for a streamed function call. Calls to freeform custom tools, which
+/// were declared as functions taking a single string, become custom tool calls again.
+pub(crate) fn function_call_item(
+    call_id: String,
+    name: String,
+    arguments: String,
+    custom_tools: &HashSet<String>,
+) -> ResponseItem {
+    if !custom_tools.contains(&name) {
+        return ResponseItem::FunctionCall {
+            id: None,
+            name,
+            arguments,
+            call_id,
+        };
+    }
+    let input = serde_json::from_str::<Value>(&arguments)
+        .ok()
+        .and_then(|args| {
+            args.get(CUSTOM_TOOL_INPUT_ARG)
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        })
+        .unwrap_or(arguments);
+    ResponseItem::CustomToolCall {
+        id: None,
+        status: None,
+        call_id,
+        name,
+        input,
+    }
+}
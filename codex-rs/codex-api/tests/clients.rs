@@ -266,6 +266,7 @@ async fn streaming_client_retries_on_transport_error() -> Result<()> {
         stream: true,
         include: Vec::new(),
         prompt_cache_key: None,
+        max_output_tokens: None,
         text: None,
     };
     let client = ResponsesClient::new(transport.clone(), provider, NoAuth);
@@ -307,6 +308,7 @@ async fn azure_default_store_attaches_ids_and_headers() -> Result<()> {
         stream: true,
         include: Vec::new(),
         prompt_cache_key: None,
+        max_output_tokens: None,
         text: None,
     };
 
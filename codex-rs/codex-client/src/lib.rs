@@ -1,5 +1,6 @@
 mod default_client;
 mod error;
+mod proxy;
 mod request;
 mod retry;
 mod sse;
@@ -10,6 +11,10 @@ pub use crate::default_client::CodexHttpClient;
 pub use crate::default_client::CodexRequestBuilder;
 pub use crate::error::StreamError;
 pub use crate::error::TransportError;
+pub use crate::proxy::ProxySettings;
+pub use crate::proxy::apply_proxy_settings;
+pub use crate::proxy::proxy_settings;
+pub use crate::proxy::set_proxy_settings;
 pub use crate::request::Request;
 pub use crate::request::RequestCompression;
 pub use crate::request::Response;
@@ -0,0 +1,113 @@
+//! The proxy outgoing HTTP requests go through. Without configured settings, reqwest already
+//! honors `HTTPS_PROXY`, `HTTP_PROXY`, `ALL_PROXY` and `NO_PROXY`; the settings from `[proxy]` in
+//! `config.toml` replace those for every client built with [`apply_proxy_settings`].
+
+use std::sync::LazyLock;
+use std::sync::RwLock;
+
+use reqwest::ClientBuilder;
+use reqwest::NoProxy;
+use reqwest::Proxy;
+
+/// An HTTP(S) or SOCKS5 proxy, with optional credentials and hosts to reach directly.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProxySettings {
+    /// `http://`, `https://`, `socks5://` or `socks5h://` URL of the proxy.
+    pub url: String,
+    /// Hosts, domains (`.example.com`), IPs and CIDR ranges that bypass the proxy.
+    pub no_proxy: Vec<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+impl ProxySettings {
+    /// The reqwest proxy for these settings, routing every scheme through `url`.
+    pub fn to_proxy(&self) -> reqwest::Result<Proxy> {
+        let mut proxy = Proxy::all(&self.url)?;
+        if let Some(username) = &self.username {
+            proxy = proxy.basic_auth(username, self.password.as_deref().unwrap_or_default());
+        }
+        if !self.no_proxy.is_empty() {
+            proxy = proxy.no_proxy(NoProxy::from_string(&self.no_proxy.join(",")));
+        }
+        Ok(proxy)
+    }
+}
+
+static PROXY_SETTINGS: LazyLock<RwLock<Option<ProxySettings>>> =
+    LazyLock::new(|| RwLock::new(None));
+
+/// Sets the proxy for clients built from now on; `None` leaves the environment variables in
+/// charge.
+pub fn set_proxy_settings(settings: Option<ProxySettings>) {
+    let Ok(mut guard) = PROXY_SETTINGS.write() else {
+        tracing::warn!("Failed to acquire proxy settings lock");
+        return;
+    };
+    *guard = settings;
+}
+
+pub fn proxy_settings() -> Option<ProxySettings> {
+    PROXY_SETTINGS.read().ok().and_then(|guard| guard.clone())
+}
+
+/// Routes `builder` through the configured proxy, if any.
+pub fn apply_proxy_settings(builder: ClientBuilder) -> ClientBuilder {
+    let Some(settings) = proxy_settings() else {
+        return builder;
+    };
+    match settings.to_proxy() {
+        Ok(proxy) => builder.proxy(proxy),
+        Err(err) => {
+            tracing::warn!("Ignoring invalid proxy URL {}: {err}", settings.url);
+            builder
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tokio::io::AsyncReadExt;
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn requests_go_through_the_proxy_with_credentials()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let settings = ProxySettings {
+            url: format!("http://{}", listener.local_addr()?),
+            no_proxy: vec!["localhost".to_string()],
+            username: Some("codex".to_string()),
+            password: Some("secret".to_string()),
+        };
+        let proxy = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await?;
+            let mut request = vec![0; 4096];
+            let read = stream.read(&mut request).await?;
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok")
+                .await?;
+            Ok::<_, std::io::Error>(String::from_utf8_lossy(&request[..read]).to_lowercase())
+        });
+
+        let client = reqwest::Client::builder()
+            .proxy(settings.to_proxy()?)
+            .build()?;
+        let body = client
+            .get("http://codex.invalid/ping")
+            .send()
+            .await?
+            .text()
+            .await?;
+
+        let request = proxy.await??;
+        assert_eq!(body, "ok");
+        assert!(request.starts_with("get http://codex.invalid/ping http/1.1"));
+        // base64("codex:secret")
+        assert!(request.contains("proxy-authorization: basic y29kzxg6c2vjcmv0"));
+        Ok(())
+    }
+}
@@ -19,6 +19,7 @@ pub enum RequirementSource {
     MdmManagedPreferences { domain: String, key: String },
     CloudRequirements,
     SystemRequirementsToml { file: AbsolutePathBuf },
+    RemoteRequirements { url: String },
     LegacyManagedConfigTomlFromFile { file: AbsolutePathBuf },
     LegacyManagedConfigTomlFromMdm,
 }
@@ -36,6 +37,7 @@ impl fmt::Display for RequirementSource {
             RequirementSource::SystemRequirementsToml { file } => {
                 write!(f, "{}", file.as_path().display())
             }
+            RequirementSource::RemoteRequirements { url } => write!(f, "{url}"),
             RequirementSource::LegacyManagedConfigTomlFromFile { file } => {
                 write!(f, "{}", file.as_path().display())
             }
@@ -84,6 +86,8 @@ pub struct ConfigRequirements {
     pub enforce_residency: ConstrainedWithSource<Option<ResidencyRequirement>>,
     /// Managed network constraints derived from requirements.
     pub network: Option<Sourced<NetworkConstraints>>,
+    /// Tool names (with `*` and `?` wildcards) the model may not call.
+    pub blocked_tools: Option<Sourced<Vec<String>>>,
 }
 
 impl Default for ConfigRequirements {
@@ -105,6 +109,7 @@ impl Default for ConfigRequirements {
             exec_policy: None,
             enforce_residency: ConstrainedWithSource::new(Constrained::allow_any(None), None),
             network: None,
+            blocked_tools: None,
         }
     }
 }
@@ -234,6 +239,10 @@ pub struct ConfigRequirementsToml {
     pub enforce_residency: Option<ResidencyRequirement>,
     #[serde(rename = "experimental_network")]
     pub network: Option<NetworkRequirementsToml>,
+    pub blocked_tools: Option<Vec<String>>,
+    /// HTTPS URL of a `requirements.toml` that fills in whatever the sources that name it leave
+    /// unset.
+    pub requirements_url: Option<String>,
 }
 
 /// Value paired with the requirement source it came from, for better error
@@ -267,6 +276,8 @@ pub struct ConfigRequirementsWithSources {
     pub rules: Option<Sourced<RequirementsExecPolicyToml>>,
     pub enforce_residency: Option<Sourced<ResidencyRequirement>>,
     pub network: Option<Sourced<NetworkRequirementsToml>>,
+    pub blocked_tools: Option<Sourced<Vec<String>>>,
+    pub requirements_url: Option<Sourced<String>>,
 }
 
 impl ConfigRequirementsWithSources {
@@ -302,6 +313,8 @@ impl ConfigRequirementsWithSources {
                 rules,
                 enforce_residency,
                 network,
+                blocked_tools,
+                requirements_url,
             }
         );
     }
@@ -315,6 +328,8 @@ impl ConfigRequirementsWithSources {
             rules,
             enforce_residency,
             network,
+            blocked_tools,
+            requirements_url,
         } = self;
         ConfigRequirementsToml {
             allowed_approval_policies: allowed_approval_policies.map(|sourced| sourced.value),
@@ -324,6 +339,8 @@ impl ConfigRequirementsWithSources {
             rules: rules.map(|sourced| sourced.value),
             enforce_residency: enforce_residency.map(|sourced| sourced.value),
             network: network.map(|sourced| sourced.value),
+            blocked_tools: blocked_tools.map(|sourced| sourced.value),
+            requirements_url: requirements_url.map(|sourced| sourced.value),
         }
     }
 }
@@ -370,6 +387,8 @@ impl ConfigRequirementsToml {
             && self.rules.is_none()
             && self.enforce_residency.is_none()
             && self.network.is_none()
+            && self.blocked_tools.is_none()
+            && self.requirements_url.is_none()
     }
 }
 
@@ -385,6 +404,8 @@ impl TryFrom<ConfigRequirementsWithSources> for ConfigRequirements {
             rules,
             enforce_residency,
             network,
+            blocked_tools,
+            requirements_url: _,
         } = toml;
 
         let approval_policy = match allowed_approval_policies {
@@ -553,6 +574,7 @@ impl TryFrom<ConfigRequirementsWithSources> for ConfigRequirements {
             exec_policy,
             enforce_residency,
             network,
+            blocked_tools,
         })
     }
 }
@@ -588,6 +610,8 @@ mod tests {
             rules,
             enforce_residency,
             network,
+            blocked_tools,
+            requirements_url,
         } = toml;
         ConfigRequirementsWithSources {
             allowed_approval_policies: allowed_approval_policies
@@ -601,6 +625,10 @@ mod tests {
             enforce_residency: enforce_residency
                 .map(|value| Sourced::new(value, RequirementSource::Unknown)),
             network: network.map(|value| Sourced::new(value, RequirementSource::Unknown)),
+            blocked_tools: blocked_tools
+                .map(|value| Sourced::new(value, RequirementSource::Unknown)),
+            requirements_url: requirements_url
+                .map(|value| Sourced::new(value, RequirementSource::Unknown)),
         }
     }
 
@@ -620,6 +648,8 @@ mod tests {
         ];
         let enforce_residency = ResidencyRequirement::Us;
         let enforce_source = source.clone();
+        let blocked_tools = vec!["mcp__github__*".to_string()];
+        let requirements_url = "https://codex.example.com/requirements.toml".to_string();
 
         // Intentionally constructed without `..Default::default()` so adding a new field to
         // `ConfigRequirementsToml` forces this test to be updated.
@@ -631,6 +661,8 @@ mod tests {
             rules: None,
             enforce_residency: Some(enforce_residency),
             network: None,
+            blocked_tools: Some(blocked_tools.clone()),
+            requirements_url: Some(requirements_url.clone()),
         };
 
         target.merge_unset_fields(source.clone(), other);
@@ -649,8 +681,10 @@ mod tests {
                 )),
                 mcp_servers: None,
                 rules: None,
-                enforce_residency: Some(Sourced::new(enforce_residency, enforce_source)),
+                enforce_residency: Some(Sourced::new(enforce_residency, enforce_source.clone())),
                 network: None,
+                blocked_tools: Some(Sourced::new(blocked_tools, enforce_source.clone())),
+                requirements_url: Some(Sourced::new(requirements_url, enforce_source)),
             }
         );
     }
@@ -683,6 +717,8 @@ mod tests {
                 rules: None,
                 enforce_residency: None,
                 network: None,
+                blocked_tools: None,
+                requirements_url: None,
             }
         );
         Ok(())
@@ -723,6 +759,8 @@ mod tests {
                 rules: None,
                 enforce_residency: None,
                 network: None,
+                blocked_tools: None,
+                requirements_url: None,
             }
         );
         Ok(())
@@ -162,6 +162,18 @@ impl ConfigLayerStack {
         &self.requirements_toml
     }
 
+    /// Returns a stack with the layers of `other` and the requirements of this
+    /// stack, for config files that were loaded again while the requirements
+    /// stay fixed.
+    pub fn with_layers_of(&self, other: &ConfigLayerStack) -> Self {
+        Self {
+            layers: other.layers.clone(),
+            user_layer_index: other.user_layer_index,
+            requirements: self.requirements.clone(),
+            requirements_toml: self.requirements_toml.clone(),
+        }
+    }
+
     /// Creates a new [ConfigLayerStack] using the specified values to inject a
     /// "user layer" into the stack. If such a layer already exists, it is
     /// replaced; otherwise, it is inserted into the stack at the appropriate
@@ -7,6 +7,7 @@ use codex_api::rate_limits::parse_promo_message;
 use codex_api::rate_limits::parse_rate_limit_for_limit;
 use http::HeaderMap;
 use serde::Deserialize;
+use std::time::Duration;
 
 use crate::auth::CodexAuth;
 use crate::error::CodexErr;
@@ -14,6 +15,7 @@ use crate::error::RetryLimitReachedError;
 use crate::error::UnexpectedResponseError;
 use crate::error::UsageLimitReachedError;
 use crate::model_provider_info::ModelProviderInfo;
+use crate::secrets::SecretCache;
 use crate::token_data::PlanType;
 
 pub(crate) fn map_api_error(err: ApiError) -> CodexErr {
@@ -90,6 +92,7 @@ pub(crate) fn map_api_error(err: ApiError) -> CodexErr {
                     CodexErr::RetryLimit(RetryLimitReachedError {
                         status,
                         request_id: extract_request_tracking_id(headers.as_ref()),
+                        retry_after: headers.as_ref().and_then(parse_retry_after),
                     })
                 } else {
                     CodexErr::UnexpectedStatus(UnexpectedResponseError {
@@ -104,6 +107,7 @@ pub(crate) fn map_api_error(err: ApiError) -> CodexErr {
             TransportError::RetryLimit => CodexErr::RetryLimit(RetryLimitReachedError {
                 status: http::StatusCode::INTERNAL_SERVER_ERROR,
                 request_id: None,
+                retry_after: None,
             }),
             TransportError::Timeout => CodexErr::Timeout,
             TransportError::Network(msg) | TransportError::Build(msg) => {
@@ -118,12 +122,75 @@ const ACTIVE_LIMIT_HEADER: &str = "x-codex-active-limit";
 const REQUEST_ID_HEADER: &str = "x-request-id";
 const OAI_REQUEST_ID_HEADER: &str = "x-oai-request-id";
 const CF_RAY_HEADER: &str = "cf-ray";
+const RETRY_AFTER_MS_HEADER: &str = "retry-after-ms";
+/// `x-ratelimit-remaining-*` / `x-ratelimit-reset-*` header suffixes, checked in order.
+const RATE_LIMIT_KINDS: [&str; 2] = ["requests", "tokens"];
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(
+                *name,
+                http::HeaderValue::from_str(value).expect("header value"),
+            );
+        }
+        headers
+    }
+
+    #[test]
+    fn parse_retry_after_reads_supported_headers() {
+        assert_eq!(
+            parse_retry_after(&headers(&[
+                ("retry-after-ms", "1500"),
+                ("retry-after", "30"),
+            ])),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(
+            parse_retry_after(&headers(&[("retry-after", "30")])),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after(&headers(&[(
+                "retry-after",
+                "Wed, 21 Oct 2015 07:28:00 GMT"
+            )])),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(
+            parse_retry_after(&headers(&[
+                ("x-ratelimit-remaining-requests", "3"),
+                ("x-ratelimit-reset-requests", "1s"),
+                ("x-ratelimit-remaining-tokens", "0"),
+                ("x-ratelimit-reset-tokens", "6m0.5s"),
+            ])),
+            Some(Duration::from_millis(360_500))
+        );
+        assert_eq!(
+            parse_retry_after(&headers(&[("x-ratelimit-reset-requests", "1s")])),
+            None
+        );
+    }
+
+    #[test]
+    fn parse_reset_duration_handles_units() {
+        assert_eq!(
+            ["20ms", "1.5s", "1h2m", "", "5x"].map(parse_reset_duration),
+            [
+                Some(Duration::from_millis(20)),
+                Some(Duration::from_millis(1500)),
+                Some(Duration::from_secs(3720)),
+                None,
+                None,
+            ]
+        );
+    }
+
     #[test]
     fn map_api_error_maps_server_overloaded() {
         let err = map_api_error(ApiError::ServerOverloaded);
@@ -236,11 +303,79 @@ fn extract_header(headers: Option<&HeaderMap>, name: &str) -> Option<String> {
     })
 }
 
-pub(crate) fn auth_provider_from_auth(
+/// Reads how long a rate-limited request should wait before being retried.
+///
+/// Prefers `retry-after-ms`, then `Retry-After` (seconds or an HTTP date), then the reset time of
+/// whichever `x-ratelimit-*` budget is exhausted.
+fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+    };
+    if let Some(millis) = header(RETRY_AFTER_MS_HEADER).and_then(|value| value.parse::<f64>().ok())
+        && millis.is_finite()
+        && millis >= 0.0
+    {
+        return Some(duration_from_millis(millis));
+    }
+    if let Some(value) = header(http::header::RETRY_AFTER.as_str()) {
+        if let Ok(seconds) = value.parse::<u64>() {
+            return Some(Duration::from_secs(seconds));
+        }
+        if let Ok(date) = DateTime::parse_from_rfc2822(value) {
+            return Some(
+                (date.with_timezone(&Utc) - Utc::now())
+                    .to_std()
+                    .unwrap_or_default(),
+            );
+        }
+    }
+    RATE_LIMIT_KINDS.iter().find_map(|kind| {
+        if header(&format!("x-ratelimit-remaining-{kind}")) != Some("0") {
+            return None;
+        }
+        header(&format!("x-ratelimit-reset-{kind}")).and_then(parse_reset_duration)
+    })
+}
+
+/// Parses reset durations such as `1s`, `6m0s`, `1.5s`, or `20ms`.
+fn parse_reset_duration(value: &str) -> Option<Duration> {
+    let mut total = 0.0;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let number: f64 = rest[..number_len].parse().ok()?;
+        rest = &rest[number_len..];
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let scale = match &rest[..unit_len] {
+            "ms" => 1.0,
+            "s" => 1_000.0,
+            "m" => 60_000.0,
+            "h" => 3_600_000.0,
+            _ => return None,
+        };
+        rest = &rest[unit_len..];
+        total += number * scale;
+    }
+    (total > 0.0).then(|| duration_from_millis(total))
+}
+
+fn duration_from_millis(millis: f64) -> Duration {
+    Duration::from_micros((millis * 1000.0).round() as u64)
+}
+
+pub(crate) async fn auth_provider_from_auth(
     auth: Option<CodexAuth>,
     provider: &ModelProviderInfo,
+    secrets: &SecretCache,
 ) -> crate::error::Result<CoreAuthProvider> {
-    if let Some(api_key) = provider.api_key()? {
+    if let Some(api_key) = provider.resolve_api_key(secrets).await? {
         return Ok(CoreAuthProvider {
             token: Some(api_key),
             account_id: None,
@@ -287,6 +422,16 @@ pub(crate) struct CoreAuthProvider {
     account_id: Option<String>,
 }
 
+impl CoreAuthProvider {
+    /// Authenticates with `token` alone, e.g. an Azure AD access token.
+    pub(crate) fn from_bearer_token(token: String) -> Self {
+        Self {
+            token: Some(token),
+            account_id: None,
+        }
+    }
+}
+
 impl ApiAuthProvider for CoreAuthProvider {
     fn bearer_token(&self) -> Option<String> {
         self.token.clone()
@@ -1,3 +1,4 @@
+mod accounts;
 mod storage;
 
 use async_trait::async_trait;
@@ -19,6 +20,15 @@ use codex_app_server_protocol::AuthMode as ApiAuthMode;
 use codex_otel::TelemetryAuthMode;
 use codex_protocol::config_types::ForcedLoginMethod;
 
+pub use crate::auth::accounts::DEFAULT_ACCOUNT;
+pub use crate::auth::accounts::account_home;
+pub use crate::auth::accounts::active_account;
+pub use crate::auth::accounts::auth_home;
+pub use crate::auth::accounts::create_account;
+pub use crate::auth::accounts::list_accounts;
+pub use crate::auth::accounts::remove_account;
+pub use crate::auth::accounts::set_active_account;
+pub use crate::auth::accounts::validate_account_name;
 pub use crate::auth::storage::AuthCredentialsStoreMode;
 pub use crate::auth::storage::AuthDotJson;
 use crate::auth::storage::AuthStorageBackend;
@@ -440,12 +450,8 @@ pub fn load_auth_dot_json(
 }
 
 pub fn enforce_login_restrictions(config: &Config) -> std::io::Result<()> {
-    let Some(auth) = load_auth(
-        &config.codex_home,
-        true,
-        config.cli_auth_credentials_store_mode,
-    )?
-    else {
+    let auth_home = config.auth_home();
+    let Some(auth) = load_auth(&auth_home, true, config.cli_auth_credentials_store_mode)? else {
         return Ok(());
     };
 
@@ -465,7 +471,7 @@ pub fn enforce_login_restrictions(config: &Config) -> std::io::Result<()> {
 
         if let Some(message) = method_violation {
             return logout_with_message(
-                &config.codex_home,
+                &auth_home,
                 message,
                 config.cli_auth_credentials_store_mode,
             );
@@ -481,7 +487,7 @@ pub fn enforce_login_restrictions(config: &Config) -> std::io::Result<()> {
             Ok(data) => data,
             Err(err) => {
                 return logout_with_message(
-                    &config.codex_home,
+                    &auth_home,
                     format!(
                         "Failed to load ChatGPT credentials while enforcing workspace restrictions: {err}. Logging out."
                     ),
@@ -502,7 +508,7 @@ pub fn enforce_login_restrictions(config: &Config) -> std::io::Result<()> {
                 ),
             };
             return logout_with_message(
-                &config.codex_home,
+                &auth_home,
                 message,
                 config.cli_auth_credentials_store_mode,
             );
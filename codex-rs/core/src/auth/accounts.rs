@@ -0,0 +1,188 @@
+//! Named accounts: several sets of CLI credentials kept side by side, e.g. a work ChatGPT plan
+//! and a personal API key. Each account stores its credentials the way `CODEX_HOME` does, in
+//! `CODEX_HOME/accounts/<name>`, so every credential store backend works unchanged. The
+//! credentials stored directly in `CODEX_HOME` are the `default` account.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use super::AuthCredentialsStoreMode;
+use super::logout_all_stores;
+
+/// Name of the account whose credentials live directly in `CODEX_HOME`.
+pub const DEFAULT_ACCOUNT: &str = "default";
+
+const ACCOUNTS_DIR: &str = "accounts";
+/// File in [`ACCOUNTS_DIR`] holding the name of the account used when none is configured.
+const ACTIVE_ACCOUNT_FILE: &str = "active";
+
+/// Account names become directory names, so they are limited to letters, digits, `-` and `_`.
+pub fn validate_account_name(name: &str) -> io::Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "invalid account name `{name}`: use only letters, digits, `-` and `_` (e.g. `work`)"
+            ),
+        ))
+    }
+}
+
+/// The directory whose credentials belong to account `name`.
+pub fn account_home(codex_home: &Path, name: &str) -> PathBuf {
+    if name == DEFAULT_ACCOUNT {
+        codex_home.to_path_buf()
+    } else {
+        codex_home.join(ACCOUNTS_DIR).join(name)
+    }
+}
+
+/// The account selected with `codex account switch`, or `None` for the default one.
+pub fn active_account(codex_home: &Path) -> Option<String> {
+    let name = std::fs::read_to_string(codex_home.join(ACCOUNTS_DIR).join(ACTIVE_ACCOUNT_FILE))
+        .ok()?
+        .trim()
+        .to_string();
+    (validate_account_name(&name).is_ok() && name != DEFAULT_ACCOUNT).then_some(name)
+}
+
+/// Makes `name` the account used when the config does not bind one.
+pub fn set_active_account(codex_home: &Path, name: &str) -> io::Result<()> {
+    validate_account_name(name)?;
+    let active_file = codex_home.join(ACCOUNTS_DIR).join(ACTIVE_ACCOUNT_FILE);
+    if name == DEFAULT_ACCOUNT {
+        return match std::fs::remove_file(active_file) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        };
+    }
+    if !account_home(codex_home, name).is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no account named `{name}`; add it with `codex login --name {name}`"),
+        ));
+    }
+    std::fs::write(active_file, format!("{name}\n"))
+}
+
+/// Creates the directory for account `name`, ready for a login to store credentials in.
+pub fn create_account(codex_home: &Path, name: &str) -> io::Result<PathBuf> {
+    validate_account_name(name)?;
+    let home = account_home(codex_home, name);
+    std::fs::create_dir_all(&home)?;
+    Ok(home)
+}
+
+/// Every account, starting with the default one, with the named accounts sorted.
+pub fn list_accounts(codex_home: &Path) -> io::Result<Vec<String>> {
+    let mut names = Vec::new();
+    match std::fs::read_dir(codex_home.join(ACCOUNTS_DIR)) {
+        Ok(entries) => {
+            for entry in entries {
+                let entry = entry?;
+                if !entry.file_type()?.is_dir() {
+                    continue;
+                }
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if validate_account_name(&name).is_ok() && name != DEFAULT_ACCOUNT {
+                    names.push(name);
+                }
+            }
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+    names.sort();
+    names.insert(0, DEFAULT_ACCOUNT.to_string());
+    Ok(names)
+}
+
+/// Logs account `name` out and forgets it. The default account is only logged out. Returns
+/// whether the account existed.
+pub fn remove_account(
+    codex_home: &Path,
+    name: &str,
+    auth_credentials_store_mode: AuthCredentialsStoreMode,
+) -> io::Result<bool> {
+    validate_account_name(name)?;
+    let home = account_home(codex_home, name);
+    if name == DEFAULT_ACCOUNT {
+        return logout_all_stores(&home, auth_credentials_store_mode);
+    }
+    if !home.is_dir() {
+        return Ok(false);
+    }
+    logout_all_stores(&home, auth_credentials_store_mode)?;
+    std::fs::remove_dir_all(&home)?;
+    if active_account(codex_home).as_deref() == Some(name) {
+        set_active_account(codex_home, DEFAULT_ACCOUNT)?;
+    }
+    Ok(true)
+}
+
+/// The directory to load and store credentials in: the `account` the config binds, otherwise the
+/// active account.
+pub fn auth_home(codex_home: &Path, account: Option<&str>) -> PathBuf {
+    match account
+        .map(str::to_string)
+        .or_else(|| active_account(codex_home))
+    {
+        Some(name) => account_home(codex_home, &name),
+        None => codex_home.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::load_auth_dot_json;
+    use crate::auth::login_with_api_key;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn accounts_keep_separate_credentials() -> io::Result<()> {
+        let codex_home = tempdir()?;
+        let codex_home = codex_home.path();
+        let mode = AuthCredentialsStoreMode::File;
+        login_with_api_key(codex_home, "sk-default", mode)?;
+        let personal = create_account(codex_home, "personal")?;
+        login_with_api_key(&personal, "sk-personal", mode)?;
+
+        assert_eq!(list_accounts(codex_home)?, vec!["default", "personal"]);
+        assert_eq!(auth_home(codex_home, None), codex_home);
+
+        set_active_account(codex_home, "personal")?;
+        let home = auth_home(codex_home, None);
+        assert_eq!(
+            load_auth_dot_json(&home, mode)?.and_then(|auth| auth.openai_api_key),
+            Some("sk-personal".to_string())
+        );
+        // A binding in the config wins over the active account.
+        assert_eq!(auth_home(codex_home, Some(DEFAULT_ACCOUNT)), codex_home);
+
+        assert!(remove_account(codex_home, "personal", mode)?);
+        assert_eq!(active_account(codex_home), None);
+        assert_eq!(list_accounts(codex_home)?, vec!["default"]);
+        assert_eq!(
+            load_auth_dot_json(codex_home, mode)?.and_then(|auth| auth.openai_api_key),
+            Some("sk-default".to_string())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_names_that_are_not_plain_directory_names() {
+        assert!(validate_account_name("work-2").is_ok());
+        assert!(validate_account_name("").is_err());
+        assert!(validate_account_name("../work").is_err());
+        assert!(set_active_account(Path::new("/nonexistent"), "missing").is_err());
+    }
+}
@@ -0,0 +1,49 @@
+//! Recovery from ChatGPT tokens that can no longer be refreshed mid-session.
+//!
+//! Instead of failing the turn, the session emits `EventMsg::AuthExpired` and waits until the
+//! client has signed in again and answers with `Op::ReauthComplete`. The stored credentials are
+//! then reloaded and the failed model request is retried.
+
+use tokio::sync::oneshot;
+use uuid::Uuid;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::error::RefreshTokenFailedError;
+use crate::protocol::AuthExpiredEvent;
+use crate::protocol::EventMsg;
+use crate::protocol::ReviewDecision;
+
+/// Pauses the turn until the client reports the outcome of a new sign-in. Returns whether the
+/// user signed in again, in which case the request should be retried.
+pub(crate) async fn request_reauth(
+    sess: &Session,
+    turn_context: &TurnContext,
+    failed: &RefreshTokenFailedError,
+) -> bool {
+    let id = Uuid::new_v4().to_string();
+    let (tx_reauth, rx_reauth) = oneshot::channel();
+    {
+        let mut active = sess.active_turn.lock().await;
+        if let Some(at) = active.as_mut() {
+            at.turn_state
+                .lock()
+                .await
+                .insert_pending_approval(id.clone(), tx_reauth);
+        }
+    }
+    sess.send_event(
+        turn_context,
+        EventMsg::AuthExpired(AuthExpiredEvent {
+            id,
+            turn_id: turn_context.sub_id.clone(),
+            message: failed.message.clone(),
+        }),
+    )
+    .await;
+    let renewed = rx_reauth.await.unwrap_or_default() == ReviewDecision::Approved;
+    if renewed {
+        sess.services.auth_manager.reload();
+    }
+    renewed
+}
@@ -0,0 +1,299 @@
+//! Azure AD (Entra ID) tokens for Azure OpenAI providers.
+//!
+//! Tokens are cached per [`ModelClient`](crate::client::ModelClient) and refreshed shortly
+//! before they expire, so a long session keeps working without re-running `az` on every request.
+
+use crate::default_client::build_reqwest_client;
+use crate::error::CodexErr;
+use crate::error::Result;
+use crate::model_provider_info::AzureAuth;
+use crate::model_provider_info::ModelProviderInfo;
+use serde::Deserialize;
+use std::fmt;
+use std::io;
+use std::time::Duration;
+use tokio::process::Command;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+const COGNITIVE_SERVICES_RESOURCE: &str = "https://cognitiveservices.azure.com";
+const COGNITIVE_SERVICES_SCOPE: &str = "https://cognitiveservices.azure.com/.default";
+const DEFAULT_AUTHORITY_HOST: &str = "https://login.microsoftonline.com";
+/// Tokens are refreshed once they are this close to expiring.
+const EXPIRY_MARGIN: Duration = Duration::from_secs(5 * 60);
+/// Lifetime assumed when the token source does not report one.
+const DEFAULT_TOKEN_LIFETIME: Duration = Duration::from_secs(10 * 60);
+
+/// Where an Azure AD token comes from, for the [`AzureAuth`] modes that use one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenSource {
+    AzureCli,
+    ClientSecret,
+}
+
+impl TokenSource {
+    fn for_auth(auth: AzureAuth) -> Option<Self> {
+        match auth {
+            AzureAuth::ApiKey => None,
+            AzureAuth::AzureCli => Some(Self::AzureCli),
+            AzureAuth::ClientSecret => Some(Self::ClientSecret),
+        }
+    }
+}
+
+struct CachedToken {
+    source: TokenSource,
+    value: String,
+    expires_at: Instant,
+}
+
+#[derive(Default)]
+pub(crate) struct AzureTokenCache {
+    token: Mutex<Option<CachedToken>>,
+}
+
+impl fmt::Debug for AzureTokenCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never print the token itself.
+        f.debug_struct("AzureTokenCache").finish_non_exhaustive()
+    }
+}
+
+impl AzureTokenCache {
+    /// Returns an Azure AD bearer token when `provider` is configured for Azure AD auth.
+    pub(crate) async fn token(&self, provider: &ModelProviderInfo) -> Result<Option<String>> {
+        self.token_from(provider, |source| async move {
+            match source {
+                TokenSource::AzureCli => fetch_azure_cli_token().await,
+                TokenSource::ClientSecret => fetch_client_secret_token().await,
+            }
+        })
+        .await
+    }
+
+    /// Like [`Self::token`], with `fetch` producing a new token and its lifetime.
+    async fn token_from<F, Fut>(
+        &self,
+        provider: &ModelProviderInfo,
+        fetch: F,
+    ) -> Result<Option<String>>
+    where
+        F: FnOnce(TokenSource) -> Fut,
+        Fut: Future<Output = io::Result<(String, Duration)>>,
+    {
+        let Some(source) = provider
+            .azure
+            .as_ref()
+            .and_then(|azure| TokenSource::for_auth(azure.auth))
+        else {
+            return Ok(None);
+        };
+
+        let mut cached = self.token.lock().await;
+        if let Some(token) = cached.as_ref()
+            && token.source == source
+            && token.expires_at > Instant::now() + EXPIRY_MARGIN
+        {
+            return Ok(Some(token.value.clone()));
+        }
+
+        let (value, lifetime) = fetch(source).await.map_err(CodexErr::Io)?;
+        *cached = Some(CachedToken {
+            source,
+            value: value.clone(),
+            expires_at: Instant::now() + lifetime,
+        });
+        Ok(Some(value))
+    }
+
+    /// Drops the cached token, so the next request fetches a new one. Called when the provider
+    /// rejects a request as unauthorized, since a revoked token can still look unexpired.
+    pub(crate) async fn clear(&self) {
+        self.token.lock().await.take();
+    }
+}
+
+#[derive(Deserialize)]
+struct AzureCliToken {
+    #[serde(rename = "accessToken")]
+    access_token: String,
+    /// Unix timestamp; only reported by recent versions of the Azure CLI.
+    expires_on: Option<i64>,
+}
+
+async fn fetch_azure_cli_token() -> io::Result<(String, Duration)> {
+    let output = Command::new("az")
+        .args([
+            "account",
+            "get-access-token",
+            "--resource",
+            COGNITIVE_SERVICES_RESOURCE,
+            "--output",
+            "json",
+        ])
+        .output()
+        .await
+        .map_err(|err| io::Error::other(format!("failed to run `az`: {err}")))?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "`az account get-access-token` failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    let token: AzureCliToken = serde_json::from_slice(&output.stdout)?;
+    let lifetime = token
+        .expires_on
+        .and_then(|expires_on| {
+            let remaining = expires_on - chrono::Utc::now().timestamp();
+            u64::try_from(remaining).ok().map(Duration::from_secs)
+        })
+        .unwrap_or(DEFAULT_TOKEN_LIFETIME);
+    Ok((token.access_token, lifetime))
+}
+
+#[derive(Deserialize)]
+struct ClientCredentialsToken {
+    access_token: String,
+    expires_in: Option<u64>,
+}
+
+async fn fetch_client_secret_token() -> io::Result<(String, Duration)> {
+    let env = |name: &str| {
+        std::env::var(name)
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .ok_or_else(|| io::Error::other(format!("{name} must be set for Azure AD auth")))
+    };
+    let authority_host =
+        std::env::var("AZURE_AUTHORITY_HOST").unwrap_or_else(|_| DEFAULT_AUTHORITY_HOST.into());
+    request_client_credentials_token(
+        &authority_host,
+        &env("AZURE_TENANT_ID")?,
+        &env("AZURE_CLIENT_ID")?,
+        &env("AZURE_CLIENT_SECRET")?,
+    )
+    .await
+}
+
+async fn request_client_credentials_token(
+    authority_host: &str,
+    tenant_id: &str,
+    client_id: &str,
+    client_secret: &str,
+) -> io::Result<(String, Duration)> {
+    let url = format!(
+        "{}/{tenant_id}/oauth2/v2.0/token",
+        authority_host.trim_end_matches('/')
+    );
+    let response = build_reqwest_client()
+        .post(url)
+        .form(&[
+            ("grant_type", "client_credentials"),
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+            ("scope", COGNITIVE_SERVICES_SCOPE),
+        ])
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(io::Error::other(format!(
+            "Azure AD token request failed with {status}: {body}"
+        )));
+    }
+    let token: ClientCredentialsToken = response.json().await.map_err(io::Error::other)?;
+    let lifetime = token
+        .expires_in
+        .map_or(DEFAULT_TOKEN_LIFETIME, Duration::from_secs);
+    Ok((token.access_token, lifetime))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::body_string_contains;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+
+    #[tokio::test]
+    async fn client_credentials_token_is_requested_for_cognitive_services() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/tenant-1/oauth2/v2.0/token"))
+            .and(body_string_contains("grant_type=client_credentials"))
+            .and(body_string_contains("client_id=client-1"))
+            .and(body_string_contains(
+                "scope=https%3A%2F%2Fcognitiveservices.azure.com%2F.default",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "token_type": "Bearer",
+                "expires_in": 3599,
+                "access_token": "aad-token",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let token =
+            request_client_credentials_token(&server.uri(), "tenant-1", "client-1", "secret")
+                .await
+                .expect("token");
+
+        assert_eq!(token, ("aad-token".to_string(), Duration::from_secs(3599)));
+    }
+
+    #[tokio::test]
+    async fn api_key_auth_does_not_fetch_tokens() {
+        let mut provider = crate::model_provider_info::create_oss_provider_with_base_url(
+            "https://xxxxx.openai.azure.com/openai/v1",
+            crate::model_provider_info::WireApi::Responses,
+        );
+        let cache = AzureTokenCache::default();
+        assert_eq!(cache.token(&provider).await.expect("token"), None);
+
+        provider.azure = Some(Default::default());
+        assert_eq!(cache.token(&provider).await.expect("token"), None);
+    }
+
+    #[tokio::test]
+    async fn cleared_tokens_are_fetched_again() {
+        let mut provider = crate::model_provider_info::create_oss_provider_with_base_url(
+            "https://xxxxx.openai.azure.com/openai/v1",
+            crate::model_provider_info::WireApi::Responses,
+        );
+        provider.azure = Some(crate::model_provider_info::AzureProviderInfo {
+            auth: AzureAuth::AzureCli,
+            ..Default::default()
+        });
+        let fetches = AtomicUsize::new(0);
+        let fetch = |source| {
+            let fetch = fetches.fetch_add(1, Ordering::SeqCst) + 1;
+            assert_eq!(source, TokenSource::AzureCli);
+            async move { Ok((format!("token-{fetch}"), DEFAULT_TOKEN_LIFETIME)) }
+        };
+        let cache = AzureTokenCache::default();
+
+        let first = cache.token_from(&provider, fetch).await.expect("token");
+        let cached = cache.token_from(&provider, fetch).await.expect("token");
+        cache.clear().await;
+        let refetched = cache.token_from(&provider, fetch).await.expect("token");
+
+        assert_eq!(
+            (first, cached, refetched),
+            (
+                Some("token-1".to_string()),
+                Some("token-1".to_string()),
+                Some("token-2".to_string())
+            )
+        );
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    }
+}
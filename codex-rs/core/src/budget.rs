@@ -0,0 +1,569 @@
+//! Enforcement of the `[budget]` limits from config.toml.
+//!
+//! Session and turn totals are kept in memory by [`BudgetTracker`]. Daily totals are shared by every
+//! session through `~/.codex/budget_usage.json`, which is only read and written when daily limits
+//! are configured. When a limit is crossed the turn pauses with
+//! `EventMsg::BudgetLimitReached` until the user answers with `Op::BudgetApproval`; an approved
+//! limit is not asked about again for the rest of the session, or of the turn for turn limits.
+
+use std::collections::HashSet;
+use std::io::Read;
+use std::io::Result;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use chrono::Local;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::sync::oneshot;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::types::BudgetLimits;
+use crate::config::types::ModelPricing;
+use crate::locked_file::with_locked_append_file;
+use crate::protocol::BudgetKind;
+use crate::protocol::BudgetLimitReachedEvent;
+use crate::protocol::BudgetScope;
+use crate::protocol::EventMsg;
+use crate::protocol::ReviewDecision;
+use crate::protocol::TokenUsage;
+use crate::state::cost_usd;
+
+/// Filename that stores today's budget usage inside `~/.codex`.
+const BUDGET_USAGE_FILENAME: &str = "budget_usage.json";
+
+/// Quantities that budget limits apply to.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) struct BudgetUsage {
+    pub(crate) tokens: i64,
+    pub(crate) cost_usd: f64,
+    pub(crate) wall_clock_secs: u64,
+    pub(crate) exec_calls: u64,
+    #[serde(default)]
+    pub(crate) steps: u64,
+}
+
+impl BudgetUsage {
+    fn add(self, other: Self) -> Self {
+        Self {
+            tokens: self.tokens + other.tokens,
+            cost_usd: self.cost_usd + other.cost_usd,
+            wall_clock_secs: self.wall_clock_secs + other.wall_clock_secs,
+            exec_calls: self.exec_calls + other.exec_calls,
+            steps: self.steps + other.steps,
+        }
+    }
+
+    fn saturating_sub(self, other: Self) -> Self {
+        Self {
+            tokens: self.tokens.saturating_sub(other.tokens),
+            cost_usd: (self.cost_usd - other.cost_usd).max(0.0),
+            wall_clock_secs: self.wall_clock_secs.saturating_sub(other.wall_clock_secs),
+            exec_calls: self.exec_calls.saturating_sub(other.exec_calls),
+            steps: self.steps.saturating_sub(other.steps),
+        }
+    }
+}
+
+/// A limit that the current usage has reached.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct BudgetExceeded {
+    pub(crate) scope: BudgetScope,
+    pub(crate) kind: BudgetKind,
+    pub(crate) used: f64,
+    pub(crate) limit: f64,
+}
+
+/// Session-wide budget counters.
+///
+/// Wall-clock time only advances while a turn is running, so an idle session does not use up
+/// its budget.
+#[derive(Debug, Default)]
+pub(crate) struct BudgetTracker {
+    usage: BudgetUsage,
+    active_time: Duration,
+    clock_started_at: Option<Instant>,
+    /// Session totals already added to the daily ledger.
+    recorded_daily: BudgetUsage,
+    /// Session totals when the current turn started.
+    turn_start: BudgetUsage,
+    acknowledged: HashSet<(BudgetScope, BudgetKind)>,
+}
+
+impl BudgetTracker {
+    pub(crate) fn record_tokens(&mut self, usage: &TokenUsage, pricing: Option<&ModelPricing>) {
+        self.usage.tokens += usage.total_tokens.max(0);
+        if let Some(pricing) = pricing {
+            self.usage.cost_usd += cost_usd(pricing, usage);
+        }
+    }
+
+    pub(crate) fn record_exec_call(&mut self) {
+        self.usage.exec_calls += 1;
+    }
+
+    pub(crate) fn record_step(&mut self) {
+        self.usage.steps += 1;
+    }
+
+    pub(crate) fn start_clock(&mut self, now: Instant) {
+        self.clock_started_at.get_or_insert(now);
+    }
+
+    /// Starts counting turn usage from `now`. Turn limits acknowledged in the previous turn are
+    /// checked again.
+    pub(crate) fn start_turn(&mut self, now: Instant) {
+        self.turn_start = self.session_usage(now);
+        self.acknowledged
+            .retain(|(scope, _)| *scope != BudgetScope::Turn);
+    }
+
+    pub(crate) fn stop_clock(&mut self, now: Instant) {
+        if let Some(started_at) = self.clock_started_at.take() {
+            self.active_time += now.saturating_duration_since(started_at);
+        }
+    }
+
+    fn session_usage(&self, now: Instant) -> BudgetUsage {
+        let running = self
+            .clock_started_at
+            .map(|started_at| now.saturating_duration_since(started_at))
+            .unwrap_or_default();
+        BudgetUsage {
+            wall_clock_secs: (self.active_time + running).as_secs(),
+            ..self.usage
+        }
+    }
+
+    fn turn_usage(&self, now: Instant) -> BudgetUsage {
+        self.session_usage(now).saturating_sub(self.turn_start)
+    }
+
+    /// Returns the session usage at `now` and the part of it not yet added to the daily ledger.
+    fn daily_delta(&self, now: Instant) -> (BudgetUsage, BudgetUsage) {
+        let usage = self.session_usage(now);
+        (usage, usage.saturating_sub(self.recorded_daily))
+    }
+
+    /// Marks session usage up to `usage` as added to the daily ledger.
+    fn mark_daily_recorded(&mut self, usage: BudgetUsage) {
+        self.recorded_daily = usage;
+    }
+
+    fn first_unacknowledged(
+        &self,
+        scope: BudgetScope,
+        limits: &BudgetLimits,
+        usage: &BudgetUsage,
+    ) -> Option<BudgetExceeded> {
+        exceeded_limits(scope, limits, usage)
+            .into_iter()
+            .find(|exceeded| !self.acknowledged.contains(&(exceeded.scope, exceeded.kind)))
+    }
+
+    fn acknowledge(&mut self, exceeded: &BudgetExceeded) {
+        self.acknowledged.insert((exceeded.scope, exceeded.kind));
+    }
+}
+
+/// Lists the limits in `limits` that `usage` has reached.
+pub(crate) fn exceeded_limits(
+    scope: BudgetScope,
+    limits: &BudgetLimits,
+    usage: &BudgetUsage,
+) -> Vec<BudgetExceeded> {
+    [
+        (
+            BudgetKind::Tokens,
+            limits.tokens.map(|limit| limit as f64),
+            usage.tokens as f64,
+        ),
+        (BudgetKind::CostUsd, limits.cost_usd, usage.cost_usd),
+        (
+            BudgetKind::WallClockMinutes,
+            limits.wall_clock_minutes.map(|limit| limit as f64),
+            usage.wall_clock_secs as f64 / 60.0,
+        ),
+        (
+            BudgetKind::ExecCalls,
+            limits.exec_calls.map(|limit| limit as f64),
+            usage.exec_calls as f64,
+        ),
+        (
+            BudgetKind::Steps,
+            limits.steps.map(|limit| limit as f64),
+            usage.steps as f64,
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(kind, limit, used)| {
+        let limit = limit?;
+        (used >= limit).then_some(BudgetExceeded {
+            scope,
+            kind,
+            used,
+            limit,
+        })
+    })
+    .collect()
+}
+
+/// Checks the configured budget and, when a limit has been reached, pauses until the user
+/// decides. Returns the exceeded limit if the user declined to continue.
+pub(crate) async fn enforce_budget(
+    sess: &Session,
+    turn_context: &TurnContext,
+) -> Option<BudgetExceeded> {
+    let budget = turn_context.config.budget;
+    if budget.is_empty() {
+        return None;
+    }
+
+    loop {
+        let exceeded = {
+            let now = Instant::now();
+            let mut tracker = sess.services.budget.lock().await;
+            let turn_usage = tracker.turn_usage(now);
+            let session_usage = tracker.session_usage(now);
+            let mut exceeded = tracker
+                .first_unacknowledged(BudgetScope::Turn, &budget.turn, &turn_usage)
+                .or_else(|| {
+                    tracker.first_unacknowledged(
+                        BudgetScope::Session,
+                        &budget.session,
+                        &session_usage,
+                    )
+                });
+            if exceeded.is_none() && !budget.daily.is_empty() {
+                let (usage, delta) = tracker.daily_delta(now);
+                match record_daily_usage(&turn_context.config.codex_home, delta).await {
+                    Ok(daily_usage) => {
+                        // Only usage that reached the ledger counts as recorded, so a failed
+                        // write is retried with the next check.
+                        tracker.mark_daily_recorded(usage);
+                        exceeded = tracker.first_unacknowledged(
+                            BudgetScope::Daily,
+                            &budget.daily,
+                            &daily_usage,
+                        );
+                    }
+                    Err(err) => warn!("failed to update daily budget usage: {err}"),
+                }
+            }
+            exceeded?
+        };
+
+        if request_budget_approval(sess, turn_context, &exceeded).await {
+            sess.services.budget.lock().await.acknowledge(&exceeded);
+        } else {
+            return Some(exceeded);
+        }
+    }
+}
+
+async fn request_budget_approval(
+    sess: &Session,
+    turn_context: &TurnContext,
+    exceeded: &BudgetExceeded,
+) -> bool {
+    let id = Uuid::new_v4().to_string();
+    let (tx_approve, rx_approve) = oneshot::channel();
+    {
+        let mut active = sess.active_turn.lock().await;
+        if let Some(at) = active.as_mut() {
+            at.turn_state
+                .lock()
+                .await
+                .insert_pending_approval(id.clone(), tx_approve);
+        }
+    }
+    sess.send_event(
+        turn_context,
+        EventMsg::BudgetLimitReached(BudgetLimitReachedEvent {
+            id,
+            turn_id: turn_context.sub_id.clone(),
+            scope: exceeded.scope,
+            kind: exceeded.kind,
+            used: exceeded.used,
+            limit: exceeded.limit,
+        }),
+    )
+    .await;
+    matches!(
+        rx_approve.await.unwrap_or_default(),
+        ReviewDecision::Approved
+            | ReviewDecision::ApprovedForSession
+            | ReviewDecision::ApprovedForProject
+            | ReviewDecision::ApprovedAlways
+    )
+}
+
+/// Message shown when the user declines to continue past a budget limit.
+pub(crate) fn budget_stop_message(exceeded: &BudgetExceeded) -> String {
+    let scope = match exceeded.scope {
+        BudgetScope::Turn => "turn",
+        BudgetScope::Session => "session",
+        BudgetScope::Daily => "daily",
+    };
+    let limit = match exceeded.kind {
+        BudgetKind::Tokens => format!("{} tokens", exceeded.limit),
+        BudgetKind::CostUsd => format!("${:.2}", exceeded.limit),
+        BudgetKind::WallClockMinutes => format!("{} minutes", exceeded.limit),
+        BudgetKind::ExecCalls => format!("{} commands", exceeded.limit),
+        BudgetKind::Steps => format!("{} model requests", exceeded.limit),
+    };
+    format!("Turn stopped: the {scope} budget of {limit} was reached.")
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct DailyLedger {
+    date: Option<NaiveDate>,
+    #[serde(flatten)]
+    usage: BudgetUsage,
+}
+
+fn budget_usage_filepath(codex_home: &Path) -> PathBuf {
+    codex_home.join(BUDGET_USAGE_FILENAME)
+}
+
+/// Adds `delta` to today's totals in the ledger and returns the updated totals. The ledger is
+/// reset when the local date changes.
+pub(crate) async fn record_daily_usage(
+    codex_home: &Path,
+    delta: BudgetUsage,
+) -> Result<BudgetUsage> {
+    tokio::fs::create_dir_all(codex_home).await?;
+    let path = budget_usage_filepath(codex_home);
+    let today = Local::now().date_naive();
+
+    with_locked_append_file(path, move |file| {
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+        let ledger = serde_json::from_str::<DailyLedger>(&contents)
+            .ok()
+            .filter(|ledger| ledger.date == Some(today))
+            .unwrap_or_default();
+        let ledger = DailyLedger {
+            date: Some(today),
+            usage: ledger.usage.add(delta),
+        };
+        let serialized = serde_json::to_string(&ledger)
+            .map_err(|e| std::io::Error::other(format!("failed to serialise budget usage: {e}")))?;
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(serialized.as_bytes())?;
+        file.flush()?;
+        Ok(ledger.usage)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn reports_every_reached_limit() {
+        let limits = BudgetLimits {
+            tokens: Some(1_000),
+            cost_usd: Some(5.0),
+            wall_clock_minutes: Some(10),
+            exec_calls: Some(3),
+            steps: None,
+        };
+        let usage = BudgetUsage {
+            tokens: 1_000,
+            cost_usd: 1.0,
+            wall_clock_secs: 900,
+            exec_calls: 2,
+            steps: 4,
+        };
+
+        assert_eq!(
+            exceeded_limits(BudgetScope::Session, &limits, &usage),
+            vec![
+                BudgetExceeded {
+                    scope: BudgetScope::Session,
+                    kind: BudgetKind::Tokens,
+                    used: 1_000.0,
+                    limit: 1_000.0,
+                },
+                BudgetExceeded {
+                    scope: BudgetScope::Session,
+                    kind: BudgetKind::WallClockMinutes,
+                    used: 15.0,
+                    limit: 10.0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn acknowledged_limits_are_skipped() {
+        let limits = BudgetLimits {
+            tokens: Some(10),
+            exec_calls: Some(1),
+            ..BudgetLimits::default()
+        };
+        let mut tracker = BudgetTracker::default();
+        tracker.record_exec_call();
+        tracker.record_tokens(
+            &TokenUsage {
+                total_tokens: 20,
+                ..TokenUsage::default()
+            },
+            None,
+        );
+        let usage = tracker.session_usage(Instant::now());
+
+        let first = tracker.first_unacknowledged(BudgetScope::Session, &limits, &usage);
+        assert_eq!(
+            first.map(|exceeded| exceeded.kind),
+            Some(BudgetKind::Tokens)
+        );
+        if let Some(first) = first {
+            tracker.acknowledge(&first);
+        }
+        let second = tracker.first_unacknowledged(BudgetScope::Session, &limits, &usage);
+        assert_eq!(
+            second.map(|exceeded| exceeded.kind),
+            Some(BudgetKind::ExecCalls)
+        );
+    }
+
+    #[test]
+    fn daily_delta_is_kept_until_marked_recorded() {
+        let now = Instant::now();
+        let mut tracker = BudgetTracker::default();
+        tracker.record_step();
+        let (_, delta) = tracker.daily_delta(now);
+        assert_eq!(delta.steps, 1);
+
+        // An unrecorded delta, e.g. after a failed ledger write, is reported again.
+        tracker.record_step();
+        let (usage, delta) = tracker.daily_delta(now);
+        assert_eq!(delta.steps, 2);
+
+        tracker.mark_daily_recorded(usage);
+        tracker.record_step();
+        let (_, delta) = tracker.daily_delta(now);
+        assert_eq!(delta.steps, 1);
+    }
+
+    #[test]
+    fn turn_limits_count_from_the_start_of_each_turn() {
+        let limits = BudgetLimits {
+            steps: Some(2),
+            ..BudgetLimits::default()
+        };
+        let now = Instant::now();
+        let mut tracker = BudgetTracker::default();
+        tracker.record_step();
+        tracker.record_step();
+        tracker.start_turn(now);
+        assert_eq!(
+            tracker.first_unacknowledged(BudgetScope::Turn, &limits, &tracker.turn_usage(now)),
+            None
+        );
+
+        tracker.record_step();
+        tracker.record_step();
+        let exceeded =
+            tracker.first_unacknowledged(BudgetScope::Turn, &limits, &tracker.turn_usage(now));
+        assert_eq!(
+            exceeded,
+            Some(BudgetExceeded {
+                scope: BudgetScope::Turn,
+                kind: BudgetKind::Steps,
+                used: 2.0,
+                limit: 2.0,
+            })
+        );
+        if let Some(exceeded) = exceeded {
+            tracker.acknowledge(&exceeded);
+        }
+        assert_eq!(
+            tracker.first_unacknowledged(BudgetScope::Turn, &limits, &tracker.turn_usage(now)),
+            None
+        );
+
+        tracker.start_turn(now);
+        tracker.record_step();
+        tracker.record_step();
+        assert_eq!(
+            tracker
+                .first_unacknowledged(BudgetScope::Turn, &limits, &tracker.turn_usage(now))
+                .map(|exceeded| exceeded.kind),
+            Some(BudgetKind::Steps)
+        );
+    }
+
+    #[tokio::test]
+    async fn daily_usage_accumulates_across_records() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let delta = BudgetUsage {
+            tokens: 100,
+            cost_usd: 0.5,
+            wall_clock_secs: 30,
+            exec_calls: 1,
+            steps: 3,
+        };
+
+        record_daily_usage(codex_home.path(), delta)
+            .await
+            .expect("first record");
+        let total = record_daily_usage(codex_home.path(), delta)
+            .await
+            .expect("second record");
+
+        assert_eq!(
+            total,
+            BudgetUsage {
+                tokens: 200,
+                cost_usd: 1.0,
+                wall_clock_secs: 60,
+                exec_calls: 2,
+                steps: 6,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn daily_usage_resets_on_a_new_day() {
+        let codex_home = TempDir::new().expect("tempdir");
+        std::fs::write(
+            budget_usage_filepath(codex_home.path()),
+            r#"{"date":"2000-01-01","tokens":500,"cost_usd":9.0,"wall_clock_secs":10,"exec_calls":4}"#,
+        )
+        .expect("write ledger");
+
+        let total = record_daily_usage(
+            codex_home.path(),
+            BudgetUsage {
+                tokens: 1,
+                ..BudgetUsage::default()
+            },
+        )
+        .await
+        .expect("record");
+
+        assert_eq!(
+            total,
+            BudgetUsage {
+                tokens: 1,
+                ..BudgetUsage::default()
+            }
+        );
+    }
+}
@@ -29,14 +29,21 @@
 use std::sync::Arc;
 use std::sync::OnceLock;
 use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 
 use crate::api_bridge::CoreAuthProvider;
 use crate::api_bridge::auth_provider_from_auth;
 use crate::api_bridge::map_api_error;
 use crate::auth::UnauthorizedRecovery;
+use crate::azure_auth::AzureTokenCache;
+use crate::secrets::SecretCache;
+use codex_api::AnthropicMessagesClient as ApiAnthropicMessagesClient;
+use codex_api::AnthropicMessagesRequest;
 use codex_api::CompactClient as ApiCompactClient;
 use codex_api::CompactionInput as ApiCompactionInput;
+use codex_api::GeminiClient as ApiGeminiClient;
+use codex_api::GeminiGenerateContentRequest;
 use codex_api::MemoriesClient as ApiMemoriesClient;
 use codex_api::MemorySummarizeInput as ApiMemorySummarizeInput;
 use codex_api::MemorySummarizeOutput as ApiMemorySummarizeOutput;
@@ -90,12 +97,14 @@ use crate::auth::RefreshTokenError;
 use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
 use crate::client_common::ResponseStream;
-use crate::default_client::build_reqwest_client;
 use crate::error::CodexErr;
 use crate::error::Result;
 use crate::flags::CODEX_RS_SSE_FIXTURE;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::WireApi;
+use crate::tool_emulation::emulate_tool_history;
+use crate::tool_emulation::map_emulated_tool_calls;
+use crate::tool_emulation::tool_emulation_instructions;
 use crate::tools::spec::create_tools_json_for_responses_api;
 
 pub const OPENAI_BETA_HEADER: &str = "OpenAI-Beta";
@@ -114,6 +123,14 @@ struct ModelClientState {
     auth_manager: Option<Arc<AuthManager>>,
     conversation_id: ThreadId,
     provider: ModelProviderInfo,
+    /// Providers to switch to, in order, once `provider` is unavailable.
+    fallback_providers: Vec<ModelProviderInfo>,
+    /// Number of fallback providers activated so far; zero means `provider` is still active.
+    active_fallback_provider: AtomicUsize,
+    /// Azure AD token for providers configured with `azure.auth`, refreshed before it expires.
+    azure_tokens: AzureTokenCache,
+    /// API key read from the provider's `api_key`, resolved once per session.
+    provider_secrets: SecretCache,
     session_source: SessionSource,
     model_verbosity: Option<VerbosityConfig>,
     enable_responses_websockets: bool,
@@ -132,6 +149,7 @@ struct CurrentClientSetup {
     auth: Option<CodexAuth>,
     api_provider: codex_api::Provider,
     api_auth: CoreAuthProvider,
+    http_client: reqwest::Client,
 }
 
 /// A session-scoped client for model-provider API calls.
@@ -141,7 +159,8 @@ struct CurrentClientSetup {
 /// fallback state).
 ///
 /// WebSocket fallback is session-scoped: once a turn activates the HTTP fallback, subsequent turns
-/// will also use HTTP for the remainder of the session.
+/// will also use HTTP for the remainder of the session. Provider fallback follows the same rule:
+/// once a turn switches to the next configured fallback provider, the session stays on it.
 ///
 /// Turn-scoped settings (model selection, reasoning controls, telemetry context, and turn
 /// metadata) are passed explicitly to the relevant methods to keep turn lifetime visible at the
@@ -204,6 +223,7 @@ impl ModelClient {
         auth_manager: Option<Arc<AuthManager>>,
         conversation_id: ThreadId,
         provider: ModelProviderInfo,
+        fallback_providers: Vec<ModelProviderInfo>,
         session_source: SessionSource,
         model_verbosity: Option<VerbosityConfig>,
         enable_responses_websockets: bool,
@@ -219,6 +239,10 @@ impl ModelClient {
                 auth_manager,
                 conversation_id,
                 provider,
+                fallback_providers,
+                active_fallback_provider: AtomicUsize::new(0),
+                azure_tokens: AzureTokenCache::default(),
+                provider_secrets: SecretCache::default(),
                 session_source,
                 model_verbosity,
                 enable_responses_websockets,
@@ -262,7 +286,7 @@ impl ModelClient {
             return Ok(Vec::new());
         }
         let client_setup = self.current_client_setup().await?;
-        let transport = ReqwestTransport::new(build_reqwest_client());
+        let transport = ReqwestTransport::new(client_setup.http_client.clone());
         let request_telemetry = Self::build_request_telemetry(otel_manager);
         let client =
             ApiCompactClient::new(transport, client_setup.api_provider, client_setup.api_auth)
@@ -270,7 +294,7 @@ impl ModelClient {
 
         let instructions = prompt.base_instructions.text.clone();
         let payload = ApiCompactionInput {
-            model: &model_info.slug,
+            model: self.provider().request_model(&model_info.slug),
             input: &prompt.input,
             instructions: &instructions,
         };
@@ -300,14 +324,14 @@ impl ModelClient {
         }
 
         let client_setup = self.current_client_setup().await?;
-        let transport = ReqwestTransport::new(build_reqwest_client());
+        let transport = ReqwestTransport::new(client_setup.http_client.clone());
         let request_telemetry = Self::build_request_telemetry(otel_manager);
         let client =
             ApiMemoriesClient::new(transport, client_setup.api_provider, client_setup.api_auth)
                 .with_telemetry(Some(request_telemetry));
 
         let payload = ApiMemorySummarizeInput {
-            model: model_info.slug.clone(),
+            model: self.provider().request_model(&model_info.slug).to_string(),
             raw_memories,
             reasoning: effort.map(|effort| Reasoning {
                 effort: Some(effort),
@@ -347,12 +371,21 @@ impl ModelClient {
         request_telemetry
     }
 
+    /// Returns the provider currently serving requests for this session.
+    fn provider(&self) -> &ModelProviderInfo {
+        match self.state.active_fallback_provider.load(Ordering::Relaxed) {
+            0 => &self.state.provider,
+            n => &self.state.fallback_providers[n - 1],
+        }
+    }
+
     /// Returns whether this session is configured to use Responses-over-WebSocket.
     ///
     /// This combines provider capability and feature gating; both must be true for websocket paths
-    /// to be eligible.
+    /// to be eligible. Providers with custom `tls` settings stay on HTTP, which honors them.
     pub fn responses_websocket_enabled(&self, model_info: &ModelInfo) -> bool {
-        self.state.provider.supports_websockets
+        self.provider().supports_websockets
+            && self.provider().tls.is_none()
             && (self.state.enable_responses_websockets
                 || self.state.enable_responses_websockets_v2
                 || model_info.prefer_websockets)
@@ -378,15 +411,21 @@ impl ModelClient {
             Some(manager) => manager.auth().await,
             None => None,
         };
-        let api_provider = self
-            .state
-            .provider
-            .to_api_provider(auth.as_ref().map(CodexAuth::auth_mode))?;
-        let api_auth = auth_provider_from_auth(auth.clone(), &self.state.provider)?;
+        let provider = self.provider();
+        let api_provider = provider.to_api_provider(auth.as_ref().map(CodexAuth::auth_mode))?;
+        let api_auth = match self.state.azure_tokens.token(provider).await? {
+            Some(token) => CoreAuthProvider::from_bearer_token(token),
+            None => {
+                auth_provider_from_auth(auth.clone(), provider, &self.state.provider_secrets)
+                    .await?
+            }
+        };
+        let http_client = provider.build_http_client()?;
         Ok(CurrentClientSetup {
             auth,
             api_provider,
             api_auth,
+            http_client,
         })
     }
 
@@ -469,9 +508,16 @@ impl ModelClientSession {
         effort: Option<ReasoningEffortConfig>,
         summary: ReasoningSummaryConfig,
     ) -> Result<ResponsesApiRequest> {
-        let instructions = &prompt.base_instructions.text;
-        let input = prompt.get_formatted_input();
-        let tools = create_tools_json_for_responses_api(&prompt.tools)?;
+        let mut instructions = prompt.base_instructions.text.clone();
+        let mut input = prompt.get_formatted_input();
+        let mut tools = create_tools_json_for_responses_api(&prompt.tools)?;
+        if prompt.emulate_tool_calls {
+            if let Some(tool_instructions) = tool_emulation_instructions(&tools) {
+                instructions.push_str(&tool_instructions);
+            }
+            input = emulate_tool_history(input);
+            tools.clear();
+        }
         let default_reasoning_effort = model_info.default_reasoning_level;
         let reasoning = if model_info.supports_reasoning_summaries {
             Some(Reasoning {
@@ -507,17 +553,22 @@ impl ModelClientSession {
         let text = create_text_param_for_request(verbosity, &prompt.output_schema);
         let prompt_cache_key = Some(self.client.state.conversation_id.to_string());
         let request = ResponsesApiRequest {
-            model: model_info.slug.clone(),
-            instructions: instructions.clone(),
+            model: self
+                .client
+                .provider()
+                .request_model(&model_info.slug)
+                .to_string(),
+            instructions,
             input,
             tools,
             tool_choice: "auto".to_string(),
-            parallel_tool_calls: prompt.parallel_tool_calls,
+            parallel_tool_calls: prompt.parallel_tool_calls && !prompt.emulate_tool_calls,
             reasoning,
             store: provider.is_azure_responses_endpoint(),
             stream: true,
             include,
             prompt_cache_key,
+            max_output_tokens: prompt.max_output_tokens,
             text,
         };
         Ok(request)
@@ -708,7 +759,7 @@ impl ModelClientSession {
     fn responses_request_compression(&self, auth: Option<&crate::auth::CodexAuth>) -> Compression {
         if self.client.state.enable_request_compression
             && auth.is_some_and(CodexAuth::is_chatgpt_auth)
-            && self.client.state.provider.is_openai()
+            && self.client.provider().is_openai()
         {
             Compression::Zstd
         } else {
@@ -732,11 +783,9 @@ impl ModelClientSession {
     ) -> Result<ResponseStream> {
         if let Some(path) = &*CODEX_RS_SSE_FIXTURE {
             warn!(path, "Streaming from fixture");
-            let stream = codex_api::stream_from_fixture(
-                path,
-                self.client.state.provider.stream_idle_timeout(),
-            )
-            .map_err(map_api_error)?;
+            let stream =
+                codex_api::stream_from_fixture(path, self.client.provider().stream_idle_timeout())
+                    .map_err(map_api_error)?;
             let (stream, _last_request_rx) = map_response_stream(stream, otel_manager.clone());
             return Ok(stream);
         }
@@ -747,7 +796,7 @@ impl ModelClientSession {
             .map(super::auth::AuthManager::unauthorized_recovery);
         loop {
             let client_setup = self.client.current_client_setup().await?;
-            let transport = ReqwestTransport::new(build_reqwest_client());
+            let transport = ReqwestTransport::new(client_setup.http_client.clone());
             let (request_telemetry, sse_telemetry) = Self::build_streaming_telemetry(otel_manager);
             let compression = self.responses_request_compression(client_setup.auth.as_ref());
             let options = self.build_responses_options(turn_metadata_header, compression);
@@ -775,7 +824,131 @@ impl ModelClientSession {
                 Err(ApiError::Transport(
                     unauthorized_transport @ TransportError::Http { status, .. },
                 )) if status == StatusCode::UNAUTHORIZED => {
-                    handle_unauthorized(unauthorized_transport, &mut auth_recovery).await?;
+                    handle_unauthorized(
+                        unauthorized_transport,
+                        &mut auth_recovery,
+                        &self.client.state.azure_tokens,
+                    )
+                    .await?;
+                    continue;
+                }
+                Err(err) => return Err(map_api_error(err)),
+            }
+        }
+    }
+
+    /// Streams a turn via the Anthropic Messages API.
+    ///
+    /// The Responses request is built as usual and then translated, so history, tools and
+    /// instructions stay in sync with the OpenAI path.
+    async fn stream_anthropic_messages(
+        &self,
+        prompt: &Prompt,
+        model_info: &ModelInfo,
+        otel_manager: &OtelManager,
+        effort: Option<ReasoningEffortConfig>,
+        summary: ReasoningSummaryConfig,
+    ) -> Result<ResponseStream> {
+        let auth_manager = self.client.state.auth_manager.clone();
+        let mut auth_recovery = auth_manager
+            .as_ref()
+            .map(super::auth::AuthManager::unauthorized_recovery);
+        loop {
+            let client_setup = self.client.current_client_setup().await?;
+            let transport = ReqwestTransport::new(client_setup.http_client.clone());
+            let (request_telemetry, sse_telemetry) = Self::build_streaming_telemetry(otel_manager);
+            let request = self.build_responses_request(
+                &client_setup.api_provider,
+                prompt,
+                model_info,
+                effort,
+                summary,
+            )?;
+            let client = ApiAnthropicMessagesClient::new(
+                transport,
+                client_setup.api_provider,
+                client_setup.api_auth,
+            )
+            .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
+            let stream_result = client
+                .stream_request(
+                    AnthropicMessagesRequest::from(&request),
+                    ApiHeaderMap::new(),
+                )
+                .await;
+
+            match stream_result {
+                Ok(stream) => {
+                    let (stream, _) = map_response_stream(stream, otel_manager.clone());
+                    return Ok(stream);
+                }
+                Err(ApiError::Transport(
+                    unauthorized_transport @ TransportError::Http { status, .. },
+                )) if status == StatusCode::UNAUTHORIZED => {
+                    handle_unauthorized(
+                        unauthorized_transport,
+                        &mut auth_recovery,
+                        &self.client.state.azure_tokens,
+                    )
+                    .await?;
+                    continue;
+                }
+                Err(err) => return Err(map_api_error(err)),
+            }
+        }
+    }
+
+    /// Streams a turn via the Gemini `streamGenerateContent` API.
+    ///
+    /// Like the Anthropic path, this translates the Responses request rather than building a
+    /// Gemini request from the prompt directly.
+    async fn stream_gemini_generate_content(
+        &self,
+        prompt: &Prompt,
+        model_info: &ModelInfo,
+        otel_manager: &OtelManager,
+        effort: Option<ReasoningEffortConfig>,
+        summary: ReasoningSummaryConfig,
+    ) -> Result<ResponseStream> {
+        let auth_manager = self.client.state.auth_manager.clone();
+        let mut auth_recovery = auth_manager
+            .as_ref()
+            .map(super::auth::AuthManager::unauthorized_recovery);
+        loop {
+            let client_setup = self.client.current_client_setup().await?;
+            let transport = ReqwestTransport::new(client_setup.http_client.clone());
+            let (request_telemetry, sse_telemetry) = Self::build_streaming_telemetry(otel_manager);
+            let request = self.build_responses_request(
+                &client_setup.api_provider,
+                prompt,
+                model_info,
+                effort,
+                summary,
+            )?;
+            let client =
+                ApiGeminiClient::new(transport, client_setup.api_provider, client_setup.api_auth)
+                    .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
+            let stream_result = client
+                .stream_request(
+                    GeminiGenerateContentRequest::from(&request),
+                    ApiHeaderMap::new(),
+                )
+                .await;
+
+            match stream_result {
+                Ok(stream) => {
+                    let (stream, _) = map_response_stream(stream, otel_manager.clone());
+                    return Ok(stream);
+                }
+                Err(ApiError::Transport(
+                    unauthorized_transport @ TransportError::Http { status, .. },
+                )) if status == StatusCode::UNAUTHORIZED => {
+                    handle_unauthorized(
+                        unauthorized_transport,
+                        &mut auth_recovery,
+                        &self.client.state.azure_tokens,
+                    )
+                    .await?;
                     continue;
                 }
                 Err(err) => return Err(map_api_error(err)),
@@ -832,7 +1005,12 @@ impl ModelClientSession {
                 Err(ApiError::Transport(
                     unauthorized_transport @ TransportError::Http { status, .. },
                 )) if status == StatusCode::UNAUTHORIZED => {
-                    handle_unauthorized(unauthorized_transport, &mut auth_recovery).await?;
+                    handle_unauthorized(
+                        unauthorized_transport,
+                        &mut auth_recovery,
+                        &self.client.state.azure_tokens,
+                    )
+                    .await?;
                     continue;
                 }
                 Err(err) => return Err(map_api_error(err)),
@@ -883,7 +1061,8 @@ impl ModelClientSession {
     /// The caller is responsible for passing per-turn settings explicitly (model selection,
     /// reasoning settings, telemetry context, and turn metadata). This method will prefer the
     /// Responses WebSocket transport when enabled and healthy, and will fall back to the HTTP
-    /// Responses API transport otherwise.
+    /// Responses API transport otherwise. When the prompt emulates tool calls, `<tool_call>`
+    /// blocks in the output are converted into tool call items.
     pub async fn stream(
         &mut self,
         prompt: &Prompt,
@@ -893,12 +1072,13 @@ impl ModelClientSession {
         summary: ReasoningSummaryConfig,
         turn_metadata_header: Option<&str>,
     ) -> Result<ResponseStream> {
-        let wire_api = self.client.state.provider.wire_api;
-        match wire_api {
+        let wire_api = self.client.provider().wire_api;
+        let stream = match wire_api {
             WireApi::Responses => {
                 let websocket_enabled = self.client.responses_websocket_enabled(model_info)
                     && !self.client.websockets_disabled();
 
+                let mut websocket_stream = None;
                 if websocket_enabled {
                     match self
                         .stream_responses_websocket(
@@ -911,26 +1091,56 @@ impl ModelClientSession {
                         )
                         .await?
                     {
-                        WebsocketStreamOutcome::Stream(stream) => return Ok(stream),
+                        WebsocketStreamOutcome::Stream(stream) => websocket_stream = Some(stream),
                         WebsocketStreamOutcome::FallbackToHttp => {
                             self.try_switch_fallback_transport(otel_manager, model_info);
                         }
                     }
                 }
 
-                self.stream_responses_api(
+                match websocket_stream {
+                    Some(stream) => stream,
+                    None => {
+                        self.stream_responses_api(
+                            prompt,
+                            model_info,
+                            otel_manager,
+                            effort,
+                            summary,
+                            turn_metadata_header,
+                        )
+                        .await?
+                    }
+                }
+            }
+            WireApi::Anthropic => {
+                self.stream_anthropic_messages(prompt, model_info, otel_manager, effort, summary)
+                    .await?
+            }
+            WireApi::Gemini => {
+                self.stream_gemini_generate_content(
                     prompt,
                     model_info,
                     otel_manager,
                     effort,
                     summary,
-                    turn_metadata_header,
                 )
-                .await
+                .await?
             }
+        };
+
+        if prompt.emulate_tool_calls {
+            Ok(map_emulated_tool_calls(stream, &prompt.tools))
+        } else {
+            Ok(stream)
         }
     }
 
+    /// Returns the provider currently serving requests, which changes after a provider fallback.
+    pub(crate) fn provider(&self) -> &ModelProviderInfo {
+        self.client.provider()
+    }
+
     /// Permanently disables WebSockets for this Codex session and resets WebSocket state.
     ///
     /// This is used after exhausting the provider retry budget, to force subsequent requests onto
@@ -958,6 +1168,36 @@ impl ModelClientSession {
         }
         activated
     }
+
+    /// Switches this Codex session to the next configured fallback provider and resets WebSocket
+    /// state so the next request connects to the new provider.
+    ///
+    /// Returns the previous and the newly active provider, or `None` when no fallback provider is
+    /// left.
+    pub(crate) fn try_switch_fallback_provider(
+        &mut self,
+        otel_manager: &OtelManager,
+    ) -> Option<(ModelProviderInfo, ModelProviderInfo)> {
+        let state = &self.client.state;
+        let previous = self.client.provider().clone();
+        state
+            .active_fallback_provider
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |active| {
+                (active < state.fallback_providers.len()).then_some(active + 1)
+            })
+            .ok()?;
+        let next = self.client.provider().clone();
+        warn!(
+            "falling back from model provider {} to {}",
+            previous.name, next.name
+        );
+        otel_manager.counter("codex.provider.fallback", 1, &[]);
+
+        self.connection = None;
+        self.websocket_last_request = None;
+        self.websocket_last_response_rx = None;
+        Some((previous, next))
+    }
 }
 
 /// Parses per-turn metadata into an HTTP header value.
@@ -1087,11 +1327,14 @@ where
 /// Handles a 401 response by optionally refreshing ChatGPT tokens once.
 ///
 /// When refresh succeeds, the caller should retry the API call; otherwise
-/// the mapped `CodexErr` is returned to the caller.
+/// the mapped `CodexErr` is returned to the caller. A cached Azure AD token is
+/// dropped either way, so the next request fetches a new one.
 async fn handle_unauthorized(
     transport: TransportError,
     auth_recovery: &mut Option<UnauthorizedRecovery>,
+    azure_tokens: &AzureTokenCache,
 ) -> Result<()> {
+    azure_tokens.clear().await;
     if let Some(recovery) = auth_recovery
         && recovery.has_next()
     {
@@ -1182,6 +1425,7 @@ mod tests {
             None,
             ThreadId::new(),
             provider,
+            Vec::new(),
             session_source,
             None,
             false,
@@ -35,6 +35,10 @@ pub struct Prompt {
     /// Whether parallel tool calls are permitted for this prompt.
     pub(crate) parallel_tool_calls: bool,
 
+    /// Describe tools in the instructions and parse tool calls from the output text instead of
+    /// relying on native tool calling.
+    pub(crate) emulate_tool_calls: bool,
+
     pub base_instructions: BaseInstructions,
 
     /// Optionally specify the personality of the model.
@@ -42,6 +46,9 @@ pub struct Prompt {
 
     /// Optional the output schema for the model's response.
     pub output_schema: Option<Value>,
+
+    /// Upper bound on the tokens the model may generate for this response.
+    pub(crate) max_output_tokens: Option<u64>,
 }
 
 impl Prompt {
@@ -260,6 +267,7 @@ mod tests {
             stream: true,
             include: vec![],
             prompt_cache_key: None,
+            max_output_tokens: None,
             text: Some(TextControls {
                 verbosity: Some(OpenAiVerbosity::Low),
                 format: None,
@@ -301,6 +309,7 @@ mod tests {
             stream: true,
             include: vec![],
             prompt_cache_key: None,
+            max_output_tokens: None,
             text: Some(text_controls),
         };
 
@@ -337,6 +346,7 @@ mod tests {
             stream: true,
             include: vec![],
             prompt_cache_key: None,
+            max_output_tokens: None,
             text: None,
         };
 
@@ -5,6 +5,7 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicU64;
+use std::time::Instant;
 
 use crate::AuthManager;
 use crate::CodexAuth;
@@ -17,6 +18,10 @@ use crate::analytics_client::AnalyticsEventsClient;
 use crate::analytics_client::AppInvocation;
 use crate::analytics_client::build_track_events_context;
 use crate::apps::render_apps_section;
+use crate::auth_expired::request_reauth;
+use crate::budget::BudgetTracker;
+use crate::budget::budget_stop_message;
+use crate::budget::enforce_budget;
 use crate::commit_attribution::commit_message_trailer_instruction;
 use crate::compact;
 use crate::compact::run_inline_auto_compact_task;
@@ -29,8 +34,12 @@ use crate::features::Feature;
 use crate::features::Features;
 use crate::features::maybe_push_unstable_features_warning;
 use crate::models_manager::manager::ModelsManager;
+use crate::output_schema::MAX_FINAL_OUTPUT_RETRIES;
+use crate::output_schema::parse_final_output;
+use crate::output_schema::retry_instructions;
 use crate::parse_command::parse_command;
 use crate::parse_turn_item;
+use crate::pinned_context::PinnedContextItem;
 use crate::rollout::session_index;
 use crate::stream_events_utils::HandleOutputCtx;
 use crate::stream_events_utils::handle_non_tool_response_item;
@@ -40,6 +49,7 @@ use crate::terminal;
 use crate::truncate::TruncationPolicy;
 use crate::turn_metadata::TurnMetadataState;
 use crate::util::error_or_panic;
+use arc_swap::ArcSwap;
 use async_channel::Receiver;
 use async_channel::Sender;
 use codex_hooks::HookEvent;
@@ -67,6 +77,7 @@ use codex_protocol::protocol::FileChange;
 use codex_protocol::protocol::HasLegacyEvent;
 use codex_protocol::protocol::ItemCompletedEvent;
 use codex_protocol::protocol::ItemStartedEvent;
+use codex_protocol::protocol::PinnedContextRolloutItem;
 use codex_protocol::protocol::RawResponseItemEvent;
 use codex_protocol::protocol::ReviewRequest;
 use codex_protocol::protocol::RolloutItem;
@@ -92,6 +103,7 @@ use rmcp::model::RequestId;
 use serde_json;
 use serde_json::Value;
 use tokio::sync::Mutex;
+use tokio::sync::Notify;
 use tokio::sync::RwLock;
 use tokio::sync::oneshot;
 use tokio::sync::watch;
@@ -114,13 +126,18 @@ use crate::client::ModelClientSession;
 use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
 use crate::codex_thread::ThreadConfigSnapshot;
+use crate::command_patterns::CommandPatterns;
 use crate::compact::collect_user_messages;
 use crate::config::CONFIG_TOML_FILE;
 use crate::config::Config;
 use crate::config::Constrained;
+use crate::config::ConstraintError;
 use crate::config::ConstraintResult;
 use crate::config::GhostSnapshotConfig;
 use crate::config::StartedNetworkProxy;
+use crate::config::reload::ConfigReload;
+use crate::config::reload::are_own_writes;
+use crate::config::reload::reload_config;
 use crate::config::resolve_web_search_mode_for_turn;
 use crate::config::types::McpServerConfig;
 use crate::config::types::ShellEnvironmentPolicy;
@@ -140,16 +157,22 @@ pub enum SteerInputError {
 }
 use crate::exec_policy::ExecPolicyUpdateError;
 use crate::feedback_tags;
+use crate::file_excerpt::is_file_excerpt;
+use crate::file_snapshots::FileSnapshots;
 use crate::file_watcher::FileWatcher;
 use crate::file_watcher::FileWatcherEvent;
 use crate::git_info::get_git_repo_root;
 use crate::instructions::UserInstructions;
 use crate::mcp::CODEX_APPS_MCP_SERVER_NAME;
+use crate::mcp::McpCallSlot;
+use crate::mcp::McpResourceInjections;
 use crate::mcp::auth::compute_auth_statuses;
+use crate::mcp::build_mcp_resource_injections;
 use crate::mcp::effective_mcp_servers;
 use crate::mcp::maybe_prompt_and_install_mcp_dependencies;
 use crate::mcp::with_codex_apps_mcp;
 use crate::mcp_connection_manager::McpConnectionManager;
+use crate::mcp_connection_manager::McpSamplingRequest;
 use crate::mcp_connection_manager::filter_codex_apps_mcp_tools_only;
 use crate::mcp_connection_manager::filter_mcp_tools_by_name;
 use crate::mcp_connection_manager::filter_non_codex_apps_mcp_tools_only;
@@ -159,6 +182,7 @@ use crate::mentions::build_skill_name_counts;
 use crate::mentions::collect_explicit_app_ids;
 use crate::mentions::collect_tool_mentions_from_messages;
 use crate::project_doc::get_user_instructions;
+use crate::project_doc::loaded_project_doc_paths;
 use crate::proposed_plan_parser::ProposedPlanParser;
 use crate::proposed_plan_parser::ProposedPlanSegment;
 use crate::proposed_plan_parser::extract_proposed_plan_text;
@@ -173,11 +197,14 @@ use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecApprovalRequestEvent;
 use crate::protocol::McpServerRefreshConfig;
+use crate::protocol::ModelProviderFallbackEvent;
 use crate::protocol::ModelRerouteEvent;
 use crate::protocol::ModelRerouteReason;
 use crate::protocol::NetworkApprovalContext;
 use crate::protocol::Op;
 use crate::protocol::PlanDeltaEvent;
+use crate::protocol::ProjectDocsLoadedEvent;
+use crate::protocol::QueuedUserInput;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::ReasoningContentDeltaEvent;
 use crate::protocol::ReasoningRawContentDeltaEvent;
@@ -192,12 +219,19 @@ use crate::protocol::SkillInterface as ProtocolSkillInterface;
 use crate::protocol::SkillMetadata as ProtocolSkillMetadata;
 use crate::protocol::SkillToolDependency as ProtocolSkillToolDependency;
 use crate::protocol::StreamErrorEvent;
+use crate::protocol::StreamRetryEvent;
 use crate::protocol::Submission;
 use crate::protocol::TokenCountEvent;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
+use crate::protocol::TurnAbortedEvent;
 use crate::protocol::TurnDiffEvent;
+use crate::protocol::TurnStructuredOutputEvent;
+use crate::protocol::UsageReport;
+use crate::protocol::UserInputQueueUpdatedEvent;
 use crate::protocol::WarningEvent;
+use crate::protocol::WorktreeCreatedEvent;
+use crate::redaction::SecretRedactor;
 use crate::rollout::RolloutRecorder;
 use crate::rollout::RolloutRecorderParams;
 use crate::rollout::map_session_init_error;
@@ -226,6 +260,8 @@ use crate::tasks::RegularTask;
 use crate::tasks::ReviewTask;
 use crate::tasks::SessionTask;
 use crate::tasks::SessionTaskContext;
+use crate::tasks::TURN_ABORTED_UNFINISHED_GUIDANCE;
+use crate::tasks::turn_aborted_marker;
 use crate::tools::ToolRouter;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::handlers::SEARCH_TOOL_BM25_TOOL_NAME;
@@ -237,12 +273,16 @@ use crate::tools::parallel::ToolCallRuntime;
 use crate::tools::sandboxing::ApprovalStore;
 use crate::tools::spec::ToolsConfig;
 use crate::tools::spec::ToolsConfigParams;
+use crate::trusted_commands::TrustScope;
+use crate::trusted_commands::is_command_trusted;
+use crate::trusted_commands::remember_trusted_command;
 use crate::turn_diff_tracker::TurnDiffTracker;
 use crate::unified_exec::UnifiedExecProcessManager;
-use crate::util::backoff;
 use crate::windows_sandbox::WindowsSandboxLevelExt;
+use crate::workspace_index::SessionWorkspaceIndex;
 use crate::zsh_exec_bridge::ZshExecBridge;
 use codex_async_utils::OrCancelExt;
+use codex_git::create_session_worktree;
 use codex_otel::OtelManager;
 use codex_otel::TelemetryAuthMode;
 use codex_protocol::config_types::CollaborationMode;
@@ -325,9 +365,12 @@ impl Codex {
         let user_instructions =
             get_user_instructions(&config, Some(&allowed_skills_for_implicit_invocation)).await;
 
+        let command_patterns = CommandPatterns::new(&config.approval_patterns)
+            .map_err(|err| CodexErr::Fatal(format!("invalid approval pattern: {err}")))?;
         let exec_policy = ExecPolicyManager::load(&config.config_layer_stack)
             .await
-            .map_err(|err| CodexErr::Fatal(format!("failed to load rules: {err}")))?;
+            .map_err(|err| CodexErr::Fatal(format!("failed to load rules: {err}")))?
+            .with_command_patterns(command_patterns);
 
         let config = Arc::new(config);
         let _ = models_manager
@@ -527,6 +570,9 @@ pub(crate) struct Session {
     pub(crate) services: SessionServices,
     js_repl: Arc<JsReplHandle>,
     next_internal_sub_id: AtomicU64,
+    /// Signalled when a turn ends, so the submission loop can start the next queued
+    /// `Op::UserInput`.
+    queued_user_input_ready: Notify,
 }
 /// The context needed for a single turn of the thread.
 #[derive(Debug)]
@@ -609,7 +655,9 @@ impl TurnContext {
             features: &features,
             web_search_mode: self.tools_config.web_search_mode,
         })
-        .with_agent_roles(config.agent_roles.clone());
+        .with_agent_roles(config.agent_roles.clone())
+        .with_blocked_tools(config.blocked_tools.clone())
+        .with_collaboration_mode(collaboration_mode.mode);
 
         Self {
             sub_id: self.sub_id.clone(),
@@ -908,6 +956,12 @@ impl Session {
                         };
                         sess.send_event_raw(event).await;
                     }
+                    Ok(FileWatcherEvent::ConfigChanged { paths }) => {
+                        let Some(sess) = weak_sess.upgrade() else {
+                            break;
+                        };
+                        sess.reload_config_files(&paths).await;
+                    }
                     Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
                     Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
                 }
@@ -915,6 +969,34 @@ impl Session {
         });
     }
 
+    /// Answers sampling requests from MCP servers until the session is dropped.
+    fn start_mcp_sampling_listener(
+        self: &Arc<Self>,
+        rx: async_channel::Receiver<McpSamplingRequest>,
+    ) {
+        let weak_sess = Arc::downgrade(self);
+        tokio::spawn(async move {
+            while let Ok(request) = rx.recv().await {
+                let Some(sess) = weak_sess.upgrade() else {
+                    break;
+                };
+                tokio::spawn(async move {
+                    let McpSamplingRequest {
+                        server_name,
+                        params,
+                        responder,
+                    } = request;
+                    let result =
+                        crate::mcp::handle_mcp_sampling_request(&sess, &server_name, params).await;
+                    if let Err(err) = &result {
+                        warn!("MCP sampling request from {server_name} failed: {err:#}");
+                    }
+                    let _ = responder.send(result);
+                });
+            }
+        });
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn make_turn_context(
         auth_manager: Option<Arc<AuthManager>>,
@@ -939,18 +1021,32 @@ impl Session {
         let otel_manager_for_context = otel_manager;
         let per_turn_config = Arc::new(per_turn_config);
 
+        let collaboration_mode = session_configuration.collaboration_mode.clone();
         let tools_config = ToolsConfig::new(&ToolsConfigParams {
             model_info: &model_info,
             features: &per_turn_config.features,
             web_search_mode: Some(per_turn_config.web_search_mode.value()),
         })
-        .with_agent_roles(per_turn_config.agent_roles.clone());
+        .with_agent_roles(per_turn_config.agent_roles.clone())
+        .with_blocked_tools(per_turn_config.blocked_tools.clone())
+        .with_collaboration_mode(collaboration_mode.mode);
+        // Plan mode is enforced rather than only requested in its instructions: commands run in
+        // a read-only sandbox with no way to escalate, so nothing can change until the user
+        // switches back to an implementation mode.
+        let (approval_policy, sandbox_policy) = if collaboration_mode.mode == ModeKind::Plan {
+            (AskForApproval::Never, SandboxPolicy::new_read_only_policy())
+        } else {
+            (
+                session_configuration.approval_policy.value(),
+                session_configuration.sandbox_policy.get().clone(),
+            )
+        };
 
         let cwd = session_configuration.cwd.clone();
         let turn_metadata_state = Arc::new(TurnMetadataState::new(
             sub_id.clone(),
             cwd.clone(),
-            session_configuration.sandbox_policy.get(),
+            &sandbox_policy,
             session_configuration.windows_sandbox_level,
             per_turn_config
                 .features
@@ -970,10 +1066,10 @@ impl Session {
             developer_instructions: session_configuration.developer_instructions.clone(),
             compact_prompt: session_configuration.compact_prompt.clone(),
             user_instructions: session_configuration.user_instructions.clone(),
-            collaboration_mode: session_configuration.collaboration_mode.clone(),
+            collaboration_mode,
             personality: session_configuration.personality,
-            approval_policy: session_configuration.approval_policy.value(),
-            sandbox_policy: session_configuration.sandbox_policy.get().clone(),
+            approval_policy,
+            sandbox_policy,
             network,
             windows_sandbox_level: session_configuration.windows_sandbox_level,
             shell_environment_policy: per_turn_config.permissions.shell_environment_policy.clone(),
@@ -999,7 +1095,7 @@ impl Session {
         exec_policy: ExecPolicyManager,
         tx_event: Sender<Event>,
         agent_status: watch::Sender<AgentStatus>,
-        initial_history: InitialHistory,
+        mut initial_history: InitialHistory,
         session_source: SessionSource,
         skills_manager: Arc<SkillsManager>,
         file_watcher: Arc<FileWatcher>,
@@ -1019,6 +1115,17 @@ impl Session {
 
         let forked_from_id = initial_history.forked_from_id();
 
+        // Close a turn the process recording the rollout never finished before anything replays
+        // it; the closing items are persisted once the recorder is running.
+        let unfinished_turn_items = match &mut initial_history {
+            InitialHistory::Resumed(resumed) => {
+                let items = unfinished_turn_items(&resumed.history);
+                resumed.history.extend(items.iter().cloned());
+                items
+            }
+            InitialHistory::New | InitialHistory::Forked(_) => Vec::new(),
+        };
+
         let (conversation_id, rollout_params) = match &initial_history {
             InitialHistory::New | InitialHistory::Forked(_) => {
                 let conversation_id = ThreadId::default();
@@ -1052,6 +1159,42 @@ impl Session {
                 ),
             ),
         };
+        // Move the session into its own worktree before anything captures the cwd. Sub-agents
+        // already inherit the parent's worktree as their cwd.
+        let worktree_requested = config.features.enabled(Feature::Worktree)
+            && !matches!(
+                session_configuration.session_source,
+                SessionSource::SubAgent(_)
+            );
+        // A worktree that cannot be created is reported once the session is configured, and the
+        // session edits its working directory directly instead.
+        let mut worktree_error = None;
+        let worktree = if worktree_requested
+            && get_git_repo_root(&session_configuration.cwd).is_some()
+        {
+            let cwd = session_configuration.cwd.clone();
+            let path = config
+                .codex_home
+                .join("worktrees")
+                .join(conversation_id.to_string());
+            let branch = format!("codex/{conversation_id}");
+            match tokio::task::spawn_blocking(move || create_session_worktree(&cwd, &path, &branch))
+                .await?
+            {
+                Ok(worktree) => {
+                    session_configuration.cwd = worktree.cwd_for(&session_configuration.cwd);
+                    Some(worktree)
+                }
+                Err(err) => {
+                    warn!("failed to create session worktree: {err}");
+                    worktree_error = Some(err.to_string());
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
         let state_builder = match &initial_history {
             InitialHistory::Resumed(resumed) => metadata::builder_from_items(
                 resumed.history.as_slice(),
@@ -1152,6 +1295,48 @@ impl Session {
             });
         }
 
+        if let Some(worktree) = &worktree {
+            post_session_configured_events.push(Event {
+                id: INITIAL_SUBMIT_ID.to_owned(),
+                msg: EventMsg::WorktreeCreated(WorktreeCreatedEvent {
+                    path: worktree.path.clone(),
+                    branch: worktree.branch.clone(),
+                    repo_root: worktree.repo_root.clone(),
+                }),
+            });
+        } else if let Some(err) = worktree_error {
+            post_session_configured_events.push(Event {
+                id: "".to_owned(),
+                msg: EventMsg::Warning(WarningEvent {
+                    message: format!(
+                        "Could not create a worktree for this session ({err}); editing {} directly.",
+                        session_configuration.cwd.display()
+                    ),
+                }),
+            });
+        } else if worktree_requested {
+            post_session_configured_events.push(Event {
+                id: "".to_owned(),
+                msg: EventMsg::Warning(WarningEvent {
+                    message: format!(
+                        "The `worktree` feature needs a git repository; editing {} directly.",
+                        session_configuration.cwd.display()
+                    ),
+                }),
+            });
+        }
+
+        match loaded_project_doc_paths(&config).await {
+            Ok(paths) if !paths.is_empty() => {
+                post_session_configured_events.push(Event {
+                    id: INITIAL_SUBMIT_ID.to_owned(),
+                    msg: EventMsg::ProjectDocsLoaded(ProjectDocsLoadedEvent { paths }),
+                });
+            }
+            Ok(_) => {}
+            Err(err) => warn!("failed to load project docs: {err}"),
+        }
+
         let auth = auth.as_ref();
         let auth_mode = auth.map(CodexAuth::auth_mode).map(TelemetryAuthMode::from);
         let otel_manager = OtelManager::new(
@@ -1293,9 +1478,9 @@ impl Session {
                 Arc::clone(&config),
                 Arc::clone(&auth_manager),
             ),
-            hooks: Hooks::new(HooksConfig {
+            hooks: ArcSwap::from_pointee(Hooks::new(HooksConfig {
                 legacy_notify_argv: config.notify.clone(),
-            }),
+            })),
             rollout: Mutex::new(rollout_recorder),
             user_shell: Arc::new(default_shell),
             shell_snapshot_tx,
@@ -1305,16 +1490,22 @@ impl Session {
             otel_manager,
             models_manager: Arc::clone(&models_manager),
             tool_approvals: Mutex::new(ApprovalStore::default()),
+            budget: Mutex::new(BudgetTracker::default()),
+            file_snapshots: Mutex::new(FileSnapshots::default()),
+            secret_redactor: SecretRedactor::new(&config.redaction)?,
             skills_manager,
             file_watcher,
             agent_control,
             network_proxy,
             network_approval: Arc::clone(&network_approval),
             state_db: state_db_ctx.clone(),
+            worktree,
+            workspace_index: SessionWorkspaceIndex::default(),
             model_client: ModelClient::new(
                 Some(Arc::clone(&auth_manager)),
                 conversation_id,
                 session_configuration.provider.clone(),
+                config.model_provider_fallbacks.clone(),
                 session_configuration.session_source.clone(),
                 config.model_verbosity,
                 config.features.enabled(Feature::ResponsesWebsockets)
@@ -1351,6 +1542,7 @@ impl Session {
             services,
             js_repl,
             next_internal_sub_id: AtomicU64::new(0),
+            queued_user_input_ready: Notify::new(),
         });
         if let Some(network_policy_decider_session) = network_policy_decider_session {
             let mut guard = network_policy_decider_session.write().await;
@@ -1360,24 +1552,26 @@ impl Session {
         // Dispatch the SessionConfiguredEvent first and then report any errors.
         // If resuming, include converted initial messages in the payload so UIs can render them immediately.
         let initial_messages = initial_history.get_event_msgs();
+        let session_configured = SessionConfiguredEvent {
+            session_id: conversation_id,
+            forked_from_id,
+            thread_name: session_configuration.thread_name.clone(),
+            model: session_configuration.collaboration_mode.model().to_string(),
+            model_provider_id: config.model_provider_id.clone(),
+            approval_policy: session_configuration.approval_policy.value(),
+            sandbox_policy: session_configuration.sandbox_policy.get().clone(),
+            cwd: session_configuration.cwd.clone(),
+            reasoning_effort: session_configuration.collaboration_mode.reasoning_effort(),
+            history_log_id,
+            history_entry_count,
+            initial_messages,
+            network_proxy: session_network_proxy,
+            rollout_path,
+        };
+        sess.state.lock().await.session_configured = Some(session_configured.clone());
         let events = std::iter::once(Event {
             id: INITIAL_SUBMIT_ID.to_owned(),
-            msg: EventMsg::SessionConfigured(SessionConfiguredEvent {
-                session_id: conversation_id,
-                forked_from_id,
-                thread_name: session_configuration.thread_name.clone(),
-                model: session_configuration.collaboration_mode.model().to_string(),
-                model_provider_id: config.model_provider_id.clone(),
-                approval_policy: session_configuration.approval_policy.value(),
-                sandbox_policy: session_configuration.sandbox_policy.get().clone(),
-                cwd: session_configuration.cwd.clone(),
-                reasoning_effort: session_configuration.collaboration_mode.reasoning_effort(),
-                history_log_id,
-                history_entry_count,
-                initial_messages,
-                network_proxy: session_network_proxy,
-                rollout_path,
-            }),
+            msg: EventMsg::SessionConfigured(session_configured),
         })
         .chain(post_session_configured_events.into_iter());
         for event in events {
@@ -1386,6 +1580,11 @@ impl Session {
 
         // Start the watcher after SessionConfigured so it cannot emit earlier events.
         sess.start_file_watcher_listener();
+        if config.features.enabled(Feature::WorkspaceIndex) {
+            sess.services
+                .workspace_index
+                .warm(&session_configuration.cwd);
+        }
 
         // Construct sandbox_state before initialize() so it can be sent to each
         // MCP server immediately after it becomes ready (avoiding blocking).
@@ -1402,20 +1601,24 @@ impl Session {
             .collect();
         required_mcp_servers.sort();
         let cancel_token = sess.mcp_startup_cancellation_token().await;
+        let (tx_sampling, rx_sampling) = async_channel::unbounded();
+        sess.start_mcp_sampling_listener(rx_sampling);
 
-        sess.services
-            .mcp_connection_manager
-            .write()
-            .await
-            .initialize(
-                &mcp_servers,
-                config.mcp_oauth_credentials_store_mode,
-                auth_statuses.clone(),
-                tx_event.clone(),
-                cancel_token,
-                sandbox_state,
-            )
-            .await;
+        {
+            let mut mcp_connection_manager = sess.services.mcp_connection_manager.write().await;
+            mcp_connection_manager.set_sampling_requests(tx_sampling);
+            mcp_connection_manager.set_max_concurrent_calls(config.mcp_max_concurrent_calls);
+            mcp_connection_manager
+                .initialize(
+                    &mcp_servers,
+                    config.mcp_oauth_credentials_store_mode,
+                    auth_statuses.clone(),
+                    tx_event.clone(),
+                    cancel_token,
+                    sandbox_state,
+                )
+                .await;
+        }
         if !required_mcp_servers.is_empty() {
             let failures = sess
                 .services
@@ -1438,6 +1641,9 @@ impl Session {
 
         // record_initial_history can emit events. We record only after the SessionConfiguredEvent is emitted.
         sess.record_initial_history(initial_history).await;
+        if !unfinished_turn_items.is_empty() {
+            sess.persist_rollout_items(&unfinished_turn_items).await;
+        }
 
         memories::start_memories_startup_task(
             &sess,
@@ -1606,9 +1812,13 @@ impl Session {
                 }
 
                 // Always add response items to conversation history
-                let reconstructed_history = self
+                let mut reconstructed_history = self
                     .reconstruct_history_from_rollout(&turn_context, &rollout_items)
                     .await;
+                self.restore_pinned_context(&rollout_items, &mut reconstructed_history)
+                    .await;
+                self.restore_file_excerpt_in_input(&reconstructed_history)
+                    .await;
                 if !reconstructed_history.is_empty() {
                     self.record_into_history(&reconstructed_history, &turn_context)
                         .await;
@@ -1623,6 +1833,7 @@ impl Session {
                 if let Some(selected_tools) = restored_tool_selection {
                     self.set_mcp_tool_selection(selected_tools).await;
                 }
+                self.restore_session_approvals(&rollout_items).await;
 
                 // Defer seeding the session's initial context until the first turn starts so
                 // turn/start overrides can be merged before we write to the rollout.
@@ -1636,9 +1847,13 @@ impl Session {
                 self.set_previous_model(previous_model).await;
 
                 // Always add response items to conversation history
-                let reconstructed_history = self
+                let mut reconstructed_history = self
                     .reconstruct_history_from_rollout(&turn_context, &rollout_items)
                     .await;
+                self.restore_pinned_context(&rollout_items, &mut reconstructed_history)
+                    .await;
+                self.restore_file_excerpt_in_input(&reconstructed_history)
+                    .await;
                 if !reconstructed_history.is_empty() {
                     self.record_into_history(&reconstructed_history, &turn_context)
                         .await;
@@ -1653,6 +1868,7 @@ impl Session {
                 if let Some(selected_tools) = restored_tool_selection {
                     self.set_mcp_tool_selection(selected_tools).await;
                 }
+                self.restore_session_approvals(&rollout_items).await;
 
                 // If persisting, persist all rollout items as-is (recorder filters)
                 if !rollout_items.is_empty() {
@@ -1687,6 +1903,65 @@ impl Session {
         })
     }
 
+    /// Restores the pins recorded in the rollout and drops their messages from `history`; the
+    /// initial context seeded for this session carries them again, as compaction will.
+    async fn restore_pinned_context(
+        &self,
+        rollout_items: &[RolloutItem],
+        history: &mut Vec<ResponseItem>,
+    ) {
+        let mut state = self.state.lock().await;
+        for item in rollout_items {
+            match item {
+                RolloutItem::PinnedContext(PinnedContextRolloutItem::Pinned {
+                    entry,
+                    contents,
+                    excerpted,
+                }) => state.restore_pinned_context(PinnedContextItem::from_rollout(
+                    entry.clone(),
+                    contents.clone(),
+                    *excerpted,
+                )),
+                RolloutItem::PinnedContext(PinnedContextRolloutItem::Unpinned { id }) => {
+                    state.remove_pinned_context(id);
+                }
+                _ => {}
+            }
+        }
+        let pinned: Vec<ResponseItem> = state
+            .pinned_context()
+            .iter()
+            .map(ResponseItem::from)
+            .collect();
+        history.retain(|item| !pinned.contains(item));
+    }
+
+    /// Offers `read_file_range` again when restored user input carried a file excerpt.
+    async fn restore_file_excerpt_in_input(&self, history: &[ResponseItem]) {
+        let has_excerpt = history.iter().any(|item| {
+            match item {
+            ResponseItem::Message { role, content, .. } if role == "user" => {
+                content.iter().any(|content| {
+                    matches!(content, ContentItem::InputText { text } if is_file_excerpt(text))
+                })
+            }
+            _ => false,
+        }
+        });
+        if has_excerpt {
+            self.state.lock().await.file_excerpt_in_input = true;
+        }
+    }
+
+    async fn restore_session_approvals(&self, rollout_items: &[RolloutItem]) {
+        let mut store = self.services.tool_approvals.lock().await;
+        for item in rollout_items {
+            if let RolloutItem::SessionApproval(approval) = item {
+                store.restore_session_approval(approval.key.clone());
+            }
+        }
+    }
+
     fn last_token_info_from_rollout(rollout_items: &[RolloutItem]) -> Option<TokenUsageInfo> {
         rollout_items.iter().rev().find_map(|item| match item {
             RolloutItem::EventMsg(EventMsg::TokenCount(ev)) => ev.info.clone(),
@@ -1752,6 +2027,22 @@ impl Session {
         state.set_previous_model(previous_model);
     }
 
+    pub(crate) async fn start_usage_turn(&self) {
+        self.state.lock().await.usage.start_turn();
+        let now = Instant::now();
+        let mut budget = self.services.budget.lock().await;
+        budget.start_clock(now);
+        budget.start_turn(now);
+    }
+
+    pub(crate) async fn stop_budget_clock(&self) {
+        self.services.budget.lock().await.stop_clock(Instant::now());
+    }
+
+    pub(crate) async fn usage_report(&self) -> UsageReport {
+        self.state.lock().await.usage.report()
+    }
+
     fn maybe_refresh_shell_snapshot_for_cwd(
         &self,
         previous_cwd: &Path,
@@ -1801,51 +2092,119 @@ impl Session {
         }
     }
 
+    /// Builds the context for a new turn with `updates` applied. When `persist` is false the
+    /// updates apply to this turn only: the session settings are left unchanged and MCP servers
+    /// keep the session sandbox. Emits an error event and returns `None` when the updates are
+    /// rejected.
     pub(crate) async fn new_turn_with_sub_id(
         &self,
         sub_id: String,
         updates: SessionSettingsUpdate,
-    ) -> ConstraintResult<Arc<TurnContext>> {
-        let (session_configuration, sandbox_policy_changed, previous_cwd, codex_home) = {
-            let mut state = self.state.lock().await;
-            match state.session_configuration.clone().apply(&updates) {
-                Ok(next) => {
-                    let previous_cwd = state.session_configuration.cwd.clone();
-                    let sandbox_policy_changed =
-                        state.session_configuration.sandbox_policy != next.sandbox_policy;
-                    let codex_home = next.codex_home.clone();
-                    state.session_configuration = next.clone();
-                    (next, sandbox_policy_changed, previous_cwd, codex_home)
-                }
-                Err(err) => {
-                    drop(state);
-                    self.send_event_raw(Event {
-                        id: sub_id.clone(),
-                        msg: EventMsg::Error(ErrorEvent {
-                            message: err.to_string(),
-                            codex_error_info: Some(CodexErrorInfo::BadRequest),
-                        }),
+        persist: bool,
+    ) -> Option<Arc<TurnContext>> {
+        // Validation may look up model metadata, so it runs before the settings are locked. The
+        // update is then applied to the settings as they are at that point, in one critical
+        // section, so a concurrent update is not lost.
+        let applied = match self.validate_turn_overrides(&updates, persist).await {
+            Ok(()) => {
+                let mut state = self.state.lock().await;
+                let current = state.session_configuration.clone();
+                current
+                    .apply(&updates)
+                    .map(|next| {
+                        if persist {
+                            state.session_configuration = next.clone();
+                        }
+                        (current, next)
                     })
-                    .await;
-                    return Err(err);
-                }
+                    .map_err(|err| err.to_string())
+            }
+            Err(message) => Err(message),
+        };
+        let (current, session_configuration) = match applied {
+            Ok(applied) => applied,
+            Err(message) => {
+                warn!("rejected turn settings: {message}");
+                self.send_event_raw(Event {
+                    id: sub_id,
+                    msg: EventMsg::Error(ErrorEvent {
+                        message,
+                        codex_error_info: Some(CodexErrorInfo::BadRequest),
+                    }),
+                })
+                .await;
+                return None;
             }
         };
 
-        self.maybe_refresh_shell_snapshot_for_cwd(
-            &previous_cwd,
-            &session_configuration.cwd,
-            &codex_home,
-        );
+        let sandbox_state_changed = persist
+            && (current.sandbox_policy != session_configuration.sandbox_policy
+                || current.cwd != session_configuration.cwd);
+        if persist {
+            self.maybe_refresh_shell_snapshot_for_cwd(
+                &current.cwd,
+                &session_configuration.cwd,
+                &session_configuration.codex_home,
+            );
+        }
 
-        Ok(self
-            .new_turn_from_configuration(
+        Some(
+            self.new_turn_from_configuration(
                 sub_id,
                 session_configuration,
                 updates.final_output_json_schema,
-                sandbox_policy_changed,
+                sandbox_state_changed,
             )
-            .await)
+            .await,
+        )
+    }
+
+    /// Rejects a one-turn model or reasoning effort override that the selected model cannot
+    /// honor. `Op::UserTurn` updates are accepted unchecked, as before: clients send the effort
+    /// they last used with every turn.
+    async fn validate_turn_overrides(
+        &self,
+        updates: &SessionSettingsUpdate,
+        persist: bool,
+    ) -> Result<(), String> {
+        if persist {
+            return Ok(());
+        }
+        let Some(collaboration_mode) = &updates.collaboration_mode else {
+            return Ok(());
+        };
+        let model = collaboration_mode.model();
+        if model.trim().is_empty() {
+            return Err(ConstraintError::empty_field("model").to_string());
+        }
+        let Some(effort) = collaboration_mode.reasoning_effort() else {
+            return Ok(());
+        };
+        let next = self
+            .state
+            .lock()
+            .await
+            .session_configuration
+            .apply(updates)
+            .map_err(|err| err.to_string())?;
+        let per_turn_config = Self::build_per_turn_config(&next);
+        let model_info = self
+            .services
+            .models_manager
+            .get_model_info(model, &per_turn_config)
+            .await;
+        let supported = &model_info.supported_reasoning_levels;
+        if supported.is_empty() || supported.iter().any(|preset| preset.effort == effort) {
+            return Ok(());
+        }
+        let supported = supported
+            .iter()
+            .map(|preset| preset.effort.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        Err(format!(
+            "reasoning effort `{effort}` is not supported by model `{model}` (supported: {supported})"
+        ))
     }
 
     async fn new_turn_from_configuration(
@@ -1853,11 +2212,11 @@ impl Session {
         sub_id: String,
         session_configuration: SessionConfiguration,
         final_output_json_schema: Option<Option<Value>>,
-        sandbox_policy_changed: bool,
+        sandbox_state_changed: bool,
     ) -> Arc<TurnContext> {
         let per_turn_config = Self::build_per_turn_config(&session_configuration);
 
-        if sandbox_policy_changed {
+        if sandbox_state_changed {
             let sandbox_state = SandboxState {
                 sandbox_policy: per_turn_config.permissions.sandbox_policy.get().clone(),
                 codex_linux_sandbox_exe: per_turn_config.codex_linux_sandbox_exe.clone(),
@@ -1904,6 +2263,9 @@ impl Session {
         if let Some(final_schema) = final_output_json_schema {
             turn_context.final_output_json_schema = final_schema;
         }
+        // `read_file_range` is only offered once there is an excerpted file to read more of.
+        turn_context.tools_config.read_file_range_tool =
+            self.state.lock().await.has_file_excerpts();
         let turn_context = Arc::new(turn_context);
         turn_context.turn_metadata_state.spawn_git_enrichment_task();
         turn_context
@@ -1984,6 +2346,88 @@ impl Session {
         state.session_configuration.original_config_do_not_use = Arc::new(config);
     }
 
+    /// Applies the edits to the config files that a running session can pick up and tells the
+    /// client what changed. MCP servers are reloaded before the next turn.
+    pub(crate) async fn reload_config_files(&self, paths: &[PathBuf]) {
+        let current = self.get_config().await;
+        let reloaded = match reload_config(&current).await {
+            Ok(reloaded) => reloaded,
+            Err(err) => {
+                self.send_warning(format!("Ignored the edited config: {err}"))
+                    .await;
+                return;
+            }
+        };
+        let mut reload = ConfigReload::between(&current, &reloaded);
+        if are_own_writes(paths) {
+            reload = reload.without_deferred();
+        }
+        if reload.is_empty() {
+            // Nothing to report, but the reloaded files are the baseline for the next reload.
+            let mut state = self.state.lock().await;
+            state.session_configuration.original_config_do_not_use = Arc::new(reload.config);
+            return;
+        }
+        let ConfigReload {
+            config,
+            command_patterns,
+            event,
+            errors,
+        } = reload;
+
+        if event.applied.iter().any(|key| key == "notify") {
+            self.services.hooks.store(Arc::new(Hooks::new(HooksConfig {
+                legacy_notify_argv: config.notify.clone(),
+            })));
+        }
+        if let Some(command_patterns) = command_patterns {
+            self.services
+                .exec_policy
+                .set_command_patterns(command_patterns);
+        }
+        if event.applied.iter().any(|key| key == "mcp_servers") {
+            let refresh_config =
+                serde_json::to_value(config.mcp_servers.get()).and_then(|mcp_servers| {
+                    Ok(McpServerRefreshConfig {
+                        mcp_servers,
+                        mcp_oauth_credentials_store_mode: serde_json::to_value(
+                            config.mcp_oauth_credentials_store_mode,
+                        )?,
+                    })
+                });
+            match refresh_config {
+                Ok(refresh_config) => {
+                    *self.pending_mcp_server_refresh_config.lock().await = Some(refresh_config);
+                }
+                Err(err) => warn!("failed to serialize reloaded MCP servers: {err}"),
+            }
+        }
+        {
+            let mut state = self.state.lock().await;
+            state.session_configuration.original_config_do_not_use = Arc::new(config);
+        }
+
+        for error in errors {
+            self.send_warning(format!("Ignored the edited config: {error}"))
+                .await;
+        }
+        if !event.applied.is_empty() || !event.deferred.is_empty() {
+            self.send_event_raw(Event {
+                id: self.next_internal_sub_id(),
+                msg: EventMsg::ConfigReloaded(event),
+            })
+            .await;
+        }
+    }
+
+    async fn send_warning(&self, message: String) {
+        self.send_event_raw(Event {
+            id: self.next_internal_sub_id(),
+            msg: EventMsg::Warning(WarningEvent { message }),
+        })
+        .await;
+    }
+
     pub(crate) async fn new_default_turn_with_sub_id(&self, sub_id: String) -> Arc<TurnContext> {
         let session_configuration = {
             let state = self.state.lock().await;
@@ -2034,15 +2478,16 @@ impl Session {
 
     /// Persist the event to rollout and send it to clients.
     pub(crate) async fn send_event(&self, turn_context: &TurnContext, msg: EventMsg) {
-        let legacy_source = msg.clone();
+        // Derive the legacy events up front so `msg` can be moved rather than copied; most
+        // events have none.
+        let legacy_events = msg.as_legacy_events(self.show_raw_agent_reasoning());
         let event = Event {
             id: turn_context.sub_id.clone(),
             msg,
         };
         self.send_event_raw(event).await;
 
-        let show_raw_agent_reasoning = self.show_raw_agent_reasoning();
-        for legacy in legacy_source.as_legacy_events(show_raw_agent_reasoning) {
+        for legacy in legacy_events {
             let legacy_event = Event {
                 id: turn_context.sub_id.clone(),
                 msg: legacy,
@@ -2057,8 +2502,7 @@ impl Session {
             self.agent_status.send_replace(status);
         }
         // Persist the event into rollout (recorder filters as needed)
-        let rollout_items = vec![RolloutItem::EventMsg(event.msg.clone())];
-        self.persist_rollout_items(&rollout_items).await;
+        self.persist_rollout_event(&event.msg).await;
         if let Err(e) = self.tx_event.send(event).await {
             debug!("dropping event because channel is closed: {e}");
         }
@@ -2074,8 +2518,7 @@ impl Session {
         if let Some(status) = agent_status_from_event(&event.msg) {
             self.agent_status.send_replace(status);
         }
-        self.persist_rollout_items(&[RolloutItem::EventMsg(event.msg.clone())])
-            .await;
+        self.persist_rollout_event(&event.msg).await;
         self.flush_rollout().await;
         if let Err(e) = self.tx_event.send(event).await {
             debug!("dropping event because channel is closed: {e}");
@@ -2140,6 +2583,12 @@ impl Session {
             .map(|task| Arc::clone(&task.turn_context))
     }
 
+    pub(crate) async fn active_turn_context(&self) -> Option<Arc<TurnContext>> {
+        let active = self.active_turn.lock().await;
+        let (_, task) = active.as_ref()?.tasks.first()?;
+        Some(Arc::clone(&task.turn_context))
+    }
+
     async fn active_turn_context_and_cancellation_token(
         &self,
     ) -> Option<(Arc<TurnContext>, CancellationToken)> {
@@ -2185,7 +2634,8 @@ impl Session {
     ///
     /// The request is keyed by `call_id` + `approval_id` so matching responses are delivered
     /// to the correct in-flight turn. If the task is aborted, this returns the
-    /// default `ReviewDecision` (`Denied`).
+    /// default `ReviewDecision` (`Denied`). `escalated` says whether the command would run outside
+    /// the sandbox once approved; remembered approvals only apply at the same level.
     #[allow(clippy::too_many_arguments)]
     pub async fn request_command_approval(
         &self,
@@ -2194,10 +2644,19 @@ impl Session {
         approval_id: Option<String>,
         command: Vec<String>,
         cwd: PathBuf,
+        escalated: bool,
         reason: Option<String>,
         network_approval_context: Option<NetworkApprovalContext>,
         proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
     ) -> ReviewDecision {
+        // Network approvals are per host, so the trust store only answers command prompts.
+        let codex_home = self.codex_home().await;
+        if network_approval_context.is_none()
+            && is_command_trusted(&codex_home, &command, &cwd, escalated).await
+        {
+            return ReviewDecision::Approved;
+        }
+
         //  command-level approvals use `call_id`.
         // `approval_id` is only present for subcommand callbacks (execve intercept)
         let effective_approval_id = approval_id.clone().unwrap_or_else(|| call_id.clone());
@@ -2218,6 +2677,9 @@ impl Session {
         }
 
         let parsed_cmd = parse_command(&command);
+        let trust_command = network_approval_context
+            .is_none()
+            .then(|| (command.clone(), cwd.clone()));
         let event = EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
             call_id,
             approval_id,
@@ -2230,7 +2692,19 @@ impl Session {
             parsed_cmd,
         });
         self.send_event(turn_context, event).await;
-        rx_approve.await.unwrap_or_default()
+        let decision = rx_approve.await.unwrap_or_default();
+
+        if let Some((command, cwd)) = trust_command
+            && let Some(scope) = TrustScope::for_decision(&decision, &cwd)
+            && let Err(err) =
+                remember_trusted_command(&codex_home, &command, escalated, scope).await
+        {
+            let message = format!("Failed to remember approved command: {err}");
+            warn!("{message}");
+            self.send_event(turn_context, EventMsg::Warning(WarningEvent { message }))
+                .await;
+        }
+        decision
     }
 
     pub async fn request_patch_approval(
@@ -2400,6 +2874,7 @@ impl Session {
         rollout_items: &[RolloutItem],
     ) -> Vec<ResponseItem> {
         let mut history = ContextManager::new();
+        let mut pins: HashMap<String, ResponseItem> = HashMap::new();
         for item in rollout_items {
             match item {
                 RolloutItem::ResponseItem(response_item) => {
@@ -2424,6 +2899,30 @@ impl Session {
                 RolloutItem::EventMsg(EventMsg::ThreadRolledBack(rollback)) => {
                     history.drop_last_n_user_turns(rollback.num_turns);
                 }
+                RolloutItem::PinnedContext(PinnedContextRolloutItem::Pinned {
+                    entry,
+                    contents,
+                    excerpted,
+                }) => {
+                    let item = PinnedContextItem::from_rollout(
+                        entry.clone(),
+                        contents.clone(),
+                        *excerpted,
+                    );
+                    pins.insert(entry.id.clone(), ResponseItem::from(&item));
+                }
+                RolloutItem::PinnedContext(PinnedContextRolloutItem::Unpinned { id }) => {
+                    // Replays `handlers::unpin_context`, which drops the pin from the history.
+                    if let Some(pinned) = pins.remove(id) {
+                        let items = history
+                            .raw_items()
+                            .iter()
+                            .filter(|item| **item != pinned)
+                            .cloned()
+                            .collect();
+                        history.replace(items);
+                    }
+                }
                 _ => {}
             }
         }
@@ -2584,12 +3083,26 @@ impl Session {
         {
             items.push(DeveloperInstructions::new(memory_prompt).into());
         }
+        if turn_context.features.enabled(Feature::MemoryTool)
+            && let Some(project_memory_prompt) = build_project_memory_instructions(
+                &turn_context.config.codex_home,
+                &turn_context.cwd,
+            )
+            .await
+        {
+            items.push(DeveloperInstructions::new(project_memory_prompt).into());
+        }
         // Add developer instructions from collaboration_mode if they exist and are non-empty
-        let (collaboration_mode, base_instructions) = {
+        let (collaboration_mode, base_instructions, pinned_context) = {
             let state = self.state.lock().await;
             (
                 state.session_configuration.collaboration_mode.clone(),
                 state.session_configuration.base_instructions.clone(),
+                state
+                    .pinned_context()
+                    .iter()
+                    .map(ResponseItem::from)
+                    .collect::<Vec<_>>(),
             )
         };
         if let Some(collab_instructions) =
@@ -2638,6 +3151,8 @@ impl Session {
             turn_context,
             shell.as_ref(),
         )));
+        // Pinned items ride along with the initial context so every compaction re-attaches them.
+        items.extend(pinned_context);
         items
     }
 
@@ -2653,6 +3168,19 @@ impl Session {
         }
     }
 
+    /// Persists one event; it is only copied when the rollout policy keeps it.
+    async fn persist_rollout_event(&self, msg: &EventMsg) {
+        let recorder = {
+            let guard = self.services.rollout.lock().await;
+            guard.clone()
+        };
+        if let Some(rec) = recorder
+            && let Err(e) = rec.record_event(msg).await
+        {
+            error!("failed to record rollout event: {e:#}");
+        }
+    }
+
     pub(crate) async fn clone_history(&self) -> ContextManager {
         let state = self.state.lock().await;
         state.clone_history()
@@ -2668,11 +3196,40 @@ impl Session {
             if let Some(token_usage) = token_usage {
                 state
                     .update_token_info_from_usage(token_usage, turn_context.model_context_window());
+                let pricing = turn_context
+                    .config
+                    .model_pricing
+                    .get(&turn_context.model_info.slug);
+                state.usage.record(token_usage, pricing);
+                self.services
+                    .budget
+                    .lock()
+                    .await
+                    .record_tokens(token_usage, pricing);
             }
         }
         self.send_token_count_event(turn_context).await;
     }
 
+    /// Counts a model request made outside the conversation, such as an MCP sampling request, in
+    /// the session's usage and budget. The context window usage is left unchanged.
+    pub(crate) async fn record_side_request_usage(
+        &self,
+        turn_context: &TurnContext,
+        token_usage: &TokenUsage,
+    ) {
+        let pricing = turn_context
+            .config
+            .model_pricing
+            .get(&turn_context.model_info.slug);
+        self.state.lock().await.usage.record(token_usage, pricing);
+        self.services
+            .budget
+            .lock()
+            .await
+            .record_tokens(token_usage, pricing);
+    }
+
     pub(crate) async fn recompute_token_usage(&self, turn_context: &TurnContext) {
         let history = self.clone_history().await;
         let base_instructions = self.get_base_instructions().await;
@@ -2833,6 +3390,13 @@ impl Session {
         if !self.enabled(Feature::GhostCommit) {
             return;
         }
+        // A turn that can neither write in its sandbox nor ask to escalate cannot change files,
+        // so there is nothing to checkpoint.
+        if matches!(turn_context.sandbox_policy, SandboxPolicy::ReadOnly { .. })
+            && turn_context.approval_policy == AskForApproval::Never
+        {
+            return;
+        }
         let token = match turn_context.tool_call_gate.subscribe().await {
             Ok(token) => token,
             Err(err) => {
@@ -2888,6 +3452,73 @@ impl Session {
         Ok(active_turn_id.clone())
     }
 
+    /// Queues an `Op::UserInput` submission while a turn is running or earlier input is still
+    /// waiting, so that it starts its own turn later. Returns the submission when it can start
+    /// now.
+    async fn queue_user_input_if_busy(&self, sub: Submission) -> Option<Submission> {
+        let busy = self.active_turn.lock().await.is_some();
+        let sub_id = sub.id.clone();
+        {
+            let mut state = self.state.lock().await;
+            if !busy && state.queued_user_input.is_empty() {
+                return Some(sub);
+            }
+            state.queued_user_input.push_back(sub);
+        }
+        self.send_user_input_queue_updated(sub_id).await;
+        None
+    }
+
+    /// Removes the next queued `Op::UserInput` submission, unless a turn is running.
+    async fn take_next_queued_user_input(&self) -> Option<Submission> {
+        if self.active_turn.lock().await.is_some() {
+            return None;
+        }
+        let sub = self.state.lock().await.queued_user_input.pop_front()?;
+        self.send_user_input_queue_updated(sub.id.clone()).await;
+        Some(sub)
+    }
+
+    /// Drops every queued `Op::UserInput` submission.
+    async fn clear_queued_user_input(&self, sub_id: String) {
+        let cleared = {
+            let mut state = self.state.lock().await;
+            let cleared = !state.queued_user_input.is_empty();
+            state.queued_user_input.clear();
+            cleared
+        };
+        if cleared {
+            self.send_user_input_queue_updated(sub_id).await;
+        }
+    }
+
+    /// Lets the submission loop start the next queued input once the running turn has ended.
+    pub(crate) fn notify_turn_ended(&self) {
+        self.queued_user_input_ready.notify_one();
+    }
+
+    async fn send_user_input_queue_updated(&self, sub_id: String) {
+        let queued = {
+            let state = self.state.lock().await;
+            state
+                .queued_user_input
+                .iter()
+                .filter_map(|sub| match &sub.op {
+                    Op::UserInput { items, .. } => Some(QueuedUserInput {
+                        id: sub.id.clone(),
+                        items: items.clone(),
+                    }),
+                    _ => None,
+                })
+                .collect()
+        };
+        self.send_event_raw(Event {
+            id: sub_id,
+            msg: EventMsg::UserInputQueueUpdated(UserInputQueueUpdatedEvent { queued }),
+        })
+        .await;
+    }
+
     /// Returns the input if there was no task running to inject into
     pub async fn inject_response_items(
         &self,
@@ -2981,6 +3612,29 @@ impl Session {
             .await
     }
 
+    pub(crate) async fn mcp_tool_input_schema(
+        &self,
+        server: &str,
+        tool: &str,
+    ) -> Option<serde_json::Value> {
+        self.services
+            .mcp_connection_manager
+            .read()
+            .await
+            .tool_input_schema(server, tool)
+            .await
+    }
+
+    /// Reserves room for a call to one of `server`'s tools, see
+    /// [`McpConnectionManager::acquire_call_slot`].
+    pub(crate) async fn acquire_mcp_call_slot(&self, server: &str) -> McpCallSlot {
+        self.services
+            .mcp_connection_manager
+            .read()
+            .await
+            .acquire_call_slot(server)
+    }
+
     pub(crate) async fn parse_mcp_tool_name(&self, tool_name: &str) -> Option<(String, String)> {
         self.services
             .mcp_connection_manager
@@ -3000,8 +3654,8 @@ impl Session {
         }
     }
 
-    pub(crate) fn hooks(&self) -> &Hooks {
-        &self.services.hooks
+    pub(crate) fn hooks(&self) -> Arc<Hooks> {
+        self.services.hooks.load_full()
     }
 
     pub(crate) fn user_shell(&self) -> Arc<shell::Shell> {
@@ -3031,9 +3685,12 @@ impl Session {
         };
         let cancel_token = self.reset_mcp_startup_cancellation_token().await;
 
-        let mut refreshed_manager = McpConnectionManager::default();
-        refreshed_manager
-            .initialize(
+        let summary = self
+            .services
+            .mcp_connection_manager
+            .write()
+            .await
+            .reload(
                 &mcp_servers,
                 store_mode,
                 auth_statuses,
@@ -3042,9 +3699,22 @@ impl Session {
                 sandbox_state,
             )
             .await;
-
-        let mut manager = self.services.mcp_connection_manager.write().await;
-        *manager = refreshed_manager;
+        let changes = [
+            ("started", &summary.started),
+            ("restarted", &summary.restarted),
+            ("stopped", &summary.stopped),
+        ]
+        .into_iter()
+        .filter(|(_, servers)| !servers.is_empty())
+        .map(|(action, servers)| format!("{action} {}", servers.join(", ")))
+        .collect::<Vec<_>>();
+        if !changes.is_empty() {
+            self.notify_background_event(
+                turn_context,
+                format!("Reloaded MCP servers: {}", changes.join("; ")),
+            )
+            .await;
+        }
     }
 
     async fn refresh_mcp_servers_if_requested(&self, turn_context: &TurnContext) {
@@ -3124,11 +3794,28 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
     let mut previous_context: Option<Arc<TurnContext>> = Some(sess.new_default_turn().await);
 
     // To break out of this loop, send Op::Shutdown.
-    while let Ok(sub) = rx_sub.recv().await {
+    loop {
+        let sub = tokio::select! {
+            sub = rx_sub.recv() => match sub {
+                Ok(sub) => sub,
+                Err(_) => break,
+            },
+            () = sess.queued_user_input_ready.notified() => {
+                if let Some(sub) = sess.take_next_queued_user_input().await {
+                    handlers::user_input_or_turn(&sess, sub.id, sub.op, &mut previous_context)
+                        .await;
+                    // Keep draining the queue when the input did not start a turn.
+                    if sess.active_turn.lock().await.is_none() {
+                        sess.notify_turn_ended();
+                    }
+                }
+                continue;
+            }
+        };
         debug!(?sub, "Submission");
         match sub.op.clone() {
             Op::Interrupt => {
-                handlers::interrupt(&sess).await;
+                handlers::interrupt(&sess, sub.id.clone()).await;
             }
             Op::CleanBackgroundTerminals => {
                 handlers::clean_background_terminals(&sess).await;
@@ -3170,7 +3857,13 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                 )
                 .await;
             }
-            Op::UserInput { .. } | Op::UserTurn { .. } => {
+            Op::UserInput { .. } => {
+                if let Some(sub) = sess.queue_user_input_if_busy(sub).await {
+                    handlers::user_input_or_turn(&sess, sub.id, sub.op, &mut previous_context)
+                        .await;
+                }
+            }
+            Op::UserTurn { .. } | Op::UserInputWithOverrides { .. } => {
                 handlers::user_input_or_turn(&sess, sub.id.clone(), sub.op, &mut previous_context)
                     .await;
             }
@@ -3184,6 +3877,17 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::PatchApproval { id, decision } => {
                 handlers::patch_approval(&sess, id, decision).await;
             }
+            Op::BudgetApproval { id, decision } => {
+                handlers::patch_approval(&sess, id, decision).await;
+            }
+            Op::ReauthComplete { id, success } => {
+                let decision = if success {
+                    ReviewDecision::Approved
+                } else {
+                    ReviewDecision::Denied
+                };
+                handlers::patch_approval(&sess, id, decision).await;
+            }
             Op::UserInputAnswer { id, response } => {
                 handlers::request_user_input_response(&sess, id, response).await;
             }
@@ -3206,9 +3910,25 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::ReloadUserConfig => {
                 handlers::reload_user_config(&sess).await;
             }
+            Op::ListMcpPrompts => {
+                handlers::list_mcp_prompts(&sess, sub.id.clone()).await;
+            }
+            Op::GetMcpPrompt {
+                server,
+                name,
+                arguments,
+            } => {
+                handlers::get_mcp_prompt(&sess, sub.id.clone(), server, name, arguments).await;
+            }
+            Op::GetMcpServerLogs { server } => {
+                handlers::get_mcp_server_logs(&sess, sub.id.clone(), server).await;
+            }
             Op::ListCustomPrompts => {
                 handlers::list_custom_prompts(&sess, sub.id.clone()).await;
             }
+            Op::GetUsage => {
+                handlers::get_usage(&sess, sub.id.clone()).await;
+            }
             Op::ListSkills { cwds, force_reload } => {
                 handlers::list_skills(&sess, sub.id.clone(), cwds, force_reload).await;
             }
@@ -3233,9 +3953,24 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::Undo => {
                 handlers::undo(&sess, sub.id.clone()).await;
             }
+            Op::RevertToCheckpoint { checkpoint_id } => {
+                handlers::revert_to_checkpoint(&sess, sub.id.clone(), checkpoint_id).await;
+            }
+            Op::MergeWorktree => {
+                handlers::merge_worktree(&sess, sub.id.clone()).await;
+            }
             Op::Compact => {
                 handlers::compact(&sess, sub.id.clone()).await;
             }
+            Op::PinContext { source } => {
+                handlers::pin_context(&sess, sub.id.clone(), source).await;
+            }
+            Op::UnpinContext { id } => {
+                handlers::unpin_context(&sess, sub.id.clone(), id).await;
+            }
+            Op::ListPinnedContext => {
+                handlers::list_pinned_context(&sess, sub.id.clone()).await;
+            }
             Op::DropMemories => {
                 handlers::drop_memories(&sess, &config, sub.id.clone()).await;
             }
@@ -3245,6 +3980,15 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::ThreadRollback { num_turns } => {
                 handlers::thread_rollback(&sess, sub.id.clone(), num_turns).await;
             }
+            Op::ResumeConversation { rollout_path } => {
+                handlers::resume_conversation(&sess, sub.id.clone(), rollout_path).await;
+            }
+            Op::ReplayRollout {
+                rollout_path,
+                speed,
+            } => {
+                handlers::replay_rollout(&sess, sub.id.clone(), rollout_path, speed);
+            }
             Op::SetThreadName { name } => {
                 handlers::set_thread_name(&sess, sub.id.clone(), name).await;
             }
@@ -3261,8 +4005,10 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                 server_name,
                 request_id,
                 decision,
+                content,
             } => {
-                handlers::resolve_elicitation(&sess, server_name, request_id, decision).await;
+                handlers::resolve_elicitation(&sess, server_name, request_id, decision, content)
+                    .await;
             }
             Op::Shutdown => {
                 if handlers::shutdown(&sess, sub.id.clone()).await {
@@ -3291,34 +4037,51 @@ mod handlers {
     use crate::mcp::auth::compute_auth_statuses;
     use crate::mcp::collect_mcp_snapshot_from_manager;
     use crate::mcp::effective_mcp_servers;
+    use crate::pinned_context::PinnedContextItem;
+    use crate::replay;
+    use crate::replay::ReplayOptions;
     use crate::review_prompts::resolve_review_request;
+    use crate::rollout::RolloutRecorder;
     use crate::rollout::session_index;
     use crate::tasks::CompactTask;
     use crate::tasks::UndoTask;
     use crate::tasks::UserShellCommandMode;
     use crate::tasks::UserShellCommandTask;
     use crate::tasks::execute_user_shell_command;
+    use codex_git::merge_session_worktree;
     use codex_protocol::custom_prompts::CustomPrompt;
     use codex_protocol::protocol::CodexErrorInfo;
     use codex_protocol::protocol::ErrorEvent;
     use codex_protocol::protocol::Event;
     use codex_protocol::protocol::EventMsg;
+    use codex_protocol::protocol::GetMcpPromptResponseEvent;
+    use codex_protocol::protocol::GetMcpServerLogsResponseEvent;
+    use codex_protocol::protocol::GetUsageResponseEvent;
+    use codex_protocol::protocol::InitialHistory;
     use codex_protocol::protocol::ListCustomPromptsResponseEvent;
+    use codex_protocol::protocol::ListMcpPromptsResponseEvent;
     use codex_protocol::protocol::ListRemoteSkillsResponseEvent;
     use codex_protocol::protocol::ListSkillsResponseEvent;
     use codex_protocol::protocol::McpServerRefreshConfig;
     use codex_protocol::protocol::Op;
+    use codex_protocol::protocol::PinnedContextRolloutItem;
+    use codex_protocol::protocol::PinnedContextSource;
+    use codex_protocol::protocol::PinnedContextUpdatedEvent;
     use codex_protocol::protocol::RemoteSkillDownloadedEvent;
     use codex_protocol::protocol::RemoteSkillHazelnutScope;
     use codex_protocol::protocol::RemoteSkillProductSurface;
     use codex_protocol::protocol::RemoteSkillSummary;
     use codex_protocol::protocol::ReviewDecision;
     use codex_protocol::protocol::ReviewRequest;
+    use codex_protocol::protocol::RolloutItem;
+    use codex_protocol::protocol::SessionConfiguredEvent;
     use codex_protocol::protocol::SkillsListEntry;
     use codex_protocol::protocol::ThreadNameUpdatedEvent;
     use codex_protocol::protocol::ThreadRolledBackEvent;
     use codex_protocol::protocol::TurnAbortReason;
+    use codex_protocol::protocol::TurnSettingsEvent;
     use codex_protocol::protocol::WarningEvent;
+    use codex_protocol::protocol::WorktreeMergedEvent;
     use codex_protocol::request_user_input::RequestUserInputResponse;
 
     use crate::context_manager::is_user_turn_boundary;
@@ -3327,15 +4090,19 @@ mod handlers {
     use codex_protocol::config_types::Settings;
     use codex_protocol::dynamic_tools::DynamicToolResponse;
     use codex_protocol::mcp::RequestId as ProtocolRequestId;
+    use codex_protocol::models::ResponseItem;
     use codex_protocol::user_input::UserInput;
     use codex_rmcp_client::ElicitationAction;
     use codex_rmcp_client::ElicitationResponse;
+    use rmcp::model::GetPromptRequestParams;
+    use std::collections::HashMap;
     use std::path::PathBuf;
     use std::sync::Arc;
     use tracing::info;
     use tracing::warn;
 
-    pub async fn interrupt(sess: &Arc<Session>) {
+    pub async fn interrupt(sess: &Arc<Session>, sub_id: String) {
+        sess.clear_queued_user_input(sub_id).await;
         sess.interrupt_task().await;
     }
 
@@ -3366,7 +4133,7 @@ mod handlers {
         op: Op,
         previous_context: &mut Option<Arc<TurnContext>>,
     ) {
-        let (items, updates) = match op {
+        let (items, updates, persist) = match op {
             Op::UserTurn {
                 cwd,
                 approval_policy,
@@ -3401,6 +4168,34 @@ mod handlers {
                         final_output_json_schema: Some(final_output_json_schema),
                         personality,
                     },
+                    true,
+                )
+            }
+            Op::UserInputWithOverrides {
+                items,
+                overrides,
+                final_output_json_schema,
+            } => {
+                let collaboration_mode = if overrides.model.is_some() || overrides.effort.is_some()
+                {
+                    Some(sess.current_collaboration_mode().await.with_updates(
+                        overrides.model,
+                        overrides.effort.map(Some),
+                        None,
+                    ))
+                } else {
+                    None
+                };
+                (
+                    items,
+                    SessionSettingsUpdate {
+                        approval_policy: overrides.approval_policy,
+                        sandbox_policy: overrides.sandbox_policy,
+                        collaboration_mode,
+                        final_output_json_schema: Some(final_output_json_schema),
+                        ..Default::default()
+                    },
+                    false,
                 )
             }
             Op::UserInput {
@@ -3412,11 +4207,19 @@ mod handlers {
                     final_output_json_schema: Some(final_output_json_schema),
                     ..Default::default()
                 },
+                true,
             ),
             _ => unreachable!(),
         };
 
-        let Ok(current_context) = sess.new_turn_with_sub_id(sub_id, updates).await else {
+        if items
+            .iter()
+            .any(|item| matches!(item, UserInput::Text { text, .. } if is_file_excerpt(text)))
+        {
+            sess.state.lock().await.file_excerpt_in_input = true;
+        }
+        let Some(current_context) = sess.new_turn_with_sub_id(sub_id, updates, persist).await
+        else {
             // new_turn_with_sub_id already emits the error event.
             return;
         };
@@ -3425,25 +4228,58 @@ mod handlers {
         current_context.otel_manager.user_prompt(&items);
 
         // Attempt to inject input into current task.
-        if let Err(SteerInputError::NoActiveTurn(items)) = sess.steer_input(items, None).await {
-            sess.seed_initial_context_if_needed(&current_context).await;
-            let previous_model = sess.previous_model().await;
-            let update_items = sess.build_settings_update_items(
-                previous_context.as_ref(),
-                previous_model.as_deref(),
-                &current_context,
-            );
-            if !update_items.is_empty() {
-                sess.record_conversation_items(&current_context, &update_items)
+        match sess.steer_input(items, None).await {
+            Err(SteerInputError::NoActiveTurn(items)) => {
+                let settings_changed = previous_context.as_ref().is_some_and(|previous| {
+                    previous.model_info.slug != current_context.model_info.slug
+                        || previous.reasoning_effort != current_context.reasoning_effort
+                        || previous.approval_policy != current_context.approval_policy
+                        || previous.sandbox_policy != current_context.sandbox_policy
+                });
+                if !persist || settings_changed {
+                    sess.send_event(
+                        &current_context,
+                        EventMsg::TurnSettings(TurnSettingsEvent {
+                            turn_id: current_context.sub_id.clone(),
+                            model: current_context.model_info.slug.clone(),
+                            effort: current_context.reasoning_effort,
+                            approval_policy: current_context.approval_policy,
+                            sandbox_policy: current_context.sandbox_policy.clone(),
+                            cwd: current_context.cwd.clone(),
+                            turn_only: !persist,
+                        }),
+                    )
                     .await;
-            }
+                }
+                sess.seed_initial_context_if_needed(&current_context).await;
+                let previous_model = sess.previous_model().await;
+                let update_items = sess.build_settings_update_items(
+                    previous_context.as_ref(),
+                    previous_model.as_deref(),
+                    &current_context,
+                );
+                if !update_items.is_empty() {
+                    sess.record_conversation_items(&current_context, &update_items)
+                        .await;
+                }
 
-            sess.refresh_mcp_servers_if_requested(&current_context)
-                .await;
-            let regular_task = sess.take_startup_regular_task().await.unwrap_or_default();
-            sess.spawn_task(Arc::clone(&current_context), items, regular_task)
+                sess.refresh_mcp_servers_if_requested(&current_context)
+                    .await;
+                let regular_task = sess.take_startup_regular_task().await.unwrap_or_default();
+                sess.spawn_task(Arc::clone(&current_context), items, regular_task)
+                    .await;
+                *previous_context = Some(current_context);
+            }
+            Ok(_) if !persist => {
+                sess.send_event(
+                    &current_context,
+                    EventMsg::Warning(WarningEvent {
+                        message: "Turn overrides were ignored because a turn is already running; the input was added to it.".to_string(),
+                    }),
+                )
                 .await;
-            *previous_context = Some(current_context);
+            }
+            Ok(_) | Err(_) => {}
         }
     }
 
@@ -3485,16 +4321,17 @@ mod handlers {
         server_name: String,
         request_id: ProtocolRequestId,
         decision: codex_protocol::approvals::ElicitationAction,
+        content: Option<serde_json::Value>,
     ) {
         let action = match decision {
             codex_protocol::approvals::ElicitationAction::Accept => ElicitationAction::Accept,
             codex_protocol::approvals::ElicitationAction::Decline => ElicitationAction::Decline,
             codex_protocol::approvals::ElicitationAction::Cancel => ElicitationAction::Cancel,
         };
-        // When accepting, send an empty object as content to satisfy MCP servers
+        // When accepting without content, send an empty object to satisfy MCP servers
         // that expect non-null content on Accept. For Decline/Cancel, content is None.
         let content = match action {
-            ElicitationAction::Accept => Some(serde_json::json!({})),
+            ElicitationAction::Accept => Some(content.unwrap_or_else(|| serde_json::json!({}))),
             ElicitationAction::Decline | ElicitationAction::Cancel => None,
         };
         let response = ElicitationResponse { action, content };
@@ -3657,6 +4494,78 @@ mod handlers {
         sess.send_event_raw(event).await;
     }
 
+    pub async fn list_mcp_prompts(sess: &Session, sub_id: String) {
+        let prompts = {
+            let mcp_connection_manager = sess.services.mcp_connection_manager.read().await;
+            crate::mcp::list_mcp_prompts(&mcp_connection_manager).await
+        };
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::ListMcpPromptsResponse(ListMcpPromptsResponseEvent { prompts }),
+        };
+        sess.send_event_raw(event).await;
+    }
+
+    pub async fn get_mcp_prompt(
+        sess: &Session,
+        sub_id: String,
+        server: String,
+        name: String,
+        arguments: HashMap<String, String>,
+    ) {
+        let params = GetPromptRequestParams {
+            meta: None,
+            name: name.clone(),
+            arguments: (!arguments.is_empty()).then(|| {
+                arguments
+                    .into_iter()
+                    .map(|(key, value)| (key, serde_json::Value::String(value)))
+                    .collect()
+            }),
+        };
+        let result = sess
+            .services
+            .mcp_connection_manager
+            .read()
+            .await
+            .get_prompt(&server, params)
+            .await
+            .map(|result| crate::mcp::prompt_messages_text(&result))
+            .map_err(|err| format!("{err:#}"));
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::GetMcpPromptResponse(GetMcpPromptResponseEvent {
+                server,
+                name,
+                result,
+            }),
+        };
+        sess.send_event_raw(event).await;
+    }
+
+    pub async fn get_mcp_server_logs(sess: &Session, sub_id: String, server: Option<String>) {
+        let logs = sess
+            .services
+            .mcp_connection_manager
+            .read()
+            .await
+            .server_logs(server.as_deref());
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::GetMcpServerLogsResponse(GetMcpServerLogsResponseEvent { logs }),
+        };
+        sess.send_event_raw(event).await;
+    }
+
+    pub async fn get_usage(sess: &Session, sub_id: String) {
+        let usage = sess.usage_report().await;
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::GetUsageResponse(GetUsageResponseEvent { usage }),
+        };
+        sess.send_event_raw(event).await;
+    }
+
     pub async fn list_custom_prompts(sess: &Session, sub_id: String) {
         let custom_prompts: Vec<CustomPrompt> =
             if let Some(dir) = crate::custom_prompts::default_prompts_dir() {
@@ -3797,10 +4706,49 @@ mod handlers {
         }
     }
 
-    pub async fn undo(sess: &Arc<Session>, sub_id: String) {
-        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
-        sess.spawn_task(turn_context, Vec::new(), UndoTask::new())
+    pub async fn undo(sess: &Arc<Session>, sub_id: String) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        sess.spawn_task(turn_context, Vec::new(), UndoTask::new())
+            .await;
+    }
+
+    pub async fn revert_to_checkpoint(sess: &Arc<Session>, sub_id: String, checkpoint_id: String) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        sess.spawn_task(
+            turn_context,
+            Vec::new(),
+            UndoTask::to_checkpoint(checkpoint_id),
+        )
+        .await;
+    }
+
+    pub async fn merge_worktree(sess: &Arc<Session>, sub_id: String) {
+        let Some(worktree) = sess.services.worktree.clone() else {
+            sess.send_event_raw(Event {
+                id: sub_id,
+                msg: EventMsg::Error(ErrorEvent {
+                    message: "This session is not running in a worktree; enable the `worktree` feature to isolate sessions.".to_string(),
+                    codex_error_info: Some(CodexErrorInfo::Other),
+                }),
+            })
             .await;
+            return;
+        };
+        let branch = worktree.branch.clone();
+        let message = format!("codex session {}", sess.conversation_id);
+        let result =
+            tokio::task::spawn_blocking(move || merge_session_worktree(&worktree, &message))
+                .await
+                .map_err(|err| err.to_string())
+                .and_then(|result| result.map_err(|err| err.to_string()));
+        let msg = match result {
+            Ok(commit) => EventMsg::WorktreeMerged(WorktreeMergedEvent { branch, commit }),
+            Err(err) => EventMsg::Error(ErrorEvent {
+                message: format!("failed to merge {branch}: {err}"),
+                codex_error_info: Some(CodexErrorInfo::Other),
+            }),
+        };
+        sess.send_event_raw(Event { id: sub_id, msg }).await;
     }
 
     pub async fn compact(sess: &Arc<Session>, sub_id: String) {
@@ -3818,6 +4766,96 @@ mod handlers {
         .await;
     }
 
+    pub async fn pin_context(sess: &Arc<Session>, sub_id: String, source: PinnedContextSource) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id.clone()).await;
+        let id = { sess.state.lock().await.next_pinned_context_id() };
+        let item = match PinnedContextItem::load(
+            id,
+            source,
+            &turn_context.cwd,
+            turn_context.config.file_excerpt_threshold_bytes,
+        )
+        .await
+        {
+            Ok(item) => item,
+            Err(err) => {
+                sess.send_event_raw(Event {
+                    id: sub_id,
+                    msg: EventMsg::Error(ErrorEvent {
+                        message: format!("Failed to pin context: {err}"),
+                        codex_error_info: Some(CodexErrorInfo::BadRequest),
+                    }),
+                })
+                .await;
+                return;
+            }
+        };
+
+        let response_item = ResponseItem::from(&item);
+        sess.persist_rollout_items(&[RolloutItem::PinnedContext(item.to_rollout_item())])
+            .await;
+        let initial_context_seeded = {
+            let mut state = sess.state.lock().await;
+            state.add_pinned_context(item);
+            state.initial_context_seeded
+        };
+        // Before the first turn the pin is picked up when the initial context is seeded.
+        if initial_context_seeded {
+            sess.record_conversation_items(&turn_context, &[response_item])
+                .await;
+            sess.recompute_token_usage(&turn_context).await;
+        }
+        list_pinned_context(sess, sub_id).await;
+    }
+
+    pub async fn unpin_context(sess: &Arc<Session>, sub_id: String, id: String) {
+        let removed = { sess.state.lock().await.remove_pinned_context(&id) };
+        let Some(removed) = removed else {
+            sess.send_event_raw(Event {
+                id: sub_id,
+                msg: EventMsg::Error(ErrorEvent {
+                    message: format!("No pinned context item with id `{id}`."),
+                    codex_error_info: Some(CodexErrorInfo::BadRequest),
+                }),
+            })
+            .await;
+            return;
+        };
+
+        sess.persist_rollout_items(&[RolloutItem::PinnedContext(
+            PinnedContextRolloutItem::Unpinned { id },
+        )])
+        .await;
+        let pinned_item = ResponseItem::from(&removed);
+        let history = sess.clone_history().await;
+        let items = history
+            .raw_items()
+            .iter()
+            .filter(|item| **item != pinned_item)
+            .cloned()
+            .collect();
+        sess.replace_history(items).await;
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id.clone()).await;
+        sess.recompute_token_usage(&turn_context).await;
+        list_pinned_context(sess, sub_id).await;
+    }
+
+    pub async fn list_pinned_context(sess: &Session, sub_id: String) {
+        let items = {
+            let state = sess.state.lock().await;
+            state
+                .pinned_context()
+                .iter()
+                .map(|item| item.entry.clone())
+                .collect()
+        };
+        sess.send_event_raw(Event {
+            id: sub_id,
+            msg: EventMsg::PinnedContextUpdated(PinnedContextUpdatedEvent { items }),
+        })
+        .await;
+    }
+
     pub async fn drop_memories(sess: &Arc<Session>, config: &Arc<Config>, sub_id: String) {
         let mut errors = Vec::new();
 
@@ -3925,6 +4963,109 @@ mod handlers {
         .await;
     }
 
+    /// Continues the conversation recorded at `rollout_path` in this thread. The restored items
+    /// are copied into this thread's rollout, as for a fork, so the thread keeps its own id.
+    pub async fn resume_conversation(sess: &Arc<Session>, sub_id: String, rollout_path: PathBuf) {
+        let error = |message: String, info: CodexErrorInfo| Event {
+            id: sub_id.clone(),
+            msg: EventMsg::Error(ErrorEvent {
+                message,
+                codex_error_info: Some(info),
+            }),
+        };
+
+        let has_active_turn = { sess.active_turn.lock().await.is_some() };
+        let has_user_turn = sess
+            .clone_history()
+            .await
+            .raw_items()
+            .iter()
+            .any(is_user_turn_boundary);
+        if has_active_turn || has_user_turn {
+            sess.send_event_raw(error(
+                "A conversation can only be resumed before the first turn.".to_string(),
+                CodexErrorInfo::BadRequest,
+            ))
+            .await;
+            return;
+        }
+
+        let mut rollout_items = match RolloutRecorder::get_rollout_history(&rollout_path).await {
+            Ok(InitialHistory::Resumed(resumed)) => resumed.history,
+            Ok(InitialHistory::New | InitialHistory::Forked(_)) => {
+                sess.send_event_raw(error(
+                    format!(
+                        "{} does not contain a conversation to resume.",
+                        rollout_path.display()
+                    ),
+                    CodexErrorInfo::BadRequest,
+                ))
+                .await;
+                return;
+            }
+            Err(err) => {
+                sess.send_event_raw(error(
+                    format!("Failed to read {}: {err}", rollout_path.display()),
+                    CodexErrorInfo::Other,
+                ))
+                .await;
+                return;
+            }
+        };
+        let unfinished_turn = super::unfinished_turn_items(&rollout_items);
+        rollout_items.extend(unfinished_turn);
+
+        let initial_history = InitialHistory::Forked(rollout_items);
+        let initial_messages = initial_history.get_event_msgs();
+        sess.replace_history(Vec::new()).await;
+        sess.record_initial_history(initial_history).await;
+
+        let session_configured = {
+            let state = sess.state.lock().await;
+            state.session_configured.clone()
+        };
+        if let Some(session_configured) = session_configured {
+            sess.send_event_raw(Event {
+                id: sub_id,
+                msg: EventMsg::SessionConfigured(SessionConfiguredEvent {
+                    initial_messages,
+                    ..session_configured
+                }),
+            })
+            .await;
+        }
+    }
+
+    /// Replays in the background so the submission loop stays free for `Op::Interrupt` and
+    /// `Op::Shutdown`; a closed event channel ends the replay.
+    pub fn replay_rollout(
+        sess: &Arc<Session>,
+        sub_id: String,
+        rollout_path: PathBuf,
+        speed: Option<f64>,
+    ) {
+        let tx_event = sess.get_tx_event();
+        let options = ReplayOptions {
+            speed: speed.unwrap_or(1.0),
+            ..Default::default()
+        };
+        tokio::spawn(async move {
+            if let Err(err) =
+                replay::replay_rollout(&rollout_path, options, &sub_id, &tx_event).await
+            {
+                let _ = tx_event
+                    .send(Event {
+                        id: sub_id,
+                        msg: EventMsg::Error(ErrorEvent {
+                            message: format!("Failed to replay {}: {err}", rollout_path.display()),
+                            codex_error_info: Some(CodexErrorInfo::Other),
+                        }),
+                    })
+                    .await;
+            }
+        });
+    }
+
     /// Persists the thread name in the session index, updates in-memory state, and emits
     /// a `ThreadNameUpdated` event on success.
     ///
@@ -4102,7 +5243,8 @@ async fn spawn_review_thread(
         features: &review_features,
         web_search_mode: Some(review_web_search_mode),
     })
-    .with_agent_roles(config.agent_roles.clone());
+    .with_agent_roles(config.agent_roles.clone())
+    .with_blocked_tools(config.blocked_tools.clone());
 
     let review_prompt = resolved.prompt.clone();
     let provider = parent_turn_context.provider.clone();
@@ -4252,6 +5394,29 @@ fn errors_to_info(errors: &[SkillError]) -> Vec<SkillErrorInfo> {
         .collect()
 }
 
+/// Returns the items that close the last turn of `rollout_items` when the process recording it
+/// stopped before the turn completed or was aborted: a model-visible `<turn_aborted>` marker and
+/// a `TurnAborted` event. Returns nothing when the last turn finished.
+fn unfinished_turn_items(rollout_items: &[RolloutItem]) -> Vec<RolloutItem> {
+    let turn_id = rollout_items.iter().rev().find_map(|item| match item {
+        RolloutItem::EventMsg(EventMsg::TurnStarted(started)) => {
+            Some(Some(started.turn_id.clone()))
+        }
+        RolloutItem::EventMsg(EventMsg::TurnComplete(_) | EventMsg::TurnAborted(_)) => Some(None),
+        _ => None,
+    });
+    let Some(Some(turn_id)) = turn_id else {
+        return Vec::new();
+    };
+    vec![
+        RolloutItem::ResponseItem(turn_aborted_marker(TURN_ABORTED_UNFINISHED_GUIDANCE)),
+        RolloutItem::EventMsg(EventMsg::TurnAborted(TurnAbortedEvent {
+            turn_id: Some(turn_id),
+            reason: TurnAbortReason::Interrupted,
+        })),
+    ]
+}
+
 /// Takes a user message as input and runs a loop where, at each sampling request, the model
 /// replies with either:
 ///
@@ -4307,7 +5472,7 @@ pub(crate) async fn run_turn(
             .mcp_connection_manager
             .read()
             .await
-            .list_all_tools()
+            .list_turn_tools()
             .or_cancel(&cancellation_token)
             .await
         {
@@ -4375,6 +5540,15 @@ pub(crate) async fn run_turn(
             .await;
     }
 
+    let McpResourceInjections {
+        items: mcp_resource_items,
+        warnings: mcp_resource_warnings,
+    } = build_mcp_resource_injections(&sess, &turn_context, &input).await;
+    for message in mcp_resource_warnings {
+        sess.send_event(&turn_context, EventMsg::Warning(WarningEvent { message }))
+            .await;
+    }
+
     let mut explicitly_enabled_connectors = collect_explicit_app_ids(&input);
     explicitly_enabled_connectors.extend(collect_explicit_app_ids_from_skill_items(
         &skill_items,
@@ -4410,6 +5584,10 @@ pub(crate) async fn run_turn(
         sess.record_conversation_items(&turn_context, &skill_items)
             .await;
     }
+    if !mcp_resource_items.is_empty() {
+        sess.record_conversation_items(&turn_context, &mcp_resource_items)
+            .await;
+    }
 
     sess.maybe_start_ghost_snapshot(Arc::clone(&turn_context), cancellation_token.child_token())
         .await;
@@ -4418,6 +5596,7 @@ pub(crate) async fn run_turn(
     // many turns, from the perspective of the user, it is a single turn.
     let turn_diff_tracker = Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new()));
     let mut server_model_warning_emitted_for_turn = false;
+    let mut final_output_retries = 0;
 
     // `ModelClientSession` is turn-scoped and caches WebSocket + sticky routing state, so we reuse
     // one instance across retries within this turn.
@@ -4470,6 +5649,19 @@ pub(crate) async fn run_turn(
             })
             .map(|user_message| user_message.message())
             .collect::<Vec<String>>();
+        if let Some(exceeded) = enforce_budget(&sess, &turn_context).await {
+            sess.send_event(
+                &turn_context,
+                EventMsg::Error(ErrorEvent {
+                    message: budget_stop_message(&exceeded),
+                    codex_error_info: None,
+                }),
+            )
+            .await;
+            return None;
+        }
+        sess.services.budget.lock().await.record_step();
+
         let turn_metadata_header = turn_context.turn_metadata_state.current_header_value();
         match run_sampling_request(
             Arc::clone(&sess),
@@ -4515,6 +5707,50 @@ pub(crate) async fn run_turn(
                 }
 
                 if !needs_follow_up {
+                    if let Some(schema) = turn_context.final_output_json_schema.as_ref() {
+                        match parse_final_output(
+                            schema,
+                            sampling_request_last_agent_message.as_deref(),
+                        ) {
+                            Ok(output) => {
+                                sess.send_event(
+                                    &turn_context,
+                                    EventMsg::TurnStructuredOutput(TurnStructuredOutputEvent {
+                                        turn_id: turn_context.sub_id.clone(),
+                                        output,
+                                        retries: final_output_retries,
+                                    }),
+                                )
+                                .await;
+                            }
+                            Err(error) if final_output_retries < MAX_FINAL_OUTPUT_RETRIES => {
+                                final_output_retries += 1;
+                                let message: ResponseItem =
+                                    DeveloperInstructions::new(retry_instructions(&error)).into();
+                                sess.record_conversation_items(
+                                    &turn_context,
+                                    std::slice::from_ref(&message),
+                                )
+                                .await;
+                                continue;
+                            }
+                            Err(error) => {
+                                sess.send_event(
+                                    &turn_context,
+                                    EventMsg::Error(ErrorEvent {
+                                        message: format!(
+                                            "The final message did not match the output schema after {final_output_retries} retries: {error}"
+                                        ),
+                                        codex_error_info: None,
+                                    }),
+                                )
+                                .await;
+                                // The turn failed: report no final message and skip the
+                                // after_agent hooks, as for an aborted completion below.
+                                return None;
+                            }
+                        }
+                    }
                     last_agent_message = sampling_request_last_agent_message;
                     let hook_outcomes = sess
                         .hooks()
@@ -4539,6 +5775,11 @@ pub(crate) async fn run_turn(
                         match hook_outcome.result {
                             HookResult::Success => {}
                             HookResult::FailedContinue(error) => {
+                                turn_context.otel_manager.counter(
+                                    "codex.hook.failure",
+                                    1,
+                                    &[("hook", "after_agent"), ("action", "continue")],
+                                );
                                 warn!(
                                     turn_id = %turn_context.sub_id,
                                     hook_name = %hook_name,
@@ -4547,6 +5788,11 @@ pub(crate) async fn run_turn(
                                 );
                             }
                             HookResult::FailedAbort(error) => {
+                                turn_context.otel_manager.counter(
+                                    "codex.hook.failure",
+                                    1,
+                                    &[("hook", "after_agent"), ("action", "abort")],
+                                );
                                 let message = format!(
                                     "after_agent hook '{hook_name}' failed and aborted turn completion: {error}"
                                 );
@@ -4876,9 +6122,11 @@ async fn run_sampling_request(
         input,
         tools,
         parallel_tool_calls: model_supports_parallel,
+        emulate_tool_calls: turn_context.config.model_supports_tool_calls == Some(false),
         base_instructions,
         personality: turn_context.personality,
         output_schema: turn_context.final_output_json_schema.clone(),
+        max_output_tokens: None,
     };
 
     let mut retries = 0;
@@ -4910,16 +6158,21 @@ async fn run_sampling_request(
                 }
                 return Err(CodexErr::UsageLimitReached(e));
             }
+            Err(CodexErr::RefreshTokenFailed(failed)) => {
+                if request_reauth(&sess, &turn_context, &failed).await {
+                    retries = 0;
+                    continue;
+                }
+                return Err(CodexErr::RefreshTokenFailed(failed));
+            }
             Err(err) => err,
         };
 
-        if !err.is_retryable() {
-            return Err(err);
-        }
-
-        // Use the configured provider-specific stream retry budget.
-        let max_retries = turn_context.provider.stream_max_retries();
-        if retries >= max_retries
+        // Use the retry budget of the provider serving the request, which is a fallback provider
+        // once the session has failed over.
+        let max_retries = client_session.provider().stream_max_retries();
+        if err.is_retryable()
+            && retries >= max_retries
             && client_session
                 .try_switch_fallback_transport(&turn_context.otel_manager, &turn_context.model_info)
         {
@@ -4933,14 +6186,15 @@ async fn run_sampling_request(
             retries = 0;
             continue;
         }
-        if retries < max_retries {
+        let retry_delay = if err.is_retryable() && retries < max_retries {
+            client_session
+                .provider()
+                .retry_delay(retries + 1, err.retry_after())
+        } else {
+            None
+        };
+        if let Some(delay) = retry_delay {
             retries += 1;
-            let delay = match &err {
-                CodexErr::Stream(_, requested_delay) => {
-                    requested_delay.unwrap_or_else(|| backoff(retries))
-                }
-                _ => backoff(retries),
-            };
             warn!(
                 "stream disconnected - retrying sampling request ({retries}/{max_retries} in {delay:?})...",
             );
@@ -4958,14 +6212,41 @@ async fn run_sampling_request(
                 // Surface retry information to any UI/front‑end so the
                 // user understands what is happening instead of staring
                 // at a seemingly frozen screen.
+                let reason = format!("{err:#}");
                 sess.notify_stream_error(
                     &turn_context,
                     format!("Reconnecting... {retries}/{max_retries}"),
                     err,
                 )
                 .await;
+                sess.send_event(
+                    &turn_context,
+                    EventMsg::StreamRetry(StreamRetryEvent {
+                        attempt: retries,
+                        max_attempts: max_retries,
+                        delay_ms: u64::try_from(delay.as_millis()).unwrap_or(u64::MAX),
+                        reason,
+                    }),
+                )
+                .await;
             }
             tokio::time::sleep(delay).await;
+        } else if err.is_provider_unavailable()
+            && let Some((previous, next)) =
+                client_session.try_switch_fallback_provider(&turn_context.otel_manager)
+        {
+            // The turn is replayed from the same prompt, so nothing from the failed attempt is
+            // kept; the new provider starts with a fresh retry budget.
+            sess.send_event(
+                &turn_context,
+                EventMsg::ModelProviderFallback(ModelProviderFallbackEvent {
+                    from_provider: previous.name,
+                    to_provider: next.name,
+                    reason: format!("{err:#}"),
+                }),
+            )
+            .await;
+            retries = 0;
         } else {
             return Err(err);
         }
@@ -4983,7 +6264,7 @@ async fn built_tools(
     let mcp_connection_manager = sess.services.mcp_connection_manager.read().await;
     let has_mcp_servers = mcp_connection_manager.has_servers();
     let mut mcp_tools = mcp_connection_manager
-        .list_all_tools()
+        .list_turn_tools()
         .or_cancel(cancellation_token)
         .await?;
     drop(mcp_connection_manager);
@@ -5486,7 +6767,10 @@ async fn try_run_sampling_request(
             turn_context.reasoning_summary,
             turn_metadata_header,
         )
-        .instrument(trace_span!("stream_request"))
+        .instrument(trace_span!(
+            "stream_request",
+            provider = %turn_context.provider.name,
+        ))
         .or_cancel(&cancellation_token)
         .await??;
 
@@ -5504,7 +6788,13 @@ async fn try_run_sampling_request(
     let mut should_emit_turn_diff = false;
     let plan_mode = turn_context.collaboration_mode.mode == ModeKind::Plan;
     let mut plan_mode_state = plan_mode.then(|| PlanModeStreamState::new(&turn_context.sub_id));
-    let receiving_span = trace_span!("receiving_stream");
+    let receiving_span = trace_span!(
+        "receiving_stream",
+        response_id = field::Empty,
+        input_tokens = field::Empty,
+        cached_input_tokens = field::Empty,
+        output_tokens = field::Empty,
+    );
     let outcome: CodexResult<SamplingRequestResult> = loop {
         let handle_responses = trace_span!(
             parent: &receiving_span,
@@ -5624,10 +6914,27 @@ async fn try_run_sampling_request(
                 sess.services.models_manager.refresh_if_new_etag(etag).await;
             }
             ResponseEvent::Completed {
-                response_id: _,
+                response_id,
                 token_usage,
                 can_append: _,
             } => {
+                receiving_span.record("response_id", response_id.as_str());
+                if let Some(usage) = token_usage.as_ref() {
+                    receiving_span.record("input_tokens", usage.input_tokens);
+                    receiving_span.record("cached_input_tokens", usage.cached_input_tokens);
+                    receiving_span.record("output_tokens", usage.output_tokens);
+                    for (kind, tokens) in [
+                        ("input", usage.input_tokens),
+                        ("cached_input", usage.cached_input_tokens),
+                        ("output", usage.output_tokens),
+                    ] {
+                        turn_context.otel_manager.counter(
+                            "codex.token_usage",
+                            tokens.max(0),
+                            &[("type", kind)],
+                        );
+                    }
+                }
                 if let Some(state) = plan_mode_state.as_mut() {
                     flush_proposed_plan_segments_all(&sess, &turn_context, state).await;
                 }
@@ -5756,6 +7063,7 @@ pub(super) fn get_last_assistant_message_from_turn(responses: &[ResponseItem]) -
     })
 }
 
+use crate::memories::project::build_project_memory_instructions;
 use crate::memories::prompts::build_memory_tool_developer_instructions;
 #[cfg(test)]
 pub(crate) use tests::make_session_and_context;
@@ -5779,6 +7087,7 @@ mod tests {
     use crate::function_tool::FunctionCallError;
     use crate::mcp_connection_manager::ToolInfo;
     use crate::models_manager::model_info;
+    use crate::resource_limits::ResourceLimitKind;
     use crate::shell::default_user_shell;
     use crate::tools::format_exec_output_str;
 
@@ -5789,12 +7098,16 @@ mod tests {
     use crate::protocol::CompactedItem;
     use crate::protocol::CreditsSnapshot;
     use crate::protocol::InitialHistory;
+    use crate::protocol::PinnedContextEntry;
+    use crate::protocol::PinnedContextSource;
     use crate::protocol::RateLimitSnapshot;
     use crate::protocol::RateLimitWindow;
     use crate::protocol::ResumedHistory;
+    use crate::protocol::SessionApprovalItem;
     use crate::protocol::TokenCountEvent;
     use crate::protocol::TokenUsage;
     use crate::protocol::TokenUsageInfo;
+    use crate::protocol::TurnCompleteEvent;
     use crate::state::TaskKind;
     use crate::tasks::SessionTask;
     use crate::tasks::SessionTaskContext;
@@ -6397,6 +7710,20 @@ mod tests {
         assert_eq!(expected, history.raw_items());
     }
 
+    #[tokio::test]
+    async fn record_initial_history_restores_file_excerpts_in_input() {
+        let (session, _turn_context) = make_session_and_context().await;
+        let contents: String = (1..=100).map(|n| format!("fn f{n}() {{}}\n")).collect();
+        let excerpt = crate::file_excerpt::excerpt_file(Path::new("/repo/big.rs"), &contents, &[]);
+        let rollout_items = vec![RolloutItem::ResponseItem(user_message(&excerpt))];
+
+        session
+            .record_initial_history(InitialHistory::Forked(rollout_items))
+            .await;
+
+        assert!(session.state.lock().await.has_file_excerpts());
+    }
+
     #[tokio::test]
     async fn record_initial_history_resumed_hydrates_previous_model() {
         let (session, turn_context) = make_session_and_context().await;
@@ -6647,6 +7974,131 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn record_initial_history_restores_session_approvals() {
+        let (session, _turn_context) = make_session_and_context().await;
+        let key = serde_json::json!({ "command": ["cargo", "test"], "cwd": "/repo" });
+
+        session
+            .record_initial_history(InitialHistory::Resumed(ResumedHistory {
+                conversation_id: ThreadId::default(),
+                history: vec![RolloutItem::SessionApproval(SessionApprovalItem {
+                    key: serde_json::to_string(&key).expect("serialize key"),
+                })],
+                rollout_path: PathBuf::from("/tmp/resume.jsonl"),
+            }))
+            .await;
+
+        let store = session.services.tool_approvals.lock().await;
+        assert_eq!(store.get(&key), Some(ReviewDecision::ApprovedForSession));
+    }
+
+    #[tokio::test]
+    async fn record_initial_history_restores_pinned_context() {
+        let (session, turn_context) = make_session_and_context().await;
+        let kept = text_pin("pin-1", "keep the core crate sync-free");
+        let removed = text_pin("pin-2", "no new globals");
+
+        session
+            .record_initial_history(InitialHistory::Resumed(ResumedHistory {
+                conversation_id: ThreadId::default(),
+                history: vec![
+                    RolloutItem::PinnedContext(kept.to_rollout_item()),
+                    RolloutItem::ResponseItem(ResponseItem::from(&kept)),
+                    RolloutItem::PinnedContext(removed.to_rollout_item()),
+                    RolloutItem::ResponseItem(ResponseItem::from(&removed)),
+                    RolloutItem::PinnedContext(PinnedContextRolloutItem::Unpinned {
+                        id: "pin-2".to_string(),
+                    }),
+                ],
+                rollout_path: PathBuf::from("/tmp/resume.jsonl"),
+            }))
+            .await;
+
+        // The pin is re-seeded with the initial context instead of kept in the resumed history.
+        let history = session.state.lock().await.clone_history();
+        assert!(!history.raw_items().contains(&ResponseItem::from(&kept)));
+
+        let compacted = compact::build_compacted_history(
+            session.build_initial_context(&turn_context).await,
+            &[],
+            "summary",
+        );
+        assert!(compacted.contains(&ResponseItem::from(&kept)));
+        assert!(!compacted.contains(&ResponseItem::from(&removed)));
+
+        let mut state = session.state.lock().await;
+        assert_eq!(state.next_pinned_context_id(), "pin-3");
+    }
+
+    fn text_pin(id: &str, text: &str) -> PinnedContextItem {
+        PinnedContextItem::from_rollout(
+            PinnedContextEntry {
+                id: id.to_string(),
+                source: PinnedContextSource::Text {
+                    text: text.to_string(),
+                },
+            },
+            text.to_string(),
+            false,
+        )
+    }
+
+    #[tokio::test]
+    async fn reconstructed_history_drops_unpinned_context() {
+        let (session, turn_context) = make_session_and_context().await;
+        let kept = text_pin("pin-1", "keep the core crate sync-free");
+        let removed = text_pin("pin-2", "no new globals");
+        let rollout_items = vec![
+            RolloutItem::PinnedContext(kept.to_rollout_item()),
+            RolloutItem::ResponseItem(ResponseItem::from(&kept)),
+            RolloutItem::PinnedContext(removed.to_rollout_item()),
+            RolloutItem::ResponseItem(ResponseItem::from(&removed)),
+            RolloutItem::PinnedContext(PinnedContextRolloutItem::Unpinned {
+                id: "pin-2".to_string(),
+            }),
+        ];
+
+        let history = session
+            .reconstruct_history_from_rollout(&turn_context, &rollout_items)
+            .await;
+
+        assert_eq!(history, vec![ResponseItem::from(&kept)]);
+    }
+
+    #[test]
+    fn unfinished_turn_items_close_only_an_unfinished_last_turn() {
+        let started = |turn_id: &str| {
+            RolloutItem::EventMsg(EventMsg::TurnStarted(TurnStartedEvent {
+                turn_id: turn_id.to_string(),
+                model_context_window: None,
+                collaboration_mode_kind: ModeKind::default(),
+            }))
+        };
+        let completed = RolloutItem::EventMsg(EventMsg::TurnComplete(TurnCompleteEvent {
+            turn_id: "turn-1".to_string(),
+            last_agent_message: None,
+            usage: None,
+        }));
+
+        assert!(unfinished_turn_items(&[started("turn-1"), completed.clone()]).is_empty());
+
+        let items = unfinished_turn_items(&[started("turn-1"), completed, started("turn-2")]);
+        let [
+            RolloutItem::ResponseItem(marker),
+            RolloutItem::EventMsg(EventMsg::TurnAborted(aborted)),
+        ] = items.as_slice()
+        else {
+            panic!("expected a marker and an abort event, got {items:?}");
+        };
+        assert_eq!(
+            marker,
+            &turn_aborted_marker(TURN_ABORTED_UNFINISHED_GUIDANCE)
+        );
+        assert_eq!(aborted.turn_id.as_deref(), Some("turn-2"));
+        assert_eq!(aborted.reason, TurnAbortReason::Interrupted);
+    }
+
     #[tokio::test]
     async fn thread_rollback_drops_last_turn_from_history() {
         let (sess, tc, rx) = make_session_and_context_with_rx().await;
@@ -7005,6 +8457,7 @@ mod tests {
             aggregated_output: StreamOutput::new("Command output".to_string()),
             duration: StdDuration::from_secs(1),
             timed_out: true,
+            resource_limit_exceeded: None,
         };
         let (_, turn_context) = make_session_and_context().await;
 
@@ -7016,6 +8469,24 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn includes_resource_limit_message() {
+        let exec = ExecToolCallOutput {
+            exit_code: 137,
+            aggregated_output: StreamOutput::new("downloading...".to_string()),
+            resource_limit_exceeded: Some(ResourceLimitKind::OutputSize),
+            ..Default::default()
+        };
+        let (_, turn_context) = make_session_and_context().await;
+
+        let out = format_exec_output_str(&exec, turn_context.truncation_policy);
+
+        assert_eq!(
+            out,
+            "command stopped after exceeding the output size limit\ndownloading..."
+        );
+    }
+
     #[tokio::test]
     async fn turn_context_with_model_updates_model_fields() {
         let (session, mut turn_context) = make_session_and_context().await;
@@ -7382,9 +8853,9 @@ mod tests {
                 Arc::clone(&config),
                 Arc::clone(&auth_manager),
             ),
-            hooks: Hooks::new(HooksConfig {
+            hooks: ArcSwap::from_pointee(Hooks::new(HooksConfig {
                 legacy_notify_argv: config.notify.clone(),
-            }),
+            })),
             rollout: Mutex::new(None),
             user_shell: Arc::new(default_user_shell()),
             shell_snapshot_tx: watch::channel(None).0,
@@ -7394,16 +8865,22 @@ mod tests {
             otel_manager: otel_manager.clone(),
             models_manager: Arc::clone(&models_manager),
             tool_approvals: Mutex::new(ApprovalStore::default()),
+            budget: Mutex::new(BudgetTracker::default()),
+            file_snapshots: Mutex::new(FileSnapshots::default()),
+            secret_redactor: SecretRedactor::default(),
             skills_manager,
             file_watcher,
             agent_control,
             network_proxy: None,
             network_approval: Arc::clone(&network_approval),
             state_db: None,
+            worktree: None,
+            workspace_index: SessionWorkspaceIndex::default(),
             model_client: ModelClient::new(
                 Some(auth_manager.clone()),
                 conversation_id,
                 session_configuration.provider.clone(),
+                config.model_provider_fallbacks.clone(),
                 session_configuration.session_source.clone(),
                 config.model_verbosity,
                 model_info.prefer_websockets
@@ -7443,6 +8920,7 @@ mod tests {
             services,
             js_repl,
             next_internal_sub_id: AtomicU64::new(0),
+            queued_user_input_ready: Notify::new(),
         };
 
         (session, turn_context)
@@ -7531,9 +9009,9 @@ mod tests {
                 Arc::clone(&config),
                 Arc::clone(&auth_manager),
             ),
-            hooks: Hooks::new(HooksConfig {
+            hooks: ArcSwap::from_pointee(Hooks::new(HooksConfig {
                 legacy_notify_argv: config.notify.clone(),
-            }),
+            })),
             rollout: Mutex::new(None),
             user_shell: Arc::new(default_user_shell()),
             shell_snapshot_tx: watch::channel(None).0,
@@ -7543,16 +9021,22 @@ mod tests {
             otel_manager: otel_manager.clone(),
             models_manager: Arc::clone(&models_manager),
             tool_approvals: Mutex::new(ApprovalStore::default()),
+            budget: Mutex::new(BudgetTracker::default()),
+            file_snapshots: Mutex::new(FileSnapshots::default()),
+            secret_redactor: SecretRedactor::default(),
             skills_manager,
             file_watcher,
             agent_control,
             network_proxy: None,
             network_approval: Arc::clone(&network_approval),
             state_db: None,
+            worktree: None,
+            workspace_index: SessionWorkspaceIndex::default(),
             model_client: ModelClient::new(
                 Some(Arc::clone(&auth_manager)),
                 conversation_id,
                 session_configuration.provider.clone(),
+                config.model_provider_fallbacks.clone(),
                 session_configuration.session_source.clone(),
                 config.model_verbosity,
                 model_info.prefer_websockets
@@ -7592,6 +9076,7 @@ mod tests {
             services,
             js_repl,
             next_internal_sub_id: AtomicU64::new(0),
+            queued_user_input_ready: Notify::new(),
         });
 
         (session, turn_context, rx_event)
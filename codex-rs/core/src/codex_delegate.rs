@@ -10,6 +10,7 @@ use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::ExecApprovalRequestEvent;
 use codex_protocol::protocol::Op;
 use codex_protocol::protocol::RequestUserInputEvent;
+use codex_protocol::protocol::ReviewDecision;
 use codex_protocol::protocol::SessionSource;
 use codex_protocol::protocol::SubAgentSource;
 use codex_protocol::protocol::Submission;
@@ -36,6 +37,7 @@ use codex_protocol::protocol::InitialHistory;
 /// The returned `events_rx` yields non-approval events emitted by the sub-agent.
 /// Approval requests are handled via `parent_session` and are not surfaced.
 /// The returned `ops_tx` allows the caller to submit additional `Op`s to the sub-agent.
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn run_codex_thread_interactive(
     config: Config,
     auth_manager: Arc<AuthManager>,
@@ -44,6 +46,7 @@ pub(crate) async fn run_codex_thread_interactive(
     parent_ctx: Arc<TurnContext>,
     cancel_token: CancellationToken,
     initial_history: Option<InitialHistory>,
+    source: SubAgentSource,
 ) -> Result<Codex, CodexErr> {
     let (tx_sub, rx_sub) = async_channel::bounded(SUBMISSION_CHANNEL_CAPACITY);
     let (tx_ops, rx_ops) = async_channel::bounded(SUBMISSION_CHANNEL_CAPACITY);
@@ -55,7 +58,7 @@ pub(crate) async fn run_codex_thread_interactive(
         Arc::clone(&parent_session.services.skills_manager),
         Arc::clone(&parent_session.services.file_watcher),
         initial_history.unwrap_or(InitialHistory::New),
-        SessionSource::SubAgent(SubAgentSource::Review),
+        SessionSource::SubAgent(source),
         parent_session.services.agent_control.clone(),
         Vec::new(),
         false,
@@ -110,6 +113,7 @@ pub(crate) async fn run_codex_thread_one_shot(
     parent_ctx: Arc<TurnContext>,
     cancel_token: CancellationToken,
     initial_history: Option<InitialHistory>,
+    source: SubAgentSource,
 ) -> Result<Codex, CodexErr> {
     // Use a child token so we can stop the delegate after completion without
     // requiring the caller to cancel the parent token.
@@ -122,6 +126,7 @@ pub(crate) async fn run_codex_thread_one_shot(
         parent_ctx,
         child_cancel.clone(),
         initial_history,
+        source,
     )
     .await?;
 
@@ -240,6 +245,32 @@ async fn forward_events(
                         )
                         .await;
                     }
+                    Event {
+                        id: _,
+                        msg: EventMsg::BudgetLimitReached(event),
+                    } => {
+                        // Nobody can confirm continuation on behalf of a sub-agent, so a
+                        // budget limit always stops it.
+                        let _ = codex
+                            .submit(Op::BudgetApproval {
+                                id: event.id,
+                                decision: ReviewDecision::Denied,
+                            })
+                            .await;
+                    }
+                    Event {
+                        id: _,
+                        msg: EventMsg::AuthExpired(event),
+                    } => {
+                        // Signing in again is up to the parent session, whose own requests
+                        // pause on the same expired credentials.
+                        let _ = codex
+                            .submit(Op::ReauthComplete {
+                                id: event.id,
+                                success: false,
+                            })
+                            .await;
+                    }
                     Event {
                         id,
                         msg: EventMsg::RequestUserInput(event),
@@ -329,6 +360,9 @@ async fn handle_exec_approval(
         approval_id,
         command,
         cwd,
+        // The sub-agent does not report its sandbox, so only approvals given for unsandboxed runs
+        // may skip the prompt.
+        true,
         reason,
         network_approval_context,
         proposed_execpolicy_amendment,
@@ -17,7 +17,6 @@ use crate::protocol::WarningEvent;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::approx_token_count;
 use crate::truncate::truncate_text;
-use crate::util::backoff;
 use codex_protocol::items::ContextCompactionItem;
 use codex_protocol::items::TurnItem;
 use codex_protocol::models::ContentItem;
@@ -182,9 +181,15 @@ async fn run_compact_task_inner(
                 return Err(e);
             }
             Err(e) => {
-                if retries < max_retries {
+                let retry_delay = if retries < max_retries {
+                    turn_context
+                        .provider
+                        .retry_delay(retries + 1, e.retry_after())
+                } else {
+                    None
+                };
+                if let Some(delay) = retry_delay {
                     retries += 1;
-                    let delay = backoff(retries);
                     sess.notify_stream_error(
                         turn_context.as_ref(),
                         format!("Reconnecting... {retries}/{max_retries}"),
@@ -96,9 +96,11 @@ async fn run_remote_compact_task_inner_impl(
         input: history.for_prompt(&turn_context.model_info.input_modalities),
         tools: vec![],
         parallel_tool_calls: false,
+        emulate_tool_calls: false,
         base_instructions,
         personality: turn_context.personality,
         output_schema: None,
+        max_output_tokens: None,
     };
 
     let mut new_history = sess
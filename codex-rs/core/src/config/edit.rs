@@ -1,4 +1,5 @@
 use crate::config::CONFIG_TOML_FILE;
+use crate::config::reload::record_own_write;
 use crate::config::types::McpServerConfig;
 use crate::config::types::Notice;
 use crate::path_utils::resolve_symlink_write_paths;
@@ -69,8 +70,10 @@ pub fn status_line_items_edit(items: &[String]) -> ConfigEdit {
 
 // TODO(jif) move to a dedicated file
 mod document_helpers {
+    use crate::config::types::McpEnvValue;
     use crate::config::types::McpServerConfig;
     use crate::config::types::McpServerTransportConfig;
+    use std::collections::HashMap;
     use toml_edit::Array as TomlArray;
     use toml_edit::InlineTable;
     use toml_edit::Item as TomlItem;
@@ -128,7 +131,7 @@ mod document_helpers {
                 if let Some(env) = env
                     && !env.is_empty()
                 {
-                    entry["env"] = table_from_pairs(env.iter());
+                    entry["env"] = env_table(env);
                 }
                 if !env_vars.is_empty() {
                     entry["env_vars"] = array_from_iter(env_vars.iter().cloned());
@@ -172,6 +175,13 @@ mod document_helpers {
         if let Some(timeout) = config.tool_timeout_sec {
             entry["tool_timeout_sec"] = value(timeout.as_secs_f64());
         }
+        if let Some(ttl) = config.tool_cache_ttl_sec {
+            entry["tool_cache_ttl_sec"] = value(ttl.as_secs_f64());
+        }
+        if let Some(max_concurrent_calls) = config.max_concurrent_calls {
+            entry["max_concurrent_calls"] =
+                value(i64::try_from(max_concurrent_calls).unwrap_or(i64::MAX));
+        }
         if let Some(enabled_tools) = &config.enabled_tools
             && !enabled_tools.is_empty()
         {
@@ -182,6 +192,33 @@ mod document_helpers {
         {
             entry["disabled_tools"] = array_from_iter(disabled_tools.iter().cloned());
         }
+        if let Some(tool_renames) = &config.tool_renames
+            && !tool_renames.is_empty()
+        {
+            entry["tool_renames"] = table_from_pairs(tool_renames.iter());
+        }
+        if let Some(tools) = &config.tools
+            && !tools.is_empty()
+        {
+            let mut tools: Vec<_> = tools.iter().collect();
+            tools.sort_by(|(a, _), (b, _)| a.cmp(b));
+            let mut table = TomlTable::new();
+            table.set_implicit(false);
+            for (tool, tool_config) in tools {
+                let mut tool_table = TomlTable::new();
+                if let Some(timeout) = tool_config.timeout_sec {
+                    tool_table["timeout_sec"] = value(timeout.as_secs_f64());
+                }
+                if let Some(retries) = tool_config.retries {
+                    tool_table["retries"] = value(i64::from(retries));
+                }
+                if let Some(ttl) = tool_config.cache_ttl_sec {
+                    tool_table["cache_ttl_sec"] = value(ttl.as_secs_f64());
+                }
+                table.insert(tool, TomlItem::Table(tool_table));
+            }
+            entry["tools"] = TomlItem::Table(table);
+        }
         if let Some(scopes) = &config.scopes
             && !scopes.is_empty()
         {
@@ -254,6 +291,33 @@ mod document_helpers {
         }
         TomlItem::Table(table)
     }
+
+    fn env_table(env: &HashMap<String, McpEnvValue>) -> TomlItem {
+        let mut entries: Vec<_> = env.iter().collect();
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let mut table = TomlTable::new();
+        table.set_implicit(false);
+        for (key, env_value) in entries {
+            let item = match env_value {
+                McpEnvValue::Literal(literal) => value(literal.clone()),
+                McpEnvValue::Keychain { keychain } => {
+                    let mut inline = InlineTable::new();
+                    inline.insert("keychain", keychain.clone().into());
+                    value(inline)
+                }
+                McpEnvValue::Command { command } => {
+                    let mut inline = InlineTable::new();
+                    inline.insert(
+                        "command",
+                        TomlArray::from_iter(command.iter().cloned()).into(),
+                    );
+                    value(inline)
+                }
+            };
+            table.insert(key, item);
+        }
+        TomlItem::Table(table)
+    }
 }
 
 struct ConfigDocument {
@@ -686,12 +750,14 @@ pub fn apply_blocking(
         return Ok(());
     }
 
-    write_atomically(&write_paths.write_path, &document.doc.to_string()).with_context(|| {
+    let contents = document.doc.to_string();
+    write_atomically(&write_paths.write_path, &contents).with_context(|| {
         format!(
             "failed to persist config.toml at {}",
             write_paths.write_path.display()
         )
     })?;
+    record_own_write(&write_paths.write_path, &contents);
 
     Ok(())
 }
@@ -804,6 +870,17 @@ impl ConfigEditsBuilder {
         self
     }
 
+    /// Forget the trust decision recorded for `project_path`.
+    pub fn clear_project_trust_level<P: AsRef<Path>>(mut self, project_path: P) -> Self {
+        self.edits.push(ConfigEdit::ClearPath {
+            segments: vec![
+                "projects".to_string(),
+                project_path.as_ref().to_string_lossy().to_string(),
+            ],
+        });
+        self
+    }
+
     /// Enable or disable a feature flag by key under the `[features]` table.
     pub fn set_feature_enabled(mut self, key: &str, enabled: bool) -> Self {
         self.edits.push(ConfigEdit::SetPath {
@@ -877,6 +954,7 @@ impl ConfigEditsBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::types::McpEnvValue;
     use crate::config::types::McpServerTransportConfig;
     use codex_protocol::openai_models::ReasoningEffort;
     use pretty_assertions::assert_eq;
@@ -1416,8 +1494,20 @@ gpt-5 = "gpt-5.1"
                     args: vec!["--flag".to_string()],
                     env: Some(
                         [
-                            ("B".to_string(), "2".to_string()),
-                            ("A".to_string(), "1".to_string()),
+                            ("B".to_string(), "2".into()),
+                            ("A".to_string(), "1".into()),
+                            (
+                                "C".to_string(),
+                                McpEnvValue::Keychain {
+                                    keychain: "jira-token".to_string(),
+                                },
+                            ),
+                            (
+                                "D".to_string(),
+                                McpEnvValue::Command {
+                                    command: vec!["op".to_string(), "read".to_string()],
+                                },
+                            ),
                         ]
                         .into_iter()
                         .collect(),
@@ -1432,7 +1522,11 @@ gpt-5 = "gpt-5.1"
                 tool_timeout_sec: None,
                 enabled_tools: Some(vec!["one".to_string(), "two".to_string()]),
                 disabled_tools: None,
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
         );
 
@@ -1456,7 +1550,11 @@ gpt-5 = "gpt-5.1"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: Some(vec!["forbidden".to_string()]),
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
         );
 
@@ -1488,6 +1586,8 @@ enabled_tools = [\"one\", \"two\"]
 [mcp_servers.stdio.env]
 A = \"1\"
 B = \"2\"
+C = { keychain = \"jira-token\" }
+D = { command = [\"op\", \"read\"] }
 ";
         assert_eq!(raw, expected);
     }
@@ -1523,7 +1623,11 @@ foo = { command = "cmd" }
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
         );
 
@@ -1569,7 +1673,11 @@ foo = { command = "cmd" } # keep me
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
         );
 
@@ -1614,7 +1722,11 @@ foo = { command = "cmd", args = ["--flag"] } # keep me
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
         );
 
@@ -1660,7 +1772,11 @@ foo = { command = "cmd" }
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
         );
 
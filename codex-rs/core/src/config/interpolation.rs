@@ -0,0 +1,206 @@
+//! Expands `${VAR}` references to environment variables in the config values that usually hold
+//! machine-specific paths, hosts, and tokens, so that a `config.toml` can be shared as is.
+//! `$${` stands for a literal `${`.
+
+use toml::Value as TomlValue;
+
+/// Values that are expanded, as key paths where `*` matches every key of a table or every item
+/// of an array.
+const INTERPOLATED_KEYS: &[&[&str]] = &[
+    &["chatgpt_base_url"],
+    &["mcp_servers", "*", "command"],
+    &["mcp_servers", "*", "args", "*"],
+    &["mcp_servers", "*", "cwd"],
+    &["mcp_servers", "*", "env", "*"],
+    &["mcp_servers", "*", "url"],
+    &["mcp_servers", "*", "http_headers", "*"],
+    &["model_providers", "*", "base_url"],
+    &["model_providers", "*", "http_headers", "*"],
+    &["model_providers", "*", "query_params", "*"],
+    &["proxy", "url"],
+    &["proxy", "username"],
+    &["proxy", "password"],
+];
+
+/// Expands environment variable references in `config`, failing with every reference to a
+/// variable that is not set.
+pub(crate) fn interpolate_env_vars(config: &mut TomlValue) -> std::io::Result<()> {
+    interpolate_with(config, &|name| std::env::var(name).ok())
+}
+
+fn interpolate_with(
+    config: &mut TomlValue,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> std::io::Result<()> {
+    let mut errors = Vec::new();
+    for keys in INTERPOLATED_KEYS {
+        interpolate_at(config, keys, String::new(), lookup, &mut errors);
+        // Profiles carry their own copies of some of these settings.
+        let profile_keys = [&["profiles", "*"][..], *keys].concat();
+        interpolate_at(config, &profile_keys, String::new(), lookup, &mut errors);
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            errors.join("; "),
+        ))
+    }
+}
+
+fn interpolate_at(
+    value: &mut TomlValue,
+    keys: &[&str],
+    path: String,
+    lookup: &dyn Fn(&str) -> Option<String>,
+    errors: &mut Vec<String>,
+) {
+    let Some((key, rest)) = keys.split_first() else {
+        if let TomlValue::String(text) = value {
+            match expand(text, lookup) {
+                Ok(expanded) => *text = expanded,
+                Err(err) => errors.push(format!("{path}: {err}")),
+            }
+        }
+        return;
+    };
+    match (value, *key) {
+        (TomlValue::Table(table), "*") => {
+            for (name, value) in table.iter_mut() {
+                interpolate_at(value, rest, join_key(&path, name), lookup, errors);
+            }
+        }
+        (TomlValue::Array(values), "*") => {
+            for (idx, value) in values.iter_mut().enumerate() {
+                interpolate_at(value, rest, format!("{path}[{idx}]"), lookup, errors);
+            }
+        }
+        (TomlValue::Table(table), key) => {
+            if let Some(value) = table.get_mut(key) {
+                interpolate_at(value, rest, join_key(&path, key), lookup, errors);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn join_key(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+fn expand(text: &str, lookup: &dyn Fn(&str) -> Option<String>) -> Result<String, String> {
+    let mut expanded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start..];
+        if let Some(escaped) = after.strip_prefix("$${") {
+            expanded.push_str("${");
+            rest = escaped;
+        } else if let Some(reference) = after.strip_prefix("${") {
+            let Some(end) = reference.find('}') else {
+                return Err(format!("`${{` without a closing `}}` in {text:?}"));
+            };
+            let name = &reference[..end];
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Err(format!(
+                    "`${{{name}}}` is not a valid environment variable name"
+                ));
+            }
+            let Some(value) = lookup(name) else {
+                return Err(format!("environment variable `{name}` is not set"));
+            };
+            expanded.push_str(&value);
+            rest = &reference[end + 1..];
+        } else {
+            expanded.push('$');
+            rest = &after[1..];
+        }
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "DOCS_HOME" => Some("/opt/docs".to_string()),
+            "DOCS_TOKEN" => Some("secret".to_string()),
+            _ => None,
+        }
+    }
+
+    fn interpolate(config: &str) -> std::io::Result<TomlValue> {
+        let mut config: TomlValue = toml::from_str(config).expect("parse config");
+        interpolate_with(&mut config, &lookup)?;
+        Ok(config)
+    }
+
+    #[test]
+    fn expands_references_in_server_and_provider_settings() {
+        let config = interpolate(
+            r#"
+model_instructions_file = "${DOCS_HOME}/untouched.md"
+
+[mcp_servers.docs]
+command = "${DOCS_HOME}/bin/docs-mcp"
+args = ["--root", "${DOCS_HOME}", "cost: $5", "$${DOCS_HOME}"]
+env = { DOCS_TOKEN = "${DOCS_TOKEN}", KEY = { keychain = "docs" } }
+
+[model_providers.internal]
+base_url = "https://${DOCS_TOKEN}.example.com/v1"
+
+[profiles.work.mcp_servers.docs]
+url = "https://docs.example.com/${DOCS_TOKEN}"
+"#,
+        )
+        .expect("interpolate");
+
+        let expected: TomlValue = toml::from_str(
+            r#"
+model_instructions_file = "${DOCS_HOME}/untouched.md"
+
+[mcp_servers.docs]
+command = "/opt/docs/bin/docs-mcp"
+args = ["--root", "/opt/docs", "cost: $5", "${DOCS_HOME}"]
+env = { DOCS_TOKEN = "secret", KEY = { keychain = "docs" } }
+
+[model_providers.internal]
+base_url = "https://secret.example.com/v1"
+
+[profiles.work.mcp_servers.docs]
+url = "https://docs.example.com/secret"
+"#,
+        )
+        .expect("parse expected");
+        assert_eq!(config, expected);
+    }
+
+    #[test]
+    fn unset_variables_are_reported_with_where_they_are_used() {
+        let err = interpolate(
+            r#"
+[mcp_servers.docs]
+command = "docs-mcp"
+args = ["${MISSING_ROOT}"]
+url = "${DOCS_HOME"
+"#,
+        )
+        .expect_err("unset variable");
+
+        assert_eq!(
+            err.to_string(),
+            "mcp_servers.docs.args[0]: environment variable `MISSING_ROOT` is not set; \
+             mcp_servers.docs.url: `${` without a closing `}` in \"${DOCS_HOME\""
+        );
+    }
+}
@@ -0,0 +1,297 @@
+//! Migrations that rewrite the user `config.toml` when settings are renamed or restructured.
+//!
+//! The file records the format it is written in as `config_version`; a file without one is at
+//! version 0. Each entry of [`MIGRATIONS`] moves a file from its index to the next version, so a
+//! new migration is added by appending to the list. Migrations run when config is loaded, edit
+//! the file in place with `toml_edit` so comments and layout survive, and leave a copy of the
+//! original next to it.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use toml_edit::DocumentMut;
+use toml_edit::Item as TomlItem;
+use toml_edit::Table as TomlTable;
+use toml_edit::value;
+
+use crate::config::CONFIG_TOML_FILE;
+use crate::path_utils::resolve_symlink_write_paths;
+use crate::path_utils::write_atomically;
+
+/// Key that records which migrations a `config.toml` has been through.
+pub const CONFIG_VERSION_KEY: &str = "config_version";
+
+struct Migration {
+    /// Shown to the user once the migration has changed their file.
+    description: &'static str,
+    /// Rewrites `doc` in place, returning whether anything changed.
+    apply: fn(&mut DocumentMut) -> bool,
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        description: "moved legacy feature toggles into [features]",
+        apply: move_legacy_feature_toggles,
+    },
+    Migration {
+        description: "renamed tools.web_search_request and ghost_snapshot settings",
+        apply: rename_legacy_keys,
+    },
+];
+
+/// The `config_version` that files written by this build are in.
+pub const CURRENT_CONFIG_VERSION: i64 = MIGRATIONS.len() as i64;
+
+/// A migration of the user `config.toml` that was written to disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigMigration {
+    pub from_version: i64,
+    pub to_version: i64,
+    /// What each applied migration changed, in order.
+    pub changes: Vec<&'static str>,
+    /// Copy of the file as it was before the migration.
+    pub backup_path: PathBuf,
+}
+
+impl ConfigMigration {
+    pub fn message(&self) -> String {
+        format!(
+            "Updated config.toml to format version {}: {}. The previous file was saved to {}.",
+            self.to_version,
+            self.changes.join("; "),
+            self.backup_path.display()
+        )
+    }
+}
+
+/// Runs the migrations the user `config.toml` in `codex_home` has not been through yet. The file
+/// is only rewritten when a migration changes something; files written by a newer version of
+/// Codex are left alone.
+pub fn migrate_user_config(codex_home: &Path) -> io::Result<Option<ConfigMigration>> {
+    let write_paths = resolve_symlink_write_paths(&codex_home.join(CONFIG_TOML_FILE))?;
+    let Some(read_path) = write_paths.read_path else {
+        return Ok(None);
+    };
+    let original = match std::fs::read_to_string(&read_path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    // A file that does not parse is reported by the loader with its location.
+    let Ok(mut doc) = original.parse::<DocumentMut>() else {
+        return Ok(None);
+    };
+
+    let from_version = doc
+        .get(CONFIG_VERSION_KEY)
+        .and_then(TomlItem::as_integer)
+        .unwrap_or(0);
+    let Some(pending) = usize::try_from(from_version)
+        .ok()
+        .and_then(|version| MIGRATIONS.get(version..))
+    else {
+        return Ok(None);
+    };
+
+    let changes: Vec<&'static str> = pending
+        .iter()
+        .filter_map(|migration| (migration.apply)(&mut doc).then_some(migration.description))
+        .collect();
+    if changes.is_empty() {
+        return Ok(None);
+    }
+    doc[CONFIG_VERSION_KEY] = value(CURRENT_CONFIG_VERSION);
+
+    let backup_path = read_path.with_file_name(format!("{CONFIG_TOML_FILE}.v{from_version}.bak"));
+    std::fs::write(&backup_path, &original)?;
+    write_atomically(&write_paths.write_path, &doc.to_string())?;
+
+    Ok(Some(ConfigMigration {
+        from_version,
+        to_version: CURRENT_CONFIG_VERSION,
+        changes,
+        backup_path,
+    }))
+}
+
+/// The top-level tables that profiles and the root share: the root itself and every
+/// `[profiles.<name>]`.
+fn for_root_and_profiles(doc: &mut DocumentMut, mut f: impl FnMut(&mut TomlTable) -> bool) -> bool {
+    let mut changed = f(doc.as_table_mut());
+    if let Some(profiles) = doc
+        .get_mut("profiles")
+        .and_then(TomlItem::as_table_like_mut)
+    {
+        for (_, profile) in profiles.iter_mut() {
+            if let Some(profile) = profile.as_table_mut() {
+                changed |= f(profile);
+            }
+        }
+    }
+    changed
+}
+
+/// Version 0 -> 1: `experimental_use_unified_exec_tool`, `experimental_use_freeform_apply_patch`
+/// and a profile's `include_apply_patch_tool` become `[features]` entries. An entry already in
+/// `[features]` wins, as it did when the legacy toggle was read.
+fn move_legacy_feature_toggles(doc: &mut DocumentMut) -> bool {
+    const TOGGLES: &[(&str, &str)] = &[
+        ("include_apply_patch_tool", "apply_patch_freeform"),
+        (
+            "experimental_use_freeform_apply_patch",
+            "apply_patch_freeform",
+        ),
+        ("experimental_use_unified_exec_tool", "unified_exec"),
+    ];
+
+    for_root_and_profiles(doc, |table| {
+        let toggles: Vec<(&str, TomlItem)> = TOGGLES
+            .iter()
+            .filter_map(|(legacy, feature)| Some((*feature, table.remove(legacy)?)))
+            .collect();
+        if toggles.is_empty() {
+            return false;
+        }
+        let features = table
+            .entry("features")
+            .or_insert_with(|| TomlItem::Table(TomlTable::new()));
+        if let Some(features) = features.as_table_like_mut() {
+            let explicit: Vec<String> = features.iter().map(|(key, _)| key.to_string()).collect();
+            for (feature, toggle) in toggles {
+                if !explicit.iter().any(|key| key == feature) {
+                    features.insert(feature, without_decor(toggle));
+                }
+            }
+        }
+        true
+    })
+}
+
+/// Version 1 -> 2: keys that were renamed but kept as aliases.
+fn rename_legacy_keys(doc: &mut DocumentMut) -> bool {
+    const RENAMES: &[(&str, &str, &str)] = &[
+        ("tools", "web_search_request", "web_search"),
+        (
+            "ghost_snapshot",
+            "ignore_untracked_files_over_bytes",
+            "ignore_large_untracked_files",
+        ),
+        (
+            "ghost_snapshot",
+            "large_untracked_dir_warning_threshold",
+            "ignore_large_untracked_dirs",
+        ),
+    ];
+
+    let mut changed = false;
+    for (table, old_key, new_key) in RENAMES {
+        let Some(table) = doc.get_mut(table).and_then(TomlItem::as_table_like_mut) else {
+            continue;
+        };
+        let Some(old_value) = table.remove(old_key) else {
+            continue;
+        };
+        // Both spellings at once never loaded; the new one is kept.
+        if table.get(new_key).is_none() {
+            table.insert(new_key, without_decor(old_value));
+        }
+        changed = true;
+    }
+    changed
+}
+
+/// Drops the whitespace and comments around a moved value so it is formatted like a new one.
+fn without_decor(mut item: TomlItem) -> TomlItem {
+    if let Some(value) = item.as_value_mut() {
+        value.decor_mut().clear();
+    }
+    item
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn migrates_legacy_keys_and_keeps_a_backup() -> anyhow::Result<()> {
+        let codex_home = TempDir::new()?;
+        let config_path = codex_home.path().join(CONFIG_TOML_FILE);
+        let original = r#"# Pinned for the team.
+model = "gpt-5.1-codex"
+experimental_use_unified_exec_tool = true
+
+[features]
+apply_patch_freeform = false
+
+[tools]
+web_search_request = true
+
+[profiles.fast]
+include_apply_patch_tool = true
+"#;
+        std::fs::write(&config_path, original)?;
+
+        let migration = migrate_user_config(codex_home.path())?.expect("config is migrated");
+
+        assert_eq!(
+            migration,
+            ConfigMigration {
+                from_version: 0,
+                to_version: CURRENT_CONFIG_VERSION,
+                changes: vec![
+                    "moved legacy feature toggles into [features]",
+                    "renamed tools.web_search_request and ghost_snapshot settings",
+                ],
+                backup_path: codex_home.path().join("config.toml.v0.bak"),
+            }
+        );
+        assert_eq!(std::fs::read_to_string(&migration.backup_path)?, original);
+        let migrated = std::fs::read_to_string(&config_path)?;
+        assert!(
+            migrated.starts_with("# Pinned for the team.\n"),
+            "{migrated}"
+        );
+        let expected: toml::Value = toml::from_str(
+            r#"
+model = "gpt-5.1-codex"
+config_version = 2
+
+[features]
+apply_patch_freeform = false
+unified_exec = true
+
+[tools]
+web_search = true
+
+[profiles.fast.features]
+apply_patch_freeform = true
+"#,
+        )?;
+        assert_eq!(toml::from_str::<toml::Value>(&migrated)?, expected);
+
+        assert_eq!(migrate_user_config(codex_home.path())?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn leaves_current_and_newer_files_alone() -> anyhow::Result<()> {
+        let codex_home = TempDir::new()?;
+        let config_path = codex_home.path().join(CONFIG_TOML_FILE);
+
+        std::fs::write(&config_path, "model = \"o3\"\n")?;
+        assert_eq!(migrate_user_config(codex_home.path())?, None);
+        assert_eq!(std::fs::read_to_string(&config_path)?, "model = \"o3\"\n");
+
+        let newer = format!(
+            "config_version = {}\n\n[tools]\nweb_search_request = true\n",
+            CURRENT_CONFIG_VERSION + 1
+        );
+        std::fs::write(&config_path, &newer)?;
+        assert_eq!(migrate_user_config(codex_home.path())?, None);
+        assert_eq!(std::fs::read_to_string(&config_path)?, newer);
+        Ok(())
+    }
+}
@@ -1,29 +1,42 @@
 use crate::auth::AuthCredentialsStoreMode;
 use crate::config::edit::ConfigEdit;
 use crate::config::edit::ConfigEditsBuilder;
+use crate::config::types::ApprovalPatterns;
 use crate::config::types::AppsConfigToml;
+use crate::config::types::Budget;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
+use crate::config::types::EnvironmentVariablePattern;
 use crate::config::types::History;
+use crate::config::types::LogFormat;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerDisabledReason;
 use crate::config::types::McpServerTransportConfig;
 use crate::config::types::MemoriesConfig;
 use crate::config::types::MemoriesToml;
+use crate::config::types::ModelPresetToml;
+use crate::config::types::ModelPricing;
 use crate::config::types::Notice;
 use crate::config::types::NotificationMethod;
 use crate::config::types::Notifications;
 use crate::config::types::OtelConfig;
 use crate::config::types::OtelConfigToml;
 use crate::config::types::OtelExporterKind;
+use crate::config::types::ProxyToml;
+use crate::config::types::Redaction;
+use crate::config::types::ResourceLimits;
+use crate::config::types::SandboxContainer;
+use crate::config::types::SandboxExtensions;
 use crate::config::types::SandboxWorkspaceWrite;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
 use crate::config::types::SkillsConfig;
 use crate::config::types::Tui;
 use crate::config::types::UriBasedFileOpener;
+use crate::config::types::WebTools;
 use crate::config::types::WindowsSandboxModeToml;
 use crate::config::types::WindowsToml;
 use crate::config_loader::CloudRequirementsLoader;
+use crate::config_loader::ConfigError;
 use crate::config_loader::ConfigLayerStack;
 use crate::config_loader::ConfigRequirements;
 use crate::config_loader::ConstrainedWithSource;
@@ -37,7 +50,9 @@ use crate::features::Feature;
 use crate::features::FeatureOverrides;
 use crate::features::Features;
 use crate::features::FeaturesToml;
+use crate::file_excerpt::DEFAULT_FILE_EXCERPT_THRESHOLD_BYTES;
 use crate::git_info::resolve_root_git_project_for_trust;
+use crate::landlock::EXEMPTABLE_SECCOMP_SYSCALLS;
 use crate::model_provider_info::LEGACY_OLLAMA_CHAT_PROVIDER_ID;
 use crate::model_provider_info::LMSTUDIO_OSS_PROVIDER_ID;
 use crate::model_provider_info::ModelProviderInfo;
@@ -82,13 +97,17 @@ use tempfile::tempdir;
 #[cfg(not(target_os = "macos"))]
 type MacOsSeatbeltProfileExtensions = ();
 
+use crate::config::interpolation::interpolate_env_vars;
 use crate::config::profile::ConfigProfile;
 use toml::Value as TomlValue;
 use toml_edit::DocumentMut;
 
 pub mod edit;
+pub(crate) mod interpolation;
+pub mod migrations;
 mod network_proxy_spec;
 pub mod profile;
+pub(crate) mod reload;
 pub mod schema;
 pub mod service;
 pub mod types;
@@ -111,6 +130,10 @@ pub(crate) const DEFAULT_AGENT_MAX_THREADS: Option<usize> = Some(6);
 
 pub const CONFIG_TOML_FILE: &str = "config.toml";
 
+/// Environment variable naming the profile to use when `--profile` is not given. It takes
+/// precedence over the `profile` key in `config.toml`.
+pub const CODEX_PROFILE_ENV_VAR: &str = "CODEX_PROFILE";
+
 #[cfg(test)]
 pub(crate) fn test_config() -> Config {
     let codex_home = tempdir().expect("create temp dir");
@@ -169,6 +192,37 @@ pub struct Config {
     /// Info needed to make an API request to the model.
     pub model_provider: ModelProviderInfo,
 
+    /// Providers to retry a turn against, in order, when `model_provider` is rate limited, failing
+    /// with server errors, or timing out.
+    pub model_provider_fallbacks: Vec<ModelProviderInfo>,
+
+    /// Token prices keyed by model slug, used to estimate the cost reported in usage events.
+    pub model_pricing: HashMap<String, ModelPricing>,
+
+    /// Named combinations of model, reasoning effort and verbosity, offered in the `/model` popup.
+    pub model_presets: BTreeMap<String, ModelPresetToml>,
+
+    /// Turn, session and daily limits that pause a turn until the user confirms continuation.
+    pub budget: Budget,
+
+    /// Domain allowlist, size cap and search endpoint for the built-in web tools.
+    pub web_tools: WebTools,
+
+    /// Environment variables stripped from spawned commands and patterns masked in tool output.
+    pub redaction: Redaction,
+
+    /// Commands that are approved or rejected without asking the user.
+    pub approval_patterns: ApprovalPatterns,
+
+    /// When set, sandboxed commands run in this container instead of the platform sandbox.
+    pub sandbox_container: Option<SandboxContainer>,
+
+    /// Extra paths, devices, and syscalls added to the generated sandbox profile.
+    pub sandbox_extensions: Option<SandboxExtensions>,
+
+    /// CPU, memory, file descriptor, and output limits for commands run by the shell tool.
+    pub resource_limits: ResourceLimits,
+
     /// Optionally specify the personality of the model
     pub personality: Option<Personality>,
 
@@ -180,6 +234,14 @@ pub struct Config {
     /// using backend-specific headers or URLs to enforce this.
     pub enforce_residency: Constrained<Option<ResidencyRequirement>>,
 
+    /// Proxy from `[proxy]` for every HTTP client; `None` leaves the proxy environment variables
+    /// in charge.
+    pub proxy: Option<codex_client::ProxySettings>,
+
+    /// Tool names (with `*` and `?` wildcards) that `requirements.toml` blocks; they are never
+    /// offered to the model.
+    pub blocked_tools: Vec<String>,
+
     /// True if the user passed in an override or set a value in config.toml
     /// for either of approval_policy or sandbox_mode.
     pub did_user_set_custom_approval_policy_or_sandbox_mode: bool,
@@ -274,6 +336,9 @@ pub struct Config {
     /// auto: Use the OS-specific keyring service if available, otherwise use a file.
     pub cli_auth_credentials_store_mode: AuthCredentialsStoreMode,
 
+    /// Named account whose credentials to use instead of the active one.
+    pub account: Option<String>,
+
     /// Definition for MCP servers that Codex can reach out to for tool calls.
     pub mcp_servers: Constrained<HashMap<String, McpServerConfig>>,
 
@@ -291,6 +356,9 @@ pub struct Config {
     /// When unset, Codex will bind to an ephemeral port chosen by the OS.
     pub mcp_oauth_callback_port: Option<u16>,
 
+    /// Most MCP tool calls, across all servers, that run at once. Unlimited when unset.
+    pub mcp_max_concurrent_calls: Option<usize>,
+
     /// Combined provider map (defaults merged with user-defined overrides).
     pub model_providers: HashMap<String, ModelProviderInfo>,
 
@@ -303,6 +371,9 @@ pub struct Config {
     /// Token budget applied when storing tool/function outputs in the context manager.
     pub tool_output_token_limit: Option<usize>,
 
+    /// Attached files larger than this many bytes are replaced by an outline and selected lines.
+    pub file_excerpt_threshold_bytes: usize,
+
     /// Maximum number of agent threads that can be open concurrently.
     pub agent_max_threads: Option<usize>,
 
@@ -319,6 +390,12 @@ pub struct Config {
     /// Directory where Codex writes log files (defaults to `$CODEX_HOME/log`).
     pub log_dir: PathBuf,
 
+    /// Format of log lines.
+    pub log_format: LogFormat,
+
+    /// File that logs are appended to instead of their usual destination.
+    pub log_file: Option<PathBuf>,
+
     /// Settings that govern if and what will be written to `~/.codex/history.jsonl`.
     pub history: History,
 
@@ -357,6 +434,10 @@ pub struct Config {
     /// Optional override to force-enable reasoning summaries for the configured model.
     pub model_supports_reasoning_summaries: Option<bool>,
 
+    /// Whether the configured model supports native tool calling. When `Some(false)`, tools are
+    /// described in the instructions and tool calls are parsed from the model's text output.
+    pub model_supports_tool_calls: Option<bool>,
+
     /// Optional verbosity control for GPT-5 models (Responses API `text.verbosity`).
     pub model_verbosity: Option<Verbosity>,
 
@@ -484,6 +565,23 @@ impl ConfigBuilder {
             None => AbsolutePathBuf::current_dir()?,
         };
         harness_overrides.cwd = Some(cwd.to_path_buf());
+        // A file that cannot be migrated is still loaded as is; the legacy keys keep working.
+        let migration_home = codex_home.clone();
+        let migration = match tokio::task::spawn_blocking(move || {
+            migrations::migrate_user_config(&migration_home)
+        })
+        .await
+        {
+            Ok(Ok(migration)) => migration,
+            Ok(Err(err)) => {
+                tracing::warn!("failed to migrate config.toml: {err}");
+                None
+            }
+            Err(err) => {
+                tracing::warn!("config.toml migration task failed: {err}");
+                None
+            }
+        };
         let config_layer_stack = load_config_layers_state(
             &codex_home,
             Some(cwd),
@@ -492,7 +590,8 @@ impl ConfigBuilder {
             cloud_requirements,
         )
         .await?;
-        let merged_toml = config_layer_stack.effective_config();
+        let mut merged_toml = config_layer_stack.effective_config();
+        interpolate_env_vars(&mut merged_toml)?;
 
         // Note that each layer in ConfigLayerStack should have resolved
         // relative paths to absolute paths based on the parent folder of the
@@ -513,12 +612,37 @@ impl ConfigBuilder {
                 return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, err));
             }
         };
-        Config::load_config_with_layer_stack(
+        let unknown_keys = crate::config_loader::unknown_config_keys(&config_layer_stack).await;
+        if config_toml.strict_config == Some(true)
+            && let Some(error) = unknown_keys.first()
+        {
+            return Err(crate::config_loader::io_error_from_config_error(
+                std::io::ErrorKind::InvalidData,
+                error.clone(),
+                None,
+            ));
+        }
+        let mut config = Config::load_config_with_layer_stack(
             config_toml,
             harness_overrides,
             codex_home,
             config_layer_stack,
-        )
+        )?;
+        config
+            .startup_warnings
+            .extend(unknown_keys.iter().map(|error| {
+            format!(
+                "{}:{}:{}: {}; it is ignored. Run `codex config validate` to check your config.",
+                error.path.display(),
+                error.range.start.line,
+                error.range.start.column,
+                error.message
+            )
+        }));
+        if let Some(migration) = migration {
+            config.startup_warnings.push(migration.message());
+        }
+        Ok(config)
     }
 }
 
@@ -591,7 +715,8 @@ pub async fn load_config_as_toml_with_cli_overrides(
     )
     .await?;
 
-    let merged_toml = config_layer_stack.effective_config();
+    let mut merged_toml = config_layer_stack.effective_config();
+    interpolate_env_vars(&mut merged_toml)?;
     let cfg = deserialize_config_toml_with_base(merged_toml, codex_home).map_err(|e| {
         tracing::error!("Failed to deserialize overridden config: {e}");
         e
@@ -707,6 +832,24 @@ fn mcp_server_matches_requirement(
     }
 }
 
+/// Checks the config files that apply in `cwd` for keys that `config.toml` does not accept and
+/// values of the wrong type. A file that does not parse fails the whole check.
+pub async fn validate_config_files(
+    codex_home: &Path,
+    cwd: &Path,
+    cli_overrides: Vec<(String, TomlValue)>,
+) -> std::io::Result<Vec<ConfigError>> {
+    let config_layer_stack = load_config_layers_state(
+        codex_home,
+        Some(AbsolutePathBuf::try_from(cwd)?),
+        &cli_overrides,
+        LoaderOverrides::default(),
+        CloudRequirementsLoader::default(),
+    )
+    .await?;
+    Ok(crate::config_loader::config_file_errors(&config_layer_stack).await)
+}
+
 pub async fn load_global_mcp_servers(
     codex_home: &Path,
 ) -> std::io::Result<BTreeMap<String, McpServerConfig>> {
@@ -846,6 +989,38 @@ pub fn set_project_trust_level(
         .apply_blocking()
 }
 
+/// Patch `CODEX_HOME/config.toml` to forget the trust decision for a project.
+pub fn clear_project_trust_level(codex_home: &Path, project_path: &Path) -> anyhow::Result<()> {
+    use crate::config::edit::ConfigEditsBuilder;
+
+    ConfigEditsBuilder::new(codex_home)
+        .clear_project_trust_level(project_path)
+        .apply_blocking()
+}
+
+/// Trust decisions recorded under `[projects]` in `CODEX_HOME/config.toml`, keyed by path.
+pub fn load_project_trust_levels(
+    codex_home: &Path,
+) -> std::io::Result<BTreeMap<String, TrustLevel>> {
+    let contents = match std::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(err) => return Err(err),
+    };
+    let root: TomlValue = toml::from_str(&contents)
+        .map_err(|err| std::io::Error::new(ErrorKind::InvalidData, err))?;
+    let Some(projects) = root.get("projects").cloned() else {
+        return Ok(BTreeMap::new());
+    };
+    let projects: HashMap<String, ProjectConfig> = projects
+        .try_into()
+        .map_err(|err| std::io::Error::new(ErrorKind::InvalidData, err))?;
+    Ok(projects
+        .into_iter()
+        .filter_map(|(path, project)| Some((path, project.trust_level?)))
+        .collect())
+}
+
 /// Save the default OSS provider preference to config.toml
 pub fn set_default_oss_provider(codex_home: &Path, provider: &str) -> std::io::Result<()> {
     // Validate that the provider is one of the known OSS providers
@@ -885,6 +1060,10 @@ pub fn set_default_oss_provider(codex_home: &Path, provider: &str) -> std::io::R
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, JsonSchema)]
 #[schemars(deny_unknown_fields)]
 pub struct ConfigToml {
+    /// Version of the config format. Written by Codex when it migrates this file to a newer
+    /// format.
+    pub config_version: Option<i64>,
+
     /// Optional override of model selection.
     pub model: Option<String>,
     /// Review model override used by the `/review` feature.
@@ -893,6 +1072,35 @@ pub struct ConfigToml {
     /// Provider to use from the model_providers map.
     pub model_provider: Option<String>,
 
+    /// Ordered list of providers from the model_providers map to fall back to when the primary
+    /// provider is rate limited, returns server errors, or times out.
+    pub model_provider_fallbacks: Option<Vec<String>>,
+
+    /// Token prices keyed by model slug, used to estimate the cost reported in usage events.
+    #[serde(default)]
+    pub model_pricing: HashMap<String, ModelPricing>,
+
+    /// Named combinations of model, reasoning effort and verbosity, chosen with `--preset` or
+    /// from the `/model` popup.
+    #[serde(default)]
+    pub model_presets: BTreeMap<String, ModelPresetToml>,
+
+    /// Turn, session and daily limits that pause a turn until the user confirms continuation.
+    #[serde(default)]
+    pub budget: Option<Budget>,
+
+    /// Domain allowlist, size cap and search endpoint for the built-in web tools.
+    #[serde(default)]
+    pub web_tools: Option<WebTools>,
+
+    /// Environment variables stripped from spawned commands and patterns masked in tool output.
+    #[serde(default)]
+    pub redaction: Option<Redaction>,
+
+    /// Commands that are approved or rejected without asking the user.
+    #[serde(default)]
+    pub approval_patterns: Option<ApprovalPatterns>,
+
     /// Size of the context window for the model, in tokens.
     pub model_context_window: Option<i64>,
 
@@ -911,6 +1119,16 @@ pub struct ConfigToml {
     /// Sandbox configuration to apply if `sandbox` is `WorkspaceWrite`.
     pub sandbox_workspace_write: Option<SandboxWorkspaceWrite>,
 
+    /// Run sandboxed commands in a Docker or Podman container instead of the platform sandbox.
+    pub sandbox_container: Option<SandboxContainer>,
+
+    /// Extra paths, devices, and syscalls to add to the generated Seatbelt and Linux sandbox
+    /// profiles.
+    pub sandbox_extensions: Option<SandboxExtensions>,
+
+    /// Limits on CPU time, memory, open files, and output size for commands the agent runs.
+    pub resource_limits: Option<ResourceLimits>,
+
     /// Optional external command to spawn for end-user notifications.
     #[serde(default)]
     pub notify: Option<Vec<String>>,
@@ -951,6 +1169,13 @@ pub struct ConfigToml {
     #[serde(default)]
     pub cli_auth_credentials_store: Option<AuthCredentialsStoreMode>,
 
+    /// Named account (see `codex account`) whose credentials to use instead of the active one.
+    pub account: Option<String>,
+
+    /// Proxy for every HTTP client, replacing `HTTPS_PROXY`, `HTTP_PROXY`, `ALL_PROXY` and
+    /// `NO_PROXY`.
+    pub proxy: Option<ProxyToml>,
+
     /// Definition for MCP servers that Codex can reach out to for tool calls.
     #[serde(default)]
     // Uses the raw MCP input shape (custom deserialization) rather than `McpServerConfig`.
@@ -969,6 +1194,12 @@ pub struct ConfigToml {
     /// When unset, Codex will bind to an ephemeral port chosen by the OS.
     pub mcp_oauth_callback_port: Option<u16>,
 
+    /// Most MCP tool calls, across all servers, that run at once; further calls wait in line in
+    /// the order they were made. Servers can set a lower `max_concurrent_calls` of their own.
+    /// When unset, no limit is enforced.
+    #[schemars(range(min = 1))]
+    pub mcp_max_concurrent_calls: Option<usize>,
+
     /// User-defined provider entries that extend/override the built-in list.
     #[serde(default)]
     pub model_providers: HashMap<String, ModelProviderInfo>,
@@ -982,6 +1213,9 @@ pub struct ConfigToml {
     /// Token budget applied when storing tool/function outputs in the context manager.
     pub tool_output_token_limit: Option<usize>,
 
+    /// Attached files larger than this many bytes are replaced by an outline and selected lines.
+    pub file_excerpt_threshold_bytes: Option<usize>,
+
     /// Optional absolute path to the Node runtime used by `js_repl`.
     pub js_repl_node_path: Option<AbsolutePathBuf>,
 
@@ -1006,6 +1240,13 @@ pub struct ConfigToml {
     /// Defaults to `$CODEX_HOME/log`.
     pub log_dir: Option<AbsolutePathBuf>,
 
+    /// Format of log lines: `text` (default) or `json` for one JSON object per line.
+    pub log_format: Option<LogFormat>,
+
+    /// File that logs are appended to instead of their usual destination (stderr, or
+    /// `codex-tui.log` in `log_dir` for the TUI).
+    pub log_file: Option<AbsolutePathBuf>,
+
     /// Optional URI-based file opener. If set, citations to files in the model
     /// output will be hyperlinked using the specified URI scheme.
     pub file_opener: Option<UriBasedFileOpener>,
@@ -1029,6 +1270,10 @@ pub struct ConfigToml {
     /// Override to force-enable reasoning summaries for the configured model.
     pub model_supports_reasoning_summaries: Option<bool>,
 
+    /// Set to `false` for models without native tool calling to emulate tool calls through the
+    /// prompt.
+    pub model_supports_tool_calls: Option<bool>,
+
     /// Optionally specify a personality for the model
     pub personality: Option<Personality>,
 
@@ -1061,6 +1306,10 @@ pub struct ConfigToml {
     /// Suppress warnings about unstable (under development) features.
     pub suppress_unstable_features_warning: Option<bool>,
 
+    /// When `true`, keys that `config.toml` does not accept stop Codex from starting instead of
+    /// being reported as warnings.
+    pub strict_config: Option<bool>,
+
     /// Settings for ghost snapshots (used for undo).
     #[serde(default)]
     pub ghost_snapshot: Option<GhostSnapshotToml>,
@@ -1275,6 +1524,7 @@ impl ConfigToml {
                     network_access,
                     exclude_tmpdir_env_var,
                     exclude_slash_tmp,
+                    ..
                 }) => SandboxPolicy::WorkspaceWrite {
                     writable_roots: writable_roots.clone(),
                     read_only_access: ReadOnlyAccess::FullAccess,
@@ -1339,21 +1589,57 @@ impl ConfigToml {
         &self,
         override_profile: Option<String>,
     ) -> Result<ConfigProfile, std::io::Error> {
-        let profile = override_profile.or_else(|| self.profile.clone());
+        match self.active_profile_name(override_profile) {
+            Some(key) => self.resolve_profile(&key),
+            None => Ok(ConfigProfile::default()),
+        }
+    }
 
-        match profile {
-            Some(key) => {
-                if let Some(profile) = self.profiles.get(key.as_str()) {
-                    return Ok(profile.clone());
-                }
+    /// The profile to use: `override_profile` (from `--profile`), then the `CODEX_PROFILE`
+    /// environment variable, then the `profile` key.
+    fn active_profile_name(&self, override_profile: Option<String>) -> Option<String> {
+        override_profile
+            .or_else(|| {
+                std::env::var(CODEX_PROFILE_ENV_VAR)
+                    .ok()
+                    .filter(|profile| !profile.is_empty())
+            })
+            .or_else(|| self.profile.clone())
+    }
 
-                Err(std::io::Error::new(
-                    std::io::ErrorKind::NotFound,
-                    format!("config profile `{key}` not found"),
-                ))
+    /// Looks up the profile named `key` with the profiles it `extends` folded in.
+    pub fn resolve_profile(&self, key: &str) -> Result<ConfigProfile, std::io::Error> {
+        let mut chain = vec![key.to_string()];
+        let mut profile = self.profiles.get(key).cloned().ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("config profile `{key}` not found"),
+            )
+        })?;
+        while let Some(parent) = profile.extends.take() {
+            if chain.contains(&parent) {
+                chain.push(parent);
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!(
+                        "config profiles extend each other in a loop: {}",
+                        chain.join(" -> ")
+                    ),
+                ));
             }
-            None => Ok(ConfigProfile::default()),
+            let Some(base) = self.profiles.get(&parent) else {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!(
+                        "config profile `{}` extends `{parent}`, which is not found",
+                        chain.last().map(String::as_str).unwrap_or(key)
+                    ),
+                ));
+            };
+            profile = profile.inherit_from(base.clone());
+            chain.push(parent);
         }
+        Ok(profile)
     }
 }
 
@@ -1367,6 +1653,8 @@ pub struct ConfigOverrides {
     pub sandbox_mode: Option<SandboxMode>,
     pub model_provider: Option<String>,
     pub config_profile: Option<String>,
+    /// Name of an entry in `[model_presets]` whose settings take precedence over the profile.
+    pub model_preset: Option<String>,
     pub codex_linux_sandbox_exe: Option<PathBuf>,
     pub js_repl_node_path: Option<PathBuf>,
     pub js_repl_node_module_dirs: Option<Vec<PathBuf>>,
@@ -1496,6 +1784,7 @@ impl Config {
             sandbox_mode,
             model_provider,
             config_profile: config_profile_key,
+            model_preset,
             codex_linux_sandbox_exe,
             js_repl_node_path: js_repl_node_path_override,
             js_repl_node_module_dirs: js_repl_node_module_dirs_override,
@@ -1511,23 +1800,20 @@ impl Config {
             additional_writable_roots,
         } = overrides;
 
-        let active_profile_name = config_profile_key
-            .as_ref()
-            .or(cfg.profile.as_ref())
-            .cloned();
+        let active_profile_name = cfg.active_profile_name(config_profile_key);
         let config_profile = match active_profile_name.as_ref() {
-            Some(key) => cfg
-                .profiles
-                .get(key)
-                .ok_or_else(|| {
-                    std::io::Error::new(
-                        std::io::ErrorKind::NotFound,
-                        format!("config profile `{key}` not found"),
-                    )
-                })?
-                .clone(),
+            Some(key) => cfg.resolve_profile(key)?,
             None => ConfigProfile::default(),
         };
+        let model_preset = match model_preset {
+            Some(name) => cfg.model_presets.get(&name).cloned().ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("model preset `{name}` not found"),
+                )
+            })?,
+            None => ModelPresetToml::default(),
+        };
 
         let feature_overrides = FeatureOverrides {
             include_apply_patch_tool: include_apply_patch_tool_override,
@@ -1625,19 +1911,84 @@ impl Config {
             .or(config_profile.model_provider)
             .or(cfg.model_provider)
             .unwrap_or_else(|| "openai".to_string());
-        let model_provider = model_providers
-            .get(&model_provider_id)
-            .ok_or_else(|| {
-                let message = if model_provider_id == LEGACY_OLLAMA_CHAT_PROVIDER_ID {
+        let lookup_model_provider = |provider_id: &str| {
+            model_providers.get(provider_id).cloned().ok_or_else(|| {
+                let message = if provider_id == LEGACY_OLLAMA_CHAT_PROVIDER_ID {
                     OLLAMA_CHAT_PROVIDER_REMOVED_ERROR.to_string()
                 } else {
-                    format!("Model provider `{model_provider_id}` not found")
+                    format!("Model provider `{provider_id}` not found")
                 };
                 std::io::Error::new(std::io::ErrorKind::NotFound, message)
-            })?
-            .clone();
+            })
+        };
+        let model_provider = lookup_model_provider(&model_provider_id)?;
+        let model_provider_fallbacks = config_profile
+            .model_provider_fallbacks
+            .or(cfg.model_provider_fallbacks)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|provider_id| *provider_id != model_provider_id)
+            .map(|provider_id| lookup_model_provider(&provider_id))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        // Surface unreadable certificate files now rather than on the first request.
+        for provider in std::iter::once(&model_provider).chain(&model_provider_fallbacks) {
+            if let Some(tls) = &provider.tls {
+                tls.apply(reqwest::Client::builder()).map_err(|err| {
+                    std::io::Error::new(
+                        err.kind(),
+                        format!(
+                            "invalid tls settings for provider `{}`: {err}",
+                            provider.name
+                        ),
+                    )
+                })?;
+            }
+        }
 
-        let shell_environment_policy = cfg.shell_environment_policy.into();
+        let redaction = cfg.redaction.unwrap_or_default();
+        crate::redaction::SecretRedactor::new(&redaction).map_err(|err| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("invalid redaction pattern: {err}"),
+            )
+        })?;
+        let approval_patterns = cfg.approval_patterns.unwrap_or_default();
+        crate::command_patterns::CommandPatterns::new(&approval_patterns).map_err(|err| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("invalid approval pattern: {err}"),
+            )
+        })?;
+        if let Some(container) = cfg.sandbox_container.as_ref()
+            && container.image.trim().is_empty()
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "sandbox_container.image must not be empty",
+            ));
+        }
+        if let Some(unsupported) = cfg
+            .sandbox_extensions
+            .iter()
+            .flat_map(|extensions| extensions.allowed_syscalls.iter())
+            .find(|name| !EXEMPTABLE_SECCOMP_SYSCALLS.contains(&name.as_str()))
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "sandbox_extensions.allowed_syscalls: unsupported syscall `{unsupported}`; expected one of {}",
+                    EXEMPTABLE_SECCOMP_SYSCALLS.join(", ")
+                ),
+            ));
+        }
+        let mut shell_environment_policy: ShellEnvironmentPolicy =
+            cfg.shell_environment_policy.into();
+        shell_environment_policy.exclude.extend(
+            redaction
+                .env_vars
+                .iter()
+                .map(|name| EnvironmentVariablePattern::new_case_insensitive(name)),
+        );
 
         let history = cfg.history.unwrap_or_default();
 
@@ -1652,6 +2003,12 @@ impl Config {
                 "agents.max_threads must be at least 1",
             ));
         }
+        if cfg.mcp_max_concurrent_calls == Some(0) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "mcp_max_concurrent_calls must be at least 1",
+            ));
+        }
         let agent_roles = cfg
             .agents
             .as_ref()
@@ -1716,7 +2073,25 @@ impl Config {
 
         let forced_login_method = cfg.forced_login_method;
 
-        let model = model.or(config_profile.model).or(cfg.model);
+        let proxy = cfg.proxy.clone().map(codex_client::ProxySettings::from);
+        if let Some(proxy) = &proxy
+            && let Err(err) = proxy.to_proxy()
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("invalid proxy.url `{}`: {err}", proxy.url),
+            ));
+        }
+
+        let account = config_profile.account.clone().or(cfg.account.clone());
+        if let Some(account) = account.as_deref() {
+            crate::auth::validate_account_name(account)?;
+        }
+
+        let model = model
+            .or(model_preset.model)
+            .or(config_profile.model)
+            .or(cfg.model);
 
         let compact_prompt = compact_prompt.or(cfg.compact_prompt).and_then(|value| {
             let trimmed = value.trim();
@@ -1800,6 +2175,7 @@ impl Config {
             exec_policy: _,
             enforce_residency,
             network: network_requirements,
+            blocked_tools,
         } = requirements;
 
         apply_requirement_constrained_value(
@@ -1821,7 +2197,9 @@ impl Config {
             &mut startup_warnings,
         )?;
 
-        let mcp_servers = constrain_mcp_servers(cfg.mcp_servers.clone(), mcp_servers.as_ref())
+        let mut configured_mcp_servers = cfg.mcp_servers.clone();
+        configured_mcp_servers.extend(config_profile.mcp_servers.clone().unwrap_or_default());
+        let mcp_servers = constrain_mcp_servers(configured_mcp_servers, mcp_servers.as_ref())
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{e}")))?;
 
         let network = match network_requirements {
@@ -1835,7 +2213,29 @@ impl Config {
                     })?;
                 Some(network)
             }
-            None => None,
+            // Without managed requirements, an egress allowlist for workspace-write starts the
+            // same proxy so sandboxed commands can reach only the listed hosts.
+            None => match (
+                constrained_sandbox_policy.value.get(),
+                cfg.sandbox_workspace_write.as_ref(),
+            ) {
+                (SandboxPolicy::WorkspaceWrite { .. }, Some(workspace_write))
+                    if workspace_write.has_egress_allowlist() =>
+                {
+                    let network = NetworkProxySpec::from_egress_allowlist(
+                        workspace_write.allowed_domains.clone(),
+                        workspace_write.allowed_cidrs.clone(),
+                    )
+                    .map_err(|err| {
+                        std::io::Error::new(
+                            err.kind(),
+                            format!("invalid sandbox_workspace_write network allowlist: {err}"),
+                        )
+                    })?;
+                    Some(network)
+                }
+                _ => None,
+            },
         };
 
         let config = Self {
@@ -1845,6 +2245,16 @@ impl Config {
             model_auto_compact_token_limit: cfg.model_auto_compact_token_limit,
             model_provider_id,
             model_provider,
+            model_provider_fallbacks,
+            model_pricing: cfg.model_pricing,
+            model_presets: cfg.model_presets,
+            budget: cfg.budget.unwrap_or_default(),
+            web_tools: cfg.web_tools.unwrap_or_default(),
+            redaction,
+            approval_patterns,
+            sandbox_container: cfg.sandbox_container,
+            sandbox_extensions: cfg.sandbox_extensions,
+            resource_limits: cfg.resource_limits.unwrap_or_default(),
             cwd: resolved_cwd,
             startup_warnings,
             permissions: Permissions {
@@ -1856,8 +2266,12 @@ impl Config {
                 macos_seatbelt_profile_extensions: None,
             },
             enforce_residency: enforce_residency.value,
+            proxy,
+            blocked_tools: blocked_tools
+                .map(|sourced| sourced.value)
+                .unwrap_or_default(),
             did_user_set_custom_approval_policy_or_sandbox_mode,
-            notify: cfg.notify,
+            notify: config_profile.notify.clone().or(cfg.notify),
             user_instructions,
             base_instructions,
             personality,
@@ -1867,11 +2281,13 @@ impl Config {
             // The config.toml omits "_mode" because it's a config file. However, "_mode"
             // is important in code to differentiate the mode from the store implementation.
             cli_auth_credentials_store_mode: cfg.cli_auth_credentials_store.unwrap_or_default(),
+            account,
             mcp_servers,
             // The config.toml omits "_mode" because it's a config file. However, "_mode"
             // is important in code to differentiate the mode from the store implementation.
             mcp_oauth_credentials_store_mode: cfg.mcp_oauth_credentials_store.unwrap_or_default(),
             mcp_oauth_callback_port: cfg.mcp_oauth_callback_port,
+            mcp_max_concurrent_calls: cfg.mcp_max_concurrent_calls,
             model_providers,
             project_doc_max_bytes: cfg.project_doc_max_bytes.unwrap_or(PROJECT_DOC_MAX_BYTES),
             project_doc_fallback_filenames: cfg
@@ -1888,11 +2304,16 @@ impl Config {
                 })
                 .collect(),
             tool_output_token_limit: cfg.tool_output_token_limit,
+            file_excerpt_threshold_bytes: cfg
+                .file_excerpt_threshold_bytes
+                .unwrap_or(DEFAULT_FILE_EXCERPT_THRESHOLD_BYTES),
             agent_max_threads,
             agent_roles,
             memories: cfg.memories.unwrap_or_default().into(),
             codex_home,
             log_dir,
+            log_format: cfg.log_format.unwrap_or_default(),
+            log_file: cfg.log_file.as_ref().map(AbsolutePathBuf::to_path_buf),
             config_layer_stack,
             history,
             ephemeral: ephemeral.unwrap_or_default(),
@@ -1907,15 +2328,20 @@ impl Config {
                 .show_raw_agent_reasoning
                 .or(show_raw_agent_reasoning)
                 .unwrap_or(false),
-            model_reasoning_effort: config_profile
-                .model_reasoning_effort
+            model_reasoning_effort: model_preset
+                .effort
+                .or(config_profile.model_reasoning_effort)
                 .or(cfg.model_reasoning_effort),
             model_reasoning_summary: config_profile
                 .model_reasoning_summary
                 .or(cfg.model_reasoning_summary)
                 .unwrap_or_default(),
             model_supports_reasoning_summaries: cfg.model_supports_reasoning_summaries,
-            model_verbosity: config_profile.model_verbosity.or(cfg.model_verbosity),
+            model_supports_tool_calls: cfg.model_supports_tool_calls,
+            model_verbosity: model_preset
+                .verbosity
+                .or(config_profile.model_verbosity)
+                .or(cfg.model_verbosity),
             chatgpt_base_url: config_profile
                 .chatgpt_base_url
                 .or(cfg.chatgpt_base_url)
@@ -1979,6 +2405,7 @@ impl Config {
                     exporter,
                     trace_exporter,
                     metrics_exporter,
+                    prometheus_listen: t.prometheus_listen,
                 }
             },
         };
@@ -2091,6 +2518,19 @@ impl Config {
             .network
             .is_some()
     }
+
+    /// Whether spawned commands are routed through the managed network proxy, either because
+    /// requirements manage the network or because `[sandbox_workspace_write]` has an allowlist.
+    /// Only managed requirements turn allowlist misses into approval prompts.
+    pub fn managed_network_enabled(&self) -> bool {
+        self.permissions.network.is_some()
+    }
+
+    /// Where CLI credentials are loaded from and saved to: the configured `account`, otherwise
+    /// the one selected with `codex account switch`.
+    pub fn auth_home(&self) -> PathBuf {
+        crate::auth::auth_home(&self.codex_home, self.account.as_deref())
+    }
 }
 
 pub(crate) fn uses_deprecated_instructions_file(config_layer_stack: &ConfigLayerStack) -> bool {
@@ -2175,7 +2615,11 @@ mod tests {
             tool_timeout_sec: None,
             enabled_tools: None,
             disabled_tools: None,
+            tool_renames: None,
+            tools: None,
+            tool_cache_ttl_sec: None,
             scopes: None,
+            max_concurrent_calls: None,
         }
     }
 
@@ -2194,7 +2638,11 @@ mod tests {
             tool_timeout_sec: None,
             enabled_tools: None,
             disabled_tools: None,
+            tool_renames: None,
+            tools: None,
+            tool_cache_ttl_sec: None,
             scopes: None,
+            max_concurrent_calls: None,
         }
     }
 
@@ -2638,6 +3086,159 @@ trust_level = "trusted"
         Ok(())
     }
 
+    #[test]
+    fn redaction_env_vars_are_excluded_from_shell_environment() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = ConfigToml {
+            redaction: Some(Redaction {
+                env_vars: vec!["AWS_SECRET_*".to_string()],
+                patterns: Vec::new(),
+            }),
+            ..Default::default()
+        };
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        let exclude = &config.permissions.shell_environment_policy.exclude;
+        assert!(
+            exclude
+                .iter()
+                .any(|pattern| pattern.matches("AWS_SECRET_ACCESS_KEY"))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn load_config_rejects_invalid_redaction_pattern() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = ConfigToml {
+            redaction: Some(Redaction {
+                env_vars: Vec::new(),
+                patterns: vec!["(unclosed".to_string()],
+            }),
+            ..Default::default()
+        };
+
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("invalid redaction pattern should be rejected");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn workspace_write_egress_allowlist_enables_managed_network() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = ConfigToml {
+            sandbox_mode: Some(SandboxMode::WorkspaceWrite),
+            sandbox_workspace_write: Some(SandboxWorkspaceWrite {
+                allowed_domains: vec!["crates.io".to_string(), "**.crates.io".to_string()],
+                allowed_cidrs: vec!["10.20.0.0/16".to_string()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+        assert!(config.managed_network_enabled());
+        assert!(!config.managed_network_requirements_enabled());
+
+        let cfg = ConfigToml {
+            sandbox_mode: Some(SandboxMode::WorkspaceWrite),
+            sandbox_workspace_write: Some(SandboxWorkspaceWrite {
+                allowed_cidrs: vec!["10.20.0.0/40".to_string()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("invalid CIDR should be rejected");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+        Ok(())
+    }
+
+    #[test]
+    fn workspace_write_with_network_access_ignores_egress_allowlist() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = ConfigToml {
+            sandbox_mode: Some(SandboxMode::WorkspaceWrite),
+            sandbox_workspace_write: Some(SandboxWorkspaceWrite {
+                network_access: true,
+                allowed_domains: vec!["crates.io".to_string()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+        assert!(!config.managed_network_enabled());
+
+        Ok(())
+    }
+
+    #[test]
+    fn sandbox_extensions_reject_unsupported_syscalls() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = ConfigToml {
+            sandbox_extensions: Some(SandboxExtensions {
+                allowed_syscalls: vec!["ptrace".to_string()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+        assert_eq!(
+            config
+                .sandbox_extensions
+                .map(|extensions| extensions.allowed_syscalls),
+            Some(vec!["ptrace".to_string()])
+        );
+
+        let cfg = ConfigToml {
+            sandbox_extensions: Some(SandboxExtensions {
+                allowed_syscalls: vec!["connect".to_string()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("network syscalls cannot be exempted");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+        Ok(())
+    }
+
     #[test]
     fn config_defaults_to_auto_oauth_store_mode() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -2796,6 +3397,154 @@ trust_level = "trusted"
         Ok(())
     }
 
+    #[test]
+    fn profiles_inherit_from_the_profiles_they_extend() -> std::io::Result<()> {
+        let cfg: ConfigToml = toml::from_str(
+            r#"
+[mcp_servers.docs]
+command = "docs-mcp"
+
+[profiles.base]
+model = "gpt-5.1"
+approval_policy = "on-request"
+notify = ["notify-send", "Codex"]
+features = { shell_snapshot = true }
+
+[profiles.base.mcp_servers.tracker]
+command = "tracker-mcp"
+
+[profiles.work]
+extends = "base"
+model = "gpt-5.1-codex"
+features = { unified_exec = true }
+
+[profiles.work.mcp_servers.docs]
+command = "docs-mcp"
+args = ["--work"]
+"#,
+        )
+        .expect("parse config");
+
+        let work = cfg.resolve_profile("work")?;
+        assert_eq!(work.extends, None);
+        assert_eq!(work.model.as_deref(), Some("gpt-5.1-codex"));
+        assert_eq!(work.approval_policy, Some(AskForApproval::OnRequest));
+        assert_eq!(
+            work.features.map(|features| features.entries),
+            Some(BTreeMap::from([
+                ("shell_snapshot".to_string(), true),
+                ("unified_exec".to_string(), true),
+            ]))
+        );
+
+        let codex_home = TempDir::new()?;
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides {
+                config_profile: Some("work".to_string()),
+                ..Default::default()
+            },
+            codex_home.path().to_path_buf(),
+        )?;
+        assert_eq!(
+            config.notify,
+            Some(vec!["notify-send".to_string(), "Codex".to_string()])
+        );
+        let mut servers = config.mcp_servers.get().keys().cloned().collect::<Vec<_>>();
+        servers.sort();
+        assert_eq!(servers, vec!["docs".to_string(), "tracker".to_string()]);
+        assert!(matches!(
+            &config.mcp_servers.get()["docs"].transport,
+            McpServerTransportConfig::Stdio { args, .. } if args == &vec!["--work".to_string()]
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn model_preset_overrides_profile_but_not_model_flag() -> std::io::Result<()> {
+        let cfg: ConfigToml = toml::from_str(
+            r#"
+profile = "work"
+model_verbosity = "high"
+
+[model_presets]
+fast = { model = "gpt-5-mini", effort = "low" }
+
+[profiles.work]
+model = "gpt-5.1-codex"
+model_reasoning_effort = "high"
+"#,
+        )
+        .expect("parse config");
+
+        let codex_home = TempDir::new()?;
+        let config = Config::load_from_base_config_with_overrides(
+            cfg.clone(),
+            ConfigOverrides {
+                model_preset: Some("fast".to_string()),
+                ..Default::default()
+            },
+            codex_home.path().to_path_buf(),
+        )?;
+        assert_eq!(config.model.as_deref(), Some("gpt-5-mini"));
+        assert_eq!(config.model_reasoning_effort, Some(ReasoningEffort::Low));
+        assert_eq!(config.model_verbosity, Some(Verbosity::High));
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg.clone(),
+            ConfigOverrides {
+                model: Some("o3".to_string()),
+                model_preset: Some("fast".to_string()),
+                ..Default::default()
+            },
+            codex_home.path().to_path_buf(),
+        )?;
+        assert_eq!(config.model.as_deref(), Some("o3"));
+        assert_eq!(config.model_reasoning_effort, Some(ReasoningEffort::Low));
+
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides {
+                model_preset: Some("slow".to_string()),
+                ..Default::default()
+            },
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("unknown preset");
+        assert_eq!(err.to_string(), "model preset `slow` not found");
+
+        Ok(())
+    }
+
+    #[test]
+    fn profiles_that_extend_each_other_in_a_loop_are_rejected() {
+        let cfg: ConfigToml = toml::from_str(
+            r#"
+[profiles.a]
+extends = "b"
+
+[profiles.b]
+extends = "a"
+
+[profiles.c]
+extends = "missing"
+"#,
+        )
+        .expect("parse config");
+
+        let err = cfg.resolve_profile("a").expect_err("loop");
+        assert_eq!(
+            err.to_string(),
+            "config profiles extend each other in a loop: a -> b -> a"
+        );
+        let err = cfg.resolve_profile("c").expect_err("missing parent");
+        assert_eq!(
+            err.to_string(),
+            "config profile `c` extends `missing`, which is not found"
+        );
+    }
+
     #[tokio::test]
     async fn project_profile_overrides_user_profile() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -3096,7 +3845,11 @@ profile = "project"
                 tool_timeout_sec: Some(Duration::from_secs(5)),
                 enabled_tools: None,
                 disabled_tools: None,
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
         );
 
@@ -3240,8 +3993,8 @@ bearer_token = "secret"
                     command: "docs-server".to_string(),
                     args: vec!["--verbose".to_string()],
                     env: Some(HashMap::from([
-                        ("ZIG_VAR".to_string(), "3".to_string()),
-                        ("ALPHA_VAR".to_string(), "1".to_string()),
+                        ("ZIG_VAR".to_string(), "3".into()),
+                        ("ALPHA_VAR".to_string(), "1".into()),
                     ])),
                     env_vars: Vec::new(),
                     cwd: None,
@@ -3253,7 +4006,11 @@ bearer_token = "secret"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
         )]);
 
@@ -3324,7 +4081,11 @@ ZIG_VAR = "3"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
         )]);
 
@@ -3375,7 +4136,11 @@ ZIG_VAR = "3"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
         )]);
 
@@ -3424,7 +4189,11 @@ ZIG_VAR = "3"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
         )]);
 
@@ -3489,7 +4258,11 @@ startup_timeout_sec = 2.0
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
         )]);
         apply_blocking(
@@ -3566,7 +4339,11 @@ X-Auth = "DOCS_AUTH"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
         )]);
 
@@ -3596,7 +4373,11 @@ X-Auth = "DOCS_AUTH"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
         );
         apply_blocking(
@@ -3664,7 +4445,11 @@ url = "https://example.com/mcp"
                     tool_timeout_sec: None,
                     enabled_tools: None,
                     disabled_tools: None,
+                    tool_renames: None,
+                    tools: None,
+                    tool_cache_ttl_sec: None,
                     scopes: None,
+                    max_concurrent_calls: None,
                 },
             ),
             (
@@ -3684,7 +4469,11 @@ url = "https://example.com/mcp"
                     tool_timeout_sec: None,
                     enabled_tools: None,
                     disabled_tools: None,
+                    tool_renames: None,
+                    tools: None,
+                    tool_cache_ttl_sec: None,
                     scopes: None,
+                    max_concurrent_calls: None,
                 },
             ),
         ]);
@@ -3767,7 +4556,11 @@ url = "https://example.com/mcp"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
         )]);
 
@@ -3812,7 +4605,11 @@ url = "https://example.com/mcp"
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
         )]);
 
@@ -3857,7 +4654,11 @@ url = "https://example.com/mcp"
                 tool_timeout_sec: None,
                 enabled_tools: Some(vec!["allowed".to_string()]),
                 disabled_tools: Some(vec!["blocked".to_string()]),
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
         )]);
 
@@ -4229,6 +5030,7 @@ model_verbosity = "high"
             env_key: Some("OPENAI_API_KEY".to_string()),
             wire_api: crate::WireApi::Responses,
             env_key_instructions: None,
+            api_key_secret: None,
             experimental_bearer_token: None,
             query_params: None,
             http_headers: None,
@@ -4236,8 +5038,12 @@ model_verbosity = "high"
             request_max_retries: Some(4),
             stream_max_retries: Some(10),
             stream_idle_timeout_ms: Some(300_000),
+            retry_base_delay_ms: None,
+            retry_max_delay_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
+            azure: None,
+            tls: None,
         };
         let model_provider_map = {
             let mut model_provider_map = built_in_model_providers();
@@ -4294,6 +5100,16 @@ model_verbosity = "high"
                 model_auto_compact_token_limit: None,
                 model_provider_id: "openai".to_string(),
                 model_provider: fixture.openai_provider.clone(),
+                model_provider_fallbacks: Vec::new(),
+                model_pricing: HashMap::new(),
+                model_presets: BTreeMap::new(),
+                budget: Budget::default(),
+                web_tools: WebTools::default(),
+                redaction: Redaction::default(),
+                approval_patterns: ApprovalPatterns::default(),
+                sandbox_container: None,
+                sandbox_extensions: None,
+                resource_limits: ResourceLimits::default(),
                 permissions: Permissions {
                     approval_policy: Constrained::allow_any(AskForApproval::Never),
                     sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
@@ -4303,23 +5119,30 @@ model_verbosity = "high"
                     macos_seatbelt_profile_extensions: None,
                 },
                 enforce_residency: Constrained::allow_any(None),
+                proxy: None,
+                blocked_tools: Vec::new(),
                 did_user_set_custom_approval_policy_or_sandbox_mode: true,
                 user_instructions: None,
                 notify: None,
                 cwd: fixture.cwd(),
                 cli_auth_credentials_store_mode: Default::default(),
+                account: None,
                 mcp_servers: Constrained::allow_any(HashMap::new()),
                 mcp_oauth_credentials_store_mode: Default::default(),
                 mcp_oauth_callback_port: None,
+                mcp_max_concurrent_calls: None,
                 model_providers: fixture.model_provider_map.clone(),
                 project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
                 project_doc_fallback_filenames: Vec::new(),
                 tool_output_token_limit: None,
+                file_excerpt_threshold_bytes: DEFAULT_FILE_EXCERPT_THRESHOLD_BYTES,
                 agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
                 agent_roles: BTreeMap::new(),
                 memories: MemoriesConfig::default(),
                 codex_home: fixture.codex_home(),
                 log_dir: fixture.codex_home().join("log"),
+                log_format: LogFormat::Text,
+                log_file: None,
                 config_layer_stack: Default::default(),
                 startup_warnings: Vec::new(),
                 history: History::default(),
@@ -4334,6 +5157,7 @@ model_verbosity = "high"
                 model_reasoning_effort: Some(ReasoningEffort::High),
                 model_reasoning_summary: ReasoningSummary::Detailed,
                 model_supports_reasoning_summaries: None,
+                model_supports_tool_calls: None,
                 model_verbosity: None,
                 personality: Some(Personality::Pragmatic),
                 chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
@@ -4408,6 +5232,16 @@ model_verbosity = "high"
             model_auto_compact_token_limit: None,
             model_provider_id: "openai-custom".to_string(),
             model_provider: fixture.openai_custom_provider.clone(),
+            model_provider_fallbacks: Vec::new(),
+            model_pricing: HashMap::new(),
+            model_presets: BTreeMap::new(),
+            budget: Budget::default(),
+            web_tools: WebTools::default(),
+            redaction: Redaction::default(),
+            approval_patterns: ApprovalPatterns::default(),
+            sandbox_container: None,
+            sandbox_extensions: None,
+            resource_limits: ResourceLimits::default(),
             permissions: Permissions {
                 approval_policy: Constrained::allow_any(AskForApproval::UnlessTrusted),
                 sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
@@ -4417,23 +5251,30 @@ model_verbosity = "high"
                 macos_seatbelt_profile_extensions: None,
             },
             enforce_residency: Constrained::allow_any(None),
+            proxy: None,
+            blocked_tools: Vec::new(),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             user_instructions: None,
             notify: None,
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
+            account: None,
             mcp_servers: Constrained::allow_any(HashMap::new()),
             mcp_oauth_credentials_store_mode: Default::default(),
             mcp_oauth_callback_port: None,
+            mcp_max_concurrent_calls: None,
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
+            file_excerpt_threshold_bytes: DEFAULT_FILE_EXCERPT_THRESHOLD_BYTES,
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
             agent_roles: BTreeMap::new(),
             memories: MemoriesConfig::default(),
             codex_home: fixture.codex_home(),
             log_dir: fixture.codex_home().join("log"),
+            log_format: LogFormat::Text,
+            log_file: None,
             config_layer_stack: Default::default(),
             startup_warnings: Vec::new(),
             history: History::default(),
@@ -4448,6 +5289,7 @@ model_verbosity = "high"
             model_reasoning_effort: None,
             model_reasoning_summary: ReasoningSummary::default(),
             model_supports_reasoning_summaries: None,
+            model_supports_tool_calls: None,
             model_verbosity: None,
             personality: Some(Personality::Pragmatic),
             chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
@@ -4520,6 +5362,16 @@ model_verbosity = "high"
             model_auto_compact_token_limit: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
+            model_provider_fallbacks: Vec::new(),
+            model_pricing: HashMap::new(),
+            model_presets: BTreeMap::new(),
+            budget: Budget::default(),
+            web_tools: WebTools::default(),
+            redaction: Redaction::default(),
+            approval_patterns: ApprovalPatterns::default(),
+            sandbox_container: None,
+            sandbox_extensions: None,
+            resource_limits: ResourceLimits::default(),
             permissions: Permissions {
                 approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
                 sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
@@ -4529,23 +5381,30 @@ model_verbosity = "high"
                 macos_seatbelt_profile_extensions: None,
             },
             enforce_residency: Constrained::allow_any(None),
+            proxy: None,
+            blocked_tools: Vec::new(),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             user_instructions: None,
             notify: None,
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
+            account: None,
             mcp_servers: Constrained::allow_any(HashMap::new()),
             mcp_oauth_credentials_store_mode: Default::default(),
             mcp_oauth_callback_port: None,
+            mcp_max_concurrent_calls: None,
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
+            file_excerpt_threshold_bytes: DEFAULT_FILE_EXCERPT_THRESHOLD_BYTES,
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
             agent_roles: BTreeMap::new(),
             memories: MemoriesConfig::default(),
             codex_home: fixture.codex_home(),
             log_dir: fixture.codex_home().join("log"),
+            log_format: LogFormat::Text,
+            log_file: None,
             config_layer_stack: Default::default(),
             startup_warnings: Vec::new(),
             history: History::default(),
@@ -4560,6 +5419,7 @@ model_verbosity = "high"
             model_reasoning_effort: None,
             model_reasoning_summary: ReasoningSummary::default(),
             model_supports_reasoning_summaries: None,
+            model_supports_tool_calls: None,
             model_verbosity: None,
             personality: Some(Personality::Pragmatic),
             chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
@@ -4618,6 +5478,16 @@ model_verbosity = "high"
             model_auto_compact_token_limit: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
+            model_provider_fallbacks: Vec::new(),
+            model_pricing: HashMap::new(),
+            model_presets: BTreeMap::new(),
+            budget: Budget::default(),
+            web_tools: WebTools::default(),
+            redaction: Redaction::default(),
+            approval_patterns: ApprovalPatterns::default(),
+            sandbox_container: None,
+            sandbox_extensions: None,
+            resource_limits: ResourceLimits::default(),
             permissions: Permissions {
                 approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
                 sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
@@ -4627,23 +5497,30 @@ model_verbosity = "high"
                 macos_seatbelt_profile_extensions: None,
             },
             enforce_residency: Constrained::allow_any(None),
+            proxy: None,
+            blocked_tools: Vec::new(),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             user_instructions: None,
             notify: None,
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
+            account: None,
             mcp_servers: Constrained::allow_any(HashMap::new()),
             mcp_oauth_credentials_store_mode: Default::default(),
             mcp_oauth_callback_port: None,
+            mcp_max_concurrent_calls: None,
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
+            file_excerpt_threshold_bytes: DEFAULT_FILE_EXCERPT_THRESHOLD_BYTES,
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
             agent_roles: BTreeMap::new(),
             memories: MemoriesConfig::default(),
             codex_home: fixture.codex_home(),
             log_dir: fixture.codex_home().join("log"),
+            log_format: LogFormat::Text,
+            log_file: None,
             config_layer_stack: Default::default(),
             startup_warnings: Vec::new(),
             history: History::default(),
@@ -4658,6 +5535,7 @@ model_verbosity = "high"
             model_reasoning_effort: Some(ReasoningEffort::High),
             model_reasoning_summary: ReasoningSummary::Detailed,
             model_supports_reasoning_summaries: None,
+            model_supports_tool_calls: None,
             model_verbosity: Some(Verbosity::High),
             personality: Some(Personality::Pragmatic),
             chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
@@ -4728,6 +5606,8 @@ model_verbosity = "high"
             rules: None,
             enforce_residency: None,
             network: None,
+            blocked_tools: None,
+            requirements_url: None,
         };
         let requirement_source = crate::config_loader::RequirementSource::Unknown;
         let requirement_source_for_error = requirement_source.clone();
@@ -4876,6 +5756,35 @@ trust_level = "trusted"
         Ok(())
     }
 
+    #[test]
+    fn test_load_and_clear_project_trust_levels() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let codex_home = temp_dir.path();
+        assert_eq!(load_project_trust_levels(codex_home)?, BTreeMap::new());
+
+        set_project_trust_level(codex_home, Path::new("/work/trusted"), TrustLevel::Trusted)?;
+        set_project_trust_level(
+            codex_home,
+            Path::new("/work/untrusted"),
+            TrustLevel::Untrusted,
+        )?;
+        assert_eq!(
+            load_project_trust_levels(codex_home)?,
+            BTreeMap::from([
+                ("/work/trusted".to_string(), TrustLevel::Trusted),
+                ("/work/untrusted".to_string(), TrustLevel::Untrusted),
+            ])
+        );
+
+        clear_project_trust_level(codex_home, Path::new("/work/trusted"))?;
+        assert_eq!(
+            load_project_trust_levels(codex_home)?,
+            BTreeMap::from([("/work/untrusted".to_string(), TrustLevel::Untrusted)])
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_set_default_oss_provider() -> std::io::Result<()> {
         let temp_dir = TempDir::new()?;
@@ -5277,6 +6186,8 @@ mcp_oauth_callback_port = 5678
             rules: None,
             enforce_residency: None,
             network: None,
+            blocked_tools: None,
+            requirements_url: None,
         };
 
         let config = ConfigBuilder::default()
@@ -96,6 +96,25 @@ impl NetworkProxySpec {
         })
     }
 
+    /// Builds a proxy that only lets traffic through to the given hosts and IP networks.
+    pub(crate) fn from_egress_allowlist(
+        allowed_domains: Vec<String>,
+        allowed_cidrs: Vec<String>,
+    ) -> std::io::Result<Self> {
+        let mut config = NetworkProxyConfig::default();
+        config.network.enabled = true;
+        config.network.allowed_domains = allowed_domains;
+        config.network.allowed_cidrs = allowed_cidrs;
+        let constraints = NetworkProxyConstraints::default();
+        build_config_state(config.clone(), constraints.clone()).map_err(|err| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{err:#}"))
+        })?;
+        Ok(Self {
+            config,
+            constraints,
+        })
+    }
+
     pub async fn start_proxy(
         &self,
         sandbox_policy: &SandboxPolicy,
@@ -1,8 +1,11 @@
+use std::collections::HashMap;
+
 use codex_utils_absolute_path::AbsolutePathBuf;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::config::types::McpServerConfig;
 use crate::config::types::Personality;
 use crate::config::types::WindowsToml;
 use crate::protocol::AskForApproval;
@@ -17,10 +20,14 @@ use codex_protocol::openai_models::ReasoningEffort;
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[schemars(deny_unknown_fields)]
 pub struct ConfigProfile {
+    /// Another profile whose settings this one starts from. Settings set here win.
+    pub extends: Option<String>,
     pub model: Option<String>,
     /// The key in the `model_providers` map identifying the
     /// [`ModelProviderInfo`] to use.
     pub model_provider: Option<String>,
+    /// Ordered fallback providers, overriding the top-level `model_provider_fallbacks`.
+    pub model_provider_fallbacks: Option<Vec<String>>,
     pub approval_policy: Option<AskForApproval>,
     pub sandbox_mode: Option<SandboxMode>,
     pub model_reasoning_effort: Option<ReasoningEffort>,
@@ -28,6 +35,8 @@ pub struct ConfigProfile {
     pub model_verbosity: Option<Verbosity>,
     pub personality: Option<Personality>,
     pub chatgpt_base_url: Option<String>,
+    /// Named account (see `codex account`) whose credentials sessions of this profile use.
+    pub account: Option<String>,
     /// Optional path to a file containing model instructions.
     pub model_instructions_file: Option<AbsolutePathBuf>,
     pub js_repl_node_path: Option<AbsolutePathBuf>,
@@ -54,6 +63,102 @@ pub struct ConfigProfile {
     #[schemars(schema_with = "crate::config::schema::features_schema")]
     pub features: Option<crate::features::FeaturesToml>,
     pub oss_provider: Option<String>,
+    /// Replaces the top-level `notify` command.
+    pub notify: Option<Vec<String>>,
+    /// MCP servers added to the top-level `mcp_servers`, replacing servers of the same name.
+    #[serde(default)]
+    // Uses the raw MCP input shape (custom deserialization) rather than `McpServerConfig`.
+    #[schemars(schema_with = "crate::config::schema::mcp_servers_schema")]
+    pub mcp_servers: Option<HashMap<String, McpServerConfig>>,
+}
+
+impl ConfigProfile {
+    /// Fills in the settings this profile leaves unset from `base`, the profile it extends.
+    /// Feature toggles and MCP servers are merged, with this profile's entries winning.
+    pub(crate) fn inherit_from(self, base: ConfigProfile) -> ConfigProfile {
+        let ConfigProfile {
+            extends,
+            model,
+            model_provider,
+            model_provider_fallbacks,
+            approval_policy,
+            sandbox_mode,
+            model_reasoning_effort,
+            model_reasoning_summary,
+            model_verbosity,
+            personality,
+            chatgpt_base_url,
+            account,
+            model_instructions_file,
+            js_repl_node_path,
+            js_repl_node_module_dirs,
+            zsh_path,
+            experimental_instructions_file,
+            experimental_compact_prompt_file,
+            include_apply_patch_tool,
+            experimental_use_unified_exec_tool,
+            experimental_use_freeform_apply_patch,
+            tools_web_search,
+            tools_view_image,
+            web_search,
+            analytics,
+            windows,
+            features,
+            oss_provider,
+            notify,
+            mcp_servers,
+        } = self;
+        let features = match (base.features, features) {
+            (Some(mut base), Some(features)) => {
+                base.entries.extend(features.entries);
+                Some(base)
+            }
+            (base, features) => features.or(base),
+        };
+        let mcp_servers = match (base.mcp_servers, mcp_servers) {
+            (Some(mut base), Some(mcp_servers)) => {
+                base.extend(mcp_servers);
+                Some(base)
+            }
+            (base, mcp_servers) => mcp_servers.or(base),
+        };
+        ConfigProfile {
+            extends,
+            model: model.or(base.model),
+            model_provider: model_provider.or(base.model_provider),
+            model_provider_fallbacks: model_provider_fallbacks.or(base.model_provider_fallbacks),
+            approval_policy: approval_policy.or(base.approval_policy),
+            sandbox_mode: sandbox_mode.or(base.sandbox_mode),
+            model_reasoning_effort: model_reasoning_effort.or(base.model_reasoning_effort),
+            model_reasoning_summary: model_reasoning_summary.or(base.model_reasoning_summary),
+            model_verbosity: model_verbosity.or(base.model_verbosity),
+            personality: personality.or(base.personality),
+            chatgpt_base_url: chatgpt_base_url.or(base.chatgpt_base_url),
+            account: account.or(base.account),
+            model_instructions_file: model_instructions_file.or(base.model_instructions_file),
+            js_repl_node_path: js_repl_node_path.or(base.js_repl_node_path),
+            js_repl_node_module_dirs: js_repl_node_module_dirs.or(base.js_repl_node_module_dirs),
+            zsh_path: zsh_path.or(base.zsh_path),
+            experimental_instructions_file: experimental_instructions_file
+                .or(base.experimental_instructions_file),
+            experimental_compact_prompt_file: experimental_compact_prompt_file
+                .or(base.experimental_compact_prompt_file),
+            include_apply_patch_tool: include_apply_patch_tool.or(base.include_apply_patch_tool),
+            experimental_use_unified_exec_tool: experimental_use_unified_exec_tool
+                .or(base.experimental_use_unified_exec_tool),
+            experimental_use_freeform_apply_patch: experimental_use_freeform_apply_patch
+                .or(base.experimental_use_freeform_apply_patch),
+            tools_web_search: tools_web_search.or(base.tools_web_search),
+            tools_view_image: tools_view_image.or(base.tools_view_image),
+            web_search: web_search.or(base.web_search),
+            analytics: analytics.or(base.analytics),
+            windows: windows.or(base.windows),
+            features,
+            oss_provider: oss_provider.or(base.oss_provider),
+            notify: notify.or(base.notify),
+            mcp_servers,
+        }
+    }
 }
 
 impl From<ConfigProfile> for codex_app_server_protocol::Profile {
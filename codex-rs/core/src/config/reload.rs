@@ -0,0 +1,296 @@
+//! Picks up edits to the config files of a running session. Settings that can change without
+//! restarting the session (`notify`, `mcp_servers` and `approval_patterns`) are applied; other
+//! changed settings, such as the model provider and its credentials, are left for the next
+//! session and reported as deferred. Edits this process made itself, such as `/model` saving the
+//! selected model, are not reported.
+
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use std::sync::Mutex as StdMutex;
+
+use codex_app_server_protocol::ConfigLayerSource;
+use codex_protocol::protocol::ConfigReloadedEvent;
+use toml::Value as TomlValue;
+
+use crate::command_patterns::CommandPatterns;
+use crate::config::Config;
+use crate::config::ConfigBuilder;
+use crate::config::ConfigOverrides;
+use crate::config_loader::ConfigLayerStackOrdering;
+
+/// Top-level config keys that a running session picks up.
+const RELOADABLE_KEYS: &[&str] = &["approval_patterns", "mcp_servers", "notify"];
+
+/// What this process last wrote to each config file.
+static OWN_WRITES: LazyLock<StdMutex<HashMap<PathBuf, String>>> =
+    LazyLock::new(|| StdMutex::new(HashMap::new()));
+
+/// Remembers that this process wrote `contents` to the config file at `path`, so sessions
+/// watching the file do not report the edit as someone else's.
+pub(crate) fn record_own_write(path: &Path, contents: &str) {
+    OWN_WRITES
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .insert(normalize_path(path), contents.to_string());
+}
+
+/// Whether each of `paths` still holds what this process last wrote to it.
+pub(crate) fn are_own_writes(paths: &[PathBuf]) -> bool {
+    let own_writes = OWN_WRITES
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    !paths.is_empty()
+        && paths.iter().all(|path| {
+            own_writes
+                .get(&normalize_path(path))
+                .is_some_and(|written| std::fs::read_to_string(path).ok().as_ref() == Some(written))
+        })
+}
+
+fn normalize_path(path: &Path) -> PathBuf {
+    dunce::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// What a running session should change after its config files were edited.
+pub(crate) struct ConfigReload {
+    /// The session's config with the reloadable settings replaced.
+    pub(crate) config: Config,
+    /// Set when `approval_patterns` changed.
+    pub(crate) command_patterns: Option<CommandPatterns>,
+    pub(crate) event: ConfigReloadedEvent,
+    /// Changed settings that could not be applied.
+    pub(crate) errors: Vec<String>,
+}
+
+/// Loads the config files again with the profile, working directory and `-c` overrides of
+/// `config`.
+pub(crate) async fn reload_config(config: &Config) -> std::io::Result<Config> {
+    let cli_overrides = config
+        .config_layer_stack
+        .get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, false)
+        .into_iter()
+        .filter(|layer| matches!(layer.name, ConfigLayerSource::SessionFlags))
+        .filter_map(|layer| layer.config.as_table())
+        .flat_map(|table| {
+            table
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+        })
+        .collect();
+    ConfigBuilder::default()
+        .codex_home(config.codex_home.clone())
+        .cli_overrides(cli_overrides)
+        .harness_overrides(ConfigOverrides {
+            cwd: Some(config.cwd.clone()),
+            config_profile: config.active_profile.clone(),
+            ..Default::default()
+        })
+        .build()
+        .await
+}
+
+impl ConfigReload {
+    /// Compares the config files a session last loaded with the reloaded ones. The returned config
+    /// holds the reloaded files, so the next reload only reports what changed after this one.
+    pub(crate) fn between(current: &Config, reloaded: &Config) -> Self {
+        let mut config = current.clone();
+        config.config_layer_stack = current
+            .config_layer_stack
+            .with_layers_of(&reloaded.config_layer_stack);
+        let mut command_patterns = None;
+        let mut applied = Vec::new();
+        let mut errors = Vec::new();
+
+        if reloaded.notify != current.notify {
+            config.notify = reloaded.notify.clone();
+            applied.push("notify".to_string());
+        }
+        if reloaded.approval_patterns != current.approval_patterns {
+            match CommandPatterns::new(&reloaded.approval_patterns) {
+                Ok(patterns) => {
+                    command_patterns = Some(patterns);
+                    config.approval_patterns = reloaded.approval_patterns.clone();
+                    applied.push("approval_patterns".to_string());
+                }
+                Err(err) => errors.push(format!("invalid approval pattern: {err}")),
+            }
+        }
+        // Setting the servers through the running session's constraint keeps the requirements
+        // it started with, which the reloaded config may not have (for example cloud ones).
+        if reloaded.mcp_servers.get() != current.mcp_servers.get() {
+            match config.mcp_servers.set(reloaded.mcp_servers.get().clone()) {
+                Ok(()) if config.mcp_servers.get() != current.mcp_servers.get() => {
+                    config.mcp_oauth_credentials_store_mode =
+                        reloaded.mcp_oauth_credentials_store_mode;
+                    applied.push("mcp_servers".to_string());
+                }
+                Ok(()) => {}
+                Err(err) => errors.push(err.to_string()),
+            }
+        }
+
+        let deferred = changed_keys(
+            &current.config_layer_stack.effective_config(),
+            &reloaded.config_layer_stack.effective_config(),
+        )
+        .into_iter()
+        .filter(|key| !RELOADABLE_KEYS.contains(&key.as_str()))
+        .collect();
+
+        Self {
+            config,
+            command_patterns,
+            event: ConfigReloadedEvent { applied, deferred },
+            errors,
+        }
+    }
+
+    /// Drops the deferred settings, for edits the user made through this process and already
+    /// knows about.
+    pub(crate) fn without_deferred(mut self) -> Self {
+        self.event.deferred.clear();
+        self
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.event.applied.is_empty() && self.event.deferred.is_empty() && self.errors.is_empty()
+    }
+}
+
+/// Top-level keys whose values differ between two effective configs, sorted.
+fn changed_keys(current: &TomlValue, reloaded: &TomlValue) -> BTreeSet<String> {
+    let (Some(current), Some(reloaded)) = (current.as_table(), reloaded.as_table()) else {
+        return BTreeSet::new();
+    };
+    current
+        .keys()
+        .chain(reloaded.keys())
+        .filter(|key| current.get(*key) != reloaded.get(*key))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CONFIG_TOML_FILE;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    async fn load(codex_home: &TempDir, config_toml: &str) -> std::io::Result<Config> {
+        std::fs::write(codex_home.path().join(CONFIG_TOML_FILE), config_toml)?;
+        ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .harness_overrides(ConfigOverrides {
+                cwd: Some(codex_home.path().to_path_buf()),
+                ..Default::default()
+            })
+            .build()
+            .await
+    }
+
+    #[tokio::test]
+    async fn applies_reloadable_settings_and_defers_the_rest() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let current = load(
+            &codex_home,
+            r#"
+model = "gpt-5.2"
+notify = ["notify-send", "Codex"]
+"#,
+        )
+        .await?;
+        std::fs::write(
+            codex_home.path().join(CONFIG_TOML_FILE),
+            r#"
+model = "gpt-5.2-codex"
+notify = ["say", "done"]
+
+[approval_patterns]
+allow = ["cargo test*"]
+
+[mcp_servers.docs]
+command = "docs-mcp"
+"#,
+        )?;
+        let reloaded = reload_config(&current).await?;
+
+        let reload = ConfigReload::between(&current, &reloaded);
+
+        assert_eq!(
+            reload.event,
+            ConfigReloadedEvent {
+                applied: vec![
+                    "notify".to_string(),
+                    "approval_patterns".to_string(),
+                    "mcp_servers".to_string(),
+                ],
+                deferred: vec!["model".to_string()],
+            }
+        );
+        assert_eq!(reload.errors, Vec::<String>::new());
+        assert_eq!(
+            reload.config.notify,
+            Some(vec!["say".to_string(), "done".to_string()])
+        );
+        assert_eq!(reload.config.model, current.model);
+        assert!(reload.config.mcp_servers.get().contains_key("docs"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn deferred_settings_are_reported_once() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let current = load(&codex_home, "model = \"gpt-5.2\"\n").await?;
+        std::fs::write(
+            codex_home.path().join(CONFIG_TOML_FILE),
+            "model = \"gpt-5.2-codex\"\n",
+        )?;
+        let first = ConfigReload::between(&current, &reload_config(&current).await?);
+        assert_eq!(first.event.deferred, vec!["model".to_string()]);
+
+        std::fs::write(
+            codex_home.path().join(CONFIG_TOML_FILE),
+            "model = \"gpt-5.2-codex\"\nnotify = [\"say\"]\n",
+        )?;
+        let second = ConfigReload::between(&first.config, &reload_config(&first.config).await?);
+
+        assert_eq!(
+            second.event,
+            ConfigReloadedEvent {
+                applied: vec!["notify".to_string()],
+                deferred: Vec::new(),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn own_writes_are_recognized_until_the_file_is_edited_again() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let path = codex_home.path().join(CONFIG_TOML_FILE);
+        std::fs::write(&path, "model = \"gpt-5.2\"\n")?;
+        assert!(!are_own_writes(std::slice::from_ref(&path)));
+
+        record_own_write(&path, "model = \"gpt-5.2\"\n");
+        assert!(are_own_writes(std::slice::from_ref(&path)));
+
+        std::fs::write(&path, "model = \"gpt-5.2-codex\"\n")?;
+        assert!(!are_own_writes(std::slice::from_ref(&path)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unchanged_files_reload_to_nothing() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let current = load(&codex_home, "notify = [\"notify-send\", \"Codex\"]\n").await?;
+
+        let reloaded = reload_config(&current).await?;
+
+        assert!(ConfigReload::between(&current, &reloaded).is_empty());
+        Ok(())
+    }
+}
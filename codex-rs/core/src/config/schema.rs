@@ -11,6 +11,7 @@ use schemars::schema::SchemaObject;
 use serde_json::Map;
 use serde_json::Value;
 use std::path::Path;
+use std::sync::LazyLock;
 
 /// Schema for the `[features]` map with known + legacy keys only.
 pub(crate) fn features_schema(schema_gen: &mut SchemaGenerator) -> Schema {
@@ -62,6 +63,73 @@ pub fn config_schema() -> RootSchema {
         .into_root_schema_for::<ConfigToml>()
 }
 
+/// Older names that settings are still read under, which the schema leaves out.
+const LEGACY_KEYS: &[&[&str]] = &[
+    &["tools", "web_search_request"],
+    &["ghost_snapshot", "ignore_untracked_files_over_bytes"],
+    &["ghost_snapshot", "large_untracked_dir_warning_threshold"],
+];
+
+static CONFIG_SCHEMA_VALUE: LazyLock<Value> =
+    LazyLock::new(|| serde_json::to_value(config_schema()).unwrap_or_default());
+
+/// Checks that the dotted `key_path` (e.g. `tui.notifications` or `profiles.work.model`) names
+/// a setting that `config.toml` accepts.
+pub fn check_config_key(key_path: &str) -> Result<(), String> {
+    let segments: Vec<&str> = key_path.split('.').collect();
+    if is_known_config_key(&segments) {
+        Ok(())
+    } else {
+        Err(format!("unknown config key `{key_path}`"))
+    }
+}
+
+/// Whether `config.toml` accepts the key at `segments`, one table name per segment.
+pub fn is_known_config_key(segments: &[&str]) -> bool {
+    let schema = &*CONFIG_SCHEMA_VALUE;
+    let definitions = schema.get("definitions").unwrap_or(&Value::Null);
+    accepts_key_path(schema, segments, definitions)
+        || LEGACY_KEYS.iter().any(|legacy| *legacy == segments)
+}
+
+fn accepts_key_path(schema: &Value, segments: &[&str], definitions: &Value) -> bool {
+    let schema = match schema {
+        Value::Bool(accepts) => return *accepts,
+        Value::Object(schema) => schema,
+        _ => return false,
+    };
+    let Some((segment, rest)) = segments.split_first() else {
+        return true;
+    };
+    if let Some(Value::String(reference)) = schema.get("$ref") {
+        return reference
+            .strip_prefix("#/definitions/")
+            .and_then(|name| definitions.get(name))
+            .is_some_and(|resolved| accepts_key_path(resolved, segments, definitions));
+    }
+    let branches: Vec<&Value> = ["allOf", "anyOf", "oneOf"]
+        .iter()
+        .filter_map(|keyword| schema.get(*keyword).and_then(Value::as_array))
+        .flatten()
+        .collect();
+    if !branches.is_empty() {
+        return branches
+            .iter()
+            .any(|branch| accepts_key_path(branch, segments, definitions));
+    }
+    if let Some(property) = schema
+        .get("properties")
+        .and_then(|properties| properties.get(*segment))
+    {
+        return accepts_key_path(property, rest, definitions);
+    }
+    match schema.get("additionalProperties") {
+        Some(additional) => accepts_key_path(additional, rest, definitions),
+        // A schema without properties only has keys when it does not say what type it is.
+        None => !schema.contains_key("properties") && !schema.contains_key("type"),
+    }
+}
+
 /// Canonicalize a JSON value by sorting its keys.
 fn canonicalize(value: &Value) -> Value {
     match value {
@@ -98,6 +166,7 @@ pub fn write_config_schema(out_path: &Path) -> anyhow::Result<()> {
 #[cfg(test)]
 mod tests {
     use super::canonicalize;
+    use super::check_config_key;
     use super::config_schema_json;
     use super::write_config_schema;
 
@@ -146,4 +215,25 @@ Run `just write-config-schema` to overwrite with your changes.\n\n{diff}"
             "fixture should match exactly with generated schema"
         );
     }
+
+    #[test]
+    fn check_config_key_follows_the_schema() {
+        for key in [
+            "model",
+            "tui.notifications",
+            "profiles.work.model",
+            "mcp_servers.docs.command",
+            "model_providers.internal.base_url",
+        ] {
+            assert_eq!(check_config_key(key), Ok(()), "{key}");
+        }
+        assert_eq!(check_config_key("tools.web_search_request"), Ok(()));
+        for key in ["modle", "tui.theme", "profiles.work.nope", "model.name"] {
+            assert_eq!(
+                check_config_key(key),
+                Err(format!("unknown config key `{key}`")),
+                "{key}"
+            );
+        }
+    }
 }
@@ -7,7 +7,9 @@ use crate::config_loader::RequirementSource;
 pub use codex_protocol::config_types::AltScreenMode;
 pub use codex_protocol::config_types::ModeKind;
 pub use codex_protocol::config_types::Personality;
+use codex_protocol::config_types::Verbosity;
 pub use codex_protocol::config_types::WebSearchMode;
+use codex_protocol::openai_models::ReasoningEffort;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
@@ -41,6 +43,33 @@ pub struct WindowsToml {
     pub sandbox: Option<WindowsSandboxModeToml>,
 }
 
+/// `[proxy]`: the proxy every HTTP client uses (model providers, login, HTTP MCP servers),
+/// replacing the `HTTPS_PROXY`, `HTTP_PROXY`, `ALL_PROXY` and `NO_PROXY` environment variables.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ProxyToml {
+    /// `http://`, `https://`, `socks5://` or `socks5h://` URL of the proxy.
+    pub url: String,
+    /// Hosts, domains (`.example.com`), IPs and CIDR ranges to reach without the proxy.
+    #[serde(default)]
+    pub no_proxy: Vec<String>,
+    /// User name for a proxy that requires basic authentication.
+    pub username: Option<String>,
+    /// Password for `username`; use `${VAR}` to keep it out of the file.
+    pub password: Option<String>,
+}
+
+impl From<ProxyToml> for codex_client::ProxySettings {
+    fn from(proxy: ProxyToml) -> Self {
+        Self {
+            url: proxy.url,
+            no_proxy: proxy.no_proxy,
+            username: proxy.username,
+            password: proxy.password,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum McpServerDisabledReason {
     Unknown,
@@ -87,7 +116,22 @@ pub struct McpServerConfig {
     #[serde(default, with = "option_duration_secs")]
     pub tool_timeout_sec: Option<Duration>,
 
+    /// How long the result of a call to a read-only tool is reused for identical calls in the
+    /// same session. Results are not cached when unset.
+    #[serde(
+        default,
+        with = "option_duration_secs",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub tool_cache_ttl_sec: Option<Duration>,
+
+    /// Most calls to this server's tools that run at once; further calls wait in line in the
+    /// order they were made. Unlimited when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_calls: Option<usize>,
+
     /// Explicit allow-list of tools exposed from this server. When set, only these tools will be registered.
+    /// Entries are tool names, globs such as `jira_*`, or the categories `@read_only` and `@mutating`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub enabled_tools: Option<Vec<String>>,
 
@@ -95,6 +139,15 @@ pub struct McpServerConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub disabled_tools: Option<Vec<String>>,
 
+    /// Names to expose tools under, keyed by the name the server gives them. Renamed tools are
+    /// still qualified with the server name; use this to resolve a collision with another server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_renames: Option<HashMap<String, String>>,
+
+    /// Per-tool overrides, keyed by the name the server gives the tool.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<HashMap<String, McpToolConfig>>,
+
     /// Optional OAuth scopes to request during MCP login.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub scopes: Option<Vec<String>>,
@@ -110,7 +163,7 @@ pub(crate) struct RawMcpServerConfig {
     #[serde(default)]
     pub args: Option<Vec<String>>,
     #[serde(default)]
-    pub env: Option<HashMap<String, String>>,
+    pub env: Option<HashMap<String, McpEnvValue>>,
     #[serde(default)]
     pub env_vars: Option<Vec<String>>,
     #[serde(default)]
@@ -132,6 +185,12 @@ pub(crate) struct RawMcpServerConfig {
     #[serde(default, with = "option_duration_secs")]
     #[schemars(with = "Option<f64>")]
     pub tool_timeout_sec: Option<Duration>,
+    #[serde(default, with = "option_duration_secs")]
+    #[schemars(with = "Option<f64>")]
+    pub tool_cache_ttl_sec: Option<Duration>,
+    #[serde(default)]
+    #[schemars(range(min = 1))]
+    pub max_concurrent_calls: Option<usize>,
     #[serde(default)]
     pub enabled: Option<bool>,
     #[serde(default)]
@@ -141,9 +200,44 @@ pub(crate) struct RawMcpServerConfig {
     #[serde(default)]
     pub disabled_tools: Option<Vec<String>>,
     #[serde(default)]
+    pub tool_renames: Option<HashMap<String, String>>,
+    #[serde(default)]
+    pub tools: Option<HashMap<String, McpToolConfig>>,
+    #[serde(default)]
     pub scopes: Option<Vec<String>>,
 }
 
+/// Overrides for calls to one tool of an MCP server.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct McpToolConfig {
+    /// Timeout in seconds for calls to this tool, replacing the server's `tool_timeout_sec`.
+    #[serde(
+        default,
+        with = "option_duration_secs",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schemars(with = "Option<f64>")]
+    pub timeout_sec: Option<Duration>,
+
+    /// How many more times a call is attempted after it times out or the connection to the
+    /// server fails. Errors returned by the tool itself are not retried. Only set this for tools
+    /// that are safe to call more than once, such as searches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+
+    /// How long results of this tool are reused for identical calls, replacing the server's
+    /// `tool_cache_ttl_sec`. Unlike that setting, it also applies to tools that are not
+    /// annotated as read-only; `0` turns caching off for this tool.
+    #[serde(
+        default,
+        with = "option_duration_secs",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schemars(with = "Option<f64>")]
+    pub cache_ttl_sec: Option<Duration>,
+}
+
 impl<'de> Deserialize<'de> for McpServerConfig {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -160,10 +254,19 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             (None, None) => None,
         };
         let tool_timeout_sec = raw.tool_timeout_sec;
+        let tool_cache_ttl_sec = raw.tool_cache_ttl_sec;
+        let max_concurrent_calls = raw.max_concurrent_calls;
+        if max_concurrent_calls == Some(0) {
+            return Err(SerdeError::custom(
+                "max_concurrent_calls must be at least 1",
+            ));
+        }
         let enabled = raw.enabled.unwrap_or_else(default_enabled);
         let required = raw.required.unwrap_or_default();
         let enabled_tools = raw.enabled_tools.clone();
         let disabled_tools = raw.disabled_tools.clone();
+        let tool_renames = raw.tool_renames.clone();
+        let tools = raw.tools.clone();
         let scopes = raw.scopes.clone();
 
         fn throw_if_set<E, T>(transport: &str, field: &str, value: Option<&T>) -> Result<(), E>
@@ -215,11 +318,15 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             transport,
             startup_timeout_sec,
             tool_timeout_sec,
+            tool_cache_ttl_sec,
+            max_concurrent_calls,
             enabled,
             required,
             disabled_reason: None,
             enabled_tools,
             disabled_tools,
+            tool_renames,
+            tools,
             scopes,
         })
     }
@@ -238,7 +345,7 @@ pub enum McpServerTransportConfig {
         #[serde(default)]
         args: Vec<String>,
         #[serde(default, skip_serializing_if = "Option::is_none")]
-        env: Option<HashMap<String, String>>,
+        env: Option<HashMap<String, McpEnvValue>>,
         #[serde(default, skip_serializing_if = "Vec::is_empty")]
         env_vars: Vec<String>,
         #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -261,6 +368,35 @@ pub enum McpServerTransportConfig {
     },
 }
 
+/// Value of an environment variable set for a stdio MCP server. Secrets can be looked up each
+/// time the server starts instead of being written into the config file.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(untagged, deny_unknown_fields)]
+pub enum McpEnvValue {
+    Literal(String),
+    /// A secret saved in the OS keychain with `codex mcp set-secret <name>`.
+    Keychain {
+        keychain: String,
+    },
+    /// The output of a command, e.g. `["op", "read", "op://vault/jira/token"]`, with surrounding
+    /// whitespace trimmed.
+    Command {
+        command: Vec<String>,
+    },
+}
+
+impl From<String> for McpEnvValue {
+    fn from(value: String) -> Self {
+        Self::Literal(value)
+    }
+}
+
+impl From<&str> for McpEnvValue {
+    fn from(value: &str) -> Self {
+        Self::Literal(value.to_string())
+    }
+}
+
 mod option_duration_secs {
     use serde::Deserialize;
     use serde::Deserializer;
@@ -340,6 +476,151 @@ pub enum HistoryPersistence {
     None,
 }
 
+/// How log lines are formatted.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable lines.
+    #[default]
+    Text,
+    /// One JSON object per line, with `timestamp`, `level`, `target`, `message`, `session_id`,
+    /// `turn_id`, `event`, `span` and `fields`.
+    Json,
+}
+
+/// Token prices for one model, in US dollars per million tokens.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ModelPricing {
+    /// Price of input tokens that were not served from the prompt cache.
+    pub input: f64,
+    /// Price of input tokens served from the prompt cache. Defaults to `input`.
+    pub cached_input: Option<f64>,
+    /// Price of output tokens, including reasoning tokens.
+    pub output: f64,
+}
+
+/// A named combination of model settings under `[model_presets]`, chosen with `--preset` or from
+/// `/model`. Unset fields keep the value from the rest of the config.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ModelPresetToml {
+    /// Model to use.
+    pub model: Option<String>,
+    /// Reasoning effort, as in `model_reasoning_effort`.
+    pub effort: Option<ReasoningEffort>,
+    /// Output verbosity, as in `model_verbosity`.
+    pub verbosity: Option<Verbosity>,
+}
+
+/// Spending limits that pause a turn until the user confirms continuation.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct Budget {
+    /// Limits on what a single turn may consume.
+    #[serde(default)]
+    pub turn: BudgetLimits,
+    /// Limits on what a single session may consume.
+    #[serde(default)]
+    pub session: BudgetLimits,
+    /// Limits on what all sessions may consume together in one local calendar day.
+    #[serde(default)]
+    pub daily: BudgetLimits,
+}
+
+impl Budget {
+    pub fn is_empty(&self) -> bool {
+        self.turn.is_empty() && self.session.is_empty() && self.daily.is_empty()
+    }
+}
+
+/// Individual budget limits. Unset limits are not enforced.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct BudgetLimits {
+    /// Total tokens billed by the model provider.
+    pub tokens: Option<i64>,
+    /// Estimated cost in US dollars, derived from `model_pricing`.
+    pub cost_usd: Option<f64>,
+    /// Minutes spent running turns.
+    pub wall_clock_minutes: Option<u64>,
+    /// Commands executed by the agent.
+    pub exec_calls: Option<u64>,
+    /// Model requests. After the first request of a turn, each one follows a round of tool calls.
+    pub steps: Option<u64>,
+}
+
+impl BudgetLimits {
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_none()
+            && self.cost_usd.is_none()
+            && self.wall_clock_minutes.is_none()
+            && self.exec_calls.is_none()
+            && self.steps.is_none()
+    }
+}
+
+/// Settings for the built-in `web_search` and `fetch_url` tools.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct WebTools {
+    /// Domains the tools may reach. Each entry also matches its subdomains. Empty allows any
+    /// domain.
+    #[serde(default)]
+    pub allowed_domains: Vec<String>,
+    /// Maximum number of bytes of text returned to the model per call.
+    #[serde(default = "default_web_tools_max_result_bytes")]
+    #[schemars(range(min = 1))]
+    pub max_result_bytes: usize,
+    /// HTML search page used by `web_search`; `{query}` is replaced by the URL-encoded query.
+    #[serde(default = "default_web_tools_search_url")]
+    pub search_url: String,
+}
+
+impl Default for WebTools {
+    fn default() -> Self {
+        Self {
+            allowed_domains: Vec::new(),
+            max_result_bytes: default_web_tools_max_result_bytes(),
+            search_url: default_web_tools_search_url(),
+        }
+    }
+}
+
+const fn default_web_tools_max_result_bytes() -> usize {
+    50_000
+}
+
+fn default_web_tools_search_url() -> String {
+    "https://html.duckduckgo.com/html/?q={query}".to_string()
+}
+
+/// Command patterns checked before the user is asked to approve a command.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ApprovalPatterns {
+    /// Commands run without asking. `*` and `?` are wildcards; entries starting with `re:` are
+    /// regular expressions.
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// Commands that are always rejected, even when `allow` or a rules file matches them.
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+/// Secrets kept out of spawned command environments and masked in tool output.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct Redaction {
+    /// Environment variable names, with `*` and `?` wildcards, removed from the environment of
+    /// spawned commands. Their values are also masked wherever they appear in tool output.
+    #[serde(default)]
+    pub env_vars: Vec<String>,
+    /// Regular expressions whose matches are masked in tool output.
+    #[serde(default)]
+    pub patterns: Vec<String>,
+}
+
 // ===== Analytics configuration =====
 
 /// Analytics settings loaded from config.toml. Fields are optional so we can apply defaults.
@@ -525,6 +806,10 @@ pub struct OtelConfigToml {
 
     /// Optional metrics exporter
     pub metrics_exporter: Option<OtelExporterKind>,
+
+    /// Address such as `127.0.0.1:9464` where the app server serves Prometheus metrics at
+    /// `/metrics`. Independent of `metrics_exporter` and analytics settings.
+    pub prometheus_listen: Option<String>,
 }
 
 /// Effective OTEL settings after defaults are applied.
@@ -535,6 +820,7 @@ pub struct OtelConfig {
     pub exporter: OtelExporterKind,
     pub trace_exporter: OtelExporterKind,
     pub metrics_exporter: OtelExporterKind,
+    pub prometheus_listen: Option<String>,
 }
 
 impl Default for OtelConfig {
@@ -545,6 +831,7 @@ impl Default for OtelConfig {
             exporter: OtelExporterKind::None,
             trace_exporter: OtelExporterKind::None,
             metrics_exporter: OtelExporterKind::Statsig,
+            prometheus_listen: None,
         }
     }
 }
@@ -676,12 +963,100 @@ pub struct SandboxWorkspaceWrite {
     pub writable_roots: Vec<AbsolutePathBuf>,
     #[serde(default)]
     pub network_access: bool,
+    /// Hosts that sandboxed commands may reach when `network_access` is false. Traffic is routed
+    /// through a local proxy that blocks every other destination. Supports `*.example.com`
+    /// (subdomains) and `**.example.com` (apex and subdomains).
+    #[serde(default)]
+    pub allowed_domains: Vec<String>,
+    /// IP networks, such as `10.20.0.0/16`, that sandboxed commands may reach by address when
+    /// `network_access` is false.
+    #[serde(default)]
+    pub allowed_cidrs: Vec<String>,
     #[serde(default)]
     pub exclude_tmpdir_env_var: bool,
     #[serde(default)]
     pub exclude_slash_tmp: bool,
 }
 
+impl SandboxWorkspaceWrite {
+    pub fn has_egress_allowlist(&self) -> bool {
+        !self.network_access && (!self.allowed_domains.is_empty() || !self.allowed_cidrs.is_empty())
+    }
+}
+
+/// Container runtime used by `[sandbox_container]`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ContainerRuntime {
+    #[default]
+    Docker,
+    Podman,
+}
+
+impl ContainerRuntime {
+    pub fn program(self) -> &'static str {
+        match self {
+            Self::Docker => "docker",
+            Self::Podman => "podman",
+        }
+    }
+}
+
+/// Runs sandboxed commands inside a Docker or Podman container instead of the platform sandbox.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SandboxContainer {
+    #[serde(default)]
+    pub runtime: ContainerRuntime,
+    /// Image the commands run in. It must provide the shell and tools the agent uses.
+    pub image: String,
+    /// Memory limit passed to `--memory`, e.g. `"4g"`.
+    pub memory: Option<String>,
+    /// CPU limit passed to `--cpus`.
+    pub cpus: Option<f64>,
+    /// Maximum number of processes, passed to `--pids-limit`.
+    pub pids_limit: Option<u32>,
+    /// Host directories mounted read-only at the same path, e.g. toolchains.
+    #[serde(default)]
+    pub read_only_mounts: Vec<AbsolutePathBuf>,
+}
+
+/// Additions to the generated Seatbelt and Linux sandbox profiles, typically set in a project's
+/// `.codex/config.toml` for toolchains that live outside the workspace.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SandboxExtensions {
+    /// Extra paths commands may read when read access is restricted.
+    #[serde(default)]
+    pub readable_roots: Vec<AbsolutePathBuf>,
+    /// Extra paths commands may write in `workspace-write` mode.
+    #[serde(default)]
+    pub writable_roots: Vec<AbsolutePathBuf>,
+    /// Device nodes, such as `/dev/kvm`, that commands may open. On Linux they are mounted into
+    /// the sandbox with device access.
+    #[serde(default)]
+    pub device_paths: Vec<AbsolutePathBuf>,
+    /// Syscalls the Linux seccomp filter should let through, from `ptrace`, `io_uring_setup`,
+    /// `io_uring_enter`, and `io_uring_register`. Network syscalls follow the network settings.
+    #[serde(default)]
+    pub allowed_syscalls: Vec<String>,
+}
+
+/// Per-command limits from `[resource_limits]`, applied to every command run on the user's behalf.
+/// Unset fields leave the corresponding limit inherited from Codex.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ResourceLimits {
+    /// CPU time, in seconds, each process may use before it is stopped (`RLIMIT_CPU`).
+    pub cpu_time_secs: Option<u64>,
+    /// Address space, in megabytes, each process may map (`RLIMIT_AS`). Linux only.
+    pub memory_mb: Option<u64>,
+    /// Number of file descriptors each process may have open (`RLIMIT_NOFILE`).
+    pub open_files: Option<u64>,
+    /// Combined stdout and stderr bytes after which the command is killed.
+    pub output_bytes: Option<u64>,
+}
+
 impl From<SandboxWorkspaceWrite> for codex_app_server_protocol::SandboxSettings {
     fn from(sandbox_workspace_write: SandboxWorkspaceWrite) -> Self {
         Self {
@@ -872,7 +1247,7 @@ mod tests {
             McpServerTransportConfig::Stdio {
                 command: "echo".to_string(),
                 args: vec!["hello".to_string(), "world".to_string()],
-                env: Some(HashMap::from([("FOO".to_string(), "BAR".to_string())])),
+                env: Some(HashMap::from([("FOO".to_string(), "BAR".into())])),
                 env_vars: Vec::new(),
                 cwd: None,
             }
@@ -924,6 +1299,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deserialize_command_config_with_env_secrets() {
+        let cfg: McpServerConfig = toml::from_str(
+            r#"
+            command = "echo"
+            env = { PLAIN = "value", JIRA_TOKEN = { keychain = "jira-token" }, GH_TOKEN = { command = ["gh", "auth", "token"] } }
+        "#,
+        )
+        .expect("should deserialize command config with env secrets");
+
+        assert_eq!(
+            cfg.transport,
+            McpServerTransportConfig::Stdio {
+                command: "echo".to_string(),
+                args: vec![],
+                env: Some(HashMap::from([
+                    ("PLAIN".to_string(), "value".into()),
+                    (
+                        "JIRA_TOKEN".to_string(),
+                        McpEnvValue::Keychain {
+                            keychain: "jira-token".to_string(),
+                        }
+                    ),
+                    (
+                        "GH_TOKEN".to_string(),
+                        McpEnvValue::Command {
+                            command: vec![
+                                "gh".to_string(),
+                                "auth".to_string(),
+                                "token".to_string(),
+                            ],
+                        }
+                    ),
+                ])),
+                env_vars: Vec::new(),
+                cwd: None,
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_env_secret_rejects_unknown_keys() {
+        toml::from_str::<McpServerConfig>(
+            r#"
+            command = "echo"
+            env = { TOKEN = { vault = "jira-token" } }
+        "#,
+        )
+        .expect_err("should reject unknown env secret source");
+    }
+
     #[test]
     fn deserialize_disabled_server_config() {
         let cfg: McpServerConfig = toml::from_str(
@@ -951,6 +1377,27 @@ mod tests {
         assert!(cfg.required);
     }
 
+    #[test]
+    fn deserialize_server_config_with_max_concurrent_calls() {
+        let cfg: McpServerConfig = toml::from_str(
+            r#"
+            command = "echo"
+            max_concurrent_calls = 2
+        "#,
+        )
+        .expect("should deserialize max_concurrent_calls");
+
+        assert_eq!(cfg.max_concurrent_calls, Some(2));
+
+        toml::from_str::<McpServerConfig>(
+            r#"
+            command = "echo"
+            max_concurrent_calls = 0
+        "#,
+        )
+        .expect_err("should reject max_concurrent_calls = 0");
+    }
+
     #[test]
     fn deserialize_streamable_http_server_config() {
         let cfg: McpServerConfig = toml::from_str(
@@ -1034,6 +1481,33 @@ mod tests {
         assert_eq!(cfg.disabled_tools, Some(vec!["blocked".to_string()]));
     }
 
+    #[test]
+    fn deserialize_server_config_with_tool_overrides() {
+        let cfg: McpServerConfig = toml::from_str(
+            r#"
+            command = "echo"
+            tool_timeout_sec = 10
+
+            [tools.search]
+            timeout_sec = 120
+            retries = 2
+        "#,
+        )
+        .expect("should deserialize tool overrides");
+
+        assert_eq!(
+            cfg.tools,
+            Some(HashMap::from([(
+                "search".to_string(),
+                McpToolConfig {
+                    timeout_sec: Some(Duration::from_secs(120)),
+                    retries: Some(2),
+                    cache_ttl_sec: None,
+                }
+            )]))
+        );
+    }
+
     #[test]
     fn deserialize_rejects_command_and_url() {
         toml::from_str::<McpServerConfig>(
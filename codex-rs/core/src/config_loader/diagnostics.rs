@@ -3,6 +3,7 @@
 
 use crate::config::CONFIG_TOML_FILE;
 use crate::config::ConfigToml;
+use crate::config::schema::is_known_config_key;
 use codex_app_server_protocol::ConfigLayerSource;
 use codex_utils_absolute_path::AbsolutePathBufGuard;
 use serde_path_to_error::Path as SerdePath;
@@ -15,6 +16,7 @@ use std::path::PathBuf;
 use toml_edit::Document;
 use toml_edit::Item;
 use toml_edit::Table;
+use toml_edit::TableLike;
 use toml_edit::Value;
 
 use super::ConfigLayerEntry;
@@ -156,19 +158,7 @@ async fn first_layer_config_error_for_entries<'a, I>(layers: I) -> Option<Config
 where
     I: IntoIterator<Item = &'a ConfigLayerEntry>,
 {
-    for layer in layers {
-        let Some(path) = config_path_for_layer(layer) else {
-            continue;
-        };
-        let contents = match tokio::fs::read_to_string(&path).await {
-            Ok(contents) => contents,
-            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
-            Err(err) => {
-                tracing::debug!("Failed to read config file {}: {err}", path.display());
-                continue;
-            }
-        };
-
+    for (path, contents) in read_layer_config_files(layers).await {
         let Some(parent) = path.parent() else {
             tracing::debug!("Config file {} has no parent directory", path.display());
             continue;
@@ -182,6 +172,98 @@ where
     None
 }
 
+/// Keys that `config.toml` does not accept in the config files of `layers`.
+pub async fn unknown_config_keys(layers: &ConfigLayerStack) -> Vec<ConfigError> {
+    let files = read_layer_config_files(
+        layers.get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, false),
+    )
+    .await;
+    files
+        .iter()
+        .flat_map(|(path, contents)| unknown_key_errors(path, contents))
+        .collect()
+}
+
+/// Everything wrong with the config files of `layers`: keys `config.toml` does not accept and,
+/// per file, the first value of the wrong type.
+pub async fn config_file_errors(layers: &ConfigLayerStack) -> Vec<ConfigError> {
+    let files = read_layer_config_files(
+        layers.get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, false),
+    )
+    .await;
+    let mut errors = Vec::new();
+    for (path, contents) in files {
+        let mut file_errors = unknown_key_errors(&path, &contents);
+        if let Some(parent) = path.parent() {
+            let _guard = AbsolutePathBufGuard::new(parent);
+            file_errors.extend(config_error_from_config_toml(&path, &contents));
+        }
+        file_errors.sort_by_key(|error| (error.range.start.line, error.range.start.column));
+        errors.extend(file_errors);
+    }
+    errors
+}
+
+async fn read_layer_config_files<'a, I>(layers: I) -> Vec<(PathBuf, String)>
+where
+    I: IntoIterator<Item = &'a ConfigLayerEntry>,
+{
+    let mut files = Vec::new();
+    for layer in layers {
+        let Some(path) = config_path_for_layer(layer) else {
+            continue;
+        };
+        match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => files.push((path, contents)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => {
+                tracing::debug!("Failed to read config file {}: {err}", path.display());
+            }
+        }
+    }
+    files
+}
+
+/// Keys in `contents` that `config.toml` does not accept. Keys under an unknown table are not
+/// reported again.
+fn unknown_key_errors(path: &Path, contents: &str) -> Vec<ConfigError> {
+    let Ok(doc) = contents.parse::<Document<String>>() else {
+        return Vec::new();
+    };
+    let mut errors = Vec::new();
+    if let Some(root) = doc.as_item().as_table_like() {
+        collect_unknown_keys(path, contents, root, &mut Vec::new(), &mut errors);
+    }
+    errors
+}
+
+fn collect_unknown_keys<'a>(
+    path: &Path,
+    contents: &str,
+    table: &'a dyn TableLike,
+    segments: &mut Vec<&'a str>,
+    errors: &mut Vec<ConfigError>,
+) {
+    for (key, item) in table.iter() {
+        segments.push(key);
+        if !is_known_config_key(segments) {
+            let range = table
+                .get_key_value(key)
+                .and_then(|(key, _)| key.span())
+                .map(|span| text_range_from_span(contents, span))
+                .unwrap_or_else(default_range);
+            errors.push(ConfigError::new(
+                path.to_path_buf(),
+                range,
+                format!("unknown config key `{}`", segments.join(".")),
+            ));
+        } else if let Some(child) = item.as_table_like() {
+            collect_unknown_keys(path, contents, child, segments, errors);
+        }
+        segments.pop();
+    }
+}
+
 fn config_path_for_layer(layer: &ConfigLayerEntry) -> Option<PathBuf> {
     match &layer.name {
         ConfigLayerSource::System { file } => Some(file.to_path_buf()),
@@ -2,6 +2,7 @@ mod diagnostics;
 mod layer_io;
 #[cfg(target_os = "macos")]
 mod macos;
+mod remote_requirements;
 
 #[cfg(test)]
 mod tests;
@@ -9,7 +10,10 @@ mod tests;
 use crate::config::CONFIG_TOML_FILE;
 use crate::config::ConfigToml;
 use crate::config::deserialize_config_toml_with_base;
+use crate::config::interpolation::interpolate_env_vars;
+use crate::config::types::ProxyToml;
 use crate::config_loader::layer_io::LoadedConfigLayers;
+use crate::default_client::set_default_client_proxy;
 use crate::git_info::resolve_root_git_project_for_trust;
 use codex_app_server_protocol::ConfigLayerSource;
 use codex_config::ConfigRequirementsWithSources;
@@ -52,11 +56,13 @@ pub use diagnostics::ConfigLoadError;
 pub use diagnostics::TextPosition;
 pub use diagnostics::TextRange;
 pub(crate) use diagnostics::config_error_from_toml;
+pub use diagnostics::config_file_errors;
 pub(crate) use diagnostics::first_layer_config_error;
 pub(crate) use diagnostics::first_layer_config_error_from_entries;
 pub use diagnostics::format_config_error;
 pub use diagnostics::format_config_error_with_source;
 pub(crate) use diagnostics::io_error_from_config_error;
+pub use diagnostics::unknown_config_keys;
 
 /// On Unix systems, load default settings from this file path, if present.
 /// Note that /etc/codex/ is treated as a "config folder," so subfolders such
@@ -76,6 +82,7 @@ const DEFAULT_PROJECT_ROOT_MARKERS: &[&str] = &[".git"];
 /// - admin:    managed preferences (*)
 /// - system    `/etc/codex/requirements.toml` (Unix) or
 ///   `%ProgramData%\OpenAI\Codex\requirements.toml` (Windows)
+/// - remote:   `requirements_url`, if one of the sources above sets it
 ///
 /// For backwards compatibility, we also load from
 /// `managed_config.toml` and map it to `requirements.toml`.
@@ -280,6 +287,14 @@ pub async fn load_config_layers_state(
         ));
     }
 
+    // `[proxy]` applies to every HTTP client built from now on, starting with the one that
+    // fetches `requirements_url`.
+    apply_proxy_settings(&layers);
+
+    // Requirements fetched from `requirements_url`, if any of the sources above
+    // set it, fill in whatever those sources left unset.
+    remote_requirements::load_remote_requirements(&mut config_requirements_toml).await?;
+
     ConfigLayerStack::new(
         layers,
         config_requirements_toml.clone().try_into()?,
@@ -287,6 +302,32 @@ pub async fn load_config_layers_state(
     )
 }
 
+/// Routes the HTTP clients built from now on through the `[proxy]` of the enabled `layers`. An
+/// invalid `[proxy]` is left for [`crate::config::ConfigBuilder::build`] to report.
+fn apply_proxy_settings(layers: &[ConfigLayerEntry]) {
+    let mut merged = TomlValue::Table(toml::map::Map::new());
+    for layer in layers.iter().filter(|layer| !layer.is_disabled()) {
+        merge_toml_values(&mut merged, &layer.config);
+    }
+    let proxy = match merged.get_mut("proxy") {
+        Some(proxy) => {
+            if let Err(err) = interpolate_env_vars(proxy) {
+                tracing::warn!("ignoring [proxy]: {err}");
+                return;
+            }
+            match ProxyToml::deserialize(proxy.clone()) {
+                Ok(proxy) => Some(codex_client::ProxySettings::from(proxy)),
+                Err(err) => {
+                    tracing::warn!("ignoring invalid [proxy]: {err}");
+                    return;
+                }
+            }
+        }
+        None => None,
+    };
+    set_default_client_proxy(proxy);
+}
+
 /// Attempts to load a config.toml file from `config_toml`.
 /// - If the file exists and is valid TOML, passes the parsed `toml::Value` to
 ///   `create_entry` and returns the resulting layer entry.
@@ -713,6 +754,33 @@ pub(crate) fn resolve_relative_paths_in_config_toml(
     ))
 }
 
+/// Resolves relative `cwd` entries of the MCP servers in a project layer, including those in its
+/// profiles, against `base_dir`, so a checked-in server runs in the same directory wherever Codex
+/// is started. In user config they stay relative to the directory Codex was started in.
+fn resolve_mcp_server_cwds(mut config: TomlValue, base_dir: &Path) -> TomlValue {
+    fn resolve(mcp_servers: Option<&mut TomlValue>, base_dir: &Path) {
+        let Some(TomlValue::Table(mcp_servers)) = mcp_servers else {
+            return;
+        };
+        for server in mcp_servers.values_mut() {
+            if let Some(TomlValue::String(cwd)) = server.get_mut("cwd")
+                && Path::new(cwd.as_str()).is_relative()
+                && !cwd.starts_with("${")
+            {
+                *cwd = base_dir.join(cwd.as_str()).to_string_lossy().into_owned();
+            }
+        }
+    }
+
+    resolve(config.get_mut("mcp_servers"), base_dir);
+    if let Some(TomlValue::Table(profiles)) = config.get_mut("profiles") {
+        for profile in profiles.values_mut() {
+            resolve(profile.get_mut("mcp_servers"), base_dir);
+        }
+    }
+    config
+}
+
 /// Ensure that every field in `original` is present in the returned
 /// `toml::Value`, taking the value from `resolved` where possible. This ensures
 /// the fields that we "removed" during the serialize/deserialize round-trip in
@@ -837,6 +905,7 @@ async fn load_project_layers(
                 };
                 let config =
                     resolve_relative_paths_in_config_toml(config, dot_codex_abs.as_path())?;
+                let config = resolve_mcp_server_cwds(config, dot_codex_abs.as_path());
                 let entry =
                     project_layer_entry(trust_context, &dot_codex_abs, &layer_dir, config, true);
                 layers.push(entry);
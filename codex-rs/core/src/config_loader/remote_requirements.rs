@@ -0,0 +1,112 @@
+//! Requirements fetched from the URL named by `requirements_url`.
+//!
+//! An organization can keep its `requirements.toml` on a web server and point managed devices at
+//! it from MDM or the system `requirements.toml`. The fetched requirements only fill in what the
+//! local sources leave unset, so a device can still be pinned tighter than the rest of the fleet.
+//!
+//! Unlike cloud requirements, fetching fails closed: if the URL is configured but cannot be
+//! loaded, configuration fails to load rather than silently running without the guardrails.
+
+use std::collections::HashMap;
+use std::io;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use codex_config::ConfigRequirementsToml;
+use codex_config::ConfigRequirementsWithSources;
+use codex_config::RequirementSource;
+use codex_config::Sourced;
+use url::Host;
+use url::Url;
+
+use crate::default_client::build_reqwest_client;
+
+const REMOTE_REQUIREMENTS_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Requirements already fetched by this process, keyed by URL, so that reloading config (for
+/// example after `config.toml` changes) does not hit the network each time.
+static FETCHED_REQUIREMENTS: LazyLock<Mutex<HashMap<String, ConfigRequirementsToml>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// If one of the sources merged so far sets `requirements_url`, fetches the requirements it
+/// points to and fills in any fields that are still unset.
+pub(super) async fn load_remote_requirements(
+    config_requirements_toml: &mut ConfigRequirementsWithSources,
+) -> io::Result<()> {
+    let Some(Sourced {
+        value: url,
+        source: url_source,
+    }) = config_requirements_toml.requirements_url.clone()
+    else {
+        return Ok(());
+    };
+
+    let requirements = fetch_remote_requirements(&url).await.map_err(|err| {
+        io::Error::other(format!(
+            "Failed to load requirements from {url} (set by {url_source}): {err}"
+        ))
+    })?;
+    config_requirements_toml
+        .merge_unset_fields(RequirementSource::RemoteRequirements { url }, requirements);
+    Ok(())
+}
+
+async fn fetch_remote_requirements(url: &str) -> Result<ConfigRequirementsToml, String> {
+    if let Some(requirements) = FETCHED_REQUIREMENTS
+        .lock()
+        .ok()
+        .and_then(|fetched| fetched.get(url).cloned())
+    {
+        return Ok(requirements);
+    }
+
+    let parsed_url = parse_requirements_url(url)?;
+    let response = build_reqwest_client()
+        .get(parsed_url)
+        .timeout(REMOTE_REQUIREMENTS_TIMEOUT)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|err| err.to_string())?;
+    let contents = response.text().await.map_err(|err| err.to_string())?;
+    let requirements: ConfigRequirementsToml =
+        toml::from_str(&contents).map_err(|err| format!("invalid requirements.toml: {err}"))?;
+
+    if let Ok(mut fetched) = FETCHED_REQUIREMENTS.lock() {
+        fetched.insert(url.to_string(), requirements.clone());
+    }
+    Ok(requirements)
+}
+
+/// Requirements must come over HTTPS; plain HTTP is only accepted for loopback hosts.
+fn parse_requirements_url(url: &str) -> Result<Url, String> {
+    let parsed = Url::parse(url).map_err(|err| format!("invalid URL: {err}"))?;
+    let is_loopback = match parsed.host() {
+        Some(Host::Domain(domain)) => domain == "localhost",
+        Some(Host::Ipv4(ip)) => ip.is_loopback(),
+        Some(Host::Ipv6(ip)) => ip.is_loopback(),
+        None => false,
+    };
+    match parsed.scheme() {
+        "https" => Ok(parsed),
+        "http" if is_loopback => Ok(parsed),
+        scheme => Err(format!("URL scheme must be https, not {scheme}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn requirements_url_must_use_https_except_on_loopback() {
+        assert!(parse_requirements_url("https://codex.example.com/requirements.toml").is_ok());
+        assert!(parse_requirements_url("http://127.0.0.1:8080/requirements.toml").is_ok());
+        assert_eq!(
+            parse_requirements_url("http://codex.example.com/requirements.toml").unwrap_err(),
+            "URL scheme must be https, not http"
+        );
+    }
+}
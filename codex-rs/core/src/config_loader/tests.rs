@@ -6,6 +6,7 @@ use crate::config::ConfigOverrides;
 use crate::config::ConfigToml;
 use crate::config::ConstraintError;
 use crate::config::ProjectConfig;
+use crate::config::types::McpServerTransportConfig;
 use crate::config_loader::CloudRequirementsLoader;
 use crate::config_loader::ConfigLayerEntry;
 use crate::config_loader::ConfigLoadError;
@@ -175,6 +176,96 @@ fn schema_error_points_to_feature_value() {
     assert_eq!(error.range.start.column, value_column);
 }
 
+#[tokio::test]
+async fn unknown_keys_are_reported_as_startup_warnings() -> std::io::Result<()> {
+    let tmp = tempdir()?;
+    let config_path = tmp.path().join(CONFIG_TOML_FILE);
+    std::fs::write(
+        &config_path,
+        "approval_polcy = \"never\"\n\n[tui]\nnotifications = true\ntheme = \"dark\"\n",
+    )?;
+
+    let config = ConfigBuilder::default()
+        .codex_home(tmp.path().to_path_buf())
+        .fallback_cwd(Some(tmp.path().to_path_buf()))
+        .build()
+        .await?;
+
+    let path = config_path.display();
+    assert_eq!(
+        config
+            .startup_warnings
+            .iter()
+            .filter(|warning| warning.contains("unknown config key"))
+            .cloned()
+            .collect::<Vec<_>>(),
+        vec![
+            format!(
+                "{path}:1:1: unknown config key `approval_polcy`; it is ignored. Run `codex config validate` to check your config."
+            ),
+            format!(
+                "{path}:5:1: unknown config key `tui.theme`; it is ignored. Run `codex config validate` to check your config."
+            ),
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn strict_config_rejects_unknown_keys() {
+    let tmp = tempdir().expect("tempdir");
+    let config_path = tmp.path().join(CONFIG_TOML_FILE);
+    std::fs::write(
+        &config_path,
+        "strict_config = true\napproval_polcy = \"never\"\n",
+    )
+    .expect("write config");
+
+    let err = ConfigBuilder::default()
+        .codex_home(tmp.path().to_path_buf())
+        .fallback_cwd(Some(tmp.path().to_path_buf()))
+        .build()
+        .await
+        .expect_err("expected error");
+
+    let config_error = config_error_from_io(&err);
+    assert_eq!(config_error.path, config_path);
+    assert_eq!(config_error.range.start.line, 2);
+    assert_eq!(config_error.message, "unknown config key `approval_polcy`");
+}
+
+#[tokio::test]
+async fn config_file_errors_lists_unknown_keys_and_type_errors() -> std::io::Result<()> {
+    let tmp = tempdir()?;
+    let config_path = tmp.path().join(CONFIG_TOML_FILE);
+    std::fs::write(
+        &config_path,
+        "model_context_window = \"large\"\n\n[mcp_servers.docs]\ncommand = \"docs-mcp\"\ntimeout = 5\n",
+    )?;
+    let layers = load_config_layers_state(
+        tmp.path(),
+        None,
+        &[] as &[(String, TomlValue)],
+        LoaderOverrides::default(),
+        CloudRequirementsLoader::default(),
+    )
+    .await?;
+
+    let errors = super::config_file_errors(&layers).await;
+
+    assert_eq!(
+        errors
+            .iter()
+            .map(|error| (error.range.start.line, error.message.as_str()))
+            .collect::<Vec<_>>(),
+        vec![
+            (1, "invalid type: string \"large\", expected i64"),
+            (5, "unknown config key `mcp_servers.docs.timeout`"),
+        ]
+    );
+    Ok(())
+}
+
 #[tokio::test]
 async fn merges_managed_config_layer_on_top() {
     let tmp = tempdir().expect("tempdir");
@@ -584,6 +675,8 @@ allowed_approval_policies = ["on-request"]
                 rules: None,
                 enforce_residency: None,
                 network: None,
+                blocked_tools: None,
+                requirements_url: None,
             })
         }),
     )
@@ -632,6 +725,8 @@ allowed_approval_policies = ["on-request"]
             rules: None,
             enforce_residency: None,
             network: None,
+            blocked_tools: None,
+            requirements_url: None,
         },
     );
     load_requirements_toml(&mut config_requirements_toml, &requirements_file).await?;
@@ -669,6 +764,8 @@ async fn load_config_layers_includes_cloud_requirements() -> anyhow::Result<()>
         rules: None,
         enforce_residency: None,
         network: None,
+        blocked_tools: None,
+        requirements_url: None,
     };
     let expected = requirements.clone();
     let cloud_requirements = CloudRequirementsLoader::new(async move { Some(requirements) });
@@ -702,6 +799,98 @@ async fn load_config_layers_includes_cloud_requirements() -> anyhow::Result<()>
     Ok(())
 }
 
+#[tokio::test]
+async fn load_config_layers_fetches_requirements_url() -> anyhow::Result<()> {
+    let server = wiremock::MockServer::start().await;
+    wiremock::Mock::given(wiremock::matchers::method("GET"))
+        .and(wiremock::matchers::path("/requirements.toml"))
+        .respond_with(wiremock::ResponseTemplate::new(200).set_body_string(
+            r#"
+allowed_approval_policies = ["on-request"]
+allowed_sandbox_modes = ["read-only"]
+blocked_tools = ["mcp__github__*"]
+"#,
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let tmp = tempdir()?;
+    let codex_home = tmp.path().join("home");
+    tokio::fs::create_dir_all(&codex_home).await?;
+    let url = format!("{}/requirements.toml", server.uri());
+    let requirements = ConfigRequirementsToml {
+        allowed_approval_policies: Some(vec![AskForApproval::Never]),
+        requirements_url: Some(url.clone()),
+        ..Default::default()
+    };
+    let cloud_requirements = CloudRequirementsLoader::new(async move { Some(requirements) });
+
+    let layers = load_config_layers_state(
+        &codex_home,
+        None,
+        &[] as &[(String, TomlValue)],
+        LoaderOverrides::default(),
+        cloud_requirements,
+    )
+    .await?;
+
+    let requirements = layers.requirements();
+    // The source that names the URL wins for fields it sets itself.
+    assert_eq!(
+        requirements.approval_policy.source,
+        Some(RequirementSource::CloudRequirements)
+    );
+    let remote_source = RequirementSource::RemoteRequirements { url };
+    assert_eq!(
+        requirements.sandbox_policy.source,
+        Some(remote_source.clone())
+    );
+    assert_eq!(
+        requirements.blocked_tools,
+        Some(crate::config_loader::Sourced::new(
+            vec!["mcp__github__*".to_string()],
+            remote_source,
+        ))
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn load_config_layers_fails_when_requirements_url_is_unreachable() -> anyhow::Result<()> {
+    let server = wiremock::MockServer::start().await;
+    wiremock::Mock::given(wiremock::matchers::method("GET"))
+        .respond_with(wiremock::ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+
+    let tmp = tempdir()?;
+    let codex_home = tmp.path().join("home");
+    tokio::fs::create_dir_all(&codex_home).await?;
+    let requirements = ConfigRequirementsToml {
+        requirements_url: Some(format!("{}/unavailable.toml", server.uri())),
+        ..Default::default()
+    };
+    let cloud_requirements = CloudRequirementsLoader::new(async move { Some(requirements) });
+
+    let err = load_config_layers_state(
+        &codex_home,
+        None,
+        &[] as &[(String, TomlValue)],
+        LoaderOverrides::default(),
+        cloud_requirements,
+    )
+    .await
+    .expect_err("an unreachable requirements_url should fail closed");
+    assert!(
+        err.to_string().contains("Failed to load requirements from"),
+        "unexpected error: {err}"
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn project_layers_prefer_closest_cwd() -> std::io::Result<()> {
     let tmp = tempdir()?;
@@ -809,6 +998,67 @@ model_instructions_file = "child.txt"
     Ok(())
 }
 
+#[tokio::test]
+async fn project_mcp_servers_merge_over_user_servers() -> std::io::Result<()> {
+    let tmp = tempdir()?;
+    let project_root = tmp.path().join("project");
+    tokio::fs::create_dir_all(project_root.join(".codex")).await?;
+    tokio::fs::write(project_root.join(".git"), "gitdir: here").await?;
+    tokio::fs::write(
+        project_root.join(".codex").join(CONFIG_TOML_FILE),
+        r#"
+[mcp_servers.docs]
+command = "docs-mcp"
+args = ["--project"]
+
+[mcp_servers.repo_index]
+command = "./index-mcp"
+cwd = "../tools"
+"#,
+    )
+    .await?;
+
+    let codex_home = tmp.path().join("home");
+    tokio::fs::create_dir_all(&codex_home).await?;
+    make_config_for_test(&codex_home, &project_root, TrustLevel::Trusted, None).await?;
+    let user_config_path = codex_home.join(CONFIG_TOML_FILE);
+    let user_config = tokio::fs::read_to_string(&user_config_path).await?;
+    tokio::fs::write(
+        &user_config_path,
+        format!(
+            "{user_config}\n[mcp_servers.docs]\ncommand = \"docs-mcp\"\n\n[mcp_servers.personal]\ncommand = \"notes-mcp\"\n"
+        ),
+    )
+    .await?;
+
+    let config = ConfigBuilder::default()
+        .codex_home(codex_home)
+        .harness_overrides(ConfigOverrides {
+            cwd: Some(project_root.clone()),
+            ..ConfigOverrides::default()
+        })
+        .build()
+        .await?;
+
+    let servers = config.mcp_servers.get();
+    let mut names = servers.keys().cloned().collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, vec!["docs", "personal", "repo_index"]);
+    let McpServerTransportConfig::Stdio { args, .. } = &servers["docs"].transport else {
+        panic!("docs should be a stdio server");
+    };
+    assert_eq!(args, &vec!["--project".to_string()]);
+    let McpServerTransportConfig::Stdio { cwd, .. } = &servers["repo_index"].transport else {
+        panic!("repo_index should be a stdio server");
+    };
+    assert_eq!(
+        cwd.as_deref(),
+        Some(project_root.join(".codex").join("../tools").as_path())
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn cli_override_model_instructions_file_sets_base_instructions() -> std::io::Result<()> {
     let tmp = tempdir()?;
@@ -0,0 +1,240 @@
+//! Running sandboxed commands inside a Docker or Podman container.
+//!
+//! When `[sandbox_container]` is configured, commands that would otherwise run under Seatbelt or
+//! Landlock are wrapped in `docker run` (or `podman run`) instead. The container only sees the
+//! paths the sandbox policy makes writable, the command's working directory, and any extra
+//! `read_only_mounts`; everything else comes from the image. Network access follows the policy:
+//! without full network access the container gets no network at all, so the managed network
+//! proxy is not available inside containers.
+
+use std::path::Path;
+
+use crate::config::types::SandboxContainer;
+use crate::protocol::SandboxPolicy;
+
+/// Variables that describe the host rather than the container and are left to the image.
+const HOST_ONLY_ENV_VARS: [&str; 2] = ["PATH", "HOME"];
+
+/// Builds the full argv, starting with the container runtime, that runs `command` in a fresh
+/// container for `settings`. Only the names in `env_keys` are forwarded (`-e KEY`), so the
+/// runtime reads their values from its own environment and they never appear in argv.
+pub(crate) fn create_container_command_args<'a>(
+    command: Vec<String>,
+    settings: &SandboxContainer,
+    sandbox_policy: &SandboxPolicy,
+    sandbox_policy_cwd: &Path,
+    command_cwd: &Path,
+    env_keys: impl IntoIterator<Item = &'a String>,
+) -> Vec<String> {
+    let mut args = vec![
+        settings.runtime.program().to_string(),
+        "run".to_string(),
+        "--rm".to_string(),
+        "-i".to_string(),
+        "--init".to_string(),
+    ];
+
+    if !sandbox_policy.has_full_network_access() {
+        args.push("--network".to_string());
+        args.push("none".to_string());
+    }
+
+    #[cfg(unix)]
+    {
+        // Run as the invoking user so files created in bind mounts keep the right owner.
+        let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+        args.push("--user".to_string());
+        args.push(format!("{uid}:{gid}"));
+    }
+
+    let writable_roots = sandbox_policy.get_writable_roots_with_cwd(sandbox_policy_cwd);
+    for writable_root in &writable_roots {
+        push_bind_mount(&mut args, writable_root.root.as_path(), false);
+        for subpath in &writable_root.read_only_subpaths {
+            if subpath.as_path().exists() {
+                push_bind_mount(&mut args, subpath.as_path(), true);
+            }
+        }
+    }
+    if !writable_roots
+        .iter()
+        .any(|writable_root| command_cwd.starts_with(writable_root.root.as_path()))
+    {
+        push_bind_mount(&mut args, command_cwd, true);
+    }
+    for path in &settings.read_only_mounts {
+        push_bind_mount(&mut args, path.as_path(), true);
+    }
+
+    args.push("-w".to_string());
+    args.push(command_cwd.to_string_lossy().to_string());
+
+    if let Some(memory) = &settings.memory {
+        args.push("--memory".to_string());
+        args.push(memory.clone());
+    }
+    if let Some(cpus) = settings.cpus {
+        args.push("--cpus".to_string());
+        args.push(cpus.to_string());
+    }
+    if let Some(pids_limit) = settings.pids_limit {
+        args.push("--pids-limit".to_string());
+        args.push(pids_limit.to_string());
+    }
+
+    let mut env_keys: Vec<&String> = env_keys
+        .into_iter()
+        .filter(|key| !HOST_ONLY_ENV_VARS.contains(&key.as_str()))
+        .collect();
+    env_keys.sort();
+    for key in env_keys {
+        args.push("-e".to_string());
+        args.push(key.clone());
+    }
+
+    args.push(settings.image.clone());
+    args.extend(command);
+    args
+}
+
+/// Mounts `path` at the same location inside the container so paths in tool calls stay valid.
+fn push_bind_mount(args: &mut Vec<String>, path: &Path, read_only: bool) {
+    let path = path.to_string_lossy();
+    let mut mount = format!("type=bind,src={path},dst={path}");
+    if read_only {
+        mount.push_str(",readonly");
+    }
+    args.push("--mount".to_string());
+    args.push(mount);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::types::ContainerRuntime;
+    use crate::protocol::ReadOnlyAccess;
+    use codex_utils_absolute_path::AbsolutePathBuf;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn settings() -> SandboxContainer {
+        SandboxContainer {
+            runtime: ContainerRuntime::Podman,
+            image: "ghcr.io/example/dev:latest".to_string(),
+            memory: Some("2g".to_string()),
+            cpus: Some(1.5),
+            pids_limit: Some(256),
+            read_only_mounts: Vec::new(),
+        }
+    }
+
+    fn user_args() -> Vec<String> {
+        #[cfg(unix)]
+        {
+            let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+            vec!["--user".to_string(), format!("{uid}:{gid}")]
+        }
+        #[cfg(not(unix))]
+        {
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn workspace_write_mounts_cwd_and_disables_network() {
+        let cwd = TempDir::new().expect("create cwd");
+        let cwd_path = cwd.path().to_string_lossy().to_string();
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: Vec::new(),
+            read_only_access: ReadOnlyAccess::FullAccess,
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+        };
+        let env_keys = [
+            "PATH".to_string(),
+            "RUST_LOG".to_string(),
+            "CODEX_SANDBOX_NETWORK_DISABLED".to_string(),
+        ];
+
+        let args = create_container_command_args(
+            vec!["cargo".to_string(), "test".to_string()],
+            &settings(),
+            &policy,
+            cwd.path(),
+            cwd.path(),
+            &env_keys,
+        );
+
+        let mut expected = vec!["podman", "run", "--rm", "-i", "--init", "--network", "none"]
+            .into_iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        expected.extend(user_args());
+        expected.extend(
+            [
+                "--mount".to_string(),
+                format!("type=bind,src={cwd_path},dst={cwd_path}"),
+                "-w".to_string(),
+                cwd_path.clone(),
+                "--memory".to_string(),
+                "2g".to_string(),
+                "--cpus".to_string(),
+                "1.5".to_string(),
+                "--pids-limit".to_string(),
+                "256".to_string(),
+                "-e".to_string(),
+                "CODEX_SANDBOX_NETWORK_DISABLED".to_string(),
+                "-e".to_string(),
+                "RUST_LOG".to_string(),
+                "ghcr.io/example/dev:latest".to_string(),
+                "cargo".to_string(),
+                "test".to_string(),
+            ]
+            .into_iter(),
+        );
+        assert_eq!(args, expected);
+    }
+
+    #[test]
+    fn read_only_policy_mounts_cwd_and_extra_paths_read_only() {
+        let cwd = TempDir::new().expect("create cwd");
+        let toolchains = TempDir::new().expect("create toolchains dir");
+        let cwd_path = cwd.path().to_string_lossy().to_string();
+        let toolchains_path = toolchains.path().to_string_lossy().to_string();
+        let settings = SandboxContainer {
+            runtime: ContainerRuntime::Docker,
+            image: "rust:1".to_string(),
+            memory: None,
+            cpus: None,
+            pids_limit: None,
+            read_only_mounts: vec![
+                AbsolutePathBuf::from_absolute_path(toolchains.path()).expect("absolute path"),
+            ],
+        };
+
+        let args = create_container_command_args(
+            vec!["ls".to_string()],
+            &settings,
+            &SandboxPolicy::new_read_only_policy(),
+            cwd.path(),
+            cwd.path(),
+            &[],
+        );
+
+        assert_eq!(args.first().map(String::as_str), Some("docker"));
+        let mounts: Vec<&str> = args
+            .windows(2)
+            .filter(|pair| pair[0] == "--mount")
+            .map(|pair| pair[1].as_str())
+            .collect();
+        assert_eq!(
+            mounts,
+            vec![
+                format!("type=bind,src={cwd_path},dst={cwd_path},readonly"),
+                format!("type=bind,src={toolchains_path},dst={toolchains_path},readonly"),
+            ]
+        );
+        assert_eq!(args[args.len() - 2..], ["rust:1", "ls"]);
+    }
+}
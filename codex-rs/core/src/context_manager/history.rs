@@ -25,6 +25,10 @@ use std::ops::Deref;
 pub(crate) struct ContextManager {
     /// The oldest items are at the beginning of the vector.
     items: Vec<ResponseItem>,
+    /// `estimate_response_item_model_visible_bytes` of each entry in `items`, kept in step with it
+    /// so token estimates do not serialize the whole history again. Context-limit checks run
+    /// after every turn and repeatedly while trimming for compaction.
+    item_bytes: Vec<i64>,
     token_info: Option<TokenUsageInfo>,
 }
 
@@ -40,6 +44,7 @@ impl ContextManager {
     pub(crate) fn new() -> Self {
         Self {
             items: Vec::new(),
+            item_bytes: Vec::new(),
             token_info: TokenUsageInfo::new_or_append(&None, &None, None),
         }
     }
@@ -75,6 +80,8 @@ impl ContextManager {
             }
 
             let processed = self.process_item(item_ref, policy);
+            self.item_bytes
+                .push(estimate_response_item_model_visible_bytes(&processed));
             self.items.push(processed);
         }
     }
@@ -114,9 +121,9 @@ impl ContextManager {
             i64::try_from(approx_token_count(&base_instructions.text)).unwrap_or(i64::MAX);
 
         let items_tokens = self
-            .items
+            .item_bytes
             .iter()
-            .map(estimate_item_token_count)
+            .map(|bytes| approx_tokens_from_byte_count_i64(*bytes))
             .fold(0i64, i64::saturating_add);
 
         Some(base_tokens.saturating_add(items_tokens))
@@ -127,16 +134,18 @@ impl ContextManager {
             // Remove the oldest item (front of the list). Items are ordered from
             // oldest → newest, so index 0 is the first entry recorded.
             let removed = self.items.remove(0);
+            self.item_bytes.remove(0);
             // If the removed item participates in a call/output pair, also remove
             // its corresponding counterpart to keep the invariants intact without
             // running a full normalization pass.
-            normalize::remove_corresponding_for(&mut self.items, &removed);
+            self.remove_corresponding_for(&removed);
         }
     }
 
     pub(crate) fn remove_last_item(&mut self) -> bool {
         if let Some(removed) = self.items.pop() {
-            normalize::remove_corresponding_for(&mut self.items, &removed);
+            self.item_bytes.pop();
+            self.remove_corresponding_for(&removed);
             true
         } else {
             false
@@ -144,9 +153,19 @@ impl ContextManager {
     }
 
     pub(crate) fn replace(&mut self, items: Vec<ResponseItem>) {
+        self.item_bytes = items
+            .iter()
+            .map(estimate_response_item_model_visible_bytes)
+            .collect();
         self.items = items;
     }
 
+    fn remove_corresponding_for(&mut self, removed: &ResponseItem) {
+        if let Some(pos) = normalize::remove_corresponding_for(&mut self.items, removed) {
+            self.item_bytes.remove(pos);
+        }
+    }
+
     /// Replace image content in the last turn if it originated from a tool output.
     /// Returns true when a tool image was replaced, false otherwise.
     pub(crate) fn replace_last_turn_images(&mut self, placeholder: &str) -> bool {
@@ -157,7 +176,7 @@ impl ContextManager {
             return false;
         };
 
-        match &mut self.items[index] {
+        let replaced = match &mut self.items[index] {
             ResponseItem::FunctionCallOutput { output, .. } => {
                 let Some(content_items) = output.content_items_mut() else {
                     return false;
@@ -176,7 +195,11 @@ impl ContextManager {
             }
             ResponseItem::Message { role, .. } if role == "user" => false,
             _ => false,
+        };
+        if replaced {
+            self.item_bytes[index] = estimate_response_item_model_visible_bytes(&self.items[index]);
         }
+        replaced
     }
 
     /// Drop the last `num_turns` user turns from this history.
@@ -193,10 +216,8 @@ impl ContextManager {
             return;
         }
 
-        let snapshot = self.items.clone();
-        let user_positions = user_message_positions(&snapshot);
+        let user_positions = user_message_positions(&self.items);
         let Some(&first_user_idx) = user_positions.first() else {
-            self.replace(snapshot);
             return;
         };
 
@@ -207,7 +228,8 @@ impl ContextManager {
             user_positions[user_positions.len() - n_from_end]
         };
 
-        self.replace(snapshot[..cut_idx].to_vec());
+        self.items.truncate(cut_idx);
+        self.item_bytes.truncate(cut_idx);
     }
 
     pub(crate) fn update_token_info(
@@ -234,8 +256,9 @@ impl ContextManager {
 
         self.items
             .iter()
+            .zip(&self.item_bytes)
             .take(last_user_index)
-            .filter(|item| {
+            .filter(|(item, _)| {
                 matches!(
                     item,
                     ResponseItem::Reasoning {
@@ -244,19 +267,19 @@ impl ContextManager {
                     }
                 )
             })
-            .map(estimate_item_token_count)
+            .map(|(_, bytes)| approx_tokens_from_byte_count_i64(*bytes))
             .fold(0i64, i64::saturating_add)
     }
 
-    // These are local items added after the most recent model-emitted item.
+    // Model-visible bytes of the local items added after the most recent model-emitted item.
     // They are not reflected in `last_token_usage.total_tokens`.
-    fn items_after_last_model_generated_item(&self) -> &[ResponseItem] {
+    fn item_bytes_after_last_model_generated_item(&self) -> &[i64] {
         let start = self
             .items
             .iter()
             .rposition(is_model_generated_item)
             .map_or(self.items.len(), |index| index.saturating_add(1));
-        &self.items[start..]
+        &self.item_bytes[start..]
     }
 
     /// When true, the server already accounted for past reasoning tokens and
@@ -268,9 +291,9 @@ impl ContextManager {
             .map(|info| info.last_token_usage.total_tokens)
             .unwrap_or(0);
         let items_after_last_model_generated_tokens = self
-            .items_after_last_model_generated_item()
+            .item_bytes_after_last_model_generated_item()
             .iter()
-            .map(estimate_item_token_count)
+            .map(|bytes| approx_tokens_from_byte_count_i64(*bytes))
             .fold(0i64, i64::saturating_add);
         if server_reasoning_included {
             last_tokens.saturating_add(items_after_last_model_generated_tokens)
@@ -287,24 +310,24 @@ impl ContextManager {
             .as_ref()
             .map(|info| info.last_token_usage.clone())
             .unwrap_or_default();
-        let items_after_last_model_generated = self.items_after_last_model_generated_item();
+        let bytes_after_last_model_generated = self.item_bytes_after_last_model_generated_item();
 
         TotalTokenUsageBreakdown {
             last_api_response_total_tokens: last_usage.total_tokens,
             all_history_items_model_visible_bytes: self
-                .items
+                .item_bytes
                 .iter()
-                .map(estimate_response_item_model_visible_bytes)
+                .copied()
                 .fold(0i64, i64::saturating_add),
             estimated_tokens_of_items_added_since_last_successful_api_response:
-                items_after_last_model_generated
+                bytes_after_last_model_generated
                     .iter()
-                    .map(estimate_item_token_count)
+                    .map(|bytes| approx_tokens_from_byte_count_i64(*bytes))
                     .fold(0i64, i64::saturating_add),
             estimated_bytes_of_items_added_since_last_successful_api_response:
-                items_after_last_model_generated
+                bytes_after_last_model_generated
                     .iter()
-                    .map(estimate_response_item_model_visible_bytes)
+                    .copied()
                     .fold(0i64, i64::saturating_add),
         }
     }
@@ -313,6 +336,9 @@ impl ContextManager {
     /// 1. every call (function/custom) has a corresponding output entry
     /// 2. every output has a corresponding call entry
     /// 3. when images are unsupported, image content is stripped from messages and tool outputs
+    ///
+    /// It runs only while building a prompt, which consumes the history, so `item_bytes` is left
+    /// as is.
     fn normalize_history(&mut self, input_modalities: &[InputModality]) {
         // all function/tool calls must have a corresponding output
         normalize::ensure_call_outputs_present(&mut self.items);
@@ -395,6 +421,7 @@ fn estimate_reasoning_length(encoded_len: usize) -> usize {
         .saturating_sub(650)
 }
 
+#[cfg(test)]
 fn estimate_item_token_count(item: &ResponseItem) -> i64 {
     let model_visible_bytes = estimate_response_item_model_visible_bytes(item);
     approx_tokens_from_byte_count_i64(model_visible_bytes)
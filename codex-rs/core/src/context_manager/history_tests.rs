@@ -196,9 +196,9 @@ fn items_after_last_model_generated_tokens_include_user_and_tool_output() {
 
     assert_eq!(
         history
-            .items_after_last_model_generated_item()
+            .item_bytes_after_last_model_generated_item()
             .iter()
-            .map(estimate_item_token_count)
+            .map(|bytes| approx_tokens_from_byte_count_i64(*bytes))
             .fold(0i64, i64::saturating_add),
         expected_tokens
     );
@@ -210,9 +210,9 @@ fn items_after_last_model_generated_tokens_are_zero_without_model_generated_item
 
     assert_eq!(
         history
-            .items_after_last_model_generated_item()
+            .item_bytes_after_last_model_generated_item()
             .iter()
-            .map(estimate_item_token_count)
+            .map(|bytes| approx_tokens_from_byte_count_i64(*bytes))
             .fold(0i64, i64::saturating_add),
         0
     );
@@ -421,6 +421,61 @@ fn remove_first_item_removes_matching_call_for_output() {
     assert_eq!(h.raw_items(), vec![]);
 }
 
+#[test]
+fn cached_item_sizes_follow_removals_and_replacements() {
+    let items = vec![
+        user_msg("first"),
+        ResponseItem::FunctionCall {
+            id: None,
+            name: "do_it".to_string(),
+            arguments: "{}".to_string(),
+            call_id: "call-1".to_string(),
+        },
+        assistant_msg("between"),
+        ResponseItem::FunctionCallOutput {
+            call_id: "call-1".to_string(),
+            output: FunctionCallOutputPayload::from_text("x".repeat(4_000)),
+        },
+        user_msg("second"),
+        custom_tool_call_output("call-2", "orphan output"),
+    ];
+    let base_instructions = BaseInstructions {
+        text: "instructions".to_string(),
+    };
+    let assert_matches_fresh = |h: &ContextManager| {
+        let fresh = create_history_with_items(h.raw_items().to_vec());
+        assert_eq!(
+            h.estimate_token_count_with_base_instructions(&base_instructions),
+            fresh.estimate_token_count_with_base_instructions(&base_instructions)
+        );
+        assert_eq!(
+            h.get_total_token_usage_breakdown()
+                .all_history_items_model_visible_bytes,
+            fresh
+                .get_total_token_usage_breakdown()
+                .all_history_items_model_visible_bytes
+        );
+    };
+
+    let mut h = create_history_with_items(items);
+    assert_matches_fresh(&h);
+    h.remove_last_item();
+    assert_matches_fresh(&h);
+    h.remove_first_item();
+    assert_matches_fresh(&h);
+    // Removing the call drops its output from the middle of the history.
+    h.remove_first_item();
+    assert_eq!(
+        h.raw_items(),
+        vec![assistant_msg("between"), user_msg("second")]
+    );
+    assert_matches_fresh(&h);
+    h.drop_last_n_user_turns(1);
+    assert_matches_fresh(&h);
+    h.replace(vec![user_msg("replaced")]);
+    assert_matches_fresh(&h);
+}
+
 #[test]
 fn remove_last_item_removes_matching_call_for_output() {
     let items = vec![
@@ -153,69 +153,66 @@ pub(crate) fn remove_orphan_outputs(items: &mut Vec<ResponseItem>) {
     });
 }
 
-pub(crate) fn remove_corresponding_for(items: &mut Vec<ResponseItem>, item: &ResponseItem) {
-    match item {
+/// Removes the call or output paired with `item`, returning the index it was at.
+pub(crate) fn remove_corresponding_for(
+    items: &mut Vec<ResponseItem>,
+    item: &ResponseItem,
+) -> Option<usize> {
+    let pos = match item {
         ResponseItem::FunctionCall { call_id, .. } => {
-            remove_first_matching(items, |i| {
+            items.iter().position(|i| {
                 matches!(
                     i,
                     ResponseItem::FunctionCallOutput {
                         call_id: existing, ..
                     } if existing == call_id
                 )
-            });
+            })
         }
         ResponseItem::FunctionCallOutput { call_id, .. } => {
-            if let Some(pos) = items.iter().position(|i| {
-                matches!(i, ResponseItem::FunctionCall { call_id: existing, .. } if existing == call_id)
-            }) {
-                items.remove(pos);
-            } else if let Some(pos) = items.iter().position(|i| {
-                matches!(i, ResponseItem::LocalShellCall { call_id: Some(existing), .. } if existing == call_id)
-            }) {
-                items.remove(pos);
-            }
+            items
+                .iter()
+                .position(|i| {
+                    matches!(i, ResponseItem::FunctionCall { call_id: existing, .. } if existing == call_id)
+                })
+                .or_else(|| {
+                    items.iter().position(|i| {
+                        matches!(i, ResponseItem::LocalShellCall { call_id: Some(existing), .. } if existing == call_id)
+                    })
+                })
         }
         ResponseItem::CustomToolCall { call_id, .. } => {
-            remove_first_matching(items, |i| {
+            items.iter().position(|i| {
                 matches!(
                     i,
                     ResponseItem::CustomToolCallOutput {
                         call_id: existing, ..
                     } if existing == call_id
                 )
-            });
+            })
         }
         ResponseItem::CustomToolCallOutput { call_id, .. } => {
-            remove_first_matching(
-                items,
+            items.iter().position(
                 |i| matches!(i, ResponseItem::CustomToolCall { call_id: existing, .. } if existing == call_id),
-            );
+            )
         }
         ResponseItem::LocalShellCall {
             call_id: Some(call_id),
             ..
         } => {
-            remove_first_matching(items, |i| {
+            items.iter().position(|i| {
                 matches!(
                     i,
                     ResponseItem::FunctionCallOutput {
                         call_id: existing, ..
                     } if existing == call_id
                 )
-            });
+            })
         }
-        _ => {}
-    }
-}
-
-fn remove_first_matching<F>(items: &mut Vec<ResponseItem>, predicate: F)
-where
-    F: Fn(&ResponseItem) -> bool,
-{
-    if let Some(pos) = items.iter().position(predicate) {
-        items.remove(pos);
-    }
+        _ => None,
+    }?;
+    items.remove(pos);
+    Some(pos)
 }
 
 /// Strip image content from messages and tool outputs when the model does not support images.
@@ -2,6 +2,7 @@ use crate::config_loader::ResidencyRequirement;
 use crate::spawn::CODEX_SANDBOX_ENV_VAR;
 use codex_client::CodexHttpClient;
 pub use codex_client::CodexRequestBuilder;
+use codex_client::ProxySettings;
 use reqwest::header::HeaderMap;
 use reqwest::header::HeaderValue;
 use std::sync::LazyLock;
@@ -87,6 +88,12 @@ pub fn set_default_client_residency_requirement(enforce_residency: Option<Reside
     *guard = enforce_residency;
 }
 
+/// Routes the clients built from now on through `proxy`; `None` leaves the proxy environment
+/// variables in charge.
+pub fn set_default_client_proxy(proxy: Option<ProxySettings>) {
+    codex_client::set_proxy_settings(proxy);
+}
+
 pub fn originator() -> Originator {
     if let Ok(guard) = ORIGINATOR.read()
         && let Some(originator) = guard.as_ref()
@@ -179,17 +186,25 @@ pub fn create_client() -> CodexHttpClient {
 }
 
 pub fn build_reqwest_client() -> reqwest::Client {
+    build_reqwest_client_builder()
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new())
+}
+
+/// Client builder with the default headers and proxy handling, for callers that need to adjust
+/// timeouts, redirects or proxies before building.
+pub fn build_reqwest_client_builder() -> reqwest::ClientBuilder {
     let ua = get_codex_user_agent();
 
     let mut builder = reqwest::Client::builder()
         // Set UA via dedicated helper to avoid header validation pitfalls
         .user_agent(ua)
         .default_headers(default_headers());
+    builder = codex_client::apply_proxy_settings(builder);
     if is_sandboxed() {
         builder = builder.no_proxy();
     }
-
-    builder.build().unwrap_or_else(|_| reqwest::Client::new())
+    builder
 }
 
 pub fn default_headers() -> HeaderMap {
@@ -206,7 +206,6 @@ impl CodexErr {
             | CodexErr::UnsupportedOperation(_)
             | CodexErr::Sandbox(_)
             | CodexErr::LandlockSandboxExecutableNotProvided
-            | CodexErr::RetryLimit(_)
             | CodexErr::ContextWindowExceeded
             | CodexErr::ThreadNotFound(_)
             | CodexErr::AgentLimitReached { .. }
@@ -214,6 +213,8 @@ impl CodexErr {
             | CodexErr::SessionConfiguredNotFirstEvent
             | CodexErr::UsageLimitReached(_)
             | CodexErr::ServerOverloaded => false,
+            // Rate limits clear on their own; other exhausted retries already backed off.
+            CodexErr::RetryLimit(err) => err.status == StatusCode::TOO_MANY_REQUESTS,
             CodexErr::Stream(..)
             | CodexErr::Timeout
             | CodexErr::UnexpectedStatus(_)
@@ -228,6 +229,56 @@ impl CodexErr {
             CodexErr::LandlockRuleset(_) | CodexErr::LandlockPathFd(_) => false,
         }
     }
+
+    /// How long the provider asked us to wait before retrying, if it said.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            CodexErr::Stream(_, delay) => *delay,
+            CodexErr::RetryLimit(err) => err.retry_after,
+            _ => None,
+        }
+    }
+
+    /// Whether the model provider itself looks unavailable (rate limited, overloaded, failing with
+    /// server errors, or timing out), so the same request may succeed against a fallback provider.
+    pub fn is_provider_unavailable(&self) -> bool {
+        match self {
+            CodexErr::UnexpectedStatus(err) => {
+                err.status.is_server_error() || err.status == StatusCode::TOO_MANY_REQUESTS
+            }
+            CodexErr::RetryLimit(_)
+            | CodexErr::ServerOverloaded
+            | CodexErr::InternalServerError
+            | CodexErr::Timeout
+            | CodexErr::Stream(..)
+            | CodexErr::ConnectionFailed(_)
+            | CodexErr::ResponseStreamFailed(_) => true,
+            CodexErr::TurnAborted
+            | CodexErr::Interrupted
+            | CodexErr::EnvVar(_)
+            | CodexErr::Fatal(_)
+            | CodexErr::UsageNotIncluded
+            | CodexErr::QuotaExceeded
+            | CodexErr::InvalidImageRequest()
+            | CodexErr::InvalidRequest(_)
+            | CodexErr::RefreshTokenFailed(_)
+            | CodexErr::UnsupportedOperation(_)
+            | CodexErr::Sandbox(_)
+            | CodexErr::LandlockSandboxExecutableNotProvided
+            | CodexErr::ContextWindowExceeded
+            | CodexErr::ThreadNotFound(_)
+            | CodexErr::AgentLimitReached { .. }
+            | CodexErr::Spawn
+            | CodexErr::SessionConfiguredNotFirstEvent
+            | CodexErr::UsageLimitReached(_)
+            | CodexErr::InternalAgentDied
+            | CodexErr::Io(_)
+            | CodexErr::Json(_)
+            | CodexErr::TokioJoin(_) => false,
+            #[cfg(target_os = "linux")]
+            CodexErr::LandlockRuleset(_) | CodexErr::LandlockPathFd(_) => false,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -393,6 +444,8 @@ fn truncate_with_ellipsis(text: &str, max_bytes: usize) -> String {
 pub struct RetryLimitReachedError {
     pub status: StatusCode,
     pub request_id: Option<String>,
+    /// Wait requested via `Retry-After` or rate-limit reset headers.
+    pub retry_after: Option<Duration>,
 }
 
 impl std::fmt::Display for RetryLimitReachedError {
@@ -728,6 +781,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn provider_unavailable_covers_rate_limits_server_errors_and_timeouts() {
+        let unexpected_status = |status| {
+            CodexErr::UnexpectedStatus(UnexpectedResponseError {
+                status,
+                body: String::new(),
+                url: None,
+                cf_ray: None,
+                request_id: None,
+            })
+        };
+        let errors = [
+            CodexErr::RetryLimit(RetryLimitReachedError {
+                status: StatusCode::TOO_MANY_REQUESTS,
+                request_id: None,
+                retry_after: None,
+            }),
+            CodexErr::ServerOverloaded,
+            CodexErr::Timeout,
+            unexpected_status(StatusCode::BAD_GATEWAY),
+            unexpected_status(StatusCode::NOT_FOUND),
+            CodexErr::InvalidRequest("bad".to_string()),
+            CodexErr::ContextWindowExceeded,
+        ];
+
+        assert_eq!(
+            errors
+                .iter()
+                .map(CodexErr::is_provider_unavailable)
+                .collect::<Vec<_>>(),
+            vec![true, true, true, true, false, false, false]
+        );
+    }
+
     #[test]
     fn sandbox_denied_uses_aggregated_output_when_stderr_empty() {
         let output = ExecToolCallOutput {
@@ -737,6 +824,7 @@ mod tests {
             aggregated_output: StreamOutput::new("aggregate detail".to_string()),
             duration: Duration::from_millis(10),
             timed_out: false,
+            resource_limit_exceeded: None,
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
@@ -754,6 +842,7 @@ mod tests {
             aggregated_output: StreamOutput::new(String::new()),
             duration: Duration::from_millis(10),
             timed_out: false,
+            resource_limit_exceeded: None,
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
@@ -771,6 +860,7 @@ mod tests {
             aggregated_output: StreamOutput::new(String::new()),
             duration: Duration::from_millis(8),
             timed_out: false,
+            resource_limit_exceeded: None,
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
@@ -815,6 +905,7 @@ mod tests {
             aggregated_output: StreamOutput::new(String::new()),
             duration: Duration::from_millis(5),
             timed_out: false,
+            resource_limit_exceeded: None,
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
@@ -6,6 +6,9 @@ use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::ExitStatus;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -17,6 +20,7 @@ use tokio::process::Child;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
+use crate::config::types::ResourceLimits;
 use crate::error::CodexErr;
 use crate::error::Result;
 use crate::error::SandboxErr;
@@ -26,6 +30,8 @@ use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandOutputDeltaEvent;
 use crate::protocol::ExecOutputStream;
 use crate::protocol::SandboxPolicy;
+use crate::resource_limits::ResourceLimitKind;
+use crate::resource_limits::detect_exceeded_limit;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::ExecRequest;
 use crate::sandboxing::SandboxManager;
@@ -43,7 +49,7 @@ pub const DEFAULT_EXEC_COMMAND_TIMEOUT_MS: u64 = 10_000;
 // for these.
 const SIGKILL_CODE: i32 = 9;
 const TIMEOUT_CODE: i32 = 64;
-const EXIT_CODE_SIGNAL_BASE: i32 = 128; // conventional shell: 128 + signal
+pub(crate) const EXIT_CODE_SIGNAL_BASE: i32 = 128; // conventional shell: 128 + signal
 const EXEC_TIMEOUT_EXIT_CODE: i32 = 124; // conventional timeout exit code
 
 // I/O buffer sizing
@@ -131,6 +137,10 @@ pub enum SandboxType {
 
     /// Only available on Windows.
     WindowsRestrictedToken,
+
+    /// Runs the command in a Docker or Podman container configured by
+    /// `[sandbox_container]`.
+    Container,
 }
 
 impl SandboxType {
@@ -140,6 +150,7 @@ impl SandboxType {
             SandboxType::MacosSeatbelt => "seatbelt",
             SandboxType::LinuxSeccomp => "seccomp",
             SandboxType::WindowsRestrictedToken => "windows_sandbox",
+            SandboxType::Container => "container",
         }
     }
 }
@@ -225,6 +236,9 @@ pub async fn process_exec_tool_call(
             codex_linux_sandbox_exe: codex_linux_sandbox_exe.as_ref(),
             use_linux_sandbox_bwrap,
             windows_sandbox_level,
+            container: None,
+            extensions: None,
+            resource_limits: None,
         })
         .map_err(CodexErr::from)?;
 
@@ -249,6 +263,7 @@ pub(crate) async fn execute_exec_env(
         sandbox_permissions,
         justification,
         arg0,
+        resource_limits,
     } = env;
 
     let network_attempt_id = match network_attempt_id.as_deref() {
@@ -270,9 +285,16 @@ pub(crate) async fn execute_exec_env(
     };
 
     let start = Instant::now();
-    let raw_output_result = exec(params, sandbox, sandbox_policy, stdout_stream).await;
+    let raw_output_result = exec(
+        params,
+        sandbox,
+        sandbox_policy,
+        stdout_stream,
+        &resource_limits,
+    )
+    .await;
     let duration = start.elapsed();
-    finalize_exec_result(raw_output_result, sandbox, duration)
+    finalize_exec_result(raw_output_result, sandbox, duration, &resource_limits)
 }
 
 #[cfg(target_os = "windows")]
@@ -453,6 +475,7 @@ async fn exec_windows_sandbox(
         stderr,
         aggregated_output,
         timed_out: capture.timed_out,
+        resource_limit_exceeded: None,
     })
 }
 
@@ -460,31 +483,38 @@ fn finalize_exec_result(
     raw_output_result: std::result::Result<RawExecToolCallOutput, CodexErr>,
     sandbox_type: SandboxType,
     duration: Duration,
+    resource_limits: &ResourceLimits,
 ) -> Result<ExecToolCallOutput> {
     match raw_output_result {
         Ok(raw_output) => {
-            #[allow(unused_mut)]
             let mut timed_out = raw_output.timed_out;
-
             #[cfg(target_family = "unix")]
-            {
-                if let Some(signal) = raw_output.exit_status.signal() {
-                    if signal == TIMEOUT_CODE {
-                        timed_out = true;
-                    } else {
-                        return Err(CodexErr::Sandbox(SandboxErr::Signal(signal)));
-                    }
+            let signal = raw_output.exit_status.signal();
+            #[cfg(not(target_family = "unix"))]
+            let signal: Option<i32> = None;
+
+            let mut exit_code = raw_output.exit_status.code().unwrap_or(-1);
+            let aggregated_output = raw_output.aggregated_output.from_utf8_lossy();
+            let resource_limit_exceeded = raw_output.resource_limit_exceeded.or_else(|| {
+                detect_exceeded_limit(resource_limits, signal, exit_code, &aggregated_output.text)
+            });
+
+            if let Some(signal) = signal {
+                if signal == TIMEOUT_CODE {
+                    timed_out = true;
+                } else if resource_limit_exceeded.is_some() {
+                    exit_code = EXIT_CODE_SIGNAL_BASE + signal;
+                } else {
+                    return Err(CodexErr::Sandbox(SandboxErr::Signal(signal)));
                 }
             }
 
-            let mut exit_code = raw_output.exit_status.code().unwrap_or(-1);
             if timed_out {
                 exit_code = EXEC_TIMEOUT_EXIT_CODE;
             }
 
             let stdout = raw_output.stdout.from_utf8_lossy();
             let stderr = raw_output.stderr.from_utf8_lossy();
-            let aggregated_output = raw_output.aggregated_output.from_utf8_lossy();
             let exec_output = ExecToolCallOutput {
                 exit_code,
                 stdout,
@@ -492,6 +522,7 @@ fn finalize_exec_result(
                 aggregated_output,
                 duration,
                 timed_out,
+                resource_limit_exceeded,
             };
 
             if timed_out {
@@ -500,7 +531,9 @@ fn finalize_exec_result(
                 }));
             }
 
-            if is_likely_sandbox_denied(sandbox_type, &exec_output) {
+            if exec_output.resource_limit_exceeded.is_none()
+                && is_likely_sandbox_denied(sandbox_type, &exec_output)
+            {
                 return Err(CodexErr::Sandbox(SandboxErr::Denied {
                     output: Box::new(exec_output),
                     network_policy_decision: None,
@@ -610,6 +643,7 @@ struct RawExecToolCallOutput {
     pub stderr: StreamOutput<Vec<u8>>,
     pub aggregated_output: StreamOutput<Vec<u8>>,
     pub timed_out: bool,
+    pub resource_limit_exceeded: Option<ResourceLimitKind>,
 }
 
 impl StreamOutput<String> {
@@ -681,6 +715,8 @@ pub struct ExecToolCallOutput {
     pub aggregated_output: StreamOutput<String>,
     pub duration: Duration,
     pub timed_out: bool,
+    /// Set when a `[resource_limits]` limit stopped the command.
+    pub resource_limit_exceeded: Option<ResourceLimitKind>,
 }
 
 impl Default for ExecToolCallOutput {
@@ -692,6 +728,7 @@ impl Default for ExecToolCallOutput {
             aggregated_output: StreamOutput::new(String::new()),
             duration: Duration::ZERO,
             timed_out: false,
+            resource_limit_exceeded: None,
         }
     }
 }
@@ -702,6 +739,7 @@ async fn exec(
     sandbox: SandboxType,
     sandbox_policy: &SandboxPolicy,
     stdout_stream: Option<StdoutStream>,
+    resource_limits: &ResourceLimits,
 ) -> Result<RawExecToolCallOutput> {
     #[cfg(target_os = "windows")]
     if sandbox == SandboxType::WindowsRestrictedToken
@@ -750,15 +788,52 @@ async fn exec(
         env,
     })
     .await?;
-    consume_truncated_output(child, expiration, stdout_stream).await
+    consume_truncated_output(
+        child,
+        expiration,
+        stdout_stream,
+        resource_limits.output_bytes,
+    )
+    .await
+}
+
+/// Shared between the stdout and stderr readers to enforce `[resource_limits] output_bytes`.
+#[derive(Clone)]
+struct OutputBudget {
+    max_bytes: u64,
+    total_bytes: Arc<AtomicU64>,
+    exceeded: CancellationToken,
+}
+
+impl OutputBudget {
+    fn new(max_bytes: u64) -> Self {
+        Self {
+            max_bytes,
+            total_bytes: Arc::new(AtomicU64::new(0)),
+            exceeded: CancellationToken::new(),
+        }
+    }
+
+    fn record(&self, bytes: usize) {
+        let total = self
+            .total_bytes
+            .fetch_add(bytes as u64, Ordering::Relaxed)
+            .saturating_add(bytes as u64);
+        if total > self.max_bytes {
+            self.exceeded.cancel();
+        }
+    }
 }
 
 /// Consumes the output of a child process, truncating it so it is suitable for
-/// use as the output of a `shell` tool call. Also enforces specified timeout.
+/// use as the output of a `shell` tool call. Also enforces specified timeout
+/// and, when `max_output_bytes` is set, kills the command once it has written
+/// more than that many bytes to stdout and stderr combined.
 async fn consume_truncated_output(
     mut child: Child,
     expiration: ExecExpiration,
     stdout_stream: Option<StdoutStream>,
+    max_output_bytes: Option<u64>,
 ) -> Result<RawExecToolCallOutput> {
     // Both stdout and stderr were configured with `Stdio::piped()`
     // above, therefore `take()` should normally return `Some`.  If it doesn't
@@ -775,17 +850,24 @@ async fn consume_truncated_output(
         ))
     })?;
 
+    let output_budget = max_output_bytes.map(OutputBudget::new);
+    let output_exceeded = output_budget
+        .as_ref()
+        .map_or_else(CancellationToken::new, |budget| budget.exceeded.clone());
     let stdout_handle = tokio::spawn(read_capped(
         BufReader::new(stdout_reader),
         stdout_stream.clone(),
         false,
+        output_budget.clone(),
     ));
     let stderr_handle = tokio::spawn(read_capped(
         BufReader::new(stderr_reader),
         stdout_stream.clone(),
         true,
+        output_budget,
     ));
 
+    let mut resource_limit_exceeded = None;
     let (exit_status, timed_out) = tokio::select! {
         status_result = child.wait() => {
             let exit_status = status_result?;
@@ -796,6 +878,12 @@ async fn consume_truncated_output(
             child.start_kill()?;
             (synthetic_exit_status(EXIT_CODE_SIGNAL_BASE + TIMEOUT_CODE), true)
         }
+        _ = output_exceeded.cancelled() => {
+            kill_child_process_group(&mut child)?;
+            child.start_kill()?;
+            resource_limit_exceeded = Some(ResourceLimitKind::OutputSize);
+            (synthetic_exit_status(EXIT_CODE_SIGNAL_BASE + SIGKILL_CODE), false)
+        }
         _ = tokio::signal::ctrl_c() => {
             kill_child_process_group(&mut child)?;
             child.start_kill()?;
@@ -857,6 +945,7 @@ async fn consume_truncated_output(
         stderr,
         aggregated_output,
         timed_out,
+        resource_limit_exceeded,
     })
 }
 
@@ -864,6 +953,7 @@ async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
     mut reader: R,
     stream: Option<StdoutStream>,
     is_stderr: bool,
+    output_budget: Option<OutputBudget>,
 ) -> io::Result<StreamOutput<Vec<u8>>> {
     let mut buf = Vec::with_capacity(AGGREGATE_BUFFER_INITIAL_CAPACITY.min(EXEC_OUTPUT_MAX_BYTES));
     let mut tmp = [0u8; READ_CHUNK_SIZE];
@@ -898,6 +988,9 @@ async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
         }
 
         append_capped(&mut buf, &tmp[..n], EXEC_OUTPUT_MAX_BYTES);
+        if let Some(output_budget) = &output_budget {
+            output_budget.record(n);
+        }
         // Continue reading to EOF to avoid back-pressure
     }
 
@@ -941,6 +1034,7 @@ mod tests {
             aggregated_output: StreamOutput::new(aggregated.to_string()),
             duration: Duration::from_millis(1),
             timed_out: false,
+            resource_limit_exceeded: None,
         }
     }
 
@@ -1022,7 +1116,7 @@ mod tests {
             writer.write_all(&bytes).await.expect("write");
         });
 
-        let out = read_capped(reader, None, false).await.expect("read");
+        let out = read_capped(reader, None, false, None).await.expect("read");
         assert_eq!(out.text.len(), EXEC_OUTPUT_MAX_BYTES);
     }
 
@@ -1149,6 +1243,7 @@ mod tests {
             SandboxType::None,
             &SandboxPolicy::new_read_only_policy(),
             None,
+            &ResourceLimits::default(),
         )
         .await?;
         assert!(output.timed_out);
@@ -1178,6 +1273,73 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(unix)]
+    fn shell_params(script: &str) -> io::Result<ExecParams> {
+        Ok(ExecParams {
+            command: vec!["/bin/sh".to_string(), "-c".to_string(), script.to_string()],
+            cwd: std::env::current_dir()?,
+            expiration: 10_000.into(),
+            env: std::env::vars().collect(),
+            network: None,
+            network_attempt_id: None,
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel::Disabled,
+            justification: None,
+            arg0: None,
+        })
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn output_limit_kills_command() -> Result<()> {
+        let resource_limits = ResourceLimits {
+            output_bytes: Some(64 * 1024),
+            ..Default::default()
+        };
+        let raw_output = exec(
+            shell_params("while :; do echo spam; done")?,
+            SandboxType::None,
+            &SandboxPolicy::new_read_only_policy(),
+            None,
+            &resource_limits,
+        )
+        .await;
+
+        let output = finalize_exec_result(
+            raw_output,
+            SandboxType::None,
+            Duration::ZERO,
+            &resource_limits,
+        )?;
+        assert_eq!(
+            output.resource_limit_exceeded,
+            Some(ResourceLimitKind::OutputSize)
+        );
+        assert_eq!(output.exit_code, EXIT_CODE_SIGNAL_BASE + SIGKILL_CODE);
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn rlimits_apply_to_spawned_command() -> Result<()> {
+        let resource_limits = ResourceLimits {
+            open_files: Some(64),
+            ..Default::default()
+        };
+        let output = exec(
+            shell_params("ulimit -n")?,
+            SandboxType::None,
+            &SandboxPolicy::new_read_only_policy(),
+            None,
+            &resource_limits,
+        )
+        .await?;
+
+        assert_eq!(output.stdout.from_utf8_lossy().text.trim(), "64");
+        assert_eq!(output.resource_limit_exceeded, None);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn process_exec_tool_call_respects_cancellation_token() -> Result<()> {
         let command = long_running_command();
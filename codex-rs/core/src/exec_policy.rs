@@ -5,6 +5,8 @@ use std::sync::Arc;
 
 use arc_swap::ArcSwap;
 
+use crate::command_patterns::CommandPatternDecision;
+use crate::command_patterns::CommandPatterns;
 use crate::config_loader::ConfigLayerStack;
 use crate::config_loader::ConfigLayerStackOrdering;
 use crate::is_dangerous_command::command_might_be_dangerous;
@@ -129,6 +131,7 @@ pub enum ExecPolicyUpdateError {
 
 pub(crate) struct ExecPolicyManager {
     policy: ArcSwap<Policy>,
+    command_patterns: ArcSwap<CommandPatterns>,
 }
 
 pub(crate) struct ExecApprovalRequest<'a> {
@@ -143,9 +146,21 @@ impl ExecPolicyManager {
     pub(crate) fn new(policy: Arc<Policy>) -> Self {
         Self {
             policy: ArcSwap::from(policy),
+            command_patterns: ArcSwap::from_pointee(CommandPatterns::default()),
         }
     }
 
+    /// Applies the `[approval_patterns]` from config ahead of the rules files.
+    pub(crate) fn with_command_patterns(self, command_patterns: CommandPatterns) -> Self {
+        self.set_command_patterns(command_patterns);
+        self
+    }
+
+    /// Replaces the `[approval_patterns]` after config.toml was edited.
+    pub(crate) fn set_command_patterns(&self, command_patterns: CommandPatterns) {
+        self.command_patterns.store(Arc::new(command_patterns));
+    }
+
     pub(crate) async fn load(config_stack: &ConfigLayerStack) -> Result<Self, ExecPolicyError> {
         let (policy, warning) = load_exec_policy_with_warning(config_stack).await?;
         if let Some(err) = warning.as_ref() {
@@ -175,6 +190,18 @@ impl ExecPolicyManager {
         // allow/prompt/forbidden rules still apply, but avoid auto-derived
         // amendments when only the heredoc fallback parser matched.
         let auto_amendment_allowed = !used_complex_parsing;
+        let pattern_decision =
+            self.command_patterns
+                .load()
+                .evaluate(command, &commands, auto_amendment_allowed);
+        if let Some(CommandPatternDecision::Deny { pattern }) = &pattern_decision {
+            return ExecApprovalRequirement::Forbidden {
+                reason: format!(
+                    "`{}` rejected: matches denied pattern `{pattern}`",
+                    render_shlex_command(command)
+                ),
+            };
+        }
         let exec_policy_fallback = |cmd: &[String]| {
             render_decision_for_unmatched_command(
                 approval_policy,
@@ -195,6 +222,12 @@ impl ExecPolicyManager {
             Decision::Forbidden => ExecApprovalRequirement::Forbidden {
                 reason: derive_forbidden_reason(command, &evaluation),
             },
+            Decision::Prompt if pattern_decision == Some(CommandPatternDecision::Allow) => {
+                ExecApprovalRequirement::Skip {
+                    bypass_sandbox: false,
+                    proposed_execpolicy_amendment: None,
+                }
+            }
             Decision::Prompt => {
                 if matches!(approval_policy, AskForApproval::Never) {
                     ExecApprovalRequirement::Forbidden {
@@ -684,6 +717,7 @@ async fn collect_policy_files(dir: impl AsRef<Path>) -> Result<Vec<PathBuf>, Exe
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::types::ApprovalPatterns;
     use crate::config_loader::ConfigLayerEntry;
     use crate::config_loader::ConfigLayerStack;
     use crate::config_loader::ConfigRequirements;
@@ -994,6 +1028,58 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         );
     }
 
+    #[tokio::test]
+    async fn approval_patterns_allow_skips_prompt_and_deny_wins() {
+        let command_patterns = CommandPatterns::new(&ApprovalPatterns {
+            allow: vec!["cargo test*".to_string(), "rm *".to_string()],
+            deny: vec!["rm -rf /*".to_string()],
+        })
+        .expect("valid patterns");
+        let manager = ExecPolicyManager::default().with_command_patterns(command_patterns);
+        let requirement_for = |command: Vec<String>| {
+            let manager = &manager;
+            async move {
+                manager
+                    .create_exec_approval_requirement_for_command(ExecApprovalRequest {
+                        command: &command,
+                        approval_policy: AskForApproval::UnlessTrusted,
+                        sandbox_policy: &SandboxPolicy::new_read_only_policy(),
+                        sandbox_permissions: SandboxPermissions::UseDefault,
+                        prefix_rule: None,
+                    })
+                    .await
+            }
+        };
+
+        assert_eq!(
+            requirement_for(vec![
+                "bash".to_string(),
+                "-lc".to_string(),
+                "cargo test -p codex-core".to_string(),
+            ])
+            .await,
+            ExecApprovalRequirement::Skip {
+                bypass_sandbox: false,
+                proposed_execpolicy_amendment: None,
+            }
+        );
+        assert_eq!(
+            requirement_for(vec!["rm".to_string(), "-rf".to_string(), "/".to_string()]).await,
+            ExecApprovalRequirement::Forbidden {
+                reason: "`rm -rf /` rejected: matches denied pattern `rm -rf /*`".to_string()
+            }
+        );
+        assert!(matches!(
+            requirement_for(vec![
+                "bash".to_string(),
+                "-lc".to_string(),
+                "cargo test && curl example.com".to_string(),
+            ])
+            .await,
+            ExecApprovalRequirement::NeedsApproval { .. }
+        ));
+    }
+
     #[test]
     fn commands_for_exec_policy_falls_back_for_empty_shell_script() {
         let command = vec!["bash".to_string(), "-lc".to_string(), "".to_string()];
@@ -123,6 +123,22 @@ pub enum Feature {
     EnableRequestCompression,
     /// Enable collab tools.
     Collab,
+    /// Enable the `delegate` tool for running bounded sub-agents.
+    Delegate,
+    /// Run each session in its own git worktree and branch.
+    Worktree,
+    /// Let `shell_command` run commands in a PTY and continue them with `write_stdin`.
+    ShellTty,
+    /// Enable the built-in `web_search` and `fetch_url` function tools.
+    WebTools,
+    /// Enable the built-in `read_file`, `write_file`, `list_dir`, `glob`, and `grep` function tools.
+    FileTools,
+    /// Index workspace files and definitions for the `search_files` and `list_symbols` tools.
+    WorkspaceIndex,
+    /// Save the full output of truncated shell commands to a session artifact file.
+    ExecOutputArtifacts,
+    /// Enable tools for starting, reading, and stopping background processes.
+    BackgroundProcesses,
     /// Enable apps.
     Apps,
     /// Route apps MCP calls through the configured gateway.
@@ -577,6 +593,54 @@ pub const FEATURES: &[FeatureSpec] = &[
         },
         default_enabled: false,
     },
+    FeatureSpec {
+        id: Feature::Delegate,
+        key: "delegate",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::Worktree,
+        key: "worktree",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::ShellTty,
+        key: "shell_tty",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::WebTools,
+        key: "web_tools",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::FileTools,
+        key: "file_tools",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::WorkspaceIndex,
+        key: "workspace_index",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::ExecOutputArtifacts,
+        key: "exec_output_artifacts",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::BackgroundProcesses,
+        key: "background_processes",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
     FeatureSpec {
         id: Feature::Apps,
         key: "apps",
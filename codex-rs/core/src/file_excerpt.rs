@@ -0,0 +1,394 @@
+//! Excerpts of files too large to attach whole.
+//!
+//! A file attached as context (a pinned `@file`, or `codex exec --attach`) that is larger than
+//! `file_excerpt_threshold_bytes` is replaced by an excerpt: an outline of the definitions found in
+//! it, its first lines and any line ranges asked for when attaching. The model reads other parts
+//! with the `read_file_range` tool, which is offered once a session holds an excerpt.
+
+use std::path::Path;
+use std::str::FromStr;
+
+use codex_utils_string::take_bytes_at_char_boundary;
+
+/// Files up to this size are attached whole.
+pub const DEFAULT_FILE_EXCERPT_THRESHOLD_BYTES: usize = 32 * 1024;
+
+/// Name of the tool that reads further line ranges of an excerpted file.
+pub(crate) const READ_FILE_RANGE_TOOL_NAME: &str = "read_file_range";
+
+/// Upper bound on the lines returned for a single range, in an excerpt or by the tool.
+pub(crate) const MAX_RANGE_LINES: usize = 400;
+
+/// Upper bound on the ranges asked for at once, when attaching or through the tool.
+pub(crate) const MAX_RANGES: usize = 8;
+
+const FILE_EXCERPT_OPEN_TAG: &str = "<file_excerpt>";
+const FILE_EXCERPT_CLOSE_TAG: &str = "</file_excerpt>";
+const HEAD_LINES: usize = 40;
+const MAX_OUTLINE_ENTRIES: usize = 200;
+const MAX_LINE_LENGTH: usize = 300;
+
+/// Keywords that start a definition worth listing in the outline, after any modifiers.
+const DEFINITION_KEYWORDS: &[&str] = &[
+    "fn",
+    "struct",
+    "enum",
+    "trait",
+    "impl",
+    "mod",
+    "macro_rules!",
+    "class",
+    "def",
+    "function",
+    "interface",
+    "func",
+    "type",
+    "namespace",
+    "module",
+];
+const MODIFIERS: &[&str] = &[
+    "pub",
+    "pub(crate)",
+    "pub(super)",
+    "export",
+    "default",
+    "async",
+    "unsafe",
+    "const",
+    "extern",
+    "abstract",
+    "public",
+    "private",
+    "protected",
+    "internal",
+    "static",
+    "final",
+    "sealed",
+    "open",
+    "data",
+];
+/// Definitions indented deeper than this (nested helpers, closures) are left out of the outline.
+const MAX_OUTLINE_INDENT: usize = 4;
+
+/// 1-indexed, inclusive range of lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl FromStr for LineRange {
+    type Err = String;
+
+    /// Parses `120-180` or a single line such as `42`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |value: &str| {
+            value
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .filter(|line| *line > 0)
+                .ok_or_else(|| format!("invalid line number `{value}`"))
+        };
+        let (start, end) = match s.split_once('-') {
+            Some((start, end)) => (parse(start)?, parse(end)?),
+            None => {
+                let line = parse(s)?;
+                (line, line)
+            }
+        };
+        if end < start {
+            return Err(format!("line range `{s}` ends before it starts"));
+        }
+        Ok(Self { start, end })
+    }
+}
+
+/// Splits a `path:120-180,300-320` reference into the path and its line ranges. References
+/// without a valid range suffix are returned unchanged.
+pub fn split_line_ranges(reference: &str) -> (&str, Vec<LineRange>) {
+    let Some((path, suffix)) = reference.rsplit_once(':') else {
+        return (reference, Vec::new());
+    };
+    match suffix
+        .split(',')
+        .map(LineRange::from_str)
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(ranges) if !path.is_empty() => (path, ranges),
+        _ => (reference, Vec::new()),
+    }
+}
+
+/// Whether `contents` is large enough to be attached as an excerpt.
+pub fn needs_excerpt(contents: &str, threshold_bytes: usize) -> bool {
+    contents.len() > threshold_bytes
+}
+
+/// Builds the excerpt of `contents`, read from `path`: its outline, first lines and `ranges`.
+pub fn excerpt_file(path: &Path, contents: &str, ranges: &[LineRange]) -> String {
+    let lines: Vec<&str> = contents.lines().collect();
+    let mut sections = vec![
+        FILE_EXCERPT_OPEN_TAG.to_string(),
+        format!("<path>{}</path>", path.display()),
+        format!(
+            "This file has {} lines and {} bytes, too large to include whole. Shown below are an outline of its definitions and selected lines.",
+            lines.len(),
+            contents.len()
+        ),
+    ];
+
+    let markdown = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| matches!(extension, "md" | "markdown" | "mdx"));
+    let outline = outline(&lines, markdown);
+    if !outline.is_empty() {
+        sections.push(format!("Outline:\n{}", outline.join("\n")));
+    }
+
+    let mut shown = vec![LineRange {
+        start: 1,
+        end: HEAD_LINES,
+    }];
+    shown.extend_from_slice(ranges);
+    for range in merge_ranges(shown, lines.len()) {
+        sections.push(format!(
+            "Lines {}-{}:\n{}",
+            range.start,
+            range.end,
+            format_range(&lines, range)
+        ));
+    }
+
+    sections.push(format!(
+        "Call `{READ_FILE_RANGE_TOOL_NAME}` with this path to read other lines."
+    ));
+    sections.push(FILE_EXCERPT_CLOSE_TAG.to_string());
+    sections.join("\n\n")
+}
+
+/// Whether `text` carries a file excerpt, possibly wrapped in another context tag. The tags must
+/// sit on lines of their own, so text that merely mentions `<file_excerpt>` does not count.
+pub fn is_file_excerpt(text: &str) -> bool {
+    let mut lines = text.lines();
+    lines.any(|line| line == FILE_EXCERPT_OPEN_TAG)
+        && lines.any(|line| line == FILE_EXCERPT_CLOSE_TAG)
+}
+
+/// Formats `range` of `lines` with `L<n>: ` prefixes, clamped to the file and to
+/// [`MAX_RANGE_LINES`].
+pub(crate) fn format_range(lines: &[&str], range: LineRange) -> String {
+    let end = range
+        .end
+        .min(lines.len())
+        .min(range.start.saturating_add(MAX_RANGE_LINES - 1));
+    lines
+        .iter()
+        .enumerate()
+        .take(end)
+        .skip(range.start.saturating_sub(1))
+        .map(|(index, line)| format!("L{}: {}", index + 1, truncate_line(line)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Sorts `ranges`, clamps them to `line_count` lines and joins the ones that touch or overlap.
+pub(crate) fn merge_ranges(mut ranges: Vec<LineRange>, line_count: usize) -> Vec<LineRange> {
+    ranges.retain(|range| range.start <= line_count);
+    ranges.sort_by_key(|range| range.start);
+    let mut merged: Vec<LineRange> = Vec::new();
+    for range in ranges {
+        let range = LineRange {
+            start: range.start,
+            end: range.end.min(line_count),
+        };
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end.saturating_add(1) => {
+                last.end = last.end.max(range.end);
+            }
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Definitions in code, or headings in markdown.
+fn outline(lines: &[&str], markdown: bool) -> Vec<String> {
+    let mut in_fence = false;
+    let mut entries: Vec<String> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| {
+            if !markdown {
+                return is_definition(line);
+            }
+            if line.starts_with("```") || line.starts_with("~~~") {
+                in_fence = !in_fence;
+            }
+            !in_fence && is_heading(line)
+        })
+        .map(|(index, line)| format!("L{}: {}", index + 1, truncate_line(line.trim_end())))
+        .collect();
+    if entries.len() > MAX_OUTLINE_ENTRIES {
+        let omitted = entries.len() - MAX_OUTLINE_ENTRIES;
+        entries.truncate(MAX_OUTLINE_ENTRIES);
+        entries.push(format!("... {omitted} more definitions"));
+    }
+    entries
+}
+
+fn is_heading(line: &str) -> bool {
+    line.starts_with('#') && line.trim_start_matches('#').starts_with(' ')
+}
+
+fn is_definition(line: &str) -> bool {
+    parse_definition(line).is_some()
+}
+
+/// A definition found on one line by [`parse_definition`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Definition<'a> {
+    pub(crate) keyword: &'static str,
+    /// The defined name, when it could be told apart from the rest of the line.
+    pub(crate) name: Option<&'a str>,
+}
+
+/// Parses a line that starts a definition, such as `pub fn parse(` or `class Parser:`.
+///
+/// Only definitions introduced by one of [`DEFINITION_KEYWORDS`] are found. Declarations that
+/// start with a type, like C functions (`int main(`) or Java methods (`public void run(`), are not.
+pub(crate) fn parse_definition(line: &str) -> Option<Definition<'_>> {
+    let trimmed = line.trim_start();
+    let indent = line.len() - trimmed.len();
+    if indent > MAX_OUTLINE_INDENT {
+        return None;
+    }
+    let mut words = trimmed.split_whitespace().peekable();
+    while words.next_if(|word| MODIFIERS.contains(word)).is_some() {}
+    let word = words.next()?;
+    let word = word.split(['<', '(', '{']).next().unwrap_or(word);
+    let keyword = *DEFINITION_KEYWORDS
+        .iter()
+        .find(|keyword| **keyword == word)?;
+    let mut next = words.next();
+    // Go methods name their receiver first: `func (s *Server) Start()`.
+    if next.is_some_and(|word| word.starts_with('(')) {
+        while next.is_some_and(|word| !word.contains(')')) {
+            next = words.next();
+        }
+        next = words.next();
+    }
+    let name = next
+        .map(|word| {
+            let end = word
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+                .unwrap_or(word.len());
+            &word[..end]
+        })
+        .filter(|name| !name.is_empty());
+    Some(Definition { keyword, name })
+}
+
+fn truncate_line(line: &str) -> &str {
+    take_bytes_at_char_boundary(line, MAX_LINE_LENGTH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn line_range_references_are_split_from_paths() {
+        assert_eq!(
+            split_line_ranges("src/lib.rs:10-20,42"),
+            (
+                "src/lib.rs",
+                vec![
+                    LineRange { start: 10, end: 20 },
+                    LineRange { start: 42, end: 42 }
+                ]
+            )
+        );
+        assert_eq!(split_line_ranges("src/lib.rs"), ("src/lib.rs", Vec::new()));
+        assert_eq!(
+            split_line_ranges("notes:draft"),
+            ("notes:draft", Vec::new())
+        );
+        assert_eq!(split_line_ranges("a.rs:20-10"), ("a.rs:20-10", Vec::new()));
+    }
+
+    #[test]
+    fn excerpt_lists_definitions_head_and_requested_ranges() {
+        let mut lines = vec![
+            "//! Parser.".to_string(),
+            "pub struct Parser {".to_string(),
+            "    input: String,".to_string(),
+            "}".to_string(),
+            "impl Parser {".to_string(),
+            "    pub(crate) async fn parse(&self) {".to_string(),
+            "        fn nested() {}".to_string(),
+            "    }".to_string(),
+            "}".to_string(),
+        ];
+        lines.extend((10..=100).map(|n| format!("// filler {n}")));
+        lines.push("#[derive(Debug)]".to_string());
+        lines.push("enum Token {}".to_string());
+        let contents = lines.join("\n");
+
+        let excerpt = excerpt_file(
+            Path::new("/repo/parser.rs"),
+            &contents,
+            &[
+                LineRange { start: 60, end: 61 },
+                LineRange { start: 35, end: 45 },
+            ],
+        );
+
+        let sections: Vec<&str> = excerpt.split("\n\n").collect();
+        assert_eq!(sections[1], "<path>/repo/parser.rs</path>");
+        assert_eq!(
+            sections[3],
+            "Outline:\nL2: pub struct Parser {\nL5: impl Parser {\nL6:     pub(crate) async fn parse(&self) {\nL102: enum Token {}"
+        );
+        // The head and the overlapping range are shown as one block.
+        assert!(sections[4].starts_with("Lines 1-45:\nL1: //! Parser.\n"));
+        assert!(sections[4].ends_with("\nL45: // filler 45"));
+        assert_eq!(
+            sections[5],
+            "Lines 60-61:\nL60: // filler 60\nL61: // filler 61"
+        );
+        assert!(is_file_excerpt(&format!(
+            "<attachment>\n{excerpt}\n</attachment>"
+        )));
+        assert!(!is_file_excerpt(
+            "why does my prompt mention <file_excerpt> and </file_excerpt>?"
+        ));
+    }
+
+    #[test]
+    fn definitions_are_parsed_with_their_names() {
+        let name = |line| parse_definition(line).and_then(|definition| definition.name);
+        assert_eq!(name("pub(crate) async fn parse(&self) {"), Some("parse"));
+        assert_eq!(name("impl<T> Parser<T> {"), Some("Parser"));
+        assert_eq!(name("class Parser(Base):"), Some("Parser"));
+        assert_eq!(name("func (s *Server) Start() error {"), Some("Start"));
+        assert_eq!(name("macro_rules! bail {"), Some("bail"));
+        assert_eq!(
+            parse_definition("export default function() {"),
+            Some(Definition {
+                keyword: "function",
+                name: None
+            })
+        );
+        assert_eq!(parse_definition("let parsed = parse(input);"), None);
+    }
+
+    #[test]
+    fn markdown_outlines_list_headings() {
+        let contents = "# Guide\n\nIntro.\n\n## Install\n\n```sh\n# not a heading\n```\n";
+        let excerpt = excerpt_file(Path::new("docs/guide.md"), contents, &[]);
+        assert!(excerpt.contains("Outline:\nL1: # Guide\nL5: ## Install\n\n"));
+    }
+}
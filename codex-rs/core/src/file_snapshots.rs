@@ -0,0 +1,131 @@
+//! File contents as the model last saw them.
+//!
+//! Snapshots are recorded when the model reads a file with `read_file`, writes one with
+//! `write_file`, or changes one with `apply_patch`. When a later patch no longer matches a file
+//! because someone edited it in the meantime, the snapshot is the common ancestor for a three-way
+//! merge between the patch and the file on disk.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_apply_patch::ApplyPatchFileChange;
+use indexmap::IndexMap;
+
+/// Files larger than this are not snapshotted; patches to them fail as before when they conflict.
+const MAX_FILE_BYTES: usize = 1024 * 1024;
+
+/// Total size of all snapshots kept for a session. The least recently recorded are dropped first.
+const MAX_TOTAL_BYTES: usize = 32 * 1024 * 1024;
+
+#[derive(Debug, Default)]
+pub(crate) struct FileSnapshots {
+    files: IndexMap<PathBuf, String>,
+    total_bytes: usize,
+}
+
+impl FileSnapshots {
+    pub(crate) fn record(&mut self, path: PathBuf, contents: String) {
+        self.forget(&path);
+        if contents.len() > MAX_FILE_BYTES {
+            return;
+        }
+        self.total_bytes += contents.len();
+        self.files.insert(path, contents);
+        while self.total_bytes > MAX_TOTAL_BYTES {
+            let Some((_, evicted)) = self.files.shift_remove_index(0) else {
+                break;
+            };
+            self.total_bytes -= evicted.len();
+        }
+    }
+
+    pub(crate) fn forget(&mut self, path: &Path) {
+        if let Some(contents) = self.files.shift_remove(path) {
+            self.total_bytes -= contents.len();
+        }
+    }
+
+    pub(crate) fn get(&self, path: &Path) -> Option<&str> {
+        self.files.get(path).map(String::as_str)
+    }
+
+    /// Records the contents an applied patch left behind.
+    pub(crate) fn record_patch(&mut self, changes: &HashMap<PathBuf, ApplyPatchFileChange>) {
+        for (path, change) in changes {
+            match change {
+                ApplyPatchFileChange::Add { content } => {
+                    self.record(path.clone(), content.clone());
+                }
+                ApplyPatchFileChange::Delete { .. } => self.forget(path),
+                ApplyPatchFileChange::Update {
+                    move_path,
+                    new_content,
+                    ..
+                } => {
+                    self.forget(path);
+                    let path = move_path.as_ref().unwrap_or(path);
+                    self.record(path.clone(), new_content.clone());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn drops_oldest_snapshots_over_the_budget() {
+        let mut snapshots = FileSnapshots::default();
+        let contents = "x".repeat(MAX_FILE_BYTES);
+        for index in 0..=MAX_TOTAL_BYTES / MAX_FILE_BYTES {
+            snapshots.record(
+                PathBuf::from(format!("/repo/{index}.txt")),
+                contents.clone(),
+            );
+        }
+        snapshots.record(
+            PathBuf::from("/repo/large.txt"),
+            "x".repeat(MAX_FILE_BYTES + 1),
+        );
+
+        assert_eq!(snapshots.get(Path::new("/repo/0.txt")), None);
+        assert_eq!(
+            snapshots.get(Path::new("/repo/1.txt")),
+            Some(contents.as_str())
+        );
+        assert_eq!(snapshots.get(Path::new("/repo/large.txt")), None);
+        assert_eq!(snapshots.total_bytes, MAX_TOTAL_BYTES);
+    }
+
+    #[test]
+    fn follows_moves_and_deletes() {
+        let mut snapshots = FileSnapshots::default();
+        snapshots.record(PathBuf::from("/repo/old.txt"), "old\n".to_string());
+        snapshots.record(PathBuf::from("/repo/gone.txt"), "gone\n".to_string());
+
+        snapshots.record_patch(&HashMap::from([
+            (
+                PathBuf::from("/repo/old.txt"),
+                ApplyPatchFileChange::Update {
+                    unified_diff: String::new(),
+                    move_path: Some(PathBuf::from("/repo/new.txt")),
+                    new_content: "new\n".to_string(),
+                },
+            ),
+            (
+                PathBuf::from("/repo/gone.txt"),
+                ApplyPatchFileChange::Delete {
+                    content: "gone\n".to_string(),
+                },
+            ),
+        ]));
+
+        assert_eq!(snapshots.get(Path::new("/repo/old.txt")), None);
+        assert_eq!(snapshots.get(Path::new("/repo/gone.txt")), None);
+        assert_eq!(snapshots.get(Path::new("/repo/new.txt")), Some("new\n"));
+    }
+}
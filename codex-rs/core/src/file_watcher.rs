@@ -1,5 +1,5 @@
-//! Watches skill roots for changes and broadcasts coarse-grained
-//! `FileWatcherEvent`s that higher-level components react to on the next turn.
+//! Watches skill roots and config files for changes and broadcasts coarse-grained
+//! `FileWatcherEvent`s that higher-level components react to.
 
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -10,6 +10,8 @@ use std::sync::Mutex;
 use std::sync::RwLock;
 use std::time::Duration;
 
+use codex_app_server_protocol::ConfigLayerSource;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use notify::Event;
 use notify::EventKind;
 use notify::RecommendedWatcher;
@@ -22,16 +24,27 @@ use tokio::time::Instant;
 use tokio::time::sleep_until;
 use tracing::warn;
 
+use crate::config::CONFIG_TOML_FILE;
 use crate::config::Config;
+use crate::config_loader::ConfigLayerStackOrdering;
 use crate::skills::loader::skill_roots_from_layer_stack_with_agents;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FileWatcherEvent {
-    SkillsChanged { paths: Vec<PathBuf> },
+    SkillsChanged {
+        paths: Vec<PathBuf>,
+    },
+    /// A user or project `config.toml` was written, created or removed.
+    ConfigChanged {
+        paths: Vec<PathBuf>,
+    },
 }
 
+#[derive(Default)]
 struct WatchState {
     skills_root_ref_counts: HashMap<PathBuf, usize>,
+    /// Folders whose `config.toml` is watched: `$CODEX_HOME` and project `.codex/` folders.
+    config_dir_ref_counts: HashMap<PathBuf, usize>,
 }
 
 struct FileWatcherInner {
@@ -94,12 +107,14 @@ pub(crate) struct FileWatcher {
 pub(crate) struct WatchRegistration {
     file_watcher: std::sync::Weak<FileWatcher>,
     roots: Vec<PathBuf>,
+    config_dirs: Vec<PathBuf>,
 }
 
 impl Drop for WatchRegistration {
     fn drop(&mut self) {
         if let Some(file_watcher) = self.file_watcher.upgrade() {
             file_watcher.unregister_roots(&self.roots);
+            file_watcher.unregister_config_dirs(&self.config_dirs);
         }
     }
 }
@@ -116,9 +131,7 @@ impl FileWatcher {
             watched_paths: HashMap::new(),
         };
         let (tx, _) = broadcast::channel(128);
-        let state = Arc::new(RwLock::new(WatchState {
-            skills_root_ref_counts: HashMap::new(),
-        }));
+        let state = Arc::new(RwLock::new(WatchState::default()));
         let file_watcher = Self {
             inner: Some(Mutex::new(inner)),
             state: Arc::clone(&state),
@@ -132,9 +145,7 @@ impl FileWatcher {
         let (tx, _) = broadcast::channel(1);
         Self {
             inner: None,
-            state: Arc::new(RwLock::new(WatchState {
-                skills_root_ref_counts: HashMap::new(),
-            })),
+            state: Arc::new(RwLock::new(WatchState::default())),
             tx,
         }
     }
@@ -155,9 +166,29 @@ impl FileWatcher {
             self.register_skills_root(root.clone());
         }
 
+        let mut config_dirs: Vec<PathBuf> = config
+            .config_layer_stack
+            .get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, true)
+            .into_iter()
+            .filter(|layer| {
+                matches!(
+                    layer.name,
+                    ConfigLayerSource::User { .. } | ConfigLayerSource::Project { .. }
+                )
+            })
+            .filter_map(|layer| layer.config_folder())
+            .map(AbsolutePathBuf::into_path_buf)
+            .collect();
+        config_dirs.sort_unstable_by(|a, b| a.as_os_str().cmp(b.as_os_str()));
+        config_dirs.dedup();
+        for dir in &config_dirs {
+            self.register_config_dir(dir.clone());
+        }
+
         WatchRegistration {
             file_watcher: Arc::downgrade(self),
             roots: registered_roots,
+            config_dirs,
         }
     }
 
@@ -173,10 +204,14 @@ impl FileWatcher {
             handle.spawn(async move {
                 let now = Instant::now();
                 let mut skills = ThrottledPaths::new(now);
+                let mut config = ThrottledPaths::new(now);
 
                 loop {
                     let now = Instant::now();
-                    let next_deadline = skills.next_deadline(now);
+                    let next_deadline = [skills.next_deadline(now), config.next_deadline(now)]
+                        .into_iter()
+                        .flatten()
+                        .min();
                     let timer_deadline = next_deadline
                         .unwrap_or_else(|| now + Duration::from_secs(60 * 60 * 24 * 365));
                     let timer = sleep_until(timer_deadline);
@@ -186,13 +221,17 @@ impl FileWatcher {
                         res = raw_rx.recv() => {
                             match res {
                                 Some(Ok(event)) => {
-                                    let skills_paths = classify_event(&event, &state);
+                                    let (skills_paths, config_paths) = classify_event(&event, &state);
                                     let now = Instant::now();
                                     skills.add(skills_paths);
+                                    config.add(config_paths);
 
                                     if let Some(paths) = skills.take_ready(now) {
                                         let _ = tx.send(FileWatcherEvent::SkillsChanged { paths });
                                     }
+                                    if let Some(paths) = config.take_ready(now) {
+                                        let _ = tx.send(FileWatcherEvent::ConfigChanged { paths });
+                                    }
                                 }
                                 Some(Err(err)) => {
                                     warn!("file watcher error: {err}");
@@ -204,6 +243,9 @@ impl FileWatcher {
                                     if let Some(paths) = skills.take_pending(now) {
                                         let _ = tx.send(FileWatcherEvent::SkillsChanged { paths });
                                     }
+                                    if let Some(paths) = config.take_pending(now) {
+                                        let _ = tx.send(FileWatcherEvent::ConfigChanged { paths });
+                                    }
                                     break;
                                 }
                             }
@@ -213,6 +255,9 @@ impl FileWatcher {
                             if let Some(paths) = skills.take_ready(now) {
                                 let _ = tx.send(FileWatcherEvent::SkillsChanged { paths });
                             }
+                            if let Some(paths) = config.take_ready(now) {
+                                let _ = tx.send(FileWatcherEvent::ConfigChanged { paths });
+                            }
                         }
                     }
                 }
@@ -237,20 +282,47 @@ impl FileWatcher {
         }
     }
 
+    fn register_config_dir(&self, dir: PathBuf) {
+        let mut state = self
+            .state
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let count = state.config_dir_ref_counts.entry(dir.clone()).or_insert(0);
+        *count += 1;
+        if *count == 1 {
+            // Watch the folder rather than the file: editors often replace the file on save, and
+            // the file may not exist yet.
+            self.watch_path(dir, RecursiveMode::NonRecursive);
+        }
+    }
+
     fn unregister_roots(&self, roots: &[PathBuf]) {
+        self.unregister_paths(roots, |state| &mut state.skills_root_ref_counts);
+    }
+
+    fn unregister_config_dirs(&self, dirs: &[PathBuf]) {
+        self.unregister_paths(dirs, |state| &mut state.config_dir_ref_counts);
+    }
+
+    fn unregister_paths(
+        &self,
+        paths: &[PathBuf],
+        ref_counts: fn(&mut WatchState) -> &mut HashMap<PathBuf, usize>,
+    ) {
         let mut state = self
             .state
             .write()
             .unwrap_or_else(std::sync::PoisonError::into_inner);
         let mut inner_guard: Option<std::sync::MutexGuard<'_, FileWatcherInner>> = None;
 
-        for root in roots {
+        for root in paths {
             let mut should_unwatch = false;
-            if let Some(count) = state.skills_root_ref_counts.get_mut(root) {
+            let counts = ref_counts(&mut state);
+            if let Some(count) = counts.get_mut(root) {
                 if *count > 1 {
                     *count -= 1;
                 } else {
-                    state.skills_root_ref_counts.remove(root);
+                    counts.remove(root);
                     should_unwatch = true;
                 }
             }
@@ -307,44 +379,49 @@ impl FileWatcher {
     }
 }
 
-fn classify_event(event: &Event, state: &RwLock<WatchState>) -> Vec<PathBuf> {
+/// Splits the paths of an event into changed skills paths and changed config files.
+fn classify_event(event: &Event, state: &RwLock<WatchState>) -> (Vec<PathBuf>, Vec<PathBuf>) {
     if !matches!(
         event.kind,
         EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
     ) {
-        return Vec::new();
+        return (Vec::new(), Vec::new());
     }
 
     let mut skills_paths = Vec::new();
-    let skills_roots = match state.read() {
-        Ok(state) => state
-            .skills_root_ref_counts
-            .keys()
-            .cloned()
-            .collect::<HashSet<_>>(),
-        Err(err) => {
-            let state = err.into_inner();
-            state
-                .skills_root_ref_counts
-                .keys()
-                .cloned()
-                .collect::<HashSet<_>>()
-        }
-    };
+    let mut config_paths = Vec::new();
+    let state = state
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let skills_roots = state
+        .skills_root_ref_counts
+        .keys()
+        .cloned()
+        .collect::<HashSet<_>>();
 
     for path in &event.paths {
         if is_skills_path(path, &skills_roots) {
             skills_paths.push(path.clone());
         }
+        if is_config_path(path, &state.config_dir_ref_counts) {
+            config_paths.push(path.clone());
+        }
     }
 
-    skills_paths
+    (skills_paths, config_paths)
 }
 
 fn is_skills_path(path: &Path, roots: &HashSet<PathBuf>) -> bool {
     roots.iter().any(|root| path.starts_with(root))
 }
 
+fn is_config_path(path: &Path, config_dirs: &HashMap<PathBuf, usize>) -> bool {
+    path.file_name() == Some(CONFIG_TOML_FILE.as_ref())
+        && path
+            .parent()
+            .is_some_and(|dir| config_dirs.contains_key(dir))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -409,6 +486,7 @@ mod tests {
         let root = path("/tmp/skills");
         let state = RwLock::new(WatchState {
             skills_root_ref_counts: HashMap::from([(root.clone(), 1)]),
+            ..Default::default()
         });
         let event = notify_event(
             EventKind::Create(CreateKind::Any),
@@ -418,7 +496,7 @@ mod tests {
             ],
         );
 
-        let classified = classify_event(&event, &state);
+        let (classified, _) = classify_event(&event, &state);
         assert_eq!(classified, vec![root.join("demo/SKILL.md")]);
     }
 
@@ -428,6 +506,7 @@ mod tests {
         let root_b = path("/tmp/workspace/.codex/skills");
         let state = RwLock::new(WatchState {
             skills_root_ref_counts: HashMap::from([(root_a.clone(), 1), (root_b.clone(), 1)]),
+            ..Default::default()
         });
         let event = notify_event(
             EventKind::Modify(ModifyKind::Any),
@@ -438,7 +517,7 @@ mod tests {
             ],
         );
 
-        let classified = classify_event(&event, &state);
+        let (classified, _) = classify_event(&event, &state);
         assert_eq!(
             classified,
             vec![root_a.join("alpha/SKILL.md"), root_b.join("beta/SKILL.md")]
@@ -450,6 +529,7 @@ mod tests {
         let root = path("/tmp/skills");
         let state = RwLock::new(WatchState {
             skills_root_ref_counts: HashMap::from([(root.clone(), 1)]),
+            ..Default::default()
         });
         let path = root.join("demo/SKILL.md");
 
@@ -457,13 +537,41 @@ mod tests {
             EventKind::Access(AccessKind::Open(AccessMode::Any)),
             vec![path.clone()],
         );
-        assert_eq!(classify_event(&access_event, &state), Vec::<PathBuf>::new());
+        assert_eq!(
+            classify_event(&access_event, &state),
+            (Vec::new(), Vec::new())
+        );
 
         let any_event = notify_event(EventKind::Any, vec![path.clone()]);
-        assert_eq!(classify_event(&any_event, &state), Vec::<PathBuf>::new());
+        assert_eq!(classify_event(&any_event, &state), (Vec::new(), Vec::new()));
 
         let other_event = notify_event(EventKind::Other, vec![path]);
-        assert_eq!(classify_event(&other_event, &state), Vec::<PathBuf>::new());
+        assert_eq!(
+            classify_event(&other_event, &state),
+            (Vec::new(), Vec::new())
+        );
+    }
+
+    #[test]
+    fn classify_event_picks_out_config_files_in_watched_folders() {
+        let codex_home = path("/tmp/codex-home");
+        let state = RwLock::new(WatchState {
+            config_dir_ref_counts: HashMap::from([(codex_home.clone(), 1)]),
+            ..Default::default()
+        });
+        let event = notify_event(
+            EventKind::Modify(ModifyKind::Any),
+            vec![
+                codex_home.join("config.toml"),
+                codex_home.join("history.jsonl"),
+                path("/tmp/elsewhere/config.toml"),
+            ],
+        );
+
+        assert_eq!(
+            classify_event(&event, &state),
+            (Vec::new(), vec![codex_home.join("config.toml")])
+        );
     }
 
     #[test]
@@ -486,6 +594,7 @@ mod tests {
         let registration = WatchRegistration {
             file_watcher: Arc::downgrade(&watcher),
             roots: vec![root],
+            config_dirs: Vec::new(),
         };
 
         drop(registration);
@@ -1,3 +1,4 @@
+use crate::config::types::SandboxExtensions;
 use crate::protocol::SandboxPolicy;
 use crate::spawn::SpawnChildRequest;
 use crate::spawn::StdioPolicy;
@@ -37,6 +38,7 @@ where
         sandbox_policy_cwd,
         use_bwrap_sandbox,
         allow_network_for_proxy(false),
+        None,
     );
     let arg0 = Some("codex-linux-sandbox");
     spawn_child_async(SpawnChildRequest {
@@ -52,6 +54,15 @@ where
     .await
 }
 
+/// Syscalls denied by the helper's seccomp filter that `[sandbox_extensions]` may exempt. Network
+/// syscalls are deliberately absent. Keep in sync with `codex-linux-sandbox`.
+pub(crate) const EXEMPTABLE_SECCOMP_SYSCALLS: [&str; 4] = [
+    "ptrace",
+    "io_uring_setup",
+    "io_uring_enter",
+    "io_uring_register",
+];
+
 pub(crate) fn allow_network_for_proxy(enforce_managed_network: bool) -> bool {
     // When managed network requirements are active, request proxy-only
     // networking from the Linux sandbox helper. Without managed requirements,
@@ -69,6 +80,7 @@ pub(crate) fn create_linux_sandbox_command_args(
     sandbox_policy_cwd: &Path,
     use_bwrap_sandbox: bool,
     allow_network_for_proxy: bool,
+    extensions: Option<&SandboxExtensions>,
 ) -> Vec<String> {
     #[expect(clippy::expect_used)]
     let sandbox_policy_cwd = sandbox_policy_cwd
@@ -92,6 +104,16 @@ pub(crate) fn create_linux_sandbox_command_args(
     if allow_network_for_proxy {
         linux_cmd.push("--allow-network-for-proxy".to_string());
     }
+    if let Some(extensions) = extensions {
+        for syscall in &extensions.allowed_syscalls {
+            linux_cmd.push("--allow-syscall".to_string());
+            linux_cmd.push(syscall.clone());
+        }
+        for device in &extensions.device_paths {
+            linux_cmd.push("--dev-bind".to_string());
+            linux_cmd.push(device.to_string_lossy().to_string());
+        }
+    }
 
     // Separator so that command arguments starting with `-` are not parsed as
     // options of the helper itself.
@@ -106,6 +128,7 @@ pub(crate) fn create_linux_sandbox_command_args(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use codex_utils_absolute_path::AbsolutePathBuf;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -115,13 +138,14 @@ mod tests {
         let policy = SandboxPolicy::new_read_only_policy();
 
         let with_bwrap =
-            create_linux_sandbox_command_args(command.clone(), &policy, cwd, true, false);
+            create_linux_sandbox_command_args(command.clone(), &policy, cwd, true, false, None);
         assert_eq!(
             with_bwrap.contains(&"--use-bwrap-sandbox".to_string()),
             true
         );
 
-        let without_bwrap = create_linux_sandbox_command_args(command, &policy, cwd, false, false);
+        let without_bwrap =
+            create_linux_sandbox_command_args(command, &policy, cwd, false, false, None);
         assert_eq!(
             without_bwrap.contains(&"--use-bwrap-sandbox".to_string()),
             false
@@ -134,13 +158,44 @@ mod tests {
         let cwd = Path::new("/tmp");
         let policy = SandboxPolicy::new_read_only_policy();
 
-        let args = create_linux_sandbox_command_args(command, &policy, cwd, true, true);
+        let args = create_linux_sandbox_command_args(command, &policy, cwd, true, true, None);
         assert_eq!(
             args.contains(&"--allow-network-for-proxy".to_string()),
             true
         );
     }
 
+    #[test]
+    fn extensions_are_forwarded_to_the_helper() {
+        let command = vec!["/bin/true".to_string()];
+        let cwd = Path::new("/tmp");
+        let policy = SandboxPolicy::new_read_only_policy();
+        let extensions = SandboxExtensions {
+            device_paths: vec![
+                AbsolutePathBuf::from_absolute_path("/dev/kvm").expect("absolute path"),
+            ],
+            allowed_syscalls: vec!["ptrace".to_string()],
+            ..Default::default()
+        };
+
+        let args = create_linux_sandbox_command_args(
+            command,
+            &policy,
+            cwd,
+            true,
+            false,
+            Some(&extensions),
+        );
+        let separator = args
+            .iter()
+            .position(|arg| arg == "--")
+            .expect("command separator");
+        assert_eq!(
+            args[separator - 4..separator],
+            ["--allow-syscall", "ptrace", "--dev-bind", "/dev/kvm"]
+        );
+    }
+
     #[test]
     fn proxy_network_requires_managed_requirements() {
         assert_eq!(allow_network_for_proxy(false), false);
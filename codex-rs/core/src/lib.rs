@@ -10,17 +10,22 @@ pub mod api_bridge;
 mod apply_patch;
 mod apps;
 pub mod auth;
+mod auth_expired;
+mod azure_auth;
+mod budget;
 mod client;
 mod client_common;
 pub mod codex;
 pub use codex::SteerInputError;
 mod codex_thread;
 mod compact_remote;
+mod container_sandbox;
 pub use codex_thread::CodexThread;
 pub use codex_thread::ThreadConfigSnapshot;
 mod agent;
 mod codex_delegate;
 mod command_canonicalization;
+mod command_patterns;
 mod commit_attribution;
 pub mod config;
 pub mod config_loader;
@@ -34,11 +39,14 @@ pub mod exec;
 pub mod exec_env;
 mod exec_policy;
 pub mod features;
+pub mod file_excerpt;
+mod file_snapshots;
 mod file_watcher;
 mod flags;
 pub mod git_info;
 pub mod instructions;
 pub mod landlock;
+mod locked_file;
 pub mod mcp;
 mod mcp_connection_manager;
 pub mod models_manager;
@@ -52,11 +60,16 @@ mod memories;
 mod mentions;
 mod message_history;
 mod model_provider_info;
+mod output_schema;
 pub mod path_utils;
 pub mod personality_migration;
+mod pinned_context;
 mod proposed_plan_parser;
+mod redaction;
+pub mod resource_limits;
 mod sandbox_tags;
 pub mod sandboxing;
+pub mod secrets;
 mod session_prefix;
 mod shell_detect;
 mod stream_events_utils;
@@ -64,10 +77,14 @@ mod tagged_block_parser;
 pub mod test_support;
 mod text_encoding;
 pub mod token_data;
+mod tool_emulation;
 mod truncate;
+mod trusted_commands;
 mod unified_exec;
 pub mod windows_sandbox;
 pub use client::X_RESPONSESAPI_INCLUDE_TIMING_METRICS_HEADER;
+pub use model_provider_info::AzureAuth;
+pub use model_provider_info::AzureProviderInfo;
 pub use model_provider_info::DEFAULT_LMSTUDIO_PORT;
 pub use model_provider_info::DEFAULT_OLLAMA_PORT;
 pub use model_provider_info::LMSTUDIO_OSS_PROVIDER_ID;
@@ -77,12 +94,14 @@ pub use model_provider_info::WireApi;
 pub use model_provider_info::built_in_model_providers;
 pub use model_provider_info::create_oss_provider_with_base_url;
 mod event_mapping;
+pub mod replay;
 pub mod review_format;
 pub mod review_prompts;
 mod seatbelt_permissions;
 mod thread_manager;
 pub mod web_search;
 pub mod windows_sandbox_read_grants;
+mod workspace_index;
 pub use codex_protocol::protocol::InitialHistory;
 pub use thread_manager::NewThread;
 pub use thread_manager::ThreadManager;
@@ -112,6 +131,8 @@ mod turn_metadata;
 mod zsh_exec_bridge;
 pub use rollout::ARCHIVED_SESSIONS_SUBDIR;
 pub use rollout::INTERACTIVE_SESSION_SOURCES;
+pub use rollout::RecordedCommand;
+pub use rollout::RecordedPatch;
 pub use rollout::RolloutRecorder;
 pub use rollout::RolloutRecorderParams;
 pub use rollout::SESSIONS_SUBDIR;
@@ -129,7 +150,13 @@ pub use rollout::list::ThreadsPage;
 pub use rollout::list::parse_cursor;
 pub use rollout::list::read_head_for_summary;
 pub use rollout::list::read_session_meta_line;
+pub use rollout::list::read_thread_model;
 pub use rollout::policy::EventPersistenceMode;
+pub use rollout::read_rollout_text;
+pub use rollout::recorded_commands;
+pub use rollout::recorded_patches;
+pub use rollout::remove_rollout;
+pub use rollout::rename_rollout;
 pub use rollout::rollout_date_parts;
 pub use rollout::session_index::find_thread_names_by_ids;
 mod function_tool;
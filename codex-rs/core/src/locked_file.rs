@@ -0,0 +1,61 @@
+//! Exclusive access to the small shared files under `~/.codex` that several sessions write.
+//!
+//! Sessions in different processes append to the message history and rewrite the budget ledger,
+//! the trusted command store, and the project memory files. Each of those updates runs under an
+//! advisory lock on the file so concurrent writers neither interleave nor lose each other's
+//! changes.
+
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::Result;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+
+/// How many times a contended lock is tried before giving up, and how long to wait in between.
+pub(crate) const MAX_RETRIES: usize = 10;
+pub(crate) const RETRY_SLEEP: Duration = Duration::from_millis(100);
+
+/// Opens `path` for reading and appending, creating it readable only by the owner, and runs `f`
+/// on a blocking thread while holding an exclusive lock on it.
+///
+/// Writes go to the end of the file on Unix. On Windows the file is not opened in append mode, so
+/// `f` must position the cursor before writing. Truncating the file first works on both.
+pub(crate) async fn with_locked_append_file<T, F>(path: PathBuf, f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut File) -> Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || -> Result<T> {
+        let mut options = OpenOptions::new();
+        options.read(true).write(true).create(true).truncate(false);
+        #[cfg(unix)]
+        {
+            options.append(true);
+            options.mode(0o600);
+        }
+        let mut file = options.open(&path)?;
+
+        // Retry a few times to avoid indefinite blocking when contended.
+        for _ in 0..MAX_RETRIES {
+            match file.try_lock() {
+                Ok(()) => return f(&mut file),
+                Err(std::fs::TryLockError::WouldBlock) => {
+                    std::thread::sleep(RETRY_SLEEP);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Err(std::io::Error::new(
+            std::io::ErrorKind::WouldBlock,
+            format!(
+                "could not acquire exclusive lock on {} after multiple attempts",
+                path.display()
+            ),
+        ))
+    })
+    .await?
+}
@@ -0,0 +1,237 @@
+//! Caps how many MCP tool calls run at once, overall and per server. Calls over the cap wait in
+//! line and start in the order they were made.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
+use std::sync::PoisonError;
+
+use tokio::sync::watch;
+
+/// Where a call stands: `Some(position)` while it waits in line (1 is next), `None` once it may
+/// run.
+type QueuePosition = Option<usize>;
+
+#[derive(Default)]
+pub(crate) struct McpCallLimiter {
+    state: StdMutex<LimiterState>,
+}
+
+#[derive(Default)]
+struct LimiterState {
+    /// Limit on calls in flight across every server. Unlimited when unset.
+    max_concurrent_calls: Option<usize>,
+    running: usize,
+    running_by_server: HashMap<String, usize>,
+    queue: VecDeque<Waiter>,
+    next_id: u64,
+}
+
+struct Waiter {
+    id: u64,
+    server: String,
+    server_limit: Option<usize>,
+    position: watch::Sender<QueuePosition>,
+}
+
+impl LimiterState {
+    fn has_room(&self, server: &str, server_limit: Option<usize>) -> bool {
+        let running_on_server = self.running_by_server.get(server).copied().unwrap_or(0);
+        self.max_concurrent_calls
+            .is_none_or(|limit| self.running < limit)
+            && server_limit.is_none_or(|limit| running_on_server < limit)
+    }
+
+    fn start(&mut self, server: &str) {
+        self.running += 1;
+        *self
+            .running_by_server
+            .entry(server.to_string())
+            .or_default() += 1;
+    }
+
+    fn finish(&mut self, server: &str) {
+        self.running = self.running.saturating_sub(1);
+        if let Some(running) = self.running_by_server.get_mut(server) {
+            *running = running.saturating_sub(1);
+            if *running == 0 {
+                self.running_by_server.remove(server);
+            }
+        }
+    }
+
+    /// Starts every waiting call that now has room, oldest first, and tells the calls still
+    /// waiting where they stand. A call only skips ahead of older ones when those are held back
+    /// by their own server's limit.
+    fn admit_waiters(&mut self) {
+        let mut idx = 0;
+        while idx < self.queue.len() {
+            let waiter = &self.queue[idx];
+            if self.has_room(&waiter.server, waiter.server_limit) {
+                if let Some(waiter) = self.queue.remove(idx) {
+                    self.start(&waiter.server);
+                    let _ = waiter.position.send(None);
+                }
+            } else {
+                idx += 1;
+            }
+        }
+        for (idx, waiter) in self.queue.iter().enumerate() {
+            waiter.position.send_if_modified(|position| {
+                let changed = *position != Some(idx + 1);
+                *position = Some(idx + 1);
+                changed
+            });
+        }
+    }
+}
+
+impl McpCallLimiter {
+    pub(crate) fn set_max_concurrent_calls(&self, max_concurrent_calls: Option<usize>) {
+        let mut state = self.lock_state();
+        state.max_concurrent_calls = max_concurrent_calls;
+        state.admit_waiters();
+    }
+
+    /// Asks for room to run a call to `server`. The returned slot is either running already or
+    /// waiting in line; in both cases dropping it gives up its place.
+    pub(crate) fn acquire(
+        self: &Arc<Self>,
+        server: &str,
+        server_limit: Option<usize>,
+    ) -> McpCallSlot {
+        let mut state = self.lock_state();
+        let id = state.next_id;
+        state.next_id += 1;
+        let (tx, rx) = watch::channel(Some(state.queue.len() + 1));
+        state.queue.push_back(Waiter {
+            id,
+            server: server.to_string(),
+            server_limit,
+            position: tx,
+        });
+        state.admit_waiters();
+        McpCallSlot {
+            limiter: Arc::clone(self),
+            id,
+            server: server.to_string(),
+            position: rx,
+        }
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, LimiterState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Room for one MCP tool call, held until the call finishes.
+pub(crate) struct McpCallSlot {
+    limiter: Arc<McpCallLimiter>,
+    id: u64,
+    server: String,
+    position: watch::Receiver<QueuePosition>,
+}
+
+impl McpCallSlot {
+    /// Where the call stands in line, or `None` once it may run.
+    pub(crate) fn queue_position(&self) -> Option<usize> {
+        *self.position.borrow()
+    }
+
+    /// Waits until the call's place in line changes, including when it may run.
+    pub(crate) async fn position_changed(&mut self) {
+        // The sender only goes away once the call is admitted, which is also a change.
+        let _ = self.position.changed().await;
+    }
+}
+
+impl Drop for McpCallSlot {
+    fn drop(&mut self) {
+        let mut state = self.limiter.lock_state();
+        if let Some(idx) = state.queue.iter().position(|waiter| waiter.id == self.id) {
+            state.queue.remove(idx);
+        } else {
+            state.finish(&self.server);
+        }
+        state.admit_waiters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn limiter(max_concurrent_calls: Option<usize>) -> Arc<McpCallLimiter> {
+        let limiter = Arc::new(McpCallLimiter::default());
+        limiter.set_max_concurrent_calls(max_concurrent_calls);
+        limiter
+    }
+
+    #[test]
+    fn calls_over_the_limit_start_in_order() {
+        let limiter = limiter(Some(1));
+        let first = limiter.acquire("docs", None);
+        let second = limiter.acquire("docs", None);
+        let third = limiter.acquire("search", None);
+        assert_eq!(first.queue_position(), None);
+        assert_eq!(second.queue_position(), Some(1));
+        assert_eq!(third.queue_position(), Some(2));
+
+        drop(first);
+        assert_eq!(second.queue_position(), None);
+        assert_eq!(third.queue_position(), Some(1));
+
+        drop(second);
+        assert_eq!(third.queue_position(), None);
+    }
+
+    #[test]
+    fn server_limit_only_holds_back_calls_to_that_server() {
+        let limiter = limiter(Some(3));
+        let fragile = limiter.acquire("fragile", Some(1));
+        let fragile_waiting = limiter.acquire("fragile", Some(1));
+        let other = limiter.acquire("other", None);
+        assert_eq!(fragile.queue_position(), None);
+        assert_eq!(fragile_waiting.queue_position(), Some(1));
+        assert_eq!(other.queue_position(), None);
+
+        drop(fragile);
+        assert_eq!(fragile_waiting.queue_position(), None);
+    }
+
+    #[test]
+    fn dropping_a_waiting_call_gives_up_its_place() {
+        let limiter = limiter(Some(1));
+        let running = limiter.acquire("docs", None);
+        let cancelled = limiter.acquire("docs", None);
+        let waiting = limiter.acquire("docs", None);
+        assert_eq!(waiting.queue_position(), Some(2));
+
+        drop(cancelled);
+        assert_eq!(waiting.queue_position(), Some(1));
+
+        drop(running);
+        assert_eq!(waiting.queue_position(), None);
+        let next = limiter.acquire("docs", None);
+        assert_eq!(next.queue_position(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn waiting_call_is_woken_when_admitted() {
+        let limiter = limiter(Some(1));
+        let running = limiter.acquire("docs", None);
+        let mut waiting = limiter.acquire("docs", None);
+
+        let wait = tokio::spawn(async move {
+            while waiting.queue_position().is_some() {
+                waiting.position_changed().await;
+            }
+            waiting
+        });
+        drop(running);
+        let admitted = wait.await.expect("join waiting call");
+        assert_eq!(admitted.queue_position(), None);
+    }
+}
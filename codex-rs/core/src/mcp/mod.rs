@@ -1,6 +1,23 @@
 pub mod auth;
+mod call_limiter;
+mod prompts;
+mod resource_mentions;
+mod sampling;
+mod secrets;
 mod skill_dependencies;
+mod tool_arguments;
+pub(crate) use call_limiter::McpCallLimiter;
+pub(crate) use call_limiter::McpCallSlot;
+pub(crate) use prompts::list_mcp_prompts;
+pub(crate) use prompts::prompt_messages_text;
+pub(crate) use resource_mentions::McpResourceInjections;
+pub(crate) use resource_mentions::build_mcp_resource_injections;
+pub use resource_mentions::mcp_resource_mention_path;
+pub(crate) use sampling::handle_mcp_sampling_request;
+pub(crate) use secrets::resolve_mcp_env;
 pub(crate) use skill_dependencies::maybe_prompt_and_install_mcp_dependencies;
+pub(crate) use tool_arguments::ToolArgumentError;
+pub(crate) use tool_arguments::check_tool_arguments;
 
 use std::collections::HashMap;
 use std::env;
@@ -11,6 +28,8 @@ use async_channel::unbounded;
 use codex_protocol::mcp::Resource;
 use codex_protocol::mcp::ResourceTemplate;
 use codex_protocol::mcp::Tool;
+use codex_protocol::protocol::Event;
+use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::McpListToolsResponseEvent;
 use codex_protocol::protocol::SandboxPolicy;
 use serde_json::Value;
@@ -138,7 +157,11 @@ fn codex_apps_mcp_server_config(config: &Config, auth: Option<&CodexAuth>) -> Mc
         tool_timeout_sec: None,
         enabled_tools: None,
         disabled_tools: None,
+        tool_renames: None,
+        tools: None,
+        tool_cache_ttl_sec: None,
         scopes: None,
+        max_concurrent_calls: None,
     }
 }
 
@@ -173,7 +196,7 @@ pub(crate) fn effective_mcp_servers(
 
 pub async fn collect_mcp_snapshot(config: &Config) -> McpListToolsResponseEvent {
     let auth_manager = AuthManager::shared(
-        config.codex_home.clone(),
+        config.auth_home(),
         false,
         config.cli_auth_credentials_store_mode,
     );
@@ -196,14 +219,6 @@ pub async fn collect_mcp_snapshot(config: &Config) -> McpListToolsResponseEvent
     drop(rx_event);
     let cancel_token = CancellationToken::new();
 
-    // Use ReadOnly sandbox policy for MCP snapshot collection (safest default)
-    let sandbox_state = SandboxState {
-        sandbox_policy: SandboxPolicy::new_read_only_policy(),
-        codex_linux_sandbox_exe: config.codex_linux_sandbox_exe.clone(),
-        sandbox_cwd: env::current_dir().unwrap_or_else(|_| PathBuf::from("/")),
-        use_linux_sandbox_bwrap: config.features.enabled(Feature::UseLinuxSandboxBwrap),
-    };
-
     mcp_connection_manager
         .initialize(
             &mcp_servers,
@@ -211,7 +226,7 @@ pub async fn collect_mcp_snapshot(config: &Config) -> McpListToolsResponseEvent
             auth_status_entries.clone(),
             tx_event,
             cancel_token.clone(),
-            sandbox_state,
+            snapshot_sandbox_state(config),
         )
         .await;
 
@@ -223,6 +238,69 @@ pub async fn collect_mcp_snapshot(config: &Config) -> McpListToolsResponseEvent
     snapshot
 }
 
+/// Starts `server` on its own, the way a session would, and lists its tools sorted by name.
+/// Fails with the error a session would report when the server does not start.
+pub async fn probe_mcp_server(
+    config: &Config,
+    server_name: &str,
+    server: &McpServerConfig,
+) -> Result<Vec<Tool>, String> {
+    let mcp_servers = HashMap::from([(server_name.to_string(), server.clone())]);
+    let auth_status_entries =
+        compute_auth_statuses(mcp_servers.iter(), config.mcp_oauth_credentials_store_mode).await;
+
+    let mut mcp_connection_manager = McpConnectionManager::default();
+    let (tx_event, rx_event) = unbounded();
+    let cancel_token = CancellationToken::new();
+    mcp_connection_manager
+        .initialize(
+            &mcp_servers,
+            config.mcp_oauth_credentials_store_mode,
+            auth_status_entries.clone(),
+            tx_event,
+            cancel_token.clone(),
+            snapshot_sandbox_state(config),
+        )
+        .await;
+
+    let startup = loop {
+        match rx_event.recv().await {
+            Ok(Event {
+                msg: EventMsg::McpStartupComplete(summary),
+                ..
+            }) => break summary,
+            Ok(_) => {}
+            Err(_) => return Err("server startup was interrupted".to_string()),
+        }
+    };
+    if let Some(failure) = startup.failed.into_iter().next() {
+        cancel_token.cancel();
+        return Err(failure.error);
+    }
+    if startup.ready.is_empty() {
+        cancel_token.cancel();
+        return Err("server startup was cancelled".to_string());
+    }
+
+    let snapshot =
+        collect_mcp_snapshot_from_manager(&mcp_connection_manager, auth_status_entries).await;
+    cancel_token.cancel();
+
+    let mut tools = snapshot.tools.into_values().collect::<Vec<_>>();
+    tools.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(tools)
+}
+
+/// Servers started outside a session get a read-only sandbox, the safest default.
+fn snapshot_sandbox_state(config: &Config) -> SandboxState {
+    SandboxState {
+        sandbox_policy: SandboxPolicy::new_read_only_policy(),
+        codex_linux_sandbox_exe: config.codex_linux_sandbox_exe.clone(),
+        sandbox_cwd: env::current_dir().unwrap_or_else(|_| PathBuf::from("/")),
+        use_linux_sandbox_bwrap: config.features.enabled(Feature::UseLinuxSandboxBwrap),
+    }
+}
+
 pub fn split_qualified_tool_name(qualified_name: &str) -> Option<(String, String)> {
     let mut parts = qualified_name.split(MCP_TOOL_NAME_DELIMITER);
     let prefix = parts.next()?;
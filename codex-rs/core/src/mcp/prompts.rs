@@ -0,0 +1,95 @@
+//! Prompt templates published by MCP servers.
+//!
+//! Clients list them with `Op::ListMcpPrompts` and offer them as commands; picking one sends
+//! `Op::GetMcpPrompt` and the expanded text comes back to be submitted as a user message.
+
+use std::collections::HashMap;
+
+use codex_protocol::mcp::Prompt;
+use rmcp::model::GetPromptResult;
+use serde_json::Value;
+
+use crate::mcp_connection_manager::McpConnectionManager;
+
+pub(crate) async fn list_mcp_prompts(
+    mcp_connection_manager: &McpConnectionManager,
+) -> HashMap<String, Vec<Prompt>> {
+    mcp_connection_manager
+        .list_all_prompts()
+        .await
+        .into_iter()
+        .map(|(server, prompts)| {
+            let prompts = prompts
+                .into_iter()
+                .filter_map(|prompt| {
+                    let name = prompt.name.clone();
+                    match serde_json::to_value(prompt).and_then(Prompt::from_mcp_value) {
+                        Ok(prompt) => Some(prompt),
+                        Err(err) => {
+                            tracing::warn!(
+                                "Failed to convert MCP prompt '{name}' from '{server}': {err}"
+                            );
+                            None
+                        }
+                    }
+                })
+                .collect();
+            (server, prompts)
+        })
+        .collect()
+}
+
+/// Joins the messages of an expanded prompt into the text of a single user message.
+pub(crate) fn prompt_messages_text(result: &GetPromptResult) -> String {
+    result
+        .messages
+        .iter()
+        .filter_map(|message| {
+            let content = serde_json::to_value(&message.content).ok()?;
+            prompt_content_text(&content)
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn prompt_content_text(content: &Value) -> Option<String> {
+    let text = |value: &Value| value.as_str().map(str::to_string);
+    match content.get("type")?.as_str()? {
+        "text" => text(content.get("text")?),
+        "resource" => {
+            let resource = content.get("resource")?;
+            text(resource.get("text")?)
+        }
+        "resource_link" => text(content.get("uri")?),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn prompt_text_keeps_text_and_embedded_resources() {
+        let result: GetPromptResult = serde_json::from_value(json!({
+            "messages": [
+                {"role": "user", "content": {"type": "text", "text": "Review this file:"}},
+                {"role": "user", "content": {
+                    "type": "resource",
+                    "resource": {"uri": "file:///src/lib.rs", "text": "fn main() {}"}
+                }},
+                {"role": "user", "content": {
+                    "type": "image", "data": "aGVsbG8=", "mimeType": "image/png"
+                }}
+            ]
+        }))
+        .expect("valid prompt result");
+
+        assert_eq!(
+            prompt_messages_text(&result),
+            "Review this file:\n\nfn main() {}"
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! MCP resources attached to user input.
+//!
+//! Clients attach a resource with a [`UserInput::Mention`] whose path comes from
+//! [`mcp_resource_mention_path`]. The resource is read when the turn starts and its contents are
+//! recorded right after the user message, the same way mentioned skills are. Like tool output, the
+//! contents are masked by the secret redactor and truncated to the turn's truncation policy.
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::user_input::UserInput;
+use rmcp::model::ReadResourceRequestParams;
+use rmcp::model::ResourceContents;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::redaction::SecretRedactor;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::formatted_truncate_text;
+
+const MCP_RESOURCE_PATH_PREFIX: &str = "mcp-resource://";
+
+/// Mention path that attaches the resource `uri` published by the MCP server `server`.
+pub fn mcp_resource_mention_path(server: &str, uri: &str) -> String {
+    format!("{MCP_RESOURCE_PATH_PREFIX}{server}/{uri}")
+}
+
+/// Splits a path built by [`mcp_resource_mention_path`] into the server name and resource URI.
+fn parse_mcp_resource_mention_path(path: &str) -> Option<(&str, &str)> {
+    path.strip_prefix(MCP_RESOURCE_PATH_PREFIX)?
+        .split_once('/')
+        .filter(|(server, uri)| !server.is_empty() && !uri.is_empty())
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct McpResourceInjections {
+    pub(crate) items: Vec<ResponseItem>,
+    pub(crate) warnings: Vec<String>,
+}
+
+pub(crate) async fn build_mcp_resource_injections(
+    sess: &Session,
+    turn_context: &TurnContext,
+    input: &[UserInput],
+) -> McpResourceInjections {
+    let mut result = McpResourceInjections::default();
+    for item in input {
+        let UserInput::Mention { path, .. } = item else {
+            continue;
+        };
+        let Some((server, uri)) = parse_mcp_resource_mention_path(path) else {
+            continue;
+        };
+        let params = ReadResourceRequestParams {
+            meta: None,
+            uri: uri.to_string(),
+        };
+        match sess.read_resource(server, params).await {
+            Ok(read) => result.items.push(resource_context_item(
+                server,
+                uri,
+                &read.contents,
+                turn_context.truncation_policy,
+                &sess.services.secret_redactor,
+            )),
+            Err(err) => result.warnings.push(format!(
+                "Failed to read MCP resource {uri} from {server}: {err:#}"
+            )),
+        }
+    }
+    result
+}
+
+fn resource_context_item(
+    server: &str,
+    uri: &str,
+    contents: &[ResourceContents],
+    truncation_policy: TruncationPolicy,
+    redactor: &SecretRedactor,
+) -> ResponseItem {
+    let body = contents
+        .iter()
+        .map(|content| match content {
+            ResourceContents::TextResourceContents { text, .. } => text.clone(),
+            ResourceContents::BlobResourceContents { mime_type, .. } => match mime_type {
+                Some(mime_type) => format!("[binary content: {mime_type}]"),
+                None => "[binary content]".to_string(),
+            },
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let body = formatted_truncate_text(&redactor.redact(&body), truncation_policy);
+    ResponseItem::Message {
+        id: None,
+        role: "user".to_string(),
+        content: vec![ContentItem::InputText {
+            text: format!(
+                "<mcp_resource>\n<server>{server}</server>\n<uri>{uri}</uri>\n{body}\n</mcp_resource>"
+            ),
+        }],
+        end_turn: None,
+        phase: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::types::Redaction;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn mention_paths_round_trip_uris_with_slashes() {
+        let path = mcp_resource_mention_path("docs", "file:///guides/setup.md");
+        assert_eq!(path, "mcp-resource://docs/file:///guides/setup.md");
+        assert_eq!(
+            parse_mcp_resource_mention_path(&path),
+            Some(("docs", "file:///guides/setup.md"))
+        );
+        assert_eq!(parse_mcp_resource_mention_path("app://connector"), None);
+        assert_eq!(parse_mcp_resource_mention_path("mcp-resource://docs"), None);
+    }
+
+    #[test]
+    fn resource_contents_are_redacted_and_truncated() {
+        let redactor = SecretRedactor::new(&Redaction {
+            env_vars: Vec::new(),
+            patterns: vec!["hunter2".to_string()],
+        })
+        .expect("valid redaction config");
+        let text = |text: &str| ResourceContents::TextResourceContents {
+            uri: "file:///notes.md".to_string(),
+            mime_type: None,
+            text: text.to_string(),
+            meta: None,
+        };
+
+        let short = resource_context_item(
+            "docs",
+            "file:///notes.md",
+            &[text("password: hunter2")],
+            TruncationPolicy::Bytes(1_000),
+            &redactor,
+        );
+        assert_eq!(
+            short,
+            ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: "<mcp_resource>\n<server>docs</server>\n<uri>file:///notes.md</uri>\npassword: [REDACTED_SECRET]\n</mcp_resource>".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            }
+        );
+
+        let long = resource_context_item(
+            "docs",
+            "file:///notes.md",
+            &[text(&"line\n".repeat(1_000))],
+            TruncationPolicy::Bytes(200),
+            &redactor,
+        );
+        let ResponseItem::Message { content, .. } = long else {
+            panic!("expected a message");
+        };
+        let [ContentItem::InputText { text }] = content.as_slice() else {
+            panic!("expected one text item");
+        };
+        assert!(text.len() < 1_000, "{text}");
+        assert!(text.contains("Total output lines: 1000"), "{text}");
+    }
+}
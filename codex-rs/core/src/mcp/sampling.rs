@@ -0,0 +1,331 @@
+//! `sampling/createMessage` requests from MCP servers.
+//!
+//! Servers that declare they need an LLM ask the client to run a completion for them. The
+//! request is answered with the session's model once the approval policy allows it, and the
+//! tokens it uses are counted in the session's usage and budget like any other model request.
+
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use codex_api::ResponseEvent;
+use codex_protocol::models::BaseInstructions;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::request_user_input::RequestUserInputArgs;
+use codex_protocol::request_user_input::RequestUserInputQuestion;
+use codex_protocol::request_user_input::RequestUserInputQuestionOption;
+use codex_protocol::request_user_input::RequestUserInputResponse;
+use codex_rmcp_client::Sampling;
+use codex_rmcp_client::SamplingResponse;
+use futures::StreamExt;
+use serde::Serialize;
+use serde_json::Value;
+use serde_json::json;
+
+use crate::Prompt;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::tools::sandboxing::remember_for_session;
+
+const MCP_SAMPLING_QUESTION_ID_PREFIX: &str = "mcp_sampling_approval";
+const MCP_SAMPLING_APPROVE: &str = "Approve Once";
+const MCP_SAMPLING_APPROVE_FOR_SESSION: &str = "Approve this Session";
+const MCP_SAMPLING_DENY: &str = "Deny";
+
+#[derive(Debug, Serialize)]
+struct McpSamplingApprovalKey {
+    mcp_sampling_server: String,
+}
+
+/// Answers a sampling request from `server_name`, or fails when it is not approved.
+pub(crate) async fn handle_mcp_sampling_request(
+    sess: &Arc<Session>,
+    server_name: &str,
+    params: Sampling,
+) -> Result<SamplingResponse> {
+    let request = serde_json::to_value(&params).context("failed to read sampling request")?;
+    let active_turn = sess.active_turn_context().await;
+    let turn_context = match active_turn.clone() {
+        Some(turn_context) => turn_context,
+        None => sess.new_default_turn().await,
+    };
+    if !sampling_is_approved(
+        sess,
+        &turn_context,
+        active_turn.is_some(),
+        server_name,
+        &request,
+    )
+    .await
+    {
+        return Err(anyhow!("the user declined the sampling request"));
+    }
+
+    let prompt = Prompt {
+        input: sampling_messages_to_input(&request)?,
+        base_instructions: BaseInstructions {
+            text: request
+                .get("systemPrompt")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+        },
+        max_output_tokens: request.get("maxTokens").and_then(Value::as_u64),
+        ..Default::default()
+    };
+    let text = stream_sampling_response(sess, &turn_context, &prompt).await?;
+    let response = json!({
+        "role": "assistant",
+        "content": { "type": "text", "text": text },
+        "model": turn_context.model_info.slug,
+        "stopReason": "endTurn",
+    });
+    serde_json::from_value(response).context("failed to build sampling response")
+}
+
+async fn sampling_is_approved(
+    sess: &Session,
+    turn_context: &TurnContext,
+    in_turn: bool,
+    server_name: &str,
+    request: &Value,
+) -> bool {
+    if matches!(turn_context.approval_policy, AskForApproval::Never) {
+        return true;
+    }
+    let key = McpSamplingApprovalKey {
+        mcp_sampling_server: server_name.to_string(),
+    };
+    if matches!(
+        sess.services.tool_approvals.lock().await.get(&key),
+        Some(ReviewDecision::ApprovedForSession)
+    ) {
+        return true;
+    }
+    // Answers to questions are routed through the running turn, so there is nobody to ask.
+    if !in_turn {
+        return false;
+    }
+
+    let question_id = format!("{MCP_SAMPLING_QUESTION_ID_PREFIX}_{server_name}");
+    let args = RequestUserInputArgs {
+        questions: vec![build_sampling_question(
+            question_id.clone(),
+            server_name,
+            request,
+        )],
+    };
+    let response = sess
+        .request_user_input(turn_context, question_id.clone(), args)
+        .await;
+    match sampling_answer(response, &question_id) {
+        Some(MCP_SAMPLING_APPROVE_FOR_SESSION) => {
+            remember_for_session(&sess.services, vec![key]).await;
+            true
+        }
+        Some(MCP_SAMPLING_APPROVE) => true,
+        _ => false,
+    }
+}
+
+fn build_sampling_question(
+    question_id: String,
+    server_name: &str,
+    request: &Value,
+) -> RequestUserInputQuestion {
+    let max_tokens = request.get("maxTokens").and_then(Value::as_u64);
+    let limit = max_tokens
+        .map(|max_tokens| format!(" (up to {max_tokens} tokens)"))
+        .unwrap_or_default();
+    RequestUserInputQuestion {
+        id: question_id,
+        header: "Approve MCP sampling?".to_string(),
+        question: format!(
+            "The MCP server \"{server_name}\" wants to run a completion with your model{limit}. \
+             Allow it?"
+        ),
+        is_other: false,
+        is_secret: false,
+        options: Some(vec![
+            RequestUserInputQuestionOption {
+                label: MCP_SAMPLING_APPROVE.to_string(),
+                description: "Run this completion.".to_string(),
+            },
+            RequestUserInputQuestionOption {
+                label: MCP_SAMPLING_APPROVE_FOR_SESSION.to_string(),
+                description: "Run completions for this server without asking again.".to_string(),
+            },
+            RequestUserInputQuestionOption {
+                label: MCP_SAMPLING_DENY.to_string(),
+                description: "Reject the request.".to_string(),
+            },
+        ]),
+    }
+}
+
+fn sampling_answer(
+    response: Option<RequestUserInputResponse>,
+    question_id: &str,
+) -> Option<&'static str> {
+    let response = response?;
+    let answers = &response.answers.get(question_id)?.answers;
+    [MCP_SAMPLING_APPROVE_FOR_SESSION, MCP_SAMPLING_APPROVE]
+        .into_iter()
+        .find(|label| answers.iter().any(|answer| answer == label))
+}
+
+/// Converts the request's messages to model input. Text and images are kept; other content,
+/// such as audio, is dropped.
+fn sampling_messages_to_input(request: &Value) -> Result<Vec<ResponseItem>> {
+    let messages = request
+        .get("messages")
+        .and_then(Value::as_array)
+        .ok_or_else(|| anyhow!("sampling request has no messages"))?;
+    let input = messages
+        .iter()
+        .filter_map(|message| {
+            let role = message.get("role").and_then(Value::as_str)?;
+            let assistant = role == "assistant";
+            // Content is a single block, or a list of blocks in newer protocol versions.
+            let blocks = match message.get("content")? {
+                Value::Array(blocks) => blocks.iter().collect::<Vec<_>>(),
+                block => vec![block],
+            };
+            let content = blocks
+                .into_iter()
+                .filter_map(|block| sampling_content_item(block, assistant))
+                .collect::<Vec<_>>();
+            (!content.is_empty()).then(|| ResponseItem::Message {
+                id: None,
+                role: role.to_string(),
+                content,
+                end_turn: None,
+                phase: None,
+            })
+        })
+        .collect::<Vec<_>>();
+    if input.is_empty() {
+        return Err(anyhow!("sampling request has no text or image content"));
+    }
+    Ok(input)
+}
+
+fn sampling_content_item(block: &Value, assistant: bool) -> Option<ContentItem> {
+    let text = |key: &str| block.get(key).and_then(Value::as_str);
+    match text("type")? {
+        "text" if assistant => Some(ContentItem::OutputText {
+            text: text("text")?.to_string(),
+        }),
+        "text" => Some(ContentItem::InputText {
+            text: text("text")?.to_string(),
+        }),
+        "image" if !assistant => Some(ContentItem::InputImage {
+            image_url: format!("data:{};base64,{}", text("mimeType")?, text("data")?),
+        }),
+        _ => None,
+    }
+}
+
+async fn stream_sampling_response(
+    sess: &Session,
+    turn_context: &TurnContext,
+    prompt: &Prompt,
+) -> Result<String> {
+    let turn_metadata_header = turn_context.turn_metadata_state.current_header_value();
+    let mut client_session = sess.services.model_client.new_session();
+    let mut stream = client_session
+        .stream(
+            prompt,
+            &turn_context.model_info,
+            &turn_context.otel_manager,
+            turn_context.reasoning_effort,
+            turn_context.reasoning_summary,
+            turn_metadata_header.as_deref(),
+        )
+        .await?;
+
+    let mut text = String::new();
+    while let Some(event) = stream.next().await.transpose()? {
+        match event {
+            ResponseEvent::OutputTextDelta(delta) => text.push_str(&delta),
+            ResponseEvent::OutputItemDone(ResponseItem::Message { content, .. })
+                if text.is_empty() =>
+            {
+                if let Some(message) = crate::compact::content_items_to_text(&content) {
+                    text.push_str(&message);
+                }
+            }
+            ResponseEvent::Completed { token_usage, .. } => {
+                if let Some(token_usage) = token_usage.as_ref() {
+                    sess.record_side_request_usage(turn_context, token_usage)
+                        .await;
+                }
+                return Ok(text);
+            }
+            _ => {}
+        }
+    }
+    Err(anyhow!(
+        "stream closed before the sampling response completed"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn sampling_messages_keep_roles_text_and_images() {
+        let request = json!({
+            "messages": [
+                {"role": "user", "content": {"type": "text", "text": "Describe this"}},
+                {"role": "user", "content": {
+                    "type": "image", "data": "aGVsbG8=", "mimeType": "image/png"
+                }},
+                {"role": "assistant", "content": [{"type": "text", "text": "A logo."}]},
+                {"role": "user", "content": {
+                    "type": "audio", "data": "aGVsbG8=", "mimeType": "audio/wav"
+                }}
+            ],
+            "maxTokens": 100
+        });
+
+        let input = sampling_messages_to_input(&request).expect("valid messages");
+
+        let message = |role: &str, content| ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content: vec![content],
+            end_turn: None,
+            phase: None,
+        };
+        assert_eq!(
+            input,
+            vec![
+                message(
+                    "user",
+                    ContentItem::InputText {
+                        text: "Describe this".to_string(),
+                    }
+                ),
+                message(
+                    "user",
+                    ContentItem::InputImage {
+                        image_url: "data:image/png;base64,aGVsbG8=".to_string(),
+                    }
+                ),
+                message(
+                    "assistant",
+                    ContentItem::OutputText {
+                        text: "A logo.".to_string(),
+                    }
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,152 @@
+//! Secrets referenced from the `env` of stdio MCP servers.
+//!
+//! Values written as `{ keychain = "<name>" }` are read from the OS keychain and values written as
+//! `{ command = [...] }` are read from a command's output, each time the server is spawned, so API
+//! tokens never have to be stored in `config.toml`.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use anyhow::anyhow;
+use codex_keyring_store::KeyringStore;
+
+use crate::config::types::McpEnvValue;
+use crate::secrets::CONFIG_SECRETS_KEYRING_SERVICE;
+use crate::secrets::run_secret_command;
+
+/// Resolves every value in a stdio server's `env` to the string passed to the process.
+pub(crate) async fn resolve_mcp_env(
+    server_name: &str,
+    env: Option<HashMap<String, McpEnvValue>>,
+    keyring_store: &dyn KeyringStore,
+) -> Result<Option<HashMap<String, String>>> {
+    let Some(env) = env else {
+        return Ok(None);
+    };
+
+    let mut resolved = HashMap::with_capacity(env.len());
+    for (key, value) in env {
+        let value = match value {
+            McpEnvValue::Literal(value) => value,
+            McpEnvValue::Keychain { keychain } => {
+                match keyring_store.load(CONFIG_SECRETS_KEYRING_SERVICE, &keychain) {
+                    Ok(Some(secret)) => secret,
+                    Ok(None) => {
+                        return Err(anyhow!(
+                            "Secret '{keychain}' for {key} of MCP server '{server_name}' is not in the keychain; save it with `codex mcp set-secret {keychain}`"
+                        ));
+                    }
+                    Err(err) => {
+                        return Err(anyhow!(
+                            "Failed to read secret '{keychain}' for {key} of MCP server '{server_name}' from the keychain: {err}"
+                        ));
+                    }
+                }
+            }
+            McpEnvValue::Command { command } => {
+                run_secret_command(&command, &format!("{key} of MCP server '{server_name}'"))
+                    .await?
+            }
+        };
+        resolved.insert(key, value);
+    }
+    Ok(Some(resolved))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_keyring_store::tests::MockKeyringStore;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn keychain_values_are_loaded_from_the_store() {
+        let store = MockKeyringStore::default();
+        store
+            .save(CONFIG_SECRETS_KEYRING_SERVICE, "jira-token", "s3cret")
+            .expect("save secret");
+        let env = HashMap::from([
+            ("PLAIN".to_string(), McpEnvValue::from("value")),
+            (
+                "JIRA_TOKEN".to_string(),
+                McpEnvValue::Keychain {
+                    keychain: "jira-token".to_string(),
+                },
+            ),
+        ]);
+
+        let resolved = resolve_mcp_env("jira", Some(env), &store)
+            .await
+            .expect("resolve env");
+
+        assert_eq!(
+            resolved,
+            Some(HashMap::from([
+                ("PLAIN".to_string(), "value".to_string()),
+                ("JIRA_TOKEN".to_string(), "s3cret".to_string()),
+            ]))
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_keychain_secret_is_an_error() {
+        let env = HashMap::from([(
+            "JIRA_TOKEN".to_string(),
+            McpEnvValue::Keychain {
+                keychain: "jira-token".to_string(),
+            },
+        )]);
+
+        let err = resolve_mcp_env("jira", Some(env), &MockKeyringStore::default())
+            .await
+            .expect_err("missing secret");
+
+        assert!(
+            err.to_string()
+                .contains("save it with `codex mcp set-secret jira-token`"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_values_use_trimmed_stdout() {
+        let env = HashMap::from([(
+            "TOKEN".to_string(),
+            McpEnvValue::Command {
+                command: vec![
+                    "sh".to_string(),
+                    "-c".to_string(),
+                    "printf '  tok-123\\n'".to_string(),
+                ],
+            },
+        )]);
+
+        let resolved = resolve_mcp_env("docs", Some(env), &MockKeyringStore::default())
+            .await
+            .expect("resolve env");
+
+        assert_eq!(
+            resolved,
+            Some(HashMap::from([(
+                "TOKEN".to_string(),
+                "tok-123".to_string()
+            )]))
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn failing_command_is_an_error() {
+        let env = HashMap::from([(
+            "TOKEN".to_string(),
+            McpEnvValue::Command {
+                command: vec!["sh".to_string(), "-c".to_string(), "exit 3".to_string()],
+            },
+        )]);
+
+        resolve_mcp_env("docs", Some(env), &MockKeyringStore::default())
+            .await
+            .expect_err("command failed");
+    }
+}
@@ -385,7 +385,11 @@ fn mcp_dependency_to_server_config(
             tool_timeout_sec: None,
             enabled_tools: None,
             disabled_tools: None,
+            tool_renames: None,
+            tools: None,
+            tool_cache_ttl_sec: None,
             scopes: None,
+            max_concurrent_calls: None,
         });
     }
 
@@ -409,7 +413,11 @@ fn mcp_dependency_to_server_config(
             tool_timeout_sec: None,
             enabled_tools: None,
             disabled_tools: None,
+            tool_renames: None,
+            tools: None,
+            tool_cache_ttl_sec: None,
             scopes: None,
+            max_concurrent_calls: None,
         });
     }
 
@@ -465,7 +473,11 @@ mod tests {
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
         )]);
 
@@ -513,7 +525,11 @@ mod tests {
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
         )]);
 
@@ -0,0 +1,353 @@
+//! Checks the arguments the model wrote for an MCP tool against the tool's input schema before
+//! they are sent, so that mistakes come back to the model as a precise error instead of an
+//! opaque failure from the server.
+//!
+//! Only the parts of JSON Schema that describe the shape of the arguments are checked (`type`,
+//! `properties`, `required`, `additionalProperties`, `items`, `enum` and `const`); other keywords
+//! are ignored, so a schema this does not understand never rejects a call. A few obvious
+//! mismatches are fixed instead of reported: numbers and booleans written as strings, objects and
+//! arrays written as JSON strings, scalars where a string is expected, and `null` for an optional
+//! property that does not accept it.
+
+use serde::Serialize;
+use serde_json::Map;
+use serde_json::Value;
+
+/// One way the arguments did not match the schema.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct ToolArgumentError {
+    /// Where the mismatch is, e.g. `arguments.files[0].path`.
+    pub(crate) path: String,
+    pub(crate) message: String,
+}
+
+/// Returns the arguments to send, with obvious mismatches fixed, or every mismatch that could
+/// not be fixed.
+pub(crate) fn check_tool_arguments(
+    input_schema: &Value,
+    arguments: Option<Value>,
+) -> Result<Option<Value>, Vec<ToolArgumentError>> {
+    let mut errors = Vec::new();
+    let arguments = match arguments {
+        Some(mut arguments) => {
+            check_value(input_schema, &mut arguments, "arguments", &mut errors);
+            Some(arguments)
+        }
+        None => {
+            // Leave the arguments out as the model did, unless the schema requires some.
+            check_value(
+                input_schema,
+                &mut Value::Object(Map::new()),
+                "arguments",
+                &mut errors,
+            );
+            None
+        }
+    };
+    if errors.is_empty() {
+        Ok(arguments)
+    } else {
+        errors.sort_by(|a, b| a.path.cmp(&b.path));
+        Err(errors)
+    }
+}
+
+fn check_value(schema: &Value, value: &mut Value, path: &str, errors: &mut Vec<ToolArgumentError>) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+
+    let types = schema_types(schema);
+    if !types.is_empty() && !types.iter().any(|ty| matches_type(value, ty)) {
+        match coerce(value, &types) {
+            Some(coerced) => *value = coerced,
+            None => {
+                errors.push(ToolArgumentError {
+                    path: path.to_string(),
+                    message: format!("expected {}, got {}", types.join(" or "), describe(value)),
+                });
+                return;
+            }
+        }
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum")
+        && !allowed.contains(value)
+    {
+        let allowed = allowed
+            .iter()
+            .map(Value::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        errors.push(ToolArgumentError {
+            path: path.to_string(),
+            message: format!("expected one of {allowed}, got {value}"),
+        });
+        return;
+    }
+    if let Some(expected) = schema.get("const")
+        && expected != value
+    {
+        errors.push(ToolArgumentError {
+            path: path.to_string(),
+            message: format!("expected {expected}, got {value}"),
+        });
+        return;
+    }
+
+    match value {
+        Value::Object(object) => check_object(schema, object, path, errors),
+        Value::Array(values) => {
+            if let Some(items) = schema.get("items").filter(|items| items.is_object()) {
+                for (idx, item) in values.iter_mut().enumerate() {
+                    check_value(items, item, &format!("{path}[{idx}]"), errors);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn check_object(
+    schema: &Map<String, Value>,
+    object: &mut Map<String, Value>,
+    path: &str,
+    errors: &mut Vec<ToolArgumentError>,
+) {
+    let properties = schema.get("properties").and_then(Value::as_object);
+    let required = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|required| {
+            required
+                .iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    if let Some(properties) = properties {
+        for (name, property_schema) in properties {
+            let Some(property) = object.get_mut(name) else {
+                continue;
+            };
+            let accepts_null = property_schema.as_object().is_none_or(|property_schema| {
+                let types = schema_types(property_schema);
+                types.is_empty() || types.contains(&"null")
+            });
+            if property.is_null() && !accepts_null && !required.contains(&name.as_str()) {
+                object.remove(name);
+                continue;
+            }
+            check_value(property_schema, property, &format!("{path}.{name}"), errors);
+        }
+    }
+
+    for name in required {
+        if !object.contains_key(name) {
+            errors.push(ToolArgumentError {
+                path: format!("{path}.{name}"),
+                message: "missing required property".to_string(),
+            });
+        }
+    }
+
+    let is_declared =
+        |name: &str| properties.is_some_and(|properties| properties.contains_key(name));
+    match schema.get("additionalProperties") {
+        Some(Value::Bool(false)) => {
+            for name in object.keys().filter(|name| !is_declared(name)) {
+                errors.push(ToolArgumentError {
+                    path: format!("{path}.{name}"),
+                    message: "unexpected property".to_string(),
+                });
+            }
+        }
+        Some(additional @ Value::Object(_)) => {
+            for (name, property) in object.iter_mut() {
+                if !is_declared(name) {
+                    check_value(additional, property, &format!("{path}.{name}"), errors);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The types a schema allows, from `type` given as a string or a list. Empty when any type is
+/// allowed.
+fn schema_types(schema: &Map<String, Value>) -> Vec<&str> {
+    match schema.get("type") {
+        Some(Value::String(ty)) => vec![ty.as_str()],
+        Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn matches_type(value: &Value, ty: &str) -> bool {
+    match ty {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+        // A type this does not know about is not held against the arguments.
+        _ => true,
+    }
+}
+
+/// Converts `value` to the first of `types` it obviously stands for.
+fn coerce(value: &Value, types: &[&str]) -> Option<Value> {
+    types.iter().find_map(|ty| {
+        let coerced = match (*ty, value) {
+            ("integer" | "number", Value::String(text)) => {
+                serde_json::from_str::<serde_json::Number>(text.trim())
+                    .ok()
+                    .map(Value::Number)
+            }
+            ("boolean", Value::String(text)) => match text.trim() {
+                "true" => Some(Value::Bool(true)),
+                "false" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            ("object" | "array", Value::String(text)) => serde_json::from_str(text).ok(),
+            ("string", Value::Number(_) | Value::Bool(_)) => Some(Value::String(value.to_string())),
+            _ => None,
+        };
+        coerced.filter(|coerced| matches_type(coerced, ty))
+    })
+}
+
+fn describe(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(_) | Value::Number(_) => format!("{value}"),
+        Value::String(_) => format!("string {value}"),
+        Value::Array(_) => "array".to_string(),
+        Value::Object(_) => "object".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn search_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": { "type": "string" },
+                "limit": { "type": "integer" },
+                "exact": { "type": "boolean" },
+                "filters": {
+                    "type": "object",
+                    "properties": { "paths": { "type": "array", "items": { "type": "string" } } },
+                    "additionalProperties": false
+                },
+                "order": { "type": "string", "enum": ["relevance", "date"] }
+            },
+            "required": ["query"]
+        })
+    }
+
+    #[test]
+    fn matching_arguments_are_sent_unchanged() {
+        let arguments = json!({ "query": "mcp", "limit": 5, "filters": { "paths": ["src"] } });
+
+        assert_eq!(
+            check_tool_arguments(&search_schema(), Some(arguments.clone())),
+            Ok(Some(arguments))
+        );
+    }
+
+    #[test]
+    fn obvious_mismatches_are_coerced() {
+        let arguments = json!({
+            "query": 42,
+            "limit": "10",
+            "exact": "true",
+            "filters": "{\"paths\": [\"src\"]}",
+            "order": null
+        });
+
+        assert_eq!(
+            check_tool_arguments(&search_schema(), Some(arguments)),
+            Ok(Some(json!({
+                "query": "42",
+                "limit": 10,
+                "exact": true,
+                "filters": { "paths": ["src"] }
+            })))
+        );
+    }
+
+    #[test]
+    fn mismatches_that_cannot_be_fixed_are_all_reported() {
+        let arguments = json!({
+            "limit": "ten",
+            "filters": { "paths": ["src", ["lib"]], "depth": 2 },
+            "order": "name"
+        });
+
+        assert_eq!(
+            check_tool_arguments(&search_schema(), Some(arguments)),
+            Err(vec![
+                ToolArgumentError {
+                    path: "arguments.filters.depth".to_string(),
+                    message: "unexpected property".to_string(),
+                },
+                ToolArgumentError {
+                    path: "arguments.filters.paths[1]".to_string(),
+                    message: "expected string, got array".to_string(),
+                },
+                ToolArgumentError {
+                    path: "arguments.limit".to_string(),
+                    message: "expected integer, got string \"ten\"".to_string(),
+                },
+                ToolArgumentError {
+                    path: "arguments.order".to_string(),
+                    message: "expected one of \"relevance\", \"date\", got \"name\"".to_string(),
+                },
+                ToolArgumentError {
+                    path: "arguments.query".to_string(),
+                    message: "missing required property".to_string(),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn missing_arguments_are_only_rejected_when_properties_are_required() {
+        assert_eq!(
+            check_tool_arguments(&json!({ "type": "object", "properties": {} }), None),
+            Ok(None)
+        );
+        assert_eq!(
+            check_tool_arguments(&search_schema(), None),
+            Err(vec![ToolArgumentError {
+                path: "arguments.query".to_string(),
+                message: "missing required property".to_string(),
+            }])
+        );
+    }
+
+    #[test]
+    fn unknown_keywords_do_not_reject_arguments() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "target": { "anyOf": [{ "type": "string" }, { "type": "integer" }] } }
+        });
+        let arguments = json!({ "target": [1, 2] });
+
+        assert_eq!(
+            check_tool_arguments(&schema, Some(arguments.clone())),
+            Ok(Some(arguments))
+        );
+    }
+}
@@ -14,49 +14,74 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::LazyLock;
 use std::sync::Mutex as StdMutex;
+use std::sync::Weak;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 use std::time::Instant;
 
 use crate::mcp::CODEX_APPS_MCP_SERVER_NAME;
+use crate::mcp::McpCallLimiter;
+use crate::mcp::McpCallSlot;
 use crate::mcp::auth::McpAuthStatusEntry;
+use crate::mcp::resolve_mcp_env;
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::anyhow;
 use async_channel::Sender;
 use codex_async_utils::CancelErr;
 use codex_async_utils::OrCancelExt;
+use codex_keyring_store::DefaultKeyringStore;
 use codex_protocol::approvals::ElicitationRequestEvent;
 use codex_protocol::mcp::CallToolResult;
 use codex_protocol::mcp::RequestId as ProtocolRequestId;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::McpListChangedEvent;
+use codex_protocol::protocol::McpListKind;
+use codex_protocol::protocol::McpServerHealthEvent;
+use codex_protocol::protocol::McpServerHealthStatus;
 use codex_protocol::protocol::McpStartupCompleteEvent;
 use codex_protocol::protocol::McpStartupFailure;
 use codex_protocol::protocol::McpStartupStatus;
 use codex_protocol::protocol::McpStartupUpdateEvent;
 use codex_protocol::protocol::SandboxPolicy;
+use codex_protocol::protocol::WarningEvent;
 use codex_rmcp_client::ElicitationResponse;
+use codex_rmcp_client::McpRoots;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
+use codex_rmcp_client::OnListChanged;
 use codex_rmcp_client::RmcpClient;
+use codex_rmcp_client::Sampling;
+use codex_rmcp_client::SamplingResponse;
 use codex_rmcp_client::SendElicitation;
+use codex_rmcp_client::SendSampling;
+use codex_rmcp_client::ServerLog;
+use codex_rmcp_client::TransportError;
 use futures::future::BoxFuture;
 use futures::future::FutureExt;
 use futures::future::Shared;
+use futures::future::join_all;
 use rmcp::model::ClientCapabilities;
 use rmcp::model::CreateElicitationRequestParams;
 use rmcp::model::ElicitationCapability;
 use rmcp::model::FormElicitationCapability;
+use rmcp::model::GetPromptRequestParams;
+use rmcp::model::GetPromptResult;
 use rmcp::model::Implementation;
 use rmcp::model::InitializeRequestParams;
 use rmcp::model::ListResourceTemplatesResult;
 use rmcp::model::ListResourcesResult;
 use rmcp::model::PaginatedRequestParams;
+use rmcp::model::Prompt;
 use rmcp::model::ProtocolVersion;
 use rmcp::model::ReadResourceRequestParams;
 use rmcp::model::ReadResourceResult;
 use rmcp::model::RequestId;
 use rmcp::model::Resource;
 use rmcp::model::ResourceTemplate;
+use rmcp::model::RootsCapabilities;
 use rmcp::model::Tool;
 
 use serde::Deserialize;
@@ -67,12 +92,17 @@ use tokio::sync::Mutex;
 use tokio::sync::oneshot;
 use tokio::task::JoinSet;
 use tokio_util::sync::CancellationToken;
+use tokio_util::sync::DropGuard;
+use tracing::debug;
 use tracing::instrument;
 use tracing::warn;
+use wildmatch::WildMatchPattern;
 
 use crate::codex::INITIAL_SUBMIT_ID;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerTransportConfig;
+use crate::util::backoff;
+use crate::util::backoff_from;
 
 /// Delimiter used to separate the server name from the tool name in a fully
 /// qualified tool name.
@@ -88,6 +118,23 @@ pub const DEFAULT_STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
 /// Default timeout for individual tool calls.
 const DEFAULT_TOOL_TIMEOUT: Duration = Duration::from_secs(60);
 
+/// How long the first turn waits for servers that are still starting before it runs without their
+/// tools. Servers with a longer `startup_timeout_sec` keep starting in the background and their
+/// tools are offered to the model once they are ready.
+const MCP_TURN_STARTUP_WAIT: Duration = DEFAULT_STARTUP_TIMEOUT;
+
+/// How often running stdio servers are pinged.
+const MCP_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long a server may take to answer a ping before it is restarted.
+const MCP_PING_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Restart attempts after a health check fails, before the server is given up on.
+const MCP_MAX_RESTART_ATTEMPTS: u32 = 5;
+
+/// Delay before the first restart attempt; later attempts back off exponentially.
+const MCP_RESTART_INITIAL_DELAY: Duration = Duration::from_secs(1);
+
 const CODEX_APPS_TOOLS_CACHE_TTL: Duration = Duration::from_secs(3600);
 const MCP_TOOLS_LIST_DURATION_METRIC: &str = "codex.mcp.tools.list.duration_ms";
 const MCP_TOOLS_FETCH_UNCACHED_DURATION_METRIC: &str = "codex.mcp.tools.fetch_uncached.duration_ms";
@@ -120,46 +167,102 @@ fn sha1_hex(s: &str) -> String {
     format!("{sha1:x}")
 }
 
-fn qualify_tools<I>(tools: I) -> HashMap<String, ToolInfo>
+/// Two tools whose fully-qualified names are the same.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ToolNameConflict {
+    qualified_name: String,
+    /// `<server>/<tool>` of the tool that kept `qualified_name`.
+    kept: String,
+    /// `<server>/<tool>` of the tool exposed as `renamed_to` instead.
+    renamed: String,
+    renamed_to: String,
+}
+
+impl ToolNameConflict {
+    fn message(&self) -> String {
+        format!(
+            "MCP tools {} and {} are both named `{}`; {} is exposed as `{}`. \
+             Set `tool_renames` in the MCP server config to choose another name.",
+            self.kept, self.renamed, self.qualified_name, self.renamed, self.renamed_to
+        )
+    }
+}
+
+#[derive(Default)]
+struct QualifiedTools {
+    tools: HashMap<String, ToolInfo>,
+    conflicts: Vec<ToolNameConflict>,
+}
+
+/// Names every tool `mcp__<server>__<tool>`, where `<tool>` is the server's name for the tool
+/// unless `tool_renames` (keyed by server) maps it to another one.
+///
+/// Tools are named in order of server and tool name, so when two tools end up with the same
+/// name the same one keeps it on every call; the other gets a name derived from a hash of its
+/// own, and the collision is reported in [`QualifiedTools::conflicts`].
+fn qualify_tools<I>(
+    tools: I,
+    tool_renames: &HashMap<String, HashMap<String, String>>,
+) -> QualifiedTools
 where
     I: IntoIterator<Item = ToolInfo>,
 {
-    let mut used_names = HashSet::new();
-    let mut seen_raw_names = HashSet::new();
-    let mut qualified_tools = HashMap::new();
+    let mut tools = tools.into_iter().collect::<Vec<_>>();
+    tools.sort_by(|a, b| (&a.server_name, &a.tool_name).cmp(&(&b.server_name, &b.tool_name)));
+
+    let mut seen_tools = HashSet::new();
+    let mut owners: HashMap<String, String> = HashMap::new();
+    let mut qualified = QualifiedTools::default();
     for tool in tools {
+        let tool_label = format!("{}/{}", tool.server_name, tool.tool_name);
+        if !seen_tools.insert(tool_label.clone()) {
+            warn!("skipping duplicated tool {tool_label}");
+            continue;
+        }
+        let exposed_name = tool_renames
+            .get(&tool.server_name)
+            .and_then(|renames| renames.get(&tool.tool_name))
+            .unwrap_or(&tool.tool_name);
         let qualified_name_raw = format!(
             "mcp{}{}{}{}",
-            MCP_TOOL_NAME_DELIMITER, tool.server_name, MCP_TOOL_NAME_DELIMITER, tool.tool_name
+            MCP_TOOL_NAME_DELIMITER, tool.server_name, MCP_TOOL_NAME_DELIMITER, exposed_name
         );
-        if !seen_raw_names.insert(qualified_name_raw.clone()) {
-            warn!("skipping duplicated tool {}", qualified_name_raw);
-            continue;
-        }
 
-        // Start from a "pretty" name (sanitized), then deterministically disambiguate on
-        // collisions by appending a hash of the *raw* (unsanitized) qualified name. This
-        // ensures tools like `foo.bar` and `foo_bar` don't collapse to the same key.
+        // Start from a "pretty" name (sanitized); collisions, such as `foo.bar` and `foo_bar`,
+        // are disambiguated below.
         let mut qualified_name = sanitize_responses_api_tool_name(&qualified_name_raw);
 
         // Enforce length constraints early; use the raw name for the hash input so the
         // output remains stable even when sanitization changes.
         if qualified_name.len() > MAX_TOOL_NAME_LENGTH {
-            let sha1_str = sha1_hex(&qualified_name_raw);
-            let prefix_len = MAX_TOOL_NAME_LENGTH - sha1_str.len();
-            qualified_name = format!("{}{}", &qualified_name[..prefix_len], sha1_str);
+            qualified_name = with_sha1_suffix(&qualified_name, &qualified_name_raw);
         }
 
-        if used_names.contains(&qualified_name) {
-            warn!("skipping duplicated tool {}", qualified_name);
-            continue;
+        if let Some(owner) = owners.get(&qualified_name) {
+            let renamed_to = with_sha1_suffix(&qualified_name, &tool_label);
+            let conflict = ToolNameConflict {
+                qualified_name,
+                kept: owner.clone(),
+                renamed: tool_label.clone(),
+                renamed_to: renamed_to.clone(),
+            };
+            qualified.conflicts.push(conflict);
+            qualified_name = renamed_to;
         }
 
-        used_names.insert(qualified_name.clone());
-        qualified_tools.insert(qualified_name, tool);
+        owners.insert(qualified_name.clone(), tool_label);
+        qualified.tools.insert(qualified_name, tool);
     }
 
-    qualified_tools
+    qualified
+}
+
+/// Replaces the end of `name` with a hash of `hash_input`, keeping it within
+/// [`MAX_TOOL_NAME_LENGTH`].
+fn with_sha1_suffix(name: &str, hash_input: &str) -> String {
+    let sha1_str = sha1_hex(hash_input);
+    let prefix_len = name.len().min(MAX_TOOL_NAME_LENGTH - sha1_str.len());
+    format!("{}{}", &name[..prefix_len], sha1_str)
 }
 
 #[derive(Clone)]
@@ -215,6 +318,16 @@ impl ElicitationRequestManager {
                     let mut lock = elicitation_requests.lock().await;
                     lock.insert((server_name.clone(), id.clone()), tx);
                 }
+                let (message, requested_schema) = match elicitation {
+                    CreateElicitationRequestParams::FormElicitationParams {
+                        message,
+                        requested_schema,
+                        ..
+                    } => (message, serde_json::to_value(requested_schema).ok()),
+                    CreateElicitationRequestParams::UrlElicitationParams { message, .. } => {
+                        (message, None)
+                    }
+                };
                 let _ = tx_event
                     .send(Event {
                         id: "mcp_elicitation_request".to_string(),
@@ -228,16 +341,8 @@ impl ElicitationRequestManager {
                                     ProtocolRequestId::Integer(value)
                                 }
                             },
-                            message: match elicitation {
-                                CreateElicitationRequestParams::FormElicitationParams {
-                                    message,
-                                    ..
-                                }
-                                | CreateElicitationRequestParams::UrlElicitationParams {
-                                    message,
-                                    ..
-                                } => message,
-                            },
+                            message,
+                            requested_schema,
                         }),
                     })
                     .await;
@@ -249,18 +354,142 @@ impl ElicitationRequestManager {
     }
 }
 
+/// A `sampling/createMessage` request from an MCP server, answered by the session with its model.
+pub(crate) struct McpSamplingRequest {
+    pub(crate) server_name: String,
+    pub(crate) params: Sampling,
+    pub(crate) responder: oneshot::Sender<Result<SamplingResponse>>,
+}
+
+fn make_sampling_sender(
+    server_name: String,
+    sampling_requests: Sender<McpSamplingRequest>,
+) -> SendSampling {
+    Box::new(move |_id, params| {
+        let sampling_requests = sampling_requests.clone();
+        let server_name = server_name.clone();
+        async move {
+            let (responder, rx) = oneshot::channel();
+            sampling_requests
+                .send(McpSamplingRequest {
+                    server_name,
+                    params,
+                    responder,
+                })
+                .await
+                .map_err(|_| anyhow!("the session no longer accepts sampling requests"))?;
+            rx.await
+                .context("sampling request was dropped before it was answered")?
+        }
+        .boxed()
+    })
+}
+
+/// Lists the tools of a server again when it announces they changed, then tells the client
+/// which list changed. Prompts and resources are not kept, so they are only announced.
+fn make_list_changed_handler(
+    server_name: String,
+    client: Weak<RmcpClient>,
+    tools: Arc<StdMutex<Vec<ToolInfo>>>,
+    timeout: Option<Duration>,
+    tx_event: Sender<Event>,
+) -> OnListChanged {
+    Box::new(move |list| {
+        let server_name = server_name.clone();
+        let client = client.clone();
+        let tools = Arc::clone(&tools);
+        let tx_event = tx_event.clone();
+        tokio::spawn(async move {
+            if list == McpListKind::Tools {
+                // The client owns this handler, so it is only gone once the server was stopped.
+                let Some(client) = client.upgrade() else {
+                    return;
+                };
+                match list_tools_for_client_uncached(&server_name, &client, timeout).await {
+                    Ok(listed_tools) => {
+                        if server_name == CODEX_APPS_MCP_SERVER_NAME {
+                            write_cached_codex_apps_tools(&listed_tools);
+                        }
+                        *tools
+                            .lock()
+                            .unwrap_or_else(std::sync::PoisonError::into_inner) = listed_tools;
+                    }
+                    Err(err) => {
+                        warn!(
+                            "Failed to list the changed tools of MCP server `{server_name}`: {err:#}"
+                        );
+                        return;
+                    }
+                }
+            }
+            let _ = tx_event
+                .send(Event {
+                    id: INITIAL_SUBMIT_ID.to_owned(),
+                    msg: EventMsg::McpListChanged(McpListChangedEvent {
+                        server: server_name,
+                        list,
+                    }),
+                })
+                .await;
+        });
+    })
+}
+
+/// Counts the tool calls running against a server. Health checks are skipped while a call runs,
+/// since a busy server may not answer pings in time.
+#[derive(Clone, Default)]
+struct ActiveCalls(Arc<AtomicUsize>);
+
+impl ActiveCalls {
+    fn start(&self) -> ActiveCallGuard {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        ActiveCallGuard(Arc::clone(&self.0))
+    }
+
+    fn any(&self) -> bool {
+        self.0.load(Ordering::SeqCst) > 0
+    }
+}
+
+struct ActiveCallGuard(Arc<AtomicUsize>);
+
+impl Drop for ActiveCallGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
 #[derive(Clone)]
 struct ManagedClient {
     client: Arc<RmcpClient>,
-    tools: Vec<ToolInfo>,
+    /// Listed again when the server sends `notifications/tools/list_changed`.
+    tools: Arc<StdMutex<Vec<ToolInfo>>>,
     tool_filter: ToolFilter,
+    /// Names to expose tools under, from the server's `tool_renames` config.
+    tool_renames: HashMap<String, String>,
     tool_timeout: Option<Duration>,
     server_supports_sandbox_state_capability: bool,
+    server_supports_prompts: bool,
+    active_calls: ActiveCalls,
 }
 
 impl ManagedClient {
+    fn tools(&self) -> Vec<ToolInfo> {
+        self.tools
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+
     /// Returns once the server has ack'd the sandbox state update.
-    async fn notify_sandbox_state_change(&self, sandbox_state: &SandboxState) -> Result<()> {
+    async fn notify_sandbox_state_change(
+        &self,
+        sandbox_state: &SandboxState,
+        roots_changed: bool,
+    ) -> Result<()> {
+        if roots_changed {
+            self.client.notify_roots_list_changed().await?;
+        }
         if !self.server_supports_sandbox_state_capability {
             return Ok(());
         }
@@ -276,12 +505,17 @@ impl ManagedClient {
     }
 }
 
+type ManagedClientFuture = Shared<BoxFuture<'static, Result<ManagedClient, StartupOutcomeError>>>;
+
 #[derive(Clone)]
 struct AsyncManagedClient {
-    client: Shared<BoxFuture<'static, Result<ManagedClient, StartupOutcomeError>>>,
+    /// Replaced by a new startup future when the server is restarted, so callers wait for the
+    /// restarted server instead of failing against the old one.
+    client: Arc<StdMutex<ManagedClientFuture>>,
 }
 
 impl AsyncManagedClient {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         server_name: String,
         config: McpServerConfig,
@@ -289,43 +523,220 @@ impl AsyncManagedClient {
         cancel_token: CancellationToken,
         tx_event: Sender<Event>,
         elicitation_requests: ElicitationRequestManager,
+        sampling_requests: Option<Sender<McpSamplingRequest>>,
+        log: ServerLog,
+        roots: McpRoots,
     ) -> Self {
-        let tool_filter = ToolFilter::from_config(&config);
-        let fut = async move {
-            if let Err(error) = validate_mcp_server_name(&server_name) {
-                return Err(error.into());
-            }
+        let client = start_managed_client(
+            server_name,
+            config,
+            store_mode,
+            cancel_token,
+            tx_event,
+            elicitation_requests,
+            sampling_requests,
+            log,
+            roots,
+        );
+        Self {
+            client: Arc::new(StdMutex::new(client)),
+        }
+    }
 
-            let client =
-                Arc::new(make_rmcp_client(&server_name, config.transport, store_mode).await?);
-            match start_server_task(
-                server_name,
-                client,
-                config.startup_timeout_sec.or(Some(DEFAULT_STARTUP_TIMEOUT)),
-                config.tool_timeout_sec.unwrap_or(DEFAULT_TOOL_TIMEOUT),
-                tool_filter,
-                tx_event,
-                elicitation_requests,
-            )
-            .or_cancel(&cancel_token)
+    async fn client(&self) -> Result<ManagedClient, StartupOutcomeError> {
+        let client = self
+            .client
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone();
+        client.await
+    }
+
+    fn replace(&self, client: ManagedClientFuture) {
+        *self
+            .client
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = client;
+    }
+
+    async fn notify_sandbox_state_change(
+        &self,
+        sandbox_state: &SandboxState,
+        roots_changed: bool,
+    ) -> Result<()> {
+        let managed = self.client().await?;
+        managed
+            .notify_sandbox_state_change(sandbox_state, roots_changed)
             .await
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn start_managed_client(
+    server_name: String,
+    config: McpServerConfig,
+    store_mode: OAuthCredentialsStoreMode,
+    cancel_token: CancellationToken,
+    tx_event: Sender<Event>,
+    elicitation_requests: ElicitationRequestManager,
+    sampling_requests: Option<Sender<McpSamplingRequest>>,
+    log: ServerLog,
+    roots: McpRoots,
+) -> ManagedClientFuture {
+    let tool_filter = ToolFilter::from_config(&config);
+    let tool_renames = config.tool_renames.clone().unwrap_or_default();
+    let fut = async move {
+        if let Err(error) = validate_mcp_server_name(&server_name) {
+            return Err(error.into());
+        }
+
+        let client =
+            Arc::new(make_rmcp_client(&server_name, config.transport, store_mode, log).await?);
+        match start_server_task(
+            server_name,
+            client,
+            config.startup_timeout_sec.or(Some(DEFAULT_STARTUP_TIMEOUT)),
+            config.tool_timeout_sec.unwrap_or(DEFAULT_TOOL_TIMEOUT),
+            tool_filter,
+            tool_renames,
+            tx_event,
+            elicitation_requests,
+            sampling_requests,
+            roots,
+        )
+        .or_cancel(&cancel_token)
+        .await
+        {
+            Ok(result) => result,
+            Err(CancelErr::Cancelled) => Err(StartupOutcomeError::Cancelled),
+        }
+    };
+    fut.boxed().shared()
+}
+
+/// Watches a stdio server after startup. The server is pinged every
+/// [`MCP_HEALTH_CHECK_INTERVAL`]; when its process has exited or a ping times out, it is
+/// restarted with backoff and its tools are listed again.
+struct McpServerSupervisor {
+    server_name: String,
+    config: McpServerConfig,
+    store_mode: OAuthCredentialsStoreMode,
+    managed_client: AsyncManagedClient,
+    tx_event: Sender<Event>,
+    elicitation_requests: ElicitationRequestManager,
+    sampling_requests: Option<Sender<McpSamplingRequest>>,
+    sandbox_state: Arc<StdMutex<Option<SandboxState>>>,
+    log: ServerLog,
+    roots: McpRoots,
+}
+
+impl McpServerSupervisor {
+    async fn run(self, cancel_token: CancellationToken) {
+        loop {
+            if tokio::time::sleep(MCP_HEALTH_CHECK_INTERVAL)
+                .or_cancel(&cancel_token)
+                .await
+                .is_err()
             {
-                Ok(result) => result,
-                Err(CancelErr::Cancelled) => Err(StartupOutcomeError::Cancelled),
+                return;
+            }
+            // Servers that failed to start, or could not be restarted, are not supervised.
+            let Ok(managed) = self.managed_client.client().await else {
+                return;
+            };
+            if managed.active_calls.any() {
+                continue;
+            }
+            let Err(error) = managed.client.ping(Some(MCP_PING_TIMEOUT)).await else {
+                continue;
+            };
+            drop(managed);
+            // A server that answers with an error, e.g. because it does not implement `ping`,
+            // is still running.
+            if !error.is::<TransportError>() {
+                debug!(
+                    "MCP server `{}` rejected a health check ping: {error:#}",
+                    self.server_name
+                );
+                continue;
+            }
+            warn!(
+                "MCP server `{}` failed a health check: {error:#}",
+                self.server_name
+            );
+            if !self.restart(format!("{error:#}"), &cancel_token).await {
+                return;
             }
-        };
-        Self {
-            client: fut.boxed().shared(),
         }
     }
 
-    async fn client(&self) -> Result<ManagedClient, StartupOutcomeError> {
-        self.client.clone().await
+    /// Returns whether the server is running again.
+    async fn restart(&self, mut error: String, cancel_token: &CancellationToken) -> bool {
+        for attempt in 1..=MCP_MAX_RESTART_ATTEMPTS {
+            self.emit(McpServerHealthStatus::Restarting {
+                attempt,
+                error: error.clone(),
+            })
+            .await;
+            let delay = backoff_from(MCP_RESTART_INITIAL_DELAY, u64::from(attempt));
+            if tokio::time::sleep(delay)
+                .or_cancel(cancel_token)
+                .await
+                .is_err()
+            {
+                return false;
+            }
+
+            self.managed_client.replace(start_managed_client(
+                self.server_name.clone(),
+                self.config.clone(),
+                self.store_mode,
+                cancel_token.child_token(),
+                self.tx_event.clone(),
+                self.elicitation_requests.clone(),
+                self.sampling_requests.clone(),
+                self.log.clone(),
+                self.roots.clone(),
+            ));
+            match self.managed_client.client().await {
+                Ok(managed) => {
+                    let sandbox_state = self
+                        .sandbox_state
+                        .lock()
+                        .unwrap_or_else(std::sync::PoisonError::into_inner)
+                        .clone();
+                    if let Some(sandbox_state) = sandbox_state
+                        && let Err(e) = managed
+                            .notify_sandbox_state_change(&sandbox_state, false)
+                            .await
+                    {
+                        warn!(
+                            "Failed to notify sandbox state to MCP server {}: {e:#}",
+                            self.server_name
+                        );
+                    }
+                    self.emit(McpServerHealthStatus::Restarted).await;
+                    return true;
+                }
+                Err(StartupOutcomeError::Cancelled) => return false,
+                Err(err) => error = startup_outcome_error_message(err),
+            }
+        }
+        self.emit(McpServerHealthStatus::Failed { error }).await;
+        false
     }
 
-    async fn notify_sandbox_state_change(&self, sandbox_state: &SandboxState) -> Result<()> {
-        let managed = self.client().await?;
-        managed.notify_sandbox_state_change(sandbox_state).await
+    async fn emit(&self, status: McpServerHealthStatus) {
+        let _ = self
+            .tx_event
+            .send(Event {
+                id: INITIAL_SUBMIT_ID.to_owned(),
+                msg: EventMsg::McpServerHealth(McpServerHealthEvent {
+                    server: self.server_name.clone(),
+                    status,
+                }),
+            })
+            .await;
     }
 }
 
@@ -349,7 +760,119 @@ pub struct SandboxState {
 #[derive(Default)]
 pub(crate) struct McpConnectionManager {
     clients: HashMap<String, AsyncManagedClient>,
+    /// Configuration each client was started with, and what stops it.
+    servers: HashMap<String, StartedServer>,
     elicitation_requests: ElicitationRequestManager,
+    /// Where sampling requests from servers are sent. Servers are only offered sampling when set.
+    sampling_requests: Option<Sender<McpSamplingRequest>>,
+    /// Latest sandbox state, sent again to servers that are restarted.
+    sandbox_state: Arc<StdMutex<Option<SandboxState>>>,
+    /// Results of tool calls reused for identical calls, see `tool_cache_ttl_sec`.
+    tool_results: StdMutex<ToolResultCache>,
+    /// What each server wrote to stderr, kept while the server stays configured so the output
+    /// of a run that crashed is still there after the server is restarted.
+    server_logs: HashMap<String, ServerLog>,
+    /// The session's working directory, offered to every server as its MCP roots.
+    roots: McpRoots,
+    /// Holds tool calls back while too many are in flight, see `mcp_max_concurrent_calls`.
+    call_limiter: Arc<McpCallLimiter>,
+    /// Set once a turn has waited for servers that are still starting.
+    startup_waited: AtomicBool,
+}
+
+struct StartedServer {
+    config: McpServerConfig,
+    startup_cancel_token: CancellationToken,
+    /// Stops the health checks when the server is removed or the manager is dropped.
+    _supervisor: Option<DropGuard>,
+}
+
+/// Most tool results kept at once; the one closest to expiring is dropped to make room.
+const MAX_CACHED_TOOL_RESULTS: usize = 256;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ToolCallKey {
+    server: String,
+    tool: String,
+    /// Arguments as JSON with object keys sorted.
+    arguments: String,
+}
+
+struct CachedToolResult {
+    expires_at: Instant,
+    result: CallToolResult,
+}
+
+#[derive(Default)]
+struct ToolResultCache {
+    entries: HashMap<ToolCallKey, CachedToolResult>,
+}
+
+impl ToolResultCache {
+    fn get(&mut self, key: &ToolCallKey, now: Instant) -> Option<CallToolResult> {
+        let cached = self.entries.get(key)?;
+        if cached.expires_at > now {
+            return Some(cached.result.clone());
+        }
+        self.entries.remove(key);
+        None
+    }
+
+    fn insert(&mut self, key: ToolCallKey, result: CallToolResult, ttl: Duration, now: Instant) {
+        self.entries.retain(|_, cached| cached.expires_at > now);
+        if self.entries.len() >= MAX_CACHED_TOOL_RESULTS
+            && let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, cached)| cached.expires_at)
+                .map(|(key, _)| key.clone())
+        {
+            self.entries.remove(&oldest);
+        }
+        self.entries.insert(
+            key,
+            CachedToolResult {
+                expires_at: now + ttl,
+                result,
+            },
+        );
+    }
+
+    fn remove_server(&mut self, server: &str) {
+        self.entries.retain(|key, _| key.server != server);
+    }
+}
+
+/// Serializes `value` with object keys sorted, so that arguments that only differ in key order
+/// share a cache entry.
+fn canonical_json(value: Option<&serde_json::Value>) -> String {
+    fn sorted(value: &serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::Object(map) => {
+                let mut entries = map.iter().collect::<Vec<_>>();
+                entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+                serde_json::Value::Object(
+                    entries
+                        .into_iter()
+                        .map(|(key, value)| (key.clone(), sorted(value)))
+                        .collect(),
+                )
+            }
+            serde_json::Value::Array(values) => {
+                serde_json::Value::Array(values.iter().map(sorted).collect())
+            }
+            value => value.clone(),
+        }
+    }
+    value.map(sorted).unwrap_or_default().to_string()
+}
+
+/// Servers changed by [`McpConnectionManager::reload`].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct McpReloadSummary {
+    pub(crate) started: Vec<String>,
+    pub(crate) restarted: Vec<String>,
+    pub(crate) stopped: Vec<String>,
 }
 
 impl McpConnectionManager {
@@ -357,6 +880,32 @@ impl McpConnectionManager {
         !self.clients.is_empty()
     }
 
+    /// Offers sampling to the servers started by the next [`Self::initialize`], forwarding their
+    /// requests to `sampling_requests`.
+    pub(crate) fn set_sampling_requests(&mut self, sampling_requests: Sender<McpSamplingRequest>) {
+        self.sampling_requests = Some(sampling_requests);
+    }
+
+    pub(crate) fn sampling_requests(&self) -> Option<Sender<McpSamplingRequest>> {
+        self.sampling_requests.clone()
+    }
+
+    /// Limits how many tool calls run at once across all servers. Unlimited when `None`.
+    pub(crate) fn set_max_concurrent_calls(&self, max_concurrent_calls: Option<usize>) {
+        self.call_limiter
+            .set_max_concurrent_calls(max_concurrent_calls);
+    }
+
+    /// Reserves room for a call to `server`'s tools, honoring both the overall limit and the
+    /// server's `max_concurrent_calls`. The call may run once the slot is no longer queued.
+    pub(crate) fn acquire_call_slot(&self, server: &str) -> McpCallSlot {
+        let server_limit = self
+            .servers
+            .get(server)
+            .and_then(|started| started.config.max_concurrent_calls);
+        self.call_limiter.acquire(server, server_limit)
+    }
+
     pub async fn initialize(
         &mut self,
         mcp_servers: &HashMap<String, McpServerConfig>,
@@ -369,11 +918,114 @@ impl McpConnectionManager {
         if cancel_token.is_cancelled() {
             return;
         }
-        let mut clients = HashMap::new();
+        self.roots
+            .set(vec![initial_sandbox_state.sandbox_cwd.clone()]);
+        self.sandbox_state = Arc::new(StdMutex::new(Some(initial_sandbox_state)));
+        let servers = mcp_servers
+            .iter()
+            .filter(|(_, cfg)| cfg.enabled)
+            .map(|(server_name, cfg)| (server_name.clone(), cfg.clone()))
+            .collect();
+        self.start_servers(servers, store_mode, auth_entries, tx_event, cancel_token)
+            .await;
+    }
+
+    /// Applies a new set of server configurations to the running servers. Servers that were
+    /// removed or disabled are stopped; new servers are started; servers whose configuration
+    /// changed are restarted. Servers with an unchanged configuration are left alone, whether they
+    /// are running, still starting, or failed.
+    pub(crate) async fn reload(
+        &mut self,
+        mcp_servers: &HashMap<String, McpServerConfig>,
+        store_mode: OAuthCredentialsStoreMode,
+        auth_entries: HashMap<String, McpAuthStatusEntry>,
+        tx_event: Sender<Event>,
+        cancel_token: CancellationToken,
+        sandbox_state: SandboxState,
+    ) -> McpReloadSummary {
+        let roots_changed = self.roots.set(vec![sandbox_state.sandbox_cwd.clone()]);
+        *self
+            .sandbox_state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(sandbox_state);
+
+        let mut summary = McpReloadSummary::default();
+        let mut to_start = Vec::new();
+        for (server_name, cfg) in mcp_servers.iter().filter(|(_, cfg)| cfg.enabled) {
+            match self.servers.get(server_name) {
+                Some(started) if started.config == *cfg => continue,
+                Some(_) => summary.restarted.push(server_name.clone()),
+                None => summary.started.push(server_name.clone()),
+            }
+            to_start.push((server_name.clone(), cfg.clone()));
+        }
+        let stopped = self
+            .servers
+            .keys()
+            .filter(|server_name| !mcp_servers.get(*server_name).is_some_and(|cfg| cfg.enabled))
+            .cloned()
+            .collect::<Vec<_>>();
+        for server_name in summary.restarted.iter().chain(&stopped) {
+            self.stop_server(server_name);
+        }
+        for server_name in &stopped {
+            self.server_logs.remove(server_name);
+        }
+        if roots_changed {
+            // Only the servers kept running are left; the others learn the roots when they start.
+            self.notify_roots_list_changed().await;
+        }
+        summary.stopped = stopped;
+        summary.started.sort();
+        summary.restarted.sort();
+        summary.stopped.sort();
+
+        if !cancel_token.is_cancelled() {
+            self.start_servers(to_start, store_mode, auth_entries, tx_event, cancel_token)
+                .await;
+        }
+        summary
+    }
+
+    async fn notify_roots_list_changed(&self) {
+        let mut join_set = JoinSet::new();
+        for async_managed_client in self.clients.values() {
+            let async_managed_client = async_managed_client.clone();
+            join_set.spawn(async move {
+                async_managed_client
+                    .client()
+                    .await?
+                    .client
+                    .notify_roots_list_changed()
+                    .await
+            });
+        }
+        while let Some(join_res) = join_set.join_next().await {
+            if let Ok(Err(err)) = join_res {
+                warn!("Failed to notify MCP server that its roots changed: {err:#}");
+            }
+        }
+    }
+
+    fn stop_server(&mut self, server_name: &str) {
+        self.lock_tool_results().remove_server(server_name);
+        self.clients.remove(server_name);
+        if let Some(started) = self.servers.remove(server_name) {
+            started.startup_cancel_token.cancel();
+        }
+    }
+
+    async fn start_servers(
+        &mut self,
+        servers: Vec<(String, McpServerConfig)>,
+        store_mode: OAuthCredentialsStoreMode,
+        auth_entries: HashMap<String, McpAuthStatusEntry>,
+        tx_event: Sender<Event>,
+        cancel_token: CancellationToken,
+    ) {
         let mut join_set = JoinSet::new();
-        let elicitation_requests = ElicitationRequestManager::default();
-        let mcp_servers = mcp_servers.clone();
-        for (server_name, cfg) in mcp_servers.into_iter().filter(|(_, cfg)| cfg.enabled) {
+        let elicitation_requests = self.elicitation_requests.clone();
+        for (server_name, cfg) in servers {
             let cancel_token = cancel_token.child_token();
             let _ = emit_update(
                 &tx_event,
@@ -383,18 +1035,59 @@ impl McpConnectionManager {
                 },
             )
             .await;
+            let log = self
+                .server_logs
+                .entry(server_name.clone())
+                .or_default()
+                .clone();
             let async_managed_client = AsyncManagedClient::new(
                 server_name.clone(),
-                cfg,
+                cfg.clone(),
                 store_mode,
                 cancel_token.clone(),
                 tx_event.clone(),
                 elicitation_requests.clone(),
+                self.sampling_requests.clone(),
+                log.clone(),
+                self.roots.clone(),
+            );
+            self.clients
+                .insert(server_name.clone(), async_managed_client.clone());
+            let supervisor_guard =
+                if matches!(cfg.transport, McpServerTransportConfig::Stdio { .. }) {
+                    let supervisor_token = CancellationToken::new();
+                    let supervisor = McpServerSupervisor {
+                        server_name: server_name.clone(),
+                        config: cfg.clone(),
+                        store_mode,
+                        managed_client: async_managed_client.clone(),
+                        tx_event: tx_event.clone(),
+                        elicitation_requests: elicitation_requests.clone(),
+                        sampling_requests: self.sampling_requests.clone(),
+                        sandbox_state: Arc::clone(&self.sandbox_state),
+                        log,
+                        roots: self.roots.clone(),
+                    };
+                    tokio::spawn(supervisor.run(supervisor_token.clone()));
+                    Some(supervisor_token.drop_guard())
+                } else {
+                    None
+                };
+            self.servers.insert(
+                server_name.clone(),
+                StartedServer {
+                    config: cfg,
+                    startup_cancel_token: cancel_token.clone(),
+                    _supervisor: supervisor_guard,
+                },
             );
-            clients.insert(server_name.clone(), async_managed_client.clone());
             let tx_event = tx_event.clone();
             let auth_entry = auth_entries.get(&server_name).cloned();
-            let sandbox_state = initial_sandbox_state.clone();
+            let sandbox_state = self
+                .sandbox_state
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .clone();
             join_set.spawn(async move {
                 let outcome = async_managed_client.client().await;
                 if cancel_token.is_cancelled() {
@@ -403,9 +1096,10 @@ impl McpConnectionManager {
                 let status = match &outcome {
                     Ok(_) => {
                         // Send sandbox state notification immediately after Ready
-                        if let Err(e) = async_managed_client
-                            .notify_sandbox_state_change(&sandbox_state)
-                            .await
+                        if let Some(sandbox_state) = sandbox_state
+                            && let Err(e) = async_managed_client
+                                .notify_sandbox_state_change(&sandbox_state, false)
+                                .await
                         {
                             warn!(
                                 "Failed to notify sandbox state to MCP server {server_name}: {e:#}",
@@ -435,14 +1129,21 @@ impl McpConnectionManager {
                 (server_name, outcome)
             });
         }
-        self.clients = clients;
-        self.elicitation_requests = elicitation_requests.clone();
         tokio::spawn(async move {
             let outcomes = join_set.join_all().await;
             let mut summary = McpStartupCompleteEvent::default();
+            let mut started_tools = Vec::new();
+            let mut tool_renames = HashMap::new();
             for (server_name, outcome) in outcomes {
                 match outcome {
-                    Ok(_) => summary.ready.push(server_name),
+                    Ok(managed_client) => {
+                        started_tools.extend(filter_tools(
+                            managed_client.tools(),
+                            managed_client.tool_filter,
+                        ));
+                        tool_renames.insert(server_name.clone(), managed_client.tool_renames);
+                        summary.ready.push(server_name);
+                    }
                     Err(StartupOutcomeError::Cancelled) => summary.cancelled.push(server_name),
                     Err(StartupOutcomeError::Failed { error }) => {
                         summary.failed.push(McpStartupFailure {
@@ -452,6 +1153,16 @@ impl McpConnectionManager {
                     }
                 }
             }
+            for conflict in qualify_tools(started_tools, &tool_renames).conflicts {
+                let _ = tx_event
+                    .send(Event {
+                        id: INITIAL_SUBMIT_ID.to_owned(),
+                        msg: EventMsg::Warning(WarningEvent {
+                            message: conflict.message(),
+                        }),
+                    })
+                    .await;
+            }
             let _ = tx_event
                 .send(Event {
                     id: INITIAL_SUBMIT_ID.to_owned(),
@@ -461,6 +1172,16 @@ impl McpConnectionManager {
         });
     }
 
+    /// The stderr lines captured for `server`, or for every configured server when `None`.
+    /// Servers without a stdio transport have nothing to capture and report no lines.
+    pub(crate) fn server_logs(&self, server: Option<&str>) -> HashMap<String, Vec<String>> {
+        self.server_logs
+            .iter()
+            .filter(|(server_name, _)| server.is_none_or(|server| server == server_name.as_str()))
+            .map(|(server_name, log)| (server_name.clone(), log.lines()))
+            .collect()
+    }
+
     async fn client_by_name(&self, name: &str) -> Result<ManagedClient> {
         self.clients
             .get(name)
@@ -521,14 +1242,52 @@ impl McpConnectionManager {
     /// fully-qualified name for the tool.
     #[instrument(level = "trace", skip_all)]
     pub async fn list_all_tools(&self) -> HashMap<String, ToolInfo> {
-        let mut tools = HashMap::new();
-        for (server_name, managed_client) in &self.clients {
-            let client = managed_client.client().await.ok();
-            if let Some(client) = client {
+        self.list_tools(None).await
+    }
+
+    /// Like [`Self::list_all_tools`], but servers that are still starting after `wait` are left
+    /// out instead of holding up the caller.
+    #[instrument(level = "trace", skip_all)]
+    pub(crate) async fn list_available_tools(&self, wait: Duration) -> HashMap<String, ToolInfo> {
+        self.list_tools(Some(wait)).await
+    }
+
+    /// Lists the tools to offer the model for a request. Only the first call waits for servers
+    /// that are still starting; later calls use the tools already available, and a slow server's
+    /// tools appear in the first request after it is ready.
+    pub(crate) async fn list_turn_tools(&self) -> HashMap<String, ToolInfo> {
+        self.list_available_tools(self.turn_startup_wait()).await
+    }
+
+    fn turn_startup_wait(&self) -> Duration {
+        if self.startup_waited.swap(true, Ordering::Relaxed) {
+            Duration::ZERO
+        } else {
+            MCP_TURN_STARTUP_WAIT
+        }
+    }
+
+    async fn list_tools(&self, wait: Option<Duration>) -> HashMap<String, ToolInfo> {
+        let server_tools = join_all(self.clients.iter().map(
+            |(server_name, managed_client)| async move {
+                let client = match wait {
+                    Some(wait) => match tokio::time::timeout(wait, managed_client.client()).await
+                    {
+                        Ok(client) => client,
+                        Err(_) => {
+                            debug!(
+                                "MCP server `{server_name}` is still starting; listing tools without it"
+                            );
+                            return None;
+                        }
+                    },
+                    None => managed_client.client().await,
+                };
+                let client = client.ok()?;
+                let mut server_tools = client.tools();
                 let rmcp_client = client.client;
                 let tool_timeout = client.tool_timeout;
                 let tool_filter = client.tool_filter;
-                let mut server_tools = client.tools;
 
                 if server_name == CODEX_APPS_MCP_SERVER_NAME {
                     match list_tools_for_client(server_name, &rmcp_client, tool_timeout).await {
@@ -543,10 +1302,22 @@ impl McpConnectionManager {
                     }
                 }
 
-                tools.extend(qualify_tools(filter_tools(server_tools, tool_filter)));
-            }
+                Some((
+                    server_name.clone(),
+                    filter_tools(server_tools, tool_filter),
+                    client.tool_renames,
+                ))
+            },
+        ))
+        .await;
+
+        let mut tools = Vec::new();
+        let mut tool_renames = HashMap::new();
+        for (server_name, server_tools, renames) in server_tools.into_iter().flatten() {
+            tools.extend(server_tools);
+            tool_renames.insert(server_name, renames);
         }
-        tools
+        qualify_tools(tools, &tool_renames).tools
     }
 
     /// Force-refresh codex apps tools by bypassing the in-process cache.
@@ -712,6 +1483,88 @@ impl McpConnectionManager {
         aggregated
     }
 
+    /// Returns the prompt templates of every server that advertises the prompts capability,
+    /// keyed by server name.
+    pub async fn list_all_prompts(&self) -> HashMap<String, Vec<Prompt>> {
+        let mut join_set = JoinSet::new();
+
+        for (server_name, async_managed_client) in &self.clients {
+            let server_name = server_name.clone();
+            let Ok(managed_client) = async_managed_client.client().await else {
+                continue;
+            };
+            if !managed_client.server_supports_prompts {
+                continue;
+            }
+            let client = managed_client.client.clone();
+            let timeout = managed_client.tool_timeout;
+
+            join_set.spawn(async move {
+                let mut collected: Vec<Prompt> = Vec::new();
+                let mut cursor: Option<String> = None;
+
+                loop {
+                    let params = cursor.as_ref().map(|next| PaginatedRequestParams {
+                        meta: None,
+                        cursor: Some(next.clone()),
+                    });
+                    let response = match client.list_prompts(params, timeout).await {
+                        Ok(result) => result,
+                        Err(err) => return (server_name, Err(err)),
+                    };
+
+                    collected.extend(response.prompts);
+
+                    match response.next_cursor {
+                        Some(next) => {
+                            if cursor.as_ref() == Some(&next) {
+                                return (
+                                    server_name,
+                                    Err(anyhow!("prompts/list returned duplicate cursor")),
+                                );
+                            }
+                            cursor = Some(next);
+                        }
+                        None => return (server_name, Ok(collected)),
+                    }
+                }
+            });
+        }
+
+        let mut aggregated: HashMap<String, Vec<Prompt>> = HashMap::new();
+
+        while let Some(join_res) = join_set.join_next().await {
+            match join_res {
+                Ok((server_name, Ok(prompts))) => {
+                    aggregated.insert(server_name, prompts);
+                }
+                Ok((server_name, Err(err))) => {
+                    warn!("Failed to list prompts for MCP server '{server_name}': {err:#}");
+                }
+                Err(err) => {
+                    warn!("Task panic when listing prompts for MCP server: {err:#}");
+                }
+            }
+        }
+
+        aggregated
+    }
+
+    /// The input schema `server` published for `tool`, or `None` when the server or the tool is
+    /// unknown.
+    pub(crate) async fn tool_input_schema(
+        &self,
+        server: &str,
+        tool: &str,
+    ) -> Option<serde_json::Value> {
+        let client = self.client_by_name(server).await.ok()?;
+        client
+            .tools()
+            .into_iter()
+            .find(|info| info.tool_name == tool)
+            .map(|info| serde_json::Value::Object(info.tool.input_schema.as_ref().clone()))
+    }
+
     /// Invoke the tool indicated by the (server, tool) pair.
     pub async fn call_tool(
         &self,
@@ -719,18 +1572,69 @@ impl McpConnectionManager {
         tool: &str,
         arguments: Option<serde_json::Value>,
     ) -> Result<CallToolResult> {
-        let client = self.client_by_name(server).await?;
-        if !client.tool_filter.allows(tool) {
+        let mut client = self.client_by_name(server).await?;
+        let read_only = client
+            .tools()
+            .iter()
+            .find(|info| info.tool_name == tool)
+            .is_some_and(|info| is_read_only_tool(&info.tool));
+        if !client.tool_filter.allows(tool, read_only) {
             return Err(anyhow!(
                 "tool '{tool}' is disabled for MCP server '{server}'"
             ));
         }
 
-        let result: rmcp::model::CallToolResult = client
-            .client
-            .call_tool(tool.to_string(), arguments, client.tool_timeout)
-            .await
-            .with_context(|| format!("tool call failed for `{server}/{tool}`"))?;
+        let server_config = self.servers.get(server).map(|started| &started.config);
+        let tool_config = server_config
+            .and_then(|config| config.tools.as_ref())
+            .and_then(|tools| tools.get(tool))
+            .cloned()
+            .unwrap_or_default();
+        let cache_ttl = tool_config
+            .cache_ttl_sec
+            .or_else(|| {
+                server_config
+                    .and_then(|config| config.tool_cache_ttl_sec)
+                    .filter(|_| read_only)
+            })
+            .filter(|ttl| !ttl.is_zero());
+        let cache_key = cache_ttl.map(|_| ToolCallKey {
+            server: server.to_string(),
+            tool: tool.to_string(),
+            arguments: canonical_json(arguments.as_ref()),
+        });
+        if let Some(cache_key) = &cache_key
+            && let Some(result) = self.lock_tool_results().get(cache_key, Instant::now())
+        {
+            return Ok(result);
+        }
+
+        let timeout = tool_config.timeout_sec.or(client.tool_timeout);
+        let retries = tool_config.retries.unwrap_or_default();
+        let mut attempt = 0;
+        let result: rmcp::model::CallToolResult = loop {
+            let active_call = client.active_calls.start();
+            let result = client
+                .client
+                .call_tool(tool.to_string(), arguments.clone(), timeout)
+                .await;
+            drop(active_call);
+            match result {
+                Err(err) if attempt < retries && err.is::<TransportError>() => {
+                    attempt += 1;
+                    warn!(
+                        "MCP tool call `{server}/{tool}` failed, retrying ({attempt}/{retries}): {err:#}"
+                    );
+                    tokio::time::sleep(backoff(u64::from(attempt))).await;
+                    // A server whose connection broke may have been restarted in the meantime.
+                    client = self.client_by_name(server).await?;
+                }
+                result => {
+                    break result
+                        .with_context(|| format!("tool call failed for `{server}/{tool}`"))?;
+                }
+            }
+        };
 
         let content = result
             .content
@@ -741,12 +1645,26 @@ impl McpConnectionManager {
             })
             .collect();
 
-        Ok(CallToolResult {
+        let result = CallToolResult {
             content,
             structured_content: result.structured_content,
             is_error: result.is_error,
             meta: result.meta.and_then(|meta| serde_json::to_value(meta).ok()),
-        })
+        };
+        // Errors are not cached so that the next identical call tries again.
+        if let (Some(cache_key), Some(cache_ttl)) = (cache_key, cache_ttl)
+            && result.is_error != Some(true)
+        {
+            self.lock_tool_results()
+                .insert(cache_key, result.clone(), cache_ttl, Instant::now());
+        }
+        Ok(result)
+    }
+
+    fn lock_tool_results(&self) -> std::sync::MutexGuard<'_, ToolResultCache> {
+        self.tool_results
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
     }
 
     /// List resources from the specified server.
@@ -798,6 +1716,23 @@ impl McpConnectionManager {
             .with_context(|| format!("resources/read failed for `{server}` ({uri})"))
     }
 
+    /// Expand a prompt template from the specified server.
+    pub async fn get_prompt(
+        &self,
+        server: &str,
+        params: GetPromptRequestParams,
+    ) -> Result<GetPromptResult> {
+        let managed = self.client_by_name(server).await?;
+        let client = managed.client.clone();
+        let timeout = managed.tool_timeout;
+        let name = params.name.clone();
+
+        client
+            .get_prompt(params, timeout)
+            .await
+            .with_context(|| format!("prompts/get failed for `{server}` ({name})"))
+    }
+
     pub async fn parse_tool_name(&self, tool_name: &str) -> Option<(String, String)> {
         self.list_all_tools()
             .await
@@ -806,6 +1741,11 @@ impl McpConnectionManager {
     }
 
     pub async fn notify_sandbox_state_change(&self, sandbox_state: &SandboxState) -> Result<()> {
+        *self
+            .sandbox_state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(sandbox_state.clone());
+        let roots_changed = self.roots.set(vec![sandbox_state.sandbox_cwd.clone()]);
         let mut join_set = JoinSet::new();
 
         for async_managed_client in self.clients.values() {
@@ -813,7 +1753,7 @@ impl McpConnectionManager {
             let async_managed_client = async_managed_client.clone();
             join_set.spawn(async move {
                 async_managed_client
-                    .notify_sandbox_state_change(&sandbox_state)
+                    .notify_sandbox_state_change(&sandbox_state, roots_changed)
                     .await
             });
         }
@@ -846,45 +1786,63 @@ async fn emit_update(
         .await
 }
 
+/// Entry of `enabled_tools`/`disabled_tools` matching tools annotated as read-only.
+const READ_ONLY_TOOL_CATEGORY: &str = "@read_only";
+/// Entry of `enabled_tools`/`disabled_tools` matching tools not annotated as read-only.
+const MUTATING_TOOL_CATEGORY: &str = "@mutating";
+
 /// A tool is allowed to be used if both are true:
-/// 1. enabled is None (no allowlist is set) or the tool is explicitly enabled.
-/// 2. The tool is not explicitly disabled.
+/// 1. enabled is None (no allowlist is set) or the tool matches an enabled entry.
+/// 2. The tool does not match a disabled entry.
+///
+/// An entry is a tool name, a glob such as `jira_*`, or a category: [`READ_ONLY_TOOL_CATEGORY`]
+/// or [`MUTATING_TOOL_CATEGORY`].
 #[derive(Default, Clone)]
 pub(crate) struct ToolFilter {
-    enabled: Option<HashSet<String>>,
-    disabled: HashSet<String>,
+    enabled: Option<Vec<String>>,
+    disabled: Vec<String>,
 }
 
 impl ToolFilter {
     fn from_config(cfg: &McpServerConfig) -> Self {
-        let enabled = cfg
-            .enabled_tools
-            .as_ref()
-            .map(|tools| tools.iter().cloned().collect::<HashSet<_>>());
-        let disabled = cfg
-            .disabled_tools
-            .as_ref()
-            .map(|tools| tools.iter().cloned().collect::<HashSet<_>>())
-            .unwrap_or_default();
-
-        Self { enabled, disabled }
+        Self {
+            enabled: cfg.enabled_tools.clone(),
+            disabled: cfg.disabled_tools.clone().unwrap_or_default(),
+        }
     }
 
-    fn allows(&self, tool_name: &str) -> bool {
+    fn allows(&self, tool_name: &str, read_only: bool) -> bool {
+        let matches = |entry: &String| tool_filter_entry_matches(entry, tool_name, read_only);
         if let Some(enabled) = &self.enabled
-            && !enabled.contains(tool_name)
+            && !enabled.iter().any(matches)
         {
             return false;
         }
 
-        !self.disabled.contains(tool_name)
+        !self.disabled.iter().any(matches)
     }
 }
 
+fn tool_filter_entry_matches(entry: &str, tool_name: &str, read_only: bool) -> bool {
+    match entry {
+        READ_ONLY_TOOL_CATEGORY => read_only,
+        MUTATING_TOOL_CATEGORY => !read_only,
+        _ => WildMatchPattern::<'*', '?'>::new(entry).matches(tool_name),
+    }
+}
+
+/// Tools count as read-only only when annotated so; the MCP default is that a tool may mutate.
+fn is_read_only_tool(tool: &Tool) -> bool {
+    tool.annotations
+        .as_ref()
+        .and_then(|annotations| annotations.read_only_hint)
+        .unwrap_or(false)
+}
+
 fn filter_tools(tools: Vec<ToolInfo>, filter: ToolFilter) -> Vec<ToolInfo> {
     tools
         .into_iter()
-        .filter(|tool| filter.allows(&tool.tool_name))
+        .filter(|tool| filter.allows(&tool.tool_name, is_read_only_tool(&tool.tool)))
         .collect()
 }
 
@@ -1006,22 +1964,28 @@ impl From<anyhow::Error> for StartupOutcomeError {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn start_server_task(
     server_name: String,
     client: Arc<RmcpClient>,
     startup_timeout: Option<Duration>, // TODO: cancel_token should handle this.
     tool_timeout: Duration,
     tool_filter: ToolFilter,
+    tool_renames: HashMap<String, String>,
     tx_event: Sender<Event>,
     elicitation_requests: ElicitationRequestManager,
+    sampling_requests: Option<Sender<McpSamplingRequest>>,
+    roots: McpRoots,
 ) -> Result<ManagedClient, StartupOutcomeError> {
     let params = InitializeRequestParams {
         meta: None,
         capabilities: ClientCapabilities {
             experimental: None,
             extensions: None,
-            roots: None,
-            sampling: None,
+            roots: Some(RootsCapabilities {
+                list_changed: Some(true),
+            }),
+            sampling: sampling_requests.as_ref().map(|_| Default::default()),
             // https://modelcontextprotocol.io/specification/2025-06-18/client/elicitation#capabilities
             // indicates this should be an empty object.
             elicitation: Some(ElicitationCapability {
@@ -1043,16 +2007,36 @@ async fn start_server_task(
         protocol_version: ProtocolVersion::V_2025_06_18,
     };
 
+    let tools = Arc::new(StdMutex::new(Vec::new()));
+    let on_list_changed = make_list_changed_handler(
+        server_name.clone(),
+        Arc::downgrade(&client),
+        Arc::clone(&tools),
+        Some(tool_timeout),
+        tx_event.clone(),
+    );
     let send_elicitation = elicitation_requests.make_sender(server_name.clone(), tx_event);
+    let send_sampling = sampling_requests
+        .map(|sampling_requests| make_sampling_sender(server_name.clone(), sampling_requests));
 
     let initialize_result = client
-        .initialize(params, startup_timeout, send_elicitation)
+        .initialize(
+            params,
+            startup_timeout,
+            send_elicitation,
+            send_sampling,
+            Some(on_list_changed),
+            roots,
+        )
         .await
         .map_err(StartupOutcomeError::from)?;
 
-    let tools = list_tools_for_client(&server_name, &client, startup_timeout)
+    let listed_tools = list_tools_for_client(&server_name, &client, startup_timeout)
         .await
         .map_err(StartupOutcomeError::from)?;
+    *tools
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = listed_tools;
 
     let server_supports_sandbox_state_capability = initialize_result
         .capabilities
@@ -1060,13 +2044,17 @@ async fn start_server_task(
         .as_ref()
         .and_then(|exp| exp.get(MCP_SANDBOX_STATE_CAPABILITY))
         .is_some();
+    let server_supports_prompts = initialize_result.capabilities.prompts.is_some();
 
     let managed = ManagedClient {
         client: Arc::clone(&client),
         tools,
         tool_timeout: Some(tool_timeout),
         tool_filter,
+        tool_renames,
         server_supports_sandbox_state_capability,
+        server_supports_prompts,
+        active_calls: ActiveCalls::default(),
     };
 
     Ok(managed)
@@ -1076,6 +2064,7 @@ async fn make_rmcp_client(
     server_name: &str,
     transport: McpServerTransportConfig,
     store_mode: OAuthCredentialsStoreMode,
+    log: ServerLog,
 ) -> Result<RmcpClient, StartupOutcomeError> {
     match transport {
         McpServerTransportConfig::Stdio {
@@ -1085,9 +2074,12 @@ async fn make_rmcp_client(
             env_vars,
             cwd,
         } => {
+            let env = resolve_mcp_env(server_name, env, &DefaultKeyringStore)
+                .await
+                .map_err(StartupOutcomeError::from)?;
             let command_os: OsString = command.into();
             let args_os: Vec<OsString> = args.into_iter().map(Into::into).collect();
-            RmcpClient::new_stdio_client(command_os, args_os, env, &env_vars, cwd)
+            RmcpClient::new_stdio_client(command_os, args_os, env, &env_vars, cwd, log)
                 .await
                 .map_err(|err| StartupOutcomeError::from(anyhow!(err)))
         }
@@ -1310,7 +2302,6 @@ mod tests {
     use super::*;
     use codex_protocol::protocol::McpAuthStatus;
     use rmcp::model::JsonObject;
-    use std::collections::HashSet;
     use std::sync::Arc;
 
     fn create_test_tool(server_name: &str, tool_name: &str) -> ToolInfo {
@@ -1348,6 +2339,20 @@ mod tests {
         result
     }
 
+    #[test]
+    fn active_calls_are_counted_until_their_guard_drops() {
+        let active_calls = ActiveCalls::default();
+        assert!(!active_calls.any());
+
+        let first = active_calls.start();
+        let second = active_calls.clone().start();
+        drop(first);
+        assert!(active_calls.any());
+
+        drop(second);
+        assert!(!active_calls.any());
+    }
+
     #[test]
     fn test_qualify_tools_short_non_duplicated_names() {
         let tools = vec![
@@ -1355,7 +2360,7 @@ mod tests {
             create_test_tool("server1", "tool2"),
         ];
 
-        let qualified_tools = qualify_tools(tools);
+        let qualified_tools = qualify_tools(tools, &HashMap::new()).tools;
 
         assert_eq!(qualified_tools.len(), 2);
         assert!(qualified_tools.contains_key("mcp__server1__tool1"));
@@ -1369,7 +2374,7 @@ mod tests {
             create_test_tool("server1", "duplicate_tool"),
         ];
 
-        let qualified_tools = qualify_tools(tools);
+        let qualified_tools = qualify_tools(tools, &HashMap::new()).tools;
 
         // Only the first tool should remain, the second is skipped
         assert_eq!(qualified_tools.len(), 1);
@@ -1391,7 +2396,7 @@ mod tests {
             ),
         ];
 
-        let qualified_tools = qualify_tools(tools);
+        let qualified_tools = qualify_tools(tools, &HashMap::new()).tools;
 
         assert_eq!(qualified_tools.len(), 2);
 
@@ -1415,7 +2420,7 @@ mod tests {
     fn test_qualify_tools_sanitizes_invalid_characters() {
         let tools = vec![create_test_tool("server.one", "tool.two")];
 
-        let qualified_tools = qualify_tools(tools);
+        let qualified_tools = qualify_tools(tools, &HashMap::new()).tools;
 
         assert_eq!(qualified_tools.len(), 1);
         let (qualified_name, tool) = qualified_tools.into_iter().next().expect("one tool");
@@ -1433,45 +2438,325 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_qualify_tools_cross_server_collision_is_deterministic() {
+        let tools = vec![create_test_tool("a__b", "c"), create_test_tool("a", "b__c")];
+
+        let qualified = qualify_tools(tools, &HashMap::new());
+
+        let kept = qualified
+            .tools
+            .get("mcp__a__b__c")
+            .expect("first tool keeps the name");
+        assert_eq!(
+            (kept.server_name.as_str(), kept.tool_name.as_str()),
+            ("a", "b__c")
+        );
+        assert_eq!(qualified.tools.len(), 2);
+        assert_eq!(
+            qualified.conflicts,
+            vec![ToolNameConflict {
+                qualified_name: "mcp__a__b__c".to_string(),
+                kept: "a/b__c".to_string(),
+                renamed: "a__b/c".to_string(),
+                renamed_to: format!("mcp__a__b__c{}", sha1_hex("a__b/c")),
+            }]
+        );
+        let renamed = qualified
+            .tools
+            .get(&qualified.conflicts[0].renamed_to)
+            .expect("second tool is exposed under the hashed name");
+        assert_eq!(renamed.server_name, "a__b");
+    }
+
+    #[test]
+    fn test_qualify_tools_applies_renames() {
+        let tools = vec![create_test_tool("a__b", "c"), create_test_tool("a", "b__c")];
+        let tool_renames = HashMap::from([(
+            "a".to_string(),
+            HashMap::from([("b__c".to_string(), "search".to_string())]),
+        )]);
+
+        let qualified = qualify_tools(tools, &tool_renames);
+
+        assert_eq!(qualified.conflicts, Vec::new());
+        let mut names = qualified.tools.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["mcp__a__b__c", "mcp__a__search"]);
+        // Calls still use the server's name for the tool.
+        assert_eq!(qualified.tools["mcp__a__search"].tool_name, "b__c");
+    }
+
+    #[tokio::test]
+    async fn list_available_tools_skips_servers_still_starting() {
+        let starting = AsyncManagedClient {
+            client: Arc::new(StdMutex::new(
+                futures::future::pending::<Result<ManagedClient, StartupOutcomeError>>()
+                    .boxed()
+                    .shared(),
+            )),
+        };
+        let manager = McpConnectionManager {
+            clients: HashMap::from([("slow".to_string(), starting)]),
+            ..Default::default()
+        };
+
+        let tools = manager
+            .list_available_tools(Duration::from_millis(10))
+            .await;
+
+        assert!(tools.is_empty());
+    }
+
+    #[tokio::test]
+    async fn only_the_first_turn_waits_for_starting_servers() {
+        let starting = AsyncManagedClient {
+            client: Arc::new(StdMutex::new(
+                futures::future::pending::<Result<ManagedClient, StartupOutcomeError>>()
+                    .boxed()
+                    .shared(),
+            )),
+        };
+        let manager = McpConnectionManager {
+            clients: HashMap::from([("slow".to_string(), starting)]),
+            ..Default::default()
+        };
+
+        assert_eq!(manager.turn_startup_wait(), MCP_TURN_STARTUP_WAIT);
+        assert_eq!(manager.turn_startup_wait(), Duration::ZERO);
+        // Later turns return right away instead of waiting on the starting server.
+        let tools = tokio::time::timeout(Duration::from_secs(1), manager.list_turn_tools())
+            .await
+            .expect("listing tools should not wait");
+        assert!(tools.is_empty());
+    }
+
+    fn stdio_server_config(command: &str) -> McpServerConfig {
+        McpServerConfig {
+            transport: McpServerTransportConfig::Stdio {
+                command: command.to_string(),
+                args: Vec::new(),
+                env: None,
+                env_vars: Vec::new(),
+                cwd: None,
+            },
+            enabled: true,
+            required: false,
+            disabled_reason: None,
+            startup_timeout_sec: None,
+            tool_timeout_sec: None,
+            enabled_tools: None,
+            disabled_tools: None,
+            tool_renames: None,
+            tools: None,
+            tool_cache_ttl_sec: None,
+            scopes: None,
+            max_concurrent_calls: None,
+        }
+    }
+
+    fn started_server(manager: &mut McpConnectionManager, name: &str, config: McpServerConfig) {
+        let failed = AsyncManagedClient {
+            client: Arc::new(StdMutex::new(
+                futures::future::ready(Err(StartupOutcomeError::Failed {
+                    error: "exited".to_string(),
+                }))
+                .boxed()
+                .shared(),
+            )),
+        };
+        manager.clients.insert(name.to_string(), failed);
+        manager.servers.insert(
+            name.to_string(),
+            StartedServer {
+                config,
+                startup_cancel_token: CancellationToken::new(),
+                _supervisor: None,
+            },
+        );
+    }
+
+    #[tokio::test]
+    async fn reload_stops_removed_servers_and_restarts_changed_ones() {
+        let mut manager = McpConnectionManager::default();
+        started_server(&mut manager, "removed", stdio_server_config("removed"));
+        started_server(&mut manager, "changed", stdio_server_config("old"));
+        started_server(&mut manager, "unchanged", stdio_server_config("unchanged"));
+        let still_starting = AsyncManagedClient {
+            client: Arc::new(StdMutex::new(
+                futures::future::pending::<Result<ManagedClient, StartupOutcomeError>>()
+                    .boxed()
+                    .shared(),
+            )),
+        };
+        manager
+            .clients
+            .insert("unchanged".to_string(), still_starting);
+        let mut disabled = stdio_server_config("disabled");
+        started_server(&mut manager, "disabled", disabled.clone());
+        disabled.enabled = false;
+        for name in ["removed", "changed"] {
+            let log = ServerLog::default();
+            log.push(format!("{name} crashed"));
+            manager.server_logs.insert(name.to_string(), log);
+        }
+        let mcp_servers = HashMap::from([
+            ("changed".to_string(), stdio_server_config("new")),
+            ("added".to_string(), stdio_server_config("added")),
+            ("unchanged".to_string(), stdio_server_config("unchanged")),
+            ("disabled".to_string(), disabled),
+        ]);
+        let (tx_event, _rx_event) = async_channel::unbounded();
+        // A cancelled token keeps the new servers from being spawned.
+        let cancel_token = CancellationToken::new();
+        cancel_token.cancel();
+
+        let summary = manager
+            .reload(
+                &mcp_servers,
+                OAuthCredentialsStoreMode::default(),
+                HashMap::new(),
+                tx_event,
+                cancel_token,
+                SandboxState {
+                    sandbox_policy: SandboxPolicy::DangerFullAccess,
+                    codex_linux_sandbox_exe: None,
+                    sandbox_cwd: PathBuf::from("/"),
+                    use_linux_sandbox_bwrap: false,
+                },
+            )
+            .await;
+
+        assert_eq!(
+            summary,
+            McpReloadSummary {
+                started: vec!["added".to_string()],
+                restarted: vec!["changed".to_string()],
+                stopped: vec!["disabled".to_string(), "removed".to_string()],
+            }
+        );
+        // The unchanged server is left to finish starting.
+        assert_eq!(
+            manager.clients.keys().collect::<Vec<_>>(),
+            vec!["unchanged"]
+        );
+        assert_eq!(
+            manager.servers.keys().collect::<Vec<_>>(),
+            vec!["unchanged"]
+        );
+        // Logs of restarted servers are kept; logs of stopped servers are dropped.
+        assert_eq!(
+            manager.server_logs(None),
+            HashMap::from([("changed".to_string(), vec!["changed crashed".to_string()])])
+        );
+        assert_eq!(manager.server_logs(Some("removed")), HashMap::new());
+    }
+
+    fn text_result(text: &str) -> CallToolResult {
+        CallToolResult {
+            content: vec![serde_json::json!({"type": "text", "text": text})],
+            structured_content: None,
+            is_error: None,
+            meta: None,
+        }
+    }
+
+    #[test]
+    fn tool_result_cache_expires_and_evicts() {
+        let key = |arguments: &str| ToolCallKey {
+            server: "docs".to_string(),
+            tool: "search".to_string(),
+            arguments: arguments.to_string(),
+        };
+        let now = Instant::now();
+        let mut cache = ToolResultCache::default();
+
+        cache.insert(key("a"), text_result("a"), Duration::from_secs(60), now);
+        assert_eq!(cache.get(&key("a"), now), Some(text_result("a")));
+        assert_eq!(cache.get(&key("b"), now), None);
+        assert_eq!(cache.get(&key("a"), now + Duration::from_secs(61)), None);
+        assert!(cache.entries.is_empty());
+
+        for i in 0..=MAX_CACHED_TOOL_RESULTS {
+            let ttl = Duration::from_secs(60 + i as u64);
+            cache.insert(key(&i.to_string()), text_result("x"), ttl, now);
+        }
+        assert_eq!(cache.entries.len(), MAX_CACHED_TOOL_RESULTS);
+        assert_eq!(cache.get(&key("0"), now), None);
+
+        cache.remove_server("docs");
+        assert!(cache.entries.is_empty());
+    }
+
+    #[test]
+    fn canonical_json_sorts_object_keys() {
+        let a = serde_json::json!({"query": "rust", "filters": {"lang": "en", "since": 2024}});
+        let b = serde_json::json!({"filters": {"since": 2024, "lang": "en"}, "query": "rust"});
+
+        assert_eq!(canonical_json(Some(&a)), canonical_json(Some(&b)));
+        assert_eq!(canonical_json(None), "null");
+    }
+
     #[test]
     fn tool_filter_allows_by_default() {
         let filter = ToolFilter::default();
 
-        assert!(filter.allows("any"));
+        assert!(filter.allows("any", false));
     }
 
     #[test]
     fn tool_filter_applies_enabled_list() {
         let filter = ToolFilter {
-            enabled: Some(HashSet::from(["allowed".to_string()])),
-            disabled: HashSet::new(),
+            enabled: Some(vec!["allowed".to_string()]),
+            disabled: Vec::new(),
         };
 
-        assert!(filter.allows("allowed"));
-        assert!(!filter.allows("denied"));
+        assert!(filter.allows("allowed", false));
+        assert!(!filter.allows("denied", false));
     }
 
     #[test]
     fn tool_filter_applies_disabled_list() {
         let filter = ToolFilter {
             enabled: None,
-            disabled: HashSet::from(["blocked".to_string()]),
+            disabled: vec!["blocked".to_string()],
         };
 
-        assert!(!filter.allows("blocked"));
-        assert!(filter.allows("open"));
+        assert!(!filter.allows("blocked", false));
+        assert!(filter.allows("open", false));
     }
 
     #[test]
     fn tool_filter_applies_enabled_then_disabled() {
         let filter = ToolFilter {
-            enabled: Some(HashSet::from(["keep".to_string(), "remove".to_string()])),
-            disabled: HashSet::from(["remove".to_string()]),
+            enabled: Some(vec!["keep".to_string(), "remove".to_string()]),
+            disabled: vec!["remove".to_string()],
+        };
+
+        assert!(filter.allows("keep", false));
+        assert!(!filter.allows("remove", false));
+        assert!(!filter.allows("unknown", false));
+    }
+
+    #[test]
+    fn tool_filter_matches_globs_and_categories() {
+        let filter = ToolFilter {
+            enabled: Some(vec!["jira_*".to_string()]),
+            disabled: vec![MUTATING_TOOL_CATEGORY.to_string()],
+        };
+
+        assert!(filter.allows("jira_search", true));
+        assert!(!filter.allows("jira_create_issue", false));
+        assert!(!filter.allows("confluence_search", true));
+
+        let filter = ToolFilter {
+            enabled: Some(vec![READ_ONLY_TOOL_CATEGORY.to_string()]),
+            disabled: vec!["get_?".to_string()],
         };
 
-        assert!(filter.allows("keep"));
-        assert!(!filter.allows("remove"));
-        assert!(!filter.allows("unknown"));
+        assert!(filter.allows("list_issues", true));
+        assert!(!filter.allows("get_x", true));
+        assert!(!filter.allows("delete_issue", false));
     }
 
     #[test]
@@ -1482,12 +2767,12 @@ mod tests {
         ];
         let server2_tools = vec![create_test_tool("server2", "tool_a")];
         let server1_filter = ToolFilter {
-            enabled: Some(HashSet::from(["tool_a".to_string(), "tool_b".to_string()])),
-            disabled: HashSet::from(["tool_b".to_string()]),
+            enabled: Some(vec!["tool_a".to_string(), "tool_b".to_string()]),
+            disabled: vec!["tool_b".to_string()],
         };
         let server2_filter = ToolFilter {
             enabled: None,
-            disabled: HashSet::from(["tool_a".to_string()]),
+            disabled: vec!["tool_a".to_string()],
         };
 
         let filtered: Vec<_> = filter_tools(server1_tools, server1_filter)
@@ -1559,7 +2844,11 @@ mod tests {
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
             auth_status: McpAuthStatus::Unsupported,
         };
@@ -1606,7 +2895,11 @@ mod tests {
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
             auth_status: McpAuthStatus::Unsupported,
         };
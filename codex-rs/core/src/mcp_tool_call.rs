@@ -1,17 +1,27 @@
 use std::time::Duration;
 use std::time::Instant;
 
+use tracing::Instrument;
+use tracing::Span;
 use tracing::error;
+use tracing::field;
+use tracing::instrument;
+use tracing::trace_span;
 
 use crate::analytics_client::AppInvocation;
 use crate::analytics_client::build_track_events_context;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::mcp::CODEX_APPS_MCP_SERVER_NAME;
+use crate::mcp::McpCallSlot;
+use crate::mcp::ToolArgumentError;
+use crate::mcp::check_tool_arguments;
 use crate::protocol::EventMsg;
 use crate::protocol::McpInvocation;
 use crate::protocol::McpToolCallBeginEvent;
 use crate::protocol::McpToolCallEndEvent;
+use crate::protocol::McpToolCallQueuedEvent;
+use crate::tools::sandboxing::remember_for_session;
 use codex_protocol::mcp::CallToolResult;
 use codex_protocol::models::FunctionCallOutputBody;
 use codex_protocol::models::FunctionCallOutputPayload;
@@ -30,6 +40,11 @@ use std::sync::Arc;
 
 /// Handles the specified tool call dispatches the appropriate
 /// `McpToolCallBegin` and `McpToolCallEnd` events to the `Session`.
+#[instrument(
+    level = "trace",
+    skip_all,
+    fields(server = %server, tool = %tool_name, call_id = %call_id, status = field::Empty)
+)]
 pub(crate) async fn handle_mcp_tool_call(
     sess: Arc<Session>,
     turn_context: &TurnContext,
@@ -58,6 +73,30 @@ pub(crate) async fn handle_mcp_tool_call(
         }
     };
 
+    // Check the arguments against the tool's input schema, so that mistakes are reported to the
+    // model precisely rather than as whatever the server makes of them.
+    let arguments_value = match sess.mcp_tool_input_schema(&server, &tool_name).await {
+        Some(input_schema) => match check_tool_arguments(&input_schema, arguments_value) {
+            Ok(arguments_value) => arguments_value,
+            Err(errors) => {
+                tracing::warn!(
+                    "rejected arguments for MCP tool `{server}/{tool_name}`: {} error(s)",
+                    errors.len()
+                );
+                return ResponseInputItem::FunctionCallOutput {
+                    call_id,
+                    output: FunctionCallOutputPayload {
+                        body: FunctionCallOutputBody::Text(invalid_arguments_output(
+                            &server, &tool_name, &errors,
+                        )),
+                        success: Some(false),
+                    },
+                };
+            }
+        },
+        None => arguments_value,
+    };
+
     let invocation = McpInvocation {
         server: server.clone(),
         tool: tool_name.clone(),
@@ -66,10 +105,14 @@ pub(crate) async fn handle_mcp_tool_call(
 
     if let Some(decision) =
         maybe_request_mcp_tool_approval(sess.as_ref(), turn_context, &call_id, &server, &tool_name)
+            .instrument(trace_span!("mcp_tool_approval"))
             .await
     {
         let result = match decision {
             McpToolApprovalDecision::Accept | McpToolApprovalDecision::AcceptAndRemember => {
+                let slot =
+                    wait_for_mcp_call_slot(sess.as_ref(), turn_context, &call_id, &invocation)
+                        .await;
                 let tool_call_begin_event = EventMsg::McpToolCallBegin(McpToolCallBeginEvent {
                     call_id: call_id.clone(),
                     invocation: invocation.clone(),
@@ -80,15 +123,18 @@ pub(crate) async fn handle_mcp_tool_call(
                 let start = Instant::now();
                 let result = sess
                     .call_tool(&server, &tool_name, arguments_value.clone())
+                    .instrument(trace_span!("mcp_call_tool"))
                     .await
                     .map_err(|e| format!("tool call error: {e:?}"));
-                let result = sanitize_mcp_tool_result_for_model(
+                drop(slot);
+                let mut result = sanitize_mcp_tool_result_for_model(
                     turn_context
                         .model_info
                         .input_modalities
                         .contains(&InputModality::Image),
                     result,
                 );
+                sess.services.secret_redactor.redact_mcp_result(&mut result);
                 if let Err(e) = &result {
                     tracing::warn!("MCP tool call error: {e:?}");
                 }
@@ -132,6 +178,7 @@ pub(crate) async fn handle_mcp_tool_call(
         };
 
         let status = if result.is_ok() { "ok" } else { "error" };
+        Span::current().record("status", status);
         turn_context
             .otel_manager
             .counter("codex.mcp.call", 1, &[("status", status)]);
@@ -139,6 +186,7 @@ pub(crate) async fn handle_mcp_tool_call(
         return ResponseInputItem::McpToolCallOutput { call_id, result };
     }
 
+    let slot = wait_for_mcp_call_slot(sess.as_ref(), turn_context, &call_id, &invocation).await;
     let tool_call_begin_event = EventMsg::McpToolCallBegin(McpToolCallBeginEvent {
         call_id: call_id.clone(),
         invocation: invocation.clone(),
@@ -149,15 +197,19 @@ pub(crate) async fn handle_mcp_tool_call(
     // Perform the tool call.
     let result = sess
         .call_tool(&server, &tool_name, arguments_value.clone())
+        .instrument(trace_span!("mcp_call_tool"))
         .await
         .map_err(|e| format!("tool call error: {e:?}"));
-    let result = sanitize_mcp_tool_result_for_model(
+    drop(slot);
+    let mut result = sanitize_mcp_tool_result_for_model(
         turn_context
             .model_info
             .input_modalities
             .contains(&InputModality::Image),
         result,
     );
+    // Redacted here so neither the client nor the model sees the secret.
+    sess.services.secret_redactor.redact_mcp_result(&mut result);
     if let Err(e) = &result {
         tracing::warn!("MCP tool call error: {e:?}");
     }
@@ -172,6 +224,7 @@ pub(crate) async fn handle_mcp_tool_call(
     maybe_track_codex_app_used(sess.as_ref(), turn_context, &server, &tool_name).await;
 
     let status = if result.is_ok() { "ok" } else { "error" };
+    Span::current().record("status", status);
     turn_context
         .otel_manager
         .counter("codex.mcp.call", 1, &[("status", status)]);
@@ -210,10 +263,42 @@ fn sanitize_mcp_tool_result_for_model(
     })
 }
 
+/// The error returned to the model for arguments that do not match the tool's input schema.
+fn invalid_arguments_output(server: &str, tool_name: &str, errors: &[ToolArgumentError]) -> String {
+    serde_json::json!({
+        "error": format!(
+            "arguments for MCP tool `{server}/{tool_name}` do not match its input schema; fix them and call the tool again"
+        ),
+        "invalid_arguments": errors,
+    })
+    .to_string()
+}
+
 async fn notify_mcp_tool_call_event(sess: &Session, turn_context: &TurnContext, event: EventMsg) {
     sess.send_event(turn_context, event).await;
 }
 
+/// Waits until the concurrent call limits leave room for this call, sending an
+/// `McpToolCallQueued` event each time its place in line changes.
+async fn wait_for_mcp_call_slot(
+    sess: &Session,
+    turn_context: &TurnContext,
+    call_id: &str,
+    invocation: &McpInvocation,
+) -> McpCallSlot {
+    let mut slot = sess.acquire_mcp_call_slot(&invocation.server).await;
+    while let Some(position) = slot.queue_position() {
+        let queued_event = EventMsg::McpToolCallQueued(McpToolCallQueuedEvent {
+            call_id: call_id.to_string(),
+            invocation: invocation.clone(),
+            position,
+        });
+        notify_mcp_tool_call_event(sess, turn_context, queued_event).await;
+        slot.position_changed().await;
+    }
+    slot
+}
+
 struct McpAppUsageMetadata {
     connector_id: Option<String>,
     app_name: Option<String>,
@@ -501,8 +586,7 @@ async fn mcp_tool_approval_is_remembered(sess: &Session, key: &McpToolApprovalKe
 }
 
 async fn remember_mcp_tool_approval(sess: &Session, key: McpToolApprovalKey) {
-    let mut store = sess.services.tool_approvals.lock().await;
-    store.put(key, ReviewDecision::ApprovedForSession);
+    remember_for_session(&sess.services, vec![key]).await;
 }
 
 fn requires_mcp_tool_approval(annotations: &ToolAnnotations) -> bool {
@@ -570,6 +654,28 @@ mod tests {
         assert_eq!(requires_mcp_tool_approval(&annotations), false);
     }
 
+    #[test]
+    fn invalid_arguments_output_lists_each_error() {
+        let errors = vec![ToolArgumentError {
+            path: "arguments.limit".to_string(),
+            message: "expected integer, got string \"ten\"".to_string(),
+        }];
+
+        let output: serde_json::Value =
+            serde_json::from_str(&invalid_arguments_output("docs", "search", &errors))
+                .expect("output is JSON");
+
+        assert_eq!(
+            output,
+            serde_json::json!({
+                "error": "arguments for MCP tool `docs/search` do not match its input schema; fix them and call the tool again",
+                "invalid_arguments": [
+                    { "path": "arguments.limit", "message": "expected integer, got string \"ten\"" }
+                ],
+            })
+        );
+    }
+
     #[test]
     fn sanitize_mcp_tool_result_for_model_rewrites_image_content() {
         let result = Ok(CallToolResult {
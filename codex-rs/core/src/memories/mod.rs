@@ -6,6 +6,7 @@
 
 mod phase1;
 mod phase2;
+pub(crate) mod project;
 pub(crate) mod prompts;
 mod start;
 mod storage;
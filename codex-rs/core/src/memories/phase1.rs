@@ -307,11 +307,13 @@ mod job {
             }],
             tools: Vec::new(),
             parallel_tool_calls: false,
+            emulate_tool_calls: false,
             base_instructions: BaseInstructions {
                 text: phase_one::PROMPT.to_string(),
             },
             personality: None,
             output_schema: Some(output_schema()),
+            max_output_tokens: None,
         };
 
         let mut client_session = session.services.model_client.new_session();
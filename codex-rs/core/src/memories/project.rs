@@ -0,0 +1,236 @@
+//! Facts the agent saves explicitly for a project with the `project_memory` tool.
+//!
+//! Unlike the startup pipeline, which extracts memories from past rollouts, these entries are
+//! written on request and injected verbatim into the developer instructions of later sessions in
+//! the same project. Each project gets one Markdown file under `~/.codex/project_memories/`
+//! containing one `- fact` bullet per entry, so users can review and edit it by hand.
+
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use askama::Template;
+use sha2::Digest;
+use sha2::Sha256;
+use tokio::fs;
+use tracing::warn;
+
+use crate::git_info::get_git_repo_root;
+use crate::locked_file::with_locked_append_file;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::truncate_text;
+
+const PROJECT_MEMORIES_SUBDIR: &str = "project_memories";
+
+/// Maximum number of tokens of project memories injected into developer instructions.
+const PROJECT_MEMORIES_INSTRUCTIONS_TOKEN_LIMIT: usize = 2_000;
+
+#[derive(Template)]
+#[template(path = "memories/project_memories.md", escape = "none")]
+struct ProjectMemoriesTemplate<'a> {
+    memories: &'a str,
+}
+
+/// Returns the memory file for the project containing `cwd`. The project is the enclosing git
+/// repository, or `cwd` itself outside of one.
+pub(crate) fn project_memory_path(codex_home: &Path, cwd: &Path) -> PathBuf {
+    let project_root = get_git_repo_root(cwd).unwrap_or_else(|| cwd.to_path_buf());
+    let canonical = project_root.canonicalize().unwrap_or(project_root);
+    let digest = format!(
+        "{:x}",
+        Sha256::digest(canonical.to_string_lossy().as_bytes())
+    );
+    let hash = digest.get(..16).unwrap_or(&digest);
+    let name = canonical
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let file_name = if name.is_empty() {
+        format!("{hash}.md")
+    } else {
+        format!("{name}-{hash}.md")
+    };
+    codex_home.join(PROJECT_MEMORIES_SUBDIR).join(file_name)
+}
+
+/// Reads the saved entries, in the order they were added. A missing file has no entries.
+pub(crate) async fn load_project_memories(path: &Path) -> std::io::Result<Vec<String>> {
+    match fs::read_to_string(path).await {
+        Ok(contents) => Ok(parse_project_memories(&contents)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Applies `update` to the saved entries and writes the result back. The file stays locked
+/// from the read to the write, so concurrent sessions don't lose each other's changes.
+pub(crate) async fn update_project_memories<T, F>(path: PathBuf, update: F) -> std::io::Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut Vec<String>) -> T + Send + 'static,
+{
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    with_locked_append_file(path, move |file| {
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+        let mut entries = parse_project_memories(&contents);
+        let result = update(&mut entries);
+        let contents: String = entries.iter().map(|entry| format!("- {entry}\n")).collect();
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(contents.as_bytes())?;
+        file.flush()?;
+        Ok(result)
+    })
+    .await
+}
+
+fn parse_project_memories(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("- "))
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Collapses whitespace so an entry always fits on a single bullet line.
+pub(crate) fn normalize_memory_entry(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Builds the developer instructions listing this project's saved memories, if any.
+pub(crate) async fn build_project_memory_instructions(
+    codex_home: &Path,
+    cwd: &Path,
+) -> Option<String> {
+    let path = project_memory_path(codex_home, cwd);
+    let entries = match load_project_memories(&path).await {
+        Ok(entries) => entries,
+        Err(err) => {
+            warn!(
+                "failed to read project memories from {}: {err}",
+                path.display()
+            );
+            return None;
+        }
+    };
+    if entries.is_empty() {
+        return None;
+    }
+    let memories = entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| format!("{}. {entry}", index + 1))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let memories = truncate_text(
+        &memories,
+        TruncationPolicy::Tokens(PROJECT_MEMORIES_INSTRUCTIONS_TOKEN_LIMIT),
+    );
+    ProjectMemoriesTemplate {
+        memories: &memories,
+    }
+    .render()
+    .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn project_memory_path_is_shared_within_a_repository() {
+        let codex_home = TempDir::new().expect("codex home");
+        let repo = TempDir::new().expect("repo");
+        std::fs::create_dir(repo.path().join(".git")).expect("git dir");
+        let nested = repo.path().join("src");
+        std::fs::create_dir(&nested).expect("nested dir");
+
+        assert_eq!(
+            project_memory_path(codex_home.path(), &nested),
+            project_memory_path(codex_home.path(), repo.path())
+        );
+    }
+
+    #[tokio::test]
+    async fn saved_memories_round_trip_and_render() {
+        let codex_home = TempDir::new().expect("codex home");
+        let cwd = TempDir::new().expect("cwd");
+        let path = project_memory_path(codex_home.path(), cwd.path());
+        let entries = vec![
+            "Use `just fmt` before committing.".to_string(),
+            "Tests live next to the code.".to_string(),
+        ];
+
+        let saved = entries.clone();
+        update_project_memories(path.clone(), move |current| current.extend(saved))
+            .await
+            .expect("save memories");
+
+        assert_eq!(
+            load_project_memories(&path).await.expect("load memories"),
+            entries
+        );
+        let instructions = build_project_memory_instructions(codex_home.path(), cwd.path())
+            .await
+            .expect("instructions");
+        assert!(
+            instructions
+                .contains("1. Use `just fmt` before committing.\n2. Tests live next to the code.")
+        );
+
+        update_project_memories(path.clone(), |entries| entries.clear())
+            .await
+            .expect("clear memories");
+        assert_eq!(
+            load_project_memories(&path).await.expect("load memories"),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            build_project_memory_instructions(codex_home.path(), cwd.path()).await,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn concurrent_updates_are_all_kept() {
+        let codex_home = TempDir::new().expect("codex home");
+        let cwd = TempDir::new().expect("cwd");
+        let path = project_memory_path(codex_home.path(), cwd.path());
+
+        let updates = (0..8).map(|n| {
+            update_project_memories(path.clone(), move |entries| {
+                entries.push(format!("fact {n}"))
+            })
+        });
+        for result in futures::future::join_all(updates).await {
+            result.expect("save memory");
+        }
+
+        let mut entries = load_project_memories(&path).await.expect("load memories");
+        entries.sort();
+        assert_eq!(
+            entries,
+            (0..8).map(|n| format!("fact {n}")).collect::<Vec<_>>()
+        );
+    }
+}
@@ -31,17 +31,17 @@ use std::path::PathBuf;
 use serde::Deserialize;
 use serde::Serialize;
 
-use std::time::Duration;
 use tokio::fs;
 use tokio::io::AsyncReadExt;
 
 use crate::config::Config;
 use crate::config::types::HistoryPersistence;
+use crate::locked_file::MAX_RETRIES;
+use crate::locked_file::RETRY_SLEEP;
+use crate::locked_file::with_locked_append_file;
 
 use codex_protocol::ThreadId;
 #[cfg(unix)]
-use std::os::unix::fs::OpenOptionsExt;
-#[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 
 /// Filename that stores the message history inside `~/.codex`.
@@ -50,9 +50,6 @@ const HISTORY_FILENAME: &str = "history.jsonl";
 /// When history exceeds the hard cap, trim it down to this fraction of `max_bytes`.
 const HISTORY_SOFT_CAP_RATIO: f64 = 0.8;
 
-const MAX_RETRIES: usize = 10;
-const RETRY_SLEEP: Duration = Duration::from_millis(100);
-
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct HistoryEntry {
     pub session_id: String,
@@ -108,52 +105,21 @@ pub(crate) async fn append_entry(
         .map_err(|e| std::io::Error::other(format!("failed to serialise history entry: {e}")))?;
     line.push('\n');
 
-    // Open the history file for read/write access (append-only on Unix).
-    let mut options = OpenOptions::new();
-    options.read(true).write(true).create(true);
-    #[cfg(unix)]
-    {
-        options.append(true);
-        options.mode(0o600);
-    }
-
-    let mut history_file = options.open(&path)?;
-
-    // Ensure permissions.
-    ensure_owner_only_permissions(&history_file).await?;
-
     let history_max_bytes = config.history.max_bytes;
 
-    // Perform a blocking write under an advisory write lock using std::fs.
-    tokio::task::spawn_blocking(move || -> Result<()> {
-        // Retry a few times to avoid indefinite blocking when contended.
-        for _ in 0..MAX_RETRIES {
-            match history_file.try_lock() {
-                Ok(()) => {
-                    // While holding the exclusive lock, write the full line.
-                    // We do not open the file with `append(true)` on Windows, so ensure the
-                    // cursor is positioned at the end before writing.
-                    history_file.seek(SeekFrom::End(0))?;
-                    history_file.write_all(line.as_bytes())?;
-                    history_file.flush()?;
-                    enforce_history_limit(&mut history_file, history_max_bytes)?;
-                    return Ok(());
-                }
-                Err(std::fs::TryLockError::WouldBlock) => {
-                    std::thread::sleep(RETRY_SLEEP);
-                }
-                Err(e) => return Err(e.into()),
-            }
-        }
-
-        Err(std::io::Error::new(
-            std::io::ErrorKind::WouldBlock,
-            "could not acquire exclusive lock on history file after multiple attempts",
-        ))
+    // Perform a blocking write under an advisory write lock.
+    with_locked_append_file(path, move |history_file| {
+        // Files created before permissions were enforced may still be readable by others.
+        ensure_owner_only_permissions(history_file)?;
+        // While holding the exclusive lock, write the full line.
+        // We do not open the file with `append(true)` on Windows, so ensure the
+        // cursor is positioned at the end before writing.
+        history_file.seek(SeekFrom::End(0))?;
+        history_file.write_all(line.as_bytes())?;
+        history_file.flush()?;
+        enforce_history_limit(history_file, history_max_bytes)
     })
-    .await??;
-
-    Ok(())
+    .await
 }
 
 /// Trim the history file to honor `max_bytes`, dropping the oldest lines while holding
@@ -266,22 +232,20 @@ pub(crate) fn lookup(log_id: u64, offset: usize, config: &Config) -> Option<Hist
 /// On Unix systems, ensure the file permissions are `0o600` (rw-------). If the
 /// permissions cannot be changed the error is propagated to the caller.
 #[cfg(unix)]
-async fn ensure_owner_only_permissions(file: &File) -> Result<()> {
+fn ensure_owner_only_permissions(file: &File) -> Result<()> {
     let metadata = file.metadata()?;
     let current_mode = metadata.permissions().mode() & 0o777;
     if current_mode != 0o600 {
         let mut perms = metadata.permissions();
         perms.set_mode(0o600);
-        let perms_clone = perms.clone();
-        let file_clone = file.try_clone()?;
-        tokio::task::spawn_blocking(move || file_clone.set_permissions(perms_clone)).await??;
+        file.set_permissions(perms)?;
     }
     Ok(())
 }
 
 #[cfg(windows)]
 // On Windows, simply succeed.
-async fn ensure_owner_only_permissions(_file: &File) -> Result<()> {
+fn ensure_owner_only_permissions(_file: &File) -> Result<()> {
     Ok(())
 }
 
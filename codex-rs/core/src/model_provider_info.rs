@@ -6,9 +6,17 @@
 //!      key. These override or extend the defaults at runtime.
 
 use crate::auth::AuthMode;
+use crate::default_client::build_reqwest_client_builder;
+use crate::error::CodexErr;
+use crate::error::ConnectionFailedError;
 use crate::error::EnvVarError;
+use crate::secrets::SecretCache;
+use crate::secrets::SecretRef;
+use crate::util::backoff_from;
 use codex_api::Provider as ApiProvider;
 use codex_api::provider::RetryConfig as ApiRetryConfig;
+use codex_keyring_store::DefaultKeyringStore;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use http::HeaderMap;
 use http::header::HeaderName;
 use http::header::HeaderValue;
@@ -16,11 +24,14 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
+use std::io;
 use std::time::Duration;
 
 const DEFAULT_STREAM_IDLE_TIMEOUT_MS: u64 = 300_000;
 const DEFAULT_STREAM_MAX_RETRIES: u64 = 5;
 const DEFAULT_REQUEST_MAX_RETRIES: u64 = 4;
+const DEFAULT_RETRY_BASE_DELAY_MS: u64 = 200;
+const DEFAULT_RETRY_MAX_DELAY_MS: u64 = 60_000;
 /// Hard cap for user-configured `stream_max_retries`.
 const MAX_STREAM_MAX_RETRIES: u64 = 100;
 /// Hard cap for user-configured `request_max_retries`.
@@ -38,6 +49,12 @@ pub enum WireApi {
     /// The Responses API exposed by OpenAI at `/v1/responses`.
     #[default]
     Responses,
+    /// The Anthropic Messages API at `/v1/messages`. The provider's `env_key` is sent as
+    /// `x-api-key`.
+    Anthropic,
+    /// The Gemini API's `streamGenerateContent` method. The provider's `env_key` is sent as
+    /// `x-goog-api-key`.
+    Gemini,
 }
 
 impl<'de> Deserialize<'de> for WireApi {
@@ -48,8 +65,13 @@ impl<'de> Deserialize<'de> for WireApi {
         let value = String::deserialize(deserializer)?;
         match value.as_str() {
             "responses" => Ok(Self::Responses),
+            "anthropic" => Ok(Self::Anthropic),
+            "gemini" => Ok(Self::Gemini),
             "chat" => Err(serde::de::Error::custom(CHAT_WIRE_API_REMOVED_ERROR)),
-            _ => Err(serde::de::Error::unknown_variant(&value, &["responses"])),
+            _ => Err(serde::de::Error::unknown_variant(
+                &value,
+                &["responses", "anthropic", "gemini"],
+            )),
         }
     }
 }
@@ -69,6 +91,12 @@ pub struct ModelProviderInfo {
     /// variable and set it.
     pub env_key_instructions: Option<String>,
 
+    /// Where to read the API key from instead of `env_key`: `{ keychain = "<name>" }` for a
+    /// secret saved with `codex config set-secret <name>`, or `{ command = [...] }` for the
+    /// output of a command. Takes precedence over `env_key`. Read with [`Self::api_key`].
+    #[serde(rename = "api_key")]
+    pub api_key_secret: Option<SecretRef>,
+
     /// Value to use with `Authorization: Bearer <token>` header. Use of this
     /// config is discouraged in favor of `env_key` for security reasons, but
     /// this may be necessary when using this programmatically.
@@ -101,6 +129,15 @@ pub struct ModelProviderInfo {
     /// the connection as lost.
     pub stream_idle_timeout_ms: Option<u64>,
 
+    /// Delay (in milliseconds) before the first retry of a failed request. Later retries back off
+    /// exponentially with jitter.
+    pub retry_base_delay_ms: Option<u64>,
+
+    /// Longest delay (in milliseconds) to wait before a retry, including delays the provider asks
+    /// for via `Retry-After` or rate-limit reset headers. Requests that ask for a longer wait are
+    /// not retried.
+    pub retry_max_delay_ms: Option<u64>,
+
     /// Does this provider require an OpenAI API Key or ChatGPT login token? If true,
     /// user is presented with login screen on first run, and login preference and token/key
     /// are stored in auth.json. If false (which is the default), login screen is skipped,
@@ -111,9 +148,141 @@ pub struct ModelProviderInfo {
     /// Whether this provider supports the Responses API WebSocket transport.
     #[serde(default)]
     pub supports_websockets: bool,
+
+    /// Azure OpenAI settings: deployment routing, `api-version`, and Azure AD auth.
+    pub azure: Option<AzureProviderInfo>,
+
+    /// TLS settings for providers behind a private CA or requiring client certificates.
+    pub tls: Option<ProviderTlsConfig>,
+}
+
+/// Azure OpenAI settings for a provider.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct AzureProviderInfo {
+    /// Value of the `api-version` query parameter sent with every request.
+    pub api_version: Option<String>,
+
+    /// Deployment names keyed by model slug. Azure routes requests by deployment, so the
+    /// deployment name is sent in place of the slug for mapped models.
+    #[serde(default)]
+    pub deployments: HashMap<String, String>,
+
+    /// How requests are authenticated.
+    #[serde(default)]
+    pub auth: AzureAuth,
+}
+
+/// Credential used for Azure OpenAI requests.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AzureAuth {
+    /// The API key from the provider's `env_key`.
+    #[default]
+    ApiKey,
+    /// An Azure AD (Entra ID) token from `az account get-access-token`.
+    AzureCli,
+    /// An Azure AD token for the service principal in `AZURE_TENANT_ID`, `AZURE_CLIENT_ID`, and
+    /// `AZURE_CLIENT_SECRET`.
+    ClientSecret,
+}
+
+/// TLS settings for requests to a provider.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ProviderTlsConfig {
+    /// PEM bundle of extra root certificates to trust, such as a private CA.
+    pub ca_certificate: Option<AbsolutePathBuf>,
+
+    /// PEM client certificate (chain) presented for mutual TLS. Requires `client_private_key`.
+    pub client_certificate: Option<AbsolutePathBuf>,
+
+    /// PEM private key of `client_certificate`.
+    pub client_private_key: Option<AbsolutePathBuf>,
+
+    /// Trust only the certificates in `ca_certificate` instead of adding them to the system
+    /// roots, so the provider's certificate must be issued by that CA.
+    #[serde(default)]
+    pub pin_ca_certificate: bool,
+}
+
+impl ProviderTlsConfig {
+    /// Applies these settings to `builder`, reading the certificate files.
+    pub fn apply(&self, mut builder: reqwest::ClientBuilder) -> io::Result<reqwest::ClientBuilder> {
+        // Client identities from PEM files need the rustls backend.
+        builder = builder.use_rustls_tls();
+
+        if let Some(path) = &self.ca_certificate {
+            let pem = read_tls_file(path)?;
+            let certificates = reqwest::Certificate::from_pem_bundle(&pem).map_err(|err| {
+                tls_config_error(format!(
+                    "failed to parse CA certificates in {}: {err}",
+                    path.display()
+                ))
+            })?;
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+        if self.pin_ca_certificate {
+            if self.ca_certificate.is_none() {
+                return Err(tls_config_error(
+                    "pin_ca_certificate requires ca_certificate".to_string(),
+                ));
+            }
+            builder = builder.tls_built_in_root_certs(false);
+        }
+
+        match (&self.client_certificate, &self.client_private_key) {
+            (Some(cert_path), Some(key_path)) => {
+                let mut pem = read_tls_file(cert_path)?;
+                pem.push(b'\n');
+                pem.extend_from_slice(&read_tls_file(key_path)?);
+                let identity = reqwest::Identity::from_pem(&pem).map_err(|err| {
+                    tls_config_error(format!(
+                        "failed to parse client identity using {} and {}: {err}",
+                        cert_path.display(),
+                        key_path.display()
+                    ))
+                })?;
+                builder = builder.identity(identity);
+            }
+            (Some(_), None) | (None, Some(_)) => {
+                return Err(tls_config_error(
+                    "client_certificate and client_private_key must both be provided for mTLS"
+                        .to_string(),
+                ));
+            }
+            (None, None) => {}
+        }
+
+        Ok(builder)
+    }
+}
+
+fn read_tls_file(path: &AbsolutePathBuf) -> io::Result<Vec<u8>> {
+    std::fs::read(path).map_err(|err| {
+        io::Error::new(
+            err.kind(),
+            format!("failed to read {}: {err}", path.display()),
+        )
+    })
+}
+
+fn tls_config_error(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
 }
 
 impl ModelProviderInfo {
+    /// HTTP client for requests to this provider, with its `tls` settings applied.
+    pub(crate) fn build_http_client(&self) -> crate::error::Result<reqwest::Client> {
+        let mut builder = build_reqwest_client_builder();
+        if let Some(tls) = &self.tls {
+            builder = tls.apply(builder)?;
+        }
+        Ok(builder.build().map_err(io::Error::other)?)
+    }
+
     fn build_header_map(&self) -> crate::error::Result<HeaderMap> {
         let capacity = self.http_headers.as_ref().map_or(0, HashMap::len)
             + self.env_http_headers.as_ref().map_or(0, HashMap::len);
@@ -158,26 +327,88 @@ impl ModelProviderInfo {
         let headers = self.build_header_map()?;
         let retry = ApiRetryConfig {
             max_attempts: self.request_max_retries(),
-            base_delay: Duration::from_millis(200),
+            base_delay: Duration::from_millis(
+                self.retry_base_delay_ms
+                    .unwrap_or(DEFAULT_RETRY_BASE_DELAY_MS),
+            ),
             retry_429: false,
             retry_5xx: true,
             retry_transport: true,
         };
 
+        let mut query_params = self.query_params.clone();
+        if let Some(api_version) = self
+            .azure
+            .as_ref()
+            .and_then(|azure| azure.api_version.as_ref())
+        {
+            query_params
+                .get_or_insert_with(HashMap::new)
+                .entry("api-version".to_string())
+                .or_insert_with(|| api_version.clone());
+        }
+
         Ok(ApiProvider {
             name: self.name.clone(),
             base_url,
-            query_params: self.query_params.clone(),
+            query_params,
             headers,
             retry,
             stream_idle_timeout: self.stream_idle_timeout(),
         })
     }
 
-    /// If `env_key` is Some, returns the API key for this provider if present
-    /// (and non-empty) in the environment. If `env_key` is required but
-    /// cannot be found, returns an error.
-    pub fn api_key(&self) -> crate::error::Result<Option<String>> {
+    /// Lists the provider's models with the bearer `token`, the way sessions send requests to it,
+    /// and returns the URL and the status it answered with. A 401 or 403 means the credentials
+    /// were rejected.
+    pub async fn probe(
+        &self,
+        auth_mode: Option<AuthMode>,
+        token: Option<&str>,
+        account_id: Option<&str>,
+        timeout: Duration,
+    ) -> crate::error::Result<(String, reqwest::StatusCode)> {
+        let api_provider = self.to_api_provider(auth_mode)?;
+        let url = api_provider.url_for_path("models");
+        let mut request = self
+            .build_http_client()?
+            .get(&url)
+            .headers(api_provider.headers)
+            .timeout(timeout);
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+        if let Some(account_id) = account_id {
+            request = request.header("ChatGPT-Account-ID", account_id);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|source| CodexErr::ConnectionFailed(ConnectionFailedError { source }))?;
+        Ok((url, response.status()))
+    }
+
+    /// Returns the API key for this provider: the `api_key` secret when one is configured,
+    /// otherwise the value of `env_key` when that is set. Errors if the secret cannot be resolved
+    /// or the `env_key` variable is missing or empty.
+    pub async fn api_key(&self) -> crate::error::Result<Option<String>> {
+        self.resolve_api_key(&SecretCache::default()).await
+    }
+
+    /// [`Self::api_key`], looking the secret up through `secrets` so that a session resolves it
+    /// once rather than before every request.
+    pub(crate) async fn resolve_api_key(
+        &self,
+        secrets: &SecretCache,
+    ) -> crate::error::Result<Option<String>> {
+        if let Some(secret) = &self.api_key_secret {
+            let purpose = format!("the API key of provider '{}'", self.name);
+            return secrets
+                .resolve(secret, &purpose, &DefaultKeyringStore)
+                .await
+                .map(Some)
+                .map_err(|err| CodexErr::Fatal(err.to_string()));
+        }
         match &self.env_key {
             Some(env_key) => {
                 let api_key = std::env::var(env_key)
@@ -195,6 +426,14 @@ impl ModelProviderInfo {
         }
     }
 
+    /// Model name to send in requests. Azure deployments replace the slugs they are mapped from.
+    pub(crate) fn request_model<'a>(&'a self, model: &'a str) -> &'a str {
+        self.azure
+            .as_ref()
+            .and_then(|azure| azure.deployments.get(model))
+            .map_or(model, String::as_str)
+    }
+
     /// Effective maximum number of request retries for this provider.
     pub fn request_max_retries(&self) -> u64 {
         self.request_max_retries
@@ -209,6 +448,30 @@ impl ModelProviderInfo {
             .min(MAX_STREAM_MAX_RETRIES)
     }
 
+    /// Delay before retry number `attempt` (1-based), or `None` when the provider asked to wait
+    /// longer than `retry_max_delay_ms` and the request should not be retried.
+    pub(crate) fn retry_delay(
+        &self,
+        attempt: u64,
+        requested: Option<Duration>,
+    ) -> Option<Duration> {
+        let max_delay = Duration::from_millis(
+            self.retry_max_delay_ms
+                .unwrap_or(DEFAULT_RETRY_MAX_DELAY_MS),
+        );
+        match requested {
+            Some(requested) if requested > max_delay => None,
+            Some(requested) => Some(requested),
+            None => {
+                let base_delay = Duration::from_millis(
+                    self.retry_base_delay_ms
+                        .unwrap_or(DEFAULT_RETRY_BASE_DELAY_MS),
+                );
+                Some(backoff_from(base_delay, attempt).min(max_delay))
+            }
+        }
+    }
+
     /// Effective idle timeout for streaming responses.
     pub fn stream_idle_timeout(&self) -> Duration {
         self.stream_idle_timeout_ms
@@ -228,6 +491,7 @@ impl ModelProviderInfo {
                 .filter(|v| !v.trim().is_empty()),
             env_key: None,
             env_key_instructions: None,
+            api_key_secret: None,
             experimental_bearer_token: None,
             wire_api: WireApi::Responses,
             query_params: None,
@@ -251,8 +515,12 @@ impl ModelProviderInfo {
             request_max_retries: None,
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
+            retry_base_delay_ms: None,
+            retry_max_delay_ms: None,
             requires_openai_auth: true,
             supports_websockets: true,
+            azure: None,
+            tls: None,
         }
     }
 
@@ -316,6 +584,7 @@ pub fn create_oss_provider_with_base_url(base_url: &str, wire_api: WireApi) -> M
         base_url: Some(base_url.into()),
         env_key: None,
         env_key_instructions: None,
+        api_key_secret: None,
         experimental_bearer_token: None,
         wire_api,
         query_params: None,
@@ -324,8 +593,12 @@ pub fn create_oss_provider_with_base_url(base_url: &str, wire_api: WireApi) -> M
         request_max_retries: None,
         stream_max_retries: None,
         stream_idle_timeout_ms: None,
+        retry_base_delay_ms: None,
+        retry_max_delay_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        azure: None,
+        tls: None,
     }
 }
 
@@ -345,6 +618,7 @@ base_url = "http://localhost:11434/v1"
             base_url: Some("http://localhost:11434/v1".into()),
             env_key: None,
             env_key_instructions: None,
+            api_key_secret: None,
             experimental_bearer_token: None,
             wire_api: WireApi::Responses,
             query_params: None,
@@ -353,8 +627,12 @@ base_url = "http://localhost:11434/v1"
             request_max_retries: None,
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
+            retry_base_delay_ms: None,
+            retry_max_delay_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
+            azure: None,
+            tls: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -374,6 +652,7 @@ query_params = { api-version = "2025-04-01-preview" }
             base_url: Some("https://xxxxx.openai.azure.com/openai".into()),
             env_key: Some("AZURE_OPENAI_API_KEY".into()),
             env_key_instructions: None,
+            api_key_secret: None,
             experimental_bearer_token: None,
             wire_api: WireApi::Responses,
             query_params: Some(maplit::hashmap! {
@@ -384,14 +663,74 @@ query_params = { api-version = "2025-04-01-preview" }
             request_max_retries: None,
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
+            retry_base_delay_ms: None,
+            retry_max_delay_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
+            azure: None,
+            tls: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
         assert_eq!(expected_provider, provider);
     }
 
+    #[test]
+    fn test_deserialize_native_azure_model_provider_toml() {
+        let azure_provider_toml = r#"
+name = "Azure"
+base_url = "https://xxxxx.openai.azure.com/openai/v1"
+
+[azure]
+api_version = "preview"
+auth = "azure_cli"
+deployments = { "gpt-5.1-codex" = "codex-prod" }
+        "#;
+
+        let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
+
+        assert_eq!(
+            provider.azure,
+            Some(AzureProviderInfo {
+                api_version: Some("preview".into()),
+                deployments: maplit::hashmap! {
+                    "gpt-5.1-codex".to_string() => "codex-prod".to_string(),
+                },
+                auth: AzureAuth::AzureCli,
+            })
+        );
+        assert_eq!(provider.request_model("gpt-5.1-codex"), "codex-prod");
+        assert_eq!(provider.request_model("gpt-5.2"), "gpt-5.2");
+    }
+
+    #[test]
+    fn azure_api_version_is_sent_unless_query_params_set_it() {
+        let mut provider = create_oss_provider_with_base_url(
+            "https://xxxxx.openai.azure.com/openai/v1",
+            WireApi::Responses,
+        );
+        provider.azure = Some(AzureProviderInfo {
+            api_version: Some("preview".into()),
+            ..Default::default()
+        });
+        assert_eq!(
+            provider.to_api_provider(None).unwrap().query_params,
+            Some(maplit::hashmap! {
+                "api-version".to_string() => "preview".to_string(),
+            })
+        );
+
+        provider.query_params = Some(maplit::hashmap! {
+            "api-version".to_string() => "2025-04-01-preview".to_string(),
+        });
+        assert_eq!(
+            provider.to_api_provider(None).unwrap().query_params,
+            Some(maplit::hashmap! {
+                "api-version".to_string() => "2025-04-01-preview".to_string(),
+            })
+        );
+    }
+
     #[test]
     fn test_deserialize_example_model_provider_toml() {
         let azure_provider_toml = r#"
@@ -406,6 +745,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             base_url: Some("https://example.com".into()),
             env_key: Some("API_KEY".into()),
             env_key_instructions: None,
+            api_key_secret: None,
             experimental_bearer_token: None,
             wire_api: WireApi::Responses,
             query_params: None,
@@ -418,14 +758,148 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             request_max_retries: None,
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
+            retry_base_delay_ms: None,
+            retry_max_delay_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
+            azure: None,
+            tls: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
         assert_eq!(expected_provider, provider);
     }
 
+    #[test]
+    fn test_deserialize_anthropic_model_provider_toml() {
+        let provider_toml = r#"
+name = "Anthropic"
+base_url = "https://api.anthropic.com/v1"
+env_key = "ANTHROPIC_API_KEY"
+wire_api = "anthropic"
+        "#;
+        let expected_provider = ModelProviderInfo {
+            name: "Anthropic".into(),
+            base_url: Some("https://api.anthropic.com/v1".into()),
+            env_key: Some("ANTHROPIC_API_KEY".into()),
+            env_key_instructions: None,
+            api_key_secret: None,
+            experimental_bearer_token: None,
+            wire_api: WireApi::Anthropic,
+            query_params: None,
+            http_headers: None,
+            env_http_headers: None,
+            request_max_retries: None,
+            stream_max_retries: None,
+            stream_idle_timeout_ms: None,
+            retry_base_delay_ms: None,
+            retry_max_delay_ms: None,
+            requires_openai_auth: false,
+            supports_websockets: false,
+            azure: None,
+            tls: None,
+        };
+
+        let provider: ModelProviderInfo = toml::from_str(provider_toml).unwrap();
+        assert_eq!(expected_provider, provider);
+    }
+
+    #[test]
+    fn test_deserialize_gemini_model_provider_toml() {
+        let provider_toml = r#"
+name = "Gemini"
+base_url = "https://generativelanguage.googleapis.com/v1beta"
+env_key = "GEMINI_API_KEY"
+wire_api = "gemini"
+        "#;
+        let expected_provider = ModelProviderInfo {
+            name: "Gemini".into(),
+            base_url: Some("https://generativelanguage.googleapis.com/v1beta".into()),
+            env_key: Some("GEMINI_API_KEY".into()),
+            env_key_instructions: None,
+            api_key_secret: None,
+            experimental_bearer_token: None,
+            wire_api: WireApi::Gemini,
+            query_params: None,
+            http_headers: None,
+            env_http_headers: None,
+            request_max_retries: None,
+            stream_max_retries: None,
+            stream_idle_timeout_ms: None,
+            retry_base_delay_ms: None,
+            retry_max_delay_ms: None,
+            requires_openai_auth: false,
+            supports_websockets: false,
+            azure: None,
+            tls: None,
+        };
+
+        let provider: ModelProviderInfo = toml::from_str(provider_toml).unwrap();
+        assert_eq!(expected_provider, provider);
+    }
+
+    #[test]
+    fn test_deserialize_api_key_secret_refs() {
+        let provider_toml = r#"
+name = "OpenRouter"
+base_url = "https://openrouter.ai/api/v1"
+api_key = { keychain = "openrouter" }
+        "#;
+        let provider: ModelProviderInfo = toml::from_str(provider_toml).unwrap();
+        assert_eq!(
+            Some(SecretRef::Keychain {
+                keychain: "openrouter".to_string(),
+            }),
+            provider.api_key_secret
+        );
+
+        let provider_toml = r#"
+name = "OpenRouter"
+api_key = { command = ["op", "read", "op://vault/openrouter/key"] }
+        "#;
+        let provider: ModelProviderInfo = toml::from_str(provider_toml).unwrap();
+        assert_eq!(
+            Some(SecretRef::Command {
+                command: vec![
+                    "op".to_string(),
+                    "read".to_string(),
+                    "op://vault/openrouter/key".to_string(),
+                ],
+            }),
+            provider.api_key_secret
+        );
+
+        let provider_toml = r#"
+name = "OpenRouter"
+api_key = { keychain = "openrouter", command = ["op"] }
+        "#;
+        assert!(toml::from_str::<ModelProviderInfo>(provider_toml).is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn api_key_secret_takes_precedence_over_env_key() {
+        let provider: ModelProviderInfo = toml::from_str(
+            r#"
+name = "OpenRouter"
+env_key = "CODEX_TEST_UNSET_PROVIDER_KEY"
+api_key = { command = ["printf", "sk-or-123"] }
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            provider.api_key().await.unwrap(),
+            Some("sk-or-123".to_string())
+        );
+
+        let provider = ModelProviderInfo {
+            api_key_secret: None,
+            ..provider
+        };
+        assert!(provider.api_key().await.is_err());
+    }
+
     #[test]
     fn test_deserialize_chat_wire_api_shows_helpful_error() {
         let provider_toml = r#"
@@ -438,4 +912,80 @@ wire_api = "chat"
         let err = toml::from_str::<ModelProviderInfo>(provider_toml).unwrap_err();
         assert!(err.to_string().contains(CHAT_WIRE_API_REMOVED_ERROR));
     }
+
+    #[test]
+    fn retry_delay_honors_requested_delay_within_max() {
+        let provider = ModelProviderInfo {
+            retry_base_delay_ms: Some(1_000),
+            retry_max_delay_ms: Some(5_000),
+            ..create_oss_provider_with_base_url("http://localhost:11434/v1", WireApi::Responses)
+        };
+
+        assert_eq!(
+            provider.retry_delay(1, Some(Duration::from_secs(2))),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(provider.retry_delay(1, Some(Duration::from_secs(6))), None);
+        assert_eq!(provider.retry_delay(10, None), Some(Duration::from_secs(5)));
+        let first = provider.retry_delay(1, None).expect("delay");
+        assert!(
+            (Duration::from_millis(900)..=Duration::from_millis(1_100)).contains(&first),
+            "unexpected first retry delay: {first:?}"
+        );
+    }
+
+    #[test]
+    fn tls_settings_are_read_relative_to_the_config_and_validated() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("client.pem"), "not a certificate").unwrap();
+        let provider_toml = r#"
+name = "Gateway"
+base_url = "https://llm.internal.example/v1"
+
+[tls]
+ca_certificate = "ca.pem"
+client_certificate = "client.pem"
+pin_ca_certificate = true
+        "#;
+
+        let provider: ModelProviderInfo = {
+            let _guard = codex_utils_absolute_path::AbsolutePathBufGuard::new(dir.path());
+            toml::from_str(provider_toml).unwrap()
+        };
+        let tls = provider.tls.expect("tls settings");
+        assert_eq!(
+            tls.client_certificate
+                .as_ref()
+                .map(AbsolutePathBuf::as_path),
+            Some(dir.path().join("client.pem").as_path())
+        );
+        assert!(tls.pin_ca_certificate);
+
+        let err = tls.apply(reqwest::Client::builder()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert!(err.to_string().contains("ca.pem"), "{err}");
+
+        let err = ProviderTlsConfig {
+            ca_certificate: None,
+            ..tls.clone()
+        }
+        .apply(reqwest::Client::builder())
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "pin_ca_certificate requires ca_certificate"
+        );
+
+        let err = ProviderTlsConfig {
+            ca_certificate: None,
+            pin_ca_certificate: false,
+            ..tls
+        }
+        .apply(reqwest::Client::builder())
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "client_certificate and client_private_key must both be provided for mTLS"
+        );
+    }
 }
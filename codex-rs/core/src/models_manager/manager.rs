@@ -4,13 +4,13 @@ use crate::api_bridge::map_api_error;
 use crate::auth::AuthManager;
 use crate::auth::AuthMode;
 use crate::config::Config;
-use crate::default_client::build_reqwest_client;
 use crate::error::CodexErr;
 use crate::error::Result as CoreResult;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::models_manager::collaboration_mode_presets::builtin_collaboration_mode_presets;
 use crate::models_manager::model_info;
 use crate::models_manager::model_presets::builtin_model_presets;
+use crate::secrets::SecretCache;
 use codex_api::ModelsClient;
 use codex_api::ReqwestTransport;
 use codex_protocol::config_types::CollaborationModeMask;
@@ -51,6 +51,7 @@ pub struct ModelsManager {
     etag: RwLock<Option<String>>,
     cache_manager: ModelsCacheManager,
     provider: ModelProviderInfo,
+    provider_secrets: SecretCache,
 }
 
 impl ModelsManager {
@@ -67,6 +68,7 @@ impl ModelsManager {
             etag: RwLock::new(None),
             cache_manager,
             provider: ModelProviderInfo::create_openai_provider(),
+            provider_secrets: SecretCache::default(),
         }
     }
 
@@ -212,8 +214,9 @@ impl ModelsManager {
         let auth = self.auth_manager.auth().await;
         let auth_mode = self.auth_manager.auth_mode();
         let api_provider = self.provider.to_api_provider(auth_mode)?;
-        let api_auth = auth_provider_from_auth(auth.clone(), &self.provider)?;
-        let transport = ReqwestTransport::new(build_reqwest_client());
+        let api_auth =
+            auth_provider_from_auth(auth.clone(), &self.provider, &self.provider_secrets).await?;
+        let transport = ReqwestTransport::new(self.provider.build_http_client()?);
         let client = ModelsClient::new(transport, api_provider, api_auth);
 
         let client_version = crate::models_manager::client_version_to_whole();
@@ -331,6 +334,7 @@ impl ModelsManager {
             etag: RwLock::new(None),
             cache_manager,
             provider,
+            provider_secrets: SecretCache::default(),
         }
     }
 
@@ -423,6 +427,7 @@ mod tests {
             base_url: Some(base_url),
             env_key: None,
             env_key_instructions: None,
+            api_key_secret: None,
             experimental_bearer_token: None,
             wire_api: WireApi::Responses,
             query_params: None,
@@ -431,8 +436,12 @@ mod tests {
             request_max_retries: Some(0),
             stream_max_retries: Some(0),
             stream_idle_timeout_ms: Some(5_000),
+            retry_base_delay_ms: None,
+            retry_max_delay_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
+            azure: None,
+            tls: None,
         }
     }
 
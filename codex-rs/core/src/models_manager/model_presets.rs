@@ -355,6 +355,48 @@ fn gpt_52_codex_upgrade() -> ModelUpgrade {
     }
 }
 
+/// Models offered by the model picker when the active provider uses the Gemini API. Gemini
+/// picks its own thinking budget, so each model has a single reasoning effort.
+static GEMINI_PRESETS: Lazy<Vec<ModelPreset>> = Lazy::new(|| {
+    [
+        (
+            "gemini-2.5-pro",
+            "Gemini's most capable model for complex coding tasks.",
+            true,
+        ),
+        (
+            "gemini-2.5-flash",
+            "Faster, lower-cost Gemini model for everyday tasks.",
+            false,
+        ),
+    ]
+    .into_iter()
+    .map(|(model, description, is_default)| ModelPreset {
+        id: model.to_string(),
+        model: model.to_string(),
+        display_name: model.to_string(),
+        description: description.to_string(),
+        default_reasoning_effort: ReasoningEffort::Medium,
+        supported_reasoning_efforts: vec![ReasoningEffortPreset {
+            effort: ReasoningEffort::Medium,
+            description: "Thinking budget chosen by the model".to_string(),
+        }],
+        supports_personality: false,
+        is_default,
+        upgrade: None,
+        show_in_picker: true,
+        supported_in_api: true,
+        input_modalities: default_input_modalities(),
+    })
+    .collect()
+});
+
+/// Presets for providers using the Gemini API, whose models are not listed by the models
+/// manager.
+pub fn gemini_model_presets() -> Vec<ModelPreset> {
+    GEMINI_PRESETS.clone()
+}
+
 pub(super) fn builtin_model_presets(_auth_mode: Option<AuthMode>) -> Vec<ModelPreset> {
     PRESETS.iter().cloned().collect()
 }
@@ -368,4 +410,13 @@ mod tests {
         let default_models = PRESETS.iter().filter(|preset| preset.is_default).count();
         assert!(default_models == 1);
     }
+
+    #[test]
+    fn only_one_gemini_model_is_default() {
+        let default_models = GEMINI_PRESETS
+            .iter()
+            .filter(|preset| preset.is_default)
+            .count();
+        assert!(default_models == 1);
+    }
 }
@@ -89,6 +89,7 @@ pub fn build_provider(
         trace_exporter,
         metrics_exporter,
         runtime_metrics,
+        prometheus_metrics: config.otel.prometheus_listen.is_some(),
     })
 }
 
@@ -0,0 +1,388 @@
+//! Checking a turn's final message against `final_output_json_schema`.
+//!
+//! The schema is sent to the model as a response format, but not every provider enforces it, so
+//! the final message is validated again here before it is reported as structured output. Only the
+//! keywords that response format schemas use are understood: `type`, `properties`, `required`,
+//! `additionalProperties`, `items`, `enum`, `const`, `anyOf`/`oneOf`/`allOf`, local `$ref`s, and
+//! the length, size, range, and `pattern` bounds. Other keywords are ignored.
+
+use serde_json::Value;
+
+/// How many times the model is asked to correct a final message that does not match the schema.
+pub(crate) const MAX_FINAL_OUTPUT_RETRIES: u32 = 2;
+
+/// Parses `message` as JSON and validates it against `schema`. The error says what is wrong and
+/// where, for example `$.items[1].name: expected string, got number`.
+pub(crate) fn parse_final_output(schema: &Value, message: Option<&str>) -> Result<Value, String> {
+    let Some(message) = message else {
+        return Err("the turn ended without a final message".to_string());
+    };
+    let output: Value = serde_json::from_str(message.trim())
+        .map_err(|err| format!("the final message is not valid JSON: {err}"))?;
+    Validator { root: schema }.validate(schema, &output, "$")?;
+    Ok(output)
+}
+
+/// The developer message that asks the model to try again after `error`.
+pub(crate) fn retry_instructions(error: &str) -> String {
+    format!(
+        "Your final message must be a single JSON value that matches the output schema for this turn, with no other text. It does not: {error}. Reply again with only the corrected JSON."
+    )
+}
+
+struct Validator<'a> {
+    root: &'a Value,
+}
+
+impl<'a> Validator<'a> {
+    fn validate(&self, schema: &'a Value, value: &Value, path: &str) -> Result<(), String> {
+        let schema = match schema {
+            Value::Bool(true) => return Ok(()),
+            Value::Bool(false) => return Err(format!("{path}: no value is allowed here")),
+            Value::Object(schema) => schema,
+            _ => return Ok(()),
+        };
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let target = self.resolve(reference).ok_or_else(|| {
+                format!("{path}: the schema has an unresolvable $ref {reference}")
+            })?;
+            self.validate(target, value, path)?;
+        }
+
+        if let Some(types) = schema.get("type") {
+            let allowed: Vec<&str> = match types {
+                Value::String(name) => vec![name.as_str()],
+                Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+                _ => Vec::new(),
+            };
+            if !allowed.is_empty() && !allowed.iter().any(|name| has_type(value, name)) {
+                return Err(format!(
+                    "{path}: expected {}, got {}",
+                    allowed.join(" or "),
+                    type_name(value)
+                ));
+            }
+        }
+
+        if let Some(options) = schema.get("enum").and_then(Value::as_array)
+            && !options.contains(value)
+        {
+            return Err(format!(
+                "{path}: {value} is not one of {}",
+                Value::from(options.clone())
+            ));
+        }
+        if let Some(expected) = schema.get("const")
+            && expected != value
+        {
+            return Err(format!("{path}: expected {expected}, got {value}"));
+        }
+
+        if let Some(all_of) = schema.get("allOf").and_then(Value::as_array) {
+            for subschema in all_of {
+                self.validate(subschema, value, path)?;
+            }
+        }
+        if let Some(options) = schema.get("anyOf").and_then(Value::as_array) {
+            let mut errors = Vec::new();
+            for option in options {
+                match self.validate(option, value, path) {
+                    Ok(()) => break,
+                    Err(err) => errors.push(err),
+                }
+            }
+            if errors.len() == options.len() {
+                return Err(format!(
+                    "{path}: does not match any allowed schema ({})",
+                    errors.join("; ")
+                ));
+            }
+        }
+        if let Some(options) = schema.get("oneOf").and_then(Value::as_array) {
+            let mut errors = Vec::new();
+            for option in options {
+                if let Err(err) = self.validate(option, value, path) {
+                    errors.push(err);
+                }
+            }
+            let matched = options.len() - errors.len();
+            if matched == 0 {
+                return Err(format!(
+                    "{path}: does not match any allowed schema ({})",
+                    errors.join("; ")
+                ));
+            }
+            if matched > 1 {
+                return Err(format!(
+                    "{path}: matches {matched} schemas where exactly one is allowed"
+                ));
+            }
+        }
+
+        match value {
+            Value::Object(object) => {
+                if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                    for name in required.iter().filter_map(Value::as_str) {
+                        if !object.contains_key(name) {
+                            return Err(format!("{path}: missing required property \"{name}\""));
+                        }
+                    }
+                }
+                let properties = schema.get("properties").and_then(Value::as_object);
+                for (name, property) in object {
+                    let property_path = format!("{path}.{name}");
+                    match properties.and_then(|properties| properties.get(name)) {
+                        Some(property_schema) => {
+                            self.validate(property_schema, property, &property_path)?;
+                        }
+                        None => match schema.get("additionalProperties") {
+                            Some(Value::Bool(false)) => {
+                                return Err(format!("{path}: unexpected property \"{name}\""));
+                            }
+                            Some(additional) => {
+                                self.validate(additional, property, &property_path)?;
+                            }
+                            None => {}
+                        },
+                    }
+                }
+            }
+            Value::Array(items) => {
+                check_bounds(schema, "minItems", "maxItems", items.len(), "items", path)?;
+                if let Some(item_schema) = schema.get("items") {
+                    for (index, item) in items.iter().enumerate() {
+                        self.validate(item_schema, item, &format!("{path}[{index}]"))?;
+                    }
+                }
+            }
+            Value::String(text) => {
+                let length = text.chars().count();
+                check_bounds(schema, "minLength", "maxLength", length, "characters", path)?;
+                if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+                    let regex = regex_lite::Regex::new(pattern).map_err(|err| {
+                        format!("{path}: the schema has an invalid pattern {pattern:?}: {err}")
+                    })?;
+                    if !regex.is_match(text) {
+                        return Err(format!("{path}: {value} does not match {pattern:?}"));
+                    }
+                }
+            }
+            Value::Number(number) => {
+                if let Some(number) = number.as_f64() {
+                    check_range(schema, number, path)?;
+                }
+            }
+            Value::Null | Value::Bool(_) => {}
+        }
+        Ok(())
+    }
+
+    /// Resolves `#`, `#/$defs/name`, and other JSON pointers into the root schema.
+    fn resolve(&self, reference: &str) -> Option<&'a Value> {
+        let pointer = reference.strip_prefix('#')?;
+        self.root.pointer(pointer)
+    }
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.as_f64().is_some_and(|number| number.fract() == 0.0),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn check_bounds(
+    schema: &serde_json::Map<String, Value>,
+    min_keyword: &str,
+    max_keyword: &str,
+    actual: usize,
+    unit: &str,
+    path: &str,
+) -> Result<(), String> {
+    let actual = actual as u64;
+    if let Some(min) = schema.get(min_keyword).and_then(Value::as_u64)
+        && actual < min
+    {
+        return Err(format!(
+            "{path}: expected at least {min} {unit}, got {actual}"
+        ));
+    }
+    if let Some(max) = schema.get(max_keyword).and_then(Value::as_u64)
+        && actual > max
+    {
+        return Err(format!(
+            "{path}: expected at most {max} {unit}, got {actual}"
+        ));
+    }
+    Ok(())
+}
+
+fn check_range(
+    schema: &serde_json::Map<String, Value>,
+    number: f64,
+    path: &str,
+) -> Result<(), String> {
+    let bound = |keyword: &str| schema.get(keyword).and_then(Value::as_f64);
+    if let Some(minimum) = bound("minimum")
+        && number < minimum
+    {
+        return Err(format!("{path}: {number} is less than {minimum}"));
+    }
+    if let Some(maximum) = bound("maximum")
+        && number > maximum
+    {
+        return Err(format!("{path}: {number} is greater than {maximum}"));
+    }
+    if let Some(minimum) = bound("exclusiveMinimum")
+        && number <= minimum
+    {
+        return Err(format!("{path}: {number} is not greater than {minimum}"));
+    }
+    if let Some(maximum) = bound("exclusiveMaximum")
+        && number >= maximum
+    {
+        return Err(format!("{path}: {number} is not less than {maximum}"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "summary": { "type": "string", "minLength": 1 },
+                "files": {
+                    "type": "array",
+                    "items": { "$ref": "#/$defs/file" },
+                    "maxItems": 2
+                },
+                "status": { "enum": ["ok", "failed"] }
+            },
+            "required": ["summary", "files", "status"],
+            "additionalProperties": false,
+            "$defs": {
+                "file": {
+                    "type": "object",
+                    "properties": {
+                        "path": { "type": "string", "pattern": "^src/" },
+                        "lines": { "type": ["integer", "null"], "minimum": 0 }
+                    },
+                    "required": ["path", "lines"],
+                    "additionalProperties": false
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn accepts_matching_output() {
+        let message = r#"
+            {"summary": "done", "files": [{"path": "src/lib.rs", "lines": 3}, {"path": "src/main.rs", "lines": null}], "status": "ok"}
+        "#;
+
+        assert_eq!(
+            parse_final_output(&schema(), Some(message)),
+            Ok(json!({
+                "summary": "done",
+                "files": [
+                    { "path": "src/lib.rs", "lines": 3 },
+                    { "path": "src/main.rs", "lines": null }
+                ],
+                "status": "ok"
+            }))
+        );
+    }
+
+    #[test]
+    fn reports_where_output_does_not_match() {
+        let cases = [
+            (None, "the turn ended without a final message"),
+            (
+                Some(r#"{"summary": "done", "files": [], "status": "ok", "extra": 1}"#),
+                "$: unexpected property \"extra\"",
+            ),
+            (
+                Some(r#"{"summary": "done", "files": []}"#),
+                "$: missing required property \"status\"",
+            ),
+            (
+                Some(r#"{"summary": "", "files": [], "status": "ok"}"#),
+                "$.summary: expected at least 1 characters, got 0",
+            ),
+            (
+                Some(r#"{"summary": "done", "files": [], "status": "maybe"}"#),
+                "$.status: \"maybe\" is not one of [\"ok\",\"failed\"]",
+            ),
+            (
+                Some(
+                    r#"{"summary": "done", "files": [{"path": "src/a.rs", "lines": 1}, {"path": "docs/b.md", "lines": 1}], "status": "ok"}"#,
+                ),
+                "$.files[1].path: \"docs/b.md\" does not match \"^src/\"",
+            ),
+            (
+                Some(
+                    r#"{"summary": "done", "files": [{"path": "src/a.rs", "lines": 1.5}], "status": "ok"}"#,
+                ),
+                "$.files[0].lines: expected integer or null, got number",
+            ),
+        ];
+
+        for (message, expected) in cases {
+            assert_eq!(
+                parse_final_output(&schema(), message),
+                Err(expected.to_string()),
+                "{message:?}"
+            );
+        }
+        assert!(
+            parse_final_output(&schema(), Some("Here is the JSON: {}"))
+                .unwrap_err()
+                .starts_with("the final message is not valid JSON: ")
+        );
+    }
+
+    #[test]
+    fn one_of_requires_exactly_one_match() {
+        let schema = json!({
+            "oneOf": [
+                { "type": "integer" },
+                { "type": "number", "minimum": 10 }
+            ]
+        });
+
+        assert_eq!(parse_final_output(&schema, Some("3")), Ok(json!(3)));
+        assert_eq!(parse_final_output(&schema, Some("10.5")), Ok(json!(10.5)));
+        assert_eq!(
+            parse_final_output(&schema, Some("12")),
+            Err("$: matches 2 schemas where exactly one is allowed".to_string())
+        );
+        assert_eq!(
+            parse_final_output(&schema, Some("2.5")),
+            Err("$: does not match any allowed schema ($: expected integer, got number; $: 2.5 is less than 10)".to_string())
+        );
+    }
+}
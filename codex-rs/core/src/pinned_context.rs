@@ -0,0 +1,238 @@
+//! Context items pinned by the user.
+//!
+//! Pinned items are rendered as tagged user-role messages. They are treated as session prefix
+//! content (see [`crate::session_prefix`]) so they never create user-turn boundaries and are
+//! never folded into a compaction summary. After every compaction they are re-attached verbatim
+//! as part of the session's initial context.
+//!
+//! A pinned file larger than `file_excerpt_threshold_bytes` is captured as an excerpt (see
+//! [`crate::file_excerpt`]); `@path:120-180` adds those lines to it.
+
+use std::path::Path;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::PinnedContextEntry;
+use codex_protocol::protocol::PinnedContextRolloutItem;
+use codex_protocol::protocol::PinnedContextSource;
+
+use crate::file_excerpt::MAX_RANGES;
+use crate::file_excerpt::excerpt_file;
+use crate::file_excerpt::needs_excerpt;
+use crate::file_excerpt::split_line_ranges;
+use crate::session_prefix::PINNED_CONTEXT_CLOSE_TAG;
+use crate::session_prefix::PINNED_CONTEXT_OPEN_TAG;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::truncate_text;
+
+/// Upper bound for the contents captured from a pinned file.
+const PINNED_FILE_MAX_TOKENS: usize = 10_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PinnedContextItem {
+    pub(crate) entry: PinnedContextEntry,
+    /// Model-visible contents captured when the item was pinned.
+    contents: String,
+    /// Whether `contents` is an excerpt of a large file rather than the whole file.
+    excerpted: bool,
+}
+
+impl PinnedContextItem {
+    /// Resolves `source` into a pinned item. File sources are read relative to `cwd` and their
+    /// contents are captured immediately so later edits do not silently change pinned context.
+    /// Files larger than `excerpt_threshold_bytes` are captured as an excerpt.
+    pub(crate) async fn load(
+        id: String,
+        source: PinnedContextSource,
+        cwd: &Path,
+        excerpt_threshold_bytes: usize,
+    ) -> std::io::Result<Self> {
+        let (contents, excerpted) = match &source {
+            PinnedContextSource::Text { text } => (text.trim().to_string(), false),
+            PinnedContextSource::File { path } => {
+                let mut file = cwd.join(path);
+                let mut ranges = Vec::new();
+                if !tokio::fs::try_exists(&file).await.unwrap_or(false) {
+                    let reference = path.to_string_lossy();
+                    let (path, requested) = split_line_ranges(&reference);
+                    file = cwd.join(path);
+                    ranges = requested;
+                }
+                if ranges.len() > MAX_RANGES {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!("at most {MAX_RANGES} line ranges can be pinned at once"),
+                    ));
+                }
+                let contents = tokio::fs::read_to_string(&file).await?;
+                if needs_excerpt(&contents, excerpt_threshold_bytes) {
+                    (excerpt_file(&file, &contents, &ranges), true)
+                } else {
+                    (
+                        truncate_text(&contents, TruncationPolicy::Tokens(PINNED_FILE_MAX_TOKENS)),
+                        false,
+                    )
+                }
+            }
+        };
+        Ok(Self {
+            entry: PinnedContextEntry { id, source },
+            contents,
+            excerpted,
+        })
+    }
+
+    /// Rebuilds an item recorded in the rollout by [`PinnedContextItem::to_rollout_item`].
+    pub(crate) fn from_rollout(
+        entry: PinnedContextEntry,
+        contents: String,
+        excerpted: bool,
+    ) -> Self {
+        Self {
+            entry,
+            contents,
+            excerpted,
+        }
+    }
+
+    /// The rollout record of pinning this item, with its captured contents.
+    pub(crate) fn to_rollout_item(&self) -> PinnedContextRolloutItem {
+        PinnedContextRolloutItem::Pinned {
+            entry: self.entry.clone(),
+            contents: self.contents.clone(),
+            excerpted: self.excerpted,
+        }
+    }
+
+    pub(crate) fn id(&self) -> &str {
+        &self.entry.id
+    }
+
+    pub(crate) fn is_excerpt(&self) -> bool {
+        self.excerpted
+    }
+}
+
+impl From<&PinnedContextItem> for ResponseItem {
+    fn from(item: &PinnedContextItem) -> Self {
+        let header = match &item.entry.source {
+            PinnedContextSource::Text { .. } => String::new(),
+            PinnedContextSource::File { path } => format!("<path>{}</path>\n", path.display()),
+        };
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: format!(
+                    "{PINNED_CONTEXT_OPEN_TAG}\n{header}{contents}\n{PINNED_CONTEXT_CLOSE_TAG}",
+                    contents = item.contents
+                ),
+            }],
+            end_turn: None,
+            phase: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_excerpt::DEFAULT_FILE_EXCERPT_THRESHOLD_BYTES;
+    use crate::file_excerpt::is_file_excerpt;
+    use crate::session_prefix::is_session_prefix;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn renders_text_and_file_pins_as_session_prefix_messages() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("ARCH.md"), "no globals\n").expect("write file");
+
+        let text_pin = PinnedContextItem::load(
+            "pin-1".to_string(),
+            PinnedContextSource::Text {
+                text: "  keep the core crate sync-free  ".to_string(),
+            },
+            dir.path(),
+            DEFAULT_FILE_EXCERPT_THRESHOLD_BYTES,
+        )
+        .await
+        .expect("load text pin");
+        let file_pin = PinnedContextItem::load(
+            "pin-2".to_string(),
+            PinnedContextSource::File {
+                path: PathBuf::from("ARCH.md"),
+            },
+            dir.path(),
+            DEFAULT_FILE_EXCERPT_THRESHOLD_BYTES,
+        )
+        .await
+        .expect("load file pin");
+
+        let rendered: Vec<String> = [&text_pin, &file_pin]
+            .into_iter()
+            .map(|item| match ResponseItem::from(item) {
+                ResponseItem::Message { content, .. } => match content.as_slice() {
+                    [ContentItem::InputText { text }] => text.clone(),
+                    other => panic!("unexpected content: {other:?}"),
+                },
+                other => panic!("unexpected item: {other:?}"),
+            })
+            .collect();
+
+        assert_eq!(
+            rendered,
+            vec![
+                "<pinned_context>\nkeep the core crate sync-free\n</pinned_context>".to_string(),
+                "<pinned_context>\n<path>ARCH.md</path>\nno globals\n\n</pinned_context>"
+                    .to_string(),
+            ]
+        );
+        assert!(rendered.iter().all(|text| is_session_prefix(text)));
+    }
+
+    #[tokio::test]
+    async fn large_files_are_pinned_as_excerpts_with_requested_lines() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let contents: String = (1..=200).map(|n| format!("fn f{n}() {{}}\n")).collect();
+        std::fs::write(dir.path().join("big.rs"), contents).expect("write file");
+
+        let pin = PinnedContextItem::load(
+            "pin-1".to_string(),
+            PinnedContextSource::File {
+                path: PathBuf::from("big.rs:150-151"),
+            },
+            dir.path(),
+            1_000,
+        )
+        .await
+        .expect("load file pin");
+
+        assert!(pin.is_excerpt());
+        assert!(is_file_excerpt(&pin.contents));
+        assert!(
+            pin.contents
+                .contains("Lines 150-151:\nL150: fn f150() {}\nL151: fn f151() {}")
+        );
+    }
+
+    #[tokio::test]
+    async fn pinning_too_many_line_ranges_is_rejected() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("big.rs"), "fn f() {}\n").expect("write file");
+        let ranges: Vec<String> = (1..=MAX_RANGES + 1).map(|n| n.to_string()).collect();
+
+        let err = PinnedContextItem::load(
+            "pin-1".to_string(),
+            PinnedContextSource::File {
+                path: PathBuf::from(format!("big.rs:{}", ranges.join(","))),
+            },
+            dir.path(),
+            1_000,
+        )
+        .await
+        .expect_err("too many ranges");
+
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+}
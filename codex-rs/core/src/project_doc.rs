@@ -11,13 +11,19 @@
 //! 2.  Collect every `AGENTS.md` found from the repository root down to the
 //!     current working directory (inclusive) and concatenate their contents in
 //!     that order.
-//! 3.  We do **not** walk past the Git root.
+//! 3.  When several files contain a section under the same Markdown heading,
+//!     only the section from the file closest to the working directory is
+//!     kept, so nested directories can override guidance from their parents.
+//!     An overridden section's subsections are dropped with it, while each
+//!     file's intro (including a leading `# Title`) is always kept.
+//! 4.  We do **not** walk past the Git root.
 
 use crate::config::Config;
 use crate::features::Feature;
 use crate::skills::SkillMetadata;
 use crate::skills::render_skills_section;
 use dunce::canonicalize as normalize_path;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use tokio::io::AsyncReadExt;
 use tracing::error;
@@ -117,24 +123,45 @@ pub(crate) async fn get_user_instructions(
 
 /// Attempt to locate and load the project documentation.
 ///
-/// On success returns `Ok(Some(contents))` where `contents` is the
-/// concatenation of all discovered docs. If no documentation file is found the
-/// function returns `Ok(None)`. Unexpected I/O failures bubble up as `Err` so
+/// On success returns `Ok(Some(contents))` where `contents` is the merge of
+/// all discovered docs (see `merge_project_docs`). If no documentation file is
+/// found the function returns `Ok(None)`. Unexpected I/O failures bubble up as `Err` so
 /// callers can decide how to handle them.
 pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String>> {
+    let (merged, _) = load_project_docs(config).await?;
+    if merged.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(merged))
+    }
+}
+
+/// The project docs that contribute to the instructions returned by
+/// `read_project_docs`, ordered from the repository root to the cwd. Unlike
+/// `discover_project_doc_paths`, this leaves out files that were cut by
+/// `project_doc_max_bytes` and files whose sections are all overridden.
+pub async fn loaded_project_doc_paths(config: &Config) -> std::io::Result<Vec<PathBuf>> {
+    let (_, paths) = load_project_docs(config).await?;
+    Ok(paths)
+}
+
+/// Reads the discovered docs within the byte budget and merges them, returning
+/// the merged text and the paths of the docs it contains.
+async fn load_project_docs(config: &Config) -> std::io::Result<(String, Vec<PathBuf>)> {
     let max_total = config.project_doc_max_bytes;
 
     if max_total == 0 {
-        return Ok(None);
+        return Ok((String::new(), Vec::new()));
     }
 
     let paths = discover_project_doc_paths(config)?;
     if paths.is_empty() {
-        return Ok(None);
+        return Ok((String::new(), Vec::new()));
     }
 
     let mut remaining: u64 = max_total as u64;
     let mut parts: Vec<String> = Vec::new();
+    let mut part_paths: Vec<PathBuf> = Vec::new();
 
     for p in paths {
         if remaining == 0 {
@@ -163,15 +190,147 @@ pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String
         let text = String::from_utf8_lossy(&data).to_string();
         if !text.trim().is_empty() {
             parts.push(text);
+            part_paths.push(p);
             remaining = remaining.saturating_sub(data.len() as u64);
         }
     }
 
-    if parts.is_empty() {
-        Ok(None)
-    } else {
-        Ok(Some(parts.join("\n\n")))
+    let (merged, included) = merge_project_docs(&parts);
+    let included_paths = included
+        .into_iter()
+        .map(|index| part_paths[index].clone())
+        .collect();
+    Ok((merged, included_paths))
+}
+
+/// A run of lines in a project doc, starting at a Markdown heading (or at the
+/// start of the file for the intro before the first section).
+struct DocSection<'a> {
+    heading: Option<String>,
+    /// Headings of the sections this one is nested in, outermost first.
+    parents: Vec<String>,
+    text: &'a str,
+}
+
+/// Concatenates docs ordered from the repository root to the cwd. A section
+/// whose heading also appears in a doc closer to the cwd is dropped together
+/// with its subsections, so the closest doc wins on conflicting headings.
+/// Returns the merged text and the indices of the docs that contributed to it.
+fn merge_project_docs(docs: &[String]) -> (String, Vec<usize>) {
+    let sections: Vec<Vec<DocSection<'_>>> =
+        docs.iter().map(|doc| split_doc_sections(doc)).collect();
+
+    let mut closest: HashMap<&str, usize> = HashMap::new();
+    for (index, doc_sections) in sections.iter().enumerate() {
+        for heading in doc_sections.iter().filter_map(|s| s.heading.as_deref()) {
+            closest.insert(heading, index);
+        }
+    }
+
+    let mut included = Vec::new();
+    let mut merged = Vec::new();
+    for (index, doc_sections) in sections.iter().enumerate() {
+        let doc = doc_sections
+            .iter()
+            .filter(|section| {
+                section
+                    .heading
+                    .iter()
+                    .chain(&section.parents)
+                    .all(|heading| closest.get(heading.as_str()) == Some(&index))
+            })
+            .map(|section| section.text)
+            .collect::<String>();
+        if !doc.trim().is_empty() {
+            included.push(index);
+            merged.push(doc);
+        }
     }
+    (merged.join("\n\n"), included)
+}
+
+/// Splits `doc` at its headings. The intro before the first heading has no
+/// heading; a leading `# Title` that is the doc's only level-1 heading is part
+/// of the intro, since every doc in a repository tends to have one.
+fn split_doc_sections(doc: &str) -> Vec<DocSection<'_>> {
+    let headings = doc_headings(doc);
+    let title = match headings.as_slice() {
+        [(offset, first), rest @ ..]
+            if doc[..*offset].trim().is_empty()
+                && heading_level(first) == 1
+                && rest.iter().all(|(_, heading)| heading_level(heading) > 1) =>
+        {
+            Some(*offset)
+        }
+        _ => None,
+    };
+
+    let mut sections = Vec::new();
+    let mut start = 0;
+    let mut current: Option<String> = None;
+    let mut open: Vec<String> = Vec::new();
+    for (offset, key) in headings {
+        if Some(offset) == title {
+            continue;
+        }
+        if offset > start {
+            sections.push(DocSection {
+                heading: current.take(),
+                parents: open.clone(),
+                text: &doc[start..offset],
+            });
+        }
+        if let Some(heading) = current.take() {
+            open.push(heading);
+        }
+        let level = heading_level(&key);
+        open.retain(|parent| heading_level(parent) < level);
+        current = Some(key);
+        start = offset;
+    }
+    if doc.len() > start {
+        sections.push(DocSection {
+            heading: current,
+            parents: open,
+            text: &doc[start..],
+        });
+    }
+    sections
+}
+
+/// Byte offsets and normalized keys of the headings in `doc`, skipping lines
+/// inside fenced code blocks.
+fn doc_headings(doc: &str) -> Vec<(usize, String)> {
+    let mut headings = Vec::new();
+    let mut offset = 0;
+    let mut in_fence = false;
+    for line in doc.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        } else if !in_fence && let Some(key) = heading_key(trimmed) {
+            headings.push((offset, key));
+        }
+        offset += line.len();
+    }
+    headings
+}
+
+fn heading_level(key: &str) -> usize {
+    key.chars().take_while(|c| *c == '#').count()
+}
+
+/// Normalizes an ATX heading line (`## Testing`) so headings match regardless
+/// of case and spacing. Returns `None` for lines that are not headings.
+fn heading_key(line: &str) -> Option<String> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let title = line.get(level..)?;
+    if !(1..=6).contains(&level) || !title.starts_with(' ') {
+        return None;
+    }
+    let title = title.trim().trim_end_matches('#').trim().to_lowercase();
+    let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+    Some(format!("{} {title}", "#".repeat(level)))
 }
 
 /// Discover the list of AGENTS.md files using the same search rules as
@@ -180,6 +339,10 @@ pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String
 /// directory (inclusive). Symlinks are allowed. When `project_doc_max_bytes`
 /// is zero, returns an empty list.
 pub fn discover_project_doc_paths(config: &Config) -> std::io::Result<Vec<PathBuf>> {
+    if config.project_doc_max_bytes == 0 {
+        return Ok(Vec::new());
+    }
+
     let mut dir = config.cwd.clone();
     if let Ok(canon) = normalize_path(&dir) {
         dir = canon;
@@ -496,6 +659,99 @@ mod tests {
         assert_eq!(res, "root doc\n\ncrate doc");
     }
 
+    /// Sections with the same heading are taken from the doc closest to the cwd.
+    #[tokio::test]
+    async fn closest_doc_wins_on_conflicting_sections() {
+        let repo = tempfile::tempdir().expect("tempdir");
+        std::fs::write(
+            repo.path().join(".git"),
+            "gitdir: /path/to/actual/git/dir\n",
+        )
+        .unwrap();
+        fs::write(
+            repo.path().join("AGENTS.md"),
+            "Root intro.\n\n## Testing\nRun `just test`.\n\n## Style\n```sh\n## testing\n```\n",
+        )
+        .unwrap();
+        let nested = repo.path().join("services/api");
+        std::fs::create_dir_all(&nested).unwrap();
+        fs::write(nested.join("AGENTS.md"), "## testing\nRun `pytest`.\n").unwrap();
+
+        let mut cfg = make_config(&repo, 4096, None).await;
+        cfg.cwd = nested.clone();
+
+        let res = get_user_instructions(&cfg, None)
+            .await
+            .expect("doc expected");
+        assert_eq!(
+            res,
+            "Root intro.\n\n## Style\n```sh\n## testing\n```\n\n\n## testing\nRun `pytest`.\n"
+        );
+        assert_eq!(
+            discover_project_doc_paths(&cfg).expect("discover paths"),
+            vec![
+                normalize_path(repo.path().join("AGENTS.md")).expect("canonical root doc"),
+                normalize_path(nested.join("AGENTS.md")).expect("canonical nested doc"),
+            ]
+        );
+    }
+
+    /// Overriding a section drops its subsections too, keeps the root's title
+    /// and intro, and only docs that contribute text are reported as loaded.
+    #[tokio::test]
+    async fn overridden_sections_take_their_subsections_with_them() {
+        let repo = tempfile::tempdir().expect("tempdir");
+        std::fs::write(
+            repo.path().join(".git"),
+            "gitdir: /path/to/actual/git/dir\n",
+        )
+        .unwrap();
+        fs::write(
+            repo.path().join("AGENTS.md"),
+            "# Guidelines\nRoot intro.\n\n## Testing\nRun `just test`.\n\n### Snapshots\nUse insta.\n\n## Style\nKeep it tidy.\n",
+        )
+        .unwrap();
+        let nested = repo.path().join("services/api");
+        std::fs::create_dir_all(&nested).unwrap();
+        fs::write(
+            nested.join("AGENTS.md"),
+            "# Guidelines\n\n## Testing\nRun `pytest`.\n",
+        )
+        .unwrap();
+        let leaf = nested.join("handlers");
+        std::fs::create_dir_all(&leaf).unwrap();
+        fs::write(leaf.join("AGENTS.md"), "## Style\nFollow PEP 8.\n").unwrap();
+
+        let mut cfg = make_config(&repo, 4096, None).await;
+        cfg.cwd = leaf.clone();
+
+        let res = get_user_instructions(&cfg, None)
+            .await
+            .expect("doc expected");
+        assert_eq!(
+            res,
+            "# Guidelines\nRoot intro.\n\n\n\n# Guidelines\n\n## Testing\nRun `pytest`.\n\n\n## Style\nFollow PEP 8.\n"
+        );
+
+        let root_doc = normalize_path(repo.path().join("AGENTS.md")).expect("canonical root doc");
+        let nested_doc = normalize_path(nested.join("AGENTS.md")).expect("canonical nested doc");
+        assert_eq!(
+            loaded_project_doc_paths(&cfg).await.expect("loaded paths"),
+            vec![
+                root_doc.clone(),
+                nested_doc,
+                normalize_path(leaf.join("AGENTS.md")).expect("canonical leaf doc"),
+            ]
+        );
+
+        // A budget that the root doc uses up leaves the nested docs out.
+        cfg.project_doc_max_bytes = 40;
+        assert_eq!(
+            loaded_project_doc_paths(&cfg).await.expect("loaded paths"),
+            vec![root_doc]
+        );
+    }
+
     /// AGENTS.override.md is preferred over AGENTS.md when both are present.
     #[tokio::test]
     async fn agents_local_md_preferred() {
@@ -0,0 +1,295 @@
+//! Masking of secrets configured under `[redaction]` in config.toml.
+//!
+//! The configured environment variables are removed from spawned command environments through
+//! `shell_environment_policy.exclude` when the config is loaded. [`SecretRedactor`] covers the
+//! other half: it masks the configured patterns, and the values those variables hold in the Codex
+//! process, in tool output before it is recorded in history, sent to the model, or written to the
+//! rollout file.
+
+use std::borrow::Cow;
+
+use codex_protocol::mcp::CallToolResult;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::protocol::ExecCommandEndEvent;
+use regex_lite::Regex;
+use serde_json::Value;
+
+use crate::config::types::EnvironmentVariablePattern;
+use crate::config::types::Redaction;
+use crate::exec::ExecToolCallOutput;
+use crate::tools::context::ToolOutput;
+
+pub(crate) const REDACTED_SECRET: &str = "[REDACTED_SECRET]";
+
+/// Values shorter than this are not masked: they are too likely to match ordinary output.
+const MIN_MASKED_VALUE_LEN: usize = 8;
+
+#[derive(Debug, Default)]
+pub(crate) struct SecretRedactor {
+    patterns: Vec<Regex>,
+}
+
+impl SecretRedactor {
+    pub(crate) fn new(redaction: &Redaction) -> Result<Self, regex_lite::Error> {
+        Self::with_env_vars(redaction, std::env::vars())
+    }
+
+    fn with_env_vars<I>(redaction: &Redaction, vars: I) -> Result<Self, regex_lite::Error>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut patterns = redaction
+            .patterns
+            .iter()
+            .map(|pattern| Regex::new(pattern))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let env_vars: Vec<EnvironmentVariablePattern> = redaction
+            .env_vars
+            .iter()
+            .map(|name| EnvironmentVariablePattern::new_case_insensitive(name))
+            .collect();
+        let mut values: Vec<String> = vars
+            .into_iter()
+            .filter(|(name, value)| {
+                value.len() >= MIN_MASKED_VALUE_LEN
+                    && env_vars.iter().any(|pattern| pattern.matches(name))
+            })
+            .map(|(_, value)| value)
+            .collect();
+        // Mask longer values first so a value containing another is not left half-masked.
+        values.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        values.dedup();
+        for value in values {
+            patterns.push(Regex::new(&regex_lite::escape(&value))?);
+        }
+
+        Ok(Self { patterns })
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    pub(crate) fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut redacted = Cow::Borrowed(text);
+        for pattern in &self.patterns {
+            let replaced = match pattern.replace_all(&redacted, REDACTED_SECRET) {
+                Cow::Owned(replaced) => replaced,
+                Cow::Borrowed(_) => continue,
+            };
+            redacted = Cow::Owned(replaced);
+        }
+        redacted
+    }
+
+    fn redact_in_place(&self, text: &mut String) {
+        let redacted = match self.redact(text) {
+            Cow::Owned(redacted) => redacted,
+            Cow::Borrowed(_) => return,
+        };
+        *text = redacted;
+    }
+
+    /// Masks the text of a function tool's output. MCP results are masked by
+    /// [`Self::redact_mcp_result`] where the call is made, before its end event is sent.
+    pub(crate) fn redact_tool_output(&self, mut output: ToolOutput) -> ToolOutput {
+        if self.is_empty() {
+            return output;
+        }
+        if let ToolOutput::Function { body, .. } = &mut output {
+            match body {
+                FunctionCallOutputBody::Text(text) => self.redact_in_place(text),
+                FunctionCallOutputBody::ContentItems(items) => {
+                    for item in items {
+                        if let FunctionCallOutputContentItem::InputText { text } = item {
+                            self.redact_in_place(text);
+                        }
+                    }
+                }
+            }
+        }
+        output
+    }
+
+    /// Masks the output of a command run outside the tool registry, such as a user `!` command.
+    pub(crate) fn redact_exec_output(&self, output: &mut ExecToolCallOutput) {
+        if self.is_empty() {
+            return;
+        }
+        self.redact_in_place(&mut output.stdout.text);
+        self.redact_in_place(&mut output.stderr.text);
+        self.redact_in_place(&mut output.aggregated_output.text);
+    }
+
+    /// Masks every string in an MCP tool result except binary payloads (`data` and `blob`).
+    pub(crate) fn redact_mcp_result(&self, result: &mut Result<CallToolResult, String>) {
+        if self.is_empty() {
+            return;
+        }
+        match result {
+            Ok(result) => {
+                for block in &mut result.content {
+                    self.redact_json(block);
+                }
+                if let Some(structured_content) = &mut result.structured_content {
+                    self.redact_json(structured_content);
+                }
+            }
+            Err(message) => self.redact_in_place(message),
+        }
+    }
+
+    fn redact_json(&self, value: &mut Value) {
+        match value {
+            Value::String(text) => self.redact_in_place(text),
+            Value::Array(values) => {
+                for value in values {
+                    self.redact_json(value);
+                }
+            }
+            Value::Object(map) => {
+                for (key, value) in map {
+                    if key != "data" && key != "blob" {
+                        self.redact_json(value);
+                    }
+                }
+            }
+            Value::Null | Value::Bool(_) | Value::Number(_) => {}
+        }
+    }
+
+    pub(crate) fn redact_exec_end(&self, event: &mut ExecCommandEndEvent) {
+        if self.is_empty() {
+            return;
+        }
+        self.redact_in_place(&mut event.aggregated_output);
+        self.redact_in_place(&mut event.formatted_output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn redactor(redaction: Redaction, vars: &[(&str, &str)]) -> SecretRedactor {
+        let vars = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()));
+        SecretRedactor::with_env_vars(&redaction, vars).expect("valid redaction config")
+    }
+
+    #[test]
+    fn masks_configured_patterns() {
+        let redactor = redactor(
+            Redaction {
+                env_vars: Vec::new(),
+                patterns: vec![r"ghp_[A-Za-z0-9]{8}".to_string()],
+            },
+            &[],
+        );
+
+        assert_eq!(
+            redactor.redact("token=ghp_abcd1234 ok"),
+            "token=[REDACTED_SECRET] ok"
+        );
+        assert!(matches!(redactor.redact("nothing here"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn masks_values_of_configured_env_vars() {
+        let redactor = redactor(
+            Redaction {
+                env_vars: vec!["aws_secret_*".to_string(), "SHORT".to_string()],
+                patterns: Vec::new(),
+            },
+            &[
+                ("AWS_SECRET_ACCESS_KEY", "wJalr+XUtnFEMI/K7MDENG"),
+                ("SHORT", "abc"),
+                ("HOME", "/home/user/projects"),
+            ],
+        );
+
+        assert_eq!(
+            redactor.redact("key wJalr+XUtnFEMI/K7MDENG in /home/user/projects abc"),
+            "key [REDACTED_SECRET] in /home/user/projects abc"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        let redaction = Redaction {
+            env_vars: Vec::new(),
+            patterns: vec!["(unclosed".to_string()],
+        };
+
+        assert!(SecretRedactor::with_env_vars(&redaction, Vec::new()).is_err());
+    }
+
+    #[test]
+    fn redacts_function_output_text() {
+        let redactor = redactor(
+            Redaction {
+                env_vars: Vec::new(),
+                patterns: vec!["hunter2".to_string()],
+            },
+            &[],
+        );
+
+        let output = redactor.redact_tool_output(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text("password: hunter2".to_string()),
+            success: Some(true),
+        });
+
+        let ToolOutput::Function { body, .. } = output else {
+            panic!("expected function output");
+        };
+        assert_eq!(
+            body,
+            FunctionCallOutputBody::Text("password: [REDACTED_SECRET]".to_string())
+        );
+    }
+
+    #[test]
+    fn redacts_mcp_result_text_but_not_binary_payloads() {
+        let redactor = redactor(
+            Redaction {
+                env_vars: Vec::new(),
+                patterns: vec!["hunter2".to_string()],
+            },
+            &[],
+        );
+        let mut result = Ok(CallToolResult {
+            content: vec![
+                serde_json::json!({"type": "text", "text": "password: hunter2"}),
+                serde_json::json!({"type": "image", "data": "hunter2", "mimeType": "image/png"}),
+            ],
+            structured_content: Some(serde_json::json!({"rows": [{"secret": "hunter2"}]})),
+            is_error: None,
+            meta: None,
+        });
+
+        redactor.redact_mcp_result(&mut result);
+
+        assert_eq!(
+            result,
+            Ok(CallToolResult {
+                content: vec![
+                    serde_json::json!({"type": "text", "text": "password: [REDACTED_SECRET]"}),
+                    serde_json::json!({"type": "image", "data": "hunter2", "mimeType": "image/png"}),
+                ],
+                structured_content: Some(
+                    serde_json::json!({"rows": [{"secret": "[REDACTED_SECRET]"}]})
+                ),
+                is_error: None,
+                meta: None,
+            })
+        );
+
+        let mut error = Err("login failed for hunter2".to_string());
+        redactor.redact_mcp_result(&mut error);
+        assert_eq!(error, Err("login failed for [REDACTED_SECRET]".to_string()));
+    }
+}
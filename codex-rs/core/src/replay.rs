@@ -0,0 +1,210 @@
+//! Replaying the events recorded in a rollout file.
+//!
+//! A replay sends a rollout's recorded events to an event channel with the gaps between them
+//! preserved, scaled by [`ReplayOptions::speed`], so clients can be exercised against real traffic
+//! without a model. Nothing is executed and nothing is written to a rollout.
+
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use async_channel::Sender;
+use chrono::DateTime;
+use chrono::Utc;
+use codex_protocol::protocol::Event;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::RolloutLine;
+
+use crate::rollout::read_rollout_text;
+
+/// Longest pause between two replayed events when [`ReplayOptions::max_gap`] is not set.
+pub const DEFAULT_MAX_REPLAY_GAP: Duration = Duration::from_secs(5);
+
+/// Slowest supported playback rate; slower positive speeds are raised to it.
+pub const MIN_REPLAY_SPEED: f64 = 0.01;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReplayOptions {
+    /// Playback rate relative to the recording; `2.0` is twice as fast. Zero sends every event
+    /// without waiting, and positive rates below [`MIN_REPLAY_SPEED`] are raised to it.
+    pub speed: f64,
+    /// Longest pause between two events, so idle time in the recording is skipped.
+    pub max_gap: Duration,
+}
+
+impl Default for ReplayOptions {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            max_gap: DEFAULT_MAX_REPLAY_GAP,
+        }
+    }
+}
+
+/// Sends the events recorded in the rollout at `path` to `tx_event` and returns how many were
+/// sent. Events inside a turn carry the turn id as their id, as they do in a live session; other
+/// events carry `sub_id`.
+pub async fn replay_rollout(
+    path: &Path,
+    options: ReplayOptions,
+    sub_id: &str,
+    tx_event: &Sender<Event>,
+) -> io::Result<usize> {
+    let text = read_rollout_text(path).await?;
+    let mut previous: Option<DateTime<Utc>> = None;
+    let mut turn_id: Option<String> = None;
+    let mut sent = 0;
+
+    for line in text.lines() {
+        let Ok(rollout_line) = serde_json::from_str::<RolloutLine>(line) else {
+            continue;
+        };
+        let RolloutItem::EventMsg(msg) = rollout_line.item else {
+            continue;
+        };
+
+        let timestamp = DateTime::parse_from_rfc3339(&rollout_line.timestamp)
+            .ok()
+            .map(|timestamp| timestamp.with_timezone(&Utc));
+        if let (Some(previous), Some(timestamp)) = (previous, timestamp) {
+            let delay = replay_delay(timestamp - previous, options);
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+        }
+        previous = timestamp.or(previous);
+
+        if let EventMsg::TurnStarted(event) = &msg {
+            turn_id = Some(event.turn_id.clone());
+        }
+        let id = turn_id.clone().unwrap_or_else(|| sub_id.to_string());
+        if matches!(msg, EventMsg::TurnComplete(_) | EventMsg::TurnAborted(_)) {
+            turn_id = None;
+        }
+
+        tx_event
+            .send(Event { id, msg })
+            .await
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "event channel closed"))?;
+        sent += 1;
+    }
+
+    Ok(sent)
+}
+
+fn replay_delay(gap: chrono::Duration, options: ReplayOptions) -> Duration {
+    if options.speed.is_nan() || options.speed <= 0.0 {
+        return Duration::ZERO;
+    }
+    let gap = gap.to_std().unwrap_or_default();
+    let speed = options.speed.max(MIN_REPLAY_SPEED);
+    // `Duration::div_f64` panics when the scaled gap does not fit in a `Duration`.
+    Duration::try_from_secs_f64(gap.as_secs_f64() / speed)
+        .unwrap_or(options.max_gap)
+        .min(options.max_gap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::AgentMessageEvent;
+    use codex_protocol::protocol::TurnCompleteEvent;
+    use codex_protocol::protocol::TurnStartedEvent;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    fn line(timestamp: &str, msg: EventMsg) -> String {
+        serde_json::to_string(&RolloutLine {
+            timestamp: timestamp.to_string(),
+            item: RolloutItem::EventMsg(msg),
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn replays_events_with_turn_ids() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("rollout.jsonl");
+        let agent_message = EventMsg::AgentMessage(AgentMessageEvent {
+            message: "hello".to_string(),
+        });
+        let lines = [
+            line(
+                "2025-01-01T00:00:00.000Z",
+                EventMsg::TurnStarted(TurnStartedEvent {
+                    turn_id: "turn-1".to_string(),
+                    model_context_window: None,
+                    collaboration_mode_kind: Default::default(),
+                }),
+            ),
+            line("2025-01-01T00:00:01.000Z", agent_message.clone()),
+            line(
+                "2025-01-01T00:00:02.000Z",
+                EventMsg::TurnComplete(TurnCompleteEvent {
+                    turn_id: "turn-1".to_string(),
+                    last_agent_message: Some("hello".to_string()),
+                    usage: None,
+                }),
+            ),
+            line("2025-01-01T00:10:00.000Z", agent_message),
+        ];
+        std::fs::write(&path, lines.join("\n")).unwrap();
+
+        let (tx_event, rx_event) = async_channel::unbounded();
+        let options = ReplayOptions {
+            speed: 0.0,
+            ..Default::default()
+        };
+        let sent = replay_rollout(&path, options, "replay", &tx_event)
+            .await
+            .unwrap();
+
+        assert_eq!(sent, 4);
+        let ids: Vec<String> = std::iter::from_fn(|| rx_event.try_recv().ok())
+            .map(|event| event.id)
+            .collect();
+        assert_eq!(ids, vec!["turn-1", "turn-1", "turn-1", "replay"]);
+    }
+
+    #[test]
+    fn scales_and_caps_gaps() {
+        let options = ReplayOptions {
+            speed: 2.0,
+            max_gap: Duration::from_secs(5),
+        };
+        assert_eq!(
+            replay_delay(chrono::Duration::seconds(4), options),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            replay_delay(chrono::Duration::minutes(10), options),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            replay_delay(chrono::Duration::seconds(-1), options),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn extreme_speeds_do_not_panic() {
+        let slow = ReplayOptions {
+            speed: f64::MIN_POSITIVE,
+            max_gap: Duration::MAX,
+        };
+        assert_eq!(
+            replay_delay(chrono::Duration::seconds(1), slow),
+            Duration::from_secs(100)
+        );
+        assert!(replay_delay(chrono::Duration::MAX, slow) > Duration::ZERO);
+        let fast = ReplayOptions {
+            speed: f64::INFINITY,
+            ..Default::default()
+        };
+        assert_eq!(
+            replay_delay(chrono::Duration::seconds(1), fast),
+            Duration::ZERO
+        );
+    }
+}
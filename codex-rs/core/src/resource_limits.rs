@@ -0,0 +1,255 @@
+//! Enforcement of `[resource_limits]` for commands Codex runs on the user's behalf.
+//!
+//! CPU time, memory, and open file limits are set by [`wrap_command`], which
+//! `SandboxManager::transform` applies to every command it prepares: the command is started by a
+//! `/bin/sh` prelude that lowers the limits with `ulimit` and then execs it. That way the limits
+//! reach commands spawned through a PTY, pipes, or the classic shell tool alike, and are inherited
+//! by everything the command starts; each process gets its own allowance. The output limit is
+//! enforced while reading the command's output in `exec.rs`, which kills the process group once
+//! the command has written too much. Once the command has finished, [`detect_exceeded_limit`]
+//! works out which limit stopped it so the tool result can tell the model.
+
+use std::fmt;
+
+use crate::config::types::ResourceLimits;
+use crate::exec::EXIT_CODE_SIGNAL_BASE;
+
+/// Lowercased messages that allocation failures print in common runtimes.
+const OUT_OF_MEMORY_MARKERS: [&str; 5] = [
+    "cannot allocate memory",
+    "out of memory",
+    "std::bad_alloc",
+    "memoryerror",
+    "memory allocation of",
+];
+
+/// Lowercased `strerror(EMFILE)`.
+const TOO_MANY_OPEN_FILES_MARKER: &str = "too many open files";
+
+/// The configured limit that stopped a command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceLimitKind {
+    CpuTime,
+    Memory,
+    OpenFiles,
+    OutputSize,
+}
+
+impl fmt::Display for ResourceLimitKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::CpuTime => "CPU time",
+            Self::Memory => "memory",
+            Self::OpenFiles => "open files",
+            Self::OutputSize => "output size",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Shell used to apply the rlimits before exec'ing the command.
+#[cfg(unix)]
+const LIMIT_SHELL: &str = "/bin/sh";
+
+/// Returns `command` started through a shell prelude that applies the rlimits in `limits`, or
+/// `command` unchanged when none are configured. Limits can only be lowered: the soft limit is set
+/// before the hard one, and a configured value above the inherited hard limit leaves the
+/// inherited limits in place.
+#[cfg(unix)]
+pub(crate) fn wrap_command(limits: &ResourceLimits, command: Vec<String>) -> Vec<String> {
+    let mut settings = Vec::new();
+    if let Some(secs) = limits.cpu_time_secs {
+        // The soft limit delivers SIGXCPU; the hard limit a second later stops processes that
+        // catch it.
+        settings.push(('t', secs, secs.saturating_add(1)));
+    }
+    #[cfg(target_os = "linux")]
+    if let Some(megabytes) = limits.memory_mb {
+        let kilobytes = megabytes.saturating_mul(1024);
+        settings.push(('v', kilobytes, kilobytes));
+    }
+    if let Some(open_files) = limits.open_files {
+        settings.push(('n', open_files, open_files));
+    }
+    if settings.is_empty() {
+        return command;
+    }
+
+    let mut script = String::new();
+    for (flag, soft, hard) in settings {
+        script.push_str(&format!(
+            "ulimit -S -{flag} {soft} 2>/dev/null; ulimit -H -{flag} {hard} 2>/dev/null; "
+        ));
+    }
+    script.push_str("exec \"$@\"");
+
+    let mut wrapped = Vec::with_capacity(4 + command.len());
+    wrapped.push(LIMIT_SHELL.to_string());
+    wrapped.push("-c".to_string());
+    wrapped.push(script);
+    wrapped.push("sh".to_string());
+    wrapped.extend(command);
+    wrapped
+}
+
+/// Rlimits are not supported on this platform; only the output limit applies.
+#[cfg(not(unix))]
+pub(crate) fn wrap_command(_limits: &ResourceLimits, command: Vec<String>) -> Vec<String> {
+    command
+}
+
+/// Returns the limit that most likely stopped a command that exited with `exit_code` (or was
+/// killed by `signal`) and printed `output`. CPU time is recognised by SIGXCPU, either on the
+/// command itself or reported by a shell as `128 + SIGXCPU`. Running out of memory or file
+/// descriptors makes the command fail on its own, so those are recognised by the error it
+/// printed. Only configured limits are reported.
+pub(crate) fn detect_exceeded_limit(
+    limits: &ResourceLimits,
+    signal: Option<i32>,
+    exit_code: i32,
+    output: &str,
+) -> Option<ResourceLimitKind> {
+    if signal.is_none() && exit_code == 0 {
+        return None;
+    }
+    let cpu_time_signal = signal.or_else(|| exit_code.checked_sub(EXIT_CODE_SIGNAL_BASE));
+    if limits.cpu_time_secs.is_some() && cpu_time_signal.is_some_and(is_cpu_time_signal) {
+        return Some(ResourceLimitKind::CpuTime);
+    }
+    let output = output.to_ascii_lowercase();
+    if limits.memory_mb.is_some()
+        && OUT_OF_MEMORY_MARKERS
+            .iter()
+            .any(|marker| output.contains(marker))
+    {
+        return Some(ResourceLimitKind::Memory);
+    }
+    if limits.open_files.is_some() && output.contains(TOO_MANY_OPEN_FILES_MARKER) {
+        return Some(ResourceLimitKind::OpenFiles);
+    }
+    None
+}
+
+#[cfg(unix)]
+fn is_cpu_time_signal(signal: i32) -> bool {
+    signal == libc::SIGXCPU
+}
+
+#[cfg(not(unix))]
+fn is_cpu_time_signal(_signal: i32) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn all_limits() -> ResourceLimits {
+        ResourceLimits {
+            cpu_time_secs: Some(60),
+            memory_mb: Some(512),
+            open_files: Some(256),
+            output_bytes: Some(1024),
+        }
+    }
+
+    #[test]
+    fn detects_limits_from_errors_in_output() {
+        assert_eq!(
+            detect_exceeded_limit(
+                &all_limits(),
+                None,
+                1,
+                "npm ERR! EMFILE: Too many open files, open 'package.json'"
+            ),
+            Some(ResourceLimitKind::OpenFiles)
+        );
+        assert_eq!(
+            detect_exceeded_limit(
+                &all_limits(),
+                None,
+                134,
+                "terminate called after throwing an instance of 'std::bad_alloc'"
+            ),
+            Some(ResourceLimitKind::Memory)
+        );
+        assert_eq!(
+            detect_exceeded_limit(&all_limits(), None, 0, "Too many open files"),
+            None,
+            "successful commands never report a limit"
+        );
+    }
+
+    #[test]
+    fn only_configured_limits_are_reported() {
+        let limits = ResourceLimits {
+            memory_mb: Some(512),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            detect_exceeded_limit(&limits, None, 1, "Too many open files"),
+            None
+        );
+        assert_eq!(
+            detect_exceeded_limit(&ResourceLimits::default(), None, 1, "out of memory"),
+            None
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn wraps_command_only_when_rlimits_are_configured() {
+        let command = vec!["cargo".to_string(), "test".to_string()];
+
+        assert_eq!(
+            wrap_command(
+                &ResourceLimits {
+                    output_bytes: Some(1024),
+                    ..Default::default()
+                },
+                command.clone()
+            ),
+            command
+        );
+        assert_eq!(
+            wrap_command(
+                &ResourceLimits {
+                    cpu_time_secs: Some(60),
+                    open_files: Some(256),
+                    ..Default::default()
+                },
+                command
+            ),
+            vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                "ulimit -S -t 60 2>/dev/null; ulimit -H -t 61 2>/dev/null; \
+                 ulimit -S -n 256 2>/dev/null; ulimit -H -n 256 2>/dev/null; exec \"$@\""
+                    .to_string(),
+                "sh".to_string(),
+                "cargo".to_string(),
+                "test".to_string(),
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn detects_cpu_time_from_signal_or_shell_exit_code() {
+        assert_eq!(
+            detect_exceeded_limit(&all_limits(), Some(libc::SIGXCPU), -1, ""),
+            Some(ResourceLimitKind::CpuTime)
+        );
+        assert_eq!(
+            detect_exceeded_limit(
+                &all_limits(),
+                None,
+                EXIT_CODE_SIGNAL_BASE + libc::SIGXCPU,
+                ""
+            ),
+            Some(ResourceLimitKind::CpuTime)
+        );
+    }
+}
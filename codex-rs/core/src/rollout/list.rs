@@ -15,6 +15,7 @@ use uuid::Uuid;
 
 use super::ARCHIVED_SESSIONS_SUBDIR;
 use super::SESSIONS_SUBDIR;
+use super::segments::read_rollout_head_lines;
 use crate::protocol::EventMsg;
 use crate::state_db;
 use codex_file_search as file_search;
@@ -989,11 +990,9 @@ impl<'a> ProviderMatcher<'a> {
 }
 
 async fn read_head_summary(path: &Path, head_limit: usize) -> io::Result<HeadTailSummary> {
-    use tokio::io::AsyncBufReadExt;
-
-    let file = tokio::fs::File::open(path).await?;
-    let reader = tokio::io::BufReader::new(file);
-    let mut lines = reader.lines();
+    let mut lines = read_rollout_head_lines(path, head_limit + USER_EVENT_SCAN_LIMIT)
+        .await?
+        .into_iter();
     let mut summary = HeadTailSummary::default();
     let mut lines_scanned = 0usize;
 
@@ -1002,12 +1001,8 @@ async fn read_head_summary(path: &Path, head_limit: usize) -> io::Result<HeadTai
             && !summary.saw_user_event
             && lines_scanned < head_limit + USER_EVENT_SCAN_LIMIT)
     {
-        let line_opt = lines.next_line().await?;
-        let Some(line) = line_opt else { break };
+        let Some(line) = lines.next() else { break };
         let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
         lines_scanned += 1;
 
         let parsed: Result<RolloutLine, _> = serde_json::from_str(trimmed);
@@ -1046,7 +1041,9 @@ async fn read_head_summary(path: &Path, head_limit: usize) -> io::Result<HeadTai
             RolloutItem::TurnContext(_) => {
                 // Not included in `head`; skip.
             }
-            RolloutItem::Compacted(_) => {
+            RolloutItem::Compacted(_)
+            | RolloutItem::SessionApproval(_)
+            | RolloutItem::PinnedContext(_) => {
                 // Not included in `head`; skip.
             }
             RolloutItem::EventMsg(ev) => {
@@ -1073,21 +1070,15 @@ async fn read_head_summary(path: &Path, head_limit: usize) -> io::Result<HeadTai
 /// Read up to `HEAD_RECORD_LIMIT` records from the start of the rollout file at `path`.
 /// This should be enough to produce a summary including the session meta line.
 pub async fn read_head_for_summary(path: &Path) -> io::Result<Vec<serde_json::Value>> {
-    use tokio::io::AsyncBufReadExt;
-
-    let file = tokio::fs::File::open(path).await?;
-    let reader = tokio::io::BufReader::new(file);
-    let mut lines = reader.lines();
+    // Events are interleaved with the head records, so scan past them as `read_head_summary` does.
+    let lines = read_rollout_head_lines(path, HEAD_RECORD_LIMIT + USER_EVENT_SCAN_LIMIT).await?;
     let mut head = Vec::new();
 
-    while head.len() < HEAD_RECORD_LIMIT {
-        let Some(line) = lines.next_line().await? else {
+    for line in lines {
+        if head.len() >= HEAD_RECORD_LIMIT {
             break;
-        };
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
         }
+        let trimmed = line.trim();
         if let Ok(rollout_line) = serde_json::from_str::<RolloutLine>(trimmed) {
             match rollout_line.item {
                 RolloutItem::SessionMeta(session_meta_line) => {
@@ -1102,7 +1093,9 @@ pub async fn read_head_for_summary(path: &Path) -> io::Result<Vec<serde_json::Va
                 }
                 RolloutItem::Compacted(_)
                 | RolloutItem::TurnContext(_)
-                | RolloutItem::EventMsg(_) => {}
+                | RolloutItem::EventMsg(_)
+                | RolloutItem::SessionApproval(_)
+                | RolloutItem::PinnedContext(_) => {}
             }
         }
     }
@@ -1117,6 +1110,17 @@ fn strip_user_message_prefix(text: &str) -> &str {
     }
 }
 
+/// Read the model of the first turn recorded in the rollout file at `path`, if any.
+pub async fn read_thread_model(path: &Path) -> io::Result<Option<String>> {
+    let lines = read_rollout_head_lines(path, HEAD_RECORD_LIMIT + USER_EVENT_SCAN_LIMIT).await?;
+    Ok(lines.iter().find_map(|line| {
+        match serde_json::from_str::<RolloutLine>(line.trim()).ok()?.item {
+            RolloutItem::TurnContext(turn_context) => Some(turn_context.model),
+            _ => None,
+        }
+    }))
+}
+
 /// Read the SessionMetaLine from the head of a rollout file for reuse by
 /// callers that need the session metadata (e.g. to derive a cwd for config).
 pub async fn read_session_meta_line(path: &Path) -> io::Result<SessionMetaLine> {
@@ -10,8 +10,10 @@ pub const INTERACTIVE_SESSION_SOURCES: &[SessionSource] =
 pub(crate) mod error;
 pub mod list;
 pub(crate) mod metadata;
+pub mod patches;
 pub(crate) mod policy;
 pub mod recorder;
+pub(crate) mod segments;
 pub(crate) mod session_index;
 pub(crate) mod truncation;
 
@@ -22,8 +24,15 @@ pub use list::find_thread_path_by_id_str;
 #[deprecated(note = "use find_thread_path_by_id_str")]
 pub use list::find_thread_path_by_id_str as find_conversation_path_by_id_str;
 pub use list::rollout_date_parts;
+pub use patches::RecordedCommand;
+pub use patches::RecordedPatch;
+pub use patches::recorded_commands;
+pub use patches::recorded_patches;
 pub use recorder::RolloutRecorder;
 pub use recorder::RolloutRecorderParams;
+pub use segments::read_rollout_text;
+pub use segments::remove_rollout;
+pub use segments::rename_rollout;
 pub use session_index::find_thread_name_by_id;
 pub use session_index::find_thread_path_by_name_str;
 
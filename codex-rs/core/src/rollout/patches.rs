@@ -0,0 +1,406 @@
+//! `apply_patch` calls and other commands that may change files, recorded in a rollout and
+//! grouped by turn.
+//!
+//! Rollouts persisted in the limited event mode only keep the model's tool calls and their
+//! outputs, not the `PatchApplyEnd` and `ExecCommandEnd` events, so both are read from the calls
+//! too. The events win when they were recorded, since they carry the exact changes and exit codes.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::models::ShellCommandToolCallParams;
+use codex_protocol::models::ShellToolCallParams;
+use codex_protocol::parse_command::ParsedCommand;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::FileChange;
+use codex_protocol::protocol::RolloutItem;
+use serde::Deserialize;
+
+use crate::parse_command::parse_command;
+
+const APPLY_PATCH_TOOL_NAME: &str = "apply_patch";
+
+/// First line `apply_patch` prints after applying a patch, kept in the tool output.
+const APPLY_PATCH_SUCCESS_MARKER: &str = "Success. Updated the following files:";
+
+/// One `apply_patch` call made by the agent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedPatch {
+    /// 1-based turn of the session the call was made in.
+    pub turn: usize,
+    pub call_id: String,
+    /// The patch as the agent wrote it, when the tool call itself was recorded.
+    pub patch: Option<String>,
+    /// Whether the patch applied, or `None` if its result was not recorded.
+    pub success: Option<bool>,
+    /// The changes the patch made, keyed by absolute path. Empty when only the call was recorded;
+    /// `patch` then describes them.
+    pub changes: HashMap<PathBuf, FileChange>,
+}
+
+/// A shell command that ran and was not recognized as only reading files, so it may have changed
+/// some.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedCommand {
+    /// 1-based turn of the session the command ran in.
+    pub turn: usize,
+    pub command: Vec<String>,
+    /// The command's exit code, or `None` when only the call was recorded.
+    pub exit_code: Option<i32>,
+}
+
+#[derive(Deserialize)]
+struct ExecCommandArguments {
+    cmd: String,
+}
+
+#[derive(Deserialize)]
+struct ApplyPatchArguments {
+    input: String,
+    #[serde(default)]
+    dry_run: bool,
+}
+
+/// Returns every `apply_patch` call in `items`, in the order they were made.
+///
+/// Turns are counted from the `TurnStarted` events; calls recorded before the first one belong
+/// to turn 1.
+pub fn recorded_patches(items: &[RolloutItem]) -> Vec<RecordedPatch> {
+    let mut patches: Vec<RecordedPatch> = Vec::new();
+    let mut index_by_call: HashMap<String, usize> = HashMap::new();
+    for (turn, item) in with_turns(items) {
+        match item {
+            RolloutItem::ResponseItem(ResponseItem::CustomToolCall {
+                name,
+                input,
+                call_id,
+                ..
+            }) if name == APPLY_PATCH_TOOL_NAME => {
+                index_by_call.insert(call_id.clone(), patches.len());
+                patches.push(RecordedPatch {
+                    turn,
+                    call_id: call_id.clone(),
+                    patch: Some(input.clone()),
+                    success: None,
+                    changes: HashMap::new(),
+                });
+            }
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            }) if name == APPLY_PATCH_TOOL_NAME => {
+                // A dry run only reports whether the patch would apply.
+                let Ok(arguments) = serde_json::from_str::<ApplyPatchArguments>(arguments) else {
+                    continue;
+                };
+                if arguments.dry_run {
+                    continue;
+                }
+                index_by_call.insert(call_id.clone(), patches.len());
+                patches.push(RecordedPatch {
+                    turn,
+                    call_id: call_id.clone(),
+                    patch: Some(arguments.input),
+                    success: None,
+                    changes: HashMap::new(),
+                });
+            }
+            RolloutItem::ResponseItem(ResponseItem::CustomToolCallOutput { call_id, output }) => {
+                record_patch_output(&mut patches, &index_by_call, call_id, output);
+            }
+            RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput { call_id, output }) => {
+                if let Some(output) = output.text_content() {
+                    record_patch_output(&mut patches, &index_by_call, call_id, output);
+                }
+            }
+            RolloutItem::EventMsg(EventMsg::PatchApplyEnd(event)) => {
+                match index_by_call.get(&event.call_id) {
+                    Some(&index) => {
+                        patches[index].success = Some(event.success);
+                        patches[index].changes = event.changes.clone();
+                    }
+                    // Patches applied through the shell tool have no `apply_patch` call.
+                    None => patches.push(RecordedPatch {
+                        turn,
+                        call_id: event.call_id.clone(),
+                        patch: None,
+                        success: Some(event.success),
+                        changes: event.changes.clone(),
+                    }),
+                }
+            }
+            _ => {}
+        }
+    }
+    patches
+}
+
+/// Fills in whether a patch applied from its tool output, unless its `PatchApplyEnd` already did.
+fn record_patch_output(
+    patches: &mut [RecordedPatch],
+    index_by_call: &HashMap<String, usize>,
+    call_id: &str,
+    output: &str,
+) {
+    if let Some(&index) = index_by_call.get(call_id)
+        && patches[index].success.is_none()
+    {
+        patches[index].success = Some(output.contains(APPLY_PATCH_SUCCESS_MARKER));
+    }
+}
+
+/// Returns the commands in `items` that may have changed files, numbered like
+/// [`recorded_patches`].
+pub fn recorded_commands(items: &[RolloutItem]) -> Vec<RecordedCommand> {
+    let mut commands: Vec<(RecordedCommand, bool)> = Vec::new();
+    let mut index_by_call: HashMap<String, usize> = HashMap::new();
+    for (turn, item) in with_turns(items) {
+        match item {
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            }) => {
+                let Some(command) = shell_call_command(name, arguments) else {
+                    continue;
+                };
+                let may_change_files = may_change_files(&parse_command(&command));
+                index_by_call.insert(call_id.clone(), commands.len());
+                commands.push((
+                    RecordedCommand {
+                        turn,
+                        command,
+                        exit_code: None,
+                    },
+                    may_change_files,
+                ));
+            }
+            RolloutItem::ResponseItem(ResponseItem::LocalShellCall {
+                call_id: Some(call_id),
+                action: LocalShellAction::Exec(action),
+                ..
+            }) => {
+                let may_change_files = may_change_files(&parse_command(&action.command));
+                index_by_call.insert(call_id.clone(), commands.len());
+                commands.push((
+                    RecordedCommand {
+                        turn,
+                        command: action.command.clone(),
+                        exit_code: None,
+                    },
+                    may_change_files,
+                ));
+            }
+            RolloutItem::EventMsg(EventMsg::ExecCommandEnd(event)) => {
+                let recorded = RecordedCommand {
+                    turn,
+                    command: event.command.clone(),
+                    exit_code: Some(event.exit_code),
+                };
+                let may_change_files = may_change_files(&event.parsed_cmd);
+                match index_by_call.get(&event.call_id) {
+                    Some(&index) => {
+                        let turn = commands[index].0.turn;
+                        commands[index] = (RecordedCommand { turn, ..recorded }, may_change_files);
+                    }
+                    // Commands the user ran with `!` have no tool call.
+                    None => commands.push((recorded, may_change_files)),
+                }
+            }
+            _ => {}
+        }
+    }
+    commands
+        .into_iter()
+        .filter_map(|(command, may_change_files)| may_change_files.then_some(command))
+        .collect()
+}
+
+/// The command a shell tool call ran, or `None` for calls to other tools.
+fn shell_call_command(name: &str, arguments: &str) -> Option<Vec<String>> {
+    match name {
+        "shell" | "container.exec" => serde_json::from_str::<ShellToolCallParams>(arguments)
+            .ok()
+            .map(|params| params.command),
+        "shell_command" => serde_json::from_str::<ShellCommandToolCallParams>(arguments)
+            .ok()
+            .map(|params| bash_lc(params.command)),
+        "exec_command" => serde_json::from_str::<ExecCommandArguments>(arguments)
+            .ok()
+            .map(|arguments| bash_lc(arguments.cmd)),
+        _ => None,
+    }
+}
+
+fn bash_lc(script: String) -> Vec<String> {
+    vec!["bash".to_string(), "-lc".to_string(), script]
+}
+
+/// Commands recognized as only reading or searching cannot have changed files.
+fn may_change_files(parsed: &[ParsedCommand]) -> bool {
+    parsed
+        .iter()
+        .any(|parsed| matches!(parsed, ParsedCommand::Unknown { .. }))
+}
+
+/// Pairs each item with the 1-based turn it belongs to.
+fn with_turns(items: &[RolloutItem]) -> impl Iterator<Item = (usize, &RolloutItem)> {
+    items.iter().scan(0usize, |turns_started, item| {
+        if matches!(item, RolloutItem::EventMsg(EventMsg::TurnStarted(_))) {
+            *turns_started += 1;
+        }
+        Some(((*turns_started).max(1), item))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::PatchApplyEndEvent;
+    use codex_protocol::protocol::PatchApplyStatus;
+    use codex_protocol::protocol::TurnStartedEvent;
+    use pretty_assertions::assert_eq;
+
+    fn turn_started() -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::TurnStarted(TurnStartedEvent {
+            turn_id: "turn".to_string(),
+            model_context_window: None,
+            collaboration_mode_kind: Default::default(),
+        }))
+    }
+
+    fn patch_call(call_id: &str, input: &str) -> RolloutItem {
+        RolloutItem::ResponseItem(ResponseItem::CustomToolCall {
+            id: None,
+            status: None,
+            call_id: call_id.to_string(),
+            name: APPLY_PATCH_TOOL_NAME.to_string(),
+            input: input.to_string(),
+        })
+    }
+
+    fn patch_end(call_id: &str, success: bool) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::PatchApplyEnd(PatchApplyEndEvent {
+            call_id: call_id.to_string(),
+            turn_id: "turn".to_string(),
+            stdout: String::new(),
+            stderr: String::new(),
+            success,
+            changes: HashMap::from([(
+                PathBuf::from("/repo/a.txt"),
+                FileChange::Add {
+                    content: "a\n".to_string(),
+                },
+            )]),
+            status: if success {
+                PatchApplyStatus::Completed
+            } else {
+                PatchApplyStatus::Failed
+            },
+        }))
+    }
+
+    #[test]
+    fn groups_patches_by_turn_with_their_results() {
+        let items = vec![
+            turn_started(),
+            patch_call("call-1", "*** Begin Patch\n*** End Patch"),
+            patch_end("call-1", true),
+            turn_started(),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: APPLY_PATCH_TOOL_NAME.to_string(),
+                arguments: r#"{"input":"patch","dry_run":true}"#.to_string(),
+                call_id: "call-2".to_string(),
+            }),
+            patch_call("call-3", "patch 3"),
+            patch_end("call-3", false),
+            patch_end("shell-call", true),
+        ];
+
+        let patches = recorded_patches(&items);
+
+        assert_eq!(
+            patches
+                .iter()
+                .map(|patch| (
+                    patch.turn,
+                    patch.call_id.as_str(),
+                    patch.patch.is_some(),
+                    patch.success
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, "call-1", true, Some(true)),
+                (2, "call-3", true, Some(false)),
+                (2, "shell-call", false, Some(true)),
+            ]
+        );
+        assert_eq!(patches[0].changes.len(), 1);
+    }
+
+    #[test]
+    fn limited_rollouts_are_read_from_the_tool_calls() {
+        // The limited event mode records neither `PatchApplyEnd` nor `ExecCommandEnd`.
+        let items = vec![
+            turn_started(),
+            patch_call(
+                "call-1",
+                "*** Begin Patch\n*** Add File: a.txt\n+a\n*** End Patch",
+            ),
+            RolloutItem::ResponseItem(ResponseItem::CustomToolCallOutput {
+                call_id: "call-1".to_string(),
+                output: "Exit code: 0\nOutput:\nSuccess. Updated the following files:\nA a.txt\n"
+                    .to_string(),
+            }),
+            patch_call(
+                "call-2",
+                "*** Begin Patch\n*** Delete File: missing.txt\n*** End Patch",
+            ),
+            RolloutItem::ResponseItem(ResponseItem::CustomToolCallOutput {
+                call_id: "call-2".to_string(),
+                output: "apply_patch verification failed: missing.txt does not exist".to_string(),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: "shell_command".to_string(),
+                arguments: r#"{"command":"cat a.txt"}"#.to_string(),
+                call_id: "call-3".to_string(),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: "exec_command".to_string(),
+                arguments: r#"{"cmd":"npm install"}"#.to_string(),
+                call_id: "call-4".to_string(),
+            }),
+        ];
+
+        let patches = recorded_patches(&items);
+        let commands = recorded_commands(&items);
+
+        assert_eq!(
+            patches
+                .iter()
+                .map(|patch| (patch.call_id.as_str(), patch.success, patch.changes.len()))
+                .collect::<Vec<_>>(),
+            vec![("call-1", Some(true), 0), ("call-2", Some(false), 0)]
+        );
+        assert_eq!(
+            commands,
+            vec![RecordedCommand {
+                turn: 1,
+                command: vec![
+                    "bash".to_string(),
+                    "-lc".to_string(),
+                    "npm install".to_string()
+                ],
+                exit_code: None,
+            }]
+        );
+    }
+}
@@ -17,9 +17,11 @@ pub(crate) fn is_persisted_response_item(item: &RolloutItem, mode: EventPersiste
         RolloutItem::ResponseItem(item) => should_persist_response_item(item),
         RolloutItem::EventMsg(ev) => should_persist_event_msg(ev, mode),
         // Persist Codex executive markers so we can analyze flows (e.g., compaction, API turns).
-        RolloutItem::Compacted(_) | RolloutItem::TurnContext(_) | RolloutItem::SessionMeta(_) => {
-            true
-        }
+        RolloutItem::Compacted(_)
+        | RolloutItem::TurnContext(_)
+        | RolloutItem::SessionMeta(_)
+        | RolloutItem::SessionApproval(_)
+        | RolloutItem::PinnedContext(_) => true,
     }
 }
 
@@ -114,15 +116,18 @@ fn event_msg_persistence_mode(ev: &EventMsg) -> Option<EventPersistenceMode> {
         | EventMsg::WebSearchEnd(_)
         | EventMsg::ExecCommandEnd(_)
         | EventMsg::PatchApplyEnd(_)
+        | EventMsg::TurnStructuredOutput(_)
         | EventMsg::McpToolCallEnd(_)
         | EventMsg::ViewImageToolCall(_)
         | EventMsg::CollabAgentSpawnEnd(_)
         | EventMsg::CollabAgentInteractionEnd(_)
         | EventMsg::CollabWaitingEnd(_)
         | EventMsg::CollabCloseEnd(_)
-        | EventMsg::CollabResumeEnd(_) => Some(EventPersistenceMode::Extended),
+        | EventMsg::CollabResumeEnd(_)
+        | EventMsg::DelegateEnd(_) => Some(EventPersistenceMode::Extended),
         EventMsg::Warning(_)
         | EventMsg::ModelReroute(_)
+        | EventMsg::ModelProviderFallback(_)
         | EventMsg::AgentMessageDelta(_)
         | EventMsg::AgentReasoningDelta(_)
         | EventMsg::AgentReasoningRawContentDelta(_)
@@ -130,6 +135,7 @@ fn event_msg_persistence_mode(ev: &EventMsg) -> Option<EventPersistenceMode> {
         | EventMsg::RawResponseItem(_)
         | EventMsg::SessionConfigured(_)
         | EventMsg::ThreadNameUpdated(_)
+        | EventMsg::McpToolCallQueued(_)
         | EventMsg::McpToolCallBegin(_)
         | EventMsg::WebSearchBegin(_)
         | EventMsg::ExecCommandBegin(_)
@@ -140,16 +146,33 @@ fn event_msg_persistence_mode(ev: &EventMsg) -> Option<EventPersistenceMode> {
         | EventMsg::DynamicToolCallRequest(_)
         | EventMsg::ElicitationRequest(_)
         | EventMsg::ApplyPatchApprovalRequest(_)
+        | EventMsg::BudgetLimitReached(_)
+        | EventMsg::AuthExpired(_)
+        | EventMsg::ProjectDocsLoaded(_)
+        | EventMsg::CheckpointCreated(_)
+        | EventMsg::WorktreeCreated(_)
+        | EventMsg::WorktreeMerged(_)
+        | EventMsg::TurnSettings(_)
+        | EventMsg::UserInputQueueUpdated(_)
+        | EventMsg::Unknown
         | EventMsg::BackgroundEvent(_)
         | EventMsg::StreamError(_)
+        | EventMsg::StreamRetry(_)
         | EventMsg::PatchApplyBegin(_)
         | EventMsg::TurnDiff(_)
         | EventMsg::GetHistoryEntryResponse(_)
         | EventMsg::UndoStarted(_)
         | EventMsg::McpListToolsResponse(_)
+        | EventMsg::ListMcpPromptsResponse(_)
+        | EventMsg::GetMcpPromptResponse(_)
+        | EventMsg::GetMcpServerLogsResponse(_)
+        | EventMsg::McpListChanged(_)
         | EventMsg::McpStartupUpdate(_)
         | EventMsg::McpStartupComplete(_)
+        | EventMsg::McpServerHealth(_)
         | EventMsg::ListCustomPromptsResponse(_)
+        | EventMsg::GetUsageResponse(_)
+        | EventMsg::PinnedContextUpdated(_)
         | EventMsg::ListSkillsResponse(_)
         | EventMsg::ListRemoteSkillsResponse(_)
         | EventMsg::RemoteSkillDownloaded(_)
@@ -162,10 +185,13 @@ fn event_msg_persistence_mode(ev: &EventMsg) -> Option<EventPersistenceMode> {
         | EventMsg::ReasoningContentDelta(_)
         | EventMsg::ReasoningRawContentDelta(_)
         | EventMsg::SkillsUpdateAvailable
+        | EventMsg::ConfigReloaded(_)
         | EventMsg::CollabAgentSpawnBegin(_)
         | EventMsg::CollabAgentInteractionBegin(_)
         | EventMsg::CollabWaitingBegin(_)
         | EventMsg::CollabCloseBegin(_)
-        | EventMsg::CollabResumeBegin(_) => None,
+        | EventMsg::CollabResumeBegin(_)
+        | EventMsg::DelegateBegin(_)
+        | EventMsg::DelegateProgress(_) => None,
     }
 }
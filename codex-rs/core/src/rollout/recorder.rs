@@ -38,6 +38,8 @@ use super::list::parse_timestamp_uuid_from_filename;
 use super::metadata;
 use super::policy::EventPersistenceMode;
 use super::policy::is_persisted_response_item;
+use super::policy::should_persist_event_msg;
+use super::segments;
 use crate::config::Config;
 use crate::default_client::originator;
 use crate::git_info::collect_git_info;
@@ -47,6 +49,7 @@ use crate::state_db::StateDbHandle;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::truncate_text;
 use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::ExecCommandEndEvent;
 use codex_protocol::protocol::InitialHistory;
 use codex_protocol::protocol::ResumedHistory;
 use codex_protocol::protocol::RolloutItem;
@@ -133,28 +136,32 @@ impl RolloutRecorderParams {
 
 const PERSISTED_EXEC_AGGREGATED_OUTPUT_MAX_BYTES: usize = 10_000;
 
-fn sanitize_rollout_item_for_persistence(
-    item: RolloutItem,
-    mode: EventPersistenceMode,
-) -> RolloutItem {
-    if mode != EventPersistenceMode::Extended {
-        return item;
-    }
-
-    match item {
-        RolloutItem::EventMsg(EventMsg::ExecCommandEnd(mut event)) => {
-            // Persist only a bounded aggregated summary of command output.
-            event.aggregated_output = truncate_text(
-                &event.aggregated_output,
-                TruncationPolicy::Bytes(PERSISTED_EXEC_AGGREGATED_OUTPUT_MAX_BYTES),
-            );
-            // Drop unnecessary fields from rollout storage since aggregated_output is all we need.
-            event.stdout.clear();
-            event.stderr.clear();
-            event.formatted_output.clear();
-            RolloutItem::EventMsg(EventMsg::ExecCommandEnd(event))
+/// The rollout copy of `msg`. Extended mode keeps command output but only a bounded
+/// `aggregated_output`, which is all a replay needs, and drops the model-facing `formatted_output`.
+fn persisted_event(msg: &EventMsg, mode: EventPersistenceMode) -> EventMsg {
+    match msg {
+        EventMsg::ExecCommandEnd(event) if mode == EventPersistenceMode::Extended => {
+            EventMsg::ExecCommandEnd(ExecCommandEndEvent {
+                call_id: event.call_id.clone(),
+                process_id: event.process_id.clone(),
+                turn_id: event.turn_id.clone(),
+                command: event.command.clone(),
+                cwd: event.cwd.clone(),
+                parsed_cmd: event.parsed_cmd.clone(),
+                source: event.source,
+                interaction_input: event.interaction_input.clone(),
+                aggregated_output: truncate_text(
+                    &event.aggregated_output,
+                    TruncationPolicy::Bytes(PERSISTED_EXEC_AGGREGATED_OUTPUT_MAX_BYTES),
+                ),
+                exit_code: event.exit_code,
+                duration: event.duration,
+                formatted_output: String::new(),
+                output_artifact: event.output_artifact.clone(),
+                status: event.status.clone(),
+            })
         }
-        _ => item,
+        _ => msg.clone(),
     }
 }
 
@@ -472,10 +479,12 @@ impl RolloutRecorder {
             // "fully qualified MCP tool calls," so we could consider
             // reformatting them in that case.
             if is_persisted_response_item(item, self.event_persistence_mode) {
-                filtered.push(sanitize_rollout_item_for_persistence(
-                    item.clone(),
-                    self.event_persistence_mode,
-                ));
+                filtered.push(match item {
+                    RolloutItem::EventMsg(msg) => {
+                        RolloutItem::EventMsg(persisted_event(msg, self.event_persistence_mode))
+                    }
+                    _ => item.clone(),
+                });
             }
         }
         if filtered.is_empty() {
@@ -487,6 +496,20 @@ impl RolloutRecorder {
             .map_err(|e| IoError::other(format!("failed to queue rollout items: {e}")))
     }
 
+    /// Queues a single event for persistence. Unlike [`Self::record_items`], the event is only
+    /// copied when the persistence policy keeps it, so large events that are not persisted (such
+    /// as command output in the default mode) are never duplicated on their way to clients.
+    pub(crate) async fn record_event(&self, msg: &EventMsg) -> std::io::Result<()> {
+        if !should_persist_event_msg(msg, self.event_persistence_mode) {
+            return Ok(());
+        }
+        let item = RolloutItem::EventMsg(persisted_event(msg, self.event_persistence_mode));
+        self.tx
+            .send(RolloutCmd::AddItems(vec![item]))
+            .await
+            .map_err(|e| IoError::other(format!("failed to queue rollout items: {e}")))
+    }
+
     /// Materialize the rollout file and persist all buffered items.
     ///
     /// This is idempotent; after first materialization, repeated calls are no-ops.
@@ -515,7 +538,7 @@ impl RolloutRecorder {
         path: &Path,
     ) -> std::io::Result<(Vec<RolloutItem>, Option<ThreadId>, usize)> {
         trace!("Resuming rollout from {path:?}");
-        let text = tokio::fs::read_to_string(path).await?;
+        let text = segments::read_rollout_text(path).await?;
         if text.trim().is_empty() {
             return Err(IoError::other("empty session file"));
         }
@@ -559,6 +582,12 @@ impl RolloutRecorder {
                     RolloutItem::EventMsg(_ev) => {
                         items.push(RolloutItem::EventMsg(_ev));
                     }
+                    RolloutItem::SessionApproval(item) => {
+                        items.push(RolloutItem::SessionApproval(item));
+                    }
+                    RolloutItem::PinnedContext(item) => {
+                        items.push(RolloutItem::PinnedContext(item));
+                    }
                 },
                 Err(e) => {
                     trace!("failed to parse rollout line: {e}");
@@ -699,7 +728,10 @@ async fn rollout_writer(
     mut state_builder: Option<ThreadMetadataBuilder>,
     default_provider: String,
 ) -> std::io::Result<()> {
-    let mut writer = file.map(|file| JsonlWriter { file });
+    let mut writer = match file {
+        Some(file) => Some(JsonlWriter::new(file, rollout_path.clone()).await?),
+        None => None,
+    };
     let mut buffered_items = Vec::<RolloutItem>::new();
     if let Some(builder) = state_builder.as_mut() {
         builder.rollout_path = rollout_path.clone();
@@ -758,9 +790,13 @@ async fn rollout_writer(
                             ));
                         };
                         let file = open_log_file(log_file_info.path.as_path())?;
-                        writer = Some(JsonlWriter {
-                            file: tokio::fs::File::from_std(file),
-                        });
+                        writer = Some(
+                            JsonlWriter::new(
+                                tokio::fs::File::from_std(file),
+                                log_file_info.path.clone(),
+                            )
+                            .await?,
+                        );
 
                         if let Some(session_meta) = meta.take() {
                             write_session_meta(
@@ -810,6 +846,11 @@ async fn rollout_writer(
                 let _ = ack.send(());
             }
             RolloutCmd::Shutdown { ack } => {
+                if let Some(writer) = writer.as_mut()
+                    && writer.len >= segments::SHUTDOWN_SEGMENT_BYTES
+                {
+                    writer.compact().await;
+                }
                 let _ = ack.send(());
             }
         }
@@ -882,6 +923,9 @@ async fn write_and_reconcile_items(
 
 struct JsonlWriter {
     file: tokio::fs::File,
+    path: PathBuf,
+    /// Bytes in the live file, which compaction resets to zero.
+    len: u64,
 }
 
 #[derive(serde::Serialize)]
@@ -892,6 +936,11 @@ struct RolloutLineRef<'a> {
 }
 
 impl JsonlWriter {
+    async fn new(file: tokio::fs::File, path: PathBuf) -> std::io::Result<Self> {
+        let len = file.metadata().await?.len();
+        Ok(Self { file, path, len })
+    }
+
     async fn write_rollout_item(&mut self, rollout_item: &RolloutItem) -> std::io::Result<()> {
         let timestamp_format: &[FormatItem] = format_description!(
             "[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:3]Z"
@@ -911,8 +960,23 @@ impl JsonlWriter {
         json.push('\n');
         self.file.write_all(json.as_bytes()).await?;
         self.file.flush().await?;
+        self.len += json.len() as u64;
+        if self.len >= segments::SEGMENT_BYTES {
+            self.compact().await;
+        }
         Ok(())
     }
+
+    /// Moves the live file into a compressed segment. Failures leave the file as it was, so
+    /// they are logged rather than ending the session.
+    async fn compact(&mut self) {
+        let path = self.path.clone();
+        match tokio::task::spawn_blocking(move || segments::compact(&path)).await {
+            Ok(Ok(())) => self.len = 0,
+            Ok(Err(err)) => warn!("failed to compact rollout {}: {err}", self.path.display()),
+            Err(err) => warn!("rollout compaction task failed: {err}"),
+        }
+    }
 }
 
 impl From<codex_state::ThreadsPage> for ThreadsPage {
@@ -1008,6 +1072,40 @@ mod tests {
     use tempfile::TempDir;
     use uuid::Uuid;
 
+    #[test]
+    fn persisted_exec_end_keeps_only_bounded_output() {
+        let msg = EventMsg::ExecCommandEnd(ExecCommandEndEvent {
+            call_id: "call-1".to_string(),
+            process_id: None,
+            turn_id: "turn-1".to_string(),
+            command: vec!["cat".to_string(), "big.log".to_string()],
+            cwd: PathBuf::from("/repo"),
+            parsed_cmd: Vec::new(),
+            source: Default::default(),
+            interaction_input: None,
+            aggregated_output: "x".repeat(PERSISTED_EXEC_AGGREGATED_OUTPUT_MAX_BYTES * 2),
+            exit_code: 0,
+            duration: std::time::Duration::from_millis(5),
+            formatted_output: "x".repeat(PERSISTED_EXEC_AGGREGATED_OUTPUT_MAX_BYTES),
+            output_artifact: None,
+            status: codex_protocol::protocol::ExecCommandStatus::Completed,
+        });
+
+        assert_eq!(
+            serde_json::to_value(persisted_event(&msg, EventPersistenceMode::Limited)).ok(),
+            serde_json::to_value(&msg).ok()
+        );
+
+        let EventMsg::ExecCommandEnd(persisted) =
+            persisted_event(&msg, EventPersistenceMode::Extended)
+        else {
+            panic!("expected ExecCommandEnd");
+        };
+        assert!(persisted.aggregated_output.len() < PERSISTED_EXEC_AGGREGATED_OUTPUT_MAX_BYTES * 2);
+        assert_eq!(persisted.formatted_output, "");
+        assert_eq!(persisted.call_id, "call-1");
+    }
+
     fn write_session_file(root: &Path, ts: &str, uuid: Uuid) -> std::io::Result<PathBuf> {
         let day_dir = root.join("sessions/2025/01/03");
         fs::create_dir_all(&day_dir)?;
@@ -0,0 +1,317 @@
+//! Compressed segments of long rollout files.
+//!
+//! A rollout is written as plain JSONL. Once the file grows past [`SEGMENT_BYTES`], or when a
+//! session shuts down with at least [`SHUTDOWN_SEGMENT_BYTES`] in it, its contents are compressed
+//! into a zstd frame appended to `<rollout>.zst` and the file is truncated so writing continues
+//! from an empty tail. `<rollout>.idx` lists each frame as one JSON line; a frame only counts once
+//! its index line is written, so a crash while compacting never loses lines. If the process stops
+//! after the index line but before the truncation, the tail still starts with the last segment
+//! and readers skip that copy.
+//!
+//! Readers go through [`read_rollout_text`] or [`read_rollout_head_lines`], which return the
+//! segments followed by the tail as if the file had never been compacted.
+
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Cursor;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Size of the live file at which it is compressed into a new segment.
+pub(crate) const SEGMENT_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Smaller tails are left uncompressed when a session shuts down.
+pub(crate) const SHUTDOWN_SEGMENT_BYTES: u64 = 64 * 1024;
+
+const ZSTD_LEVEL: i32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct SegmentIndexEntry {
+    /// Offset of the frame in the segments file.
+    offset: u64,
+    compressed_bytes: u64,
+    uncompressed_bytes: u64,
+}
+
+pub(crate) fn segments_path(rollout_path: &Path) -> PathBuf {
+    companion_path(rollout_path, "zst")
+}
+
+pub(crate) fn index_path(rollout_path: &Path) -> PathBuf {
+    companion_path(rollout_path, "idx")
+}
+
+fn companion_path(rollout_path: &Path, extension: &str) -> PathBuf {
+    let mut path = rollout_path.as_os_str().to_owned();
+    path.push(".");
+    path.push(extension);
+    PathBuf::from(path)
+}
+
+/// Returns the whole rollout at `path`, including compressed segments.
+pub async fn read_rollout_text(path: &Path) -> io::Result<String> {
+    let path = path.to_path_buf();
+    blocking(move || {
+        let mut text = Vec::new();
+        let last_segment = read_segments(&path, usize::MAX, &mut text)?;
+        open_tail(&path, &last_segment)?.read_to_end(&mut text)?;
+        String::from_utf8(text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    })
+    .await
+}
+
+/// Returns the first `limit` non-empty lines of the rollout at `path`, decompressing only the
+/// segments needed to reach them.
+pub(crate) async fn read_rollout_head_lines(path: &Path, limit: usize) -> io::Result<Vec<String>> {
+    let path = path.to_path_buf();
+    blocking(move || {
+        let mut lines = Vec::new();
+        let mut text = Vec::new();
+        let last_segment = read_segments(&path, limit, &mut text)?;
+        push_lines(&mut lines, Cursor::new(text), limit)?;
+        if lines.len() < limit {
+            push_lines(&mut lines, open_tail(&path, &last_segment)?, limit)?;
+        }
+        Ok(lines)
+    })
+    .await
+}
+
+/// Moves the rollout at `from` and its compressed segments to `to`.
+pub async fn rename_rollout(from: &Path, to: &Path) -> io::Result<()> {
+    for (from, to) in [
+        (segments_path(from), segments_path(to)),
+        (index_path(from), index_path(to)),
+    ] {
+        match tokio::fs::rename(&from, &to).await {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+    }
+    tokio::fs::rename(from, to).await
+}
+
+/// Deletes the rollout at `path` and its compressed segments.
+pub async fn remove_rollout(path: &Path) -> io::Result<()> {
+    for companion in [segments_path(path), index_path(path)] {
+        match tokio::fs::remove_file(&companion).await {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+    }
+    tokio::fs::remove_file(path).await
+}
+
+/// Compresses the live file at `rollout_path` into a new segment and truncates it.
+pub(crate) fn compact(rollout_path: &Path) -> io::Result<()> {
+    let entries = read_index(rollout_path)?;
+    let mut segments = OpenOptions::new()
+        .read(true)
+        .append(true)
+        .create(true)
+        .open(segments_path(rollout_path))?;
+    let last_segment = match entries.last() {
+        Some(entry) => read_frame(&mut segments, entry)?,
+        None => Vec::new(),
+    };
+
+    let live = std::fs::read(rollout_path)?;
+    let live = live.strip_prefix(last_segment.as_slice()).unwrap_or(&live);
+    if !live.is_empty() {
+        let frame = zstd::encode_all(live, ZSTD_LEVEL)?;
+        // Drop a frame left behind by a compaction that never wrote its index line.
+        let offset = entries
+            .last()
+            .map_or(0, |entry| entry.offset + entry.compressed_bytes);
+        segments.set_len(offset)?;
+        segments.write_all(&frame)?;
+        segments.sync_all()?;
+
+        let entry = SegmentIndexEntry {
+            offset,
+            compressed_bytes: frame.len() as u64,
+            uncompressed_bytes: live.len() as u64,
+        };
+        let mut line = serde_json::to_string(&entry)?;
+        line.push('\n');
+        let mut index = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(index_path(rollout_path))?;
+        index.write_all(line.as_bytes())?;
+        index.sync_all()?;
+    }
+
+    OpenOptions::new()
+        .write(true)
+        .open(rollout_path)?
+        .set_len(0)
+}
+
+fn read_index(rollout_path: &Path) -> io::Result<Vec<SegmentIndexEntry>> {
+    let text = match std::fs::read_to_string(index_path(rollout_path)) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    // A torn last line is a compaction that did not finish; its frame is ignored.
+    Ok(text
+        .lines()
+        .map_while(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+fn read_frame(segments: &mut File, entry: &SegmentIndexEntry) -> io::Result<Vec<u8>> {
+    let mut frame = vec![0; entry.compressed_bytes as usize];
+    segments.seek(SeekFrom::Start(entry.offset))?;
+    segments.read_exact(&mut frame)?;
+    zstd::decode_all(frame.as_slice())
+}
+
+/// Appends decompressed segments to `text` until it holds at least `line_limit` lines, and
+/// returns the last segment when every segment was read.
+fn read_segments(
+    rollout_path: &Path,
+    line_limit: usize,
+    text: &mut Vec<u8>,
+) -> io::Result<Vec<u8>> {
+    let entries = read_index(rollout_path)?;
+    if entries.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut segments = File::open(segments_path(rollout_path))?;
+    let mut last_segment = Vec::new();
+    let mut lines = 0;
+    for entry in &entries {
+        if lines >= line_limit {
+            return Ok(Vec::new());
+        }
+        let segment = read_frame(&mut segments, entry)?;
+        lines += segment.iter().filter(|byte| **byte == b'\n').count();
+        text.extend_from_slice(&segment);
+        last_segment = segment;
+    }
+    Ok(last_segment)
+}
+
+/// Opens the live file, skipping a copy of `last_segment` left by an interrupted compaction.
+fn open_tail(rollout_path: &Path, last_segment: &[u8]) -> io::Result<Box<dyn BufRead + Send>> {
+    let mut file = File::open(rollout_path)?;
+    if last_segment.is_empty() {
+        return Ok(Box::new(BufReader::new(file)));
+    }
+    let mut prefix = Vec::with_capacity(last_segment.len());
+    (&mut file)
+        .take(last_segment.len() as u64)
+        .read_to_end(&mut prefix)?;
+    if prefix == last_segment {
+        Ok(Box::new(BufReader::new(file)))
+    } else {
+        Ok(Box::new(BufReader::new(Cursor::new(prefix).chain(file))))
+    }
+}
+
+fn push_lines(lines: &mut Vec<String>, reader: impl BufRead, limit: usize) -> io::Result<()> {
+    for line in reader.lines() {
+        if lines.len() >= limit {
+            break;
+        }
+        let line = line?;
+        if !line.trim().is_empty() {
+            lines.push(line);
+        }
+    }
+    Ok(())
+}
+
+async fn blocking<T: Send + 'static>(
+    f: impl FnOnce() -> io::Result<T> + Send + 'static,
+) -> io::Result<T> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|err| io::Error::other(format!("rollout read task failed: {err}")))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    fn append(path: &Path, text: &str) {
+        OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)
+            .unwrap()
+            .write_all(text.as_bytes())
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn reads_segments_followed_by_the_live_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("rollout.jsonl");
+        append(&path, "{\"a\":1}\n{\"a\":2}\n");
+        compact(&path).unwrap();
+        append(&path, "{\"a\":3}\n");
+        compact(&path).unwrap();
+        append(&path, "{\"a\":4}\n");
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{\"a\":4}\n");
+        assert_eq!(
+            read_rollout_text(&path).await.unwrap(),
+            "{\"a\":1}\n{\"a\":2}\n{\"a\":3}\n{\"a\":4}\n"
+        );
+        assert_eq!(
+            read_rollout_head_lines(&path, 3).await.unwrap(),
+            vec!["{\"a\":1}", "{\"a\":2}", "{\"a\":3}"]
+        );
+
+        let moved = dir.path().join("archived.jsonl");
+        rename_rollout(&path, &moved).await.unwrap();
+        assert!(!segments_path(&path).exists());
+        assert_eq!(
+            read_rollout_text(&moved).await.unwrap(),
+            "{\"a\":1}\n{\"a\":2}\n{\"a\":3}\n{\"a\":4}\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn recovers_from_an_interrupted_compaction() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("rollout.jsonl");
+        append(&path, "{\"a\":1}\n");
+        compact(&path).unwrap();
+        // The index line was written but the live file was never truncated.
+        append(&path, "{\"a\":2}\n");
+        let live = std::fs::read(&path).unwrap();
+        compact(&path).unwrap();
+        std::fs::write(&path, [live.as_slice(), b"{\"a\":3}\n"].concat()).unwrap();
+        // A frame was written without its index line.
+        append(&segments_path(&path), "garbage");
+
+        assert_eq!(
+            read_rollout_text(&path).await.unwrap(),
+            "{\"a\":1}\n{\"a\":2}\n{\"a\":3}\n"
+        );
+
+        compact(&path).unwrap();
+        assert_eq!(
+            read_rollout_text(&path).await.unwrap(),
+            "{\"a\":1}\n{\"a\":2}\n{\"a\":3}\n"
+        );
+    }
+}
@@ -6,6 +6,10 @@ sandbox placement and transformation of portable CommandSpec into a
 ready‑to‑spawn environment.
 */
 
+use crate::config::types::ResourceLimits;
+use crate::config::types::SandboxContainer;
+use crate::config::types::SandboxExtensions;
+use crate::container_sandbox::create_container_command_args;
 use crate::exec::ExecExpiration;
 use crate::exec::ExecToolCallOutput;
 use crate::exec::SandboxType;
@@ -13,18 +17,22 @@ use crate::exec::StdoutStream;
 use crate::exec::execute_exec_env;
 use crate::landlock::allow_network_for_proxy;
 use crate::landlock::create_linux_sandbox_command_args;
+use crate::protocol::ReadOnlyAccess;
 use crate::protocol::SandboxPolicy;
+use crate::resource_limits::wrap_command;
 #[cfg(target_os = "macos")]
 use crate::seatbelt::MACOS_PATH_TO_SEATBELT_EXECUTABLE;
 #[cfg(target_os = "macos")]
-use crate::seatbelt::create_seatbelt_command_args;
+use crate::seatbelt::create_seatbelt_command_args_with_extensions;
 #[cfg(target_os = "macos")]
+use crate::seatbelt_permissions::MacOsSeatbeltProfileExtensions;
 use crate::spawn::CODEX_SANDBOX_ENV_VAR;
 use crate::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR;
 use crate::tools::sandboxing::SandboxablePreference;
 use codex_network_proxy::NetworkProxy;
 use codex_protocol::config_types::WindowsSandboxLevel;
 pub use codex_protocol::models::SandboxPermissions;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
@@ -53,6 +61,9 @@ pub struct ExecRequest {
     pub sandbox_permissions: SandboxPermissions,
     pub justification: Option<String>,
     pub arg0: Option<String>,
+    /// Output cap and limit detection from `[resource_limits]`; the rlimits are already applied
+    /// by `command`.
+    pub resource_limits: ResourceLimits,
 }
 
 /// Bundled arguments for sandbox transformation.
@@ -70,6 +81,10 @@ pub(crate) struct SandboxTransformRequest<'a> {
     pub codex_linux_sandbox_exe: Option<&'a PathBuf>,
     pub use_linux_sandbox_bwrap: bool,
     pub windows_sandbox_level: WindowsSandboxLevel,
+    pub container: Option<&'a SandboxContainer>,
+    pub extensions: Option<&'a SandboxExtensions>,
+    /// `[resource_limits]` to apply to the command, when it is run on the user's behalf.
+    pub resource_limits: Option<&'a ResourceLimits>,
 }
 
 pub enum SandboxPreference {
@@ -82,6 +97,8 @@ pub enum SandboxPreference {
 pub(crate) enum SandboxTransformError {
     #[error("missing codex-linux-sandbox executable path")]
     MissingLinuxSandboxExecutable,
+    #[error("container sandbox selected without [sandbox_container] settings")]
+    MissingContainerConfig,
     #[cfg(not(target_os = "macos"))]
     #[error("seatbelt sandbox is only available on macOS")]
     SeatbeltUnavailable,
@@ -101,32 +118,33 @@ impl SandboxManager {
         pref: SandboxablePreference,
         windows_sandbox_level: WindowsSandboxLevel,
         has_managed_network_requirements: bool,
+        container: Option<&SandboxContainer>,
     ) -> SandboxType {
+        // Containers run without a network when the policy restricts it, so the managed proxy
+        // cannot reach them; keep the platform sandbox in that case.
+        let platform_sandbox = || match container {
+            Some(_) if !has_managed_network_requirements => SandboxType::Container,
+            _ => crate::safety::get_platform_sandbox(
+                windows_sandbox_level != WindowsSandboxLevel::Disabled,
+            )
+            .unwrap_or(SandboxType::None),
+        };
         match pref {
             SandboxablePreference::Forbid => SandboxType::None,
             SandboxablePreference::Require => {
                 // Require a platform sandbox when available; on Windows this
                 // respects the experimental_windows_sandbox feature.
-                crate::safety::get_platform_sandbox(
-                    windows_sandbox_level != WindowsSandboxLevel::Disabled,
-                )
-                .unwrap_or(SandboxType::None)
+                platform_sandbox()
             }
             SandboxablePreference::Auto => match policy {
                 SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => {
                     if has_managed_network_requirements {
-                        crate::safety::get_platform_sandbox(
-                            windows_sandbox_level != WindowsSandboxLevel::Disabled,
-                        )
-                        .unwrap_or(SandboxType::None)
+                        platform_sandbox()
                     } else {
                         SandboxType::None
                     }
                 }
-                _ => crate::safety::get_platform_sandbox(
-                    windows_sandbox_level != WindowsSandboxLevel::Disabled,
-                )
-                .unwrap_or(SandboxType::None),
+                _ => platform_sandbox(),
             },
         }
     }
@@ -145,7 +163,13 @@ impl SandboxManager {
             codex_linux_sandbox_exe,
             use_linux_sandbox_bwrap,
             windows_sandbox_level,
+            container,
+            extensions,
+            resource_limits,
         } = request;
+        let extended_policy =
+            extensions.map(|extensions| extend_sandbox_policy(policy, extensions));
+        let policy = extended_policy.as_ref().unwrap_or(policy);
         let mut env = spec.env;
         if !policy.has_full_network_access() {
             env.insert(
@@ -157,6 +181,9 @@ impl SandboxManager {
         let mut command = Vec::with_capacity(1 + spec.args.len());
         command.push(spec.program);
         command.append(&mut spec.args);
+        // Apply the rlimits inside the sandbox so they hold however the command is spawned.
+        let resource_limits = resource_limits.cloned().unwrap_or_default();
+        let command = wrap_command(&resource_limits, command);
 
         let (command, sandbox_env, arg0_override) = match sandbox {
             SandboxType::None => (command, HashMap::new(), None),
@@ -170,12 +197,23 @@ impl SandboxManager {
                 let zsh_exec_bridge_allowed_unix_sockets = zsh_exec_bridge_wrapper_socket
                     .as_ref()
                     .map_or_else(Vec::new, |path| vec![path.clone()]);
-                let mut args = create_seatbelt_command_args(
+                let profile_extensions = MacOsSeatbeltProfileExtensions {
+                    device_paths: extensions.map_or_else(Vec::new, |extensions| {
+                        extensions
+                            .device_paths
+                            .iter()
+                            .map(AbsolutePathBuf::to_path_buf)
+                            .collect()
+                    }),
+                    ..Default::default()
+                };
+                let mut args = create_seatbelt_command_args_with_extensions(
                     command.clone(),
                     policy,
                     sandbox_policy_cwd,
                     enforce_managed_network,
                     network,
+                    Some(&profile_extensions),
                     &zsh_exec_bridge_allowed_unix_sockets,
                 );
                 let mut full_command = Vec::with_capacity(1 + args.len());
@@ -185,6 +223,19 @@ impl SandboxManager {
             }
             #[cfg(not(target_os = "macos"))]
             SandboxType::MacosSeatbelt => return Err(SandboxTransformError::SeatbeltUnavailable),
+            SandboxType::Container => {
+                let settings = container.ok_or(SandboxTransformError::MissingContainerConfig)?;
+                env.insert(CODEX_SANDBOX_ENV_VAR.to_string(), "container".to_string());
+                let full_command = create_container_command_args(
+                    command,
+                    settings,
+                    policy,
+                    sandbox_policy_cwd,
+                    &spec.cwd,
+                    env.keys(),
+                );
+                (full_command, HashMap::new(), None)
+            }
             SandboxType::LinuxSeccomp => {
                 let exe = codex_linux_sandbox_exe
                     .ok_or(SandboxTransformError::MissingLinuxSandboxExecutable)?;
@@ -195,6 +246,7 @@ impl SandboxManager {
                     sandbox_policy_cwd,
                     use_linux_sandbox_bwrap,
                     allow_proxy_network,
+                    extensions,
                 );
                 let mut full_command = Vec::with_capacity(1 + args.len());
                 full_command.push(exe.to_string_lossy().to_string());
@@ -229,6 +281,7 @@ impl SandboxManager {
             sandbox_permissions: spec.sandbox_permissions,
             justification: spec.justification,
             arg0: arg0_override,
+            resource_limits,
         })
     }
 
@@ -237,6 +290,41 @@ impl SandboxManager {
     }
 }
 
+/// Adds the `[sandbox_extensions]` roots to `policy`. Readable roots only matter when read access
+/// is restricted, and policies without filesystem restrictions are left unchanged.
+fn extend_sandbox_policy(policy: &SandboxPolicy, extensions: &SandboxExtensions) -> SandboxPolicy {
+    let mut policy = policy.clone();
+    match &mut policy {
+        SandboxPolicy::WorkspaceWrite {
+            writable_roots,
+            read_only_access,
+            ..
+        } => {
+            append_missing_roots(writable_roots, &extensions.writable_roots);
+            extend_read_only_access(read_only_access, &extensions.readable_roots);
+        }
+        SandboxPolicy::ReadOnly { access } => {
+            extend_read_only_access(access, &extensions.readable_roots);
+        }
+        SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => {}
+    }
+    policy
+}
+
+fn extend_read_only_access(access: &mut ReadOnlyAccess, roots: &[AbsolutePathBuf]) {
+    if let ReadOnlyAccess::Restricted { readable_roots, .. } = access {
+        append_missing_roots(readable_roots, roots);
+    }
+}
+
+fn append_missing_roots(roots: &mut Vec<AbsolutePathBuf>, extra: &[AbsolutePathBuf]) {
+    for root in extra {
+        if !roots.contains(root) {
+            roots.push(root.clone());
+        }
+    }
+}
+
 pub async fn execute_env(
     env: ExecRequest,
     policy: &SandboxPolicy,
@@ -248,10 +336,16 @@ pub async fn execute_env(
 #[cfg(test)]
 mod tests {
     use super::SandboxManager;
+    use super::extend_sandbox_policy;
+    use crate::config::types::ContainerRuntime;
+    use crate::config::types::SandboxContainer;
+    use crate::config::types::SandboxExtensions;
     use crate::exec::SandboxType;
+    use crate::protocol::ReadOnlyAccess;
     use crate::protocol::SandboxPolicy;
     use crate::tools::sandboxing::SandboxablePreference;
     use codex_protocol::config_types::WindowsSandboxLevel;
+    use codex_utils_absolute_path::AbsolutePathBuf;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -262,6 +356,7 @@ mod tests {
             SandboxablePreference::Auto,
             WindowsSandboxLevel::Disabled,
             false,
+            None,
         );
         assert_eq!(sandbox, SandboxType::None);
     }
@@ -275,7 +370,91 @@ mod tests {
             SandboxablePreference::Auto,
             WindowsSandboxLevel::Disabled,
             true,
+            None,
         );
         assert_eq!(sandbox, expected);
     }
+
+    #[test]
+    fn configured_container_replaces_platform_sandbox() {
+        let manager = SandboxManager::new();
+        let container = SandboxContainer {
+            runtime: ContainerRuntime::Docker,
+            image: "rust:1".to_string(),
+            memory: None,
+            cpus: None,
+            pids_limit: None,
+            read_only_mounts: Vec::new(),
+        };
+
+        assert_eq!(
+            manager.select_initial(
+                &SandboxPolicy::new_read_only_policy(),
+                SandboxablePreference::Auto,
+                WindowsSandboxLevel::Disabled,
+                false,
+                Some(&container),
+            ),
+            SandboxType::Container
+        );
+        assert_eq!(
+            manager.select_initial(
+                &SandboxPolicy::DangerFullAccess,
+                SandboxablePreference::Auto,
+                WindowsSandboxLevel::Disabled,
+                false,
+                Some(&container),
+            ),
+            SandboxType::None
+        );
+        assert_eq!(
+            manager.select_initial(
+                &SandboxPolicy::new_read_only_policy(),
+                SandboxablePreference::Forbid,
+                WindowsSandboxLevel::Disabled,
+                false,
+                Some(&container),
+            ),
+            SandboxType::None
+        );
+    }
+
+    #[test]
+    fn extensions_add_roots_to_restricted_policies() {
+        let toolchains = AbsolutePathBuf::from_absolute_path("/opt/toolchains").expect("path");
+        let cache = AbsolutePathBuf::from_absolute_path("/var/cache/build").expect("path");
+        let extensions = SandboxExtensions {
+            readable_roots: vec![toolchains.clone()],
+            writable_roots: vec![cache.clone()],
+            ..Default::default()
+        };
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![cache.clone()],
+            read_only_access: ReadOnlyAccess::Restricted {
+                include_platform_defaults: true,
+                readable_roots: Vec::new(),
+            },
+            network_access: false,
+            exclude_tmpdir_env_var: false,
+            exclude_slash_tmp: false,
+        };
+
+        assert_eq!(
+            extend_sandbox_policy(&policy, &extensions),
+            SandboxPolicy::WorkspaceWrite {
+                writable_roots: vec![cache],
+                read_only_access: ReadOnlyAccess::Restricted {
+                    include_platform_defaults: true,
+                    readable_roots: vec![toolchains],
+                },
+                network_access: false,
+                exclude_tmpdir_env_var: false,
+                exclude_slash_tmp: false,
+            }
+        );
+        assert_eq!(
+            extend_sandbox_policy(&SandboxPolicy::DangerFullAccess, &extensions),
+            SandboxPolicy::DangerFullAccess
+        );
+    }
 }
@@ -492,6 +492,7 @@ mod tests {
                 ]),
                 macos_accessibility: true,
                 macos_calendar: true,
+                device_paths: Vec::new(),
             }),
             &[],
         );
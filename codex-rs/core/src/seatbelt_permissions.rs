@@ -30,6 +30,8 @@ pub struct MacOsSeatbeltProfileExtensions {
     pub macos_automation: MacOsAutomationPermission,
     pub macos_accessibility: bool,
     pub macos_calendar: bool,
+    /// Device nodes the command may read, write, and `ioctl`.
+    pub device_paths: Vec<PathBuf>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
@@ -127,6 +129,20 @@ pub(crate) fn build_seatbelt_extensions(
         clauses.push("(allow mach-lookup (global-name \"com.apple.CalendarAgent\"))".to_string());
     }
 
+    let mut dir_params = Vec::new();
+    if !extensions.device_paths.is_empty() {
+        let mut devices = Vec::new();
+        for (index, device) in extensions.device_paths.iter().enumerate() {
+            let param = format!("DEVICE_PATH_{index}");
+            devices.push(format!("    (literal (param \"{param}\"))"));
+            dir_params.push((param, device.clone()));
+        }
+        clauses.push(format!(
+            "(allow file-read* file-write* file-ioctl\n{}\n)",
+            devices.join("\n")
+        ));
+    }
+
     if clauses.is_empty() {
         SeatbeltExtensionPolicy::default()
     } else {
@@ -135,7 +151,7 @@ pub(crate) fn build_seatbelt_extensions(
                 "; macOS permission profile extensions\n{}\n",
                 clauses.join("\n")
             ),
-            dir_params: Vec::new(),
+            dir_params,
         }
     }
 }
@@ -166,6 +182,8 @@ mod tests {
     use super::MacOsPreferencesPermission;
     use super::MacOsSeatbeltProfileExtensions;
     use super::build_seatbelt_extensions;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
 
     #[test]
     fn preferences_read_only_emits_read_clauses_only() {
@@ -239,6 +257,26 @@ mod tests {
         assert!(policy.policy.contains("com.apple.CalendarAgent"));
     }
 
+    #[test]
+    fn device_paths_emit_literal_clauses_with_params() {
+        let policy = build_seatbelt_extensions(&MacOsSeatbeltProfileExtensions {
+            device_paths: vec![PathBuf::from("/dev/tty.usbserial")],
+            ..Default::default()
+        });
+        assert!(
+            policy
+                .policy
+                .contains("(literal (param \"DEVICE_PATH_0\"))")
+        );
+        assert_eq!(
+            policy.dir_params,
+            vec![(
+                "DEVICE_PATH_0".to_string(),
+                PathBuf::from("/dev/tty.usbserial")
+            )]
+        );
+    }
+
     #[test]
     fn default_extensions_emit_preferences_read_only_policy() {
         let policy = build_seatbelt_extensions(&MacOsSeatbeltProfileExtensions::default());
@@ -0,0 +1,237 @@
+//! Secrets that config files refer to instead of containing.
+//!
+//! `{ keychain = "<name>" }` is read from the OS keychain (macOS Keychain, Secret Service, or
+//! Windows Credential Manager), where `codex config set-secret <name>` saves it, and
+//! `{ command = [...] }` is the output of a command such as a password manager's CLI.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::process::Stdio;
+use std::time::Duration;
+
+use anyhow::Result;
+use anyhow::anyhow;
+use codex_keyring_store::DefaultKeyringStore;
+use codex_keyring_store::KeyringStore;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::process::Command;
+use tokio::sync::Mutex;
+
+/// Keychain service that `{ keychain = "<name>" }` entries are stored under. The name predates
+/// provider API keys and is kept so that secrets saved by earlier versions are still found.
+pub const CONFIG_SECRETS_KEYRING_SERVICE: &str = "Codex MCP Secrets";
+
+/// A secret looked up when it is needed rather than written into the config file.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, JsonSchema)]
+#[serde(untagged, deny_unknown_fields)]
+pub enum SecretRef {
+    /// A secret saved in the OS keychain with `codex config set-secret <name>`.
+    Keychain { keychain: String },
+    /// The output of a command, e.g. `["op", "read", "op://vault/openrouter/key"]`, with
+    /// surrounding whitespace trimmed.
+    Command { command: Vec<String> },
+}
+
+/// Stores `value` in the OS keychain so it can be referenced as `{ keychain = "<name>" }`.
+pub fn save_config_secret(name: &str, value: &str) -> Result<()> {
+    DefaultKeyringStore
+        .save(CONFIG_SECRETS_KEYRING_SERVICE, name, value)
+        .map_err(|err| anyhow!("failed to save secret '{name}' to the keychain: {err}"))
+}
+
+/// Secrets resolved for a session, so that a command that prompts (for example to unlock a
+/// password manager) runs once rather than before every request.
+#[derive(Default)]
+pub(crate) struct SecretCache {
+    values: Mutex<HashMap<SecretRef, String>>,
+}
+
+impl fmt::Debug for SecretCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never print the secrets themselves.
+        f.debug_struct("SecretCache").finish_non_exhaustive()
+    }
+}
+
+impl SecretCache {
+    /// Returns the value of `secret`, which is used for `purpose` (e.g. "the API key of provider
+    /// 'openrouter'") in error messages.
+    pub(crate) async fn resolve(
+        &self,
+        secret: &SecretRef,
+        purpose: &str,
+        keyring_store: &dyn KeyringStore,
+    ) -> Result<String> {
+        let mut values = self.values.lock().await;
+        if let Some(value) = values.get(secret) {
+            return Ok(value.clone());
+        }
+        let value = match secret {
+            SecretRef::Keychain { keychain } => {
+                match keyring_store.load(CONFIG_SECRETS_KEYRING_SERVICE, keychain) {
+                    Ok(Some(value)) => value,
+                    Ok(None) => {
+                        return Err(anyhow!(
+                            "Secret '{keychain}' for {purpose} is not in the keychain; save it with `codex config set-secret {keychain}`"
+                        ));
+                    }
+                    Err(err) => {
+                        return Err(anyhow!(
+                            "Failed to read secret '{keychain}' for {purpose} from the keychain: {err}"
+                        ));
+                    }
+                }
+            }
+            SecretRef::Command { command } => run_secret_command(command, purpose).await?,
+        };
+        values.insert(secret.clone(), value.clone());
+        Ok(value)
+    }
+}
+
+/// How long a secret command may run, including time spent waiting for the user to unlock a
+/// password manager, before it is killed.
+const SECRET_COMMAND_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Runs `command` and returns its standard output with surrounding whitespace trimmed.
+pub(crate) async fn run_secret_command(command: &[String], purpose: &str) -> Result<String> {
+    run_secret_command_with_timeout(command, purpose, SECRET_COMMAND_TIMEOUT).await
+}
+
+async fn run_secret_command_with_timeout(
+    command: &[String],
+    purpose: &str,
+    timeout: Duration,
+) -> Result<String> {
+    let Some((program, args)) = command.split_first() else {
+        return Err(anyhow!("Command for {purpose} is empty"));
+    };
+    // Stdin is closed so a command that prompts on it fails instead of reading from the TUI's
+    // terminal; prompts that open the terminal directly still work.
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(timeout, output)
+        .await
+        .map_err(|_| {
+            anyhow!(
+                "`{program}` for {purpose} did not finish within {} seconds",
+                timeout.as_secs()
+            )
+        })?
+        .map_err(|err| anyhow!("Failed to run `{program}` for {purpose}: {err}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!(
+            "`{program}` for {purpose} exited with {}: {}",
+            output.status,
+            stderr.trim()
+        ));
+    }
+    let stdout = String::from_utf8(output.stdout)
+        .map_err(|_| anyhow!("`{program}` for {purpose} printed invalid UTF-8"))?;
+    Ok(stdout.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_keyring_store::tests::MockKeyringStore;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn keychain_secrets_are_loaded_from_the_store() {
+        let store = MockKeyringStore::default();
+        store
+            .save(CONFIG_SECRETS_KEYRING_SERVICE, "openrouter", "sk-or-123")
+            .expect("save secret");
+        let secret = SecretRef::Keychain {
+            keychain: "openrouter".to_string(),
+        };
+
+        let value = SecretCache::default()
+            .resolve(&secret, "the API key of provider 'openrouter'", &store)
+            .await
+            .expect("resolve secret");
+
+        assert_eq!(value, "sk-or-123");
+    }
+
+    #[tokio::test]
+    async fn missing_keychain_secret_names_the_command_to_save_it() {
+        let secret = SecretRef::Keychain {
+            keychain: "openrouter".to_string(),
+        };
+
+        let err = SecretCache::default()
+            .resolve(
+                &secret,
+                "the API key of provider 'openrouter'",
+                &MockKeyringStore::default(),
+            )
+            .await
+            .expect_err("missing secret");
+
+        assert_eq!(
+            err.to_string(),
+            "Secret 'openrouter' for the API key of provider 'openrouter' is not in the keychain; save it with `codex config set-secret openrouter`"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_secrets_run_once_per_cache() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let counter = dir.path().join("runs");
+        let secret = SecretRef::Command {
+            command: vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                format!("echo run >> '{}'; printf '  sk-123\\n'", counter.display()),
+            ],
+        };
+        let cache = SecretCache::default();
+
+        for _ in 0..2 {
+            let value = cache
+                .resolve(&secret, "the API key", &MockKeyringStore::default())
+                .await
+                .expect("resolve secret");
+            assert_eq!(value, "sk-123");
+        }
+
+        let runs = std::fs::read_to_string(&counter).expect("read counter");
+        assert_eq!(runs.lines().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_secrets_do_not_read_stdin() {
+        let command = vec!["cat".to_string()];
+
+        let value = run_secret_command(&command, "the API key")
+            .await
+            .expect("resolve secret");
+
+        assert_eq!(value, "");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_secrets_time_out() {
+        let command = vec!["sleep".to_string(), "5".to_string()];
+
+        let err = run_secret_command_with_timeout(&command, "the API key", Duration::from_secs(1))
+            .await
+            .expect_err("timed out");
+
+        assert_eq!(
+            err.to_string(),
+            "`sleep` for the API key did not finish within 1 seconds"
+        );
+    }
+}
@@ -1473,6 +1473,7 @@ permissions:
                         ],),
                     macos_accessibility: true,
                     macos_calendar: true,
+                    device_paths: Vec::new(),
                 }
             )
         );
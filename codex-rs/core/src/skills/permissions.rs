@@ -199,6 +199,7 @@ fn build_macos_seatbelt_profile_extensions(
         ),
         macos_accessibility: permissions.accessibility,
         macos_calendar: permissions.calendar,
+        device_paths: Vec::new(),
     };
     Some(extensions)
 }
@@ -486,6 +487,7 @@ mod tests {
                         ],),
                     macos_accessibility: true,
                     macos_calendar: true,
+                    device_paths: Vec::new(),
                 }
             )
         );
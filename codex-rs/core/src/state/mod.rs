@@ -1,9 +1,12 @@
 mod service;
 mod session;
 mod turn;
+mod usage;
 
 pub(crate) use service::SessionServices;
 pub(crate) use session::SessionState;
 pub(crate) use turn::ActiveTurn;
 pub(crate) use turn::RunningTask;
 pub(crate) use turn::TaskKind;
+pub(crate) use usage::UsageTracker;
+pub(crate) use usage::cost_usd;
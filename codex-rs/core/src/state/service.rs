@@ -4,18 +4,24 @@ use crate::AuthManager;
 use crate::RolloutRecorder;
 use crate::agent::AgentControl;
 use crate::analytics_client::AnalyticsEventsClient;
+use crate::budget::BudgetTracker;
 use crate::client::ModelClient;
 use crate::config::StartedNetworkProxy;
 use crate::exec_policy::ExecPolicyManager;
+use crate::file_snapshots::FileSnapshots;
 use crate::file_watcher::FileWatcher;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::models_manager::manager::ModelsManager;
+use crate::redaction::SecretRedactor;
 use crate::skills::SkillsManager;
 use crate::state_db::StateDbHandle;
 use crate::tools::network_approval::NetworkApprovalService;
 use crate::tools::sandboxing::ApprovalStore;
 use crate::unified_exec::UnifiedExecProcessManager;
+use crate::workspace_index::SessionWorkspaceIndex;
 use crate::zsh_exec_bridge::ZshExecBridge;
+use arc_swap::ArcSwap;
+use codex_git::SessionWorktree;
 use codex_hooks::Hooks;
 use codex_otel::OtelManager;
 use tokio::sync::Mutex;
@@ -29,7 +35,8 @@ pub(crate) struct SessionServices {
     pub(crate) unified_exec_manager: UnifiedExecProcessManager,
     pub(crate) zsh_exec_bridge: ZshExecBridge,
     pub(crate) analytics_events_client: AnalyticsEventsClient,
-    pub(crate) hooks: Hooks,
+    /// Replaced when `notify` changes in config.toml.
+    pub(crate) hooks: ArcSwap<Hooks>,
     pub(crate) rollout: Mutex<Option<RolloutRecorder>>,
     pub(crate) user_shell: Arc<crate::shell::Shell>,
     pub(crate) shell_snapshot_tx: watch::Sender<Option<Arc<crate::shell_snapshot::ShellSnapshot>>>,
@@ -39,12 +46,21 @@ pub(crate) struct SessionServices {
     pub(crate) models_manager: Arc<ModelsManager>,
     pub(crate) otel_manager: OtelManager,
     pub(crate) tool_approvals: Mutex<ApprovalStore>,
+    pub(crate) budget: Mutex<BudgetTracker>,
+    /// File contents as the model last saw them, used to merge patches with concurrent edits.
+    pub(crate) file_snapshots: Mutex<FileSnapshots>,
+    /// Masks configured secrets in tool output before it is recorded or sent to the model.
+    pub(crate) secret_redactor: SecretRedactor,
     pub(crate) skills_manager: Arc<SkillsManager>,
     pub(crate) file_watcher: Arc<FileWatcher>,
     pub(crate) agent_control: AgentControl,
     pub(crate) network_proxy: Option<StartedNetworkProxy>,
     pub(crate) network_approval: Arc<NetworkApprovalService>,
     pub(crate) state_db: Option<StateDbHandle>,
+    /// Worktree the session edits instead of the repository it was started from.
+    pub(crate) worktree: Option<SessionWorktree>,
+    /// Keeps the workspace index of the session's working directory alive while it runs.
+    pub(crate) workspace_index: SessionWorkspaceIndex,
     /// Session-scoped model client shared across turns.
     pub(crate) model_client: ModelClient,
 }
@@ -3,12 +3,17 @@
 use codex_protocol::models::ResponseItem;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::collections::VecDeque;
 
 use crate::codex::SessionConfiguration;
 use crate::context_manager::ContextManager;
+use crate::pinned_context::PinnedContextItem;
 use crate::protocol::RateLimitSnapshot;
+use crate::protocol::SessionConfiguredEvent;
+use crate::protocol::Submission;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
+use crate::state::UsageTracker;
 use crate::tasks::RegularTask;
 use crate::truncate::TruncationPolicy;
 
@@ -31,6 +36,18 @@ pub(crate) struct SessionState {
     pub(crate) startup_regular_task: Option<RegularTask>,
     pub(crate) active_mcp_tool_selection: Option<Vec<String>>,
     pub(crate) active_connector_selection: HashSet<String>,
+    /// Context items pinned by the user; re-attached verbatim after compaction.
+    pinned_context: Vec<PinnedContextItem>,
+    next_pinned_context_id: u64,
+    /// Whether user input has carried a file excerpt, e.g. from `codex exec --attach`.
+    pub(crate) file_excerpt_in_input: bool,
+    pub(crate) usage: UsageTracker,
+    /// The `SessionConfigured` event sent at startup, sent again with the restored transcript
+    /// after `Op::ResumeConversation`.
+    pub(crate) session_configured: Option<SessionConfiguredEvent>,
+    /// `Op::UserInput` submissions received while a turn was running, started in order as turns
+    /// end.
+    pub(crate) queued_user_input: VecDeque<Submission>,
 }
 
 impl SessionState {
@@ -49,6 +66,12 @@ impl SessionState {
             startup_regular_task: None,
             active_mcp_tool_selection: None,
             active_connector_selection: HashSet::new(),
+            pinned_context: Vec::new(),
+            next_pinned_context_id: 1,
+            file_excerpt_in_input: false,
+            usage: UsageTracker::default(),
+            session_configured: None,
+            queued_user_input: VecDeque::new(),
         }
     }
 
@@ -217,6 +240,53 @@ impl SessionState {
     pub(crate) fn clear_connector_selection(&mut self) {
         self.active_connector_selection.clear();
     }
+
+    // Allocates the id for the next pinned context item.
+    pub(crate) fn next_pinned_context_id(&mut self) -> String {
+        let id = format!("pin-{}", self.next_pinned_context_id);
+        self.next_pinned_context_id += 1;
+        id
+    }
+
+    pub(crate) fn add_pinned_context(&mut self, item: PinnedContextItem) {
+        self.pinned_context.push(item);
+    }
+
+    // Adds a pinned item replayed from the rollout, keeping the ids allocated later unique.
+    pub(crate) fn restore_pinned_context(&mut self, item: PinnedContextItem) {
+        if let Some(number) = item
+            .id()
+            .strip_prefix("pin-")
+            .and_then(|number| number.parse::<u64>().ok())
+        {
+            self.next_pinned_context_id = self.next_pinned_context_id.max(number + 1);
+        }
+        self.pinned_context
+            .retain(|pinned| pinned.id() != item.id());
+        self.pinned_context.push(item);
+    }
+
+    // Removes the pinned item with `id`, returning it when present.
+    pub(crate) fn remove_pinned_context(&mut self, id: &str) -> Option<PinnedContextItem> {
+        let index = self
+            .pinned_context
+            .iter()
+            .position(|item| item.id() == id)?;
+        Some(self.pinned_context.remove(index))
+    }
+
+    pub(crate) fn pinned_context(&self) -> &[PinnedContextItem] {
+        &self.pinned_context
+    }
+
+    // Whether the history may hold a file excerpt, in which case turns offer `read_file_range`.
+    pub(crate) fn has_file_excerpts(&self) -> bool {
+        self.file_excerpt_in_input
+            || self
+                .pinned_context
+                .iter()
+                .any(PinnedContextItem::is_excerpt)
+    }
 }
 
 // Sometimes new snapshots don't include credits or plan information.
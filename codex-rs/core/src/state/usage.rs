@@ -0,0 +1,116 @@
+//! Token usage and cost accounting for a session.
+
+use crate::config::types::ModelPricing;
+use crate::protocol::TokenUsage;
+use crate::protocol::UsageReport;
+use crate::protocol::UsageSummary;
+
+/// Accumulates usage per turn and per session.
+///
+/// Unlike the context-window accounting in `ContextManager`, these totals only ever grow: they
+/// count every token the provider billed, including compaction requests.
+#[derive(Debug)]
+pub(crate) struct UsageTracker {
+    turn: UsageSummary,
+    session: UsageSummary,
+}
+
+impl Default for UsageTracker {
+    fn default() -> Self {
+        Self {
+            turn: empty_summary(),
+            session: empty_summary(),
+        }
+    }
+}
+
+impl UsageTracker {
+    /// Starts counting a new turn; the session totals are kept.
+    pub(crate) fn start_turn(&mut self) {
+        self.turn = empty_summary();
+    }
+
+    pub(crate) fn record(&mut self, usage: &TokenUsage, pricing: Option<&ModelPricing>) {
+        let cost = pricing.map(|pricing| cost_usd(pricing, usage));
+        for summary in [&mut self.turn, &mut self.session] {
+            summary.token_usage.add_assign(usage);
+            summary.cost_usd = summary.cost_usd.zip(cost).map(|(total, cost)| total + cost);
+        }
+    }
+
+    pub(crate) fn report(&self) -> UsageReport {
+        UsageReport {
+            turn: self.turn.clone(),
+            session: self.session.clone(),
+        }
+    }
+}
+
+pub(crate) fn cost_usd(pricing: &ModelPricing, usage: &TokenUsage) -> f64 {
+    let cached_price = pricing.cached_input.unwrap_or(pricing.input);
+    (usage.non_cached_input() as f64 * pricing.input
+        + usage.cached_input() as f64 * cached_price
+        + usage.output_tokens.max(0) as f64 * pricing.output)
+        / 1_000_000.0
+}
+
+fn empty_summary() -> UsageSummary {
+    UsageSummary {
+        token_usage: TokenUsage::default(),
+        cost_usd: Some(0.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn usage(input: i64, cached: i64, output: i64) -> TokenUsage {
+        TokenUsage {
+            input_tokens: input,
+            cached_input_tokens: cached,
+            output_tokens: output,
+            reasoning_output_tokens: 0,
+            total_tokens: input + output,
+        }
+    }
+
+    #[test]
+    fn turn_usage_resets_while_session_usage_accumulates() {
+        let pricing = ModelPricing {
+            input: 2.0,
+            cached_input: Some(0.5),
+            output: 10.0,
+        };
+        let mut tracker = UsageTracker::default();
+        tracker.start_turn();
+        tracker.record(&usage(1_000_000, 0, 100_000), Some(&pricing));
+        tracker.start_turn();
+        tracker.record(&usage(1_000_000, 500_000, 0), Some(&pricing));
+
+        assert_eq!(
+            tracker.report(),
+            UsageReport {
+                turn: UsageSummary {
+                    token_usage: usage(1_000_000, 500_000, 0),
+                    cost_usd: Some(1.25),
+                },
+                session: UsageSummary {
+                    token_usage: usage(2_000_000, 500_000, 100_000),
+                    cost_usd: Some(4.25),
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn unpriced_usage_makes_cost_unknown() {
+        let mut tracker = UsageTracker::default();
+        tracker.start_turn();
+        tracker.record(&usage(10, 0, 5), None);
+
+        assert_eq!(tracker.report().turn.cost_usd, None);
+        assert_eq!(tracker.report().session.cost_usd, None);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::codex::TurnContext;
+use crate::protocol::CheckpointCreatedEvent;
 use crate::protocol::EventMsg;
 use crate::protocol::WarningEvent;
 use crate::state::TaskKind;
@@ -110,6 +111,16 @@ impl SessionTask for GhostSnapshotTask {
                                     ghost_commit: ghost_commit.clone(),
                                 }])
                                 .await;
+                            session
+                                .session
+                                .send_event(
+                                    &ctx_for_task,
+                                    EventMsg::CheckpointCreated(CheckpointCreatedEvent {
+                                        checkpoint_id: ghost_commit.id().to_string(),
+                                        turn_id: ctx_for_task.sub_id.clone(),
+                                    }),
+                                )
+                                .await;
                             info!("ghost commit captured: {}", ghost_commit.id());
                         }
                         Ok(Err(err)) => match err {
@@ -47,6 +47,21 @@ pub(crate) use user_shell::execute_user_shell_command;
 
 const GRACEFULL_INTERRUPTION_TIMEOUT_MS: u64 = 100;
 const TURN_ABORTED_INTERRUPTED_GUIDANCE: &str = "The user interrupted the previous turn on purpose. Any running unified exec processes were terminated. If any tools/commands were aborted, they may have partially executed; verify current state before retrying.";
+pub(crate) const TURN_ABORTED_UNFINISHED_GUIDANCE: &str = "The previous turn never finished because Codex stopped while it was running. Any tools/commands that were running may have partially executed; verify current state before retrying.";
+
+/// The model-visible marker recorded after a turn that was aborted, with `guidance` on what
+/// happened.
+pub(crate) fn turn_aborted_marker(guidance: &str) -> ResponseItem {
+    ResponseItem::Message {
+        id: None,
+        role: "user".to_string(),
+        content: vec![ContentItem::InputText {
+            text: format!("{TURN_ABORTED_OPEN_TAG}\n{guidance}\n</turn_aborted>"),
+        }],
+        end_turn: None,
+        phase: None,
+    }
+}
 
 /// Thin wrapper that exposes the parts of [`Session`] task runners need.
 #[derive(Clone)]
@@ -121,6 +136,7 @@ impl Session {
     ) {
         self.abort_all_tasks(TurnAbortReason::Replaced).await;
         self.clear_connector_selection().await;
+        self.start_usage_turn().await;
         self.seed_initial_context_if_needed(turn_context.as_ref())
             .await;
 
@@ -188,6 +204,8 @@ impl Session {
         }
         if reason == TurnAbortReason::Interrupted {
             self.close_unified_exec_processes().await;
+        } else {
+            self.notify_turn_ended();
         }
     }
 
@@ -222,11 +240,15 @@ impl Session {
             self.record_conversation_items(turn_context.as_ref(), &pending_response_items)
                 .await;
         }
+        self.stop_budget_clock().await;
+        let usage = self.usage_report().await;
         let event = EventMsg::TurnComplete(TurnCompleteEvent {
             turn_id: turn_context.sub_id.clone(),
             last_agent_message,
+            usage: Some(usage),
         });
         self.send_event(turn_context.as_ref(), event).await;
+        self.notify_turn_ended();
     }
 
     async fn register_new_active_task(&self, task: RunningTask) {
@@ -277,6 +299,7 @@ impl Session {
         }
 
         task.handle.abort();
+        self.stop_budget_clock().await;
 
         // Set previous model even when interrupted so model-switch handling stays correct.
         self.set_previous_model(Some(task.turn_context.model_info.slug.clone()))
@@ -288,17 +311,7 @@ impl Session {
             .await;
 
         if reason == TurnAbortReason::Interrupted {
-            let marker = ResponseItem::Message {
-                id: None,
-                role: "user".to_string(),
-                content: vec![ContentItem::InputText {
-                    text: format!(
-                        "{TURN_ABORTED_OPEN_TAG}\n{TURN_ABORTED_INTERRUPTED_GUIDANCE}\n</turn_aborted>"
-                    ),
-                }],
-                end_turn: None,
-                phase: None,
-            };
+            let marker = turn_aborted_marker(TURN_ABORTED_INTERRUPTED_GUIDANCE);
             self.record_into_history(std::slice::from_ref(&marker), task.turn_context.as_ref())
                 .await;
             self.persist_rollout_items(&[RolloutItem::ResponseItem(marker)])
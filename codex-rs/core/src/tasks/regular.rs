@@ -91,7 +91,12 @@ impl SessionTask for RegularTask {
         cancellation_token: CancellationToken,
     ) -> Option<String> {
         let sess = session.clone_session();
-        let run_turn_span = trace_span!("run_turn");
+        ctx.otel_manager.counter("codex.turn", 1, &[]);
+        let run_turn_span = trace_span!(
+            "run_turn",
+            turn_id = %ctx.sub_id,
+            model = %ctx.model_info.slug,
+        );
         sess.set_server_reasoning_included(false).await;
         sess.services
             .otel_manager
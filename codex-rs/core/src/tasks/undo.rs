@@ -17,11 +17,23 @@ use tracing::error;
 use tracing::info;
 use tracing::warn;
 
-pub(crate) struct UndoTask;
+/// Restores a ghost snapshot: the latest one, or a specific checkpoint along with every snapshot
+/// taken after it.
+pub(crate) struct UndoTask {
+    checkpoint_id: Option<String>,
+}
 
 impl UndoTask {
     pub(crate) fn new() -> Self {
-        Self
+        Self {
+            checkpoint_id: None,
+        }
+    }
+
+    pub(crate) fn to_checkpoint(checkpoint_id: String) -> Self {
+        Self {
+            checkpoint_id: Some(checkpoint_id),
+        }
     }
 }
 
@@ -77,13 +89,21 @@ impl SessionTask for UndoTask {
                 .enumerate()
                 .rev()
                 .find_map(|(idx, item)| match item {
-                    ResponseItem::GhostSnapshot { ghost_commit } => {
+                    ResponseItem::GhostSnapshot { ghost_commit }
+                        if self
+                            .checkpoint_id
+                            .as_deref()
+                            .is_none_or(|id| id == ghost_commit.id()) =>
+                    {
                         Some((idx, ghost_commit.clone()))
                     }
                     _ => None,
                 })
         else {
-            completed.message = Some("No ghost snapshot available to undo.".to_string());
+            completed.message = Some(match &self.checkpoint_id {
+                Some(id) => format!("Checkpoint {id} not found."),
+                None => "No ghost snapshot available to undo.".to_string(),
+            });
             sess.send_event(ctx.as_ref(), EventMsg::UndoCompleted(completed))
                 .await;
             return None;
@@ -100,7 +120,13 @@ impl SessionTask for UndoTask {
 
         match restore_result {
             Ok(Ok(())) => {
-                items.remove(idx);
+                // Later snapshots describe states that no longer exist on disk.
+                let mut index = 0;
+                items.retain(|item| {
+                    let keep = index < idx || !matches!(item, ResponseItem::GhostSnapshot { .. });
+                    index += 1;
+                    keep
+                });
                 sess.replace_history(items).await;
                 let short_id: String = commit_id.chars().take(7).collect();
                 info!(commit_id = commit_id, "Undo restored ghost snapshot");
@@ -24,6 +24,7 @@ use crate::protocol::ExecCommandSource;
 use crate::protocol::ExecCommandStatus;
 use crate::protocol::SandboxPolicy;
 use crate::protocol::TurnStartedEvent;
+use crate::resource_limits::wrap_command;
 use crate::sandboxing::ExecRequest;
 use crate::sandboxing::SandboxPermissions;
 use crate::state::TaskKind;
@@ -143,8 +144,10 @@ pub(crate) async fn execute_user_shell_command(
         )
         .await;
 
+    // User commands skip `SandboxManager::transform`, so apply the rlimits here.
+    let resource_limits = turn_context.config.resource_limits.clone();
     let exec_env = ExecRequest {
-        command: exec_command.clone(),
+        command: wrap_command(&resource_limits, exec_command),
         cwd: cwd.clone(),
         env: create_env(
             &turn_context.shell_environment_policy,
@@ -160,6 +163,7 @@ pub(crate) async fn execute_user_shell_command(
         sandbox_permissions: SandboxPermissions::UseDefault,
         justification: None,
         arg0: None,
+        resource_limits,
     };
 
     let stdout_stream = Some(StdoutStream {
@@ -183,6 +187,7 @@ pub(crate) async fn execute_user_shell_command(
                 aggregated_output: StreamOutput::new(aborted_message.clone()),
                 duration: Duration::ZERO,
                 timed_out: false,
+                resource_limit_exceeded: None,
             };
             persist_user_shell_output(
                 &session,
@@ -204,18 +209,22 @@ pub(crate) async fn execute_user_shell_command(
                         parsed_cmd: parsed_cmd.clone(),
                         source: ExecCommandSource::UserShell,
                         interaction_input: None,
-                        stdout: String::new(),
-                        stderr: aborted_message.clone(),
                         aggregated_output: aborted_message.clone(),
                         exit_code: -1,
                         duration: Duration::ZERO,
                         formatted_output: aborted_message,
+                        output_artifact: None,
                         status: ExecCommandStatus::Failed,
                     }),
                 )
                 .await;
         }
-        Ok(Ok(output)) => {
+        Ok(Ok(mut output)) => {
+            // Redacted before the event and the history item are built from it.
+            session
+                .services
+                .secret_redactor
+                .redact_exec_output(&mut output);
             session
                 .send_event(
                     turn_context.as_ref(),
@@ -228,8 +237,6 @@ pub(crate) async fn execute_user_shell_command(
                         parsed_cmd: parsed_cmd.clone(),
                         source: ExecCommandSource::UserShell,
                         interaction_input: None,
-                        stdout: output.stdout.text.clone(),
-                        stderr: output.stderr.text.clone(),
                         aggregated_output: output.aggregated_output.text.clone(),
                         exit_code: output.exit_code,
                         duration: output.duration,
@@ -237,6 +244,7 @@ pub(crate) async fn execute_user_shell_command(
                             &output,
                             turn_context.truncation_policy,
                         ),
+                        output_artifact: None,
                         status: if output.exit_code == 0 {
                             ExecCommandStatus::Completed
                         } else {
@@ -252,14 +260,19 @@ pub(crate) async fn execute_user_shell_command(
         Ok(Err(err)) => {
             error!("user shell command failed: {err:?}");
             let message = format!("execution error: {err:?}");
-            let exec_output = ExecToolCallOutput {
+            let mut exec_output = ExecToolCallOutput {
                 exit_code: -1,
                 stdout: StreamOutput::new(String::new()),
                 stderr: StreamOutput::new(message.clone()),
                 aggregated_output: StreamOutput::new(message.clone()),
                 duration: Duration::ZERO,
                 timed_out: false,
+                resource_limit_exceeded: None,
             };
+            session
+                .services
+                .secret_redactor
+                .redact_exec_output(&mut exec_output);
             session
                 .send_event(
                     turn_context.as_ref(),
@@ -272,8 +285,6 @@ pub(crate) async fn execute_user_shell_command(
                         parsed_cmd,
                         source: ExecCommandSource::UserShell,
                         interaction_input: None,
-                        stdout: exec_output.stdout.text.clone(),
-                        stderr: exec_output.stderr.text.clone(),
                         aggregated_output: exec_output.aggregated_output.text.clone(),
                         exit_code: exec_output.exit_code,
                         duration: exec_output.duration,
@@ -281,6 +292,7 @@ pub(crate) async fn execute_user_shell_command(
                             &exec_output,
                             turn_context.truncation_policy,
                         ),
+                        output_artifact: None,
                         status: ExecCommandStatus::Failed,
                     }),
                 )
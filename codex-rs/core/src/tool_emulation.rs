@@ -0,0 +1,453 @@
+//! Prompt-based tool calling for models without native tool support.
+//!
+//! Tools are described in the instructions and the model answers with `<tool_call>` blocks on
+//! their own lines. Those blocks are hidden from the streamed text and turned back into function
+//! calls once the message completes. Earlier calls and their outputs are replayed to the model as
+//! plain text, since the provider does not understand tool items.
+
+use crate::client_common::ResponseEvent;
+use crate::client_common::ResponseStream;
+use crate::client_common::tools::ToolSpec;
+use crate::error::Result;
+use crate::tagged_block_parser::TagSpec;
+use crate::tagged_block_parser::TaggedLineParser;
+use crate::tagged_block_parser::TaggedLineSegment;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use futures::StreamExt;
+use serde_json::Value;
+use serde_json::json;
+use std::collections::HashSet;
+use tokio::sync::mpsc;
+use tracing::warn;
+use uuid::Uuid;
+
+const TOOL_EMULATION_TEMPLATE: &str = include_str!("../templates/tool_emulation/instructions.md");
+const TOOLS_PLACEHOLDER: &str = "{{tools}}";
+const TOOL_CALL_OPEN_TAG: &str = "<tool_call>";
+const TOOL_CALL_CLOSE_TAG: &str = "</tool_call>";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EmulationTag {
+    ToolCall,
+}
+
+fn tool_call_parser() -> TaggedLineParser<EmulationTag> {
+    TaggedLineParser::new(vec![TagSpec {
+        open: TOOL_CALL_OPEN_TAG,
+        close: TOOL_CALL_CLOSE_TAG,
+        tag: EmulationTag::ToolCall,
+    }])
+}
+
+/// Renders the instructions section that teaches the model the `<tool_call>` format.
+///
+/// `tools` is the Responses API tool list. Hosted tools (web search, image generation) run on the
+/// provider side and cannot be emulated, so they are left out.
+pub(crate) fn tool_emulation_instructions(tools: &[Value]) -> Option<String> {
+    let described: Vec<Value> = tools.iter().filter_map(describe_tool).collect();
+    if described.is_empty() {
+        return None;
+    }
+    let rendered = described
+        .iter()
+        .map(Value::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some(TOOL_EMULATION_TEMPLATE.replace(TOOLS_PLACEHOLDER, &rendered))
+}
+
+fn describe_tool(tool: &Value) -> Option<Value> {
+    let name = tool.get("name")?.as_str()?;
+    let description = tool
+        .get("description")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    match tool.get("type").and_then(Value::as_str) {
+        Some("function") => Some(json!({
+            "name": name,
+            "description": description,
+            "parameters": tool.get("parameters").cloned().unwrap_or_else(|| json!({})),
+        })),
+        Some("custom") => Some(json!({
+            "name": name,
+            "description": description,
+            "input": "freeform string",
+        })),
+        _ => None,
+    }
+}
+
+/// Rewrites tool calls and their outputs in `input` into plain messages.
+pub(crate) fn emulate_tool_history(input: Vec<ResponseItem>) -> Vec<ResponseItem> {
+    input
+        .into_iter()
+        .map(|item| match item {
+            ResponseItem::FunctionCall {
+                name, arguments, ..
+            } => {
+                let arguments =
+                    serde_json::from_str::<Value>(&arguments).unwrap_or(Value::String(arguments));
+                tool_call_message(&name, arguments)
+            }
+            ResponseItem::CustomToolCall { name, input, .. } => {
+                tool_call_message(&name, Value::String(input))
+            }
+            ResponseItem::FunctionCallOutput { output, .. } => {
+                tool_result_message(output.body.to_text().unwrap_or_default())
+            }
+            ResponseItem::CustomToolCallOutput { output, .. } => tool_result_message(output),
+            other => other,
+        })
+        .collect()
+}
+
+fn tool_call_message(name: &str, arguments: Value) -> ResponseItem {
+    let call = json!({"name": name, "arguments": arguments});
+    text_message(
+        "assistant",
+        ContentItem::OutputText {
+            text: format!("{TOOL_CALL_OPEN_TAG}\n{call}\n{TOOL_CALL_CLOSE_TAG}"),
+        },
+    )
+}
+
+fn tool_result_message(output: String) -> ResponseItem {
+    text_message(
+        "user",
+        ContentItem::InputText {
+            text: format!("<tool_result>\n{output}\n</tool_result>"),
+        },
+    )
+}
+
+fn text_message(role: &str, content: ContentItem) -> ResponseItem {
+    ResponseItem::Message {
+        id: None,
+        role: role.to_string(),
+        content: vec![content],
+        end_turn: None,
+        phase: None,
+    }
+}
+
+/// Splits an assistant reply into its prose and the tool calls it requested. Calls to the tools in
+/// `freeform_tools` become custom tool calls; every other call is a function call.
+fn parse_tool_calls(text: &str, freeform_tools: &HashSet<String>) -> (String, Vec<ResponseItem>) {
+    let mut parser = tool_call_parser();
+    let mut prose = String::new();
+    let mut blocks = Vec::new();
+    let mut current = String::new();
+    for segment in parser.parse(text).into_iter().chain(parser.finish()) {
+        match segment {
+            TaggedLineSegment::Normal(text) => prose.push_str(&text),
+            TaggedLineSegment::TagStart(EmulationTag::ToolCall) => current.clear(),
+            TaggedLineSegment::TagDelta(EmulationTag::ToolCall, delta) => {
+                current.push_str(&delta);
+            }
+            TaggedLineSegment::TagEnd(EmulationTag::ToolCall) => {
+                blocks.push(std::mem::take(&mut current));
+            }
+        }
+    }
+    let calls = blocks
+        .iter()
+        .filter_map(|block| tool_call_item(block, freeform_tools))
+        .collect();
+    (prose.trim().to_string(), calls)
+}
+
+fn tool_call_item(block: &str, freeform_tools: &HashSet<String>) -> Option<ResponseItem> {
+    let call: Value = match serde_json::from_str(block.trim()) {
+        Ok(call) => call,
+        Err(err) => {
+            warn!("ignoring malformed emulated tool call: {err}");
+            return None;
+        }
+    };
+    let name = call.get("name")?.as_str()?.to_string();
+    let call_id = format!("call_{}", Uuid::new_v4().simple());
+    // Models often stringify the arguments object, so the kind of call follows the tool named.
+    let arguments = match call.get("arguments") {
+        Some(Value::String(text)) => text.clone(),
+        Some(arguments) => arguments.to_string(),
+        None if freeform_tools.contains(&name) => String::new(),
+        None => json!({}).to_string(),
+    };
+    Some(if freeform_tools.contains(&name) {
+        ResponseItem::CustomToolCall {
+            id: None,
+            status: None,
+            call_id,
+            name,
+            input: arguments,
+        }
+    } else {
+        ResponseItem::FunctionCall {
+            id: None,
+            name,
+            arguments,
+            call_id,
+        }
+    })
+}
+
+/// Converts `<tool_call>` blocks in streamed assistant messages into calls to `tools`, the tools
+/// sent with the request.
+pub(crate) fn map_emulated_tool_calls(
+    mut stream: ResponseStream,
+    tools: &[ToolSpec],
+) -> ResponseStream {
+    let freeform_tools: HashSet<String> = tools
+        .iter()
+        .filter_map(|tool| match tool {
+            ToolSpec::Freeform(tool) => Some(tool.name.clone()),
+            ToolSpec::Function(_) | ToolSpec::LocalShell {} | ToolSpec::WebSearch { .. } => None,
+        })
+        .collect();
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent>>(1600);
+    tokio::spawn(async move {
+        let mut parser = tool_call_parser();
+        while let Some(event) = stream.next().await {
+            let events = match event {
+                Ok(ResponseEvent::OutputItemAdded(item)) => {
+                    parser = tool_call_parser();
+                    vec![ResponseEvent::OutputItemAdded(item)]
+                }
+                Ok(ResponseEvent::OutputTextDelta(delta)) => visible_deltas(parser.parse(&delta)),
+                Ok(ResponseEvent::OutputItemDone(ResponseItem::Message {
+                    id,
+                    role,
+                    content,
+                    end_turn,
+                    phase,
+                })) if role == "assistant" => {
+                    let mut events = visible_deltas(parser.finish());
+                    let text: String = content
+                        .iter()
+                        .filter_map(|item| match item {
+                            ContentItem::OutputText { text } => Some(text.as_str()),
+                            ContentItem::InputText { .. } | ContentItem::InputImage { .. } => None,
+                        })
+                        .collect();
+                    let (prose, calls) = parse_tool_calls(&text, &freeform_tools);
+                    events.push(ResponseEvent::OutputItemDone(ResponseItem::Message {
+                        id,
+                        role,
+                        content: vec![ContentItem::OutputText { text: prose }],
+                        end_turn,
+                        phase,
+                    }));
+                    events.extend(calls.into_iter().map(ResponseEvent::OutputItemDone));
+                    events
+                }
+                Ok(event) => vec![event],
+                Err(err) => {
+                    let _ = tx_event.send(Err(err)).await;
+                    return;
+                }
+            };
+            for event in events {
+                if tx_event.send(Ok(event)).await.is_err() {
+                    return;
+                }
+            }
+        }
+    });
+    ResponseStream { rx_event }
+}
+
+fn visible_deltas(segments: Vec<TaggedLineSegment<EmulationTag>>) -> Vec<ResponseEvent> {
+    segments
+        .into_iter()
+        .filter_map(|segment| match segment {
+            TaggedLineSegment::Normal(text) => Some(ResponseEvent::OutputTextDelta(text)),
+            TaggedLineSegment::TagStart(_)
+            | TaggedLineSegment::TagDelta(..)
+            | TaggedLineSegment::TagEnd(_) => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn assistant_text(text: &str) -> ResponseItem {
+        text_message(
+            "assistant",
+            ContentItem::OutputText {
+                text: text.to_string(),
+            },
+        )
+    }
+
+    #[test]
+    fn instructions_describe_local_tools_only() {
+        let tools = vec![
+            json!({
+                "type": "function",
+                "name": "shell",
+                "description": "Runs a command",
+                "strict": false,
+                "parameters": {"type": "object"},
+            }),
+            json!({"type": "custom", "name": "apply_patch", "description": "Edits files"}),
+            json!({"type": "web_search"}),
+        ];
+
+        let instructions = tool_emulation_instructions(&tools).expect("instructions");
+
+        let expected_tools = [
+            json!({
+                "name": "shell",
+                "description": "Runs a command",
+                "parameters": {"type": "object"},
+            }),
+            json!({
+                "name": "apply_patch",
+                "description": "Edits files",
+                "input": "freeform string",
+            }),
+        ];
+        assert!(instructions.ends_with(&format!("{}\n{}\n", expected_tools[0], expected_tools[1])));
+        assert_eq!(
+            tool_emulation_instructions(&[json!({"type": "web_search"})]),
+            None
+        );
+    }
+
+    fn freeform_tools() -> HashSet<String> {
+        HashSet::from(["apply_patch".to_string()])
+    }
+
+    #[test]
+    fn parses_prose_and_tool_calls() {
+        let (prose, calls) = parse_tool_calls(
+            "Listing files.\n<tool_call>\n{\"name\": \"shell\", \"arguments\": {\"command\": [\"ls\"]}}\n</tool_call>\n<tool_call>\n{\"name\": \"apply_patch\", \"arguments\": \"*** Begin Patch\"}\n</tool_call>\n",
+            &freeform_tools(),
+        );
+
+        assert_eq!(prose, "Listing files.");
+        let calls: Vec<(String, String)> = calls
+            .into_iter()
+            .map(|call| match call {
+                ResponseItem::FunctionCall {
+                    name, arguments, ..
+                } => (name, arguments),
+                ResponseItem::CustomToolCall { name, input, .. } => (name, input),
+                other => panic!("unexpected item: {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            calls,
+            vec![
+                ("shell".to_string(), "{\"command\":[\"ls\"]}".to_string()),
+                ("apply_patch".to_string(), "*** Begin Patch".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn stringified_function_arguments_stay_a_function_call() {
+        let (_, calls) = parse_tool_calls(
+            "<tool_call>\n{\"name\": \"shell\", \"arguments\": \"{\\\"command\\\": [\\\"ls\\\"]}\"}\n</tool_call>\n",
+            &freeform_tools(),
+        );
+
+        assert_eq!(calls.len(), 1);
+        assert!(matches!(
+            &calls[0],
+            ResponseItem::FunctionCall { name, arguments, .. }
+                if name == "shell" && arguments == "{\"command\": [\"ls\"]}"
+        ));
+    }
+
+    #[test]
+    fn malformed_tool_calls_are_ignored() {
+        let (prose, calls) =
+            parse_tool_calls("<tool_call>\nnot json\n</tool_call>\n", &freeform_tools());
+
+        assert_eq!(prose, "");
+        assert_eq!(calls, Vec::new());
+    }
+
+    #[test]
+    fn history_replays_tool_calls_as_text() {
+        let history = emulate_tool_history(vec![
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: "{\"command\":[\"ls\"]}".to_string(),
+                call_id: "call_1".to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "call_1".to_string(),
+                output: FunctionCallOutputPayload::from_text("README.md".to_string()),
+            },
+        ]);
+
+        assert_eq!(
+            history,
+            vec![
+                assistant_text(&format!(
+                    "<tool_call>\n{}\n</tool_call>",
+                    json!({"name": "shell", "arguments": {"command": ["ls"]}})
+                )),
+                text_message(
+                    "user",
+                    ContentItem::InputText {
+                        text: "<tool_result>\nREADME.md\n</tool_result>".to_string(),
+                    },
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn stream_hides_tool_call_blocks_and_emits_calls() {
+        let (tx, rx_event) = mpsc::channel(16);
+        let text =
+            "Checking.\n<tool_call>\n{\"name\": \"shell\", \"arguments\": {}}\n</tool_call>\n";
+        for event in [
+            ResponseEvent::OutputItemAdded(assistant_text("")),
+            ResponseEvent::OutputTextDelta("Checking.\n<tool_".to_string()),
+            ResponseEvent::OutputTextDelta(
+                "call>\n{\"name\": \"shell\", \"arguments\": {}}\n</tool_call>\n".to_string(),
+            ),
+            ResponseEvent::OutputItemDone(assistant_text(text)),
+        ] {
+            tx.send(Ok(event)).await.expect("send");
+        }
+        drop(tx);
+
+        let events: Vec<ResponseEvent> = map_emulated_tool_calls(ResponseStream { rx_event }, &[])
+            .map(|event| event.expect("event"))
+            .collect()
+            .await;
+
+        let deltas: String = events
+            .iter()
+            .filter_map(|event| match event {
+                ResponseEvent::OutputTextDelta(delta) => Some(delta.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(deltas, "Checking.\n");
+        let done: Vec<&ResponseItem> = events
+            .iter()
+            .filter_map(|event| match event {
+                ResponseEvent::OutputItemDone(item) => Some(item),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(done.len(), 2);
+        assert_eq!(done[0], &assistant_text("Checking."));
+        assert!(matches!(
+            done[1],
+            ResponseItem::FunctionCall { name, arguments, .. } if name == "shell" && arguments == "{}"
+        ));
+    }
+}
@@ -3,6 +3,7 @@ use crate::codex::TurnContext;
 use crate::error::CodexErr;
 use crate::error::SandboxErr;
 use crate::exec::ExecToolCallOutput;
+use crate::features::Feature;
 use crate::function_tool::FunctionCallError;
 use crate::parse_command::parse_command;
 use crate::protocol::EventMsg;
@@ -22,6 +23,7 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 use std::time::Duration;
+use tracing::warn;
 
 use super::format_exec_output_str;
 
@@ -70,6 +72,12 @@ pub(crate) async fn emit_exec_command_begin(
     interaction_input: Option<String>,
     process_id: Option<&str>,
 ) {
+    if matches!(
+        source,
+        ExecCommandSource::Agent | ExecCommandSource::UnifiedExecStartup
+    ) {
+        ctx.session.services.budget.lock().await.record_exec_call();
+    }
     ctx.session
         .send_event(
             ctx.turn,
@@ -149,6 +157,15 @@ impl ToolEmitter {
     }
 
     pub async fn emit(&self, ctx: ToolEventCtx<'_>, stage: ToolEventStage) {
+        self.emit_with_output_artifact(ctx, stage, None).await;
+    }
+
+    async fn emit_with_output_artifact(
+        &self,
+        ctx: ToolEventCtx<'_>,
+        stage: ToolEventStage,
+        output_artifact: Option<&Path>,
+    ) {
         match (self, stage) {
             (
                 Self::Shell {
@@ -162,7 +179,15 @@ impl ToolEmitter {
             ) => {
                 emit_exec_stage(
                     ctx,
-                    ExecCommandInput::new(command, cwd.as_path(), parsed_cmd, *source, None, None),
+                    ExecCommandInput::new(
+                        command,
+                        cwd.as_path(),
+                        parsed_cmd,
+                        *source,
+                        None,
+                        None,
+                        output_artifact,
+                    ),
                     stage,
                 )
                 .await;
@@ -271,6 +296,7 @@ impl ToolEmitter {
                         *source,
                         None,
                         process_id.as_deref(),
+                        output_artifact,
                     ),
                     stage,
                 )
@@ -286,13 +312,51 @@ impl ToolEmitter {
     fn format_exec_output_for_model(
         &self,
         output: &ExecToolCallOutput,
+        output_artifact: Option<&Path>,
         ctx: ToolEventCtx<'_>,
     ) -> String {
         match self {
-            Self::Shell { freeform: true, .. } => {
-                super::format_exec_output_for_model_freeform(output, ctx.turn.truncation_policy)
+            Self::Shell { freeform: true, .. } => super::format_exec_output_for_model_freeform(
+                output,
+                ctx.turn.truncation_policy,
+                output_artifact,
+            ),
+            _ => super::format_exec_output_for_model_structured(
+                output,
+                ctx.turn.truncation_policy,
+                output_artifact,
+            ),
+        }
+    }
+
+    /// Saves the full output of a shell command that will be truncated for the model, so the
+    /// user (and the model) can still get at the lines that were cut.
+    async fn write_output_artifact(
+        &self,
+        output: &ExecToolCallOutput,
+        ctx: ToolEventCtx<'_>,
+    ) -> Option<PathBuf> {
+        if !matches!(self, Self::Shell { .. })
+            || !ctx.turn.features.enabled(Feature::ExecOutputArtifacts)
+        {
+            return None;
+        }
+        let codex_home = ctx.session.codex_home().await;
+        match super::write_exec_output_artifact(
+            &codex_home,
+            &ctx.session.conversation_id.to_string(),
+            ctx.call_id,
+            output,
+            ctx.turn.truncation_policy,
+            &ctx.session.services.secret_redactor,
+        )
+        .await
+        {
+            Ok(path) => path,
+            Err(err) => {
+                warn!("failed to save exec output artifact: {err}");
+                None
             }
-            _ => super::format_exec_output_for_model_structured(output, ctx.turn.truncation_policy),
         }
     }
 
@@ -301,9 +365,11 @@ impl ToolEmitter {
         ctx: ToolEventCtx<'_>,
         out: Result<ExecToolCallOutput, ToolError>,
     ) -> Result<String, FunctionCallError> {
-        let (event, result) = match out {
+        let (event, result, output_artifact) = match out {
             Ok(output) => {
-                let content = self.format_exec_output_for_model(&output, ctx);
+                let output_artifact = self.write_output_artifact(&output, ctx).await;
+                let content =
+                    self.format_exec_output_for_model(&output, output_artifact.as_deref(), ctx);
                 let exit_code = output.exit_code;
                 let event = ToolEventStage::Success(output);
                 let result = if exit_code == 0 {
@@ -311,20 +377,22 @@ impl ToolEmitter {
                 } else {
                     Err(FunctionCallError::RespondToModel(content))
                 };
-                (event, result)
+                (event, result, output_artifact)
             }
             Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { output })))
             | Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output, .. }))) => {
-                let response = self.format_exec_output_for_model(&output, ctx);
+                let output_artifact = self.write_output_artifact(&output, ctx).await;
+                let response =
+                    self.format_exec_output_for_model(&output, output_artifact.as_deref(), ctx);
                 let event = ToolEventStage::Failure(ToolEventFailure::Output(*output));
                 let result = Err(FunctionCallError::RespondToModel(response));
-                (event, result)
+                (event, result, output_artifact)
             }
             Err(ToolError::Codex(err)) => {
                 let message = format!("execution error: {err:?}");
                 let event = ToolEventStage::Failure(ToolEventFailure::Message(message.clone()));
                 let result = Err(FunctionCallError::RespondToModel(message));
-                (event, result)
+                (event, result, None)
             }
             Err(ToolError::Rejected(msg)) => {
                 // Normalize common rejection messages for exec tools so tests and
@@ -348,10 +416,11 @@ impl ToolEmitter {
                 };
                 let event = ToolEventStage::Failure(ToolEventFailure::Rejected(normalized.clone()));
                 let result = Err(FunctionCallError::RespondToModel(normalized));
-                (event, result)
+                (event, result, None)
             }
         };
-        self.emit(ctx, event).await;
+        self.emit_with_output_artifact(ctx, event, output_artifact.as_deref())
+            .await;
         result
     }
 }
@@ -363,6 +432,7 @@ struct ExecCommandInput<'a> {
     source: ExecCommandSource,
     interaction_input: Option<&'a str>,
     process_id: Option<&'a str>,
+    output_artifact: Option<&'a Path>,
 }
 
 impl<'a> ExecCommandInput<'a> {
@@ -373,6 +443,7 @@ impl<'a> ExecCommandInput<'a> {
         source: ExecCommandSource,
         interaction_input: Option<&'a str>,
         process_id: Option<&'a str>,
+        output_artifact: Option<&'a Path>,
     ) -> Self {
         Self {
             command,
@@ -381,13 +452,12 @@ impl<'a> ExecCommandInput<'a> {
             source,
             interaction_input,
             process_id,
+            output_artifact,
         }
     }
 }
 
 struct ExecCommandResult {
-    stdout: String,
-    stderr: String,
     aggregated_output: String,
     exit_code: i32,
     duration: Duration,
@@ -416,8 +486,6 @@ async fn emit_exec_stage(
         ToolEventStage::Success(output)
         | ToolEventStage::Failure(ToolEventFailure::Output(output)) => {
             let exec_result = ExecCommandResult {
-                stdout: output.stdout.text.clone(),
-                stderr: output.stderr.text.clone(),
                 aggregated_output: output.aggregated_output.text.clone(),
                 exit_code: output.exit_code,
                 duration: output.duration,
@@ -433,8 +501,6 @@ async fn emit_exec_stage(
         ToolEventStage::Failure(ToolEventFailure::Message(message)) => {
             let text = message.to_string();
             let exec_result = ExecCommandResult {
-                stdout: String::new(),
-                stderr: text.clone(),
                 aggregated_output: text.clone(),
                 exit_code: -1,
                 duration: Duration::ZERO,
@@ -446,8 +512,6 @@ async fn emit_exec_stage(
         ToolEventStage::Failure(ToolEventFailure::Rejected(message)) => {
             let text = message.to_string();
             let exec_result = ExecCommandResult {
-                stdout: String::new(),
-                stderr: text.clone(),
                 aggregated_output: text.clone(),
                 exit_code: -1,
                 duration: Duration::ZERO,
@@ -464,27 +528,29 @@ async fn emit_exec_end(
     exec_input: ExecCommandInput<'_>,
     exec_result: ExecCommandResult,
 ) {
+    let mut event = ExecCommandEndEvent {
+        call_id: ctx.call_id.to_string(),
+        process_id: exec_input.process_id.map(str::to_owned),
+        turn_id: ctx.turn.sub_id.clone(),
+        command: exec_input.command.to_vec(),
+        cwd: exec_input.cwd.to_path_buf(),
+        parsed_cmd: exec_input.parsed_cmd.to_vec(),
+        source: exec_input.source,
+        interaction_input: exec_input.interaction_input.map(str::to_owned),
+        aggregated_output: exec_result.aggregated_output,
+        exit_code: exec_result.exit_code,
+        duration: exec_result.duration,
+        formatted_output: exec_result.formatted_output,
+        output_artifact: exec_input.output_artifact.map(Path::to_path_buf),
+        status: exec_result.status,
+    };
+    // Redacted here so neither the client nor the rollout file sees the secret.
     ctx.session
-        .send_event(
-            ctx.turn,
-            EventMsg::ExecCommandEnd(ExecCommandEndEvent {
-                call_id: ctx.call_id.to_string(),
-                process_id: exec_input.process_id.map(str::to_owned),
-                turn_id: ctx.turn.sub_id.clone(),
-                command: exec_input.command.to_vec(),
-                cwd: exec_input.cwd.to_path_buf(),
-                parsed_cmd: exec_input.parsed_cmd.to_vec(),
-                source: exec_input.source,
-                interaction_input: exec_input.interaction_input.map(str::to_owned),
-                stdout: exec_result.stdout,
-                stderr: exec_result.stderr,
-                aggregated_output: exec_result.aggregated_output,
-                exit_code: exec_result.exit_code,
-                duration: exec_result.duration,
-                formatted_output: exec_result.formatted_output,
-                status: exec_result.status,
-            }),
-        )
+        .services
+        .secret_redactor
+        .redact_exec_end(&mut event);
+    ctx.session
+        .send_event(ctx.turn, EventMsg::ExecCommandEnd(event))
         .await;
 }
 
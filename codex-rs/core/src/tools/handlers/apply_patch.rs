@@ -29,13 +29,19 @@ use crate::tools::spec::ApplyPatchToolArgs;
 use crate::tools::spec::JsonSchema;
 use async_trait::async_trait;
 use codex_apply_patch::ApplyPatchAction;
+use codex_apply_patch::ApplyPatchConflict;
+use codex_apply_patch::ApplyPatchError;
 use codex_apply_patch::ApplyPatchFileChange;
+use codex_apply_patch::MaybeApplyPatchVerified;
 use codex_utils_absolute_path::AbsolutePathBuf;
 
 pub struct ApplyPatchHandler;
 
 const APPLY_PATCH_LARK_GRAMMAR: &str = include_str!("tool_apply_patch.lark");
 
+/// Lines shown around each conflict when reporting a patch that could not be merged.
+const CONFLICT_REPORT_CONTEXT_LINES: usize = 3;
+
 fn file_paths_for_action(action: &ApplyPatchAction) -> Vec<AbsolutePathBuf> {
     let mut keys = Vec::new();
     let cwd = action.cwd.as_path();
@@ -60,6 +66,101 @@ fn to_abs_path(cwd: &Path, path: &Path) -> Option<AbsolutePathBuf> {
     AbsolutePathBuf::resolve_path_against_base(path, cwd).ok()
 }
 
+/// Verifies an apply_patch invocation. Updates to files that changed since the model last saw
+/// them are merged with the contents recorded for the session.
+async fn verify_apply_patch(
+    session: &Session,
+    command: &[String],
+    cwd: &Path,
+) -> MaybeApplyPatchVerified {
+    let snapshots = session.services.file_snapshots.lock().await;
+    codex_apply_patch::maybe_parse_apply_patch_verified_with_base(command, cwd, |path| {
+        snapshots.get(path)
+    })
+}
+
+/// Tells the model which files were left unchanged because its edits overlapped with changes
+/// made since it last saw them.
+fn format_conflict_report(conflicts: &[ApplyPatchConflict]) -> String {
+    let mut report = String::from(
+        "These files changed since you last saw them and your edits conflict with those changes, so they were left unchanged. In each conflict, `ours` is your patch, `original` is the file as you last saw it, and `theirs` is the file on disk. Re-read the files before patching them again.",
+    );
+    for conflict in conflicts {
+        report.push_str(&format!(
+            "\n\n{}:\n{}",
+            conflict.path.display(),
+            conflict_regions(&conflict.merged)
+        ));
+    }
+    report
+}
+
+/// Returns the conflicts in `merged` with a few lines of context, eliding everything else.
+fn conflict_regions(merged: &str) -> String {
+    let lines: Vec<&str> = merged.lines().collect();
+    let mut shown = vec![false; lines.len()];
+    let mut conflict_start = None;
+    for (index, line) in lines.iter().enumerate() {
+        if line.starts_with("<<<<<<<") {
+            conflict_start = Some(index);
+        } else if line.starts_with(">>>>>>>")
+            && let Some(start) = conflict_start.take()
+        {
+            let first = start.saturating_sub(CONFLICT_REPORT_CONTEXT_LINES);
+            let last = (index + CONFLICT_REPORT_CONTEXT_LINES + 1).min(lines.len());
+            shown[first..last].fill(true);
+        }
+    }
+
+    let mut regions = Vec::new();
+    let mut elided = false;
+    for (line, shown) in lines.iter().zip(&shown) {
+        if *shown {
+            regions.push(*line);
+            elided = false;
+        } else if !elided {
+            regions.push("...");
+            elided = true;
+        }
+    }
+    regions.join("\n")
+}
+
+/// Explains why a patch failed verification. When it did not match the files, the status of every
+/// hunk is listed so the model can fix them all at once.
+fn verification_failure_message(command: &[String], cwd: &Path, error: &ApplyPatchError) -> String {
+    let message = format!("apply_patch verification failed: {error}");
+    if !matches!(
+        error,
+        ApplyPatchError::ComputeReplacements(_) | ApplyPatchError::IoError(_)
+    ) {
+        return message;
+    }
+    match codex_apply_patch::validate_apply_patch(command, cwd) {
+        Some(validation) if validation.hunks.len() > 1 => format!("{message}\n\n{validation}"),
+        _ => message,
+    }
+}
+
+/// Reports what applying `action` would do without changing any files.
+fn dry_run_report(action: &ApplyPatchAction) -> String {
+    match codex_apply_patch::parse_patch(&action.patch) {
+        Ok(args) => format!(
+            "Dry run; no files were changed.\n{}",
+            codex_apply_patch::validate_hunks(&args.hunks, &action.cwd)
+        ),
+        Err(err) => format!("apply_patch verification failed: {err}"),
+    }
+}
+
+fn with_conflict_report(content: String, conflicts: &[ApplyPatchConflict]) -> String {
+    if conflicts.is_empty() {
+        content
+    } else {
+        format!("{content}\n\n{}", format_conflict_report(conflicts))
+    }
+}
+
 #[async_trait]
 impl ToolHandler for ApplyPatchHandler {
     fn kind(&self) -> ToolKind {
@@ -87,12 +188,12 @@ impl ToolHandler for ApplyPatchHandler {
             payload,
         } = invocation;
 
-        let patch_input = match payload {
+        let (patch_input, dry_run) = match payload {
             ToolPayload::Function { arguments } => {
                 let args: ApplyPatchToolArgs = parse_arguments(&arguments)?;
-                args.input
+                (args.input, args.dry_run)
             }
-            ToolPayload::Custom { input } => input,
+            ToolPayload::Custom { input } => (input, false),
             _ => {
                 return Err(FunctionCallError::RespondToModel(
                     "apply_patch handler received unsupported payload".to_string(),
@@ -104,8 +205,23 @@ impl ToolHandler for ApplyPatchHandler {
         // Avoid building temporary ExecParams/command vectors; derive directly from inputs.
         let cwd = turn.cwd.clone();
         let command = vec!["apply_patch".to_string(), patch_input.clone()];
-        match codex_apply_patch::maybe_parse_apply_patch_verified(&command, &cwd) {
-            codex_apply_patch::MaybeApplyPatchVerified::Body(changes) => {
+        match verify_apply_patch(session.as_ref(), &command, &cwd).await {
+            MaybeApplyPatchVerified::Body(changes) => {
+                let conflicts = changes.conflicts().to_vec();
+                if changes.is_empty() && !conflicts.is_empty() {
+                    return Err(FunctionCallError::RespondToModel(format_conflict_report(
+                        &conflicts,
+                    )));
+                }
+                if dry_run {
+                    return Ok(ToolOutput::Function {
+                        body: FunctionCallOutputBody::Text(with_conflict_report(
+                            dry_run_report(&changes),
+                            &conflicts,
+                        )),
+                        success: Some(true),
+                    });
+                }
                 match apply_patch::apply_patch(turn.as_ref(), changes).await {
                     InternalApplyPatchInvocation::Output(item) => {
                         let content = item?;
@@ -156,29 +272,35 @@ impl ToolHandler for ApplyPatchHandler {
                             Some(&tracker),
                         );
                         let content = emitter.finish(event_ctx, out).await?;
+                        session
+                            .services
+                            .file_snapshots
+                            .lock()
+                            .await
+                            .record_patch(req.action.changes());
                         Ok(ToolOutput::Function {
-                            body: FunctionCallOutputBody::Text(content),
+                            body: FunctionCallOutputBody::Text(with_conflict_report(
+                                content, &conflicts,
+                            )),
                             success: Some(true),
                         })
                     }
                 }
             }
-            codex_apply_patch::MaybeApplyPatchVerified::CorrectnessError(parse_error) => {
-                Err(FunctionCallError::RespondToModel(format!(
-                    "apply_patch verification failed: {parse_error}"
-                )))
-            }
-            codex_apply_patch::MaybeApplyPatchVerified::ShellParseError(error) => {
-                tracing::trace!("Failed to parse apply_patch input, {error:?}");
+            MaybeApplyPatchVerified::CorrectnessError(parse_error) => {
                 Err(FunctionCallError::RespondToModel(
-                    "apply_patch handler received invalid patch input".to_string(),
+                    verification_failure_message(&command, &cwd, &parse_error),
                 ))
             }
-            codex_apply_patch::MaybeApplyPatchVerified::NotApplyPatch => {
+            MaybeApplyPatchVerified::ShellParseError(error) => {
+                tracing::trace!("Failed to parse apply_patch input, {error:?}");
                 Err(FunctionCallError::RespondToModel(
-                    "apply_patch handler received non-apply_patch input".to_string(),
+                    "apply_patch handler received invalid patch input".to_string(),
                 ))
             }
+            MaybeApplyPatchVerified::NotApplyPatch => Err(FunctionCallError::RespondToModel(
+                "apply_patch handler received non-apply_patch input".to_string(),
+            )),
         }
     }
 }
@@ -194,14 +316,20 @@ pub(crate) async fn intercept_apply_patch(
     call_id: &str,
     tool_name: &str,
 ) -> Result<Option<ToolOutput>, FunctionCallError> {
-    match codex_apply_patch::maybe_parse_apply_patch_verified(command, cwd) {
-        codex_apply_patch::MaybeApplyPatchVerified::Body(changes) => {
+    match verify_apply_patch(session, command, cwd).await {
+        MaybeApplyPatchVerified::Body(changes) => {
             session
                 .record_model_warning(
                     format!("apply_patch was requested via {tool_name}. Use the apply_patch tool instead of exec_command."),
                     turn,
                 )
                 .await;
+            let conflicts = changes.conflicts().to_vec();
+            if changes.is_empty() && !conflicts.is_empty() {
+                return Err(FunctionCallError::RespondToModel(format_conflict_report(
+                    &conflicts,
+                )));
+            }
             match apply_patch::apply_patch(turn, changes).await {
                 InternalApplyPatchInvocation::Output(item) => {
                     let content = item?;
@@ -243,23 +371,31 @@ pub(crate) async fn intercept_apply_patch(
                     let event_ctx =
                         ToolEventCtx::new(session, turn, call_id, tracker.as_ref().copied());
                     let content = emitter.finish(event_ctx, out).await?;
+                    session
+                        .services
+                        .file_snapshots
+                        .lock()
+                        .await
+                        .record_patch(req.action.changes());
                     Ok(Some(ToolOutput::Function {
-                        body: FunctionCallOutputBody::Text(content),
+                        body: FunctionCallOutputBody::Text(with_conflict_report(
+                            content, &conflicts,
+                        )),
                         success: Some(true),
                     }))
                 }
             }
         }
-        codex_apply_patch::MaybeApplyPatchVerified::CorrectnessError(parse_error) => {
-            Err(FunctionCallError::RespondToModel(format!(
-                "apply_patch verification failed: {parse_error}"
-            )))
+        MaybeApplyPatchVerified::CorrectnessError(parse_error) => {
+            Err(FunctionCallError::RespondToModel(
+                verification_failure_message(command, cwd, &parse_error),
+            ))
         }
-        codex_apply_patch::MaybeApplyPatchVerified::ShellParseError(error) => {
+        MaybeApplyPatchVerified::ShellParseError(error) => {
             tracing::trace!("Failed to parse apply_patch input, {error:?}");
             Ok(None)
         }
-        codex_apply_patch::MaybeApplyPatchVerified::NotApplyPatch => Ok(None),
+        MaybeApplyPatchVerified::NotApplyPatch => Ok(None),
     }
 }
 
@@ -286,6 +422,15 @@ pub(crate) fn create_apply_patch_json_tool() -> ToolSpec {
             description: Some(r#"The entire contents of the apply_patch command"#.to_string()),
         },
     );
+    properties.insert(
+        "dry_run".to_string(),
+        JsonSchema::Boolean {
+            description: Some(
+                "Check every hunk against the files and report whether it applies, applies with fuzzy matching, or conflicts, without changing anything. Defaults to false."
+                    .to_string(),
+            ),
+        },
+    );
 
     ToolSpec::Function(ResponsesApiTool {
         name: "apply_patch".to_string(),
@@ -370,8 +515,8 @@ It is important to remember:
 #[cfg(test)]
 mod tests {
     use super::*;
-    use codex_apply_patch::MaybeApplyPatchVerified;
     use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
     use tempfile::TempDir;
 
     #[test]
@@ -397,4 +542,75 @@ mod tests {
         let keys = file_paths_for_action(&action);
         assert_eq!(keys.len(), 2);
     }
+
+    #[test]
+    fn verification_failure_lists_every_hunk() {
+        let tmp = TempDir::new().expect("tmp");
+        let cwd = tmp.path();
+        std::fs::write(cwd.join("a.txt"), "one\ntwo\n").expect("write a.txt");
+        let patch = r#"*** Begin Patch
+*** Update File: a.txt
+@@
+-one
++ONE
+@@
+-three
++THREE
+*** Update File: b.txt
+@@
+-old
++new
+*** End Patch"#;
+        let argv = vec!["apply_patch".to_string(), patch.to_string()];
+        let error = match codex_apply_patch::maybe_parse_apply_patch_verified(&argv, cwd) {
+            MaybeApplyPatchVerified::CorrectnessError(error) => error,
+            other => panic!("expected a verification error, got: {other:?}"),
+        };
+
+        let message = verification_failure_message(&argv, cwd, &error);
+
+        let (_, report) = message
+            .split_once("\n\n")
+            .expect("message includes a report");
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(
+            lines[0],
+            "Patch validation: 1 clean, 0 fuzzy, 2 conflicting"
+        );
+        assert_eq!(lines[1], "- a.txt (chunk 1): applies");
+        assert!(
+            lines[2].starts_with("- a.txt (chunk 2): conflicts: Failed to find expected lines")
+        );
+        assert!(report.contains("- b.txt: conflicts: Failed to read file to update"));
+    }
+
+    #[test]
+    fn conflict_report_shows_conflicts_with_context() {
+        let mut lines: Vec<String> = (1..=12).map(|line| line.to_string()).collect();
+        lines.splice(
+            6..7,
+            [
+                "<<<<<<< ours",
+                "seven from the patch",
+                "||||||| original",
+                "7",
+                "=======",
+                "seven on disk",
+                ">>>>>>> theirs",
+            ]
+            .map(String::from),
+        );
+        let conflict = ApplyPatchConflict {
+            path: PathBuf::from("/repo/numbers.txt"),
+            merged: format!("{}\n", lines.join("\n")),
+        };
+
+        let report = format_conflict_report(&[conflict]);
+
+        let (_, regions) = report.split_once("\n\n").expect("report lists files");
+        assert_eq!(
+            regions,
+            "/repo/numbers.txt:\n...\n4\n5\n6\n<<<<<<< ours\nseven from the patch\n||||||| original\n7\n=======\nseven on disk\n>>>>>>> theirs\n8\n9\n10\n..."
+        );
+    }
 }
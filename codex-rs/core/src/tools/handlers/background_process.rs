@@ -0,0 +1,122 @@
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::is_safe_command::is_known_safe_command;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::handlers::unified_exec::ExecCommandArgs;
+use crate::tools::handlers::unified_exec::exec_command;
+use crate::tools::handlers::unified_exec::format_response;
+use crate::tools::handlers::unified_exec::get_command;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::unified_exec::UnifiedExecContext;
+
+/// Handles `start_background_process`, `read_background_output`, and
+/// `stop_background_process`. Background processes live in the session's unified exec process
+/// store, so they show up alongside other running terminals and are killed when the session ends.
+pub struct BackgroundProcessHandler;
+
+#[derive(Debug, Deserialize)]
+struct ReadBackgroundOutputArgs {
+    // Matches the `session_id` reported by `start_background_process`.
+    session_id: i32,
+    #[serde(default)]
+    max_output_tokens: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StopBackgroundProcessArgs {
+    session_id: i32,
+}
+
+#[async_trait]
+impl ToolHandler for BackgroundProcessHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn is_mutating(&self, invocation: &ToolInvocation) -> bool {
+        match invocation.tool_name.as_str() {
+            "start_background_process" => {
+                let ToolPayload::Function { arguments } = &invocation.payload else {
+                    return true;
+                };
+                let Ok(args) = serde_json::from_str::<ExecCommandArgs>(arguments) else {
+                    return true;
+                };
+                !is_known_safe_command(&get_command(&args, invocation.session.user_shell()))
+            }
+            "read_background_output" => false,
+            _ => true,
+        }
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            tracker,
+            call_id,
+            tool_name,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "background process handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let manager = &session.services.unified_exec_manager;
+        let content = match tool_name.as_str() {
+            "start_background_process" => {
+                let args: ExecCommandArgs = parse_arguments(&arguments)?;
+                let context = UnifiedExecContext::new(session.clone(), turn.clone(), call_id);
+                return exec_command(&context, &tracker, &tool_name, args.into_background()).await;
+            }
+            "read_background_output" => {
+                let args: ReadBackgroundOutputArgs = parse_arguments(&arguments)?;
+                let response = manager
+                    .read_output(&args.session_id.to_string(), args.max_output_tokens)
+                    .await
+                    .map_err(|err| {
+                        FunctionCallError::RespondToModel(format!(
+                            "read_background_output failed: {err}"
+                        ))
+                    })?;
+                format_response(&response)
+            }
+            "stop_background_process" => {
+                let args: StopBackgroundProcessArgs = parse_arguments(&arguments)?;
+                manager
+                    .terminate_process(&args.session_id.to_string())
+                    .await
+                    .map_err(|err| {
+                        FunctionCallError::RespondToModel(format!(
+                            "stop_background_process failed: {err}"
+                        ))
+                    })?;
+                format!("Stopped background process {}", args.session_id)
+            }
+            other => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "unsupported background process function {other}"
+                )));
+            }
+        };
+
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(content),
+            success: Some(true),
+        })
+    }
+}
@@ -0,0 +1,215 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::protocol::DelegateBeginEvent;
+use codex_protocol::protocol::DelegateEndEvent;
+use codex_protocol::protocol::DelegateProgressEvent;
+use codex_protocol::protocol::ErrorEvent;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_protocol::protocol::SubAgentSource;
+use codex_protocol::user_input::UserInput;
+use serde::Deserialize;
+use tokio_util::sync::CancellationToken;
+
+use crate::agent::next_thread_spawn_depth;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::codex_delegate::run_codex_thread_one_shot;
+use crate::config::Config;
+use crate::features::Feature;
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::multi_agents::build_agent_shared_config;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+/// Token budget of a delegate sub-agent when the model does not ask for less.
+pub(crate) const DELEGATE_MAX_TOKENS: i64 = 200_000;
+
+pub struct DelegateHandler;
+
+#[derive(Deserialize)]
+struct DelegateArgs {
+    task: String,
+    #[serde(default)]
+    allow_edits: bool,
+    max_tokens: Option<i64>,
+}
+
+#[async_trait]
+impl ToolHandler for DelegateHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            call_id,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "delegate handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+        let args: DelegateArgs = parse_arguments(&arguments)?;
+        let task = args.task.trim().to_string();
+        if task.is_empty() {
+            return Err(FunctionCallError::RespondToModel(
+                "task must not be empty".to_string(),
+            ));
+        }
+        let config = build_delegate_config(&turn, &args)?;
+
+        session
+            .send_event(
+                &turn,
+                DelegateBeginEvent {
+                    call_id: call_id.clone(),
+                    task: task.clone(),
+                }
+                .into(),
+            )
+            .await;
+        let result = run_delegate(&session, &turn, &call_id, config, task).await;
+        let (output, error) = match &result {
+            Ok(output) => (Some(output.clone()), None),
+            Err(error) => (None, Some(error.clone())),
+        };
+        session
+            .send_event(
+                &turn,
+                DelegateEndEvent {
+                    call_id,
+                    output,
+                    error,
+                }
+                .into(),
+            )
+            .await;
+
+        match result {
+            Ok(output) => Ok(ToolOutput::Function {
+                body: FunctionCallOutputBody::Text(output),
+                success: Some(true),
+            }),
+            Err(error) => Err(FunctionCallError::RespondToModel(format!(
+                "delegate did not finish: {error}"
+            ))),
+        }
+    }
+}
+
+/// The sub-agent inherits the turn's model and sandbox, but runs without approvals, stays
+/// read-only unless edits are allowed, cannot delegate or spawn agents itself, and stops at its
+/// own token budget.
+fn build_delegate_config(
+    turn: &TurnContext,
+    args: &DelegateArgs,
+) -> Result<Config, FunctionCallError> {
+    let mut config =
+        build_agent_shared_config(turn, next_thread_spawn_depth(&turn.session_source))?;
+    if !args.allow_edits {
+        config
+            .permissions
+            .sandbox_policy
+            .set(SandboxPolicy::new_read_only_policy())
+            .map_err(|err| {
+                FunctionCallError::RespondToModel(format!("sandbox_policy is invalid: {err}"))
+            })?;
+    }
+    config.features.disable(Feature::Delegate);
+    config.features.disable(Feature::Collab);
+    let max_tokens = args
+        .max_tokens
+        .unwrap_or(DELEGATE_MAX_TOKENS)
+        .clamp(1, DELEGATE_MAX_TOKENS);
+    config.budget.session.tokens = Some(
+        config
+            .budget
+            .session
+            .tokens
+            .map_or(max_tokens, |tokens| tokens.min(max_tokens)),
+    );
+    Ok(config)
+}
+
+/// Runs the sub-agent to completion, forwarding its events to the parent turn, and returns its
+/// final message.
+async fn run_delegate(
+    session: &Arc<Session>,
+    turn: &Arc<TurnContext>,
+    call_id: &str,
+    config: Config,
+    task: String,
+) -> Result<String, String> {
+    // Dropping the guard stops the sub-agent if the parent turn is interrupted mid-call.
+    let cancel_token = CancellationToken::new();
+    let _cancel_guard = cancel_token.clone().drop_guard();
+    let io = run_codex_thread_one_shot(
+        config,
+        Arc::clone(&session.services.auth_manager),
+        Arc::clone(&session.services.models_manager),
+        vec![UserInput::Text {
+            text: task,
+            text_elements: Vec::new(),
+        }],
+        Arc::clone(session),
+        Arc::clone(turn),
+        cancel_token,
+        None,
+        SubAgentSource::Other("delegate".to_string()),
+    )
+    .await
+    .map_err(|err| format!("failed to start sub-agent: {err}"))?;
+
+    let mut last_error = None;
+    while let Ok(event) = io.next_event().await {
+        match event.msg {
+            EventMsg::TurnComplete(complete) => {
+                return complete.last_agent_message.ok_or_else(|| {
+                    last_error
+                        .unwrap_or_else(|| "sub-agent finished without a final message".to_string())
+                });
+            }
+            EventMsg::TurnAborted(_) => {
+                return Err(last_error.unwrap_or_else(|| "sub-agent was interrupted".to_string()));
+            }
+            EventMsg::AgentMessageContentDelta(_)
+            | EventMsg::AgentReasoningRawContentDelta(_)
+            | EventMsg::ReasoningContentDelta(_)
+            | EventMsg::ReasoningRawContentDelta(_)
+            | EventMsg::PlanDelta(_)
+            | EventMsg::ExecCommandOutputDelta(_)
+            | EventMsg::RawResponseItem(_) => {}
+            msg => {
+                if let EventMsg::Error(ErrorEvent { message, .. }) = &msg {
+                    last_error = Some(message.clone());
+                }
+                session
+                    .send_event(
+                        turn,
+                        DelegateProgressEvent {
+                            call_id: call_id.to_string(),
+                            msg: Box::new(msg),
+                        }
+                        .into(),
+                    )
+                    .await;
+            }
+        }
+    }
+    Err(last_error.unwrap_or_else(|| "sub-agent exited before finishing".to_string()))
+}
@@ -0,0 +1,202 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use globset::GlobBuilder;
+use globset::GlobMatcher;
+use ignore::WalkBuilder;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::ensure_path_readable;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct GlobHandler;
+
+const DEFAULT_LIMIT: usize = 100;
+const MAX_LIMIT: usize = 2000;
+
+fn default_limit() -> usize {
+    DEFAULT_LIMIT
+}
+
+#[derive(Deserialize)]
+struct GlobArgs {
+    pattern: String,
+    #[serde(default)]
+    dir_path: Option<String>,
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+#[async_trait]
+impl ToolHandler for GlobHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation { payload, turn, .. } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "glob handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: GlobArgs = parse_arguments(&arguments)?;
+
+        let pattern = args.pattern.trim();
+        if pattern.is_empty() {
+            return Err(FunctionCallError::RespondToModel(
+                "pattern must not be empty".to_string(),
+            ));
+        }
+
+        if args.limit == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "limit must be greater than zero".to_string(),
+            ));
+        }
+
+        let root = match args.dir_path {
+            Some(dir_path) => {
+                let path = PathBuf::from(dir_path);
+                if !path.is_absolute() {
+                    return Err(FunctionCallError::RespondToModel(
+                        "dir_path must be an absolute path".to_string(),
+                    ));
+                }
+                path
+            }
+            None => turn.cwd.clone(),
+        };
+        ensure_path_readable(&turn, &root)?;
+        if !root.is_dir() {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "`{}` is not a directory",
+                root.display()
+            )));
+        }
+
+        let matcher = GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .map_err(|err| {
+                FunctionCallError::RespondToModel(format!("invalid glob pattern: {err}"))
+            })?
+            .compile_matcher();
+        let limit = args.limit.min(MAX_LIMIT);
+        let walk_root = root.clone();
+        let (matches, truncated) =
+            tokio::task::spawn_blocking(move || glob_files(&walk_root, &matcher, limit))
+                .await
+                .map_err(|err| {
+                    FunctionCallError::RespondToModel(format!("glob search failed: {err}"))
+                })?;
+
+        let mut output = Vec::with_capacity(matches.len() + 2);
+        output.push(format!("Absolute path: {}", root.display()));
+        if matches.is_empty() {
+            output.push("No files matched.".to_string());
+        }
+        output.extend(matches);
+        if truncated {
+            output.push(format!(
+                "More than {limit} files matched; narrow the pattern to see the rest."
+            ));
+        }
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(output.join("\n")),
+            success: Some(true),
+        })
+    }
+}
+
+/// Walks `root` honoring ignore files and returns the sorted relative paths of files matching
+/// `matcher`, capped at `limit`, plus whether more files matched.
+fn glob_files(root: &Path, matcher: &GlobMatcher, limit: usize) -> (Vec<String>, bool) {
+    let mut matches = Vec::new();
+    for entry in WalkBuilder::new(root)
+        .require_git(false)
+        .sort_by_file_path(Path::cmp)
+        .build()
+        .flatten()
+    {
+        if !entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_file())
+        {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        if !matcher.is_match(relative) {
+            continue;
+        }
+        if matches.len() == limit {
+            return (matches, true);
+        }
+        matches.push(relative.to_string_lossy().replace('\\', "/"));
+    }
+    (matches, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    fn matcher(pattern: &str) -> GlobMatcher {
+        GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .expect("valid glob")
+            .compile_matcher()
+    }
+
+    #[test]
+    fn glob_files_matches_relative_paths_and_skips_ignored() {
+        let temp = tempdir().expect("tempdir");
+        let root = temp.path();
+        std::fs::create_dir_all(root.join("src/nested")).unwrap();
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        std::fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        std::fs::write(root.join("main.rs"), "").unwrap();
+        std::fs::write(root.join("src/lib.rs"), "").unwrap();
+        std::fs::write(root.join("src/nested/mod.rs"), "").unwrap();
+        std::fs::write(root.join("src/notes.md"), "").unwrap();
+        std::fs::write(root.join("target/build.rs"), "").unwrap();
+
+        assert_eq!(
+            glob_files(root, &matcher("**/*.rs"), 10),
+            (
+                vec![
+                    "main.rs".to_string(),
+                    "src/lib.rs".to_string(),
+                    "src/nested/mod.rs".to_string(),
+                ],
+                false
+            )
+        );
+        assert_eq!(
+            glob_files(root, &matcher("*.rs"), 10),
+            (vec!["main.rs".to_string()], false)
+        );
+        assert_eq!(
+            glob_files(root, &matcher("**/*.rs"), 2),
+            (vec!["main.rs".to_string(), "src/lib.rs".to_string()], true)
+        );
+    }
+}
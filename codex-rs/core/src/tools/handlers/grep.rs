@@ -0,0 +1,444 @@
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_utils_string::take_bytes_at_char_boundary;
+use grep_regex::RegexMatcher;
+use grep_regex::RegexMatcherBuilder;
+use grep_searcher::BinaryDetection;
+use grep_searcher::Searcher;
+use grep_searcher::SearcherBuilder;
+use grep_searcher::Sink;
+use grep_searcher::SinkContext;
+use grep_searcher::SinkContextKind;
+use grep_searcher::SinkMatch;
+use ignore::WalkBuilder;
+use ignore::overrides::Override;
+use ignore::overrides::OverrideBuilder;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::ensure_path_readable;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct GrepHandler;
+
+const DEFAULT_LIMIT: usize = 100;
+const MAX_LIMIT: usize = 1000;
+const MAX_CONTEXT_LINES: usize = 10;
+const MAX_LINE_LENGTH: usize = 500;
+
+fn default_limit() -> usize {
+    DEFAULT_LIMIT
+}
+
+#[derive(Deserialize)]
+struct GrepArgs {
+    pattern: String,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    include: Option<String>,
+    #[serde(default)]
+    case_insensitive: bool,
+    #[serde(default)]
+    context_lines: usize,
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+#[async_trait]
+impl ToolHandler for GrepHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation { payload, turn, .. } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "grep handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: GrepArgs = parse_arguments(&arguments)?;
+
+        if args.pattern.is_empty() {
+            return Err(FunctionCallError::RespondToModel(
+                "pattern must not be empty".to_string(),
+            ));
+        }
+
+        if args.limit == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "limit must be greater than zero".to_string(),
+            ));
+        }
+
+        let search_path = match args.path {
+            Some(path) => {
+                let path = PathBuf::from(path);
+                if !path.is_absolute() {
+                    return Err(FunctionCallError::RespondToModel(
+                        "path must be an absolute path".to_string(),
+                    ));
+                }
+                path
+            }
+            None => turn.cwd.clone(),
+        };
+        ensure_path_readable(&turn, &search_path)?;
+        if !search_path.exists() {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "`{}` does not exist",
+                search_path.display()
+            )));
+        }
+
+        let matcher = RegexMatcherBuilder::new()
+            .case_insensitive(args.case_insensitive)
+            .build(&args.pattern)
+            .map_err(|err| FunctionCallError::RespondToModel(format!("invalid regex: {err}")))?;
+        let include = args
+            .include
+            .as_deref()
+            .map(str::trim)
+            .filter(|include| !include.is_empty())
+            .map(|include| {
+                OverrideBuilder::new(&search_path)
+                    .add(include)
+                    .and_then(|builder| builder.build())
+                    .map_err(|err| {
+                        FunctionCallError::RespondToModel(format!("invalid include glob: {err}"))
+                    })
+            })
+            .transpose()?;
+        let options = SearchOptions {
+            context_lines: args.context_lines.min(MAX_CONTEXT_LINES),
+            limit: args.limit.min(MAX_LIMIT),
+            multi_line: spans_lines(&args.pattern),
+        };
+
+        let root = search_path.clone();
+        let results =
+            tokio::task::spawn_blocking(move || search(&root, &matcher, include, &options))
+                .await
+                .map_err(|err| {
+                    FunctionCallError::RespondToModel(format!("search failed: {err}"))
+                })?;
+
+        if results.matches == 0 {
+            return Ok(ToolOutput::Function {
+                body: FunctionCallOutputBody::Text("No matches found.".to_string()),
+                success: Some(false),
+            });
+        }
+
+        let mut output = Vec::with_capacity(results.lines.len() + 2);
+        output.push(format!("Absolute path: {}", search_path.display()));
+        output.extend(results.lines);
+        if results.truncated {
+            output.push(format!(
+                "Showing the first {} matches; narrow the pattern or path if you need more.",
+                results.matches
+            ));
+        }
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(output.join("\n")),
+            success: Some(true),
+        })
+    }
+}
+
+struct SearchOptions {
+    context_lines: usize,
+    limit: usize,
+    /// Lets matches span lines; line-by-line search never matches a pattern containing `\n`.
+    multi_line: bool,
+}
+
+/// Whether `pattern` contains a newline, either literally or as the `\n` escape.
+fn spans_lines(pattern: &str) -> bool {
+    pattern.contains('\n') || pattern.contains(r"\n")
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct SearchResults {
+    /// Output lines in ripgrep's format: `file:line:text` for matches, `file-line-text` for
+    /// context, and `--` between non-adjacent groups.
+    lines: Vec<String>,
+    matches: usize,
+    truncated: bool,
+}
+
+/// Searches every file under `root` that is not excluded by ignore files (or `root` itself when
+/// it is a file), in path order. Unreadable and binary files are skipped.
+fn search(
+    root: &Path,
+    matcher: &RegexMatcher,
+    include: Option<Override>,
+    options: &SearchOptions,
+) -> SearchResults {
+    let mut searcher = SearcherBuilder::new()
+        .line_number(true)
+        .multi_line(options.multi_line)
+        .before_context(options.context_lines)
+        .after_context(options.context_lines)
+        .binary_detection(BinaryDetection::quit(b'\x00'))
+        .build();
+    let mut walker = WalkBuilder::new(root);
+    walker.require_git(false).sort_by_file_path(Path::cmp);
+    if let Some(include) = include {
+        walker.overrides(include);
+    }
+
+    let mut results = SearchResults::default();
+    for entry in walker.build().flatten() {
+        if !entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_file())
+        {
+            continue;
+        }
+        let display_path = match entry.path().strip_prefix(root) {
+            Ok(relative) if !relative.as_os_str().is_empty() => relative,
+            _ => entry.path(),
+        };
+        let mut sink = GrepSink {
+            path: display_path.to_string_lossy().replace('\\', "/"),
+            options,
+            results: &mut results,
+            first_line: true,
+        };
+        // Unreadable files are skipped rather than failing the whole search.
+        let _ = searcher.search_path(matcher, entry.path(), &mut sink);
+        if results.truncated {
+            break;
+        }
+    }
+    results
+}
+
+struct GrepSink<'a> {
+    path: String,
+    options: &'a SearchOptions,
+    results: &'a mut SearchResults,
+    first_line: bool,
+}
+
+impl GrepSink<'_> {
+    fn at_limit(&self) -> bool {
+        self.results.matches >= self.options.limit
+    }
+
+    fn push(&mut self, separator: char, line_number: Option<u64>, bytes: &[u8]) {
+        if self.first_line {
+            self.first_line = false;
+            if self.options.context_lines > 0 && !self.results.lines.is_empty() {
+                self.results.lines.push("--".to_string());
+            }
+        }
+        let text = String::from_utf8_lossy(bytes);
+        let text =
+            take_bytes_at_char_boundary(text.trim_end_matches(['\r', '\n']), MAX_LINE_LENGTH);
+        let line_number = line_number.unwrap_or_default();
+        self.results.lines.push(format!(
+            "{}{separator}{line_number}{separator}{text}",
+            self.path
+        ));
+    }
+}
+
+impl Sink for GrepSink<'_> {
+    type Error = io::Error;
+
+    // Searching continues past the limit only to find out whether another match follows.
+    fn matched(&mut self, _searcher: &Searcher, mat: &SinkMatch<'_>) -> Result<bool, io::Error> {
+        if self.at_limit() {
+            self.results.truncated = true;
+            return Ok(false);
+        }
+        // A multi-line match is shown one line at a time, like ripgrep does.
+        for (offset, line) in mat.bytes().split_inclusive(|&b| b == b'\n').enumerate() {
+            let line_number = mat.line_number().map(|number| number + offset as u64);
+            self.push(':', line_number, line);
+        }
+        self.results.matches += 1;
+        Ok(true)
+    }
+
+    fn context(
+        &mut self,
+        _searcher: &Searcher,
+        context: &SinkContext<'_>,
+    ) -> Result<bool, io::Error> {
+        // Context before a match past the limit is not shown, since that match is not.
+        if !(self.at_limit() && *context.kind() == SinkContextKind::Before) {
+            self.push('-', context.line_number(), context.bytes());
+        }
+        Ok(true)
+    }
+
+    fn context_break(&mut self, _searcher: &Searcher) -> Result<bool, io::Error> {
+        if !self.at_limit() {
+            self.results.lines.push("--".to_string());
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    fn run(
+        root: &Path,
+        pattern: &str,
+        include: Option<&str>,
+        context_lines: usize,
+    ) -> SearchResults {
+        let matcher = RegexMatcherBuilder::new().build(pattern).expect("regex");
+        let include = include.map(|include| {
+            OverrideBuilder::new(root)
+                .add(include)
+                .and_then(|builder| builder.build())
+                .expect("override")
+        });
+        search(
+            root,
+            &matcher,
+            include,
+            &SearchOptions {
+                context_lines,
+                limit: 3,
+                multi_line: spans_lines(pattern),
+            },
+        )
+    }
+
+    #[test]
+    fn search_reports_matches_with_context_and_skips_ignored_files() {
+        let temp = tempdir().expect("tempdir");
+        let root = temp.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        std::fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        std::fs::write(
+            root.join("src/lib.rs"),
+            "use std::fs;\n\nfn alpha() {}\nfn beta() {}\n\n\n\nfn alpha_two() {}\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("src/notes.md"), "alpha notes\n").unwrap();
+        std::fs::write(root.join("target/out.rs"), "fn alpha() {}\n").unwrap();
+        std::fs::write(root.join("src/blob.bin"), b"alpha\x00\x01").unwrap();
+
+        assert_eq!(
+            run(root, r"fn alpha", None, 1),
+            SearchResults {
+                lines: vec![
+                    "src/lib.rs-2-".to_string(),
+                    "src/lib.rs:3:fn alpha() {}".to_string(),
+                    "src/lib.rs-4-fn beta() {}".to_string(),
+                    "--".to_string(),
+                    "src/lib.rs-7-".to_string(),
+                    "src/lib.rs:8:fn alpha_two() {}".to_string(),
+                ],
+                matches: 2,
+                truncated: false,
+            }
+        );
+        assert_eq!(
+            run(root, "alpha", Some("*.md"), 0),
+            SearchResults {
+                lines: vec!["src/notes.md:1:alpha notes".to_string()],
+                matches: 1,
+                truncated: false,
+            }
+        );
+    }
+
+    #[test]
+    fn search_stops_at_limit() {
+        let temp = tempdir().expect("tempdir");
+        let root = temp.path();
+        std::fs::write(root.join("a.txt"), "x\nx\n").unwrap();
+        std::fs::write(root.join("b.txt"), "x\nx\n").unwrap();
+
+        assert_eq!(
+            run(root, "x", None, 0),
+            SearchResults {
+                lines: vec![
+                    "a.txt:1:x".to_string(),
+                    "a.txt:2:x".to_string(),
+                    "b.txt:1:x".to_string(),
+                ],
+                matches: 3,
+                truncated: true,
+            }
+        );
+    }
+
+    #[test]
+    fn search_with_exactly_limit_matches_is_not_truncated() {
+        let temp = tempdir().expect("tempdir");
+        let root = temp.path();
+        std::fs::write(
+            root.join("a.txt"),
+            "x
+x
+",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("b.txt"),
+            "x
+y
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            run(root, "x", None, 1),
+            SearchResults {
+                lines: vec![
+                    "a.txt:1:x".to_string(),
+                    "a.txt:2:x".to_string(),
+                    "--".to_string(),
+                    "b.txt:1:x".to_string(),
+                    "b.txt-2-y".to_string(),
+                ],
+                matches: 3,
+                truncated: false,
+            }
+        );
+    }
+
+    #[test]
+    fn patterns_containing_newlines_match_across_lines() {
+        let temp = tempdir().expect("tempdir");
+        let root = temp.path();
+        std::fs::write(root.join("a.txt"), "alpha\nbeta\ngamma\n").unwrap();
+        std::fs::write(root.join("b.txt"), "alpha\ngamma\nbeta\n").unwrap();
+
+        let expected = SearchResults {
+            lines: vec!["a.txt:1:alpha".to_string(), "a.txt:2:beta".to_string()],
+            matches: 1,
+            truncated: false,
+        };
+        assert_eq!(run(root, "alpha\nbeta", None, 0), expected);
+        assert_eq!(run(root, r"alpha\nbeta", None, 0), expected);
+    }
+}
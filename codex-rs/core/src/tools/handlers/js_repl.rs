@@ -52,6 +52,7 @@ fn build_js_repl_exec_output(
         aggregated_output: StreamOutput::new(aggregated_output),
         duration,
         timed_out: false,
+        resource_limit_exceeded: None,
     }
 }
 
@@ -367,8 +368,6 @@ mod tests {
         assert_eq!(event.cwd, turn.cwd);
         assert_eq!(event.source, ExecCommandSource::Agent);
         assert_eq!(event.interaction_input, None);
-        assert_eq!(event.stdout, "hello");
-        assert_eq!(event.stderr, "");
         assert!(event.aggregated_output.contains("hello"));
         assert_eq!(event.exit_code, 0);
         assert_eq!(event.duration, Duration::from_millis(12));
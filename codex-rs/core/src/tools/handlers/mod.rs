@@ -1,5 +1,9 @@
 pub mod apply_patch;
+mod background_process;
+mod delegate;
 mod dynamic;
+mod glob;
+mod grep;
 mod grep_files;
 mod js_repl;
 mod list_dir;
@@ -7,20 +11,34 @@ mod mcp;
 mod mcp_resource;
 pub(crate) mod multi_agents;
 mod plan;
+mod project_memory;
 mod read_file;
+mod read_file_range;
 mod request_user_input;
 mod search_tool_bm25;
 mod shell;
 mod test_sync;
 pub(crate) mod unified_exec;
 mod view_image;
+mod web;
+mod workspace_index;
+mod write_file;
 
 pub use plan::PLAN_TOOL;
 use serde::Deserialize;
+use std::path::Path;
+use std::path::PathBuf;
 
+use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
 pub use apply_patch::ApplyPatchHandler;
+pub use background_process::BackgroundProcessHandler;
+use codex_utils_absolute_path::AbsolutePathBuf;
+pub(crate) use delegate::DELEGATE_MAX_TOKENS;
+pub use delegate::DelegateHandler;
 pub use dynamic::DynamicToolHandler;
+pub use glob::GlobHandler;
+pub use grep::GrepHandler;
 pub use grep_files::GrepFilesHandler;
 pub use js_repl::JsReplHandler;
 pub use js_repl::JsReplResetHandler;
@@ -29,7 +47,9 @@ pub use mcp::McpHandler;
 pub use mcp_resource::McpResourceHandler;
 pub use multi_agents::MultiAgentHandler;
 pub use plan::PlanHandler;
+pub use project_memory::ProjectMemoryHandler;
 pub use read_file::ReadFileHandler;
+pub use read_file_range::ReadFileRangeHandler;
 pub use request_user_input::RequestUserInputHandler;
 pub(crate) use request_user_input::request_user_input_tool_description;
 pub(crate) use search_tool_bm25::DEFAULT_LIMIT as SEARCH_TOOL_BM25_DEFAULT_LIMIT;
@@ -40,6 +60,9 @@ pub use shell::ShellHandler;
 pub use test_sync::TestSyncHandler;
 pub use unified_exec::UnifiedExecHandler;
 pub use view_image::ViewImageHandler;
+pub use web::WebToolsHandler;
+pub use workspace_index::WorkspaceIndexHandler;
+pub use write_file::WriteFileHandler;
 
 fn parse_arguments<T>(arguments: &str) -> Result<T, FunctionCallError>
 where
@@ -49,3 +72,77 @@ where
         FunctionCallError::RespondToModel(format!("failed to parse function arguments: {err}"))
     })
 }
+
+/// Rejects paths that a sandboxed command in this turn would not be allowed to read.
+fn ensure_path_readable(turn: &TurnContext, path: &Path) -> Result<(), FunctionCallError> {
+    let policy = &turn.sandbox_policy;
+    if policy.has_full_disk_read_access() {
+        return Ok(());
+    }
+    let resolved = resolve_existing_prefix(path);
+    let readable = policy
+        .get_readable_roots_with_cwd(&turn.cwd)
+        .iter()
+        .any(|root| resolved.starts_with(resolve_existing_prefix(root.as_path())));
+    if readable {
+        Ok(())
+    } else {
+        Err(FunctionCallError::RespondToModel(format!(
+            "`{}` is outside the readable roots of the current sandbox policy",
+            path.display()
+        )))
+    }
+}
+
+/// Rejects paths that a sandboxed command in this turn would not be allowed to write.
+fn ensure_path_writable(turn: &TurnContext, path: &Path) -> Result<(), FunctionCallError> {
+    let policy = &turn.sandbox_policy;
+    if policy.has_full_disk_write_access() {
+        return Ok(());
+    }
+    let resolved = resolve_existing_prefix(path);
+    let writable = policy
+        .get_writable_roots_with_cwd(&turn.cwd)
+        .iter()
+        .any(|root| {
+            resolved.starts_with(resolve_existing_prefix(root.root.as_path()))
+                && !root
+                    .read_only_subpaths
+                    .iter()
+                    .any(|subpath| resolved.starts_with(resolve_existing_prefix(subpath.as_path())))
+        });
+    if writable {
+        Ok(())
+    } else {
+        Err(FunctionCallError::RespondToModel(format!(
+            "`{}` is not writable under the current sandbox policy; use apply_patch so the change can be approved",
+            path.display()
+        )))
+    }
+}
+
+/// Normalizes `path` and resolves symlinks in its longest existing prefix, so a link inside a
+/// sandbox root cannot be used to reach files outside it while paths that do not exist yet still
+/// resolve.
+fn resolve_existing_prefix(path: &Path) -> PathBuf {
+    let normalized = AbsolutePathBuf::from_absolute_path(path)
+        .map(AbsolutePathBuf::into_path_buf)
+        .unwrap_or_else(|_| path.to_path_buf());
+    let mut existing = normalized.as_path();
+    let mut missing = Vec::new();
+    loop {
+        if let Ok(canonical) = dunce::canonicalize(existing) {
+            return missing
+                .iter()
+                .rev()
+                .fold(canonical, |resolved, component| resolved.join(component));
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(component)) => {
+                missing.push(component);
+                existing = parent;
+            }
+            _ => return normalized,
+        }
+    }
+}
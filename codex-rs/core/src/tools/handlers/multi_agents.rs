@@ -811,7 +811,7 @@ fn build_agent_resume_config(
     Ok(config)
 }
 
-fn build_agent_shared_config(
+pub(crate) fn build_agent_shared_config(
     turn: &TurnContext,
     child_depth: i32,
 ) -> Result<Config, FunctionCallError> {
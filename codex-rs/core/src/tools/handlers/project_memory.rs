@@ -0,0 +1,116 @@
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+use crate::function_tool::FunctionCallError;
+use crate::memories::project::load_project_memories;
+use crate::memories::project::normalize_memory_entry;
+use crate::memories::project::project_memory_path;
+use crate::memories::project::update_project_memories;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct ProjectMemoryHandler;
+
+#[derive(Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum ProjectMemoryArgs {
+    Save { text: String },
+    List { query: Option<String> },
+    Delete { index: usize },
+}
+
+#[async_trait]
+impl ToolHandler for ProjectMemoryHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation { turn, payload, .. } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "project_memory handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+        let args: ProjectMemoryArgs = parse_arguments(&arguments)?;
+
+        let path = project_memory_path(&turn.config.codex_home, &turn.cwd);
+        let message = match args {
+            ProjectMemoryArgs::Save { text } => {
+                let entry = normalize_memory_entry(&text);
+                if entry.is_empty() {
+                    return Err(FunctionCallError::RespondToModel(
+                        "text must not be empty".to_string(),
+                    ));
+                }
+                let count = update(path, move |entries| {
+                    entries.push(entry);
+                    entries.len()
+                })
+                .await?;
+                format!("Saved project memory {count}.")
+            }
+            ProjectMemoryArgs::List { query } => {
+                let entries = load_project_memories(&path).await.map_err(|err| {
+                    FunctionCallError::RespondToModel(format!(
+                        "failed to read project memories: {err}"
+                    ))
+                })?;
+                let query = query.map(|query| query.to_lowercase());
+                let matching = entries
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, entry)| {
+                        query
+                            .as_deref()
+                            .is_none_or(|query| entry.to_lowercase().contains(query))
+                    })
+                    .map(|(index, entry)| format!("{}. {entry}", index + 1))
+                    .collect::<Vec<_>>();
+                if matching.is_empty() {
+                    "No matching project memories.".to_string()
+                } else {
+                    matching.join("\n")
+                }
+            }
+            ProjectMemoryArgs::Delete { index } => {
+                let removed = update(path, move |entries| {
+                    if index == 0 || index > entries.len() {
+                        return Err(FunctionCallError::RespondToModel(format!(
+                            "index must be between 1 and {}",
+                            entries.len()
+                        )));
+                    }
+                    Ok(entries.remove(index - 1))
+                })
+                .await??;
+                format!("Deleted project memory {index}: {removed}")
+            }
+        };
+
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(message),
+            success: Some(true),
+        })
+    }
+}
+
+async fn update<T, F>(path: PathBuf, f: F) -> Result<T, FunctionCallError>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut Vec<String>) -> T + Send + 'static,
+{
+    update_project_memories(path, f).await.map_err(|err| {
+        FunctionCallError::RespondToModel(format!("failed to save project memories: {err}"))
+    })
+}
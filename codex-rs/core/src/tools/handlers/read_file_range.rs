@@ -0,0 +1,125 @@
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use serde::Deserialize;
+
+use crate::file_excerpt::LineRange;
+use crate::file_excerpt::MAX_RANGE_LINES;
+use crate::file_excerpt::MAX_RANGES;
+use crate::file_excerpt::format_range;
+use crate::file_excerpt::merge_ranges;
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::ensure_path_readable;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+/// Reads further line ranges of a file that was attached as an excerpt.
+pub struct ReadFileRangeHandler;
+
+#[derive(Deserialize)]
+struct ReadFileRangeArgs {
+    /// Path of the file, absolute or relative to the working directory.
+    path: String,
+    /// Ranges such as `120-180` or `42`.
+    ranges: Vec<String>,
+}
+
+#[async_trait]
+impl ToolHandler for ReadFileRangeHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation { payload, turn, .. } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "read_file_range handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: ReadFileRangeArgs = parse_arguments(&arguments)?;
+        if args.ranges.is_empty() || args.ranges.len() > MAX_RANGES {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "ranges must list between 1 and {MAX_RANGES} line ranges"
+            )));
+        }
+        let ranges = args
+            .ranges
+            .iter()
+            .map(|range| range.parse::<LineRange>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(FunctionCallError::RespondToModel)?;
+
+        let path = turn.resolve_path(Some(args.path));
+        ensure_path_readable(&turn, &path)?;
+        let contents = tokio::fs::read_to_string(&path).await.map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to read `{}`: {err}", path.display()))
+        })?;
+
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(read_ranges(&contents, ranges)),
+            success: Some(true),
+        })
+    }
+}
+
+/// The requested ranges of `contents`, merged and numbered like the excerpt they extend.
+fn read_ranges(contents: &str, ranges: Vec<LineRange>) -> String {
+    let lines: Vec<&str> = contents.lines().collect();
+    let merged = merge_ranges(ranges, lines.len());
+    if merged.is_empty() {
+        return format!("The file has only {} lines.", lines.len());
+    }
+    merged
+        .into_iter()
+        .map(|range| {
+            let mut section = format!(
+                "Lines {}-{} of {}:\n{}",
+                range.start,
+                range.end,
+                lines.len(),
+                format_range(&lines, range)
+            );
+            if range.end - range.start >= MAX_RANGE_LINES {
+                section.push_str(&format!(
+                    "\n(Only the first {MAX_RANGE_LINES} lines of this range are shown.)"
+                ));
+            }
+            section
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn ranges_are_merged_clamped_and_numbered() {
+        let contents = "one\ntwo\nthree\nfour\nfive\n";
+        let ranges = vec![
+            LineRange { start: 4, end: 9 },
+            LineRange { start: 1, end: 1 },
+            LineRange { start: 2, end: 2 },
+        ];
+
+        assert_eq!(
+            read_ranges(contents, ranges),
+            "Lines 1-2 of 5:\nL1: one\nL2: two\n\nLines 4-5 of 5:\nL4: four\nL5: five"
+        );
+        assert_eq!(
+            read_ranges(contents, vec![LineRange { start: 8, end: 9 }]),
+            "The file has only 5 lines."
+        );
+    }
+}
@@ -20,12 +20,15 @@ use crate::tools::events::ToolEmitter;
 use crate::tools::events::ToolEventCtx;
 use crate::tools::handlers::apply_patch::intercept_apply_patch;
 use crate::tools::handlers::parse_arguments;
+use crate::tools::handlers::unified_exec;
+use crate::tools::handlers::unified_exec::ExecCommandArgs;
 use crate::tools::orchestrator::ToolOrchestrator;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
 use crate::tools::runtimes::shell::ShellRequest;
 use crate::tools::runtimes::shell::ShellRuntime;
 use crate::tools::sandboxing::ToolCtx;
+use crate::unified_exec::UnifiedExecContext;
 
 pub struct ShellHandler;
 
@@ -209,6 +212,16 @@ impl ToolHandler for ShellCommandHandler {
         };
 
         let params: ShellCommandToolCallParams = parse_arguments(&arguments)?;
+        if params.tty == Some(true) && turn.tools_config.shell_tty {
+            let context = UnifiedExecContext::new(session, turn, call_id);
+            return unified_exec::exec_command(
+                &context,
+                &tracker,
+                &tool_name,
+                ExecCommandArgs::for_tty_shell_command(params),
+            )
+            .await;
+        }
         let prefix_rule = params.prefix_rule.clone();
         let exec_params = Self::to_exec_params(
             &params,
@@ -432,6 +445,7 @@ mod tests {
             workdir,
             login,
             timeout_ms,
+            tty: None,
             sandbox_permissions: Some(sandbox_permissions),
             prefix_rule: None,
             justification: justification.clone(),
@@ -5,6 +5,7 @@ use crate::protocol::TerminalInteractionEvent;
 use crate::sandboxing::SandboxPermissions;
 use crate::shell::Shell;
 use crate::shell::get_shell_by_model_provided_path;
+use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
@@ -19,6 +20,7 @@ use crate::unified_exec::UnifiedExecResponse;
 use crate::unified_exec::WriteStdinRequest;
 use async_trait::async_trait;
 use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::ShellCommandToolCallParams;
 use serde::Deserialize;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -64,6 +66,10 @@ fn default_exec_yield_time_ms() -> u64 {
     10000
 }
 
+/// How long `start_background_process` waits before returning, long enough to catch commands
+/// that fail immediately.
+const BACKGROUND_START_YIELD_TIME_MS: u64 = 1000;
+
 fn default_write_stdin_yield_time_ms() -> u64 {
     250
 }
@@ -76,6 +82,36 @@ fn default_tty() -> bool {
     false
 }
 
+impl ExecCommandArgs {
+    /// Runs a `shell_command` call that asked for a terminal in a PTY, with the same shell and
+    /// approval parameters it would otherwise have used. Its `timeout_ms` becomes the time to
+    /// wait before handing back a session ID, since the process is kept rather than killed.
+    pub(crate) fn for_tty_shell_command(params: ShellCommandToolCallParams) -> Self {
+        Self {
+            cmd: params.command,
+            workdir: params.workdir,
+            shell: None,
+            login: params.login.unwrap_or_else(default_login),
+            tty: true,
+            yield_time_ms: params.timeout_ms.unwrap_or_else(default_exec_yield_time_ms),
+            max_output_tokens: None,
+            sandbox_permissions: params.sandbox_permissions.unwrap_or_default(),
+            justification: params.justification,
+            prefix_rule: params.prefix_rule,
+        }
+    }
+
+    /// Runs a `start_background_process` call: no terminal, and only a short wait before the
+    /// session ID is returned so the turn can continue while the process keeps running.
+    pub(crate) fn into_background(self) -> Self {
+        Self {
+            tty: false,
+            yield_time_ms: BACKGROUND_START_YIELD_TIME_MS,
+            ..self
+        }
+    }
+}
+
 #[async_trait]
 impl ToolHandler for UnifiedExecHandler {
     fn kind(&self) -> ToolKind {
@@ -128,74 +164,7 @@ impl ToolHandler for UnifiedExecHandler {
         let response = match tool_name.as_str() {
             "exec_command" => {
                 let args: ExecCommandArgs = parse_arguments(&arguments)?;
-                let process_id = manager.allocate_process_id().await;
-                let command = get_command(&args, session.user_shell());
-
-                let ExecCommandArgs {
-                    workdir,
-                    tty,
-                    yield_time_ms,
-                    max_output_tokens,
-                    sandbox_permissions,
-                    justification,
-                    prefix_rule,
-                    ..
-                } = args;
-
-                if sandbox_permissions.requires_escalated_permissions()
-                    && !matches!(
-                        context.turn.approval_policy,
-                        codex_protocol::protocol::AskForApproval::OnRequest
-                    )
-                {
-                    let approval_policy = context.turn.approval_policy;
-                    manager.release_process_id(&process_id).await;
-                    return Err(FunctionCallError::RespondToModel(format!(
-                        "approval policy is {approval_policy:?}; reject command — you cannot ask for escalated permissions if the approval policy is {approval_policy:?}"
-                    )));
-                }
-
-                let workdir = workdir.filter(|value| !value.is_empty());
-
-                let workdir = workdir.map(|dir| context.turn.resolve_path(Some(dir)));
-                let cwd = workdir.clone().unwrap_or_else(|| context.turn.cwd.clone());
-
-                if let Some(output) = intercept_apply_patch(
-                    &command,
-                    &cwd,
-                    Some(yield_time_ms),
-                    context.session.as_ref(),
-                    context.turn.as_ref(),
-                    Some(&tracker),
-                    &context.call_id,
-                    tool_name.as_str(),
-                )
-                .await?
-                {
-                    manager.release_process_id(&process_id).await;
-                    return Ok(output);
-                }
-
-                manager
-                    .exec_command(
-                        ExecCommandRequest {
-                            command,
-                            process_id,
-                            yield_time_ms,
-                            max_output_tokens,
-                            workdir,
-                            network: context.turn.network.clone(),
-                            tty,
-                            sandbox_permissions,
-                            justification,
-                            prefix_rule,
-                        },
-                        &context,
-                    )
-                    .await
-                    .map_err(|err| {
-                        FunctionCallError::RespondToModel(format!("exec_command failed: {err:?}"))
-                    })?
+                return exec_command(&context, &tracker, &tool_name, args).await;
             }
             "write_stdin" => {
                 let args: WriteStdinArgs = parse_arguments(&arguments)?;
@@ -238,6 +207,90 @@ impl ToolHandler for UnifiedExecHandler {
     }
 }
 
+/// Starts a command through the unified exec process manager. Commands still running after
+/// `yield_time_ms` keep their process and can be continued with `write_stdin`.
+pub(crate) async fn exec_command(
+    context: &UnifiedExecContext,
+    tracker: &SharedTurnDiffTracker,
+    tool_name: &str,
+    args: ExecCommandArgs,
+) -> Result<ToolOutput, FunctionCallError> {
+    let manager: &UnifiedExecProcessManager = &context.session.services.unified_exec_manager;
+    let process_id = manager.allocate_process_id().await;
+    let command = get_command(&args, context.session.user_shell());
+
+    let ExecCommandArgs {
+        workdir,
+        tty,
+        yield_time_ms,
+        max_output_tokens,
+        sandbox_permissions,
+        justification,
+        prefix_rule,
+        ..
+    } = args;
+
+    if sandbox_permissions.requires_escalated_permissions()
+        && !matches!(
+            context.turn.approval_policy,
+            codex_protocol::protocol::AskForApproval::OnRequest
+        )
+    {
+        let approval_policy = context.turn.approval_policy;
+        manager.release_process_id(&process_id).await;
+        return Err(FunctionCallError::RespondToModel(format!(
+            "approval policy is {approval_policy:?}; reject command — you cannot ask for escalated permissions if the approval policy is {approval_policy:?}"
+        )));
+    }
+
+    let workdir = workdir.filter(|value| !value.is_empty());
+
+    let workdir = workdir.map(|dir| context.turn.resolve_path(Some(dir)));
+    let cwd = workdir.clone().unwrap_or_else(|| context.turn.cwd.clone());
+
+    if let Some(output) = intercept_apply_patch(
+        &command,
+        &cwd,
+        Some(yield_time_ms),
+        context.session.as_ref(),
+        context.turn.as_ref(),
+        Some(tracker),
+        &context.call_id,
+        tool_name,
+    )
+    .await?
+    {
+        manager.release_process_id(&process_id).await;
+        return Ok(output);
+    }
+
+    let response = manager
+        .exec_command(
+            ExecCommandRequest {
+                command,
+                process_id,
+                yield_time_ms,
+                max_output_tokens,
+                workdir,
+                network: context.turn.network.clone(),
+                tty,
+                sandbox_permissions,
+                justification,
+                prefix_rule,
+            },
+            context,
+        )
+        .await
+        .map_err(|err| {
+            FunctionCallError::RespondToModel(format!("exec_command failed: {err:?}"))
+        })?;
+
+    Ok(ToolOutput::Function {
+        body: FunctionCallOutputBody::Text(format_response(&response)),
+        success: Some(true),
+    })
+}
+
 pub(crate) fn get_command(args: &ExecCommandArgs, session_shell: Arc<Shell>) -> Vec<String> {
     let model_shell = args.shell.as_ref().map(|shell_str| {
         let mut shell = get_shell_by_model_provided_path(&PathBuf::from(shell_str));
@@ -250,7 +303,7 @@ pub(crate) fn get_command(args: &ExecCommandArgs, session_shell: Arc<Shell>) ->
     shell.derive_exec_args(&args.cmd, args.login)
 }
 
-fn format_response(response: &UnifiedExecResponse) -> String {
+pub(crate) fn format_response(response: &UnifiedExecResponse) -> String {
     let mut sections = Vec::new();
 
     if !response.chunk_id.is_empty() {
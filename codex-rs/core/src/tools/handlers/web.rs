@@ -0,0 +1,443 @@
+use std::collections::HashSet;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use regex_lite::Regex;
+use serde::Deserialize;
+use url::Url;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::types::WebTools;
+use crate::default_client::build_reqwest_client_builder;
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::formatted_truncate_text;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_REDIRECTS: usize = 5;
+/// Hard cap on bytes read from a response, before conversion to text and the per-call cap.
+const MAX_DOWNLOAD_BYTES: usize = 4 * 1024 * 1024;
+const MAX_SEARCH_RESULTS: usize = 10;
+
+static HIDDEN_ELEMENTS: LazyLock<Regex> = LazyLock::new(|| {
+    #[expect(clippy::expect_used)]
+    Regex::new(
+        r"(?is)<!--.*?-->|<script\b.*?</script>|<style\b.*?</style>|<noscript\b.*?</noscript>|<head\b.*?</head>",
+    )
+    .expect("valid regex")
+});
+static BLOCK_TAGS: LazyLock<Regex> = LazyLock::new(|| {
+    #[expect(clippy::expect_used)]
+    Regex::new(
+        r"(?i)</?(p|div|br|li|ul|ol|h[1-6]|tr|table|pre|section|article|header|footer|blockquote)\b[^>]*>",
+    )
+    .expect("valid regex")
+});
+static TAGS: LazyLock<Regex> = LazyLock::new(|| {
+    #[expect(clippy::expect_used)]
+    Regex::new(r"(?s)<[^>]*>").expect("valid regex")
+});
+static ANCHORS: LazyLock<Regex> = LazyLock::new(|| {
+    #[expect(clippy::expect_used)]
+    Regex::new(r#"(?is)<a\b[^>]*?href\s*=\s*["']([^"']+)["'][^>]*>(.*?)</a>"#).expect("valid regex")
+});
+
+pub struct WebToolsHandler;
+
+#[derive(Deserialize)]
+struct WebSearchArgs {
+    query: String,
+}
+
+#[derive(Deserialize)]
+struct FetchUrlArgs {
+    url: String,
+}
+
+#[async_trait]
+impl ToolHandler for WebToolsHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            tool_name,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "{tool_name} handler received unsupported payload"
+                )));
+            }
+        };
+        let settings = &turn.config.web_tools;
+        let client = web_client(&session, &turn)?;
+        let text = match tool_name.as_str() {
+            "web_search" => {
+                let args: WebSearchArgs = parse_arguments(&arguments)?;
+                web_search(&client, settings, args.query.trim()).await?
+            }
+            "fetch_url" => {
+                let args: FetchUrlArgs = parse_arguments(&arguments)?;
+                let url = parse_allowed_url(settings, args.url.trim())?;
+                fetch_text(&client, url).await?
+            }
+            other => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "unsupported web tool {other}"
+                )));
+            }
+        };
+
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(formatted_truncate_text(
+                &text,
+                TruncationPolicy::Bytes(settings.max_result_bytes),
+            )),
+            success: Some(true),
+        })
+    }
+}
+
+/// Requests go through the session's network proxy when one is running, so its policy applies.
+/// Without a proxy they are only made when the turn's sandbox allows network access.
+fn web_client(session: &Session, turn: &TurnContext) -> Result<reqwest::Client, FunctionCallError> {
+    let allowed_domains = turn.config.web_tools.allowed_domains.clone();
+    let mut builder = build_reqwest_client_builder()
+        .timeout(REQUEST_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::custom(move |attempt| {
+            if attempt.previous().len() >= MAX_REDIRECTS {
+                attempt.error("too many redirects")
+            } else if is_allowed_url(&allowed_domains, attempt.url()) {
+                attempt.follow()
+            } else {
+                let message = format!("redirect to {} is not allowed", attempt.url());
+                attempt.error(message)
+            }
+        }));
+    if let Some(network_proxy) = &session.services.network_proxy {
+        let proxy_url = format!("http://{}", network_proxy.proxy().http_addr());
+        let proxy = reqwest::Proxy::all(proxy_url).map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to configure network proxy: {err}"))
+        })?;
+        // Replaces the configured proxy, which would otherwise be tried first.
+        builder = builder.no_proxy().proxy(proxy);
+    } else if !turn.sandbox_policy.has_full_network_access() {
+        return Err(FunctionCallError::RespondToModel(
+            "network access is disabled by the current sandbox policy".to_string(),
+        ));
+    }
+    builder.build().map_err(|err| {
+        FunctionCallError::RespondToModel(format!("failed to build HTTP client: {err}"))
+    })
+}
+
+async fn web_search(
+    client: &reqwest::Client,
+    settings: &WebTools,
+    query: &str,
+) -> Result<String, FunctionCallError> {
+    if query.is_empty() {
+        return Err(FunctionCallError::RespondToModel(
+            "query must not be empty".to_string(),
+        ));
+    }
+    let encoded: String = url::form_urlencoded::byte_serialize(query.as_bytes()).collect();
+    let search_url = Url::parse(&settings.search_url.replace("{query}", &encoded))
+        .map_err(|err| FunctionCallError::RespondToModel(format!("invalid search_url: {err}")))?;
+    let (html, _) = fetch_body(client, search_url.clone()).await?;
+
+    let results = search_results(&html, &search_url, &settings.allowed_domains);
+    if results.is_empty() {
+        return Ok(format!("No results for \"{query}\"."));
+    }
+    Ok(results
+        .iter()
+        .enumerate()
+        .map(|(index, (title, url))| format!("{}. {title}\n   {url}", index + 1))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+async fn fetch_text(client: &reqwest::Client, url: Url) -> Result<String, FunctionCallError> {
+    let (body, is_html) = fetch_body(client, url).await?;
+    Ok(if is_html { html_to_text(&body) } else { body })
+}
+
+/// Returns the response body, capped at `MAX_DOWNLOAD_BYTES`, and whether it is HTML.
+async fn fetch_body(
+    client: &reqwest::Client,
+    url: Url,
+) -> Result<(String, bool), FunctionCallError> {
+    let mut response = client.get(url.clone()).send().await.map_err(|err| {
+        FunctionCallError::RespondToModel(format!("failed to fetch {url}: {err}"))
+    })?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "failed to fetch {url}: HTTP {status}"
+        )));
+    }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_ascii_lowercase);
+    if let Some(content_type) = &content_type
+        && !is_text_content_type(content_type)
+    {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "{url} returned unsupported content type {content_type}"
+        )));
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|err| FunctionCallError::RespondToModel(format!("failed to read {url}: {err}")))?
+    {
+        let remaining = MAX_DOWNLOAD_BYTES - body.len();
+        body.extend_from_slice(&chunk[..chunk.len().min(remaining)]);
+        if body.len() >= MAX_DOWNLOAD_BYTES {
+            break;
+        }
+    }
+    let body = String::from_utf8_lossy(&body).into_owned();
+    let is_html = match &content_type {
+        Some(content_type) => content_type.contains("html"),
+        None => {
+            let start = body
+                .trim_start()
+                .chars()
+                .take(14)
+                .collect::<String>()
+                .to_ascii_lowercase();
+            start.starts_with("<!doctype html") || start.starts_with("<html")
+        }
+    };
+    Ok((body, is_html))
+}
+
+fn parse_allowed_url(settings: &WebTools, raw: &str) -> Result<Url, FunctionCallError> {
+    let url = Url::parse(raw)
+        .map_err(|err| FunctionCallError::RespondToModel(format!("invalid url {raw}: {err}")))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "only http and https urls can be fetched, got {raw}"
+        )));
+    }
+    if !is_allowed_url(&settings.allowed_domains, &url) {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "{} is not in web_tools.allowed_domains",
+            url.host_str().unwrap_or_default()
+        )));
+    }
+    Ok(url)
+}
+
+fn is_allowed_url(allowed_domains: &[String], url: &Url) -> bool {
+    if allowed_domains.is_empty() {
+        return true;
+    }
+    let Some(host) = url.host_str() else {
+        return false;
+    };
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    allowed_domains.iter().any(|domain| {
+        let domain = domain.trim_start_matches("*.").to_ascii_lowercase();
+        host == domain || host.ends_with(&format!(".{domain}"))
+    })
+}
+
+fn is_text_content_type(content_type: &str) -> bool {
+    let mime = content_type.split(';').next().unwrap_or_default().trim();
+    mime.starts_with("text/")
+        || matches!(
+            mime,
+            "application/json" | "application/xml" | "application/xhtml+xml"
+        )
+        || mime.ends_with("+json")
+        || mime.ends_with("+xml")
+}
+
+/// Extracts result links from an HTML search page, skipping links back to the search engine and
+/// links outside the allowlist. DuckDuckGo-style redirect links that carry the target in a `uddg`
+/// parameter are unwrapped.
+fn search_results(
+    html: &str,
+    search_url: &Url,
+    allowed_domains: &[String],
+) -> Vec<(String, String)> {
+    let mut seen = HashSet::new();
+    let mut results = Vec::new();
+    for captures in ANCHORS.captures_iter(html) {
+        let Some(mut url) = search_url.join(&decode_entities(&captures[1])).ok() else {
+            continue;
+        };
+        if let Some(target) = url
+            .query_pairs()
+            .find(|(key, _)| key == "uddg")
+            .and_then(|(_, target)| Url::parse(&target).ok())
+        {
+            url = target;
+        }
+        let title = collapse_whitespace(&decode_entities(&TAGS.replace_all(&captures[2], "")));
+        if title.is_empty()
+            || !matches!(url.scheme(), "http" | "https")
+            || url.host_str() == search_url.host_str()
+            || !is_allowed_url(allowed_domains, &url)
+            || !seen.insert(url.to_string())
+        {
+            continue;
+        }
+        results.push((title, url.to_string()));
+        if results.len() == MAX_SEARCH_RESULTS {
+            break;
+        }
+    }
+    results
+}
+
+fn html_to_text(html: &str) -> String {
+    let visible = HIDDEN_ELEMENTS.replace_all(html, "");
+    let blocks = BLOCK_TAGS.replace_all(&visible, "\n");
+    let text = decode_entities(&TAGS.replace_all(&blocks, ""));
+    let mut lines = Vec::new();
+    for line in text.lines().map(collapse_whitespace) {
+        if !line.is_empty() || lines.last().is_some_and(|last: &String| !last.is_empty()) {
+            lines.push(line);
+        }
+    }
+    lines.join("\n").trim().to_string()
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| decode_entity(&rest[1..end]).map(|ch| (ch, end)));
+        match entity {
+            Some((ch, end)) => {
+                decoded.push(ch);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => {
+            let code = name.strip_prefix('#')?;
+            let value = match code.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => code.parse().ok()?,
+            };
+            char::from_u32(value)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn html_to_text_keeps_visible_text_and_block_breaks() {
+        let html = r#"<html><head><title>Docs</title><style>p { color: red; }</style></head>
+<body><h1>Install</h1><script>track();</script><p>Run <code>cargo&nbsp;add</code> &amp; build.</p>
+<ul><li>one</li><li>two &#x2713;</li></ul></body></html>"#;
+
+        assert_eq!(
+            html_to_text(html),
+            "Install\n\nRun cargo add & build.\n\none\n\ntwo \u{2713}"
+        );
+    }
+
+    #[test]
+    fn allowed_domains_match_subdomains_only() {
+        let allowed = vec!["docs.rs".to_string(), "*.python.org".to_string()];
+        let allowed_url = |raw: &str| is_allowed_url(&allowed, &Url::parse(raw).unwrap());
+
+        assert!(allowed_url("https://docs.rs/serde"));
+        assert!(allowed_url("https://static.docs.rs/x.css"));
+        assert!(allowed_url("https://docs.python.org/3/"));
+        assert!(!allowed_url("https://notdocs.rs/"));
+        assert!(!allowed_url("https://example.com/?docs.rs"));
+        assert!(is_allowed_url(
+            &[],
+            &Url::parse("https://example.com").unwrap()
+        ));
+    }
+
+    #[test]
+    fn search_results_unwrap_redirects_and_skip_engine_links() {
+        let search_url = Url::parse("https://html.duckduckgo.com/html/?q=serde").unwrap();
+        let html = r#"
+<a class="result__a" href="//duckduckgo.com/l/?uddg=https%3A%2F%2Fdocs.rs%2Fserde&amp;rut=1">serde - <b>Rust</b></a>
+<a href="/html/?q=serde&amp;s=30">Next page</a>
+<a class="result__a" href="https://serde.rs/">Serde &amp; friends</a>
+<a class="result__a" href="https://serde.rs/">Serde duplicate</a>
+"#;
+
+        assert_eq!(
+            search_results(html, &search_url, &[]),
+            vec![
+                (
+                    "serde - Rust".to_string(),
+                    "https://docs.rs/serde".to_string()
+                ),
+                (
+                    "Serde & friends".to_string(),
+                    "https://serde.rs/".to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            search_results(html, &search_url, &["serde.rs".to_string()]),
+            vec![(
+                "Serde & friends".to_string(),
+                "https://serde.rs/".to_string()
+            )]
+        );
+    }
+}
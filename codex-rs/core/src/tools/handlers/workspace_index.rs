@@ -0,0 +1,274 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use codex_file_search::FileSearchResults;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use serde::Deserialize;
+
+use crate::codex::TurnContext;
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::ensure_path_readable;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::workspace_index::LIST_SYMBOLS_TOOL_NAME;
+use crate::workspace_index::SEARCH_FILES_TOOL_NAME;
+use crate::workspace_index::Symbol;
+use crate::workspace_index::WorkspaceIndex;
+use crate::workspace_index::find_symbols;
+use crate::workspace_index::symbols_in;
+
+/// Answers `search_files` and `list_symbols` from the workspace index of the working directory.
+pub struct WorkspaceIndexHandler;
+
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 500;
+
+fn default_limit() -> usize {
+    DEFAULT_LIMIT
+}
+
+#[derive(Deserialize)]
+struct SearchFilesArgs {
+    query: String,
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+#[derive(Deserialize)]
+struct ListSymbolsArgs {
+    #[serde(default)]
+    query: Option<String>,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+#[async_trait]
+impl ToolHandler for WorkspaceIndexHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            tool_name,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "{tool_name} handler received unsupported payload"
+                )));
+            }
+        };
+
+        let body = match tool_name.as_str() {
+            SEARCH_FILES_TOOL_NAME => {
+                let args: SearchFilesArgs = parse_arguments(&arguments)?;
+                let query = args.query.trim().to_string();
+                if query.is_empty() {
+                    return Err(FunctionCallError::RespondToModel(
+                        "query must not be empty".to_string(),
+                    ));
+                }
+                let limit = checked_limit(args.limit)?;
+                let root = turn.cwd.clone();
+                ensure_path_readable(&turn, &root)?;
+                let index = session.services.workspace_index.for_root(&root);
+                blocking(move || {
+                    let files = index.files()?;
+                    Some(format_file_matches(&files.search(&query, limit), &root))
+                })
+                .await?
+            }
+            LIST_SYMBOLS_TOOL_NAME => {
+                let args: ListSymbolsArgs = parse_arguments(&arguments)?;
+                let query = args
+                    .query
+                    .map(|query| query.trim().to_string())
+                    .filter(|query| !query.is_empty());
+                if query.is_none() && args.path.is_none() {
+                    return Err(FunctionCallError::RespondToModel(
+                        "provide a query, a path, or both".to_string(),
+                    ));
+                }
+                let limit = checked_limit(args.limit)?;
+                let path = turn.resolve_path(args.path);
+                ensure_path_readable(&turn, &path)?;
+                if path.is_file() {
+                    let contents = tokio::fs::read_to_string(&path).await.map_err(|err| {
+                        FunctionCallError::RespondToModel(format!(
+                            "failed to read `{}`: {err}",
+                            path.display()
+                        ))
+                    })?;
+                    let display_path = path.strip_prefix(&turn.cwd).unwrap_or(&path);
+                    let symbols = symbols_in(
+                        &Arc::from(display_path.to_string_lossy().as_ref()),
+                        &contents,
+                    );
+                    format_symbols(&symbols, query.as_deref(), limit)
+                } else {
+                    // Directories under the working directory share the session's index. Others
+                    // must sit in a workspace root, whose index lasts for this lookup unless
+                    // another session holds it, so a lookup never walks an unbounded tree.
+                    let (root, index) = if path.starts_with(&turn.cwd) {
+                        let index = session.services.workspace_index.for_root(&turn.cwd);
+                        (turn.cwd.clone(), index)
+                    } else {
+                        let root = workspace_root_containing(&turn, &path).ok_or_else(|| {
+                            FunctionCallError::RespondToModel(format!(
+                                "`{}` is outside the workspace; pass a directory inside the working directory or a writable root, or a single file",
+                                path.display()
+                            ))
+                        })?;
+                        let index = WorkspaceIndex::for_root(&root);
+                        (root, index)
+                    };
+                    blocking(move || {
+                        let symbols = index.symbols()?;
+                        let prefix = path.strip_prefix(&root).unwrap_or(Path::new(""));
+                        let in_path: Vec<Symbol> = symbols
+                            .iter()
+                            .filter(|symbol| Path::new(symbol.path.as_ref()).starts_with(prefix))
+                            .cloned()
+                            .collect();
+                        Some(format_symbols(&in_path, query.as_deref(), limit))
+                    })
+                    .await?
+                }
+            }
+            _ => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "unsupported workspace index tool `{tool_name}`"
+                )));
+            }
+        };
+
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(body),
+            success: Some(true),
+        })
+    }
+}
+
+/// The configured writable root that contains `path`, if any.
+fn workspace_root_containing(turn: &TurnContext, path: &Path) -> Option<PathBuf> {
+    let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = &turn.sandbox_policy else {
+        return None;
+    };
+    writable_roots
+        .iter()
+        .map(AbsolutePathBuf::as_path)
+        .find(|root| path.starts_with(root))
+        .map(Path::to_path_buf)
+}
+
+fn checked_limit(limit: usize) -> Result<usize, FunctionCallError> {
+    if limit == 0 {
+        return Err(FunctionCallError::RespondToModel(
+            "limit must be greater than zero".to_string(),
+        ));
+    }
+    Ok(limit.min(MAX_LIMIT))
+}
+
+/// Runs an index lookup, which may wait for the first walk of the workspace, off the runtime.
+async fn blocking(
+    lookup: impl FnOnce() -> Option<String> + Send + 'static,
+) -> Result<String, FunctionCallError> {
+    tokio::task::spawn_blocking(lookup)
+        .await
+        .map_err(|err| FunctionCallError::RespondToModel(format!("index lookup failed: {err}")))?
+        .ok_or_else(|| {
+            FunctionCallError::RespondToModel(
+                "the workspace is still being indexed; try again shortly".to_string(),
+            )
+        })
+}
+
+fn format_file_matches(results: &FileSearchResults, root: &Path) -> String {
+    let mut output = Vec::with_capacity(results.matches.len() + 2);
+    output.push(format!("Absolute path: {}", root.display()));
+    if results.matches.is_empty() {
+        output.push("No files matched.".to_string());
+    }
+    output.extend(
+        results
+            .matches
+            .iter()
+            .map(|file_match| file_match.path.to_string_lossy().replace('\\', "/")),
+    );
+    let omitted = results.total_match_count - results.matches.len();
+    if omitted > 0 {
+        output.push(format!(
+            "{omitted} more files matched; refine the query to see them."
+        ));
+    }
+    output.join("\n")
+}
+
+/// One `path:line: text` entry per symbol, filtered by `query` when there is one.
+fn format_symbols(symbols: &[Symbol], query: Option<&str>, limit: usize) -> String {
+    let matches: Vec<&Symbol> = match query {
+        Some(query) => find_symbols(symbols, query),
+        None => symbols.iter().collect(),
+    };
+    if matches.is_empty() {
+        return "No symbols found.".to_string();
+    }
+    let mut output: Vec<String> = matches
+        .iter()
+        .take(limit)
+        .map(|symbol| format!("{}:{}: {}", symbol.path, symbol.line, symbol.text))
+        .collect();
+    if matches.len() > limit {
+        output.push(format!(
+            "{} more symbols found; narrow the query or path to see them.",
+            matches.len() - limit
+        ));
+    }
+    output.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn symbols_are_listed_with_their_location() {
+        let path: Arc<str> = Arc::from("src/parser.rs");
+        let symbols = symbols_in(
+            &path,
+            "pub struct Parser;\nimpl Parser {\n    pub fn parse(&self) {}\n}\nfn helper() {}\n",
+        );
+
+        assert_eq!(
+            format_symbols(&symbols, Some("parse"), 2),
+            "src/parser.rs:3: pub fn parse(&self) {}\nsrc/parser.rs:1: pub struct Parser;\n1 more symbols found; narrow the query or path to see them."
+        );
+        assert_eq!(
+            format_symbols(&symbols, None, 10),
+            "src/parser.rs:1: pub struct Parser;\nsrc/parser.rs:2: impl Parser {\nsrc/parser.rs:3: pub fn parse(&self) {}\nsrc/parser.rs:5: fn helper() {}"
+        );
+        assert_eq!(
+            format_symbols(&symbols, Some("lexer"), 10),
+            "No symbols found."
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::FileChange;
+use serde::Deserialize;
+use similar::TextDiff;
+
+use crate::error::CodexErr;
+use crate::exec::ExecToolCallOutput;
+use crate::exec::StreamOutput;
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::events::ToolEmitter;
+use crate::tools::events::ToolEventCtx;
+use crate::tools::handlers::ensure_path_writable;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::tools::sandboxing::ToolError;
+
+pub struct WriteFileHandler;
+
+#[derive(Deserialize)]
+struct WriteFileArgs {
+    file_path: String,
+    content: String,
+}
+
+#[async_trait]
+impl ToolHandler for WriteFileHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn is_mutating(&self, _invocation: &ToolInvocation) -> bool {
+        true
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            tracker,
+            call_id,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "write_file handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let WriteFileArgs { file_path, content } = parse_arguments(&arguments)?;
+        let path = PathBuf::from(&file_path);
+        if !path.is_absolute() {
+            return Err(FunctionCallError::RespondToModel(
+                "file_path must be an absolute path".to_string(),
+            ));
+        }
+        // Writes are never prompted for, so policies that want to review every edit must go
+        // through apply_patch instead.
+        if turn.approval_policy == AskForApproval::UnlessTrusted {
+            return Err(FunctionCallError::RespondToModel(
+                "write_file is unavailable under the current approval policy; use apply_patch so the change can be approved".to_string(),
+            ));
+        }
+        ensure_path_writable(&turn, &path)?;
+        if path.is_dir() {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "`{file_path}` is a directory"
+            )));
+        }
+
+        let change = match tokio::fs::read(&path).await {
+            Ok(existing) => {
+                let existing = String::from_utf8_lossy(&existing);
+                FileChange::Update {
+                    unified_diff: TextDiff::from_lines(existing.as_ref(), content.as_str())
+                        .unified_diff()
+                        .context_radius(1)
+                        .to_string(),
+                    move_path: None,
+                }
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => FileChange::Add {
+                content: content.clone(),
+            },
+            Err(err) => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "failed to read `{file_path}`: {err}"
+                )));
+            }
+        };
+
+        let emitter = ToolEmitter::apply_patch(HashMap::from([(path.clone(), change)]), true);
+        let event_ctx =
+            ToolEventCtx::new(session.as_ref(), turn.as_ref(), &call_id, Some(&tracker));
+        emitter.begin(event_ctx).await;
+
+        let started = Instant::now();
+        let result = write_contents(&path, &content).await;
+        let summary = format!(
+            "Wrote {} bytes ({} lines) to {file_path}",
+            content.len(),
+            content.lines().count()
+        );
+        let out = result
+            .map(|()| ExecToolCallOutput {
+                stdout: StreamOutput::new(summary.clone()),
+                aggregated_output: StreamOutput::new(summary.clone()),
+                duration: started.elapsed(),
+                ..Default::default()
+            })
+            .map_err(|err| ToolError::Codex(CodexErr::Io(err)));
+        let event_ctx =
+            ToolEventCtx::new(session.as_ref(), turn.as_ref(), &call_id, Some(&tracker));
+        emitter.finish(event_ctx, out).await?;
+        session
+            .services
+            .file_snapshots
+            .lock()
+            .await
+            .record(path, content);
+
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(summary),
+            success: Some(true),
+        })
+    }
+}
+
+async fn write_contents(path: &Path, content: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(path, content).await
+}
@@ -592,24 +592,20 @@ impl JsReplManager {
         };
 
         let sandbox = SandboxManager::new();
-        let has_managed_network_requirements = turn
-            .config
-            .config_layer_stack
-            .requirements_toml()
-            .network
-            .is_some();
+        let has_managed_network = turn.config.managed_network_enabled();
         let sandbox_type = sandbox.select_initial(
             &turn.sandbox_policy,
             SandboxablePreference::Auto,
             turn.windows_sandbox_level,
-            has_managed_network_requirements,
+            has_managed_network,
+            None,
         );
         let exec_env = sandbox
             .transform(crate::sandboxing::SandboxTransformRequest {
                 spec,
                 policy: &turn.sandbox_policy,
                 sandbox: sandbox_type,
-                enforce_managed_network: has_managed_network_requirements,
+                enforce_managed_network: has_managed_network,
                 network: None,
                 sandbox_policy_cwd: &turn.cwd,
                 codex_linux_sandbox_exe: turn.codex_linux_sandbox_exe.as_ref(),
@@ -617,6 +613,9 @@ impl JsReplManager {
                     .features
                     .enabled(crate::features::Feature::UseLinuxSandboxBwrap),
                 windows_sandbox_level: turn.windows_sandbox_level,
+                container: None,
+                extensions: turn.config.sandbox_extensions.as_ref(),
+                resource_limits: None,
             })
             .map_err(|err| format!("failed to configure sandbox for js_repl: {err}"))?;
 
@@ -11,7 +11,11 @@ pub mod runtimes;
 pub mod sandboxing;
 pub mod spec;
 
+use std::path::Path;
+use std::path::PathBuf;
+
 use crate::exec::ExecToolCallOutput;
+use crate::redaction::SecretRedactor;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::formatted_truncate_text;
 use crate::truncate::truncate_text;
@@ -26,9 +30,11 @@ pub(crate) const TELEMETRY_PREVIEW_TRUNCATION_NOTICE: &str =
 
 /// Format the combined exec output for sending back to the model.
 /// Includes exit code and duration metadata; truncates large bodies safely.
+/// `output_artifact` points the model at the untruncated output when it was saved.
 pub fn format_exec_output_for_model_structured(
     exec_output: &ExecToolCallOutput,
     truncation_policy: TruncationPolicy,
+    output_artifact: Option<&Path>,
 ) -> String {
     let ExecToolCallOutput {
         exit_code,
@@ -40,6 +46,8 @@ pub fn format_exec_output_for_model_structured(
     struct ExecMetadata {
         exit_code: i32,
         duration_seconds: f32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        full_output_path: Option<String>,
     }
 
     #[derive(Serialize)]
@@ -58,6 +66,7 @@ pub fn format_exec_output_for_model_structured(
         metadata: ExecMetadata {
             exit_code: *exit_code,
             duration_seconds,
+            full_output_path: output_artifact.map(|path| path.display().to_string()),
         },
     };
 
@@ -68,6 +77,7 @@ pub fn format_exec_output_for_model_structured(
 pub fn format_exec_output_for_model_freeform(
     exec_output: &ExecToolCallOutput,
     truncation_policy: TruncationPolicy,
+    output_artifact: Option<&Path>,
 ) -> String {
     // round to 1 decimal place
     let duration_seconds = ((exec_output.duration.as_secs_f32()) * 10.0).round() / 10.0;
@@ -85,6 +95,9 @@ pub fn format_exec_output_for_model_freeform(
     if total_lines != formatted_output.lines().count() {
         sections.push(format!("Total output lines: {total_lines}"));
     }
+    if let Some(path) = output_artifact {
+        sections.push(format!("Full output: {}", path.display()));
+    }
 
     sections.push("Output:".to_string());
     sections.push(formatted_output);
@@ -102,7 +115,40 @@ pub fn format_exec_output_str(
     formatted_truncate_text(&content, truncation_policy)
 }
 
-/// Extracts exec output content and prepends a timeout message if the command timed out.
+/// Saves the untruncated output of `call_id` under `codex_home/artifacts/<thread_id>/` when it
+/// does not fit in `truncation_policy`, returning the artifact path. Configured secrets are masked
+/// in the saved file too.
+pub(crate) async fn write_exec_output_artifact(
+    codex_home: &Path,
+    thread_id: &str,
+    call_id: &str,
+    exec_output: &ExecToolCallOutput,
+    truncation_policy: TruncationPolicy,
+    redactor: &SecretRedactor,
+) -> std::io::Result<Option<PathBuf>> {
+    let content = build_content_with_timeout(exec_output);
+    if content.len() <= truncation_policy.byte_budget() {
+        return Ok(None);
+    }
+    let file_name: String = call_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let dir = codex_home.join("artifacts").join(thread_id);
+    tokio::fs::create_dir_all(&dir).await?;
+    let path = dir.join(format!("{file_name}.log"));
+    tokio::fs::write(&path, redactor.redact(&content).as_bytes()).await?;
+    Ok(Some(path))
+}
+
+/// Extracts exec output content and prepends a message if the command timed out or was stopped
+/// by a configured resource limit.
 fn build_content_with_timeout(exec_output: &ExecToolCallOutput) -> String {
     if exec_output.timed_out {
         format!(
@@ -110,6 +156,11 @@ fn build_content_with_timeout(exec_output: &ExecToolCallOutput) -> String {
             exec_output.duration.as_millis(),
             exec_output.aggregated_output.text
         )
+    } else if let Some(limit) = exec_output.resource_limit_exceeded {
+        format!(
+            "command stopped after exceeding the {limit} limit\n{}",
+            exec_output.aggregated_output.text
+        )
     } else {
         exec_output.aggregated_output.text.clone()
     }
@@ -250,6 +250,7 @@ impl NetworkApprovalService {
                 None,
                 attempt.command.clone(),
                 attempt.cwd.clone(),
+                false,
                 Some(format!(
                     "Network access to \"{}\" is blocked by policy.",
                     request.host
@@ -268,7 +269,9 @@ impl NetworkApprovalService {
                 approved_hosts.insert(request.host);
                 NetworkDecision::Allow
             }
-            ReviewDecision::ApprovedForSession => {
+            ReviewDecision::ApprovedForSession
+            | ReviewDecision::ApprovedForProject
+            | ReviewDecision::ApprovedAlways => {
                 let mut approved_hosts = self.session_approved_hosts.lock().await;
                 approved_hosts.insert(request.host);
                 NetworkDecision::Allow
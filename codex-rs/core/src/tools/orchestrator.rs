@@ -136,6 +136,8 @@ impl ToolOrchestrator {
                     call_id: &tool_ctx.call_id,
                     retry_reason: reason,
                     network_approval_context: None,
+                    escalated: tool.sandbox_mode_for_first_attempt(req)
+                        == SandboxOverride::BypassSandboxFirstAttempt,
                 };
                 let decision = tool.start_approval_async(req, approval_ctx).await;
 
@@ -147,26 +149,33 @@ impl ToolOrchestrator {
                     }
                     ReviewDecision::Approved
                     | ReviewDecision::ApprovedExecpolicyAmendment { .. }
-                    | ReviewDecision::ApprovedForSession => {}
+                    | ReviewDecision::ApprovedForSession
+                    | ReviewDecision::ApprovedForProject
+                    | ReviewDecision::ApprovedAlways => {}
                 }
                 already_approved = true;
             }
         }
 
         // 2) First attempt under the selected sandbox.
-        let has_managed_network_requirements = turn_ctx
-            .config
-            .config_layer_stack
-            .requirements_toml()
-            .network
-            .is_some();
+        // Allowlist misses only become approval prompts under managed requirements; a
+        // `[sandbox_workspace_write]` allowlist still routes traffic through the proxy.
+        let has_managed_network_requirements =
+            turn_ctx.config.managed_network_requirements_enabled();
+        let has_managed_network = turn_ctx.config.managed_network_enabled();
+        let container = if tool.supports_container_sandbox() {
+            turn_ctx.config.sandbox_container.as_ref()
+        } else {
+            None
+        };
         let initial_sandbox = match tool.sandbox_mode_for_first_attempt(req) {
             SandboxOverride::BypassSandboxFirstAttempt => crate::exec::SandboxType::None,
             SandboxOverride::NoOverride => self.sandbox.select_initial(
                 &turn_ctx.sandbox_policy,
                 tool.sandbox_preference(),
                 turn_ctx.windows_sandbox_level,
-                has_managed_network_requirements,
+                has_managed_network,
+                container,
             ),
         };
 
@@ -176,12 +185,15 @@ impl ToolOrchestrator {
         let initial_attempt = SandboxAttempt {
             sandbox: initial_sandbox,
             policy: &turn_ctx.sandbox_policy,
-            enforce_managed_network: has_managed_network_requirements,
+            enforce_managed_network: has_managed_network,
             manager: &self.sandbox,
             sandbox_cwd: &turn_ctx.cwd,
             codex_linux_sandbox_exe: turn_ctx.codex_linux_sandbox_exe.as_ref(),
             use_linux_sandbox_bwrap,
             windows_sandbox_level: turn_ctx.windows_sandbox_level,
+            container,
+            extensions: turn_ctx.config.sandbox_extensions.as_ref(),
+            resource_limits: Some(&turn_ctx.config.resource_limits),
         };
 
         let (first_result, first_deferred_network_approval) = Self::run_attempt(
@@ -264,6 +276,7 @@ impl ToolOrchestrator {
                         call_id: &tool_ctx.call_id,
                         retry_reason: Some(retry_reason),
                         network_approval_context: network_approval_context.clone(),
+                        escalated: true,
                     };
 
                     let decision = tool.start_approval_async(req, approval_ctx).await;
@@ -275,19 +288,24 @@ impl ToolOrchestrator {
                         }
                         ReviewDecision::Approved
                         | ReviewDecision::ApprovedExecpolicyAmendment { .. }
-                        | ReviewDecision::ApprovedForSession => {}
+                        | ReviewDecision::ApprovedForSession
+                        | ReviewDecision::ApprovedForProject
+                        | ReviewDecision::ApprovedAlways => {}
                     }
                 }
 
                 let escalated_attempt = SandboxAttempt {
                     sandbox: crate::exec::SandboxType::None,
                     policy: &turn_ctx.sandbox_policy,
-                    enforce_managed_network: has_managed_network_requirements,
+                    enforce_managed_network: has_managed_network,
                     manager: &self.sandbox,
                     sandbox_cwd: &turn_ctx.cwd,
                     codex_linux_sandbox_exe: None,
                     use_linux_sandbox_bwrap,
                     windows_sandbox_level: turn_ctx.windows_sandbox_level,
+                    container: None,
+                    extensions: turn_ctx.config.sandbox_extensions.as_ref(),
+                    resource_limits: Some(&turn_ctx.config.resource_limits),
                 };
 
                 // Second attempt.
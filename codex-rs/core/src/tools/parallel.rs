@@ -6,6 +6,7 @@ use tokio_util::either::Either;
 use tokio_util::sync::CancellationToken;
 use tokio_util::task::AbortOnDropHandle;
 use tracing::Instrument;
+use tracing::field;
 use tracing::instrument;
 use tracing::trace_span;
 
@@ -67,6 +68,7 @@ impl ToolCallRuntime {
             tool_name = call.tool_name.as_str(),
             call_id = call.call_id.as_str(),
             aborted = false,
+            error = field::Empty,
         );
 
         let handle: AbortOnDropHandle<Result<ResponseInputItem, FunctionCallError>> =
@@ -84,7 +86,7 @@ impl ToolCallRuntime {
                             Either::Right(lock.write().await)
                         };
 
-                        router
+                        let result = router
                             .dispatch_tool_call(
                                 session,
                                 turn,
@@ -93,7 +95,11 @@ impl ToolCallRuntime {
                                 crate::tools::router::ToolCallSource::Direct,
                             )
                             .instrument(dispatch_span.clone())
-                            .await
+                            .await;
+                        if let Err(err) = &result {
+                            dispatch_span.record("error", err.to_string().as_str());
+                        }
+                        result
                     } => res,
                 }
             }));
@@ -195,6 +195,11 @@ impl ToolRegistry {
                 let output = guard.take().ok_or_else(|| {
                     FunctionCallError::Fatal("tool produced no output".to_string())
                 })?;
+                let output = invocation
+                    .session
+                    .services
+                    .secret_redactor
+                    .redact_tool_output(output);
                 Ok(output.into_response(&call_id_owned, &payload_for_response))
             }
             Err(err) => Err(err),
@@ -272,6 +277,12 @@ impl ToolRegistryBuilder {
     //     }
     // }
 
+    /// Drops the specs and handlers of every tool whose name does not satisfy `keep`.
+    pub fn retain_tools(&mut self, keep: impl Fn(&str) -> bool) {
+        self.specs.retain(|configured| keep(configured.spec.name()));
+        self.handlers.retain(|name, _| keep(name));
+    }
+
     pub fn build(self) -> (Vec<ConfiguredToolSpec>, ToolRegistry) {
         let registry = ToolRegistry::new(self.handlers);
         (self.specs, registry)
@@ -388,6 +399,11 @@ async fn dispatch_after_tool_use_hook(
         match hook_outcome.result {
             HookResult::Success => {}
             HookResult::FailedContinue(error) => {
+                turn.otel_manager.counter(
+                    "codex.hook.failure",
+                    1,
+                    &[("hook", "after_tool_use"), ("action", "continue")],
+                );
                 warn!(
                     call_id = %invocation.call_id,
                     tool_name = %invocation.tool_name,
@@ -397,6 +413,11 @@ async fn dispatch_after_tool_use_hook(
                 );
             }
             HookResult::FailedAbort(error) => {
+                turn.otel_manager.counter(
+                    "codex.hook.failure",
+                    1,
+                    &[("hook", "after_tool_use"), ("action", "abort")],
+                );
                 warn!(
                     call_id = %invocation.call_id,
                     tool_name = %invocation.tool_name,
@@ -171,6 +171,19 @@ impl ToolRouter {
             ));
         }
 
+        // Blocked tools are never offered, but a model can still name one, and js_repl can call
+        // any tool by name.
+        if turn.tools_config.is_tool_blocked(&tool_name) {
+            let err = FunctionCallError::RespondToModel(format!(
+                "tool `{tool_name}` is blocked by managed requirements"
+            ));
+            return Ok(Self::failure_response(
+                failure_call_id,
+                payload_outputs_custom,
+                err,
+            ));
+        }
+
         let invocation = ToolInvocation {
             session,
             turn,
@@ -331,4 +344,51 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn blocked_tools_are_not_offered_or_dispatched() -> anyhow::Result<()> {
+        let (session, mut turn) = make_session_and_context().await;
+        turn.tools_config.blocked_tools = vec!["shell*".to_string()];
+
+        let session = Arc::new(session);
+        let turn = Arc::new(turn);
+        let router = ToolRouter::from_config(
+            &turn.tools_config,
+            None,
+            None,
+            turn.dynamic_tools.as_slice(),
+        );
+        assert!(
+            router
+                .specs()
+                .iter()
+                .all(|spec| !spec.name().starts_with("shell")),
+            "blocked tools should not be offered to the model"
+        );
+
+        let call = ToolCall {
+            tool_name: "shell_command".to_string(),
+            call_id: "call-3".to_string(),
+            payload: ToolPayload::Function {
+                arguments: "{}".to_string(),
+            },
+        };
+        let tracker = Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new()));
+        let response = router
+            .dispatch_tool_call(session, turn, tracker, call, ToolCallSource::JsRepl)
+            .await?;
+
+        match response {
+            ResponseInputItem::FunctionCallOutput { output, .. } => {
+                let content = output.text_content().unwrap_or_default();
+                assert!(
+                    content.contains("tool `shell_command` is blocked by managed requirements"),
+                    "unexpected tool call message: {content}",
+                );
+            }
+            other => panic!("expected function call output, got {other:?}"),
+        }
+
+        Ok(())
+    }
 }
@@ -83,6 +83,9 @@ impl Sandboxable for ApplyPatchRuntime {
     fn escalate_on_failure(&self) -> bool {
         true
     }
+    fn supports_container_sandbox(&self) -> bool {
+        false
+    }
 }
 
 impl Approvable<ApplyPatchRequest> for ApplyPatchRuntime {
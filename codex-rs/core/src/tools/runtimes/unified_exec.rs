@@ -113,6 +113,7 @@ impl Approvable<UnifiedExecRequest> for UnifiedExecRuntime<'_> {
                         None,
                         command,
                         cwd,
+                        ctx.escalated,
                         reason,
                         ctx.network_approval_context.clone(),
                         req.exec_approval_requirement
@@ -6,6 +6,9 @@
 
 use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::config::types::ResourceLimits;
+use crate::config::types::SandboxContainer;
+use crate::config::types::SandboxExtensions;
 use crate::error::CodexErr;
 use crate::protocol::SandboxPolicy;
 use crate::sandboxing::CommandSpec;
@@ -18,6 +21,8 @@ use codex_protocol::approvals::ExecPolicyAmendment;
 use codex_protocol::approvals::NetworkApprovalContext;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::SessionApprovalItem;
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::hash::Hash;
@@ -50,6 +55,40 @@ impl ApprovalStore {
             self.map.insert(s, value);
         }
     }
+
+    /// Restores an approval recorded by [`remember_for_session`] in a resumed rollout.
+    pub fn restore_session_approval(&mut self, serialized_key: String) {
+        self.map
+            .insert(serialized_key, ReviewDecision::ApprovedForSession);
+    }
+}
+
+/// Caches `keys` as approved for the rest of the session and records them in the rollout, so a
+/// resumed session does not ask again.
+pub(crate) async fn remember_for_session<K>(services: &SessionServices, keys: Vec<K>)
+where
+    K: Serialize,
+{
+    let keys: Vec<String> = keys
+        .iter()
+        .filter_map(|key| serde_json::to_string(key).ok())
+        .collect();
+    {
+        let mut store = services.tool_approvals.lock().await;
+        for key in &keys {
+            store.restore_session_approval(key.clone());
+        }
+    }
+    let items: Vec<RolloutItem> = keys
+        .into_iter()
+        .map(|key| RolloutItem::SessionApproval(SessionApprovalItem { key }))
+        .collect();
+    let recorder = services.rollout.lock().await.clone();
+    if let Some(recorder) = recorder
+        && let Err(err) = recorder.record_items(&items).await
+    {
+        tracing::error!("failed to record session approvals: {err:#}");
+    }
 }
 
 /// Takes a vector of approval keys and returns a ReviewDecision.
@@ -96,11 +135,14 @@ where
         ],
     );
 
-    if matches!(decision, ReviewDecision::ApprovedForSession) {
-        let mut store = services.tool_approvals.lock().await;
-        for key in keys {
-            store.put(key, ReviewDecision::ApprovedForSession);
-        }
+    // Persistent approvals also cover the rest of this session.
+    if matches!(
+        decision,
+        ReviewDecision::ApprovedForSession
+            | ReviewDecision::ApprovedForProject
+            | ReviewDecision::ApprovedAlways
+    ) {
+        remember_for_session(services, keys).await;
     }
 
     decision
@@ -113,6 +155,8 @@ pub(crate) struct ApprovalCtx<'a> {
     pub call_id: &'a str,
     pub retry_reason: Option<String>,
     pub network_approval_context: Option<NetworkApprovalContext>,
+    /// Whether the approved command runs without the sandbox.
+    pub escalated: bool,
 }
 
 // Specifies what tool orchestrator should do with a given tool call.
@@ -248,6 +292,11 @@ pub(crate) trait Sandboxable {
     fn escalate_on_failure(&self) -> bool {
         true
     }
+    /// Whether the tool can run in `[sandbox_container]` when one is configured. Tools that
+    /// re-invoke the Codex executable cannot, since it is not part of the image.
+    fn supports_container_sandbox(&self) -> bool {
+        true
+    }
 }
 
 pub(crate) struct ToolCtx<'a> {
@@ -286,6 +335,9 @@ pub(crate) struct SandboxAttempt<'a> {
     pub codex_linux_sandbox_exe: Option<&'a std::path::PathBuf>,
     pub use_linux_sandbox_bwrap: bool,
     pub windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel,
+    pub container: Option<&'a SandboxContainer>,
+    pub extensions: Option<&'a SandboxExtensions>,
+    pub resource_limits: Option<&'a ResourceLimits>,
 }
 
 impl<'a> SandboxAttempt<'a> {
@@ -305,6 +357,9 @@ impl<'a> SandboxAttempt<'a> {
                 codex_linux_sandbox_exe: self.codex_linux_sandbox_exe,
                 use_linux_sandbox_bwrap: self.use_linux_sandbox_bwrap,
                 windows_sandbox_level: self.windows_sandbox_level,
+                container: self.container,
+                extensions: self.extensions,
+                resource_limits: self.resource_limits,
             })
     }
 }
@@ -5,7 +5,10 @@ use crate::client_common::tools::ToolSpec;
 use crate::config::AgentRoleConfig;
 use crate::features::Feature;
 use crate::features::Features;
+use crate::file_excerpt::MAX_RANGE_LINES;
+use crate::file_excerpt::READ_FILE_RANGE_TOOL_NAME;
 use crate::mcp_connection_manager::ToolInfo;
+use crate::tools::handlers::DELEGATE_MAX_TOKENS;
 use crate::tools::handlers::PLAN_TOOL;
 use crate::tools::handlers::SEARCH_TOOL_BM25_DEFAULT_LIMIT;
 use crate::tools::handlers::SEARCH_TOOL_BM25_TOOL_NAME;
@@ -16,6 +19,9 @@ use crate::tools::handlers::multi_agents::MAX_WAIT_TIMEOUT_MS;
 use crate::tools::handlers::multi_agents::MIN_WAIT_TIMEOUT_MS;
 use crate::tools::handlers::request_user_input_tool_description;
 use crate::tools::registry::ToolRegistryBuilder;
+use crate::workspace_index::LIST_SYMBOLS_TOOL_NAME;
+use crate::workspace_index::SEARCH_FILES_TOOL_NAME;
+use codex_protocol::config_types::ModeKind;
 use codex_protocol::config_types::WebSearchMode;
 use codex_protocol::dynamic_tools::DynamicToolSpec;
 use codex_protocol::models::VIEW_IMAGE_TOOL_NAME;
@@ -28,6 +34,7 @@ use serde_json::Value as JsonValue;
 use serde_json::json;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
+use wildmatch::WildMatchPattern;
 
 const SEARCH_TOOL_BM25_DESCRIPTION_TEMPLATE: &str =
     include_str!("../../templates/search_tool/tool_description.md");
@@ -43,7 +50,21 @@ pub(crate) struct ToolsConfig {
     pub js_repl_tools_only: bool,
     pub collab_tools: bool,
     pub collaboration_modes_tools: bool,
+    pub project_memory_tool: bool,
+    pub delegate_tool: bool,
+    pub shell_tty: bool,
+    pub background_process_tools: bool,
+    pub web_tools: bool,
+    pub file_tools: bool,
+    pub write_file_tool: bool,
+    /// Set per turn once the session holds an excerpt of a large attached file.
+    pub read_file_range_tool: bool,
+    pub workspace_index_tools: bool,
+    /// Set in plan mode: MCP tools are offered only when annotated as read-only.
+    pub read_only_mcp_tools: bool,
     pub experimental_supported_tools: Vec<String>,
+    /// Tool names (with `*` and `?` wildcards) blocked by `requirements.toml`.
+    pub blocked_tools: Vec<String>,
 }
 
 pub(crate) struct ToolsConfigParams<'a> {
@@ -66,6 +87,10 @@ impl ToolsConfig {
         let include_collab_tools = features.enabled(Feature::Collab);
         let include_collaboration_modes_tools = features.enabled(Feature::CollaborationModes);
         let include_search_tool = features.enabled(Feature::Apps);
+        let include_project_memory_tool = features.enabled(Feature::MemoryTool);
+        let include_delegate_tool = features.enabled(Feature::Delegate);
+        let include_web_tools = features.enabled(Feature::WebTools);
+        let include_file_tools = features.enabled(Feature::FileTools);
 
         let shell_type = if !features.enabled(Feature::ShellTool) {
             ConfigShellToolType::Disabled
@@ -104,7 +129,20 @@ impl ToolsConfig {
             js_repl_tools_only: include_js_repl_tools_only,
             collab_tools: include_collab_tools,
             collaboration_modes_tools: include_collaboration_modes_tools,
+            project_memory_tool: include_project_memory_tool,
+            delegate_tool: include_delegate_tool,
+            shell_tty: shell_type == ConfigShellToolType::ShellCommand
+                && features.enabled(Feature::ShellTty),
+            background_process_tools: shell_type != ConfigShellToolType::Disabled
+                && features.enabled(Feature::BackgroundProcesses),
+            web_tools: include_web_tools,
+            file_tools: include_file_tools,
+            write_file_tool: include_file_tools,
+            read_file_range_tool: false,
+            workspace_index_tools: features.enabled(Feature::WorkspaceIndex),
+            read_only_mcp_tools: false,
             experimental_supported_tools: model_info.experimental_supported_tools.clone(),
+            blocked_tools: Vec::new(),
         }
     }
 
@@ -112,6 +150,32 @@ impl ToolsConfig {
         self.agent_roles = agent_roles;
         self
     }
+
+    pub fn with_blocked_tools(mut self, blocked_tools: Vec<String>) -> Self {
+        self.blocked_tools = blocked_tools;
+        self
+    }
+
+    pub fn is_tool_blocked(&self, tool_name: &str) -> bool {
+        self.blocked_tools
+            .iter()
+            .any(|pattern| WildMatchPattern::<'*', '?'>::new(pattern).matches(tool_name))
+    }
+
+    /// Plan mode must not change files, so it drops the editing tools, the tools that run code
+    /// outside the shell, and MCP tools that are not annotated as read-only.
+    pub fn with_collaboration_mode(mut self, mode: ModeKind) -> Self {
+        if mode == ModeKind::Plan {
+            self.apply_patch_tool_type = None;
+            self.write_file_tool = false;
+            self.js_repl_enabled = false;
+            self.js_repl_tools_only = false;
+            self.shell_tty = false;
+            self.background_process_tools = false;
+            self.read_only_mcp_tools = true;
+        }
+        self
+    }
 }
 
 /// Generic JSON‑Schema subset needed for our tool definitions
@@ -285,6 +349,98 @@ fn create_exec_command_tool() -> ToolSpec {
     })
 }
 
+fn create_start_background_process_tool() -> ToolSpec {
+    let mut properties = BTreeMap::from([
+        (
+            "cmd".to_string(),
+            JsonSchema::String {
+                description: Some("Shell command to start.".to_string()),
+            },
+        ),
+        (
+            "workdir".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional working directory to run the command in; defaults to the turn cwd."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "login".to_string(),
+            JsonSchema::Boolean {
+                description: Some(
+                    "Whether to run the shell with -l/-i semantics. Defaults to true.".to_string(),
+                ),
+            },
+        ),
+    ]);
+    properties.extend(create_approval_parameters());
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "start_background_process".to_string(),
+        description: "Starts a long-running command such as a dev server or file watcher in the background and returns its session ID after a second, along with any early output. Use read_background_output to check on it and stop_background_process when it is no longer needed.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["cmd".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_read_background_output_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "session_id".to_string(),
+            JsonSchema::Number {
+                description: Some("Session ID returned by start_background_process.".to_string()),
+            },
+        ),
+        (
+            "max_output_tokens".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Maximum number of tokens to return. Excess output will be truncated."
+                        .to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "read_background_output".to_string(),
+        description: "Returns the output a background process has written since it was last read, and whether it is still running or has exited.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["session_id".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_stop_background_process_tool() -> ToolSpec {
+    let properties = BTreeMap::from([(
+        "session_id".to_string(),
+        JsonSchema::Number {
+            description: Some("Session ID returned by start_background_process.".to_string()),
+        },
+    )]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "stop_background_process".to_string(),
+        description: "Stops a background process started with start_background_process."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["session_id".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_write_stdin_tool() -> ToolSpec {
     let properties = BTreeMap::from([
         (
@@ -385,7 +541,7 @@ Examples of valid command strings:
     })
 }
 
-fn create_shell_command_tool() -> ToolSpec {
+fn create_shell_command_tool(include_tty: bool) -> ToolSpec {
     let mut properties = BTreeMap::from([
         (
             "command".to_string(),
@@ -417,6 +573,17 @@ fn create_shell_command_tool() -> ToolSpec {
             },
         ),
     ]);
+    if include_tty {
+        properties.insert(
+            "tty".to_string(),
+            JsonSchema::Boolean {
+                description: Some(
+                    "Run the command in a pseudo-terminal for programs that need one (prompts, pagers, REPLs, `docker exec -it`). If it is still running after timeout_ms (10 seconds by default) it is kept alive and the result includes a session ID; use write_stdin to send input or poll for more output. Defaults to false."
+                        .to_string(),
+                ),
+            },
+        );
+    }
     properties.extend(create_approval_parameters());
 
     let description = if cfg!(windows) {
@@ -982,86 +1149,461 @@ fn create_read_file_tool() -> ToolSpec {
             },
         ),
         (
-            "limit".to_string(),
-            JsonSchema::Number {
-                description: Some("The maximum number of lines to return.".to_string()),
+            "limit".to_string(),
+            JsonSchema::Number {
+                description: Some("The maximum number of lines to return.".to_string()),
+            },
+        ),
+        (
+            "mode".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional mode selector: \"slice\" for simple ranges (default) or \"indentation\" \
+                     to expand around an anchor line."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "indentation".to_string(),
+            JsonSchema::Object {
+                properties: indentation_properties,
+                required: None,
+                additional_properties: Some(false.into()),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "read_file".to_string(),
+        description:
+            "Reads a local file with 1-indexed line numbers, supporting slice and indentation-aware block modes."
+                .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["file_path".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_list_dir_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "dir_path".to_string(),
+            JsonSchema::String {
+                description: Some("Absolute path to the directory to list.".to_string()),
+            },
+        ),
+        (
+            "offset".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "The entry number to start listing from. Must be 1 or greater.".to_string(),
+                ),
+            },
+        ),
+        (
+            "limit".to_string(),
+            JsonSchema::Number {
+                description: Some("The maximum number of entries to return.".to_string()),
+            },
+        ),
+        (
+            "depth".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "The maximum directory depth to traverse. Must be 1 or greater.".to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "list_dir".to_string(),
+        description:
+            "Lists entries in a local directory with 1-indexed entry numbers and simple type labels."
+                .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["dir_path".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_write_file_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "file_path".to_string(),
+            JsonSchema::String {
+                description: Some("Absolute path to the file to create or overwrite.".to_string()),
+            },
+        ),
+        (
+            "content".to_string(),
+            JsonSchema::String {
+                description: Some("The complete new contents of the file.".to_string()),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "write_file".to_string(),
+        description: "Creates or overwrites a local file with the given contents, creating parent directories as needed. Only paths writable under the current sandbox policy are accepted; prefer apply_patch for small edits to existing files."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["file_path".to_string(), "content".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_glob_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "pattern".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Glob matched against paths relative to dir_path, e.g. \"**/*.rs\" or \"src/*.ts\". \"*\" does not cross directory separators."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "dir_path".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Absolute path to the directory to search. Defaults to the session's working directory."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "limit".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Maximum number of file paths to return (defaults to 100).".to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "glob".to_string(),
+        description: "Finds files whose paths match a glob pattern, skipping files excluded by .gitignore and similar ignore files. Results are sorted paths relative to dir_path."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["pattern".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_read_file_range_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "path".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Path of the excerpted file, as given in its excerpt.".to_string(),
+                ),
+            },
+        ),
+        (
+            "ranges".to_string(),
+            JsonSchema::Array {
+                items: Box::new(JsonSchema::String { description: None }),
+                description: Some(format!(
+                    "1-indexed, inclusive line ranges such as \"120-180\", or single lines such as \"42\". At most {MAX_RANGE_LINES} lines are returned per range."
+                )),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: READ_FILE_RANGE_TOOL_NAME.to_string(),
+        description: "Reads line ranges of a file that was attached as an excerpt (an outline and selected lines) because it is too large to include whole."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["path".to_string(), "ranges".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_search_files_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "query".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Fuzzy query matched against paths relative to the working directory, e.g. \"toolspec\" for \"src/tools/spec.rs\"."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "limit".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Maximum number of file paths to return (defaults to 50).".to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: SEARCH_FILES_TOOL_NAME.to_string(),
+        description: "Finds workspace files by fuzzy path match, best matches first, using an index kept in the background instead of walking the directory. Files excluded by .gitignore are skipped, and files created in the last few seconds may be missing."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["query".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_list_symbols_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "query".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Case-insensitive substring of the symbol name. Exact names are listed first."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "path".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "File or directory to list symbols from, absolute or relative to the working directory. Directories must be inside the working directory or a writable root. Defaults to the whole workspace."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "limit".to_string(),
+            JsonSchema::Number {
+                description: Some("Maximum number of symbols to return (defaults to 50).".to_string()),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: LIST_SYMBOLS_TOOL_NAME.to_string(),
+        description: "Lists definitions (functions, types, classes, modules) in the workspace as `path:line: text`. Give a query to find a symbol by name, a path to outline a file or directory, or both."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: None,
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_grep_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "pattern".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Regular expression to search for (Rust regex syntax). Patterns containing `\\n` match across lines."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "path".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Absolute path of the file or directory to search. Defaults to the session's working directory."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "include".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional glob limiting which files are searched (e.g. \"*.rs\" or \"src/**/*.ts\")."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "case_insensitive".to_string(),
+            JsonSchema::Boolean {
+                description: Some("Match without regard to case.".to_string()),
+            },
+        ),
+        (
+            "context_lines".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Lines of context to include before and after each match (max 10, defaults to 0)."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "limit".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Maximum number of matching lines to return (defaults to 100).".to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "grep".to_string(),
+        description: "Searches file contents with a regular expression, skipping files excluded by .gitignore and binary files. Matches are reported as `path:line:text`, context lines as `path-line-text`, with `--` between separate groups."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["pattern".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_project_memory_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "action".to_string(),
+            JsonSchema::String {
+                description: Some("One of: save, list, delete".to_string()),
+            },
+        ),
+        (
+            "text".to_string(),
+            JsonSchema::String {
+                description: Some("Fact or preference to remember, for `save`.".to_string()),
             },
         ),
         (
-            "mode".to_string(),
+            "query".to_string(),
             JsonSchema::String {
                 description: Some(
-                    "Optional mode selector: \"slice\" for simple ranges (default) or \"indentation\" \
-                     to expand around an anchor line."
+                    "Optional case-insensitive filter for `list`; omit to list every memory."
                         .to_string(),
                 ),
             },
         ),
         (
-            "indentation".to_string(),
-            JsonSchema::Object {
-                properties: indentation_properties,
-                required: None,
-                additional_properties: Some(false.into()),
+            "index".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "1-based number of the memory to remove, for `delete`.".to_string(),
+                ),
             },
         ),
     ]);
 
     ToolSpec::Function(ResponsesApiTool {
-        name: "read_file".to_string(),
-        description:
-            "Reads a local file with 1-indexed line numbers, supporting slice and indentation-aware block modes."
-                .to_string(),
+        name: "project_memory".to_string(),
+        description: "Stores durable facts about the current project or the user's preferences so they are available in future sessions. Saved memories are shown at the start of every session in this project.".to_string(),
         strict: false,
         parameters: JsonSchema::Object {
             properties,
-            required: Some(vec!["file_path".to_string()]),
+            required: Some(vec!["action".to_string()]),
             additional_properties: Some(false.into()),
         },
     })
 }
 
-fn create_list_dir_tool() -> ToolSpec {
+fn create_delegate_tool() -> ToolSpec {
     let properties = BTreeMap::from([
         (
-            "dir_path".to_string(),
+            "task".to_string(),
             JsonSchema::String {
-                description: Some("Absolute path to the directory to list.".to_string()),
-            },
-        ),
-        (
-            "offset".to_string(),
-            JsonSchema::Number {
                 description: Some(
-                    "The entry number to start listing from. Must be 1 or greater.".to_string(),
+                    "Self-contained description of the work. The sub-agent does not see this conversation, so include every detail it needs and say what its final answer should contain."
+                        .to_string(),
                 ),
             },
         ),
         (
-            "limit".to_string(),
-            JsonSchema::Number {
-                description: Some("The maximum number of entries to return.".to_string()),
+            "allow_edits".to_string(),
+            JsonSchema::Boolean {
+                description: Some(
+                    "Let the sub-agent modify files within the current sandbox. Defaults to false, which keeps it read-only."
+                        .to_string(),
+                ),
             },
         ),
         (
-            "depth".to_string(),
+            "max_tokens".to_string(),
             JsonSchema::Number {
-                description: Some(
-                    "The maximum directory depth to traverse. Must be 1 or greater.".to_string(),
-                ),
+                description: Some(format!(
+                    "Token budget for the sub-agent. Defaults to and is capped at {DELEGATE_MAX_TOKENS}."
+                )),
             },
         ),
     ]);
 
     ToolSpec::Function(ResponsesApiTool {
-        name: "list_dir".to_string(),
-        description:
-            "Lists entries in a local directory with 1-indexed entry numbers and simple type labels."
-                .to_string(),
+        name: "delegate".to_string(),
+        description: "Runs a focused task in a separate sub-agent with its own conversation and token budget, and returns the sub-agent's final message. Use it to research or implement well-scoped pieces of work without filling this conversation with intermediate steps. Several delegate calls in one response run in parallel.".to_string(),
         strict: false,
         parameters: JsonSchema::Object {
             properties,
-            required: Some(vec!["dir_path".to_string()]),
+            required: Some(vec!["task".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_web_search_tool() -> ToolSpec {
+    let properties = BTreeMap::from([(
+        "query".to_string(),
+        JsonSchema::String {
+            description: Some("Search terms.".to_string()),
+        },
+    )]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "web_search".to_string(),
+        description: "Searches the web and returns the titles and URLs of the top results. Use fetch_url to read a result.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["query".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_fetch_url_tool() -> ToolSpec {
+    let properties = BTreeMap::from([(
+        "url".to_string(),
+        JsonSchema::String {
+            description: Some("Absolute http or https URL to fetch.".to_string()),
+        },
+    )]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "fetch_url".to_string(),
+        description: "Fetches a web page or text document and returns its readable text. HTML is converted to plain text and long pages are truncated.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["url".to_string()]),
             additional_properties: Some(false.into()),
         },
     })
@@ -1205,6 +1747,9 @@ fn create_read_mcp_resource_tool() -> ToolSpec {
 #[derive(Serialize, Deserialize)]
 pub(crate) struct ApplyPatchToolArgs {
     pub(crate) input: String,
+    /// Validate the patch and report the status of every hunk without applying it.
+    #[serde(default)]
+    pub(crate) dry_run: bool,
 }
 
 /// Returns JSON values that are compatible with Function Calling in the
@@ -1402,8 +1947,12 @@ pub(crate) fn build_specs(
     dynamic_tools: &[DynamicToolSpec],
 ) -> ToolRegistryBuilder {
     use crate::tools::handlers::ApplyPatchHandler;
+    use crate::tools::handlers::BackgroundProcessHandler;
+    use crate::tools::handlers::DelegateHandler;
     use crate::tools::handlers::DynamicToolHandler;
+    use crate::tools::handlers::GlobHandler;
     use crate::tools::handlers::GrepFilesHandler;
+    use crate::tools::handlers::GrepHandler;
     use crate::tools::handlers::JsReplHandler;
     use crate::tools::handlers::JsReplResetHandler;
     use crate::tools::handlers::ListDirHandler;
@@ -1411,7 +1960,9 @@ pub(crate) fn build_specs(
     use crate::tools::handlers::McpResourceHandler;
     use crate::tools::handlers::MultiAgentHandler;
     use crate::tools::handlers::PlanHandler;
+    use crate::tools::handlers::ProjectMemoryHandler;
     use crate::tools::handlers::ReadFileHandler;
+    use crate::tools::handlers::ReadFileRangeHandler;
     use crate::tools::handlers::RequestUserInputHandler;
     use crate::tools::handlers::SearchToolBm25Handler;
     use crate::tools::handlers::ShellCommandHandler;
@@ -1419,6 +1970,9 @@ pub(crate) fn build_specs(
     use crate::tools::handlers::TestSyncHandler;
     use crate::tools::handlers::UnifiedExecHandler;
     use crate::tools::handlers::ViewImageHandler;
+    use crate::tools::handlers::WebToolsHandler;
+    use crate::tools::handlers::WorkspaceIndexHandler;
+    use crate::tools::handlers::WriteFileHandler;
     use std::sync::Arc;
 
     let mut builder = ToolRegistryBuilder::new();
@@ -1454,7 +2008,12 @@ pub(crate) fn build_specs(
             // Do nothing.
         }
         ConfigShellToolType::ShellCommand => {
-            builder.push_spec_with_parallel_support(create_shell_command_tool(), true);
+            builder
+                .push_spec_with_parallel_support(create_shell_command_tool(config.shell_tty), true);
+            if config.shell_tty {
+                builder.push_spec(create_write_stdin_tool());
+                builder.register_handler("write_stdin", unified_exec_handler);
+            }
         }
     }
 
@@ -1518,25 +2077,65 @@ pub(crate) fn build_specs(
         builder.register_handler("grep_files", grep_files_handler);
     }
 
-    if config
-        .experimental_supported_tools
-        .contains(&"read_file".to_string())
+    if config.file_tools
+        || config
+            .experimental_supported_tools
+            .contains(&"read_file".to_string())
     {
         let read_file_handler = Arc::new(ReadFileHandler);
         builder.push_spec_with_parallel_support(create_read_file_tool(), true);
         builder.register_handler("read_file", read_file_handler);
     }
 
-    if config
-        .experimental_supported_tools
-        .iter()
-        .any(|tool| tool == "list_dir")
+    if config.file_tools
+        || config
+            .experimental_supported_tools
+            .iter()
+            .any(|tool| tool == "list_dir")
     {
         let list_dir_handler = Arc::new(ListDirHandler);
         builder.push_spec_with_parallel_support(create_list_dir_tool(), true);
         builder.register_handler("list_dir", list_dir_handler);
     }
 
+    if config.read_file_range_tool {
+        builder.push_spec_with_parallel_support(create_read_file_range_tool(), true);
+        builder.register_handler(READ_FILE_RANGE_TOOL_NAME, Arc::new(ReadFileRangeHandler));
+    }
+
+    if config.file_tools {
+        builder.push_spec_with_parallel_support(create_glob_tool(), true);
+        builder.register_handler("glob", Arc::new(GlobHandler));
+        builder.push_spec_with_parallel_support(create_grep_tool(), true);
+        builder.register_handler("grep", Arc::new(GrepHandler));
+    }
+
+    if config.workspace_index_tools {
+        let workspace_index_handler = Arc::new(WorkspaceIndexHandler);
+        builder.push_spec_with_parallel_support(create_search_files_tool(), true);
+        builder.push_spec_with_parallel_support(create_list_symbols_tool(), true);
+        builder.register_handler(SEARCH_FILES_TOOL_NAME, workspace_index_handler.clone());
+        builder.register_handler(LIST_SYMBOLS_TOOL_NAME, workspace_index_handler);
+    }
+
+    if config.background_process_tools {
+        let background_process_handler = Arc::new(BackgroundProcessHandler);
+        builder.push_spec(create_start_background_process_tool());
+        builder.push_spec_with_parallel_support(create_read_background_output_tool(), true);
+        builder.push_spec(create_stop_background_process_tool());
+        builder.register_handler(
+            "start_background_process",
+            background_process_handler.clone(),
+        );
+        builder.register_handler("read_background_output", background_process_handler.clone());
+        builder.register_handler("stop_background_process", background_process_handler);
+    }
+
+    if config.write_file_tool {
+        builder.push_spec(create_write_file_tool());
+        builder.register_handler("write_file", Arc::new(WriteFileHandler));
+    }
+
     if config
         .experimental_supported_tools
         .contains(&"test_sync_tool".to_string())
@@ -1546,23 +2145,44 @@ pub(crate) fn build_specs(
         builder.register_handler("test_sync_tool", test_sync_handler);
     }
 
-    match config.web_search_mode {
-        Some(WebSearchMode::Cached) => {
-            builder.push_spec(ToolSpec::WebSearch {
-                external_web_access: Some(false),
-            });
-        }
-        Some(WebSearchMode::Live) => {
-            builder.push_spec(ToolSpec::WebSearch {
-                external_web_access: Some(true),
-            });
+    if config.web_tools {
+        // The built-in tools replace the provider-hosted search, which shares its name.
+        let web_tools_handler = Arc::new(WebToolsHandler);
+        builder.push_spec_with_parallel_support(create_web_search_tool(), true);
+        builder.push_spec_with_parallel_support(create_fetch_url_tool(), true);
+        builder.register_handler("web_search", web_tools_handler.clone());
+        builder.register_handler("fetch_url", web_tools_handler);
+    } else {
+        match config.web_search_mode {
+            Some(WebSearchMode::Cached) => {
+                builder.push_spec(ToolSpec::WebSearch {
+                    external_web_access: Some(false),
+                });
+            }
+            Some(WebSearchMode::Live) => {
+                builder.push_spec(ToolSpec::WebSearch {
+                    external_web_access: Some(true),
+                });
+            }
+            Some(WebSearchMode::Disabled) | None => {}
         }
-        Some(WebSearchMode::Disabled) | None => {}
     }
 
     builder.push_spec_with_parallel_support(create_view_image_tool(), true);
     builder.register_handler("view_image", view_image_handler);
 
+    if config.project_memory_tool {
+        let project_memory_handler = Arc::new(ProjectMemoryHandler);
+        builder.push_spec(create_project_memory_tool());
+        builder.register_handler("project_memory", project_memory_handler);
+    }
+
+    if config.delegate_tool {
+        let delegate_handler = Arc::new(DelegateHandler);
+        builder.push_spec_with_parallel_support(create_delegate_tool(), true);
+        builder.register_handler("delegate", delegate_handler);
+    }
+
     if config.collab_tools {
         let multi_agent_handler = Arc::new(MultiAgentHandler);
         builder.push_spec(create_spawn_agent_tool(config));
@@ -1582,6 +2202,9 @@ pub(crate) fn build_specs(
         entries.sort_by(|a, b| a.0.cmp(&b.0));
 
         for (name, tool) in entries.into_iter() {
+            if config.read_only_mcp_tools && !is_read_only_mcp_tool(&tool) {
+                continue;
+            }
             match mcp_tool_to_openai_tool(name.clone(), tool.clone()) {
                 Ok(converted_tool) => {
                     builder.push_spec(ToolSpec::Function(converted_tool));
@@ -1611,9 +2234,21 @@ pub(crate) fn build_specs(
         }
     }
 
+    if !config.blocked_tools.is_empty() {
+        builder.retain_tools(|name| !config.is_tool_blocked(name));
+    }
+
     builder
 }
 
+/// MCP tools may change state unless they are annotated as read-only.
+fn is_read_only_mcp_tool(tool: &rmcp::model::Tool) -> bool {
+    tool.annotations
+        .as_ref()
+        .and_then(|annotations| annotations.read_only_hint)
+        .unwrap_or(false)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::client_common::tools::FreeformTool;
@@ -2001,6 +2636,109 @@ mod tests {
         );
     }
 
+    #[test]
+    fn web_tools_replace_provider_web_search() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::WebTools);
+
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Live),
+        });
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+
+        assert_contains_tool_names(&tools, &["web_search", "fetch_url"]);
+        assert_eq!(
+            find_tool(&tools, "web_search").spec,
+            create_web_search_tool()
+        );
+        assert_eq!(find_tool(&tools, "fetch_url").spec, create_fetch_url_tool());
+    }
+
+    #[test]
+    fn file_tools_register_read_write_list_glob_and_grep() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::FileTools);
+
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+
+        assert_contains_tool_names(&tools, &["read_file", "write_file", "list_dir", "glob"]);
+        assert_eq!(
+            find_tool(&tools, "write_file").spec,
+            create_write_file_tool()
+        );
+        assert_eq!(find_tool(&tools, "glob").spec, create_glob_tool());
+        assert!(find_tool(&tools, "glob").supports_parallel_tool_calls);
+        assert!(!find_tool(&tools, "write_file").supports_parallel_tool_calls);
+
+        let plan_config = tools_config.with_collaboration_mode(ModeKind::Plan);
+        let (tools, _) = build_specs(&plan_config, None, None, &[]).build();
+        assert_contains_tool_names(&tools, &["read_file", "list_dir", "glob"]);
+        assert!(!tools.iter().any(|tool| tool.spec.name() == "write_file"));
+    }
+
+    #[test]
+    fn read_file_range_is_offered_only_with_file_excerpts() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let features = Features::with_defaults();
+        let mut tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+        assert!(
+            !tools
+                .iter()
+                .any(|tool| tool.spec.name() == READ_FILE_RANGE_TOOL_NAME)
+        );
+
+        tools_config.read_file_range_tool = true;
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+        assert_contains_tool_names(&tools, &[READ_FILE_RANGE_TOOL_NAME]);
+        assert_eq!(
+            find_tool(&tools, READ_FILE_RANGE_TOOL_NAME).spec,
+            create_read_file_range_tool()
+        );
+    }
+
+    #[test]
+    fn workspace_index_feature_registers_search_files_and_list_symbols() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::WorkspaceIndex);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+
+        assert_contains_tool_names(&tools, &[SEARCH_FILES_TOOL_NAME, LIST_SYMBOLS_TOOL_NAME]);
+        assert_eq!(
+            find_tool(&tools, SEARCH_FILES_TOOL_NAME).spec,
+            create_search_files_tool()
+        );
+        assert!(find_tool(&tools, LIST_SYMBOLS_TOOL_NAME).supports_parallel_tool_calls);
+    }
+
     #[test]
     fn mcp_resource_tools_are_hidden_without_mcp_servers() {
         let config = test_config();
@@ -2405,6 +3143,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn plan_mode_offers_only_read_only_tools() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::JsRepl);
+        features.enable(Feature::BackgroundProcesses);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        })
+        .with_collaboration_mode(ModeKind::Plan);
+        let mut read_only_tool =
+            mcp_tool("lookup", "Looks something up", json!({"type": "object"}));
+        read_only_tool.annotations = Some(rmcp::model::ToolAnnotations {
+            destructive_hint: None,
+            idempotent_hint: None,
+            open_world_hint: None,
+            read_only_hint: Some(true),
+            title: None,
+        });
+
+        let (tools, _) = build_specs(
+            &tools_config,
+            Some(HashMap::from([
+                (
+                    "test_server/write_thing".to_string(),
+                    mcp_tool("write_thing", "Writes something", json!({"type": "object"})),
+                ),
+                ("test_server/lookup".to_string(), read_only_tool),
+            ])),
+            None,
+            &[],
+        )
+        .build();
+
+        assert_contains_tool_names(&tools, &["test_server/lookup", "read_mcp_resource"]);
+        for name in [
+            "test_server/write_thing",
+            "js_repl",
+            "start_background_process",
+            "apply_patch",
+        ] {
+            assert!(
+                !tools.iter().any(|tool| tool.spec.name() == name),
+                "{name} should not be offered in plan mode"
+            );
+        }
+    }
+
     #[test]
     fn test_build_specs_mcp_tools_sorted_by_name() {
         let config = test_config();
@@ -2756,9 +3546,66 @@ Examples of valid command strings:
         assert_eq!(description, &expected);
     }
 
+    #[test]
+    fn shell_tty_adds_tty_parameter_and_write_stdin() {
+        let config = test_config();
+        let mut model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        model_info.shell_type = ConfigShellToolType::ShellCommand;
+        let mut features = Features::with_defaults();
+        features.enable(Feature::ShellTty);
+        features.disable(Feature::UnifiedExec);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+        assert_eq!(tools_config.shell_type, ConfigShellToolType::ShellCommand);
+
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+
+        assert_eq!(
+            find_tool(&tools, "shell_command").spec,
+            create_shell_command_tool(true)
+        );
+        assert_eq!(
+            find_tool(&tools, "write_stdin").spec,
+            create_write_stdin_tool()
+        );
+    }
+
+    #[test]
+    fn background_processes_register_start_read_and_stop() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::BackgroundProcesses);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+
+        assert_eq!(
+            find_tool(&tools, "start_background_process").spec,
+            create_start_background_process_tool()
+        );
+        assert_eq!(
+            find_tool(&tools, "read_background_output").spec,
+            create_read_background_output_tool()
+        );
+        assert_eq!(
+            find_tool(&tools, "stop_background_process").spec,
+            create_stop_background_process_tool()
+        );
+    }
+
     #[test]
     fn test_shell_command_tool() {
-        let tool = super::create_shell_command_tool();
+        let tool = super::create_shell_command_tool(false);
         let ToolSpec::Function(ResponsesApiTool {
             description, name, ..
         }) = &tool
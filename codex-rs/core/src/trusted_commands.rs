@@ -0,0 +1,350 @@
+//! Commands the user chose to stop being asked about.
+//!
+//! Answering an exec approval with [`ReviewDecision::ApprovedForProject`] or
+//! [`ReviewDecision::ApprovedAlways`] records the command in `~/.codex/trusted_commands.json`.
+//! Before the next approval prompt the store is read again, so commands trusted in another session
+//! are picked up without a restart. Commands are keyed by their canonical approval form, which
+//! makes `bash -lc 'ls'` and `/bin/bash -lc 'ls'` the same entry, and projects by the root of the
+//! git repository containing the command's working directory. Approvals for commands that ran
+//! outside the sandbox are kept apart from sandboxed ones, so trusting `rm -rf build` inside the
+//! sandbox never lets it run unsandboxed without a prompt.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Result;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::command_canonicalization::canonicalize_command_for_approval;
+use crate::git_info::resolve_root_git_project_for_trust;
+use crate::locked_file::with_locked_append_file;
+use crate::protocol::ReviewDecision;
+
+/// Filename that stores trusted commands inside `~/.codex`.
+const TRUSTED_COMMANDS_FILENAME: &str = "trusted_commands.json";
+
+/// Where a remembered approval applies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TrustScope {
+    /// Only for commands run inside this project root.
+    Project(PathBuf),
+    /// For commands run anywhere.
+    Always,
+}
+
+impl TrustScope {
+    /// Returns the scope to remember for `decision`, or `None` when the decision is not persisted.
+    pub(crate) fn for_decision(decision: &ReviewDecision, cwd: &Path) -> Option<Self> {
+        match decision {
+            ReviewDecision::ApprovedForProject => Some(Self::Project(project_root(cwd))),
+            ReviewDecision::ApprovedAlways => Some(Self::Always),
+            ReviewDecision::Approved
+            | ReviewDecision::ApprovedExecpolicyAmendment { .. }
+            | ReviewDecision::ApprovedForSession
+            | ReviewDecision::Denied
+            | ReviewDecision::Abort => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+struct TrustedCommands {
+    /// Commands trusted to run inside the sandbox. Stored at the top level so files written before
+    /// escalated approvals were tracked keep their meaning.
+    #[serde(flatten)]
+    sandboxed: CommandTrust,
+    /// Commands trusted to run without the sandbox.
+    #[serde(default, skip_serializing_if = "CommandTrust::is_empty")]
+    escalated: CommandTrust,
+}
+
+impl TrustedCommands {
+    fn level(&self, escalated: bool) -> &CommandTrust {
+        if escalated {
+            &self.escalated
+        } else {
+            &self.sandboxed
+        }
+    }
+
+    fn level_mut(&mut self, escalated: bool) -> &mut CommandTrust {
+        if escalated {
+            &mut self.escalated
+        } else {
+            &mut self.sandboxed
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+struct CommandTrust {
+    #[serde(default)]
+    always: BTreeSet<Vec<String>>,
+    #[serde(default)]
+    projects: BTreeMap<PathBuf, BTreeSet<Vec<String>>>,
+}
+
+impl CommandTrust {
+    fn is_empty(&self) -> bool {
+        self.always.is_empty() && self.projects.is_empty()
+    }
+
+    fn is_trusted(&self, command: &[String], project: &Path) -> bool {
+        self.always.contains(command)
+            || self
+                .projects
+                .get(project)
+                .is_some_and(|commands| commands.contains(command))
+    }
+
+    fn insert(&mut self, command: Vec<String>, scope: TrustScope) {
+        match scope {
+            TrustScope::Project(project) => {
+                self.projects.entry(project).or_default().insert(command);
+            }
+            TrustScope::Always => {
+                self.always.insert(command);
+            }
+        }
+    }
+}
+
+fn trusted_commands_filepath(codex_home: &Path) -> PathBuf {
+    codex_home.join(TRUSTED_COMMANDS_FILENAME)
+}
+
+/// Projects are identified by their git root so approvals made in a subdirectory carry over to
+/// the rest of the repository. Directories outside a repository are their own project.
+fn project_root(cwd: &Path) -> PathBuf {
+    resolve_root_git_project_for_trust(cwd).unwrap_or_else(|| cwd.to_path_buf())
+}
+
+/// Returns whether the user previously chose to always approve `command` when run from `cwd`,
+/// either inside the sandbox or, when `escalated` is set, without it.
+pub(crate) async fn is_command_trusted(
+    codex_home: &Path,
+    command: &[String],
+    cwd: &Path,
+    escalated: bool,
+) -> bool {
+    let path = trusted_commands_filepath(codex_home);
+    let contents = match tokio::fs::read_to_string(&path).await {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return false,
+        Err(err) => {
+            warn!("failed to read {}: {err}", path.display());
+            return false;
+        }
+    };
+    let trusted = match serde_json::from_str::<TrustedCommands>(&contents) {
+        Ok(trusted) => trusted,
+        Err(err) => {
+            warn!("failed to parse {}: {err}", path.display());
+            return false;
+        }
+    };
+    trusted.level(escalated).is_trusted(
+        &canonicalize_command_for_approval(command),
+        &project_root(cwd),
+    )
+}
+
+/// Adds `command` to the trust store for `scope`, at the sandbox level it was approved for.
+pub(crate) async fn remember_trusted_command(
+    codex_home: &Path,
+    command: &[String],
+    escalated: bool,
+    scope: TrustScope,
+) -> Result<()> {
+    tokio::fs::create_dir_all(codex_home).await?;
+    let path = trusted_commands_filepath(codex_home);
+    let command = canonicalize_command_for_approval(command);
+
+    let display_path = path.display().to_string();
+    with_locked_append_file(path, move |file| {
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+        let mut trusted = if contents.trim().is_empty() {
+            TrustedCommands::default()
+        } else {
+            serde_json::from_str::<TrustedCommands>(&contents).map_err(|e| {
+                std::io::Error::other(format!("failed to parse {display_path}: {e}"))
+            })?
+        };
+        trusted.level_mut(escalated).insert(command, scope);
+        let serialized = serde_json::to_string_pretty(&trusted).map_err(|e| {
+            std::io::Error::other(format!("failed to serialise trusted commands: {e}"))
+        })?;
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(serialized.as_bytes())?;
+        file.flush()?;
+        Ok(())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn argv(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[tokio::test]
+    async fn project_trust_only_applies_to_that_project() {
+        let codex_home = TempDir::new().expect("create codex home");
+        let project = TempDir::new().expect("create project");
+        let other = TempDir::new().expect("create other project");
+        let command = argv(&["cargo", "test"]);
+
+        remember_trusted_command(
+            codex_home.path(),
+            &command,
+            false,
+            TrustScope::for_decision(&ReviewDecision::ApprovedForProject, project.path())
+                .expect("project scope"),
+        )
+        .await
+        .expect("remember command");
+
+        assert!(is_command_trusted(codex_home.path(), &command, project.path(), false).await);
+        assert!(!is_command_trusted(codex_home.path(), &command, other.path(), false).await);
+        assert!(
+            !is_command_trusted(
+                codex_home.path(),
+                &argv(&["cargo", "build"]),
+                project.path(),
+                false,
+            )
+            .await
+        );
+    }
+
+    #[tokio::test]
+    async fn always_trust_applies_everywhere_and_matches_shell_wrappers() {
+        let codex_home = TempDir::new().expect("create codex home");
+        let cwd = TempDir::new().expect("create cwd");
+
+        remember_trusted_command(
+            codex_home.path(),
+            &argv(&["/bin/bash", "-lc", "git status"]),
+            false,
+            TrustScope::Always,
+        )
+        .await
+        .expect("remember command");
+
+        assert!(
+            is_command_trusted(
+                codex_home.path(),
+                &argv(&["git", "status"]),
+                cwd.path(),
+                false
+            )
+            .await
+        );
+        assert!(
+            is_command_trusted(
+                codex_home.path(),
+                &argv(&["bash", "-lc", "git status"]),
+                cwd.path(),
+                false,
+            )
+            .await
+        );
+    }
+
+    #[tokio::test]
+    async fn remembering_keeps_existing_entries() {
+        let codex_home = TempDir::new().expect("create codex home");
+        let project = PathBuf::from("/work/project");
+
+        remember_trusted_command(codex_home.path(), &argv(&["ls"]), false, TrustScope::Always)
+            .await
+            .expect("remember ls");
+        remember_trusted_command(
+            codex_home.path(),
+            &argv(&["make"]),
+            true,
+            TrustScope::Project(project.clone()),
+        )
+        .await
+        .expect("remember make");
+
+        let contents = std::fs::read_to_string(trusted_commands_filepath(codex_home.path()))
+            .expect("read trust store");
+        let trusted: TrustedCommands = serde_json::from_str(&contents).expect("parse trust store");
+        assert_eq!(
+            trusted,
+            TrustedCommands {
+                sandboxed: CommandTrust {
+                    always: BTreeSet::from([argv(&["ls"])]),
+                    projects: BTreeMap::new(),
+                },
+                escalated: CommandTrust {
+                    always: BTreeSet::new(),
+                    projects: BTreeMap::from([(project, BTreeSet::from([argv(&["make"])]))]),
+                },
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn sandboxed_trust_does_not_approve_escalated_runs() {
+        let codex_home = TempDir::new().expect("create codex home");
+        let cwd = TempDir::new().expect("create cwd");
+        let command = argv(&["rm", "-rf", "build"]);
+
+        remember_trusted_command(codex_home.path(), &command, false, TrustScope::Always)
+            .await
+            .expect("remember command");
+
+        assert!(is_command_trusted(codex_home.path(), &command, cwd.path(), false).await);
+        assert!(!is_command_trusted(codex_home.path(), &command, cwd.path(), true).await);
+    }
+
+    #[test]
+    fn store_without_escalated_entries_keeps_its_format() {
+        let contents = r#"{"always":[["ls"]],"projects":{}}"#;
+
+        let trusted: TrustedCommands = serde_json::from_str(contents).expect("parse trust store");
+
+        assert_eq!(trusted.sandboxed.always, BTreeSet::from([argv(&["ls"])]));
+        assert!(trusted.escalated.is_empty());
+        assert_eq!(
+            serde_json::to_string(&trusted).expect("serialize trust store"),
+            contents
+        );
+    }
+
+    #[test]
+    fn only_persistent_decisions_have_a_scope() {
+        let cwd = Path::new("/tmp");
+
+        assert_eq!(
+            TrustScope::for_decision(&ReviewDecision::ApprovedAlways, cwd),
+            Some(TrustScope::Always)
+        );
+        assert_eq!(
+            TrustScope::for_decision(&ReviewDecision::ApprovedForSession, cwd),
+            None
+        );
+        assert_eq!(
+            TrustScope::for_decision(&ReviewDecision::Approved, cwd),
+            None
+        );
+    }
+}
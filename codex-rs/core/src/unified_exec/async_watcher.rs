@@ -232,6 +232,7 @@ pub(crate) async fn emit_exec_end_for_unified_exec(
         aggregated_output: StreamOutput::new(aggregated_output),
         duration,
         timed_out: false,
+        resource_limit_exceeded: None,
     };
     let event_ctx = ToolEventCtx::new(session_ref.as_ref(), turn_ref.as_ref(), &call_id, None);
     let emitter = ToolEmitter::unified_exec(
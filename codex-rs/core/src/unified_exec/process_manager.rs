@@ -33,6 +33,7 @@ use crate::unified_exec::ExecCommandRequest;
 use crate::unified_exec::MAX_UNIFIED_EXEC_PROCESSES;
 use crate::unified_exec::MAX_YIELD_TIME_MS;
 use crate::unified_exec::MIN_EMPTY_YIELD_TIME_MS;
+use crate::unified_exec::MIN_YIELD_TIME_MS;
 use crate::unified_exec::ProcessEntry;
 use crate::unified_exec::ProcessStore;
 use crate::unified_exec::UnifiedExecContext;
@@ -324,32 +325,18 @@ impl UnifiedExecProcessManager {
         &self,
         request: WriteStdinRequest<'_>,
     ) -> Result<UnifiedExecResponse, UnifiedExecError> {
-        let process_id = request.process_id.to_string();
-
-        let PreparedProcessHandles {
-            writer_tx,
-            output_buffer,
-            output_notify,
-            output_closed,
-            output_closed_notify,
-            cancellation_token,
-            command: session_command,
-            process_id,
-            tty,
-            ..
-        } = self.prepare_process_handles(process_id.as_str()).await?;
+        let handles = self.prepare_process_handles(request.process_id).await?;
 
         if !request.input.is_empty() {
-            if !tty {
+            if !handles.tty {
                 return Err(UnifiedExecError::StdinClosed);
             }
-            Self::send_input(&writer_tx, request.input.as_bytes()).await?;
+            Self::send_input(&handles.writer_tx, request.input.as_bytes()).await?;
             // Give the remote process a brief window to react so that we are
             // more likely to capture its output in the poll below.
             tokio::time::sleep(Duration::from_millis(100)).await;
         }
 
-        let max_tokens = resolve_max_tokens(request.max_output_tokens);
         let yield_time_ms = {
             let time_ms = clamp_yield_time(request.yield_time_ms);
             if request.input.is_empty() {
@@ -358,6 +345,53 @@ impl UnifiedExecProcessManager {
                 time_ms
             }
         };
+        self.poll_process(handles, yield_time_ms, request.max_output_tokens)
+            .await
+    }
+
+    /// Returns the output a background process produced since it was last read, after giving
+    /// it a short moment to flush, along with whether it is still running.
+    pub(crate) async fn read_output(
+        &self,
+        process_id: &str,
+        max_output_tokens: Option<usize>,
+    ) -> Result<UnifiedExecResponse, UnifiedExecError> {
+        let handles = self.prepare_process_handles(process_id).await?;
+        self.poll_process(handles, MIN_YIELD_TIME_MS, max_output_tokens)
+            .await
+    }
+
+    /// Stops a single process and forgets it. Its `ExecCommandEnd` is still emitted by the exit
+    /// watcher once the process is gone.
+    pub(crate) async fn terminate_process(&self, process_id: &str) -> Result<(), UnifiedExecError> {
+        let entry = self.process_store.lock().await.remove(process_id).ok_or(
+            UnifiedExecError::UnknownProcessId {
+                process_id: process_id.to_string(),
+            },
+        )?;
+        Self::unregister_network_attempt_for_entry(&entry).await;
+        entry.process.terminate();
+        Ok(())
+    }
+
+    async fn poll_process(
+        &self,
+        handles: PreparedProcessHandles,
+        yield_time_ms: u64,
+        max_output_tokens: Option<usize>,
+    ) -> Result<UnifiedExecResponse, UnifiedExecError> {
+        let PreparedProcessHandles {
+            output_buffer,
+            output_notify,
+            output_closed,
+            output_closed_notify,
+            cancellation_token,
+            command: session_command,
+            process_id,
+            ..
+        } = handles;
+
+        let max_tokens = resolve_max_tokens(max_output_tokens);
         let start = Instant::now();
         let deadline = start + Duration::from_millis(yield_time_ms);
         let collected = Self::collect_output_until_deadline(
@@ -392,9 +426,7 @@ impl UnifiedExecProcessManager {
                 (None, exit_code, call_id)
             }
             ProcessStatus::Unknown => {
-                return Err(UnifiedExecError::UnknownProcessId {
-                    process_id: request.process_id.to_string(),
-                });
+                return Err(UnifiedExecError::UnknownProcessId { process_id });
             }
         };
 
@@ -407,7 +439,7 @@ impl UnifiedExecProcessManager {
             process_id,
             exit_code,
             original_token_count: Some(original_token_count),
-            session_command: Some(session_command.clone()),
+            session_command: Some(session_command),
         };
 
         Ok(response)
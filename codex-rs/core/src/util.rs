@@ -38,10 +38,15 @@ macro_rules! feedback_tags {
 }
 
 pub fn backoff(attempt: u64) -> Duration {
-    let exp = BACKOFF_FACTOR.powi(attempt.saturating_sub(1) as i32);
-    let base = (INITIAL_DELAY_MS as f64 * exp) as u64;
+    backoff_from(Duration::from_millis(INITIAL_DELAY_MS), attempt)
+}
+
+/// Jittered exponential backoff starting at `initial_delay` for the first attempt.
+pub(crate) fn backoff_from(initial_delay: Duration, attempt: u64) -> Duration {
+    let exp = BACKOFF_FACTOR.powi(attempt.saturating_sub(1).min(32) as i32);
+    let base = initial_delay.as_millis() as f64 * exp;
     let jitter = rand::rng().random_range(0.9..1.1);
-    Duration::from_millis((base as f64 * jitter) as u64)
+    Duration::from_millis((base * jitter) as u64)
 }
 
 pub(crate) fn error_or_panic(message: impl std::string::ToString) {
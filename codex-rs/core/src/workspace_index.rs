@@ -0,0 +1,306 @@
+//! Files and definitions of the workspace, for the `search_files` and `list_symbols` tools.
+//!
+//! The file list is the shared [`FileIndex`] of the working directory, the same one that backs `@`
+//! path completion in the TUI. Definitions are found with the line heuristics used for file
+//! excerpts and cached per file by modification time, so a refreshed file list only re-reads the
+//! files that changed. Sessions hold the index of their working directory through
+//! [`SessionWorkspaceIndex`], and an index is dropped once no session uses it.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+use std::sync::Weak;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use codex_file_search::FileIndex;
+use codex_file_search::FileIndexSnapshot;
+use codex_utils_string::take_bytes_at_char_boundary;
+
+use crate::file_excerpt::parse_definition;
+
+pub(crate) const SEARCH_FILES_TOOL_NAME: &str = "search_files";
+pub(crate) const LIST_SYMBOLS_TOOL_NAME: &str = "list_symbols";
+
+/// A file list older than this is refreshed in the background while it keeps being served.
+const MAX_INDEX_AGE: Duration = Duration::from_secs(10);
+/// How long a lookup waits for the first walk of a workspace.
+const FIRST_WALK_TIMEOUT: Duration = Duration::from_secs(30);
+/// Larger files are skipped when collecting definitions; they are usually generated.
+const MAX_SYMBOL_FILE_BYTES: u64 = 512 * 1024;
+const MAX_SYMBOL_TEXT_BYTES: usize = 200;
+/// Extensions of the files definitions are collected from: languages whose functions and types
+/// start with a keyword that [`parse_definition`] recognizes. C, C++, Objective-C, Java, C#,
+/// Kotlin and F# are left out, since only their types would be listed.
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "rs", "py", "pyi", "js", "jsx", "mjs", "cjs", "ts", "tsx", "go", "scala", "swift", "rb", "php",
+    "ex", "exs", "lua", "sh",
+];
+
+static INDEXES: LazyLock<Mutex<HashMap<PathBuf, Weak<WorkspaceIndex>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// A definition found in a workspace file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Symbol {
+    /// Path relative to the workspace root.
+    pub(crate) path: Arc<str>,
+    /// 1-indexed line of the definition.
+    pub(crate) line: usize,
+    pub(crate) keyword: &'static str,
+    pub(crate) name: String,
+    /// The defining line, trimmed.
+    pub(crate) text: String,
+}
+
+/// Files and definitions under one workspace root, shared by the sessions of this process.
+pub(crate) struct WorkspaceIndex {
+    root: PathBuf,
+    files: FileIndex,
+    symbols: Mutex<SymbolCache>,
+}
+
+#[derive(Default)]
+struct SymbolCache {
+    /// The file list `all` was collected from.
+    collected_from: Option<Arc<FileIndexSnapshot>>,
+    files: HashMap<Arc<str>, CachedFile>,
+    /// Every cached symbol, ordered by path and line.
+    all: Arc<Vec<Symbol>>,
+}
+
+struct CachedFile {
+    modified: SystemTime,
+    symbols: Vec<Symbol>,
+}
+
+impl WorkspaceIndex {
+    /// The index of `root`, shared with every other holder of it.
+    pub(crate) fn for_root(root: &Path) -> Arc<Self> {
+        let mut indexes = lock(&INDEXES);
+        if let Some(index) = indexes.get(root).and_then(Weak::upgrade) {
+            return index;
+        }
+        indexes.retain(|_, index| index.strong_count() > 0);
+        let index = Arc::new(Self {
+            root: root.to_path_buf(),
+            files: FileIndex::for_root(root),
+            symbols: Mutex::new(SymbolCache::default()),
+        });
+        indexes.insert(root.to_path_buf(), Arc::downgrade(&index));
+        index
+    }
+
+    /// The workspace files, waiting for the first walk if there has not been one. Blocks.
+    pub(crate) fn files(&self) -> Option<Arc<FileIndexSnapshot>> {
+        self.files.refresh_if_older_than(MAX_INDEX_AGE);
+        self.files.wait_for_snapshot(FIRST_WALK_TIMEOUT)
+    }
+
+    /// Definitions in the workspace files, ordered by path and line. Only files that changed since
+    /// the file list the cache was collected from are read again. Blocks.
+    pub(crate) fn symbols(&self) -> Option<Arc<Vec<Symbol>>> {
+        let snapshot = self.files()?;
+        let mut cache = lock(&self.symbols);
+        if cache
+            .collected_from
+            .as_ref()
+            .is_some_and(|collected_from| Arc::ptr_eq(collected_from, &snapshot))
+        {
+            return Some(cache.all.clone());
+        }
+
+        let mut files = HashMap::new();
+        for path in snapshot.files().iter().filter(|path| is_source_file(path)) {
+            let full_path = snapshot.root().join(path.as_ref());
+            let Ok(metadata) = std::fs::metadata(&full_path) else {
+                continue;
+            };
+            let Ok(modified) = metadata.modified() else {
+                continue;
+            };
+            if metadata.len() > MAX_SYMBOL_FILE_BYTES {
+                continue;
+            }
+            let cached = match cache.files.remove(path) {
+                Some(cached) if cached.modified == modified => cached,
+                _ => CachedFile {
+                    modified,
+                    symbols: std::fs::read_to_string(&full_path)
+                        .map(|contents| symbols_in(path, &contents))
+                        .unwrap_or_default(),
+                },
+            };
+            files.insert(path.clone(), cached);
+        }
+
+        // The snapshot lists files sorted by path, so collecting in its order keeps `all` sorted.
+        let all = snapshot
+            .files()
+            .iter()
+            .filter_map(|path| files.get(path))
+            .flat_map(|cached| cached.symbols.iter().cloned())
+            .collect();
+        *cache = SymbolCache {
+            collected_from: Some(snapshot),
+            files,
+            all: Arc::new(all),
+        };
+        Some(cache.all.clone())
+    }
+}
+
+/// The workspace index a session uses, which it keeps alive for as long as the session runs.
+#[derive(Default)]
+pub(crate) struct SessionWorkspaceIndex {
+    current: Mutex<Option<Arc<WorkspaceIndex>>>,
+}
+
+impl SessionWorkspaceIndex {
+    /// The index of `root`. The session holds it until it moves to another working directory.
+    pub(crate) fn for_root(&self, root: &Path) -> Arc<WorkspaceIndex> {
+        let mut current = lock(&self.current);
+        if let Some(index) = current.as_ref()
+            && index.root == root
+        {
+            return index.clone();
+        }
+        let index = WorkspaceIndex::for_root(root);
+        *current = Some(index.clone());
+        index
+    }
+
+    /// Starts walking `root` and collecting its definitions in the background, so the first
+    /// lookup of the session does not wait for them.
+    pub(crate) fn warm(&self, root: &Path) {
+        let index = self.for_root(root);
+        tokio::task::spawn_blocking(move || index.symbols());
+    }
+}
+
+/// Definitions in `contents`, read from `path`.
+pub(crate) fn symbols_in(path: &Arc<str>, contents: &str) -> Vec<Symbol> {
+    contents
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let definition = parse_definition(line)?;
+            Some(Symbol {
+                path: path.clone(),
+                line: index + 1,
+                keyword: definition.keyword,
+                name: definition.name?.to_string(),
+                text: take_bytes_at_char_boundary(line.trim(), MAX_SYMBOL_TEXT_BYTES).to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Symbols whose name contains `query`, ignoring case. Exact names come first, then prefixes, and
+/// definitions come before `impl` blocks of the same name.
+pub(crate) fn find_symbols<'a>(symbols: &'a [Symbol], query: &str) -> Vec<&'a Symbol> {
+    let query = query.to_lowercase();
+    let mut matches: Vec<((u8, bool), &Symbol)> = symbols
+        .iter()
+        .filter_map(|symbol| {
+            let name = symbol.name.to_lowercase();
+            let rank = if name == query {
+                0
+            } else if name.starts_with(&query) {
+                1
+            } else if name.contains(&query) {
+                2
+            } else {
+                return None;
+            };
+            Some(((rank, symbol.keyword == "impl"), symbol))
+        })
+        .collect();
+    // Stable, so symbols of the same rank keep their path and line order.
+    matches.sort_by_key(|(rank, _)| *rank);
+    matches.into_iter().map(|(_, symbol)| symbol).collect()
+}
+
+fn is_source_file(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| SOURCE_EXTENSIONS.contains(&extension))
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn symbols_are_collected_and_ranked_by_name() {
+        let temp = tempdir().expect("tempdir");
+        let root = temp.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(
+            root.join("src/parser.rs"),
+            "pub struct Parser;\n\nimpl Parser {\n    pub fn parse_all(&self) {}\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("src/lexer.py"),
+            "class Lexer:\n    def parse(self):\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("README.md"), "# fn parse\n").unwrap();
+
+        let index = WorkspaceIndex::for_root(root);
+        let symbols = index.symbols().expect("symbols");
+        assert_eq!(
+            symbols
+                .iter()
+                .map(|symbol| format!("{}:{} {}", symbol.path, symbol.line, symbol.name))
+                .collect::<Vec<_>>(),
+            vec![
+                "src/lexer.py:1 Lexer",
+                "src/lexer.py:2 parse",
+                "src/parser.rs:1 Parser",
+                "src/parser.rs:3 Parser",
+                "src/parser.rs:4 parse_all",
+            ]
+        );
+
+        assert_eq!(
+            find_symbols(&symbols, "PARSE")
+                .iter()
+                .map(|symbol| symbol.text.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "def parse(self):",
+                "pub struct Parser;",
+                "pub fn parse_all(&self) {}",
+                "impl Parser {",
+            ]
+        );
+    }
+
+    #[test]
+    fn index_is_dropped_when_its_last_session_ends() {
+        let temp = tempdir().expect("tempdir");
+        let session = SessionWorkspaceIndex::default();
+        let index = session.for_root(temp.path());
+        assert!(Arc::ptr_eq(&index, &WorkspaceIndex::for_root(temp.path())));
+
+        let weak = Arc::downgrade(&index);
+        drop(index);
+        assert!(weak.upgrade().is_some());
+        drop(session);
+        assert!(weak.upgrade().is_none());
+    }
+}
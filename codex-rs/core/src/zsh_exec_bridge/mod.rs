@@ -311,6 +311,7 @@ impl ZshExecBridge {
             )),
             duration: start.elapsed(),
             timed_out,
+            resource_limit_exceeded: None,
         };
 
         Self::map_exec_result(req.sandbox, output)
@@ -357,6 +358,9 @@ impl ZshExecBridge {
                 Some(approval_id),
                 command_for_approval,
                 PathBuf::from(cwd),
+                // The intercepted command's sandbox is not known here, so only approvals given
+                // for unsandboxed runs may skip the prompt.
+                true,
                 approval_reason,
                 None,
                 None::<ExecPolicyAmendment>,
@@ -366,6 +370,8 @@ impl ZshExecBridge {
         let (action, reason, user_rejected) = match decision {
             ReviewDecision::Approved
             | ReviewDecision::ApprovedForSession
+            | ReviewDecision::ApprovedForProject
+            | ReviewDecision::ApprovedAlways
             | ReviewDecision::ApprovedExecpolicyAmendment { .. } => {
                 (WrapperExecAction::Run, None, false)
             }
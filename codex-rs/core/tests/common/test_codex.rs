@@ -8,6 +8,7 @@ use codex_core::CodexAuth;
 use codex_core::CodexThread;
 use codex_core::ModelProviderInfo;
 use codex_core::ThreadManager;
+use codex_core::WireApi;
 use codex_core::built_in_model_providers;
 use codex_core::config::Config;
 use codex_core::features::Feature;
@@ -441,6 +442,33 @@ fn function_call_output<'a>(bodies: &'a [Value], call_id: &str) -> &'a Value {
     panic!("function_call_output {call_id} not found");
 }
 
+/// A provider speaking `wire_api` to a mock server at `base_url`, e.g. `{server.uri()}/v1`.
+/// Requests are not retried, so failures surface at once.
+pub fn mock_provider(name: &str, base_url: String, wire_api: WireApi) -> ModelProviderInfo {
+    ModelProviderInfo {
+        name: name.into(),
+        base_url: Some(base_url),
+        // ModelClient requires the provider's env var to be set; PATH always is.
+        env_key: Some("PATH".into()),
+        env_key_instructions: None,
+        api_key_secret: None,
+        experimental_bearer_token: None,
+        wire_api,
+        query_params: None,
+        http_headers: None,
+        env_http_headers: None,
+        request_max_retries: Some(0),
+        stream_max_retries: Some(0),
+        stream_idle_timeout_ms: Some(2000),
+        retry_base_delay_ms: None,
+        retry_max_delay_ms: None,
+        requires_openai_auth: false,
+        supports_websockets: false,
+        azure: None,
+        tls: None,
+    }
+}
+
 pub fn test_codex() -> TestCodexBuilder {
     TestCodexBuilder {
         config_mutators: vec![],
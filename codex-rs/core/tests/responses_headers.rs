@@ -45,6 +45,7 @@ async fn responses_stream_includes_subagent_header_on_review() {
         base_url: Some(format!("{}/v1", server.uri())),
         env_key: None,
         env_key_instructions: None,
+        api_key_secret: None,
         experimental_bearer_token: None,
         wire_api: WireApi::Responses,
         query_params: None,
@@ -53,8 +54,12 @@ async fn responses_stream_includes_subagent_header_on_review() {
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
+        retry_base_delay_ms: None,
+        retry_max_delay_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        azure: None,
+        tls: None,
     };
 
     let codex_home = TempDir::new().expect("failed to create TempDir");
@@ -89,6 +94,7 @@ async fn responses_stream_includes_subagent_header_on_review() {
         None,
         conversation_id,
         provider.clone(),
+        Vec::new(),
         session_source,
         config.model_verbosity,
         false,
@@ -150,6 +156,7 @@ async fn responses_stream_includes_subagent_header_on_other() {
         base_url: Some(format!("{}/v1", server.uri())),
         env_key: None,
         env_key_instructions: None,
+        api_key_secret: None,
         experimental_bearer_token: None,
         wire_api: WireApi::Responses,
         query_params: None,
@@ -158,8 +165,12 @@ async fn responses_stream_includes_subagent_header_on_other() {
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
+        retry_base_delay_ms: None,
+        retry_max_delay_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        azure: None,
+        tls: None,
     };
 
     let codex_home = TempDir::new().expect("failed to create TempDir");
@@ -195,6 +206,7 @@ async fn responses_stream_includes_subagent_header_on_other() {
         None,
         conversation_id,
         provider.clone(),
+        Vec::new(),
         session_source,
         config.model_verbosity,
         false,
@@ -250,6 +262,7 @@ async fn responses_respects_model_info_overrides_from_config() {
         base_url: Some(format!("{}/v1", server.uri())),
         env_key: None,
         env_key_instructions: None,
+        api_key_secret: None,
         experimental_bearer_token: None,
         wire_api: WireApi::Responses,
         query_params: None,
@@ -258,8 +271,12 @@ async fn responses_respects_model_info_overrides_from_config() {
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
+        retry_base_delay_ms: None,
+        retry_max_delay_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        azure: None,
+        tls: None,
     };
 
     let codex_home = TempDir::new().expect("failed to create TempDir");
@@ -300,6 +317,7 @@ async fn responses_respects_model_info_overrides_from_config() {
         None,
         conversation_id,
         provider.clone(),
+        Vec::new(),
         session_source,
         config.model_verbosity,
         false,
@@ -0,0 +1,110 @@
+//! Verifies that turns stream through the Anthropic Messages API when configured.
+
+use codex_core::WireApi;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::sse_response;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::mock_provider;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::matchers::header;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+fn anthropic_sse(events: Vec<Value>) -> String {
+    events
+        .into_iter()
+        .map(|event| {
+            format!(
+                "event: {}\ndata: {event}\n\n",
+                event["type"].as_str().unwrap_or_default()
+            )
+        })
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn turn_streams_from_anthropic_messages_api() {
+    skip_if_no_network!();
+
+    let server = MockServer::start().await;
+    // ModelClient requires the provider's env var to be set; PATH always is.
+    let api_key = std::env::var("PATH").expect("PATH is set");
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .and(header("x-api-key", api_key.as_str()))
+        .and(header("anthropic-version", "2023-06-01"))
+        .respond_with(sse_response(anthropic_sse(vec![
+            json!({"type": "message_start", "message": {"id": "msg_1", "usage": {"input_tokens": 12}}}),
+            json!({"type": "content_block_start", "index": 0, "content_block": {"type": "text", "text": ""}}),
+            json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "hello from claude"}}),
+            json!({"type": "content_block_stop", "index": 0}),
+            json!({"type": "message_delta", "delta": {"stop_reason": "end_turn"}, "usage": {"output_tokens": 4}}),
+            json!({"type": "message_stop"}),
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = mock_provider(
+        "Anthropic",
+        format!("{}/v1", server.uri()),
+        WireApi::Anthropic,
+    );
+    let TestCodex { codex, .. } = test_codex()
+        .with_config(move |config| {
+            config.model_provider = provider;
+        })
+        .build(&server)
+        .await
+        .unwrap();
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+
+    let message = wait_for_event_match(&codex, |event| match event {
+        EventMsg::AgentMessage(event) => Some(event.message.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(message, "hello from claude");
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TurnComplete(_))).await;
+
+    let requests = server.received_requests().await.unwrap_or_default();
+    let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(body["stream"], json!(true));
+    // Environment context and the prompt are merged into one user message.
+    let last_message = body["messages"]
+        .as_array()
+        .and_then(|messages| messages.last())
+        .expect("messages");
+    assert_eq!(last_message["role"], json!("user"));
+    // The end of the conversation is a prompt cache breakpoint.
+    assert_eq!(
+        last_message["content"]
+            .as_array()
+            .and_then(|blocks| blocks.last()),
+        Some(&json!({"type": "text", "text": "hello", "cache_control": {"type": "ephemeral"}}))
+    );
+    assert_eq!(
+        body["system"][0]["cache_control"],
+        json!({"type": "ephemeral"})
+    );
+}
@@ -0,0 +1,160 @@
+use anyhow::Result;
+use codex_app_server_protocol::AuthMode;
+use codex_core::CodexAuth;
+use codex_core::auth::AuthCredentialsStoreMode;
+use codex_core::auth::AuthDotJson;
+use codex_core::auth::save_auth;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path_regex;
+
+/// Rejects the next model request as unauthorized. The test credentials are not on disk, so
+/// reloading them fails and the session asks the user to sign in again.
+async fn mount_unauthorized_once(server: &MockServer) {
+    Mock::given(method("POST"))
+        .and(path_regex(".*/responses$"))
+        .respond_with(ResponseTemplate::new(401))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn turn_resumes_after_signing_in_again() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    mount_unauthorized_once(&server).await;
+    let response = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_assistant_message("msg-1", "done"),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+
+    let test = test_codex()
+        .with_auth(CodexAuth::create_dummy_chatgpt_auth_for_testing())
+        .build(&server)
+        .await?;
+
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+
+    let id = wait_for_event_match(&test.codex, |event| match event {
+        EventMsg::AuthExpired(event) => Some(event.id.clone()),
+        _ => None,
+    })
+    .await;
+
+    // Stands in for the browser sign-in, which stores new credentials before reporting back.
+    save_auth(
+        test.home.path(),
+        &AuthDotJson {
+            auth_mode: Some(AuthMode::ApiKey),
+            openai_api_key: Some("sk-renewed".to_string()),
+            tokens: None,
+            last_refresh: None,
+        },
+        AuthCredentialsStoreMode::File,
+    )?;
+    test.codex
+        .submit(Op::ReauthComplete { id, success: true })
+        .await?;
+
+    let message = wait_for_event_match(&test.codex, |event| match event {
+        EventMsg::AgentMessage(event) => Some(event.message.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(message, "done");
+    wait_for_event(&test.codex, |event| {
+        matches!(event, EventMsg::TurnComplete(_))
+    })
+    .await;
+
+    assert_eq!(
+        response.single_request().header("authorization"),
+        Some("Bearer sk-renewed".to_string())
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn turn_fails_when_sign_in_is_declined() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    mount_unauthorized_once(&server).await;
+    let response = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_assistant_message("msg-1", "done"),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+
+    let test = test_codex()
+        .with_auth(CodexAuth::create_dummy_chatgpt_auth_for_testing())
+        .build(&server)
+        .await?;
+
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+
+    let id = wait_for_event_match(&test.codex, |event| match event {
+        EventMsg::AuthExpired(event) => Some(event.id.clone()),
+        _ => None,
+    })
+    .await;
+    test.codex
+        .submit(Op::ReauthComplete { id, success: false })
+        .await?;
+
+    wait_for_event(&test.codex, |event| matches!(event, EventMsg::Error(_))).await;
+    wait_for_event(&test.codex, |event| {
+        matches!(event, EventMsg::TurnComplete(_))
+    })
+    .await;
+
+    assert!(
+        response.requests().is_empty(),
+        "the request must not be retried after the user declined to sign in"
+    );
+    Ok(())
+}
@@ -0,0 +1,81 @@
+//! Verifies Azure OpenAI deployment routing and `api-version` handling.
+
+use std::collections::HashMap;
+
+use codex_core::AzureProviderInfo;
+use codex_core::ModelProviderInfo;
+use codex_core::WireApi;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::sse;
+use core_test_support::responses::sse_response;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::mock_provider;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+use wiremock::matchers::query_param;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn azure_requests_use_deployment_name_and_api_version() {
+    skip_if_no_network!();
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/responses"))
+        .and(query_param("api-version", "preview"))
+        .respond_with(sse_response(sse(vec![
+            ev_response_created("resp-1"),
+            ev_assistant_message("msg-1", "hello from azure"),
+            ev_completed("resp-1"),
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let base_url = format!("{}/v1", server.uri());
+    let TestCodex { codex, .. } = test_codex()
+        .with_model("gpt-5.1")
+        .with_config(move |config| {
+            config.model_provider = ModelProviderInfo {
+                azure: Some(AzureProviderInfo {
+                    api_version: Some("preview".into()),
+                    deployments: HashMap::from([(
+                        "gpt-5.1".to_string(),
+                        "codex-deployment".to_string(),
+                    )]),
+                    ..Default::default()
+                }),
+                ..mock_provider("Azure", base_url, WireApi::Responses)
+            };
+        })
+        .build(&server)
+        .await
+        .unwrap();
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TurnComplete(_))).await;
+
+    let requests = server.received_requests().await.unwrap_or_default();
+    let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(body["model"], "codex-deployment");
+}
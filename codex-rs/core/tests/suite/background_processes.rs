@@ -0,0 +1,99 @@
+#![cfg(not(target_os = "windows"))]
+
+use anyhow::Result;
+use codex_core::features::Feature;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::skip_if_sandbox;
+use core_test_support::test_codex::test_codex;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn background_process_can_be_read_and_stopped() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+    skip_if_sandbox!(Ok(()));
+
+    let server = start_mock_server().await;
+    let calls = [
+        (
+            "start",
+            "start_background_process",
+            json!({ "cmd": "echo ready; sleep 30", "login": false }),
+        ),
+        (
+            "read",
+            "read_background_output",
+            json!({ "session_id": 1000 }),
+        ),
+        (
+            "stop",
+            "stop_background_process",
+            json!({ "session_id": 1000 }),
+        ),
+        (
+            "read-after-stop",
+            "read_background_output",
+            json!({ "session_id": 1000 }),
+        ),
+    ];
+    let mut responses: Vec<String> = calls
+        .iter()
+        .enumerate()
+        .map(|(index, (call_id, name, args))| {
+            let response_id = format!("resp-{index}");
+            sse(vec![
+                ev_response_created(&response_id),
+                ev_function_call(call_id, name, &args.to_string()),
+                ev_completed(&response_id),
+            ])
+        })
+        .collect();
+    responses.push(sse(vec![
+        ev_response_created("resp-final"),
+        ev_assistant_message("msg-1", "done"),
+        ev_completed("resp-final"),
+    ]));
+    let response = mount_sse_sequence(&server, responses).await;
+    let test = test_codex()
+        .with_config(|config| {
+            config.features.enable(Feature::BackgroundProcesses);
+        })
+        .build(&server)
+        .await?;
+
+    test.submit_turn("start the dev server").await?;
+
+    let requests = response.requests();
+    let start_output = requests[1]
+        .function_call_output_text("start")
+        .expect("start_background_process output");
+    assert!(
+        start_output.contains("Process running with session ID 1000")
+            && start_output.contains("ready"),
+        "unexpected start_background_process output: {start_output}"
+    );
+    let read_output = requests[2]
+        .function_call_output_text("read")
+        .expect("read_background_output output");
+    assert!(
+        read_output.contains("Process running with session ID 1000"),
+        "unexpected read_background_output output: {read_output}"
+    );
+    assert_eq!(
+        requests[3].function_call_output_text("stop"),
+        Some("Stopped background process 1000".to_string())
+    );
+    assert_eq!(
+        requests[4].function_call_output_text("read-after-stop"),
+        Some("read_background_output failed: Unknown process id 1000".to_string())
+    );
+
+    Ok(())
+}
@@ -0,0 +1,186 @@
+//! Verifies that configured budget limits pause the turn until the user decides.
+
+use codex_core::config::types::BudgetLimits;
+use codex_core::protocol::BudgetKind;
+use codex_core::protocol::BudgetLimitReachedEvent;
+use codex_core::protocol::BudgetScope;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::ReviewDecision;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed_with_tokens;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
+use wiremock::MockServer;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn declining_budget_limit_stops_turn() {
+    skip_if_no_network!();
+
+    let server = MockServer::start().await;
+    let responses = mount_sse_sequence(
+        &server,
+        vec![sse(vec![
+            ev_response_created("resp-1"),
+            ev_assistant_message("msg-1", "first"),
+            ev_completed_with_tokens("resp-1", 1_000),
+        ])],
+    )
+    .await;
+
+    let TestCodex { codex, .. } = test_codex()
+        .with_config(|config| {
+            config.budget.session = BudgetLimits {
+                tokens: Some(500),
+                ..BudgetLimits::default()
+            };
+        })
+        .build(&server)
+        .await
+        .unwrap();
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "one".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TurnComplete(_))).await;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "two".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+
+    let request = wait_for_event_match(&codex, |event| match event {
+        EventMsg::BudgetLimitReached(event) => Some(event.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(
+        request,
+        BudgetLimitReachedEvent {
+            id: request.id.clone(),
+            turn_id: request.turn_id.clone(),
+            scope: BudgetScope::Session,
+            kind: BudgetKind::Tokens,
+            used: 1_000.0,
+            limit: 500.0,
+        }
+    );
+
+    codex
+        .submit(Op::BudgetApproval {
+            id: request.id,
+            decision: ReviewDecision::Denied,
+        })
+        .await
+        .unwrap();
+    let message = wait_for_event_match(&codex, |event| match event {
+        EventMsg::Error(event) => Some(event.message.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(
+        message,
+        "Turn stopped: the session budget of 500 tokens was reached."
+    );
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TurnComplete(_))).await;
+    assert_eq!(responses.requests().len(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn approved_budget_limit_is_not_checked_again() {
+    skip_if_no_network!();
+
+    let server = MockServer::start().await;
+    let responses = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_assistant_message("msg-1", "first"),
+                ev_completed_with_tokens("resp-1", 1_000),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_assistant_message("msg-2", "second"),
+                ev_completed_with_tokens("resp-2", 1_000),
+            ]),
+            sse(vec![
+                ev_response_created("resp-3"),
+                ev_assistant_message("msg-3", "third"),
+                ev_completed_with_tokens("resp-3", 1_000),
+            ]),
+        ],
+    )
+    .await;
+
+    let TestCodex { codex, .. } = test_codex()
+        .with_config(|config| {
+            config.budget.session = BudgetLimits {
+                tokens: Some(500),
+                ..BudgetLimits::default()
+            };
+        })
+        .build(&server)
+        .await
+        .unwrap();
+
+    let mut budget_requests = 0;
+    for text in ["one", "two", "three"] {
+        codex
+            .submit(Op::UserInput {
+                items: vec![UserInput::Text {
+                    text: text.into(),
+                    text_elements: Vec::new(),
+                }],
+                final_output_json_schema: None,
+            })
+            .await
+            .unwrap();
+        loop {
+            let event = wait_for_event(&codex, |event| {
+                matches!(
+                    event,
+                    EventMsg::BudgetLimitReached(_) | EventMsg::TurnComplete(_)
+                )
+            })
+            .await;
+            match event {
+                EventMsg::BudgetLimitReached(event) => {
+                    budget_requests += 1;
+                    codex
+                        .submit(Op::BudgetApproval {
+                            id: event.id,
+                            decision: ReviewDecision::Approved,
+                        })
+                        .await
+                        .unwrap();
+                }
+                _ => break,
+            }
+        }
+    }
+
+    assert_eq!(budget_requests, 1);
+    assert_eq!(responses.requests().len(), 3);
+}
@@ -1307,6 +1307,7 @@ async fn azure_responses_request_includes_store_and_reasoning_ids() {
         base_url: Some(format!("{}/openai", server.uri())),
         env_key: None,
         env_key_instructions: None,
+        api_key_secret: None,
         experimental_bearer_token: None,
         wire_api: WireApi::Responses,
         query_params: None,
@@ -1315,8 +1316,12 @@ async fn azure_responses_request_includes_store_and_reasoning_ids() {
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
+        retry_base_delay_ms: None,
+        retry_max_delay_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        azure: None,
+        tls: None,
     };
 
     let codex_home = TempDir::new().unwrap();
@@ -1350,6 +1355,7 @@ async fn azure_responses_request_includes_store_and_reasoning_ids() {
         None,
         conversation_id,
         provider.clone(),
+        Vec::new(),
         SessionSource::Exec,
         config.model_verbosity,
         false,
@@ -1893,6 +1899,7 @@ async fn azure_overrides_assign_properties_used_for_responses_url() {
         base_url: Some(format!("{}/openai", server.uri())),
         // Reuse the existing environment variable to avoid using unsafe code
         env_key: Some(existing_env_var_with_random_value.to_string()),
+        api_key_secret: None,
         experimental_bearer_token: None,
         query_params: Some(std::collections::HashMap::from([(
             "api-version".to_string(),
@@ -1908,8 +1915,12 @@ async fn azure_overrides_assign_properties_used_for_responses_url() {
         request_max_retries: None,
         stream_max_retries: None,
         stream_idle_timeout_ms: None,
+        retry_base_delay_ms: None,
+        retry_max_delay_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        azure: None,
+        tls: None,
     };
 
     // Init session
@@ -1982,6 +1993,7 @@ async fn env_var_overrides_loaded_auth() {
             "2025-04-01-preview".to_string(),
         )])),
         env_key_instructions: None,
+        api_key_secret: None,
         experimental_bearer_token: None,
         wire_api: WireApi::Responses,
         http_headers: Some(std::collections::HashMap::from([(
@@ -1992,8 +2004,12 @@ async fn env_var_overrides_loaded_auth() {
         request_max_retries: None,
         stream_max_retries: None,
         stream_idle_timeout_ms: None,
+        retry_base_delay_ms: None,
+        retry_max_delay_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        azure: None,
+        tls: None,
     };
 
     // Init session
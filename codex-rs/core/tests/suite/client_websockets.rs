@@ -1066,6 +1066,7 @@ fn websocket_provider(server: &WebSocketTestServer) -> ModelProviderInfo {
         base_url: Some(format!("{}/v1", server.uri())),
         env_key: None,
         env_key_instructions: None,
+        api_key_secret: None,
         experimental_bearer_token: None,
         wire_api: WireApi::Responses,
         query_params: None,
@@ -1074,8 +1075,12 @@ fn websocket_provider(server: &WebSocketTestServer) -> ModelProviderInfo {
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
+        retry_base_delay_ms: None,
+        retry_max_delay_ms: None,
         requires_openai_auth: false,
         supports_websockets: true,
+        azure: None,
+        tls: None,
     }
 }
 
@@ -1150,6 +1155,7 @@ async fn websocket_harness_with_options(
         None,
         conversation_id,
         provider.clone(),
+        Vec::new(),
         SessionSource::Exec,
         config.model_verbosity,
         websocket_enabled,
@@ -1,8 +1,11 @@
 use anyhow::Result;
+use codex_core::config::Constrained;
+use codex_core::protocol::AskForApproval;
 use codex_core::protocol::COLLABORATION_MODE_CLOSE_TAG;
 use codex_core::protocol::COLLABORATION_MODE_OPEN_TAG;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
+use codex_core::protocol::SandboxPolicy;
 use codex_protocol::config_types::CollaborationMode;
 use codex_protocol::config_types::ModeKind;
 use codex_protocol::config_types::Settings;
@@ -747,3 +750,93 @@ async fn empty_collaboration_instructions_are_ignored() -> Result<()> {
 
     Ok(())
 }
+
+fn tool_names(body: &Value) -> Vec<String> {
+    body["tools"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|tool| tool.get("name").and_then(Value::as_str))
+        .map(str::to_string)
+        .collect()
+}
+
+fn latest_permissions_text(input: &[Value]) -> String {
+    developer_texts(input)
+        .into_iter()
+        .rfind(|text| text.contains("<permissions instructions>"))
+        .unwrap_or_default()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn plan_mode_turns_are_read_only_until_mode_changes() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let req1 = mount_sse_once(
+        &server,
+        sse(vec![ev_response_created("resp-1"), ev_completed("resp-1")]),
+    )
+    .await;
+    let req2 = mount_sse_once(
+        &server,
+        sse(vec![ev_response_created("resp-2"), ev_completed("resp-2")]),
+    )
+    .await;
+
+    let test = test_codex()
+        .with_config(|config| {
+            config.permissions.approval_policy = Constrained::allow_any(AskForApproval::OnRequest);
+            config.permissions.sandbox_policy =
+                Constrained::allow_any(SandboxPolicy::new_workspace_write_policy());
+        })
+        .build(&server)
+        .await?;
+
+    for (mode, text) in [
+        (ModeKind::Plan, "plan it"),
+        (ModeKind::Default, "implement it"),
+    ] {
+        test.codex
+            .submit(Op::OverrideTurnContext {
+                cwd: None,
+                approval_policy: None,
+                sandbox_policy: None,
+                windows_sandbox_level: None,
+                model: None,
+                effort: None,
+                summary: None,
+                collaboration_mode: Some(collab_mode_with_mode_and_instructions(mode, None)),
+                personality: None,
+            })
+            .await?;
+        test.codex
+            .submit(Op::UserInput {
+                items: vec![UserInput::Text {
+                    text: text.into(),
+                    text_elements: Vec::new(),
+                }],
+                final_output_json_schema: None,
+            })
+            .await?;
+        wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+    }
+
+    let plan_request = req1.single_request();
+    assert!(!tool_names(&plan_request.body_json()).contains(&"apply_patch".to_string()));
+    let plan_permissions = latest_permissions_text(&plan_request.input());
+    assert!(
+        plan_permissions.contains("`sandbox_mode` is `read-only`"),
+        "plan mode should run read-only: {plan_permissions}"
+    );
+
+    let implement_request = req2.single_request();
+    assert!(tool_names(&implement_request.body_json()).contains(&"apply_patch".to_string()));
+    let implement_permissions = latest_permissions_text(&implement_request.input());
+    assert!(
+        implement_permissions.contains("`sandbox_mode` is `workspace-write`"),
+        "default mode should restore the configured sandbox: {implement_permissions}"
+    );
+
+    Ok(())
+}
@@ -10,6 +10,8 @@ use codex_core::protocol::EventMsg;
 use codex_core::protocol::ItemCompletedEvent;
 use codex_core::protocol::ItemStartedEvent;
 use codex_core::protocol::Op;
+use codex_core::protocol::PinnedContextEntry;
+use codex_core::protocol::PinnedContextSource;
 use codex_core::protocol::RolloutItem;
 use codex_core::protocol::RolloutLine;
 use codex_core::protocol::SandboxPolicy;
@@ -495,6 +497,112 @@ async fn manual_compact_uses_custom_prompt() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pinned_context_survives_manual_compact() {
+    skip_if_no_network!();
+
+    let server = start_mock_server().await;
+    let first_turn = sse(vec![
+        ev_assistant_message("m0", FIRST_REPLY),
+        ev_completed_with_tokens("r0", 80),
+    ]);
+    let compact_turn = sse(vec![
+        ev_assistant_message("m1", SUMMARY_TEXT),
+        ev_completed_with_tokens("r1", 100),
+    ]);
+    let follow_up_turn = sse(vec![
+        ev_assistant_message("m2", FINAL_REPLY),
+        ev_completed_with_tokens("r2", 120),
+    ]);
+    let request_log =
+        mount_sse_sequence(&server, vec![first_turn, compact_turn, follow_up_turn]).await;
+
+    let model_provider = non_openai_model_provider(&server);
+    let mut builder = test_codex().with_config(move |config| {
+        config.model_provider = model_provider;
+        set_test_compact_prompt(config);
+    });
+    let codex = builder
+        .build(&server)
+        .await
+        .expect("create conversation")
+        .codex;
+
+    let pinned_text = "PINNED: the storage layer must never import the network crate";
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "USER_ONE".to_string(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .expect("submit first user turn");
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    codex
+        .submit(Op::PinContext {
+            source: PinnedContextSource::Text {
+                text: pinned_text.to_string(),
+            },
+        })
+        .await
+        .expect("pin context");
+    let pinned = wait_for_event_match(&codex, |ev| match ev {
+        EventMsg::PinnedContextUpdated(event) => Some(event.items.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(
+        pinned,
+        vec![PinnedContextEntry {
+            id: "pin-1".to_string(),
+            source: PinnedContextSource::Text {
+                text: pinned_text.to_string(),
+            },
+        }]
+    );
+
+    codex.submit(Op::Compact).await.expect("trigger compact");
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: THIRD_USER_MSG.to_string(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .expect("submit follow-up turn");
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    let requests = request_log.requests();
+    assert_eq!(requests.len(), 3, "expected turn, compact, and follow-up");
+    let follow_up_texts = requests[2].message_input_texts("user");
+    let pinned_messages: Vec<&String> = follow_up_texts
+        .iter()
+        .filter(|text| text.contains(pinned_text))
+        .collect();
+    assert_eq!(
+        pinned_messages.len(),
+        1,
+        "pinned context should be re-attached exactly once after compaction: {follow_up_texts:?}"
+    );
+    assert!(
+        pinned_messages[0].starts_with("<pinned_context>"),
+        "pinned context should stay verbatim, got {pinned_messages:?}"
+    );
+    assert!(
+        follow_up_texts
+            .iter()
+            .any(|text| text.contains(SUMMARY_TEXT) && !text.contains(pinned_text)),
+        "summary should not absorb pinned context: {follow_up_texts:?}"
+    );
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn manual_compact_emits_api_and_local_token_usage_events() {
     skip_if_no_network!();
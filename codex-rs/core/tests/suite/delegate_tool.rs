@@ -0,0 +1,166 @@
+use codex_core::features::Feature;
+use codex_core::protocol::DelegateBeginEvent;
+use codex_core::protocol::DelegateEndEvent;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn delegate_runs_sub_agent_and_returns_its_final_message() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let call_id = "delegate-1";
+    let task = "List the crates in this workspace.";
+    let arguments = json!({ "task": task }).to_string();
+    let response = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_function_call(call_id, "delegate", &arguments),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_assistant_message("msg-sub", "core, exec, tui"),
+                ev_completed("resp-2"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-3"),
+                ev_assistant_message("msg-parent", "done"),
+                ev_completed("resp-3"),
+            ]),
+        ],
+    )
+    .await;
+    let test = test_codex()
+        .with_config(|config| {
+            config.features.enable(Feature::Delegate);
+        })
+        .build(&server)
+        .await?;
+
+    test.submit_turn("survey the workspace").await?;
+
+    let requests = response.requests();
+    assert_eq!(requests.len(), 3);
+    let sub_agent_request = &requests[1];
+    assert!(
+        sub_agent_request
+            .message_input_texts("user")
+            .iter()
+            .any(|text| text == task),
+        "sub-agent did not receive the task"
+    );
+    let sub_agent_tools = sub_agent_request.body_json()["tools"].clone();
+    assert!(
+        !sub_agent_tools
+            .as_array()
+            .into_iter()
+            .flatten()
+            .any(|tool| tool.get("name").and_then(Value::as_str) == Some("delegate")),
+        "sub-agent must not be able to delegate: {sub_agent_tools}"
+    );
+    assert_eq!(
+        requests[2].function_call_output_text(call_id),
+        Some("core, exec, tui".to_string())
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn delegate_emits_begin_progress_and_end_events() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let call_id = "delegate-1";
+    let task = "Summarize README.md.";
+    let arguments = json!({ "task": task, "max_tokens": 5_000 }).to_string();
+    mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_function_call(call_id, "delegate", &arguments),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_assistant_message("msg-sub", "It is short."),
+                ev_completed("resp-2"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-3"),
+                ev_assistant_message("msg-parent", "done"),
+                ev_completed("resp-3"),
+            ]),
+        ],
+    )
+    .await;
+    let test = test_codex()
+        .with_config(|config| {
+            config.features.enable(Feature::Delegate);
+        })
+        .build(&server)
+        .await?;
+
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "summarize".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+
+    let mut begin = None;
+    let mut end = None;
+    let mut progress_messages = Vec::new();
+    loop {
+        match test.codex.next_event().await?.msg {
+            EventMsg::DelegateBegin(event) => begin = Some(event),
+            EventMsg::DelegateProgress(event) => {
+                if let EventMsg::AgentMessage(message) = *event.msg {
+                    progress_messages.push(message.message);
+                }
+            }
+            EventMsg::DelegateEnd(event) => end = Some(event),
+            EventMsg::TurnComplete(_) => break,
+            _ => {}
+        }
+    }
+
+    assert_eq!(
+        begin,
+        Some(DelegateBeginEvent {
+            call_id: call_id.to_string(),
+            task: task.to_string(),
+        })
+    );
+    assert_eq!(progress_messages, vec!["It is short.".to_string()]);
+    assert_eq!(
+        end,
+        Some(DelegateEndEvent {
+            call_id: call_id.to_string(),
+            output: Some("It is short.".to_string()),
+            error: None,
+        })
+    );
+
+    Ok(())
+}
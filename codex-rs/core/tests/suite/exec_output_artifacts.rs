@@ -0,0 +1,103 @@
+#![cfg(not(target_os = "windows"))]
+
+use std::fs;
+
+use anyhow::Result;
+use codex_core::features::Feature;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+fn shell_command_turn(call_id: &str, command: &str) -> Vec<String> {
+    let args = json!({ "command": command, "login": false });
+    vec![
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_function_call(call_id, "shell_command", &args.to_string()),
+            ev_completed("resp-1"),
+        ]),
+        sse(vec![
+            ev_response_created("resp-2"),
+            ev_assistant_message("msg-1", "done"),
+            ev_completed("resp-2"),
+        ]),
+    ]
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn truncated_shell_output_is_saved_to_artifact() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let call_id = "shell-long";
+    let response = mount_sse_sequence(&server, shell_command_turn(call_id, "seq 1 400")).await;
+    let test = test_codex()
+        .with_model("gpt-5-codex")
+        .with_config(|config| {
+            config.features.enable(Feature::ExecOutputArtifacts);
+            config.features.disable(Feature::UnifiedExec);
+            config.tool_output_token_limit = Some(200);
+        })
+        .build(&server)
+        .await?;
+
+    test.submit_turn("print a lot of numbers").await?;
+
+    let artifact = test
+        .codex_home_path()
+        .join("artifacts")
+        .join(test.session_configured.session_id.to_string())
+        .join(format!("{call_id}.log"));
+    let expected: String = (1..=400).map(|n| format!("{n}\n")).collect();
+    assert_eq!(fs::read_to_string(&artifact)?, expected);
+
+    let output = response.requests()[1]
+        .function_call_output_text(call_id)
+        .expect("shell_command output");
+    assert!(
+        output.contains(&format!("Full output: {}\n", artifact.display()))
+            && output.contains("tokens truncated")
+            && output.trim_end().ends_with("400"),
+        "unexpected shell_command output: {output}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn short_shell_output_is_not_saved() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let call_id = "shell-short";
+    let response = mount_sse_sequence(&server, shell_command_turn(call_id, "echo hi")).await;
+    let test = test_codex()
+        .with_model("gpt-5-codex")
+        .with_config(|config| {
+            config.features.enable(Feature::ExecOutputArtifacts);
+            config.features.disable(Feature::UnifiedExec);
+        })
+        .build(&server)
+        .await?;
+
+    test.submit_turn("say hi").await?;
+
+    let output = response.requests()[1]
+        .function_call_output_text(call_id)
+        .expect("shell_command output");
+    assert!(
+        !output.contains("Full output:"),
+        "unexpected shell_command output: {output}"
+    );
+    assert!(!test.codex_home_path().join("artifacts").exists());
+
+    Ok(())
+}
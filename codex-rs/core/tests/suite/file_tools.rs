@@ -0,0 +1,186 @@
+use std::fs;
+
+use codex_core::features::Feature;
+use codex_core::protocol::SandboxPolicy;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+fn tool_call_turn(call_id: &str, name: &str, arguments: &serde_json::Value) -> Vec<String> {
+    vec![
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_function_call(call_id, name, &arguments.to_string()),
+            ev_completed("resp-1"),
+        ]),
+        sse(vec![
+            ev_response_created("resp-2"),
+            ev_assistant_message("msg-1", "done"),
+            ev_completed("resp-2"),
+        ]),
+    ]
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn write_file_creates_file_inside_workspace() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let test = test_codex()
+        .with_config(|config| {
+            config.features.enable(Feature::FileTools);
+        })
+        .build(&server)
+        .await?;
+    let target = test.workspace_path("notes/todo.txt");
+    let call_id = "write-1";
+    let response = mount_sse_sequence(
+        &server,
+        tool_call_turn(
+            call_id,
+            "write_file",
+            &json!({ "file_path": target, "content": "one\ntwo\n" }),
+        ),
+    )
+    .await;
+
+    test.submit_turn_with_policy(
+        "write the todo list",
+        SandboxPolicy::new_workspace_write_policy(),
+    )
+    .await?;
+
+    let requests = response.requests();
+    assert_eq!(
+        requests[1].function_call_output_text(call_id),
+        Some(format!("Wrote 8 bytes (2 lines) to {}", target.display()))
+    );
+    assert_eq!(fs::read_to_string(&target)?, "one\ntwo\n");
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn write_file_is_rejected_by_read_only_sandbox() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let test = test_codex()
+        .with_config(|config| {
+            config.features.enable(Feature::FileTools);
+        })
+        .build(&server)
+        .await?;
+    let target = test.workspace_path("blocked.txt");
+    let call_id = "write-1";
+    let response = mount_sse_sequence(
+        &server,
+        tool_call_turn(
+            call_id,
+            "write_file",
+            &json!({ "file_path": target, "content": "nope" }),
+        ),
+    )
+    .await;
+
+    test.submit_turn_with_policy("write a file", SandboxPolicy::new_read_only_policy())
+        .await?;
+
+    let requests = response.requests();
+    assert_eq!(
+        requests[1].function_call_output_text(call_id),
+        Some(format!(
+            "`{}` is not writable under the current sandbox policy; use apply_patch so the change can be approved",
+            target.display()
+        ))
+    );
+    assert!(!target.exists());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn glob_lists_matching_files_relative_to_cwd() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let test = test_codex()
+        .with_config(|config| {
+            config.features.enable(Feature::FileTools);
+        })
+        .build(&server)
+        .await?;
+    fs::create_dir_all(test.workspace_path("src/bin"))?;
+    fs::write(test.workspace_path("src/lib.rs"), "")?;
+    fs::write(test.workspace_path("src/bin/main.rs"), "")?;
+    fs::write(test.workspace_path("README.md"), "")?;
+    let call_id = "glob-1";
+    let response = mount_sse_sequence(
+        &server,
+        tool_call_turn(call_id, "glob", &json!({ "pattern": "src/**/*.rs" })),
+    )
+    .await;
+
+    test.submit_turn("find the rust sources").await?;
+
+    let requests = response.requests();
+    assert_eq!(
+        requests[1].function_call_output_text(call_id),
+        Some(format!(
+            "Absolute path: {}\nsrc/bin/main.rs\nsrc/lib.rs",
+            test.cwd_path().display()
+        ))
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn grep_reports_matches_with_line_numbers() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let test = test_codex()
+        .with_config(|config| {
+            config.features.enable(Feature::FileTools);
+        })
+        .build(&server)
+        .await?;
+    fs::create_dir_all(test.workspace_path("src"))?;
+    fs::write(
+        test.workspace_path("src/lib.rs"),
+        "pub fn parse() {}\n\npub fn render() {}\n",
+    )?;
+    fs::write(test.workspace_path("README.md"), "Call parse() first.\n")?;
+    let call_id = "grep-1";
+    let response = mount_sse_sequence(
+        &server,
+        tool_call_turn(
+            call_id,
+            "grep",
+            &json!({ "pattern": r"fn \w+", "include": "*.rs", "context_lines": 1 }),
+        ),
+    )
+    .await;
+
+    test.submit_turn("find the functions").await?;
+
+    let requests = response.requests();
+    assert_eq!(
+        requests[1].function_call_output_text(call_id),
+        Some(format!(
+            "Absolute path: {}\nsrc/lib.rs:1:pub fn parse() {{}}\nsrc/lib.rs-2-\nsrc/lib.rs:3:pub fn render() {{}}",
+            test.cwd_path().display()
+        ))
+    );
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+//! Verifies that turns stream through the Gemini API when configured.
+
+use codex_core::WireApi;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::sse_response;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::mock_provider;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::matchers::header;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+use wiremock::matchers::query_param;
+
+fn gemini_sse(chunks: Vec<Value>) -> String {
+    chunks
+        .into_iter()
+        .map(|chunk| format!("data: {chunk}\r\n\r\n"))
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn turn_streams_from_gemini_generate_content() {
+    skip_if_no_network!();
+
+    let server = MockServer::start().await;
+    // ModelClient requires the provider's env var to be set; PATH always is.
+    let api_key = std::env::var("PATH").expect("PATH is set");
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-2.5-pro:streamGenerateContent"))
+        .and(query_param("alt", "sse"))
+        .and(header("x-goog-api-key", api_key.as_str()))
+        .respond_with(sse_response(gemini_sse(vec![
+            json!({"responseId": "resp_1", "candidates": [{"content": {"role": "model", "parts": [{"text": "hello from "}]}}]}),
+            json!({"candidates": [{"content": {"role": "model", "parts": [{"text": "gemini"}]}, "finishReason": "STOP"}],
+                "usageMetadata": {"promptTokenCount": 12, "candidatesTokenCount": 3}}),
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = mock_provider(
+        "Gemini",
+        format!("{}/v1beta", server.uri()),
+        WireApi::Gemini,
+    );
+    let TestCodex { codex, .. } = test_codex()
+        .with_model("gemini-2.5-pro")
+        .with_config(move |config| {
+            config.model_provider = provider;
+        })
+        .build(&server)
+        .await
+        .unwrap();
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+
+    let message = wait_for_event_match(&codex, |event| match event {
+        EventMsg::AgentMessage(event) => Some(event.message.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(message, "hello from gemini");
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TurnComplete(_))).await;
+
+    let requests = server.received_requests().await.unwrap_or_default();
+    let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert!(body["systemInstruction"]["parts"][0]["text"].is_string());
+    let last_content = body["contents"]
+        .as_array()
+        .and_then(|contents| contents.last())
+        .expect("contents");
+    assert_eq!(last_content["role"], json!("user"));
+    assert!(
+        last_content["parts"]
+            .as_array()
+            .expect("parts")
+            .contains(&json!({"text": "hello"}))
+    );
+}
@@ -1,10 +1,14 @@
 use codex_core::features::Feature;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::ProjectDocsLoadedEvent;
 use core_test_support::responses::ev_completed;
 use core_test_support::responses::ev_response_created;
 use core_test_support::responses::mount_sse_once;
 use core_test_support::responses::sse;
 use core_test_support::responses::start_mock_server;
 use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
 
 const HIERARCHICAL_AGENTS_SNIPPET: &str =
     "Files called AGENTS.md commonly appear in many places inside a container";
@@ -75,3 +79,62 @@ async fn hierarchical_agents_emits_when_no_project_doc() {
         "expected hierarchical agents message appended: {instructions}"
     );
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn nested_project_docs_are_merged_and_reported() {
+    let server = start_mock_server().await;
+    let resp_mock = mount_sse_once(
+        &server,
+        sse(vec![ev_response_created("resp1"), ev_completed("resp1")]),
+    )
+    .await;
+
+    let mut builder = test_codex().with_config(|config| {
+        let root = config.cwd.clone();
+        std::fs::create_dir(root.join(".git")).expect("create .git");
+        std::fs::write(
+            root.join("AGENTS.md"),
+            "## Testing\nRun the root suite.\n\n## Style\nKeep it tidy.\n",
+        )
+        .expect("write root AGENTS.md");
+        let nested = root.join("services").join("api");
+        std::fs::create_dir_all(&nested).expect("create nested dir");
+        std::fs::write(nested.join("AGENTS.md"), "## Testing\nRun the api suite.\n")
+            .expect("write nested AGENTS.md");
+        config.cwd = nested;
+    });
+    let test = builder.build(&server).await.expect("build test codex");
+
+    let loaded = wait_for_event_match(&test.codex, |event| match event {
+        EventMsg::ProjectDocsLoaded(event) => Some(event.clone()),
+        _ => None,
+    })
+    .await;
+    let root = dunce::canonicalize(test.cwd.path()).expect("canonical cwd");
+    assert_eq!(
+        loaded,
+        ProjectDocsLoadedEvent {
+            paths: vec![
+                root.join("AGENTS.md"),
+                root.join("services").join("api").join("AGENTS.md"),
+            ],
+        }
+    );
+
+    test.submit_turn("hello").await.expect("submit turn");
+
+    let request = resp_mock.single_request();
+    let user_messages = request.message_input_texts("user");
+    let instructions = user_messages
+        .iter()
+        .find(|text| text.starts_with("# AGENTS.md instructions for "))
+        .expect("instructions message");
+    assert!(
+        instructions.contains("Keep it tidy.") && instructions.contains("Run the api suite."),
+        "expected merged AGENTS.md sections: {instructions}"
+    );
+    assert!(
+        !instructions.contains("Run the root suite."),
+        "expected the nested doc to override the root Testing section: {instructions}"
+    );
+}
@@ -11,9 +11,11 @@ use core_test_support::skip_if_no_network;
 use core_test_support::test_codex::TestCodex;
 use core_test_support::test_codex::test_codex;
 use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
 use pretty_assertions::assert_eq;
 use responses::ev_assistant_message;
 use responses::ev_completed;
+use responses::mount_sse_sequence;
 use responses::sse;
 use responses::start_mock_server;
 
@@ -107,3 +109,116 @@ async fn codex_returns_json_result(model: String) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+fn structured_turn(text: &str, cwd: &std::path::Path) -> anyhow::Result<Op> {
+    Ok(Op::UserTurn {
+        items: vec![UserInput::Text {
+            text: text.into(),
+            text_elements: Vec::new(),
+        }],
+        final_output_json_schema: Some(serde_json::from_str(SCHEMA)?),
+        cwd: cwd.to_path_buf(),
+        approval_policy: AskForApproval::Never,
+        sandbox_policy: SandboxPolicy::DangerFullAccess,
+        model: "gpt-5.1".to_string(),
+        effort: None,
+        summary: ReasoningSummary::Auto,
+        collaboration_mode: None,
+        personality: None,
+    })
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn mismatched_final_message_is_sent_back_to_the_model() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let mock = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_assistant_message("m1", r#"{"explanation": "explanation"}"#),
+                ev_completed("r1"),
+            ]),
+            sse(vec![
+                ev_assistant_message(
+                    "m2",
+                    r#"{"explanation": "explanation", "final_answer": "final_answer"}"#,
+                ),
+                ev_completed("r2"),
+            ]),
+        ],
+    )
+    .await;
+
+    let TestCodex { codex, cwd, .. } = test_codex().build(&server).await?;
+    codex
+        .submit(structured_turn("hello world", cwd.path())?)
+        .await?;
+
+    let structured = wait_for_event_match(&codex, |ev| match ev {
+        EventMsg::TurnStructuredOutput(event) => Some(event.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(structured.retries, 1);
+    assert_eq!(
+        structured.output,
+        serde_json::json!({"explanation": "explanation", "final_answer": "final_answer"})
+    );
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(
+        requests[1]
+            .message_input_texts("developer")
+            .iter()
+            .any(|text| text.contains("$: missing required property \"final_answer\"")),
+        "the retry request should explain what was wrong"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn turn_fails_when_retries_are_exhausted() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let bodies = (1..=3)
+        .map(|n| {
+            sse(vec![
+                ev_assistant_message(&format!("m{n}"), "not json"),
+                ev_completed(&format!("r{n}")),
+            ])
+        })
+        .collect();
+    let mock = mount_sse_sequence(&server, bodies).await;
+
+    let TestCodex { codex, cwd, .. } = test_codex().build(&server).await?;
+    codex
+        .submit(structured_turn("hello world", cwd.path())?)
+        .await?;
+
+    let error = wait_for_event_match(&codex, |ev| match ev {
+        EventMsg::Error(event) => Some(event.clone()),
+        _ => None,
+    })
+    .await;
+    assert!(
+        error
+            .message
+            .starts_with("The final message did not match the output schema after 2 retries: "),
+        "{}",
+        error.message
+    );
+    let complete = wait_for_event_match(&codex, |ev| match ev {
+        EventMsg::TurnComplete(event) => Some(event.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(complete.last_agent_message, None);
+    assert_eq!(mock.requests().len(), 3);
+
+    Ok(())
+}
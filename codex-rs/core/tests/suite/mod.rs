@@ -57,10 +57,15 @@ pub static CODEX_ALIASES_TEMP_DIR: TestCodexAliasesGuard = unsafe {
 #[cfg(not(target_os = "windows"))]
 mod abort_tasks;
 mod agent_websocket;
+mod anthropic_provider;
 mod apply_patch_cli;
 #[cfg(not(target_os = "windows"))]
 mod approvals;
+mod auth_expired;
 mod auth_refresh;
+mod azure_provider;
+mod background_processes;
+mod budget_limits;
 mod cli_stream;
 mod client;
 mod client_websockets;
@@ -69,10 +74,14 @@ mod collaboration_instructions;
 mod compact;
 mod compact_remote;
 mod compact_resume_fork;
+mod delegate_tool;
 mod deprecation_notice;
 mod exec;
+mod exec_output_artifacts;
 mod exec_policy;
+mod file_tools;
 mod fork_thread;
+mod gemini_provider;
 mod grep_files;
 mod hierarchical_agents;
 mod image_rollout;
@@ -93,7 +102,9 @@ mod pending_input;
 mod permissions_messages;
 mod personality;
 mod personality_migration;
+mod project_memory;
 mod prompt_caching;
+mod provider_fallback;
 mod quota_exceeded;
 mod read_file;
 mod remote_models;
@@ -107,14 +118,18 @@ mod rollout_list_find;
 mod safety_check_downgrade;
 mod search_tool;
 mod seatbelt;
+mod secret_redaction;
 mod shell_command;
 mod shell_serialization;
 mod shell_snapshot;
+mod shell_tty;
 mod skills;
 mod sqlite_state;
 mod stream_error_allows_next_turn;
 mod stream_no_completed;
+mod stream_retry;
 mod text_encoding_fix;
+mod tool_emulation;
 mod tool_harness;
 mod tool_parallelism;
 mod tools;
@@ -123,8 +138,11 @@ mod turn_state;
 mod undo;
 mod unified_exec;
 mod unstable_features_warning;
+mod usage_accounting;
 mod user_notification;
 mod user_shell_cmd;
 mod view_image;
 mod web_search;
+mod web_tools;
 mod websocket_fallback;
+mod worktree;
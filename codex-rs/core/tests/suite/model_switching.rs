@@ -7,6 +7,8 @@ use codex_core::protocol::AskForApproval;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
 use codex_core::protocol::SandboxPolicy;
+use codex_core::protocol::TurnOverrides;
+use codex_core::protocol::TurnSettingsEvent;
 use codex_protocol::config_types::ReasoningSummary;
 use codex_protocol::openai_models::ConfigShellToolType;
 use codex_protocol::openai_models::InputModality;
@@ -28,7 +30,10 @@ use core_test_support::responses::start_mock_server;
 use core_test_support::skip_if_no_network;
 use core_test_support::test_codex::test_codex;
 use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
 use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
 use wiremock::MockServer;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -115,6 +120,165 @@ async fn model_change_appends_model_instructions_developer_message() -> Result<(
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn turn_overrides_apply_to_a_single_turn() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = MockServer::start().await;
+    let resp_mock = mount_sse_sequence(
+        &server,
+        vec![sse_completed("resp-1"), sse_completed("resp-2")],
+    )
+    .await;
+
+    let mut builder = test_codex().with_model("gpt-5.2-codex");
+    let test = builder.build(&server).await?;
+    let user_input = |text: &str| {
+        vec![UserInput::Text {
+            text: text.into(),
+            text_elements: Vec::new(),
+        }]
+    };
+
+    test.codex
+        .submit(Op::UserInputWithOverrides {
+            items: user_input("just this turn"),
+            overrides: TurnOverrides {
+                model: Some("gpt-5.1-codex-max".to_string()),
+                effort: Some(ReasoningEffort::High),
+                ..Default::default()
+            },
+            final_output_json_schema: None,
+        })
+        .await?;
+    let override_settings = wait_for_event_match(&test.codex, |ev| match ev {
+        EventMsg::TurnSettings(settings) => Some(settings.clone()),
+        _ => None,
+    })
+    .await;
+    wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+    assert_eq!(
+        override_settings,
+        TurnSettingsEvent {
+            turn_id: override_settings.turn_id.clone(),
+            model: "gpt-5.1-codex-max".to_string(),
+            effort: Some(ReasoningEffort::High),
+            approval_policy: test.config.permissions.approval_policy.value(),
+            sandbox_policy: test.config.permissions.sandbox_policy.get().clone(),
+            cwd: test.config.cwd.clone(),
+            turn_only: true,
+        }
+    );
+
+    test.codex
+        .submit(Op::UserInput {
+            items: user_input("back to the session model"),
+            final_output_json_schema: None,
+        })
+        .await?;
+    let restored_settings = wait_for_event_match(&test.codex, |ev| match ev {
+        EventMsg::TurnSettings(settings) => Some(settings.clone()),
+        _ => None,
+    })
+    .await;
+    wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+    assert_eq!(
+        (
+            restored_settings.model.as_str(),
+            restored_settings.turn_only
+        ),
+        ("gpt-5.2-codex", false)
+    );
+
+    let models: Vec<Value> = resp_mock
+        .requests()
+        .iter()
+        .map(|request| request.body_json()["model"].clone())
+        .collect();
+    assert_eq!(
+        models,
+        vec![json!("gpt-5.1-codex-max"), json!("gpt-5.2-codex")]
+    );
+    assert_eq!(
+        resp_mock.requests()[0].body_json()["reasoning"]["effort"],
+        json!("high")
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn turn_override_rejects_unsupported_reasoning_effort() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = MockServer::start().await;
+    let mut builder = test_codex().with_model("gpt-5.1-codex-mini");
+    let test = builder.build(&server).await?;
+
+    test.codex
+        .submit(Op::UserInputWithOverrides {
+            items: vec![UserInput::Text {
+                text: "think less".into(),
+                text_elements: Vec::new(),
+            }],
+            overrides: TurnOverrides {
+                effort: Some(ReasoningEffort::Low),
+                ..Default::default()
+            },
+            final_output_json_schema: None,
+        })
+        .await?;
+    let error = wait_for_event_match(&test.codex, |ev| match ev {
+        EventMsg::Error(error) => Some(error.message.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(
+        error,
+        "reasoning effort `low` is not supported by model `gpt-5.1-codex-mini` (supported: medium, high)"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn user_turn_keeps_sending_the_requested_reasoning_effort() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = MockServer::start().await;
+    let resp_mock = mount_sse_sequence(&server, vec![sse_completed("resp-1")]).await;
+    let mut builder = test_codex().with_model("gpt-5.1-codex-mini");
+    let test = builder.build(&server).await?;
+
+    // Only one-turn overrides are checked against the model; `UserTurn` callers resend the effort
+    // they last used on every turn.
+    test.codex
+        .submit(Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: "think less".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+            cwd: test.cwd_path().to_path_buf(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            model: test.session_configured.model.clone(),
+            effort: Some(ReasoningEffort::Low),
+            summary: ReasoningSummary::Auto,
+            collaboration_mode: None,
+            personality: None,
+        })
+        .await?;
+    wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    assert_eq!(
+        resp_mock.single_request().body_json()["reasoning"]["effort"],
+        json!("low")
+    );
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn model_and_personality_change_only_appends_model_instructions() -> Result<()> {
     skip_if_no_network!(Ok(()));
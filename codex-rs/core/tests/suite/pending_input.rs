@@ -1,5 +1,7 @@
+use codex_core::CodexThread;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
+use codex_core::protocol::QueuedUserInput;
 use codex_protocol::user_input::UserInput;
 use core_test_support::responses;
 use core_test_support::responses::ev_completed;
@@ -10,6 +12,7 @@ use core_test_support::streaming_sse::StreamingSseChunk;
 use core_test_support::streaming_sse::start_streaming_sse_server;
 use core_test_support::test_codex::test_codex;
 use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
 use pretty_assertions::assert_eq;
 use serde_json::Value;
 use tokio::sync::oneshot;
@@ -26,6 +29,22 @@ fn ev_message_item_done(id: &str, text: &str) -> Value {
     })
 }
 
+async fn wait_for_queue_update(codex: &CodexThread) -> Vec<QueuedUserInput> {
+    wait_for_event_match(codex, |event| match event {
+        EventMsg::UserInputQueueUpdated(update) => Some(update.queued.clone()),
+        _ => None,
+    })
+    .await
+}
+
+async fn wait_for_turn_complete(codex: &CodexThread) -> String {
+    wait_for_event_match(codex, |event| match event {
+        EventMsg::TurnComplete(complete) => Some(complete.turn_id.clone()),
+        _ => None,
+    })
+    .await
+}
+
 fn sse_event(event: Value) -> String {
     responses::sse(vec![event])
 }
@@ -114,13 +133,13 @@ async fn injected_user_input_triggers_follow_up_request_with_deltas() {
     .await;
 
     codex
-        .submit(Op::UserInput {
-            items: vec![UserInput::Text {
+        .steer_input(
+            vec![UserInput::Text {
                 text: "second prompt".into(),
                 text_elements: Vec::new(),
             }],
-            final_output_json_schema: None,
-        })
+            None,
+        )
         .await
         .unwrap();
 
@@ -144,3 +163,107 @@ async fn injected_user_input_triggers_follow_up_request_with_deltas() {
 
     server.shutdown().await;
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn user_input_during_turn_is_queued_for_next_turn() {
+    let (gate_completed_tx, gate_completed_rx) = oneshot::channel();
+
+    let first_chunks = vec![
+        StreamingSseChunk {
+            gate: None,
+            body: sse_event(ev_response_created("resp-1")),
+        },
+        StreamingSseChunk {
+            gate: None,
+            body: sse_event(ev_message_item_added("msg-1", "")),
+        },
+        StreamingSseChunk {
+            gate: None,
+            body: sse_event(ev_output_text_delta("first turn")),
+        },
+        StreamingSseChunk {
+            gate: None,
+            body: sse_event(ev_message_item_done("msg-1", "first turn")),
+        },
+        StreamingSseChunk {
+            gate: Some(gate_completed_rx),
+            body: sse_event(ev_completed("resp-1")),
+        },
+    ];
+
+    let second_chunks = vec![
+        StreamingSseChunk {
+            gate: None,
+            body: sse_event(ev_response_created("resp-2")),
+        },
+        StreamingSseChunk {
+            gate: None,
+            body: sse_event(ev_completed("resp-2")),
+        },
+    ];
+
+    let (server, _completions) =
+        start_streaming_sse_server(vec![first_chunks, second_chunks]).await;
+
+    let codex = test_codex()
+        .with_model("gpt-5.1")
+        .build_with_streaming_server(&server)
+        .await
+        .unwrap()
+        .codex;
+
+    let first_turn_id = codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "first prompt".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+
+    wait_for_event(&codex, |event| {
+        matches!(event, EventMsg::AgentMessageContentDelta(_))
+    })
+    .await;
+
+    let second_items = vec![UserInput::Text {
+        text: "second prompt".into(),
+        text_elements: Vec::new(),
+    }];
+    let second_turn_id = codex
+        .submit(Op::UserInput {
+            items: second_items.clone(),
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(
+        wait_for_queue_update(&codex).await,
+        vec![QueuedUserInput {
+            id: second_turn_id.clone(),
+            items: second_items,
+        }]
+    );
+
+    let _ = gate_completed_tx.send(());
+
+    assert_eq!(wait_for_turn_complete(&codex).await, first_turn_id);
+
+    assert_eq!(wait_for_queue_update(&codex).await, Vec::new());
+
+    assert_eq!(wait_for_turn_complete(&codex).await, second_turn_id);
+
+    let requests = server.requests().await;
+    assert_eq!(requests.len(), 2);
+    let first_body: Value = serde_json::from_slice(&requests[0]).expect("parse first request");
+    let second_body: Value = serde_json::from_slice(&requests[1]).expect("parse second request");
+    assert!(!message_input_texts(&first_body, "user").contains(&"second prompt".to_string()));
+    let second_texts = message_input_texts(&second_body, "user");
+    assert!(second_texts.iter().any(|text| text == "first prompt"));
+    assert!(second_texts.iter().any(|text| text == "second prompt"));
+
+    server.shutdown().await;
+}
@@ -0,0 +1,116 @@
+//! Verifies that facts saved with the `project_memory` tool reach later sessions in the project.
+//!
+//! Turns are submitted with `Op::UserInput` so both sessions run in the shared project directory
+//! configured as `cwd` rather than the per-session directory `submit_turn` would use.
+
+use std::sync::Arc;
+
+use codex_core::features::Feature;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_function_call_agent_response;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tempfile::TempDir;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn saved_project_memory_is_injected_into_next_session() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let home = Arc::new(TempDir::new()?);
+    let project = Arc::new(TempDir::new()?);
+
+    let server = start_mock_server().await;
+    let project_dir = project.path().to_path_buf();
+    let first = test_codex()
+        .with_home(Arc::clone(&home))
+        .with_config(move |config| {
+            config.features.enable(Feature::MemoryTool);
+            config.cwd = project_dir;
+        })
+        .build(&server)
+        .await?;
+    let call_id = "memory-save";
+    let arguments = json!({
+        "action": "save",
+        "text": "Run `just fmt`\nbefore committing.",
+    })
+    .to_string();
+    let mocks =
+        mount_function_call_agent_response(&server, call_id, &arguments, "project_memory").await;
+    first
+        .codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "remember the formatter".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+    wait_for_event(&first.codex, |event| {
+        matches!(event, EventMsg::TurnComplete(_))
+    })
+    .await;
+    assert_eq!(
+        mocks
+            .completion
+            .single_request()
+            .function_call_output_text(call_id),
+        Some("Saved project memory 1.".to_string())
+    );
+
+    let server = start_mock_server().await;
+    let project_dir = project.path().to_path_buf();
+    let second = test_codex()
+        .with_home(Arc::clone(&home))
+        .with_config(move |config| {
+            config.features.enable(Feature::MemoryTool);
+            config.cwd = project_dir;
+        })
+        .build(&server)
+        .await?;
+    let response = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_assistant_message("msg-1", "done"),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    second
+        .codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+    wait_for_event(&second.codex, |event| {
+        matches!(event, EventMsg::TurnComplete(_))
+    })
+    .await;
+
+    let developer_texts = response.single_request().message_input_texts("developer");
+    assert!(
+        developer_texts
+            .iter()
+            .any(|text| text.contains("1. Run `just fmt` before committing.")),
+        "project memory missing from developer messages: {developer_texts:?}"
+    );
+
+    Ok(())
+}
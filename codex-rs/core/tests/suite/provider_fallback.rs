@@ -0,0 +1,166 @@
+//! Verifies that a turn moves to the next configured provider when the primary is unavailable.
+
+use codex_core::ModelProviderInfo;
+use codex_core::WireApi;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::ModelProviderFallbackEvent;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::mock_provider;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+fn provider(name: &str, server: &MockServer) -> ModelProviderInfo {
+    mock_provider(name, format!("{}/v1", server.uri()), WireApi::Responses)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn turn_switches_to_fallback_provider_on_server_errors() {
+    skip_if_no_network!();
+
+    let primary = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/responses"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount(&primary)
+        .await;
+
+    let fallback = MockServer::start().await;
+    let fallback_mock = mount_sse_once(
+        &fallback,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_assistant_message("msg-1", "served by fallback"),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+
+    let primary_provider = provider("primary", &primary);
+    let fallback_provider = provider("fallback", &fallback);
+    let TestCodex { codex, .. } = test_codex()
+        .with_config(move |config| {
+            config.model_provider = primary_provider;
+            config.model_provider_fallbacks = vec![fallback_provider];
+        })
+        .build(&primary)
+        .await
+        .unwrap();
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+
+    let event = wait_for_event_match(&codex, |event| match event {
+        EventMsg::ModelProviderFallback(event) => Some(event.clone()),
+        _ => None,
+    })
+    .await;
+    let ModelProviderFallbackEvent {
+        from_provider,
+        to_provider,
+        ..
+    } = event;
+    assert_eq!(
+        (from_provider.as_str(), to_provider.as_str()),
+        ("primary", "fallback")
+    );
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TurnComplete(_))).await;
+
+    let request = fallback_mock.single_request();
+    assert!(
+        request
+            .message_input_texts("user")
+            .iter()
+            .any(|text| text == "hello")
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn fallback_provider_uses_its_own_stream_retry_budget() {
+    skip_if_no_network!();
+
+    let primary = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/responses"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount(&primary)
+        .await;
+
+    // The first fallback stream ends before `response.completed`, which is retried only when
+    // the fallback provider's own `stream_max_retries` is used.
+    let fallback = MockServer::start().await;
+    let fallback_mock = mount_sse_sequence(
+        &fallback,
+        vec![
+            sse(vec![ev_response_created("resp-1")]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_assistant_message("msg-1", "served by fallback"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    let primary_provider = provider("primary", &primary);
+    let fallback_provider = ModelProviderInfo {
+        stream_max_retries: Some(1),
+        retry_base_delay_ms: Some(1),
+        ..provider("fallback", &fallback)
+    };
+    let TestCodex { codex, .. } = test_codex()
+        .with_config(move |config| {
+            config.model_provider = primary_provider;
+            config.model_provider_fallbacks = vec![fallback_provider];
+        })
+        .build(&primary)
+        .await
+        .unwrap();
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+
+    let complete = wait_for_event_match(&codex, |event| match event {
+        EventMsg::TurnComplete(event) => Some(event.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(
+        complete.last_agent_message.as_deref(),
+        Some("served by fallback")
+    );
+    assert_eq!(fallback_mock.requests().len(), 2);
+}
@@ -11,11 +11,14 @@ use std::time::UNIX_EPOCH;
 use codex_core::CodexAuth;
 use codex_core::config::types::McpServerConfig;
 use codex_core::config::types::McpServerTransportConfig;
+use codex_core::config::types::McpToolConfig;
 use codex_core::models_manager::manager::RefreshStrategy;
 
 use codex_core::protocol::AskForApproval;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::McpInvocation;
+use codex_core::protocol::McpListChangedEvent;
+use codex_core::protocol::McpListKind;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::SandboxPolicy;
@@ -91,7 +94,7 @@ async fn stdio_server_round_trip() -> anyhow::Result<()> {
                         args: Vec::new(),
                         env: Some(HashMap::from([(
                             "MCP_TEST_VALUE".to_string(),
-                            expected_env_value.to_string(),
+                            expected_env_value.into(),
                         )])),
                         env_vars: Vec::new(),
                         cwd: None,
@@ -103,7 +106,11 @@ async fn stdio_server_round_trip() -> anyhow::Result<()> {
                     tool_timeout_sec: None,
                     enabled_tools: None,
                     disabled_tools: None,
+                    tool_renames: None,
+                    tools: None,
+                    tool_cache_ttl_sec: None,
                     scopes: None,
+                    max_concurrent_calls: None,
                 },
             );
             config
@@ -233,7 +240,7 @@ async fn stdio_image_responses_round_trip() -> anyhow::Result<()> {
                         args: Vec::new(),
                         env: Some(HashMap::from([(
                             "MCP_TEST_IMAGE_DATA_URL".to_string(),
-                            OPENAI_PNG.to_string(),
+                            OPENAI_PNG.into(),
                         )])),
                         env_vars: Vec::new(),
                         cwd: None,
@@ -245,7 +252,11 @@ async fn stdio_image_responses_round_trip() -> anyhow::Result<()> {
                     tool_timeout_sec: None,
                     enabled_tools: None,
                     disabled_tools: None,
+                    tool_renames: None,
+                    tools: None,
+                    tool_cache_ttl_sec: None,
                     scopes: None,
+                    max_concurrent_calls: None,
                 },
             );
             config
@@ -449,7 +460,7 @@ async fn stdio_image_responses_are_sanitized_for_text_only_model() -> anyhow::Re
                         args: Vec::new(),
                         env: Some(HashMap::from([(
                             "MCP_TEST_IMAGE_DATA_URL".to_string(),
-                            OPENAI_PNG.to_string(),
+                            OPENAI_PNG.into(),
                         )])),
                         env_vars: Vec::new(),
                         cwd: None,
@@ -461,7 +472,11 @@ async fn stdio_image_responses_are_sanitized_for_text_only_model() -> anyhow::Re
                     tool_timeout_sec: None,
                     enabled_tools: None,
                     disabled_tools: None,
+                    tool_renames: None,
+                    tools: None,
+                    tool_cache_ttl_sec: None,
                     scopes: None,
+                    max_concurrent_calls: None,
                 },
             );
             config
@@ -526,6 +541,588 @@ async fn stdio_image_responses_are_sanitized_for_text_only_model() -> anyhow::Re
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[serial(mcp_test_value)]
+async fn attached_mcp_resource_is_sent_with_user_message() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+    let mock = mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_response_created("resp-1"),
+            responses::ev_assistant_message("msg-1", "Read the note."),
+            responses::ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+
+    let server_name = "rmcp";
+    let rmcp_test_server_bin = stdio_server_bin()?;
+    let fixture = test_codex()
+        .with_config(move |config| {
+            let mut servers = config.mcp_servers.get().clone();
+            servers.insert(
+                server_name.to_string(),
+                McpServerConfig {
+                    transport: McpServerTransportConfig::Stdio {
+                        command: rmcp_test_server_bin,
+                        args: Vec::new(),
+                        env: None,
+                        env_vars: Vec::new(),
+                        cwd: None,
+                    },
+                    enabled: true,
+                    required: false,
+                    disabled_reason: None,
+                    startup_timeout_sec: Some(Duration::from_secs(10)),
+                    tool_timeout_sec: None,
+                    enabled_tools: None,
+                    disabled_tools: None,
+                    tool_renames: None,
+                    tools: None,
+                    tool_cache_ttl_sec: None,
+                    scopes: None,
+                    max_concurrent_calls: None,
+                },
+            );
+            config
+                .mcp_servers
+                .set(servers)
+                .expect("test mcp servers should accept any configuration");
+        })
+        .build(&server)
+        .await?;
+
+    fixture
+        .codex
+        .submit(Op::UserInput {
+            items: vec![
+                UserInput::Text {
+                    text: "summarize the attached note".into(),
+                    text_elements: Vec::new(),
+                },
+                UserInput::Mention {
+                    name: "example-note".into(),
+                    path: codex_core::mcp::mcp_resource_mention_path(
+                        server_name,
+                        "memo://codex/example-note",
+                    ),
+                },
+            ],
+            final_output_json_schema: None,
+        })
+        .await?;
+    wait_for_event(&fixture.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    let user_texts = mock.single_request().message_input_texts("user");
+    let resource_text = user_texts
+        .iter()
+        .find(|text| text.starts_with("<mcp_resource>"))
+        .expect("attached resource should be recorded as a user message");
+    assert!(resource_text.contains("<uri>memo://codex/example-note</uri>"));
+    assert!(
+        resource_text.contains("This is a sample MCP resource served by the rmcp test server.")
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[serial(mcp_test_value)]
+async fn stdio_server_prompts_are_listed_and_expanded() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+    let server_name = "rmcp";
+    let rmcp_test_server_bin = stdio_server_bin()?;
+    let fixture = test_codex()
+        .with_config(move |config| {
+            let mut servers = config.mcp_servers.get().clone();
+            servers.insert(
+                server_name.to_string(),
+                McpServerConfig {
+                    transport: McpServerTransportConfig::Stdio {
+                        command: rmcp_test_server_bin,
+                        args: Vec::new(),
+                        env: None,
+                        env_vars: Vec::new(),
+                        cwd: None,
+                    },
+                    enabled: true,
+                    required: false,
+                    disabled_reason: None,
+                    startup_timeout_sec: Some(Duration::from_secs(10)),
+                    tool_timeout_sec: None,
+                    enabled_tools: None,
+                    disabled_tools: None,
+                    tool_renames: None,
+                    tools: None,
+                    tool_cache_ttl_sec: None,
+                    scopes: None,
+                    max_concurrent_calls: None,
+                },
+            );
+            config
+                .mcp_servers
+                .set(servers)
+                .expect("test mcp servers should accept any configuration");
+        })
+        .build(&server)
+        .await?;
+
+    fixture.codex.submit(Op::ListMcpPrompts).await?;
+    let listed = wait_for_event(&fixture.codex, |ev| {
+        matches!(ev, EventMsg::ListMcpPromptsResponse(_))
+    })
+    .await;
+    let EventMsg::ListMcpPromptsResponse(listed) = listed else {
+        unreachable!("event guard guarantees ListMcpPromptsResponse");
+    };
+    let prompts = listed
+        .prompts
+        .get(server_name)
+        .expect("prompts from the rmcp test server");
+    assert_eq!(prompts.len(), 1);
+    assert_eq!(prompts[0].name, "summarize-memo");
+    let arguments = prompts[0].arguments.as_deref().unwrap_or_default();
+    assert_eq!(arguments.len(), 1);
+    assert_eq!(arguments[0].name, "audience");
+    assert_eq!(arguments[0].required, Some(true));
+
+    fixture
+        .codex
+        .submit(Op::GetMcpPrompt {
+            server: server_name.to_string(),
+            name: "summarize-memo".to_string(),
+            arguments: HashMap::from([("audience".to_string(), "reviewers".to_string())]),
+        })
+        .await?;
+    let expanded = wait_for_event(&fixture.codex, |ev| {
+        matches!(ev, EventMsg::GetMcpPromptResponse(_))
+    })
+    .await;
+    let EventMsg::GetMcpPromptResponse(expanded) = expanded else {
+        unreachable!("event guard guarantees GetMcpPromptResponse");
+    };
+    assert_eq!(
+        expanded.result,
+        Ok("Summarize memo://codex/example-note for reviewers.".to_string())
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[serial(mcp_test_value)]
+async fn stdio_server_tool_list_changes_reach_the_next_turn() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+    let server_name = "rmcp";
+    let mock = responses::mount_sse_sequence(
+        &server,
+        vec![
+            responses::sse(vec![
+                responses::ev_response_created("resp-1"),
+                responses::ev_function_call(
+                    "call-add",
+                    &format!("mcp__{server_name}__add_tool"),
+                    "{}",
+                ),
+                responses::ev_completed("resp-1"),
+            ]),
+            responses::sse(vec![
+                responses::ev_assistant_message("msg-1", "Added a tool."),
+                responses::ev_completed("resp-2"),
+            ]),
+            responses::sse(vec![
+                responses::ev_assistant_message("msg-2", "Done."),
+                responses::ev_completed("resp-3"),
+            ]),
+        ],
+    )
+    .await;
+
+    let rmcp_test_server_bin = stdio_server_bin()?;
+    let fixture = test_codex()
+        .with_config(move |config| {
+            let mut servers = config.mcp_servers.get().clone();
+            servers.insert(
+                server_name.to_string(),
+                McpServerConfig {
+                    transport: McpServerTransportConfig::Stdio {
+                        command: rmcp_test_server_bin,
+                        args: Vec::new(),
+                        env: None,
+                        env_vars: Vec::new(),
+                        cwd: None,
+                    },
+                    enabled: true,
+                    required: false,
+                    disabled_reason: None,
+                    startup_timeout_sec: Some(Duration::from_secs(10)),
+                    tool_timeout_sec: None,
+                    enabled_tools: None,
+                    disabled_tools: None,
+                    tool_renames: None,
+                    tools: None,
+                    tool_cache_ttl_sec: None,
+                    scopes: None,
+                    max_concurrent_calls: None,
+                },
+            );
+            config
+                .mcp_servers
+                .set(servers)
+                .expect("test mcp servers should accept any configuration");
+        })
+        .build(&server)
+        .await?;
+
+    let session_model = fixture.session_configured.model.clone();
+    fixture
+        .codex
+        .submit(Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: "add a tool".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+            cwd: fixture.cwd.path().to_path_buf(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            model: session_model,
+            effort: None,
+            summary: ReasoningSummary::Auto,
+            collaboration_mode: None,
+            personality: None,
+        })
+        .await?;
+    // The tools are listed again in the background, so the turn may end first.
+    let mut tools_changed = false;
+    let mut turn_complete = false;
+    wait_for_event(&fixture.codex, |ev| {
+        match ev {
+            EventMsg::McpListChanged(McpListChangedEvent {
+                list: McpListKind::Tools,
+                ..
+            }) => tools_changed = true,
+            EventMsg::TurnComplete(_) => turn_complete = true,
+            _ => {}
+        }
+        tools_changed && turn_complete
+    })
+    .await;
+    fixture.submit_turn("use the new tool").await?;
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 3);
+    let has_added_tool = |request: &responses::ResponsesRequest| {
+        request.body_json()["tools"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .any(|tool| tool["name"] == format!("mcp__{server_name}__added"))
+    };
+    assert!(!has_added_tool(&requests[0]));
+    assert!(has_added_tool(&requests[2]));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[serial(mcp_test_value)]
+async fn stdio_server_sampling_uses_session_model() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+    let call_id = "call-sample";
+    let server_name = "rmcp";
+    let tool_name = format!("mcp__{server_name}__sample");
+    let mock = responses::mount_sse_sequence(
+        &server,
+        vec![
+            responses::sse(vec![
+                responses::ev_response_created("resp-1"),
+                responses::ev_function_call(
+                    call_id,
+                    &tool_name,
+                    "{\"prompt\":\"What is the capital of France?\"}",
+                ),
+                responses::ev_completed("resp-1"),
+            ]),
+            responses::sse(vec![
+                responses::ev_response_created("resp-2"),
+                responses::ev_assistant_message("msg-sample", "Paris."),
+                responses::ev_completed("resp-2"),
+            ]),
+            responses::sse(vec![
+                responses::ev_assistant_message("msg-1", "The server says Paris."),
+                responses::ev_completed("resp-3"),
+            ]),
+        ],
+    )
+    .await;
+
+    let rmcp_test_server_bin = stdio_server_bin()?;
+    let fixture = test_codex()
+        .with_config(move |config| {
+            let mut servers = config.mcp_servers.get().clone();
+            servers.insert(
+                server_name.to_string(),
+                McpServerConfig {
+                    transport: McpServerTransportConfig::Stdio {
+                        command: rmcp_test_server_bin,
+                        args: Vec::new(),
+                        env: None,
+                        env_vars: Vec::new(),
+                        cwd: None,
+                    },
+                    enabled: true,
+                    required: false,
+                    disabled_reason: None,
+                    startup_timeout_sec: Some(Duration::from_secs(10)),
+                    tool_timeout_sec: None,
+                    enabled_tools: None,
+                    disabled_tools: None,
+                    tool_renames: None,
+                    tools: None,
+                    tool_cache_ttl_sec: None,
+                    scopes: None,
+                    max_concurrent_calls: None,
+                },
+            );
+            config
+                .mcp_servers
+                .set(servers)
+                .expect("test mcp servers should accept any configuration");
+        })
+        .build(&server)
+        .await?;
+    let session_model = fixture.session_configured.model.clone();
+
+    fixture
+        .codex
+        .submit(Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: "ask the rmcp server to sample".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+            cwd: fixture.cwd.path().to_path_buf(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            model: session_model,
+            effort: None,
+            summary: ReasoningSummary::Auto,
+            collaboration_mode: None,
+            personality: None,
+        })
+        .await?;
+    wait_for_event(&fixture.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(
+        requests[1].message_input_texts("user"),
+        vec!["What is the capital of France?".to_string()]
+    );
+    let output = requests[2]
+        .function_call_output_text(call_id)
+        .expect("sample tool output");
+    assert!(
+        output.contains("SAMPLED: Paris."),
+        "unexpected sample tool output: {output}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[serial(mcp_test_value)]
+async fn stdio_server_roots_are_the_session_cwd() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+    let call_id = "call-roots";
+    let server_name = "rmcp";
+    let tool_name = format!("mcp__{server_name}__roots");
+    let mock = responses::mount_sse_sequence(
+        &server,
+        vec![
+            responses::sse(vec![
+                responses::ev_response_created("resp-1"),
+                responses::ev_function_call(call_id, &tool_name, "{}"),
+                responses::ev_completed("resp-1"),
+            ]),
+            responses::sse(vec![
+                responses::ev_assistant_message("msg-1", "The server sees the workspace."),
+                responses::ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    let rmcp_test_server_bin = stdio_server_bin()?;
+    let fixture = test_codex()
+        .with_config(move |config| {
+            let mut servers = config.mcp_servers.get().clone();
+            servers.insert(
+                server_name.to_string(),
+                McpServerConfig {
+                    transport: McpServerTransportConfig::Stdio {
+                        command: rmcp_test_server_bin,
+                        args: Vec::new(),
+                        env: None,
+                        env_vars: Vec::new(),
+                        cwd: None,
+                    },
+                    enabled: true,
+                    required: false,
+                    disabled_reason: None,
+                    startup_timeout_sec: Some(Duration::from_secs(10)),
+                    tool_timeout_sec: None,
+                    enabled_tools: None,
+                    disabled_tools: None,
+                    tool_renames: None,
+                    tools: None,
+                    tool_cache_ttl_sec: None,
+                    scopes: None,
+                    max_concurrent_calls: None,
+                },
+            );
+            config
+                .mcp_servers
+                .set(servers)
+                .expect("test mcp servers should accept any configuration");
+        })
+        .build(&server)
+        .await?;
+
+    fixture
+        .submit_turn("ask the rmcp server for its roots")
+        .await?;
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 2);
+    let output = requests[1]
+        .function_call_output_text(call_id)
+        .expect("roots tool output");
+    let roots: Value = serde_json::from_str(&output)?;
+    let cwd_uri = url::Url::from_file_path(fixture.cwd.path()).expect("cwd is absolute");
+    assert_eq!(
+        roots.pointer("/roots/0/uri").and_then(Value::as_str),
+        Some(cwd_uri.as_str())
+    );
+
+    Ok(())
+}
+
+/// Has the model call the test server's `slow` tool, whose first two calls never return, with a
+/// 500ms timeout and `retries` retries. Returns the tool output sent back to the model.
+async fn slow_tool_output(retries: u32) -> anyhow::Result<String> {
+    let server = responses::start_mock_server().await;
+    let call_id = "call-slow";
+    let server_name = "rmcp";
+    let tool_name = format!("mcp__{server_name}__slow");
+    let mock = responses::mount_sse_sequence(
+        &server,
+        vec![
+            responses::sse(vec![
+                responses::ev_response_created("resp-1"),
+                responses::ev_function_call(call_id, &tool_name, "{\"hang_calls\":2}"),
+                responses::ev_completed("resp-1"),
+            ]),
+            responses::sse(vec![
+                responses::ev_assistant_message("msg-1", "The slow tool finished."),
+                responses::ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    let rmcp_test_server_bin = stdio_server_bin()?;
+    let fixture = test_codex()
+        .with_config(move |config| {
+            let mut servers = config.mcp_servers.get().clone();
+            servers.insert(
+                server_name.to_string(),
+                McpServerConfig {
+                    transport: McpServerTransportConfig::Stdio {
+                        command: rmcp_test_server_bin,
+                        args: Vec::new(),
+                        env: None,
+                        env_vars: Vec::new(),
+                        cwd: None,
+                    },
+                    enabled: true,
+                    required: false,
+                    disabled_reason: None,
+                    startup_timeout_sec: Some(Duration::from_secs(10)),
+                    tool_timeout_sec: None,
+                    enabled_tools: None,
+                    disabled_tools: None,
+                    tool_renames: None,
+                    tools: Some(HashMap::from([(
+                        "slow".to_string(),
+                        McpToolConfig {
+                            timeout_sec: Some(Duration::from_millis(500)),
+                            retries: Some(retries),
+                            cache_ttl_sec: None,
+                        },
+                    )])),
+                    tool_cache_ttl_sec: None,
+                    scopes: None,
+                    max_concurrent_calls: None,
+                },
+            );
+            config
+                .mcp_servers
+                .set(servers)
+                .expect("test mcp servers should accept any configuration");
+        })
+        .build(&server)
+        .await?;
+
+    fixture.submit_turn("call the slow rmcp tool").await?;
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 2);
+    Ok(requests[1]
+        .function_call_output_text(call_id)
+        .expect("slow tool output"))
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[serial(mcp_test_value)]
+async fn stdio_tool_calls_that_time_out_are_retried() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    // Two timed-out calls and two retries: the third call answers.
+    let output = slow_tool_output(2).await?;
+    assert!(
+        output.contains("slow call 3"),
+        "unexpected output: {output}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[serial(mcp_test_value)]
+async fn stdio_tool_calls_fail_once_retries_are_used_up() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let output = slow_tool_output(1).await?;
+    assert!(
+        output.contains("timed out awaiting tools/call after 500ms"),
+        "unexpected output: {output}"
+    );
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 #[serial(mcp_test_value)]
 async fn stdio_server_propagates_whitelisted_env_vars() -> anyhow::Result<()> {
@@ -579,7 +1176,11 @@ async fn stdio_server_propagates_whitelisted_env_vars() -> anyhow::Result<()> {
                     tool_timeout_sec: None,
                     enabled_tools: None,
                     disabled_tools: None,
+                    tool_renames: None,
+                    tools: None,
+                    tool_cache_ttl_sec: None,
                     scopes: None,
+                    max_concurrent_calls: None,
                 },
             );
             config
@@ -738,7 +1339,11 @@ async fn streamable_http_tool_call_round_trip() -> anyhow::Result<()> {
                     tool_timeout_sec: None,
                     enabled_tools: None,
                     disabled_tools: None,
+                    tool_renames: None,
+                    tools: None,
+                    tool_cache_ttl_sec: None,
                     scopes: None,
+                    max_concurrent_calls: None,
                 },
             );
             config
@@ -929,7 +1534,11 @@ async fn streamable_http_with_oauth_round_trip() -> anyhow::Result<()> {
                     tool_timeout_sec: None,
                     enabled_tools: None,
                     disabled_tools: None,
+                    tool_renames: None,
+                    tools: None,
+                    tool_cache_ttl_sec: None,
                     scopes: None,
+                    max_concurrent_calls: None,
                 },
             );
             config
@@ -132,7 +132,11 @@ fn rmcp_server_config(command: String) -> McpServerConfig {
         tool_timeout_sec: None,
         enabled_tools: None,
         disabled_tools: None,
+        tool_renames: None,
+        tools: None,
+        tool_cache_ttl_sec: None,
         scopes: None,
+        max_concurrent_calls: None,
     }
 }
 
@@ -0,0 +1,62 @@
+use anyhow::Result;
+use codex_core::features::Feature;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::skip_if_windows;
+use core_test_support::test_codex::test_codex;
+use serde_json::json;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn shell_output_masks_configured_secret_patterns() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+    skip_if_windows!(Ok(()));
+
+    let server = start_mock_server().await;
+    let call_id = "print-secret";
+    let args = json!({
+        "command": "echo token=hunter2-1234",
+        "login": false,
+    });
+    let response = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_function_call(call_id, "shell_command", &args.to_string()),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_assistant_message("msg-1", "done"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+    let test = test_codex()
+        .with_model("gpt-5-codex")
+        .with_config(|config| {
+            config.features.disable(Feature::UnifiedExec);
+            config.redaction.patterns = vec![r"hunter2-[0-9]+".to_string()];
+        })
+        .build(&server)
+        .await?;
+
+    test.submit_turn("print the token").await?;
+
+    let output = response.requests()[1]
+        .function_call_output_text(call_id)
+        .expect("shell_command output");
+    assert!(
+        output.contains("token=[REDACTED_SECRET]") && !output.contains("hunter2"),
+        "secret was not masked: {output}"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use anyhow::Result;
+use codex_core::features::Feature;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::skip_if_sandbox;
+use core_test_support::skip_if_windows;
+use core_test_support::test_codex::test_codex;
+use serde_json::json;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn shell_command_tty_keeps_process_for_write_stdin() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+    skip_if_sandbox!(Ok(()));
+    skip_if_windows!(Ok(()));
+
+    let server = start_mock_server().await;
+    let open_call_id = "tty-open";
+    let stdin_call_id = "tty-stdin";
+    let open_args = json!({
+        "command": "read -r line; echo \"got:$line\"",
+        "login": false,
+        "tty": true,
+        "timeout_ms": 300,
+    });
+    let stdin_args = json!({
+        "session_id": 1000,
+        "chars": "hello\n",
+        "yield_time_ms": 2000,
+    });
+    let response = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_function_call(open_call_id, "shell_command", &open_args.to_string()),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_function_call(stdin_call_id, "write_stdin", &stdin_args.to_string()),
+                ev_completed("resp-2"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-3"),
+                ev_assistant_message("msg-1", "done"),
+                ev_completed("resp-3"),
+            ]),
+        ],
+    )
+    .await;
+    let test = test_codex()
+        .with_model("gpt-5-codex")
+        .with_config(|config| {
+            config.features.enable(Feature::ShellTty);
+            config.features.disable(Feature::UnifiedExec);
+        })
+        .build(&server)
+        .await?;
+
+    test.submit_turn("answer the prompt").await?;
+
+    let requests = response.requests();
+    let open_output = requests[1]
+        .function_call_output_text(open_call_id)
+        .expect("shell_command output");
+    assert!(
+        open_output.contains("Process running with session ID 1000"),
+        "unexpected shell_command output: {open_output}"
+    );
+    let stdin_output = requests[2]
+        .function_call_output_text(stdin_call_id)
+        .expect("write_stdin output");
+    assert!(
+        stdin_output.contains("Process exited with code 0") && stdin_output.contains("got:hello"),
+        "unexpected write_stdin output: {stdin_output}"
+    );
+
+    Ok(())
+}
@@ -68,6 +68,7 @@ async fn continue_after_stream_error() {
         base_url: Some(format!("{}/v1", server.uri())),
         env_key: Some("PATH".into()),
         env_key_instructions: None,
+        api_key_secret: None,
         experimental_bearer_token: None,
         wire_api: WireApi::Responses,
         query_params: None,
@@ -76,8 +77,12 @@ async fn continue_after_stream_error() {
         request_max_retries: Some(1),
         stream_max_retries: Some(1),
         stream_idle_timeout_ms: Some(2_000),
+        retry_base_delay_ms: None,
+        retry_max_delay_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        azure: None,
+        tls: None,
     };
 
     let TestCodex { codex, .. } = test_codex()
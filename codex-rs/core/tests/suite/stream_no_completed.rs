@@ -75,6 +75,7 @@ async fn retries_on_early_close() {
         // provider is not set.
         env_key: Some("PATH".into()),
         env_key_instructions: None,
+        api_key_secret: None,
         experimental_bearer_token: None,
         wire_api: WireApi::Responses,
         query_params: None,
@@ -84,8 +85,12 @@ async fn retries_on_early_close() {
         request_max_retries: Some(0),
         stream_max_retries: Some(1),
         stream_idle_timeout_ms: Some(2000),
+        retry_base_delay_ms: None,
+        retry_max_delay_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        azure: None,
+        tls: None,
     };
 
     let TestCodex { codex, .. } = test_codex()
@@ -0,0 +1,112 @@
+//! Verifies that rate-limited requests are retried after the delay the provider asks for.
+
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::StreamRetryEvent;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_response_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::sse_response;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn rate_limited_request_is_retried_after_retry_after() {
+    skip_if_no_network!();
+
+    let server = MockServer::start().await;
+    let responses = mount_response_sequence(
+        &server,
+        vec![
+            ResponseTemplate::new(429).insert_header("retry-after-ms", "10"),
+            sse_response(sse(vec![
+                ev_response_created("resp-1"),
+                ev_assistant_message("msg-1", "done"),
+                ev_completed("resp-1"),
+            ])),
+        ],
+    )
+    .await;
+
+    let TestCodex { codex, .. } = test_codex()
+        .with_config(|config| {
+            config.model_provider.request_max_retries = Some(0);
+            config.model_provider.stream_max_retries = Some(2);
+        })
+        .build(&server)
+        .await
+        .unwrap();
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+
+    let retry = wait_for_event_match(&codex, |event| match event {
+        EventMsg::StreamRetry(event) => Some(event.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(
+        retry,
+        StreamRetryEvent {
+            attempt: 1,
+            max_attempts: 2,
+            delay_ms: 10,
+            reason: "exceeded retry limit, last status: 429 Too Many Requests".to_string(),
+        }
+    );
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TurnComplete(_))).await;
+    assert_eq!(responses.requests().len(), 2);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn retry_after_longer_than_max_delay_fails_turn() {
+    skip_if_no_network!();
+
+    let server = MockServer::start().await;
+    let responses = mount_response_sequence(
+        &server,
+        vec![ResponseTemplate::new(429).insert_header("retry-after", "120")],
+    )
+    .await;
+
+    let TestCodex { codex, .. } = test_codex()
+        .with_config(|config| {
+            config.model_provider.request_max_retries = Some(0);
+            config.model_provider.stream_max_retries = Some(2);
+            config.model_provider.retry_max_delay_ms = Some(30_000);
+        })
+        .build(&server)
+        .await
+        .unwrap();
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+
+    wait_for_event(&codex, |event| matches!(event, EventMsg::Error(_))).await;
+    assert_eq!(responses.requests().len(), 1);
+}
@@ -0,0 +1,103 @@
+//! Verifies prompt-based tool calling for models without native tool support.
+
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn emulated_tool_calls_run_tools_and_replay_results_as_text() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let plan_call = json!({
+        "name": "update_plan",
+        "arguments": {"plan": [{"step": "Inspect workspace", "status": "in_progress"}]},
+    });
+    let responses = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_assistant_message(
+                    "msg-1",
+                    &format!("Updating the plan.\n<tool_call>\n{plan_call}\n</tool_call>\n"),
+                ),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_assistant_message("msg-2", "done"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    let TestCodex { codex, .. } = test_codex()
+        .with_config(|config| {
+            config.model_supports_tool_calls = Some(false);
+        })
+        .build(&server)
+        .await?;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "plan it".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+
+    let plan = wait_for_event(&codex, |event| matches!(event, EventMsg::PlanUpdate(_))).await;
+    let EventMsg::PlanUpdate(plan) = plan else {
+        unreachable!("wait_for_event returned a non-plan event");
+    };
+    assert_eq!(plan.plan[0].step, "Inspect workspace");
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TurnComplete(_))).await;
+
+    let requests = responses.requests();
+    assert_eq!(requests.len(), 2);
+    let first = requests[0].body_json();
+    assert_eq!(first.get("tools"), Some(&json!([])));
+    assert!(
+        requests[0]
+            .instructions_text()
+            .contains("\"name\":\"update_plan\"")
+    );
+
+    let follow_up = &requests[1];
+    assert_eq!(
+        follow_up.inputs_of_type("function_call"),
+        Vec::<Value>::new()
+    );
+    let replayed_call = json!(format!("<tool_call>\n{plan_call}\n</tool_call>"));
+    assert!(
+        follow_up
+            .inputs_of_type("message")
+            .iter()
+            .any(|item| item["role"] == "assistant" && item["content"][0]["text"] == replayed_call)
+    );
+    assert!(
+        follow_up
+            .message_input_texts("user")
+            .iter()
+            .any(|text| text.starts_with("<tool_result>\n"))
+    );
+
+    Ok(())
+}
@@ -370,7 +370,11 @@ async fn mcp_tool_call_output_exceeds_limit_truncated_for_model() -> Result<()>
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
         );
         config
@@ -452,7 +456,7 @@ async fn mcp_image_output_preserves_image_and_no_text_summary() -> Result<()> {
                     args: Vec::new(),
                     env: Some(HashMap::from([(
                         "MCP_TEST_IMAGE_DATA_URL".to_string(),
-                        openai_png.to_string(),
+                        openai_png.into(),
                     )])),
                     env_vars: Vec::new(),
                     cwd: None,
@@ -464,7 +468,11 @@ async fn mcp_image_output_preserves_image_and_no_text_summary() -> Result<()> {
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
         );
         config
@@ -729,7 +737,11 @@ async fn mcp_tool_call_output_not_truncated_with_custom_limit() -> Result<()> {
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                tool_renames: None,
+                tools: None,
+                tool_cache_ttl_sec: None,
                 scopes: None,
+                max_concurrent_calls: None,
             },
         );
         config
@@ -10,9 +10,13 @@ use anyhow::Result;
 use anyhow::bail;
 use codex_core::CodexThread;
 use codex_core::features::Feature;
+use codex_core::protocol::AskForApproval;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
+use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::UndoCompletedEvent;
+use codex_protocol::config_types::ReasoningSummary;
+use codex_protocol::user_input::UserInput;
 use core_test_support::responses::ev_apply_patch_function_call;
 use core_test_support::responses::ev_assistant_message;
 use core_test_support::responses::ev_completed;
@@ -22,6 +26,7 @@ use core_test_support::responses::sse;
 use core_test_support::skip_if_no_network;
 use core_test_support::test_codex::TestCodexHarness;
 use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
 use core_test_support::wait_for_event_match;
 use pretty_assertions::assert_eq;
 
@@ -108,6 +113,62 @@ async fn run_apply_patch_turn(
     harness.submit(prompt).await
 }
 
+/// Runs an apply_patch turn and returns the checkpoint taken before it.
+async fn run_checkpointed_apply_patch_turn(
+    harness: &TestCodexHarness,
+    prompt: &str,
+    call_id: &str,
+    patch: &str,
+) -> Result<String> {
+    mount_sse_sequence(
+        harness.server(),
+        apply_patch_responses(call_id, patch, "ok"),
+    )
+    .await;
+    let test = harness.test();
+    test.codex
+        .submit(Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: prompt.into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+            cwd: test.cwd_path().to_path_buf(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::DangerFullAccess,
+            model: test.session_configured.model.clone(),
+            effort: None,
+            summary: ReasoningSummary::Auto,
+            collaboration_mode: None,
+            personality: None,
+        })
+        .await?;
+    let checkpoint_id = wait_for_event_match(&test.codex, |msg| match msg {
+        EventMsg::CheckpointCreated(event) => Some(event.checkpoint_id.clone()),
+        _ => None,
+    })
+    .await;
+    wait_for_event(&test.codex, |msg| matches!(msg, EventMsg::TurnComplete(_))).await;
+    Ok(checkpoint_id)
+}
+
+async fn revert_to_checkpoint(
+    codex: &Arc<CodexThread>,
+    checkpoint_id: &str,
+) -> Result<UndoCompletedEvent> {
+    codex
+        .submit(Op::RevertToCheckpoint {
+            checkpoint_id: checkpoint_id.to_string(),
+        })
+        .await?;
+    let event = wait_for_event_match(codex, |msg| match msg {
+        EventMsg::UndoCompleted(done) => Some(done.clone()),
+        _ => None,
+    })
+    .await;
+    Ok(event)
+}
+
 async fn invoke_undo(codex: &Arc<CodexThread>) -> Result<UndoCompletedEvent> {
     codex.submit(Op::Undo).await?;
     let event = wait_for_event_match(codex, |msg| match msg {
@@ -376,6 +437,62 @@ async fn undo_sequential_turns_consumes_snapshots() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn revert_to_checkpoint_restores_earlier_turn_and_drops_later_checkpoints() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = undo_harness().await?;
+    init_git_repo(harness.cwd())?;
+
+    let story = harness.path("story.txt");
+    fs::write(&story, "initial\n")?;
+    git(harness.cwd(), &["add", "story.txt"])?;
+    git(harness.cwd(), &["commit", "-m", "seed story"])?;
+
+    run_checkpointed_apply_patch_turn(
+        &harness,
+        "first change",
+        "checkpoint-turn-1",
+        "*** Begin Patch\n*** Update File: story.txt\n@@\n-initial\n+turn one\n*** End Patch",
+    )
+    .await?;
+    let second = run_checkpointed_apply_patch_turn(
+        &harness,
+        "second change",
+        "checkpoint-turn-2",
+        "*** Begin Patch\n*** Update File: story.txt\n@@\n-turn one\n+turn two\n*** End Patch",
+    )
+    .await?;
+    let third = run_checkpointed_apply_patch_turn(
+        &harness,
+        "third change",
+        "checkpoint-turn-3",
+        "*** Begin Patch\n*** Update File: story.txt\n@@\n-turn two\n+turn three\n*** End Patch",
+    )
+    .await?;
+    assert_eq!(fs::read_to_string(&story)?, "turn three\n");
+
+    let codex = Arc::clone(&harness.test().codex);
+    let reverted = revert_to_checkpoint(&codex, &second).await?;
+    assert!(reverted.success, "revert failed: {:?}", reverted.message);
+    assert_eq!(fs::read_to_string(&story)?, "turn one\n");
+
+    let missing = revert_to_checkpoint(&codex, &third).await?;
+    assert_eq!(
+        missing,
+        UndoCompletedEvent {
+            success: false,
+            message: Some(format!("Checkpoint {third} not found.")),
+        }
+    );
+
+    expect_successful_undo(&codex).await?;
+    assert_eq!(fs::read_to_string(&story)?, "initial\n");
+    expect_failed_undo(&codex).await?;
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn undo_without_snapshot_reports_failure() -> Result<()> {
     skip_if_no_network!(Ok(()));
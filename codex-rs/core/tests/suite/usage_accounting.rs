@@ -0,0 +1,142 @@
+//! Verifies per-turn and per-session usage reporting with configured model pricing.
+
+use codex_core::config::types::ModelPricing;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::TokenUsage;
+use codex_core::protocol::UsageReport;
+use codex_core::protocol::UsageSummary;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use wiremock::MockServer;
+
+fn ev_completed_with_usage(id: &str, input: i64, cached: i64, output: i64) -> Value {
+    json!({
+        "type": "response.completed",
+        "response": {
+            "id": id,
+            "usage": {
+                "input_tokens": input,
+                "input_tokens_details": {"cached_tokens": cached},
+                "output_tokens": output,
+                "output_tokens_details": null,
+                "total_tokens": input + output
+            }
+        }
+    })
+}
+
+fn token_usage(input: i64, cached: i64, output: i64) -> TokenUsage {
+    TokenUsage {
+        input_tokens: input,
+        cached_input_tokens: cached,
+        output_tokens: output,
+        reasoning_output_tokens: 0,
+        total_tokens: input + output,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn turn_complete_and_get_usage_report_costs() {
+    skip_if_no_network!();
+
+    let server = MockServer::start().await;
+    mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_assistant_message("msg-1", "first"),
+                ev_completed_with_usage("resp-1", 1_000, 0, 100),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_assistant_message("msg-2", "second"),
+                ev_completed_with_usage("resp-2", 1_000, 500, 0),
+            ]),
+        ],
+    )
+    .await;
+
+    let TestCodex { codex, .. } = test_codex()
+        .with_model("gpt-5.1")
+        .with_config(|config| {
+            config.model_pricing.insert(
+                "gpt-5.1".to_string(),
+                ModelPricing {
+                    input: 2_000.0,
+                    cached_input: Some(500.0),
+                    output: 10_000.0,
+                },
+            );
+        })
+        .build(&server)
+        .await
+        .unwrap();
+
+    let mut turn_usage = Vec::new();
+    for text in ["one", "two"] {
+        codex
+            .submit(Op::UserInput {
+                items: vec![UserInput::Text {
+                    text: text.into(),
+                    text_elements: Vec::new(),
+                }],
+                final_output_json_schema: None,
+            })
+            .await
+            .unwrap();
+        turn_usage.push(
+            wait_for_event_match(&codex, |event| match event {
+                EventMsg::TurnComplete(event) => Some(event.usage.clone()),
+                _ => None,
+            })
+            .await,
+        );
+    }
+
+    let expected = UsageReport {
+        turn: UsageSummary {
+            token_usage: token_usage(1_000, 500, 0),
+            cost_usd: Some(1.25),
+        },
+        session: UsageSummary {
+            token_usage: token_usage(2_000, 500, 100),
+            cost_usd: Some(4.25),
+        },
+    };
+    assert_eq!(
+        turn_usage,
+        vec![
+            Some(UsageReport {
+                turn: UsageSummary {
+                    token_usage: token_usage(1_000, 0, 100),
+                    cost_usd: Some(3.0),
+                },
+                session: UsageSummary {
+                    token_usage: token_usage(1_000, 0, 100),
+                    cost_usd: Some(3.0),
+                },
+            }),
+            Some(expected.clone()),
+        ]
+    );
+
+    codex.submit(Op::GetUsage).await.unwrap();
+    let reported = wait_for_event_match(&codex, |event| match event {
+        EventMsg::GetUsageResponse(event) => Some(event.usage.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(reported, expected);
+}
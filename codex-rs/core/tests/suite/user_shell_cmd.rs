@@ -61,15 +61,17 @@ async fn user_shell_cmd_ls_and_cat_in_temp_dir() {
         .unwrap();
     let msg = wait_for_event(&codex, |ev| matches!(ev, EventMsg::ExecCommandEnd(_))).await;
     let EventMsg::ExecCommandEnd(ExecCommandEndEvent {
-        stdout, exit_code, ..
+        aggregated_output,
+        exit_code,
+        ..
     }) = msg
     else {
         unreachable!()
     };
     assert_eq!(exit_code, 0);
     assert!(
-        stdout.contains(file_name),
-        "ls output should include {file_name}, got: {stdout:?}"
+        aggregated_output.contains(file_name),
+        "ls output should include {file_name}, got: {aggregated_output:?}"
     );
 
     // 2) shell command should print the file contents verbatim
@@ -80,7 +82,7 @@ async fn user_shell_cmd_ls_and_cat_in_temp_dir() {
         .unwrap();
     let msg = wait_for_event(&codex, |ev| matches!(ev, EventMsg::ExecCommandEnd(_))).await;
     let EventMsg::ExecCommandEnd(ExecCommandEndEvent {
-        mut stdout,
+        mut aggregated_output,
         exit_code,
         ..
     }) = msg
@@ -90,9 +92,9 @@ async fn user_shell_cmd_ls_and_cat_in_temp_dir() {
     assert_eq!(exit_code, 0);
     if cfg!(windows) {
         // Windows shells emit CRLF line endings; normalize so the assertion remains portable.
-        stdout = stdout.replace("\r\n", "\n");
+        aggregated_output = aggregated_output.replace("\r\n", "\n");
     }
-    assert_eq!(stdout, contents);
+    assert_eq!(aggregated_output, contents);
 }
 
 #[tokio::test]
@@ -295,7 +297,7 @@ async fn user_shell_command_history_is_persisted_and_shared_with_model() -> anyh
     })
     .await;
     assert_eq!(end_event.exit_code, 0);
-    assert_eq!(end_event.stdout.trim(), "not-set");
+    assert_eq!(end_event.aggregated_output.trim(), "not-set");
 
     let _ = wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
 
@@ -0,0 +1,144 @@
+use codex_core::features::Feature;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+use wiremock::matchers::query_param;
+
+fn tool_call_turn(call_id: &str, name: &str, arguments: &serde_json::Value) -> Vec<String> {
+    vec![
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_function_call(call_id, name, &arguments.to_string()),
+            ev_completed("resp-1"),
+        ]),
+        sse(vec![
+            ev_response_created("resp-2"),
+            ev_assistant_message("msg-1", "done"),
+            ev_completed("resp-2"),
+        ]),
+    ]
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn fetch_url_returns_page_text() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/docs/install"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(
+            "<html><head><title>Install</title></head><body><h1>Install</h1><p>Run <code>make</code>.</p></body></html>",
+            "text/html; charset=utf-8",
+        ))
+        .mount(&server)
+        .await;
+    let call_id = "fetch-1";
+    let url = format!("{}/docs/install", server.uri());
+    let response = mount_sse_sequence(
+        &server,
+        tool_call_turn(call_id, "fetch_url", &json!({ "url": url })),
+    )
+    .await;
+    let test = test_codex()
+        .with_config(|config| {
+            config.features.enable(Feature::WebTools);
+        })
+        .build(&server)
+        .await?;
+
+    test.submit_turn("read the install docs").await?;
+
+    let requests = response.requests();
+    assert_eq!(
+        requests[1].function_call_output_text(call_id),
+        Some("Install\n\nRun make.".to_string())
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn fetch_url_rejects_domains_outside_allowlist() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let call_id = "fetch-1";
+    let url = format!("{}/secret", server.uri());
+    let response = mount_sse_sequence(
+        &server,
+        tool_call_turn(call_id, "fetch_url", &json!({ "url": url })),
+    )
+    .await;
+    let test = test_codex()
+        .with_config(|config| {
+            config.features.enable(Feature::WebTools);
+            config.web_tools.allowed_domains = vec!["docs.rs".to_string()];
+        })
+        .build(&server)
+        .await?;
+
+    test.submit_turn("read the secret").await?;
+
+    let requests = response.requests();
+    assert_eq!(
+        requests[1].function_call_output_text(call_id),
+        Some("127.0.0.1 is not in web_tools.allowed_domains".to_string())
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn web_search_lists_results_from_search_page() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/search"))
+        .and(query_param("q", "serde derive"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(
+            r#"<html><body>
+<a class="result__a" href="https://serde.rs/derive.html">Using derive - <b>Serde</b></a>
+<a class="result__a" href="/search?q=serde+derive&amp;page=2">Next</a>
+</body></html>"#,
+            "text/html",
+        ))
+        .mount(&server)
+        .await;
+    let call_id = "search-1";
+    let response = mount_sse_sequence(
+        &server,
+        tool_call_turn(call_id, "web_search", &json!({ "query": "serde derive" })),
+    )
+    .await;
+    let search_url = format!("{}/search?q={{query}}", server.uri());
+    let test = test_codex()
+        .with_config(move |config| {
+            config.features.enable(Feature::WebTools);
+            config.web_tools.search_url = search_url;
+        })
+        .build(&server)
+        .await?;
+
+    test.submit_turn("how do I derive serde traits").await?;
+
+    let requests = response.requests();
+    assert_eq!(
+        requests[1].function_call_output_text(call_id),
+        Some("1. Using derive - Serde\n   https://serde.rs/derive.html".to_string())
+    );
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use codex_core::features::Feature;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::WorktreeCreatedEvent;
+use codex_core::protocol::WorktreeMergedEvent;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
+
+fn git_output(path: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(path)
+        .output()
+        .with_context(|| format!("failed to run git {args:?}"))?;
+    if !output.status.success() {
+        let exit_status = output.status;
+        bail!("git {args:?} exited with {exit_status}");
+    }
+    Ok(String::from_utf8(output.stdout)
+        .context("stdout was not valid utf8")?
+        .trim()
+        .to_string())
+}
+
+fn init_git_repo(path: &Path) -> Result<()> {
+    git_output(path, &["init", "--initial-branch=main"])?;
+    git_output(path, &["config", "core.autocrlf", "false"])?;
+    git_output(path, &["config", "user.name", "Codex Tests"])?;
+    git_output(path, &["config", "user.email", "codex-tests@example.com"])?;
+    fs::write(path.join("README.txt"), "original\n")?;
+    git_output(path, &["add", "README.txt"])?;
+    git_output(path, &["commit", "-m", "Add README.txt"])?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn worktree_session_isolates_edits_until_merged() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let test = test_codex()
+        .with_config(|config| {
+            config.features.enable(Feature::Worktree);
+            init_git_repo(&config.cwd).expect("init git repo");
+        })
+        .build(&server)
+        .await?;
+    let repo_root = git_output(test.cwd_path(), &["rev-parse", "--show-toplevel"])?;
+    let thread_id = test.session_configured.session_id;
+
+    let created = wait_for_event_match(&test.codex, |event| match event {
+        EventMsg::WorktreeCreated(event) => Some(event.clone()),
+        _ => None,
+    })
+    .await;
+    let worktree_path = test
+        .config
+        .codex_home
+        .join("worktrees")
+        .join(thread_id.to_string());
+    assert_eq!(
+        created,
+        WorktreeCreatedEvent {
+            path: worktree_path.clone(),
+            branch: format!("codex/{thread_id}"),
+            repo_root: repo_root.clone().into(),
+        }
+    );
+    assert_eq!(test.session_configured.cwd, worktree_path);
+
+    fs::write(worktree_path.join("README.txt"), "edited by the agent\n")?;
+    assert_eq!(
+        fs::read_to_string(test.cwd_path().join("README.txt"))?,
+        "original\n"
+    );
+
+    test.codex.submit(Op::MergeWorktree).await?;
+    let merged = wait_for_event_match(&test.codex, |event| match event {
+        EventMsg::WorktreeMerged(event) => Some(event.clone()),
+        _ => None,
+    })
+    .await;
+
+    assert_eq!(
+        merged,
+        WorktreeMergedEvent {
+            branch: format!("codex/{thread_id}"),
+            commit: Some(git_output(test.cwd_path(), &["rev-parse", "HEAD"])?),
+        }
+    );
+    assert_eq!(
+        fs::read_to_string(test.cwd_path().join("README.txt"))?,
+        "edited by the agent\n"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn merge_worktree_without_worktree_reports_error() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let test = test_codex().build(&server).await?;
+
+    test.codex.submit(Op::MergeWorktree).await?;
+    let message = wait_for_event_match(&test.codex, |event| match event {
+        EventMsg::Error(event) => Some(event.message.clone()),
+        _ => None,
+    })
+    .await;
+
+    assert_eq!(
+        message,
+        "This session is not running in a worktree; enable the `worktree` feature to isolate sessions."
+    );
+
+    Ok(())
+}
@@ -103,6 +103,7 @@ impl AppServerClient {
                 capabilities: Some(InitializeCapabilities {
                     experimental_api: true,
                     opt_out_notification_methods: None,
+                    protocol_version: None,
                 }),
             },
         };
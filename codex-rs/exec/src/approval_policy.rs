@@ -0,0 +1,330 @@
+//! Approval requests answered from a policy file (`--approval-policy-file`).
+//!
+//! Nobody is around to answer approval requests in a headless run. A policy file lists rules that
+//! approve or deny them instead, so the agent can still ask to run a command outside the sandbox
+//! or to edit a file, and gets an answer straight away:
+//!
+//! ```toml
+//! default = "deny"
+//!
+//! [[rules]]
+//! command = "cargo test*"
+//! decision = "approve"
+//!
+//! [[rules]]
+//! path = "docs/*"
+//! decision = "approve"
+//! ```
+//!
+//! The first rule that matches decides; requests no rule matches get `default`. A `bash -lc`
+//! script is approved only when every command in it is, and a script that cannot be split into
+//! plain commands is denied. In a path pattern `*` stays within one directory and `**` crosses
+//! directories.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::bail;
+use codex_protocol::protocol::ReviewDecision;
+use codex_shell_command::bash::extract_bash_command;
+use codex_shell_command::bash::parse_shell_lc_plain_commands;
+use globset::GlobBuilder;
+use globset::GlobMatcher;
+use serde::Deserialize;
+use shlex::try_join;
+use wildmatch::WildMatch;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum PolicyDecision {
+    Approve,
+    Deny,
+}
+
+impl PolicyDecision {
+    pub(crate) fn review_decision(self) -> ReviewDecision {
+        match self {
+            PolicyDecision::Approve => ReviewDecision::Approved,
+            PolicyDecision::Deny => ReviewDecision::Denied,
+        }
+    }
+
+    pub(crate) fn describe(self) -> &'static str {
+        match self {
+            PolicyDecision::Approve => "approved",
+            PolicyDecision::Deny => "denied",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PolicyFileToml {
+    default: PolicyDecision,
+    #[serde(default)]
+    rules: Vec<RuleToml>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleToml {
+    /// Pattern for the command line; `*` matches any text.
+    command: Option<String>,
+    /// Pattern for a file the agent wants to edit, relative to the working directory; `*` does
+    /// not match `/`, `**` does.
+    path: Option<String>,
+    decision: PolicyDecision,
+}
+
+#[derive(Debug)]
+enum Matcher {
+    Command(WildMatch),
+    Path(GlobMatcher),
+}
+
+#[derive(Debug)]
+struct Rule {
+    matcher: Matcher,
+    decision: PolicyDecision,
+}
+
+#[derive(Debug)]
+pub(crate) struct ApprovalPolicyFile {
+    default: PolicyDecision,
+    rules: Vec<Rule>,
+}
+
+impl ApprovalPolicyFile {
+    pub(crate) fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read approval policy {}", path.display()))?;
+        Self::parse(&contents)
+            .with_context(|| format!("invalid approval policy {}", path.display()))
+    }
+
+    fn parse(contents: &str) -> anyhow::Result<Self> {
+        let file: PolicyFileToml = toml::from_str(contents)?;
+        let mut rules = Vec::with_capacity(file.rules.len());
+        for (index, rule) in file.rules.into_iter().enumerate() {
+            let matcher = match (rule.command, rule.path) {
+                (Some(command), None) => Matcher::Command(WildMatch::new(&command)),
+                (None, Some(path)) => Matcher::Path(
+                    GlobBuilder::new(&path)
+                        .literal_separator(true)
+                        .build()
+                        .with_context(|| format!("rule {} has an invalid path pattern", index + 1))?
+                        .compile_matcher(),
+                ),
+                _ => bail!(
+                    "rule {} must set exactly one of `command` or `path`",
+                    index + 1
+                ),
+            };
+            rules.push(Rule {
+                matcher,
+                decision: rule.decision,
+            });
+        }
+        Ok(Self {
+            default: file.default,
+            rules,
+        })
+    }
+
+    /// Decides a request to run `command`. A `bash -lc` script is approved only if every command
+    /// in it is, so `cargo *` does not approve `cargo test && rm -rf ~`. Scripts that cannot be
+    /// split into plain commands are denied, since nobody is around to look at them.
+    pub(crate) fn decide_command(&self, command: &[String]) -> PolicyDecision {
+        if extract_bash_command(command).is_none() {
+            return self.decide_command_line(command);
+        }
+        let Some(commands) = parse_shell_lc_plain_commands(command) else {
+            return PolicyDecision::Deny;
+        };
+        if !commands.is_empty()
+            && commands
+                .iter()
+                .all(|command| self.decide_command_line(command) == PolicyDecision::Approve)
+        {
+            PolicyDecision::Approve
+        } else {
+            PolicyDecision::Deny
+        }
+    }
+
+    fn decide_command_line(&self, command: &[String]) -> PolicyDecision {
+        let command_line =
+            try_join(command.iter().map(String::as_str)).unwrap_or_else(|_| command.join(" "));
+        self.rules
+            .iter()
+            .find_map(|rule| match &rule.matcher {
+                Matcher::Command(pattern) if pattern.matches(&command_line) => Some(rule.decision),
+                _ => None,
+            })
+            .unwrap_or(self.default)
+    }
+
+    /// Decides a request to edit `paths`: approved only if every path is.
+    pub(crate) fn decide_patch<'a>(
+        &self,
+        paths: impl IntoIterator<Item = &'a PathBuf>,
+        cwd: &Path,
+    ) -> PolicyDecision {
+        let all_approved = paths.into_iter().all(|path| {
+            let relative = path.strip_prefix(cwd).unwrap_or(path);
+            let decision = self
+                .rules
+                .iter()
+                .find_map(|rule| match &rule.matcher {
+                    Matcher::Path(pattern) if pattern.is_match(relative) => Some(rule.decision),
+                    _ => None,
+                })
+                .unwrap_or(self.default);
+            decision == PolicyDecision::Approve
+        });
+        if all_approved {
+            PolicyDecision::Approve
+        } else {
+            PolicyDecision::Deny
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn command(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn first_matching_rule_decides() -> anyhow::Result<()> {
+        let policy = ApprovalPolicyFile::parse(
+            r#"
+default = "deny"
+
+[[rules]]
+command = "cargo test --release*"
+decision = "deny"
+
+[[rules]]
+command = "cargo *"
+decision = "approve"
+
+[[rules]]
+path = "docs/*"
+decision = "approve"
+"#,
+        )?;
+
+        assert_eq!(
+            policy.decide_command(&command(&["bash", "-lc", "cargo test -p codex-exec"])),
+            PolicyDecision::Approve
+        );
+        assert_eq!(
+            policy.decide_command(&command(&["cargo", "test", "--release"])),
+            PolicyDecision::Deny
+        );
+        assert_eq!(
+            policy.decide_command(&command(&["rm", "-rf", "target"])),
+            PolicyDecision::Deny
+        );
+
+        let cwd = Path::new("/repo");
+        assert_eq!(
+            policy.decide_patch(&[PathBuf::from("/repo/docs/exec.md")], cwd),
+            PolicyDecision::Approve
+        );
+        assert_eq!(
+            policy.decide_patch(
+                &[
+                    PathBuf::from("/repo/docs/exec.md"),
+                    PathBuf::from("/repo/src/lib.rs"),
+                ],
+                cwd
+            ),
+            PolicyDecision::Deny
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn scripts_are_approved_only_when_every_command_is() -> anyhow::Result<()> {
+        let policy = ApprovalPolicyFile::parse(
+            r#"
+default = "deny"
+
+[[rules]]
+command = "cargo *"
+decision = "approve"
+"#,
+        )?;
+
+        assert_eq!(
+            policy.decide_command(&command(&["bash", "-lc", "cargo fmt && cargo test"])),
+            PolicyDecision::Approve
+        );
+        assert_eq!(
+            policy.decide_command(&command(&["bash", "-lc", "cargo test && rm -rf ~"])),
+            PolicyDecision::Deny
+        );
+        assert_eq!(
+            policy.decide_command(&command(&["bash", "-lc", "cargo test $(rm -rf ~)"])),
+            PolicyDecision::Deny
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn path_star_does_not_cross_directories() -> anyhow::Result<()> {
+        let policy = ApprovalPolicyFile::parse(
+            r#"
+default = "deny"
+
+[[rules]]
+path = "src/*.rs"
+decision = "approve"
+
+[[rules]]
+path = "docs/**"
+decision = "approve"
+"#,
+        )?;
+
+        let cwd = Path::new("/repo");
+        assert_eq!(
+            policy.decide_patch(&[PathBuf::from("/repo/src/lib.rs")], cwd),
+            PolicyDecision::Approve
+        );
+        assert_eq!(
+            policy.decide_patch(&[PathBuf::from("/repo/src/nested/evil.rs")], cwd),
+            PolicyDecision::Deny
+        );
+        assert_eq!(
+            policy.decide_patch(&[PathBuf::from("/repo/docs/guides/exec.md")], cwd),
+            PolicyDecision::Approve
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_rules_without_exactly_one_pattern() {
+        let err = ApprovalPolicyFile::parse(
+            r#"
+default = "approve"
+
+[[rules]]
+decision = "deny"
+"#,
+        )
+        .expect_err("rule has no pattern");
+        assert_eq!(
+            err.to_string(),
+            "rule 1 must set exactly one of `command` or `path`"
+        );
+        assert!(ApprovalPolicyFile::parse("rules = []").is_err());
+    }
+}
@@ -0,0 +1,230 @@
+//! Files, directories and stdin attached to the first turn with `--attach`.
+//!
+//! Each attached file becomes its own text input ahead of the prompt, tagged with where it came
+//! from, so a log or source file can be handed to the agent without quoting it into the prompt.
+//! Files larger than `file_excerpt_threshold_bytes` are attached as an excerpt the agent can read
+//! more of (see [`codex_core::file_excerpt`]).
+
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::bail;
+use codex_core::file_excerpt::excerpt_file;
+use codex_core::file_excerpt::needs_excerpt;
+use codex_protocol::user_input::UserInput;
+use ignore::WalkBuilder;
+
+use crate::decode_prompt_bytes;
+
+/// `--attach` value that reads stdin.
+const STDIN_ATTACHMENT: &str = "-";
+
+/// Upper bound for everything attached to a turn, so a stray directory does not fill the context
+/// window.
+const MAX_ATTACHMENT_BYTES: usize = 1024 * 1024;
+
+const ATTACHMENT_OPEN_TAG: &str = "<attachment>";
+const ATTACHMENT_CLOSE_TAG: &str = "</attachment>";
+
+/// Whether one of the `--attach` values reads stdin.
+pub(crate) fn attaches_stdin(attach: &[PathBuf]) -> bool {
+    attach
+        .iter()
+        .any(|path| path.as_os_str() == STDIN_ATTACHMENT)
+}
+
+/// An attachment read from disk or stdin.
+pub(crate) struct Attachment {
+    source: String,
+    /// Absolute path of an attached file, which lets it be excerpted; `None` for stdin.
+    path: Option<PathBuf>,
+    contents: String,
+}
+
+/// Reads every `--attach` value. Directories are walked recursively, skipping hidden files, files
+/// ignored by git and files that are not text.
+pub(crate) fn load_attachments(attach: &[PathBuf]) -> anyhow::Result<Vec<Attachment>> {
+    let mut attachments = Vec::new();
+    for path in attach {
+        if path.as_os_str() == STDIN_ATTACHMENT {
+            let mut bytes = Vec::new();
+            std::io::stdin()
+                .read_to_end(&mut bytes)
+                .context("failed to read the stdin attachment")?;
+            let contents = decode_prompt_bytes(&bytes)
+                .map_err(|err| anyhow::anyhow!("failed to read the stdin attachment: {err}"))?;
+            attachments.push(Attachment {
+                source: "stdin".to_string(),
+                path: None,
+                contents,
+            });
+        } else if path.is_dir() {
+            for file in walk_dir(path) {
+                // Binary files cannot be attached as text; a directory usually has a few.
+                if let Ok(contents) = std::fs::read_to_string(&file) {
+                    attachments.push(Attachment::file(&file, contents));
+                }
+            }
+        } else {
+            let contents = std::fs::read_to_string(path)
+                .with_context(|| format!("failed to attach {}", path.display()))?;
+            attachments.push(Attachment::file(path, contents));
+        }
+    }
+    Ok(attachments)
+}
+
+/// Turns attachments into text inputs, excerpting files larger than `excerpt_threshold_bytes`.
+pub(crate) fn attachment_inputs(
+    attachments: Vec<Attachment>,
+    excerpt_threshold_bytes: usize,
+) -> anyhow::Result<Vec<UserInput>> {
+    let texts: Vec<String> = attachments
+        .into_iter()
+        .map(|attachment| match &attachment.path {
+            Some(path) if needs_excerpt(&attachment.contents, excerpt_threshold_bytes) => {
+                format_attachment(
+                    &attachment.source,
+                    &excerpt_file(path, &attachment.contents, &[]),
+                )
+            }
+            _ => format_attachment(&attachment.source, &attachment.contents),
+        })
+        .collect();
+
+    let total_bytes: usize = texts.iter().map(String::len).sum();
+    if total_bytes > MAX_ATTACHMENT_BYTES {
+        bail!(
+            "attachments add up to {total_bytes} bytes, more than the {MAX_ATTACHMENT_BYTES} allowed; attach fewer files"
+        );
+    }
+    Ok(texts
+        .into_iter()
+        .map(|text| UserInput::Text {
+            text,
+            text_elements: Vec::new(),
+        })
+        .collect())
+}
+
+impl Attachment {
+    fn file(path: &Path, contents: String) -> Self {
+        Self {
+            source: path.display().to_string(),
+            path: std::path::absolute(path).ok(),
+            contents,
+        }
+    }
+}
+
+fn walk_dir(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = WalkBuilder::new(dir)
+        .build()
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_file())
+        })
+        .map(ignore::DirEntry::into_path)
+        .collect();
+    files.sort();
+    files
+}
+
+fn format_attachment(source: &str, contents: &str) -> String {
+    let contents = contents.trim_end();
+    format!("{ATTACHMENT_OPEN_TAG}\n<source>{source}</source>\n{contents}\n{ATTACHMENT_CLOSE_TAG}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::file_excerpt::DEFAULT_FILE_EXCERPT_THRESHOLD_BYTES;
+    use codex_core::file_excerpt::is_file_excerpt;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn texts(attachments: Vec<UserInput>) -> Vec<String> {
+        attachments
+            .into_iter()
+            .filter_map(|input| match input {
+                UserInput::Text { text, .. } => Some(text),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn attaches_files_and_text_files_in_directories() -> anyhow::Result<()> {
+        let dir = TempDir::new()?;
+        let log = dir.path().join("test.log");
+        std::fs::write(&log, "FAILED tests::it_works\n")?;
+        let src = dir.path().join("src");
+        std::fs::create_dir(&src)?;
+        std::fs::write(src.join("b.rs"), "fn b() {}\n")?;
+        std::fs::write(src.join("a.rs"), "fn a() {}\n")?;
+        std::fs::write(src.join("logo.png"), [0x89, 0x50, 0x4e, 0x47, 0xff, 0xfe])?;
+
+        let attachments = texts(attachment_inputs(
+            load_attachments(&[log.clone(), src.clone()])?,
+            DEFAULT_FILE_EXCERPT_THRESHOLD_BYTES,
+        )?);
+
+        assert_eq!(
+            attachments,
+            vec![
+                format!(
+                    "<attachment>\n<source>{}</source>\nFAILED tests::it_works\n</attachment>",
+                    log.display()
+                ),
+                format!(
+                    "<attachment>\n<source>{}</source>\nfn a() {{}}\n</attachment>",
+                    src.join("a.rs").display()
+                ),
+                format!(
+                    "<attachment>\n<source>{}</source>\nfn b() {{}}\n</attachment>",
+                    src.join("b.rs").display()
+                ),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_missing_files_and_oversized_attachments() -> anyhow::Result<()> {
+        let dir = TempDir::new()?;
+        let missing = dir.path().join("missing.log");
+        assert!(load_attachments(&[missing]).is_err());
+
+        let large = dir.path().join("large.log");
+        std::fs::write(&large, "x".repeat(MAX_ATTACHMENT_BYTES + 1))?;
+        let err = attachment_inputs(load_attachments(&[large])?, usize::MAX)
+            .expect_err("attachment is too large");
+        assert!(err.to_string().contains("attach fewer files"), "{err}");
+        Ok(())
+    }
+
+    #[test]
+    fn large_files_are_attached_as_excerpts() -> anyhow::Result<()> {
+        let dir = TempDir::new()?;
+        let source = dir.path().join("big.rs");
+        let contents: String = (1..=5_000).map(|n| format!("fn f{n}() {{}}\n")).collect();
+        std::fs::write(&source, &contents)?;
+
+        let attachments = texts(attachment_inputs(
+            load_attachments(std::slice::from_ref(&source))?,
+            1_000,
+        )?);
+
+        assert_eq!(attachments.len(), 1);
+        assert!(attachments[0].len() < contents.len());
+        assert!(is_file_excerpt(&attachments[0]));
+        assert!(attachments[0].contains(&format!("<path>{}</path>", source.display())));
+        assert!(attachments[0].contains("L40: fn f40() {}"));
+        assert!(attachments[0].contains("... 4800 more definitions"));
+        Ok(())
+    }
+}
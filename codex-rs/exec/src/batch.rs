@@ -0,0 +1,494 @@
+//! `codex exec --tasks <FILE>`: runs every prompt listed in a tasks file and reports on all of them.
+//!
+//! Each task runs as its own `codex exec --output-format json` child process, so tasks get
+//! separate sessions and can run side by side without sharing any state. The `run.completed`
+//! event each child ends with is collected into a single report.
+
+use std::collections::HashSet;
+use std::ffi::OsString;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::bail;
+use clap::ValueEnum;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::process::Command;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::cli::Cli;
+use crate::cli::OutputFormat;
+use crate::exec_events::AgentMessageItem;
+use crate::exec_events::ItemCompletedEvent;
+use crate::exec_events::RunCompletedEvent;
+use crate::exec_events::RunStatus;
+use crate::exec_events::ThreadEvent;
+use crate::exec_events::ThreadItemDetails;
+use crate::exec_events::Usage;
+
+/// Contents of the file passed to `--tasks`.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+struct TasksFile {
+    tasks: Vec<BatchTask>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+struct BatchTask {
+    /// Shown in the report; defaults to `task-<n>`.
+    #[serde(default)]
+    name: Option<String>,
+    prompt: String,
+    /// Directory to run in, relative to the tasks file; defaults to the `--cd` directory.
+    #[serde(default)]
+    cwd: Option<PathBuf>,
+    /// Branch to switch `cwd` to before the task runs.
+    #[serde(default)]
+    branch: Option<String>,
+}
+
+/// A task from the file with its defaults filled in.
+#[derive(Debug, Clone, PartialEq)]
+struct ResolvedTask {
+    name: String,
+    prompt: String,
+    cwd: PathBuf,
+    branch: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+struct TaskReport {
+    name: String,
+    cwd: PathBuf,
+    branch: Option<String>,
+    status: RunStatus,
+    exit_code: i32,
+    thread_id: Option<String>,
+    files_changed: Vec<String>,
+    usage: Usage,
+    last_message: Option<String>,
+    /// Why the task failed when it did not get to report on itself.
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct BatchReport {
+    tasks: Vec<TaskReport>,
+}
+
+pub(crate) async fn run_batch(tasks_path: &Path, cli: &Cli) -> anyhow::Result<()> {
+    if cli.command.is_some() {
+        bail!("--tasks cannot be combined with `resume` or `review`");
+    }
+    let contents = std::fs::read_to_string(tasks_path)
+        .with_context(|| format!("failed to read tasks file {}", tasks_path.display()))?;
+    let default_cwd = match &cli.cwd {
+        Some(cwd) => std::path::absolute(cwd)?,
+        None => std::env::current_dir()?,
+    };
+    let tasks_dir = std::path::absolute(tasks_path)?
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| default_cwd.clone());
+    let parallel = cli.parallel.max(1);
+    let tasks = parse_tasks(&contents, &tasks_dir, &default_cwd, parallel)
+        .with_context(|| format!("invalid tasks file {}", tasks_path.display()))?;
+
+    let shared_args = Arc::new(child_args(cli)?);
+    let semaphore = Arc::new(Semaphore::new(parallel));
+    let total = tasks.len();
+    let mut running = JoinSet::new();
+    for (index, task) in tasks.into_iter().enumerate() {
+        let shared_args = Arc::clone(&shared_args);
+        let semaphore = Arc::clone(&semaphore);
+        running.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let report = run_task(&task, &shared_args).await;
+            eprintln!(
+                "[{}/{total}] {}: {}",
+                index + 1,
+                report.name,
+                status_label(report.status)
+            );
+            (index, report)
+        });
+    }
+
+    let mut reports: Vec<Option<TaskReport>> = vec![None; total];
+    while let Some(joined) = running.join_next().await {
+        let (index, report) = joined?;
+        reports[index] = Some(report);
+    }
+    let reports: Vec<TaskReport> = reports.into_iter().flatten().collect();
+    let any_failed = reports
+        .iter()
+        .any(|report| report.status == RunStatus::Failed);
+
+    let json = cli.json || cli.output_format == OutputFormat::Json;
+    print_report(BatchReport { tasks: reports }, json)?;
+    if any_failed {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn parse_tasks(
+    contents: &str,
+    tasks_dir: &Path,
+    default_cwd: &Path,
+    parallel: usize,
+) -> anyhow::Result<Vec<ResolvedTask>> {
+    let file: TasksFile = serde_yaml::from_str(contents)?;
+    if file.tasks.is_empty() {
+        bail!("no tasks are listed");
+    }
+
+    let mut names = HashSet::new();
+    let mut switched_dirs = HashSet::new();
+    let mut tasks = Vec::with_capacity(file.tasks.len());
+    for (index, task) in file.tasks.into_iter().enumerate() {
+        let name = task.name.unwrap_or_else(|| format!("task-{}", index + 1));
+        if task.prompt.trim().is_empty() {
+            bail!("task `{name}` has an empty prompt");
+        }
+        if !names.insert(name.clone()) {
+            bail!("more than one task is named `{name}`");
+        }
+        let cwd = match task.cwd {
+            Some(cwd) => tasks_dir.join(cwd),
+            None => default_cwd.to_path_buf(),
+        };
+        // Tasks running side by side cannot check out different branches of one checkout.
+        if task.branch.is_some() && parallel > 1 && !switched_dirs.insert(cwd.clone()) {
+            bail!(
+                "task `{name}` switches branches in {}, which another task also does; use a separate checkout or --parallel 1",
+                cwd.display()
+            );
+        }
+        tasks.push(ResolvedTask {
+            name,
+            prompt: task.prompt,
+            cwd,
+            branch: task.branch,
+        });
+    }
+    Ok(tasks)
+}
+
+/// Flags every task's `codex exec` is started with, taken from the batch invocation.
+fn child_args(cli: &Cli) -> anyhow::Result<Vec<OsString>> {
+    let mut args: Vec<OsString> = vec!["--output-format".into(), "json".into()];
+    for raw_override in &cli.config_overrides.raw_overrides {
+        args.extend(["-c".into(), raw_override.into()]);
+    }
+    if let Some(model) = &cli.model {
+        args.extend(["--model".into(), model.into()]);
+    }
+    if let Some(preset) = &cli.model_preset {
+        args.extend(["--preset".into(), preset.into()]);
+    }
+    if cli.oss {
+        args.push("--oss".into());
+    }
+    if let Some(provider) = &cli.oss_provider {
+        args.extend(["--local-provider".into(), provider.into()]);
+    }
+    if let Some(sandbox_mode) = cli
+        .sandbox_mode
+        .and_then(|sandbox_mode| sandbox_mode.to_possible_value())
+    {
+        args.extend(["--sandbox".into(), sandbox_mode.get_name().into()]);
+    }
+    if let Some(profile) = &cli.config_profile {
+        args.extend(["--profile".into(), profile.into()]);
+    }
+    if cli.full_auto {
+        args.push("--full-auto".into());
+    }
+    if cli.dangerously_bypass_approvals_and_sandbox {
+        args.push("--dangerously-bypass-approvals-and-sandbox".into());
+    }
+    if cli.skip_git_repo_check {
+        args.push("--skip-git-repo-check".into());
+    }
+    if cli.ephemeral {
+        args.push("--ephemeral".into());
+    }
+    // Tasks run in other directories, so paths are made absolute first.
+    for dir in &cli.add_dir {
+        args.extend(["--add-dir".into(), std::path::absolute(dir)?.into()]);
+    }
+    for image in &cli.images {
+        args.extend(["--image".into(), std::path::absolute(image)?.into()]);
+    }
+    if let Some(policy) = &cli.approval_policy_file {
+        args.extend([
+            "--approval-policy-file".into(),
+            std::path::absolute(policy)?.into(),
+        ]);
+    }
+    if let Some(schema) = &cli.output_schema {
+        args.extend([
+            "--output-schema".into(),
+            std::path::absolute(schema)?.into(),
+        ]);
+    }
+    Ok(args)
+}
+
+/// The command that runs this CLI again: `codex-exec` itself, or `codex exec` when it was started
+/// from the multitool binary.
+fn exec_command() -> std::io::Result<Command> {
+    let exe = std::env::current_exe()?;
+    let mut command = Command::new(&exe);
+    if exe
+        .file_stem()
+        .is_some_and(|stem| stem.to_string_lossy() != "codex-exec")
+    {
+        command.arg("exec");
+    }
+    Ok(command)
+}
+
+async fn run_task(task: &ResolvedTask, shared_args: &[OsString]) -> TaskReport {
+    let mut report = TaskReport {
+        name: task.name.clone(),
+        cwd: task.cwd.clone(),
+        branch: task.branch.clone(),
+        status: RunStatus::Failed,
+        exit_code: 1,
+        thread_id: None,
+        files_changed: Vec::new(),
+        usage: Usage::default(),
+        last_message: None,
+        error: None,
+    };
+
+    if let Some(branch) = &task.branch
+        && let Err(err) = switch_branch(&task.cwd, branch).await
+    {
+        report.error = Some(err);
+        return report;
+    }
+
+    let output = match exec_command() {
+        Ok(mut command) => {
+            command
+                .args(shared_args)
+                .arg("--cd")
+                .arg(&task.cwd)
+                .arg("--")
+                .arg(&task.prompt)
+                .stdin(Stdio::null())
+                .output()
+                .await
+        }
+        Err(err) => Err(err),
+    };
+    let output = match output {
+        Ok(output) => output,
+        Err(err) => {
+            report.error = Some(format!("failed to start codex exec: {err}"));
+            return report;
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (run_completed, last_message) = read_task_events(&stdout);
+    report.last_message = last_message;
+    match run_completed {
+        Some(run_completed) => {
+            report.status = run_completed.status;
+            report.exit_code = run_completed.exit_code;
+            report.thread_id = run_completed.thread_id;
+            report.files_changed = run_completed.files_changed;
+            report.usage = run_completed.usage;
+        }
+        None => {
+            // The task exited before it could report on itself, e.g. because its config is
+            // invalid; the last thing it printed says why.
+            report.exit_code = output.status.code().unwrap_or(1);
+            report.error = String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .rev()
+                .find(|line| !line.trim().is_empty())
+                .map(str::to_string)
+                .or_else(|| Some(format!("codex exec exited with {}", output.status)));
+        }
+    }
+    report
+}
+
+async fn switch_branch(cwd: &Path, branch: &str) -> Result<(), String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(cwd)
+        .args(["switch", branch])
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .map_err(|err| format!("failed to run git: {err}"))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "failed to switch to branch `{branch}`: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+/// Picks the `run.completed` summary and the last agent message out of a task's JSONL output.
+fn read_task_events(stdout: &str) -> (Option<RunCompletedEvent>, Option<String>) {
+    let mut run_completed = None;
+    let mut last_message = None;
+    for event in stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<ThreadEvent>(line).ok())
+    {
+        match event {
+            ThreadEvent::RunCompleted(event) => run_completed = Some(event),
+            ThreadEvent::ItemCompleted(ItemCompletedEvent { item }) => {
+                if let ThreadItemDetails::AgentMessage(AgentMessageItem { text }) = item.details {
+                    last_message = Some(text);
+                }
+            }
+            _ => {}
+        }
+    }
+    (run_completed, last_message)
+}
+
+fn status_label(status: RunStatus) -> &'static str {
+    match status {
+        RunStatus::Completed => "completed",
+        RunStatus::Failed => "failed",
+        RunStatus::Interrupted => "interrupted",
+    }
+}
+
+#[allow(clippy::print_stdout)]
+fn print_report(report: BatchReport, json: bool) -> anyhow::Result<()> {
+    if json {
+        println!("{}", serde_json::to_string(&report)?);
+        return Ok(());
+    }
+    for task in report.tasks {
+        let branch = task
+            .branch
+            .map(|branch| format!(" ({branch})"))
+            .unwrap_or_default();
+        println!(
+            "{}: {} in {}{branch}",
+            task.name,
+            status_label(task.status),
+            task.cwd.display()
+        );
+        if !task.files_changed.is_empty() {
+            println!("  files changed: {}", task.files_changed.join(", "));
+        }
+        if let Some(error) = task.error {
+            println!("  error: {error}");
+        }
+        if let Some(message) = task.last_message {
+            for line in message.lines() {
+                println!("  | {line}");
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_tasks_relative_to_the_tasks_file() -> anyhow::Result<()> {
+        let tasks = parse_tasks(
+            r#"
+tasks:
+  - name: deps
+    prompt: Bump the dependencies.
+    cwd: repos/api
+    branch: main
+  - prompt: Fix the flaky tests.
+"#,
+            Path::new("/nightly"),
+            Path::new("/work"),
+            1,
+        )?;
+
+        assert_eq!(
+            tasks,
+            vec![
+                ResolvedTask {
+                    name: "deps".to_string(),
+                    prompt: "Bump the dependencies.".to_string(),
+                    cwd: PathBuf::from("/nightly/repos/api"),
+                    branch: Some("main".to_string()),
+                },
+                ResolvedTask {
+                    name: "task-2".to_string(),
+                    prompt: "Fix the flaky tests.".to_string(),
+                    cwd: PathBuf::from("/work"),
+                    branch: None,
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_parallel_branch_switches_in_one_checkout() {
+        let contents = r#"
+tasks:
+  - prompt: one
+    branch: main
+  - prompt: two
+    branch: release
+"#;
+        assert!(parse_tasks(contents, Path::new("/nightly"), Path::new("/work"), 1).is_ok());
+        let err = parse_tasks(contents, Path::new("/nightly"), Path::new("/work"), 2)
+            .expect_err("tasks share a checkout");
+        assert!(
+            err.to_string().contains("switches branches in /work"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn reads_summary_and_last_message_from_task_output() {
+        let stdout = [
+            r#"{"type":"thread.started","thread_id":"t1"}"#,
+            "not json",
+            r#"{"type":"item.completed","item":{"id":"item_0","type":"agent_message","text":"Done."}}"#,
+            r#"{"type":"run.completed","status":"completed","exit_code":0,"thread_id":"t1","files_changed":["a.rs"],"usage":{"input_tokens":3,"cached_input_tokens":0,"output_tokens":2}}"#,
+        ]
+        .join("\n");
+
+        let (run_completed, last_message) = read_task_events(&stdout);
+
+        assert_eq!(
+            run_completed,
+            Some(RunCompletedEvent {
+                status: RunStatus::Completed,
+                exit_code: 0,
+                thread_id: Some("t1".to_string()),
+                files_changed: vec!["a.rs".to_string()],
+                usage: Usage {
+                    input_tokens: 3,
+                    cached_input_tokens: 0,
+                    output_tokens: 2,
+                },
+            })
+        );
+        assert_eq!(last_message, Some("Done.".to_string()));
+    }
+}
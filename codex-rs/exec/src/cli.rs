@@ -22,10 +22,20 @@ pub struct Cli {
     )]
     pub images: Vec<PathBuf>,
 
+    /// File or directory to attach as context to the first turn; `-` attaches stdin. Directories
+    /// are read recursively, skipping hidden, git-ignored and non-text files.
+    #[arg(long = "attach", value_name = "PATH", action = clap::ArgAction::Append)]
+    pub attach: Vec<PathBuf>,
+
     /// Model the agent should use.
     #[arg(long, short = 'm', global = true)]
     pub model: Option<String>,
 
+    /// Model preset from `[model_presets]` in config.toml; sets the model, reasoning effort and
+    /// verbosity it defines.
+    #[arg(long = "preset", global = true)]
+    pub model_preset: Option<String>,
+
     /// Use open-source provider.
     #[arg(long = "oss", default_value_t = false)]
     pub oss: bool,
@@ -41,6 +51,7 @@ pub struct Cli {
     pub sandbox_mode: Option<codex_utils_cli::SandboxModeCliArg>,
 
     /// Configuration profile from config.toml to specify default options.
+    /// Defaults to `$CODEX_PROFILE`.
     #[arg(long = "profile", short = 'p')]
     pub config_profile: Option<String>,
 
@@ -59,6 +70,16 @@ pub struct Cli {
     )]
     pub dangerously_bypass_approvals_and_sandbox: bool,
 
+    /// TOML file of rules that approve or deny the agent's requests to run commands outside the
+    /// sandbox or to edit files, instead of refusing them all. See docs/exec.md for the format.
+    #[arg(
+        long = "approval-policy-file",
+        value_name = "FILE",
+        global = true,
+        conflicts_with = "dangerously_bypass_approvals_and_sandbox"
+    )]
+    pub approval_policy_file: Option<PathBuf>,
+
     /// Tell the agent to use the specified directory as its working root.
     #[clap(long = "cd", short = 'C', value_name = "DIR")]
     pub cwd: Option<PathBuf>,
@@ -86,7 +107,7 @@ pub struct Cli {
     #[arg(long = "color", value_enum, default_value_t = Color::Auto)]
     pub color: Color,
 
-    /// Print events to stdout as JSONL.
+    /// Print events to stdout as JSONL. Same as `--output-format json`.
     #[arg(
         long = "json",
         alias = "experimental-json",
@@ -95,10 +116,47 @@ pub struct Cli {
     )]
     pub json: bool,
 
+    /// Format of what is printed to stdout: the final message, or one JSON event per line ending
+    /// with a `run.completed` summary.
+    #[arg(
+        long = "output-format",
+        value_enum,
+        default_value_t = OutputFormat::Text,
+        global = true
+    )]
+    pub output_format: OutputFormat,
+
+    /// Print the JSON Schema of the events `--output-format json` emits, then exit.
+    #[arg(long = "print-event-schema", default_value_t = false)]
+    pub print_event_schema: bool,
+
     /// Specifies file where the last message from the agent should be written.
     #[arg(long = "output-last-message", short = 'o', value_name = "FILE")]
     pub last_message_file: Option<PathBuf>,
 
+    /// Continue a previous session by id or thread name, sending the prompt as its next turn.
+    /// Same as `codex exec resume <SESSION_ID> <PROMPT>`.
+    #[arg(long = "resume", value_name = "SESSION_ID")]
+    pub resume: Option<String>,
+
+    /// Run every task listed in a YAML tasks file, each in its own session, and print a report
+    /// on all of them instead of a single final message.
+    #[arg(
+        long = "tasks",
+        value_name = "FILE",
+        conflicts_with_all = ["prompt", "last_message_file", "resume", "attach"]
+    )]
+    pub tasks: Option<PathBuf>,
+
+    /// How many tasks from `--tasks` may run at the same time.
+    #[arg(
+        long = "parallel",
+        value_name = "N",
+        default_value_t = 1,
+        requires = "tasks"
+    )]
+    pub parallel: usize,
+
     /// Initial instructions for the agent. If not provided as an argument (or
     /// if `-` is used), instructions are read from stdin.
     #[arg(value_name = "PROMPT", value_hint = clap::ValueHint::Other)]
@@ -165,6 +223,20 @@ pub struct ResumeArgs {
     pub prompt: Option<String>,
 }
 
+impl ResumeArgs {
+    /// Arguments for `--resume <SESSION_ID>`, which takes its prompt from the top-level
+    /// positional.
+    pub(crate) fn for_session(session_id: String) -> Self {
+        Self {
+            session_id: Some(session_id),
+            last: false,
+            all: false,
+            images: Vec::new(),
+            prompt: None,
+        }
+    }
+}
+
 impl From<ResumeArgsRaw> for ResumeArgs {
     fn from(raw: ResumeArgsRaw) -> Self {
         // When --last is used without an explicit prompt, treat the positional as the prompt
@@ -240,6 +312,14 @@ pub struct ReviewArgs {
     pub prompt: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
 #[value(rename_all = "kebab-case")]
 pub enum Color {
@@ -283,4 +363,22 @@ mod tests {
         });
         assert_eq!(effective_prompt.as_deref(), Some(PROMPT));
     }
+
+    #[test]
+    fn resume_flag_takes_session_id_and_prompt() {
+        let cli = Cli::parse_from([
+            "codex-exec",
+            "--resume",
+            "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "--json",
+            "run the tests again",
+        ]);
+
+        assert_eq!(
+            cli.resume.as_deref(),
+            Some("67e55044-10b1-426f-9247-bb680e5fe0c8")
+        );
+        assert_eq!(cli.prompt.as_deref(), Some("run the tests again"));
+        assert!(cli.command.is_none());
+    }
 }
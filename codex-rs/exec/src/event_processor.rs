@@ -4,6 +4,8 @@ use codex_core::config::Config;
 use codex_core::protocol::Event;
 use codex_core::protocol::SessionConfiguredEvent;
 
+use crate::exec_events::RunStatus;
+
 pub(crate) enum CodexStatus {
     Running,
     InitiateShutdown,
@@ -22,7 +24,8 @@ pub(crate) trait EventProcessor {
     /// Handle a single event emitted by the agent.
     fn process_event(&mut self, event: Event) -> CodexStatus;
 
-    fn print_final_output(&mut self) {}
+    /// Called once the run is over, however it ended, with how it ended.
+    fn print_final_output(&mut self, _status: RunStatus) {}
 }
 
 pub(crate) fn handle_last_message(last_agent_message: Option<&str>, output_file: &Path) {
@@ -11,6 +11,9 @@ use codex_core::protocol::CollabCloseBeginEvent;
 use codex_core::protocol::CollabCloseEndEvent;
 use codex_core::protocol::CollabWaitingBeginEvent;
 use codex_core::protocol::CollabWaitingEndEvent;
+use codex_core::protocol::DelegateBeginEvent;
+use codex_core::protocol::DelegateEndEvent;
+use codex_core::protocol::DelegateProgressEvent;
 use codex_core::protocol::DeprecationNoticeEvent;
 use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::Event;
@@ -20,8 +23,11 @@ use codex_core::protocol::ExecCommandEndEvent;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::ItemCompletedEvent;
 use codex_core::protocol::McpInvocation;
+use codex_core::protocol::McpServerHealthEvent;
+use codex_core::protocol::McpServerHealthStatus;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::ModelProviderFallbackEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::SessionConfiguredEvent;
@@ -31,6 +37,8 @@ use codex_core::protocol::TurnCompleteEvent;
 use codex_core::protocol::TurnDiffEvent;
 use codex_core::protocol::WarningEvent;
 use codex_core::protocol::WebSearchEndEvent;
+use codex_core::protocol::WorktreeCreatedEvent;
+use codex_core::protocol::WorktreeMergedEvent;
 use codex_core::web_search::web_search_detail;
 use codex_protocol::items::TurnItem;
 use codex_protocol::num_format::format_with_separators;
@@ -46,6 +54,7 @@ use std::time::Instant;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
 use crate::event_processor::handle_last_message;
+use crate::exec_events::RunStatus;
 use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
 use codex_utils_sandbox_summary::create_config_summary_entries;
@@ -189,6 +198,17 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 );
             }
             EventMsg::ModelReroute(_) => {}
+            EventMsg::ModelProviderFallback(ModelProviderFallbackEvent {
+                from_provider,
+                to_provider,
+                ..
+            }) => {
+                ts_msg!(
+                    self,
+                    "{} switched model provider from {from_provider} to {to_provider}",
+                    "warning:".style(self.yellow).style(self.bold)
+                );
+            }
             EventMsg::DeprecationNotice(DeprecationNoticeEvent { summary, details }) => {
                 ts_msg!(
                     self,
@@ -216,6 +236,22 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     status_text
                 );
             }
+            EventMsg::McpServerHealth(McpServerHealthEvent { server, status }) => {
+                let status_text = match status {
+                    McpServerHealthStatus::Restarting { attempt, error } => {
+                        format!("restarting (attempt {attempt}): {error}")
+                    }
+                    McpServerHealthStatus::Restarted => "restarted".to_string(),
+                    McpServerHealthStatus::Failed { error } => format!("failed: {error}"),
+                };
+                ts_msg!(
+                    self,
+                    "{} {} {}",
+                    "mcp:".style(self.cyan),
+                    server,
+                    status_text
+                );
+            }
             EventMsg::McpStartupComplete(summary) => {
                 let mut parts = Vec::new();
                 if !summary.ready.is_empty() {
@@ -249,6 +285,43 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 };
                 ts_msg!(self, "{}", message.style(self.dimmed));
             }
+            EventMsg::StreamRetry(_) => {
+                // The retry is already reported by the preceding StreamError.
+            }
+            EventMsg::WorktreeCreated(WorktreeCreatedEvent { path, branch, .. }) => {
+                ts_msg!(
+                    self,
+                    "{} {} ({branch})",
+                    "worktree:".style(self.cyan),
+                    path.display()
+                );
+            }
+            EventMsg::WorktreeMerged(WorktreeMergedEvent { branch, commit }) => match commit {
+                Some(commit) => ts_msg!(
+                    self,
+                    "{} {branch} at {commit}",
+                    "worktree merged:".style(self.cyan)
+                ),
+                None => ts_msg!(
+                    self,
+                    "{} {branch} has nothing to merge",
+                    "worktree:".style(self.cyan)
+                ),
+            },
+            EventMsg::ProjectDocsLoaded(_)
+            | EventMsg::TurnSettings(_)
+            | EventMsg::UserInputQueueUpdated(_)
+            | EventMsg::Unknown
+            | EventMsg::TurnStructuredOutput(_)
+            | EventMsg::CheckpointCreated(_) => {
+                // Ignore.
+            }
+            EventMsg::BudgetLimitReached(_) => {
+                // Declined automatically; the resulting error explains which limit was hit.
+            }
+            EventMsg::AuthExpired(_) => {
+                // Declined automatically; the resulting error asks the user to sign in again.
+            }
             EventMsg::TurnStarted(_) => {
                 // Ignore.
             }
@@ -769,6 +842,50 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     receiver_thread_id.to_string().style(self.dimmed)
                 );
             }
+            EventMsg::DelegateBegin(DelegateBeginEvent { call_id, task }) => {
+                ts_msg!(
+                    self,
+                    "{} {}",
+                    "delegate".style(self.magenta),
+                    format_collab_invocation("delegate", &call_id, Some(&task)).style(self.bold)
+                );
+            }
+            EventMsg::DelegateProgress(DelegateProgressEvent { call_id: _, msg }) => {
+                if let EventMsg::ExecCommandBegin(ExecCommandBeginEvent { command, .. }) = *msg {
+                    eprintln!(
+                        "  {} {}",
+                        "exec".style(self.dimmed),
+                        escape_command(&command).style(self.dimmed)
+                    );
+                }
+            }
+            EventMsg::DelegateEnd(DelegateEndEvent {
+                call_id,
+                output,
+                error,
+            }) => match error {
+                Some(error) => {
+                    let title = format!(
+                        "{} failed: {}",
+                        format_collab_invocation("delegate", &call_id, None),
+                        truncate_preview(error.trim(), 120)
+                    );
+                    ts_msg!(self, "{}", title.style(self.red));
+                }
+                None => {
+                    let title = format!(
+                        "{} completed:",
+                        format_collab_invocation("delegate", &call_id, None)
+                    );
+                    ts_msg!(self, "{}", title.style(self.green));
+                    if let Some(output) = output {
+                        eprintln!(
+                            "  {}",
+                            truncate_preview(output.trim(), 120).style(self.dimmed)
+                        );
+                    }
+                }
+            },
             EventMsg::ShutdownComplete => return CodexStatus::Shutdown,
             EventMsg::ThreadNameUpdated(_)
             | EventMsg::ExecApprovalRequest(_)
@@ -777,7 +894,14 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::ExecCommandOutputDelta(_)
             | EventMsg::GetHistoryEntryResponse(_)
             | EventMsg::McpListToolsResponse(_)
+            | EventMsg::ListMcpPromptsResponse(_)
+            | EventMsg::GetMcpPromptResponse(_)
+            | EventMsg::GetMcpServerLogsResponse(_)
+            | EventMsg::McpListChanged(_)
+            | EventMsg::McpToolCallQueued(_)
             | EventMsg::ListCustomPromptsResponse(_)
+            | EventMsg::GetUsageResponse(_)
+            | EventMsg::PinnedContextUpdated(_)
             | EventMsg::ListSkillsResponse(_)
             | EventMsg::ListRemoteSkillsResponse(_)
             | EventMsg::RemoteSkillDownloaded(_)
@@ -795,6 +919,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::ReasoningContentDelta(_)
             | EventMsg::ReasoningRawContentDelta(_)
             | EventMsg::SkillsUpdateAvailable
+            | EventMsg::ConfigReloaded(_)
             | EventMsg::UndoCompleted(_)
             | EventMsg::UndoStarted(_)
             | EventMsg::ThreadRolledBack(_)
@@ -806,7 +931,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
         CodexStatus::Running
     }
 
-    fn print_final_output(&mut self) {
+    fn print_final_output(&mut self, _status: RunStatus) {
         if let Some(usage_info) = &self.last_total_token_usage {
             eprintln!(
                 "{}\n{}",
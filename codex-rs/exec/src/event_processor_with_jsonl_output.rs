@@ -26,6 +26,8 @@ use crate::exec_events::McpToolCallStatus;
 use crate::exec_events::PatchApplyStatus;
 use crate::exec_events::PatchChangeKind;
 use crate::exec_events::ReasoningItem;
+use crate::exec_events::RunCompletedEvent;
+use crate::exec_events::RunStatus;
 use crate::exec_events::ThreadErrorEvent;
 use crate::exec_events::ThreadEvent;
 use crate::exec_events::ThreadItem;
@@ -70,6 +72,9 @@ pub struct EventProcessorWithJsonOutput {
     running_collab_tool_calls: HashMap<String, RunningCollabToolCall>,
     running_web_search_calls: HashMap<String, String>,
     last_critical_error: Option<ThreadErrorEvent>,
+    // Summarized by the final `run.completed` event.
+    thread_id: Option<String>,
+    files_changed: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -113,6 +118,31 @@ impl EventProcessorWithJsonOutput {
             running_collab_tool_calls: HashMap::new(),
             running_web_search_calls: HashMap::new(),
             last_critical_error: None,
+            thread_id: None,
+            files_changed: Vec::new(),
+        }
+    }
+
+    /// The `run.completed` event that ends the stream of a run that ended with `status`.
+    pub fn run_completed_event(&self, status: RunStatus) -> ThreadEvent {
+        ThreadEvent::RunCompleted(RunCompletedEvent {
+            status,
+            exit_code: status.exit_code(),
+            thread_id: self.thread_id.clone(),
+            files_changed: self.files_changed.clone(),
+            usage: self.total_usage(),
+        })
+    }
+
+    fn total_usage(&self) -> Usage {
+        if let Some(u) = &self.last_total_token_usage {
+            Usage {
+                input_tokens: u.input_tokens,
+                cached_input_tokens: u.cached_input_tokens,
+                output_tokens: u.output_tokens,
+            }
+        } else {
+            Usage::default()
         }
     }
 
@@ -200,9 +230,12 @@ impl EventProcessorWithJsonOutput {
     }
 
     fn handle_session_configured(
-        &self,
+        &mut self,
         payload: &protocol::SessionConfiguredEvent,
     ) -> Vec<ThreadEvent> {
+        if self.thread_id.is_none() {
+            self.thread_id = Some(payload.session_id.to_string());
+        }
         vec![ThreadEvent::ThreadStarted(ThreadStartedEvent {
             thread_id: payload.session_id.to_string(),
         })]
@@ -651,20 +684,25 @@ impl EventProcessorWithJsonOutput {
             } else {
                 PatchApplyStatus::Failed
             };
+            let changes: Vec<FileUpdateChange> = running_patch_apply
+                .changes
+                .iter()
+                .map(|(path, change)| FileUpdateChange {
+                    path: path.to_str().unwrap_or("").to_string(),
+                    kind: self.map_change_kind(change),
+                })
+                .collect();
+            if ev.success {
+                for change in &changes {
+                    if !self.files_changed.contains(&change.path) {
+                        self.files_changed.push(change.path.clone());
+                    }
+                }
+            }
             let item = ThreadItem {
                 id: self.get_next_item_id(),
 
-                details: ThreadItemDetails::FileChange(FileChangeItem {
-                    changes: running_patch_apply
-                        .changes
-                        .iter()
-                        .map(|(path, change)| FileUpdateChange {
-                            path: path.to_str().unwrap_or("").to_string(),
-                            kind: self.map_change_kind(change),
-                        })
-                        .collect(),
-                    status,
-                }),
+                details: ThreadItemDetails::FileChange(FileChangeItem { changes, status }),
             };
 
             return vec![ThreadEvent::ItemCompleted(ItemCompletedEvent { item })];
@@ -750,15 +788,7 @@ impl EventProcessorWithJsonOutput {
     }
 
     fn handle_task_complete(&mut self) -> Vec<ThreadEvent> {
-        let usage = if let Some(u) = &self.last_total_token_usage {
-            Usage {
-                input_tokens: u.input_tokens,
-                cached_input_tokens: u.cached_input_tokens,
-                output_tokens: u.output_tokens,
-            }
-        } else {
-            Usage::default()
-        };
+        let usage = self.total_usage();
 
         let mut items = Vec::new();
 
@@ -843,18 +873,10 @@ impl EventProcessor for EventProcessorWithJsonOutput {
         });
     }
 
-    #[allow(clippy::print_stdout)]
     fn process_event(&mut self, event: protocol::Event) -> CodexStatus {
         let aggregated = self.collect_thread_events(&event);
         for conv_event in aggregated {
-            match serde_json::to_string(&conv_event) {
-                Ok(line) => {
-                    println!("{line}");
-                }
-                Err(e) => {
-                    error!("Failed to serialize event: {e:?}");
-                }
-            }
+            print_thread_event(&conv_event);
         }
 
         let protocol::Event { msg, .. } = event;
@@ -877,4 +899,20 @@ impl EventProcessor for EventProcessorWithJsonOutput {
             _ => CodexStatus::Running,
         }
     }
+
+    fn print_final_output(&mut self, status: RunStatus) {
+        print_thread_event(&self.run_completed_event(status));
+    }
+}
+
+#[allow(clippy::print_stdout)]
+fn print_thread_event(event: &ThreadEvent) {
+    match serde_json::to_string(event) {
+        Ok(line) => {
+            println!("{line}");
+        }
+        Err(e) => {
+            error!("Failed to serialize event: {e:?}");
+        }
+    }
 }
@@ -4,11 +4,14 @@
 // For both modes, any other output must be written to stderr.
 #![deny(clippy::print_stdout)]
 
+mod approval_policy;
+mod attachments;
+mod batch;
 mod cli;
 mod event_processor;
 mod event_processor_with_human_output;
 pub mod event_processor_with_jsonl_output;
-pub mod exec_events;
+pub use codex_protocol::exec_events;
 
 pub use cli::Cli;
 pub use cli::Command;
@@ -27,6 +30,8 @@ use codex_core::config::ConfigOverrides;
 use codex_core::config::find_codex_home;
 use codex_core::config::load_config_as_toml_with_cli_overrides;
 use codex_core::config::resolve_oss_provider;
+use codex_core::config::types::LogFormat;
+use codex_core::config_loader::CloudRequirementsLoader;
 use codex_core::config_loader::ConfigLoadError;
 use codex_core::config_loader::format_config_error_with_source;
 use codex_core::format_exec_policy_error_with_source;
@@ -36,9 +41,12 @@ use codex_core::protocol::AskForApproval;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
+use codex_core::protocol::ReviewDecision;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SessionSource;
+use codex_otel::json_log::JsonLogLayer;
+use codex_otel::json_log::log_writer;
 use codex_protocol::approvals::ElicitationAction;
 use codex_protocol::config_types::SandboxMode;
 use codex_protocol::user_input::UserInput;
@@ -63,9 +71,11 @@ use tracing_subscriber::EnvFilter;
 use tracing_subscriber::prelude::*;
 use uuid::Uuid;
 
+use crate::approval_policy::ApprovalPolicyFile;
 use crate::cli::Command as ExecCommand;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
+use crate::exec_events::RunStatus;
 use codex_core::default_client::set_default_client_residency_requirement;
 use codex_core::default_client::set_default_originator;
 use codex_core::find_thread_path_by_id_str;
@@ -93,28 +103,66 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         tracing::warn!(?err, "Failed to set codex exec originator override {err:?}");
     }
 
+    if let Some(tasks_path) = cli.tasks.as_deref() {
+        return batch::run_batch(tasks_path, &cli).await;
+    }
+
     let Cli {
         command,
         images,
+        attach,
         model: model_cli_arg,
+        model_preset,
         oss,
         oss_provider,
         config_profile,
         full_auto,
         dangerously_bypass_approvals_and_sandbox,
+        approval_policy_file,
         cwd,
         skip_git_repo_check,
         add_dir,
         ephemeral,
         color,
         last_message_file,
-        json: json_mode,
+        json,
+        output_format,
+        print_event_schema,
+        resume,
+        tasks: _,
+        parallel: _,
         sandbox_mode: sandbox_mode_cli_arg,
         prompt,
         output_schema: output_schema_path,
         config_overrides,
     } = cli;
 
+    if print_event_schema {
+        let schema = serde_json::to_string_pretty(&exec_events::json_schema())?;
+        #[allow(clippy::print_stdout)]
+        {
+            println!("{schema}");
+        }
+        return Ok(());
+    }
+    let json_mode = json || output_format == cli::OutputFormat::Json;
+    let command = match (resume, command) {
+        (Some(_), Some(_)) => {
+            anyhow::bail!("--resume cannot be combined with the `resume` or `review` subcommands")
+        }
+        (Some(session_id), None) => Some(ExecCommand::Resume(cli::ResumeArgs::for_session(
+            session_id,
+        ))),
+        (None, command) => command,
+    };
+    // Read before anything else, relative to where `codex exec` was started rather than `--cd`.
+    let stdin_attached = attachments::attaches_stdin(&attach);
+    let attachments = attachments::load_attachments(&attach)?;
+    let approval_policy = approval_policy_file
+        .as_deref()
+        .map(ApprovalPolicyFile::load)
+        .transpose()?;
+
     let (stdout_with_ansi, stderr_with_ansi) = match color {
         cli::Color::Always => (true, true),
         cli::Color::Never => (false, false),
@@ -132,11 +180,6 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         .or_else(|_| EnvFilter::try_new(default_level))
         .unwrap_or_else(|_| EnvFilter::new(default_level));
 
-    let fmt_layer = tracing_subscriber::fmt::layer()
-        .with_ansi(stderr_with_ansi)
-        .with_writer(std::io::stderr)
-        .with_filter(env_filter);
-
     let sandbox_mode = if full_auto {
         Some(SandboxMode::WorkspaceWrite)
     } else if dangerously_bypass_approvals_and_sandbox {
@@ -197,18 +240,34 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         }
     };
 
-    let cloud_auth_manager = AuthManager::shared(
-        codex_home.clone(),
-        false,
-        config_toml.cli_auth_credentials_store.unwrap_or_default(),
-    );
-    let chatgpt_base_url = config_toml
-        .chatgpt_base_url
-        .clone()
-        .unwrap_or_else(|| "https://chatgpt.com/backend-api/".to_string());
+    // The selected profile may use another account, so cloud requirements are fetched with the
+    // credentials of the resolved config.
     // TODO(gt): Make cloud requirements failures blocking once we can fail-closed.
-    let cloud_requirements =
-        cloud_requirements_loader(cloud_auth_manager, chatgpt_base_url, codex_home.clone());
+    let cloud_requirements = match ConfigBuilder::default()
+        .codex_home(codex_home.clone())
+        .cli_overrides(cli_kv_overrides.clone())
+        .harness_overrides(ConfigOverrides {
+            config_profile: config_profile.clone(),
+            cwd: resolved_cwd.clone(),
+            ..Default::default()
+        })
+        .build()
+        .await
+    {
+        Ok(config) => cloud_requirements_loader(
+            AuthManager::shared(
+                config.auth_home(),
+                false,
+                config.cli_auth_credentials_store_mode,
+            ),
+            config.chatgpt_base_url,
+            config.codex_home,
+        ),
+        Err(err) => {
+            warn!(error = %err, "failed to preload config for cloud requirements");
+            CloudRequirementsLoader::default()
+        }
+    };
 
     let model_provider = if oss {
         let resolved = resolve_oss_provider(
@@ -245,8 +304,14 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         model,
         review_model: None,
         config_profile,
-        // Default to never ask for approvals in headless mode. Feature flags can override.
-        approval_policy: Some(AskForApproval::Never),
+        model_preset,
+        // Default to never ask for approvals in headless mode, since nobody can answer them. A
+        // policy file answers them instead. Feature flags can override.
+        approval_policy: Some(if approval_policy.is_some() {
+            AskForApproval::OnRequest
+        } else {
+            AskForApproval::Never
+        }),
         sandbox_mode,
         cwd: resolved_cwd,
         model_provider: model_provider.clone(),
@@ -265,12 +330,14 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         additional_writable_roots: add_dir,
     };
 
-    let config = ConfigBuilder::default()
+    let mut config = ConfigBuilder::default()
         .cli_overrides(cli_kv_overrides)
         .harness_overrides(overrides)
         .cloud_requirements(cloud_requirements)
         .build()
         .await?;
+    let attachments =
+        attachments::attachment_inputs(attachments, config.file_excerpt_threshold_bytes)?;
 
     #[allow(clippy::print_stderr)]
     match check_execpolicy_for_warnings(&config.config_layer_stack).await {
@@ -309,8 +376,24 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
 
     let otel_tracing_layer = otel.as_ref().and_then(|o| o.tracing_layer());
 
+    let writer = log_writer(config.log_file.as_deref())?;
+    let (fmt_layer, json_log_layer) = match config.log_format {
+        LogFormat::Text => {
+            let fmt_layer = tracing_subscriber::fmt::layer()
+                .with_ansi(stderr_with_ansi && config.log_file.is_none())
+                .with_writer(writer)
+                .with_filter(env_filter);
+            (Some(fmt_layer), None)
+        }
+        LogFormat::Json => (
+            None,
+            Some(JsonLogLayer::new(writer).with_filter(env_filter)),
+        ),
+    };
+
     let _ = tracing_subscriber::registry()
         .with(fmt_layer)
+        .with(json_log_layer)
         .with(otel_tracing_layer)
         .with(otel_logger_layer)
         .try_init();
@@ -323,289 +406,367 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             last_message_file.clone(),
         )),
     };
-    let required_mcp_servers: HashSet<String> = config
-        .mcp_servers
-        .get()
-        .iter()
-        .filter(|(_, server)| server.enabled && server.required)
-        .map(|(name, _)| name.clone())
-        .collect();
 
-    if oss {
-        // We're in the oss section, so provider_id should be Some
-        // Let's handle None case gracefully though just in case
-        let provider_id = match model_provider.as_ref() {
-            Some(id) => id,
-            None => {
-                error!("OSS provider unexpectedly not set when oss flag is used");
-                return Err(anyhow::anyhow!(
-                    "OSS provider not set but oss flag was used"
-                ));
-            }
-        };
-        ensure_oss_provider_ready(provider_id, &config)
-            .await
-            .map_err(|e| anyhow::anyhow!("OSS setup failed: {e}"))?;
-    }
+    // Every way the run can end below leaves this block, so the final output, including the
+    // `run.completed` event, always reports how the run actually ended.
+    let result = async {
+        let required_mcp_servers: HashSet<String> = config
+            .mcp_servers
+            .get()
+            .iter()
+            .filter(|(_, server)| server.enabled && server.required)
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        if oss {
+            // We're in the oss section, so provider_id should be Some
+            // Let's handle None case gracefully though just in case
+            let provider_id = match model_provider.as_ref() {
+                Some(id) => id,
+                None => {
+                    error!("OSS provider unexpectedly not set when oss flag is used");
+                    return Err(anyhow::anyhow!(
+                        "OSS provider not set but oss flag was used"
+                    ));
+                }
+            };
+            ensure_oss_provider_ready(provider_id, &mut config)
+                .await
+                .map_err(|e| anyhow::anyhow!("OSS setup failed: {e}"))?;
+        }
 
-    let default_cwd = config.cwd.to_path_buf();
-    let default_approval_policy = config.permissions.approval_policy.value();
-    let default_sandbox_policy = config.permissions.sandbox_policy.get();
-    let default_effort = config.model_reasoning_effort;
-    let default_summary = config.model_reasoning_summary;
-
-    // When --yolo (dangerously_bypass_approvals_and_sandbox) is set, also skip the git repo check
-    // since the user is explicitly running in an externally sandboxed environment.
-    if !skip_git_repo_check
-        && !dangerously_bypass_approvals_and_sandbox
-        && get_git_repo_root(&default_cwd).is_none()
-    {
-        eprintln!("Not inside a trusted directory and --skip-git-repo-check was not specified.");
-        std::process::exit(1);
-    }
+        let default_cwd = config.cwd.to_path_buf();
+        let default_approval_policy = config.permissions.approval_policy.value();
+        let default_sandbox_policy = config.permissions.sandbox_policy.get();
+        let default_effort = config.model_reasoning_effort;
+        let default_summary = config.model_reasoning_summary;
+
+        // When --yolo (dangerously_bypass_approvals_and_sandbox) is set, also skip the git repo check
+        // since the user is explicitly running in an externally sandboxed environment.
+        if !skip_git_repo_check
+            && !dangerously_bypass_approvals_and_sandbox
+            && get_git_repo_root(&default_cwd).is_none()
+        {
+            eprintln!(
+                "Not inside a trusted directory and --skip-git-repo-check was not specified."
+            );
+            return Ok(RunStatus::Failed);
+        }
 
-    let auth_manager = AuthManager::shared(
-        config.codex_home.clone(),
-        true,
-        config.cli_auth_credentials_store_mode,
-    );
-    let thread_manager = Arc::new(ThreadManager::new(
-        config.codex_home.clone(),
-        auth_manager.clone(),
-        SessionSource::Exec,
-    ));
-    let default_model = thread_manager
-        .get_models_manager()
-        .get_default_model(&config.model, RefreshStrategy::OnlineIfUncached)
-        .await;
-
-    // Handle resume subcommand by resolving a rollout path and using explicit resume API.
-    let NewThread {
-        thread_id: primary_thread_id,
-        thread,
-        session_configured,
-    } = if let Some(ExecCommand::Resume(args)) = command.as_ref() {
-        let resume_path = resolve_resume_path(&config, args).await?;
-
-        if let Some(path) = resume_path {
-            thread_manager
-                .resume_thread_from_rollout(config.clone(), path, auth_manager.clone())
-                .await?
+        let auth_manager = AuthManager::shared(
+            config.auth_home(),
+            true,
+            config.cli_auth_credentials_store_mode,
+        );
+        let thread_manager = Arc::new(ThreadManager::new(
+            config.codex_home.clone(),
+            auth_manager.clone(),
+            SessionSource::Exec,
+        ));
+        let default_model = thread_manager
+            .get_models_manager()
+            .get_default_model(&config.model, RefreshStrategy::OnlineIfUncached)
+            .await;
+
+        // Handle resume subcommand by resolving a rollout path and using explicit resume API.
+        let NewThread {
+            thread_id: primary_thread_id,
+            thread,
+            session_configured,
+        } = if let Some(ExecCommand::Resume(args)) = command.as_ref() {
+            let resume_path = resolve_resume_path(&config, args).await?;
+
+            if let Some(path) = resume_path {
+                thread_manager
+                    .resume_thread_from_rollout(config.clone(), path, auth_manager.clone())
+                    .await?
+            } else {
+                thread_manager.start_thread(config.clone()).await?
+            }
         } else {
             thread_manager.start_thread(config.clone()).await?
-        }
-    } else {
-        thread_manager.start_thread(config.clone()).await?
-    };
-    let (initial_operation, prompt_summary) = match (command, prompt, images) {
-        (Some(ExecCommand::Review(review_cli)), _, _) => {
-            let review_request = build_review_request(review_cli)?;
-            let summary = codex_core::review_prompts::user_facing_hint(&review_request.target);
-            (InitialOperation::Review { review_request }, summary)
-        }
-        (Some(ExecCommand::Resume(args)), root_prompt, imgs) => {
-            let prompt_arg = args
-                .prompt
-                .clone()
-                .or_else(|| {
-                    if args.last {
-                        args.session_id.clone()
-                    } else {
-                        None
-                    }
-                })
-                .or(root_prompt);
-            let prompt_text = resolve_prompt(prompt_arg);
-            let mut items: Vec<UserInput> = imgs
-                .into_iter()
-                .chain(args.images.into_iter())
-                .map(|path| UserInput::LocalImage { path })
-                .collect();
-            items.push(UserInput::Text {
-                text: prompt_text.clone(),
-                // CLI input doesn't track UI element ranges, so none are available here.
-                text_elements: Vec::new(),
-            });
-            let output_schema = load_output_schema(output_schema_path.clone());
-            (
-                InitialOperation::UserTurn {
-                    items,
-                    output_schema,
-                },
-                prompt_text,
-            )
-        }
-        (None, root_prompt, imgs) => {
-            let prompt_text = resolve_prompt(root_prompt);
-            let mut items: Vec<UserInput> = imgs
-                .into_iter()
-                .map(|path| UserInput::LocalImage { path })
-                .collect();
-            items.push(UserInput::Text {
-                text: prompt_text.clone(),
-                // CLI input doesn't track UI element ranges, so none are available here.
-                text_elements: Vec::new(),
-            });
-            let output_schema = load_output_schema(output_schema_path);
-            (
-                InitialOperation::UserTurn {
-                    items,
-                    output_schema,
-                },
-                prompt_text,
-            )
-        }
-    };
+        };
+        let (initial_operation, prompt_summary) = match (command, prompt, images) {
+            (Some(ExecCommand::Review(review_cli)), _, _) => {
+                let review_request = build_review_request(review_cli)?;
+                let summary = codex_core::review_prompts::user_facing_hint(&review_request.target);
+                (InitialOperation::Review { review_request }, summary)
+            }
+            (Some(ExecCommand::Resume(args)), root_prompt, imgs) => {
+                let prompt_arg = args
+                    .prompt
+                    .clone()
+                    .or_else(|| {
+                        if args.last {
+                            args.session_id.clone()
+                        } else {
+                            None
+                        }
+                    })
+                    .or(root_prompt);
+                ensure_prompt_not_on_stdin(prompt_arg.as_deref(), stdin_attached)?;
+                let prompt_text = resolve_prompt(prompt_arg);
+                let mut items: Vec<UserInput> = imgs
+                    .into_iter()
+                    .chain(args.images.into_iter())
+                    .map(|path| UserInput::LocalImage { path })
+                    .collect();
+                items.extend(attachments);
+                items.push(UserInput::Text {
+                    text: prompt_text.clone(),
+                    // CLI input doesn't track UI element ranges, so none are available here.
+                    text_elements: Vec::new(),
+                });
+                let output_schema = load_output_schema(output_schema_path.clone());
+                (
+                    InitialOperation::UserTurn {
+                        items,
+                        output_schema,
+                    },
+                    prompt_text,
+                )
+            }
+            (None, root_prompt, imgs) => {
+                ensure_prompt_not_on_stdin(root_prompt.as_deref(), stdin_attached)?;
+                let prompt_text = resolve_prompt(root_prompt);
+                let mut items: Vec<UserInput> = imgs
+                    .into_iter()
+                    .map(|path| UserInput::LocalImage { path })
+                    .collect();
+                items.extend(attachments);
+                items.push(UserInput::Text {
+                    text: prompt_text.clone(),
+                    // CLI input doesn't track UI element ranges, so none are available here.
+                    text_elements: Vec::new(),
+                });
+                let output_schema = load_output_schema(output_schema_path);
+                (
+                    InitialOperation::UserTurn {
+                        items,
+                        output_schema,
+                    },
+                    prompt_text,
+                )
+            }
+        };
 
-    // Print the effective configuration and initial request so users can see what Codex
-    // is using.
-    event_processor.print_config_summary(&config, &prompt_summary, &session_configured);
+        // Print the effective configuration and initial request so users can see what Codex
+        // is using.
+        event_processor.print_config_summary(&config, &prompt_summary, &session_configured);
 
-    info!("Codex initialized with event: {session_configured:?}");
+        info!("Codex initialized with event: {session_configured:?}");
 
-    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<ThreadEventEnvelope>();
-    let attached_threads = Arc::new(Mutex::new(HashSet::from([primary_thread_id])));
-    spawn_thread_listener(primary_thread_id, thread.clone(), tx.clone());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<ThreadEventEnvelope>();
+        let attached_threads = Arc::new(Mutex::new(HashSet::from([primary_thread_id])));
+        spawn_thread_listener(primary_thread_id, thread.clone(), tx.clone());
 
-    {
-        let thread = thread.clone();
-        tokio::spawn(async move {
-            if tokio::signal::ctrl_c().await.is_ok() {
-                tracing::debug!("Keyboard interrupt");
-                // Immediately notify Codex to abort any in-flight task.
-                thread.submit(Op::Interrupt).await.ok();
-            }
-        });
-    }
+        {
+            let thread = thread.clone();
+            tokio::spawn(async move {
+                if tokio::signal::ctrl_c().await.is_ok() {
+                    tracing::debug!("Keyboard interrupt");
+                    // Immediately notify Codex to abort any in-flight task.
+                    thread.submit(Op::Interrupt).await.ok();
+                }
+            });
+        }
 
-    {
-        let thread_manager = Arc::clone(&thread_manager);
-        let attached_threads = Arc::clone(&attached_threads);
-        let tx = tx.clone();
-        let mut thread_created_rx = thread_manager.subscribe_thread_created();
-        tokio::spawn(async move {
-            loop {
-                match thread_created_rx.recv().await {
-                    Ok(thread_id) => {
-                        if attached_threads.lock().await.contains(&thread_id) {
-                            continue;
-                        }
-                        match thread_manager.get_thread(thread_id).await {
-                            Ok(thread) => {
-                                attached_threads.lock().await.insert(thread_id);
-                                spawn_thread_listener(thread_id, thread, tx.clone());
+        {
+            let thread_manager = Arc::clone(&thread_manager);
+            let attached_threads = Arc::clone(&attached_threads);
+            let tx = tx.clone();
+            let mut thread_created_rx = thread_manager.subscribe_thread_created();
+            tokio::spawn(async move {
+                loop {
+                    match thread_created_rx.recv().await {
+                        Ok(thread_id) => {
+                            if attached_threads.lock().await.contains(&thread_id) {
+                                continue;
                             }
-                            Err(err) => {
-                                warn!("failed to attach listener for thread {thread_id}: {err}")
+                            match thread_manager.get_thread(thread_id).await {
+                                Ok(thread) => {
+                                    attached_threads.lock().await.insert(thread_id);
+                                    spawn_thread_listener(thread_id, thread, tx.clone());
+                                }
+                                Err(err) => {
+                                    warn!("failed to attach listener for thread {thread_id}: {err}")
+                                }
                             }
                         }
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {
+                            warn!("thread_created receiver lagged; skipping resync");
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
                     }
-                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {
-                        warn!("thread_created receiver lagged; skipping resync");
-                    }
-                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
                 }
-            }
-        });
-    }
-
-    match initial_operation {
-        InitialOperation::UserTurn {
-            items,
-            output_schema,
-        } => {
-            let task_id = thread
-                .submit(Op::UserTurn {
-                    items,
-                    cwd: default_cwd,
-                    approval_policy: default_approval_policy,
-                    sandbox_policy: default_sandbox_policy.clone(),
-                    model: default_model,
-                    effort: default_effort,
-                    summary: default_summary,
-                    final_output_json_schema: output_schema,
-                    collaboration_mode: None,
-                    personality: None,
-                })
-                .await?;
-            info!("Sent prompt with event ID: {task_id}");
-            task_id
-        }
-        InitialOperation::Review { review_request } => {
-            let task_id = thread.submit(Op::Review { review_request }).await?;
-            info!("Sent review request with event ID: {task_id}");
-            task_id
+            });
         }
-    };
 
-    // Run the loop until the task is complete.
-    // Track whether a fatal error was reported by the server so we can
-    // exit with a non-zero status for automation-friendly signaling.
-    let mut error_seen = false;
-    let mut shutdown_requested = false;
-    while let Some(envelope) = rx.recv().await {
-        let ThreadEventEnvelope {
-            thread_id,
-            thread,
-            event,
-        } = envelope;
-        if matches!(event.msg, EventMsg::Error(_)) {
-            error_seen = true;
-        }
-        if shutdown_requested
-            && !matches!(&event.msg, EventMsg::ShutdownComplete | EventMsg::Error(_))
-        {
-            continue;
-        }
-        if let EventMsg::ElicitationRequest(ev) = &event.msg {
-            // Automatically cancel elicitation requests in exec mode.
-            thread
-                .submit(Op::ResolveElicitation {
-                    server_name: ev.server_name.clone(),
-                    request_id: ev.id.clone(),
-                    decision: ElicitationAction::Cancel,
-                })
-                .await?;
-        }
-        if let EventMsg::McpStartupUpdate(update) = &event.msg
-            && required_mcp_servers.contains(&update.server)
-            && let codex_core::protocol::McpStartupStatus::Failed { error } = &update.status
-        {
-            error_seen = true;
-            eprintln!(
-                "Required MCP server '{}' failed to initialize: {error}",
-                update.server
-            );
-            if !shutdown_requested {
-                thread.submit(Op::Shutdown).await?;
-                shutdown_requested = true;
+        match initial_operation {
+            InitialOperation::UserTurn {
+                items,
+                output_schema,
+            } => {
+                let task_id = thread
+                    .submit(Op::UserTurn {
+                        items,
+                        cwd: default_cwd.clone(),
+                        approval_policy: default_approval_policy,
+                        sandbox_policy: default_sandbox_policy.clone(),
+                        model: default_model,
+                        effort: default_effort,
+                        summary: default_summary,
+                        final_output_json_schema: output_schema,
+                        collaboration_mode: None,
+                        personality: None,
+                    })
+                    .await?;
+                info!("Sent prompt with event ID: {task_id}");
+                task_id
             }
-        }
-        if thread_id != primary_thread_id && matches!(&event.msg, EventMsg::TurnComplete(_)) {
-            continue;
-        }
-        let shutdown = event_processor.process_event(event);
-        if thread_id != primary_thread_id && matches!(shutdown, CodexStatus::InitiateShutdown) {
-            continue;
-        }
-        match shutdown {
-            CodexStatus::Running => continue,
-            CodexStatus::InitiateShutdown => {
+            InitialOperation::Review { review_request } => {
+                let task_id = thread.submit(Op::Review { review_request }).await?;
+                info!("Sent review request with event ID: {task_id}");
+                task_id
+            }
+        };
+
+        // Run the loop until the task is complete.
+        // Track whether a fatal error was reported by the server so we can
+        // exit with a non-zero status for automation-friendly signaling.
+        let mut error_seen = false;
+        let mut interrupted = false;
+        let mut shutdown_requested = false;
+        while let Some(envelope) = rx.recv().await {
+            let ThreadEventEnvelope {
+                thread_id,
+                thread,
+                event,
+            } = envelope;
+            if matches!(event.msg, EventMsg::Error(_)) {
+                error_seen = true;
+            }
+            if thread_id == primary_thread_id && matches!(event.msg, EventMsg::TurnAborted(_)) {
+                interrupted = true;
+            }
+            if shutdown_requested
+                && !matches!(&event.msg, EventMsg::ShutdownComplete | EventMsg::Error(_))
+            {
+                continue;
+            }
+            if let EventMsg::ElicitationRequest(ev) = &event.msg {
+                // Automatically cancel elicitation requests in exec mode.
+                thread
+                    .submit(Op::ResolveElicitation {
+                        server_name: ev.server_name.clone(),
+                        request_id: ev.id.clone(),
+                        decision: ElicitationAction::Cancel,
+                        content: None,
+                    })
+                    .await?;
+            }
+            if let Some(policy) = &approval_policy {
+                match &event.msg {
+                    EventMsg::ExecApprovalRequest(ev) => {
+                        let decision = policy.decide_command(&ev.command);
+                        eprintln!(
+                            "Approval policy {} running `{}`",
+                            decision.describe(),
+                            ev.command.join(" ")
+                        );
+                        thread
+                            .submit(Op::ExecApproval {
+                                id: ev.effective_approval_id(),
+                                turn_id: Some(ev.turn_id.clone()),
+                                decision: decision.review_decision(),
+                            })
+                            .await?;
+                    }
+                    EventMsg::ApplyPatchApprovalRequest(ev) => {
+                        let decision = policy.decide_patch(ev.changes.keys(), &default_cwd);
+                        eprintln!(
+                            "Approval policy {} editing {} file(s)",
+                            decision.describe(),
+                            ev.changes.len()
+                        );
+                        thread
+                            .submit(Op::PatchApproval {
+                                id: ev.call_id.clone(),
+                                decision: decision.review_decision(),
+                            })
+                            .await?;
+                    }
+                    _ => {}
+                }
+            }
+            if let EventMsg::BudgetLimitReached(ev) = &event.msg {
+                // Nobody can confirm a budget overrun in exec mode, so stop the turn.
+                thread
+                    .submit(Op::BudgetApproval {
+                        id: ev.id.clone(),
+                        decision: ReviewDecision::Denied,
+                    })
+                    .await?;
+            }
+            if let EventMsg::AuthExpired(ev) = &event.msg {
+                // Nobody can sign in again in exec mode, so stop the turn.
+                thread
+                    .submit(Op::ReauthComplete {
+                        id: ev.id.clone(),
+                        success: false,
+                    })
+                    .await?;
+            }
+            if let EventMsg::McpStartupUpdate(update) = &event.msg
+                && required_mcp_servers.contains(&update.server)
+                && let codex_core::protocol::McpStartupStatus::Failed { error } = &update.status
+            {
+                error_seen = true;
+                eprintln!(
+                    "Required MCP server '{}' failed to initialize: {error}",
+                    update.server
+                );
                 if !shutdown_requested {
                     thread.submit(Op::Shutdown).await?;
                     shutdown_requested = true;
                 }
             }
-            CodexStatus::Shutdown if thread_id == primary_thread_id => break,
-            CodexStatus::Shutdown => continue,
+            if thread_id != primary_thread_id && matches!(&event.msg, EventMsg::TurnComplete(_)) {
+                continue;
+            }
+            let shutdown = event_processor.process_event(event);
+            if thread_id != primary_thread_id && matches!(shutdown, CodexStatus::InitiateShutdown) {
+                continue;
+            }
+            match shutdown {
+                CodexStatus::Running => continue,
+                CodexStatus::InitiateShutdown => {
+                    if !shutdown_requested {
+                        thread.submit(Op::Shutdown).await?;
+                        shutdown_requested = true;
+                    }
+                }
+                CodexStatus::Shutdown if thread_id == primary_thread_id => break,
+                CodexStatus::Shutdown => continue,
+            }
         }
+        let status = if error_seen {
+            RunStatus::Failed
+        } else if interrupted {
+            RunStatus::Interrupted
+        } else {
+            RunStatus::Completed
+        };
+        Ok::<RunStatus, anyhow::Error>(status)
     }
-    event_processor.print_final_output();
-    if error_seen {
-        std::process::exit(1);
-    }
+    .await;
 
-    Ok(())
+    let status = result.as_ref().map_or(RunStatus::Failed, |status| *status);
+    event_processor.print_final_output(status);
+    match result? {
+        RunStatus::Completed => Ok(()),
+        status => std::process::exit(status.exit_code()),
+    }
 }
 
 fn spawn_thread_listener(
@@ -785,6 +946,17 @@ fn decode_utf16(
     String::from_utf16(&units).map_err(|_| PromptDecodeError::InvalidUtf16 { encoding })
 }
 
+/// stdin can only be read once, so it cannot hold both an attachment and the prompt.
+fn ensure_prompt_not_on_stdin(
+    prompt_arg: Option<&str>,
+    stdin_attached: bool,
+) -> anyhow::Result<()> {
+    if stdin_attached && matches!(prompt_arg, None | Some("-")) {
+        anyhow::bail!("Pass the prompt as an argument when attaching stdin with `--attach -`.");
+    }
+    Ok(())
+}
+
 fn resolve_prompt(prompt_arg: Option<String>) -> String {
     match prompt_arg {
         Some(p) if p != "-" => p,
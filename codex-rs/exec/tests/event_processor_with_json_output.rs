@@ -44,6 +44,8 @@ use codex_exec::exec_events::McpToolCallStatus;
 use codex_exec::exec_events::PatchApplyStatus;
 use codex_exec::exec_events::PatchChangeKind;
 use codex_exec::exec_events::ReasoningItem;
+use codex_exec::exec_events::RunCompletedEvent;
+use codex_exec::exec_events::RunStatus;
 use codex_exec::exec_events::ThreadErrorEvent;
 use codex_exec::exec_events::ThreadEvent;
 use codex_exec::exec_events::ThreadItem;
@@ -315,6 +317,7 @@ fn plan_update_emits_todo_list_started_updated_and_completed() {
         EventMsg::TurnComplete(codex_core::protocol::TurnCompleteEvent {
             turn_id: "turn-1".to_string(),
             last_agent_message: None,
+            usage: None,
         }),
     );
     let out_complete = ep.collect_thread_events(&complete);
@@ -683,6 +686,7 @@ fn plan_update_after_complete_starts_new_todo_list_with_new_id() {
         EventMsg::TurnComplete(codex_core::protocol::TurnCompleteEvent {
             turn_id: "turn-1".to_string(),
             last_agent_message: None,
+            usage: None,
         }),
     );
     let _ = ep.collect_thread_events(&complete);
@@ -836,6 +840,7 @@ fn error_followed_by_task_complete_produces_turn_failed() {
         EventMsg::TurnComplete(codex_core::protocol::TurnCompleteEvent {
             turn_id: "turn-1".to_string(),
             last_agent_message: None,
+            usage: None,
         }),
     );
     assert_eq!(
@@ -897,12 +902,11 @@ fn exec_command_end_success_produces_completed_command_item() {
             parsed_cmd,
             source: ExecCommandSource::Agent,
             interaction_input: None,
-            stdout: String::new(),
-            stderr: String::new(),
             aggregated_output: "hi\n".to_string(),
             exit_code: 0,
             duration: Duration::from_millis(5),
             formatted_output: String::new(),
+            output_artifact: None,
             status: CoreExecCommandStatus::Completed,
         }),
     );
@@ -985,12 +989,11 @@ fn command_execution_output_delta_updates_item_progress() {
             parsed_cmd,
             source: ExecCommandSource::Agent,
             interaction_input: None,
-            stdout: String::new(),
-            stderr: String::new(),
             aggregated_output: String::new(),
             exit_code: 0,
             duration: Duration::from_millis(3),
             formatted_output: String::new(),
+            output_artifact: None,
             status: CoreExecCommandStatus::Completed,
         }),
     );
@@ -1059,12 +1062,11 @@ fn exec_command_end_failure_produces_failed_command_item() {
             parsed_cmd,
             source: ExecCommandSource::Agent,
             interaction_input: None,
-            stdout: String::new(),
-            stderr: String::new(),
             aggregated_output: String::new(),
             exit_code: 1,
             duration: Duration::from_millis(2),
             formatted_output: String::new(),
+            output_artifact: None,
             status: CoreExecCommandStatus::Failed,
         }),
     );
@@ -1101,12 +1103,11 @@ fn exec_command_end_without_begin_is_ignored() {
             parsed_cmd: Vec::new(),
             source: ExecCommandSource::Agent,
             interaction_input: None,
-            stdout: String::new(),
-            stderr: String::new(),
             aggregated_output: String::new(),
             exit_code: 0,
             duration: Duration::from_millis(1),
             formatted_output: String::new(),
+            output_artifact: None,
             status: CoreExecCommandStatus::Completed,
         }),
     );
@@ -1290,6 +1291,7 @@ fn task_complete_produces_turn_completed_with_usage() {
         EventMsg::TurnComplete(codex_core::protocol::TurnCompleteEvent {
             turn_id: "turn-1".to_string(),
             last_agent_message: Some("done".to_string()),
+            usage: None,
         }),
     );
     let out = ep.collect_thread_events(&complete_event);
@@ -1304,3 +1306,108 @@ fn task_complete_produces_turn_completed_with_usage() {
         })]
     );
 }
+
+#[test]
+fn run_completed_summarizes_thread_files_and_usage() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+    let session_id = ThreadId::from_string("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+    ep.collect_thread_events(&event(
+        "e1",
+        EventMsg::SessionConfigured(SessionConfiguredEvent {
+            session_id,
+            forked_from_id: None,
+            thread_name: None,
+            model: "codex-mini-latest".to_string(),
+            model_provider_id: "test-provider".to_string(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            cwd: PathBuf::from("/home/user/project"),
+            reasoning_effort: None,
+            history_log_id: 0,
+            history_entry_count: 0,
+            initial_messages: None,
+            network_proxy: None,
+            rollout_path: None,
+        }),
+    ));
+
+    // Only patches that applied count as changed files, each listed once.
+    let patches = [
+        ("call-1", "src/lib.rs", true),
+        ("call-2", "src/main.rs", false),
+        ("call-3", "src/lib.rs", true),
+    ];
+    for (call_id, path, success) in patches {
+        let changes = std::collections::HashMap::from([(
+            PathBuf::from(path),
+            FileChange::Add {
+                content: "+hello".to_string(),
+            },
+        )]);
+        ep.collect_thread_events(&event(
+            "p1",
+            EventMsg::PatchApplyBegin(PatchApplyBeginEvent {
+                call_id: call_id.to_string(),
+                turn_id: "turn-1".to_string(),
+                auto_approved: true,
+                changes: changes.clone(),
+            }),
+        ));
+        let status = if success {
+            CorePatchApplyStatus::Completed
+        } else {
+            CorePatchApplyStatus::Failed
+        };
+        ep.collect_thread_events(&event(
+            "p2",
+            EventMsg::PatchApplyEnd(PatchApplyEndEvent {
+                call_id: call_id.to_string(),
+                turn_id: "turn-1".to_string(),
+                stdout: String::new(),
+                stderr: String::new(),
+                success,
+                changes,
+                status,
+            }),
+        ));
+    }
+
+    let usage = codex_core::protocol::TokenUsage {
+        input_tokens: 1200,
+        cached_input_tokens: 200,
+        output_tokens: 345,
+        reasoning_output_tokens: 0,
+        total_tokens: 0,
+    };
+    ep.collect_thread_events(&event(
+        "e2",
+        EventMsg::TokenCount(codex_core::protocol::TokenCountEvent {
+            info: Some(codex_core::protocol::TokenUsageInfo {
+                total_token_usage: usage.clone(),
+                last_token_usage: usage,
+                model_context_window: None,
+            }),
+            rate_limits: None,
+        }),
+    ));
+
+    let summary = ep.run_completed_event(RunStatus::Failed);
+    assert_eq!(
+        summary,
+        ThreadEvent::RunCompleted(RunCompletedEvent {
+            status: RunStatus::Failed,
+            exit_code: 1,
+            thread_id: Some("67e55044-10b1-426f-9247-bb680e5fe0c8".to_string()),
+            files_changed: vec!["src/lib.rs".to_string()],
+            usage: Usage {
+                input_tokens: 1200,
+                cached_input_tokens: 200,
+                output_tokens: 345,
+            },
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(&summary).unwrap()["type"],
+        json!("run.completed")
+    );
+}
@@ -0,0 +1,295 @@
+//! A shared list of the files under a directory, built in the background and reused.
+//!
+//! Walking a large workspace is slow enough to notice on every `@` search in the TUI and on every
+//! lookup the agent makes. [`FileIndex::for_root`] hands out one index per root for as long as
+//! some caller holds it. The index is walked once in the background and refreshed when callers
+//! find it stale. Searches seeded from an index see the files as of its last refresh.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Condvar;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+use std::sync::Weak;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use nucleo::Config;
+use nucleo::Matcher;
+use nucleo::Utf32Str;
+use nucleo::pattern::CaseMatching;
+use nucleo::pattern::Normalization;
+use nucleo::pattern::Pattern;
+
+use crate::FileMatch;
+use crate::FileSearchResults;
+use crate::cmp_by_score_desc_then_path_asc;
+use crate::get_file_path;
+use crate::walk_builder;
+
+/// Threads used by a background walk; it competes with the UI and the agent, not a user waiting.
+const WALK_THREADS: usize = 2;
+
+/// Indexes still held by a caller; an index is dropped with its last [`FileIndex`].
+static INDEXES: LazyLock<Mutex<HashMap<PathBuf, Weak<FileIndexInner>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Files under one root, honoring `.gitignore` and similar ignore files.
+#[derive(Clone)]
+pub struct FileIndex {
+    inner: Arc<FileIndexInner>,
+}
+
+struct FileIndexInner {
+    root: PathBuf,
+    snapshot: Mutex<Option<Arc<FileIndexSnapshot>>>,
+    updated: Condvar,
+    refreshing: AtomicBool,
+}
+
+/// The files of a [`FileIndex`] as of one walk.
+#[derive(Debug)]
+pub struct FileIndexSnapshot {
+    root: PathBuf,
+    /// Sorted paths relative to `root`.
+    files: Vec<Arc<str>>,
+    built_at: Instant,
+}
+
+impl std::fmt::Debug for FileIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileIndex")
+            .field("root", &self.inner.root)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FileIndex {
+    /// The index of `root` shared by every caller in this process. It is empty until
+    /// [`FileIndex::refresh`] or a search seeded from it has walked the directory.
+    pub fn for_root(root: &Path) -> Self {
+        let mut indexes = lock(&INDEXES);
+        if let Some(inner) = indexes.get(root).and_then(Weak::upgrade) {
+            return Self { inner };
+        }
+        indexes.retain(|_, index| index.strong_count() > 0);
+        let index = Self::new(root.to_path_buf());
+        indexes.insert(root.to_path_buf(), Arc::downgrade(&index.inner));
+        index
+    }
+
+    fn new(root: PathBuf) -> Self {
+        Self {
+            inner: Arc::new(FileIndexInner {
+                root,
+                snapshot: Mutex::new(None),
+                updated: Condvar::new(),
+                refreshing: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.inner.root
+    }
+
+    /// The files as of the last completed walk, if there was one.
+    pub fn snapshot(&self) -> Option<Arc<FileIndexSnapshot>> {
+        lock(&self.inner.snapshot).clone()
+    }
+
+    /// Walks the root again on a background thread, unless a walk is already running.
+    pub fn refresh(&self) {
+        if self.inner.refreshing.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let index = self.clone();
+        thread::spawn(move || {
+            let files = walk_files(index.root());
+            index.publish(files);
+            index.inner.refreshing.store(false, Ordering::Release);
+        });
+    }
+
+    /// Starts a refresh if there is no snapshot yet or the current one is older than `max_age`.
+    pub fn refresh_if_older_than(&self, max_age: Duration) {
+        if self
+            .snapshot()
+            .is_none_or(|snapshot| snapshot.age() > max_age)
+        {
+            self.refresh();
+        }
+    }
+
+    /// The current snapshot, waiting up to `timeout` for the first walk to finish.
+    pub fn wait_for_snapshot(&self, timeout: Duration) -> Option<Arc<FileIndexSnapshot>> {
+        let deadline = Instant::now() + timeout;
+        let mut snapshot = lock(&self.inner.snapshot);
+        if snapshot.is_none() {
+            self.refresh();
+        }
+        loop {
+            if let Some(snapshot) = snapshot.as_ref() {
+                return Some(snapshot.clone());
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return None;
+            }
+            snapshot = self
+                .inner
+                .updated
+                .wait_timeout(snapshot, remaining)
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+    }
+
+    /// Replaces the snapshot with `files`, relative paths found by a complete walk of the root.
+    pub(crate) fn publish(&self, mut files: Vec<Arc<str>>) {
+        files.sort_unstable();
+        *lock(&self.inner.snapshot) = Some(Arc::new(FileIndexSnapshot {
+            root: self.inner.root.clone(),
+            files,
+            built_at: Instant::now(),
+        }));
+        self.inner.updated.notify_all();
+    }
+}
+
+impl FileIndexSnapshot {
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Sorted paths relative to the root.
+    pub fn files(&self) -> &[Arc<str>] {
+        &self.files
+    }
+
+    pub fn age(&self) -> Duration {
+        self.built_at.elapsed()
+    }
+
+    /// Fuzzy-matches `query` against the indexed paths, best matches first.
+    pub fn search(&self, query: &str, limit: usize) -> FileSearchResults {
+        let pattern = Pattern::parse(query, CaseMatching::Smart, Normalization::Smart);
+        let mut matcher = Matcher::new(Config::DEFAULT.match_paths());
+        let mut buf = Vec::new();
+        let mut matches: Vec<(u32, &str)> = self
+            .files
+            .iter()
+            .filter_map(|path| {
+                let score = pattern.score(Utf32Str::new(path, &mut buf), &mut matcher)?;
+                Some((score, path.as_ref()))
+            })
+            .collect();
+        let total_match_count = matches.len();
+        matches.sort_by(cmp_by_score_desc_then_path_asc::<(u32, &str), _, _>(
+            |m| m.0,
+            |m| m.1,
+        ));
+        FileSearchResults {
+            matches: matches
+                .into_iter()
+                .take(limit)
+                .map(|(score, path)| FileMatch {
+                    score,
+                    path: PathBuf::from(path),
+                    root: self.root.clone(),
+                    indices: None,
+                })
+                .collect(),
+            total_match_count,
+        }
+    }
+}
+
+/// Relative paths of the files under `root`, walked the same way as a search session.
+fn walk_files(root: &Path) -> Vec<Arc<str>> {
+    let roots = [root.to_path_buf()];
+    let files = Mutex::new(Vec::new());
+    walk_builder(&roots, WALK_THREADS, true)
+        .build_parallel()
+        .run(|| {
+            let files = &files;
+            let roots = &roots;
+            Box::new(move |entry| {
+                let Ok(entry) = entry else {
+                    return ignore::WalkState::Continue;
+                };
+                if entry.file_type().is_some_and(|ft| ft.is_dir()) {
+                    return ignore::WalkState::Continue;
+                }
+                if let Some((_, relative_path)) = get_file_path(entry.path(), roots) {
+                    lock(files).push(Arc::from(relative_path));
+                }
+                ignore::WalkState::Continue
+            })
+        });
+    files.into_inner().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::fs;
+
+    #[test]
+    fn index_lists_files_and_searches_them() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src/tools")).unwrap();
+        fs::create_dir_all(dir.path().join("target")).unwrap();
+        fs::write(dir.path().join(".gitignore"), "target/\n").unwrap();
+        fs::write(dir.path().join("src/tools/spec.rs"), "").unwrap();
+        fs::write(dir.path().join("src/lib.rs"), "").unwrap();
+        fs::write(dir.path().join("target/spec.rs"), "").unwrap();
+
+        let index = FileIndex::new(dir.path().to_path_buf());
+        let snapshot = index
+            .wait_for_snapshot(Duration::from_secs(5))
+            .expect("snapshot");
+        assert_eq!(
+            snapshot
+                .files()
+                .iter()
+                .map(AsRef::as_ref)
+                .collect::<Vec<&str>>(),
+            vec![".gitignore", "src/lib.rs", "src/tools/spec.rs"]
+        );
+
+        let results = snapshot.search("toolspec", 10);
+        assert_eq!(results.total_match_count, 1);
+        assert_eq!(results.matches[0].path, PathBuf::from("src/tools/spec.rs"));
+        assert_eq!(
+            results.matches[0].full_path(),
+            dir.path().join("src/tools/spec.rs")
+        );
+    }
+
+    #[test]
+    fn shared_index_is_dropped_with_its_last_holder() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = FileIndex::for_root(dir.path());
+        assert!(Arc::ptr_eq(
+            &index.inner,
+            &FileIndex::for_root(dir.path()).inner
+        ));
+
+        let weak = Arc::downgrade(&index.inner);
+        drop(index);
+        assert!(weak.upgrade().is_none());
+    }
+}
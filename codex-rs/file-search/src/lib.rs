@@ -35,8 +35,14 @@ use nucleo::pattern::AtomKind;
 use nucleo::pattern::Pattern;
 
 mod cli;
+mod index;
 
 pub use cli::Cli;
+pub use index::FileIndex;
+pub use index::FileIndexSnapshot;
+
+/// A session seeded from a [`FileIndex`] snapshot older than this starts a refresh for the next one.
+const INDEX_SESSION_MAX_AGE: Duration = Duration::from_secs(10);
 
 /// A single match result returned from the search.
 ///
@@ -143,6 +149,32 @@ pub fn create_session(
     options: FileSearchOptions,
     reporter: Arc<dyn SessionReporter>,
     cancel_flag: Option<Arc<AtomicBool>>,
+) -> anyhow::Result<FileSearchSession> {
+    start_session(search_directories, options, reporter, cancel_flag, None)
+}
+
+/// Like [`create_session`] for the root of `index`, but matches the indexed files instead of
+/// walking the directory when the index has a snapshot. The snapshot is refreshed in the
+/// background once it is a few seconds old; without one, the session walks the directory and
+/// stores what it found in the index.
+pub fn create_indexed_session(
+    index: &FileIndex,
+    options: FileSearchOptions,
+    reporter: Arc<dyn SessionReporter>,
+    cancel_flag: Option<Arc<AtomicBool>>,
+) -> anyhow::Result<FileSearchSession> {
+    let search_directories = vec![index.root().to_path_buf()];
+    // The index always honors ignore files.
+    let index = options.respect_gitignore.then(|| index.clone());
+    start_session(search_directories, options, reporter, cancel_flag, index)
+}
+
+fn start_session(
+    search_directories: Vec<PathBuf>,
+    options: FileSearchOptions,
+    reporter: Arc<dyn SessionReporter>,
+    cancel_flag: Option<Arc<AtomicBool>>,
+    index: Option<FileIndex>,
 ) -> anyhow::Result<FileSearchSession> {
     let FileSearchOptions {
         limit,
@@ -188,7 +220,7 @@ pub fn create_session(
     thread::spawn(move || matcher_worker(matcher_inner, work_rx, nucleo));
 
     let walker_inner = inner.clone();
-    thread::spawn(move || walker_worker(walker_inner, override_matcher, injector));
+    thread::spawn(move || walker_worker(walker_inner, override_matcher, injector, index));
 
     Ok(FileSearchSession { inner })
 }
@@ -379,29 +411,21 @@ fn get_file_path<'a>(path: &'a Path, search_directories: &[PathBuf]) -> Option<(
     rel_path.to_str().map(|p| (root_idx, p))
 }
 
-fn walker_worker(
-    inner: Arc<SessionInner>,
-    override_matcher: Option<ignore::overrides::Override>,
-    injector: Injector<Arc<str>>,
-) {
-    let Some(first_root) = inner.search_directories.first() else {
-        let _ = inner.work_tx.send(WorkSignal::WalkComplete);
-        return;
-    };
-
-    let mut walk_builder = WalkBuilder::new(first_root);
-    for root in inner.search_directories.iter().skip(1) {
+/// A walk over `roots` configured like every file search.
+fn walk_builder(roots: &[PathBuf], threads: usize, respect_gitignore: bool) -> WalkBuilder {
+    let mut walk_builder = WalkBuilder::new(&roots[0]);
+    for root in roots.iter().skip(1) {
         walk_builder.add(root);
     }
     walk_builder
-        .threads(inner.threads)
+        .threads(threads)
         // Allow hidden entries.
         .hidden(false)
         // Follow symlinks to search their contents.
         .follow_links(true)
         // Don't require git to be present to apply to apply git-related ignore rules.
         .require_git(false);
-    if !inner.respect_gitignore {
+    if !respect_gitignore {
         walk_builder
             .git_ignore(false)
             .git_global(false)
@@ -409,11 +433,55 @@ fn walker_worker(
             .ignore(false)
             .parents(false);
     }
+    walk_builder
+}
+
+fn walker_worker(
+    inner: Arc<SessionInner>,
+    override_matcher: Option<ignore::overrides::Override>,
+    injector: Injector<Arc<str>>,
+    index: Option<FileIndex>,
+) {
+    if inner.search_directories.is_empty() {
+        let _ = inner.work_tx.send(WorkSignal::WalkComplete);
+        return;
+    }
+
+    if let Some(snapshot) = index.as_ref().and_then(FileIndex::snapshot) {
+        for relative_path in snapshot.files() {
+            let full_path = snapshot.root().join(relative_path.as_ref());
+            let excluded = override_matcher
+                .as_ref()
+                .is_some_and(|matcher| matcher.matched(&full_path, false).is_ignore());
+            if excluded {
+                continue;
+            }
+            if let Some(full_path) = full_path.to_str() {
+                injector.push(Arc::from(full_path), |_, cols| {
+                    cols[0] = Utf32String::from(relative_path.as_ref());
+                });
+            }
+        }
+        if let Some(index) = &index {
+            index.refresh_if_older_than(INDEX_SESSION_MAX_AGE);
+        }
+        let _ = inner.work_tx.send(WorkSignal::WalkComplete);
+        return;
+    }
+
+    let mut walk_builder = walk_builder(
+        &inner.search_directories,
+        inner.threads,
+        inner.respect_gitignore,
+    );
+    // Excluded files are left out of the index as well; it only learns from unfiltered walks.
+    let index = index.filter(|_| override_matcher.is_none());
     if let Some(override_matcher) = override_matcher {
         walk_builder.overrides(override_matcher);
     }
 
     let walker = walk_builder.build_parallel();
+    let found: Mutex<Vec<Arc<str>>> = Mutex::new(Vec::new());
 
     walker.run(|| {
         const CHECK_INTERVAL: usize = 1024;
@@ -422,6 +490,7 @@ fn walker_worker(
         let injector = injector.clone();
         let cancelled = inner.cancelled.clone();
         let shutdown = inner.shutdown.clone();
+        let found = index.is_some().then_some(&found);
 
         Box::new(move |entry| {
             let entry = match entry {
@@ -439,6 +508,11 @@ fn walker_worker(
                 injector.push(Arc::from(full_path), |_, cols| {
                     cols[0] = Utf32String::from(relative_path);
                 });
+                if let Some(found) = found
+                    && let Ok(mut found) = found.lock()
+                {
+                    found.push(Arc::from(relative_path));
+                }
             }
             n += 1;
             if n >= CHECK_INTERVAL {
@@ -450,6 +524,14 @@ fn walker_worker(
             ignore::WalkState::Continue
         })
     });
+    let walk_finished =
+        !inner.cancelled.load(Ordering::Relaxed) && !inner.shutdown.load(Ordering::Relaxed);
+    if let Some(index) = index
+        && walk_finished
+        && let Ok(found) = found.into_inner()
+    {
+        index.publish(found);
+    }
     let _ = inner.work_tx.send(WorkSignal::WalkComplete);
 }
 
@@ -830,6 +912,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn indexed_session_stores_its_walk_and_reuses_it() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("alpha.txt"), "alpha").unwrap();
+        fs::write(dir.path().join("beta.txt"), "beta").unwrap();
+        let index = FileIndex::for_root(dir.path());
+
+        let reporter = Arc::new(RecordingReporter::default());
+        let session =
+            create_indexed_session(&index, FileSearchOptions::default(), reporter.clone(), None)
+                .expect("session");
+        session.update_query("alpha");
+        assert!(reporter.wait_for_complete(Duration::from_secs(5)));
+        drop(session);
+        let snapshot = index.snapshot().expect("walk stored in the index");
+        assert_eq!(snapshot.files().len(), 2);
+
+        // A later session matches the indexed files without walking again.
+        fs::remove_file(dir.path().join("alpha.txt")).unwrap();
+        let reporter = Arc::new(RecordingReporter::default());
+        let session =
+            create_indexed_session(&index, FileSearchOptions::default(), reporter.clone(), None)
+                .expect("session");
+        session.update_query("alpha");
+        assert!(reporter.wait_for_complete(Duration::from_secs(5)));
+        assert_eq!(
+            reporter
+                .snapshot()
+                .matches
+                .iter()
+                .map(|file_match| file_match.path.clone())
+                .collect::<Vec<_>>(),
+            vec![PathBuf::from("alpha.txt")]
+        );
+    }
+
     #[test]
     fn session_emits_complete_when_query_changes_with_no_matches() {
         let dir = tempfile::tempdir().unwrap();
@@ -67,32 +67,45 @@ impl BwrapNetworkMode {
 /// returns `command` unchanged so we avoid unnecessary sandboxing overhead.
 /// If network isolation is requested, we still wrap with bubblewrap so network
 /// namespace restrictions apply while preserving full filesystem access.
+///
+/// `device_paths` are mounted with `--dev-bind` so the command can open them;
+/// plain binds do not allow device access.
 pub(crate) fn create_bwrap_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
     options: BwrapOptions,
+    device_paths: &[PathBuf],
 ) -> Result<Vec<String>> {
     if sandbox_policy.has_full_disk_write_access() {
         return if options.network_mode == BwrapNetworkMode::FullAccess {
             Ok(command)
         } else {
-            Ok(create_bwrap_flags_full_filesystem(command, options))
+            Ok(create_bwrap_flags_full_filesystem(
+                command,
+                options,
+                device_paths,
+            ))
         };
     }
 
-    create_bwrap_flags(command, sandbox_policy, cwd, options)
+    create_bwrap_flags(command, sandbox_policy, cwd, options, device_paths)
 }
 
-fn create_bwrap_flags_full_filesystem(command: Vec<String>, options: BwrapOptions) -> Vec<String> {
+fn create_bwrap_flags_full_filesystem(
+    command: Vec<String>,
+    options: BwrapOptions,
+    device_paths: &[PathBuf],
+) -> Vec<String> {
     let mut args = vec![
         "--new-session".to_string(),
         "--die-with-parent".to_string(),
         "--bind".to_string(),
         "/".to_string(),
         "/".to_string(),
-        "--unshare-pid".to_string(),
     ];
+    push_device_binds(&mut args, device_paths);
+    args.push("--unshare-pid".to_string());
     if options.network_mode.should_unshare_network() {
         args.push("--unshare-net".to_string());
     }
@@ -111,11 +124,13 @@ fn create_bwrap_flags(
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
     options: BwrapOptions,
+    device_paths: &[PathBuf],
 ) -> Result<Vec<String>> {
     let mut args = Vec::new();
     args.push("--new-session".to_string());
     args.push("--die-with-parent".to_string());
     args.extend(create_filesystem_args(sandbox_policy, cwd)?);
+    push_device_binds(&mut args, device_paths);
     // Isolate the PID namespace.
     args.push("--unshare-pid".to_string());
     if options.network_mode.should_unshare_network() {
@@ -205,6 +220,14 @@ fn create_filesystem_args(sandbox_policy: &SandboxPolicy, cwd: &Path) -> Result<
     Ok(args)
 }
 
+fn push_device_binds(args: &mut Vec<String>, device_paths: &[PathBuf]) {
+    for device in device_paths {
+        args.push("--dev-bind".to_string());
+        args.push(path_to_string(device));
+        args.push(path_to_string(device));
+    }
+}
+
 /// Collect unique read-only subpaths across all writable roots.
 fn collect_read_only_subpaths(writable_roots: &[WritableRoot]) -> Vec<PathBuf> {
     let mut subpaths: BTreeSet<PathBuf> = BTreeSet::new();
@@ -331,6 +354,7 @@ mod tests {
                 mount_proc: true,
                 network_mode: BwrapNetworkMode::FullAccess,
             },
+            &[],
         )
         .expect("create bwrap args");
 
@@ -348,6 +372,7 @@ mod tests {
                 mount_proc: true,
                 network_mode: BwrapNetworkMode::ProxyOnly,
             },
+            &[],
         )
         .expect("create bwrap args");
 
@@ -368,4 +393,23 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn device_paths_are_dev_bound_after_the_filesystem_view() {
+        let args = create_bwrap_command_args(
+            vec!["/bin/true".to_string()],
+            &SandboxPolicy::new_read_only_policy(),
+            Path::new("/"),
+            BwrapOptions::default(),
+            &[PathBuf::from("/dev/kvm")],
+        )
+        .expect("create bwrap args");
+
+        let dev_binds: Vec<&str> = args
+            .windows(2)
+            .filter(|window| window[0] == "--dev-bind")
+            .map(|window| window[1].as_str())
+            .collect();
+        assert_eq!(dev_binds, vec!["/dev/null", "/dev/kvm"]);
+    }
 }
@@ -4,6 +4,7 @@
 //! Landlock helpers remain available here as legacy/backup utilities.
 use std::collections::BTreeMap;
 use std::path::Path;
+use std::path::PathBuf;
 
 use codex_core::error::CodexErr;
 use codex_core::error::Result;
@@ -38,11 +39,15 @@ use seccompiler::apply_filter;
 /// - installing the network seccomp filter when network access is disabled.
 ///
 /// Filesystem restrictions are intentionally handled by bubblewrap.
+/// `allowed_syscalls` are exempted from the seccomp filter, and
+/// `device_paths` stay writable under the legacy Landlock rules.
 pub(crate) fn apply_sandbox_policy_to_current_thread(
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
     apply_landlock_fs: bool,
     allow_network_for_proxy: bool,
+    allowed_syscalls: &[i64],
+    device_paths: &[PathBuf],
 ) -> Result<()> {
     let install_network_seccomp =
         should_install_network_seccomp(sandbox_policy, allow_network_for_proxy);
@@ -58,7 +63,7 @@ pub(crate) fn apply_sandbox_policy_to_current_thread(
     }
 
     if install_network_seccomp {
-        install_network_seccomp_filter_on_current_thread()?;
+        install_network_seccomp_filter_on_current_thread(allowed_syscalls)?;
     }
 
     if apply_landlock_fs && !sandbox_policy.has_full_disk_write_access() {
@@ -69,17 +74,33 @@ pub(crate) fn apply_sandbox_policy_to_current_thread(
             ));
         }
 
-        let writable_roots = sandbox_policy
+        let mut writable_roots: Vec<AbsolutePathBuf> = sandbox_policy
             .get_writable_roots_with_cwd(cwd)
             .into_iter()
             .map(|writable_root| writable_root.root)
             .collect();
+        for device in device_paths {
+            writable_roots.push(AbsolutePathBuf::from_absolute_path(device)?);
+        }
         install_filesystem_landlock_rules_on_current_thread(writable_roots)?;
     }
 
     Ok(())
 }
 
+/// Maps a syscall name accepted by `--allow-syscall` to its number. Only
+/// syscalls denied by the filter for reasons other than networking can be
+/// exempted; network syscalls follow the sandbox policy.
+pub(crate) fn exemptable_syscall_number(name: &str) -> Option<i64> {
+    match name {
+        "ptrace" => Some(libc::SYS_ptrace),
+        "io_uring_setup" => Some(libc::SYS_io_uring_setup),
+        "io_uring_enter" => Some(libc::SYS_io_uring_enter),
+        "io_uring_register" => Some(libc::SYS_io_uring_register),
+        _ => None,
+    }
+}
+
 fn should_install_network_seccomp(
     sandbox_policy: &SandboxPolicy,
     allow_network_for_proxy: bool,
@@ -139,14 +160,18 @@ fn install_filesystem_landlock_rules_on_current_thread(
 /// AF_UNIX domain sockets.
 ///
 /// The filter is applied to the current thread so only the sandboxed child
-/// inherits it.
-fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(), SandboxErr> {
+/// inherits it. Syscalls in `allowed_syscalls` are left out of the deny list.
+fn install_network_seccomp_filter_on_current_thread(
+    allowed_syscalls: &[i64],
+) -> std::result::Result<(), SandboxErr> {
     // Build rule map.
     let mut rules: BTreeMap<i64, Vec<SeccompRule>> = BTreeMap::new();
 
     // Helper – insert unconditional deny rule for syscall number.
     let mut deny_syscall = |nr: i64| {
-        rules.insert(nr, vec![]); // empty rule vec = unconditional match
+        if !allowed_syscalls.contains(&nr) {
+            rules.insert(nr, vec![]); // empty rule vec = unconditional match
+        }
     };
 
     deny_syscall(libc::SYS_connect);
@@ -203,6 +228,7 @@ fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(),
 
 #[cfg(test)]
 mod tests {
+    use super::exemptable_syscall_number;
     use super::should_install_network_seccomp;
     use codex_core::protocol::SandboxPolicy;
     use pretty_assertions::assert_eq;
@@ -234,4 +260,11 @@ mod tests {
             true
         ));
     }
+
+    #[test]
+    fn only_non_network_syscalls_can_be_exempted() {
+        assert_eq!(exemptable_syscall_number("ptrace"), Some(libc::SYS_ptrace));
+        assert_eq!(exemptable_syscall_number("connect"), None);
+        assert_eq!(exemptable_syscall_number("socket"), None);
+    }
 }
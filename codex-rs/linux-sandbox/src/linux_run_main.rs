@@ -10,6 +10,7 @@ use crate::bwrap::BwrapNetworkMode;
 use crate::bwrap::BwrapOptions;
 use crate::bwrap::create_bwrap_command_args;
 use crate::landlock::apply_sandbox_policy_to_current_thread;
+use crate::landlock::exemptable_syscall_number;
 use crate::vendored_bwrap::exec_vendored_bwrap;
 use crate::vendored_bwrap::run_vendored_bwrap_main;
 
@@ -55,6 +56,15 @@ pub struct LandlockCommand {
     #[arg(long = "no-proc", default_value_t = false)]
     pub no_proc: bool,
 
+    /// Syscall to exempt from the seccomp filter. May be repeated.
+    #[arg(long = "allow-syscall")]
+    pub allow_syscall: Vec<String>,
+
+    /// Device node to mount into the bubblewrap sandbox with device access,
+    /// such as `/dev/kvm`. May be repeated.
+    #[arg(long = "dev-bind")]
+    pub dev_bind: Vec<PathBuf>,
+
     /// Full command args to run under the Linux sandbox helper.
     #[arg(trailing_var_arg = true)]
     pub command: Vec<String>,
@@ -75,6 +85,8 @@ pub fn run_main() -> ! {
         apply_seccomp_then_exec,
         allow_network_for_proxy,
         no_proc,
+        allow_syscall,
+        dev_bind,
         command,
     } = LandlockCommand::parse();
 
@@ -82,6 +94,14 @@ pub fn run_main() -> ! {
         panic!("No command specified to execute.");
     }
 
+    let allowed_syscalls = allow_syscall
+        .iter()
+        .map(|name| {
+            exemptable_syscall_number(name)
+                .unwrap_or_else(|| panic!("unsupported --allow-syscall value: {name}"))
+        })
+        .collect::<Vec<_>>();
+
     // Inner stage: apply seccomp/no_new_privs after bubblewrap has already
     // established the filesystem view.
     if apply_seccomp_then_exec {
@@ -90,6 +110,8 @@ pub fn run_main() -> ! {
             &sandbox_policy_cwd,
             false,
             allow_network_for_proxy,
+            &allowed_syscalls,
+            &dev_bind,
         ) {
             panic!("error applying Linux sandbox restrictions: {e:?}");
         }
@@ -102,6 +124,8 @@ pub fn run_main() -> ! {
             &sandbox_policy_cwd,
             false,
             allow_network_for_proxy,
+            &allowed_syscalls,
+            &dev_bind,
         ) {
             panic!("error applying Linux sandbox restrictions: {e:?}");
         }
@@ -117,6 +141,7 @@ pub fn run_main() -> ! {
             &sandbox_policy,
             use_bwrap_sandbox,
             allow_network_for_proxy,
+            &allow_syscall,
             command,
         );
         run_bwrap_with_proc_fallback(
@@ -125,6 +150,7 @@ pub fn run_main() -> ! {
             inner,
             !no_proc,
             allow_network_for_proxy,
+            &dev_bind,
         );
     }
 
@@ -134,6 +160,8 @@ pub fn run_main() -> ! {
         &sandbox_policy_cwd,
         true,
         allow_network_for_proxy,
+        &allowed_syscalls,
+        &dev_bind,
     ) {
         panic!("error applying legacy Linux sandbox restrictions: {e:?}");
     }
@@ -146,6 +174,7 @@ fn run_bwrap_with_proc_fallback(
     inner: Vec<String>,
     mount_proc: bool,
     allow_network_for_proxy: bool,
+    device_paths: &[PathBuf],
 ) -> ! {
     let mut mount_proc = mount_proc;
 
@@ -159,7 +188,13 @@ fn run_bwrap_with_proc_fallback(
         mount_proc,
         network_mode,
     };
-    let argv = build_bwrap_argv(inner, sandbox_policy, sandbox_policy_cwd, options);
+    let argv = build_bwrap_argv(
+        inner,
+        sandbox_policy,
+        sandbox_policy_cwd,
+        options,
+        device_paths,
+    );
     exec_vendored_bwrap(argv);
 }
 
@@ -181,9 +216,16 @@ fn build_bwrap_argv(
     sandbox_policy: &codex_core::protocol::SandboxPolicy,
     sandbox_policy_cwd: &Path,
     options: BwrapOptions,
+    device_paths: &[PathBuf],
 ) -> Vec<String> {
-    let mut args = create_bwrap_command_args(inner, sandbox_policy, sandbox_policy_cwd, options)
-        .unwrap_or_else(|err| panic!("error building bubblewrap command: {err:?}"));
+    let mut args = create_bwrap_command_args(
+        inner,
+        sandbox_policy,
+        sandbox_policy_cwd,
+        options,
+        device_paths,
+    )
+    .unwrap_or_else(|err| panic!("error building bubblewrap command: {err:?}"));
 
     let command_separator_index = args
         .iter()
@@ -212,6 +254,7 @@ fn preflight_proc_mount_support(
             mount_proc: true,
             network_mode: BwrapNetworkMode::FullAccess,
         },
+        &[],
     );
     let stderr = run_bwrap_in_child_capture_stderr(preflight_argv);
     !is_proc_mount_failure(stderr.as_str())
@@ -316,6 +359,7 @@ fn build_inner_seccomp_command(
     sandbox_policy: &codex_core::protocol::SandboxPolicy,
     use_bwrap_sandbox: bool,
     allow_network_for_proxy: bool,
+    allowed_syscalls: &[String],
     command: Vec<String>,
 ) -> Vec<String> {
     let current_exe = match std::env::current_exe() {
@@ -341,6 +385,10 @@ fn build_inner_seccomp_command(
     if allow_network_for_proxy {
         inner.push("--allow-network-for-proxy".to_string());
     }
+    for syscall in allowed_syscalls {
+        inner.push("--allow-syscall".to_string());
+        inner.push(syscall.clone());
+    }
     inner.push("--".to_string());
     inner.extend(command);
     inner
@@ -397,6 +445,7 @@ mod tests {
                 mount_proc: true,
                 network_mode: BwrapNetworkMode::FullAccess,
             },
+            &[],
         );
         assert_eq!(
             argv,
@@ -431,6 +480,7 @@ mod tests {
                 mount_proc: true,
                 network_mode: BwrapNetworkMode::Isolated,
             },
+            &[],
         );
         assert_eq!(argv.contains(&"--unshare-net".to_string()), true);
     }
@@ -445,6 +495,7 @@ mod tests {
                 mount_proc: true,
                 network_mode: BwrapNetworkMode::ProxyOnly,
             },
+            &[],
         );
         assert_eq!(argv.contains(&"--unshare-net".to_string()), true);
     }
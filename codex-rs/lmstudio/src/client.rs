@@ -3,6 +3,16 @@ use codex_core::config::Config;
 use std::io;
 use std::path::Path;
 
+/// Capabilities a local LM Studio server reports for a single model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelCapabilities {
+    /// Context length in tokens: the loaded context length when the model is loaded, otherwise
+    /// the model's maximum.
+    pub context_window: Option<i64>,
+    /// Whether the model supports native tool calling.
+    pub supports_tool_calls: bool,
+}
+
 #[derive(Clone)]
 pub struct LMStudioClient {
     client: reqwest::Client,
@@ -123,6 +133,47 @@ impl LMStudioClient {
         }
     }
 
+    /// Query LM Studio's native REST API (`/api/v0/models/{model}`) for the model's context size
+    /// and tool calling support.
+    ///
+    /// Returns `None` when the model is unknown or the server predates capability reporting.
+    pub async fn fetch_model_capabilities(
+        &self,
+        model: &str,
+    ) -> io::Result<Option<ModelCapabilities>> {
+        // The provider points at the OpenAI-compatible `/v1` API; the native API sits beside it.
+        let api_root = self
+            .base_url
+            .trim_end_matches('/')
+            .trim_end_matches("/v1")
+            .to_string();
+        let url = format!("{api_root}/api/v0/models/{model}");
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| io::Error::other(format!("Request failed: {e}")))?;
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+        let json: serde_json::Value = response.json().await.map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("JSON parse error: {e}"))
+        })?;
+        let Some(capabilities) = json["capabilities"].as_array() else {
+            return Ok(None);
+        };
+        let context_window = json["loaded_context_length"]
+            .as_i64()
+            .or_else(|| json["max_context_length"].as_i64());
+        Ok(Some(ModelCapabilities {
+            context_window,
+            supports_tool_calls: capabilities
+                .iter()
+                .any(|capability| capability.as_str() == Some("tool_use")),
+        }))
+    }
+
     // Find lms, checking fallback paths if not in PATH
     fn find_lms() -> std::io::Result<String> {
         Self::find_lms_with_home_dir(None)
@@ -299,6 +350,83 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_fetch_model_capabilities() {
+        if std::env::var(codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok() {
+            tracing::info!(
+                "{} is set; skipping test_fetch_model_capabilities",
+                codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR
+            );
+            return;
+        }
+
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .and(wiremock::matchers::path(
+                "/api/v0/models/qwen2.5-7b-instruct",
+            ))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_raw(
+                    serde_json::json!({
+                        "id": "qwen2.5-7b-instruct",
+                        "state": "loaded",
+                        "max_context_length": 32768,
+                        "loaded_context_length": 8192,
+                        "capabilities": ["tool_use"]
+                    })
+                    .to_string(),
+                    "application/json",
+                ),
+            )
+            .mount(&server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .and(wiremock::matchers::path("/api/v0/models/gemma-2-2b"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_raw(
+                    serde_json::json!({
+                        "id": "gemma-2-2b",
+                        "state": "not-loaded",
+                        "max_context_length": 4096,
+                        "capabilities": []
+                    })
+                    .to_string(),
+                    "application/json",
+                ),
+            )
+            .mount(&server)
+            .await;
+
+        let client = LMStudioClient::from_host_root(format!("{}/v1", server.uri()));
+        assert_eq!(
+            client
+                .fetch_model_capabilities("qwen2.5-7b-instruct")
+                .await
+                .expect("fetch capabilities"),
+            Some(ModelCapabilities {
+                context_window: Some(8192),
+                supports_tool_calls: true,
+            })
+        );
+        assert_eq!(
+            client
+                .fetch_model_capabilities("gemma-2-2b")
+                .await
+                .expect("fetch capabilities"),
+            Some(ModelCapabilities {
+                context_window: Some(4096),
+                supports_tool_calls: false,
+            })
+        );
+        assert_eq!(
+            client
+                .fetch_model_capabilities("missing")
+                .await
+                .expect("fetch capabilities"),
+            None
+        );
+    }
+
     #[tokio::test]
     async fn test_check_server_happy_path() {
         if std::env::var(codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok() {
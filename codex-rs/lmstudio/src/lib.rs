@@ -1,6 +1,7 @@
 mod client;
 
 pub use client::LMStudioClient;
+pub use client::ModelCapabilities;
 use codex_core::config::Config;
 
 /// Default OSS model to use when `--oss` is passed without an explicit `-m`.
@@ -44,3 +45,27 @@ pub async fn ensure_oss_ready(config: &Config) -> std::io::Result<()> {
 
     Ok(())
 }
+
+/// Fill in the context window and tool calling support of the configured model from what the
+/// local LM Studio server reports. Values set explicitly in the config are left untouched.
+pub async fn apply_model_capabilities(config: &mut Config) -> std::io::Result<()> {
+    let model = config
+        .model
+        .clone()
+        .unwrap_or_else(|| DEFAULT_OSS_MODEL.to_string());
+    let lmstudio_client = LMStudioClient::try_from_provider(config).await?;
+    let Some(capabilities) = lmstudio_client.fetch_model_capabilities(&model).await? else {
+        return Ok(());
+    };
+
+    if config.model_context_window.is_none() {
+        config.model_context_window = capabilities.context_window;
+    }
+    if config.model_supports_tool_calls.is_none() {
+        if !capabilities.supports_tool_calls {
+            tracing::info!("{model} does not support tool calling; emulating tools via the prompt");
+        }
+        config.model_supports_tool_calls = Some(capabilities.supports_tool_calls);
+    }
+    Ok(())
+}
@@ -492,6 +492,13 @@ pub(crate) struct ExchangedTokens {
     pub refresh_token: String,
 }
 
+/// Client for the requests a login makes, going through the proxy from `[proxy]` when one is set.
+pub(crate) fn http_client() -> io::Result<reqwest::Client> {
+    codex_client::apply_proxy_settings(reqwest::Client::builder())
+        .build()
+        .map_err(io::Error::other)
+}
+
 pub(crate) async fn exchange_code_for_tokens(
     issuer: &str,
     client_id: &str,
@@ -506,7 +513,7 @@ pub(crate) async fn exchange_code_for_tokens(
         refresh_token: String,
     }
 
-    let client = reqwest::Client::new();
+    let client = http_client()?;
     let resp = client
         .post(format!("{issuer}/oauth/token"))
         .header("Content-Type", "application/x-www-form-urlencoded")
@@ -697,7 +704,7 @@ pub(crate) async fn obtain_api_key(
     struct ExchangeResp {
         access_token: String,
     }
-    let client = reqwest::Client::new();
+    let client = http_client()?;
     let resp = client
         .post(format!("{issuer}/oauth/token"))
         .header("Content-Type", "application/x-www-form-urlencoded")
@@ -3,6 +3,7 @@
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
 use codex_core::protocol::AskForApproval;
+use codex_core::protocol::ReviewDecision;
 use codex_protocol::ThreadId;
 use codex_protocol::config_types::SandboxMode;
 use codex_utils_json_to_toml::json_to_toml;
@@ -255,6 +256,132 @@ pub(crate) fn create_tool_for_codex_tool_call_reply_param() -> Tool {
     }
 }
 
+/// Builds a `Tool` definition for the `codex-list-sessions` tool-call.
+pub(crate) fn create_tool_for_codex_list_sessions() -> Tool {
+    let mut input_schema = JsonObject::new();
+    input_schema.insert("type".to_string(), serde_json::json!("object"));
+    input_schema.insert("properties".to_string(), serde_json::json!({}));
+
+    Tool {
+        name: "codex-list-sessions".into(),
+        title: Some("Codex Sessions".to_string()),
+        input_schema: Arc::new(input_schema),
+        output_schema: None,
+        description: Some(
+            "List the Codex sessions started by this server with their status.".into(),
+        ),
+        annotations: None,
+        execution: None,
+        icons: None,
+        meta: None,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexSessionStatusParam {
+    /// The thread id for this Codex session.
+    pub thread_id: String,
+}
+
+/// Builds a `Tool` definition for the `codex-session-status` tool-call.
+pub(crate) fn create_tool_for_codex_session_status_param() -> Tool {
+    let schema = SchemaSettings::draft2019_09()
+        .with(|s| {
+            s.inline_subschemas = true;
+            s.option_add_null_type = false;
+        })
+        .into_generator()
+        .into_root_schema_for::<CodexSessionStatusParam>();
+
+    let input_schema =
+        create_tool_input_schema(schema, "Codex session status tool schema should serialize");
+
+    Tool {
+        name: "codex-session-status".into(),
+        title: Some("Codex Session Status".to_string()),
+        input_schema,
+        output_schema: None,
+        description: Some(
+            "Report whether a Codex session is idle, running, or waiting for an exec approval, \
+             with its pending approvals and last agent message."
+                .into(),
+        ),
+        annotations: None,
+        execution: None,
+        icons: None,
+        meta: None,
+    }
+}
+
+/// Custom enum mirroring the [`ReviewDecision`] variants an orchestrator can
+/// pick, with `JsonSchema` support.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CodexToolCallExecDecision {
+    Approved,
+    ApprovedForSession,
+    Denied,
+    Abort,
+}
+
+impl From<CodexToolCallExecDecision> for ReviewDecision {
+    fn from(value: CodexToolCallExecDecision) -> Self {
+        match value {
+            CodexToolCallExecDecision::Approved => ReviewDecision::Approved,
+            CodexToolCallExecDecision::ApprovedForSession => ReviewDecision::ApprovedForSession,
+            CodexToolCallExecDecision::Denied => ReviewDecision::Denied,
+            CodexToolCallExecDecision::Abort => ReviewDecision::Abort,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexApproveExecParam {
+    /// The thread id for this Codex session.
+    pub thread_id: String,
+
+    /// The approval to answer, as reported by `codex-session-status`.
+    /// Defaults to the oldest pending approval.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_id: Option<String>,
+
+    /// Decision for the command: `approved`, `approved-for-session`, `denied`,
+    /// or `abort`.
+    pub decision: CodexToolCallExecDecision,
+}
+
+/// Builds a `Tool` definition for the `codex-approve-exec` tool-call.
+pub(crate) fn create_tool_for_codex_approve_exec_param() -> Tool {
+    let schema = SchemaSettings::draft2019_09()
+        .with(|s| {
+            s.inline_subschemas = true;
+            s.option_add_null_type = false;
+        })
+        .into_generator()
+        .into_root_schema_for::<CodexApproveExecParam>();
+
+    let input_schema =
+        create_tool_input_schema(schema, "Codex approve exec tool schema should serialize");
+
+    Tool {
+        name: "codex-approve-exec".into(),
+        title: Some("Codex Approve Exec".to_string()),
+        input_schema,
+        output_schema: None,
+        description: Some(
+            "Answer a command approval that a Codex session is waiting on, for clients that do \
+             not support elicitation."
+                .into(),
+        ),
+        annotations: None,
+        execution: None,
+        icons: None,
+        meta: None,
+    }
+}
+
 fn create_tool_input_schema(
     schema: schemars::schema::RootSchema,
     panic_message: &str,
@@ -428,4 +555,43 @@ mod tests {
         });
         assert_eq!(expected_tool_json, tool_json);
     }
+
+    #[test]
+    fn verify_codex_approve_exec_json_schema() {
+        let tool = create_tool_for_codex_approve_exec_param();
+        let tool_json = serde_json::to_value(&tool).expect("tool serializes");
+        let expected_tool_json = serde_json::json!({
+          "description": "Answer a command approval that a Codex session is waiting on, for clients that do not support elicitation.",
+          "inputSchema": {
+            "properties": {
+              "approvalId": {
+                "description": "The approval to answer, as reported by `codex-session-status`. Defaults to the oldest pending approval.",
+                "type": "string"
+              },
+              "decision": {
+                "description": "Decision for the command: `approved`, `approved-for-session`, `denied`, or `abort`.",
+                "enum": [
+                  "approved",
+                  "approved-for-session",
+                  "denied",
+                  "abort"
+                ],
+                "type": "string"
+              },
+              "threadId": {
+                "description": "The thread id for this Codex session.",
+                "type": "string"
+              }
+            },
+            "required": [
+              "decision",
+              "threadId"
+            ],
+            "type": "object"
+          },
+          "name": "codex-approve-exec",
+          "title": "Codex Approve Exec"
+        });
+        assert_eq!(expected_tool_json, tool_json);
+    }
 }
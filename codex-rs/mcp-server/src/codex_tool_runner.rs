@@ -9,6 +9,8 @@ use crate::exec_approval::handle_exec_approval_request;
 use crate::outgoing_message::OutgoingMessageSender;
 use crate::outgoing_message::OutgoingNotificationMeta;
 use crate::patch_approval::handle_patch_approval_request;
+use crate::session_registry::PendingExecApproval;
+use crate::session_registry::SessionRegistry;
 use codex_core::CodexThread;
 use codex_core::NewThread;
 use codex_core::ThreadManager;
@@ -19,6 +21,7 @@ use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExecApprovalRequestEvent;
 use codex_core::protocol::Op;
+use codex_core::protocol::ReviewDecision;
 use codex_core::protocol::Submission;
 use codex_core::protocol::TurnCompleteEvent;
 use codex_protocol::ThreadId;
@@ -63,6 +66,7 @@ pub async fn run_codex_tool_session(
     outgoing: Arc<OutgoingMessageSender>,
     thread_manager: Arc<ThreadManager>,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ThreadId>>>,
+    sessions: Arc<SessionRegistry>,
 ) {
     let NewThread {
         thread_id,
@@ -82,6 +86,14 @@ pub async fn run_codex_tool_session(
         }
     };
 
+    sessions
+        .register(
+            thread_id,
+            session_configured.model.clone(),
+            session_configured.cwd.clone(),
+        )
+        .await;
+
     let session_configured_event = Event {
         // Use a fake id value for now.
         id: "".to_string(),
@@ -105,6 +117,7 @@ pub async fn run_codex_tool_session(
         .lock()
         .await
         .insert(id.clone(), thread_id);
+    sessions.turn_started(thread_id).await;
     let submission = Submission {
         id: sub_id.clone(),
         op: Op::UserInput {
@@ -127,6 +140,7 @@ pub async fn run_codex_tool_session(
         outgoing.send_response(id.clone(), result).await;
         // unregister the id so we don't keep it in the map
         running_requests_id_to_codex_uuid.lock().await.remove(&id);
+        sessions.turn_finished(thread_id, None).await;
         return;
     }
 
@@ -136,6 +150,7 @@ pub async fn run_codex_tool_session(
         outgoing,
         id,
         running_requests_id_to_codex_uuid,
+        sessions,
     )
     .await;
 }
@@ -147,11 +162,13 @@ pub async fn run_codex_tool_session_reply(
     request_id: RequestId,
     prompt: String,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ThreadId>>>,
+    sessions: Arc<SessionRegistry>,
 ) {
     running_requests_id_to_codex_uuid
         .lock()
         .await
         .insert(request_id.clone(), thread_id);
+    sessions.turn_started(thread_id).await;
     if let Err(e) = thread
         .submit(Op::UserInput {
             items: vec![UserInput::Text {
@@ -175,6 +192,7 @@ pub async fn run_codex_tool_session_reply(
             .lock()
             .await
             .remove(&request_id);
+        sessions.turn_finished(thread_id, None).await;
         return;
     }
 
@@ -184,6 +202,7 @@ pub async fn run_codex_tool_session_reply(
         outgoing,
         request_id,
         running_requests_id_to_codex_uuid,
+        sessions,
     )
     .await;
 }
@@ -194,6 +213,7 @@ async fn run_codex_tool_session_inner(
     outgoing: Arc<OutgoingMessageSender>,
     request_id: RequestId,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ThreadId>>>,
+    sessions: Arc<SessionRegistry>,
 ) {
     let request_id_str = request_id.to_string();
 
@@ -221,11 +241,23 @@ async fn run_codex_tool_session_inner(
                             cwd,
                             call_id,
                             approval_id: _,
-                            reason: _,
+                            reason,
                             proposed_execpolicy_amendment: _,
                             parsed_cmd,
                             network_approval_context: _,
                         } = ev;
+                        sessions
+                            .add_pending_exec_approval(
+                                thread_id,
+                                PendingExecApproval {
+                                    approval_id: approval_id.clone(),
+                                    turn_id: event.id.clone(),
+                                    command: command.clone(),
+                                    cwd: cwd.clone(),
+                                    reason,
+                                },
+                            )
+                            .await;
                         handle_exec_approval_request(
                             command,
                             cwd,
@@ -238,6 +270,7 @@ async fn run_codex_tool_session_inner(
                             approval_id,
                             parsed_cmd,
                             thread_id,
+                            sessions.clone(),
                         )
                         .await;
                         continue;
@@ -253,6 +286,7 @@ async fn run_codex_tool_session_inner(
                             Some(true),
                         );
                         outgoing.send_response(request_id.clone(), result).await;
+                        sessions.turn_finished(thread_id, None).await;
                         break;
                     }
                     EventMsg::Warning(_) => {
@@ -262,6 +296,32 @@ async fn run_codex_tool_session_inner(
                         // TODO: forward elicitation requests to the client?
                         continue;
                     }
+                    EventMsg::BudgetLimitReached(ev) => {
+                        // MCP clients cannot confirm budget overruns, so stop the turn.
+                        if let Err(e) = thread
+                            .submit(Op::BudgetApproval {
+                                id: ev.id,
+                                decision: ReviewDecision::Denied,
+                            })
+                            .await
+                        {
+                            tracing::error!("Failed to decline budget limit: {e}");
+                        }
+                        continue;
+                    }
+                    EventMsg::AuthExpired(ev) => {
+                        // MCP clients cannot sign in again mid-turn, so stop the turn.
+                        if let Err(e) = thread
+                            .submit(Op::ReauthComplete {
+                                id: ev.id,
+                                success: false,
+                            })
+                            .await
+                        {
+                            tracing::error!("Failed to decline re-authentication: {e}");
+                        }
+                        continue;
+                    }
                     EventMsg::ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent {
                         call_id,
                         turn_id: _,
@@ -287,6 +347,9 @@ async fn run_codex_tool_session_inner(
                     EventMsg::TurnComplete(TurnCompleteEvent {
                         last_agent_message, ..
                     }) => {
+                        sessions
+                            .turn_finished(thread_id, last_agent_message.clone())
+                            .await;
                         let text = match last_agent_message {
                             Some(msg) => msg,
                             None => "".to_string(),
@@ -300,6 +363,9 @@ async fn run_codex_tool_session_inner(
                             .remove(&request_id);
                         break;
                     }
+                    EventMsg::TurnAborted(_) => {
+                        sessions.turn_finished(thread_id, None).await;
+                    }
                     EventMsg::SessionConfigured(_) => {
                         tracing::error!("unexpected SessionConfigured event");
                     }
@@ -312,7 +378,9 @@ async fn run_codex_tool_session_inner(
                     EventMsg::AgentReasoningDelta(_) => {
                         // TODO: think how we want to support this in the MCP
                     }
-                    EventMsg::McpStartupUpdate(_) | EventMsg::McpStartupComplete(_) => {
+                    EventMsg::McpStartupUpdate(_)
+                    | EventMsg::McpStartupComplete(_)
+                    | EventMsg::McpServerHealth(_) => {
                         // Ignored in MCP tool runner.
                     }
                     EventMsg::AgentMessage(AgentMessageEvent { .. }) => {
@@ -324,10 +392,17 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::TokenCount(_)
                     | EventMsg::AgentReasoning(_)
                     | EventMsg::AgentReasoningSectionBreak(_)
+                    | EventMsg::McpToolCallQueued(_)
                     | EventMsg::McpToolCallBegin(_)
                     | EventMsg::McpToolCallEnd(_)
                     | EventMsg::McpListToolsResponse(_)
+                    | EventMsg::ListMcpPromptsResponse(_)
+                    | EventMsg::GetMcpPromptResponse(_)
+                    | EventMsg::GetMcpServerLogsResponse(_)
+                    | EventMsg::McpListChanged(_)
                     | EventMsg::ListCustomPromptsResponse(_)
+                    | EventMsg::GetUsageResponse(_)
+                    | EventMsg::PinnedContextUpdated(_)
                     | EventMsg::ListSkillsResponse(_)
                     | EventMsg::ListRemoteSkillsResponse(_)
                     | EventMsg::RemoteSkillDownloaded(_)
@@ -340,11 +415,11 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::PatchApplyBegin(_)
                     | EventMsg::PatchApplyEnd(_)
                     | EventMsg::TurnDiff(_)
+                    | EventMsg::TurnStructuredOutput(_)
                     | EventMsg::WebSearchBegin(_)
                     | EventMsg::WebSearchEnd(_)
                     | EventMsg::GetHistoryEntryResponse(_)
                     | EventMsg::PlanUpdate(_)
-                    | EventMsg::TurnAborted(_)
                     | EventMsg::UserMessage(_)
                     | EventMsg::ShutdownComplete
                     | EventMsg::ViewImageToolCall(_)
@@ -356,6 +431,7 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::ReasoningContentDelta(_)
                     | EventMsg::ReasoningRawContentDelta(_)
                     | EventMsg::SkillsUpdateAvailable
+                    | EventMsg::ConfigReloaded(_)
                     | EventMsg::UndoStarted(_)
                     | EventMsg::UndoCompleted(_)
                     | EventMsg::ExitedReviewMode(_)
@@ -363,6 +439,8 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::DynamicToolCallRequest(_)
                     | EventMsg::ContextCompacted(_)
                     | EventMsg::ModelReroute(_)
+                    | EventMsg::ModelProviderFallback(_)
+                    | EventMsg::StreamRetry(_)
                     | EventMsg::ThreadRolledBack(_)
                     | EventMsg::CollabAgentSpawnBegin(_)
                     | EventMsg::CollabAgentSpawnEnd(_)
@@ -374,7 +452,17 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::CollabCloseEnd(_)
                     | EventMsg::CollabResumeBegin(_)
                     | EventMsg::CollabResumeEnd(_)
-                    | EventMsg::DeprecationNotice(_) => {
+                    | EventMsg::DelegateBegin(_)
+                    | EventMsg::DelegateProgress(_)
+                    | EventMsg::DelegateEnd(_)
+                    | EventMsg::DeprecationNotice(_)
+                    | EventMsg::ProjectDocsLoaded(_)
+                    | EventMsg::CheckpointCreated(_)
+                    | EventMsg::WorktreeCreated(_)
+                    | EventMsg::WorktreeMerged(_)
+                    | EventMsg::TurnSettings(_)
+                    | EventMsg::UserInputQueueUpdated(_)
+                    | EventMsg::Unknown => {
                         // For now, we do not do anything extra for these
                         // events. Note that
                         // send(codex_event_to_notification(&event)) above has
@@ -391,6 +479,7 @@ async fn run_codex_tool_session_inner(
                     Some(true),
                 );
                 outgoing.send_response(request_id.clone(), result).await;
+                sessions.turn_finished(thread_id, None).await;
                 break;
             }
         }
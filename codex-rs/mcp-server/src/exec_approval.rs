@@ -14,6 +14,8 @@ use serde_json::Value;
 use serde_json::json;
 use tracing::error;
 
+use crate::session_registry::SessionRegistry;
+
 /// Conforms to the MCP elicitation request params shape, so it can be used as
 /// the `params` field of an `elicitation/create` request.
 #[derive(Debug, Deserialize, Serialize)]
@@ -60,6 +62,7 @@ pub(crate) async fn handle_exec_approval_request(
     approval_id: String,
     codex_parsed_cmd: Vec<ParsedCommand>,
     thread_id: ThreadId,
+    sessions: Arc<SessionRegistry>,
 ) {
     let escaped_command =
         shlex::try_join(command.iter().map(String::as_str)).unwrap_or_else(|_| command.join(" "));
@@ -104,7 +107,15 @@ pub(crate) async fn handle_exec_approval_request(
         let approval_id = approval_id.clone();
         let event_id = event_id.clone();
         tokio::spawn(async move {
-            on_exec_approval_response(approval_id, event_id, on_response, codex).await;
+            on_exec_approval_response(
+                approval_id,
+                event_id,
+                on_response,
+                codex,
+                thread_id,
+                sessions,
+            )
+            .await;
         });
     }
 }
@@ -114,6 +125,8 @@ async fn on_exec_approval_response(
     event_id: String,
     receiver: tokio::sync::oneshot::Receiver<serde_json::Value>,
     codex: Arc<CodexThread>,
+    thread_id: ThreadId,
+    sessions: Arc<SessionRegistry>,
 ) {
     let response = receiver.await;
     let value = match response {
@@ -134,6 +147,15 @@ async fn on_exec_approval_response(
         }
     });
 
+    // The approval may already have been answered with the `codex-approve-exec` tool.
+    if sessions
+        .take_pending_exec_approval(thread_id, Some(&approval_id))
+        .await
+        .is_none()
+    {
+        return;
+    }
+
     if let Err(err) = codex
         .submit(Op::ExecApproval {
             id: approval_id,
@@ -6,8 +6,10 @@ use codex_core::ThreadManager;
 use codex_core::config::Config;
 use codex_core::default_client::USER_AGENT_SUFFIX;
 use codex_core::default_client::get_codex_user_agent;
+use codex_core::protocol::Op;
 use codex_core::protocol::Submission;
 use codex_protocol::ThreadId;
+use codex_protocol::protocol::PROTOCOL_VERSION;
 use codex_protocol::protocol::SessionSource;
 use rmcp::model::CallToolRequestParams;
 use rmcp::model::CallToolResult;
@@ -29,11 +31,17 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::task;
 
+use crate::codex_tool_config::CodexApproveExecParam;
+use crate::codex_tool_config::CodexSessionStatusParam;
 use crate::codex_tool_config::CodexToolCallParam;
 use crate::codex_tool_config::CodexToolCallReplyParam;
+use crate::codex_tool_config::create_tool_for_codex_approve_exec_param;
+use crate::codex_tool_config::create_tool_for_codex_list_sessions;
+use crate::codex_tool_config::create_tool_for_codex_session_status_param;
 use crate::codex_tool_config::create_tool_for_codex_tool_call_param;
 use crate::codex_tool_config::create_tool_for_codex_tool_call_reply_param;
 use crate::outgoing_message::OutgoingMessageSender;
+use crate::session_registry::SessionRegistry;
 
 pub(crate) struct MessageProcessor {
     outgoing: Arc<OutgoingMessageSender>,
@@ -41,6 +49,7 @@ pub(crate) struct MessageProcessor {
     codex_linux_sandbox_exe: Option<PathBuf>,
     thread_manager: Arc<ThreadManager>,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ThreadId>>>,
+    sessions: Arc<SessionRegistry>,
 }
 
 impl MessageProcessor {
@@ -53,7 +62,7 @@ impl MessageProcessor {
     ) -> Self {
         let outgoing = Arc::new(outgoing);
         let auth_manager = AuthManager::shared(
-            config.codex_home.clone(),
+            config.auth_home(),
             false,
             config.cli_auth_credentials_store_mode,
         );
@@ -68,6 +77,7 @@ impl MessageProcessor {
             codex_linux_sandbox_exe,
             thread_manager,
             running_requests_id_to_codex_uuid: Arc::new(Mutex::new(HashMap::new())),
+            sessions: Arc::new(SessionRegistry::default()),
         }
     }
 
@@ -232,6 +242,10 @@ impl MessageProcessor {
         };
         if let serde_json::Value::Object(ref mut obj) = server_info_value {
             obj.insert("user_agent".to_string(), json!(get_codex_user_agent()));
+            obj.insert(
+                "codex_protocol_version".to_string(),
+                json!(PROTOCOL_VERSION),
+            );
         }
 
         let mut result_value = match serde_json::to_value(InitializeResult {
@@ -312,6 +326,9 @@ impl MessageProcessor {
             tools: vec![
                 create_tool_for_codex_tool_call_param(),
                 create_tool_for_codex_tool_call_reply_param(),
+                create_tool_for_codex_list_sessions(),
+                create_tool_for_codex_session_status_param(),
+                create_tool_for_codex_approve_exec_param(),
             ],
             next_cursor: None,
         };
@@ -331,6 +348,15 @@ impl MessageProcessor {
                 self.handle_tool_call_codex_session_reply(id, arguments)
                     .await
             }
+            "codex-list-sessions" => self.handle_tool_call_codex_list_sessions(id).await,
+            "codex-session-status" => {
+                self.handle_tool_call_codex_session_status(id, arguments)
+                    .await
+            }
+            "codex-approve-exec" => {
+                self.handle_tool_call_codex_approve_exec(id, arguments)
+                    .await
+            }
             _ => {
                 let result = CallToolResult {
                     content: vec![rmcp::model::Content::text(format!("Unknown tool '{name}'"))],
@@ -400,6 +426,7 @@ impl MessageProcessor {
         let outgoing = self.outgoing.clone();
         let thread_manager = self.thread_manager.clone();
         let running_requests_id_to_codex_uuid = self.running_requests_id_to_codex_uuid.clone();
+        let sessions = self.sessions.clone();
 
         // Spawn an async task to handle the Codex session so that we do not
         // block the synchronous message-processing loop.
@@ -412,6 +439,7 @@ impl MessageProcessor {
                 outgoing,
                 thread_manager,
                 running_requests_id_to_codex_uuid,
+                sessions,
             )
             .await;
         });
@@ -500,6 +528,7 @@ impl MessageProcessor {
         tokio::spawn({
             let outgoing = outgoing.clone();
             let running_requests_id_to_codex_uuid = running_requests_id_to_codex_uuid.clone();
+            let sessions = self.sessions.clone();
 
             async move {
                 crate::codex_tool_runner::run_codex_tool_session_reply(
@@ -509,12 +538,111 @@ impl MessageProcessor {
                     request_id,
                     prompt,
                     running_requests_id_to_codex_uuid,
+                    sessions,
                 )
                 .await;
             }
         });
     }
 
+    async fn handle_tool_call_codex_list_sessions(&self, request_id: RequestId) {
+        let sessions = self.sessions.summaries().await;
+        let result = create_call_tool_result_with_json(json!({ "sessions": sessions }));
+        self.outgoing.send_response(request_id, result).await;
+    }
+
+    async fn handle_tool_call_codex_session_status(
+        &self,
+        request_id: RequestId,
+        arguments: Option<rmcp::model::JsonObject>,
+    ) {
+        let param = match parse_tool_arguments::<CodexSessionStatusParam>(
+            "codex-session-status",
+            arguments,
+        ) {
+            Ok(param) => param,
+            Err(result) => {
+                self.outgoing.send_response(request_id, result).await;
+                return;
+            }
+        };
+        let result = match ThreadId::from_string(&param.thread_id) {
+            Ok(thread_id) => match self.sessions.summary(thread_id).await {
+                Some(summary) => create_call_tool_result_with_json(json!(summary)),
+                None => create_error_call_tool_result(format!(
+                    "Session not found for thread_id: {thread_id}"
+                )),
+            },
+            Err(e) => create_error_call_tool_result(format!("Failed to parse thread_id: {e}")),
+        };
+        self.outgoing.send_response(request_id, result).await;
+    }
+
+    async fn handle_tool_call_codex_approve_exec(
+        &self,
+        request_id: RequestId,
+        arguments: Option<rmcp::model::JsonObject>,
+    ) {
+        let param =
+            match parse_tool_arguments::<CodexApproveExecParam>("codex-approve-exec", arguments) {
+                Ok(param) => param,
+                Err(result) => {
+                    self.outgoing.send_response(request_id, result).await;
+                    return;
+                }
+            };
+        let result = self.approve_exec(param).await;
+        self.outgoing.send_response(request_id, result).await;
+    }
+
+    async fn approve_exec(&self, param: CodexApproveExecParam) -> CallToolResult {
+        let CodexApproveExecParam {
+            thread_id,
+            approval_id,
+            decision,
+        } = param;
+        let thread_id = match ThreadId::from_string(&thread_id) {
+            Ok(thread_id) => thread_id,
+            Err(e) => {
+                return create_error_call_tool_result(format!("Failed to parse thread_id: {e}"));
+            }
+        };
+        let thread = match self.thread_manager.get_thread(thread_id).await {
+            Ok(thread) => thread,
+            Err(_) => {
+                return create_error_call_tool_result(format!(
+                    "Session not found for thread_id: {thread_id}"
+                ));
+            }
+        };
+        let Some(pending) = self
+            .sessions
+            .take_pending_exec_approval(thread_id, approval_id.as_deref())
+            .await
+        else {
+            return create_error_call_tool_result(match approval_id {
+                Some(approval_id) => format!("No pending exec approval '{approval_id}'"),
+                None => format!("No pending exec approval for thread_id: {thread_id}"),
+            });
+        };
+
+        if let Err(e) = thread
+            .submit(Op::ExecApproval {
+                id: pending.approval_id.clone(),
+                turn_id: Some(pending.turn_id.clone()),
+                decision: decision.into(),
+            })
+            .await
+        {
+            return create_error_call_tool_result(format!("Failed to submit exec approval: {e}"));
+        }
+        create_call_tool_result_with_json(json!({
+            "threadId": thread_id,
+            "approvalId": pending.approval_id,
+            "command": pending.command,
+        }))
+    }
+
     fn handle_set_level(&self, params: rmcp::model::SetLevelRequestParams) {
         tracing::info!("logging/setLevel -> params: {:?}", params);
     }
@@ -597,3 +725,35 @@ impl MessageProcessor {
         tracing::info!("notifications/initialized");
     }
 }
+
+fn parse_tool_arguments<T: serde::de::DeserializeOwned>(
+    tool_name: &str,
+    arguments: Option<rmcp::model::JsonObject>,
+) -> Result<T, CallToolResult> {
+    let arguments = arguments.ok_or_else(|| {
+        create_error_call_tool_result(format!("Missing arguments for {tool_name} tool-call."))
+    })?;
+    serde_json::from_value(serde_json::Value::Object(arguments)).map_err(|e| {
+        create_error_call_tool_result(format!("Failed to parse arguments for {tool_name}: {e}"))
+    })
+}
+
+fn create_error_call_tool_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![rmcp::model::Content::text(text)],
+        structured_content: None,
+        is_error: Some(true),
+        meta: None,
+    }
+}
+
+/// Returns `value` as structured content, mirrored as JSON text for clients that
+/// only read `content`.
+fn create_call_tool_result_with_json(value: serde_json::Value) -> CallToolResult {
+    CallToolResult {
+        content: vec![rmcp::model::Content::text(value.to_string())],
+        structured_content: Some(value),
+        is_error: None,
+        meta: None,
+    }
+}
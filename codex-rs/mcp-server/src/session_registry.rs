@@ -0,0 +1,250 @@
+//! Book-keeping for the sessions started through this server, so orchestrators can list them,
+//! check on a running turn, and answer exec approvals without going through elicitation.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use codex_protocol::ThreadId;
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum SessionStatus {
+    /// No turn is running; the session accepts a `codex-reply`.
+    Idle,
+    Running,
+    /// A turn is paused until a pending exec approval is answered.
+    WaitingForApproval,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PendingExecApproval {
+    pub approval_id: String,
+    #[serde(skip)]
+    pub turn_id: String,
+    pub command: Vec<String>,
+    pub cwd: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionSummary {
+    pub thread_id: ThreadId,
+    pub model: String,
+    pub cwd: PathBuf,
+    pub status: SessionStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_agent_message: Option<String>,
+    pub pending_exec_approvals: Vec<PendingExecApproval>,
+}
+
+#[derive(Debug)]
+struct SessionRecord {
+    model: String,
+    cwd: PathBuf,
+    running: bool,
+    last_agent_message: Option<String>,
+    pending_exec_approvals: Vec<PendingExecApproval>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct SessionRegistry {
+    sessions: Mutex<HashMap<ThreadId, SessionRecord>>,
+}
+
+impl SessionRegistry {
+    pub(crate) async fn register(&self, thread_id: ThreadId, model: String, cwd: PathBuf) {
+        self.sessions.lock().await.insert(
+            thread_id,
+            SessionRecord {
+                model,
+                cwd,
+                running: false,
+                last_agent_message: None,
+                pending_exec_approvals: Vec::new(),
+            },
+        );
+    }
+
+    pub(crate) async fn turn_started(&self, thread_id: ThreadId) {
+        if let Some(record) = self.sessions.lock().await.get_mut(&thread_id) {
+            record.running = true;
+        }
+    }
+
+    /// Records the end of a turn, whether it completed or failed.
+    pub(crate) async fn turn_finished(
+        &self,
+        thread_id: ThreadId,
+        last_agent_message: Option<String>,
+    ) {
+        if let Some(record) = self.sessions.lock().await.get_mut(&thread_id) {
+            record.running = false;
+            record.pending_exec_approvals.clear();
+            if last_agent_message.is_some() {
+                record.last_agent_message = last_agent_message;
+            }
+        }
+    }
+
+    pub(crate) async fn add_pending_exec_approval(
+        &self,
+        thread_id: ThreadId,
+        approval: PendingExecApproval,
+    ) {
+        if let Some(record) = self.sessions.lock().await.get_mut(&thread_id) {
+            record.pending_exec_approvals.push(approval);
+        }
+    }
+
+    /// Removes a pending approval so it is answered exactly once. Without an `approval_id`, the
+    /// oldest pending approval is taken.
+    pub(crate) async fn take_pending_exec_approval(
+        &self,
+        thread_id: ThreadId,
+        approval_id: Option<&str>,
+    ) -> Option<PendingExecApproval> {
+        let mut sessions = self.sessions.lock().await;
+        let pending = &mut sessions.get_mut(&thread_id)?.pending_exec_approvals;
+        let idx = match approval_id {
+            Some(approval_id) => pending
+                .iter()
+                .position(|approval| approval.approval_id == approval_id)?,
+            None if pending.is_empty() => return None,
+            None => 0,
+        };
+        Some(pending.remove(idx))
+    }
+
+    pub(crate) async fn summary(&self, thread_id: ThreadId) -> Option<SessionSummary> {
+        self.sessions
+            .lock()
+            .await
+            .get(&thread_id)
+            .map(|record| record.summary(thread_id))
+    }
+
+    /// Summaries of every session, oldest first (thread ids are time-ordered).
+    pub(crate) async fn summaries(&self) -> Vec<SessionSummary> {
+        let mut summaries: Vec<_> = self
+            .sessions
+            .lock()
+            .await
+            .iter()
+            .map(|(thread_id, record)| record.summary(*thread_id))
+            .collect();
+        summaries.sort_by_key(|summary| summary.thread_id.to_string());
+        summaries
+    }
+}
+
+impl SessionRecord {
+    fn summary(&self, thread_id: ThreadId) -> SessionSummary {
+        let status = if !self.pending_exec_approvals.is_empty() {
+            SessionStatus::WaitingForApproval
+        } else if self.running {
+            SessionStatus::Running
+        } else {
+            SessionStatus::Idle
+        };
+        SessionSummary {
+            thread_id,
+            model: self.model.clone(),
+            cwd: self.cwd.clone(),
+            status,
+            last_agent_message: self.last_agent_message.clone(),
+            pending_exec_approvals: self.pending_exec_approvals.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn approval(approval_id: &str) -> PendingExecApproval {
+        PendingExecApproval {
+            approval_id: approval_id.to_string(),
+            turn_id: "1".to_string(),
+            command: vec!["ls".to_string()],
+            cwd: PathBuf::from("/repo"),
+            reason: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn status_follows_the_turn_and_its_approvals() {
+        let registry = SessionRegistry::default();
+        let thread_id = ThreadId::new();
+        registry
+            .register(thread_id, "gpt-5.2".to_string(), PathBuf::from("/repo"))
+            .await;
+        let status = |summary: Option<SessionSummary>| summary.map(|summary| summary.status);
+
+        assert_eq!(
+            status(registry.summary(thread_id).await),
+            Some(SessionStatus::Idle)
+        );
+        registry.turn_started(thread_id).await;
+        assert_eq!(
+            status(registry.summary(thread_id).await),
+            Some(SessionStatus::Running)
+        );
+        registry
+            .add_pending_exec_approval(thread_id, approval("a1"))
+            .await;
+        assert_eq!(
+            status(registry.summary(thread_id).await),
+            Some(SessionStatus::WaitingForApproval)
+        );
+
+        registry
+            .turn_finished(thread_id, Some("done".to_string()))
+            .await;
+        let summary = registry.summary(thread_id).await.expect("session");
+        assert_eq!(summary.status, SessionStatus::Idle);
+        assert_eq!(summary.last_agent_message, Some("done".to_string()));
+        assert_eq!(summary.pending_exec_approvals, Vec::new());
+    }
+
+    #[tokio::test]
+    async fn pending_approvals_are_taken_once() {
+        let registry = SessionRegistry::default();
+        let thread_id = ThreadId::new();
+        registry
+            .register(thread_id, "gpt-5.2".to_string(), PathBuf::from("/repo"))
+            .await;
+        registry
+            .add_pending_exec_approval(thread_id, approval("a1"))
+            .await;
+        registry
+            .add_pending_exec_approval(thread_id, approval("a2"))
+            .await;
+
+        assert_eq!(
+            registry
+                .take_pending_exec_approval(thread_id, Some("a2"))
+                .await,
+            Some(approval("a2"))
+        );
+        assert_eq!(
+            registry
+                .take_pending_exec_approval(thread_id, Some("a2"))
+                .await,
+            None
+        );
+        assert_eq!(
+            registry.take_pending_exec_approval(thread_id, None).await,
+            Some(approval("a1"))
+        );
+        assert_eq!(
+            registry.take_pending_exec_approval(thread_id, None).await,
+            None
+        );
+    }
+}
@@ -180,7 +180,8 @@ impl McpProcess {
                     "name": "codex-mcp-server",
                     "title": "Codex",
                     "version": "0.0.0",
-                    "user_agent": user_agent
+                    "user_agent": user_agent,
+                    "codex_protocol_version": codex_core::protocol::PROTOCOL_VERSION
                 },
                 "protocolVersion": ProtocolVersion::V_2025_03_26
             })
@@ -218,6 +219,25 @@ impl McpProcess {
         .await
     }
 
+    /// Calls one of the other tools exposed by the server, returning the request id.
+    pub async fn send_tool_call(
+        &mut self,
+        name: &'static str,
+        arguments: serde_json::Value,
+    ) -> anyhow::Result<i64> {
+        let tool_call_params = CallToolRequestParams {
+            meta: None,
+            name: name.into(),
+            arguments: Some(match arguments {
+                serde_json::Value::Object(map) => map,
+                _ => anyhow::bail!("tool arguments must be a JSON object"),
+            }),
+            task: None,
+        };
+        self.send_request("tools/call", Some(serde_json::to_value(tool_call_params)?))
+            .await
+    }
+
     async fn send_request(
         &mut self,
         method: &str,
@@ -175,6 +175,130 @@ async fn shell_command_approval_triggers_elicitation() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Test that an orchestrator that does not answer elicitations can see the
+/// pending approval with `codex-session-status` and answer it with
+/// `codex-approve-exec`.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_shell_command_approval_via_approve_exec_tool() {
+    if env::var(CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok() {
+        println!(
+            "Skipping test because it cannot execute when network is disabled in a Codex sandbox."
+        );
+        return;
+    }
+
+    if let Err(err) = shell_command_approval_via_approve_exec_tool().await {
+        panic!("failure: {err}");
+    }
+}
+
+async fn shell_command_approval_via_approve_exec_tool() -> anyhow::Result<()> {
+    let workdir_for_shell_function_call = TempDir::new()?;
+    let created_filename = "created_by_approve_exec.txt";
+    let created_file = workdir_for_shell_function_call
+        .path()
+        .join(created_filename);
+    let shell_command = vec![
+        "python3".to_string(),
+        "-c".to_string(),
+        format!("import pathlib; pathlib.Path('{created_filename}').touch()"),
+    ];
+
+    let McpHandle {
+        process: mut mcp_process,
+        server: _server,
+        dir: _dir,
+    } = create_mcp_process(vec![
+        create_shell_command_sse_response(
+            shell_command,
+            Some(workdir_for_shell_function_call.path()),
+            Some(5_000),
+            "call1234",
+        )?,
+        create_final_assistant_message_sse_response("File created!")?,
+    ])
+    .await?;
+
+    let codex_request_id = mcp_process
+        .send_codex_tool_call(CodexToolCallParam {
+            prompt: "create a file".to_string(),
+            ..Default::default()
+        })
+        .await?;
+    // Leave the elicitation unanswered, as a client without elicitation support would.
+    let elicitation_request = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process.read_stream_until_request_message(),
+    )
+    .await??;
+    let params = serde_json::from_value::<ExecApprovalElicitRequestParams>(
+        elicitation_request
+            .request
+            .params
+            .ok_or_else(|| anyhow::anyhow!("elicitation_request.params must be set"))?,
+    )?;
+
+    let status_request_id = mcp_process
+        .send_tool_call(
+            "codex-session-status",
+            json!({ "threadId": params.thread_id }),
+        )
+        .await?;
+    let status_response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process.read_stream_until_response_message(RequestId::Number(status_request_id)),
+    )
+    .await??;
+    let status = &status_response.result["structuredContent"];
+    assert_eq!(status["status"], json!("waiting-for-approval"));
+    assert_eq!(
+        status["pendingExecApprovals"][0]["approvalId"],
+        json!("call1234")
+    );
+
+    let approve_request_id = mcp_process
+        .send_tool_call(
+            "codex-approve-exec",
+            json!({ "threadId": params.thread_id, "decision": "approved" }),
+        )
+        .await?;
+    let approve_response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process.read_stream_until_response_message(RequestId::Number(approve_request_id)),
+    )
+    .await??;
+    assert_eq!(
+        approve_response.result["structuredContent"]["approvalId"],
+        json!("call1234")
+    );
+
+    let codex_response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process.read_stream_until_response_message(RequestId::Number(codex_request_id)),
+    )
+    .await??;
+    assert_eq!(
+        codex_response.result["structuredContent"]["content"],
+        json!("File created!")
+    );
+    assert!(created_file.is_file(), "created file should exist");
+
+    let list_request_id = mcp_process
+        .send_tool_call("codex-list-sessions", json!({}))
+        .await?;
+    let list_response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process.read_stream_until_response_message(RequestId::Number(list_request_id)),
+    )
+    .await??;
+    let sessions = &list_response.result["structuredContent"]["sessions"];
+    assert_eq!(sessions[0]["threadId"], json!(params.thread_id));
+    assert_eq!(sessions[0]["status"], json!("idle"));
+    assert_eq!(sessions[0]["lastAgentMessage"], json!("File created!"));
+
+    Ok(())
+}
+
 fn create_expected_elicitation_request_params(
     command: Vec<String>,
     workdir: &Path,
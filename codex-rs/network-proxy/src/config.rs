@@ -36,6 +36,9 @@ pub struct NetworkProxySettings {
     pub mode: NetworkMode,
     #[serde(default)]
     pub allowed_domains: Vec<String>,
+    /// IP networks that are allowed in addition to `allowed_domains`, e.g. `10.0.0.0/8`.
+    #[serde(default)]
+    pub allowed_cidrs: Vec<String>,
     #[serde(default)]
     pub denied_domains: Vec<String>,
     #[serde(default)]
@@ -57,6 +60,7 @@ impl Default for NetworkProxySettings {
             dangerously_allow_non_loopback_admin: false,
             mode: NetworkMode::default(),
             allowed_domains: Vec::new(),
+            allowed_cidrs: Vec::new(),
             denied_domains: Vec::new(),
             allow_unix_sockets: Vec::new(),
             allow_local_binding: true,
@@ -342,6 +346,7 @@ mod tests {
                 dangerously_allow_non_loopback_admin: false,
                 mode: NetworkMode::Full,
                 allowed_domains: Vec::new(),
+                allowed_cidrs: Vec::new(),
                 denied_domains: Vec::new(),
                 allow_unix_sockets: Vec::new(),
                 allow_local_binding: true,
@@ -168,6 +168,72 @@ pub(crate) fn compile_globset(patterns: &[String]) -> Result<GlobSet> {
     Ok(builder.build()?)
 }
 
+/// An IP network such as `10.0.0.0/8` or `fd00::/8`. A bare address is a network of one host.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IpCidr {
+    network: IpAddr,
+    prefix: u8,
+}
+
+impl IpCidr {
+    pub(crate) fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+        let (addr, prefix) = match input.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (input, None),
+        };
+        let network = addr
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .with_context(|| format!("invalid CIDR: {input}"))?;
+        let max_prefix = if network.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(prefix) => prefix
+                .parse::<u8>()
+                .with_context(|| format!("invalid CIDR prefix length: {input}"))?,
+            None => max_prefix,
+        };
+        ensure!(
+            prefix <= max_prefix,
+            "invalid CIDR prefix length: {input} (must be at most {max_prefix})"
+        );
+        Ok(Self { network, prefix })
+    }
+
+    pub(crate) fn contains(&self, ip: IpAddr) -> bool {
+        match (self.network, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                ipv4_in_cidr(ip, network.octets(), self.prefix)
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = if self.prefix == 0 {
+                    0
+                } else {
+                    u128::MAX << (128 - self.prefix)
+                };
+                (u128::from(ip) & mask) == (u128::from(network) & mask)
+            }
+            (IpAddr::V4(_), IpAddr::V6(ip)) => ip
+                .to_ipv4_mapped()
+                .is_some_and(|v4| self.contains(v4.into())),
+            (IpAddr::V6(_), IpAddr::V4(_)) => false,
+        }
+    }
+}
+
+pub(crate) fn compile_cidrs(cidrs: &[String]) -> Result<Vec<IpCidr>> {
+    cidrs.iter().map(|cidr| IpCidr::parse(cidr)).collect()
+}
+
+/// Returns true if `host` is an IP literal inside one of `cidrs`.
+pub(crate) fn host_in_cidrs(cidrs: &[IpCidr], host: &Host) -> bool {
+    let host = host.as_str();
+    let host = host.split_once('%').map(|(ip, _)| ip).unwrap_or(host);
+    host.parse::<IpAddr>()
+        .is_ok_and(|ip| cidrs.iter().any(|cidr| cidr.contains(ip)))
+}
+
 #[derive(Debug, Clone)]
 pub(crate) enum DomainPattern {
     Any,
@@ -331,6 +397,31 @@ mod tests {
         assert!(!NetworkMode::Limited.allows_method("CONNECT"));
     }
 
+    #[test]
+    fn cidrs_match_addresses_in_range() {
+        let cidrs = compile_cidrs(&[
+            "10.20.0.0/16".to_string(),
+            "192.0.2.7".to_string(),
+            "fd00::/8".to_string(),
+        ])
+        .unwrap();
+
+        let matches = |host: &str| host_in_cidrs(&cidrs, &Host::parse(host).unwrap());
+        assert_eq!(true, matches("10.20.3.4"));
+        assert_eq!(false, matches("10.21.0.1"));
+        assert_eq!(true, matches("192.0.2.7"));
+        assert_eq!(false, matches("192.0.2.8"));
+        assert_eq!(true, matches("[fd12::1]"));
+        assert_eq!(true, matches("::ffff:10.20.0.1"));
+        assert_eq!(false, matches("registry.internal"));
+    }
+
+    #[test]
+    fn compile_cidrs_rejects_invalid_entries() {
+        assert!(compile_cidrs(&["10.0.0.0/33".to_string()]).is_err());
+        assert!(compile_cidrs(&["example.com/8".to_string()]).is_err());
+    }
+
     #[test]
     fn compile_globset_normalizes_trailing_dots() {
         let set = compile_globset(&["Example.COM.".to_string()]).unwrap();
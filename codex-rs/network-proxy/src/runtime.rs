@@ -1,6 +1,8 @@
 use crate::config::NetworkMode;
 use crate::config::NetworkProxyConfig;
 use crate::policy::Host;
+use crate::policy::IpCidr;
+use crate::policy::host_in_cidrs;
 use crate::policy::is_loopback_host;
 use crate::policy::is_non_public_ip;
 use crate::policy::normalize_host;
@@ -145,6 +147,7 @@ fn blocked_request_violation_log_line(entry: &BlockedRequest) -> String {
 pub struct ConfigState {
     pub config: NetworkProxyConfig,
     pub allow_set: GlobSet,
+    pub allow_cidrs: Vec<IpCidr>,
     pub deny_set: GlobSet,
     pub constraints: NetworkProxyConstraints,
     pub blocked: VecDeque<BlockedRequest>,
@@ -292,16 +295,17 @@ impl NetworkProxyState {
             Ok(host) => host,
             Err(_) => return Ok(HostBlockDecision::Blocked(HostBlockReason::NotAllowed)),
         };
-        let (deny_set, allow_set, allow_local_binding, allowed_domains_empty, allowed_domains) = {
+        let (deny_set, allow_set, allow_cidrs, allow_local_binding, allowed_domains) = {
             let guard = self.state.read().await;
             (
                 guard.deny_set.clone(),
                 guard.allow_set.clone(),
+                guard.allow_cidrs.clone(),
                 guard.config.network.allow_local_binding,
-                guard.config.network.allowed_domains.is_empty(),
                 guard.config.network.allowed_domains.clone(),
             )
         };
+        let allowlist_empty = allowed_domains.is_empty() && allow_cidrs.is_empty();
 
         let host_str = host.as_str();
 
@@ -313,7 +317,8 @@ impl NetworkProxyState {
             return Ok(HostBlockDecision::Blocked(HostBlockReason::Denied));
         }
 
-        let is_allowlisted = allow_set.is_match(host_str);
+        let in_allowed_cidr = host_in_cidrs(&allow_cidrs, &host);
+        let is_allowlisted = allow_set.is_match(host_str) || in_allowed_cidr;
         if !allow_local_binding {
             // If the intent is "prevent access to local/internal networks", we must not rely solely
             // on string checks like `localhost` / `127.0.0.1`. Attackers can use DNS rebinding or
@@ -338,7 +343,7 @@ impl NetworkProxyState {
             };
 
             if local_literal {
-                if !is_explicit_local_allowlisted(&allowed_domains, &host) {
+                if !in_allowed_cidr && !is_explicit_local_allowlisted(&allowed_domains, &host) {
                     return Ok(HostBlockDecision::Blocked(HostBlockReason::NotAllowedLocal));
                 }
             } else if host_resolves_to_non_public_ip(host_str, port).await {
@@ -346,7 +351,7 @@ impl NetworkProxyState {
             }
         }
 
-        if allowed_domains_empty || !is_allowlisted {
+        if allowlist_empty || !is_allowlisted {
             Ok(HostBlockDecision::Blocked(HostBlockReason::NotAllowed))
         } else {
             Ok(HostBlockDecision::Allowed)
@@ -552,6 +557,11 @@ fn log_policy_changes(previous: &NetworkProxyConfig, next: &NetworkProxyConfig)
         &previous.network.allowed_domains,
         &next.network.allowed_domains,
     );
+    log_domain_list_changes(
+        "CIDR allowlist",
+        &previous.network.allowed_cidrs,
+        &next.network.allowed_cidrs,
+    );
     log_domain_list_changes(
         "denylist",
         &previous.network.denied_domains,
@@ -688,6 +698,28 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn host_blocked_allows_ip_literals_in_allowed_cidrs() {
+        let state = network_proxy_state_for_policy(NetworkProxySettings {
+            allowed_cidrs: vec!["10.20.0.0/16".to_string()],
+            allow_local_binding: false,
+            ..NetworkProxySettings::default()
+        });
+
+        assert_eq!(
+            state.host_blocked("10.20.1.2", 443).await.unwrap(),
+            HostBlockDecision::Allowed
+        );
+        assert_eq!(
+            state.host_blocked("10.30.1.2", 443).await.unwrap(),
+            HostBlockDecision::Blocked(HostBlockReason::NotAllowedLocal)
+        );
+        assert_eq!(
+            state.host_blocked("8.8.8.8", 443).await.unwrap(),
+            HostBlockDecision::Blocked(HostBlockReason::NotAllowed)
+        );
+    }
+
     #[tokio::test]
     async fn blocked_snapshot_does_not_consume_entries() {
         let state = network_proxy_state_for_policy(NetworkProxySettings::default());
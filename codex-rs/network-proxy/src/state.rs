@@ -1,6 +1,7 @@
 use crate::config::NetworkMode;
 use crate::config::NetworkProxyConfig;
 use crate::policy::DomainPattern;
+use crate::policy::compile_cidrs;
 use crate::policy::compile_globset;
 use crate::runtime::ConfigState;
 use serde::Deserialize;
@@ -54,9 +55,11 @@ pub fn build_config_state(
 ) -> anyhow::Result<ConfigState> {
     let deny_set = compile_globset(&config.network.denied_domains)?;
     let allow_set = compile_globset(&config.network.allowed_domains)?;
+    let allow_cidrs = compile_cidrs(&config.network.allowed_cidrs)?;
     Ok(ConfigState {
         config,
         allow_set,
+        allow_cidrs,
         deny_set,
         constraints,
         blocked: std::collections::VecDeque::new(),
@@ -17,6 +17,15 @@ use codex_core::config::Config;
 
 const OLLAMA_CONNECTION_ERROR: &str = "No running Ollama server detected. Start it with: `ollama serve` (after installing). Install instructions: https://github.com/ollama/ollama?tab=readme-ov-file#ollama";
 
+/// Capabilities a local Ollama server reports for a single model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelCapabilities {
+    /// Maximum context length in tokens, when the model metadata includes one.
+    pub context_window: Option<i64>,
+    /// Whether the model supports native tool calling.
+    pub supports_tool_calls: bool,
+}
+
 /// Client for interacting with a local Ollama instance.
 pub struct OllamaClient {
     client: reqwest::Client,
@@ -149,6 +158,43 @@ impl OllamaClient {
         }
     }
 
+    /// Query `/api/show` for the model's context size and tool calling support.
+    ///
+    /// Returns `None` when the model is unknown or the server predates capability reporting.
+    pub async fn fetch_model_capabilities(
+        &self,
+        model: &str,
+    ) -> io::Result<Option<ModelCapabilities>> {
+        let show_url = format!("{}/api/show", self.host_root.trim_end_matches('/'));
+        let resp = self
+            .client
+            .post(show_url)
+            .json(&serde_json::json!({ "model": model }))
+            .send()
+            .await
+            .map_err(io::Error::other)?;
+        if !resp.status().is_success() {
+            return Ok(None);
+        }
+        let val = resp.json::<JsonValue>().await.map_err(io::Error::other)?;
+        let Some(capabilities) = val.get("capabilities").and_then(|c| c.as_array()) else {
+            return Ok(None);
+        };
+        // Context length is keyed by architecture, e.g. `llama.context_length`.
+        let context_window = val
+            .get("model_info")
+            .and_then(|info| info.as_object())
+            .and_then(|info| {
+                info.iter()
+                    .find(|(key, _)| key.ends_with(".context_length"))
+                    .and_then(|(_, value)| value.as_i64())
+            });
+        Ok(Some(ModelCapabilities {
+            context_window,
+            supports_tool_calls: capabilities.iter().any(|c| c.as_str() == Some("tools")),
+        }))
+    }
+
     /// Start a model pull and emit streaming events. The returned stream ends when
     /// a Success event is observed or the server closes the connection.
     pub async fn pull_model_stream(
@@ -294,6 +340,72 @@ mod tests {
         assert!(models.contains(&"mistral".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_fetch_model_capabilities() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/api/show"))
+            .and(wiremock::matchers::body_json(
+                serde_json::json!({ "model": "qwen3:8b" }),
+            ))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_raw(
+                    serde_json::json!({
+                        "capabilities": ["completion", "tools"],
+                        "model_info": {
+                            "general.architecture": "qwen3",
+                            "qwen3.context_length": 40960
+                        }
+                    })
+                    .to_string(),
+                    "application/json",
+                ),
+            )
+            .mount(&server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/api/show"))
+            .and(wiremock::matchers::body_json(
+                serde_json::json!({ "model": "gemma2" }),
+            ))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_raw(
+                serde_json::json!({ "capabilities": ["completion"], "model_info": {} }).to_string(),
+                "application/json",
+            ))
+            .mount(&server)
+            .await;
+
+        let client = OllamaClient::from_host_root(server.uri());
+
+        assert_eq!(
+            client
+                .fetch_model_capabilities("qwen3:8b")
+                .await
+                .expect("capabilities"),
+            Some(ModelCapabilities {
+                context_window: Some(40960),
+                supports_tool_calls: true,
+            })
+        );
+        assert_eq!(
+            client
+                .fetch_model_capabilities("gemma2")
+                .await
+                .expect("capabilities"),
+            Some(ModelCapabilities {
+                context_window: None,
+                supports_tool_calls: false,
+            })
+        );
+        assert_eq!(
+            client
+                .fetch_model_capabilities("missing")
+                .await
+                .expect("capabilities"),
+            None
+        );
+    }
+
     #[tokio::test]
     async fn test_fetch_version() {
         if std::env::var(codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok() {
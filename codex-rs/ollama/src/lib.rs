@@ -3,6 +3,7 @@ mod parser;
 mod pull;
 mod url;
 
+pub use client::ModelCapabilities;
 pub use client::OllamaClient;
 use codex_core::ModelProviderInfo;
 use codex_core::config::Config;
@@ -48,6 +49,30 @@ pub async fn ensure_oss_ready(config: &Config) -> std::io::Result<()> {
     Ok(())
 }
 
+/// Fill in the context window and tool calling support of the configured model from what the
+/// local Ollama server reports. Values set explicitly in the config are left untouched.
+pub async fn apply_model_capabilities(config: &mut Config) -> std::io::Result<()> {
+    let model = config
+        .model
+        .clone()
+        .unwrap_or_else(|| DEFAULT_OSS_MODEL.to_string());
+    let ollama_client = crate::OllamaClient::try_from_oss_provider(config).await?;
+    let Some(capabilities) = ollama_client.fetch_model_capabilities(&model).await? else {
+        return Ok(());
+    };
+
+    if config.model_context_window.is_none() {
+        config.model_context_window = capabilities.context_window;
+    }
+    if config.model_supports_tool_calls.is_none() {
+        if !capabilities.supports_tool_calls {
+            tracing::info!("{model} does not support tool calling; emulating tools via the prompt");
+        }
+        config.model_supports_tool_calls = Some(capabilities.supports_tool_calls);
+    }
+    Ok(())
+}
+
 fn min_responses_version() -> Version {
     Version::new(0, 13, 4)
 }
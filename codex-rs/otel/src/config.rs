@@ -38,6 +38,9 @@ pub struct OtelSettings {
     pub trace_exporter: OtelExporter,
     pub metrics_exporter: OtelExporter,
     pub runtime_metrics: bool,
+    /// Keep cumulative metrics that can be rendered for a Prometheus scrape, even when
+    /// `metrics_exporter` is `None`.
+    pub prometheus_metrics: bool,
 }
 
 #[derive(Clone, Debug)]
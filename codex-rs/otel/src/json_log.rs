@@ -0,0 +1,277 @@
+//! Structured JSON log output.
+//!
+//! [`JsonLogLayer`] writes every tracing event as one JSON object per line, for log pipelines that
+//! cannot parse the human-readable format. The top-level fields are the same on every line:
+//!
+//! - `timestamp`: RFC 3339 time in UTC.
+//! - `level` and `target`.
+//! - `message`: the formatted message, or `null`.
+//! - `session_id`: the thread the event belongs to, or `null`.
+//! - `turn_id`: the turn the event belongs to, or `null`.
+//! - `event`: the name of a telemetry event such as `codex.tool_result`, or `null`.
+//! - `span`: the innermost enclosing span, or `null`.
+//! - `fields`: every other field of the event and its enclosing spans.
+
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+use chrono::SecondsFormat;
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::Map;
+use serde_json::Value;
+use tracing::Event;
+use tracing::Metadata;
+use tracing::Subscriber;
+use tracing::field::Field;
+use tracing::field::Visit;
+use tracing::span::Attributes;
+use tracing::span::Id;
+use tracing::span::Record;
+use tracing_subscriber::Layer;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+
+/// Field names that identify the session, in order of preference.
+const SESSION_ID_FIELDS: &[&str] = &["thread_id", "conversation.id", "conversation_id"];
+
+/// Field names that identify the turn, in order of preference.
+const TURN_ID_FIELDS: &[&str] = &["turn_id", "turn.id"];
+
+const EVENT_NAME_FIELD: &str = "event.name";
+
+/// A [`Layer`] that writes events to `make_writer` as JSON lines.
+pub struct JsonLogLayer<W> {
+    make_writer: W,
+}
+
+impl<W> JsonLogLayer<W>
+where
+    W: for<'writer> MakeWriter<'writer> + 'static,
+{
+    pub fn new(make_writer: W) -> Self {
+        Self { make_writer }
+    }
+}
+
+/// Returns a writer that appends to `log_file`, or writes to stderr when there is none.
+pub fn log_writer(log_file: Option<&Path>) -> io::Result<BoxMakeWriter> {
+    match log_file {
+        Some(path) => {
+            let file = OpenOptions::new().create(true).append(true).open(path)?;
+            Ok(BoxMakeWriter::new(Mutex::new(file)))
+        }
+        None => Ok(BoxMakeWriter::new(io::stderr)),
+    }
+}
+
+/// Fields recorded on a span, stored in its extensions.
+struct SpanFields(Map<String, Value>);
+
+#[derive(Serialize)]
+struct LogLine<'a> {
+    timestamp: String,
+    level: &'a str,
+    target: &'a str,
+    message: Option<Value>,
+    session_id: Option<Value>,
+    turn_id: Option<Value>,
+    event: Option<Value>,
+    span: Option<&'a str>,
+    fields: Map<String, Value>,
+}
+
+impl<S, W> Layer<S> for JsonLogLayer<W>
+where
+    S: Subscriber + for<'lookup> LookupSpan<'lookup>,
+    W: for<'writer> MakeWriter<'writer> + 'static,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = Map::new();
+        attrs.record(&mut JsonVisitor(&mut fields));
+        span.extensions_mut().insert(SpanFields(fields));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        if let Some(SpanFields(fields)) = span.extensions_mut().get_mut::<SpanFields>() {
+            values.record(&mut JsonVisitor(fields));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut fields = Map::new();
+        let mut span_name = None;
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                if let Some(SpanFields(span_fields)) = span.extensions().get::<SpanFields>() {
+                    fields.extend(span_fields.clone());
+                }
+                span_name = Some(span.name());
+            }
+        }
+        event.record(&mut JsonVisitor(&mut fields));
+
+        let line = log_line(event.metadata(), span_name, fields);
+        if let Ok(mut line) = serde_json::to_vec(&line) {
+            line.push(b'\n');
+            let mut writer = self.make_writer.make_writer_for(event.metadata());
+            let _ = writer.write_all(&line);
+        }
+    }
+}
+
+fn log_line<'a>(
+    metadata: &'a Metadata<'a>,
+    span: Option<&'a str>,
+    mut fields: Map<String, Value>,
+) -> LogLine<'a> {
+    LogLine {
+        timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+        level: metadata.level().as_str(),
+        target: metadata.target(),
+        message: fields.remove("message"),
+        session_id: take_first(&mut fields, SESSION_ID_FIELDS),
+        turn_id: take_first(&mut fields, TURN_ID_FIELDS),
+        event: fields.remove(EVENT_NAME_FIELD),
+        span,
+        fields,
+    }
+}
+
+/// Removes every field in `names` and returns the first one that was present.
+fn take_first(fields: &mut Map<String, Value>, names: &[&str]) -> Option<Value> {
+    names
+        .iter()
+        .filter_map(|name| fields.remove(*name))
+        .reduce(|first, _| first)
+}
+
+struct JsonVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for JsonVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn std::error::Error + 'static)) {
+        self.0
+            .insert(field.name().to_string(), Value::from(value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), Value::from(format!("{value:?}")));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::sync::Arc;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn writes_stable_fields_from_spans_and_events() {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber =
+            tracing_subscriber::registry().with(JsonLogLayer::new(move || writer.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let session = tracing::info_span!("session_loop", thread_id = "thread-1");
+            let _session = session.enter();
+            let turn = tracing::info_span!(
+                "run_turn",
+                turn_id = "turn-1",
+                model = tracing::field::Empty
+            );
+            turn.record("model", "gpt-5");
+            let _turn = turn.enter();
+            tracing::info!(
+                event.name = "codex.tool_result",
+                tool_name = "shell",
+                "done"
+            );
+            drop(_turn);
+            tracing::warn!(attempt = 2, "retrying");
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<Value> = output
+            .lines()
+            .map(|line| {
+                let mut line: Value = serde_json::from_str(line).unwrap();
+                line.as_object_mut().unwrap().remove("timestamp");
+                line
+            })
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                serde_json::json!({
+                    "level": "INFO",
+                    "target": "codex_otel::json_log::tests",
+                    "message": "done",
+                    "session_id": "thread-1",
+                    "turn_id": "turn-1",
+                    "event": "codex.tool_result",
+                    "span": "run_turn",
+                    "fields": { "model": "gpt-5", "tool_name": "shell" },
+                }),
+                serde_json::json!({
+                    "level": "WARN",
+                    "target": "codex_otel::json_log::tests",
+                    "message": "retrying",
+                    "session_id": "thread-1",
+                    "turn_id": null,
+                    "event": null,
+                    "span": "session_loop",
+                    "fields": { "attempt": 2 },
+                }),
+            ]
+        );
+    }
+}
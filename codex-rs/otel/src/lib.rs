@@ -1,4 +1,5 @@
 pub mod config;
+pub mod json_log;
 pub mod metrics;
 pub mod otel_provider;
 pub mod traces;
@@ -86,6 +86,7 @@ struct MetricsClientInner {
     histograms: Mutex<HashMap<String, Histogram<f64>>>,
     duration_histograms: Mutex<HashMap<String, Histogram<f64>>>,
     runtime_reader: Option<Arc<ManualReader>>,
+    prometheus_reader: Option<Arc<ManualReader>>,
     default_tags: BTreeMap<String, String>,
 }
 
@@ -193,6 +194,7 @@ impl MetricsClient {
             exporter,
             export_interval,
             runtime_reader,
+            prometheus_reader,
             default_tags,
         } = config;
 
@@ -219,13 +221,33 @@ impl MetricsClient {
             )
         });
 
+        let prometheus_reader = prometheus_reader.then(|| {
+            Arc::new(
+                ManualReader::builder()
+                    .with_temporality(Temporality::Cumulative)
+                    .build(),
+            )
+        });
+        let manual_readers = [runtime_reader.clone(), prometheus_reader.clone()]
+            .into_iter()
+            .flatten()
+            .collect();
+
         let (meter_provider, meter) = match exporter {
             MetricsExporter::InMemory(exporter) => {
-                build_provider(resource, exporter, export_interval, runtime_reader.clone())
+                build_provider(resource, Some(exporter), export_interval, manual_readers)
+            }
+            MetricsExporter::Otlp(OtelExporter::None) if prometheus_reader.is_some() => {
+                build_provider::<opentelemetry_otlp::MetricExporter>(
+                    resource,
+                    None,
+                    export_interval,
+                    manual_readers,
+                )
             }
             MetricsExporter::Otlp(exporter) => {
                 let exporter = build_otlp_metric_exporter(exporter, Temporality::Delta)?;
-                build_provider(resource, exporter, export_interval, runtime_reader.clone())
+                build_provider(resource, Some(exporter), export_interval, manual_readers)
             }
         };
 
@@ -236,6 +258,7 @@ impl MetricsClient {
             histograms: Mutex::new(HashMap::new()),
             duration_histograms: Mutex::new(HashMap::new()),
             runtime_reader,
+            prometheus_reader,
             default_tags,
         })))
     }
@@ -284,6 +307,18 @@ impl MetricsClient {
         Ok(snapshot)
     }
 
+    /// Render every metric recorded so far in the Prometheus text exposition format.
+    pub fn prometheus_text(&self) -> Result<String> {
+        let Some(reader) = &self.0.prometheus_reader else {
+            return Err(MetricsError::PrometheusUnavailable);
+        };
+        let mut snapshot = ResourceMetrics::default();
+        reader
+            .collect(&mut snapshot)
+            .map_err(|source| MetricsError::PrometheusCollect { source })?;
+        Ok(crate::metrics::prometheus::render(&snapshot))
+    }
+
     /// Flush metrics and stop the underlying OTEL meter provider.
     pub fn shutdown(&self) -> Result<()> {
         self.0.shutdown()
@@ -308,23 +343,25 @@ fn os_resource_attributes() -> Vec<KeyValue> {
 
 fn build_provider<E>(
     resource: Resource,
-    exporter: E,
+    exporter: Option<E>,
     interval: Option<Duration>,
-    runtime_reader: Option<Arc<ManualReader>>,
+    manual_readers: Vec<Arc<ManualReader>>,
 ) -> (SdkMeterProvider, Meter)
 where
     E: opentelemetry_sdk::metrics::exporter::PushMetricExporter + 'static,
 {
-    let mut reader_builder = PeriodicReader::builder(exporter);
-    if let Some(interval) = interval {
-        reader_builder = reader_builder.with_interval(interval);
-    }
-    let reader = reader_builder.build();
     let mut provider_builder = SdkMeterProvider::builder().with_resource(resource);
-    if let Some(reader) = runtime_reader {
+    for reader in manual_readers {
         provider_builder = provider_builder.with_reader(SharedManualReader::new(reader));
     }
-    let provider = provider_builder.with_reader(reader).build();
+    if let Some(exporter) = exporter {
+        let mut reader_builder = PeriodicReader::builder(exporter);
+        if let Some(interval) = interval {
+            reader_builder = reader_builder.with_interval(interval);
+        }
+        provider_builder = provider_builder.with_reader(reader_builder.build());
+    }
+    let provider = provider_builder.build();
     let meter = provider.meter(METER_NAME);
     (provider, meter)
 }
@@ -43,4 +43,13 @@ pub enum MetricsError {
         #[source]
         source: opentelemetry_sdk::error::OTelSdkError,
     },
+
+    #[error("prometheus metrics reader is not enabled")]
+    PrometheusUnavailable,
+
+    #[error("failed to collect prometheus metrics from metrics reader")]
+    PrometheusCollect {
+        #[source]
+        source: opentelemetry_sdk::error::OTelSdkError,
+    },
 }
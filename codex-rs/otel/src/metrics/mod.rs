@@ -2,6 +2,7 @@ mod client;
 mod config;
 mod error;
 pub(crate) mod names;
+mod prometheus;
 pub(crate) mod runtime_metrics;
 pub(crate) mod timer;
 pub(crate) mod validation;
@@ -11,6 +12,7 @@ pub use crate::metrics::config::MetricsConfig;
 pub use crate::metrics::config::MetricsExporter;
 pub use crate::metrics::error::MetricsError;
 pub use crate::metrics::error::Result;
+pub use crate::metrics::prometheus::PROMETHEUS_CONTENT_TYPE;
 use std::sync::OnceLock;
 
 static GLOBAL_METRICS: OnceLock<MetricsClient> = OnceLock::new();
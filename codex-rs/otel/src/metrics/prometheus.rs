@@ -0,0 +1,158 @@
+//! Rendering metric snapshots in the Prometheus text exposition format.
+//!
+//! Metric names have every character Prometheus does not allow replaced with `_`, so
+//! `codex.tool.call` becomes `codex_tool_call_total` and `codex.tool.call.duration_ms` becomes the
+//! `codex_tool_call_duration_ms` histogram.
+
+use std::fmt::Write as _;
+
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::metrics::data::AggregatedMetrics;
+use opentelemetry_sdk::metrics::data::Metric;
+use opentelemetry_sdk::metrics::data::MetricData;
+use opentelemetry_sdk::metrics::data::ResourceMetrics;
+use opentelemetry_sdk::metrics::data::ScopeMetrics;
+
+/// `Content-Type` of a response carrying [`MetricsClient::prometheus_text`].
+///
+/// [`MetricsClient::prometheus_text`]: crate::metrics::MetricsClient::prometheus_text
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+pub(crate) fn render(snapshot: &ResourceMetrics) -> String {
+    let mut out = String::new();
+    for metric in snapshot.scope_metrics().flat_map(ScopeMetrics::metrics) {
+        match metric.data() {
+            AggregatedMetrics::F64(data) => render_data(&mut out, metric, data),
+            AggregatedMetrics::U64(data) => render_data(&mut out, metric, data),
+            AggregatedMetrics::I64(data) => render_data(&mut out, metric, data),
+        }
+    }
+    out
+}
+
+trait Sample: Copy {
+    fn as_f64(self) -> f64;
+}
+
+impl Sample for f64 {
+    fn as_f64(self) -> f64 {
+        self
+    }
+}
+
+impl Sample for u64 {
+    fn as_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Sample for i64 {
+    fn as_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+fn render_data<T: Sample>(out: &mut String, metric: &Metric, data: &MetricData<T>) {
+    let name = metric_name(metric.name());
+    match data {
+        MetricData::Sum(sum) => {
+            let (name, kind) = if sum.is_monotonic() {
+                (format!("{name}_total"), "counter")
+            } else {
+                (name, "gauge")
+            };
+            write_header(out, &name, metric.description(), kind);
+            for point in sum.data_points() {
+                let labels = labels(point.attributes());
+                write_sample(out, &name, &labels, point.value().as_f64());
+            }
+        }
+        MetricData::Gauge(gauge) => {
+            write_header(out, &name, metric.description(), "gauge");
+            for point in gauge.data_points() {
+                let labels = labels(point.attributes());
+                write_sample(out, &name, &labels, point.value().as_f64());
+            }
+        }
+        MetricData::Histogram(histogram) => {
+            write_header(out, &name, metric.description(), "histogram");
+            let bucket_name = format!("{name}_bucket");
+            for point in histogram.data_points() {
+                let labels = labels(point.attributes());
+                let mut cumulative = 0;
+                for (bound, count) in point.bounds().zip(point.bucket_counts()) {
+                    cumulative += count;
+                    let labels = with_le(&labels, &format_value(bound));
+                    write_sample(out, &bucket_name, &labels, cumulative as f64);
+                }
+                let labels_inf = with_le(&labels, "+Inf");
+                write_sample(out, &bucket_name, &labels_inf, point.count() as f64);
+                write_sample(out, &format!("{name}_sum"), &labels, point.sum().as_f64());
+                write_sample(out, &format!("{name}_count"), &labels, point.count() as f64);
+            }
+        }
+        MetricData::ExponentialHistogram(_) => {}
+    }
+}
+
+fn write_header(out: &mut String, name: &str, description: &str, kind: &str) {
+    if !description.is_empty() {
+        let description = description.replace('\\', "\\\\").replace('\n', "\\n");
+        let _ = writeln!(out, "# HELP {name} {description}");
+    }
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn write_sample(out: &mut String, name: &str, labels: &[(String, String)], value: f64) {
+    out.push_str(name);
+    if !labels.is_empty() {
+        out.push('{');
+        for (index, (key, value)) in labels.iter().enumerate() {
+            if index > 0 {
+                out.push(',');
+            }
+            let value = value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            let _ = write!(out, "{key}=\"{value}\"");
+        }
+        out.push('}');
+    }
+    let _ = writeln!(out, " {}", format_value(value));
+}
+
+fn labels<'a>(attributes: impl Iterator<Item = &'a KeyValue>) -> Vec<(String, String)> {
+    let mut labels: Vec<(String, String)> = attributes
+        .map(|kv| (metric_name(kv.key.as_str()), kv.value.as_str().to_string()))
+        .collect();
+    labels.sort();
+    labels
+}
+
+fn with_le(labels: &[(String, String)], le: &str) -> Vec<(String, String)> {
+    let mut labels = labels.to_vec();
+    labels.push(("le".to_string(), le.to_string()));
+    labels
+}
+
+fn metric_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
@@ -72,20 +72,24 @@ impl OtelProvider {
         let trace_enabled = !matches!(settings.trace_exporter, OtelExporter::None);
 
         let metric_exporter = crate::config::resolve_exporter(&settings.metrics_exporter);
-        let metrics = if matches!(metric_exporter, OtelExporter::None) {
-            None
-        } else {
-            let mut config = MetricsConfig::otlp(
-                settings.environment.clone(),
-                settings.service_name.clone(),
-                settings.service_version.clone(),
-                metric_exporter,
-            );
-            if settings.runtime_metrics {
-                config = config.with_runtime_reader();
-            }
-            Some(MetricsClient::new(config)?)
-        };
+        let metrics =
+            if matches!(metric_exporter, OtelExporter::None) && !settings.prometheus_metrics {
+                None
+            } else {
+                let mut config = MetricsConfig::otlp(
+                    settings.environment.clone(),
+                    settings.service_name.clone(),
+                    settings.service_version.clone(),
+                    metric_exporter,
+                );
+                if settings.runtime_metrics {
+                    config = config.with_runtime_reader();
+                }
+                if settings.prometheus_metrics {
+                    config = config.with_prometheus_reader();
+                }
+                Some(MetricsClient::new(config)?)
+            };
 
         if let Some(metrics) = metrics.as_ref() {
             crate::metrics::install_global(metrics.clone());
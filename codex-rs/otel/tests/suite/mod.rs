@@ -1,5 +1,6 @@
 mod manager_metrics;
 mod otlp_http_loopback;
+mod prometheus;
 mod runtime_summary;
 mod send;
 mod snapshot;
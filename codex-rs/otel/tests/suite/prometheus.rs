@@ -0,0 +1,69 @@
+use codex_otel::metrics::MetricsClient;
+use codex_otel::metrics::MetricsConfig;
+use codex_otel::metrics::MetricsError;
+use codex_otel::metrics::Result;
+use opentelemetry_sdk::metrics::InMemoryMetricExporter;
+use std::time::Duration;
+
+#[test]
+fn prometheus_text_renders_cumulative_counters_and_histograms() -> Result<()> {
+    let exporter = InMemoryMetricExporter::default();
+    let metrics = MetricsClient::new(
+        MetricsConfig::in_memory("test", "codex-cli", env!("CARGO_PKG_VERSION"), exporter)
+            .with_prometheus_reader(),
+    )?;
+
+    metrics.counter("codex.mcp.call", 1, &[("status", "error")])?;
+    metrics.record_duration(
+        "codex.tool.call.duration_ms",
+        Duration::from_millis(7),
+        &[("tool", "shell")],
+    )?;
+    let _ = metrics.prometheus_text()?;
+    metrics.counter("codex.mcp.call", 2, &[("status", "error")])?;
+
+    let text = metrics.prometheus_text()?;
+
+    assert!(
+        text.contains("# TYPE codex_mcp_call_total counter\n"),
+        "{text}"
+    );
+    assert!(
+        text.contains("codex_mcp_call_total{status=\"error\"} 3\n"),
+        "{text}"
+    );
+    assert!(
+        text.contains("# TYPE codex_tool_call_duration_ms histogram\n"),
+        "{text}"
+    );
+    assert!(
+        text.contains("codex_tool_call_duration_ms_bucket{le=\"10\",tool=\"shell\"} 1\n"),
+        "{text}"
+    );
+    assert!(
+        text.contains("codex_tool_call_duration_ms_bucket{le=\"+Inf\",tool=\"shell\"} 1\n"),
+        "{text}"
+    );
+    assert!(
+        text.contains("codex_tool_call_duration_ms_sum{tool=\"shell\"} 7\n"),
+        "{text}"
+    );
+    Ok(())
+}
+
+#[test]
+fn prometheus_text_requires_the_reader() -> Result<()> {
+    let exporter = InMemoryMetricExporter::default();
+    let metrics = MetricsClient::new(MetricsConfig::in_memory(
+        "test",
+        "codex-cli",
+        env!("CARGO_PKG_VERSION"),
+        exporter,
+    ))?;
+
+    assert!(matches!(
+        metrics.prometheus_text(),
+        Err(MetricsError::PrometheusUnavailable)
+    ));
+    Ok(())
+}
@@ -102,9 +102,11 @@ pub struct ElicitationRequestEvent {
     #[ts(type = "string | number")]
     pub id: RequestId,
     pub message: String,
-    // TODO: MCP servers can request we fill out a schema for the elicitation. We don't support
-    // this yet.
-    // pub requested_schema: ElicitRequestParamsRequestedSchema,
+    /// JSON schema of the object the server asks the user to fill in; its properties are flat
+    /// fields of type string, number, integer, boolean, or a string enum. Unset when the server
+    /// only asks for a confirmation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requested_schema: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
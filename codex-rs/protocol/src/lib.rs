@@ -5,6 +5,7 @@ pub mod approvals;
 pub mod config_types;
 pub mod custom_prompts;
 pub mod dynamic_tools;
+pub mod exec_events;
 pub mod items;
 pub mod mcp;
 pub mod message_history;
@@ -25,6 +25,7 @@ use crate::dynamic_tools::DynamicToolResponse;
 use crate::dynamic_tools::DynamicToolSpec;
 use crate::items::TurnItem;
 use crate::mcp::CallToolResult;
+use crate::mcp::Prompt as McpPrompt;
 use crate::mcp::RequestId;
 use crate::mcp::Resource as McpResource;
 use crate::mcp::ResourceTemplate as McpResourceTemplate;
@@ -68,6 +69,33 @@ pub const COLLABORATION_MODE_OPEN_TAG: &str = "<collaboration_mode>";
 pub const COLLABORATION_MODE_CLOSE_TAG: &str = "</collaboration_mode>";
 pub const USER_MESSAGE_BEGIN: &str = "## My request for Codex:";
 
+/// Version of the event protocol spoken by this build. Bump it when adding an [`EventMsg`]
+/// variant and list the variant in [`EVENT_TYPE_PROTOCOL_VERSIONS`], so clients that declare an
+/// older version are not sent event types they cannot decode.
+pub const PROTOCOL_VERSION: u32 = 9;
+
+/// Event types added after protocol version 1, with the [`PROTOCOL_VERSION`] that added them.
+pub const EVENT_TYPE_PROTOCOL_VERSIONS: &[(&str, u32)] = &[
+    ("user_input_queue_updated", 2),
+    ("mcp_server_health", 3),
+    ("list_mcp_prompts_response", 4),
+    ("get_mcp_prompt_response", 4),
+    ("get_mcp_server_logs_response", 5),
+    ("mcp_list_changed", 6),
+    ("mcp_tool_call_queued", 7),
+    ("config_reloaded", 8),
+    ("auth_expired", 9),
+];
+
+/// Event types (the `type` of a serialized [`EventMsg`]) that a client speaking
+/// `protocol_version` does not know.
+pub fn event_types_newer_than(protocol_version: u32) -> impl Iterator<Item = &'static str> {
+    EVENT_TYPE_PROTOCOL_VERSIONS
+        .iter()
+        .filter(move |(_, version)| *version > protocol_version)
+        .map(|(event_type, _)| *event_type)
+}
+
 /// Submission Queue Entry - requests from user
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct Submission {
@@ -101,6 +129,11 @@ pub enum Op {
     ///
     /// Prefer [`Op::UserTurn`] so the caller provides full turn context
     /// (cwd/approval/sandbox/model/etc.) for each turn.
+    ///
+    /// Input submitted while a turn is running is queued and starts its own
+    /// turn, with the submission id as turn id, once the running turn ends.
+    /// The queue is reported with [`EventMsg::UserInputQueueUpdated`] and
+    /// cleared by [`Op::Interrupt`].
     UserInput {
         /// User input items, see `InputItem`
         items: Vec<UserInput>,
@@ -148,6 +181,20 @@ pub enum Op {
         personality: Option<Personality>,
     },
 
+    /// Like [`Op::UserInput`], but runs this turn with `overrides` applied on
+    /// top of the session's settings. The overrides are not persisted: the next
+    /// turn uses the session settings again.
+    UserInputWithOverrides {
+        /// User input items, see `InputItem`
+        items: Vec<UserInput>,
+        /// Settings that apply to this turn only.
+        #[serde(default)]
+        overrides: TurnOverrides,
+        /// Optional JSON Schema used to constrain the final assistant message for this turn.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        final_output_json_schema: Option<Value>,
+    },
+
     /// Override parts of the persistent turn context for subsequent turns.
     ///
     /// All fields are optional; when omitted, the existing value is preserved.
@@ -216,6 +263,23 @@ pub enum Op {
         decision: ReviewDecision,
     },
 
+    /// Resolve a `BudgetLimitReached` pause: approving continues the turn, denying stops it.
+    BudgetApproval {
+        /// The id of the budget limit request we are resolving
+        id: String,
+        /// The user's decision in response to the request.
+        decision: ReviewDecision,
+    },
+
+    /// Resolve an `AuthExpired` pause once the user has tried to sign in again: on success the
+    /// credentials are reloaded and the failed request is retried, otherwise the turn stops.
+    ReauthComplete {
+        /// The id of the `AuthExpired` event we are resolving
+        id: String,
+        /// Whether the user signed in again.
+        success: bool,
+    },
+
     /// Resolve an MCP elicitation request.
     ResolveElicitation {
         /// Name of the MCP server that issued the request.
@@ -224,6 +288,10 @@ pub enum Op {
         request_id: RequestId,
         /// User's decision for the request.
         decision: ElicitationAction,
+        /// Values for the fields of the request's `requested_schema`, sent with `Accept`.
+        /// Defaults to an empty object.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        content: Option<serde_json::Value>,
     },
 
     /// Resolve a request_user_input tool call.
@@ -268,9 +336,33 @@ pub enum Op {
     /// enable/disable state) without restarting the thread.
     ReloadUserConfig,
 
+    /// Request the prompt templates published by MCP servers.
+    /// Reply is delivered via `EventMsg::ListMcpPromptsResponse`.
+    ListMcpPrompts,
+
+    /// Expand the prompt template `name` of the MCP server `server` with `arguments`.
+    /// Reply is delivered via `EventMsg::GetMcpPromptResponse`.
+    GetMcpPrompt {
+        server: String,
+        name: String,
+        #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+        arguments: std::collections::HashMap<String, String>,
+    },
+
+    /// Request what MCP servers wrote to stderr, for `server` or for every configured server.
+    /// Reply is delivered via `EventMsg::GetMcpServerLogsResponse`.
+    GetMcpServerLogs {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        server: Option<String>,
+    },
+
     /// Request the list of available custom prompts.
     ListCustomPrompts,
 
+    /// Request token usage and estimated cost for the current turn and the session.
+    /// Reply is delivered via `EventMsg::GetUsageResponse`.
+    GetUsage,
+
     /// Request the list of skills for the provided `cwd` values or the session default.
     ListSkills {
         /// Working directories to scope repo skills discovery.
@@ -299,6 +391,21 @@ pub enum Op {
     /// to generate a summary which will be returned as an AgentMessage event.
     Compact,
 
+    /// Pin a context item so compaction never summarizes or drops it.
+    ///
+    /// Pinned items are injected into the conversation immediately and are
+    /// re-attached verbatim after every compaction. The server replies with
+    /// [`EventMsg::PinnedContextUpdated`].
+    PinContext { source: PinnedContextSource },
+
+    /// Remove a previously pinned context item by id.
+    /// The server replies with [`EventMsg::PinnedContextUpdated`].
+    UnpinContext { id: String },
+
+    /// Request the list of pinned context items for this thread.
+    /// The server replies with [`EventMsg::PinnedContextUpdated`].
+    ListPinnedContext,
+
     /// Drop all persisted memory artifacts and memory-tracking DB rows.
     DropMemories,
 
@@ -313,12 +420,36 @@ pub enum Op {
     /// Request Codex to undo a turn (turn are stacked so it is the same effect as CMD + Z).
     Undo,
 
+    /// Restore the working tree to a checkpoint reported by `EventMsg::CheckpointCreated`.
+    /// The checkpoint and every later one are discarded, as if `Undo` had been repeated
+    /// back to it.
+    RevertToCheckpoint { checkpoint_id: String },
+
+    /// Commit the session worktree reported by `EventMsg::WorktreeCreated` and merge its branch
+    /// into the repository the session was started from.
+    MergeWorktree,
+
     /// Request Codex to drop the last N user turns from in-memory context.
     ///
     /// This does not attempt to revert local filesystem changes. Clients are
     /// responsible for undoing any edits on disk.
     ThreadRollback { num_turns: u32 },
 
+    /// Continue the conversation recorded at `rollout_path` in this thread, for example after the
+    /// process that was running it crashed. Restores its history, token usage, session approvals
+    /// and tool selection, and closes a turn that never finished. Only valid before the first turn
+    /// of this thread; the restored transcript is replayed in a new `EventMsg::SessionConfigured`.
+    ResumeConversation { rollout_path: PathBuf },
+
+    /// Re-emit the events recorded at `rollout_path` on this thread's event stream, paced like the
+    /// recording. `speed` scales the pacing (default `1.0`; `0` sends everything at once). Nothing
+    /// is executed, and neither the thread's history nor its rollout changes.
+    ReplayRollout {
+        rollout_path: PathBuf,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        speed: Option<f64>,
+    },
+
     /// Request a code review from the agent.
     Review { review_request: ReviewRequest },
 
@@ -871,9 +1002,16 @@ pub enum EventMsg {
     /// Model routing changed from the requested model to a different model.
     ModelReroute(ModelRerouteEvent),
 
+    /// The session switched to the next configured fallback model provider after the active
+    /// provider was rate limited, failed with server errors, or timed out.
+    ModelProviderFallback(ModelProviderFallbackEvent),
+
     /// Conversation history was compacted (either automatically or manually).
     ContextCompacted(ContextCompactedEvent),
 
+    /// The set of pinned context items changed (or was requested).
+    PinnedContextUpdated(PinnedContextUpdatedEvent),
+
     /// Conversation history was rolled back by dropping the last N user turns.
     ThreadRolledBack(ThreadRolledBackEvent),
 
@@ -926,6 +1064,15 @@ pub enum EventMsg {
     /// Aggregate MCP startup completion summary.
     McpStartupComplete(McpStartupCompleteEvent),
 
+    /// A running MCP server stopped answering and is being restarted.
+    McpServerHealth(McpServerHealthEvent),
+
+    /// An MCP server changed the tools, prompts or resources it publishes.
+    McpListChanged(McpListChangedEvent),
+
+    /// An MCP tool call is waiting for room under the concurrent call limits.
+    McpToolCallQueued(McpToolCallQueuedEvent),
+
     McpToolCallBegin(McpToolCallBeginEvent),
 
     McpToolCallEnd(McpToolCallEndEvent),
@@ -958,20 +1105,49 @@ pub enum EventMsg {
 
     ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent),
 
+    /// A configured budget limit was reached; the turn is paused until the user
+    /// answers with `Op::BudgetApproval`.
+    BudgetLimitReached(BudgetLimitReachedEvent),
+
+    /// The stored ChatGPT tokens could not be refreshed; the turn is paused until the client
+    /// signs in again and answers with `Op::ReauthComplete`.
+    AuthExpired(AuthExpiredEvent),
+
     /// Notification advising the user that something they are using has been
     /// deprecated and should be phased out.
     DeprecationNotice(DeprecationNoticeEvent),
 
+    /// Effective settings for a turn whose model, reasoning effort, approval
+    /// policy or sandbox differ from the previous turn, or that was submitted
+    /// with [`Op::UserInputWithOverrides`].
+    TurnSettings(TurnSettingsEvent),
+
+    /// Project instruction files (`AGENTS.md` and fallbacks) merged into the
+    /// session's user instructions.
+    ProjectDocsLoaded(ProjectDocsLoadedEvent),
+
     BackgroundEvent(BackgroundEventEvent),
 
+    /// A snapshot of the working tree was taken before the turn could change files.
+    CheckpointCreated(CheckpointCreatedEvent),
+
     UndoStarted(UndoStartedEvent),
 
+    /// The session runs in its own git worktree instead of the repository it was started from.
+    WorktreeCreated(WorktreeCreatedEvent),
+
+    /// The session worktree was merged back into the original repository.
+    WorktreeMerged(WorktreeMergedEvent),
+
     UndoCompleted(UndoCompletedEvent),
 
     /// Notification that a model stream experienced an error or disconnect
     /// and the system is handling it (e.g., retrying with backoff).
     StreamError(StreamErrorEvent),
 
+    /// Notification that a failed model request will be retried after a delay.
+    StreamRetry(StreamRetryEvent),
+
     /// Notification that the agent is about to apply a code patch. Mirrors
     /// `ExecCommandBegin` so front‑ends can show progress indicators.
     PatchApplyBegin(PatchApplyBeginEvent),
@@ -981,15 +1157,31 @@ pub enum EventMsg {
 
     TurnDiff(TurnDiffEvent),
 
+    /// The final message of a turn with `final_output_json_schema`, parsed and validated against
+    /// the schema.
+    TurnStructuredOutput(TurnStructuredOutputEvent),
+
     /// Response to GetHistoryEntryRequest.
     GetHistoryEntryResponse(GetHistoryEntryResponseEvent),
 
     /// List of MCP tools available to the agent.
     McpListToolsResponse(McpListToolsResponseEvent),
 
+    /// List of prompt templates published by MCP servers.
+    ListMcpPromptsResponse(ListMcpPromptsResponseEvent),
+
+    /// Response to `Op::GetMcpPrompt`.
+    GetMcpPromptResponse(GetMcpPromptResponseEvent),
+
+    /// Response to `Op::GetMcpServerLogs`.
+    GetMcpServerLogsResponse(GetMcpServerLogsResponseEvent),
+
     /// List of custom prompts available to the agent.
     ListCustomPromptsResponse(ListCustomPromptsResponseEvent),
 
+    /// Response to `Op::GetUsage`.
+    GetUsageResponse(GetUsageResponseEvent),
+
     /// List of skills available to the agent.
     ListSkillsResponse(ListSkillsResponseEvent),
 
@@ -1002,6 +1194,9 @@ pub enum EventMsg {
     /// Notification that skill data may have been updated and clients may want to reload.
     SkillsUpdateAvailable,
 
+    /// The config files were edited while the session was running.
+    ConfigReloaded(ConfigReloadedEvent),
+
     PlanUpdate(UpdatePlanArgs),
 
     TurnAborted(TurnAbortedEvent),
@@ -1045,6 +1240,24 @@ pub enum EventMsg {
     CollabResumeBegin(CollabResumeBeginEvent),
     /// Collab interaction: resume end.
     CollabResumeEnd(CollabResumeEndEvent),
+
+    /// A `delegate` tool call started a sub-agent.
+    DelegateBegin(DelegateBeginEvent),
+    /// An event emitted by a running delegate sub-agent.
+    DelegateProgress(DelegateProgressEvent),
+    /// A delegate sub-agent finished.
+    DelegateEnd(DelegateEndEvent),
+
+    /// The [`Op::UserInput`] submissions waiting for the running turn to end
+    /// changed.
+    UserInputQueueUpdated(UserInputQueueUpdatedEvent),
+
+    /// An event type this build does not know, sent by a newer version of Codex. Clients ignore it
+    /// instead of failing to decode the stream.
+    #[serde(other)]
+    #[schemars(skip)]
+    #[ts(skip)]
+    Unknown,
 }
 
 impl From<CollabAgentSpawnBeginEvent> for EventMsg {
@@ -1107,6 +1320,24 @@ impl From<CollabResumeEndEvent> for EventMsg {
     }
 }
 
+impl From<DelegateBeginEvent> for EventMsg {
+    fn from(event: DelegateBeginEvent) -> Self {
+        EventMsg::DelegateBegin(event)
+    }
+}
+
+impl From<DelegateProgressEvent> for EventMsg {
+    fn from(event: DelegateProgressEvent) -> Self {
+        EventMsg::DelegateProgress(event)
+    }
+}
+
+impl From<DelegateEndEvent> for EventMsg {
+    fn from(event: DelegateEndEvent) -> Self {
+        EventMsg::DelegateEnd(event)
+    }
+}
+
 /// Agent lifecycle status, derived from emitted events.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS, Default)]
 #[serde(rename_all = "snake_case")]
@@ -1325,6 +1556,14 @@ impl ErrorEvent {
     }
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct ConfigReloadedEvent {
+    /// Config keys whose new values now apply to this session.
+    pub applied: Vec<String>,
+    /// Config keys that changed but only take effect in a new session.
+    pub deferred: Vec<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct WarningEvent {
     pub message: String,
@@ -1344,6 +1583,16 @@ pub struct ModelRerouteEvent {
     pub reason: ModelRerouteReason,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct ModelProviderFallbackEvent {
+    /// Display name of the provider that became unavailable.
+    pub from_provider: String,
+    /// Display name of the provider now serving the session.
+    pub to_provider: String,
+    /// Error that triggered the switch.
+    pub reason: String,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ContextCompactedEvent;
 
@@ -1351,6 +1600,9 @@ pub struct ContextCompactedEvent;
 pub struct TurnCompleteEvent {
     pub turn_id: String,
     pub last_agent_message: Option<String>,
+    /// Token usage and estimated cost of this turn and of the session so far.
+    #[serde(default)]
+    pub usage: Option<UsageReport>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -1376,6 +1628,27 @@ pub struct TokenUsage {
     pub total_tokens: i64,
 }
 
+/// Token counts and estimated cost accumulated over part of a session.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct UsageSummary {
+    pub token_usage: TokenUsage,
+    /// Estimated cost in US dollars, or `None` when a model without configured pricing was used.
+    pub cost_usd: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct UsageReport {
+    /// Usage of the current turn, or of the most recent one when no turn is running.
+    pub turn: UsageSummary,
+    /// Usage of the whole session. Unlike `TokenUsageInfo`, this is not reset by compaction.
+    pub session: UsageSummary,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct GetUsageResponseEvent {
+    pub usage: UsageReport,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
 pub struct TokenUsageInfo {
     pub total_token_usage: TokenUsage,
@@ -1644,6 +1917,15 @@ pub struct McpInvocation {
     pub arguments: Option<serde_json::Value>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct McpToolCallQueuedEvent {
+    /// Identifier of the McpToolCallBegin event sent once the call starts.
+    pub call_id: String,
+    pub invocation: McpInvocation,
+    /// Place in line, starting at 1 for the next call to start. Sent again whenever it changes.
+    pub position: usize,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
 pub struct McpToolCallBeginEvent {
     /// Identifier so this can be paired with the McpToolCallEnd event.
@@ -1921,6 +2203,31 @@ pub enum RolloutItem {
     Compacted(CompactedItem),
     TurnContext(TurnContextItem),
     EventMsg(EventMsg),
+    SessionApproval(SessionApprovalItem),
+    PinnedContext(PinnedContextRolloutItem),
+}
+
+/// A tool call the user approved for the rest of the session, restored when it is resumed.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, TS)]
+pub struct SessionApprovalItem {
+    /// The serialized approval key of the tool call.
+    pub key: String,
+}
+
+/// A change to the session's pinned context, replayed when it is resumed or forked.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type")]
+pub enum PinnedContextRolloutItem {
+    /// An item was pinned with the contents captured at that time.
+    Pinned {
+        entry: PinnedContextEntry,
+        contents: String,
+        #[serde(default)]
+        excerpted: bool,
+    },
+    /// The pinned item with this id was removed.
+    Unpinned { id: String },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, TS)]
@@ -2158,11 +2465,7 @@ pub struct ExecCommandEndEvent {
     #[ts(optional)]
     pub interaction_input: Option<String>,
 
-    /// Captured stdout
-    pub stdout: String,
-    /// Captured stderr
-    pub stderr: String,
-    /// Captured aggregated output
+    /// Captured stdout and stderr, interleaved in the order they were produced.
     #[serde(default)]
     pub aggregated_output: String,
     /// The command's exit code.
@@ -2172,6 +2475,10 @@ pub struct ExecCommandEndEvent {
     pub duration: Duration,
     /// Formatted output from the command, as seen by the model.
     pub formatted_output: String,
+    /// File holding the untruncated output when `formatted_output` had to be truncated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub output_artifact: Option<PathBuf>,
     /// Completion status for this command execution.
     pub status: ExecCommandStatus,
 }
@@ -2230,13 +2537,86 @@ pub struct DeprecationNoticeEvent {
     pub details: Option<String>,
 }
 
+/// Settings applied to a single turn by [`Op::UserInputWithOverrides`]. Unset
+/// fields keep the session's current value.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct TurnOverrides {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effort: Option<ReasoningEffortConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_policy: Option<AskForApproval>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_policy: Option<SandboxPolicy>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct TurnSettingsEvent {
+    pub turn_id: String,
+    pub model: String,
+    pub effort: Option<ReasoningEffortConfig>,
+    pub approval_policy: AskForApproval,
+    pub sandbox_policy: SandboxPolicy,
+    pub cwd: PathBuf,
+    /// True when these settings apply to this turn only and the session
+    /// settings are restored afterwards.
+    pub turn_only: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct UserInputQueueUpdatedEvent {
+    /// Queued input, in the order the turns will start.
+    pub queued: Vec<QueuedUserInput>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct QueuedUserInput {
+    /// Id of the submission, which becomes the id of its turn.
+    pub id: String,
+    pub items: Vec<UserInput>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct ProjectDocsLoadedEvent {
+    /// Files that contributed instructions, ordered from the repository root
+    /// to the current working directory.
+    pub paths: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct CheckpointCreatedEvent {
+    /// Identifier to pass to `Op::RevertToCheckpoint`.
+    pub checkpoint_id: String,
+    /// Turn ID whose changes the checkpoint precedes.
+    pub turn_id: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct WorktreeCreatedEvent {
+    /// Root of the worktree the session edits.
+    pub path: PathBuf,
+    /// Branch checked out in the worktree.
+    pub branch: String,
+    /// Root of the repository the session was started from.
+    pub repo_root: PathBuf,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct WorktreeMergedEvent {
+    /// Branch that was merged.
+    pub branch: String,
+    /// New `HEAD` of the original repository, or `None` when there was nothing to merge.
+    pub commit: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct UndoStartedEvent {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
 pub struct UndoCompletedEvent {
     pub success: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -2261,6 +2641,68 @@ pub struct StreamErrorEvent {
     pub additional_details: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct StreamRetryEvent {
+    /// 1-based number of the upcoming retry.
+    #[ts(type = "number")]
+    pub attempt: u64,
+    /// Retries allowed before the turn fails.
+    #[ts(type = "number")]
+    pub max_attempts: u64,
+    /// Wait before the retry is sent, in milliseconds.
+    #[ts(type = "number")]
+    pub delay_ms: u64,
+    /// Error that triggered the retry.
+    pub reason: String,
+}
+
+/// Which budget a `BudgetLimitReachedEvent` refers to.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetScope {
+    /// Consumption of the current turn.
+    Turn,
+    /// Consumption of the current session.
+    Session,
+    /// Consumption of all sessions during the current local calendar day.
+    Daily,
+}
+
+/// The quantity a budget limit applies to.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetKind {
+    Tokens,
+    CostUsd,
+    WallClockMinutes,
+    ExecCalls,
+    Steps,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct BudgetLimitReachedEvent {
+    /// Identifier to pass back in `Op::BudgetApproval`.
+    pub id: String,
+    /// Turn ID that this pause belongs to.
+    pub turn_id: String,
+    pub scope: BudgetScope,
+    pub kind: BudgetKind,
+    /// Amount consumed so far, in the unit of `kind`.
+    pub used: f64,
+    /// Configured limit, in the unit of `kind`.
+    pub limit: f64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct AuthExpiredEvent {
+    /// Identifier to pass back in `Op::ReauthComplete`.
+    pub id: String,
+    /// Turn ID that this pause belongs to.
+    pub turn_id: String,
+    /// Why the tokens could not be refreshed.
+    pub message: String,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct StreamInfoEvent {
     pub message: String,
@@ -2314,6 +2756,15 @@ pub struct TurnDiffEvent {
     pub unified_diff: String,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct TurnStructuredOutputEvent {
+    pub turn_id: String,
+    /// The final message parsed as JSON.
+    pub output: Value,
+    /// How many times the model was asked to correct a final message that did not match.
+    pub retries: u32,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct GetHistoryEntryResponseEvent {
     pub offset: usize,
@@ -2335,6 +2786,27 @@ pub struct McpListToolsResponseEvent {
     pub auth_statuses: std::collections::HashMap<String, McpAuthStatus>,
 }
 
+/// Response payload for `Op::ListMcpPrompts`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ListMcpPromptsResponseEvent {
+    /// Prompt templates grouped by server name.
+    pub prompts: std::collections::HashMap<String, Vec<McpPrompt>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct GetMcpPromptResponseEvent {
+    pub server: String,
+    pub name: String,
+    /// Text of the expanded prompt's messages, or why the server could not expand it.
+    pub result: Result<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct GetMcpServerLogsResponseEvent {
+    /// The latest lines each server wrote to stderr, oldest first, keyed by server name.
+    pub logs: std::collections::HashMap<String, Vec<String>>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct McpStartupUpdateEvent {
     /// Server name being started.
@@ -2366,6 +2838,43 @@ pub struct McpStartupFailure {
     pub error: String,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct McpServerHealthEvent {
+    /// Name of the server.
+    pub server: String,
+    pub status: McpServerHealthStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case", tag = "state")]
+#[ts(rename_all = "snake_case", tag = "state")]
+pub enum McpServerHealthStatus {
+    /// The server exited or stopped answering pings; restart `attempt` is starting.
+    Restarting { attempt: u32, error: String },
+    /// The server was restarted and its tools were listed again.
+    Restarted,
+    /// The server could not be restarted. Its tools fail for the rest of the session.
+    Failed { error: String },
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct McpListChangedEvent {
+    /// Name of the server.
+    pub server: String,
+    pub list: McpListKind,
+}
+
+/// A list published by an MCP server, which the server announces changes to with
+/// `notifications/<list>/list_changed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum McpListKind {
+    Tools,
+    Prompts,
+    Resources,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
 #[serde(rename_all = "snake_case")]
 #[ts(rename_all = "snake_case")]
@@ -2388,6 +2897,31 @@ impl fmt::Display for McpAuthStatus {
     }
 }
 
+/// Where a pinned context item comes from.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type")]
+pub enum PinnedContextSource {
+    /// Free-form text supplied by the user (for example architectural constraints).
+    Text { text: String },
+    /// A file whose contents are captured when the item is pinned.
+    File { path: PathBuf },
+}
+
+/// A single pinned context item.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct PinnedContextEntry {
+    pub id: String,
+    pub source: PinnedContextSource,
+}
+
+/// Response payload for `Op::PinContext`, `Op::UnpinContext`, and
+/// `Op::ListPinnedContext`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct PinnedContextUpdatedEvent {
+    pub items: Vec<PinnedContextEntry>,
+}
+
 /// Response payload for `Op::ListCustomPrompts`.
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ListCustomPromptsResponseEvent {
@@ -2608,6 +3142,16 @@ pub enum ReviewDecision {
     /// remainder of the session.
     ApprovedForSession,
 
+    /// User has approved this command and wants future identical commands in
+    /// the same project to be approved without asking. The decision is
+    /// persisted to the trust store in `CODEX_HOME`.
+    ApprovedForProject,
+
+    /// User has approved this command and wants future identical commands to
+    /// be approved without asking in any project. The decision is persisted to
+    /// the trust store in `CODEX_HOME`.
+    ApprovedAlways,
+
     /// User has denied this command and the agent should not execute it, but
     /// it should continue the session and try something else.
     #[default]
@@ -2626,6 +3170,8 @@ impl ReviewDecision {
             ReviewDecision::Approved => "approved",
             ReviewDecision::ApprovedExecpolicyAmendment { .. } => "approved_with_amendment",
             ReviewDecision::ApprovedForSession => "approved_for_session",
+            ReviewDecision::ApprovedForProject => "approved_for_project",
+            ReviewDecision::ApprovedAlways => "approved_always",
             ReviewDecision::Denied => "denied",
             ReviewDecision::Abort => "abort",
         }
@@ -2790,6 +3336,32 @@ pub struct CollabResumeEndEvent {
     pub status: AgentStatus,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct DelegateBeginEvent {
+    /// Identifier for the `delegate` tool call.
+    pub call_id: String,
+    /// Task handed to the sub-agent.
+    pub task: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct DelegateProgressEvent {
+    /// Identifier for the `delegate` tool call.
+    pub call_id: String,
+    /// The sub-agent event, unchanged.
+    pub msg: Box<EventMsg>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct DelegateEndEvent {
+    /// Identifier for the `delegate` tool call.
+    pub call_id: String,
+    /// Final message of the sub-agent, returned to the model as the tool result.
+    pub output: Option<String>,
+    /// Why the sub-agent stopped without finishing its task.
+    pub error: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3212,4 +3784,16 @@ mod tests {
 
         assert_eq!(info.model_context_window, Some(258_400));
     }
+
+    #[test]
+    fn unknown_event_types_decode_as_unknown() -> Result<()> {
+        let event: Event = serde_json::from_value(json!({
+            "id": "1",
+            "msg": { "type": "added_in_a_later_version", "detail": 1 },
+        }))?;
+
+        assert_eq!(event.id, "1");
+        assert!(matches!(event.msg, EventMsg::Unknown));
+        Ok(())
+    }
 }
@@ -29,7 +29,8 @@ pub enum UserInput {
         name: String,
         path: std::path::PathBuf,
     },
-    /// Explicit mention selected by the user (name + app://connector id).
+    /// Explicit mention selected by the user: name + `app://` connector id, or name + MCP resource
+    /// path (`mcp-resource://<server>/<uri>`).
     Mention { name: String, path: String },
 }
 
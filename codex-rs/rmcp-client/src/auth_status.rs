@@ -64,8 +64,9 @@ async fn supports_oauth_login_with_headers(url: &str, default_headers: &HeaderMa
     let base_url = Url::parse(url)?;
 
     // Use no_proxy to avoid a bug in the system-configuration crate that
-    // can result in a panic. See #8912.
-    let builder = Client::builder().timeout(DISCOVERY_TIMEOUT).no_proxy();
+    // can result in a panic. See #8912. A proxy set in config.toml still applies.
+    let builder =
+        codex_client::apply_proxy_settings(Client::builder().timeout(DISCOVERY_TIMEOUT).no_proxy());
     let client = apply_default_headers(builder, default_headers).build()?;
 
     let mut last_error: Option<Error> = None;
@@ -1,17 +1,25 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 
 use rmcp::ErrorData as McpError;
 use rmcp::ServiceExt;
 use rmcp::handler::server::ServerHandler;
 use rmcp::model::CallToolRequestParams;
 use rmcp::model::CallToolResult;
+use rmcp::model::ErrorCode;
+use rmcp::model::GetPromptRequestParams;
+use rmcp::model::GetPromptResult;
 use rmcp::model::JsonObject;
+use rmcp::model::ListPromptsResult;
 use rmcp::model::ListResourceTemplatesResult;
 use rmcp::model::ListResourcesResult;
 use rmcp::model::ListToolsResult;
 use rmcp::model::PaginatedRequestParams;
+use rmcp::model::Prompt;
 use rmcp::model::RawResource;
 use rmcp::model::RawResourceTemplate;
 use rmcp::model::ReadResourceRequestParams;
@@ -31,10 +39,15 @@ struct TestToolServer {
     tools: Arc<Vec<Tool>>,
     resources: Arc<Vec<Resource>>,
     resource_templates: Arc<Vec<ResourceTemplate>>,
+    /// Set by the `add_tool` tool, which publishes the `added` tool.
+    added: Arc<AtomicBool>,
+    /// How many times the `slow` tool has been called.
+    slow_calls: Arc<AtomicUsize>,
 }
 
 const MEMO_URI: &str = "memo://codex/example-note";
 const MEMO_CONTENT: &str = "This is a sample MCP resource served by the rmcp test server.";
+const SUMMARIZE_MEMO_PROMPT: &str = "summarize-memo";
 const SMALL_PNG_BASE64: &str = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR4nGP4z8DwHwAFAAH/iZk9HQAAAABJRU5ErkJggg==";
 
 pub fn stdio() -> (tokio::io::Stdin, tokio::io::Stdout) {
@@ -47,6 +60,10 @@ impl TestToolServer {
             Self::echo_tool(),
             Self::image_tool(),
             Self::image_scenario_tool(),
+            Self::sample_tool(),
+            Self::add_tool_tool(),
+            Self::roots_tool(),
+            Self::slow_tool(),
         ];
         let resources = vec![Self::memo_resource()];
         let resource_templates = vec![Self::memo_template()];
@@ -54,6 +71,8 @@ impl TestToolServer {
             tools: Arc::new(tools),
             resources: Arc::new(resources),
             resource_templates: Arc::new(resource_templates),
+            added: Arc::new(AtomicBool::new(false)),
+            slow_calls: Arc::new(AtomicUsize::new(0)),
         }
     }
 
@@ -93,6 +112,77 @@ impl TestToolServer {
         )
     }
 
+    fn sample_tool() -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {
+                "prompt": { "type": "string" }
+            },
+            "required": ["prompt"],
+            "additionalProperties": false
+        }))
+        .expect("sample tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed("sample"),
+            Cow::Borrowed("Ask the client's model to answer the prompt through MCP sampling."),
+            Arc::new(schema),
+        )
+    }
+
+    fn add_tool_tool() -> Tool {
+        Self::no_args_tool(
+            "add_tool",
+            "Publish the `added` tool and tell the client the tool list changed.",
+        )
+    }
+
+    fn added_tool() -> Tool {
+        Self::no_args_tool("added", "Tool published by `add_tool`.")
+    }
+
+    fn roots_tool() -> Tool {
+        Self::no_args_tool("roots", "Return the client's MCP roots as JSON.")
+    }
+
+    fn slow_tool() -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {
+                "hang_calls": { "type": "integer", "minimum": 0 }
+            },
+            "required": ["hang_calls"],
+            "additionalProperties": false
+        }))
+        .expect("slow tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed("slow"),
+            Cow::Borrowed(
+                "Never answer the first `hang_calls` calls; later calls return their call number.",
+            ),
+            Arc::new(schema),
+        )
+    }
+
+    fn no_args_tool(name: &'static str, description: &'static str) -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {},
+            "additionalProperties": false
+        }))
+        .expect("tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed(name),
+            Cow::Borrowed(description),
+            Arc::new(schema),
+        )
+    }
+
     /// Tool intended for manual testing of Codex TUI rendering for MCP image tool results.
     ///
     /// This exists to exercise edge cases where a `CallToolResult.content` includes image blocks
@@ -179,6 +269,19 @@ impl TestToolServer {
     fn memo_text() -> &'static str {
         MEMO_CONTENT
     }
+
+    fn summarize_memo_prompt() -> Prompt {
+        serde_json::from_value(json!({
+            "name": SUMMARIZE_MEMO_PROMPT,
+            "description": "Summarize the example memo for an audience.",
+            "arguments": [{
+                "name": "audience",
+                "description": "Who the summary is for.",
+                "required": true,
+            }],
+        }))
+        .expect("valid prompt")
+    }
 }
 
 #[derive(Deserialize)]
@@ -205,6 +308,16 @@ enum ImageScenario {
     TextOnly,
 }
 
+#[derive(Deserialize)]
+struct SampleArgs {
+    prompt: String,
+}
+
+#[derive(Deserialize)]
+struct SlowArgs {
+    hang_calls: usize,
+}
+
 #[derive(Deserialize, Debug)]
 struct ImageScenarioArgs {
     scenario: ImageScenario,
@@ -221,20 +334,42 @@ impl ServerHandler for TestToolServer {
                 .enable_tools()
                 .enable_tool_list_changed()
                 .enable_resources()
+                .enable_prompts()
                 .build(),
             ..ServerInfo::default()
         }
     }
 
+    /// Answers pings with an error when `MCP_TEST_REJECT_PING` is set, like servers that do not
+    /// implement `ping`.
+    fn ping(
+        &self,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> impl std::future::Future<Output = Result<(), McpError>> + Send + '_ {
+        let result = if std::env::var_os("MCP_TEST_REJECT_PING").is_some() {
+            Err(McpError::new(
+                ErrorCode::METHOD_NOT_FOUND,
+                "ping is not supported",
+                None,
+            ))
+        } else {
+            Ok(())
+        };
+        std::future::ready(result)
+    }
+
     fn list_tools(
         &self,
         _request: Option<PaginatedRequestParams>,
         _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> impl std::future::Future<Output = Result<ListToolsResult, McpError>> + Send + '_ {
-        let tools = self.tools.clone();
+        let mut tools = (*self.tools).clone();
+        if self.added.load(Ordering::SeqCst) {
+            tools.push(Self::added_tool());
+        }
         async move {
             Ok(ListToolsResult {
-                tools: (*tools).clone(),
+                tools,
                 next_cursor: None,
                 meta: None,
             })
@@ -290,10 +425,52 @@ impl ServerHandler for TestToolServer {
         }
     }
 
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<ListPromptsResult, McpError> {
+        Ok(ListPromptsResult {
+            prompts: vec![Self::summarize_memo_prompt()],
+            next_cursor: None,
+            meta: None,
+        })
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParams,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        if request.name != SUMMARIZE_MEMO_PROMPT {
+            return Err(McpError::invalid_params(
+                "unknown prompt",
+                Some(json!({ "name": request.name })),
+            ));
+        }
+        let audience = request
+            .arguments
+            .as_ref()
+            .and_then(|arguments| arguments.get("audience"))
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| McpError::invalid_params("missing audience", None))?;
+        let result = serde_json::from_value(json!({
+            "messages": [{
+                "role": "user",
+                "content": {
+                    "type": "text",
+                    "text": format!("Summarize {MEMO_URI} for {audience}."),
+                },
+            }],
+        }))
+        .expect("valid prompt result");
+        Ok(result)
+    }
+
     async fn call_tool(
         &self,
         request: CallToolRequestParams,
-        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> Result<CallToolResult, McpError> {
         match request.name.as_ref() {
             "echo" => {
@@ -348,6 +525,69 @@ impl ServerHandler for TestToolServer {
                 let args = Self::parse_call_args::<ImageScenarioArgs>(&request, "image_scenario")?;
                 Self::image_scenario_result(args)
             }
+            "sample" => {
+                let args = Self::parse_call_args::<SampleArgs>(&request, "sample")?;
+                let params = serde_json::from_value(json!({
+                    "messages": [{
+                        "role": "user",
+                        "content": { "type": "text", "text": args.prompt },
+                    }],
+                    "maxTokens": 256,
+                }))
+                .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                let result = context
+                    .peer
+                    .create_message(params)
+                    .await
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                let result = serde_json::to_value(&result)
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                let text = result
+                    .pointer("/content/text")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or_default();
+                Ok(CallToolResult::success(vec![rmcp::model::Content::text(
+                    format!("SAMPLED: {text}"),
+                )]))
+            }
+            "add_tool" => {
+                self.added.store(true, Ordering::SeqCst);
+                context
+                    .peer
+                    .notify_tool_list_changed()
+                    .await
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                Ok(CallToolResult::success(vec![rmcp::model::Content::text(
+                    "added",
+                )]))
+            }
+            "roots" => {
+                let roots = context
+                    .peer
+                    .list_roots()
+                    .await
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                let roots = serde_json::to_string(&roots)
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                Ok(CallToolResult::success(vec![rmcp::model::Content::text(
+                    roots,
+                )]))
+            }
+            "slow" => {
+                let args = Self::parse_call_args::<SlowArgs>(&request, "slow")?;
+                let call = self.slow_calls.fetch_add(1, Ordering::SeqCst) + 1;
+                if call <= args.hang_calls {
+                    std::future::pending::<()>().await;
+                }
+                Ok(CallToolResult::success(vec![rmcp::model::Content::text(
+                    format!("slow call {call}"),
+                )]))
+            }
+            "added" if self.added.load(Ordering::SeqCst) => {
+                Ok(CallToolResult::success(vec![rmcp::model::Content::text(
+                    "ADDED",
+                )]))
+            }
             other => Err(McpError::invalid_params(
                 format!("unknown tool: {other}"),
                 None,
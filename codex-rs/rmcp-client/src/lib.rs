@@ -4,6 +4,8 @@ mod oauth;
 mod perform_oauth_login;
 mod program_resolver;
 mod rmcp_client;
+mod roots;
+mod server_log;
 mod utils;
 
 pub use auth_status::determine_streamable_http_auth_status;
@@ -22,6 +24,13 @@ pub use rmcp::model::ElicitationAction;
 pub use rmcp_client::Elicitation;
 pub use rmcp_client::ElicitationResponse;
 pub use rmcp_client::ListToolsWithConnectorIdResult;
+pub use rmcp_client::OnListChanged;
 pub use rmcp_client::RmcpClient;
+pub use rmcp_client::Sampling;
+pub use rmcp_client::SamplingResponse;
 pub use rmcp_client::SendElicitation;
+pub use rmcp_client::SendSampling;
 pub use rmcp_client::ToolWithConnectorId;
+pub use roots::McpRoots;
+pub use server_log::ServerLog;
+pub use utils::TransportError;
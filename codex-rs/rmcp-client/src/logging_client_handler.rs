@@ -1,11 +1,16 @@
 use std::sync::Arc;
 
+use codex_protocol::protocol::McpListKind;
 use rmcp::ClientHandler;
 use rmcp::RoleClient;
 use rmcp::model::CancelledNotificationParam;
 use rmcp::model::ClientInfo;
 use rmcp::model::CreateElicitationRequestParams;
 use rmcp::model::CreateElicitationResult;
+use rmcp::model::CreateMessageRequestParams;
+use rmcp::model::CreateMessageResult;
+use rmcp::model::ErrorCode;
+use rmcp::model::ListRootsResult;
 use rmcp::model::LoggingLevel;
 use rmcp::model::LoggingMessageNotificationParam;
 use rmcp::model::ProgressNotificationParam;
@@ -17,19 +22,41 @@ use tracing::error;
 use tracing::info;
 use tracing::warn;
 
+use crate::McpRoots;
+use crate::rmcp_client::OnListChanged;
 use crate::rmcp_client::SendElicitation;
+use crate::rmcp_client::SendSampling;
 
 #[derive(Clone)]
 pub(crate) struct LoggingClientHandler {
     client_info: ClientInfo,
     send_elicitation: Arc<SendElicitation>,
+    send_sampling: Option<Arc<SendSampling>>,
+    on_list_changed: Option<Arc<OnListChanged>>,
+    roots: McpRoots,
 }
 
 impl LoggingClientHandler {
-    pub(crate) fn new(client_info: ClientInfo, send_elicitation: SendElicitation) -> Self {
+    pub(crate) fn new(
+        client_info: ClientInfo,
+        send_elicitation: SendElicitation,
+        send_sampling: Option<SendSampling>,
+        on_list_changed: Option<OnListChanged>,
+        roots: McpRoots,
+    ) -> Self {
         Self {
             client_info,
             send_elicitation: Arc::new(send_elicitation),
+            send_sampling: send_sampling.map(Arc::new),
+            on_list_changed: on_list_changed.map(Arc::new),
+            roots,
+        }
+    }
+
+    fn list_changed(&self, list: McpListKind) {
+        info!("MCP server {list:?} list changed");
+        if let Some(on_list_changed) = self.on_list_changed.as_ref() {
+            on_list_changed(list);
         }
     }
 }
@@ -45,6 +72,32 @@ impl ClientHandler for LoggingClientHandler {
             .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))
     }
 
+    async fn create_message(
+        &self,
+        params: CreateMessageRequestParams,
+        context: RequestContext<RoleClient>,
+    ) -> Result<CreateMessageResult, rmcp::ErrorData> {
+        let Some(send_sampling) = self.send_sampling.as_ref() else {
+            return Err(rmcp::ErrorData::new(
+                ErrorCode::METHOD_NOT_FOUND,
+                "sampling is not supported by this client",
+                None,
+            ));
+        };
+        send_sampling(context.id, params)
+            .await
+            .map_err(|err| rmcp::ErrorData::internal_error(format!("{err:#}"), None))
+    }
+
+    async fn list_roots(
+        &self,
+        _context: RequestContext<RoleClient>,
+    ) -> Result<ListRootsResult, rmcp::ErrorData> {
+        self.roots
+            .list()
+            .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))
+    }
+
     async fn on_cancelled(
         &self,
         params: CancelledNotificationParam,
@@ -76,15 +129,15 @@ impl ClientHandler for LoggingClientHandler {
     }
 
     async fn on_resource_list_changed(&self, _context: NotificationContext<RoleClient>) {
-        info!("MCP server resource list changed");
+        self.list_changed(McpListKind::Resources);
     }
 
     async fn on_tool_list_changed(&self, _context: NotificationContext<RoleClient>) {
-        info!("MCP server tool list changed");
+        self.list_changed(McpListKind::Tools);
     }
 
     async fn on_prompt_list_changed(&self, _context: NotificationContext<RoleClient>) {
-        info!("MCP server prompt list changed");
+        self.list_changed(McpListKind::Prompts);
     }
 
     fn get_info(&self) -> ClientInfo {
@@ -8,6 +8,7 @@ use std::time::Duration;
 
 use anyhow::Result;
 use anyhow::anyhow;
+use codex_protocol::protocol::McpListKind;
 use futures::FutureExt;
 use futures::future::BoxFuture;
 use oauth2::TokenResponse;
@@ -19,15 +20,21 @@ use rmcp::model::ClientNotification;
 use rmcp::model::ClientRequest;
 use rmcp::model::CreateElicitationRequestParams;
 use rmcp::model::CreateElicitationResult;
+use rmcp::model::CreateMessageRequestParams;
+use rmcp::model::CreateMessageResult;
 use rmcp::model::CustomNotification;
 use rmcp::model::CustomRequest;
 use rmcp::model::Extensions;
+use rmcp::model::GetPromptRequestParams;
+use rmcp::model::GetPromptResult;
 use rmcp::model::InitializeRequestParams;
 use rmcp::model::InitializeResult;
+use rmcp::model::ListPromptsResult;
 use rmcp::model::ListResourceTemplatesResult;
 use rmcp::model::ListResourcesResult;
 use rmcp::model::ListToolsResult;
 use rmcp::model::PaginatedRequestParams;
+use rmcp::model::PingRequest;
 use rmcp::model::ReadResourceRequestParams;
 use rmcp::model::ReadResourceResult;
 use rmcp::model::RequestId;
@@ -51,6 +58,8 @@ use tokio::time;
 use tracing::info;
 use tracing::warn;
 
+use crate::McpRoots;
+use crate::ServerLog;
 use crate::load_oauth_tokens;
 use crate::logging_client_handler::LoggingClientHandler;
 use crate::oauth::OAuthCredentialsStoreMode;
@@ -60,6 +69,7 @@ use crate::program_resolver;
 use crate::utils::apply_default_headers;
 use crate::utils::build_default_headers;
 use crate::utils::create_env_for_mcp_server;
+use crate::utils::http_client_builder;
 use crate::utils::run_with_timeout;
 
 enum PendingTransport {
@@ -154,6 +164,16 @@ pub type SendElicitation = Box<
     dyn Fn(RequestId, Elicitation) -> BoxFuture<'static, Result<ElicitationResponse>> + Send + Sync,
 >;
 
+pub type Sampling = CreateMessageRequestParams;
+pub type SamplingResponse = CreateMessageResult;
+
+/// Interface for answering `sampling/createMessage` requests from the server with a model.
+pub type SendSampling =
+    Box<dyn Fn(RequestId, Sampling) -> BoxFuture<'static, Result<SamplingResponse>> + Send + Sync>;
+
+/// Called when the server sends a `notifications/<list>/list_changed` notification.
+pub type OnListChanged = Box<dyn Fn(McpListKind) + Send + Sync>;
+
 pub struct ToolWithConnectorId {
     pub tool: Tool,
     pub connector_id: Option<String>,
@@ -178,6 +198,7 @@ impl RmcpClient {
         env: Option<HashMap<String, String>>,
         env_vars: &[String],
         cwd: Option<PathBuf>,
+        log: ServerLog,
     ) -> io::Result<Self> {
         let program_name = program.to_string_lossy().into_owned();
 
@@ -213,6 +234,7 @@ impl RmcpClient {
                     match reader.next_line().await {
                         Ok(Some(line)) => {
                             info!("MCP server stderr ({program_name}): {line}");
+                            log.push(line);
                         }
                         Ok(None) => break,
                         Err(error) => {
@@ -290,8 +312,7 @@ impl RmcpClient {
                         StreamableHttpClientTransportConfig::with_uri(url.to_string())
                             .auth_header(access_token);
                     let http_client =
-                        apply_default_headers(reqwest::Client::builder(), &default_headers)
-                            .build()?;
+                        apply_default_headers(http_client_builder(), &default_headers).build()?;
                     let transport =
                         StreamableHttpClientTransport::with_client(http_client, http_config);
                     PendingTransport::StreamableHttp { transport }
@@ -305,7 +326,7 @@ impl RmcpClient {
             }
 
             let http_client =
-                apply_default_headers(reqwest::Client::builder(), &default_headers).build()?;
+                apply_default_headers(http_client_builder(), &default_headers).build()?;
 
             let transport = StreamableHttpClientTransport::with_client(http_client, http_config);
             PendingTransport::StreamableHttp { transport }
@@ -319,13 +340,26 @@ impl RmcpClient {
 
     /// Perform the initialization handshake with the MCP server.
     /// https://modelcontextprotocol.io/specification/2025-06-18/basic/lifecycle#initialization
+    ///
+    /// Sampling requests from the server are answered with `send_sampling`; without it they fail
+    /// as unsupported, so `params` should only advertise the sampling capability when it is set.
+    /// `roots/list` requests are answered with `roots`.
     pub async fn initialize(
         &self,
         params: InitializeRequestParams,
         timeout: Option<Duration>,
         send_elicitation: SendElicitation,
+        send_sampling: Option<SendSampling>,
+        on_list_changed: Option<OnListChanged>,
+        roots: McpRoots,
     ) -> Result<InitializeResult> {
-        let client_handler = LoggingClientHandler::new(params.clone(), send_elicitation);
+        let client_handler = LoggingClientHandler::new(
+            params.clone(),
+            send_elicitation,
+            send_sampling,
+            on_list_changed,
+            roots,
+        );
 
         let (transport, oauth_persistor, process_group_guard) = {
             let mut guard = self.state.lock().await;
@@ -486,6 +520,32 @@ impl RmcpClient {
         Ok(result)
     }
 
+    pub async fn list_prompts(
+        &self,
+        params: Option<PaginatedRequestParams>,
+        timeout: Option<Duration>,
+    ) -> Result<ListPromptsResult> {
+        self.refresh_oauth_if_needed().await;
+        let service = self.service().await?;
+        let fut = service.list_prompts(params);
+        let result = run_with_timeout(fut, timeout, "prompts/list").await?;
+        self.persist_oauth_tokens().await;
+        Ok(result)
+    }
+
+    pub async fn get_prompt(
+        &self,
+        params: GetPromptRequestParams,
+        timeout: Option<Duration>,
+    ) -> Result<GetPromptResult> {
+        self.refresh_oauth_if_needed().await;
+        let service = self.service().await?;
+        let fut = service.get_prompt(params);
+        let result = run_with_timeout(fut, timeout, "prompts/get").await?;
+        self.persist_oauth_tokens().await;
+        Ok(result)
+    }
+
     pub async fn call_tool(
         &self,
         name: String,
@@ -515,6 +575,23 @@ impl RmcpClient {
         Ok(result)
     }
 
+    /// Checks that the server is still answering requests.
+    /// https://modelcontextprotocol.io/specification/2025-06-18/basic/utilities/ping
+    pub async fn ping(&self, timeout: Option<Duration>) -> Result<()> {
+        let service = self.service().await?;
+        let fut = service.send_request(ClientRequest::PingRequest(PingRequest::default()));
+        run_with_timeout(fut, timeout, "ping").await?;
+        Ok(())
+    }
+
+    /// Tells the server that the roots given to [`RmcpClient::initialize`] changed, so it asks
+    /// for them again.
+    pub async fn notify_roots_list_changed(&self) -> Result<()> {
+        let service = self.service().await?;
+        service.notify_roots_list_changed().await?;
+        Ok(())
+    }
+
     pub async fn send_custom_notification(
         &self,
         method: &str,
@@ -593,8 +670,7 @@ async fn create_oauth_transport_and_runtime(
     StreamableHttpClientTransport<AuthClient<reqwest::Client>>,
     OAuthPersistor,
 )> {
-    let http_client =
-        apply_default_headers(reqwest::Client::builder(), &default_headers).build()?;
+    let http_client = apply_default_headers(http_client_builder(), &default_headers).build()?;
     let mut oauth_state = OAuthState::new(url.to_string(), Some(http_client.clone())).await?;
 
     oauth_state
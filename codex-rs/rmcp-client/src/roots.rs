@@ -0,0 +1,69 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use rmcp::model::ListRootsResult;
+use serde_json::json;
+use url::Url;
+
+/// Directories offered to servers as MCP roots, the answer to their `roots/list` requests.
+/// Clones share the same list, so one `McpRoots` can be handed to every client of a session.
+#[derive(Clone, Debug, Default)]
+pub struct McpRoots(Arc<Mutex<Vec<PathBuf>>>);
+
+impl McpRoots {
+    /// Replaces the roots and returns whether they changed, in which case servers should be sent
+    /// `notifications/roots/list_changed`.
+    pub fn set(&self, roots: Vec<PathBuf>) -> bool {
+        let mut current = self.lock();
+        if *current == roots {
+            return false;
+        }
+        *current = roots;
+        true
+    }
+
+    /// The roots as `file://` URIs named after their directory. Relative paths cannot be turned
+    /// into URIs and are left out.
+    pub(crate) fn list(&self) -> serde_json::Result<ListRootsResult> {
+        let roots = self
+            .lock()
+            .iter()
+            .filter_map(|path| {
+                let uri = Url::from_file_path(path).ok()?;
+                let name = path.file_name().map(|name| name.to_string_lossy());
+                Some(json!({ "uri": uri.as_str(), "name": name }))
+            })
+            .collect::<Vec<_>>();
+        serde_json::from_value(json!({ "roots": roots }))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<PathBuf>> {
+        self.0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn roots_are_listed_as_file_uris() {
+        let roots = McpRoots::default();
+        let project = std::env::temp_dir().join("my project");
+        assert!(roots.set(vec![project.clone(), PathBuf::from("relative")]));
+        assert!(!roots.set(vec![project.clone(), PathBuf::from("relative")]));
+
+        let listed =
+            serde_json::to_value(roots.list().expect("valid roots")).expect("roots serialize");
+        let uri = Url::from_file_path(&project).expect("absolute path");
+        assert!(uri.as_str().ends_with("/my%20project"));
+        assert_eq!(
+            listed,
+            json!({ "roots": [{ "uri": uri.as_str(), "name": "my project" }] })
+        );
+    }
+}
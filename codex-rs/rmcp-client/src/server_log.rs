@@ -0,0 +1,54 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+/// Most lines kept per server; older lines are dropped first.
+const MAX_SERVER_LOG_LINES: usize = 500;
+
+/// The latest lines a stdio MCP server wrote to stderr. Clones share the same buffer, so a log
+/// kept across restarts of a server shows the output of every run.
+#[derive(Clone, Debug, Default)]
+pub struct ServerLog(Arc<Mutex<VecDeque<String>>>);
+
+impl ServerLog {
+    pub fn push(&self, line: String) {
+        let mut lines = self.lock();
+        if lines.len() == MAX_SERVER_LOG_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    /// Captured lines, oldest first.
+    pub fn lines(&self) -> Vec<String> {
+        self.lock().iter().cloned().collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<String>> {
+        self.0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn server_log_keeps_the_latest_lines() {
+        let log = ServerLog::default();
+        for i in 0..MAX_SERVER_LOG_LINES + 2 {
+            log.push(format!("line {i}"));
+        }
+
+        let lines = log.lines();
+        assert_eq!(lines.len(), MAX_SERVER_LOG_LINES);
+        assert_eq!(lines.first().map(String::as_str), Some("line 2"));
+        assert_eq!(
+            lines.last().map(String::as_str),
+            Some(format!("line {}", MAX_SERVER_LOG_LINES + 1).as_str())
+        );
+    }
+}
@@ -2,7 +2,6 @@ use std::collections::HashMap;
 use std::env;
 use std::time::Duration;
 
-use anyhow::Context;
 use anyhow::Result;
 use anyhow::anyhow;
 use reqwest::ClientBuilder;
@@ -12,6 +11,20 @@ use reqwest::header::HeaderValue;
 use rmcp::service::ServiceError;
 use tokio::time;
 
+/// A request that got no answer from the server: it timed out or the connection failed.
+/// Errors the server answered with are not `TransportError`s. Check with
+/// `error.is::<TransportError>()`.
+#[derive(Debug)]
+pub struct TransportError(String);
+
+impl std::fmt::Display for TransportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for TransportError {}
+
 pub(crate) async fn run_with_timeout<F, T>(
     fut: F,
     timeout: Option<Duration>,
@@ -20,14 +33,16 @@ pub(crate) async fn run_with_timeout<F, T>(
 where
     F: std::future::Future<Output = Result<T, ServiceError>>,
 {
-    if let Some(duration) = timeout {
-        let result = time::timeout(duration, fut)
-            .await
-            .with_context(|| anyhow!("timed out awaiting {label} after {duration:?}"))?;
-        result.map_err(|err| anyhow!("{label} failed: {err}"))
-    } else {
-        fut.await.map_err(|err| anyhow!("{label} failed: {err}"))
-    }
+    let result = match timeout {
+        Some(duration) => time::timeout(duration, fut).await.map_err(|_| {
+            TransportError(format!("timed out awaiting {label} after {duration:?}"))
+        })?,
+        None => fut.await,
+    };
+    result.map_err(|err| match err {
+        ServiceError::McpError(_) => anyhow!("{label} failed: {err}"),
+        err => TransportError(format!("{label} failed: {err}")).into(),
+    })
 }
 
 pub(crate) fn create_env_for_mcp_server(
@@ -101,6 +116,11 @@ pub(crate) fn build_default_headers(
     Ok(headers)
 }
 
+/// A client builder that goes through the proxy from `[proxy]` in `config.toml`, if one is set.
+pub(crate) fn http_client_builder() -> ClientBuilder {
+    codex_client::apply_proxy_settings(ClientBuilder::new())
+}
+
 pub(crate) fn apply_default_headers(
     builder: ClientBuilder,
     default_headers: &HeaderMap,
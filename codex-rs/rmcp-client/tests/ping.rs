@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::time::Duration;
+
+use codex_rmcp_client::ElicitationAction;
+use codex_rmcp_client::ElicitationResponse;
+use codex_rmcp_client::McpRoots;
+use codex_rmcp_client::RmcpClient;
+use codex_rmcp_client::ServerLog;
+use codex_rmcp_client::TransportError;
+use futures::FutureExt as _;
+use rmcp::model::ClientCapabilities;
+use rmcp::model::Implementation;
+use rmcp::model::InitializeRequestParams;
+use rmcp::model::ProtocolVersion;
+
+async fn start_client(env: HashMap<String, String>) -> anyhow::Result<RmcpClient> {
+    let program = codex_utils_cargo_bin::cargo_bin("test_stdio_server")?;
+    let client = RmcpClient::new_stdio_client(
+        program.into(),
+        Vec::<OsString>::new(),
+        Some(env),
+        &[],
+        None,
+        ServerLog::default(),
+    )
+    .await?;
+    client
+        .initialize(
+            InitializeRequestParams {
+                meta: None,
+                capabilities: ClientCapabilities::default(),
+                client_info: Implementation {
+                    name: "codex-test".into(),
+                    version: "0.0.0-test".into(),
+                    title: None,
+                    description: None,
+                    icons: None,
+                    website_url: None,
+                },
+                protocol_version: ProtocolVersion::V_2025_06_18,
+            },
+            Some(Duration::from_secs(5)),
+            Box::new(|_, _| {
+                async {
+                    Ok(ElicitationResponse {
+                        action: ElicitationAction::Decline,
+                        content: None,
+                    })
+                }
+                .boxed()
+            }),
+            None,
+            None,
+            McpRoots::default(),
+        )
+        .await?;
+    Ok(client)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn ping_answered_by_the_server_succeeds() -> anyhow::Result<()> {
+    let client = start_client(HashMap::new()).await?;
+
+    client.ping(Some(Duration::from_secs(5))).await?;
+
+    Ok(())
+}
+
+/// A server that rejects `ping` is still running, so the error must not look like a lost
+/// connection. The MCP supervisor only restarts servers on [`TransportError`]s.
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn ping_rejected_by_the_server_is_not_a_transport_error() -> anyhow::Result<()> {
+    let client = start_client(HashMap::from([(
+        "MCP_TEST_REJECT_PING".to_string(),
+        "1".to_string(),
+    )]))
+    .await?;
+
+    let error = client
+        .ping(Some(Duration::from_secs(5)))
+        .await
+        .expect_err("server rejects ping");
+
+    assert!(
+        !error.is::<TransportError>(),
+        "unexpected transport error: {error:#}"
+    );
+    Ok(())
+}
@@ -9,6 +9,7 @@ use std::time::Duration;
 use anyhow::Context;
 use anyhow::Result;
 use codex_rmcp_client::RmcpClient;
+use codex_rmcp_client::ServerLog;
 
 fn process_exists(pid: u32) -> bool {
     std::process::Command::new("kill")
@@ -73,6 +74,7 @@ async fn drop_kills_wrapper_process_group() -> Result<()> {
         )])),
         &[],
         None,
+        ServerLog::default(),
     )
     .await?;
 
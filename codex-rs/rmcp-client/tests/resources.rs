@@ -4,7 +4,9 @@ use std::time::Duration;
 
 use codex_rmcp_client::ElicitationAction;
 use codex_rmcp_client::ElicitationResponse;
+use codex_rmcp_client::McpRoots;
 use codex_rmcp_client::RmcpClient;
+use codex_rmcp_client::ServerLog;
 use codex_utils_cargo_bin::CargoBinError;
 use futures::FutureExt as _;
 use rmcp::model::AnnotateAble;
@@ -61,6 +63,7 @@ async fn rmcp_client_can_list_and_read_resources() -> anyhow::Result<()> {
         None,
         &[],
         None,
+        ServerLog::default(),
     )
     .await?;
 
@@ -77,6 +80,9 @@ async fn rmcp_client_can_list_and_read_resources() -> anyhow::Result<()> {
                 }
                 .boxed()
             }),
+            None,
+            None,
+            McpRoots::default(),
         )
         .await?;
 
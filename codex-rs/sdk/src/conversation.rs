@@ -0,0 +1,242 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_core::CodexThread;
+use codex_core::NewThread;
+use codex_core::ThreadManager;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::Event;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::Op;
+use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::protocol::TurnAbortReason;
+use codex_protocol::user_input::UserInput;
+use tokio_util::sync::CancellationToken;
+
+use crate::error::Error;
+use crate::error::Result;
+
+/// A running conversation with the agent.
+///
+/// Input is submitted with [`Conversation::submit`] and everything the agent does is reported as
+/// [`Event`]s, read in order with [`Conversation::next_event`]. Events must be read for the
+/// conversation to make progress.
+pub struct Conversation {
+    id: ThreadId,
+    model: String,
+    rollout_path: Option<PathBuf>,
+    thread: Arc<CodexThread>,
+    thread_manager: Arc<ThreadManager>,
+}
+
+/// What happened during a turn run with [`Conversation::run`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct TurnOutcome {
+    /// Every event received while waiting for the turn, ending with `TurnComplete` or
+    /// `TurnAborted`.
+    pub events: Vec<Event>,
+    /// The agent's final message, when the turn completed with one.
+    pub last_agent_message: Option<String>,
+    /// Whether the turn was interrupted instead of completing.
+    pub aborted: bool,
+}
+
+impl Conversation {
+    pub(crate) fn new(new_thread: NewThread, thread_manager: Arc<ThreadManager>) -> Self {
+        let NewThread {
+            thread_id,
+            thread,
+            session_configured,
+        } = new_thread;
+        Self {
+            id: thread_id,
+            model: session_configured.model,
+            rollout_path: session_configured.rollout_path,
+            thread,
+            thread_manager,
+        }
+    }
+
+    pub fn id(&self) -> ThreadId {
+        self.id
+    }
+
+    /// Model the conversation started with.
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// File the conversation is recorded in, for [`Codex::resume_conversation`]. `None` for
+    /// ephemeral conversations.
+    ///
+    /// [`Codex::resume_conversation`]: crate::Codex::resume_conversation
+    pub fn rollout_path(&self) -> Option<&std::path::Path> {
+        self.rollout_path.as_deref()
+    }
+
+    /// Starts a turn with `input` and returns its id. When a turn is already running, the input is
+    /// queued and its turn starts once the running turn ends; to add input to the running turn,
+    /// use [`Conversation::steer`].
+    pub async fn submit(&self, input: Vec<UserInput>) -> Result<String> {
+        self.submit_op(Op::UserInput {
+            items: input,
+            final_output_json_schema: None,
+        })
+        .await
+    }
+
+    /// Submits a plain text message; see [`Conversation::submit`].
+    pub async fn send_message(&self, text: impl Into<String>) -> Result<String> {
+        self.submit(vec![UserInput::Text {
+            text: text.into(),
+            text_elements: Vec::new(),
+        }])
+        .await
+    }
+
+    /// Adds `input` to the running turn without interrupting it and returns the turn's id. The agent
+    /// sees the input at its next step, after the tool calls it is running finish, so completed
+    /// work is kept.
+    ///
+    /// Unlike [`Conversation::submit`], this never starts a new turn: it fails with
+    /// [`Error::NoActiveTurn`] when no turn is running, and with [`Error::TurnMismatch`] when
+    /// `expected_turn_id` is set and another turn is running.
+    pub async fn steer(
+        &self,
+        input: Vec<UserInput>,
+        expected_turn_id: Option<&str>,
+    ) -> Result<String> {
+        Ok(self.thread.steer_input(input, expected_turn_id).await?)
+    }
+
+    /// Waits for the next event.
+    ///
+    /// Cancel safe: dropping the future before it completes does not lose an event.
+    pub async fn next_event(&self) -> Result<Event> {
+        self.thread.next_event().await.map_err(Error::agent)
+    }
+
+    /// Answers an `ExecApprovalRequest` event. `approval_id` is the event's
+    /// `effective_approval_id()`.
+    pub async fn respond_to_exec_approval(
+        &self,
+        approval_id: impl Into<String>,
+        decision: ReviewDecision,
+    ) -> Result<()> {
+        self.submit_op(Op::ExecApproval {
+            id: approval_id.into(),
+            turn_id: None,
+            decision,
+        })
+        .await
+        .map(drop)
+    }
+
+    /// Answers an `ApplyPatchApprovalRequest` event. `call_id` is the event's `call_id`.
+    pub async fn respond_to_patch_approval(
+        &self,
+        call_id: impl Into<String>,
+        decision: ReviewDecision,
+    ) -> Result<()> {
+        self.submit_op(Op::PatchApproval {
+            id: call_id.into(),
+            decision,
+        })
+        .await
+        .map(drop)
+    }
+
+    /// Stops the running turn. The turn ends with a `TurnAborted` event.
+    pub async fn interrupt(&self) -> Result<()> {
+        self.submit_op(Op::Interrupt).await.map(drop)
+    }
+
+    /// Submits `input` and collects the events received until its turn ends, waiting for a running
+    /// turn to end first.
+    ///
+    /// Cancelling `cancel` interrupts the turn; the call still returns once the agent has stopped.
+    /// Approval requests are declined, so configure an approval policy that does not ask when
+    /// the turn must be able to run commands or edit files.
+    pub async fn run(
+        &self,
+        input: Vec<UserInput>,
+        cancel: &CancellationToken,
+    ) -> Result<TurnOutcome> {
+        let turn_id = self.submit(input).await?;
+        let mut events = Vec::new();
+        let mut started = false;
+        let mut interrupted = false;
+        loop {
+            let event = tokio::select! {
+                event = self.next_event() => event?,
+                _ = cancel.cancelled(), if !interrupted => {
+                    interrupted = true;
+                    self.interrupt().await?;
+                    continue;
+                }
+            };
+            match &event.msg {
+                EventMsg::TurnStarted(started_event) if started_event.turn_id == turn_id => {
+                    started = true;
+                }
+                EventMsg::ExecApprovalRequest(request) => {
+                    self.respond_to_exec_approval(
+                        request.effective_approval_id(),
+                        ReviewDecision::Denied,
+                    )
+                    .await?;
+                }
+                EventMsg::ApplyPatchApprovalRequest(request) => {
+                    self.respond_to_patch_approval(request.call_id.clone(), ReviewDecision::Denied)
+                        .await?;
+                }
+                EventMsg::TurnComplete(complete) if complete.turn_id == turn_id => {
+                    let last_agent_message = complete.last_agent_message.clone();
+                    events.push(event);
+                    return Ok(TurnOutcome {
+                        events,
+                        last_agent_message,
+                        aborted: false,
+                    });
+                }
+                // An interrupt before the turn started drops the queued input.
+                EventMsg::TurnAborted(aborted)
+                    if aborted.turn_id.as_deref() == Some(turn_id.as_str())
+                        || (!started && aborted.reason == TurnAbortReason::Interrupted) =>
+                {
+                    events.push(event);
+                    return Ok(TurnOutcome {
+                        events,
+                        last_agent_message: None,
+                        aborted: true,
+                    });
+                }
+                _ => {}
+            }
+            events.push(event);
+        }
+    }
+
+    /// Ends the conversation, waiting for the agent to finish shutting down.
+    pub async fn shutdown(self) -> Result<()> {
+        self.submit_op(Op::Shutdown).await?;
+        loop {
+            match self.next_event().await {
+                Ok(Event {
+                    msg: EventMsg::ShutdownComplete,
+                    ..
+                })
+                | Err(Error::ConversationClosed) => break,
+                Ok(_) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        self.thread_manager.remove_thread(&self.id).await;
+        Ok(())
+    }
+
+    async fn submit_op(&self, op: Op) -> Result<String> {
+        self.thread.submit(op).await.map_err(Error::agent)
+    }
+}
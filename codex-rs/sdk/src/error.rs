@@ -0,0 +1,61 @@
+use std::io;
+
+use codex_core::SteerInputError;
+use codex_core::error::CodexErr;
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// A value passed to [`CodexBuilder::config`](crate::CodexBuilder::config) is malformed.
+    #[error("invalid config override: {0}")]
+    InvalidConfigOverride(String),
+
+    /// The configuration could not be loaded.
+    #[error("failed to load config: {0}")]
+    Config(#[source] io::Error),
+
+    /// The conversation has shut down and no longer accepts input or produces events.
+    #[error("conversation is closed")]
+    ConversationClosed,
+
+    /// [`Conversation::steer`](crate::Conversation::steer) was called while no turn is running.
+    #[error("no active turn to steer")]
+    NoActiveTurn,
+
+    /// [`Conversation::steer`](crate::Conversation::steer) expected a different turn than the one
+    /// running.
+    #[error("expected active turn id `{expected}` but found `{actual}`")]
+    TurnMismatch { expected: String, actual: String },
+
+    /// Input with no items was submitted.
+    #[error("input must not be empty")]
+    EmptyInput,
+
+    /// The agent failed to carry out a request.
+    #[error(transparent)]
+    Agent(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl Error {
+    pub(crate) fn agent(err: CodexErr) -> Self {
+        match err {
+            CodexErr::InternalAgentDied => Self::ConversationClosed,
+            err => Self::Agent(Box::new(err)),
+        }
+    }
+}
+
+impl From<SteerInputError> for Error {
+    fn from(err: SteerInputError) -> Self {
+        match err {
+            SteerInputError::NoActiveTurn(_) => Self::NoActiveTurn,
+            SteerInputError::ExpectedTurnMismatch { expected, actual } => {
+                Self::TurnMismatch { expected, actual }
+            }
+            SteerInputError::EmptyInput => Self::EmptyInput,
+        }
+    }
+}
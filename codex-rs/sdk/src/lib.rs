@@ -0,0 +1,230 @@
+//! Embed the Codex agent in a Rust application.
+//!
+//! This crate is the supported way to drive Codex from another program. It wraps the internals of
+//! `codex-core` behind a small API:
+//!
+//! - [`CodexBuilder`] resolves configuration the same way the CLI does, starting from
+//!   `$CODEX_HOME/config.toml` and applying the builder's settings on top.
+//! - [`Codex`] starts and resumes conversations.
+//! - [`Conversation`] submits typed input, streams [`Event`]s, answers approval requests, and
+//!   steers or interrupts the running turn.
+//!
+//! ```no_run
+//! # async fn example() -> codex_sdk::Result<()> {
+//! use codex_sdk::Codex;
+//! use codex_sdk::EventMsg;
+//!
+//! let codex = Codex::builder().cwd("/path/to/repo").build().await?;
+//! let conversation = codex.start_conversation().await?;
+//! conversation.send_message("Explain what this repository does").await?;
+//! loop {
+//!     let event = conversation.next_event().await?;
+//!     match event.msg {
+//!         EventMsg::AgentMessage(message) => println!("{}", message.message),
+//!         EventMsg::TurnComplete(_) => break,
+//!         _ => {}
+//!     }
+//! }
+//! conversation.shutdown().await
+//! # }
+//! ```
+//!
+//! # Stability
+//!
+//! Items defined in this crate follow semantic versioning independently of the rest of the
+//! workspace: internal crates such as `codex-core` may change in any release, but a breaking change
+//! here is only made together with a major version bump of this crate.
+//!
+//! Events and inputs are re-exported from `codex-protocol` and are versioned by the event protocol
+//! (see [`PROTOCOL_VERSION`]). New event types and fields may be added without a major bump, so
+//! matches over [`EventMsg`] should always include a wildcard arm.
+
+mod conversation;
+mod error;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_core::AuthManager;
+use codex_core::ThreadManager;
+use codex_core::config::Config;
+use codex_core::config::ConfigBuilder;
+use codex_core::config::ConfigOverrides;
+use codex_protocol::protocol::SessionSource;
+use codex_utils_cli::CliConfigOverrides;
+
+pub use codex_protocol::ThreadId;
+pub use codex_protocol::config_types::SandboxMode;
+pub use codex_protocol::protocol::AskForApproval;
+pub use codex_protocol::protocol::Event;
+pub use codex_protocol::protocol::EventMsg;
+pub use codex_protocol::protocol::PROTOCOL_VERSION;
+pub use codex_protocol::protocol::ReviewDecision;
+pub use codex_protocol::user_input::UserInput;
+pub use conversation::Conversation;
+pub use conversation::TurnOutcome;
+pub use error::Error;
+pub use error::Result;
+pub use tokio_util::sync::CancellationToken;
+
+/// Entry point for embedding Codex. Create one with [`Codex::builder`] and share it between all
+/// conversations of the application.
+pub struct Codex {
+    config: Config,
+    auth_manager: Arc<AuthManager>,
+    thread_manager: Arc<ThreadManager>,
+}
+
+impl Codex {
+    pub fn builder() -> CodexBuilder {
+        CodexBuilder::default()
+    }
+
+    /// Starts a new conversation.
+    pub async fn start_conversation(&self) -> Result<Conversation> {
+        let new_thread = self
+            .thread_manager
+            .start_thread(self.config.clone())
+            .await
+            .map_err(Error::agent)?;
+        Ok(Conversation::new(
+            new_thread,
+            Arc::clone(&self.thread_manager),
+        ))
+    }
+
+    /// Resumes the conversation recorded in the rollout file at `rollout_path`, as returned by
+    /// [`Conversation::rollout_path`].
+    pub async fn resume_conversation(
+        &self,
+        rollout_path: impl Into<PathBuf>,
+    ) -> Result<Conversation> {
+        let new_thread = self
+            .thread_manager
+            .resume_thread_from_rollout(
+                self.config.clone(),
+                rollout_path.into(),
+                Arc::clone(&self.auth_manager),
+            )
+            .await
+            .map_err(Error::agent)?;
+        Ok(Conversation::new(
+            new_thread,
+            Arc::clone(&self.thread_manager),
+        ))
+    }
+
+    /// Working directory that new conversations run in.
+    pub fn cwd(&self) -> &std::path::Path {
+        &self.config.cwd
+    }
+
+    /// Directory Codex reads its configuration and credentials from and records sessions in.
+    pub fn codex_home(&self) -> &std::path::Path {
+        &self.config.codex_home
+    }
+}
+
+/// Configures a [`Codex`]. Every setting that is not set falls back to `config.toml` and then to
+/// the CLI's defaults.
+#[derive(Debug, Default, Clone)]
+pub struct CodexBuilder {
+    codex_home: Option<PathBuf>,
+    cwd: Option<PathBuf>,
+    model: Option<String>,
+    approval_policy: Option<AskForApproval>,
+    sandbox_mode: Option<SandboxMode>,
+    linux_sandbox_exe: Option<PathBuf>,
+    ephemeral: bool,
+    config_overrides: CliConfigOverrides,
+}
+
+impl CodexBuilder {
+    /// Reads configuration and credentials from `codex_home` instead of `$CODEX_HOME` or
+    /// `~/.codex`.
+    pub fn codex_home(mut self, codex_home: impl Into<PathBuf>) -> Self {
+        self.codex_home = Some(codex_home.into());
+        self
+    }
+
+    /// Runs conversations in `cwd` instead of the process's current directory.
+    pub fn cwd(mut self, cwd: impl Into<PathBuf>) -> Self {
+        self.cwd = Some(cwd.into());
+        self
+    }
+
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    pub fn approval_policy(mut self, approval_policy: AskForApproval) -> Self {
+        self.approval_policy = Some(approval_policy);
+        self
+    }
+
+    pub fn sandbox_mode(mut self, sandbox_mode: SandboxMode) -> Self {
+        self.sandbox_mode = Some(sandbox_mode);
+        self
+    }
+
+    /// Path of the `codex-linux-sandbox` executable, required on Linux for sandboxed commands.
+    pub fn linux_sandbox_exe(mut self, linux_sandbox_exe: impl Into<PathBuf>) -> Self {
+        self.linux_sandbox_exe = Some(linux_sandbox_exe.into());
+        self
+    }
+
+    /// Keeps conversations in memory only: no rollout file is written, so they cannot be resumed.
+    pub fn ephemeral(mut self, ephemeral: bool) -> Self {
+        self.ephemeral = ephemeral;
+        self
+    }
+
+    /// Overrides a `config.toml` setting, like `codex -c key=value`. `key` is a dotted path and
+    /// `value` is parsed as TOML, falling back to a plain string.
+    pub fn config(mut self, key: &str, value: &str) -> Self {
+        self.config_overrides
+            .raw_overrides
+            .push(format!("{key}={value}"));
+        self
+    }
+
+    pub async fn build(self) -> Result<Codex> {
+        let cli_overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(Error::InvalidConfigOverride)?;
+        let harness_overrides = ConfigOverrides {
+            model: self.model,
+            cwd: self.cwd,
+            approval_policy: self.approval_policy,
+            sandbox_mode: self.sandbox_mode,
+            codex_linux_sandbox_exe: self.linux_sandbox_exe,
+            ephemeral: self.ephemeral.then_some(true),
+            ..Default::default()
+        };
+        let mut builder = ConfigBuilder::default()
+            .cli_overrides(cli_overrides)
+            .harness_overrides(harness_overrides);
+        if let Some(codex_home) = self.codex_home {
+            builder = builder.codex_home(codex_home);
+        }
+        let config = builder.build().await.map_err(Error::Config)?;
+
+        let auth_manager = AuthManager::shared(
+            config.auth_home(),
+            true,
+            config.cli_auth_credentials_store_mode,
+        );
+        let thread_manager = Arc::new(ThreadManager::new(
+            config.codex_home.clone(),
+            Arc::clone(&auth_manager),
+            SessionSource::Exec,
+        ));
+        Ok(Codex {
+            config,
+            auth_manager,
+            thread_manager,
+        })
+    }
+}
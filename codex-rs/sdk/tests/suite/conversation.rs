@@ -0,0 +1,114 @@
+use codex_sdk::CancellationToken;
+use codex_sdk::Codex;
+use codex_sdk::EventMsg;
+use codex_sdk::UserInput;
+use core_test_support::responses;
+use core_test_support::skip_if_no_network;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+use wiremock::MockServer;
+
+async fn build_codex(
+    server: &MockServer,
+    codex_home: &TempDir,
+    cwd: &TempDir,
+) -> codex_sdk::Result<Codex> {
+    Codex::builder()
+        .codex_home(codex_home.path())
+        .cwd(cwd.path())
+        .config(
+            "model_providers.mock",
+            &format!(
+                "{{ name = \"mock\", base_url = \"{}/v1\", wire_api = \"responses\" }}",
+                server.uri()
+            ),
+        )
+        .config("model_provider", "\"mock\"")
+        .ephemeral(true)
+        .build()
+        .await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn runs_a_turn_against_the_configured_provider() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+    let response_mock = responses::mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_response_created("resp-1"),
+            responses::ev_assistant_message("msg-1", "hi there"),
+            responses::ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+
+    let codex_home = TempDir::new()?;
+    let cwd = TempDir::new()?;
+    let codex = build_codex(&server, &codex_home, &cwd).await?;
+    let conversation = codex.start_conversation().await?;
+
+    let outcome = conversation
+        .run(
+            vec![UserInput::Text {
+                text: "hello".to_string(),
+                text_elements: Vec::new(),
+            }],
+            &CancellationToken::new(),
+        )
+        .await?;
+
+    assert_eq!(outcome.last_agent_message.as_deref(), Some("hi there"));
+    assert!(!outcome.aborted);
+    assert!(
+        outcome
+            .events
+            .iter()
+            .any(|event| matches!(event.msg, EventMsg::AgentMessage(_)))
+    );
+    assert_eq!(conversation.rollout_path(), None);
+    assert!(
+        response_mock
+            .single_request()
+            .message_input_texts("user")
+            .contains(&"hello".to_string())
+    );
+
+    conversation.shutdown().await?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn steering_requires_a_running_turn() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+    let codex_home = TempDir::new()?;
+    let cwd = TempDir::new()?;
+    let codex = build_codex(&server, &codex_home, &cwd).await?;
+    let conversation = codex.start_conversation().await?;
+
+    let result = conversation
+        .steer(
+            vec![UserInput::Text {
+                text: "use the other approach".to_string(),
+                text_elements: Vec::new(),
+            }],
+            None,
+        )
+        .await;
+
+    assert!(matches!(result, Err(codex_sdk::Error::NoActiveTurn)));
+    conversation.shutdown().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn rejects_malformed_config_overrides() {
+    let result = Codex::builder().config("", "1").build().await;
+    assert!(matches!(
+        result,
+        Err(codex_sdk::Error::InvalidConfigOverride(_))
+    ));
+}
@@ -27,6 +27,9 @@ use crate::pager_overlay::Overlay;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::Renderable;
 use crate::resume_picker::SessionSelection;
+use crate::startup_profile;
+use crate::startup_profile::StartupPhase;
+use crate::transcript_spill::TranscriptSpill;
 use crate::tui;
 use crate::tui::TuiEvent;
 use crate::update_action::UpdateAction;
@@ -35,6 +38,7 @@ use codex_app_server_protocol::ConfigLayerSource;
 use codex_core::AuthManager;
 use codex_core::CodexAuth;
 use codex_core::ThreadManager;
+use codex_core::auth::CLIENT_ID;
 use codex_core::config::Config;
 use codex_core::config::ConfigBuilder;
 use codex_core::config::ConfigOverrides;
@@ -50,6 +54,7 @@ use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::FinalOutput;
 use codex_core::protocol::ListSkillsResponseEvent;
+use codex_core::protocol::McpServerRefreshConfig;
 use codex_core::protocol::Op;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::SessionSource;
@@ -57,6 +62,8 @@ use codex_core::protocol::SkillErrorInfo;
 use codex_core::protocol::TokenUsage;
 #[cfg(target_os = "windows")]
 use codex_core::windows_sandbox::WindowsSandboxLevelExt;
+use codex_login::ServerOptions;
+use codex_login::run_login_server;
 use codex_otel::OtelManager;
 use codex_otel::TelemetryAuthMode;
 use codex_protocol::ThreadId;
@@ -187,6 +194,16 @@ fn emit_skill_load_warnings(app_event_tx: &AppEventSender, errors: &[SkillErrorI
     }
 }
 
+/// Payload for `Op::RefreshMcpServers` carrying the MCP settings of `config`.
+fn mcp_server_refresh_config(config: &Config) -> serde_json::Result<McpServerRefreshConfig> {
+    Ok(McpServerRefreshConfig {
+        mcp_servers: serde_json::to_value(config.mcp_servers.get())?,
+        mcp_oauth_credentials_store_mode: serde_json::to_value(
+            config.mcp_oauth_credentials_store_mode,
+        )?,
+    })
+}
+
 fn emit_project_config_warnings(app_event_tx: &AppEventSender, config: &Config) {
     let mut disabled_folders = Vec::new();
 
@@ -531,6 +548,8 @@ pub(crate) struct App {
     pub(crate) file_search: FileSearchManager,
 
     pub(crate) transcript_cells: Vec<Arc<dyn HistoryCell>>,
+    /// Moves older `transcript_cells` out of memory as the session grows.
+    transcript_spill: TranscriptSpill,
 
     // Pager overlay state (Transcript or Static like Diff)
     pub(crate) overlay: Option<Overlay>,
@@ -680,6 +699,42 @@ impl App {
             .add_info_message(format!("Opened {url} in your browser."), None);
     }
 
+    /// Runs the browser sign-in for the account this session uses and reports the outcome with
+    /// `AppEvent::ReauthFinished`.
+    fn start_reauth(&mut self, id: String) {
+        let opts = ServerOptions::new(
+            self.config.auth_home(),
+            CLIENT_ID.to_string(),
+            self.config.forced_chatgpt_workspace_id.clone(),
+            self.config.cli_auth_credentials_store_mode,
+        );
+        let server = match run_login_server(opts) {
+            Ok(server) => server,
+            Err(err) => {
+                self.chat_widget
+                    .add_error_message(format!("Failed to start sign-in: {err}"));
+                self.chat_widget
+                    .submit_op(Op::ReauthComplete { id, success: false });
+                return;
+            }
+        };
+        self.chat_widget.add_info_message(
+            format!(
+                "Finish signing in with ChatGPT in your browser: {}",
+                server.auth_url
+            ),
+            Some("The turn resumes once you are signed in.".to_string()),
+        );
+        let app_event_tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let result = server
+                .block_until_done()
+                .await
+                .map_err(|err| err.to_string());
+            app_event_tx.send(AppEvent::ReauthFinished { id, result });
+        });
+    }
+
     async fn shutdown_current_thread(&mut self) {
         if let Some(thread_id) = self.chat_widget.thread_id() {
             // Clear any in-flight rollback guard when switching threads.
@@ -899,6 +954,7 @@ impl App {
     fn reset_for_thread_switch(&mut self, tui: &mut tui::Tui) -> Result<()> {
         self.overlay = None;
         self.transcript_cells.clear();
+        self.transcript_spill = TranscriptSpill::default();
         self.deferred_history_lines.clear();
         self.has_emitted_history_lines = false;
         self.backtrack = BacktrackState::default();
@@ -1027,14 +1083,11 @@ impl App {
             auth_manager.clone(),
             SessionSource::Cli,
         ));
-        let mut model = thread_manager
-            .get_models_manager()
-            .get_default_model(&config.model, RefreshStrategy::Offline)
-            .await;
-        let available_models = thread_manager
-            .get_models_manager()
-            .list_models(RefreshStrategy::Offline)
-            .await;
+        let models_manager = thread_manager.get_models_manager();
+        let (mut model, available_models) = tokio::join!(
+            models_manager.get_default_model(&config.model, RefreshStrategy::Offline),
+            models_manager.list_models(RefreshStrategy::Offline),
+        );
         let exit_info = handle_model_migration_prompt_if_needed(
             tui,
             &mut config,
@@ -1200,6 +1253,7 @@ impl App {
             file_search,
             enhanced_keys_supported,
             transcript_cells: Vec::new(),
+            transcript_spill: TranscriptSpill::default(),
             overlay: None,
             deferred_history_lines: Vec::new(),
             has_emitted_history_lines: false,
@@ -1387,6 +1441,7 @@ impl App {
                             }
                         },
                     )?;
+                    startup_profile::milestone(StartupPhase::FirstFrame);
                     if self.chat_widget.external_editor_state() == ExternalEditorState::Requested {
                         self.chat_widget
                             .set_external_editor_state(ExternalEditorState::Active);
@@ -1605,6 +1660,10 @@ impl App {
                 }
                 self.transcript_cells.push(cell.clone());
                 let mut display = cell.display_lines(tui.terminal.last_known_screen_size.width);
+                self.transcript_spill.spill_old_cells(
+                    &mut self.transcript_cells,
+                    tui.terminal.last_known_screen_size.width,
+                );
                 if !display.is_empty() {
                     // Only insert a separating blank line for new cells that are not
                     // part of an ongoing stream. Streaming continuations should not
@@ -1688,6 +1747,23 @@ impl App {
                 ));
                 tui.frame_requester().schedule_frame();
             }
+            AppEvent::RunMcpPrompt {
+                server,
+                prompt,
+                arguments,
+            } => {
+                self.chat_widget.run_mcp_prompt(server, prompt, arguments);
+            }
+            AppEvent::FillMcpElicitation {
+                form,
+                content,
+                error,
+            } => {
+                self.chat_widget.fill_mcp_elicitation(form, content, error);
+            }
+            AppEvent::AttachMcpResource { server, uri, name } => {
+                self.chat_widget.attach_mcp_resource(server, uri, name);
+            }
             AppEvent::OpenAppLink {
                 app_id,
                 title,
@@ -1711,6 +1787,26 @@ impl App {
             AppEvent::OpenUrlInBrowser { url } => {
                 self.open_url_in_browser(url);
             }
+            AppEvent::StartReauth { id } => {
+                self.start_reauth(id);
+            }
+            AppEvent::ReauthFinished { id, result } => {
+                let success = match result {
+                    Ok(()) => {
+                        self.auth_manager.reload();
+                        self.chat_widget
+                            .add_info_message("Signed in again. Resuming.".to_string(), None);
+                        true
+                    }
+                    Err(err) => {
+                        self.chat_widget
+                            .add_error_message(format!("Sign-in failed: {err}"));
+                        false
+                    }
+                };
+                self.chat_widget
+                    .submit_op(Op::ReauthComplete { id, success });
+            }
             AppEvent::RefreshConnectors { force_refetch } => {
                 self.chat_widget.refresh_connectors(force_refetch);
             }
@@ -1734,6 +1830,10 @@ impl App {
                 self.chat_widget.set_model(&model);
                 self.refresh_status_line();
             }
+            AppEvent::UpdateModelVerbosity(verbosity) => {
+                // Verbosity is fixed for the lifetime of a session, so this only affects new ones.
+                self.config.model_verbosity = verbosity;
+            }
             AppEvent::UpdateCollaborationMode(mask) => {
                 self.chat_widget.set_collaboration_mask(mask);
                 self.refresh_status_line();
@@ -1781,6 +1881,9 @@ impl App {
                     self.launch_external_editor(tui).await;
                 }
             }
+            AppEvent::OpenOutputArtifact(path) => {
+                self.open_output_artifact(tui, &path).await;
+            }
             AppEvent::OpenWindowsSandboxEnablePrompt { preset } => {
                 self.chat_widget.open_windows_sandbox_enable_prompt(preset);
             }
@@ -2449,6 +2552,26 @@ impl App {
                     }
                 }
             }
+            AppEvent::ReloadMcpServers => {
+                let refresh_config = match self.refresh_in_memory_config_from_disk().await {
+                    Ok(()) => mcp_server_refresh_config(&self.config).map_err(Into::into),
+                    Err(err) => Err(err),
+                };
+                match refresh_config {
+                    Ok(config) => {
+                        self.chat_widget.sync_mcp_servers(&self.config);
+                        self.chat_widget.submit_op(Op::RefreshMcpServers { config });
+                        self.chat_widget.add_info_message(
+                            "MCP servers will be reloaded before the next turn.".to_string(),
+                            None,
+                        );
+                    }
+                    Err(err) => {
+                        self.chat_widget
+                            .add_error_message(format!("Failed to reload MCP servers: {err}"));
+                    }
+                }
+            }
             AppEvent::OpenPermissionsPopup => {
                 self.chat_widget.open_permissions_popup();
             }
@@ -2765,6 +2888,43 @@ impl App {
         tui.frame_requester().schedule_frame();
     }
 
+    async fn open_output_artifact(&mut self, tui: &mut tui::Tui, path: &Path) {
+        let editor_cmd = match external_editor::resolve_editor_command() {
+            Ok(cmd) => cmd,
+            Err(external_editor::EditorError::MissingEditor) => {
+                self.chat_widget.add_info_message(
+                    format!(
+                        "Full output is saved at {}; set $VISUAL or $EDITOR to open it from Codex.",
+                        path.display()
+                    ),
+                    None,
+                );
+                return;
+            }
+            Err(err) => {
+                self.chat_widget
+                    .add_to_history(history_cell::new_error_event(format!(
+                        "Failed to open editor: {err}",
+                    )));
+                return;
+            }
+        };
+
+        let result = tui
+            .with_restored(tui::RestoreMode::KeepRaw, || async {
+                external_editor::open_in_editor(path, &editor_cmd).await
+            })
+            .await;
+        if let Err(err) = result {
+            self.chat_widget
+                .add_to_history(history_cell::new_error_event(format!(
+                    "Failed to open {}: {err}",
+                    path.display()
+                )));
+        }
+        tui.frame_requester().schedule_frame();
+    }
+
     fn request_external_editor_launch(&mut self, tui: &mut tui::Tui) {
         self.chat_widget
             .set_external_editor_state(ExternalEditorState::Requested);
@@ -3180,6 +3340,7 @@ mod tests {
             runtime_sandbox_policy_override: None,
             file_search,
             transcript_cells: Vec::new(),
+            transcript_spill: TranscriptSpill::default(),
             overlay: None,
             deferred_history_lines: Vec::new(),
             has_emitted_history_lines: false,
@@ -3238,6 +3399,7 @@ mod tests {
                 runtime_sandbox_policy_override: None,
                 file_search,
                 transcript_cells: Vec::new(),
+                transcript_spill: TranscriptSpill::default(),
                 overlay: None,
                 deferred_history_lines: Vec::new(),
                 has_emitted_history_lines: false,
@@ -8,6 +8,7 @@
 //! Exit is modelled explicitly via `AppEvent::Exit(ExitMode)` so callers can request shutdown-first
 //! quits without reaching into the app loop or coupling to shutdown/exit sequencing.
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use codex_chatgpt::connectors::AppInfo;
@@ -15,18 +16,21 @@ use codex_core::protocol::Event;
 use codex_core::protocol::RateLimitSnapshot;
 use codex_file_search::FileMatch;
 use codex_protocol::ThreadId;
+use codex_protocol::mcp::Prompt as McpPrompt;
 use codex_protocol::openai_models::ModelPreset;
 use codex_utils_approval_presets::ApprovalPreset;
 
 use crate::bottom_pane::ApprovalRequest;
 use crate::bottom_pane::StatusLineItem;
 use crate::history_cell::HistoryCell;
+use crate::mcp_elicitation::ElicitationForm;
 
 use codex_core::features::Feature;
 use codex_core::protocol::AskForApproval;
 use codex_core::protocol::SandboxPolicy;
 use codex_protocol::config_types::CollaborationModeMask;
 use codex_protocol::config_types::Personality;
+use codex_protocol::config_types::Verbosity;
 use codex_protocol::openai_models::ReasoningEffort;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -111,11 +115,44 @@ pub(crate) enum AppEvent {
         is_enabled: bool,
     },
 
+    /// Run an MCP prompt, asking for each declared argument missing from `arguments` first.
+    RunMcpPrompt {
+        server: String,
+        prompt: McpPrompt,
+        arguments: HashMap<String, String>,
+    },
+
+    /// Answer an MCP elicitation request, asking for each field missing from `content` first.
+    /// `error` explains why the previous answer was rejected.
+    FillMcpElicitation {
+        form: ElicitationForm,
+        content: serde_json::Map<String, serde_json::Value>,
+        error: Option<String>,
+    },
+
+    /// Attach an MCP resource to the next user message.
+    AttachMcpResource {
+        server: String,
+        uri: String,
+        name: String,
+    },
+
     /// Open the provided URL in the user's browser.
     OpenUrlInBrowser {
         url: String,
     },
 
+    /// Sign in to ChatGPT again to resume the turn paused by the `AuthExpired` event `id`.
+    StartReauth {
+        id: String,
+    },
+
+    /// Result of the sign-in started by `StartReauth`.
+    ReauthFinished {
+        id: String,
+        result: Result<(), String>,
+    },
+
     /// Refresh app connector state and mention bindings.
     RefreshConnectors {
         force_refetch: bool,
@@ -142,6 +179,9 @@ pub(crate) enum AppEvent {
     /// Update the current model slug in the running app and widget.
     UpdateModel(String),
 
+    /// Update the verbosity used by sessions started from now on.
+    UpdateModelVerbosity(Option<Verbosity>),
+
     /// Update the active collaboration mask in the running app and widget.
     UpdateCollaborationMode(CollaborationModeMask),
 
@@ -299,6 +339,9 @@ pub(crate) enum AppEvent {
         enabled: bool,
     },
 
+    /// Re-read `mcp_servers` from config and apply the changes to the running session.
+    ReloadMcpServers,
+
     /// Notify that the manage skills popup was closed.
     ManageSkillsClosed,
 
@@ -337,6 +380,9 @@ pub(crate) enum AppEvent {
     /// Launch the external editor after a normal draw has completed.
     LaunchExternalEditor,
 
+    /// Open a saved command output artifact in the external editor.
+    OpenOutputArtifact(PathBuf),
+
     /// Async update of the current git branch for status line rendering.
     StatusLineBranchUpdated {
         cwd: PathBuf,
@@ -13,6 +13,7 @@ use crate::exec_command::strip_bash_lc_and_escape;
 use crate::history_cell;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
+use crate::mcp_elicitation::ElicitationForm;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::Renderable;
@@ -56,6 +57,8 @@ pub(crate) enum ApprovalRequest {
         server_name: String,
         request_id: RequestId,
         message: String,
+        /// Fields to fill in once the request is accepted.
+        form: Option<ElicitationForm>,
     },
 }
 
@@ -191,10 +194,16 @@ impl ApprovalOverlay {
                     ApprovalVariant::McpElicitation {
                         server_name,
                         request_id,
+                        form,
                     },
                     ApprovalDecision::McpElicitation(decision),
                 ) => {
-                    self.handle_elicitation_decision(server_name, request_id, *decision);
+                    self.handle_elicitation_decision(
+                        server_name,
+                        request_id,
+                        form.as_ref(),
+                        *decision,
+                    );
                 }
                 _ => {}
             }
@@ -221,17 +230,30 @@ impl ApprovalOverlay {
         }));
     }
 
+    /// Accepting a request that asks for fields opens its form instead of answering right away.
     fn handle_elicitation_decision(
         &self,
         server_name: &str,
         request_id: &RequestId,
+        form: Option<&ElicitationForm>,
         decision: ElicitationAction,
     ) {
+        if decision == ElicitationAction::Accept
+            && let Some(form) = form
+        {
+            self.app_event_tx.send(AppEvent::FillMcpElicitation {
+                form: form.clone(),
+                content: serde_json::Map::new(),
+                error: None,
+            });
+            return;
+        }
         self.app_event_tx
             .send(AppEvent::CodexOp(Op::ResolveElicitation {
                 server_name: server_name.to_string(),
                 request_id: request_id.clone(),
                 decision,
+                content: None,
             }));
     }
 
@@ -303,10 +325,12 @@ impl BottomPaneView for ApprovalOverlay {
                 ApprovalVariant::McpElicitation {
                     server_name,
                     request_id,
+                    ..
                 } => {
                     self.handle_elicitation_decision(
                         server_name,
                         request_id,
+                        None,
                         ElicitationAction::Cancel,
                     );
                 }
@@ -406,6 +430,7 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                 server_name,
                 request_id,
                 message,
+                form,
             } => {
                 let header = Paragraph::new(vec![
                     Line::from(vec!["Server: ".into(), server_name.clone().bold()]),
@@ -417,6 +442,7 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                     variant: ApprovalVariant::McpElicitation {
                         server_name,
                         request_id,
+                        form,
                     },
                     header: Box::new(header),
                 }
@@ -439,6 +465,7 @@ enum ApprovalVariant {
     McpElicitation {
         server_name: String,
         request_id: RequestId,
+        form: Option<ElicitationForm>,
     },
 }
 
@@ -515,12 +542,32 @@ fn exec_options(
             additional_shortcuts: vec![key_hint::plain(KeyCode::Char('p'))],
         })
     }))
-    .chain([ApprovalOption {
-        label: "No, and tell Codex what to do differently".to_string(),
-        decision: ApprovalDecision::Review(ReviewDecision::Abort),
-        display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
-        additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
-    }])
+    .chain([
+        ApprovalOption {
+            label: "Yes, and don't ask again for this command in this session".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::ApprovedForSession),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('a'))],
+        },
+        ApprovalOption {
+            label: "Yes, and don't ask again for this command in this project".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::ApprovedForProject),
+            display_shortcut: None,
+            additional_shortcuts: Vec::new(),
+        },
+        ApprovalOption {
+            label: "Yes, and don't ask again for this command anywhere".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::ApprovedAlways),
+            display_shortcut: None,
+            additional_shortcuts: Vec::new(),
+        },
+        ApprovalOption {
+            label: "No, and tell Codex what to do differently".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::Abort),
+            display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+        },
+    ])
     .collect()
 }
 
@@ -710,6 +757,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn exec_options_offer_persistent_approvals() {
+        let options = exec_options(None, None);
+
+        let labels: Vec<String> = options.into_iter().map(|option| option.label).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "Yes, proceed".to_string(),
+                "Yes, and don't ask again for this command in this session".to_string(),
+                "Yes, and don't ask again for this command in this project".to_string(),
+                "Yes, and don't ask again for this command anywhere".to_string(),
+                "No, and tell Codex what to do differently".to_string(),
+            ]
+        );
+    }
+
     #[test]
     fn network_exec_prompt_title_includes_host() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
@@ -47,6 +47,8 @@
 //! and attachment pruning, and clears pending paste state on success.
 //! Slash commands with arguments (like `/plan` and `/review`) reuse the same preparation path so
 //! pasted content and text elements are preserved when extracting args.
+//! MCP prompts (`/<server>:<prompt>`) are not expanded here: selecting or submitting one clears
+//! the composer and sends `AppEvent::RunMcpPrompt`, since the server expands the template.
 //!
 //! # Remote Image Rows (Up/Down/Delete)
 //!
@@ -137,6 +139,7 @@ use super::chat_composer_history::HistoryEntry;
 use super::command_popup::CommandItem;
 use super::command_popup::CommandPopup;
 use super::command_popup::CommandPopupFlags;
+use super::command_popup::McpPromptCommand;
 use super::file_search_popup::FileSearchPopup;
 use super::footer::CollaborationModeIndicator;
 use super::footer::FooterMode;
@@ -312,6 +315,7 @@ pub(crate) struct ChatComposer {
     // When true, disables paste-burst logic and inserts characters immediately.
     disable_paste_burst: bool,
     custom_prompts: Vec<CustomPrompt>,
+    mcp_prompts: Vec<McpPromptCommand>,
     footer_mode: FooterMode,
     footer_hint_override: Option<Vec<(String, String)>>,
     remote_image_urls: Vec<String>,
@@ -415,6 +419,7 @@ impl ChatComposer {
             paste_burst: PasteBurst::default(),
             disable_paste_burst: false,
             custom_prompts: Vec::new(),
+            mcp_prompts: Vec::new(),
             footer_mode: FooterMode::ComposerEmpty,
             footer_hint_override: None,
             remote_image_urls: Vec::new(),
@@ -1240,6 +1245,13 @@ impl ChatComposer {
                                 }
                             }
                         }
+                        CommandItem::McpPrompt(idx) => {
+                            if let Some(command) = popup.mcp_prompt(idx) {
+                                let text = format!("/{} ", command.command());
+                                self.textarea.set_text_clearing_elements(&text);
+                                cursor_target = Some(text.len());
+                            }
+                        }
                     }
                     if let Some(pos) = cursor_target {
                         self.textarea.set_cursor(pos);
@@ -1327,6 +1339,13 @@ impl ChatComposer {
                             }
                             return (InputResult::None, true);
                         }
+                        CommandItem::McpPrompt(idx) => {
+                            if let Some(command) = popup.mcp_prompt(idx).cloned() {
+                                self.textarea.set_text_clearing_elements("");
+                                self.run_mcp_prompt(command);
+                            }
+                            return (InputResult::None, true);
+                        }
                     }
                 }
                 // Fallback to default newline handling if no command selected.
@@ -2207,6 +2226,9 @@ impl ChatComposer {
         if let Some(result) = self.try_dispatch_bare_slash_command() {
             return (result, true);
         }
+        if let Some(result) = self.try_dispatch_mcp_prompt() {
+            return (result, true);
+        }
 
         // If we're in a paste-like burst capture, treat Enter/Ctrl+Shift+Q as part of the burst
         // and accumulate it rather than submitting or inserting immediately.
@@ -2312,6 +2334,34 @@ impl ChatComposer {
         }
     }
 
+    /// Check if the first line names an MCP prompt and run it. Arguments are asked for one at a
+    /// time, so anything typed after the name is ignored.
+    fn try_dispatch_mcp_prompt(&mut self) -> Option<InputResult> {
+        if !self.slash_commands_enabled() {
+            return None;
+        }
+        let first_line = self.textarea.text().lines().next().unwrap_or("");
+        let (name, _rest, _rest_offset) = parse_slash_name(first_line)?;
+        let command = self.find_mcp_prompt(name)?.clone();
+        self.textarea.set_text_clearing_elements("");
+        self.run_mcp_prompt(command);
+        Some(InputResult::None)
+    }
+
+    fn find_mcp_prompt(&self, name: &str) -> Option<&McpPromptCommand> {
+        self.mcp_prompts
+            .iter()
+            .find(|command| command.command() == name)
+    }
+
+    fn run_mcp_prompt(&self, command: McpPromptCommand) {
+        self.app_event_tx.send(AppEvent::RunMcpPrompt {
+            server: command.server,
+            prompt: command.prompt,
+            arguments: HashMap::new(),
+        });
+    }
+
     /// Check if the input is a slash command with args (e.g., /review args) and dispatch it.
     /// Returns Some(InputResult) if a command was dispatched, None otherwise.
     fn try_dispatch_slash_command_with_args(&mut self) -> Option<InputResult> {
@@ -3044,7 +3094,7 @@ impl ChatComposer {
                 .iter()
                 .any(|prompt| prompt.name == prompt_name);
         }
-        false
+        self.find_mcp_prompt(name).is_some()
     }
 
     /// If the cursor is currently within a slash command on the first line,
@@ -3098,7 +3148,10 @@ impl ChatComposer {
 
         self.custom_prompts.iter().any(|prompt| {
             fuzzy_match(&format!("{PROMPTS_CMD_PREFIX}:{}", prompt.name), name).is_some()
-        })
+        }) || self
+            .mcp_prompts
+            .iter()
+            .any(|command| fuzzy_match(&command.command(), name).is_some())
     }
 
     /// Synchronize `self.command_popup` with the current text in the
@@ -3153,6 +3206,7 @@ impl ChatComposer {
                             windows_degraded_sandbox_active: self.windows_degraded_sandbox_active,
                         },
                     );
+                    command_popup.set_mcp_prompts(self.mcp_prompts.clone());
                     command_popup.on_composer_text_change(first_line.to_string());
                     self.active_popup = ActivePopup::Command(command_popup);
                 }
@@ -3166,6 +3220,13 @@ impl ChatComposer {
         }
     }
 
+    pub(crate) fn set_mcp_prompts(&mut self, prompts: Vec<McpPromptCommand>) {
+        self.mcp_prompts = prompts.clone();
+        if let ActivePopup::Command(popup) = &mut self.active_popup {
+            popup.set_mcp_prompts(prompts);
+        }
+    }
+
     /// Synchronize `self.file_search_popup` with the current text in the textarea.
     /// Note this is only called when self.active_popup is NOT Command.
     fn sync_file_search_popup(&mut self, query: String) {
@@ -5452,7 +5513,7 @@ mod tests {
                 Some(CommandItem::Builtin(cmd)) => {
                     assert_eq!(cmd.command(), "model")
                 }
-                Some(CommandItem::UserPrompt(_)) => {
+                Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
                     panic!("unexpected prompt selected for '/mo'")
                 }
                 None => panic!("no selected command for '/mo'"),
@@ -5509,7 +5570,7 @@ mod tests {
                 Some(CommandItem::Builtin(cmd)) => {
                     assert_eq!(cmd.command(), "resume")
                 }
-                Some(CommandItem::UserPrompt(_)) => {
+                Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
                     panic!("unexpected prompt selected for '/res'")
                 }
                 None => panic!("no selected command for '/res'"),
@@ -12,6 +12,7 @@ use crate::render::RectExt;
 use crate::slash_command::SlashCommand;
 use codex_protocol::custom_prompts::CustomPrompt;
 use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
+use codex_protocol::mcp::Prompt as McpPrompt;
 use std::collections::HashSet;
 
 // Hide alias commands in the default popup list so each unique action appears once.
@@ -19,18 +20,35 @@ use std::collections::HashSet;
 // `approvals` is an alias of `permissions`.
 const ALIAS_COMMANDS: &[SlashCommand] = &[SlashCommand::Quit, SlashCommand::Approvals];
 
-/// A selectable item in the popup: either a built-in command or a user prompt.
+/// A selectable item in the popup: a built-in command, a user prompt or an MCP prompt.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) enum CommandItem {
     Builtin(SlashCommand),
     // Index into `prompts`
     UserPrompt(usize),
+    // Index into `mcp_prompts`
+    McpPrompt(usize),
+}
+
+/// A prompt template published by an MCP server, offered as `/<server>:<prompt>`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct McpPromptCommand {
+    pub(crate) server: String,
+    pub(crate) prompt: McpPrompt,
+}
+
+impl McpPromptCommand {
+    /// Command name without the leading `/`.
+    pub(crate) fn command(&self) -> String {
+        format!("{}:{}", self.server, self.prompt.name)
+    }
 }
 
 pub(crate) struct CommandPopup {
     command_filter: String,
     builtins: Vec<(&'static str, SlashCommand)>,
     prompts: Vec<CustomPrompt>,
+    mcp_prompts: Vec<McpPromptCommand>,
     state: ScrollState,
 }
 
@@ -62,6 +80,7 @@ impl CommandPopup {
             command_filter: String::new(),
             builtins,
             prompts,
+            mcp_prompts: Vec::new(),
             state: ScrollState::new(),
         }
     }
@@ -81,6 +100,15 @@ impl CommandPopup {
         self.prompts.get(idx)
     }
 
+    pub(crate) fn set_mcp_prompts(&mut self, mut prompts: Vec<McpPromptCommand>) {
+        prompts.sort_by_key(McpPromptCommand::command);
+        self.mcp_prompts = prompts;
+    }
+
+    pub(crate) fn mcp_prompt(&self, idx: usize) -> Option<&McpPromptCommand> {
+        self.mcp_prompts.get(idx)
+    }
+
     /// Update the filter string based on the current composer text. The text
     /// passed in is expected to start with a leading '/'. Everything after the
     /// *first* '/' on the *first* line becomes the active filter that is used
@@ -139,6 +167,10 @@ impl CommandPopup {
             for idx in 0..self.prompts.len() {
                 out.push((CommandItem::UserPrompt(idx), None));
             }
+            // Then MCP prompts, sorted by server and name.
+            for idx in 0..self.mcp_prompts.len() {
+                out.push((CommandItem::McpPrompt(idx), None));
+            }
             return out;
         }
 
@@ -185,6 +217,15 @@ impl CommandPopup {
                 prompt_prefix_len,
             );
         }
+        // MCP prompts match both "server:name" and the bare prompt name.
+        for (idx, p) in self.mcp_prompts.iter().enumerate() {
+            push_match(
+                CommandItem::McpPrompt(idx),
+                &p.command(),
+                Some(&p.prompt.name),
+                p.server.chars().count() + 1,
+            );
+        }
 
         out.extend(exact);
         out.extend(prefix);
@@ -217,6 +258,13 @@ impl CommandPopup {
                             description,
                         )
                     }
+                    CommandItem::McpPrompt(i) => {
+                        let command = &self.mcp_prompts[i];
+                        let description = command.prompt.description.clone().unwrap_or_else(|| {
+                            format!("send prompt from MCP server {}", command.server)
+                        });
+                        (format!("/{}", command.command()), description)
+                    }
                 };
                 GenericDisplayRow {
                     name,
@@ -287,7 +335,7 @@ mod tests {
         let matches = popup.filtered_items();
         let has_init = matches.iter().any(|item| match item {
             CommandItem::Builtin(cmd) => cmd.command() == "init",
-            CommandItem::UserPrompt(_) => false,
+            CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => false,
         });
         assert!(
             has_init,
@@ -305,7 +353,9 @@ mod tests {
         let selected = popup.selected_item();
         match selected {
             Some(CommandItem::Builtin(cmd)) => assert_eq!(cmd.command(), "init"),
-            Some(CommandItem::UserPrompt(_)) => panic!("unexpected prompt selected for '/init'"),
+            Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
+                panic!("unexpected prompt selected for '/init'")
+            }
             None => panic!("expected a selected command for exact match"),
         }
     }
@@ -317,7 +367,7 @@ mod tests {
         let matches = popup.filtered_items();
         match matches.first() {
             Some(CommandItem::Builtin(cmd)) => assert_eq!(cmd.command(), "model"),
-            Some(CommandItem::UserPrompt(_)) => {
+            Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
                 panic!("unexpected prompt ranked before '/model' for '/mo'")
             }
             None => panic!("expected at least one match for '/mo'"),
@@ -334,7 +384,7 @@ mod tests {
             .into_iter()
             .filter_map(|item| match item {
                 CommandItem::Builtin(cmd) => Some(cmd.command()),
-                CommandItem::UserPrompt(_) => None,
+                CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => None,
             })
             .collect();
         assert_eq!(cmds, vec!["model", "mention", "mcp"]);
@@ -395,6 +445,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn mcp_prompts_match_by_server_or_prompt_name() {
+        let mut popup = CommandPopup::new(Vec::new(), CommandPopupFlags::default());
+        popup.set_mcp_prompts(vec![McpPromptCommand {
+            server: "github".to_string(),
+            prompt: McpPrompt {
+                name: "review-pr".to_string(),
+                title: None,
+                description: Some("Review a pull request".to_string()),
+                arguments: None,
+            },
+        }]);
+
+        for filter in ["/github:rev", "/review"] {
+            popup.on_composer_text_change(filter.to_string());
+            let selected = popup
+                .filtered_items()
+                .into_iter()
+                .find_map(|item| match item {
+                    CommandItem::McpPrompt(i) => popup.mcp_prompt(i).map(McpPromptCommand::command),
+                    _ => None,
+                });
+            assert_eq!(selected.as_deref(), Some("github:review-pr"), "{filter}");
+        }
+
+        let rows = popup.rows_from_matches(vec![(CommandItem::McpPrompt(0), None)]);
+        assert_eq!(rows[0].name, "/github:review-pr");
+        assert_eq!(
+            rows[0].description.as_deref(),
+            Some("Review a pull request")
+        );
+    }
+
     #[test]
     fn prompt_description_uses_frontmatter_metadata() {
         let popup = CommandPopup::new(
@@ -442,7 +525,7 @@ mod tests {
             .into_iter()
             .filter_map(|item| match item {
                 CommandItem::Builtin(cmd) => Some(cmd.command()),
-                CommandItem::UserPrompt(_) => None,
+                CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => None,
             })
             .collect();
         assert!(
@@ -473,7 +556,7 @@ mod tests {
             .into_iter()
             .filter_map(|item| match item {
                 CommandItem::Builtin(cmd) => Some(cmd.command()),
-                CommandItem::UserPrompt(_) => None,
+                CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => None,
             })
             .collect();
         assert!(
@@ -542,7 +625,7 @@ mod tests {
             .into_iter()
             .filter_map(|item| match item {
                 CommandItem::Builtin(cmd) => Some(cmd.command()),
-                CommandItem::UserPrompt(_) => None,
+                CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => None,
             })
             .collect();
         assert!(
@@ -578,7 +661,7 @@ mod tests {
             .into_iter()
             .filter_map(|item| match item {
                 CommandItem::Builtin(cmd) => Some(cmd.command()),
-                CommandItem::UserPrompt(_) => None,
+                CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => None,
             })
             .collect();
 
@@ -24,12 +24,17 @@ use super::textarea::TextAreaState;
 /// Callback invoked when the user submits a custom prompt.
 pub(crate) type PromptSubmitted = Box<dyn Fn(String) + Send + Sync>;
 
+/// Callback invoked when the user dismisses the view without submitting.
+pub(crate) type PromptCancelled = Box<dyn Fn() + Send + Sync>;
+
 /// Minimal multi-line text input view to collect custom review instructions.
 pub(crate) struct CustomPromptView {
     title: String,
     placeholder: String,
     context_label: Option<String>,
     on_submit: PromptSubmitted,
+    on_cancel: Option<PromptCancelled>,
+    allow_empty: bool,
 
     // UI state
     textarea: TextArea,
@@ -49,11 +54,24 @@ impl CustomPromptView {
             placeholder,
             context_label,
             on_submit,
+            on_cancel: None,
+            allow_empty: false,
             textarea: TextArea::new(),
             textarea_state: RefCell::new(TextAreaState::default()),
             complete: false,
         }
     }
+
+    /// Lets Enter submit an empty input, for values that may be left out.
+    pub(crate) fn allow_empty_submission(mut self) -> Self {
+        self.allow_empty = true;
+        self
+    }
+
+    pub(crate) fn on_cancel(mut self, on_cancel: PromptCancelled) -> Self {
+        self.on_cancel = Some(on_cancel);
+        self
+    }
 }
 
 impl BottomPaneView for CustomPromptView {
@@ -70,7 +88,7 @@ impl BottomPaneView for CustomPromptView {
                 ..
             } => {
                 let text = self.textarea.text().trim().to_string();
-                if !text.is_empty() {
+                if !text.is_empty() || self.allow_empty {
                     (self.on_submit)(text);
                     self.complete = true;
                 }
@@ -88,6 +106,11 @@ impl BottomPaneView for CustomPromptView {
     }
 
     fn on_ctrl_c(&mut self) -> CancellationEvent {
+        if !self.complete
+            && let Some(on_cancel) = self.on_cancel.take()
+        {
+            on_cancel();
+        }
         self.complete = true;
         CancellationEvent::Handled
     }
@@ -79,6 +79,8 @@ pub(crate) struct SelectionViewParams {
     pub col_width_mode: ColumnWidthMode,
     pub header: Box<dyn Renderable>,
     pub initial_selected_idx: Option<usize>,
+    /// Run when the popup is dismissed without picking an item.
+    pub on_cancel: Option<SelectionAction>,
 }
 
 impl Default for SelectionViewParams {
@@ -95,6 +97,7 @@ impl Default for SelectionViewParams {
             col_width_mode: ColumnWidthMode::AutoVisible,
             header: Box::new(()),
             initial_selected_idx: None,
+            on_cancel: None,
         }
     }
 }
@@ -120,6 +123,7 @@ pub(crate) struct ListSelectionView {
     last_selected_actual_idx: Option<usize>,
     header: Box<dyn Renderable>,
     initial_selected_idx: Option<usize>,
+    on_cancel: Option<SelectionAction>,
 }
 
 impl ListSelectionView {
@@ -161,6 +165,7 @@ impl ListSelectionView {
             last_selected_actual_idx: None,
             header,
             initial_selected_idx: params.initial_selected_idx,
+            on_cancel: params.on_cancel,
         };
         s.apply_filter();
         s
@@ -469,6 +474,11 @@ impl BottomPaneView for ListSelectionView {
     }
 
     fn on_ctrl_c(&mut self) -> CancellationEvent {
+        if !self.complete
+            && let Some(on_cancel) = self.on_cancel.take()
+        {
+            on_cancel(&self.app_event_tx);
+        }
         self.complete = true;
         CancellationEvent::Handled
     }
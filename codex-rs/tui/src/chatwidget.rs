@@ -39,6 +39,8 @@ use std::time::Instant;
 
 use crate::bottom_pane::StatusLineItem;
 use crate::bottom_pane::StatusLineSetupView;
+use crate::startup_profile;
+use crate::startup_profile::StartupPhase;
 use crate::status::RateLimitWindowDisplay;
 use crate::status::format_directory_display;
 use crate::status::format_tokens_compact;
@@ -60,6 +62,7 @@ use codex_core::git_info::current_branch_name;
 use codex_core::git_info::get_git_repo_root;
 use codex_core::git_info::local_git_branches;
 use codex_core::models_manager::manager::ModelsManager;
+use codex_core::models_manager::model_presets::gemini_model_presets;
 use codex_core::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
 use codex_core::protocol::AgentMessageDeltaEvent;
 use codex_core::protocol::AgentMessageEvent;
@@ -68,10 +71,16 @@ use codex_core::protocol::AgentReasoningEvent;
 use codex_core::protocol::AgentReasoningRawContentDeltaEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
+use codex_core::protocol::AuthExpiredEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::BudgetKind;
+use codex_core::protocol::BudgetLimitReachedEvent;
+use codex_core::protocol::BudgetScope;
 use codex_core::protocol::CodexErrorInfo;
+use codex_core::protocol::ConfigReloadedEvent;
 use codex_core::protocol::CreditsSnapshot;
 use codex_core::protocol::DeprecationNoticeEvent;
+use codex_core::protocol::ElicitationAction;
 use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
@@ -81,27 +90,38 @@ use codex_core::protocol::ExecCommandEndEvent;
 use codex_core::protocol::ExecCommandOutputDeltaEvent;
 use codex_core::protocol::ExecCommandSource;
 use codex_core::protocol::ExitedReviewModeEvent;
+use codex_core::protocol::GetMcpPromptResponseEvent;
 use codex_core::protocol::ListCustomPromptsResponseEvent;
+use codex_core::protocol::ListMcpPromptsResponseEvent;
 use codex_core::protocol::ListSkillsResponseEvent;
+use codex_core::protocol::McpListChangedEvent;
+use codex_core::protocol::McpListKind;
 use codex_core::protocol::McpListToolsResponseEvent;
+use codex_core::protocol::McpServerHealthEvent;
+use codex_core::protocol::McpServerHealthStatus;
 use codex_core::protocol::McpStartupCompleteEvent;
 use codex_core::protocol::McpStartupStatus;
 use codex_core::protocol::McpStartupUpdateEvent;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::McpToolCallQueuedEvent;
+use codex_core::protocol::ModelProviderFallbackEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::PatchApplyBeginEvent;
+use codex_core::protocol::PinnedContextSource;
 use codex_core::protocol::RateLimitSnapshot;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SkillMetadata as ProtocolSkillMetadata;
 use codex_core::protocol::StreamErrorEvent;
+use codex_core::protocol::StreamRetryEvent;
 use codex_core::protocol::TerminalInteractionEvent;
 use codex_core::protocol::TokenUsage;
 use codex_core::protocol::TokenUsageInfo;
 use codex_core::protocol::TurnAbortReason;
 use codex_core::protocol::TurnCompleteEvent;
 use codex_core::protocol::TurnDiffEvent;
+use codex_core::protocol::TurnSettingsEvent;
 use codex_core::protocol::UndoCompletedEvent;
 use codex_core::protocol::UndoStartedEvent;
 use codex_core::protocol::UserMessageEvent;
@@ -109,6 +129,8 @@ use codex_core::protocol::ViewImageToolCallEvent;
 use codex_core::protocol::WarningEvent;
 use codex_core::protocol::WebSearchBeginEvent;
 use codex_core::protocol::WebSearchEndEvent;
+use codex_core::protocol::WorktreeCreatedEvent;
+use codex_core::protocol::WorktreeMergedEvent;
 use codex_core::skills::model::SkillMetadata;
 #[cfg(target_os = "windows")]
 use codex_core::windows_sandbox::WindowsSandboxLevelExt;
@@ -125,6 +147,8 @@ use codex_protocol::config_types::Settings;
 #[cfg(target_os = "windows")]
 use codex_protocol::config_types::WindowsSandboxLevel;
 use codex_protocol::items::AgentMessageItem;
+use codex_protocol::mcp::Prompt as McpPrompt;
+use codex_protocol::mcp::Resource as McpResource;
 use codex_protocol::models::MessagePhase;
 use codex_protocol::models::local_image_label_text;
 use codex_protocol::parse_command::ParsedCommand;
@@ -176,6 +200,7 @@ use crate::bottom_pane::ExperimentalFeaturesView;
 use crate::bottom_pane::FeedbackAudience;
 use crate::bottom_pane::InputResult;
 use crate::bottom_pane::LocalImageAttachment;
+use crate::bottom_pane::McpPromptCommand;
 use crate::bottom_pane::MentionBinding;
 use crate::bottom_pane::QUIT_SHORTCUT_TIMEOUT;
 use crate::bottom_pane::SelectionAction;
@@ -200,6 +225,8 @@ use crate::history_cell::WebSearchCell;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
 use crate::markdown::append_markdown;
+use crate::mcp_elicitation::ElicitationForm;
+use crate::mcp_elicitation::FieldKind;
 use crate::multi_agents;
 use crate::render::Insets;
 use crate::render::renderable::ColumnRenderable;
@@ -235,7 +262,9 @@ use chrono::Local;
 use codex_core::AuthManager;
 use codex_core::CodexAuth;
 use codex_core::ThreadManager;
+use codex_core::WireApi;
 use codex_core::protocol::AskForApproval;
+use codex_core::protocol::ReviewDecision;
 use codex_core::protocol::SandboxPolicy;
 use codex_file_search::FileMatch;
 use codex_protocol::openai_models::InputModality;
@@ -258,6 +287,13 @@ struct RunningCommand {
     source: ExecCommandSource,
 }
 
+// An MCP resource attached with `/resources` that has not been sent yet.
+struct PendingMcpResource {
+    server: String,
+    uri: String,
+    name: String,
+}
+
 struct UnifiedExecProcessSummary {
     key: String,
     call_id: String,
@@ -519,6 +555,8 @@ pub(crate) struct ChatWidget {
     plan_stream_controller: Option<PlanStreamController>,
     running_commands: HashMap<String, RunningCommand>,
     suppressed_exec_calls: HashSet<String>,
+    // Full output of the most recent command whose output was truncated, opened by `/output`.
+    last_output_artifact: Option<PathBuf>,
     skills_all: Vec<ProtocolSkillMetadata>,
     skills_initial_state: Option<HashMap<PathBuf, bool>>,
     last_unified_wait: Option<UnifiedExecWaitState>,
@@ -537,6 +575,11 @@ pub(crate) struct ChatWidget {
     /// bottom pane is treated as "running" while this is populated, even if no agent turn is
     /// currently executing.
     mcp_startup_status: Option<HashMap<String, McpStartupStatus>>,
+    /// Set by `/resources` so the next `McpListToolsResponse` opens the resource picker instead of
+    /// printing the tool list.
+    mcp_resource_picker_requested: bool,
+    /// MCP resources picked with `/resources`, sent as mentions with the next user message.
+    pending_mcp_resources: Vec<PendingMcpResource>,
     connectors_cache: ConnectorsCacheState,
     connectors_prefetch_in_flight: bool,
     // Queue of interruptive UI events deferred during an active write cycle
@@ -549,6 +592,8 @@ pub(crate) struct ChatWidget {
     current_status_header: String,
     // Previous status header to restore after a transient stream retry.
     retry_status_header: Option<String>,
+    // MCP tool call whose place in the call queue is shown in the status details.
+    queued_mcp_call_id: Option<String>,
     // Set when commentary output completes; once stream queues go idle we restore the status row.
     pending_status_indicator_restore: bool,
     thread_id: Option<ThreadId>,
@@ -1673,7 +1718,55 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    fn on_mcp_server_health(&mut self, ev: McpServerHealthEvent) {
+        let McpServerHealthEvent { server, status } = ev;
+        match status {
+            McpServerHealthStatus::Restarting { attempt: 1, error } => {
+                self.on_warning(format!(
+                    "MCP server `{server}` stopped responding ({error}). Restarting it."
+                ));
+            }
+            McpServerHealthStatus::Restarting { .. } => {}
+            McpServerHealthStatus::Restarted => {
+                self.add_info_message(format!("MCP server `{server}` restarted."), None);
+            }
+            McpServerHealthStatus::Failed { error } => {
+                self.on_warning(format!(
+                    "MCP server `{server}` could not be restarted: {error}. Its tools are unavailable for the rest of the session."
+                ));
+            }
+        }
+    }
+
+    fn on_mcp_list_changed(&mut self, ev: McpListChangedEvent) {
+        match ev.list {
+            McpListKind::Tools => {
+                self.add_info_message(
+                    format!("MCP server `{}` updated its tools.", ev.server),
+                    None,
+                );
+            }
+            // Prompts are offered as slash commands, so list them again.
+            McpListKind::Prompts => self.submit_op(Op::ListMcpPrompts),
+            // Resources are listed when the picker opens.
+            McpListKind::Resources => {}
+        }
+    }
+
+    fn on_config_reloaded(&mut self, ev: ConfigReloadedEvent) {
+        let ConfigReloadedEvent { applied, deferred } = ev;
+        let message = if applied.is_empty() {
+            "Config files changed.".to_string()
+        } else {
+            format!("Reloaded config: {}.", applied.join(", "))
+        };
+        let hint = (!deferred.is_empty())
+            .then(|| format!("{} will apply in a new session.", deferred.join(", ")));
+        self.add_info_message(message, hint);
+    }
+
     fn on_mcp_startup_complete(&mut self, ev: McpStartupCompleteEvent) {
+        startup_profile::milestone(StartupPhase::McpStartup);
         let mut parts = Vec::new();
         if !ev.failed.is_empty() {
             let failed_servers: Vec<_> = ev.failed.iter().map(|f| f.server.clone()).collect();
@@ -1692,6 +1785,9 @@ impl ChatWidget {
         self.mcp_startup_status = None;
         self.update_task_running_state();
         self.maybe_send_next_queued_input();
+        if !self.config.mcp_servers.is_empty() {
+            self.submit_op(Op::ListMcpPrompts);
+        }
         self.request_redraw();
     }
 
@@ -2049,7 +2145,28 @@ impl ChatWidget {
         self.sync_unified_exec_footer();
     }
 
+    fn on_mcp_tool_call_queued(&mut self, ev: McpToolCallQueuedEvent) {
+        let McpToolCallQueuedEvent {
+            call_id,
+            invocation,
+            position,
+        } = ev;
+        self.bottom_pane.ensure_status_indicator();
+        self.set_status(
+            self.current_status_header.clone(),
+            Some(format!(
+                "{}.{} is waiting for other MCP calls to finish (#{position} in line)",
+                invocation.server, invocation.tool
+            )),
+        );
+        self.queued_mcp_call_id = Some(call_id);
+    }
+
     fn on_mcp_tool_call_begin(&mut self, ev: McpToolCallBeginEvent) {
+        if self.queued_mcp_call_id.as_ref() == Some(&ev.call_id) {
+            self.queued_mcp_call_id = None;
+            self.set_status_header(self.current_status_header.clone());
+        }
         let ev2 = ev.clone();
         self.defer_or_handle(|q| q.push_mcp_begin(ev), |s| s.handle_mcp_begin_now(ev2));
     }
@@ -2132,6 +2249,112 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    fn on_auth_expired(&mut self, event: AuthExpiredEvent) {
+        let AuthExpiredEvent { id, message, .. } = event;
+        let sign_in_id = id.clone();
+        let sign_in: Vec<SelectionAction> = vec![Box::new(move |tx| {
+            tx.send(AppEvent::StartReauth {
+                id: sign_in_id.clone(),
+            });
+        })];
+        let stop_id = id.clone();
+        let stop: Vec<SelectionAction> = vec![Box::new(move |tx| {
+            tx.send(AppEvent::CodexOp(Op::ReauthComplete {
+                id: stop_id.clone(),
+                success: false,
+            }));
+        })];
+        let items = vec![
+            SelectionItem {
+                name: "Sign in again".to_string(),
+                description: Some("Sign in with ChatGPT in your browser, then resume.".to_string()),
+                actions: sign_in,
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Stop turn".to_string(),
+                description: Some("End the turn now.".to_string()),
+                actions: stop,
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ];
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Your ChatGPT sign-in expired. Sign in again to continue?".to_string()),
+            subtitle: Some(message),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            // Dismissing the prompt must not leave the paused turn waiting forever.
+            on_cancel: Some(Box::new(move |tx: &AppEventSender| {
+                tx.send(AppEvent::CodexOp(Op::ReauthComplete {
+                    id: id.clone(),
+                    success: false,
+                }));
+            })),
+            ..Default::default()
+        });
+        self.request_redraw();
+    }
+
+    fn on_budget_limit_reached(&mut self, event: BudgetLimitReachedEvent) {
+        let BudgetLimitReachedEvent {
+            id,
+            scope,
+            kind,
+            used,
+            limit,
+            ..
+        } = event;
+        let scope = match scope {
+            BudgetScope::Turn => "Turn",
+            BudgetScope::Session => "Session",
+            BudgetScope::Daily => "Daily",
+        };
+        let amount = match kind {
+            BudgetKind::Tokens => format!("{} / {} tokens", used as i64, limit as i64),
+            BudgetKind::CostUsd => format!("${used:.2} / ${limit:.2}"),
+            BudgetKind::WallClockMinutes => format!("{used:.0} / {limit:.0} minutes"),
+            BudgetKind::ExecCalls => format!("{} / {} commands", used as u64, limit as u64),
+            BudgetKind::Steps => format!("{} / {} model requests", used as u64, limit as u64),
+        };
+        let items = [
+            (
+                "Continue",
+                "Keep going and stop checking this limit.",
+                ReviewDecision::Approved,
+            ),
+            ("Stop turn", "End the turn now.", ReviewDecision::Denied),
+        ]
+        .into_iter()
+        .map(|(name, description, decision)| {
+            let id = id.clone();
+            let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                tx.send(AppEvent::CodexOp(Op::BudgetApproval {
+                    id: id.clone(),
+                    decision: decision.clone(),
+                }));
+            })];
+            SelectionItem {
+                name: name.to_string(),
+                description: Some(description.to_string()),
+                actions,
+                dismiss_on_select: true,
+                ..Default::default()
+            }
+        })
+        .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some(format!("{scope} budget reached ({amount}). Continue?")),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+        self.request_redraw();
+    }
+
     fn on_background_event(&mut self, message: String) {
         debug!("BackgroundEvent: {message}");
         self.bottom_pane.ensure_status_indicator();
@@ -2165,6 +2388,26 @@ impl ChatWidget {
         }
     }
 
+    fn on_worktree_created(&mut self, event: WorktreeCreatedEvent) {
+        self.add_info_message(
+            format!(
+                "Working in an isolated worktree at {} on branch {}.",
+                event.path.display(),
+                event.branch
+            ),
+            Some("Run /merge to bring the changes back into your checkout.".to_string()),
+        );
+    }
+
+    fn on_worktree_merged(&mut self, event: WorktreeMergedEvent) {
+        let WorktreeMergedEvent { branch, commit } = event;
+        let message = match commit {
+            Some(commit) => format!("Merged {branch} into your checkout at {commit}."),
+            None => format!("Nothing to merge from {branch}."),
+        };
+        self.add_info_message(message, None);
+    }
+
     fn on_stream_error(&mut self, message: String, additional_details: Option<String>) {
         if self.retry_status_header.is_none() {
             self.retry_status_header = Some(self.current_status_header.clone());
@@ -2324,6 +2567,9 @@ impl ChatWidget {
         };
         let is_unified_exec_interaction =
             matches!(source, ExecCommandSource::UnifiedExecInteraction);
+        if let Some(path) = ev.output_artifact.as_ref() {
+            self.last_output_artifact = Some(path.clone());
+        }
 
         let needs_new = self
             .active_cell
@@ -2352,12 +2598,14 @@ impl ChatWidget {
                     exit_code: ev.exit_code,
                     formatted_output: String::new(),
                     aggregated_output: String::new(),
+                    output_artifact: None,
                 }
             } else {
                 CommandOutput {
                     exit_code: ev.exit_code,
                     formatted_output: ev.formatted_output.clone(),
                     aggregated_output: ev.aggregated_output.clone(),
+                    output_artifact: ev.output_artifact.clone(),
                 }
             };
             cell.complete_call(&ev.call_id, output, ev.duration);
@@ -2428,10 +2676,12 @@ impl ChatWidget {
             server_name: ev.server_name.clone(),
         });
 
+        let form = ElicitationForm::from_request(&ev);
         let request = ApprovalRequest::McpElicitation {
             server_name: ev.server_name,
             request_id: ev.id,
             message: ev.message,
+            form,
         };
         self.bottom_pane
             .push_approval_request(request, &self.config.features);
@@ -2641,6 +2891,7 @@ impl ChatWidget {
             plan_stream_controller: None,
             running_commands: HashMap::new(),
             suppressed_exec_calls: HashSet::new(),
+            last_output_artifact: None,
             last_unified_wait: None,
             unified_exec_wait_streak: None,
             turn_sleep_inhibitor: SleepInhibitor::new(prevent_idle_sleep),
@@ -2648,6 +2899,8 @@ impl ChatWidget {
             unified_exec_processes: Vec::new(),
             agent_turn_running: false,
             mcp_startup_status: None,
+            mcp_resource_picker_requested: false,
+            pending_mcp_resources: Vec::new(),
             connectors_cache: ConnectorsCacheState::default(),
             connectors_prefetch_in_flight: false,
             interrupts: InterruptManager::new(),
@@ -2655,6 +2908,7 @@ impl ChatWidget {
             full_reasoning_buffer: String::new(),
             current_status_header: String::from("Working"),
             retry_status_header: None,
+            queued_mcp_call_id: None,
             pending_status_indicator_restore: false,
             thread_id: None,
             thread_name: None,
@@ -2804,6 +3058,7 @@ impl ChatWidget {
             plan_stream_controller: None,
             running_commands: HashMap::new(),
             suppressed_exec_calls: HashSet::new(),
+            last_output_artifact: None,
             last_unified_wait: None,
             unified_exec_wait_streak: None,
             turn_sleep_inhibitor: SleepInhibitor::new(prevent_idle_sleep),
@@ -2811,6 +3066,8 @@ impl ChatWidget {
             unified_exec_processes: Vec::new(),
             agent_turn_running: false,
             mcp_startup_status: None,
+            mcp_resource_picker_requested: false,
+            pending_mcp_resources: Vec::new(),
             connectors_cache: ConnectorsCacheState::default(),
             connectors_prefetch_in_flight: false,
             interrupts: InterruptManager::new(),
@@ -2818,6 +3075,7 @@ impl ChatWidget {
             full_reasoning_buffer: String::new(),
             current_status_header: String::from("Working"),
             retry_status_header: None,
+            queued_mcp_call_id: None,
             pending_status_indicator_restore: false,
             thread_id: None,
             thread_name: None,
@@ -2956,6 +3214,7 @@ impl ChatWidget {
             plan_stream_controller: None,
             running_commands: HashMap::new(),
             suppressed_exec_calls: HashSet::new(),
+            last_output_artifact: None,
             last_unified_wait: None,
             unified_exec_wait_streak: None,
             turn_sleep_inhibitor: SleepInhibitor::new(prevent_idle_sleep),
@@ -2963,6 +3222,8 @@ impl ChatWidget {
             unified_exec_processes: Vec::new(),
             agent_turn_running: false,
             mcp_startup_status: None,
+            mcp_resource_picker_requested: false,
+            pending_mcp_resources: Vec::new(),
             connectors_cache: ConnectorsCacheState::default(),
             connectors_prefetch_in_flight: false,
             interrupts: InterruptManager::new(),
@@ -2970,6 +3231,7 @@ impl ChatWidget {
             full_reasoning_buffer: String::new(),
             current_status_header: String::from("Working"),
             retry_status_header: None,
+            queued_mcp_call_id: None,
             pending_status_indicator_restore: false,
             thread_id: None,
             thread_name: None,
@@ -3270,6 +3532,18 @@ impl ChatWidget {
             SlashCommand::Review => {
                 self.open_review_popup();
             }
+            SlashCommand::Merge => {
+                self.submit_op(Op::MergeWorktree);
+            }
+            SlashCommand::Pin => {
+                self.submit_op(Op::ListPinnedContext);
+            }
+            SlashCommand::Unpin => {
+                self.add_info_message(
+                    "Usage: /unpin <id>".to_string(),
+                    Some("Run /pin to list pinned context ids.".to_string()),
+                );
+            }
             SlashCommand::Rename => {
                 self.otel_manager.counter("codex.thread.rename", 1, &[]);
                 self.show_rename_prompt();
@@ -3403,6 +3677,16 @@ impl ChatWidget {
                     tx.send(AppEvent::DiffResult(text));
                 });
             }
+            SlashCommand::Output => {
+                if let Some(path) = self.last_output_artifact.clone() {
+                    self.app_event_tx.send(AppEvent::OpenOutputArtifact(path));
+                } else {
+                    self.add_info_message(
+                        "No truncated command output to open yet.".to_string(),
+                        None,
+                    );
+                }
+            }
             SlashCommand::Mention => {
                 self.insert_str("@");
             }
@@ -3433,6 +3717,9 @@ impl ChatWidget {
             SlashCommand::Mcp => {
                 self.add_mcp_output();
             }
+            SlashCommand::Resources => {
+                self.open_mcp_resource_picker();
+            }
             SlashCommand::Apps => {
                 self.add_connectors_output();
             }
@@ -3557,6 +3844,28 @@ impl ChatWidget {
                     self.queue_user_message(user_message);
                 }
             }
+            SlashCommand::Pin if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                let source = match prepared_args.strip_prefix('@') {
+                    Some(path) => PinnedContextSource::File {
+                        path: PathBuf::from(path.trim()),
+                    },
+                    None => PinnedContextSource::Text {
+                        text: prepared_args,
+                    },
+                };
+                self.submit_op(Op::PinContext { source });
+                self.bottom_pane.drain_pending_submission_state();
+            }
+            SlashCommand::Unpin if !trimmed.is_empty() => {
+                let id = trimmed.to_string();
+                self.submit_op(Op::UnpinContext { id });
+                self.bottom_pane.drain_pending_submission_state();
+            }
             SlashCommand::Review if !trimmed.is_empty() => {
                 let Some((prepared_args, _prepared_elements)) =
                     self.bottom_pane.prepare_inline_args_submission(false)
@@ -3573,6 +3882,15 @@ impl ChatWidget {
                 });
                 self.bottom_pane.drain_pending_submission_state();
             }
+            SlashCommand::Mcp if trimmed == "reload" => {
+                self.app_event_tx.send(AppEvent::ReloadMcpServers);
+                self.bottom_pane.drain_pending_submission_state();
+            }
+            SlashCommand::Mcp if trimmed.split_whitespace().next() == Some("logs") => {
+                let server = trimmed.split_whitespace().nth(1).map(str::to_string);
+                self.submit_op(Op::GetMcpServerLogs { server });
+                self.bottom_pane.drain_pending_submission_state();
+            }
             SlashCommand::SandboxReadRoot if !trimmed.is_empty() => {
                 let Some((prepared_args, _prepared_elements)) =
                     self.bottom_pane.prepare_inline_args_submission(false)
@@ -3753,6 +4071,13 @@ impl ChatWidget {
             });
         }
 
+        for resource in self.pending_mcp_resources.drain(..) {
+            items.push(UserInput::Mention {
+                name: resource.name,
+                path: codex_core::mcp::mcp_resource_mention_path(&resource.server, &resource.uri),
+            });
+        }
+
         let mentions = collect_tool_mentions(&text, &HashMap::new());
         let bound_names: HashSet<String> = mention_bindings
             .iter()
@@ -3967,7 +4292,7 @@ impl ChatWidget {
     /// `replay_initial_messages()`. Callers should treat `None` as a "fake" id
     /// that must not be used to correlate follow-up actions.
     fn dispatch_event_msg(&mut self, id: Option<String>, msg: EventMsg, from_replay: bool) {
-        let is_stream_error = matches!(&msg, EventMsg::StreamError(_));
+        let is_stream_error = matches!(&msg, EventMsg::StreamError(_) | EventMsg::StreamRetry(_));
         if !is_stream_error {
             self.restore_retry_status_header_if_present();
         }
@@ -4011,6 +4336,14 @@ impl ChatWidget {
             }
             EventMsg::Warning(WarningEvent { message }) => self.on_warning(message),
             EventMsg::ModelReroute(_) => {}
+            EventMsg::TurnSettings(event) => self.on_turn_settings(event, from_replay),
+            EventMsg::ModelProviderFallback(ModelProviderFallbackEvent {
+                from_provider,
+                to_provider,
+                reason,
+            }) => self.on_warning(format!(
+                "{from_provider} is unavailable; switched to {to_provider}. {reason}"
+            )),
             EventMsg::Error(ErrorEvent {
                 message,
                 codex_error_info,
@@ -4032,6 +4365,9 @@ impl ChatWidget {
             }
             EventMsg::McpStartupUpdate(ev) => self.on_mcp_startup_update(ev),
             EventMsg::McpStartupComplete(ev) => self.on_mcp_startup_complete(ev),
+            EventMsg::McpServerHealth(ev) => self.on_mcp_server_health(ev),
+            EventMsg::McpListChanged(ev) => self.on_mcp_list_changed(ev),
+            EventMsg::ConfigReloaded(ev) => self.on_config_reloaded(ev),
             EventMsg::TurnAborted(ev) => match ev.reason {
                 TurnAbortReason::Interrupted => {
                     self.on_interrupted_turn(ev.reason);
@@ -4064,6 +4400,7 @@ impl ChatWidget {
             EventMsg::PatchApplyEnd(ev) => self.on_patch_apply_end(ev),
             EventMsg::ExecCommandEnd(ev) => self.on_exec_command_end(ev),
             EventMsg::ViewImageToolCall(ev) => self.on_view_image_tool_call(ev),
+            EventMsg::McpToolCallQueued(ev) => self.on_mcp_tool_call_queued(ev),
             EventMsg::McpToolCallBegin(ev) => self.on_mcp_tool_call_begin(ev),
             EventMsg::McpToolCallEnd(ev) => self.on_mcp_tool_call_end(ev),
             EventMsg::WebSearchBegin(ev) => self.on_web_search_begin(ev),
@@ -4071,8 +4408,25 @@ impl ChatWidget {
             EventMsg::GetHistoryEntryResponse(ev) => self.on_get_history_entry_response(ev),
             EventMsg::McpListToolsResponse(ev) => self.on_list_mcp_tools(ev),
             EventMsg::ListCustomPromptsResponse(ev) => self.on_list_custom_prompts(ev),
+            EventMsg::ListMcpPromptsResponse(ev) => self.on_list_mcp_prompts(ev),
+            EventMsg::GetMcpPromptResponse(ev) => self.on_get_mcp_prompt(ev),
+            EventMsg::GetMcpServerLogsResponse(ev) => {
+                self.add_to_history(history_cell::new_mcp_server_logs_output(ev.logs));
+            }
+            EventMsg::PinnedContextUpdated(ev) => {
+                if !from_replay {
+                    self.add_to_history(history_cell::new_pinned_context_output(&ev.items));
+                }
+            }
             EventMsg::ListSkillsResponse(ev) => self.on_list_skills(ev),
-            EventMsg::ListRemoteSkillsResponse(_) | EventMsg::RemoteSkillDownloaded(_) => {}
+            EventMsg::ListRemoteSkillsResponse(_)
+            | EventMsg::RemoteSkillDownloaded(_)
+            | EventMsg::GetUsageResponse(_)
+            | EventMsg::ProjectDocsLoaded(_)
+            | EventMsg::UserInputQueueUpdated(_)
+            | EventMsg::TurnStructuredOutput(_)
+            | EventMsg::CheckpointCreated(_)
+            | EventMsg::Unknown => {}
             EventMsg::SkillsUpdateAvailable => {
                 self.submit_op(Op::ListSkills {
                     cwds: Vec::new(),
@@ -4085,13 +4439,29 @@ impl ChatWidget {
             EventMsg::BackgroundEvent(BackgroundEventEvent { message }) => {
                 self.on_background_event(message)
             }
+            EventMsg::BudgetLimitReached(ev) => self.on_budget_limit_reached(ev),
+            EventMsg::AuthExpired(ev) => self.on_auth_expired(ev),
             EventMsg::UndoStarted(ev) => self.on_undo_started(ev),
             EventMsg::UndoCompleted(ev) => self.on_undo_completed(ev),
+            EventMsg::WorktreeCreated(ev) => self.on_worktree_created(ev),
+            EventMsg::WorktreeMerged(ev) => self.on_worktree_merged(ev),
             EventMsg::StreamError(StreamErrorEvent {
                 message,
                 additional_details,
                 ..
             }) => self.on_stream_error(message, additional_details),
+            EventMsg::StreamRetry(StreamRetryEvent {
+                attempt,
+                max_attempts,
+                delay_ms,
+                reason,
+            }) => self.on_stream_error(
+                format!(
+                    "Reconnecting... {attempt}/{max_attempts} (retrying in {}s)",
+                    delay_ms.div_ceil(1000)
+                ),
+                Some(reason),
+            ),
             EventMsg::UserMessage(ev) => {
                 if from_replay {
                     self.on_user_message_event(ev);
@@ -4116,6 +4486,9 @@ impl ChatWidget {
             EventMsg::CollabCloseEnd(ev) => self.on_collab_event(multi_agents::close_end(ev)),
             EventMsg::CollabResumeBegin(ev) => self.on_collab_event(multi_agents::resume_begin(ev)),
             EventMsg::CollabResumeEnd(ev) => self.on_collab_event(multi_agents::resume_end(ev)),
+            EventMsg::DelegateBegin(ev) => self.on_collab_event(multi_agents::delegate_begin(ev)),
+            EventMsg::DelegateProgress(_) => {}
+            EventMsg::DelegateEnd(ev) => self.on_collab_event(multi_agents::delegate_end(ev)),
             EventMsg::ThreadRolledBack(rollback) => {
                 if from_replay {
                     self.app_event_tx.send(AppEvent::ApplyThreadRollback {
@@ -4812,6 +5185,12 @@ impl ChatWidget {
             return;
         }
 
+        // The models manager lists OpenAI models; Gemini providers get their own presets.
+        if self.config.model_provider.wire_api == WireApi::Gemini {
+            self.open_model_popup_with_presets(gemini_model_presets());
+            return;
+        }
+
         let presets: Vec<ModelPreset> = match self.models_manager.try_list_models() {
             Ok(models) => models,
             Err(_) => {
@@ -4948,34 +5327,31 @@ impl ChatWidget {
             .into_iter()
             .partition(|preset| Self::is_auto_model(&preset.model));
 
-        if auto_presets.is_empty() {
+        let mut items = self.model_preset_items();
+        let has_model_presets = !items.is_empty();
+        if auto_presets.is_empty() && !has_model_presets {
             self.open_all_models_popup(other_presets);
             return;
         }
 
         auto_presets.sort_by_key(|preset| Self::auto_model_order(&preset.model));
 
-        let mut items: Vec<SelectionItem> = auto_presets
-            .into_iter()
-            .map(|preset| {
-                let description =
-                    (!preset.description.is_empty()).then_some(preset.description.clone());
-                let model = preset.model.clone();
-                let actions = Self::model_selection_actions(
-                    model.clone(),
-                    Some(preset.default_reasoning_effort),
-                );
-                SelectionItem {
-                    name: model.clone(),
-                    description,
-                    is_current: model.as_str() == current_model,
-                    is_default: preset.is_default,
-                    actions,
-                    dismiss_on_select: true,
-                    ..Default::default()
-                }
-            })
-            .collect();
+        items.extend(auto_presets.into_iter().map(|preset| {
+            let description =
+                (!preset.description.is_empty()).then_some(preset.description.clone());
+            let model = preset.model.clone();
+            let actions =
+                Self::model_selection_actions(model.clone(), Some(preset.default_reasoning_effort));
+            SelectionItem {
+                name: model.clone(),
+                description,
+                is_current: model.as_str() == current_model,
+                is_default: preset.is_default,
+                actions,
+                dismiss_on_select: true,
+                ..Default::default()
+            }
+        }));
 
         if !other_presets.is_empty() {
             let all_models = other_presets;
@@ -5000,10 +5376,12 @@ impl ChatWidget {
             });
         }
 
-        let header = self.model_menu_header(
-            "Select Model",
-            "Pick a quick auto mode or browse all models.",
-        );
+        let subtitle = if has_model_presets {
+            "Pick one of your presets or browse all models."
+        } else {
+            "Pick a quick auto mode or browse all models."
+        };
+        let header = self.model_menu_header("Select Model", subtitle);
         self.bottom_pane.show_selection_view(SelectionViewParams {
             footer_hint: Some(standard_popup_hint_line()),
             items,
@@ -5012,6 +5390,46 @@ impl ChatWidget {
         });
     }
 
+    /// Items for the `[model_presets]` defined in config, which set the model and reasoning effort
+    /// right away and the verbosity for new sessions.
+    fn model_preset_items(&self) -> Vec<SelectionItem> {
+        let current_model = self.current_model();
+        let current_effort = self.effective_reasoning_effort();
+        self.config
+            .model_presets
+            .iter()
+            .map(|(name, preset)| {
+                let model = preset
+                    .model
+                    .clone()
+                    .unwrap_or_else(|| current_model.to_string());
+                let effort = preset.effort.or(current_effort);
+                let mut details = vec![model.clone()];
+                if let Some(effort) = preset.effort {
+                    details.push(format!("{effort} effort"));
+                }
+                if let Some(verbosity) = preset.verbosity {
+                    details.push(format!("{verbosity} verbosity in new sessions"));
+                }
+
+                let mut actions = Self::model_selection_actions(model.clone(), effort);
+                if let Some(verbosity) = preset.verbosity {
+                    actions.push(Box::new(move |tx| {
+                        tx.send(AppEvent::UpdateModelVerbosity(Some(verbosity)));
+                    }));
+                }
+                SelectionItem {
+                    name: name.clone(),
+                    description: Some(details.join(", ")),
+                    is_current: model == current_model && effort == current_effort,
+                    actions,
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+
     fn is_auto_model(model: &str) -> bool {
         model.starts_with("codex-auto-")
     }
@@ -6086,6 +6504,31 @@ impl ChatWidget {
             .unwrap_or(false)
     }
 
+    /// Notes a turn that runs with one-turn overrides, and adopts session settings that another
+    /// client changed so the footer and the next `UserTurn` match what core runs.
+    fn on_turn_settings(&mut self, event: TurnSettingsEvent, from_replay: bool) {
+        if event.turn_only {
+            let effort = event
+                .effort
+                .map(|effort| format!(" ({effort})"))
+                .unwrap_or_default();
+            self.add_info_message(
+                format!("This turn uses {}{effort}.", event.model),
+                Some("Later turns return to the session settings.".to_string()),
+            );
+            return;
+        }
+        if from_replay {
+            return;
+        }
+        if event.model != self.current_model() {
+            self.set_model(&event.model);
+        }
+        if event.effort != self.effective_reasoning_effort() {
+            self.set_reasoning_effort(event.effort);
+        }
+    }
+
     /// Set the reasoning effort in the stored collaboration mode.
     pub(crate) fn set_reasoning_effort(&mut self, effort: Option<ReasoningEffortConfig>) {
         self.current_collaboration_mode =
@@ -6419,6 +6862,31 @@ impl ChatWidget {
         }
     }
 
+    pub(crate) fn open_mcp_resource_picker(&mut self) {
+        if self.config.mcp_servers.is_empty() {
+            self.add_to_history(history_cell::empty_mcp_output());
+        } else {
+            self.mcp_resource_picker_requested = true;
+            self.submit_op(Op::ListMcpTools);
+        }
+    }
+
+    pub(crate) fn attach_mcp_resource(&mut self, server: String, uri: String, name: String) {
+        if self
+            .pending_mcp_resources
+            .iter()
+            .any(|resource| resource.server == server && resource.uri == uri)
+        {
+            return;
+        }
+        self.add_info_message(
+            format!("Attached MCP resource {name}."),
+            Some("It will be sent with your next message.".to_string()),
+        );
+        self.pending_mcp_resources
+            .push(PendingMcpResource { server, uri, name });
+    }
+
     pub(crate) fn add_connectors_output(&mut self) {
         if !self.connectors_enabled() {
             self.add_info_message(
@@ -6794,6 +7262,10 @@ impl ChatWidget {
     }
 
     fn on_list_mcp_tools(&mut self, ev: McpListToolsResponseEvent) {
+        if std::mem::take(&mut self.mcp_resource_picker_requested) {
+            self.show_mcp_resource_picker(ev.resources);
+            return;
+        }
         self.add_to_history(history_cell::new_mcp_tools_output(
             &self.config,
             ev.tools,
@@ -6803,6 +7275,63 @@ impl ChatWidget {
         ));
     }
 
+    fn show_mcp_resource_picker(&mut self, resources: HashMap<String, Vec<McpResource>>) {
+        let mut resources: Vec<(String, McpResource)> = resources
+            .into_iter()
+            .flat_map(|(server, resources)| {
+                resources
+                    .into_iter()
+                    .map(move |resource| (server.clone(), resource))
+            })
+            .collect();
+        if resources.is_empty() {
+            self.add_info_message(
+                "No MCP resources available.".to_string(),
+                Some("None of the configured MCP servers publish resources.".to_string()),
+            );
+            return;
+        }
+        resources.sort_by(|(a_server, a), (b_server, b)| {
+            a_server
+                .cmp(b_server)
+                .then_with(|| a.name.cmp(&b.name))
+                .then_with(|| a.uri.cmp(&b.uri))
+        });
+
+        let items = resources
+            .into_iter()
+            .map(|(server, resource)| {
+                let name = resource.title.unwrap_or(resource.name);
+                let uri = resource.uri;
+                let search_value = format!("{server} {name} {uri}");
+                let description = format!("{server} · {uri}");
+                SelectionItem {
+                    name: name.clone(),
+                    description: Some(description),
+                    actions: vec![Box::new(move |tx: &AppEventSender| {
+                        tx.send(AppEvent::AttachMcpResource {
+                            server: server.clone(),
+                            uri: uri.clone(),
+                            name: name.clone(),
+                        });
+                    })],
+                    dismiss_on_select: true,
+                    search_value: Some(search_value),
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Attach an MCP resource".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            is_searchable: true,
+            search_placeholder: Some("Type to search resources".to_string()),
+            ..Default::default()
+        });
+    }
+
     fn on_list_custom_prompts(&mut self, ev: ListCustomPromptsResponseEvent) {
         let len = ev.custom_prompts.len();
         debug!("received {len} custom prompts");
@@ -6810,6 +7339,226 @@ impl ChatWidget {
         self.bottom_pane.set_custom_prompts(ev.custom_prompts);
     }
 
+    fn on_list_mcp_prompts(&mut self, ev: ListMcpPromptsResponseEvent) {
+        let prompts = ev
+            .prompts
+            .into_iter()
+            .flat_map(|(server, prompts)| {
+                prompts.into_iter().map(move |prompt| McpPromptCommand {
+                    server: server.clone(),
+                    prompt,
+                })
+            })
+            .collect();
+        self.bottom_pane.set_mcp_prompts(prompts);
+    }
+
+    /// Runs the MCP prompt once every declared argument has a value, asking for the first
+    /// missing one otherwise. Optional arguments may be left empty and are then not sent.
+    pub(crate) fn run_mcp_prompt(
+        &mut self,
+        server: String,
+        prompt: McpPrompt,
+        mut arguments: HashMap<String, String>,
+    ) {
+        let next_argument = prompt
+            .arguments
+            .iter()
+            .flatten()
+            .find(|argument| !arguments.contains_key(&argument.name))
+            .cloned();
+        let Some(argument) = next_argument else {
+            arguments.retain(|_, value| !value.is_empty());
+            self.submit_op(Op::GetMcpPrompt {
+                server,
+                name: prompt.name,
+                arguments,
+            });
+            return;
+        };
+
+        let required = argument.required.unwrap_or(false);
+        let title = format!(
+            "/{server}:{} · {}",
+            prompt.name,
+            argument.title.as_deref().unwrap_or(&argument.name)
+        );
+        let placeholder = match (&argument.description, required) {
+            (Some(description), true) => description.clone(),
+            (Some(description), false) => format!("{description} (optional)"),
+            (None, true) => format!("Type a value for {} and press Enter", argument.name),
+            (None, false) => format!("Type a value for {} or press Enter to skip", argument.name),
+        };
+        let tx = self.app_event_tx.clone();
+        let view = CustomPromptView::new(
+            title,
+            placeholder,
+            None,
+            Box::new(move |value: String| {
+                let mut arguments = arguments.clone();
+                arguments.insert(argument.name.clone(), value);
+                tx.send(AppEvent::RunMcpPrompt {
+                    server: server.clone(),
+                    prompt: prompt.clone(),
+                    arguments,
+                });
+            }),
+        );
+        let view = if required {
+            view
+        } else {
+            view.allow_empty_submission()
+        };
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
+    /// Answers the MCP elicitation once every field has a value, asking for the first missing
+    /// one otherwise. Optional fields may be skipped and are then left out of the answer;
+    /// dismissing a prompt cancels the request.
+    pub(crate) fn fill_mcp_elicitation(
+        &mut self,
+        form: ElicitationForm,
+        mut content: serde_json::Map<String, serde_json::Value>,
+        error: Option<String>,
+    ) {
+        let next_field = form
+            .fields
+            .iter()
+            .find(|field| !content.contains_key(&field.name))
+            .cloned();
+        let Some(field) = next_field else {
+            content.retain(|_, value| !value.is_null());
+            self.submit_op(Op::ResolveElicitation {
+                server_name: form.server_name,
+                request_id: form.request_id,
+                decision: ElicitationAction::Accept,
+                content: Some(serde_json::Value::Object(content)),
+            });
+            return;
+        };
+
+        let title = format!("{} · {}", form.server_name, field.label());
+        let cancel_op = Op::ResolveElicitation {
+            server_name: form.server_name.clone(),
+            request_id: form.request_id.clone(),
+            decision: ElicitationAction::Cancel,
+            content: None,
+        };
+        let choices = match &field.kind {
+            FieldKind::Boolean => Some(vec![
+                ("Yes".to_string(), serde_json::Value::Bool(true)),
+                ("No".to_string(), serde_json::Value::Bool(false)),
+            ]),
+            FieldKind::Choice(choices) => Some(
+                choices
+                    .iter()
+                    .map(|(value, name)| (name.clone(), serde_json::Value::String(value.clone())))
+                    .collect(),
+            ),
+            FieldKind::Text | FieldKind::Number | FieldKind::Integer => None,
+        };
+
+        if let Some(mut choices) = choices {
+            if !field.required {
+                choices.push(("Skip".to_string(), serde_json::Value::Null));
+            }
+            let items = choices
+                .into_iter()
+                .map(|(name, value)| {
+                    let form = form.clone();
+                    let content = content.clone();
+                    let field_name = field.name.clone();
+                    SelectionItem {
+                        name,
+                        actions: vec![Box::new(move |tx: &AppEventSender| {
+                            let mut content = content.clone();
+                            content.insert(field_name.clone(), value.clone());
+                            tx.send(AppEvent::FillMcpElicitation {
+                                form: form.clone(),
+                                content,
+                                error: None,
+                            });
+                        })],
+                        dismiss_on_select: true,
+                        ..Default::default()
+                    }
+                })
+                .collect();
+            self.bottom_pane.show_selection_view(SelectionViewParams {
+                title: Some(title),
+                subtitle: Some(field.description.clone().unwrap_or(form.message)),
+                footer_hint: Some(standard_popup_hint_line()),
+                items,
+                on_cancel: Some(Box::new(move |tx: &AppEventSender| {
+                    tx.send(AppEvent::CodexOp(cancel_op.clone()));
+                })),
+                ..Default::default()
+            });
+            return;
+        }
+
+        let placeholder = match (&field.description, field.required) {
+            (Some(description), true) => description.clone(),
+            (Some(description), false) => format!("{description} (optional)"),
+            (None, true) => format!("Type a value for {} and press Enter", field.label()),
+            (None, false) => format!("Type a value for {} or press Enter to skip", field.label()),
+        };
+        let context_label = error.unwrap_or_else(|| form.message.clone());
+        let tx = self.app_event_tx.clone();
+        let cancel_tx = self.app_event_tx.clone();
+        let required = field.required;
+        let view = CustomPromptView::new(
+            title,
+            placeholder,
+            Some(context_label),
+            Box::new(move |input: String| {
+                let mut content = content.clone();
+                let mut error = None;
+                if input.is_empty() {
+                    content.insert(field.name.clone(), serde_json::Value::Null);
+                } else {
+                    match field.parse(&input) {
+                        Ok(value) => {
+                            content.insert(field.name.clone(), value);
+                        }
+                        Err(err) => error = Some(err),
+                    }
+                }
+                tx.send(AppEvent::FillMcpElicitation {
+                    form: form.clone(),
+                    content,
+                    error,
+                });
+            }),
+        )
+        .on_cancel(Box::new(move || {
+            cancel_tx.send(AppEvent::CodexOp(cancel_op.clone()));
+        }));
+        let view = if required {
+            view
+        } else {
+            view.allow_empty_submission()
+        };
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
+    fn on_get_mcp_prompt(&mut self, ev: GetMcpPromptResponseEvent) {
+        let GetMcpPromptResponseEvent {
+            server,
+            name,
+            result,
+        } = ev;
+        match result {
+            Ok(text) if text.trim().is_empty() => {
+                self.add_info_message(format!("MCP prompt /{server}:{name} is empty."), None);
+            }
+            Ok(text) => self.submit_user_message(text.into()),
+            Err(err) => {
+                self.add_error_message(format!("Failed to run MCP prompt /{server}:{name}: {err}"))
+            }
+        }
+    }
+
     fn on_list_skills(&mut self, ev: ListSkillsResponseEvent) {
         self.set_skills_from_response(&ev);
     }
@@ -6864,6 +7613,11 @@ impl ChatWidget {
         }
     }
 
+    /// Picks up `mcp_servers` from a config reloaded from disk.
+    pub(crate) fn sync_mcp_servers(&mut self, config: &Config) {
+        self.config.mcp_servers = config.mcp_servers.clone();
+    }
+
     pub(crate) fn update_connector_enabled(&mut self, connector_id: &str, enabled: bool) {
         let ConnectorsCacheState::Ready(mut snapshot) = self.connectors_cache.clone() else {
             return;
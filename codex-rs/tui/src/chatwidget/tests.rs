@@ -20,6 +20,7 @@ use codex_core::config::Config;
 use codex_core::config::ConfigBuilder;
 use codex_core::config::Constrained;
 use codex_core::config::ConstraintError;
+use codex_core::config::types::ModelPresetToml;
 #[cfg(target_os = "windows")]
 use codex_core::config::types::WindowsSandboxModeToml;
 use codex_core::config_loader::RequirementSource;
@@ -42,7 +43,10 @@ use codex_core::protocol::ExecCommandStatus as CoreExecCommandStatus;
 use codex_core::protocol::ExecPolicyAmendment;
 use codex_core::protocol::ExitedReviewModeEvent;
 use codex_core::protocol::FileChange;
+use codex_core::protocol::GetMcpServerLogsResponseEvent;
 use codex_core::protocol::ItemCompletedEvent;
+use codex_core::protocol::McpListChangedEvent;
+use codex_core::protocol::McpListKind;
 use codex_core::protocol::McpStartupCompleteEvent;
 use codex_core::protocol::McpStartupStatus;
 use codex_core::protocol::McpStartupUpdateEvent;
@@ -55,6 +59,7 @@ use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SessionSource;
 use codex_core::protocol::StreamErrorEvent;
+use codex_core::protocol::StreamRetryEvent;
 use codex_core::protocol::TerminalInteractionEvent;
 use codex_core::protocol::ThreadRolledBackEvent;
 use codex_core::protocol::TokenCountEvent;
@@ -75,6 +80,7 @@ use codex_protocol::config_types::CollaborationMode;
 use codex_protocol::config_types::ModeKind;
 use codex_protocol::config_types::Personality;
 use codex_protocol::config_types::Settings;
+use codex_protocol::config_types::Verbosity;
 use codex_protocol::items::AgentMessageContent;
 use codex_protocol::items::AgentMessageItem;
 use codex_protocol::items::TurnItem;
@@ -1615,6 +1621,7 @@ async fn make_chatwidget_manual(
         plan_stream_controller: None,
         running_commands: HashMap::new(),
         suppressed_exec_calls: HashSet::new(),
+        last_output_artifact: None,
         skills_all: Vec::new(),
         skills_initial_state: None,
         last_unified_wait: None,
@@ -1624,6 +1631,8 @@ async fn make_chatwidget_manual(
         unified_exec_processes: Vec::new(),
         agent_turn_running: false,
         mcp_startup_status: None,
+        mcp_resource_picker_requested: false,
+        pending_mcp_resources: Vec::new(),
         connectors_cache: ConnectorsCacheState::default(),
         connectors_prefetch_in_flight: false,
         interrupts: InterruptManager::new(),
@@ -1631,6 +1640,7 @@ async fn make_chatwidget_manual(
         full_reasoning_buffer: String::new(),
         current_status_header: String::from("Working"),
         retry_status_header: None,
+        queued_mcp_call_id: None,
         pending_status_indicator_restore: false,
         thread_id: None,
         thread_name: None,
@@ -2117,6 +2127,93 @@ async fn plan_implementation_popup_snapshot() {
     assert_snapshot!("plan_implementation_popup", popup);
 }
 
+#[tokio::test]
+async fn budget_limit_popup_sends_decision() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("gpt-5")).await;
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::BudgetLimitReached(BudgetLimitReachedEvent {
+            id: "budget-1".into(),
+            turn_id: "turn-1".into(),
+            scope: BudgetScope::Session,
+            kind: BudgetKind::Tokens,
+            used: 1_200.0,
+            limit: 1_000.0,
+        }),
+    });
+
+    let popup = render_bottom_popup(&chat, 80);
+    assert_snapshot!("budget_limit_popup", popup);
+
+    chat.handle_key_event(KeyEvent::from(KeyCode::Down));
+    chat.handle_key_event(KeyEvent::from(KeyCode::Enter));
+    let event = rx.try_recv().expect("expected AppEvent");
+    let AppEvent::CodexOp(op) = event else {
+        panic!("expected CodexOp, got {event:?}");
+    };
+    assert_eq!(
+        op,
+        Op::BudgetApproval {
+            id: "budget-1".into(),
+            decision: ReviewDecision::Denied,
+        }
+    );
+}
+
+#[tokio::test]
+async fn auth_expired_popup_signs_in_again_or_stops() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("gpt-5")).await;
+    let auth_expired = |id: &str| Event {
+        id: "sub-1".into(),
+        msg: EventMsg::AuthExpired(AuthExpiredEvent {
+            id: id.into(),
+            turn_id: "turn-1".into(),
+            message: "Your refresh token has expired.".into(),
+        }),
+    };
+
+    chat.handle_codex_event(auth_expired("reauth-1"));
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(popup.contains("Your ChatGPT sign-in expired"), "{popup}");
+    assert!(popup.contains("Sign in again"), "{popup}");
+    chat.handle_key_event(KeyEvent::from(KeyCode::Enter));
+    let event = rx.try_recv().expect("expected AppEvent");
+    assert!(
+        matches!(&event, AppEvent::StartReauth { id } if id == "reauth-1"),
+        "expected StartReauth, got {event:?}"
+    );
+
+    chat.handle_codex_event(auth_expired("reauth-2"));
+    chat.handle_key_event(KeyEvent::from(KeyCode::Down));
+    chat.handle_key_event(KeyEvent::from(KeyCode::Enter));
+    let event = rx.try_recv().expect("expected AppEvent");
+    let AppEvent::CodexOp(op) = event else {
+        panic!("expected CodexOp, got {event:?}");
+    };
+    assert_eq!(
+        op,
+        Op::ReauthComplete {
+            id: "reauth-2".into(),
+            success: false,
+        }
+    );
+
+    // Dismissing the prompt stops the paused turn instead of leaving it waiting.
+    chat.handle_codex_event(auth_expired("reauth-3"));
+    chat.handle_key_event(KeyEvent::from(KeyCode::Esc));
+    let event = rx.try_recv().expect("expected AppEvent");
+    let AppEvent::CodexOp(op) = event else {
+        panic!("expected CodexOp, got {event:?}");
+    };
+    assert_eq!(
+        op,
+        Op::ReauthComplete {
+            id: "reauth-3".into(),
+            success: false,
+        }
+    );
+}
+
 #[tokio::test]
 async fn plan_implementation_popup_no_selected_snapshot() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("gpt-5")).await;
@@ -2275,6 +2372,7 @@ async fn plan_implementation_popup_skips_replayed_turn_complete() {
     chat.replay_initial_messages(vec![EventMsg::TurnComplete(TurnCompleteEvent {
         turn_id: "turn-1".to_string(),
         last_agent_message: Some("Plan details".to_string()),
+        usage: None,
     })]);
 
     let popup = render_bottom_popup(&chat, 80);
@@ -2300,6 +2398,7 @@ async fn plan_implementation_popup_shows_once_when_replay_precedes_live_turn_com
     chat.replay_initial_messages(vec![EventMsg::TurnComplete(TurnCompleteEvent {
         turn_id: "turn-1".to_string(),
         last_agent_message: Some("Plan details".to_string()),
+        usage: None,
     })]);
     let replay_popup = render_bottom_popup(&chat, 80);
     assert!(
@@ -2312,6 +2411,7 @@ async fn plan_implementation_popup_shows_once_when_replay_precedes_live_turn_com
         msg: EventMsg::TurnComplete(TurnCompleteEvent {
             turn_id: "turn-1".to_string(),
             last_agent_message: Some("Plan details".to_string()),
+            usage: None,
         }),
     });
 
@@ -2333,6 +2433,7 @@ async fn plan_implementation_popup_shows_once_when_replay_precedes_live_turn_com
         msg: EventMsg::TurnComplete(TurnCompleteEvent {
             turn_id: "turn-1".to_string(),
             last_agent_message: Some("Plan details".to_string()),
+            usage: None,
         }),
     });
     let duplicate_popup = render_bottom_popup(&chat, 80);
@@ -2725,12 +2826,11 @@ fn end_exec(
             parsed_cmd,
             source,
             interaction_input,
-            stdout: stdout.to_string(),
-            stderr: stderr.to_string(),
             aggregated_output: aggregated.clone(),
             exit_code,
             duration: std::time::Duration::from_millis(5),
             formatted_output: aggregated,
+            output_artifact: None,
             status: if exit_code == 0 {
                 CoreExecCommandStatus::Completed
             } else {
@@ -3191,12 +3291,11 @@ async fn exec_end_without_begin_uses_event_command() {
             parsed_cmd,
             source: ExecCommandSource::Agent,
             interaction_input: None,
-            stdout: "done".to_string(),
-            stderr: String::new(),
             aggregated_output: "done".to_string(),
             exit_code: 0,
             duration: std::time::Duration::from_millis(5),
             formatted_output: "done".to_string(),
+            output_artifact: None,
             status: CoreExecCommandStatus::Completed,
         }),
     });
@@ -3329,6 +3428,7 @@ async fn unified_exec_wait_after_final_agent_message_snapshot() {
         msg: EventMsg::TurnComplete(TurnCompleteEvent {
             turn_id: "turn-1".to_string(),
             last_agent_message: Some("Final response.".into()),
+            usage: None,
         }),
     });
 
@@ -3371,6 +3471,7 @@ async fn unified_exec_wait_before_streamed_agent_message_snapshot() {
         msg: EventMsg::TurnComplete(TurnCompleteEvent {
             turn_id: "turn-1".to_string(),
             last_agent_message: None,
+            usage: None,
         }),
     });
 
@@ -3424,6 +3525,7 @@ async fn unified_exec_waiting_multiple_empty_snapshots() {
         msg: EventMsg::TurnComplete(TurnCompleteEvent {
             turn_id: "turn-1".to_string(),
             last_agent_message: None,
+            usage: None,
         }),
     });
 
@@ -3476,6 +3578,7 @@ async fn unified_exec_non_empty_then_empty_snapshots() {
         msg: EventMsg::TurnComplete(TurnCompleteEvent {
             turn_id: "turn-1".to_string(),
             last_agent_message: None,
+            usage: None,
         }),
     });
 
@@ -3695,6 +3798,74 @@ async fn plan_slash_command_with_args_submits_prompt_in_plan_mode() {
     assert_eq!(chat.active_collaboration_mode_kind(), ModeKind::Plan);
 }
 
+#[tokio::test]
+async fn attached_mcp_resources_are_sent_once_with_next_message() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
+
+    let configured = codex_core::protocol::SessionConfiguredEvent {
+        session_id: ThreadId::new(),
+        forked_from_id: None,
+        thread_name: None,
+        model: "test-model".to_string(),
+        model_provider_id: "test-provider".to_string(),
+        approval_policy: AskForApproval::Never,
+        sandbox_policy: SandboxPolicy::new_read_only_policy(),
+        cwd: PathBuf::from("/home/user/project"),
+        reasoning_effort: Some(ReasoningEffortConfig::default()),
+        history_log_id: 0,
+        history_entry_count: 0,
+        initial_messages: None,
+        network_proxy: None,
+        rollout_path: None,
+    };
+    chat.handle_codex_event(Event {
+        id: "configured".into(),
+        msg: EventMsg::SessionConfigured(configured),
+    });
+    drain_insert_history(&mut rx);
+
+    chat.attach_mcp_resource(
+        "docs".to_string(),
+        "file:///guides/setup.md".to_string(),
+        "Setup guide".to_string(),
+    );
+    chat.attach_mcp_resource(
+        "docs".to_string(),
+        "file:///guides/setup.md".to_string(),
+        "Setup guide".to_string(),
+    );
+
+    chat.bottom_pane
+        .set_composer_text("summarize this".to_string(), Vec::new(), Vec::new());
+    chat.handle_key_event(KeyEvent::from(KeyCode::Enter));
+    let items = match next_submit_op(&mut op_rx) {
+        Op::UserTurn { items, .. } => items,
+        other => panic!("expected Op::UserTurn, got {other:?}"),
+    };
+    assert_eq!(
+        items,
+        vec![
+            UserInput::Text {
+                text: "summarize this".to_string(),
+                text_elements: Vec::new(),
+            },
+            UserInput::Mention {
+                name: "Setup guide".to_string(),
+                path: "mcp-resource://docs/file:///guides/setup.md".to_string(),
+            },
+        ]
+    );
+
+    chat.bottom_pane
+        .set_composer_text("thanks".to_string(), Vec::new(), Vec::new());
+    chat.handle_key_event(KeyEvent::from(KeyCode::Enter));
+    let items = match next_submit_op(&mut op_rx) {
+        Op::UserTurn { items, .. } => items,
+        other => panic!("expected Op::UserTurn, got {other:?}"),
+    };
+    assert_eq!(items.len(), 1);
+}
+
 #[tokio::test]
 async fn collaboration_modes_defaults_to_code_on_startup() {
     let codex_home = tempdir().expect("tempdir");
@@ -3973,6 +4144,73 @@ async fn slash_fork_requests_current_fork() {
     assert_matches!(rx.try_recv(), Ok(AppEvent::ForkCurrentSession));
 }
 
+#[tokio::test]
+async fn slash_mcp_reload_requests_mcp_server_reload() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.bottom_pane
+        .set_composer_text("/mcp reload".to_string(), Vec::new(), Vec::new());
+    chat.handle_key_event(KeyEvent::from(KeyCode::Enter));
+
+    let mut requested = false;
+    while let Ok(event) = rx.try_recv() {
+        requested |= matches!(event, AppEvent::ReloadMcpServers);
+    }
+    assert!(requested, "expected AppEvent::ReloadMcpServers");
+}
+
+#[tokio::test]
+async fn mcp_prompt_list_changes_request_prompts_again() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual(None).await;
+
+    chat.handle_codex_event(Event {
+        id: "list-changed".into(),
+        msg: EventMsg::McpListChanged(McpListChangedEvent {
+            server: "github".to_string(),
+            list: McpListKind::Prompts,
+        }),
+    });
+
+    assert_matches!(op_rx.try_recv(), Ok(Op::ListMcpPrompts));
+}
+
+#[tokio::test]
+async fn slash_mcp_logs_requests_and_shows_server_logs() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
+
+    chat.bottom_pane
+        .set_composer_text("/mcp logs github".to_string(), Vec::new(), Vec::new());
+    chat.handle_key_event(KeyEvent::from(KeyCode::Enter));
+
+    let mut requested = None;
+    while let Ok(op) = op_rx.try_recv() {
+        if let Op::GetMcpServerLogs { server } = op {
+            requested = Some(server);
+        }
+    }
+    assert_eq!(requested, Some(Some("github".to_string())));
+
+    chat.handle_codex_event(Event {
+        id: "logs".into(),
+        msg: EventMsg::GetMcpServerLogsResponse(GetMcpServerLogsResponseEvent {
+            logs: HashMap::from([(
+                "github".to_string(),
+                vec!["error: GITHUB_TOKEN is not set".to_string()],
+            )]),
+        }),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    let output = cells
+        .last()
+        .map(|lines| lines_to_single_string(lines))
+        .expect("expected logs cell");
+    assert!(
+        output.contains("github") && output.contains("error: GITHUB_TOKEN is not set"),
+        "expected server log in output, got: {output:?}"
+    );
+}
+
 #[tokio::test]
 async fn slash_rollout_displays_current_path() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -4190,6 +4428,195 @@ async fn custom_prompt_submit_sends_review_op() {
     }
 }
 
+#[tokio::test]
+async fn mcp_prompt_asks_for_arguments_before_expanding() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
+    let prompt = McpPrompt {
+        name: "review-pr".to_string(),
+        title: None,
+        description: None,
+        arguments: Some(vec![
+            codex_protocol::mcp::PromptArgument {
+                name: "number".to_string(),
+                title: None,
+                description: None,
+                required: Some(true),
+            },
+            codex_protocol::mcp::PromptArgument {
+                name: "focus".to_string(),
+                title: None,
+                description: None,
+                required: None,
+            },
+        ]),
+    };
+
+    chat.run_mcp_prompt("github".to_string(), prompt.clone(), HashMap::new());
+    chat.handle_paste("42".to_string());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let arguments = match rx.try_recv() {
+        Ok(AppEvent::RunMcpPrompt { arguments, .. }) => arguments,
+        other => panic!("expected RunMcpPrompt, got {other:?}"),
+    };
+    assert_eq!(
+        arguments,
+        HashMap::from([("number".to_string(), "42".to_string())])
+    );
+
+    // The optional argument can be skipped with an empty value.
+    chat.run_mcp_prompt("github".to_string(), prompt.clone(), arguments);
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let arguments = match rx.try_recv() {
+        Ok(AppEvent::RunMcpPrompt { arguments, .. }) => arguments,
+        other => panic!("expected RunMcpPrompt, got {other:?}"),
+    };
+
+    chat.run_mcp_prompt("github".to_string(), prompt, arguments);
+    match op_rx.try_recv() {
+        Ok(Op::GetMcpPrompt {
+            server,
+            name,
+            arguments,
+        }) => {
+            assert_eq!(server, "github");
+            assert_eq!(name, "review-pr");
+            assert_eq!(
+                arguments,
+                HashMap::from([("number".to_string(), "42".to_string())])
+            );
+        }
+        other => panic!("expected Op::GetMcpPrompt, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn mcp_elicitation_form_collects_fields_and_answers_the_server() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
+
+    chat.handle_codex_event(Event {
+        id: "elicitation".into(),
+        msg: EventMsg::ElicitationRequest(ElicitationRequestEvent {
+            server_name: "deploy".to_string(),
+            id: codex_protocol::mcp::RequestId::Integer(7),
+            message: "Where should this go?".to_string(),
+            requested_schema: Some(serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "env": { "type": "string", "enum": ["prod", "staging"] },
+                    "replicas": { "type": "integer" },
+                },
+                "required": ["env", "replicas"],
+            })),
+        }),
+    });
+
+    // Accepting the request opens its form.
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+    let (form, content) = match rx.try_recv() {
+        Ok(AppEvent::FillMcpElicitation {
+            form,
+            content,
+            error: None,
+        }) => (form, content),
+        other => panic!("expected FillMcpElicitation, got {other:?}"),
+    };
+    assert!(content.is_empty());
+
+    // The first choice is picked from a list.
+    chat.fill_mcp_elicitation(form, content, None);
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let (form, content) = match rx.try_recv() {
+        Ok(AppEvent::FillMcpElicitation {
+            form,
+            content,
+            error: None,
+        }) => (form, content),
+        other => panic!("expected FillMcpElicitation, got {other:?}"),
+    };
+
+    // A value that does not fit the field type is asked for again.
+    chat.fill_mcp_elicitation(form, content, None);
+    chat.handle_paste("three".to_string());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let (form, content) = match rx.try_recv() {
+        Ok(AppEvent::FillMcpElicitation {
+            form,
+            content,
+            error: Some(error),
+        }) => {
+            assert_eq!(error, "replicas must be a whole number.");
+            (form, content)
+        }
+        other => panic!("expected FillMcpElicitation with an error, got {other:?}"),
+    };
+
+    chat.fill_mcp_elicitation(form, content, None);
+    chat.handle_paste("3".to_string());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let (form, content) = match rx.try_recv() {
+        Ok(AppEvent::FillMcpElicitation {
+            form,
+            content,
+            error: None,
+        }) => (form, content),
+        other => panic!("expected FillMcpElicitation, got {other:?}"),
+    };
+
+    chat.fill_mcp_elicitation(form, content, None);
+    match op_rx.try_recv() {
+        Ok(Op::ResolveElicitation {
+            server_name,
+            request_id,
+            decision,
+            content,
+        }) => {
+            assert_eq!(server_name, "deploy");
+            assert_eq!(request_id, codex_protocol::mcp::RequestId::Integer(7));
+            assert_eq!(decision, ElicitationAction::Accept);
+            assert_eq!(
+                content,
+                Some(serde_json::json!({ "env": "prod", "replicas": 3 }))
+            );
+        }
+        other => panic!("expected Op::ResolveElicitation, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn dismissing_an_mcp_elicitation_form_cancels_the_request() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.handle_codex_event(Event {
+        id: "elicitation".into(),
+        msg: EventMsg::ElicitationRequest(ElicitationRequestEvent {
+            server_name: "deploy".to_string(),
+            id: codex_protocol::mcp::RequestId::Integer(8),
+            message: "Name the release".to_string(),
+            requested_schema: Some(serde_json::json!({
+                "type": "object",
+                "properties": { "name": { "type": "string" } },
+            })),
+        }),
+    });
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+    let form = match rx.try_recv() {
+        Ok(AppEvent::FillMcpElicitation { form, .. }) => form,
+        other => panic!("expected FillMcpElicitation, got {other:?}"),
+    };
+    chat.fill_mcp_elicitation(form, serde_json::Map::new(), None);
+    chat.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+
+    match rx.try_recv() {
+        Ok(AppEvent::CodexOp(Op::ResolveElicitation {
+            decision, content, ..
+        })) => {
+            assert_eq!(decision, ElicitationAction::Cancel);
+            assert_eq!(content, None);
+        }
+        other => panic!("expected a cancelled elicitation, got {other:?}"),
+    }
+}
+
 /// Hitting Enter on an empty custom prompt view does not submit.
 #[tokio::test]
 async fn custom_prompt_enter_empty_does_not_send() {
@@ -4988,6 +5415,70 @@ async fn model_picker_hides_show_in_picker_false_models_from_cache() {
     );
 }
 
+#[tokio::test]
+async fn model_picker_lists_gemini_models_for_gemini_providers() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("gemini-2.5-pro")).await;
+    chat.thread_id = Some(ThreadId::new());
+    chat.config.model_provider.wire_api = WireApi::Gemini;
+
+    chat.open_model_popup();
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(
+        popup.contains("gemini-2.5-flash"),
+        "expected Gemini models in picker:\n{popup}"
+    );
+    assert!(
+        !popup.contains("gpt-5.2-codex"),
+        "expected OpenAI models to be excluded from picker:\n{popup}"
+    );
+}
+
+#[tokio::test]
+async fn model_picker_offers_configured_model_presets() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("test-visible-model")).await;
+    chat.thread_id = Some(ThreadId::new());
+    chat.config.model_presets = BTreeMap::from([(
+        "fast".to_string(),
+        ModelPresetToml {
+            model: Some("gpt-5-mini".to_string()),
+            effort: Some(ReasoningEffortConfig::Low),
+            verbosity: Some(Verbosity::Low),
+        },
+    )]);
+
+    chat.open_model_popup_with_presets(Vec::new());
+    let popup = render_bottom_popup(&chat, 100);
+    assert!(
+        popup.contains("fast") && popup.contains("gpt-5-mini, low effort"),
+        "expected the preset in the picker:\n{popup}"
+    );
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let mut events = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        events.push(event);
+    }
+    assert!(
+        events
+            .iter()
+            .any(|event| matches!(event, AppEvent::UpdateModel(model) if model == "gpt-5-mini")),
+        "expected the preset model to be selected: {events:?}"
+    );
+    assert!(
+        events.iter().any(|event| matches!(
+            event,
+            AppEvent::UpdateReasoningEffort(Some(ReasoningEffortConfig::Low))
+        )),
+        "expected the preset effort to be selected: {events:?}"
+    );
+    assert!(
+        events
+            .iter()
+            .any(|event| matches!(event, AppEvent::UpdateModelVerbosity(Some(Verbosity::Low)))),
+        "expected the preset verbosity to be applied to new sessions: {events:?}"
+    );
+}
+
 #[tokio::test]
 async fn server_overloaded_error_does_not_switch_models() {
     let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(Some("boomslang")).await;
@@ -5819,7 +6310,7 @@ async fn approval_modal_exec_multiline_prefix_hides_execpolicy_option_snapshot()
         .draw(|f| chat.render(f.area(), f.buffer_mut()))
         .expect("draw approval modal (multiline prefix)");
     let contents = terminal.backend().vt100().screen().contents();
-    assert!(!contents.contains("don't ask again"));
+    assert!(!contents.contains("commands that start with"));
     assert_snapshot!(
         "approval_modal_exec_multiline_prefix_no_execpolicy",
         contents
@@ -6054,6 +6545,7 @@ async fn turn_complete_keeps_unified_exec_processes() {
         msg: EventMsg::TurnComplete(TurnCompleteEvent {
             turn_id: "turn-1".to_string(),
             last_agent_message: None,
+            usage: None,
         }),
     });
 
@@ -6815,6 +7307,70 @@ async fn stream_error_restores_hidden_status_indicator() {
     assert_eq!(status.details(), Some(details));
 }
 
+#[tokio::test]
+async fn stream_retry_shows_retry_delay_in_status_indicator() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.bottom_pane.set_task_running(true);
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::StreamRetry(StreamRetryEvent {
+            attempt: 1,
+            max_attempts: 5,
+            delay_ms: 2_500,
+            reason: "exceeded retry limit, last status: 429 Too Many Requests".to_string(),
+        }),
+    });
+
+    assert!(drain_insert_history(&mut rx).is_empty());
+    let status = chat
+        .bottom_pane
+        .status_widget()
+        .expect("status indicator should be visible");
+    assert_eq!(status.header(), "Reconnecting... 1/5 (retrying in 3s)");
+    assert_eq!(
+        status.details(),
+        Some("exceeded retry limit, last status: 429 Too Many Requests")
+    );
+}
+
+#[tokio::test]
+async fn turn_settings_note_one_turn_overrides_and_adopt_session_changes() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("gpt-5.2-codex")).await;
+    let settings = |model: &str, turn_only: bool| TurnSettingsEvent {
+        turn_id: "turn-1".to_string(),
+        model: model.to_string(),
+        effort: Some(ReasoningEffortConfig::High),
+        approval_policy: AskForApproval::Never,
+        sandbox_policy: SandboxPolicy::new_read_only_policy(),
+        cwd: PathBuf::from("/repo"),
+        turn_only,
+    };
+
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::TurnSettings(settings("gpt-5.1-codex-max", true)),
+    });
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected one info history cell");
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains("This turn uses gpt-5.1-codex-max (high)."),
+        "info cell missing content: {rendered}"
+    );
+    assert_eq!(chat.current_model(), "gpt-5.2-codex");
+
+    chat.handle_codex_event(Event {
+        id: "sub-2".into(),
+        msg: EventMsg::TurnSettings(settings("gpt-5.1-codex-max", false)),
+    });
+    assert!(drain_insert_history(&mut rx).is_empty());
+    assert_eq!(chat.current_model(), "gpt-5.1-codex-max");
+    assert_eq!(
+        chat.current_reasoning_effort(),
+        Some(ReasoningEffortConfig::High)
+    );
+}
+
 #[tokio::test]
 async fn warning_event_adds_warning_history_cell() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -6889,6 +7445,7 @@ async fn status_line_branch_refreshes_after_turn_complete() {
         msg: EventMsg::TurnComplete(TurnCompleteEvent {
             turn_id: "turn-1".to_string(),
             last_agent_message: None,
+            usage: None,
         }),
     });
 
@@ -7030,6 +7587,7 @@ async fn multiple_agent_messages_in_single_turn_emit_multiple_headers() {
         msg: EventMsg::TurnComplete(TurnCompleteEvent {
             turn_id: "turn-1".to_string(),
             last_agent_message: None,
+            usage: None,
         }),
     });
 
@@ -7188,12 +7746,11 @@ async fn chatwidget_exec_and_status_layout_vt100_snapshot() {
             parsed_cmd,
             source: ExecCommandSource::Agent,
             interaction_input: None,
-            stdout: String::new(),
-            stderr: String::new(),
             aggregated_output: String::new(),
             exit_code: 0,
             duration: std::time::Duration::from_millis(16000),
             formatted_output: String::new(),
+            output_artifact: None,
             status: CoreExecCommandStatus::Completed,
         }),
     });
@@ -7324,6 +7881,7 @@ printf 'fenced within fenced\n'
         msg: EventMsg::TurnComplete(TurnCompleteEvent {
             turn_id: "turn-1".to_string(),
             last_agent_message: None,
+            usage: None,
         }),
     });
     for lines in drain_insert_history(&mut rx) {
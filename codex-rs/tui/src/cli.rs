@@ -53,6 +53,11 @@ pub struct Cli {
     #[arg(long, short = 'm')]
     pub model: Option<String>,
 
+    /// Model preset from `[model_presets]` in config.toml; sets the model, reasoning effort and
+    /// verbosity it defines.
+    #[arg(long = "preset")]
+    pub model_preset: Option<String>,
+
     /// Convenience flag to select the local open source model provider. Equivalent to -c
     /// model_provider=oss; verifies a local LM Studio or Ollama server is running.
     #[arg(long = "oss", default_value_t = false)]
@@ -64,6 +69,7 @@ pub struct Cli {
     pub oss_provider: Option<String>,
 
     /// Configuration profile from config.toml to specify default options.
+    /// Defaults to `$CODEX_PROFILE`.
     #[arg(long = "profile", short = 'p')]
     pub config_profile: Option<String>,
 
@@ -110,6 +116,11 @@ pub struct Cli {
     #[arg(long = "no-alt-screen", default_value_t = false)]
     pub no_alt_screen: bool,
 
+    /// Print how long each startup phase took (config load, cloud auth, auth, MCP startup,
+    /// rollout scan and first frame) after exiting.
+    #[arg(long = "profile-startup", default_value_t = false)]
+    pub profile_startup: bool,
+
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 }
@@ -164,6 +164,14 @@ fn render_debug_config_lines(stack: &ConfigLayerStack) -> Vec<Line<'static>> {
         ));
     }
 
+    if let Some(blocked_tools) = requirements.blocked_tools.as_ref() {
+        requirement_lines.push(requirement_line(
+            "blocked_tools",
+            join_or_empty(blocked_tools.value.clone()),
+            Some(&blocked_tools.source),
+        ));
+    }
+
     if requirement_lines.is_empty() {
         lines.push("  <none>".dim().into());
     } else {
@@ -532,6 +540,8 @@ mod tests {
             rules: None,
             enforce_residency: Some(ResidencyRequirement::Us),
             network: None,
+            blocked_tools: None,
+            requirements_url: None,
         };
 
         let user_file = if cfg!(windows) {
@@ -650,6 +660,8 @@ approval_policy = "never"
             rules: None,
             enforce_residency: None,
             network: None,
+            blocked_tools: None,
+            requirements_url: None,
         };
 
         let stack = ConfigLayerStack::new(Vec::new(), requirements, requirements_toml)
@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -11,6 +12,8 @@ pub(crate) struct CommandOutput {
     pub(crate) aggregated_output: String,
     /// The formatted output of the command, as seen by the model.
     pub(crate) formatted_output: String,
+    /// File holding the full output when the model only saw a truncated copy.
+    pub(crate) output_artifact: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -97,6 +100,7 @@ impl ExecCell {
                     exit_code: 1,
                     formatted_output: String::new(),
                     aggregated_output: String::new(),
+                    output_artifact: None,
                 });
             }
         }
@@ -486,6 +486,14 @@ impl ExecCell {
                     ));
                 }
             }
+
+            if let Some(path) = output.output_artifact.as_ref() {
+                lines.push(Line::from(vec![
+                    Span::from(layout.output_block.subsequent_prefix),
+                    format!("full output saved to {}", path.display()).dim(),
+                    " · /output to open".dim(),
+                ]));
+            }
         }
 
         lines
@@ -633,6 +641,7 @@ mod tests {
             exit_code: 0,
             aggregated_output,
             formatted_output: String::new(),
+            output_artifact: None,
         };
         let width = 20;
         let layout = EXEC_DISPLAY_LAYOUT;
@@ -1,5 +1,6 @@
 use std::env;
 use std::fs;
+use std::path::Path;
 use std::process::Stdio;
 
 use color_eyre::eyre::Report;
@@ -52,14 +53,22 @@ pub(crate) fn resolve_editor_command() -> std::result::Result<Vec<String>, Edito
 
 /// Write `seed` to a temp file, launch the editor command, and return the updated content.
 pub(crate) async fn run_editor(seed: &str, editor_cmd: &[String]) -> Result<String> {
-    if editor_cmd.is_empty() {
-        return Err(Report::msg("editor command is empty"));
-    }
-
     // Convert to TempPath immediately so no file handle stays open on Windows.
     let temp_path = Builder::new().suffix(".md").tempfile()?.into_temp_path();
     fs::write(&temp_path, seed)?;
 
+    open_in_editor(&temp_path, editor_cmd).await?;
+
+    let contents = fs::read_to_string(&temp_path)?;
+    Ok(contents)
+}
+
+/// Launch the editor command on `path` and wait for it to exit.
+pub(crate) async fn open_in_editor(path: &Path, editor_cmd: &[String]) -> Result<()> {
+    if editor_cmd.is_empty() {
+        return Err(Report::msg("editor command is empty"));
+    }
+
     let mut cmd = {
         #[cfg(windows)]
         {
@@ -75,7 +84,7 @@ pub(crate) async fn run_editor(seed: &str, editor_cmd: &[String]) -> Result<Stri
         cmd.args(&editor_cmd[1..]);
     }
     let status = cmd
-        .arg(&temp_path)
+        .arg(path)
         .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
@@ -85,9 +94,7 @@ pub(crate) async fn run_editor(seed: &str, editor_cmd: &[String]) -> Result<Stri
     if !status.success() {
         return Err(Report::msg(format!("editor exited with status {status}")));
     }
-
-    let contents = fs::read_to_string(&temp_path)?;
-    Ok(contents)
+    Ok(())
 }
 
 #[cfg(test)]
@@ -4,6 +4,9 @@
 //! `AppEvent::StartFileSearch(query)`. This manager owns a single
 //! `codex-file-search` session for the current search root, updates the query
 //! on every keystroke, and drops the session when the query becomes empty.
+//!
+//! Sessions match against the shared `FileIndex` of the search root, which is walked in the
+//! background as soon as the manager is created, so the first `@` does not wait for a walk.
 
 use codex_file_search as file_search;
 use std::path::PathBuf;
@@ -15,7 +18,7 @@ use crate::app_event_sender::AppEventSender;
 
 pub(crate) struct FileSearchManager {
     state: Arc<Mutex<SearchState>>,
-    search_dir: PathBuf,
+    index: file_search::FileIndex,
     app_tx: AppEventSender,
 }
 
@@ -27,13 +30,15 @@ struct SearchState {
 
 impl FileSearchManager {
     pub fn new(search_dir: PathBuf, tx: AppEventSender) -> Self {
+        let index = file_search::FileIndex::for_root(&search_dir);
+        index.refresh();
         Self {
             state: Arc::new(Mutex::new(SearchState {
                 latest_query: String::new(),
                 session: None,
                 session_token: 0,
             })),
-            search_dir,
+            index,
             app_tx: tx,
         }
     }
@@ -42,7 +47,8 @@ impl FileSearchManager {
     /// This should be called when the session's CWD changes on resume.
     /// Drops the current session so it will be recreated with the new directory on next query.
     pub fn update_search_dir(&mut self, new_dir: PathBuf) {
-        self.search_dir = new_dir;
+        self.index = file_search::FileIndex::for_root(&new_dir);
+        self.index.refresh();
         #[expect(clippy::unwrap_used)]
         let mut st = self.state.lock().unwrap();
         st.session.take();
@@ -80,8 +86,8 @@ impl FileSearchManager {
             app_tx: self.app_tx.clone(),
             session_token,
         });
-        let session = file_search::create_session(
-            vec![self.search_dir.clone()],
+        let session = file_search::create_indexed_session(
+            &self.index,
             file_search::FileSearchOptions {
                 compute_indices: true,
                 ..Default::default()
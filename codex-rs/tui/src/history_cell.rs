@@ -42,6 +42,8 @@ use codex_core::config::types::McpServerTransportConfig;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::McpAuthStatus;
 use codex_core::protocol::McpInvocation;
+use codex_core::protocol::PinnedContextEntry;
+use codex_core::protocol::PinnedContextSource;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_core::web_search::web_search_detail;
 use codex_otel::RuntimeMetricsSummary;
@@ -769,6 +771,38 @@ pub(crate) fn new_unified_exec_processes_output(
     CompositeHistoryCell::new(vec![Box::new(command), Box::new(summary)])
 }
 
+/// Lists the context items pinned for this thread (`/pin` without arguments).
+pub(crate) fn new_pinned_context_output(items: &[PinnedContextEntry]) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = vec!["Pinned context".bold().into()];
+    if items.is_empty() {
+        lines.push(vec!["  • ".dim(), "No pinned context.".italic()].into());
+        lines.push(
+            "    Use /pin <text> or /pin @<file> to keep context through compaction."
+                .dim()
+                .into(),
+        );
+        return PlainHistoryCell { lines };
+    }
+    for item in items {
+        let summary = match &item.source {
+            PinnedContextSource::Text { text } => {
+                truncate_text(text.lines().next().unwrap_or_default(), 60)
+            }
+            PinnedContextSource::File { path } => format!("file: {}", path.display()),
+        };
+        lines.push(
+            vec![
+                "  • ".dim(),
+                item.id.clone().cyan(),
+                "  ".into(),
+                summary.into(),
+            ]
+            .into(),
+        );
+    }
+    PlainHistoryCell { lines }
+}
+
 fn truncate_exec_snippet(full_cmd: &str) -> String {
     let mut snippet = match full_cmd.split_once('\n') {
         Some((first, _)) => format!("{first} ..."),
@@ -830,6 +864,31 @@ pub fn new_approval_decision_cell(
                 ],
             )
         }
+        ApprovedForProject => {
+            let snippet = Span::from(exec_snippet(&command)).dim();
+            (
+                "✔ ".green(),
+                vec![
+                    "You ".into(),
+                    "approved".bold(),
+                    " codex to run ".into(),
+                    snippet,
+                    " every time in this project".bold(),
+                ],
+            )
+        }
+        ApprovedAlways => {
+            let snippet = Span::from(exec_snippet(&command)).dim();
+            (
+                "✔ ".green(),
+                vec![
+                    "You ".into(),
+                    "approved".bold(),
+                    " codex to always run ".into(),
+                    snippet,
+                ],
+            )
+        }
         Denied => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
@@ -1835,6 +1894,42 @@ pub(crate) fn new_mcp_tools_output(
 
     PlainHistoryCell { lines }
 }
+
+/// Most lines of each server's log shown by `/mcp logs`.
+const MCP_SERVER_LOG_LINES_SHOWN: usize = 50;
+
+/// Render the stderr lines captured for MCP servers, the latest lines of each server last.
+pub(crate) fn new_mcp_server_logs_output(logs: HashMap<String, Vec<String>>) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = vec![
+        "/mcp logs".magenta().into(),
+        "".into(),
+        vec!["🔌  ".into(), "MCP Server Logs".bold()].into(),
+    ];
+    if logs.is_empty() {
+        lines.push("".into());
+        lines.push("  • No MCP servers are running.".italic().into());
+        return PlainHistoryCell { lines };
+    }
+
+    let mut servers: Vec<_> = logs.into_iter().collect();
+    servers.sort_by(|(a, _), (b, _)| a.cmp(b));
+    for (server, log) in servers {
+        lines.push("".into());
+        lines.push(vec!["  • ".into(), server.bold()].into());
+        if log.is_empty() {
+            lines.push("    No output on stderr.".italic().into());
+            continue;
+        }
+        let skipped = log.len().saturating_sub(MCP_SERVER_LOG_LINES_SHOWN);
+        if skipped > 0 {
+            lines.push(format!("    … {skipped} earlier lines").dim().into());
+        }
+        for line in log.into_iter().skip(skipped) {
+            lines.push(vec!["    ".into(), line.dim()].into());
+        }
+    }
+    PlainHistoryCell { lines }
+}
 pub(crate) fn new_info_event(message: String, hint: Option<String>) -> PlainHistoryCell {
     let mut line = vec!["• ".dim(), message.into()];
     if let Some(hint) = hint {
@@ -2146,6 +2241,7 @@ pub(crate) fn new_patch_apply_failure(stderr: String) -> PlainHistoryCell {
                 exit_code: 1,
                 formatted_output: String::new(),
                 aggregated_output: stderr,
+                output_artifact: None,
             }),
             OutputLinesParams {
                 line_limit: TOOL_CALL_MAX_LINES,
@@ -2526,6 +2622,33 @@ mod tests {
         assert!(rendered[0].contains("Worked for"));
     }
 
+    #[test]
+    fn pinned_context_output_snapshot() {
+        let cell = new_pinned_context_output(&[
+            PinnedContextEntry {
+                id: "pin-1".to_string(),
+                source: PinnedContextSource::Text {
+                    text: "Never call the network from codex-core tests\nsecond line".to_string(),
+                },
+            },
+            PinnedContextEntry {
+                id: "pin-2".to_string(),
+                source: PinnedContextSource::File {
+                    path: PathBuf::from("docs/architecture.md"),
+                },
+            },
+        ]);
+        let rendered = render_lines(&cell.display_lines(80)).join("\n");
+        insta::assert_snapshot!(rendered);
+    }
+
+    #[test]
+    fn pinned_context_output_empty_snapshot() {
+        let cell = new_pinned_context_output(&[]);
+        let rendered = render_lines(&cell.display_lines(80)).join("\n");
+        insta::assert_snapshot!(rendered);
+    }
+
     #[test]
     fn ps_output_empty_snapshot() {
         let cell = new_unified_exec_processes_output(Vec::new());
@@ -2592,7 +2715,7 @@ mod tests {
     async fn mcp_tools_output_masks_sensitive_values() {
         let mut config = test_config().await;
         let mut env = HashMap::new();
-        env.insert("TOKEN".to_string(), "secret".to_string());
+        env.insert("TOKEN".to_string(), "secret".into());
         let stdio_config = McpServerConfig {
             transport: McpServerTransportConfig::Stdio {
                 command: "docs-server".to_string(),
@@ -2608,7 +2731,11 @@ mod tests {
             tool_timeout_sec: None,
             enabled_tools: None,
             disabled_tools: None,
+            tool_renames: None,
+            tools: None,
+            tool_cache_ttl_sec: None,
             scopes: None,
+            max_concurrent_calls: None,
         };
         let mut servers = config.mcp_servers.get().clone();
         servers.insert("docs".to_string(), stdio_config);
@@ -2631,7 +2758,11 @@ mod tests {
             tool_timeout_sec: None,
             enabled_tools: None,
             disabled_tools: None,
+            tool_renames: None,
+            tools: None,
+            tool_cache_ttl_sec: None,
             scopes: None,
+            max_concurrent_calls: None,
         };
         servers.insert("http".to_string(), http_config);
         config
@@ -3362,6 +3493,7 @@ mod tests {
                 exit_code: 1,
                 formatted_output: String::new(),
                 aggregated_output: stderr,
+                output_artifact: None,
             },
             Duration::from_millis(1),
         );
@@ -3410,6 +3542,7 @@ mod tests {
                 exit_code: 1,
                 formatted_output: String::new(),
                 aggregated_output: stderr,
+                output_artifact: None,
             },
             Duration::from_millis(5),
         );
@@ -22,6 +22,7 @@ use codex_core::config::ConfigOverrides;
 use codex_core::config::find_codex_home;
 use codex_core::config::load_config_as_toml_with_cli_overrides;
 use codex_core::config::resolve_oss_provider;
+use codex_core::config::types::LogFormat;
 use codex_core::config_loader::CloudRequirementsLoader;
 use codex_core::config_loader::ConfigLoadError;
 use codex_core::config_loader::format_config_error_with_source;
@@ -34,6 +35,7 @@ use codex_core::protocol::AskForApproval;
 use codex_core::read_session_meta_line;
 use codex_core::terminal::Multiplexer;
 use codex_core::windows_sandbox::WindowsSandboxLevelExt;
+use codex_otel::json_log::JsonLogLayer;
 use codex_protocol::config_types::AltScreenMode;
 use codex_protocol::config_types::SandboxMode;
 use codex_protocol::config_types::WindowsSandboxLevel;
@@ -43,12 +45,15 @@ use codex_state::log_db;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use codex_utils_oss::ensure_oss_provider_ready;
 use codex_utils_oss::get_default_model_for_oss_provider;
+use codex_utils_oss::model_capability_overrides;
 use cwd_prompt::CwdPromptAction;
 use cwd_prompt::CwdPromptOutcome;
 use cwd_prompt::CwdSelection;
+use startup_profile::StartupPhase;
 use std::fs::OpenOptions;
 use std::path::Path;
 use std::path::PathBuf;
+use std::time::Instant;
 use tracing::error;
 use tracing_appender::non_blocking;
 use tracing_subscriber::EnvFilter;
@@ -84,6 +89,7 @@ pub mod live_wrap;
 mod markdown;
 mod markdown_render;
 mod markdown_stream;
+mod mcp_elicitation;
 mod mention_codec;
 mod model_migration;
 mod multi_agents;
@@ -99,6 +105,7 @@ mod session_log;
 mod shimmer;
 mod skills_helpers;
 mod slash_command;
+mod startup_profile;
 mod status;
 mod status_indicator_widget;
 mod streaming;
@@ -106,6 +113,7 @@ mod style;
 mod terminal_palette;
 mod text_formatting;
 mod tooltips;
+mod transcript_spill;
 mod tui;
 mod ui_consts;
 pub mod update_action;
@@ -118,6 +126,7 @@ mod wrapping;
 #[cfg(test)]
 pub mod test_backend;
 
+use crate::onboarding::has_untrusted_project_config;
 use crate::onboarding::onboarding_screen::OnboardingScreenArgs;
 use crate::onboarding::onboarding_screen::run_onboarding_app;
 use crate::tui::Tui;
@@ -131,6 +140,9 @@ pub async fn run_main(
     mut cli: Cli,
     codex_linux_sandbox_exe: Option<PathBuf>,
 ) -> std::io::Result<AppExitInfo> {
+    if cli.profile_startup {
+        startup_profile::enable();
+    }
     let (sandbox_mode, approval_policy) = if cli.full_auto {
         (
             Some(SandboxMode::WorkspaceWrite),
@@ -160,7 +172,7 @@ pub async fn run_main(
     let raw_overrides = cli.config_overrides.raw_overrides.clone();
     // `oss` model provider.
     let overrides_cli = codex_utils_cli::CliConfigOverrides { raw_overrides };
-    let cli_kv_overrides = match overrides_cli.parse_overrides() {
+    let mut cli_kv_overrides = match overrides_cli.parse_overrides() {
         // Parse `-c` overrides from the CLI.
         Ok(v) => v,
         #[allow(clippy::print_stderr)]
@@ -187,10 +199,9 @@ pub async fn run_main(
     };
 
     #[allow(clippy::print_stderr)]
-    let config_toml = match load_config_as_toml_with_cli_overrides(
-        &codex_home,
-        &config_cwd,
-        cli_kv_overrides.clone(),
+    let config_toml = match startup_profile::measure(
+        StartupPhase::ConfigLoad,
+        load_config_as_toml_with_cli_overrides(&codex_home, &config_cwd, cli_kv_overrides.clone()),
     )
     .await
     {
@@ -219,20 +230,35 @@ pub async fn run_main(
         tracing::warn!(error = %err, "failed to run personality migration");
     }
 
-    let cloud_auth_manager = AuthManager::shared(
-        codex_home.to_path_buf(),
-        false,
-        config_toml.cli_auth_credentials_store.unwrap_or_default(),
-    );
-    let chatgpt_base_url = config_toml
-        .chatgpt_base_url
-        .clone()
-        .unwrap_or_else(|| "https://chatgpt.com/backend-api/".to_string());
-    let cloud_requirements = cloud_requirements_loader(
-        cloud_auth_manager,
-        chatgpt_base_url,
-        codex_home.to_path_buf(),
-    );
+    // The selected profile may use another account, so cloud requirements are fetched with the
+    // credentials of the resolved config.
+    let auth_started = Instant::now();
+    let cloud_requirements = match ConfigBuilder::default()
+        .codex_home(codex_home.clone())
+        .cli_overrides(cli_kv_overrides.clone())
+        .harness_overrides(ConfigOverrides {
+            config_profile: cli.config_profile.clone(),
+            cwd: cwd.clone(),
+            ..Default::default()
+        })
+        .build()
+        .await
+    {
+        Ok(config) => cloud_requirements_loader(
+            AuthManager::shared(
+                config.auth_home(),
+                false,
+                config.cli_auth_credentials_store_mode,
+            ),
+            config.chatgpt_base_url,
+            config.codex_home,
+        ),
+        Err(err) => {
+            tracing::warn!(error = %err, "failed to preload config for cloud requirements");
+            CloudRequirementsLoader::default()
+        }
+    };
+    startup_profile::record(StartupPhase::CloudAuth, auth_started);
 
     let model_provider_override = if cli.oss {
         let resolved = resolve_oss_provider(
@@ -279,13 +305,14 @@ pub async fn run_main(
         cwd,
         model_provider: model_provider_override.clone(),
         config_profile: cli.config_profile.clone(),
+        model_preset: cli.model_preset.clone(),
         codex_linux_sandbox_exe,
         show_raw_agent_reasoning: cli.oss.then_some(true),
         additional_writable_roots: additional_dirs,
         ..Default::default()
     };
 
-    let config = load_config_or_exit(
+    let mut config = load_config_or_exit(
         cli_kv_overrides.clone(),
         overrides.clone(),
         cloud_requirements.clone(),
@@ -338,7 +365,12 @@ pub async fn run_main(
         log_file_opts.mode(0o600);
     }
 
-    let log_file = log_file_opts.open(log_dir.join("codex-tui.log"))?;
+    let log_file = log_file_opts.open(
+        config
+            .log_file
+            .clone()
+            .unwrap_or_else(|| log_dir.join("codex-tui.log")),
+    )?;
 
     // Wrap file in non‑blocking writer.
     let (non_blocking, _guard) = non_blocking(log_file);
@@ -350,18 +382,27 @@ pub async fn run_main(
         })
     };
 
-    let file_layer = tracing_subscriber::fmt::layer()
-        .with_writer(non_blocking)
-        // `with_target(true)` is the default, but we previously disabled it for file output.
-        // Keep it enabled so we can selectively enable targets via `RUST_LOG=...` and then
-        // grep for a specific module/target while troubleshooting.
-        .with_target(true)
-        .with_ansi(false)
-        .with_span_events(
-            tracing_subscriber::fmt::format::FmtSpan::NEW
-                | tracing_subscriber::fmt::format::FmtSpan::CLOSE,
-        )
-        .with_filter(env_filter());
+    let (file_layer, json_file_layer) = match config.log_format {
+        LogFormat::Text => {
+            let file_layer = tracing_subscriber::fmt::layer()
+                .with_writer(non_blocking)
+                // `with_target(true)` is the default, but we previously disabled it for file
+                // output. Keep it enabled so we can selectively enable targets via `RUST_LOG=...`
+                // and then grep for a specific module/target while troubleshooting.
+                .with_target(true)
+                .with_ansi(false)
+                .with_span_events(
+                    tracing_subscriber::fmt::format::FmtSpan::NEW
+                        | tracing_subscriber::fmt::format::FmtSpan::CLOSE,
+                )
+                .with_filter(env_filter());
+            (Some(file_layer), None)
+        }
+        LogFormat::Json => (
+            None,
+            Some(JsonLogLayer::new(non_blocking).with_filter(env_filter())),
+        ),
+    };
 
     let feedback = codex_feedback::CodexFeedback::new();
     let feedback_layer = feedback.logger_layer();
@@ -379,7 +420,9 @@ pub async fn run_main(
                 ));
             }
         };
-        ensure_oss_provider_ready(provider_id, &config).await?;
+        ensure_oss_provider_ready(provider_id, &mut config).await?;
+        // Keep the probed model capabilities when onboarding or resume reloads the config.
+        cli_kv_overrides.extend(model_capability_overrides(&config));
     }
 
     let otel = match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
@@ -412,6 +455,7 @@ pub async fn run_main(
 
     let _ = tracing_subscriber::registry()
         .with(file_layer)
+        .with(json_file_layer)
         .with(feedback_layer)
         .with(feedback_metadata_layer)
         .with(log_db_layer)
@@ -419,7 +463,7 @@ pub async fn run_main(
         .with(otel_tracing_layer)
         .try_init();
 
-    run_ratatui_app(
+    let exit_info = run_ratatui_app(
         cli,
         config,
         overrides,
@@ -428,7 +472,17 @@ pub async fn run_main(
         feedback,
     )
     .await
-    .map_err(|err| std::io::Error::other(err.to_string()))
+    .map_err(|err| std::io::Error::other(err.to_string()));
+
+    // The terminal has been restored by now, so the report lands in the normal scrollback.
+    if let Some(report) = startup_profile::report() {
+        tracing::info!("startup profile:\n{report}");
+        #[allow(clippy::print_stderr)]
+        {
+            eprintln!("{report}");
+        }
+    }
+    exit_info
 }
 
 async fn run_ratatui_app(
@@ -443,6 +497,39 @@ async fn run_ratatui_app(
 
     tooltips::announcement::prewarm();
 
+    // Credentials and the `--last` session lookup only need the config, so load them while the
+    // terminal is set up and the update prompt is shown.
+    let auth_task = {
+        let config = initial_config.clone();
+        tokio::task::spawn_blocking(move || {
+            let started = Instant::now();
+            let auth_manager = AuthManager::shared(
+                config.auth_home(),
+                false,
+                config.cli_auth_credentials_store_mode,
+            );
+            let login_status = get_login_status(&config);
+            startup_profile::record(StartupPhase::Auth, started);
+            (auth_manager, login_status)
+        })
+    };
+    let use_fork = cli.fork_picker || cli.fork_last || cli.fork_session_id.is_some();
+    let last_session_scan = if (use_fork && cli.fork_last && cli.fork_session_id.is_none())
+        || (!use_fork && cli.resume_session_id.is_none() && cli.resume_last)
+    {
+        let config = initial_config.clone();
+        let filter_cwd = !use_fork && !cli.resume_show_all;
+        Some(tokio::spawn(async move {
+            startup_profile::measure(
+                StartupPhase::RolloutScan,
+                find_last_session(&config, use_fork, filter_cwd),
+            )
+            .await
+        }))
+    } else {
+        None
+    };
+
     // Forward panic reports through tracing so they appear in the UI status
     // line, but do not swallow the default/color-eyre panic handler.
     // Chain to the previous hook so users still get a rich panic report
@@ -482,16 +569,12 @@ async fn run_ratatui_app(
     // Initialize high-fidelity session event logging if enabled.
     session_log::maybe_init(&initial_config);
 
-    let auth_manager = AuthManager::shared(
-        initial_config.codex_home.clone(),
-        false,
-        initial_config.cli_auth_credentials_store_mode,
-    );
-    let login_status = get_login_status(&initial_config);
+    let (auth_manager, login_status) = auth_task.await?;
     let should_show_trust_screen_flag = should_show_trust_screen(&initial_config);
     let should_show_onboarding =
         should_show_onboarding(login_status, &initial_config, should_show_trust_screen_flag);
     let mut trust_decision_was_made = false;
+    let mut config_reloaded = false;
 
     let config = if should_show_onboarding {
         let show_login_screen = should_show_login_screen(login_status, &initial_config);
@@ -533,6 +616,7 @@ async fn run_ratatui_app(
         // If the user made an explicit trust decision, or we showed the login flow, reload config
         // so current process state reflects persisted trust/auth changes.
         if onboarding_result.directory_trust_decision.is_some() || show_login_screen {
+            config_reloaded = true;
             load_config_or_exit(
                 cli_kv_overrides.clone(),
                 overrides.clone(),
@@ -561,7 +645,19 @@ async fn run_ratatui_app(
         })
     };
 
-    let use_fork = cli.fork_picker || cli.fork_last || cli.fork_session_id.is_some();
+    // A reloaded config may pick a different provider, so the early lookup is only used when the
+    // config is unchanged.
+    let last_session_path = match last_session_scan {
+        Some(scan) if !config_reloaded => scan.await?,
+        Some(_) => {
+            startup_profile::measure(
+                StartupPhase::RolloutScan,
+                find_last_session(&config, use_fork, !use_fork && !cli.resume_show_all),
+            )
+            .await
+        }
+        None => None,
+    };
     let session_selection = if use_fork {
         if let Some(id_str) = cli.fork_session_id.as_deref() {
             let is_uuid = Uuid::parse_str(id_str).is_ok();
@@ -575,25 +671,9 @@ async fn run_ratatui_app(
                 None => return missing_session_exit(id_str, "fork"),
             }
         } else if cli.fork_last {
-            let provider_filter = vec![config.model_provider_id.clone()];
-            match RolloutRecorder::list_threads(
-                &config,
-                1,
-                None,
-                ThreadSortKey::UpdatedAt,
-                INTERACTIVE_SESSION_SOURCES,
-                Some(provider_filter.as_slice()),
-                &config.model_provider_id,
-            )
-            .await
-            {
-                Ok(page) => page
-                    .items
-                    .first()
-                    .map(|it| resume_picker::SessionSelection::Fork(it.path.clone()))
-                    .unwrap_or(resume_picker::SessionSelection::StartFresh),
-                Err(_) => resume_picker::SessionSelection::StartFresh,
-            }
+            last_session_path
+                .map(resume_picker::SessionSelection::Fork)
+                .unwrap_or(resume_picker::SessionSelection::StartFresh)
         } else if cli.fork_picker {
             match resume_picker::run_fork_picker(&mut tui, &config, cli.fork_show_all).await? {
                 resume_picker::SessionSelection::Exit => {
@@ -624,27 +704,9 @@ async fn run_ratatui_app(
             None => return missing_session_exit(id_str, "resume"),
         }
     } else if cli.resume_last {
-        let provider_filter = vec![config.model_provider_id.clone()];
-        let filter_cwd = if cli.resume_show_all {
-            None
-        } else {
-            Some(config.cwd.as_path())
-        };
-        match RolloutRecorder::find_latest_thread_path(
-            &config,
-            1,
-            None,
-            ThreadSortKey::UpdatedAt,
-            INTERACTIVE_SESSION_SOURCES,
-            Some(provider_filter.as_slice()),
-            &config.model_provider_id,
-            filter_cwd,
-        )
-        .await
-        {
-            Ok(Some(path)) => resume_picker::SessionSelection::Resume(path),
-            _ => resume_picker::SessionSelection::StartFresh,
-        }
+        last_session_path
+            .map(resume_picker::SessionSelection::Resume)
+            .unwrap_or(resume_picker::SessionSelection::StartFresh)
     } else if cli.resume_picker {
         match resume_picker::run_resume_picker(&mut tui, &config, cli.resume_show_all).await? {
             resume_picker::SessionSelection::Exit => {
@@ -745,6 +807,43 @@ async fn run_ratatui_app(
     app_result
 }
 
+/// The most recently updated interactive session for the configured provider: the one `--fork
+/// --last` forks, or, with `filter_cwd`, the one in this directory that `--resume --last`
+/// resumes.
+async fn find_last_session(config: &Config, fork: bool, filter_cwd: bool) -> Option<PathBuf> {
+    let provider_filter = vec![config.model_provider_id.clone()];
+    if fork {
+        return RolloutRecorder::list_threads(
+            config,
+            1,
+            None,
+            ThreadSortKey::UpdatedAt,
+            INTERACTIVE_SESSION_SOURCES,
+            Some(provider_filter.as_slice()),
+            &config.model_provider_id,
+        )
+        .await
+        .ok()?
+        .items
+        .into_iter()
+        .next()
+        .map(|item| item.path);
+    }
+    RolloutRecorder::find_latest_thread_path(
+        config,
+        1,
+        None,
+        ThreadSortKey::UpdatedAt,
+        INTERACTIVE_SESSION_SOURCES,
+        Some(provider_filter.as_slice()),
+        &config.model_provider_id,
+        filter_cwd.then_some(config.cwd.as_path()),
+    )
+    .await
+    .ok()
+    .flatten()
+}
+
 pub(crate) async fn read_session_cwd(path: &Path) -> Option<PathBuf> {
     // Prefer the latest TurnContext cwd so resume/fork reflects the most recent
     // session directory (for the changed-cwd prompt). The alternative would be
@@ -769,7 +868,7 @@ pub(crate) async fn read_session_cwd(path: &Path) -> Option<PathBuf> {
 }
 
 async fn parse_latest_turn_context_cwd(path: &Path) -> Option<PathBuf> {
-    let text = tokio::fs::read_to_string(path).await.ok()?;
+    let text = codex_core::read_rollout_text(path).await.ok()?;
     for line in text.lines().rev() {
         let trimmed = line.trim();
         if trimmed.is_empty() {
@@ -908,15 +1007,18 @@ async fn load_config_or_exit_with_fallback_cwd(
     cloud_requirements: CloudRequirementsLoader,
     fallback_cwd: Option<PathBuf>,
 ) -> Config {
+    let config = startup_profile::measure(
+        StartupPhase::ConfigLoad,
+        ConfigBuilder::default()
+            .cli_overrides(cli_kv_overrides)
+            .harness_overrides(overrides)
+            .cloud_requirements(cloud_requirements)
+            .fallback_cwd(fallback_cwd)
+            .build(),
+    )
+    .await;
     #[allow(clippy::print_stderr)]
-    match ConfigBuilder::default()
-        .cli_overrides(cli_kv_overrides)
-        .harness_overrides(overrides)
-        .cloud_requirements(cloud_requirements)
-        .fallback_cwd(fallback_cwd)
-        .build()
-        .await
-    {
+    match config {
         Ok(config) => config,
         Err(err) => {
             eprintln!("Error loading configuration: {err}");
@@ -929,12 +1031,16 @@ async fn load_config_or_exit_with_fallback_cwd(
 /// or if the current cwd project is already trusted. If not, we need to
 /// show the trust screen.
 fn should_show_trust_screen(config: &Config) -> bool {
-    if config.did_user_set_custom_approval_policy_or_sandbox_mode {
-        // Respect explicit approval/sandbox overrides made by the user.
+    if config.active_project.trust_level.is_some() {
         return false;
     }
-    // otherwise, show only if no trust decision has been made
-    config.active_project.trust_level.is_none()
+    // The project's own config.toml is only applied once it is trusted, so ask even when the
+    // user set their own approval/sandbox policy.
+    if has_untrusted_project_config(config) {
+        return true;
+    }
+    // Otherwise respect explicit approval/sandbox overrides made by the user.
+    !config.did_user_set_custom_approval_policy_or_sandbox_mode
 }
 
 fn should_show_onboarding(
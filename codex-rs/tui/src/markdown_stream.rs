@@ -1,12 +1,29 @@
+//! Incremental markdown rendering for streamed assistant output.
+//!
+//! Re-rendering the whole message for every committed line makes long responses quadratic, so
+//! the collector only keeps the unstable tail of the message. Once a top-level block is followed
+//! by a blank line and the start of another block that cannot continue it, everything before that
+//! block is final: its lines have been committed and the source is dropped. Later commits render
+//! only the remaining tail.
+
 use ratatui::text::Line;
 
 use crate::markdown;
 
+/// Stand-in for the blocks already dropped from the buffer. Rendering it ahead of the tail
+/// reproduces the separator the full message would have between the two, and it always renders
+/// as exactly one line, which is then discarded.
+const DROPPED_BLOCKS_STAND_IN: &str = "x\n\n";
+
 /// Newline-gated accumulator that renders markdown and commits only fully
 /// completed logical lines.
 pub(crate) struct MarkdownStreamCollector {
+    /// Source that has not been finalized yet; finalized blocks are dropped from the front.
     buffer: String,
+    /// Lines of `buffer`'s render that have been committed.
     committed_line_count: usize,
+    /// Whether blocks were dropped from the front of `buffer`.
+    has_dropped_blocks: bool,
     width: Option<usize>,
 }
 
@@ -15,6 +32,7 @@ impl MarkdownStreamCollector {
         Self {
             buffer: String::new(),
             committed_line_count: 0,
+            has_dropped_blocks: false,
             width,
         }
     }
@@ -22,6 +40,7 @@ impl MarkdownStreamCollector {
     pub fn clear(&mut self) {
         self.buffer.clear();
         self.committed_line_count = 0;
+        self.has_dropped_blocks = false;
     }
 
     pub fn push_delta(&mut self, delta: &str) {
@@ -29,19 +48,15 @@ impl MarkdownStreamCollector {
         self.buffer.push_str(delta);
     }
 
-    /// Render the full buffer and return only the newly completed logical lines
-    /// since the last commit. When the buffer does not end with a newline, the
-    /// final rendered line is considered incomplete and is not emitted.
+    /// Render the unfinished part of the message and return only the newly
+    /// completed logical lines since the last commit. When the buffer does not
+    /// end with a newline, the final rendered line is considered incomplete and
+    /// is not emitted.
     pub fn commit_complete_lines(&mut self) -> Vec<Line<'static>> {
-        let source = self.buffer.clone();
-        let last_newline_idx = source.rfind('\n');
-        let source = if let Some(last_newline_idx) = last_newline_idx {
-            source[..=last_newline_idx].to_string()
-        } else {
+        let Some(last_newline_idx) = self.buffer.rfind('\n') else {
             return Vec::new();
         };
-        let mut rendered: Vec<Line<'static>> = Vec::new();
-        markdown::append_markdown(&source, self.width, &mut rendered);
+        let rendered = self.render(&self.buffer[..=last_newline_idx]);
         let mut complete_line_count = rendered.len();
         if complete_line_count > 0
             && crate::render::line_utils::is_blank_line_spaces_only(
@@ -55,35 +70,31 @@ impl MarkdownStreamCollector {
             return Vec::new();
         }
 
-        let out_slice = &rendered[self.committed_line_count..complete_line_count];
-
-        let out = out_slice.to_vec();
+        let out = rendered[self.committed_line_count..complete_line_count].to_vec();
         self.committed_line_count = complete_line_count;
+        self.drop_finished_blocks(last_newline_idx + 1);
         out
     }
 
     /// Finalize the stream: emit all remaining lines beyond the last commit.
     /// If the buffer does not end with a newline, a temporary one is appended
-    /// for rendering. Optionally unwraps ```markdown language fences in
-    /// non-test builds.
+    /// for rendering.
     pub fn finalize_and_drain(&mut self) -> Vec<Line<'static>> {
-        let raw_buffer = self.buffer.clone();
-        let mut source: String = raw_buffer.clone();
+        let mut source = std::mem::take(&mut self.buffer);
+        let raw_len = source.len();
         if !source.ends_with('\n') {
             source.push('\n');
         }
         tracing::debug!(
-            raw_len = raw_buffer.len(),
+            raw_len,
             source_len = source.len(),
             "markdown finalize (raw length: {}, rendered length: {})",
-            raw_buffer.len(),
+            raw_len,
             source.len()
         );
         tracing::trace!("markdown finalize (raw source):\n---\n{source}\n---");
 
-        let mut rendered: Vec<Line<'static>> = Vec::new();
-        markdown::append_markdown(&source, self.width, &mut rendered);
-
+        let rendered = self.render(&source);
         let out = if self.committed_line_count >= rendered.len() {
             Vec::new()
         } else {
@@ -94,6 +105,106 @@ impl MarkdownStreamCollector {
         self.clear();
         out
     }
+
+    /// Renders `source`, which continues the blocks dropped so far.
+    fn render(&self, source: &str) -> Vec<Line<'static>> {
+        let mut rendered: Vec<Line<'static>> = Vec::new();
+        if self.has_dropped_blocks {
+            markdown::append_markdown(
+                &format!("{DROPPED_BLOCKS_STAND_IN}{source}"),
+                self.width,
+                &mut rendered,
+            );
+            rendered.remove(0);
+        } else {
+            markdown::append_markdown(source, self.width, &mut rendered);
+        }
+        rendered
+    }
+
+    /// Drops the blocks in `buffer[..complete_len]` that nothing streamed later can change.
+    fn drop_finished_blocks(&mut self, complete_len: usize) {
+        let Some(boundary) = last_block_boundary(&self.buffer[..complete_len]) else {
+            return;
+        };
+        let finished_line_count = self.render(&self.buffer[..boundary]).len();
+        // Only drop blocks whose lines have all been committed.
+        if finished_line_count > self.committed_line_count {
+            return;
+        }
+        self.buffer.drain(..boundary);
+        self.committed_line_count -= finished_line_count;
+        self.has_dropped_blocks = true;
+    }
+}
+
+/// Byte offset of the last line in `source` that starts a top-level block independent of
+/// everything before it, i.e. the blocks before it render the same whatever follows.
+///
+/// That holds for a complete line starting in the first column after a blank line outside a
+/// fenced code block, unless it is a list item, which could continue (and renumber) a list.
+/// Indented lines could continue a list item or an indented code block.
+fn last_block_boundary(source: &str) -> Option<usize> {
+    let mut boundary = None;
+    let mut open_fence: Option<(char, usize)> = None;
+    let mut seen_content = false;
+    let mut after_blank = false;
+    let mut offset = 0;
+    for line in source.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        if !line.ends_with('\n') {
+            break;
+        }
+        let text = line.trim_end();
+        if text.is_empty() {
+            after_blank = open_fence.is_none();
+            continue;
+        }
+        if after_blank
+            && seen_content
+            && !text.starts_with(char::is_whitespace)
+            && !is_list_item(text)
+        {
+            boundary = Some(start);
+        }
+        after_blank = false;
+        seen_content = true;
+        open_fence = match (open_fence, fence(text)) {
+            (None, Some((marker, len, _))) => Some((marker, len)),
+            (Some((marker, len)), Some((close, close_len, info)))
+                if close == marker && close_len >= len && info.is_empty() =>
+            {
+                None
+            }
+            (open, _) => open,
+        };
+    }
+    boundary
+}
+
+/// The marker character, marker length and info string of a code fence line.
+fn fence(line: &str) -> Option<(char, usize, &str)> {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = trimmed.chars().take_while(|c| *c == marker).count();
+    (len >= 3).then(|| (marker, len, trimmed[len..].trim()))
+}
+
+fn is_list_item(line: &str) -> bool {
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    let rest = match line.as_bytes().first() {
+        Some(b'-' | b'*' | b'+') => &line[1..],
+        Some(b'0'..=b'9') if digits <= 9 => match line[digits..].as_bytes().first() {
+            Some(b'.' | b')') => &line[digits + 1..],
+            _ => return false,
+        },
+        _ => return false,
+    };
+    rest.is_empty() || rest.starts_with([' ', '\t'])
 }
 
 #[cfg(test)]
@@ -667,4 +778,33 @@ mod tests {
         ])
         .await;
     }
+
+    #[tokio::test]
+    async fn finished_blocks_are_dropped_from_the_buffer() {
+        let mut c = super::MarkdownStreamCollector::new(None);
+        c.push_delta("First paragraph.\n\n```\ncode\n\nmore code\n");
+        c.commit_complete_lines();
+        // The blank line inside the open fence is not a block boundary.
+        assert_eq!(c.buffer, "```\ncode\n\nmore code\n");
+
+        c.push_delta("```\n\n- item\n\nClosing paragraph.\n");
+        c.commit_complete_lines();
+        assert_eq!(c.buffer, "Closing paragraph.\n");
+    }
+
+    #[tokio::test]
+    async fn streaming_across_dropped_blocks_matches_full() {
+        assert_streamed_equals_full(&[
+            "# Title\n\n",
+            "Intro paragraph with `code`.\n\n",
+            "> quoted\n\n",
+            "```rust\nfn main() {}\n\n}\n```\n\n",
+            "1. one\n\n",
+            "2. two\n\n",
+            "---\n\n",
+            "    indented code\n\n",
+            "Trailing paragraph",
+        ])
+        .await;
+    }
 }
@@ -0,0 +1,230 @@
+//! Forms for MCP elicitation requests. Servers describe the input they want as a flat object
+//! schema; each property becomes a field the user fills in on its own prompt.
+
+use codex_protocol::approvals::ElicitationRequestEvent;
+use codex_protocol::mcp::RequestId;
+use serde_json::Value;
+
+/// An elicitation request whose schema asks for at least one field.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ElicitationForm {
+    pub server_name: String,
+    pub request_id: RequestId,
+    pub message: String,
+    pub fields: Vec<ElicitationField>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ElicitationField {
+    pub name: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub required: bool,
+    pub kind: FieldKind,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum FieldKind {
+    Text,
+    Number,
+    Integer,
+    Boolean,
+    /// Allowed values, each with the name shown for it.
+    Choice(Vec<(String, String)>),
+}
+
+impl ElicitationForm {
+    /// Returns `None` when the request asks for no fields, so confirming it is enough.
+    pub(crate) fn from_request(ev: &ElicitationRequestEvent) -> Option<Self> {
+        let schema = ev.requested_schema.as_ref()?;
+        let required = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|names| names.iter().filter_map(Value::as_str).collect::<Vec<_>>())
+            .unwrap_or_default();
+        let fields = schema
+            .get("properties")
+            .and_then(Value::as_object)?
+            .iter()
+            .map(|(name, property)| ElicitationField {
+                name: name.clone(),
+                title: string_field(property, "title"),
+                description: string_field(property, "description"),
+                required: required.contains(&name.as_str()),
+                kind: FieldKind::from_property(property),
+            })
+            .collect::<Vec<_>>();
+        if fields.is_empty() {
+            return None;
+        }
+        Some(Self {
+            server_name: ev.server_name.clone(),
+            request_id: ev.id.clone(),
+            message: ev.message.clone(),
+            fields,
+        })
+    }
+}
+
+impl ElicitationField {
+    pub(crate) fn label(&self) -> &str {
+        self.title.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Converts what the user typed into the field's value. Only text, number, and integer
+    /// fields are typed; the others are picked from a list.
+    pub(crate) fn parse(&self, input: &str) -> Result<Value, String> {
+        match &self.kind {
+            FieldKind::Number => input
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(Value::Number)
+                .ok_or_else(|| format!("{} must be a number.", self.label())),
+            FieldKind::Integer => input
+                .parse::<i64>()
+                .map(Value::from)
+                .map_err(|_| format!("{} must be a whole number.", self.label())),
+            FieldKind::Text | FieldKind::Boolean | FieldKind::Choice(_) => {
+                Ok(Value::String(input.to_string()))
+            }
+        }
+    }
+}
+
+impl FieldKind {
+    fn from_property(property: &Value) -> Self {
+        if let Some(values) = property.get("enum").and_then(Value::as_array) {
+            let names = property.get("enumNames").and_then(Value::as_array);
+            let choices = values
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, value)| {
+                    let value = value.as_str()?.to_string();
+                    let name = names
+                        .and_then(|names| names.get(idx))
+                        .and_then(Value::as_str)
+                        .map_or_else(|| value.clone(), str::to_string);
+                    Some((value, name))
+                })
+                .collect();
+            return Self::Choice(choices);
+        }
+        if let Some(options) = property.get("oneOf").and_then(Value::as_array) {
+            let choices = options
+                .iter()
+                .filter_map(|option| {
+                    let value = option.get("const")?.as_str()?.to_string();
+                    let name = string_field(option, "title").unwrap_or_else(|| value.clone());
+                    Some((value, name))
+                })
+                .collect();
+            return Self::Choice(choices);
+        }
+        match property.get("type").and_then(Value::as_str) {
+            Some("number") => Self::Number,
+            Some("integer") => Self::Integer,
+            Some("boolean") => Self::Boolean,
+            _ => Self::Text,
+        }
+    }
+}
+
+fn string_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn request(requested_schema: Option<Value>) -> ElicitationRequestEvent {
+        ElicitationRequestEvent {
+            server_name: "deploy".to_string(),
+            id: RequestId::Integer(7),
+            message: "Where should this go?".to_string(),
+            requested_schema,
+        }
+    }
+
+    #[test]
+    fn schema_properties_become_fields() {
+        let form = ElicitationForm::from_request(&request(Some(json!({
+            "type": "object",
+            "properties": {
+                "env": {
+                    "type": "string",
+                    "title": "Environment",
+                    "enum": ["prod", "staging"],
+                    "enumNames": ["Production", "Staging"],
+                },
+                "replicas": { "type": "integer", "description": "How many to run" },
+                "notify": { "type": "boolean" },
+            },
+            "required": ["env"],
+        }))))
+        .expect("form with fields");
+
+        assert_eq!(
+            form.fields,
+            vec![
+                ElicitationField {
+                    name: "env".to_string(),
+                    title: Some("Environment".to_string()),
+                    description: None,
+                    required: true,
+                    kind: FieldKind::Choice(vec![
+                        ("prod".to_string(), "Production".to_string()),
+                        ("staging".to_string(), "Staging".to_string()),
+                    ]),
+                },
+                ElicitationField {
+                    name: "replicas".to_string(),
+                    title: None,
+                    description: Some("How many to run".to_string()),
+                    required: false,
+                    kind: FieldKind::Integer,
+                },
+                ElicitationField {
+                    name: "notify".to_string(),
+                    title: None,
+                    description: None,
+                    required: false,
+                    kind: FieldKind::Boolean,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn requests_without_fields_have_no_form() {
+        assert_eq!(ElicitationForm::from_request(&request(None)), None);
+        assert_eq!(
+            ElicitationForm::from_request(&request(Some(
+                json!({ "type": "object", "properties": {} })
+            ))),
+            None
+        );
+    }
+
+    #[test]
+    fn typed_values_are_checked_against_the_field_type() {
+        let field = |kind| ElicitationField {
+            name: "count".to_string(),
+            title: None,
+            description: None,
+            required: true,
+            kind,
+        };
+
+        assert_eq!(field(FieldKind::Integer).parse("3"), Ok(json!(3)));
+        assert_eq!(
+            field(FieldKind::Integer).parse("3.5"),
+            Err("count must be a whole number.".to_string())
+        );
+        assert_eq!(field(FieldKind::Number).parse("3.5"), Ok(json!(3.5)));
+        assert_eq!(field(FieldKind::Text).parse("three"), Ok(json!("three")));
+    }
+}
@@ -9,6 +9,8 @@ use codex_core::protocol::CollabResumeBeginEvent;
 use codex_core::protocol::CollabResumeEndEvent;
 use codex_core::protocol::CollabWaitingBeginEvent;
 use codex_core::protocol::CollabWaitingEndEvent;
+use codex_core::protocol::DelegateBeginEvent;
+use codex_core::protocol::DelegateEndEvent;
 use codex_protocol::ThreadId;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
@@ -127,6 +129,38 @@ pub(crate) fn resume_end(ev: CollabResumeEndEvent) -> PlainHistoryCell {
     collab_event("Agent resumed", details)
 }
 
+pub(crate) fn delegate_begin(ev: DelegateBeginEvent) -> PlainHistoryCell {
+    let DelegateBeginEvent { call_id, task } = ev;
+    let mut details = vec![detail_line("call", call_id)];
+    if let Some(line) = prompt_line(&task) {
+        details.push(line);
+    }
+    collab_event("Delegating task", details)
+}
+
+pub(crate) fn delegate_end(ev: DelegateEndEvent) -> PlainHistoryCell {
+    let DelegateEndEvent {
+        call_id,
+        output,
+        error,
+    } = ev;
+    let mut details = vec![detail_line("call", call_id)];
+    if let Some(error) = error {
+        let error_preview = truncate_text(
+            &error.split_whitespace().collect::<Vec<_>>().join(" "),
+            COLLAB_AGENT_ERROR_PREVIEW_GRAPHEMES,
+        );
+        details.push(detail_line("error", Span::from(error_preview).red()));
+    } else if let Some(output) = output {
+        let output_preview = truncate_text(
+            &output.split_whitespace().collect::<Vec<_>>().join(" "),
+            COLLAB_AGENT_RESPONSE_PREVIEW_GRAPHEMES,
+        );
+        details.push(detail_line("result", output_preview));
+    }
+    collab_event("Delegate finished", details)
+}
+
 fn collab_event(title: impl Into<String>, details: Vec<Line<'static>>) -> PlainHistoryCell {
     let title = title.into();
     let mut lines: Vec<Line<'static>> =
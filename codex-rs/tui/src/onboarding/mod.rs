@@ -2,4 +2,5 @@ mod auth;
 pub mod onboarding_screen;
 mod trust_directory;
 pub use trust_directory::TrustDirectorySelection;
+pub(crate) use trust_directory::has_untrusted_project_config;
 mod welcome;
@@ -22,6 +22,7 @@ use crate::onboarding::auth::SignInOption;
 use crate::onboarding::auth::SignInState;
 use crate::onboarding::trust_directory::TrustDirectorySelection;
 use crate::onboarding::trust_directory::TrustDirectoryWidget;
+use crate::onboarding::trust_directory::has_untrusted_project_config;
 use crate::onboarding::welcome::WelcomeWidget;
 use crate::tui::FrameRequester;
 use crate::tui::Tui;
@@ -103,7 +104,7 @@ impl OnboardingScreen {
                 highlighted_mode,
                 error: None,
                 sign_in_state: Arc::new(RwLock::new(SignInState::PickMode)),
-                codex_home: codex_home.clone(),
+                codex_home: config.auth_home(),
                 cli_auth_credentials_store_mode,
                 login_status,
                 auth_manager,
@@ -123,6 +124,7 @@ impl OnboardingScreen {
                 cwd,
                 codex_home,
                 show_windows_create_sandbox_hint,
+                has_project_config: has_untrusted_project_config(&config),
                 should_quit: false,
                 selection: None,
                 highlighted,
@@ -1,6 +1,9 @@
 use std::path::PathBuf;
 
+use codex_app_server_protocol::ConfigLayerSource;
+use codex_core::config::Config;
 use codex_core::config::set_project_trust_level;
+use codex_core::config_loader::ConfigLayerStackOrdering;
 use codex_core::git_info::resolve_root_git_project_for_trust;
 use codex_protocol::config_types::TrustLevel;
 use crossterm::event::KeyCode;
@@ -28,6 +31,8 @@ pub(crate) struct TrustDirectoryWidget {
     pub codex_home: PathBuf,
     pub cwd: PathBuf,
     pub show_windows_create_sandbox_hint: bool,
+    /// The directory has a `.codex/config.toml` that is only applied once it is trusted.
+    pub has_project_config: bool,
     pub should_quit: bool,
     pub selection: Option<TrustDirectorySelection>,
     pub highlighted: TrustDirectorySelection,
@@ -37,9 +42,20 @@ pub(crate) struct TrustDirectoryWidget {
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TrustDirectorySelection {
     Trust,
+    /// Continue without applying the directory's own config.
+    DontTrust,
     Quit,
 }
 
+/// Whether the cwd has project config that is ignored because the project is not trusted.
+pub(crate) fn has_untrusted_project_config(config: &Config) -> bool {
+    config
+        .config_layer_stack
+        .get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, true)
+        .into_iter()
+        .any(|layer| matches!(layer.name, ConfigLayerSource::Project { .. }) && layer.is_disabled())
+}
+
 impl WidgetRef for &TrustDirectoryWidget {
     fn render_ref(&self, area: Rect, buf: &mut Buffer) {
         let mut column = ColumnRenderable::new();
@@ -60,12 +76,18 @@ impl WidgetRef for &TrustDirectoryWidget {
         );
         column.push("");
 
-        let options: Vec<(&str, TrustDirectorySelection)> = vec![
-            ("Yes, continue", TrustDirectorySelection::Trust),
-            ("No, quit", TrustDirectorySelection::Quit),
-        ];
+        if self.has_project_config {
+            column.push(
+                Paragraph::new(
+                    "This directory has its own Codex settings in .codex/config.toml. They are only applied if you trust it.".to_string(),
+                )
+                .wrap(Wrap { trim: true })
+                .inset(Insets::tlbr(0, 2, 0, 0)),
+            );
+            column.push("");
+        }
 
-        for (idx, (text, selection)) in options.iter().enumerate() {
+        for (idx, (text, selection)) in self.options().iter().enumerate() {
             column.push(selection_option_row(
                 idx,
                 text.to_string(),
@@ -108,19 +130,17 @@ impl KeyboardHandler for TrustDirectoryWidget {
             return;
         }
 
+        let [(_, first), (_, second)] = self.options();
         match key_event.code {
             KeyCode::Up | KeyCode::Char('k') => {
-                self.highlighted = TrustDirectorySelection::Trust;
+                self.highlighted = first;
             }
             KeyCode::Down | KeyCode::Char('j') => {
-                self.highlighted = TrustDirectorySelection::Quit;
+                self.highlighted = second;
             }
-            KeyCode::Char('1') | KeyCode::Char('y') => self.handle_trust(),
-            KeyCode::Char('2') | KeyCode::Char('n') => self.handle_quit(),
-            KeyCode::Enter => match self.highlighted {
-                TrustDirectorySelection::Trust => self.handle_trust(),
-                TrustDirectorySelection::Quit => self.handle_quit(),
-            },
+            KeyCode::Char('1') | KeyCode::Char('y') => self.handle_selection(first),
+            KeyCode::Char('2') | KeyCode::Char('n') => self.handle_selection(second),
+            KeyCode::Enter => self.handle_selection(self.highlighted),
             _ => {}
         }
     }
@@ -137,15 +157,45 @@ impl StepStateProvider for TrustDirectoryWidget {
 }
 
 impl TrustDirectoryWidget {
-    fn handle_trust(&mut self) {
+    fn options(&self) -> [(&'static str, TrustDirectorySelection); 2] {
+        if self.has_project_config {
+            [
+                (
+                    "Yes, trust it and apply its settings",
+                    TrustDirectorySelection::Trust,
+                ),
+                (
+                    "No, continue without its settings",
+                    TrustDirectorySelection::DontTrust,
+                ),
+            ]
+        } else {
+            [
+                ("Yes, continue", TrustDirectorySelection::Trust),
+                ("No, quit", TrustDirectorySelection::Quit),
+            ]
+        }
+    }
+
+    fn handle_selection(&mut self, selection: TrustDirectorySelection) {
+        match selection {
+            TrustDirectorySelection::Trust => self.save_trust_level(TrustLevel::Trusted, selection),
+            TrustDirectorySelection::DontTrust => {
+                self.save_trust_level(TrustLevel::Untrusted, selection)
+            }
+            TrustDirectorySelection::Quit => self.handle_quit(),
+        }
+    }
+
+    fn save_trust_level(&mut self, trust_level: TrustLevel, selection: TrustDirectorySelection) {
         let target =
             resolve_root_git_project_for_trust(&self.cwd).unwrap_or_else(|| self.cwd.clone());
-        if let Err(e) = set_project_trust_level(&self.codex_home, &target, TrustLevel::Trusted) {
-            tracing::error!("Failed to set project trusted: {e:?}");
+        if let Err(e) = set_project_trust_level(&self.codex_home, &target, trust_level) {
+            tracing::error!("Failed to set project {trust_level}: {e:?}");
             self.error = Some(format!("Failed to set trust for {}: {e}", target.display()));
         }
 
-        self.selection = Some(TrustDirectorySelection::Trust);
+        self.selection = Some(selection);
     }
 
     fn handle_quit(&mut self) {
@@ -179,6 +229,7 @@ mod tests {
             codex_home: codex_home.path().to_path_buf(),
             cwd: PathBuf::from("."),
             show_windows_create_sandbox_hint: false,
+            has_project_config: false,
             should_quit: false,
             selection: None,
             highlighted: TrustDirectorySelection::Quit,
@@ -197,6 +248,30 @@ mod tests {
         assert!(widget.should_quit);
     }
 
+    #[test]
+    fn declining_project_config_continues_untrusted() -> anyhow::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cwd = TempDir::new()?;
+        let mut widget = TrustDirectoryWidget {
+            codex_home: codex_home.path().to_path_buf(),
+            cwd: cwd.path().to_path_buf(),
+            show_windows_create_sandbox_hint: false,
+            has_project_config: true,
+            should_quit: false,
+            selection: None,
+            highlighted: TrustDirectorySelection::Trust,
+            error: None,
+        };
+
+        widget.handle_key_event(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+
+        assert!(!widget.should_quit());
+        assert_eq!(widget.selection, Some(TrustDirectorySelection::DontTrust));
+        let config = std::fs::read_to_string(codex_home.path().join("config.toml"))?;
+        assert!(config.contains(r#"trust_level = "untrusted""#), "{config}");
+        Ok(())
+    }
+
     #[test]
     fn renders_snapshot_for_git_repo() {
         let codex_home = TempDir::new().expect("temp home");
@@ -204,6 +279,7 @@ mod tests {
             codex_home: codex_home.path().to_path_buf(),
             cwd: PathBuf::from("/workspace/project"),
             show_windows_create_sandbox_hint: false,
+            has_project_config: false,
             should_quit: false,
             selection: None,
             highlighted: TrustDirectorySelection::Trust,
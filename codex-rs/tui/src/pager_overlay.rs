@@ -14,7 +14,14 @@
 //! `TranscriptOverlay::sync_live_tail` uses the key to decide when the cached tail must be
 //! recomputed. `ChatWidget` is responsible for producing a key that changes when the active cell
 //! mutates in place or when its transcript output is time-dependent.
+//!
+//! Sessions can accumulate thousands of cells, so the pager keeps a [`PagerLayout`] with the
+//! measured height and starting row of every renderable. Frames only draw the renderables that
+//! intersect the viewport, committed cells are appended without touching the ones already laid
+//! out, and highlighting a cell swaps only that cell's renderable. Everything is measured again
+//! only when the width changes or the cells are replaced wholesale.
 
+use std::cell::RefCell;
 use std::io::Result;
 use std::sync::Arc;
 
@@ -131,9 +138,70 @@ fn render_key_hints(area: Rect, buf: &mut Buffer, pairs: &[(&[KeyBinding], &str)
     Paragraph::new(vec![Line::from(spans).dim()]).render_ref(area, buf);
 }
 
+/// Row layout of a pager's renderables at one width.
+///
+/// Measuring a renderable wraps all of its lines, so each one is measured once per width and the
+/// row it starts at is kept alongside. Renderables appended later are measured on the next frame
+/// without revisiting the others.
+#[derive(Debug, Default)]
+struct PagerLayout {
+    width: u16,
+    /// `tops[i]` is the first row of renderable `i`; the final entry is the bottom of the last
+    /// measured renderable. Empty until the first measurement.
+    tops: Vec<usize>,
+}
+
+impl PagerLayout {
+    /// Measures the renderables that are not laid out yet, starting over if `width` changed.
+    fn measure(&mut self, renderables: &[Box<dyn Renderable>], width: u16) {
+        if self.width != width {
+            self.width = width;
+            self.tops.clear();
+        }
+        if self.tops.is_empty() {
+            self.tops.push(0);
+        }
+        let measured = self.tops.len() - 1;
+        let mut bottom = self.total_height();
+        for renderable in renderables.iter().skip(measured) {
+            bottom += renderable.desired_height(width) as usize;
+            self.tops.push(bottom);
+        }
+    }
+
+    /// Forgets the measurements of renderable `idx` and every one after it.
+    fn truncate(&mut self, idx: usize) {
+        self.tops.truncate(idx + 1);
+    }
+
+    fn clear(&mut self) {
+        self.tops.clear();
+    }
+
+    fn total_height(&self) -> usize {
+        self.tops.last().copied().unwrap_or(0)
+    }
+
+    /// First and one-past-last row of renderable `idx`, if it has been measured.
+    fn rows(&self, idx: usize) -> Option<(usize, usize)> {
+        match self.tops.get(idx..idx + 2) {
+            Some([top, bottom]) => Some((*top, *bottom)),
+            _ => None,
+        }
+    }
+
+    /// Index of the renderable that covers `row`.
+    fn index_at(&self, row: usize) -> usize {
+        self.tops.get(1..).map_or(0, |bottoms| {
+            bottoms.partition_point(|bottom| *bottom <= row)
+        })
+    }
+}
+
 /// Generic widget for rendering a pager view.
 struct PagerView {
     renderables: Vec<Box<dyn Renderable>>,
+    layout: RefCell<PagerLayout>,
     scroll_offset: usize,
     title: String,
     last_content_height: Option<usize>,
@@ -146,6 +214,7 @@ impl PagerView {
     fn new(renderables: Vec<Box<dyn Renderable>>, title: String, scroll_offset: usize) -> Self {
         Self {
             renderables,
+            layout: RefCell::new(PagerLayout::default()),
             scroll_offset,
             title,
             last_content_height: None,
@@ -155,10 +224,33 @@ impl PagerView {
     }
 
     fn content_height(&self, width: u16) -> usize {
-        self.renderables
-            .iter()
-            .map(|c| c.desired_height(width) as usize)
-            .sum()
+        let mut layout = self.layout.borrow_mut();
+        layout.measure(&self.renderables, width);
+        layout.total_height()
+    }
+
+    fn set_renderables(&mut self, renderables: Vec<Box<dyn Renderable>>) {
+        self.renderables = renderables;
+        self.layout.get_mut().clear();
+    }
+
+    /// Appends a renderable; it is measured on the next frame.
+    fn push_renderable(&mut self, renderable: Box<dyn Renderable>) {
+        self.renderables.push(renderable);
+    }
+
+    fn pop_renderable(&mut self) -> Option<Box<dyn Renderable>> {
+        let renderable = self.renderables.pop()?;
+        self.layout.get_mut().truncate(self.renderables.len());
+        Some(renderable)
+    }
+
+    /// Swaps renderable `idx` for one of the same height, such as the same cell in another
+    /// style, keeping the layout.
+    fn replace_renderable(&mut self, idx: usize, renderable: Box<dyn Renderable>) {
+        if let Some(slot) = self.renderables.get_mut(idx) {
+            *slot = renderable;
+        }
     }
 
     fn render(&mut self, area: Rect, buf: &mut Buffer) {
@@ -190,26 +282,31 @@ impl PagerView {
         header.dim().render_ref(area, buf);
     }
 
+    /// Draws the renderables that intersect the viewport. Expects the layout to be measured for
+    /// `area.width`, which `render` does first.
     fn render_content(&self, area: Rect, buf: &mut Buffer) {
-        let mut y = -(self.scroll_offset as isize);
+        let layout = self.layout.borrow();
+        let view_bottom = self.scroll_offset.saturating_add(area.height as usize);
         let mut drawn_bottom = area.y;
-        for renderable in &self.renderables {
-            let top = y;
-            let height = renderable.desired_height(area.width) as isize;
-            y += height;
-            let bottom = y;
-            if bottom < area.y as isize {
-                continue;
-            }
-            if top > area.y as isize + area.height as isize {
+        let first = layout.index_at(self.scroll_offset);
+        for (idx, renderable) in self.renderables.iter().enumerate().skip(first) {
+            let Some((top, bottom)) = layout.rows(idx) else {
+                break;
+            };
+            if top >= view_bottom {
                 break;
             }
-            if top < 0 {
-                let drawn = render_offset_content(area, buf, &**renderable, (-top) as u16);
+            if bottom <= self.scroll_offset {
+                continue;
+            }
+            if top < self.scroll_offset {
+                let offset = (self.scroll_offset - top) as u16;
+                let drawn = render_offset_content(area, buf, &**renderable, offset);
                 drawn_bottom = drawn_bottom.max(area.y + drawn);
             } else {
-                let draw_height = (height as u16).min(area.height.saturating_sub(top as u16));
-                let draw_area = Rect::new(area.x, area.y + top as u16, area.width, draw_height);
+                let y = (top - self.scroll_offset) as u16;
+                let draw_height = ((bottom - top) as u16).min(area.height.saturating_sub(y));
+                let draw_area = Rect::new(area.x, area.y + y, area.width, draw_height);
                 renderable.render(draw_area, buf);
                 drawn_bottom = drawn_bottom.max(draw_area.y.saturating_add(draw_area.height));
             }
@@ -351,16 +448,13 @@ impl PagerView {
     }
 
     fn ensure_chunk_visible(&mut self, idx: usize, area: Rect) {
-        if area.height == 0 || idx >= self.renderables.len() {
+        if area.height == 0 {
             return;
         }
-        let first = self
-            .renderables
-            .iter()
-            .take(idx)
-            .map(|r| r.desired_height(area.width) as usize)
-            .sum();
-        let last = first + self.renderables[idx].desired_height(area.width) as usize;
+        self.content_height(area.width);
+        let Some((first, last)) = self.layout.borrow().rows(idx) else {
+            return;
+        };
         let current_top = self.scroll_offset;
         let current_bottom = current_top.saturating_add(area.height.saturating_sub(1) as usize);
         if first < current_top {
@@ -474,39 +568,44 @@ impl TranscriptOverlay {
         cells
             .iter()
             .enumerate()
-            .flat_map(|(i, c)| {
-                let mut v: Vec<Box<dyn Renderable>> = Vec::new();
-                let mut cell_renderable = if c.as_any().is::<UserHistoryCell>() {
-                    Box::new(CachedRenderable::new(CellRenderable {
-                        cell: c.clone(),
-                        style: if highlight_cell == Some(i) {
-                            user_message_style().reversed()
-                        } else {
-                            user_message_style()
-                        },
-                    })) as Box<dyn Renderable>
-                } else {
-                    Box::new(CachedRenderable::new(CellRenderable {
-                        cell: c.clone(),
-                        style: Style::default(),
-                    })) as Box<dyn Renderable>
-                };
-                if !c.is_stream_continuation() && i > 0 {
-                    cell_renderable = Box::new(InsetRenderable::new(
-                        cell_renderable,
-                        Insets::tlbr(1, 0, 0, 0),
-                    ));
-                }
-                v.push(cell_renderable);
-                v
-            })
+            .map(|(i, c)| Self::render_cell(i, c, highlight_cell))
             .collect()
     }
 
+    /// The renderable for committed cell `i`; there is exactly one per cell.
+    fn render_cell(
+        i: usize,
+        c: &Arc<dyn HistoryCell>,
+        highlight_cell: Option<usize>,
+    ) -> Box<dyn Renderable> {
+        let mut cell_renderable = if c.as_any().is::<UserHistoryCell>() {
+            Box::new(CachedRenderable::new(CellRenderable {
+                cell: c.clone(),
+                style: if highlight_cell == Some(i) {
+                    user_message_style().reversed()
+                } else {
+                    user_message_style()
+                },
+            })) as Box<dyn Renderable>
+        } else {
+            Box::new(CachedRenderable::new(CellRenderable {
+                cell: c.clone(),
+                style: Style::default(),
+            })) as Box<dyn Renderable>
+        };
+        if !c.is_stream_continuation() && i > 0 {
+            cell_renderable = Box::new(InsetRenderable::new(
+                cell_renderable,
+                Insets::tlbr(1, 0, 0, 0),
+            ));
+        }
+        cell_renderable
+    }
+
     /// Insert a committed history cell while keeping any cached live tail.
     ///
-    /// The live tail is temporarily removed, the committed cells are rebuilt,
-    /// then the tail is reattached. If the tail previously had no leading
+    /// The live tail is temporarily removed, a renderable for the new cell is
+    /// appended, then the tail is reattached. If the tail previously had no leading
     /// spacing because it was the only renderable, we add the missing inset
     /// when the first committed cell arrives.
     ///
@@ -518,7 +617,10 @@ impl TranscriptOverlay {
         let had_prior_cells = !self.cells.is_empty();
         let tail_renderable = self.take_live_tail_renderable();
         self.cells.push(cell);
-        self.view.renderables = Self::render_cells(&self.cells, self.highlight_cell);
+        if let Some(cell) = self.cells.last() {
+            let renderable = Self::render_cell(self.cells.len() - 1, cell, self.highlight_cell);
+            self.view.push_renderable(renderable);
+        }
         if let Some(tail) = tail_renderable {
             let tail = if !had_prior_cells
                 && self
@@ -532,7 +634,7 @@ impl TranscriptOverlay {
             } else {
                 tail
             };
-            self.view.renderables.push(tail);
+            self.view.push_renderable(tail);
         }
         if follow_bottom {
             self.view.scroll_offset = usize::MAX;
@@ -595,7 +697,7 @@ impl TranscriptOverlay {
         if let Some(key) = next_key {
             let lines = compute_lines(width).unwrap_or_default();
             if !lines.is_empty() {
-                self.view.renderables.push(Self::live_tail_renderable(
+                self.view.push_renderable(Self::live_tail_renderable(
                     lines,
                     !self.cells.is_empty(),
                     key.is_stream_continuation,
@@ -608,8 +710,14 @@ impl TranscriptOverlay {
     }
 
     pub(crate) fn set_highlight_cell(&mut self, cell: Option<usize>) {
-        self.highlight_cell = cell;
-        self.rebuild_renderables();
+        let previous = std::mem::replace(&mut self.highlight_cell, cell);
+        // Highlighting only restyles a cell, so just the affected renderables are swapped.
+        for idx in [previous, cell].into_iter().flatten() {
+            if let Some(c) = self.cells.get(idx) {
+                let renderable = Self::render_cell(idx, c, self.highlight_cell);
+                self.view.replace_renderable(idx, renderable);
+            }
+        }
         if let Some(idx) = self.highlight_cell {
             self.view.scroll_chunk_into_view(idx);
         }
@@ -625,9 +733,10 @@ impl TranscriptOverlay {
 
     fn rebuild_renderables(&mut self) {
         let tail_renderable = self.take_live_tail_renderable();
-        self.view.renderables = Self::render_cells(&self.cells, self.highlight_cell);
+        self.view
+            .set_renderables(Self::render_cells(&self.cells, self.highlight_cell));
         if let Some(tail) = tail_renderable {
-            self.view.renderables.push(tail);
+            self.view.push_renderable(tail);
         }
     }
 
@@ -637,7 +746,7 @@ impl TranscriptOverlay {
     /// cell renderables, so this relies on the live tail always being the final entry in
     /// `view.renderables` when present.
     fn take_live_tail_renderable(&mut self) -> Option<Box<dyn Renderable>> {
-        (self.view.renderables.len() > self.cells.len()).then(|| self.view.renderables.pop())?
+        (self.view.renderables.len() > self.cells.len()).then(|| self.view.pop_renderable())?
     }
 
     fn live_tail_renderable(
@@ -1006,6 +1115,7 @@ mod tests {
                 exit_code: 0,
                 aggregated_output: "src\nREADME.md\n".into(),
                 formatted_output: "src\nREADME.md\n".into(),
+                output_artifact: None,
             },
             Duration::from_millis(420),
         );
@@ -1205,6 +1315,67 @@ mod tests {
         assert_eq!(pv.content_height(80), 5);
     }
 
+    /// A one-line cell that counts how often its transcript is built.
+    #[derive(Debug)]
+    struct CountingCell {
+        builds: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl crate::history_cell::HistoryCell for CountingCell {
+        fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+            self.transcript_lines(width)
+        }
+
+        fn transcript_lines(&self, _width: u16) -> Vec<Line<'static>> {
+            self.builds
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            vec![Line::from("line")]
+        }
+    }
+
+    #[test]
+    fn transcript_overlay_only_touches_visible_and_new_cells() {
+        use std::sync::atomic::Ordering;
+
+        let builds = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let cells: Vec<Arc<dyn HistoryCell>> = (0..1_000)
+            .map(|_| {
+                Arc::new(CountingCell {
+                    builds: builds.clone(),
+                }) as Arc<dyn HistoryCell>
+            })
+            .collect();
+        let mut overlay = TranscriptOverlay::new(cells);
+        let area = Rect::new(0, 0, 40, 12);
+        let mut buf = Buffer::empty(area);
+
+        // The first frame measures every cell once.
+        overlay.render(area, &mut buf);
+        assert!(builds.load(Ordering::Relaxed) >= 1_000);
+
+        // Later frames only draw the cells in the viewport.
+        builds.store(0, Ordering::Relaxed);
+        overlay.render(area, &mut buf);
+        let visible = builds.load(Ordering::Relaxed);
+        assert!(visible > 0 && visible <= usize::from(area.height));
+
+        // A committed cell is measured on its own; the others keep their layout.
+        overlay.insert_cell(Arc::new(CountingCell {
+            builds: builds.clone(),
+        }));
+        builds.store(0, Ordering::Relaxed);
+        overlay.render(area, &mut buf);
+        assert!(builds.load(Ordering::Relaxed) <= usize::from(area.height) + 1);
+        assert!(overlay.view.is_scrolled_to_bottom());
+
+        // Highlighting restyles one cell without re-measuring the transcript.
+        overlay.set_highlight_cell(Some(0));
+        builds.store(0, Ordering::Relaxed);
+        overlay.render(area, &mut buf);
+        assert!(builds.load(Ordering::Relaxed) <= usize::from(area.height) + 1);
+        assert_eq!(overlay.view.scroll_offset, 0);
+    }
+
     #[test]
     fn pager_view_ensure_chunk_visible_scrolls_down_when_needed() {
         let mut pv = PagerView::new(
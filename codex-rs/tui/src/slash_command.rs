@@ -28,16 +28,21 @@ pub enum SlashCommand {
     Fork,
     Init,
     Compact,
+    Pin,
+    Unpin,
+    Merge,
     Plan,
     Collab,
     Agent,
     // Undo,
     Diff,
+    Output,
     Mention,
     Status,
     DebugConfig,
     Statusline,
     Mcp,
+    Resources,
     Apps,
     Logout,
     Quit,
@@ -63,6 +68,9 @@ impl SlashCommand {
             SlashCommand::New => "start a new chat during a conversation",
             SlashCommand::Init => "create an AGENTS.md file with instructions for Codex",
             SlashCommand::Compact => "summarize conversation to prevent hitting the context limit",
+            SlashCommand::Pin => "pin text or a file (@path) so compaction keeps it verbatim",
+            SlashCommand::Unpin => "remove a pinned context item: /unpin <id>",
+            SlashCommand::Merge => "merge the session worktree back into your checkout",
             SlashCommand::Review => "review my current changes and find issues",
             SlashCommand::Rename => "rename the current thread",
             SlashCommand::Resume => "resume a saved chat",
@@ -70,6 +78,7 @@ impl SlashCommand {
             // SlashCommand::Undo => "ask Codex to undo a turn",
             SlashCommand::Quit | SlashCommand::Exit => "exit Codex",
             SlashCommand::Diff => "show git diff (including untracked files)",
+            SlashCommand::Output => "open the full output of the last truncated command",
             SlashCommand::Mention => "mention a file",
             SlashCommand::Skills => "use skills to improve how Codex performs specific tasks",
             SlashCommand::Status => "show current session configuration and token usage",
@@ -92,6 +101,7 @@ impl SlashCommand {
             }
             SlashCommand::Experimental => "toggle experimental features",
             SlashCommand::Mcp => "list configured MCP tools",
+            SlashCommand::Resources => "attach an MCP resource to your next message",
             SlashCommand::Apps => "manage apps",
             SlashCommand::Logout => "log out of Codex",
             SlashCommand::Rollout => "print the rollout file path",
@@ -110,7 +120,10 @@ impl SlashCommand {
         matches!(
             self,
             SlashCommand::Review
+                | SlashCommand::Mcp
                 | SlashCommand::Rename
+                | SlashCommand::Pin
+                | SlashCommand::Unpin
                 | SlashCommand::Plan
                 | SlashCommand::SandboxReadRoot
         )
@@ -124,6 +137,7 @@ impl SlashCommand {
             | SlashCommand::Fork
             | SlashCommand::Init
             | SlashCommand::Compact
+            | SlashCommand::Merge
             // | SlashCommand::Undo
             | SlashCommand::Model
             | SlashCommand::Personality
@@ -138,14 +152,18 @@ impl SlashCommand {
             | SlashCommand::MemoryDrop
             | SlashCommand::MemoryUpdate => false,
             SlashCommand::Diff
+            | SlashCommand::Output
             | SlashCommand::Rename
             | SlashCommand::Mention
+            | SlashCommand::Pin
+            | SlashCommand::Unpin
             | SlashCommand::Skills
             | SlashCommand::Status
             | SlashCommand::DebugConfig
             | SlashCommand::Ps
             | SlashCommand::Clean
             | SlashCommand::Mcp
+            | SlashCommand::Resources
             | SlashCommand::Apps
             | SlashCommand::Feedback
             | SlashCommand::Quit
@@ -0,0 +1,197 @@
+//! Startup timings reported by `--profile-startup`.
+//!
+//! Phases are recorded from wherever they run during startup and summarized after the TUI has
+//! restored the terminal, so the report does not compete with the UI. Recording does nothing
+//! unless [`enable`] was called.
+
+use std::future::Future;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StartupPhase {
+    /// Reading `config.toml` and building the effective config, including reloads.
+    ConfigLoad,
+    /// Resolving the config and credentials that cloud requirements are fetched with.
+    CloudAuth,
+    /// Loading stored credentials for the session.
+    Auth,
+    /// Looking up the session to resume or fork with `--last`.
+    RolloutScan,
+    /// Until every configured MCP server of the first session has started or failed.
+    McpStartup,
+    /// Until the first frame of the chat view has been drawn.
+    FirstFrame,
+}
+
+impl StartupPhase {
+    fn label(self) -> &'static str {
+        match self {
+            StartupPhase::ConfigLoad => "config load",
+            StartupPhase::CloudAuth => "cloud auth",
+            StartupPhase::Auth => "auth",
+            StartupPhase::RolloutScan => "rollout scan",
+            StartupPhase::McpStartup => "mcp startup",
+            StartupPhase::FirstFrame => "first frame",
+        }
+    }
+}
+
+static PROFILE: LazyLock<Mutex<Option<StartupProfile>>> = LazyLock::new(|| Mutex::new(None));
+/// Lets per-frame calls skip the lock when profiling is off.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Starts profiling; timings are measured from this call.
+pub(crate) fn enable() {
+    if let Ok(mut profile) = PROFILE.lock() {
+        *profile = Some(StartupProfile::new(Instant::now()));
+        ENABLED.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Awaits `fut` and records how long it took under `phase`.
+pub(crate) async fn measure<T>(phase: StartupPhase, fut: impl Future<Output = T>) -> T {
+    let started = Instant::now();
+    let output = fut.await;
+    record(phase, started);
+    output
+}
+
+/// Records the time from `started` until now under `phase`. Phases recorded more than once
+/// accumulate.
+pub(crate) fn record(phase: StartupPhase, started: Instant) {
+    with_profile(|profile| profile.record(phase, started, Instant::now()));
+}
+
+/// Records that `phase` has been reached, measured from the start of profiling. Only the first
+/// call per phase counts.
+pub(crate) fn milestone(phase: StartupPhase) {
+    with_profile(|profile| profile.milestone(phase, Instant::now()));
+}
+
+/// The summary to print on exit, if profiling was enabled.
+pub(crate) fn report() -> Option<String> {
+    PROFILE
+        .lock()
+        .ok()
+        .and_then(|profile| profile.as_ref().map(StartupProfile::report))
+}
+
+fn with_profile(f: impl FnOnce(&mut StartupProfile)) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    if let Ok(mut profile) = PROFILE.lock()
+        && let Some(profile) = profile.as_mut()
+    {
+        f(profile);
+    }
+}
+
+#[derive(Debug)]
+struct StartupProfile {
+    enabled_at: Instant,
+    phases: Vec<PhaseTiming>,
+}
+
+#[derive(Debug)]
+struct PhaseTiming {
+    phase: StartupPhase,
+    took: Duration,
+    runs: u32,
+    /// When the last run finished, relative to the start of profiling.
+    done_at: Duration,
+}
+
+impl StartupProfile {
+    fn new(enabled_at: Instant) -> Self {
+        Self {
+            enabled_at,
+            phases: Vec::new(),
+        }
+    }
+
+    fn record(&mut self, phase: StartupPhase, started: Instant, finished: Instant) {
+        let took = finished.saturating_duration_since(started);
+        let done_at = finished.saturating_duration_since(self.enabled_at);
+        match self.phases.iter_mut().find(|timing| timing.phase == phase) {
+            Some(timing) => {
+                timing.took += took;
+                timing.runs += 1;
+                timing.done_at = timing.done_at.max(done_at);
+            }
+            None => self.phases.push(PhaseTiming {
+                phase,
+                took,
+                runs: 1,
+                done_at,
+            }),
+        }
+    }
+
+    fn milestone(&mut self, phase: StartupPhase, reached: Instant) {
+        if !self.phases.iter().any(|timing| timing.phase == phase) {
+            self.record(phase, self.enabled_at, reached);
+        }
+    }
+
+    fn report(&self) -> String {
+        let mut phases: Vec<&PhaseTiming> = self.phases.iter().collect();
+        phases.sort_by_key(|timing| timing.done_at);
+        let mut lines = vec![format!("{:<14}{:>10}{:>10}", "startup", "took", "done at")];
+        for timing in phases {
+            let runs = if timing.runs > 1 {
+                format!("  ({} runs)", timing.runs)
+            } else {
+                String::new()
+            };
+            lines.push(format!(
+                "{:<14}{:>10}{:>10}{runs}",
+                timing.phase.label(),
+                format_millis(timing.took),
+                format_millis(timing.done_at),
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+fn format_millis(duration: Duration) -> String {
+    format!("{}ms", duration.as_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn report_accumulates_runs_and_orders_by_completion() {
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        let mut profile = StartupProfile::new(start);
+
+        profile.record(StartupPhase::ConfigLoad, at(0), at(40));
+        profile.record(StartupPhase::CloudAuth, at(5), at(10));
+        profile.record(StartupPhase::Auth, at(10), at(15));
+        profile.milestone(StartupPhase::FirstFrame, at(120));
+        profile.record(StartupPhase::ConfigLoad, at(60), at(80));
+        profile.milestone(StartupPhase::FirstFrame, at(500));
+
+        assert_eq!(
+            profile.report(),
+            [
+                "startup             took   done at",
+                "cloud auth           5ms      10ms",
+                "auth                 5ms      15ms",
+                "config load         60ms      80ms  (2 runs)",
+                "first frame        120ms     120ms",
+            ]
+            .join("\n")
+        );
+    }
+}
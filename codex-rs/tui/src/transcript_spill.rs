@@ -0,0 +1,307 @@
+//! Bounds how much of the transcript the TUI keeps in memory.
+//!
+//! `App::transcript_cells` holds every committed history cell so `Ctrl+T` and backtracking can
+//! show the whole session, which makes day-long sessions grow without limit. Once the transcript
+//! is longer than [`RESIDENT_TRANSCRIPT_CELLS`], older cells are replaced by [`SpilledCell`]s:
+//! their transcript lines are written to a temporary spill file and read back only when the
+//! transcript overlay scrolls them into view.
+//!
+//! User messages and session headers stay in memory, since backtracking locates turns by
+//! downcasting to those types; they are small compared to agent and command output.
+
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::history_cell::HistoryCell;
+use crate::history_cell::SessionInfoCell;
+use crate::history_cell::UserHistoryCell;
+use crate::wrapping::RtOptions;
+use crate::wrapping::word_wrap_lines;
+
+/// Number of most recent transcript cells that are always kept in memory.
+pub(crate) const RESIDENT_TRANSCRIPT_CELLS: usize = 1_000;
+
+/// Moves older transcript cells to a spill file as the transcript grows.
+#[derive(Debug, Default)]
+pub(crate) struct TranscriptSpill {
+    /// Created with the first spilled cell.
+    file: Option<Arc<SpillFile>>,
+    /// Cells before this index have already been considered for spilling.
+    spilled_len: usize,
+    /// Set when the spill file could not be created; cells then stay in memory.
+    unavailable: bool,
+}
+
+impl TranscriptSpill {
+    /// Spills the cells that are no longer among the most recent [`RESIDENT_TRANSCRIPT_CELLS`].
+    /// `width` is the width their transcript lines are captured at.
+    pub(crate) fn spill_old_cells(&mut self, cells: &mut [Arc<dyn HistoryCell>], width: u16) {
+        self.spill_cells_before(
+            cells,
+            cells.len().saturating_sub(RESIDENT_TRANSCRIPT_CELLS),
+            width,
+        );
+    }
+
+    fn spill_cells_before(&mut self, cells: &mut [Arc<dyn HistoryCell>], end: usize, width: u16) {
+        // The transcript may have been trimmed by a backtrack since the last call.
+        self.spilled_len = self.spilled_len.min(cells.len());
+        if self.unavailable || end <= self.spilled_len {
+            return;
+        }
+        let file = match &self.file {
+            Some(file) => file.clone(),
+            None => match SpillFile::create() {
+                Ok(file) => self.file.insert(Arc::new(file)).clone(),
+                Err(err) => {
+                    tracing::warn!("keeping the whole transcript in memory: {err}");
+                    self.unavailable = true;
+                    return;
+                }
+            },
+        };
+        for cell in &mut cells[self.spilled_len..end] {
+            if !is_spillable(cell.as_ref()) {
+                continue;
+            }
+            match SpilledCell::spill(&file, cell.as_ref(), width) {
+                Ok(spilled) => *cell = Arc::new(spilled),
+                Err(err) => {
+                    tracing::warn!("failed to spill transcript cell: {err}");
+                    break;
+                }
+            }
+        }
+        self.spilled_len = end;
+    }
+}
+
+fn is_spillable(cell: &dyn HistoryCell) -> bool {
+    let cell = cell.as_any();
+    !(cell.is::<UserHistoryCell>() || cell.is::<SessionInfoCell>() || cell.is::<SpilledCell>())
+}
+
+/// Append-only temporary file holding the transcript lines of spilled cells. It is deleted once
+/// the last cell referring to it is dropped.
+#[derive(Debug)]
+struct SpillFile {
+    file: Mutex<File>,
+}
+
+impl SpillFile {
+    fn create() -> io::Result<Self> {
+        Ok(Self {
+            file: Mutex::new(tempfile::tempfile()?),
+        })
+    }
+
+    /// Appends `lines` and returns the offset and length they were written at.
+    fn append(&self, lines: &[Line<'static>]) -> io::Result<(u64, usize)> {
+        let stored: Vec<StoredLine> = lines.iter().map(StoredLine::from).collect();
+        let bytes = serde_json::to_vec(&stored)?;
+        let mut file = self.lock()?;
+        let offset = file.seek(SeekFrom::End(0))?;
+        file.write_all(&bytes)?;
+        Ok((offset, bytes.len()))
+    }
+
+    fn read(&self, offset: u64, len: usize) -> io::Result<Vec<Line<'static>>> {
+        let mut bytes = vec![0; len];
+        {
+            let mut file = self.lock()?;
+            file.seek(SeekFrom::Start(offset))?;
+            file.read_exact(&mut bytes)?;
+        }
+        let stored: Vec<StoredLine> = serde_json::from_slice(&bytes)?;
+        Ok(stored.into_iter().map(Line::from).collect())
+    }
+
+    fn lock(&self) -> io::Result<MutexGuard<'_, File>> {
+        self.file
+            .lock()
+            .map_err(|_| io::Error::other("transcript spill file lock poisoned"))
+    }
+}
+
+/// A transcript cell whose lines live in the spill file.
+#[derive(Debug)]
+pub(crate) struct SpilledCell {
+    file: Arc<SpillFile>,
+    offset: u64,
+    len: usize,
+    /// Width the lines were captured at; narrower views wrap them again.
+    width: u16,
+    is_stream_continuation: bool,
+}
+
+impl SpilledCell {
+    fn spill(file: &Arc<SpillFile>, cell: &dyn HistoryCell, width: u16) -> io::Result<Self> {
+        let (offset, len) = file.append(&cell.transcript_lines(width))?;
+        Ok(Self {
+            file: file.clone(),
+            offset,
+            len,
+            width,
+            is_stream_continuation: cell.is_stream_continuation(),
+        })
+    }
+}
+
+impl HistoryCell for SpilledCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        let lines = match self.file.read(self.offset, self.len) {
+            Ok(lines) => lines,
+            Err(err) => {
+                tracing::warn!("failed to read spilled transcript cell: {err}");
+                return vec!["(transcript output unavailable)".dim().into()];
+            }
+        };
+        if width < self.width {
+            word_wrap_lines(lines.iter(), RtOptions::new(width as usize))
+        } else {
+            lines
+        }
+    }
+
+    fn is_stream_continuation(&self) -> bool {
+        self.is_stream_continuation
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredLine {
+    #[serde(default, skip_serializing_if = "StoredStyle::is_default")]
+    style: StoredStyle,
+    spans: Vec<StoredSpan>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredSpan {
+    content: String,
+    #[serde(default, skip_serializing_if = "StoredStyle::is_default")]
+    style: StoredStyle,
+}
+
+#[derive(Default, PartialEq, Serialize, Deserialize)]
+struct StoredStyle {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fg: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bg: Option<String>,
+    #[serde(default)]
+    add_modifier: u16,
+    #[serde(default)]
+    sub_modifier: u16,
+}
+
+impl StoredStyle {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl From<&Line<'static>> for StoredLine {
+    fn from(line: &Line<'static>) -> Self {
+        Self {
+            style: line.style.into(),
+            spans: line
+                .spans
+                .iter()
+                .map(|span| StoredSpan {
+                    content: span.content.to_string(),
+                    style: span.style.into(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<StoredLine> for Line<'static> {
+    fn from(line: StoredLine) -> Self {
+        Line::from(
+            line.spans
+                .into_iter()
+                .map(|span| Span::styled(span.content, Style::from(span.style)))
+                .collect::<Vec<_>>(),
+        )
+        .style(Style::from(line.style))
+    }
+}
+
+impl From<Style> for StoredStyle {
+    fn from(style: Style) -> Self {
+        Self {
+            fg: style.fg.map(|color| color.to_string()),
+            bg: style.bg.map(|color| color.to_string()),
+            add_modifier: style.add_modifier.bits(),
+            sub_modifier: style.sub_modifier.bits(),
+        }
+    }
+}
+
+impl From<StoredStyle> for Style {
+    fn from(style: StoredStyle) -> Self {
+        let color = |color: Option<String>| color.and_then(|color| Color::from_str(&color).ok());
+        let mut out = Style::default()
+            .add_modifier(Modifier::from_bits_truncate(style.add_modifier))
+            .remove_modifier(Modifier::from_bits_truncate(style.sub_modifier));
+        out.fg = color(style.fg);
+        out.bg = color(style.bg);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history_cell::AgentMessageCell;
+    use pretty_assertions::assert_eq;
+
+    fn user_cell(message: &str) -> Arc<dyn HistoryCell> {
+        Arc::new(UserHistoryCell {
+            message: message.to_string(),
+            text_elements: Vec::new(),
+            local_image_paths: Vec::new(),
+            remote_image_urls: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn old_cells_are_read_back_from_the_spill_file() {
+        let answer = Line::from(vec!["plain ".into(), "styled".cyan().bold()]);
+        let mut cells = vec![
+            user_cell("question"),
+            Arc::new(AgentMessageCell::new(vec![answer.clone()], true)) as Arc<dyn HistoryCell>,
+            Arc::new(AgentMessageCell::new(vec!["recent".into()], true)) as Arc<dyn HistoryCell>,
+        ];
+        let before: Vec<_> = cells.iter().map(|cell| cell.transcript_lines(80)).collect();
+
+        let mut spill = TranscriptSpill::default();
+        spill.spill_cells_before(&mut cells, 2, 80);
+
+        assert!(cells[0].as_any().is::<UserHistoryCell>());
+        assert!(cells[1].as_any().is::<SpilledCell>());
+        assert!(!cells[2].as_any().is::<SpilledCell>());
+        let after: Vec<_> = cells.iter().map(|cell| cell.transcript_lines(80)).collect();
+        assert_eq!(after, before);
+        // Narrower views wrap the stored lines again.
+        assert!(cells[1].display_lines(4).len() > before[1].len());
+    }
+}
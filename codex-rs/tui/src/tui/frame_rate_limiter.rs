@@ -3,6 +3,12 @@
 //! Widgets sometimes call `FrameRequester::schedule_frame()` more frequently than a user can
 //! perceive. This limiter clamps draw notifications to a maximum of 120 FPS to avoid wasted work.
 //!
+//! Streaming output (agent deltas, command output, large diffs) requests a frame for every event,
+//! which would keep the TUI redrawing at the full rate for as long as the stream lasts. Once frames
+//! have been drawn back to back for [`BURST_GRACE`], the limiter holds them to 30 FPS until the
+//! stream pauses for [`IDLE_GAP`]; isolated requests such as key presses still draw at the full
+//! rate.
+//!
 //! This is intentionally a small, pure helper so it can be unit-tested in isolation and used by
 //! the async frame scheduler without adding complexity to the app/event loop.
 
@@ -12,10 +18,21 @@ use std::time::Instant;
 /// A 120 FPS minimum frame interval (≈8.33ms).
 pub(super) const MIN_FRAME_INTERVAL: Duration = Duration::from_nanos(8_333_334);
 
+/// A 30 FPS frame interval (≈33.3ms) used during sustained bursts of redraw requests.
+const SUSTAINED_FRAME_INTERVAL: Duration = Duration::from_nanos(33_333_334);
+
+/// How long frames may follow each other at the full rate before a burst is throttled.
+const BURST_GRACE: Duration = Duration::from_millis(250);
+
+/// A pause between frames at least this long ends a burst.
+const IDLE_GAP: Duration = Duration::from_millis(100);
+
 /// Remembers the most recent emitted draw, allowing deadlines to be clamped forward.
 #[derive(Debug, Default)]
 pub(super) struct FrameRateLimiter {
     last_emitted_at: Option<Instant>,
+    /// When the current run of back-to-back draws started.
+    burst_started_at: Option<Instant>,
 }
 
 impl FrameRateLimiter {
@@ -24,16 +41,38 @@ impl FrameRateLimiter {
         let Some(last_emitted_at) = self.last_emitted_at else {
             return requested;
         };
+        let interval = if self.is_sustained_burst(requested) {
+            SUSTAINED_FRAME_INTERVAL
+        } else {
+            MIN_FRAME_INTERVAL
+        };
         let min_allowed = last_emitted_at
-            .checked_add(MIN_FRAME_INTERVAL)
+            .checked_add(interval)
             .unwrap_or(last_emitted_at);
         requested.max(min_allowed)
     }
 
     /// Records that a draw notification was emitted at `emitted_at`.
     pub(super) fn mark_emitted(&mut self, emitted_at: Instant) {
+        let continues_burst = self
+            .last_emitted_at
+            .is_some_and(|last| emitted_at.saturating_duration_since(last) < IDLE_GAP);
+        if !continues_burst {
+            self.burst_started_at = Some(emitted_at);
+        }
         self.last_emitted_at = Some(emitted_at);
     }
+
+    /// Whether a draw at `requested` would extend a burst that has outlasted [`BURST_GRACE`].
+    fn is_sustained_burst(&self, requested: Instant) -> bool {
+        let (Some(started_at), Some(last_emitted_at)) =
+            (self.burst_started_at, self.last_emitted_at)
+        else {
+            return false;
+        };
+        requested.saturating_duration_since(last_emitted_at) < IDLE_GAP
+            && last_emitted_at.saturating_duration_since(started_at) >= BURST_GRACE
+    }
 }
 
 #[cfg(test)]
@@ -59,4 +98,33 @@ mod tests {
         let too_soon = t0 + Duration::from_millis(1);
         assert_eq!(limiter.clamp_deadline(too_soon), t0 + MIN_FRAME_INTERVAL);
     }
+
+    #[test]
+    fn sustained_bursts_are_held_to_the_lower_rate() {
+        let t0 = Instant::now();
+        let mut limiter = FrameRateLimiter::default();
+
+        // Request a frame every millisecond for the whole grace period.
+        let mut last = t0;
+        limiter.mark_emitted(last);
+        while last.duration_since(t0) < BURST_GRACE {
+            last = limiter.clamp_deadline(last + Duration::from_millis(1));
+            limiter.mark_emitted(last);
+        }
+        let next = last + Duration::from_millis(1);
+        assert_eq!(
+            limiter.clamp_deadline(next),
+            last + SUSTAINED_FRAME_INTERVAL
+        );
+
+        // A pause ends the burst, so the next request draws right away.
+        let after_pause = last + IDLE_GAP;
+        assert_eq!(limiter.clamp_deadline(after_pause), after_pause);
+        limiter.mark_emitted(after_pause);
+        let soon = after_pause + Duration::from_millis(1);
+        assert_eq!(
+            limiter.clamp_deadline(soon),
+            after_pause + MIN_FRAME_INTERVAL
+        );
+    }
 }
@@ -71,7 +71,8 @@ impl FrameRequester {
 ///
 /// This type is internal to `FrameRequester` and is spawned as a task to handle scheduling logic.
 ///
-/// To avoid wasted redraw work, draw notifications are clamped to a maximum of 120 FPS (see
+/// To avoid wasted redraw work, draw notifications are clamped to a maximum of 120 FPS, and to
+/// 30 FPS during sustained bursts of requests such as streaming output (see
 /// [`FrameRateLimiter`]).
 struct FrameScheduler {
     receiver: mpsc::UnboundedReceiver<Instant>,
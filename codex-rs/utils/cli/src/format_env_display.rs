@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
-pub fn format_env_display(env: Option<&HashMap<String, String>>, env_vars: &[String]) -> String {
+/// Lists the configured variable names with their values masked.
+pub fn format_env_display<V>(env: Option<&HashMap<String, V>>, env_vars: &[String]) -> String {
     let mut parts: Vec<String> = Vec::new();
 
     if let Some(map) = env {
@@ -26,9 +27,9 @@ mod tests {
 
     #[test]
     fn returns_dash_when_empty() {
-        assert_eq!(format_env_display(None, &[]), "-");
+        assert_eq!(format_env_display::<String>(None, &[]), "-");
 
-        let empty_map = HashMap::new();
+        let empty_map = HashMap::<String, String>::new();
         assert_eq!(format_env_display(Some(&empty_map), &[]), "-");
     }
 
@@ -45,7 +46,10 @@ mod tests {
     fn formats_env_vars_with_dollar_prefix() {
         let vars = vec!["TOKEN".to_string(), "PATH".to_string()];
 
-        assert_eq!(format_env_display(None, &vars), "TOKEN=*****, PATH=*****");
+        assert_eq!(
+            format_env_display::<String>(None, &vars),
+            "TOKEN=*****, PATH=*****"
+        );
     }
 
     #[test]
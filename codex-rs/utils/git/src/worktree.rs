@@ -0,0 +1,304 @@
+use std::ffi::OsString;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::GitToolingError;
+use crate::operations::ensure_git_repository;
+use crate::operations::repo_subdir;
+use crate::operations::resolve_head;
+use crate::operations::resolve_repository_root;
+use crate::operations::run_git_for_status;
+use crate::operations::run_git_for_stdout;
+
+/// A linked worktree checked out on its own branch so a session can edit files without touching
+/// the repository it was started from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionWorktree {
+    /// Root of the repository the worktree was created from.
+    pub repo_root: PathBuf,
+    /// Root of the linked worktree.
+    pub path: PathBuf,
+    /// Branch checked out in the worktree.
+    pub branch: String,
+}
+
+impl SessionWorktree {
+    /// Maps a directory inside the original repository to the same directory inside the
+    /// worktree.
+    pub fn cwd_for(&self, original_cwd: &Path) -> PathBuf {
+        if original_cwd.starts_with(&self.path) {
+            return original_cwd.to_path_buf();
+        }
+        match repo_subdir(&self.repo_root, original_cwd) {
+            Some(subdir) => self.path.join(subdir),
+            None => self.path.clone(),
+        }
+    }
+}
+
+/// Creates a worktree at `worktree_path` on a new `branch` forked from the current `HEAD` of the
+/// repository containing `repo_path`.
+///
+/// When the worktree already exists (for example when a session is resumed) it is reused as is,
+/// and when only the branch exists it is checked out again.
+pub fn create_session_worktree(
+    repo_path: &Path,
+    worktree_path: &Path,
+    branch: &str,
+) -> Result<SessionWorktree, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    if worktree_path.join(".git").exists() {
+        // A resumed session may start inside the worktree itself, so point back at the
+        // checkout it was created from.
+        let repo_root = if repo_path.starts_with(worktree_path) {
+            main_worktree_root(worktree_path)?
+        } else {
+            resolve_repository_root(repo_path)?
+        };
+        return Ok(SessionWorktree {
+            repo_root,
+            path: worktree_path.to_path_buf(),
+            branch: branch.to_string(),
+        });
+    }
+    let repo_root = resolve_repository_root(repo_path)?;
+    let worktree = SessionWorktree {
+        repo_root: repo_root.clone(),
+        path: worktree_path.to_path_buf(),
+        branch: branch.to_string(),
+    };
+    if resolve_head(repo_root.as_path())?.is_none() {
+        return Err(GitToolingError::NoCommits { path: repo_root });
+    }
+
+    let mut args = vec![OsString::from("worktree"), OsString::from("add")];
+    if branch_exists(repo_root.as_path(), branch)? {
+        args.push(worktree_path.as_os_str().to_os_string());
+        args.push(OsString::from(branch));
+    } else {
+        args.push(OsString::from("-b"));
+        args.push(OsString::from(branch));
+        args.push(worktree_path.as_os_str().to_os_string());
+        args.push(OsString::from("HEAD"));
+    }
+    run_git_for_status(repo_root.as_path(), args, None)?;
+    Ok(worktree)
+}
+
+/// Commits any pending changes in the worktree and merges its branch into whatever is checked
+/// out in the original repository.
+///
+/// Returns the new `HEAD` of the original repository, or `None` when the branch has nothing that
+/// is not already merged. A conflicting merge is aborted so the original checkout is left as it
+/// was, and the worktree keeps its commits so the merge can be retried.
+pub fn merge_session_worktree(
+    worktree: &SessionWorktree,
+    message: &str,
+) -> Result<Option<String>, GitToolingError> {
+    let identity = default_commit_identity();
+    let worktree_path = worktree.path.as_path();
+    run_git_for_status(worktree_path, ["add", "--all"], None)?;
+    let status = run_git_for_stdout(worktree_path, ["status", "--porcelain"], None)?;
+    if !status.is_empty() {
+        run_git_for_status(
+            worktree_path,
+            ["commit", "--no-verify", "-m", message],
+            Some(identity.as_slice()),
+        )?;
+    }
+
+    let repo_root = worktree.repo_root.as_path();
+    let unmerged = run_git_for_stdout(
+        repo_root,
+        [
+            OsString::from("rev-list"),
+            OsString::from("--count"),
+            OsString::from(format!("HEAD..{}", worktree.branch)),
+        ],
+        None,
+    )?;
+    if unmerged == "0" {
+        return Ok(None);
+    }
+
+    if let Err(err) = run_git_for_status(
+        repo_root,
+        ["merge", "--no-edit", worktree.branch.as_str()],
+        Some(identity.as_slice()),
+    ) {
+        let _ = run_git_for_status(repo_root, ["merge", "--abort"], None);
+        return Err(err);
+    }
+    resolve_head(repo_root)
+}
+
+/// The first entry of `git worktree list` is always the main checkout.
+fn main_worktree_root(worktree_path: &Path) -> Result<PathBuf, GitToolingError> {
+    let list = run_git_for_stdout(worktree_path, ["worktree", "list", "--porcelain"], None)?;
+    list.lines()
+        .next()
+        .and_then(|line| line.strip_prefix("worktree "))
+        .map(PathBuf::from)
+        .ok_or_else(|| GitToolingError::NotAGitRepository {
+            path: worktree_path.to_path_buf(),
+        })
+}
+
+fn branch_exists(repo_root: &Path, branch: &str) -> Result<bool, GitToolingError> {
+    match run_git_for_status(
+        repo_root,
+        [
+            OsString::from("rev-parse"),
+            OsString::from("--verify"),
+            OsString::from("--quiet"),
+            OsString::from(format!("refs/heads/{branch}")),
+        ],
+        None,
+    ) {
+        Ok(()) => Ok(true),
+        Err(GitToolingError::GitCommand { .. }) => Ok(false),
+        Err(other) => Err(other),
+    }
+}
+
+/// Returns the author and committer identity for commits made on behalf of a session.
+fn default_commit_identity() -> Vec<(OsString, OsString)> {
+    [
+        ("GIT_AUTHOR_NAME", "Codex"),
+        ("GIT_AUTHOR_EMAIL", "codex@codex.local"),
+        ("GIT_COMMITTER_NAME", "Codex"),
+        ("GIT_COMMITTER_EMAIL", "codex@codex.local"),
+    ]
+    .into_iter()
+    .map(|(key, value)| (OsString::from(key), OsString::from(value)))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::process::Command;
+    use tempfile::tempdir;
+
+    fn run_git_in(repo_path: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .current_dir(repo_path)
+            .args(args)
+            .status()
+            .expect("git command");
+        assert!(status.success(), "git command failed: {args:?}");
+    }
+
+    fn run_git_stdout(repo_path: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .current_dir(repo_path)
+            .args(args)
+            .output()
+            .expect("git command");
+        assert!(output.status.success(), "git command failed: {args:?}");
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    fn init_test_repo(repo: &Path) {
+        run_git_in(repo, &["init", "--initial-branch=main"]);
+        run_git_in(repo, &["config", "core.autocrlf", "false"]);
+        run_git_in(repo, &["config", "user.name", "Tester"]);
+        run_git_in(repo, &["config", "user.email", "test@example.com"]);
+        std::fs::write(repo.join("tracked.txt"), "original\n").unwrap();
+        std::fs::create_dir(repo.join("nested")).unwrap();
+        std::fs::write(repo.join("nested/keep.txt"), "keep\n").unwrap();
+        run_git_in(repo, &["add", "."]);
+        run_git_in(repo, &["commit", "-m", "init"]);
+    }
+
+    #[test]
+    fn create_session_worktree_isolates_edits_and_maps_cwd() -> Result<(), GitToolingError> {
+        let temp = tempdir()?;
+        let repo = temp.path().join("repo");
+        std::fs::create_dir(&repo)?;
+        init_test_repo(&repo);
+        let worktree_path = temp.path().join("worktrees").join("session");
+
+        let worktree = create_session_worktree(&repo, &worktree_path, "codex/session")?;
+        assert_eq!(
+            worktree,
+            SessionWorktree {
+                repo_root: PathBuf::from(run_git_stdout(&repo, &["rev-parse", "--show-toplevel"])),
+                path: worktree_path.clone(),
+                branch: "codex/session".to_string(),
+            }
+        );
+        assert_eq!(
+            worktree.cwd_for(&repo.join("nested")),
+            worktree_path.join("nested")
+        );
+
+        std::fs::write(worktree_path.join("tracked.txt"), "changed\n")?;
+        assert_eq!(
+            std::fs::read_to_string(repo.join("tracked.txt"))?,
+            "original\n"
+        );
+
+        // Reusing the same worktree is a no-op, including from inside it.
+        let reused = create_session_worktree(&repo, &worktree_path, "codex/session")?;
+        assert_eq!(reused, worktree);
+        let nested = worktree_path.join("nested");
+        let reused = create_session_worktree(&nested, &worktree_path, "codex/session")?;
+        assert_eq!(reused, worktree);
+        assert_eq!(reused.cwd_for(&nested), nested);
+        Ok(())
+    }
+
+    #[test]
+    fn merge_session_worktree_commits_and_merges_changes() -> Result<(), GitToolingError> {
+        let temp = tempdir()?;
+        let repo = temp.path().join("repo");
+        std::fs::create_dir(&repo)?;
+        init_test_repo(&repo);
+        let worktree_path = temp.path().join("worktree");
+        let worktree = create_session_worktree(&repo, &worktree_path, "codex/session")?;
+
+        assert_eq!(merge_session_worktree(&worktree, "codex session")?, None);
+
+        std::fs::write(worktree_path.join("tracked.txt"), "changed\n")?;
+        std::fs::write(worktree_path.join("new.txt"), "new\n")?;
+        let head = merge_session_worktree(&worktree, "codex session")?;
+
+        assert_eq!(head, Some(run_git_stdout(&repo, &["rev-parse", "HEAD"])));
+        assert_eq!(
+            std::fs::read_to_string(repo.join("tracked.txt"))?,
+            "changed\n"
+        );
+        assert_eq!(std::fs::read_to_string(repo.join("new.txt"))?, "new\n");
+        assert_eq!(run_git_stdout(&repo, &["status", "--porcelain"]), "");
+        Ok(())
+    }
+
+    #[test]
+    fn merge_session_worktree_aborts_conflicting_merge() -> Result<(), GitToolingError> {
+        let temp = tempdir()?;
+        let repo = temp.path().join("repo");
+        std::fs::create_dir(&repo)?;
+        init_test_repo(&repo);
+        let worktree_path = temp.path().join("worktree");
+        let worktree = create_session_worktree(&repo, &worktree_path, "codex/session")?;
+
+        std::fs::write(repo.join("tracked.txt"), "user edit\n")?;
+        run_git_in(&repo, &["commit", "-am", "user edit"]);
+        let user_head = run_git_stdout(&repo, &["rev-parse", "HEAD"]);
+        std::fs::write(worktree_path.join("tracked.txt"), "agent edit\n")?;
+
+        assert!(matches!(
+            merge_session_worktree(&worktree, "codex session"),
+            Err(GitToolingError::GitCommand { .. })
+        ));
+        assert_eq!(run_git_stdout(&repo, &["rev-parse", "HEAD"]), user_head);
+        assert_eq!(run_git_stdout(&repo, &["status", "--porcelain"]), "");
+        assert_eq!(
+            std::fs::read_to_string(repo.join("tracked.txt"))?,
+            "user edit\n"
+        );
+        Ok(())
+    }
+}
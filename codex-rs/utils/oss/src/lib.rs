@@ -14,21 +14,30 @@ pub fn get_default_model_for_oss_provider(provider_id: &str) -> Option<&'static
 }
 
 /// Ensures the specified OSS provider is ready (models downloaded, service reachable).
+///
+/// This also probes the model's context size and tool calling support and records them on
+/// `config` unless they are configured explicitly.
 pub async fn ensure_oss_provider_ready(
     provider_id: &str,
-    config: &Config,
+    config: &mut Config,
 ) -> Result<(), std::io::Error> {
     match provider_id {
         LMSTUDIO_OSS_PROVIDER_ID => {
             codex_lmstudio::ensure_oss_ready(config)
                 .await
                 .map_err(|e| std::io::Error::other(format!("OSS setup failed: {e}")))?;
+            if let Err(err) = codex_lmstudio::apply_model_capabilities(config).await {
+                tracing::warn!("Failed to probe model capabilities from LM Studio: {err}");
+            }
         }
         OLLAMA_OSS_PROVIDER_ID => {
             codex_ollama::ensure_responses_supported(&config.model_provider).await?;
             codex_ollama::ensure_oss_ready(config)
                 .await
                 .map_err(|e| std::io::Error::other(format!("OSS setup failed: {e}")))?;
+            if let Err(err) = codex_ollama::apply_model_capabilities(config).await {
+                tracing::warn!("Failed to probe model capabilities from Ollama: {err}");
+            }
         }
         _ => {
             // Unknown provider, skip setup
@@ -37,6 +46,24 @@ pub async fn ensure_oss_provider_ready(
     Ok(())
 }
 
+/// Returns config overrides that carry probed model capabilities across config reloads.
+pub fn model_capability_overrides(config: &Config) -> Vec<(String, toml::Value)> {
+    let mut overrides = Vec::new();
+    if let Some(context_window) = config.model_context_window {
+        overrides.push((
+            "model_context_window".to_string(),
+            toml::Value::Integer(context_window),
+        ));
+    }
+    if let Some(supports_tool_calls) = config.model_supports_tool_calls {
+        overrides.push((
+            "model_supports_tool_calls".to_string(),
+            toml::Value::Boolean(supports_tool_calls),
+        ));
+    }
+    overrides
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,6 +3,7 @@
 use anyhow::Context;
 use anyhow::Result;
 use codex_windows_sandbox::allow_null_device;
+use codex_windows_sandbox::assign_to_new_job;
 use codex_windows_sandbox::convert_string_sid_to_sid;
 use codex_windows_sandbox::create_process_as_user;
 use codex_windows_sandbox::create_readonly_token_with_caps_from;
@@ -11,6 +12,7 @@ use codex_windows_sandbox::get_current_token_for_restriction;
 use codex_windows_sandbox::hide_current_user_profile_dir;
 use codex_windows_sandbox::log_note;
 use codex_windows_sandbox::parse_policy;
+use codex_windows_sandbox::terminate_job;
 use codex_windows_sandbox::to_wide;
 use codex_windows_sandbox::SandboxPolicy;
 use serde::Deserialize;
@@ -27,12 +29,6 @@ use windows_sys::Win32::Storage::FileSystem::CreateFileW;
 use windows_sys::Win32::Storage::FileSystem::FILE_GENERIC_READ;
 use windows_sys::Win32::Storage::FileSystem::FILE_GENERIC_WRITE;
 use windows_sys::Win32::Storage::FileSystem::OPEN_EXISTING;
-use windows_sys::Win32::System::JobObjects::AssignProcessToJobObject;
-use windows_sys::Win32::System::JobObjects::CreateJobObjectW;
-use windows_sys::Win32::System::JobObjects::JobObjectExtendedLimitInformation;
-use windows_sys::Win32::System::JobObjects::SetInformationJobObject;
-use windows_sys::Win32::System::JobObjects::JOBOBJECT_EXTENDED_LIMIT_INFORMATION;
-use windows_sys::Win32::System::JobObjects::JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
 use windows_sys::Win32::System::Threading::TerminateProcess;
 use windows_sys::Win32::System::Threading::WaitForSingleObject;
 use windows_sys::Win32::System::Threading::INFINITE;
@@ -62,25 +58,6 @@ struct RunnerRequest {
 
 const WAIT_TIMEOUT: u32 = 0x0000_0102;
 
-unsafe fn create_job_kill_on_close() -> Result<HANDLE> {
-    let h = CreateJobObjectW(std::ptr::null_mut(), std::ptr::null());
-    if h == 0 {
-        return Err(anyhow::anyhow!("CreateJobObjectW failed"));
-    }
-    let mut limits: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = std::mem::zeroed();
-    limits.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
-    let ok = SetInformationJobObject(
-        h,
-        JobObjectExtendedLimitInformation,
-        &mut limits as *mut _ as *mut _,
-        std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
-    );
-    if ok == 0 {
-        return Err(anyhow::anyhow!("SetInformationJobObject failed"));
-    }
-    Ok(h)
-}
-
 fn read_request_file(req_path: &Path) -> Result<String> {
     let content = std::fs::read_to_string(req_path)
         .with_context(|| format!("read request file {}", req_path.display()));
@@ -249,13 +226,8 @@ pub fn main() -> Result<()> {
         }
     };
 
-    // Optional job kill on close.
-    let h_job = unsafe { create_job_kill_on_close().ok() };
-    if let Some(job) = h_job {
-        unsafe {
-            let _ = AssignProcessToJobObject(job, proc_info.hProcess);
-        }
-    }
+    // Keep the command and anything it spawns in a kill-on-close job, so a timeout ends the tree.
+    let h_job = unsafe { assign_to_new_job(proc_info.hProcess) };
 
     // Wait for process.
     let wait_res = unsafe {
@@ -269,7 +241,12 @@ pub fn main() -> Result<()> {
     let exit_code: i32;
     unsafe {
         if timed_out {
-            let _ = TerminateProcess(proc_info.hProcess, 1);
+            match h_job {
+                Some(job) => terminate_job(job, 1),
+                None => {
+                    let _ = TerminateProcess(proc_info.hProcess, 1);
+                }
+            }
             exit_code = 128 + 64;
         } else {
             let mut raw_exit: u32 = 1;
@@ -0,0 +1,155 @@
+//! Job objects that keep a sandboxed command and everything it spawns together.
+//!
+//! A restricted token only limits what each process may touch; it does not stop a command from
+//! leaving background processes behind. Assigning the command to a job created here means the
+//! whole tree can be terminated on timeout, and closing the last handle to the job kills whatever
+//! is still running.
+
+use anyhow::anyhow;
+use anyhow::Result;
+use windows_sys::Win32::Foundation::CloseHandle;
+use windows_sys::Win32::Foundation::HANDLE;
+use windows_sys::Win32::System::JobObjects::AssignProcessToJobObject;
+use windows_sys::Win32::System::JobObjects::CreateJobObjectW;
+use windows_sys::Win32::System::JobObjects::JobObjectExtendedLimitInformation;
+use windows_sys::Win32::System::JobObjects::SetInformationJobObject;
+use windows_sys::Win32::System::JobObjects::TerminateJobObject;
+use windows_sys::Win32::System::JobObjects::JOBOBJECT_EXTENDED_LIMIT_INFORMATION;
+use windows_sys::Win32::System::JobObjects::JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+
+/// Creates a job whose processes are killed when its last handle is closed.
+///
+/// # Safety
+/// The returned handle must be closed with `CloseHandle`.
+pub unsafe fn create_job_kill_on_close() -> Result<HANDLE> {
+    let h = CreateJobObjectW(std::ptr::null_mut(), std::ptr::null());
+    if h == 0 {
+        return Err(anyhow!("CreateJobObjectW failed"));
+    }
+    let mut limits: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = std::mem::zeroed();
+    limits.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+    let ok = SetInformationJobObject(
+        h,
+        JobObjectExtendedLimitInformation,
+        &mut limits as *mut _ as *mut _,
+        std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+    );
+    if ok == 0 {
+        CloseHandle(h);
+        return Err(anyhow!("SetInformationJobObject failed"));
+    }
+    Ok(h)
+}
+
+/// Creates a kill-on-close job and assigns `process` to it. Returns `None` when either step
+/// fails, in which case the process keeps running outside a job.
+///
+/// # Safety
+/// `process` must be a valid process handle. The returned handle must be closed with
+/// `CloseHandle`.
+pub unsafe fn assign_to_new_job(process: HANDLE) -> Option<HANDLE> {
+    let job = create_job_kill_on_close().ok()?;
+    if AssignProcessToJobObject(job, process) == 0 {
+        CloseHandle(job);
+        return None;
+    }
+    Some(job)
+}
+
+/// Terminates every process in `job` with `exit_code`.
+///
+/// # Safety
+/// `job` must be a valid job handle.
+pub unsafe fn terminate_job(job: HANDLE, exit_code: u32) {
+    let _ = TerminateJobObject(job, exit_code);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::winutil::to_wide;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+    use std::time::Instant;
+    use windows_sys::Win32::Foundation::WAIT_OBJECT_0;
+    use windows_sys::Win32::System::Threading::CreateProcessW;
+    use windows_sys::Win32::System::Threading::OpenProcess;
+    use windows_sys::Win32::System::Threading::ResumeThread;
+    use windows_sys::Win32::System::Threading::WaitForSingleObject;
+    use windows_sys::Win32::System::Threading::CREATE_SUSPENDED;
+    use windows_sys::Win32::System::Threading::PROCESS_INFORMATION;
+    use windows_sys::Win32::System::Threading::PROCESS_SYNCHRONIZE;
+    use windows_sys::Win32::System::Threading::STARTUPINFOW;
+
+    const WAIT_TIMEOUT: u32 = 0x0000_0102;
+
+    #[cfg(windows)]
+    #[test]
+    fn terminating_the_job_kills_grandchildren() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let pid_path = dir.path().join("grandchild.pid");
+        // The child starts a grandchild, records its pid, and then outlives the test's timeout.
+        let mut cmdline = to_wide(format!(
+            "powershell.exe -NoProfile -NonInteractive -Command \"$p = Start-Process -PassThru -WindowStyle Hidden ping.exe -ArgumentList '-n','120','127.0.0.1'; Set-Content -Path '{}' -Value $p.Id; Start-Sleep -Seconds 120\"",
+            pid_path.display()
+        ));
+
+        let mut si: STARTUPINFOW = unsafe { std::mem::zeroed() };
+        si.cb = std::mem::size_of::<STARTUPINFOW>() as u32;
+        let mut pi: PROCESS_INFORMATION = unsafe { std::mem::zeroed() };
+        let ok = unsafe {
+            CreateProcessW(
+                std::ptr::null(),
+                cmdline.as_mut_ptr(),
+                std::ptr::null(),
+                std::ptr::null(),
+                0,
+                CREATE_SUSPENDED,
+                std::ptr::null(),
+                std::ptr::null(),
+                &si,
+                &mut pi,
+            )
+        };
+        assert_ne!(ok, 0, "CreateProcessW failed");
+        let job = unsafe { assign_to_new_job(pi.hProcess) }.expect("assign to job");
+        unsafe {
+            ResumeThread(pi.hThread);
+        }
+
+        let deadline = Instant::now() + Duration::from_secs(60);
+        let grandchild_pid = loop {
+            if let Some(pid) = std::fs::read_to_string(&pid_path)
+                .ok()
+                .and_then(|pid| pid.trim().parse::<u32>().ok())
+            {
+                break pid;
+            }
+            assert!(Instant::now() < deadline, "grandchild was not started");
+            std::thread::sleep(Duration::from_millis(100));
+        };
+        let grandchild = unsafe { OpenProcess(PROCESS_SYNCHRONIZE, 0, grandchild_pid) };
+        assert_ne!(grandchild, 0, "grandchild is not running");
+        assert_eq!(
+            unsafe { WaitForSingleObject(pi.hProcess, 500) },
+            WAIT_TIMEOUT,
+            "child should still be running"
+        );
+
+        unsafe {
+            terminate_job(job, 1);
+        }
+
+        assert_eq!(
+            unsafe { WaitForSingleObject(grandchild, 10_000) },
+            WAIT_OBJECT_0,
+            "grandchild should be terminated with the job"
+        );
+        unsafe {
+            CloseHandle(grandchild);
+            CloseHandle(pi.hThread);
+            CloseHandle(pi.hProcess);
+            CloseHandle(job);
+        }
+    }
+}
@@ -13,6 +13,7 @@ windows_modules!(
     env,
     hide_users,
     identity,
+    job,
     logging,
     path_normalization,
     policy,
@@ -67,6 +68,10 @@ pub use identity::require_logon_sandbox_creds;
 #[cfg(target_os = "windows")]
 pub use identity::sandbox_setup_is_complete;
 #[cfg(target_os = "windows")]
+pub use job::assign_to_new_job;
+#[cfg(target_os = "windows")]
+pub use job::terminate_job;
+#[cfg(target_os = "windows")]
 pub use logging::log_note;
 #[cfg(target_os = "windows")]
 pub use logging::LOG_FILE_NAME;
@@ -153,6 +158,8 @@ mod windows_impl {
     use super::env::apply_no_network_to_env;
     use super::env::ensure_non_interactive_pager;
     use super::env::normalize_null_device_env;
+    use super::job::assign_to_new_job;
+    use super::job::terminate_job;
     use super::logging::debug_log;
     use super::logging::log_failure;
     use super::logging::log_start;
@@ -184,7 +191,9 @@ mod windows_impl {
     use windows_sys::Win32::System::Pipes::CreatePipe;
     use windows_sys::Win32::System::Threading::CreateProcessAsUserW;
     use windows_sys::Win32::System::Threading::GetExitCodeProcess;
+    use windows_sys::Win32::System::Threading::ResumeThread;
     use windows_sys::Win32::System::Threading::WaitForSingleObject;
+    use windows_sys::Win32::System::Threading::CREATE_SUSPENDED;
     use windows_sys::Win32::System::Threading::CREATE_UNICODE_ENVIRONMENT;
     use windows_sys::Win32::System::Threading::INFINITE;
     use windows_sys::Win32::System::Threading::PROCESS_INFORMATION;
@@ -378,7 +387,7 @@ mod windows_impl {
                 ptr::null_mut(),
                 ptr::null_mut(),
                 1,
-                CREATE_UNICODE_ENVIRONMENT,
+                CREATE_UNICODE_ENVIRONMENT | CREATE_SUSPENDED,
                 env_block.as_ptr() as *mut c_void,
                 to_wide(cwd).as_ptr(),
                 &si,
@@ -409,6 +418,12 @@ mod windows_impl {
             return Err(anyhow::anyhow!("CreateProcessAsUserW failed: {}", err));
         }
 
+        // The process starts suspended so it is in the job before it can spawn anything.
+        let h_job = unsafe { assign_to_new_job(pi.hProcess) };
+        unsafe {
+            ResumeThread(pi.hThread);
+        }
+
         unsafe {
             CloseHandle(in_r);
             // Close the parent's stdin write end so the child sees EOF immediately.
@@ -472,7 +487,12 @@ mod windows_impl {
             }
         } else {
             unsafe {
-                windows_sys::Win32::System::Threading::TerminateProcess(pi.hProcess, 1);
+                match h_job {
+                    Some(job) => terminate_job(job, 1),
+                    None => {
+                        windows_sys::Win32::System::Threading::TerminateProcess(pi.hProcess, 1);
+                    }
+                }
             }
         }
 
@@ -483,6 +503,11 @@ mod windows_impl {
             if pi.hProcess != 0 {
                 CloseHandle(pi.hProcess);
             }
+            // Closing the job kills background processes the command left behind, which would
+            // otherwise keep the output pipes open and block the readers below.
+            if let Some(job) = h_job {
+                CloseHandle(job);
+            }
             CloseHandle(h_token);
         }
         let _ = t_out.join();
@@ -1,4 +1,4 @@
-// based on event types from codex-rs/exec/src/exec_events.rs
+// based on event types from codex-rs/protocol/src/exec_events.rs
 
 import type { ThreadItem } from "./items";
 
@@ -68,6 +68,20 @@ export type ThreadErrorEvent = {
   message: string;
 };
 
+/** Emitted once as the last event, summarizing the whole run. */
+export type RunCompletedEvent = {
+  type: "run.completed";
+  status: "completed" | "failed";
+  /** The status `codex exec` exits with. */
+  exit_code: number;
+  /** The thread the run happened in, when one was started. */
+  thread_id: string | null;
+  /** Paths of the files changed by patches that were applied. */
+  files_changed: string[];
+  /** Tokens used across every turn of the run. */
+  usage: Usage;
+};
+
 /** Top-level JSONL events emitted by codex exec. */
 export type ThreadEvent =
   | ThreadStartedEvent
@@ -77,4 +91,5 @@ export type ThreadEvent =
   | ItemStartedEvent
   | ItemUpdatedEvent
   | ItemCompletedEvent
-  | ThreadErrorEvent;
+  | ThreadErrorEvent
+  | RunCompletedEvent;
@@ -9,6 +9,7 @@ export type {
   ItemCompletedEvent,
   ThreadError,
   ThreadErrorEvent,
+  RunCompletedEvent,
   Usage,
 } from "./events";
 export type {